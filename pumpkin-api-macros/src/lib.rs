@@ -2,7 +2,10 @@ use proc_macro::TokenStream;
 use quote::quote;
 use std::sync::LazyLock;
 use std::sync::Mutex;
-use syn::{parse_macro_input, parse_quote, ImplItem, ItemFn, ItemImpl, ItemStruct};
+use syn::{
+    parse_macro_input, parse_quote, punctuated::Punctuated, Expr, ExprLit, ImplItem, ItemFn,
+    ItemImpl, ItemStruct, Lit, MetaNameValue, Token,
+};
 
 static PLUGIN_METHODS: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
 
@@ -29,8 +32,51 @@ pub fn plugin_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
     TokenStream::new()
 }
 
+/// Parses a `key = ["a", "b"]` attribute value into a list of plugin names.
+fn parse_plugin_list(meta: &MetaNameValue) -> syn::Result<Vec<String>> {
+    let Expr::Array(array) = &meta.value else {
+        return Err(syn::Error::new_spanned(
+            &meta.value,
+            "expected an array of plugin names",
+        ));
+    };
+    array
+        .elems
+        .iter()
+        .map(|elem| match elem {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(name),
+                ..
+            }) => Ok(name.value()),
+            _ => Err(syn::Error::new_spanned(elem, "expected a string literal")),
+        })
+        .collect()
+}
+
 #[proc_macro_attribute]
-pub fn plugin_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn plugin_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    // Parse the optional dependency declarations
+    let args =
+        parse_macro_input!(attr with Punctuated::<MetaNameValue, Token![,]>::parse_terminated);
+
+    let mut depends = Vec::new();
+    let mut soft_depends = Vec::new();
+    for arg in &args {
+        let target = if arg.path.is_ident("depends") {
+            &mut depends
+        } else if arg.path.is_ident("soft_depends") {
+            &mut soft_depends
+        } else {
+            return syn::Error::new_spanned(&arg.path, "expected `depends` or `soft_depends`")
+                .to_compile_error()
+                .into();
+        };
+        match parse_plugin_list(arg) {
+            Ok(names) => target.extend(names),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    }
+
     // Parse the input struct
     let input_struct = parse_macro_input!(item as ItemStruct);
     let struct_ident = &input_struct.ident;
@@ -53,6 +99,9 @@ pub fn plugin_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
             version: env!("CARGO_PKG_VERSION"),
             authors: env!("CARGO_PKG_AUTHORS"),
             description: env!("CARGO_PKG_DESCRIPTION"),
+            website: env!("CARGO_PKG_HOMEPAGE"),
+            depends: &[#(#depends),*],
+            soft_depends: &[#(#soft_depends),*],
         };
 
         #input_struct
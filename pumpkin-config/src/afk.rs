@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct AfkConfig {
+    /// How many seconds players have to be idle before they are marked as AFK automatically, 0
+    /// to only mark them with `/afk`
+    pub idle_seconds: u32,
+    /// Whether all players are told when someone becomes AFK or returns
+    pub broadcast: bool,
+    /// Whether AFK players are left out when counting if enough players sleep to skip the night
+    pub ignored_by_sleep: bool,
+    /// Whether mobs leave AFK players alone
+    pub ignored_by_mobs: bool,
+}
+
+impl Default for AfkConfig {
+    fn default() -> Self {
+        Self {
+            idle_seconds: 300,
+            broadcast: true,
+            ignored_by_sleep: false,
+            ignored_by_mobs: false,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use pumpkin_util::text::color::NamedColor;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct BroadcastConfig {
+    /// The text in front of every `/broadcast` message
+    pub prefix: String,
+    /// The color of the prefix
+    pub prefix_color: NamedColor,
+    /// The color of the message, unless the message sets its own color
+    pub color: NamedColor,
+}
+
+impl Default for BroadcastConfig {
+    fn default() -> Self {
+        Self {
+            prefix: "[Broadcast] ".to_string(),
+            prefix_color: NamedColor::Red,
+            color: NamedColor::Gold,
+        }
+    }
+}
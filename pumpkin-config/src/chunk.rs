@@ -18,7 +18,8 @@ pub struct ChunkCompression {
 impl Default for ChunkCompression {
     fn default() -> Self {
         Self {
-            algorithm: Compression::LZ4,
+            // Vanilla uses zlib by default, so worlds stay readable by every version
+            algorithm: Compression::ZLib,
             level: 6,
         }
     }
@@ -10,6 +10,15 @@ pub struct CommandsConfig {
     pub log_console: bool, // TODO: commands...
     /// The op permission level of everyone that is not in the ops file
     pub default_op_level: PermissionLvl,
+    /// Whether players can `/ignore` operators. If not, the chat and private messages of
+    /// operators always reach everyone.
+    pub allow_ignoring_operators: bool,
+    /// Whether the essentials like `/heal`, `/feed`, `/fly`, `/god`, `/repair` and `/hat` are
+    /// registered
+    pub essentials: bool,
+    /// The names or aliases of commands which are not registered, e.g. `["god", "tpa"]`. They can
+    /// be enabled again at runtime with `/command enable`.
+    pub disabled: Vec<String>,
 }
 
 impl Default for CommandsConfig {
@@ -18,6 +27,9 @@ impl Default for CommandsConfig {
             use_console: true,
             log_console: true,
             default_op_level: PermissionLvl::Zero,
+            allow_ignoring_operators: true,
+            essentials: true,
+            disabled: Vec::new(),
         }
     }
 }
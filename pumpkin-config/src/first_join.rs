@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct FirstJoinConfig {
+    /// Whether to run the commands when a player joins the server for the first time
+    pub enabled: bool,
+    /// The commands run by the console, without a leading `/`. Placeholders like
+    /// `%player_name%` are replaced with the joining player
+    pub commands: Vec<String>,
+}
+
+impl Default for FirstJoinConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            commands: vec!["give %player_name% minecraft:bread 8".to_string()],
+        }
+    }
+}
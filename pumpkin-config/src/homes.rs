@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct HomesConfig {
+    /// How many homes each player can set with `/sethome`, 0 for no limit
+    pub limit: u32,
+}
+
+impl Default for HomesConfig {
+    fn default() -> Self {
+        Self { limit: 3 }
+    }
+}
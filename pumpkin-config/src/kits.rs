@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct KitsConfig {
+    /// The kits given by `/kit`, keyed by kit name
+    pub kits: HashMap<String, KitConfig>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct KitConfig {
+    /// The items of the kit, as an item id followed by an optional count (e.g. `minecraft:bread 8`)
+    pub items: Vec<String>,
+    /// How many seconds a player has to wait before redeeming the kit again, 0 for no cooldown
+    #[serde(default)]
+    pub cooldown: u64,
+}
+
+impl Default for KitsConfig {
+    fn default() -> Self {
+        Self {
+            kits: HashMap::from([(
+                "starter".to_string(),
+                KitConfig {
+                    items: vec![
+                        "minecraft:stone_sword".to_string(),
+                        "minecraft:stone_pickaxe".to_string(),
+                        "minecraft:bread 16".to_string(),
+                    ],
+                    cooldown: 86400,
+                },
+            )]),
+        }
+    }
+}
@@ -5,7 +5,7 @@ use pumpkin_util::{Difficulty, GameMode, PermissionLvl};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use std::{
-    env, fs,
+    env, fs, io,
     net::{Ipv4Addr, SocketAddr},
     num::NonZeroU8,
     path::Path,
@@ -17,20 +17,42 @@ pub mod networking;
 
 pub mod resource_pack;
 
+pub use afk::AfkConfig;
+pub use broadcast::BroadcastConfig;
 pub use commands::CommandsConfig;
+pub use first_join::FirstJoinConfig;
+pub use homes::HomesConfig;
+pub use kits::{KitConfig, KitsConfig};
 pub use networking::auth::AuthenticationConfig;
 pub use networking::compression::CompressionConfig;
 pub use networking::lan_broadcast::LANBroadcastConfig;
 pub use networking::rcon::RCONConfig;
 pub use pvp::PVPConfig;
+pub use random_teleport::RandomTeleportConfig;
+pub use repair::RepairConfig;
 pub use server_links::ServerLinksConfig;
+pub use tab_list::TabListConfig;
+pub use teleport_requests::TeleportRequestsConfig;
+pub use teleport_warmup::TeleportWarmupConfig;
+pub use world::{NamedWorldConfig, WorldConfig, WorldEnvironment};
 
+mod afk;
+mod broadcast;
 mod commands;
+mod first_join;
+mod homes;
+mod kits;
 
 pub mod chunk;
 pub mod op;
 mod pvp;
+mod random_teleport;
+mod repair;
 mod server_links;
+mod tab_list;
+mod teleport_requests;
+mod teleport_warmup;
+mod world;
 
 use networking::NetworkingConfig;
 use resource_pack::ResourcePackConfig;
@@ -57,6 +79,17 @@ pub struct AdvancedConfiguration {
     pub commands: CommandsConfig,
     pub pvp: PVPConfig,
     pub server_links: ServerLinksConfig,
+    pub world: WorldConfig,
+    pub tab_list: TabListConfig,
+    pub broadcast: BroadcastConfig,
+    pub first_join: FirstJoinConfig,
+    pub kits: KitsConfig,
+    pub homes: HomesConfig,
+    pub teleport_requests: TeleportRequestsConfig,
+    pub random_teleport: RandomTeleportConfig,
+    pub teleport_warmup: TeleportWarmupConfig,
+    pub afk: AfkConfig,
+    pub repair: RepairConfig,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -68,6 +101,8 @@ pub struct BasicConfiguration {
     pub seed: String,
     /// The maximum number of players allowed on the server. Specifying `0` disables the limit.
     pub max_players: u32,
+    /// The radius around the world spawn in which only operators can build. Specifying `0` disables spawn protection.
+    pub spawn_protection: u32,
     /// The maximum view distance for players.
     pub view_distance: NonZeroU8,
     /// The maximum simulated view distance.
@@ -78,6 +113,8 @@ pub struct BasicConfiguration {
     pub op_permission_level: PermissionLvl,
     /// Whether the Nether dimension is enabled.
     pub allow_nether: bool,
+    /// Whether the End dimension is enabled.
+    pub allow_end: bool,
     /// Whether the server is in hardcore mode.
     pub hardcore: bool,
     /// Whether online mode is enabled. Requires valid Minecraft accounts.
@@ -104,11 +141,13 @@ impl Default for BasicConfiguration {
             server_address: SocketAddr::new(Ipv4Addr::new(0, 0, 0, 0).into(), 25565),
             seed: "".to_string(),
             max_players: 100000,
+            spawn_protection: 16,
             view_distance: NonZeroU8::new(10).unwrap(),
             simulation_distance: NonZeroU8::new(10).unwrap(),
             default_difficulty: Difficulty::Normal,
             op_permission_level: PermissionLvl::Four,
             allow_nether: true,
+            allow_end: true,
             hardcore: false,
             online_mode: true,
             encryption: true,
@@ -163,11 +202,74 @@ trait LoadConfiguration {
         config
     }
 
+    /// Replaces a single value in the configuration file, keeping all others as they are.
+    fn save_value(key: &str, value: toml::Value) -> io::Result<()> {
+        Self::edit_file(|table| {
+            table.insert(key.to_string(), value);
+        })
+    }
+
+    /// Replaces a single value in a section of the configuration file, like `[world]`, see
+    /// [`Self::save_value`].
+    fn save_section_value(section: &str, key: &str, value: toml::Value) -> io::Result<()> {
+        Self::edit_file(|table| {
+            let section = table
+                .entry(section)
+                .or_insert(toml::Value::Table(toml::Table::new()));
+            if let Some(section) = section.as_table_mut() {
+                section.insert(key.to_string(), value);
+            }
+        })
+    }
+
+    fn edit_file(edit: impl FnOnce(&mut toml::Table)) -> io::Result<()> {
+        let path = env::current_dir()?
+            .join(CONFIG_ROOT_FOLDER)
+            .join(Self::get_path());
+        let mut table: toml::Table = match fs::read_to_string(&path) {
+            Ok(file_content) => toml::from_str(&file_content).map_err(io::Error::other)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => toml::Table::new(),
+            Err(err) => return Err(err),
+        };
+        edit(&mut table);
+        fs::write(&path, toml::to_string(&table).map_err(io::Error::other)?)
+    }
+
     fn get_path() -> &'static Path;
 
     fn validate(&self);
 }
 
+impl BasicConfiguration {
+    /// Writes a new MOTD to the configuration file, so it is kept across restarts.
+    /// This does not change [`BASIC_CONFIG`], which is only loaded once.
+    pub fn save_motd(motd: &str) -> io::Result<()> {
+        Self::save_value("motd", toml::Value::String(motd.to_string()))
+    }
+
+    /// Writes a new maximum player count to the configuration file, see [`Self::save_motd`].
+    pub fn save_max_players(max_players: u32) -> io::Result<()> {
+        Self::save_value("max_players", toml::Value::Integer(max_players.into()))
+    }
+
+    /// Writes a new spawn protection radius to the configuration file, see [`Self::save_motd`].
+    pub fn save_spawn_protection(radius: u32) -> io::Result<()> {
+        Self::save_value("spawn_protection", toml::Value::Integer(radius.into()))
+    }
+}
+
+impl AdvancedConfiguration {
+    /// Writes a new `playersSleepingPercentage` to the configuration file, so it is kept across
+    /// restarts. This does not change [`ADVANCED_CONFIG`], which is only loaded once.
+    pub fn save_players_sleeping_percentage(percentage: u32) -> io::Result<()> {
+        Self::save_section_value(
+            "world",
+            "players_sleeping_percentage",
+            toml::Value::Integer(percentage.into()),
+        )
+    }
+}
+
 impl LoadConfiguration for AdvancedConfiguration {
     fn get_path() -> &'static Path {
         Path::new("features.toml")
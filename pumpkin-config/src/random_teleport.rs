@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct RandomTeleportConfig {
+    /// How many blocks away from the center of the world border `/rtp` can teleport players
+    pub radius: u32,
+    /// How many seconds a player has to wait before using `/rtp` again, 0 for no cooldown
+    pub cooldown: u64,
+}
+
+impl Default for RandomTeleportConfig {
+    fn default() -> Self {
+        Self {
+            radius: 5000,
+            cooldown: 300,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct RepairConfig {
+    /// How many seconds players who are not operators have to wait before using `/repair` again,
+    /// 0 for no cooldown
+    pub cooldown: u64,
+}
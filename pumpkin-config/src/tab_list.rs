@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct TabListConfig {
+    /// Whether to show a header and footer in the player list
+    pub enabled: bool,
+    /// The text above the player list. Placeholders like `%server_online%` are replaced
+    pub header: String,
+    /// The text below the player list. Placeholders like `%server_tps%` are replaced
+    pub footer: String,
+}
+
+impl Default for TabListConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            header: "Pumpkin".to_string(),
+            footer: "%server_online% online, %server_tps% TPS".to_string(),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct TeleportRequestsConfig {
+    /// How many seconds a `/tpa` or `/tpahere` request can be accepted before it expires
+    pub timeout: u32,
+}
+
+impl Default for TeleportRequestsConfig {
+    fn default() -> Self {
+        Self { timeout: 120 }
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct TeleportWarmupConfig {
+    /// How many seconds players have to stand still before `/home`, `/spawn`, `/rtp` or `/warp`
+    /// teleports them, 0 to teleport them right away. Operators are never kept waiting
+    pub seconds: u32,
+}
+
+impl Default for TeleportWarmupConfig {
+    fn default() -> Self {
+        Self { seconds: 3 }
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The settings of the worlds. The ones named after vanilla game rules are only the values new
+/// worlds start with, existing worlds use the game rules of their level.dat. Only
+/// `players_sleeping_percentage` applies to all worlds.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct WorldConfig {
+    /// The generator used by worlds which are not listed in `generators`, except for the nether
+    /// and the end which use their own generators
+    pub default_generator: String,
+    /// The generator used by each world, keyed by world name (e.g. `world_nether = "void"`).
+    /// Plugins can register their own generators
+    pub generators: HashMap<String, String>,
+    /// Worlds loaded in addition to the overworld, the nether and the end, keyed by world name
+    pub worlds: HashMap<String, NamedWorldConfig>,
+    /// How many random blocks of each subchunk are ticked each tick, which makes crops grow,
+    /// grass spread and ice melt. Like vanilla's `randomTickSpeed`, 0 disables random ticks
+    pub random_tick_speed: u32,
+    /// Whether fire spreads and burns blocks away, like vanilla's `doFireTick`
+    pub do_fire_tick: bool,
+    /// Whether mobs spawn naturally, like vanilla's `doMobSpawning`
+    pub do_mob_spawning: bool,
+    /// Whether the time of day advances, like vanilla's `doDaylightCycle`
+    pub do_daylight_cycle: bool,
+    /// Whether the weather changes by itself, like vanilla's `doWeatherCycle`
+    pub do_weather_cycle: bool,
+    /// Whether the weather changes blocks: Snow piles up and water freezes in cold biomes, and
+    /// snow and ice melt in warm ones. Snow and ice still melt next to light sources without it
+    pub weather_changes_blocks: bool,
+    /// How many snow layers pile up while it snows, like vanilla's `snowAccumulationHeight`.
+    /// 0 keeps snow from piling up
+    pub snow_accumulation_height: u8,
+    /// How many percent of the players in a world have to sleep to skip the night, like vanilla's
+    /// `playersSleepingPercentage`. At 0 any one player skips it and above 100 it is never
+    /// skipped. Can be changed with `/sleeppercentage`
+    pub players_sleeping_percentage: u32,
+    /// Whether pistons, dispensers and droppers are also powered like the block above them,
+    /// which vanilla does by accident. Many redstone contraptions rely on it
+    pub quasi_connectivity: bool,
+    /// How many chunks around the spawn point of the overworld are always ticked, like vanilla's
+    /// `spawnChunkRadius`. They are loaded at startup, together with two more rings of chunks
+    /// around them which are kept loaded. 0 does not keep any spawn chunks loaded
+    pub spawn_chunk_radius: u8,
+    /// How many ticks pass between saving the worlds and their level.dat, like vanilla which
+    /// saves every 5 minutes. 0 only saves with `/save-all` and when the server stops
+    pub autosave_interval: u64,
+    /// The changed chunks are saved spread over the autosave interval, but at most this many
+    /// each tick
+    pub autosave_max_chunks_per_tick: usize,
+}
+
+impl WorldConfig {
+    /// Returns the name of the generator the given world should use
+    #[must_use]
+    pub fn get_generator(&self, world: &str) -> &str {
+        self.worlds
+            .get(world)
+            .and_then(|config| config.generator.as_deref())
+            .or_else(|| self.generators.get(world).map(String::as_str))
+            .unwrap_or(&self.default_generator)
+    }
+
+    /// Whether a generator is configured for the given world, instead of the default one
+    #[must_use]
+    pub fn has_generator(&self, world: &str) -> bool {
+        self.generators.contains_key(world)
+            || self
+                .worlds
+                .get(world)
+                .is_some_and(|config| config.generator.is_some())
+    }
+}
+
+/// A world loaded in addition to the vanilla dimensions
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct NamedWorldConfig {
+    /// The folder of the world, relative to the server folder. Defaults to the world name
+    pub folder: Option<String>,
+    /// The generator of the world. Defaults to the generator of the environment, unless the world
+    /// is listed in `generators`
+    pub generator: Option<String>,
+    /// The seed a new world is created with. Defaults to the seed of the basic config
+    pub seed: Option<String>,
+    /// The dimension the world is, which decides how clients render it
+    pub environment: WorldEnvironment,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldEnvironment {
+    #[default]
+    Overworld,
+    Nether,
+    End,
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            default_generator: "default".to_string(),
+            generators: HashMap::new(),
+            worlds: HashMap::new(),
+            random_tick_speed: 3,
+            do_fire_tick: true,
+            do_mob_spawning: true,
+            do_daylight_cycle: true,
+            do_weather_cycle: true,
+            weather_changes_blocks: true,
+            snow_accumulation_height: 1,
+            players_sleeping_percentage: 100,
+            quasi_connectivity: true,
+            spawn_chunk_radius: 2,
+            autosave_interval: 6000,
+            autosave_max_chunks_per_tick: 24,
+        }
+    }
+}
@@ -1,19 +1,145 @@
+use std::collections::HashMap;
+
+use heck::ToPascalCase;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+use serde::Deserialize;
 
 use crate::array_to_tokenstream;
 
+/// The climate of a biome from the `worldgen/biome` registry
+#[derive(Deserialize)]
+struct BiomeClimate {
+    has_precipitation: bool,
+    temperature: f32,
+    #[serde(default)]
+    temperature_modifier: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SyncedRegistries {
+    #[serde(rename = "worldgen/biome")]
+    biomes: HashMap<String, BiomeClimate>,
+}
+
 pub(crate) fn build() -> TokenStream {
     println!("cargo:rerun-if-changed=../assets/biome.json");
+    println!("cargo:rerun-if-changed=../assets/synced_registries.json");
+
+    let climates = serde_json::from_str::<SyncedRegistries>(include_str!(
+        "../../assets/synced_registries.json"
+    ))
+    .expect("Failed to parse synced_registries.json")
+    .biomes;
 
     let biomes: Vec<String> = serde_json::from_str(include_str!("../../assets/biome.json"))
         .expect("Failed to parse biome.json");
     let variants = array_to_tokenstream(&biomes);
+    let count = biomes.len();
+    let type_from_name = &biomes
+        .iter()
+        .map(|biome| {
+            let id = &biome;
+            let name = format_ident!("{}", biome.to_pascal_case());
+
+            quote! {
+                #id => Some(Self::#name),
+            }
+        })
+        .collect::<TokenStream>();
+    let type_to_name = &biomes
+        .iter()
+        .map(|biome| {
+            let id = &biome;
+            let name = format_ident!("{}", biome.to_pascal_case());
+
+            quote! {
+                Self::#name => #id,
+            }
+        })
+        .collect::<TokenStream>();
+    let climate = |biome: &String| {
+        climates
+            .get(biome)
+            .unwrap_or_else(|| panic!("Biome {biome} has no climate"))
+    };
+    let type_temperature = &biomes
+        .iter()
+        .map(|biome| {
+            let name = format_ident!("{}", biome.to_pascal_case());
+            let temperature = climate(biome).temperature;
+            quote! {
+                Self::#name => #temperature,
+            }
+        })
+        .collect::<TokenStream>();
+    let type_has_precipitation = &biomes
+        .iter()
+        .map(|biome| {
+            let name = format_ident!("{}", biome.to_pascal_case());
+            let has_precipitation = climate(biome).has_precipitation;
+            quote! {
+                Self::#name => #has_precipitation,
+            }
+        })
+        .collect::<TokenStream>();
+    let frozen = biomes
+        .iter()
+        .filter(|biome| climate(biome).temperature_modifier.as_deref() == Some("frozen"))
+        .map(|biome| format_ident!("{}", biome.to_pascal_case()));
 
     quote! {
-        #[derive(Clone, Copy)]
+        #[doc = r" How the temperature of a biome changes from place to place"]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum TemperatureModifier {
+            None,
+            #[doc = r" Frozen oceans are only partly frozen, some places are warmer than the biome"]
+            Frozen,
+        }
+
+        #[doc = r" The biomes, in the order of the biome registry, so their discriminant is their registry id"]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum Biome {
             #variants
         }
+
+        impl Biome {
+            #[doc = r" The number of biomes in the registry"]
+            pub const COUNT: usize = #count;
+
+            #[doc = r" Try to parse Biome from a resource location string, without the namespace"]
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #type_from_name
+                    _ => None
+                }
+            }
+            pub const fn to_name(&self) -> &'static str {
+                match self {
+                    #type_to_name
+                }
+            }
+
+            #[doc = r" The base temperature, below 0.15 it snows instead of raining"]
+            pub const fn temperature(&self) -> f32 {
+                match self {
+                    #type_temperature
+                }
+            }
+
+            #[doc = r" Whether it rains or snows in the biome, it does not in deserts for example"]
+            pub const fn has_precipitation(&self) -> bool {
+                match self {
+                    #type_has_precipitation
+                }
+            }
+
+            pub const fn temperature_modifier(&self) -> TemperatureModifier {
+                match self {
+                    #(Self::#frozen)|* => TemperatureModifier::Frozen,
+                    _ => TemperatureModifier::None,
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+
+use heck::{ToPascalCase, ToShoutySnakeCase};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpawnEntry {
+    #[serde(rename = "type")]
+    entity: String,
+    weight: u32,
+    min_count: u32,
+    max_count: u32,
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed=../assets/biome_spawns.json");
+
+    let biomes: BTreeMap<String, BTreeMap<String, Vec<SpawnEntry>>> =
+        serde_json::from_str(include_str!("../../assets/biome_spawns.json"))
+            .expect("Failed to parse biome_spawns.json");
+
+    let mut arms = TokenStream::new();
+    for (biome, groups) in &biomes {
+        let biome = format_ident!("{}", biome.to_pascal_case());
+        for (group, entries) in groups {
+            let group = format_ident!("{}", group.to_pascal_case());
+            let entries = entries.iter().map(|entry| {
+                let entity = entry
+                    .entity
+                    .strip_prefix("minecraft:")
+                    .unwrap_or(&entry.entity);
+                let entity = format_ident!("{}", entity.to_shouty_snake_case());
+                let SpawnEntry {
+                    weight,
+                    min_count,
+                    max_count,
+                    ..
+                } = entry;
+                quote! {
+                    SpawnEntry {
+                        entity: EntityType::#entity,
+                        weight: #weight,
+                        min_count: #min_count,
+                        max_count: #max_count,
+                    },
+                }
+            });
+            arms.extend(quote! {
+                (Self::#biome, SpawnGroup::#group) => {
+                    const ENTRIES: &[SpawnEntry] = &[#(#entries)*];
+                    ENTRIES
+                }
+            });
+        }
+    }
+
+    quote! {
+        use crate::entity::{EntityType, SpawnGroup};
+
+        #[doc = r" A mob which spawns naturally in a biome, together with others of its kind"]
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct SpawnEntry {
+            pub entity: EntityType,
+            #[doc = r" How likely the entry is picked, relative to the others of the spawn group"]
+            pub weight: u32,
+            pub min_count: u32,
+            pub max_count: u32,
+        }
+
+        impl Biome {
+            #[doc = r" The mobs of the spawn group which spawn naturally in the biome"]
+            pub const fn spawn_entries(&self, group: SpawnGroup) -> &'static [SpawnEntry] {
+                match (self, group) {
+                    #arms
+                    _ => &[],
+                }
+            }
+        }
+    }
+}
@@ -5,6 +5,7 @@ use heck::ToPascalCase;
 use proc_macro2::TokenStream;
 
 mod biome;
+mod biome_spawn;
 mod chunk_status;
 mod damage_type;
 mod entity_pose;
@@ -37,6 +38,7 @@ pub fn main() {
     write_generated_file(entity_type::build(), "entity_type.rs");
     write_generated_file(noise_parameter::build(), "noise_parameter.rs");
     write_generated_file(biome::build(), "biome.rs");
+    write_generated_file(biome_spawn::build(), "biome_spawn.rs");
     write_generated_file(damage_type::build(), "damage_type.rs");
     write_generated_file(message_type::build(), "message_type.rs");
     write_generated_file(spawn_egg::build(), "spawn_egg.rs");
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use heck::ToPascalCase;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
 use serde::Deserialize;
@@ -14,6 +15,7 @@ pub struct EntityType {
     pub fire_immune: bool,
     pub dimension: [f32; 2],
     pub eye_height: f32,
+    pub spawn_group: String,
 }
 
 pub struct NamedEntityType<'a>(&'a str, &'a EntityType);
@@ -40,6 +42,7 @@ impl ToTokens for NamedEntityType<'_> {
 
         let dimension0 = entity.dimension[0];
         let dimension1 = entity.dimension[1];
+        let spawn_group = format_ident!("{}", entity.spawn_group.to_pascal_case());
 
         tokens.extend(quote! {
             EntityType {
@@ -51,6 +54,7 @@ impl ToTokens for NamedEntityType<'_> {
                 dimension: [#dimension0, #dimension1], // Correctly construct the array
                 eye_height: #eye_height,
                 resource_name: #name,
+                spawn_group: SpawnGroup::#spawn_group,
             }
         });
     }
@@ -87,6 +91,19 @@ pub(crate) fn build() -> TokenStream {
         });
     }
     quote! {
+        #[doc = r" The groups of entities, which are counted separately when mobs spawn naturally"]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum SpawnGroup {
+            Monster,
+            Creature,
+            Ambient,
+            Axolotls,
+            UndergroundWaterCreature,
+            WaterCreature,
+            WaterAmbient,
+            Misc,
+        }
+
         #[derive(Clone, Copy, Debug, PartialEq)]
         pub struct EntityType {
             pub id: u16,
@@ -97,6 +114,7 @@ pub(crate) fn build() -> TokenStream {
             pub dimension: [f32; 2],
             pub eye_height: f32,
             pub resource_name: &'static str,
+            pub spawn_group: SpawnGroup,
         }
 
         impl EntityType {
@@ -28,6 +28,8 @@ pub struct ItemComponents {
     pub attribute_modifiers: Option<AttributeModifiers>,
     #[serde(rename = "minecraft:tool")]
     pub tool: Option<ToolComponent>,
+    #[serde(rename = "minecraft:repairable")]
+    pub repairable: Option<Repairable>,
 }
 
 impl ToTokens for ItemComponents {
@@ -146,6 +148,14 @@ impl ToTokens for ItemComponents {
             None => quote! { None },
         };
 
+        let repairable = match &self.repairable {
+            Some(repairable) => {
+                let items = LitStr::new(&repairable.items, Span::call_site());
+                quote! { Some(#items) }
+            }
+            None => quote! { None },
+        };
+
         tokens.extend(quote! {
             ItemComponents {
                 item_name: #item_name,
@@ -154,7 +164,8 @@ impl ToTokens for ItemComponents {
                 damage: #damage,
                 max_damage: #max_damage,
                 attribute_modifiers: #attribute_modifiers,
-                tool: #tool
+                tool: #tool,
+                repairable: #repairable
             }
         });
     }
@@ -178,6 +189,11 @@ pub struct JukeboxPlayable {
     pub song: String,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct Repairable {
+    pub items: String,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct AttributeModifiers {
     pub modifiers: Vec<Modifier>,
@@ -211,6 +227,7 @@ pub(crate) fn build() -> TokenStream {
 
     let mut type_from_raw_id_arms = TokenStream::new();
     let mut type_from_name = TokenStream::new();
+    let mut type_to_name = TokenStream::new();
 
     let mut constants = TokenStream::new();
 
@@ -236,6 +253,10 @@ pub(crate) fn build() -> TokenStream {
         type_from_name.extend(quote! {
             #name => Some(Self::#const_ident),
         });
+
+        type_to_name.extend(quote! {
+            #id_lit => #name,
+        });
     }
 
     quote! {
@@ -255,7 +276,9 @@ pub(crate) fn build() -> TokenStream {
             pub damage: Option<u16>,
             pub max_damage: Option<u16>,
             pub attribute_modifiers: Option<AttributeModifiers>,
-            pub tool: Option<ToolComponent>
+            pub tool: Option<ToolComponent>,
+            #[doc = r" The item or the tag, starting with `#`, of the items which repair this item in an anvil"]
+            pub repairable: Option<&'static str>
         }
 
         #[derive(Clone, Copy, Debug)]
@@ -313,6 +336,14 @@ pub(crate) fn build() -> TokenStream {
                     _ => None
                 }
             }
+            #[doc = r" Returns the resource location of the item, without the namespace"]
+            pub const fn registry_key(&self) -> &'static str {
+                match self.id {
+                    #type_to_name
+                    _ => "air"
+                }
+            }
+
             #[doc = r" Try to parse a Item from a raw id"]
             pub const fn from_id(id: u16) -> Option<Self> {
                 match id {
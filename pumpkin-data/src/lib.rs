@@ -23,6 +23,7 @@ pub mod sound {
 
 pub mod chunk {
     include!(concat!(env!("OUT_DIR"), "/biome.rs"));
+    include!(concat!(env!("OUT_DIR"), "/biome_spawn.rs"));
     include!(concat!(env!("OUT_DIR"), "/noise_parameter.rs"));
     include!(concat!(env!("OUT_DIR"), "/chunk_status.rs"));
 }
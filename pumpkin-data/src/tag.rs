@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::fmt::Formatter;
 use std::sync::LazyLock;
 
-#[derive(Deserialize, Eq, PartialEq, Hash)]
+#[derive(Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 // TODO: We should parse this from vanilla ig
 pub enum RegistryKey {
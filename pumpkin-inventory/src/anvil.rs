@@ -0,0 +1,376 @@
+//! The results of anvils and grindstones, which repair, combine, rename and disenchant items like
+//! in vanilla.
+
+use pumpkin_data::{
+    item::Item,
+    tag::{get_tag_values, RegistryKey},
+};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_registry::{Enchantment, SYNCED_REGISTRIES};
+use pumpkin_world::item::ItemStack;
+
+/// The cost from which an anvil is too expensive for players who are not in creative mode
+pub const TOO_EXPENSIVE_COST: i32 = 40;
+
+const ENCHANTMENTS: &str = "enchantments";
+const STORED_ENCHANTMENTS: &str = "stored_enchantments";
+
+/// What an anvil makes of the items put into it
+pub struct AnvilResult {
+    pub item: ItemStack,
+    /// The experience levels taking the item costs
+    pub cost: i32,
+    /// How many of the right item are used up
+    pub right_used: u8,
+}
+
+fn strip_namespace(name: &str) -> &str {
+    name.strip_prefix("minecraft:").unwrap_or(name)
+}
+
+/// Whether the name is the entry or in the tag, starting with `#`, the reference points to
+fn is_in(reference: &str, registry: RegistryKey, name: &str) -> bool {
+    let name = strip_namespace(name);
+    match reference.strip_prefix('#') {
+        Some(tag) => get_tag_values(registry, tag)
+            .is_some_and(|values| values.iter().flatten().any(|value| value == name)),
+        None => strip_namespace(reference) == name,
+    }
+}
+
+fn enchantment(name: &str) -> Option<&'static Enchantment> {
+    SYNCED_REGISTRIES.enchantment.get(strip_namespace(name))
+}
+
+fn damage(stack: &ItemStack) -> i32 {
+    stack
+        .get_component("damage")
+        .and_then(NbtTag::extract_int)
+        .unwrap_or_else(|| stack.item.components.damage.map_or(0, i32::from))
+}
+
+fn set_damage(stack: &mut ItemStack, damage: i32) {
+    if damage > 0 {
+        stack.set_component("damage", damage);
+    } else {
+        stack.remove_component("damage");
+    }
+}
+
+fn repair_cost(stack: &ItemStack) -> i32 {
+    stack
+        .get_component("repair_cost")
+        .and_then(NbtTag::extract_int)
+        .unwrap_or(0)
+}
+
+fn set_repair_cost(stack: &mut ItemStack, cost: i32) {
+    if cost > 0 {
+        stack.set_component("repair_cost", cost);
+    } else {
+        stack.remove_component("repair_cost");
+    }
+}
+
+/// Each time an item is worked on in an anvil, the next time costs more
+fn increased_repair_cost(cost: i32) -> i32 {
+    cost.saturating_mul(2).saturating_add(1)
+}
+
+fn is_enchanted_book(stack: &ItemStack) -> bool {
+    stack.item.id == Item::ENCHANTED_BOOK.id
+}
+
+/// Enchanted books store their enchantments in another component than the items they are for
+fn enchantments_component(stack: &ItemStack) -> &'static str {
+    if is_enchanted_book(stack) {
+        STORED_ENCHANTMENTS
+    } else {
+        ENCHANTMENTS
+    }
+}
+
+/// The enchantments of the stack with their levels. They are either in a `levels` compound or
+/// directly in the component.
+fn enchantments(stack: &ItemStack) -> Vec<(String, i32)> {
+    let Some(NbtTag::Compound(component)) = stack.get_component(enchantments_component(stack))
+    else {
+        return Vec::new();
+    };
+    component
+        .get_compound("levels")
+        .unwrap_or(component)
+        .child_tags
+        .iter()
+        .filter_map(|(name, level)| Some((name.clone(), level.extract_int()?)))
+        .filter(|(_, level)| *level > 0)
+        .collect()
+}
+
+fn set_enchantments(stack: &mut ItemStack, enchantments: &[(String, i32)]) {
+    let id = enchantments_component(stack);
+    if enchantments.is_empty() {
+        stack.remove_component(id);
+        return;
+    }
+    let mut levels = NbtCompound::new();
+    for (name, level) in enchantments {
+        levels.put_int(name, *level);
+    }
+    let mut component = NbtCompound::new();
+    component.put_component("levels", levels);
+    stack.set_component(id, component);
+}
+
+fn level_of(enchantments: &[(String, i32)], name: &str) -> i32 {
+    enchantments
+        .iter()
+        .find(|(existing, _)| strip_namespace(existing) == strip_namespace(name))
+        .map_or(0, |(_, level)| *level)
+}
+
+fn set_level(enchantments: &mut Vec<(String, i32)>, name: &str, level: i32) {
+    match enchantments
+        .iter_mut()
+        .find(|(existing, _)| strip_namespace(existing) == strip_namespace(name))
+    {
+        Some((_, existing)) => *existing = level,
+        None => enchantments.push((name.to_string(), level)),
+    }
+}
+
+/// Whether the enchantments can be on the same item
+fn are_compatible(first: &str, second: &str) -> bool {
+    let excludes = |enchantment_name: &str, other: &str| {
+        enchantment(enchantment_name)
+            .and_then(|enchantment| enchantment.exclusive_set.as_deref())
+            .is_some_and(|set| is_in(set, RegistryKey::Enchantment, other))
+    };
+    strip_namespace(first) != strip_namespace(second)
+        && !excludes(first, second)
+        && !excludes(second, first)
+}
+
+fn is_curse(name: &str) -> bool {
+    is_in("#minecraft:curse", RegistryKey::Enchantment, name)
+}
+
+/// Whether the right item repairs the left one, like diamonds repair diamond tools
+fn is_repair_material(left: &ItemStack, right: &ItemStack) -> bool {
+    left.item
+        .components
+        .repairable
+        .is_some_and(|items| is_in(items, RegistryKey::Item, right.item.registry_key()))
+}
+
+/// Adds the enchantments of the right item to the result. Returns the cost, or `None` if none of
+/// them can be added.
+fn combine_enchantments(
+    result: &mut ItemStack,
+    left: &ItemStack,
+    right: &ItemStack,
+) -> Option<i32> {
+    let right_is_book = is_enchanted_book(right);
+    let mut combined = enchantments(result);
+    let mut cost = 0;
+    let mut any_compatible = false;
+    let mut any_incompatible = false;
+    for (name, right_level) in enchantments(right) {
+        let Some(properties) = enchantment(&name) else {
+            continue;
+        };
+        let current = level_of(&combined, &name);
+        let mut level = if current == right_level {
+            right_level + 1
+        } else {
+            right_level.max(current)
+        };
+
+        let mut compatible = is_enchanted_book(left)
+            || is_in(
+                &properties.supported_items,
+                RegistryKey::Item,
+                left.item.registry_key(),
+            );
+        for (other, _) in &combined {
+            if !are_compatible(&name, other) && strip_namespace(other) != strip_namespace(&name) {
+                compatible = false;
+                cost += 1;
+            }
+        }
+        if !compatible {
+            any_incompatible = true;
+            continue;
+        }
+        any_compatible = true;
+        level = level.min(i32::from(properties.max_level));
+        set_level(&mut combined, &name, level);
+        let anvil_cost = if right_is_book {
+            (properties.anvil_cost / 2).max(1)
+        } else {
+            properties.anvil_cost
+        };
+        cost += anvil_cost * level;
+        if left.item_count > 1 {
+            cost = TOO_EXPENSIVE_COST;
+        }
+    }
+    if any_incompatible && !any_compatible {
+        return None;
+    }
+    set_enchantments(result, &combined);
+    Some(cost)
+}
+
+/// Renames the result to the name the player entered. An empty name removes the custom name.
+/// Returns whether the name changed.
+fn rename(result: &mut ItemStack, name: &str) -> bool {
+    let custom_name = result
+        .get_component("custom_name")
+        .and_then(NbtTag::extract_string)
+        .cloned();
+    if name.trim().is_empty() {
+        return result.remove_component("custom_name").is_some();
+    }
+    if custom_name.as_deref() == Some(name) {
+        return false;
+    }
+    result.set_component("custom_name", name);
+    true
+}
+
+/// What an anvil makes of the left item, the optional right item and the name the player entered,
+/// or `None` if it can't do anything with them
+pub fn anvil_result(
+    left: &ItemStack,
+    right: Option<&ItemStack>,
+    name: Option<&str>,
+) -> Option<AnvilResult> {
+    let mut result = left.clone();
+    let mut cost = 0;
+    let mut right_used = 0;
+    let max_damage = left.item.components.max_damage.map(i32::from);
+
+    if let Some(right) = right {
+        let right_is_book = is_enchanted_book(right) && !enchantments(right).is_empty();
+        match max_damage {
+            // Each material repairs a quarter of the durability
+            Some(max_damage) if is_repair_material(left, right) => {
+                let mut repaired = damage(&result).min(max_damage / 4);
+                if repaired <= 0 {
+                    return None;
+                }
+                while repaired > 0 && right_used < right.item_count {
+                    set_damage(&mut result, damage(&result) - repaired);
+                    cost += 1;
+                    right_used += 1;
+                    repaired = damage(&result).min(max_damage / 4);
+                }
+            }
+            _ => {
+                if !right_is_book && (right.item.id != left.item.id || max_damage.is_none()) {
+                    return None;
+                }
+                right_used = right.item_count;
+                // Two items of the same kind add up their durability, with a bonus
+                if let (Some(max_damage), false) = (max_damage, right_is_book) {
+                    let remaining = (max_damage - damage(left))
+                        + (max_damage - damage(right))
+                        + max_damage * 12 / 100;
+                    let new_damage = (max_damage - remaining).max(0);
+                    if new_damage < damage(left) {
+                        set_damage(&mut result, new_damage);
+                        cost += 2;
+                    }
+                }
+                cost += combine_enchantments(&mut result, left, right)?;
+            }
+        }
+    }
+
+    let renamed = name.is_some_and(|name| rename(&mut result, name));
+    let rename_cost = i32::from(renamed);
+    if cost + rename_cost <= 0 {
+        return None;
+    }
+
+    let mut total_cost = repair_cost(left) + right.map_or(0, repair_cost) + cost + rename_cost;
+    // Only renaming is never too expensive
+    if cost == 0 && total_cost >= TOO_EXPENSIVE_COST {
+        total_cost = TOO_EXPENSIVE_COST - 1;
+    }
+    let mut new_repair_cost = repair_cost(&result).max(right.map_or(0, repair_cost));
+    if cost > 0 {
+        new_repair_cost = increased_repair_cost(new_repair_cost);
+    }
+    set_repair_cost(&mut result, new_repair_cost);
+
+    Some(AnvilResult {
+        item: result,
+        cost: total_cost,
+        right_used,
+    })
+}
+
+/// Removes all enchantments but curses. Enchanted books without enchantments become books.
+fn remove_non_curses(mut stack: ItemStack) -> ItemStack {
+    let curses: Vec<_> = enchantments(&stack)
+        .into_iter()
+        .filter(|(name, _)| is_curse(name))
+        .collect();
+    set_enchantments(&mut stack, &curses);
+    if is_enchanted_book(&stack) && curses.is_empty() {
+        stack.item = Item::BOOK;
+    }
+    let repair_cost = curses.iter().fold(0, |cost, _| increased_repair_cost(cost));
+    set_repair_cost(&mut stack, repair_cost);
+    stack
+}
+
+/// What a grindstone makes of the items put into it, or `None` if it can't do anything with them.
+/// A single item loses its enchantments, two items of the same kind are combined.
+pub fn grindstone_result(top: Option<&ItemStack>, bottom: Option<&ItemStack>) -> Option<ItemStack> {
+    if top.is_some_and(|top| top.item_count > 1)
+        || bottom.is_some_and(|bottom| bottom.item_count > 1)
+    {
+        return None;
+    }
+    let (top, bottom) = match (top, bottom) {
+        (None, None) => return None,
+        (Some(item), None) | (None, Some(item)) => {
+            if enchantments(item).is_empty() {
+                return None;
+            }
+            return Some(remove_non_curses(item.clone()));
+        }
+        (Some(top), Some(bottom)) => (top, bottom),
+    };
+
+    if top.item.id != bottom.item.id {
+        return None;
+    }
+    let mut result = top.clone();
+    match top.item.components.max_damage.map(i32::from) {
+        Some(max_damage) => {
+            let remaining =
+                (max_damage - damage(top)) + (max_damage - damage(bottom)) + max_damage * 5 / 100;
+            set_damage(&mut result, (max_damage - remaining).max(0));
+        }
+        None => {
+            if top.item.components.max_stack_size < 2 || top != bottom {
+                return None;
+            }
+            result.item_count = 2;
+        }
+    }
+
+    let mut combined = enchantments(&result);
+    for (name, level) in enchantments(bottom) {
+        let current = level_of(&combined, &name);
+        if !is_curse(&name) || current == 0 {
+            set_level(&mut combined, &name, level.max(current));
+        }
+    }
+    set_enchantments(&mut result, &combined);
+    Some(remove_non_curses(result))
+}
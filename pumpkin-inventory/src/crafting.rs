@@ -2,69 +2,69 @@ use pumpkin_data::{
     item::Item,
     tag::{get_tag_values, RegistryEntryList, RegistryKey, TagType},
 };
-use pumpkin_registry::{flatten_3x3, RecipeResult, RECIPES};
+use pumpkin_registry::{find_recipe, flatten_3x3, RecipeResult, RecipeType};
 use pumpkin_world::item::ItemStack;
 use rayon::prelude::*;
 
 #[inline(always)]
-fn check_ingredient_type(ingredient_type: &TagType, input: ItemStack) -> bool {
+fn check_ingredient_type(ingredient_type: &TagType, input: &ItemStack) -> bool {
     match ingredient_type {
         TagType::Tag(tag) => {
-            let _items = match get_tag_values(RegistryKey::Item, tag) {
+            let items = match get_tag_values(RegistryKey::Item, tag) {
                 Some(items) => items,
                 None => return false,
             };
-            // TODO
-            false
-            // items.iter().any(|tag| check_ingredient_type(&tag, input))
+            let name = input.item.registry_key();
+            items.iter().flatten().any(|item| item == name)
         }
-        TagType::Item(item) => Item::from_name(item).is_some_and(|item| item.id == input.item.id),
+        TagType::Item(item) => Item::from_name(&item.replace("minecraft:", ""))
+            .is_some_and(|item| item.id == input.item.id),
     }
 }
 
 pub fn check_if_matches_crafting(input: [[Option<ItemStack>; 3]; 3]) -> Option<ItemStack> {
     let input = flatten_3x3(input);
-    RECIPES
-        .par_iter()
-        .find_any(|recipe| {
-            let patterns = recipe.pattern();
-            if patterns
-                .iter()
-                .flatten()
-                .flatten()
-                .all(|slot| slot.is_none())
-            {
-                false
-            } else if recipe.recipe_type.is_shapeless() {
-                shapeless_crafting_match(input, recipe.pattern())
-            } else {
-                patterns.par_iter().any(|pattern| {
-                    pattern.iter().enumerate().all(|(i, row)| {
-                        row.iter()
-                            .enumerate()
-                            .all(|(j, item)| match (item, input[i][j]) {
-                                (Some(item), Some(input)) => ingredient_slot_check(item, input),
-                                (None, None) => true,
-                                (Some(_), None) | (None, Some(_)) => false,
-                            })
-                    })
+    let result = find_recipe(|recipe| {
+        let patterns = recipe.pattern();
+        if !matches!(recipe.recipe_type, RecipeType::Crafting(_)) {
+            false
+        } else if patterns
+            .iter()
+            .flatten()
+            .flatten()
+            .all(|slot| slot.is_none())
+        {
+            false
+        } else if recipe.recipe_type.is_shapeless() {
+            shapeless_crafting_match(&input, recipe.pattern())
+        } else {
+            patterns.par_iter().any(|pattern| {
+                pattern.iter().enumerate().all(|(i, row)| {
+                    row.iter()
+                        .enumerate()
+                        .all(|(j, item)| match (item, &input[i][j]) {
+                            (Some(item), Some(input)) => ingredient_slot_check(item, input),
+                            (None, None) => true,
+                            (Some(_), None) | (None, Some(_)) => false,
+                        })
                 })
-            }
-        })
-        .map(|recipe| match recipe.result() {
-            RecipeResult::Single { id, .. } => Some(ItemStack {
-                item: Item::from_name(id).unwrap(),
-                item_count: 1,
-            }),
-            RecipeResult::Many { id, count, .. } => Some(ItemStack {
-                item: Item::from_name(id).unwrap(),
-                item_count: *count,
-            }),
-            RecipeResult::Special => None,
-        })?
+            })
+        }
+    })?;
+    match result {
+        RecipeResult::Single { id, .. } => Some(ItemStack::new(
+            1,
+            Item::from_name(&id.replace("minecraft:", ""))?,
+        )),
+        RecipeResult::Many { id, count, .. } => Some(ItemStack::new(
+            count,
+            Item::from_name(&id.replace("minecraft:", ""))?,
+        )),
+        RecipeResult::Special => None,
+    }
 }
 
-fn ingredient_slot_check(recipe_item: &RegistryEntryList, input: ItemStack) -> bool {
+fn ingredient_slot_check(recipe_item: &RegistryEntryList, input: &ItemStack) -> bool {
     match recipe_item {
         RegistryEntryList::Single(ingredient) => check_ingredient_type(ingredient, input),
         RegistryEntryList::Many(ingredients) => ingredients
@@ -73,7 +73,7 @@ fn ingredient_slot_check(recipe_item: &RegistryEntryList, input: ItemStack) -> b
     }
 }
 fn shapeless_crafting_match(
-    input: [[Option<ItemStack>; 3]; 3],
+    input: &[[Option<ItemStack>; 3]; 3],
     pattern: &[[[Option<RegistryEntryList>; 3]; 3]],
 ) -> bool {
     let mut pattern: Vec<RegistryEntryList> = pattern
@@ -83,7 +83,7 @@ fn shapeless_crafting_match(
         .flatten()
         .cloned()
         .collect();
-    for item in input.into_iter().flatten().flatten() {
+    for item in input.iter().flatten().flatten() {
         if let Some(index) = pattern.iter().enumerate().find_map(|(i, recipe_item)| {
             if ingredient_slot_check(recipe_item, item) {
                 Some(i)
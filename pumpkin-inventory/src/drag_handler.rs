@@ -71,10 +71,8 @@ impl DragHandler {
             Err(InventoryError::MultiplePlayersDragging)?
         }
         let mut slots = container.all_slots();
-        let slots_cloned: Vec<Option<ItemStack>> = slots
-            .iter()
-            .map(|stack| stack.map(|item| item.to_owned()))
-            .collect();
+        let slots_cloned: Vec<Option<ItemStack>> =
+            slots.iter().map(|stack| (**stack).clone()).collect();
         let Some(carried_item) = maybe_carried_item else {
             return Ok(());
         };
@@ -83,15 +81,14 @@ impl DragHandler {
             // Checked in any function that uses this function.
             MouseDragType::Middle => {
                 for slot in &drag.slots {
-                    *slots[*slot] = *maybe_carried_item;
+                    *slots[*slot] = maybe_carried_item.clone();
                 }
             }
             MouseDragType::Right => {
-                let mut single_item = *carried_item;
+                let mut single_item = carried_item.clone();
                 single_item.item_count = 1;
 
-                let changing_slots =
-                    drag.possibly_changing_slots(&slots_cloned, carried_item.item.id);
+                let changing_slots = drag.possibly_changing_slots(&slots_cloned, &single_item);
                 changing_slots.for_each(|slot| {
                     if carried_item.item_count != 0 {
                         carried_item.item_count -= 1;
@@ -103,7 +100,7 @@ impl DragHandler {
                                 carried_item.item_count += 1;
                             }
                         } else {
-                            *slots[slot] = Some(single_item)
+                            *slots[slot] = Some(single_item.clone())
                         }
                     }
                 });
@@ -116,8 +113,8 @@ impl DragHandler {
                 // TODO: Handle dragging a stack with greater amount than item allows as max unstackable
                 // In that specific case, follow MouseDragType::Right behaviours instead!
 
-                let changing_slots =
-                    drag.possibly_changing_slots(&slots_cloned, carried_item.item.id);
+                let carried = carried_item.clone();
+                let changing_slots = drag.possibly_changing_slots(&slots_cloned, &carried);
                 let amount_of_slots = changing_slots.clone().count();
                 let (amount_per_slot, remainder) = if amount_of_slots == 0 {
                     // TODO: please work lol
@@ -128,9 +125,9 @@ impl DragHandler {
                         carried_item.item_count.rem_euclid(amount_of_slots as u8),
                     )
                 };
-                let mut item_in_each_slot = *carried_item;
+                let mut item_in_each_slot = carried_item.clone();
                 item_in_each_slot.item_count = amount_per_slot;
-                changing_slots.for_each(|slot| *slots[slot] = Some(item_in_each_slot));
+                changing_slots.for_each(|slot| *slots[slot] = Some(item_in_each_slot.clone()));
 
                 if remainder > 0 {
                     carried_item.item_count = remainder;
@@ -153,14 +150,14 @@ impl Drag {
     fn possibly_changing_slots<'a>(
         &'a self,
         slots: &'a [Option<ItemStack>],
-        carried_item_id: u16,
+        carried_item: &'a ItemStack,
     ) -> impl Iterator<Item = usize> + 'a + Clone {
         self.slots.iter().filter_map(move |slot_index| {
             let slot = &slots[*slot_index];
 
             match slot {
                 Some(item_slot) => {
-                    if item_slot.item.id == carried_item_id {
+                    if item_slot == carried_item {
                         Some(*slot_index)
                     } else {
                         None
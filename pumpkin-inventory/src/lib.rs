@@ -3,6 +3,7 @@ use crate::player::PlayerInventory;
 use pumpkin_data::screen::WindowType;
 use pumpkin_world::item::ItemStack;
 
+mod anvil;
 pub mod container_click;
 mod crafting;
 pub mod drag_handler;
@@ -11,6 +12,7 @@ mod open_container;
 pub mod player;
 pub mod window_property;
 
+pub use anvil::TOO_EXPENSIVE_COST;
 pub use error::InventoryError;
 pub use open_container::*;
 
@@ -36,7 +38,7 @@ pub trait Container: Sync + Send {
         if taking_crafted {
             match (all_slots[slot].as_mut(), carried_item.as_mut()) {
                 (Some(s1), Some(s2)) => {
-                    if s1.item.id == s2.item.id {
+                    if s1 == s2 {
                         handle_item_change(all_slots[slot], carried_item, mouse_click);
                     }
                 }
@@ -88,10 +90,18 @@ pub trait Container: Sync + Send {
     fn crafted_item_slot(&self) -> Option<ItemStack> {
         self.all_slots_ref()
             .get(self.crafting_output_slot()?)?
-            .copied()
+            .cloned()
     }
 
     fn recipe_used(&mut self) {}
+
+    /// The experience levels taking the crafted item costs, like in an anvil
+    fn level_cost(&self) -> i32 {
+        0
+    }
+
+    /// Sets the name the crafted item gets, like in an anvil
+    fn set_item_name(&mut self, _name: &str) {}
 }
 
 pub struct EmptyContainer;
@@ -130,7 +140,7 @@ pub fn handle_item_take(
     let Some(item) = item_slot else {
         return;
     };
-    let mut new_item = *item;
+    let mut new_item = item.clone();
 
     match mouse_click {
         MouseClick::Left => {
@@ -152,12 +162,10 @@ pub fn handle_item_change(
     match (current_slot.as_mut(), carried_slot.as_mut()) {
         // Swap or combine current and carried
         (Some(current), Some(carried)) => {
-            if current.item.id == carried.item.id {
+            if current == carried {
                 combine_stacks(carried_slot, current, mouse_click);
             } else if mouse_click == MouseClick::Left {
-                let carried = *carried;
-                *carried_slot = Some(current.to_owned());
-                *current_slot = Some(carried.to_owned());
+                std::mem::swap(current, carried);
             }
         }
         // Put held stack into empty slot
@@ -168,7 +176,7 @@ pub fn handle_item_change(
             }
             MouseClick::Right => {
                 carried.item_count -= 1;
-                let mut new = *carried;
+                let mut new = carried.clone();
                 new.item_count = 1;
                 *current_slot = Some(new);
             }
@@ -297,4 +305,10 @@ impl<'a> Container for OptionallyCombinedContainer<'a, 'a> {
             None => self.inventory.recipe_used(),
         }
     }
+
+    fn level_cost(&self) -> i32 {
+        self.container
+            .as_ref()
+            .map_or(0, |container| container.level_cost())
+    }
 }
@@ -1,3 +1,4 @@
+use crate::anvil::{anvil_result, grindstone_result};
 use crate::crafting::check_if_matches_crafting;
 use crate::Container;
 use pumpkin_data::screen::WindowType;
@@ -57,6 +58,25 @@ impl OpenContainer {
         }
     }
 
+    /// A container which does not belong to a block, like the ender chest of a player. The same
+    /// container can be opened more than once.
+    pub fn new_virtual_container(
+        player_id: i32,
+        container: Arc<Mutex<Box<dyn Container>>>,
+    ) -> Self {
+        Self {
+            players: vec![player_id],
+            container,
+            location: None,
+            block: None,
+        }
+    }
+
+    /// Whether the container does not belong to a block, see [`Self::new_virtual_container`]
+    pub fn is_virtual(&self) -> bool {
+        self.location.is_none() && self.block.is_none()
+    }
+
     pub fn is_location(&self, try_position: BlockPos) -> bool {
         if let Some(location) = self.location {
             location == try_position
@@ -69,6 +89,51 @@ impl OpenContainer {
         self.container.lock().await.clear_all_slots();
     }
 
+    /// Returns a copy of the items in all slots of the container.
+    pub async fn get_items(&self) -> Vec<Option<ItemStack>> {
+        self.container
+            .lock()
+            .await
+            .all_slots_ref()
+            .into_iter()
+            .map(|slot| slot.cloned())
+            .collect()
+    }
+
+    /// Replaces the items in the slots of the container, e.g. with the items of a block entity.
+    pub async fn set_items(&self, items: &[Option<ItemStack>]) {
+        let mut container = self.container.lock().await;
+        for (slot, item) in container.all_slots().into_iter().zip(items) {
+            slot.clone_from(item);
+        }
+    }
+
+    /// Changes the items in the slots of the container, e.g. when a hopper moves an item. The
+    /// container is locked meanwhile, so the players using it can't change them at the same time.
+    pub async fn update_items<R>(
+        &self,
+        update: impl FnOnce(&mut [&mut Option<ItemStack>]) -> R,
+    ) -> R {
+        let mut container = self.container.lock().await;
+        update(&mut container.all_slots())
+    }
+
+    /// Takes the items out of the input slots of a container which crafts something, e.g. to give
+    /// them back to the player closing it. Other containers keep their items.
+    pub async fn take_crafting_input_items(&self) -> Vec<ItemStack> {
+        let mut container = self.container.lock().await;
+        let Some(output_slot) = container.crafting_output_slot() else {
+            return Vec::new();
+        };
+        container
+            .all_slots()
+            .into_iter()
+            .enumerate()
+            .filter(|(slot, _)| *slot != output_slot)
+            .filter_map(|(_, stack)| stack.take())
+            .collect()
+    }
+
     pub fn clear_all_players(&mut self) {
         self.players.clear();
     }
@@ -98,7 +163,7 @@ pub struct Chest([Option<ItemStack>; 27]);
 
 impl Chest {
     pub fn new() -> Self {
-        Self([None; 27])
+        Self([const { None }; 27])
     }
 }
 impl Container for Chest {
@@ -159,8 +224,8 @@ impl Container for CraftingTable {
     }
 
     fn craft(&mut self) -> bool {
-        let old_output = self.output;
-        self.output = check_if_matches_crafting(self.input);
+        let old_output = self.output.take();
+        self.output = check_if_matches_crafting(self.input.clone());
         old_output != self.output
             || self.input.iter().flatten().any(|s| s.is_some())
             || self.output.is_some()
@@ -215,3 +280,248 @@ impl Container for Furnace {
         slots
     }
 }
+
+#[derive(Default)]
+pub struct Hopper([Option<ItemStack>; 5]);
+
+impl Container for Hopper {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Hopper
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Hopper"
+    }
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+}
+
+#[derive(Default)]
+pub struct Dispenser([Option<ItemStack>; 9]);
+
+impl Container for Dispenser {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Generic3x3
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Dispenser"
+    }
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+}
+
+#[derive(Default)]
+pub struct Dropper([Option<ItemStack>; 9]);
+
+impl Container for Dropper {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Generic3x3
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Dropper"
+    }
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+}
+
+#[derive(Default)]
+pub struct EnderChest([Option<ItemStack>; 27]);
+
+impl Container for EnderChest {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Generic9x3
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Ender Chest"
+    }
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+}
+
+/// A container whose items are deleted once it is closed, as it is not kept anywhere
+pub struct Trash([Option<ItemStack>; 36]);
+
+impl Default for Trash {
+    fn default() -> Self {
+        Self([const { None }; 36])
+    }
+}
+
+impl Container for Trash {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Generic9x4
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Trash"
+    }
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+}
+
+#[derive(Default)]
+pub struct Anvil {
+    input: [Option<ItemStack>; 2],
+    output: Option<ItemStack>,
+    /// The name the player entered, `None` until they entered one
+    item_name: Option<String>,
+    cost: i32,
+    /// How many of the right item the output uses up
+    right_used: u8,
+}
+
+impl Container for Anvil {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Anvil
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Repair & Name"
+    }
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        let mut slots: Vec<_> = self.input.iter_mut().collect();
+        slots.push(&mut self.output);
+        slots
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        let mut slots: Vec<_> = self.input.iter().map(|slot| slot.as_ref()).collect();
+        slots.push(self.output.as_ref());
+        slots
+    }
+
+    fn all_combinable_slots(&self) -> Vec<Option<&ItemStack>> {
+        self.input.iter().map(|slot| slot.as_ref()).collect()
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.input.iter_mut().collect()
+    }
+
+    fn craft(&mut self) -> bool {
+        let old_output = self.output.take();
+        let old_cost = self.cost;
+        let result = match &self.input {
+            [Some(left), right] => anvil_result(left, right.as_ref(), self.item_name.as_deref()),
+            [None, _] => None,
+        };
+        (self.output, self.cost, self.right_used) = match result {
+            Some(result) => (Some(result.item), result.cost, result.right_used),
+            None => (None, 0, 0),
+        };
+        old_output != self.output
+            || old_cost != self.cost
+            || self.input.iter().any(Option::is_some)
+            || self.output.is_some()
+    }
+
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
+        *slot < 2
+    }
+
+    fn recipe_used(&mut self) {
+        self.input[0] = None;
+        if let Some(right) = &mut self.input[1] {
+            if right.item_count > self.right_used {
+                right.item_count -= self.right_used;
+            } else {
+                self.input[1] = None;
+            }
+        }
+        self.item_name = None;
+    }
+
+    fn level_cost(&self) -> i32 {
+        self.cost
+    }
+
+    fn set_item_name(&mut self, name: &str) {
+        self.item_name = Some(name.to_string());
+    }
+}
+
+#[derive(Default)]
+pub struct Grindstone {
+    input: [Option<ItemStack>; 2],
+    output: Option<ItemStack>,
+}
+
+impl Container for Grindstone {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Grindstone
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Repair & Disenchant"
+    }
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        let mut slots: Vec<_> = self.input.iter_mut().collect();
+        slots.push(&mut self.output);
+        slots
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        let mut slots: Vec<_> = self.input.iter().map(|slot| slot.as_ref()).collect();
+        slots.push(self.output.as_ref());
+        slots
+    }
+
+    fn all_combinable_slots(&self) -> Vec<Option<&ItemStack>> {
+        self.input.iter().map(|slot| slot.as_ref()).collect()
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.input.iter_mut().collect()
+    }
+
+    fn craft(&mut self) -> bool {
+        let old_output = self.output.take();
+        self.output = grindstone_result(self.input[0].as_ref(), self.input[1].as_ref());
+        old_output != self.output || self.input.iter().any(Option::is_some) || self.output.is_some()
+    }
+
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
+        *slot < 2
+    }
+
+    fn recipe_used(&mut self) {
+        // TODO: Drop the experience of the removed enchantments
+        self.input = [None, None];
+    }
+}
@@ -30,10 +30,10 @@ impl PlayerInventory {
 
     pub fn new() -> Self {
         Self {
-            crafting: [None; 4],
+            crafting: [const { None }; 4],
             crafting_output: None,
-            items: [None; 36],
-            armor: [None; 4],
+            items: [const { None }; 36],
+            armor: [const { None }; 4],
             offhand: None,
             // TODO: What when player spawns in with an different index ?
             selected: 0,
@@ -151,11 +151,14 @@ impl PlayerInventory {
     }
 
     /// Checks if we can merge an existing item into an Stack or if a any new Slot is empty
-    pub fn collect_item_slot(&self, item_id: u16) -> Option<usize> {
+    pub fn collect_item_slot(&self, stack: &ItemStack) -> Option<usize> {
         // Lets try to merge first
         // TODO: Max stack size
-        if let Some(stack) = self.get_slot_with_item(item_id, 64) {
-            return Some(stack);
+        if let Some(index) = self.items.iter().position(|item| {
+            item.as_ref()
+                .is_some_and(|item| item == stack && item.item_count <= 64)
+        }) {
+            return Some(index + 9);
         }
         if let Some(empty) = self.get_empty_slot() {
             return Some(empty);
@@ -268,9 +271,9 @@ impl Container for PlayerInventory {
     }
 
     fn craft(&mut self) -> bool {
-        let v1 = [self.crafting[0], self.crafting[1], None];
-        let v2 = [self.crafting[2], self.crafting[3], None];
-        let v3 = [None; 3];
+        let v1 = [self.crafting[0].clone(), self.crafting[1].clone(), None];
+        let v2 = [self.crafting[2].clone(), self.crafting[3].clone(), None];
+        let v3 = [const { None }; 3];
         let together = [v1, v2, v3];
 
         self.crafting_output = check_if_matches_crafting(together);
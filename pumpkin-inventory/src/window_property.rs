@@ -55,6 +55,14 @@ pub enum Anvil {
     RepairCost,
 }
 
+impl WindowPropertyTrait for Anvil {
+    fn to_id(self) -> i16 {
+        match self {
+            Anvil::RepairCost => 0,
+        }
+    }
+}
+
 pub enum BrewingStand {
     BrewTime,
     FuelTime,
@@ -14,6 +14,23 @@ pub fn event(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as ItemStruct);
     let name = &input.ident;
 
+    // Events marked with `#[cancellable]` have a `cancelled` field at this point
+    let is_cancellable = input.fields.iter().any(|field| {
+        field
+            .ident
+            .as_ref()
+            .is_some_and(|ident| ident == "cancelled")
+    });
+    let as_cancellable = if is_cancellable {
+        quote! {
+            fn as_cancellable(&self) -> Option<&dyn crate::plugin::Cancellable> {
+                Some(self)
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     quote! {
         impl crate::plugin::Event for #name {
             fn get_name_static() -> &'static str {
@@ -31,6 +48,8 @@ pub fn event(item: TokenStream) -> TokenStream {
             fn as_any(&self) -> &dyn std::any::Any {
                 self
             }
+
+            #as_cancellable
         }
     }
     .into()
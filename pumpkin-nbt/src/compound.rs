@@ -151,6 +151,33 @@ impl NbtCompound {
         None
     }
 
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut NbtTag> {
+        self.child_tags
+            .iter_mut()
+            .find(|(key, _)| key.as_str() == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Removes a tag, returning it if it existed
+    pub fn remove(&mut self, name: &str) -> Option<NbtTag> {
+        let index = self.child_tags.iter().position(|(key, _)| key == name)?;
+        Some(self.child_tags.remove(index).1)
+    }
+
+    /// Whether every tag of this compound is contained in the tag of the other compound with
+    /// the same key, see [`NbtTag::is_contained_in`]
+    pub fn is_contained_in(&self, other: &NbtCompound) -> bool {
+        self.child_tags.iter().all(|(key, value)| {
+            other
+                .get(key)
+                .is_some_and(|other| value.is_contained_in(other))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.child_tags.is_empty()
+    }
+
     pub fn get_short(&self, name: &str) -> Option<i16> {
         self.get(name).and_then(|tag| tag.extract_short())
     }
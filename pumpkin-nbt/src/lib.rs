@@ -15,6 +15,7 @@ use thiserror::Error;
 pub mod compound;
 pub mod deserializer;
 pub mod serializer;
+pub mod snbt;
 pub mod tag;
 
 pub use deserializer::{from_bytes, from_bytes_unnamed};
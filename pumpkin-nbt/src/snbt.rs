@@ -0,0 +1,328 @@
+//! Parses stringified NBT, the text format of NBT used in commands, e.g.
+//! `{CustomName: "Chest", Items: [{Slot: 0b, id: "minecraft:stone", count: 1}]}`.
+
+use thiserror::Error;
+
+use crate::{compound::NbtCompound, tag::NbtTag};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Invalid NBT at position {position}: {message}")]
+pub struct SnbtError {
+    /// The character the error was found at
+    pub position: usize,
+    pub message: String,
+}
+
+/// Parses a compound, like `{id: "minecraft:chest"}`. Nothing may follow it.
+pub fn from_snbt(input: &str) -> Result<NbtCompound, SnbtError> {
+    let mut parser = Parser::new(input);
+    let compound = parser.compound()?;
+    parser.end()?;
+    Ok(compound)
+}
+
+/// Parses a single tag of any type, like `5b`, `"text"` or `[1, 2, 3]`. Nothing may follow it.
+pub fn tag_from_snbt(input: &str) -> Result<NbtTag, SnbtError> {
+    let mut parser = Parser::new(input);
+    let tag = parser.tag()?;
+    parser.end()?;
+    Ok(tag)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn error(&self, message: impl Into<String>) -> SnbtError {
+        SnbtError {
+            position: self.position,
+            message: message.into(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.input[self.position..].chars().next()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SnbtError> {
+        if self.peek() != Some(expected) {
+            return Err(self.error(format!("Expected '{expected}'")));
+        }
+        self.position += expected.len_utf8();
+        Ok(())
+    }
+
+    fn end(&mut self) -> Result<(), SnbtError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error("Unexpected trailing data")),
+        }
+    }
+
+    fn tag(&mut self) -> Result<NbtTag, SnbtError> {
+        match self.peek() {
+            Some('{') => Ok(NbtTag::Compound(self.compound()?)),
+            Some('[') => self.list_or_array(),
+            Some('"' | '\'') => Ok(NbtTag::String(self.quoted_string()?)),
+            Some(_) => {
+                let start = self.position;
+                let word = self.unquoted_string();
+                if word.is_empty() {
+                    self.position = start;
+                    return Err(self.error("Expected a value"));
+                }
+                Ok(unquoted_tag(word))
+            }
+            None => Err(self.error("Expected a value")),
+        }
+    }
+
+    fn compound(&mut self) -> Result<NbtCompound, SnbtError> {
+        self.expect('{')?;
+        let mut compound = NbtCompound::new();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(compound);
+        }
+        loop {
+            let key = match self.peek() {
+                Some('"' | '\'') => self.quoted_string()?,
+                _ => self.unquoted_string().to_string(),
+            };
+            if key.is_empty() {
+                return Err(self.error("Expected a key"));
+            }
+            self.expect(':')?;
+            let value = self.tag()?;
+            compound.put(&key, value);
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(compound);
+                }
+                _ => return Err(self.error("Expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn list_or_array(&mut self) -> Result<NbtTag, SnbtError> {
+        self.expect('[')?;
+        let rest = &self.input[self.position..];
+        let array_type = ['B', 'I', 'L']
+            .into_iter()
+            .find(|prefix| rest.starts_with(&format!("{prefix};")));
+        if let Some(array_type) = array_type {
+            self.position += 2;
+            return self.array(array_type);
+        }
+
+        let values = self.values(']')?;
+        if let Some(first) = values.first() {
+            if values
+                .iter()
+                .any(|value| value.get_type_id() != first.get_type_id())
+            {
+                return Err(self.error("All values of a list must have the same type"));
+            }
+        }
+        Ok(NbtTag::List(values.into_boxed_slice()))
+    }
+
+    fn array(&mut self, array_type: char) -> Result<NbtTag, SnbtError> {
+        let values = self.values(']')?;
+        let invalid = || self.error(format!("Invalid value in {array_type} array"));
+        Ok(match array_type {
+            'B' => NbtTag::ByteArray(
+                values
+                    .iter()
+                    .map(|value| match value {
+                        NbtTag::Byte(byte) => Ok(*byte as u8),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            'I' => NbtTag::IntArray(
+                values
+                    .iter()
+                    .map(|value| match value {
+                        NbtTag::Int(int) => Ok(*int),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => NbtTag::LongArray(
+                values
+                    .iter()
+                    .map(|value| match value {
+                        NbtTag::Long(long) => Ok(*long),
+                        NbtTag::Int(int) => Ok(i64::from(*int)),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+
+    /// Reads values separated by commas, until the closing character
+    fn values(&mut self, close: char) -> Result<Vec<NbtTag>, SnbtError> {
+        let mut values = Vec::new();
+        if self.peek() == Some(close) {
+            self.position += 1;
+            return Ok(values);
+        }
+        loop {
+            values.push(self.tag()?);
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(c) if c == close => {
+                    self.position += 1;
+                    return Ok(values);
+                }
+                _ => return Err(self.error(format!("Expected ',' or '{close}'"))),
+            }
+        }
+    }
+
+    fn quoted_string(&mut self) -> Result<String, SnbtError> {
+        let quote = self.peek().ok_or_else(|| self.error("Expected a string"))?;
+        self.position += 1;
+        let mut string = String::new();
+        let mut chars = self.input[self.position..].char_indices();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    let Some((_, escaped)) = chars.next() else {
+                        break;
+                    };
+                    string.push(escaped);
+                }
+                c if c == quote => {
+                    self.position += offset + 1;
+                    return Ok(string);
+                }
+                c => string.push(c),
+            }
+        }
+        Err(self.error("Unclosed string"))
+    }
+
+    fn unquoted_string(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let input = self.input;
+        let rest = &input[self.position..];
+        let length = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')))
+            .unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+}
+
+/// Reads a number, with a suffix for its type like `5b`, or a boolean. Anything else is a
+/// string, like vanilla does.
+fn unquoted_tag(word: &str) -> NbtTag {
+    match word {
+        "true" => return NbtTag::Byte(1),
+        "false" => return NbtTag::Byte(0),
+        _ => {}
+    }
+    let is_integer = |number: &str| {
+        let digits = number.strip_prefix(['-', '+']).unwrap_or(number);
+        !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+    };
+    let is_decimal =
+        |number: &str| number.parse::<f64>().is_ok() && number.chars().any(|c| c.is_ascii_digit());
+    let (number, suffix) = word.split_at(word.len() - 1);
+    let tag = match suffix.to_ascii_lowercase().as_str() {
+        "b" if is_integer(number) => number.parse().ok().map(NbtTag::Byte),
+        "s" if is_integer(number) => number.parse().ok().map(NbtTag::Short),
+        "l" if is_integer(number) => number.parse().ok().map(NbtTag::Long),
+        "f" if is_decimal(number) => number.parse().ok().map(NbtTag::Float),
+        "d" if is_decimal(number) => number.parse().ok().map(NbtTag::Double),
+        _ if is_integer(word) => word.parse().ok().map(NbtTag::Int),
+        _ if is_decimal(word) && word.contains('.') => word.parse().ok().map(NbtTag::Double),
+        _ => None,
+    };
+    tag.unwrap_or_else(|| NbtTag::String(word.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{compound::NbtCompound, tag::NbtTag};
+
+    use super::{from_snbt, tag_from_snbt};
+
+    #[test]
+    fn parses_values() {
+        assert_eq!(tag_from_snbt("5b"), Ok(NbtTag::Byte(5)));
+        assert_eq!(tag_from_snbt("-3s"), Ok(NbtTag::Short(-3)));
+        assert_eq!(tag_from_snbt("42"), Ok(NbtTag::Int(42)));
+        assert_eq!(tag_from_snbt("7L"), Ok(NbtTag::Long(7)));
+        assert_eq!(tag_from_snbt("1.5f"), Ok(NbtTag::Float(1.5)));
+        assert_eq!(tag_from_snbt("2.5"), Ok(NbtTag::Double(2.5)));
+        assert_eq!(tag_from_snbt("true"), Ok(NbtTag::Byte(1)));
+        // Unquoted strings can't contain colons
+        assert!(tag_from_snbt("minecraft:stone").is_err());
+        assert_eq!(
+            tag_from_snbt("\"minecraft:stone\""),
+            Ok(NbtTag::String("minecraft:stone".to_string()))
+        );
+        assert_eq!(
+            tag_from_snbt("'say \\'hi\\''"),
+            Ok(NbtTag::String("say 'hi'".to_string()))
+        );
+        assert_eq!(
+            tag_from_snbt("[I; 1, 2]"),
+            Ok(NbtTag::IntArray(vec![1, 2].into_boxed_slice()))
+        );
+    }
+
+    #[test]
+    fn parses_compounds() {
+        let compound =
+            from_snbt(r#"{Items: [{Slot: 3b, id: "minecraft:diamond"}], Lock: ''}"#).unwrap();
+        let mut item = NbtCompound::new();
+        item.put("Slot", NbtTag::Byte(3));
+        item.put("id", NbtTag::String("minecraft:diamond".to_string()));
+        let mut expected = NbtCompound::new();
+        expected.put(
+            "Items",
+            NbtTag::List(vec![NbtTag::Compound(item)].into_boxed_slice()),
+        );
+        expected.put("Lock", NbtTag::String(String::new()));
+        assert_eq!(compound, expected);
+    }
+
+    #[test]
+    fn compares_partially() {
+        let expected = from_snbt("{Items: [{id: \"minecraft:diamond\"}]}").unwrap();
+        let actual =
+            from_snbt("{Items: [{Slot: 0b, id: \"minecraft:stone\"}, {Slot: 3b, id: \"minecraft:diamond\"}], Lock: ''}")
+                .unwrap();
+        assert!(expected.is_contained_in(&actual));
+        assert!(!actual.is_contained_in(&expected));
+        assert!(NbtCompound::new().is_contained_in(&actual));
+    }
+
+    #[test]
+    fn rejects_invalid_nbt() {
+        assert!(from_snbt("{id: }").is_err());
+        assert!(from_snbt("{id: 1").is_err());
+        assert!(from_snbt("{id: 1} 2").is_err());
+        assert!(from_snbt("{list: [1, 2b]}").is_err());
+        assert!(from_snbt("{name: \"unclosed}").is_err());
+    }
+}
@@ -336,6 +336,21 @@ impl NbtTag {
         }
     }
 
+    /// Whether the other tag contains this one, like vanilla compares NBT in commands: Keys
+    /// of compounds which are not in this tag and values of lists which are not in this list
+    /// are ignored.
+    pub fn is_contained_in(&self, other: &NbtTag) -> bool {
+        match (self, other) {
+            (NbtTag::Compound(compound), NbtTag::Compound(other)) => {
+                compound.is_contained_in(other)
+            }
+            (NbtTag::List(list), NbtTag::List(other)) => list
+                .iter()
+                .all(|value| other.iter().any(|other| value.is_contained_in(other))),
+            _ => self == other,
+        }
+    }
+
     pub fn extract_compound(&self) -> Option<&NbtCompound> {
         match self {
             NbtTag::Compound(compound) => Some(compound),
@@ -389,3 +404,21 @@ impl From<bool> for NbtTag {
         NbtTag::Byte(value as i8)
     }
 }
+
+impl From<i32> for NbtTag {
+    fn from(value: i32) -> Self {
+        NbtTag::Int(value)
+    }
+}
+
+impl From<String> for NbtTag {
+    fn from(value: String) -> Self {
+        NbtTag::String(value)
+    }
+}
+
+impl From<NbtCompound> for NbtTag {
+    fn from(value: NbtCompound) -> Self {
+        NbtTag::Compound(value)
+    }
+}
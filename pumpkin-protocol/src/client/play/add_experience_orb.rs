@@ -0,0 +1,26 @@
+use pumpkin_data::packet::clientbound::PLAY_ADD_EXPERIENCE_ORB;
+use pumpkin_macros::client_packet;
+use pumpkin_util::math::vector3::Vector3;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Shows an experience orb, which the client sizes by its value
+#[derive(Serialize)]
+#[client_packet(PLAY_ADD_EXPERIENCE_ORB)]
+pub struct CAddExperienceOrb {
+    entity_id: VarInt,
+    position: Vector3<f64>,
+    /// The experience points the orb gives
+    value: i16,
+}
+
+impl CAddExperienceOrb {
+    pub fn new(entity_id: VarInt, position: Vector3<f64>, value: i16) -> Self {
+        Self {
+            entity_id,
+            position,
+            value,
+        }
+    }
+}
@@ -1,13 +1,20 @@
 use crate::{bytebuf::ByteBufMut, codec::bit_set::BitSet, ClientPacket, VarInt};
 
 use bytes::{BufMut, BytesMut};
-use pumpkin_data::packet::clientbound::PLAY_LEVEL_CHUNK_WITH_LIGHT;
+use pumpkin_data::{chunk::Biome, packet::clientbound::PLAY_LEVEL_CHUNK_WITH_LIGHT};
 use pumpkin_macros::client_packet;
+use pumpkin_nbt::{Nbt, END_ID};
+use pumpkin_util::math::ceil_log2;
 use pumpkin_world::{
-    chunk::{ChunkData, SUBCHUNKS_COUNT},
+    block::entity::block_entity_type_id,
+    chunk::{ChunkData, ChunkSectionBiomes, SUBCHUNKS_COUNT},
+    light::{ChunkLight, LightKind, LightSection, LIGHT_SECTION_BYTES, MAX_LIGHT},
     DIRECT_PALETTE_BITS,
 };
 
+/// The most bits per entry of an indirect biome palette, above which registry ids are sent directly
+const MAX_INDIRECT_BIOME_BITS: u8 = 3;
+
 #[client_packet(PLAY_LEVEL_CHUNK_WITH_LIGHT)]
 pub struct CChunkData<'a>(pub &'a ChunkData);
 
@@ -24,124 +31,209 @@ impl ClientPacket for CChunkData<'_> {
         buf.put_slice(&heightmap_nbt);
 
         let mut data_buf = BytesMut::new();
-        self.0.subchunks.array_iter().for_each(|subchunk| {
-            let block_count = subchunk.len() as i16;
-            // Block count
-            data_buf.put_i16(block_count);
-            //// Block states
-
-            let palette = &subchunk;
-            // TODO: make dynamic block_size work
-            // TODO: make direct block_size work
-            enum PaletteType {
-                Indirect(u32),
-                Direct,
-            }
-            let palette_type = {
-                let palette_bit_len = 64 - (palette.len() as i64 - 1).leading_zeros();
-                if palette_bit_len > 8 {
-                    PaletteType::Direct
-                } else if palette_bit_len > 3 {
-                    PaletteType::Indirect(palette_bit_len)
-                } else {
-                    PaletteType::Indirect(4)
+        self.0
+            .subchunks
+            .array_iter()
+            .enumerate()
+            .for_each(|(section, subchunk)| {
+                let block_count = subchunk.len() as i16;
+                // Block count
+                data_buf.put_i16(block_count);
+                //// Block states
+
+                let palette = &subchunk;
+                // TODO: make dynamic block_size work
+                // TODO: make direct block_size work
+                enum PaletteType {
+                    Indirect(u32),
+                    Direct,
                 }
-                // TODO: fix indirect palette to work correctly
-                // PaletteType::Direct
-            };
-
-            match palette_type {
-                PaletteType::Indirect(block_size) => {
-                    // Bits per entry
-                    data_buf.put_u8(block_size as u8);
-                    // Palette length
-                    data_buf.put_var_int(&VarInt(palette.len() as i32));
-
-                    palette.iter().for_each(|id| {
-                        // Palette
-                        data_buf.put_var_int(&VarInt(*id as i32));
-                    });
-                    // Data array length
-                    let data_array_len = subchunk.len().div_ceil(64 / block_size as usize);
-                    data_buf.put_var_int(&VarInt(data_array_len as i32));
-
-                    data_buf.reserve(data_array_len * 8);
-                    for block_clump in subchunk.chunks(64 / block_size as usize) {
-                        let mut out_long: i64 = 0;
-                        for block in block_clump.iter().rev() {
-                            let index = palette
-                                .iter()
-                                .position(|b| b == block)
-                                .expect("Its just got added, ofc it should be there");
-                            out_long = out_long << block_size | (index as i64);
+                let palette_type = {
+                    let palette_bit_len = 64 - (palette.len() as i64 - 1).leading_zeros();
+                    if palette_bit_len > 8 {
+                        PaletteType::Direct
+                    } else if palette_bit_len > 3 {
+                        PaletteType::Indirect(palette_bit_len)
+                    } else {
+                        PaletteType::Indirect(4)
+                    }
+                    // TODO: fix indirect palette to work correctly
+                    // PaletteType::Direct
+                };
+
+                match palette_type {
+                    PaletteType::Indirect(block_size) => {
+                        // Bits per entry
+                        data_buf.put_u8(block_size as u8);
+                        // Palette length
+                        data_buf.put_var_int(&VarInt(palette.len() as i32));
+
+                        palette.iter().for_each(|id| {
+                            // Palette
+                            data_buf.put_var_int(&VarInt(*id as i32));
+                        });
+                        // Data array length
+                        let data_array_len = subchunk.len().div_ceil(64 / block_size as usize);
+                        data_buf.put_var_int(&VarInt(data_array_len as i32));
+
+                        data_buf.reserve(data_array_len * 8);
+                        for block_clump in subchunk.chunks(64 / block_size as usize) {
+                            let mut out_long: i64 = 0;
+                            for block in block_clump.iter().rev() {
+                                let index = palette
+                                    .iter()
+                                    .position(|b| b == block)
+                                    .expect("Its just got added, ofc it should be there");
+                                out_long = out_long << block_size | (index as i64);
+                            }
+                            data_buf.put_i64(out_long);
                         }
-                        data_buf.put_i64(out_long);
                     }
-                }
-                PaletteType::Direct => {
-                    // Bits per entry
-                    data_buf.put_u8(DIRECT_PALETTE_BITS as u8);
-                    // Data array length
-                    let data_array_len = subchunk.len().div_ceil(64 / DIRECT_PALETTE_BITS as usize);
-                    data_buf.put_var_int(&VarInt(data_array_len as i32));
-
-                    data_buf.reserve(data_array_len * 8);
-                    for block_clump in subchunk.chunks(64 / DIRECT_PALETTE_BITS as usize) {
-                        let mut out_long: i64 = 0;
-                        let mut shift = 0;
-                        for block in block_clump {
-                            out_long |= (*block as i64) << shift;
-                            shift += DIRECT_PALETTE_BITS;
+                    PaletteType::Direct => {
+                        // Bits per entry
+                        data_buf.put_u8(DIRECT_PALETTE_BITS as u8);
+                        // Data array length
+                        let data_array_len =
+                            subchunk.len().div_ceil(64 / DIRECT_PALETTE_BITS as usize);
+                        data_buf.put_var_int(&VarInt(data_array_len as i32));
+
+                        data_buf.reserve(data_array_len * 8);
+                        for block_clump in subchunk.chunks(64 / DIRECT_PALETTE_BITS as usize) {
+                            let mut out_long: i64 = 0;
+                            let mut shift = 0;
+                            for block in block_clump {
+                                out_long |= (*block as i64) << shift;
+                                shift += DIRECT_PALETTE_BITS;
+                            }
+                            data_buf.put_i64(out_long);
                         }
-                        data_buf.put_i64(out_long);
                     }
                 }
-            }
 
-            //// Biomes
-            // TODO: make biomes work
-            data_buf.put_u8(0);
-            // This seems to be the biome
-            data_buf.put_var_int(&VarInt(10));
-            data_buf.put_var_int(&VarInt(0));
-        });
+                //// Biomes
+                write_biomes(&mut data_buf, self.0.biomes.get(section));
+            });
 
         // Size
         buf.put_var_int(&VarInt(data_buf.len() as i32));
         // Data
         buf.put_slice(&data_buf);
 
-        // TODO: block entities
-        buf.put_var_int(&VarInt(0));
-
-        // Sky Light Mask
-        // All of the chunks, this is not optimal and uses way more data than needed but will be
-        // overhauled with full lighting system.
-        buf.put_bit_set(&BitSet(VarInt(1), vec![0b01111111111111111111111110]));
-        // Block Light Mask
-        buf.put_bit_set(&BitSet(VarInt(1), vec![0]));
-        // Empty Sky Light Mask
-        buf.put_bit_set(&BitSet(VarInt(1), vec![0b0]));
-        // Empty Block Light Mask
-        buf.put_bit_set(&BitSet(VarInt(1), vec![0]));
-
-        buf.put_var_int(&VarInt(SUBCHUNKS_COUNT as i32));
-        self.0.subchunks.array_iter().for_each(|chunk| {
-            let mut chunk_light = [0u8; 2048];
-            for (i, _) in chunk.iter().enumerate() {
-                // if !block .is_air() {
-                //     continue;
-                // }
-                let index = i / 2;
-                let mask = if i % 2 == 1 { 0xF0 } else { 0x0F };
-                chunk_light[index] |= mask;
+        // Block entities
+        let block_entities: Vec<_> = self
+            .0
+            .block_entities
+            .values()
+            .filter_map(|block_entity| {
+                block_entity_type_id(block_entity.id()).map(|id| (block_entity, id))
+            })
+            .collect();
+        buf.put_var_int(&VarInt(block_entities.len() as i32));
+        for (block_entity, id) in block_entities {
+            let position = block_entity.position().0;
+            // Packed XZ
+            buf.put_u8((((position.x & 15) << 4) | (position.z & 15)) as u8);
+            // Y
+            buf.put_i16(position.y as i16);
+            // Type
+            buf.put_var_int(&VarInt(id as i32));
+            // Data
+            match block_entity.client_nbt() {
+                Some(nbt) => buf.put_slice(&Nbt::new(String::new(), nbt).write_unnamed()),
+                None => buf.put_u8(END_ID),
             }
+        }
 
-            buf.put_var_int(&VarInt(chunk_light.len() as i32));
-            buf.put_slice(&chunk_light);
-        });
+        write_light(buf, &self.0.light, (1 << (SUBCHUNKS_COUNT + 2)) - 1);
+    }
+}
 
-        // Block Lighting
+/// Writes the biomes of a subchunk, which are plains when they are not known.
+fn write_biomes(buf: &mut impl BufMut, biomes: Option<&ChunkSectionBiomes>) {
+    let Some(biomes) = biomes else {
+        // Single valued palette
+        buf.put_u8(0);
+        buf.put_var_int(&VarInt(Biome::Plains as i32));
+        buf.put_var_int(&VarInt(0));
+        return;
+    };
+    let palette: Vec<i32> = biomes
+        .palette
+        .iter()
+        .map(|name| {
+            name.strip_prefix("minecraft:")
+                .and_then(Biome::from_name)
+                .unwrap_or(Biome::Plains) as i32
+        })
+        .collect();
+    if palette.len() == 1 {
+        buf.put_u8(0);
+        buf.put_var_int(&VarInt(palette[0]));
         buf.put_var_int(&VarInt(0));
+        return;
+    }
+
+    let indices = biomes.palette_indices();
+    let indirect_bits = ceil_log2(palette.len() as u32);
+    let (bits, values) = if indirect_bits <= MAX_INDIRECT_BIOME_BITS {
+        buf.put_u8(indirect_bits);
+        buf.put_var_int(&VarInt(palette.len() as i32));
+        for id in &palette {
+            buf.put_var_int(&VarInt(*id));
+        }
+        (indirect_bits, indices.map(|index| index as i64))
+    } else {
+        let direct_bits = ceil_log2(Biome::COUNT as u32);
+        buf.put_u8(direct_bits);
+        (direct_bits, indices.map(|index| i64::from(palette[index])))
+    };
+
+    let per_long = 64 / bits as usize;
+    buf.put_var_int(&VarInt(values.len().div_ceil(per_long) as i32));
+    for clump in values.chunks(per_long) {
+        let long = clump
+            .iter()
+            .rev()
+            .fold(0, |long, value| long << bits | value);
+        buf.put_i64(long);
+    }
+}
+
+/// Writes the light of the given sections, where bit 0 is the section below the world and the
+/// last bit the one above it. Sections without any light are only marked as empty.
+pub(crate) fn write_light(buf: &mut impl BufMut, light: &ChunkLight, sections: u32) {
+    let mut masks = [0i64; 4];
+    let mut arrays: [Vec<Box<[u8; LIGHT_SECTION_BYTES]>>; 2] = [Vec::new(), Vec::new()];
+    for (kind_index, kind) in [LightKind::Sky, LightKind::Block].into_iter().enumerate() {
+        for i in (0..SUBCHUNKS_COUNT + 2).filter(|i| sections & (1 << i) != 0) {
+            let section = match i {
+                0 => &LightSection::Uniform(0),
+                // The sky is above the world
+                i if i == SUBCHUNKS_COUNT + 1 && kind == LightKind::Sky => {
+                    &LightSection::Uniform(MAX_LIGHT)
+                }
+                i if i == SUBCHUNKS_COUNT + 1 => &LightSection::Uniform(0),
+                i => &light.sections(kind)[i - 1],
+            };
+            if *section == LightSection::Uniform(0) {
+                masks[kind_index + 2] |= 1 << i;
+            } else {
+                masks[kind_index] |= 1 << i;
+                arrays[kind_index].push(section.to_bytes());
+            }
+        }
+    }
+
+    // Sky Light Mask, Block Light Mask, Empty Sky Light Mask, Empty Block Light Mask
+    for mask in masks {
+        buf.put_bit_set(&BitSet(VarInt(1), vec![mask]));
+    }
+    // Sky Light arrays, Block Light arrays
+    for arrays in arrays {
+        buf.put_var_int(&VarInt(arrays.len() as i32));
+        for array in arrays {
+            buf.put_var_int(&VarInt(array.len() as i32));
+            buf.put_slice(array.as_slice());
+        }
     }
 }
@@ -0,0 +1,23 @@
+use pumpkin_data::packet::clientbound::PLAY_COOLDOWN;
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::{codec::identifier::Identifier, VarInt};
+
+#[derive(Serialize)]
+#[client_packet(PLAY_COOLDOWN)]
+/// Applies a use cooldown to every item of a cooldown group, which is the item id unless the
+/// item declares its own group. A cooldown of 0 ticks removes the cooldown.
+pub struct CCooldown {
+    cooldown_group: Identifier,
+    cooldown_ticks: VarInt,
+}
+
+impl CCooldown {
+    pub fn new(cooldown_group: Identifier, cooldown_ticks: VarInt) -> Self {
+        Self {
+            cooldown_group,
+            cooldown_ticks,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use pumpkin_data::packet::clientbound::PLAY_CUSTOM_PAYLOAD;
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+/// Sends a plugin message on the given channel to the client.
+#[derive(Serialize)]
+#[client_packet(PLAY_CUSTOM_PAYLOAD)]
+pub struct CCustomPayload<'a> {
+    channel: &'a str,
+    data: &'a [u8],
+}
+
+impl<'a> CCustomPayload<'a> {
+    pub fn new(channel: &'a str, data: &'a [u8]) -> Self {
+        Self { channel, data }
+    }
+}
@@ -1,5 +1,6 @@
 mod acknowledge_block;
 mod actionbar;
+mod add_experience_orb;
 mod block_destroy_stage;
 mod block_entity_data;
 mod block_event;
@@ -15,6 +16,8 @@ mod combat_death;
 mod command_suggestions;
 mod commands;
 mod cookie_request;
+mod cooldown;
+mod custom_payload;
 mod damage_event;
 mod disconnect;
 mod disguised_chat_message;
@@ -41,9 +44,12 @@ mod player_chat_message;
 mod player_info_update;
 mod player_position;
 mod player_remove;
+mod recipe_book_add;
+mod recipe_book_remove;
 mod remove_entities;
 mod reset_score;
 mod respawn;
+mod section_blocks_update;
 mod server_links;
 mod set_border_center;
 mod set_border_lerp_size;
@@ -57,13 +63,16 @@ mod set_equipment;
 mod set_experience;
 mod set_health;
 mod set_held_item;
+mod set_player_team;
 mod set_time;
 mod set_title;
+mod set_titles_animation;
 mod sound_effect;
 mod spawn_entity;
 mod store_cookie;
 mod subtitle;
 mod system_chat_message;
+mod tab_list;
 mod take_item;
 mod teleport_entity;
 mod transfer;
@@ -71,12 +80,14 @@ mod unload_chunk;
 mod update_entity_pos;
 mod update_entity_pos_rot;
 mod update_entity_rot;
+mod update_light;
 mod update_objectives;
 mod update_score;
 mod worldevent;
 
 pub use acknowledge_block::*;
 pub use actionbar::*;
+pub use add_experience_orb::*;
 pub use block_destroy_stage::*;
 pub use block_entity_data::*;
 pub use block_event::*;
@@ -92,6 +103,8 @@ pub use combat_death::*;
 pub use command_suggestions::*;
 pub use commands::*;
 pub use cookie_request::*;
+pub use cooldown::*;
+pub use custom_payload::*;
 pub use damage_event::*;
 pub use disconnect::*;
 pub use disguised_chat_message::*;
@@ -118,9 +131,12 @@ pub use player_chat_message::*;
 pub use player_info_update::*;
 pub use player_position::*;
 pub use player_remove::*;
+pub use recipe_book_add::*;
+pub use recipe_book_remove::*;
 pub use remove_entities::*;
 pub use reset_score::*;
 pub use respawn::*;
+pub use section_blocks_update::*;
 pub use server_links::*;
 pub use set_border_center::*;
 pub use set_border_lerp_size::*;
@@ -134,13 +150,16 @@ pub use set_equipment::*;
 pub use set_experience::*;
 pub use set_health::*;
 pub use set_held_item::*;
+pub use set_player_team::*;
 pub use set_time::*;
 pub use set_title::*;
+pub use set_titles_animation::*;
 pub use sound_effect::*;
 pub use spawn_entity::*;
 pub use store_cookie::*;
 pub use subtitle::*;
 pub use system_chat_message::*;
+pub use tab_list::*;
 pub use take_item::*;
 pub use teleport_entity::*;
 pub use transfer::*;
@@ -148,6 +167,7 @@ pub use unload_chunk::*;
 pub use update_entity_pos::*;
 pub use update_entity_pos_rot::*;
 pub use update_entity_rot::*;
+pub use update_light::*;
 pub use update_objectives::*;
 pub use update_score::*;
 pub use worldevent::*;
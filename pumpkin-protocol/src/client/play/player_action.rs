@@ -1,3 +1,5 @@
+use pumpkin_util::text::TextComponent;
+
 use crate::{Property, VarInt};
 
 pub enum PlayerAction<'a> {
@@ -11,6 +13,7 @@ pub enum PlayerAction<'a> {
     /// Listed ?
     UpdateListed(bool),
     UpdateLatency(u8),
-    UpdateDisplayName(u8),
+    /// The name shown in the player list, or `None` to show the account name
+    UpdateDisplayName(Option<TextComponent>),
     UpdateListOrder,
 }
@@ -42,7 +42,9 @@ impl ClientPacket for CPlayerInfoUpdate<'_> {
                     PlayerAction::UpdateGameMode(gamemode) => p.put_var_int(gamemode),
                     PlayerAction::UpdateListed(listed) => p.put_bool(*listed),
                     PlayerAction::UpdateLatency(_) => todo!(),
-                    PlayerAction::UpdateDisplayName(_) => todo!(),
+                    PlayerAction::UpdateDisplayName(display_name) => {
+                        p.put_option(display_name, |p, v| p.put_slice(&v.encode()));
+                    }
                     PlayerAction::UpdateListOrder => todo!(),
                 }
             }
@@ -0,0 +1,160 @@
+use bytes::BufMut;
+use pumpkin_data::packet::clientbound::PLAY_RECIPE_BOOK_ADD;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+/// Adds recipes to the recipe book of the client, which is how the client learns about recipes.
+#[client_packet(PLAY_RECIPE_BOOK_ADD)]
+pub struct CRecipeBookAdd<'a> {
+    entries: &'a [RecipeDisplayEntry],
+    /// Whether the recipes replace all recipes the client knows
+    replace: bool,
+}
+
+impl<'a> CRecipeBookAdd<'a> {
+    pub fn new(entries: &'a [RecipeDisplayEntry], replace: bool) -> Self {
+        Self { entries, replace }
+    }
+}
+
+pub struct RecipeDisplayEntry {
+    /// Identifies the recipe in later packets, e.g. when it is removed
+    pub id: VarInt,
+    pub display: RecipeDisplay,
+    pub group: Option<VarInt>,
+    /// The id of the `recipe_book_category`
+    pub category: VarInt,
+    /// 0x01: Show a notification, 0x02: Highlight the recipe as new
+    pub flags: u8,
+}
+
+/// How a recipe is shown in the recipe book
+pub enum RecipeDisplay {
+    CraftingShapeless {
+        ingredients: Vec<SlotDisplay>,
+        result: SlotDisplay,
+        crafting_station: SlotDisplay,
+    },
+    CraftingShaped {
+        width: VarInt,
+        height: VarInt,
+        ingredients: Vec<SlotDisplay>,
+        result: SlotDisplay,
+        crafting_station: SlotDisplay,
+    },
+    Furnace {
+        ingredient: SlotDisplay,
+        fuel: SlotDisplay,
+        result: SlotDisplay,
+        crafting_station: SlotDisplay,
+        cooking_time: VarInt,
+        experience: f32,
+    },
+}
+
+impl RecipeDisplay {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        match self {
+            Self::CraftingShapeless {
+                ingredients,
+                result,
+                crafting_station,
+            } => {
+                bytebuf.put_var_int(&VarInt(0));
+                bytebuf.put_list(ingredients, |p, slot| slot.write(p));
+                result.write(bytebuf);
+                crafting_station.write(bytebuf);
+            }
+            Self::CraftingShaped {
+                width,
+                height,
+                ingredients,
+                result,
+                crafting_station,
+            } => {
+                bytebuf.put_var_int(&VarInt(1));
+                bytebuf.put_var_int(width);
+                bytebuf.put_var_int(height);
+                bytebuf.put_list(ingredients, |p, slot| slot.write(p));
+                result.write(bytebuf);
+                crafting_station.write(bytebuf);
+            }
+            Self::Furnace {
+                ingredient,
+                fuel,
+                result,
+                crafting_station,
+                cooking_time,
+                experience,
+            } => {
+                bytebuf.put_var_int(&VarInt(2));
+                ingredient.write(bytebuf);
+                fuel.write(bytebuf);
+                result.write(bytebuf);
+                crafting_station.write(bytebuf);
+                bytebuf.put_var_int(cooking_time);
+                bytebuf.put_f32(*experience);
+            }
+        }
+    }
+}
+
+/// How a slot of a recipe is shown in the recipe book
+pub enum SlotDisplay {
+    Empty,
+    AnyFuel,
+    Item(VarInt),
+    ItemStack {
+        item: VarInt,
+        count: VarInt,
+    },
+    /// Cycles through the items of a tag, e.g. `minecraft:planks`
+    Tag(String),
+    /// Cycles through the given displays
+    Composite(Vec<SlotDisplay>),
+}
+
+impl SlotDisplay {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        match self {
+            Self::Empty => bytebuf.put_var_int(&VarInt(0)),
+            Self::AnyFuel => bytebuf.put_var_int(&VarInt(1)),
+            Self::Item(item) => {
+                bytebuf.put_var_int(&VarInt(2));
+                bytebuf.put_var_int(item);
+            }
+            Self::ItemStack { item, count } => {
+                bytebuf.put_var_int(&VarInt(3));
+                // A slot without component changes
+                bytebuf.put_var_int(count);
+                bytebuf.put_var_int(item);
+                bytebuf.put_var_int(&VarInt(0));
+                bytebuf.put_var_int(&VarInt(0));
+            }
+            Self::Tag(tag) => {
+                bytebuf.put_var_int(&VarInt(4));
+                bytebuf.put_string(tag);
+            }
+            Self::Composite(displays) => {
+                bytebuf.put_var_int(&VarInt(7));
+                bytebuf.put_list(displays, |p, display| display.write(p));
+            }
+        }
+    }
+}
+
+impl ClientPacket for CRecipeBookAdd<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_list(self.entries, |p, entry| {
+            p.put_var_int(&entry.id);
+            entry.display.write(p);
+            p.put_option(&entry.group, |p, group| p.put_var_int(group));
+            p.put_var_int(&entry.category);
+            // No crafting requirements, so the client does not check the ingredients itself
+            p.put_bool(false);
+            p.put_u8(entry.flags);
+        });
+        bytebuf.put_bool(self.replace);
+    }
+}
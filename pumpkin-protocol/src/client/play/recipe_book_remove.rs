@@ -0,0 +1,21 @@
+use pumpkin_data::packet::clientbound::PLAY_RECIPE_BOOK_REMOVE;
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+#[derive(Serialize)]
+#[client_packet(PLAY_RECIPE_BOOK_REMOVE)]
+pub struct CRecipeBookRemove<'a> {
+    count: VarInt,
+    recipe_ids: &'a [VarInt],
+}
+
+impl<'a> CRecipeBookRemove<'a> {
+    pub fn new(recipe_ids: &'a [VarInt]) -> Self {
+        Self {
+            count: VarInt(recipe_ids.len() as i32),
+            recipe_ids,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use bytes::BufMut;
+use pumpkin_data::packet::clientbound::PLAY_SECTION_BLOCKS_UPDATE;
+use pumpkin_macros::client_packet;
+use pumpkin_util::math::vector3::Vector3;
+
+use crate::{
+    bytebuf::ByteBufMut,
+    codec::{var_long::VarLong, Codec},
+    ClientPacket, VarInt,
+};
+
+/// Changes many blocks of one chunk section at once, which is cheaper than a block update for
+/// each of them
+#[client_packet(PLAY_SECTION_BLOCKS_UPDATE)]
+pub struct CSectionBlocksUpdate<'a> {
+    /// The coordinates of the section, in sections
+    section: Vector3<i32>,
+    /// The positions inside the section, from 0 to 15, with their new block state ids
+    blocks: &'a [(Vector3<u8>, u16)],
+}
+
+impl<'a> CSectionBlocksUpdate<'a> {
+    pub fn new(section: Vector3<i32>, blocks: &'a [(Vector3<u8>, u16)]) -> Self {
+        Self { section, blocks }
+    }
+}
+
+impl ClientPacket for CSectionBlocksUpdate<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        let section = ((i64::from(self.section.x) & 0x3F_FFFF) << 42)
+            | ((i64::from(self.section.z) & 0x3F_FFFF) << 20)
+            | (i64::from(self.section.y) & 0xF_FFFF);
+        bytebuf.put_i64(section);
+        bytebuf.put_var_int(&VarInt(self.blocks.len() as i32));
+        for (position, state_id) in self.blocks {
+            let entry = (i64::from(*state_id) << 12)
+                | (i64::from(position.x) << 8)
+                | (i64::from(position.z) << 4)
+                | i64::from(position.y);
+            VarLong(entry).encode(bytebuf);
+        }
+    }
+}
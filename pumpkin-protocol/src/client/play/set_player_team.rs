@@ -0,0 +1,85 @@
+use bytes::BufMut;
+use pumpkin_data::packet::clientbound::PLAY_SET_PLAYER_TEAM;
+use pumpkin_macros::client_packet;
+use pumpkin_util::text::TextComponent;
+
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+#[client_packet(PLAY_SET_PLAYER_TEAM)]
+pub struct CSetPlayerTeam<'a> {
+    team_name: &'a str,
+    action: TeamAction<'a>,
+}
+
+impl<'a> CSetPlayerTeam<'a> {
+    pub fn new(team_name: &'a str, action: TeamAction<'a>) -> Self {
+        Self { team_name, action }
+    }
+}
+
+pub enum TeamAction<'a> {
+    Create {
+        info: TeamInfo<'a>,
+        entities: &'a [String],
+    },
+    Remove,
+    UpdateInfo(TeamInfo<'a>),
+    AddEntities(&'a [String]),
+    RemoveEntities(&'a [String]),
+}
+
+pub struct TeamInfo<'a> {
+    pub display_name: &'a TextComponent,
+    /// 0x01: Allow friendly fire, 0x02: can see invisible players on the same team
+    pub friendly_flags: u8,
+    /// `always`, `hideForOtherTeams`, `hideForOwnTeam` or `never`
+    pub name_tag_visibility: &'a str,
+    /// `always`, `pushOtherTeams`, `pushOwnTeam` or `never`
+    pub collision_rule: &'a str,
+    /// The chat formatting color id, used for the name tag
+    pub color: VarInt,
+    pub prefix: &'a TextComponent,
+    pub suffix: &'a TextComponent,
+}
+
+impl TeamInfo<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_slice(&self.display_name.encode());
+        bytebuf.put_u8(self.friendly_flags);
+        bytebuf.put_string(self.name_tag_visibility);
+        bytebuf.put_string(self.collision_rule);
+        bytebuf.put_var_int(&self.color);
+        bytebuf.put_slice(&self.prefix.encode());
+        bytebuf.put_slice(&self.suffix.encode());
+    }
+}
+
+fn put_entities(bytebuf: &mut impl BufMut, entities: &[String]) {
+    bytebuf.put_list(entities, |p, entity| p.put_string(entity));
+}
+
+impl ClientPacket for CSetPlayerTeam<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_string(self.team_name);
+        match &self.action {
+            TeamAction::Create { info, entities } => {
+                bytebuf.put_u8(0);
+                info.write(bytebuf);
+                put_entities(bytebuf, entities);
+            }
+            TeamAction::Remove => bytebuf.put_u8(1),
+            TeamAction::UpdateInfo(info) => {
+                bytebuf.put_u8(2);
+                info.write(bytebuf);
+            }
+            TeamAction::AddEntities(entities) => {
+                bytebuf.put_u8(3);
+                put_entities(bytebuf, entities);
+            }
+            TeamAction::RemoveEntities(entities) => {
+                bytebuf.put_u8(4);
+                put_entities(bytebuf, entities);
+            }
+        }
+    }
+}
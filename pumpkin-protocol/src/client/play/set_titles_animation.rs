@@ -0,0 +1,23 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_TITLES_ANIMATION;
+
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+/// How long titles fade in, stay and fade out, in ticks
+#[derive(Serialize)]
+#[client_packet(PLAY_SET_TITLES_ANIMATION)]
+pub struct CTitleAnimation {
+    fade_in: i32,
+    stay: i32,
+    fade_out: i32,
+}
+
+impl CTitleAnimation {
+    pub const fn new(fade_in: i32, stay: i32, fade_out: i32) -> Self {
+        Self {
+            fade_in,
+            stay,
+            fade_out,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use pumpkin_data::packet::clientbound::PLAY_TAB_LIST;
+use pumpkin_util::text::TextComponent;
+
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[client_packet(PLAY_TAB_LIST)]
+pub struct CTabList<'a> {
+    header: &'a TextComponent,
+    footer: &'a TextComponent,
+}
+
+impl<'a> CTabList<'a> {
+    pub fn new(header: &'a TextComponent, footer: &'a TextComponent) -> Self {
+        Self { header, footer }
+    }
+}
@@ -0,0 +1,34 @@
+use bytes::BufMut;
+use pumpkin_data::packet::clientbound::PLAY_LIGHT_UPDATE;
+use pumpkin_macros::client_packet;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_world::light::ChunkLight;
+
+use super::chunk_data::write_light;
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+#[client_packet(PLAY_LIGHT_UPDATE)]
+pub struct CUpdateLight<'a> {
+    chunk: Vector2<i32>,
+    light: &'a ChunkLight,
+    /// The changed sections, where bit 0 is the section below the world
+    sections: u32,
+}
+
+impl<'a> CUpdateLight<'a> {
+    pub fn new(chunk: Vector2<i32>, light: &'a ChunkLight, sections: u32) -> Self {
+        Self {
+            chunk,
+            light,
+            sections,
+        }
+    }
+}
+
+impl ClientPacket for CUpdateLight<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_var_int(&VarInt(self.chunk.x));
+        bytebuf.put_var_int(&VarInt(self.chunk.z));
+        write_light(bytebuf, self.light, self.sections);
+    }
+}
@@ -1,11 +1,14 @@
-use crate::VarInt;
+use crate::{bytebuf::ByteBufMut, VarInt};
+use bytes::BufMut;
 use pumpkin_data::item::Item;
+use pumpkin_nbt::{serializer::WriteAdaptor, tag::NbtTag};
 use pumpkin_world::item::ItemStack;
 use serde::ser::SerializeSeq;
 use serde::{
     de::{self, SeqAccess},
     Deserialize, Serialize, Serializer,
 };
+use uuid::Uuid;
 
 #[derive(Debug, Clone)]
 pub struct Slot {
@@ -13,7 +16,7 @@ pub struct Slot {
     item_id: Option<VarInt>,
     num_components_to_add: Option<VarInt>,
     num_components_to_remove: Option<VarInt>,
-    components_to_add: Option<Vec<(VarInt, ())>>, // The second type depends on the varint
+    components_to_add: Option<Vec<(VarInt, EncodedComponent)>>,
     components_to_remove: Option<Vec<VarInt>>,
 }
 
@@ -133,10 +136,7 @@ impl Slot {
     pub fn to_item(self) -> Option<ItemStack> {
         let item_id = self.item_id?.0.try_into().unwrap();
         let item = Item::from_id(item_id)?;
-        Some(ItemStack {
-            item,
-            item_count: self.item_count.0.try_into().unwrap(),
-        })
+        Some(ItemStack::new(self.item_count.0.try_into().unwrap(), item))
     }
 
     pub const fn empty() -> Self {
@@ -151,15 +151,147 @@ impl Slot {
     }
 }
 
+/// A component in the format of the protocol, which depends on the type of the component
+#[derive(Debug, Clone)]
+pub struct EncodedComponent(Box<[u8]>);
+
+impl Serialize for EncodedComponent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+fn put_nbt(bytebuf: &mut Vec<u8>, tag: &NbtTag) -> Option<()> {
+    tag.serialize(&mut WriteAdaptor::new(bytebuf)).ok()
+}
+
+/// Encodes the components the client knows how to show. Other components are only kept on the
+/// server.
+fn encode_component(id: &str, tag: &NbtTag) -> Option<(VarInt, EncodedComponent)> {
+    let mut bytebuf = Vec::new();
+    let component_id = match id {
+        "minecraft:custom_data" => {
+            if !matches!(tag, NbtTag::Compound(_)) {
+                return None;
+            }
+            put_nbt(&mut bytebuf, tag)?;
+            0
+        }
+        "minecraft:damage" => {
+            bytebuf.put_var_int(&tag.extract_int()?.into());
+            3
+        }
+        // Text components are NBT, either a string or a compound like `{text: "...", color: "..."}`
+        "minecraft:custom_name" => {
+            put_nbt(&mut bytebuf, tag)?;
+            5
+        }
+        "minecraft:lore" => {
+            let lines = tag.extract_list()?;
+            bytebuf.put_var_int(&lines.len().into());
+            for line in lines {
+                put_nbt(&mut bytebuf, line)?;
+            }
+            8
+        }
+        "minecraft:repair_cost" => {
+            bytebuf.put_var_int(&tag.extract_int()?.into());
+            17
+        }
+        "minecraft:custom_model_data" => {
+            let (floats, flags, strings, colors) = match tag {
+                NbtTag::Compound(data) => (
+                    data.get_list("floats").unwrap_or_default(),
+                    data.get_list("flags").unwrap_or_default(),
+                    data.get_list("strings").unwrap_or_default(),
+                    data.get_list("colors").unwrap_or_default(),
+                ),
+                NbtTag::Int(_) | NbtTag::Float(_) => {
+                    (std::slice::from_ref(tag), &[][..], &[][..], &[][..])
+                }
+                _ => return None,
+            };
+            bytebuf.put_list(floats, |p, tag| {
+                p.put_f32(
+                    tag.extract_float()
+                        .or_else(|| tag.extract_int().map(|int| int as f32))
+                        .unwrap_or(0.0),
+                );
+            });
+            bytebuf.put_list(flags, |p, tag| {
+                p.put_bool(tag.extract_bool().unwrap_or(false))
+            });
+            bytebuf.put_list(strings, |p, tag| {
+                p.put_string(tag.extract_string().map_or("", String::as_str));
+            });
+            bytebuf.put_list(colors, |p, tag| p.put_i32(tag.extract_int().unwrap_or(0)));
+            14
+        }
+        // The owner of a player head, either only a name or `{name, id, properties}`
+        "minecraft:profile" => {
+            let (name, id, properties) = match tag {
+                NbtTag::String(name) => (Some(name), None, &[][..]),
+                NbtTag::Compound(profile) => (
+                    profile.get_string("name"),
+                    profile.get_int_array("id"),
+                    profile.get_list("properties").unwrap_or_default(),
+                ),
+                _ => return None,
+            };
+            // Longer names are not valid, the client would disconnect
+            let name = name.filter(|name| name.len() <= 16);
+            bytebuf.put_option(&name, |p, name| p.put_string_len(name, 16));
+            let id = id.and_then(|id| <[i32; 4]>::try_from(id).ok()).map(|id| {
+                Uuid::from_u128(
+                    id.iter()
+                        .fold(0, |uuid, part| uuid << 32 | u128::from(*part as u32)),
+                )
+            });
+            bytebuf.put_option(&id, |p, id| p.put_uuid(id));
+            let properties: Vec<_> = properties
+                .iter()
+                .filter_map(NbtTag::extract_compound)
+                .collect();
+            bytebuf.put_list(&properties, |p, property| {
+                p.put_string(property.get_string("name").map_or("", String::as_str));
+                p.put_string(property.get_string("value").map_or("", String::as_str));
+                p.put_option(&property.get_string("signature"), |p, signature| {
+                    p.put_string(signature);
+                });
+            });
+            57
+        }
+        _ => return None,
+    };
+    Some((
+        VarInt(component_id),
+        EncodedComponent(bytebuf.into_boxed_slice()),
+    ))
+}
+
 impl From<&ItemStack> for Slot {
     fn from(item: &ItemStack) -> Self {
+        let components: Vec<_> = item
+            .components
+            .child_tags
+            .iter()
+            .filter_map(|(id, tag)| encode_component(id, tag))
+            .collect();
+        let (num_components_to_add, components_to_add) = if components.is_empty() {
+            (None, None)
+        } else {
+            (Some(components.len().into()), Some(components))
+        };
         Slot {
             item_count: item.item_count.into(),
             item_id: Some(VarInt(item.item.id as i32)),
+            num_components_to_add,
             // TODO: add these
-            num_components_to_add: None,
             num_components_to_remove: None,
-            components_to_add: None,
+            components_to_add,
             components_to_remove: None,
         }
     }
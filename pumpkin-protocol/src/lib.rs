@@ -99,7 +99,7 @@ pub struct StatusResponse {
     /// Information about currently connected Players. Optional
     pub players: Option<Players>,
     /// The description displayed also called MOTD (Message of the day). Optional
-    pub description: String,
+    pub description: TextComponent,
     /// The icon displayed, Optional
     pub favicon: Option<String>,
     /// Players are forced to use Secure chat
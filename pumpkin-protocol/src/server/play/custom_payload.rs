@@ -0,0 +1,26 @@
+use bytes::Buf;
+use pumpkin_data::packet::serverbound::PLAY_CUSTOM_PAYLOAD;
+use pumpkin_macros::server_packet;
+
+use crate::{
+    bytebuf::{ByteBuf, ReadingError},
+    codec::identifier::Identifier,
+    ServerPacket,
+};
+const MAX_PAYLOAD_SIZE: usize = 32767;
+
+/// A plugin message sent by the client on the given channel.
+#[server_packet(PLAY_CUSTOM_PAYLOAD)]
+pub struct SCustomPayload {
+    pub channel: Identifier,
+    pub data: bytes::Bytes,
+}
+
+impl ServerPacket for SCustomPayload {
+    fn read(bytebuf: &mut impl Buf) -> Result<Self, ReadingError> {
+        Ok(Self {
+            channel: bytebuf.try_get_identifier()?,
+            data: bytebuf.try_copy_to_bytes_len(bytebuf.remaining(), MAX_PAYLOAD_SIZE)?,
+        })
+    }
+}
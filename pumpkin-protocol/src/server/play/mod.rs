@@ -8,6 +8,7 @@ mod close_container;
 mod command_suggestion;
 mod confirm_teleport;
 mod cookie_response;
+mod custom_payload;
 mod interact;
 mod keep_alive;
 mod pick_item;
@@ -21,6 +22,7 @@ mod player_loaded;
 mod player_position;
 mod player_position_rotation;
 mod player_rotation;
+mod rename_item;
 mod set_creative_slot;
 mod set_held_item;
 mod swing_arm;
@@ -38,6 +40,7 @@ pub use close_container::*;
 pub use command_suggestion::*;
 pub use confirm_teleport::*;
 pub use cookie_response::*;
+pub use custom_payload::*;
 pub use interact::*;
 pub use keep_alive::*;
 pub use pick_item::*;
@@ -51,6 +54,7 @@ pub use player_loaded::*;
 pub use player_position::*;
 pub use player_position_rotation::*;
 pub use player_rotation::*;
+pub use rename_item::*;
 pub use set_creative_slot::*;
 pub use set_held_item::*;
 pub use swing_arm::*;
@@ -0,0 +1,9 @@
+use pumpkin_data::packet::serverbound::PLAY_RENAME_ITEM;
+use pumpkin_macros::server_packet;
+
+/// Sent when the player changes the name in an anvil
+#[derive(serde::Deserialize)]
+#[server_packet(PLAY_RENAME_ITEM)]
+pub struct SRenameItem {
+    pub item_name: String,
+}
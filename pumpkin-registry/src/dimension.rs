@@ -23,6 +23,18 @@ pub struct Dimension {
     ultrawarm: bool,
 }
 
+impl Dimension {
+    /// Uses the build height of another dimension, keeping the logical height
+    #[must_use]
+    pub fn with_height_of(self, other: &Self) -> Self {
+        Self {
+            min_y: other.min_y,
+            height: other.height,
+            ..self
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default, Debug)]
 pub enum DimensionEffects {
     #[serde(rename = "minecraft:overworld")]
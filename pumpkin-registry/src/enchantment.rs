@@ -4,4 +4,12 @@ use serde::{Deserialize, Serialize};
 pub struct Enchantment {
     // TODO: Add things :D
     //  description: Text<'static>,
+    pub max_level: u8,
+    /// How many levels each level of the enchantment adds to the cost of an anvil
+    pub anvil_cost: i32,
+    /// The tag of the items the enchantment can be applied to, starting with `#`
+    pub supported_items: String,
+    /// The tag of the enchantments this enchantment can't be combined with, starting with `#`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_set: Option<String>,
 }
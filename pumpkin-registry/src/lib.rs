@@ -5,13 +5,16 @@ use biome::Biome;
 use chat_type::ChatType;
 use damage_type::DamageType;
 use dimension::Dimension;
-use enchantment::Enchantment;
+pub use enchantment::Enchantment;
 use indexmap::IndexMap;
 use instrument::Instrument;
 use jukebox_song::JukeboxSong;
 use paint::Painting;
 use pumpkin_protocol::{client::config::RegistryEntry, codec::identifier::Identifier};
-pub use recipe::{flatten_3x3, Recipe, RecipeResult, RecipeType, RECIPES};
+pub use recipe::{
+    find_recipe, flatten_3x3, recipe_display, recipe_displays, register_recipe, unregister_recipe,
+    Cooking, Recipe, RecipeCategory, RecipeResult, RecipeType, RECIPES,
+};
 use serde::{Deserialize, Serialize};
 use trim_material::TrimMaterial;
 use trim_pattern::TrimPattern;
@@ -53,7 +56,7 @@ pub struct SyncedRegistry {
     dimension_type: IndexMap<String, Dimension>,
     damage_type: IndexMap<String, DamageType>,
     banner_pattern: IndexMap<String, BannerPattern>,
-    enchantment: IndexMap<String, Enchantment>,
+    pub enchantment: IndexMap<String, Enchantment>,
     pub jukebox_song: IndexMap<String, JukeboxSong>,
     instrument: IndexMap<String, Instrument>,
 }
@@ -152,10 +155,15 @@ impl Registry {
             registry_entries,
         };
 
+        // Chunks of every dimension have the height of the overworld, so the client has to
+        // expect it in the other dimensions as well
+        let overworld = &SYNCED_REGISTRIES.dimension_type["overworld"];
         let registry_entries = SYNCED_REGISTRIES
             .dimension_type
             .iter()
-            .map(|(name, nbt)| RegistryEntry::from_nbt(name, nbt))
+            .map(|(name, dimension)| {
+                RegistryEntry::from_nbt(name, &dimension.clone().with_height_of(overworld))
+            })
             .collect();
         let dimension_type = Registry {
             registry_id: Identifier::vanilla("dimension_type"),
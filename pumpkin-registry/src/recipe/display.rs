@@ -0,0 +1,142 @@
+use pumpkin_data::{
+    item::Item,
+    tag::{RegistryEntryList, TagType},
+};
+use pumpkin_protocol::{
+    client::play::{RecipeDisplay, RecipeDisplayEntry, SlotDisplay},
+    codec::var_int::VarInt,
+};
+
+use super::read::{Recipe, RecipeCategory, RecipeResult, RecipeType};
+
+fn item_display(name: &str) -> SlotDisplay {
+    Item::from_name(&name.replace("minecraft:", "")).map_or(SlotDisplay::Empty, |item| {
+        SlotDisplay::Item(VarInt(i32::from(item.id)))
+    })
+}
+
+fn tag_display(tag: &TagType) -> SlotDisplay {
+    match tag {
+        TagType::Item(name) => item_display(name),
+        TagType::Tag(tag) => SlotDisplay::Tag(tag.clone()),
+    }
+}
+
+fn ingredient_display(ingredient: Option<&RegistryEntryList>) -> SlotDisplay {
+    match ingredient {
+        None => SlotDisplay::Empty,
+        Some(RegistryEntryList::Single(tag)) => tag_display(tag),
+        Some(RegistryEntryList::Many(tags)) => {
+            SlotDisplay::Composite(tags.iter().map(tag_display).collect())
+        }
+    }
+}
+
+fn result_display(result: &RecipeResult) -> SlotDisplay {
+    match result {
+        RecipeResult::Many { id, count, .. } if *count != 1 => {
+            Item::from_name(&id.replace("minecraft:", "")).map_or(SlotDisplay::Empty, |item| {
+                SlotDisplay::ItemStack {
+                    item: VarInt(i32::from(item.id)),
+                    count: VarInt(i32::from(*count)),
+                }
+            })
+        }
+        RecipeResult::Many { id, .. } | RecipeResult::Single { id, .. } => item_display(id),
+        RecipeResult::Special => SlotDisplay::Empty,
+    }
+}
+
+/// The id of the `recipe_book_category` the recipe is shown in
+fn book_category(recipe_type: RecipeType, category: RecipeCategory) -> i32 {
+    match (recipe_type, category) {
+        (RecipeType::Crafting(_), RecipeCategory::Building) => 0,
+        (RecipeType::Crafting(_), RecipeCategory::Redstone) => 1,
+        (RecipeType::Crafting(_), RecipeCategory::Equipment) => 2,
+        (RecipeType::Crafting(_), _) => 3,
+        (RecipeType::Smelting, RecipeCategory::Food) => 4,
+        (RecipeType::Smelting, RecipeCategory::Blocks) => 5,
+        (RecipeType::Smelting, _) => 6,
+        (RecipeType::Blasting, RecipeCategory::Blocks) => 7,
+        (RecipeType::Blasting, _) => 8,
+        (RecipeType::Smoking, _) => 9,
+        (RecipeType::StoneCutting, _) => 10,
+        (RecipeType::Smithing(_), _) => 11,
+        (RecipeType::CampfireCooking, _) => 12,
+    }
+}
+
+impl Recipe {
+    /// Returns how the recipe is shown in the recipe book, or `None` if it can not be shown yet.
+    ///
+    /// # Arguments
+    /// - `id`: The id the client knows the recipe by.
+    pub fn display(&self, id: i32) -> Option<RecipeDisplayEntry> {
+        if !self.implemented() && self.cooking().is_none() {
+            return None;
+        }
+        let grid = self.pattern().first()?;
+        let result = result_display(self.result());
+        let display = match self.recipe_type {
+            RecipeType::Crafting(_) if self.recipe_type.is_shapeless() => {
+                RecipeDisplay::CraftingShapeless {
+                    ingredients: grid
+                        .iter()
+                        .flatten()
+                        .flatten()
+                        .map(|ingredient| ingredient_display(Some(ingredient)))
+                        .collect(),
+                    result,
+                    crafting_station: item_display("crafting_table"),
+                }
+            }
+            RecipeType::Crafting(_) => {
+                // The pattern is flattened to the top left corner
+                let width = grid
+                    .iter()
+                    .filter_map(|row| row.iter().rposition(Option::is_some))
+                    .max()?
+                    + 1;
+                let height = grid
+                    .iter()
+                    .rposition(|row| row.iter().any(Option::is_some))?
+                    + 1;
+                RecipeDisplay::CraftingShaped {
+                    width: VarInt(width as i32),
+                    height: VarInt(height as i32),
+                    ingredients: grid[..height]
+                        .iter()
+                        .flat_map(|row| row[..width].iter().map(Option::as_ref))
+                        .map(ingredient_display)
+                        .collect(),
+                    result,
+                    crafting_station: item_display("crafting_table"),
+                }
+            }
+            recipe_type => {
+                let cooking = self.cooking()?;
+                let crafting_station = match recipe_type {
+                    RecipeType::Blasting => "blast_furnace",
+                    RecipeType::Smoking => "smoker",
+                    RecipeType::CampfireCooking => "campfire",
+                    _ => "furnace",
+                };
+                RecipeDisplay::Furnace {
+                    ingredient: ingredient_display(grid[0][0].as_ref()),
+                    fuel: SlotDisplay::AnyFuel,
+                    result,
+                    crafting_station: item_display(crafting_station),
+                    cooking_time: VarInt(i32::from(cooking.cooking_time)),
+                    experience: cooking.experience,
+                }
+            }
+        };
+        Some(RecipeDisplayEntry {
+            id: VarInt(id),
+            display,
+            group: None,
+            category: VarInt(book_category(self.recipe_type, self.category())),
+            flags: 0,
+        })
+    }
+}
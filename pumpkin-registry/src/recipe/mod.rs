@@ -1,7 +1,12 @@
+mod display;
 mod read;
 mod recipe_formats;
+mod registry;
 
-pub use read::{Recipe, RecipeResult, RecipeType};
+pub use read::{Cooking, Recipe, RecipeCategory, RecipeResult, RecipeType};
+pub use registry::{
+    find_recipe, recipe_display, recipe_displays, register_recipe, unregister_recipe,
+};
 use std::sync::LazyLock;
 pub fn flatten_3x3<T: Clone>(input: [[Option<T>; 3]; 3]) -> [[Option<T>; 3]; 3] {
     let mut final_output = [const { [const { None }; 3] }; 3];
@@ -43,8 +48,27 @@ pub static RECIPES: LazyLock<Vec<Recipe>> =
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
+
+    use pumpkin_data::tag::{RegistryEntryList, TagType};
+    use pumpkin_protocol::client::play::RecipeDisplay;
+
     use super::flatten_3x3;
-    use crate::RECIPES;
+    use crate::{
+        recipe_display, register_recipe, unregister_recipe, Recipe, RecipeResult, RECIPES,
+    };
+
+    fn diamond() -> RegistryEntryList {
+        RegistryEntryList::Single(TagType::Item("minecraft:diamond".to_string()))
+    }
+
+    fn result() -> RecipeResult {
+        RecipeResult::Many {
+            count: 2,
+            id: "minecraft:diamond_block".to_string(),
+            components: None,
+        }
+    }
 
     #[test]
     fn row_flatten() {
@@ -87,4 +111,41 @@ mod test {
     fn check_parsing() {
         assert!(!RECIPES.is_empty())
     }
+
+    #[test]
+    fn shaped_recipes_are_validated() {
+        let keys = HashMap::from([('D', diamond())]);
+        assert!(Recipe::shaped(&["DDDD"], keys.clone(), result()).is_err());
+        assert!(Recipe::shaped(&["DX"], keys.clone(), result()).is_err());
+        assert!(Recipe::shaped(&["   "], keys.clone(), result()).is_err());
+
+        let recipe = Recipe::shaped(&["   ", " DD"], keys, result()).unwrap();
+        let pattern = &recipe.pattern()[0];
+        assert!(pattern[0][0].is_some() && pattern[0][1].is_some());
+        assert!(pattern[0][2].is_none() && pattern[1].iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn custom_recipes() {
+        let recipe = || Recipe::shapeless(vec![diamond(), diamond()], result()).unwrap();
+        assert!(register_recipe("minecraft:diamond_block", recipe()).is_err());
+        assert!(register_recipe("diamond_block", recipe()).is_err());
+
+        let display_id = register_recipe("test:diamond_block", recipe()).unwrap();
+        assert!(display_id >= RECIPES.len() as i32);
+        assert!(register_recipe("test:diamond_block", recipe()).is_err());
+
+        let display = recipe_display("test:diamond_block").unwrap();
+        assert!(matches!(
+            display.display,
+            RecipeDisplay::CraftingShapeless { ref ingredients, .. } if ingredients.len() == 2
+        ));
+
+        assert_eq!(unregister_recipe("test:diamond_block"), Some(display_id));
+        assert!(recipe_display("test:diamond_block").is_none());
+        // Ids are not reused
+        let new_id = register_recipe("test:diamond_block", recipe()).unwrap();
+        assert_ne!(new_id, display_id);
+        unregister_recipe("test:diamond_block");
+    }
 }
@@ -4,7 +4,7 @@ use crate::recipe::read::SpecialCraftingType::{
     ArmorDye, BannerDuplicate, BookCloning, Firework, RepairItem, ShieldDecoration,
     ShulkerboxColoring, SuspiciousStew, TippedArrow,
 };
-use crate::recipe::recipe_formats::{ShapedCrafting, ShapelessCrafting};
+use crate::recipe::recipe_formats::{CookingRecipe, ShapedCrafting, ShapelessCrafting};
 use pumpkin_data::tag::RegistryEntryList;
 use serde::de::{Error, MapAccess, Visitor};
 use serde::{de, Deserialize, Deserializer};
@@ -109,7 +109,7 @@ pub mod ingredients {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum RecipeResult {
     Many {
         count: u8,
@@ -201,6 +201,19 @@ impl<'de> Deserialize<'de> for RecipeResult {
 }
 pub struct RecipeKeys(pub(super) HashMap<char, RegistryEntryList>);
 
+/// The recipe book tab a recipe is shown in. Which values are used depends on the recipe type.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecipeCategory {
+    Building,
+    Redstone,
+    Equipment,
+    #[default]
+    Misc,
+    Food,
+    Blocks,
+}
+
 impl<'de> Deserialize<'de> for RecipeKeys {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -279,7 +292,7 @@ impl<'de> Deserialize<'de> for Recipe {
                 A: MapAccess<'de>,
             {
                 let mut recipe_type: Option<&str> = None;
-                let mut category: Option<&str> = None;
+                let mut category: Option<RecipeCategory> = None;
                 let mut group: Option<&str> = None;
                 let mut keys: Option<RecipeKeys> = None;
                 let mut pattern: Option<Vec<&str>> = None;
@@ -298,7 +311,7 @@ impl<'de> Deserialize<'de> for Recipe {
                     (match key {
                         Fields::Type => visit_option(&mut map, &mut recipe_type, "type"),
                         Fields::Group => visit_option(&mut map, &mut group, "group"),
-                        Fields::Category => visit_option(&mut map, &mut category, "group"),
+                        Fields::Category => visit_option(&mut map, &mut category, "category"),
                         Fields::Key => visit_option(&mut map, &mut keys, "key"),
                         Fields::Pattern => visit_option(&mut map, &mut pattern, "pattern"),
                         Fields::Result => visit_option(&mut map, &mut result, "result"),
@@ -335,7 +348,7 @@ impl<'de> Deserialize<'de> for Recipe {
                     _ => result.ok_or_else(|| de::Error::missing_field("result"))?,
                 };
 
-                match recipe_type {
+                let recipe = match recipe_type {
                     RecipeType::Crafting(CraftingType::Shaped) => {
                         let mut rows = [[None; 3], [None; 3], [None; 3]];
                         pattern
@@ -351,39 +364,49 @@ impl<'de> Deserialize<'de> for Recipe {
                             .enumerate()
                             .for_each(|(i, row)| rows[i] = row);
                         let keys = keys.ok_or_else(|| de::Error::missing_field("keys"))?;
-                        Ok(Recipe::from(ShapedCrafting::new(keys, rows, result)))
+                        Recipe::from(ShapedCrafting::new(keys, rows, result))
                     }
                     RecipeType::Crafting(CraftingType::Shapeless) => {
                         let ingredients =
                             ingredients.ok_or_else(|| de::Error::missing_field("ingredients"))?;
-                        Ok(Recipe::from(ShapelessCrafting::new(ingredients.0, result)))
+                        Recipe::from(ShapelessCrafting::new(ingredients.0, result))
                     }
-                    RecipeType::Crafting(CraftingType::Special(_)) => Ok(Recipe::from(Test {
-                        recipe_type,
-                        result,
-                    })),
-                    RecipeType::Crafting(CraftingType::DecoratedPot) => Ok(Recipe::from(Test {
-                        recipe_type,
-                        result,
-                    })),
                     RecipeType::Crafting(CraftingType::Transmute) => {
                         let _input =
                             transmute_input.ok_or_else(|| de::Error::missing_field("input"))?;
                         // Maybe also has material
-                        Ok(Recipe::from(Test {
+                        Recipe::from(Test {
                             recipe_type,
                             result,
-                        }))
+                        })
                     }
-                    RecipeType::Smithing(_) => Ok(Recipe::from(Test {
+                    RecipeType::Smithing(_) => Recipe::from(Test {
                         recipe_type,
                         result: RecipeResult::Special,
-                    })),
-                    _ => Ok(Recipe::from(Test {
+                    }),
+                    RecipeType::Smelting
+                    | RecipeType::Blasting
+                    | RecipeType::Smoking
+                    | RecipeType::CampfireCooking => {
+                        let ingredient =
+                            ingredient.ok_or_else(|| de::Error::missing_field("ingredient"))?;
+                        Recipe::from(CookingRecipe::new(
+                            recipe_type,
+                            ingredient,
+                            result,
+                            Cooking {
+                                cooking_time: cookingtime
+                                    .unwrap_or_else(|| default_cooking_time(recipe_type)),
+                                experience: experience.unwrap_or_default(),
+                            },
+                        ))
+                    }
+                    _ => Recipe::from(Test {
                         recipe_type,
                         result,
-                    })),
-                }
+                    }),
+                };
+                Ok(recipe.with_category(category.unwrap_or_default()))
             }
         }
 
@@ -425,13 +448,112 @@ fn visit_option<'de, T: Deserialize<'de>, Map: MapAccess<'de>>(
     }
 }
 
+/// How long an item is cooked and how much experience it gives
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cooking {
+    /// In ticks
+    pub cooking_time: u16,
+    pub experience: f32,
+}
+
+const fn default_cooking_time(recipe_type: RecipeType) -> u16 {
+    match recipe_type {
+        RecipeType::Blasting | RecipeType::Smoking => 100,
+        RecipeType::CampfireCooking => 600,
+        _ => 200,
+    }
+}
+
+#[derive(Clone)]
 pub struct Recipe {
     pub recipe_type: RecipeType,
     pattern: Vec<[[Option<RegistryEntryList>; 3]; 3]>,
     result: RecipeResult,
+    category: RecipeCategory,
+    cooking: Option<Cooking>,
 }
 
 impl Recipe {
+    /// Creates a shaped crafting recipe, like the ones of data packs.
+    ///
+    /// # Arguments
+    /// - `pattern`: Up to 3 rows of up to 3 characters. A space is an empty slot.
+    /// - `keys`: The ingredient of each character used in the pattern.
+    /// - `result`: The crafted item.
+    pub fn shaped(
+        pattern: &[&str],
+        keys: HashMap<char, RegistryEntryList>,
+        result: RecipeResult,
+    ) -> Result<Self, String> {
+        if pattern.is_empty()
+            || pattern.len() > 3
+            || pattern.iter().any(|row| row.chars().count() > 3)
+        {
+            return Err("A pattern must have 1 to 3 rows of up to 3 characters".to_string());
+        }
+        let mut rows = [[None; 3]; 3];
+        for (i, row) in pattern.iter().enumerate() {
+            for (j, char) in row.chars().enumerate() {
+                if char == ' ' {
+                    continue;
+                }
+                if !keys.contains_key(&char) {
+                    return Err(format!("The pattern uses {char}, which has no ingredient"));
+                }
+                rows[i][j] = Some(char);
+            }
+        }
+        if rows.iter().flatten().all(Option::is_none) {
+            return Err("A pattern must contain an ingredient".to_string());
+        }
+        Ok(Self::from(ShapedCrafting::new(
+            RecipeKeys(keys),
+            rows,
+            result,
+        )))
+    }
+
+    /// Creates a shapeless crafting recipe with 1 to 9 ingredients.
+    pub fn shapeless(
+        ingredients: Vec<RegistryEntryList>,
+        result: RecipeResult,
+    ) -> Result<Self, String> {
+        if ingredients.is_empty() || ingredients.len() > 9 {
+            return Err("A shapeless recipe must have 1 to 9 ingredients".to_string());
+        }
+        Ok(Self::from(ShapelessCrafting::new(ingredients, result)))
+    }
+
+    /// Creates a furnace recipe.
+    ///
+    /// # Arguments
+    /// - `ingredient`: The item which is smelted.
+    /// - `result`: The smelted item.
+    /// - `cooking_time`: How long smelting takes, in ticks.
+    /// - `experience`: The experience given for smelting one item.
+    pub fn smelting(
+        ingredient: RegistryEntryList,
+        result: RecipeResult,
+        cooking_time: u16,
+        experience: f32,
+    ) -> Self {
+        Self::from(CookingRecipe::new(
+            RecipeType::Smelting,
+            ingredient,
+            result,
+            Cooking {
+                cooking_time,
+                experience,
+            },
+        ))
+    }
+
+    /// Sets the recipe book tab the recipe is shown in.
+    #[must_use]
+    pub fn with_category(self, category: RecipeCategory) -> Self {
+        Self { category, ..self }
+    }
+
     pub fn pattern(&self) -> &[[[Option<RegistryEntryList>; 3]; 3]] {
         &self.pattern
     }
@@ -440,6 +562,15 @@ impl Recipe {
         &self.result
     }
 
+    pub fn category(&self) -> RecipeCategory {
+        self.category
+    }
+
+    /// Only set for furnace, blast furnace, smoker and campfire recipes
+    pub fn cooking(&self) -> Option<Cooking> {
+        self.cooking
+    }
+
     pub fn implemented(&self) -> bool {
         match self.recipe_type {
             RecipeType::Crafting(crafting_type) => {
@@ -481,12 +612,18 @@ pub trait RecipeTrait: Sized {
 
     fn pattern(&self) -> Vec<[[Option<RegistryEntryList>; 3]; 3]>;
 
+    fn cooking(&self) -> Option<Cooking> {
+        None
+    }
+
     fn result(self) -> RecipeResult;
 
     fn to_recipe(self) -> Recipe {
         Recipe {
             recipe_type: self.recipe_type(),
             pattern: self.pattern().into_iter().map(flatten_3x3).collect(),
+            cooking: self.cooking(),
+            category: RecipeCategory::default(),
             result: self.result(),
         }
     }
@@ -1,7 +1,7 @@
 use pumpkin_data::tag::RegistryEntryList;
 
 use super::super::recipe::RecipeType;
-use super::read::{CraftingType, RecipeKeys, RecipeResult, RecipeTrait};
+use super::read::{Cooking, CraftingType, RecipeKeys, RecipeResult, RecipeTrait};
 pub struct ShapedCrafting {
     keys: RecipeKeys,
     pattern: [[Option<char>; 3]; 3],
@@ -90,3 +90,46 @@ impl RecipeTrait for ShapelessCrafting {
         self.output
     }
 }
+
+pub struct CookingRecipe {
+    recipe_type: RecipeType,
+    ingredient: RegistryEntryList,
+    output: RecipeResult,
+    cooking: Cooking,
+}
+
+impl CookingRecipe {
+    pub(crate) fn new(
+        recipe_type: RecipeType,
+        ingredient: RegistryEntryList,
+        output: RecipeResult,
+        cooking: Cooking,
+    ) -> Self {
+        Self {
+            recipe_type,
+            ingredient,
+            output,
+            cooking,
+        }
+    }
+}
+
+impl RecipeTrait for CookingRecipe {
+    fn recipe_type(&self) -> RecipeType {
+        self.recipe_type
+    }
+
+    fn pattern(&self) -> Vec<[[Option<RegistryEntryList>; 3]; 3]> {
+        let mut pattern = [const { [const { None }; 3] }; 3];
+        pattern[0][0] = Some(self.ingredient.clone());
+        vec![pattern]
+    }
+
+    fn cooking(&self) -> Option<Cooking> {
+        Some(self.cooking)
+    }
+
+    fn result(self) -> RecipeResult {
+        self.output
+    }
+}
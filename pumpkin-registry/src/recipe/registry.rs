@@ -0,0 +1,105 @@
+use std::sync::{LazyLock, RwLock};
+
+use indexmap::IndexMap;
+use pumpkin_protocol::client::play::RecipeDisplayEntry;
+use rayon::prelude::*;
+
+use super::{Recipe, RecipeResult, RECIPES};
+
+/// Recipes added at runtime, e.g. by plugins
+struct CustomRecipes {
+    recipes: IndexMap<String, (i32, Recipe)>,
+    /// Display ids are never reused, so a client can not confuse a new recipe with a removed one
+    next_display_id: i32,
+}
+
+static CUSTOM_RECIPES: LazyLock<RwLock<CustomRecipes>> = LazyLock::new(|| {
+    RwLock::new(CustomRecipes {
+        recipes: IndexMap::new(),
+        next_display_id: RECIPES.len() as i32,
+    })
+});
+
+/// Adds a recipe, which is crafted like the recipes of data packs.
+///
+/// # Arguments
+/// - `id`: A namespaced id like `my_plugin:ruby_block`. The `minecraft` namespace is reserved for
+///   the recipes of data packs.
+///
+/// # Returns
+/// The id the client knows the recipe by, or an error if the id is invalid or already taken.
+pub fn register_recipe(id: &str, recipe: Recipe) -> Result<i32, String> {
+    let Some((namespace, path)) = id.split_once(':') else {
+        return Err(format!("Recipe id {id} has no namespace"));
+    };
+    if namespace.is_empty() || path.is_empty() {
+        return Err(format!("Invalid recipe id {id}"));
+    }
+    if namespace == "minecraft" {
+        return Err(format!(
+            "Recipe id {id} collides with the recipes of data packs"
+        ));
+    }
+
+    let mut custom = CUSTOM_RECIPES.write().unwrap();
+    if custom.recipes.contains_key(id) {
+        return Err(format!("Recipe {id} is already registered"));
+    }
+    let display_id = custom.next_display_id;
+    custom.next_display_id += 1;
+    custom.recipes.insert(id.to_string(), (display_id, recipe));
+    Ok(display_id)
+}
+
+/// Removes a recipe added with [`register_recipe`].
+///
+/// # Returns
+/// The id the client knows the recipe by, if it was removed.
+pub fn unregister_recipe(id: &str) -> Option<i32> {
+    CUSTOM_RECIPES
+        .write()
+        .unwrap()
+        .recipes
+        .shift_remove(id)
+        .map(|(display_id, _)| display_id)
+}
+
+/// Returns the result of the first recipe of data packs or added at runtime which satisfies the
+/// predicate.
+pub fn find_recipe(predicate: impl Fn(&Recipe) -> bool + Sync) -> Option<RecipeResult> {
+    if let Some(recipe) = RECIPES.par_iter().find_any(|recipe| predicate(recipe)) {
+        return Some(recipe.result().clone());
+    }
+    CUSTOM_RECIPES
+        .read()
+        .unwrap()
+        .recipes
+        .values()
+        .find(|(_, recipe)| predicate(recipe))
+        .map(|(_, recipe)| recipe.result().clone())
+}
+
+/// Returns how the recipe added with the given id is shown in the recipe book.
+pub fn recipe_display(id: &str) -> Option<RecipeDisplayEntry> {
+    let custom = CUSTOM_RECIPES.read().unwrap();
+    let (display_id, recipe) = custom.recipes.get(id)?;
+    recipe.display(*display_id)
+}
+
+/// Returns every recipe which can be shown in the recipe book, which is how clients learn about
+/// recipes.
+pub fn recipe_displays() -> Vec<RecipeDisplayEntry> {
+    let custom = CUSTOM_RECIPES.read().unwrap();
+    RECIPES
+        .iter()
+        .enumerate()
+        .map(|(display_id, recipe)| (display_id as i32, recipe))
+        .chain(
+            custom
+                .recipes
+                .values()
+                .map(|(display_id, recipe)| (*display_id, recipe)),
+        )
+        .filter_map(|(display_id, recipe)| recipe.display(display_id))
+        .collect()
+}
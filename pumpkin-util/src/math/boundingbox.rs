@@ -57,6 +57,14 @@ impl BoundingBox {
         }
     }
 
+    /// The box grown by the amounts on both sides of each axis
+    pub fn expand(&self, x: f64, y: f64, z: f64) -> Self {
+        Self {
+            min: self.min.add_raw(-x, -y, -z),
+            max: self.max.add_raw(x, y, z),
+        }
+    }
+
     pub fn intersects(&self, other: &BoundingBox) -> bool {
         self.min.x < other.max.x
             && self.max.x > other.min.x
@@ -0,0 +1,105 @@
+//! The temperature at a position, which decides whether it rains or snows there and whether
+//! water freezes, like in vanilla.
+//!
+//! Each biome has a base temperature. It gets colder higher above the sea, and frozen oceans have
+//! warmer patches in them. The noises for both use fixed seeds, so they are the same in every
+//! world.
+
+use std::sync::LazyLock;
+
+use pumpkin_data::chunk::{Biome, TemperatureModifier};
+use pumpkin_util::{
+    math::vector3::Vector3,
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+
+use crate::generation::noise::simplex::OctaveSimplexNoiseSampler;
+
+/// The sea level of the overworld, from which it gets colder the higher a position is
+pub const SEA_LEVEL: i32 = 63;
+
+/// Below this temperature it snows instead of raining and water freezes
+pub const SNOW_TEMPERATURE: f32 = 0.15;
+
+/// The temperature of the warmer patches in frozen oceans
+const FROZEN_PATCH_TEMPERATURE: f32 = 0.2;
+
+/// How many blocks above the sea level it starts to get colder
+const COOLING_START: i32 = 17;
+
+static TEMPERATURE_NOISE: LazyLock<OctaveSimplexNoiseSampler> =
+    LazyLock::new(|| OctaveSimplexNoiseSampler::new(&mut LegacyRand::from_seed(1234), &[0]));
+static FROZEN_TEMPERATURE_NOISE: LazyLock<OctaveSimplexNoiseSampler> = LazyLock::new(|| {
+    OctaveSimplexNoiseSampler::new(&mut LegacyRand::from_seed(3456), &[-2, -1, 0])
+});
+static BIOME_INFO_NOISE: LazyLock<OctaveSimplexNoiseSampler> =
+    LazyLock::new(|| OctaveSimplexNoiseSampler::new(&mut LegacyRand::from_seed(2345), &[0]));
+
+/// The temperature of the biome at the position, before it gets colder with the height
+fn modified_temperature(biome: Biome, position: Vector3<i32>) -> f32 {
+    let temperature = biome.temperature();
+    match biome.temperature_modifier() {
+        TemperatureModifier::None => temperature,
+        TemperatureModifier::Frozen => {
+            let (x, z) = (f64::from(position.x), f64::from(position.z));
+            let frozen = FROZEN_TEMPERATURE_NOISE.sample(x * 0.05, z * 0.05, false) * 7.0;
+            let info = BIOME_INFO_NOISE.sample(x * 0.2, z * 0.2, false);
+            if frozen + info < 0.3 && BIOME_INFO_NOISE.sample(x * 0.09, z * 0.09, false) < 0.8 {
+                FROZEN_PATCH_TEMPERATURE
+            } else {
+                temperature
+            }
+        }
+    }
+}
+
+/// The temperature at the position in the biome
+#[must_use]
+pub fn temperature_at(biome: Biome, position: Vector3<i32>) -> f32 {
+    let temperature = modified_temperature(biome, position);
+    let cooling_start = SEA_LEVEL + COOLING_START;
+    if position.y <= cooling_start {
+        return temperature;
+    }
+    let noise = (TEMPERATURE_NOISE.sample(
+        f64::from(position.x) / 8.0,
+        f64::from(position.z) / 8.0,
+        false,
+    ) * 8.0) as f32;
+    temperature - (noise + (position.y - cooling_start) as f32) * 0.05 / 40.0
+}
+
+/// Whether it snows at the position instead of raining, and water freezes there
+#[must_use]
+pub fn is_cold_enough_to_snow(biome: Biome, position: Vector3<i32>) -> bool {
+    temperature_at(biome, position) < SNOW_TEMPERATURE
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cold_biomes_snow() {
+        let position = Vector3::new(0, SEA_LEVEL, 0);
+        assert!(is_cold_enough_to_snow(Biome::SnowyPlains, position));
+        assert!(!is_cold_enough_to_snow(Biome::Plains, position));
+        assert!(!is_cold_enough_to_snow(Biome::Desert, position));
+    }
+
+    #[test]
+    fn mountains_get_colder() {
+        let low = temperature_at(Biome::Plains, Vector3::new(0, SEA_LEVEL, 0));
+        let high = temperature_at(Biome::Plains, Vector3::new(0, 300, 0));
+        assert!(high < low);
+        // Windswept hills are warm at the sea but snowy on their peaks
+        assert!(!is_cold_enough_to_snow(
+            Biome::WindsweptHills,
+            Vector3::new(0, SEA_LEVEL, 0)
+        ));
+        assert!(is_cold_enough_to_snow(
+            Biome::WindsweptHills,
+            Vector3::new(0, 200, 0)
+        ));
+    }
+}
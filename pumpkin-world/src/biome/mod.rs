@@ -0,0 +1,56 @@
+use std::cell::Cell;
+
+use enum_dispatch::enum_dispatch;
+use pumpkin_data::chunk::Biome;
+
+use crate::generation::noise_router::multi_noise_sampler::MultiNoiseSampler;
+
+use overworld::OVERWORLD_BIOME_SEARCH_TREE;
+
+pub mod climate;
+pub mod multi_noise;
+pub mod overworld;
+
+#[derive(Clone)]
+#[enum_dispatch(BiomeSupplierImpl)]
+pub enum BiomeSupplier {
+    Debug(DebugBiomeSupplier),
+    MultiNoise(MultiNoiseBiomeSupplier),
+}
+
+#[enum_dispatch]
+pub trait BiomeSupplierImpl {
+    /// Returns the biome at the given biome coordinates
+    fn biome(&self, x: i32, y: i32, z: i32, noise: &mut MultiNoiseSampler) -> Biome;
+}
+
+#[derive(Clone)]
+pub struct DebugBiomeSupplier {}
+
+impl BiomeSupplierImpl for DebugBiomeSupplier {
+    fn biome(&self, _x: i32, _y: i32, _z: i32, _noise: &mut MultiNoiseSampler) -> Biome {
+        Biome::Plains
+    }
+}
+
+thread_local! {
+    /// The entry found by the last search of this thread, which is most likely close to the next
+    /// one because biomes are sampled next to each other.
+    static LAST_RESULT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Chooses the overworld biome whose climate is closest to the sampled climate noise.
+#[derive(Clone)]
+pub struct MultiNoiseBiomeSupplier;
+
+impl BiomeSupplierImpl for MultiNoiseBiomeSupplier {
+    fn biome(&self, x: i32, y: i32, z: i32, noise: &mut MultiNoiseSampler) -> Biome {
+        let point = noise.sample(x, y, z);
+        LAST_RESULT.with(|last_result| {
+            let mut previous = last_result.get();
+            let biome = *OVERWORLD_BIOME_SEARCH_TREE.get(&point, &mut previous);
+            last_result.set(previous);
+            biome
+        })
+    }
+}
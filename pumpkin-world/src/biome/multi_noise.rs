@@ -0,0 +1,402 @@
+/// The number of parameters biomes are searched by: the six climate parameters and the offset
+pub const PARAMETER_COUNT: usize = 7;
+
+/// The number of children of each node of the search tree
+const TREE_WIDTH: usize = 6;
+
+/// Converts a climate value to the fixed point representation biomes are searched with
+pub fn to_long(value: f32) -> i64 {
+    (value * 10000.0) as i64
+}
+
+/// A range of a climate parameter, in the fixed point representation of [`to_long`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParameterRange {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl ParameterRange {
+    pub fn new(min: f32, max: f32) -> Self {
+        Self {
+            min: to_long(min),
+            max: to_long(max),
+        }
+    }
+
+    pub fn point(value: f32) -> Self {
+        Self::new(value, value)
+    }
+
+    /// The range from the minimum of this range to the maximum of `other`
+    pub const fn combine(self, other: Self) -> Self {
+        Self {
+            min: self.min,
+            max: other.max,
+        }
+    }
+
+    /// The distance of a value to the range, which is 0 for values inside of it
+    fn distance(&self, value: i64) -> i64 {
+        (value - self.max).max(self.min - value).max(0)
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    fn center(&self) -> i64 {
+        (self.min + self.max) / 2
+    }
+}
+
+/// The climate a biome generates in. The biome closest to the sampled climate is chosen, where
+/// the offset makes a biome less likely.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoiseHypercube {
+    pub temperature: ParameterRange,
+    pub humidity: ParameterRange,
+    pub continentalness: ParameterRange,
+    pub erosion: ParameterRange,
+    pub depth: ParameterRange,
+    pub weirdness: ParameterRange,
+    pub offset: i64,
+}
+
+impl NoiseHypercube {
+    fn parameters(&self) -> [ParameterRange; PARAMETER_COUNT] {
+        [
+            self.temperature,
+            self.humidity,
+            self.continentalness,
+            self.erosion,
+            self.depth,
+            self.weirdness,
+            ParameterRange {
+                min: self.offset,
+                max: self.offset,
+            },
+        ]
+    }
+}
+
+/// The climate sampled at a position, in the fixed point representation of [`to_long`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoiseValuePoint {
+    pub temperature: i64,
+    pub humidity: i64,
+    pub continentalness: i64,
+    pub erosion: i64,
+    pub depth: i64,
+    pub weirdness: i64,
+}
+
+impl NoiseValuePoint {
+    pub fn new(
+        temperature: f32,
+        humidity: f32,
+        continentalness: f32,
+        erosion: f32,
+        depth: f32,
+        weirdness: f32,
+    ) -> Self {
+        Self {
+            temperature: to_long(temperature),
+            humidity: to_long(humidity),
+            continentalness: to_long(continentalness),
+            erosion: to_long(erosion),
+            depth: to_long(depth),
+            weirdness: to_long(weirdness),
+        }
+    }
+
+    fn values(&self) -> [i64; PARAMETER_COUNT] {
+        [
+            self.temperature,
+            self.humidity,
+            self.continentalness,
+            self.erosion,
+            self.depth,
+            self.weirdness,
+            0,
+        ]
+    }
+}
+
+fn squared_distance(
+    parameters: &[ParameterRange; PARAMETER_COUNT],
+    point: &[i64; PARAMETER_COUNT],
+) -> i64 {
+    parameters
+        .iter()
+        .zip(point)
+        .map(|(range, value)| {
+            let distance = range.distance(*value);
+            distance * distance
+        })
+        .sum()
+}
+
+enum Node {
+    Leaf {
+        parameters: [ParameterRange; PARAMETER_COUNT],
+        /// The index of the entry in [`SearchTree::entries`]
+        entry: usize,
+    },
+    Branch {
+        /// The ranges enclosing the ones of all children
+        parameters: [ParameterRange; PARAMETER_COUNT],
+        children: Box<[Node]>,
+    },
+}
+
+impl Node {
+    fn parameters(&self) -> &[ParameterRange; PARAMETER_COUNT] {
+        match self {
+            Self::Leaf { parameters, .. } | Self::Branch { parameters, .. } => parameters,
+        }
+    }
+
+    fn branch(children: Vec<Self>) -> Self {
+        Self::Branch {
+            parameters: enclosing_parameters(children.iter().map(Self::parameters)),
+            children: children.into_boxed_slice(),
+        }
+    }
+}
+
+fn enclosing_parameters<'a>(
+    mut parameters: impl Iterator<Item = &'a [ParameterRange; PARAMETER_COUNT]>,
+) -> [ParameterRange; PARAMETER_COUNT] {
+    let mut enclosing = *parameters.next().expect("Nodes can not be empty");
+    for parameters in parameters {
+        for (enclosing, range) in enclosing.iter_mut().zip(parameters) {
+            *enclosing = enclosing.union(range);
+        }
+    }
+    enclosing
+}
+
+fn range_length_sum(parameters: &[ParameterRange; PARAMETER_COUNT]) -> i64 {
+    parameters
+        .iter()
+        .map(|range| (range.max - range.min).abs())
+        .sum()
+}
+
+/// Sorts by the centers of the ranges, starting with `parameter` and continuing with the
+/// following ones
+fn sort_by_centers<T>(
+    items: &mut [T],
+    parameters: impl Fn(&T) -> [ParameterRange; PARAMETER_COUNT],
+    parameter: usize,
+    abs: bool,
+) {
+    items.sort_by_cached_key(|item| {
+        let parameters = parameters(item);
+        (0..PARAMETER_COUNT)
+            .map(|i| {
+                let center = parameters[(parameter + i) % PARAMETER_COUNT].center();
+                if abs {
+                    center.abs()
+                } else {
+                    center
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+}
+
+/// The nodes are grouped into batches of the largest power of [`TREE_WIDTH`] below their count
+fn batch_size(count: usize) -> usize {
+    let exponent = ((count as f64 - 0.01).ln() / (TREE_WIDTH as f64).ln()).floor();
+    TREE_WIDTH.pow(exponent as u32)
+}
+
+fn create_node(mut nodes: Vec<Node>) -> Node {
+    if nodes.len() == 1 {
+        return nodes.pop().unwrap();
+    }
+    if nodes.len() <= TREE_WIDTH {
+        nodes.sort_by_cached_key(|node| {
+            node.parameters()
+                .iter()
+                .map(|range| range.center().abs())
+                .sum::<i64>()
+        });
+        return Node::branch(nodes);
+    }
+
+    // Split the nodes along the parameter which results in the smallest batches
+    let batch_size = batch_size(nodes.len());
+    let mut order: Vec<usize> = (0..nodes.len()).collect();
+    let mut best: Option<(i64, usize, Vec<usize>)> = None;
+    for parameter in 0..PARAMETER_COUNT {
+        sort_by_centers(&mut order, |i| *nodes[*i].parameters(), parameter, false);
+        let cost = order
+            .chunks(batch_size)
+            .map(|batch| {
+                range_length_sum(&enclosing_parameters(
+                    batch.iter().map(|i| nodes[*i].parameters()),
+                ))
+            })
+            .sum::<i64>();
+        if best
+            .as_ref()
+            .is_none_or(|(best_cost, ..)| cost < *best_cost)
+        {
+            best = Some((cost, parameter, order.clone()));
+        }
+    }
+    let (_, parameter, order) = best.unwrap();
+
+    let mut nodes: Vec<Option<Node>> = nodes.into_iter().map(Some).collect();
+    let mut batches: Vec<(Vec<Node>, [ParameterRange; PARAMETER_COUNT])> = order
+        .chunks(batch_size)
+        .map(|batch| {
+            let batch: Vec<Node> = batch.iter().map(|i| nodes[*i].take().unwrap()).collect();
+            let parameters = enclosing_parameters(batch.iter().map(Node::parameters));
+            (batch, parameters)
+        })
+        .collect();
+    sort_by_centers(&mut batches, |(_, parameters)| *parameters, parameter, true);
+
+    Node::branch(
+        batches
+            .into_iter()
+            .map(|(batch, _)| create_node(batch))
+            .collect(),
+    )
+}
+
+/// Finds the entry with the climate closest to a sampled one, the same way vanilla does.
+pub struct SearchTree<T> {
+    root: Node,
+    entries: Vec<(NoiseHypercube, T)>,
+}
+
+impl<T> SearchTree<T> {
+    /// # Panics
+    /// If there are no entries.
+    pub fn new(entries: Vec<(NoiseHypercube, T)>) -> Self {
+        assert!(!entries.is_empty(), "Search trees need at least one entry");
+        let leaves = entries
+            .iter()
+            .enumerate()
+            .map(|(entry, (hypercube, _))| Node::Leaf {
+                parameters: hypercube.parameters(),
+                entry,
+            })
+            .collect();
+        Self {
+            root: create_node(leaves),
+            entries,
+        }
+    }
+
+    pub fn entries(&self) -> &[(NoiseHypercube, T)] {
+        &self.entries
+    }
+
+    /// Gets the value of the entry closest to the point.
+    ///
+    /// `previous` is the entry found by the previous search, which is likely close to the point
+    /// as well and lets the search skip most of the tree. It is updated with the found entry.
+    pub fn get(&self, point: &NoiseValuePoint, previous: &mut Option<usize>) -> &T {
+        let values = point.values();
+        let entry = self
+            .search(&self.root, &values, *previous)
+            .expect("The tree is never empty");
+        *previous = Some(entry);
+        &self.entries[entry].1
+    }
+
+    fn search(
+        &self,
+        node: &Node,
+        point: &[i64; PARAMETER_COUNT],
+        alternative: Option<usize>,
+    ) -> Option<usize> {
+        let children = match node {
+            Node::Leaf { entry, .. } => return Some(*entry),
+            Node::Branch { children, .. } => children,
+        };
+
+        let mut best = alternative;
+        let mut best_distance = alternative.map_or(i64::MAX, |entry| {
+            squared_distance(&self.entries[entry].0.parameters(), point)
+        });
+        for child in children.iter() {
+            let distance = squared_distance(child.parameters(), point);
+            if best_distance <= distance {
+                continue;
+            }
+            let Some(entry) = self.search(child, point, best) else {
+                continue;
+            };
+            let entry_distance = match child {
+                Node::Leaf { .. } => distance,
+                Node::Branch { .. } => squared_distance(&self.entries[entry].0.parameters(), point),
+            };
+            if best_distance <= entry_distance {
+                continue;
+            }
+            best_distance = entry_distance;
+            best = Some(entry);
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::random::{xoroshiro128::Xoroshiro, RandomImpl};
+
+    use super::{squared_distance, NoiseHypercube, NoiseValuePoint, ParameterRange, SearchTree};
+
+    fn random_range(random: &mut Xoroshiro) -> ParameterRange {
+        let a = random.next_f32() * 2.0 - 1.0;
+        let b = random.next_f32() * 2.0 - 1.0;
+        ParameterRange::new(a.min(b), a.max(b))
+    }
+
+    #[test]
+    fn finds_closest_entry() {
+        let mut random = Xoroshiro::from_seed(123);
+        let entries: Vec<_> = (0..500)
+            .map(|i| {
+                let hypercube = NoiseHypercube {
+                    temperature: random_range(&mut random),
+                    humidity: random_range(&mut random),
+                    continentalness: random_range(&mut random),
+                    erosion: random_range(&mut random),
+                    depth: random_range(&mut random),
+                    weirdness: random_range(&mut random),
+                    offset: 0,
+                };
+                (hypercube, i)
+            })
+            .collect();
+        let tree = SearchTree::new(entries.clone());
+
+        let mut previous = None;
+        for _ in 0..1000 {
+            let mut value = || random.next_f32() * 2.4 - 1.2;
+            let point = NoiseValuePoint::new(value(), value(), value(), value(), value(), value());
+            let found = *tree.get(&point, &mut previous);
+
+            let closest = entries
+                .iter()
+                .map(|(hypercube, _)| squared_distance(&hypercube.parameters(), &point.values()))
+                .min()
+                .unwrap();
+            assert_eq!(
+                squared_distance(&entries[found].0.parameters(), &point.values()),
+                closest
+            );
+        }
+    }
+}
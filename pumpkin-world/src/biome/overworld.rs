@@ -0,0 +1,1003 @@
+use std::sync::LazyLock;
+
+use pumpkin_data::chunk::Biome;
+
+use super::multi_noise::{NoiseHypercube, ParameterRange, SearchTree};
+
+/// The biomes of the overworld and the climates they generate in, like in vanilla
+pub static OVERWORLD_BIOME_SEARCH_TREE: LazyLock<SearchTree<Biome>> =
+    LazyLock::new(|| SearchTree::new(overworld_biome_parameters()));
+
+const DEFAULT: ParameterRange = range(-1.0, 1.0);
+
+/// From frozen to hot
+const TEMPERATURES: [ParameterRange; 5] = [
+    range(-1.0, -0.45),
+    range(-0.45, -0.15),
+    range(-0.15, 0.2),
+    range(0.2, 0.55),
+    range(0.55, 1.0),
+];
+
+/// From arid to humid
+const HUMIDITIES: [ParameterRange; 5] = [
+    range(-1.0, -0.35),
+    range(-0.35, -0.1),
+    range(-0.1, 0.1),
+    range(0.1, 0.3),
+    range(0.3, 1.0),
+];
+
+/// From mountainous to flat
+const EROSIONS: [ParameterRange; 7] = [
+    range(-1.0, -0.78),
+    range(-0.78, -0.375),
+    range(-0.375, -0.2225),
+    range(-0.2225, 0.05),
+    range(0.05, 0.45),
+    range(0.45, 0.55),
+    range(0.55, 1.0),
+];
+
+const FROZEN_TEMPERATURE: ParameterRange = TEMPERATURES[0];
+const UNFROZEN_TEMPERATURE: ParameterRange = TEMPERATURES[1].combine(TEMPERATURES[4]);
+
+const MUSHROOM_FIELDS_CONTINENTALNESS: ParameterRange = range(-1.2, -1.05);
+const DEEP_OCEAN_CONTINENTALNESS: ParameterRange = range(-1.05, -0.455);
+const OCEAN_CONTINENTALNESS: ParameterRange = range(-0.455, -0.19);
+const COAST_CONTINENTALNESS: ParameterRange = range(-0.19, -0.11);
+const RIVER_CONTINENTALNESS: ParameterRange = range(-0.11, 0.55);
+const NEAR_INLAND_CONTINENTALNESS: ParameterRange = range(-0.11, 0.03);
+const MID_INLAND_CONTINENTALNESS: ParameterRange = range(0.03, 0.3);
+const FAR_INLAND_CONTINENTALNESS: ParameterRange = range(0.3, 1.0);
+
+/// Ocean biomes by depth (deep and shallow) and temperature
+const OCEAN_BIOMES: [[Biome; 5]; 2] = [
+    [
+        Biome::DeepFrozenOcean,
+        Biome::DeepColdOcean,
+        Biome::DeepOcean,
+        Biome::DeepLukewarmOcean,
+        Biome::WarmOcean,
+    ],
+    [
+        Biome::FrozenOcean,
+        Biome::ColdOcean,
+        Biome::Ocean,
+        Biome::LukewarmOcean,
+        Biome::WarmOcean,
+    ],
+];
+
+/// The following biome tables are by temperature and humidity
+const COMMON_BIOMES: [[Biome; 5]; 5] = [
+    [
+        Biome::SnowyPlains,
+        Biome::SnowyPlains,
+        Biome::SnowyPlains,
+        Biome::SnowyTaiga,
+        Biome::Taiga,
+    ],
+    [
+        Biome::Plains,
+        Biome::Plains,
+        Biome::Forest,
+        Biome::Taiga,
+        Biome::OldGrowthSpruceTaiga,
+    ],
+    [
+        Biome::FlowerForest,
+        Biome::Plains,
+        Biome::Forest,
+        Biome::BirchForest,
+        Biome::DarkForest,
+    ],
+    [
+        Biome::Savanna,
+        Biome::Savanna,
+        Biome::Forest,
+        Biome::Jungle,
+        Biome::Jungle,
+    ],
+    [Biome::Desert; 5],
+];
+
+/// Replace the common biomes where the weirdness is positive
+const UNCOMMON_BIOMES: [[Option<Biome>; 5]; 5] = [
+    [
+        Some(Biome::IceSpikes),
+        None,
+        Some(Biome::SnowyTaiga),
+        None,
+        None,
+    ],
+    [None, None, None, None, Some(Biome::OldGrowthPineTaiga)],
+    [
+        Some(Biome::SunflowerPlains),
+        None,
+        None,
+        Some(Biome::OldGrowthBirchForest),
+        None,
+    ],
+    [
+        None,
+        None,
+        Some(Biome::Plains),
+        Some(Biome::SparseJungle),
+        Some(Biome::BambooJungle),
+    ],
+    [None; 5],
+];
+
+const NEAR_MOUNTAIN_BIOMES: [[Biome; 5]; 5] = [
+    [
+        Biome::SnowyPlains,
+        Biome::SnowyPlains,
+        Biome::SnowyPlains,
+        Biome::SnowyTaiga,
+        Biome::SnowyTaiga,
+    ],
+    [
+        Biome::Meadow,
+        Biome::Meadow,
+        Biome::Forest,
+        Biome::Taiga,
+        Biome::OldGrowthSpruceTaiga,
+    ],
+    [
+        Biome::Meadow,
+        Biome::Meadow,
+        Biome::Meadow,
+        Biome::Meadow,
+        Biome::PaleGarden,
+    ],
+    [
+        Biome::SavannaPlateau,
+        Biome::SavannaPlateau,
+        Biome::Forest,
+        Biome::Forest,
+        Biome::Jungle,
+    ],
+    [
+        Biome::Badlands,
+        Biome::Badlands,
+        Biome::Badlands,
+        Biome::WoodedBadlands,
+        Biome::WoodedBadlands,
+    ],
+];
+
+/// Replace the near mountain biomes where the weirdness is positive
+const SPECIAL_NEAR_MOUNTAIN_BIOMES: [[Option<Biome>; 5]; 5] = [
+    [Some(Biome::IceSpikes), None, None, None, None],
+    [
+        Some(Biome::CherryGrove),
+        None,
+        Some(Biome::Meadow),
+        Some(Biome::Meadow),
+        Some(Biome::OldGrowthPineTaiga),
+    ],
+    [
+        Some(Biome::CherryGrove),
+        Some(Biome::CherryGrove),
+        Some(Biome::Forest),
+        Some(Biome::BirchForest),
+        None,
+    ],
+    [None; 5],
+    [
+        Some(Biome::ErodedBadlands),
+        Some(Biome::ErodedBadlands),
+        None,
+        None,
+        None,
+    ],
+];
+
+const WINDSWEPT_BIOMES: [[Option<Biome>; 5]; 5] = [
+    [
+        Some(Biome::WindsweptGravellyHills),
+        Some(Biome::WindsweptGravellyHills),
+        Some(Biome::WindsweptHills),
+        Some(Biome::WindsweptForest),
+        Some(Biome::WindsweptForest),
+    ],
+    [
+        Some(Biome::WindsweptGravellyHills),
+        Some(Biome::WindsweptGravellyHills),
+        Some(Biome::WindsweptHills),
+        Some(Biome::WindsweptForest),
+        Some(Biome::WindsweptForest),
+    ],
+    [
+        Some(Biome::WindsweptHills),
+        Some(Biome::WindsweptHills),
+        Some(Biome::WindsweptHills),
+        Some(Biome::WindsweptForest),
+        Some(Biome::WindsweptForest),
+    ],
+    [None; 5],
+    [None; 5],
+];
+
+const fn range(min: f32, max: f32) -> ParameterRange {
+    ParameterRange {
+        min: (min * 10000.0) as i64,
+        max: (max * 10000.0) as i64,
+    }
+}
+
+/// Builds the vanilla overworld biome parameters, in the same order as vanilla, which matters
+/// for biomes with equally close climates.
+pub fn overworld_biome_parameters() -> Vec<(NoiseHypercube, Biome)> {
+    let mut builder = OverworldBiomeBuilder {
+        entries: Vec::with_capacity(7593),
+    };
+    builder.add_ocean_biomes();
+    builder.add_land_biomes();
+    builder.add_cave_biomes();
+    builder.entries
+}
+
+struct OverworldBiomeBuilder {
+    entries: Vec<(NoiseHypercube, Biome)>,
+}
+
+impl OverworldBiomeBuilder {
+    #[allow(clippy::too_many_arguments)]
+    fn add_with_depth(
+        &mut self,
+        temperature: ParameterRange,
+        humidity: ParameterRange,
+        continentalness: ParameterRange,
+        erosion: ParameterRange,
+        depth: ParameterRange,
+        weirdness: ParameterRange,
+        biome: Biome,
+    ) {
+        self.entries.push((
+            NoiseHypercube {
+                temperature,
+                humidity,
+                continentalness,
+                erosion,
+                depth,
+                weirdness,
+                offset: 0,
+            },
+            biome,
+        ));
+    }
+
+    /// Adds a surface biome, both at the surface and one block layer below it
+    fn add_surface(
+        &mut self,
+        temperature: ParameterRange,
+        humidity: ParameterRange,
+        continentalness: ParameterRange,
+        erosion: ParameterRange,
+        weirdness: ParameterRange,
+        biome: Biome,
+    ) {
+        for depth in [0.0, 1.0] {
+            self.add_with_depth(
+                temperature,
+                humidity,
+                continentalness,
+                erosion,
+                range(depth, depth),
+                weirdness,
+                biome,
+            );
+        }
+    }
+
+    fn add_ocean_biomes(&mut self) {
+        self.add_surface(
+            DEFAULT,
+            DEFAULT,
+            MUSHROOM_FIELDS_CONTINENTALNESS,
+            DEFAULT,
+            DEFAULT,
+            Biome::MushroomFields,
+        );
+        for (t, temperature) in TEMPERATURES.into_iter().enumerate() {
+            self.add_surface(
+                temperature,
+                DEFAULT,
+                DEEP_OCEAN_CONTINENTALNESS,
+                DEFAULT,
+                DEFAULT,
+                OCEAN_BIOMES[0][t],
+            );
+            self.add_surface(
+                temperature,
+                DEFAULT,
+                OCEAN_CONTINENTALNESS,
+                DEFAULT,
+                DEFAULT,
+                OCEAN_BIOMES[1][t],
+            );
+        }
+    }
+
+    fn add_land_biomes(&mut self) {
+        self.add_mid_biomes(range(-1.0, -0.933_333_34));
+        self.add_high_biomes(range(-0.933_333_34, -0.766_666_7));
+        self.add_peak_biomes(range(-0.766_666_7, -0.566_666_66));
+        self.add_high_biomes(range(-0.566_666_66, -0.4));
+        self.add_mid_biomes(range(-0.4, -0.266_666_68));
+        self.add_low_biomes(range(-0.266_666_68, -0.05));
+        self.add_valley_biomes(range(-0.05, 0.05));
+        self.add_low_biomes(range(0.05, 0.266_666_68));
+        self.add_mid_biomes(range(0.266_666_68, 0.4));
+        self.add_high_biomes(range(0.4, 0.566_666_66));
+        self.add_peak_biomes(range(0.566_666_66, 0.766_666_7));
+        self.add_high_biomes(range(0.766_666_7, 0.933_333_34));
+        self.add_mid_biomes(range(0.933_333_34, 1.0));
+    }
+
+    fn add_peak_biomes(&mut self, weirdness: ParameterRange) {
+        for (t, temperature) in TEMPERATURES.into_iter().enumerate() {
+            for (h, humidity) in HUMIDITIES.into_iter().enumerate() {
+                let regular = regular_biome(t, h, weirdness);
+                let badlands_or_regular = badlands_or_regular_biome(t, h, weirdness);
+                let mountain_slope = mountain_slope_biome(t, h, weirdness);
+                let near_mountain = near_mountain_biome(t, h, weirdness);
+                let windswept = windswept_or_regular_biome(t, h, weirdness);
+                let windswept_savanna = windswept_savanna_or(t, h, weirdness, windswept);
+                let peak = peak_biome(t, h, weirdness);
+
+                let mut add = |continentalness, erosion, biome| {
+                    self.add_surface(
+                        temperature,
+                        humidity,
+                        continentalness,
+                        erosion,
+                        weirdness,
+                        biome,
+                    );
+                };
+                add(
+                    COAST_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[0],
+                    peak,
+                );
+                add(
+                    COAST_CONTINENTALNESS.combine(NEAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[1],
+                    near_mountain,
+                );
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[1],
+                    peak,
+                );
+                add(
+                    COAST_CONTINENTALNESS.combine(NEAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[2].combine(EROSIONS[3]),
+                    regular,
+                );
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[2],
+                    mountain_slope,
+                );
+                add(MID_INLAND_CONTINENTALNESS, EROSIONS[3], badlands_or_regular);
+                add(FAR_INLAND_CONTINENTALNESS, EROSIONS[3], badlands_or_regular);
+                add(
+                    COAST_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[4],
+                    regular,
+                );
+                add(
+                    COAST_CONTINENTALNESS.combine(NEAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[5],
+                    windswept_savanna,
+                );
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[5],
+                    windswept,
+                );
+                add(
+                    COAST_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[6],
+                    regular,
+                );
+            }
+        }
+    }
+
+    fn add_high_biomes(&mut self, weirdness: ParameterRange) {
+        for (t, temperature) in TEMPERATURES.into_iter().enumerate() {
+            for (h, humidity) in HUMIDITIES.into_iter().enumerate() {
+                let regular = regular_biome(t, h, weirdness);
+                let badlands_or_regular = badlands_or_regular_biome(t, h, weirdness);
+                let mountain_slope = mountain_slope_biome(t, h, weirdness);
+                let near_mountain = near_mountain_biome(t, h, weirdness);
+                let windswept = windswept_or_regular_biome(t, h, weirdness);
+                let windswept_savanna = windswept_savanna_or(t, h, weirdness, regular);
+                let peak = peak_biome(t, h, weirdness);
+
+                let mut add = |continentalness, erosion, biome| {
+                    self.add_surface(
+                        temperature,
+                        humidity,
+                        continentalness,
+                        erosion,
+                        weirdness,
+                        biome,
+                    );
+                };
+                add(
+                    COAST_CONTINENTALNESS,
+                    EROSIONS[0].combine(EROSIONS[1]),
+                    regular,
+                );
+                add(NEAR_INLAND_CONTINENTALNESS, EROSIONS[0], near_mountain);
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[0],
+                    peak,
+                );
+                add(NEAR_INLAND_CONTINENTALNESS, EROSIONS[1], regular);
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[1],
+                    mountain_slope,
+                );
+                add(
+                    COAST_CONTINENTALNESS.combine(NEAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[2].combine(EROSIONS[3]),
+                    regular,
+                );
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[2],
+                    near_mountain,
+                );
+                add(MID_INLAND_CONTINENTALNESS, EROSIONS[3], badlands_or_regular);
+                add(FAR_INLAND_CONTINENTALNESS, EROSIONS[3], badlands_or_regular);
+                add(
+                    COAST_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[4],
+                    regular,
+                );
+                add(
+                    COAST_CONTINENTALNESS.combine(NEAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[5],
+                    windswept_savanna,
+                );
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[5],
+                    windswept,
+                );
+                add(
+                    COAST_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[6],
+                    regular,
+                );
+            }
+        }
+    }
+
+    /// Stony shores and swamps, which mid and low biomes have in common
+    fn add_shores_and_swamps(&mut self, weirdness: ParameterRange) {
+        self.add_surface(
+            DEFAULT,
+            DEFAULT,
+            COAST_CONTINENTALNESS,
+            EROSIONS[0].combine(EROSIONS[2]),
+            weirdness,
+            Biome::StonyShore,
+        );
+        self.add_surface(
+            TEMPERATURES[1].combine(TEMPERATURES[2]),
+            DEFAULT,
+            NEAR_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+            EROSIONS[6],
+            weirdness,
+            Biome::Swamp,
+        );
+        self.add_surface(
+            TEMPERATURES[3].combine(TEMPERATURES[4]),
+            DEFAULT,
+            NEAR_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+            EROSIONS[6],
+            weirdness,
+            Biome::MangroveSwamp,
+        );
+    }
+
+    fn add_mid_biomes(&mut self, weirdness: ParameterRange) {
+        self.add_shores_and_swamps(weirdness);
+        for (t, temperature) in TEMPERATURES.into_iter().enumerate() {
+            for (h, humidity) in HUMIDITIES.into_iter().enumerate() {
+                let regular = regular_biome(t, h, weirdness);
+                let badlands_or_regular = badlands_or_regular_biome(t, h, weirdness);
+                let mountain_slope = mountain_slope_biome(t, h, weirdness);
+                let windswept = windswept_or_regular_biome(t, h, weirdness);
+                let near_mountain = near_mountain_biome(t, h, weirdness);
+                let shore = shore_biome(t);
+                let windswept_savanna = windswept_savanna_or(t, h, weirdness, regular);
+                let eroded_shore = eroded_shore_biome(t, h, weirdness);
+
+                let mut add = |continentalness, erosion, biome| {
+                    self.add_surface(
+                        temperature,
+                        humidity,
+                        continentalness,
+                        erosion,
+                        weirdness,
+                        biome,
+                    );
+                };
+                add(
+                    NEAR_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[0],
+                    mountain_slope,
+                );
+                add(
+                    NEAR_INLAND_CONTINENTALNESS.combine(MID_INLAND_CONTINENTALNESS),
+                    EROSIONS[1],
+                    near_mountain,
+                );
+                add(
+                    FAR_INLAND_CONTINENTALNESS,
+                    EROSIONS[1],
+                    if t == 0 {
+                        mountain_slope
+                    } else {
+                        near_mountain
+                    },
+                );
+                add(NEAR_INLAND_CONTINENTALNESS, EROSIONS[2], regular);
+                add(MID_INLAND_CONTINENTALNESS, EROSIONS[2], badlands_or_regular);
+                add(FAR_INLAND_CONTINENTALNESS, EROSIONS[2], near_mountain);
+                add(
+                    COAST_CONTINENTALNESS.combine(NEAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[3],
+                    regular,
+                );
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[3],
+                    badlands_or_regular,
+                );
+                if weirdness.max < 0 {
+                    add(COAST_CONTINENTALNESS, EROSIONS[4], shore);
+                    add(
+                        NEAR_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                        EROSIONS[4],
+                        regular,
+                    );
+                } else {
+                    add(
+                        COAST_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                        EROSIONS[4],
+                        regular,
+                    );
+                }
+                add(COAST_CONTINENTALNESS, EROSIONS[5], eroded_shore);
+                add(NEAR_INLAND_CONTINENTALNESS, EROSIONS[5], windswept_savanna);
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[5],
+                    windswept,
+                );
+                add(
+                    COAST_CONTINENTALNESS,
+                    EROSIONS[6],
+                    if weirdness.max < 0 { shore } else { regular },
+                );
+                if t == 0 {
+                    add(
+                        NEAR_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                        EROSIONS[6],
+                        regular,
+                    );
+                }
+            }
+        }
+    }
+
+    fn add_low_biomes(&mut self, weirdness: ParameterRange) {
+        self.add_shores_and_swamps(weirdness);
+        for (t, temperature) in TEMPERATURES.into_iter().enumerate() {
+            for (h, humidity) in HUMIDITIES.into_iter().enumerate() {
+                let regular = regular_biome(t, h, weirdness);
+                let badlands_or_regular = badlands_or_regular_biome(t, h, weirdness);
+                let near_mountain = near_mountain_biome(t, h, weirdness);
+                let shore = shore_biome(t);
+                let windswept_savanna = windswept_savanna_or(t, h, weirdness, regular);
+                let eroded_shore = eroded_shore_biome(t, h, weirdness);
+
+                let mut add = |continentalness, erosion, biome| {
+                    self.add_surface(
+                        temperature,
+                        humidity,
+                        continentalness,
+                        erosion,
+                        weirdness,
+                        biome,
+                    );
+                };
+                add(
+                    NEAR_INLAND_CONTINENTALNESS,
+                    EROSIONS[0].combine(EROSIONS[1]),
+                    badlands_or_regular,
+                );
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[0].combine(EROSIONS[1]),
+                    near_mountain,
+                );
+                add(
+                    NEAR_INLAND_CONTINENTALNESS,
+                    EROSIONS[2].combine(EROSIONS[3]),
+                    regular,
+                );
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[2].combine(EROSIONS[3]),
+                    badlands_or_regular,
+                );
+                add(
+                    COAST_CONTINENTALNESS,
+                    EROSIONS[3].combine(EROSIONS[4]),
+                    shore,
+                );
+                add(
+                    NEAR_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[4],
+                    regular,
+                );
+                add(COAST_CONTINENTALNESS, EROSIONS[5], eroded_shore);
+                add(NEAR_INLAND_CONTINENTALNESS, EROSIONS[5], windswept_savanna);
+                add(
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[5],
+                    regular,
+                );
+                add(COAST_CONTINENTALNESS, EROSIONS[6], shore);
+                if t == 0 {
+                    add(
+                        NEAR_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                        EROSIONS[6],
+                        regular,
+                    );
+                }
+            }
+        }
+    }
+
+    fn add_valley_biomes(&mut self, weirdness: ParameterRange) {
+        let (frozen_coast, unfrozen_coast) = if weirdness.max < 0 {
+            (Biome::StonyShore, Biome::StonyShore)
+        } else {
+            (Biome::FrozenRiver, Biome::River)
+        };
+        let mut add = |temperature, continentalness, erosion, biome| {
+            self.add_surface(
+                temperature,
+                DEFAULT,
+                continentalness,
+                erosion,
+                weirdness,
+                biome,
+            );
+        };
+        add(
+            FROZEN_TEMPERATURE,
+            COAST_CONTINENTALNESS,
+            EROSIONS[0].combine(EROSIONS[1]),
+            frozen_coast,
+        );
+        add(
+            UNFROZEN_TEMPERATURE,
+            COAST_CONTINENTALNESS,
+            EROSIONS[0].combine(EROSIONS[1]),
+            unfrozen_coast,
+        );
+        add(
+            FROZEN_TEMPERATURE,
+            NEAR_INLAND_CONTINENTALNESS,
+            EROSIONS[0].combine(EROSIONS[1]),
+            Biome::FrozenRiver,
+        );
+        add(
+            UNFROZEN_TEMPERATURE,
+            NEAR_INLAND_CONTINENTALNESS,
+            EROSIONS[0].combine(EROSIONS[1]),
+            Biome::River,
+        );
+        add(
+            FROZEN_TEMPERATURE,
+            COAST_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+            EROSIONS[2].combine(EROSIONS[5]),
+            Biome::FrozenRiver,
+        );
+        add(
+            UNFROZEN_TEMPERATURE,
+            COAST_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+            EROSIONS[2].combine(EROSIONS[5]),
+            Biome::River,
+        );
+        add(
+            FROZEN_TEMPERATURE,
+            COAST_CONTINENTALNESS,
+            EROSIONS[6],
+            Biome::FrozenRiver,
+        );
+        add(
+            UNFROZEN_TEMPERATURE,
+            COAST_CONTINENTALNESS,
+            EROSIONS[6],
+            Biome::River,
+        );
+        add(
+            TEMPERATURES[1].combine(TEMPERATURES[2]),
+            RIVER_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+            EROSIONS[6],
+            Biome::Swamp,
+        );
+        add(
+            TEMPERATURES[3].combine(TEMPERATURES[4]),
+            RIVER_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+            EROSIONS[6],
+            Biome::MangroveSwamp,
+        );
+        add(
+            FROZEN_TEMPERATURE,
+            RIVER_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+            EROSIONS[6],
+            Biome::FrozenRiver,
+        );
+
+        for (t, temperature) in TEMPERATURES.into_iter().enumerate() {
+            for (h, humidity) in HUMIDITIES.into_iter().enumerate() {
+                self.add_surface(
+                    temperature,
+                    humidity,
+                    MID_INLAND_CONTINENTALNESS.combine(FAR_INLAND_CONTINENTALNESS),
+                    EROSIONS[0].combine(EROSIONS[1]),
+                    weirdness,
+                    badlands_or_regular_biome(t, h, weirdness),
+                );
+            }
+        }
+    }
+
+    fn add_cave_biomes(&mut self) {
+        let caves = range(0.2, 0.9);
+        self.add_with_depth(
+            DEFAULT,
+            DEFAULT,
+            range(0.8, 1.0),
+            DEFAULT,
+            caves,
+            DEFAULT,
+            Biome::DripstoneCaves,
+        );
+        self.add_with_depth(
+            DEFAULT,
+            range(0.7, 1.0),
+            DEFAULT,
+            DEFAULT,
+            caves,
+            DEFAULT,
+            Biome::LushCaves,
+        );
+        self.add_with_depth(
+            DEFAULT,
+            DEFAULT,
+            DEFAULT,
+            EROSIONS[0].combine(EROSIONS[1]),
+            range(1.1, 1.1),
+            DEFAULT,
+            Biome::DeepDark,
+        );
+    }
+}
+
+fn regular_biome(t: usize, h: usize, weirdness: ParameterRange) -> Biome {
+    if weirdness.max < 0 {
+        return COMMON_BIOMES[t][h];
+    }
+    UNCOMMON_BIOMES[t][h].unwrap_or(COMMON_BIOMES[t][h])
+}
+
+fn badlands_or_regular_biome(t: usize, h: usize, weirdness: ParameterRange) -> Biome {
+    if t == 4 {
+        badlands_biome(h, weirdness)
+    } else {
+        regular_biome(t, h, weirdness)
+    }
+}
+
+fn windswept_savanna_or(t: usize, h: usize, weirdness: ParameterRange, biome: Biome) -> Biome {
+    if t > 1 && h < 4 && weirdness.max >= 0 {
+        Biome::WindsweptSavanna
+    } else {
+        biome
+    }
+}
+
+fn eroded_shore_biome(t: usize, h: usize, weirdness: ParameterRange) -> Biome {
+    let biome = if weirdness.max >= 0 {
+        regular_biome(t, h, weirdness)
+    } else {
+        shore_biome(t)
+    };
+    windswept_savanna_or(t, h, weirdness, biome)
+}
+
+fn shore_biome(t: usize) -> Biome {
+    match t {
+        0 => Biome::SnowyBeach,
+        4 => Biome::Desert,
+        _ => Biome::Beach,
+    }
+}
+
+fn badlands_biome(h: usize, weirdness: ParameterRange) -> Biome {
+    match h {
+        0 | 1 if weirdness.max < 0 => Biome::Badlands,
+        0 | 1 => Biome::ErodedBadlands,
+        2 => Biome::Badlands,
+        _ => Biome::WoodedBadlands,
+    }
+}
+
+fn near_mountain_biome(t: usize, h: usize, weirdness: ParameterRange) -> Biome {
+    if weirdness.max >= 0 {
+        if let Some(biome) = SPECIAL_NEAR_MOUNTAIN_BIOMES[t][h] {
+            return biome;
+        }
+    }
+    NEAR_MOUNTAIN_BIOMES[t][h]
+}
+
+fn peak_biome(t: usize, h: usize, weirdness: ParameterRange) -> Biome {
+    match t {
+        0..=2 if weirdness.max < 0 => Biome::JaggedPeaks,
+        0..=2 => Biome::FrozenPeaks,
+        3 => Biome::StonyPeaks,
+        _ => badlands_biome(h, weirdness),
+    }
+}
+
+fn mountain_slope_biome(t: usize, h: usize, weirdness: ParameterRange) -> Biome {
+    if t >= 3 {
+        near_mountain_biome(t, h, weirdness)
+    } else if h <= 1 {
+        Biome::SnowySlopes
+    } else {
+        Biome::Grove
+    }
+}
+
+fn windswept_or_regular_biome(t: usize, h: usize, weirdness: ParameterRange) -> Biome {
+    WINDSWEPT_BIOMES[t][h].unwrap_or_else(|| regular_biome(t, h, weirdness))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pumpkin_data::chunk::Biome;
+    use serde::Deserialize;
+
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{overworld_biome_parameters, OVERWORLD_BIOME_SEARCH_TREE};
+    use crate::biome::multi_noise::{NoiseValuePoint, ParameterRange};
+    use crate::generation::{get_world_gen, Seed, DEFAULT_GENERATOR};
+    use crate::global_path;
+
+    #[derive(Deserialize)]
+    struct MultiNoiseEntry {
+        temperature: [f32; 2],
+        humidity: [f32; 2],
+        continentalness: [f32; 2],
+        erosion: [f32; 2],
+        depth: [f32; 2],
+        weirdness: [f32; 2],
+    }
+
+    #[test]
+    fn parameters_match_vanilla() {
+        let parameters = overworld_biome_parameters();
+        assert_eq!(parameters.len(), 7593);
+
+        // The extracted parameters only contain the last climate of each biome
+        let extracted: HashMap<String, HashMap<String, MultiNoiseEntry>> =
+            serde_json::from_str(include_str!("../../../assets/multi_noise.json")).unwrap();
+        let extracted = &extracted["overworld"];
+        let mut last = HashMap::new();
+        for (hypercube, biome) in &parameters {
+            last.insert(format!("minecraft:{}", biome.to_name()), hypercube);
+        }
+        assert_eq!(last.len(), extracted.len());
+
+        let matches = |range: &ParameterRange, [min, max]: [f32; 2]| {
+            (range.min as f32 / 10000.0 - min).abs() < 2e-4
+                && (range.max as f32 / 10000.0 - max).abs() < 2e-4
+        };
+        for (biome, entry) in extracted {
+            let hypercube = last[biome];
+            assert!(
+                matches(&hypercube.temperature, entry.temperature)
+                    && matches(&hypercube.humidity, entry.humidity)
+                    && matches(&hypercube.continentalness, entry.continentalness)
+                    && matches(&hypercube.erosion, entry.erosion)
+                    && matches(&hypercube.depth, entry.depth)
+                    && matches(&hypercube.weirdness, entry.weirdness),
+                "{biome} does not match"
+            );
+        }
+    }
+
+    #[test]
+    fn climates_resolve_to_vanilla_biomes() {
+        // Temperature, humidity, continentalness, erosion, depth, weirdness
+        let cases = [
+            ([0.0, 0.0, -1.1, 0.0, 0.0, 0.0], Biome::MushroomFields),
+            ([-0.6, 0.0, -0.8, 0.0, 0.0, 0.0], Biome::DeepFrozenOcean),
+            ([0.7, 0.0, -0.3, 0.0, 0.0, 0.0], Biome::WarmOcean),
+            ([0.0, 0.0, 0.5, 0.6, 0.0, 0.0], Biome::Swamp),
+            ([0.0, 0.0, 0.0, 0.5, 0.0, 0.0], Biome::River),
+            ([-0.2, -0.2, 0.2, 0.2, 0.0, -0.2], Biome::Plains),
+            // Hot and far inland with erosion index 3 is a plateau, which is badlands when hot
+            ([0.6, 0.0, 0.5, 0.0, 0.0, -0.5], Biome::Badlands),
+            ([0.0, 0.0, 0.5, -0.9, 0.0, -0.7], Biome::JaggedPeaks),
+            ([0.0, 0.0, 0.5, -0.9, 0.0, 0.7], Biome::FrozenPeaks),
+            ([0.0, 0.0, 0.5, -0.9, 1.1, 0.0], Biome::DeepDark),
+            ([0.0, 0.0, 0.9, 0.0, 0.5, 0.0], Biome::DripstoneCaves),
+            ([0.0, 0.8, 0.0, 0.0, 0.5, 0.0], Biome::LushCaves),
+        ];
+        for ([t, h, c, e, d, w], expected) in cases {
+            let point = NoiseValuePoint::new(t, h, c, e, d, w);
+            assert_eq!(
+                *OVERWORLD_BIOME_SEARCH_TREE.get(&point, &mut None),
+                expected,
+                "{point:?}"
+            );
+        }
+    }
+
+    /// A biome vanilla generated, like `/locate` or the debug screen show it
+    #[derive(Deserialize)]
+    struct VanillaBiome {
+        seed: i64,
+        x: i32,
+        y: i32,
+        z: i32,
+        biome: String,
+    }
+
+    #[test]
+    #[ignore = "needs biomes sampled in vanilla in assets/vanilla_biomes.json"]
+    fn biomes_match_vanilla() {
+        let expected: Vec<VanillaBiome> = serde_json::from_str(
+            &std::fs::read_to_string(global_path!("../../assets/vanilla_biomes.json")).unwrap(),
+        )
+        .unwrap();
+        assert!(!expected.is_empty());
+        for entry in expected {
+            let generator = get_world_gen(DEFAULT_GENERATOR, Seed(entry.seed as u64)).unwrap();
+            let biome = generator.get_biome(Vector3::new(entry.x, entry.y, entry.z));
+            assert_eq!(
+                format!("minecraft:{}", biome.to_name()),
+                entry.biome,
+                "seed {} at {} {} {}",
+                entry.seed,
+                entry.x,
+                entry.y,
+                entry.z
+            );
+        }
+    }
+}
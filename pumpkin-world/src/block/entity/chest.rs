@@ -0,0 +1,100 @@
+use std::any::Any;
+
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::position::BlockPos;
+
+use crate::{item::ItemStack, loot::chest::get_chest_loot_table};
+
+use super::{read_items, write_items, BlockEntity};
+
+pub const CHEST_SIZE: usize = 27;
+
+/// A chest or trapped chest.
+pub struct ChestBlockEntity {
+    id: &'static str,
+    position: BlockPos,
+    pub items: [Option<ItemStack>; CHEST_SIZE],
+    /// The name shown when the chest is opened, as a JSON text component
+    pub custom_name: Option<String>,
+    /// The loot table the chest is filled from when it is first opened, e.g.
+    /// `minecraft:chests/simple_dungeon`
+    pub loot_table: Option<String>,
+    pub loot_table_seed: i64,
+}
+
+impl ChestBlockEntity {
+    #[must_use]
+    pub fn new(id: &'static str, position: BlockPos) -> Self {
+        Self {
+            id,
+            position,
+            items: Default::default(),
+            custom_name: None,
+            loot_table: None,
+            loot_table_seed: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn from_nbt(id: &'static str, position: BlockPos, nbt: &NbtCompound) -> Self {
+        let mut chest = Self::new(id, position);
+        read_items(nbt, &mut chest.items);
+        chest.custom_name = nbt.get_string("CustomName").cloned();
+        chest.loot_table = nbt.get_string("LootTable").cloned();
+        chest.loot_table_seed = nbt.get_long("LootTableSeed").unwrap_or_default();
+        chest
+    }
+}
+
+impl BlockEntity for ChestBlockEntity {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        if let Some(custom_name) = &self.custom_name {
+            nbt.put("CustomName", custom_name.clone());
+        }
+        // An unopened chest has no items yet
+        if let Some(loot_table) = &self.loot_table {
+            nbt.put("LootTable", loot_table.clone());
+            if self.loot_table_seed != 0 {
+                nbt.put_long("LootTableSeed", self.loot_table_seed);
+            }
+            return;
+        }
+        write_items(nbt, &self.items);
+    }
+
+    fn unpack_loot(&mut self) -> bool {
+        let Some(loot_table) = self.loot_table.take() else {
+            return false;
+        };
+        // Unknown loot tables leave the chest empty, like in vanilla
+        if let Some(loot_table) = get_chest_loot_table(&loot_table) {
+            loot_table.fill(self.loot_table_seed, &mut self.items);
+        }
+        self.loot_table_seed = 0;
+        true
+    }
+
+    fn slots(&self) -> Option<&[Option<ItemStack>]> {
+        Some(&self.items)
+    }
+
+    fn slots_mut(&mut self) -> Option<&mut [Option<ItemStack>]> {
+        Some(&mut self.items)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
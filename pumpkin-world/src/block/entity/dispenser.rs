@@ -0,0 +1,98 @@
+use std::any::Any;
+
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::position::BlockPos;
+
+use crate::{item::ItemStack, loot::chest::get_chest_loot_table};
+
+use super::{read_items, write_items, BlockEntity};
+
+pub const DISPENSER_SIZE: usize = 9;
+
+/// A dispenser or dropper.
+pub struct DispenserBlockEntity {
+    id: &'static str,
+    position: BlockPos,
+    pub items: [Option<ItemStack>; DISPENSER_SIZE],
+    /// The name shown when the dispenser is opened, as a JSON text component
+    pub custom_name: Option<String>,
+    /// The loot table the dispenser is filled from when it is first used, e.g.
+    /// `minecraft:chests/jungle_temple_dispenser`
+    pub loot_table: Option<String>,
+    pub loot_table_seed: i64,
+}
+
+impl DispenserBlockEntity {
+    #[must_use]
+    pub fn new(id: &'static str, position: BlockPos) -> Self {
+        Self {
+            id,
+            position,
+            items: Default::default(),
+            custom_name: None,
+            loot_table: None,
+            loot_table_seed: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn from_nbt(id: &'static str, position: BlockPos, nbt: &NbtCompound) -> Self {
+        let mut dispenser = Self::new(id, position);
+        read_items(nbt, &mut dispenser.items);
+        dispenser.custom_name = nbt.get_string("CustomName").cloned();
+        dispenser.loot_table = nbt.get_string("LootTable").cloned();
+        dispenser.loot_table_seed = nbt.get_long("LootTableSeed").unwrap_or_default();
+        dispenser
+    }
+}
+
+impl BlockEntity for DispenserBlockEntity {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        if let Some(custom_name) = &self.custom_name {
+            nbt.put("CustomName", custom_name.clone());
+        }
+        if let Some(loot_table) = &self.loot_table {
+            nbt.put("LootTable", loot_table.clone());
+            if self.loot_table_seed != 0 {
+                nbt.put_long("LootTableSeed", self.loot_table_seed);
+            }
+            return;
+        }
+        write_items(nbt, &self.items);
+    }
+
+    fn unpack_loot(&mut self) -> bool {
+        let Some(loot_table) = self.loot_table.take() else {
+            return false;
+        };
+        if let Some(loot_table) = get_chest_loot_table(&loot_table) {
+            loot_table.fill(self.loot_table_seed, &mut self.items);
+        }
+        self.loot_table_seed = 0;
+        true
+    }
+
+    fn slots(&self) -> Option<&[Option<ItemStack>]> {
+        Some(&self.items)
+    }
+
+    fn slots_mut(&mut self) -> Option<&mut [Option<ItemStack>]> {
+        Some(&mut self.items)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
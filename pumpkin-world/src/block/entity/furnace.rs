@@ -0,0 +1,80 @@
+use std::any::Any;
+
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::position::BlockPos;
+
+use crate::item::ItemStack;
+
+use super::{read_items, write_items, BlockEntity};
+
+/// The slot of the item which is smelted
+pub const INPUT_SLOT: usize = 0;
+pub const FUEL_SLOT: usize = 1;
+/// The slot of the smelted item
+pub const RESULT_SLOT: usize = 2;
+
+/// A furnace, blast furnace or smoker. Only its items are used by the server, as furnaces do not
+/// smelt yet, the rest of its data is kept as it was loaded.
+pub struct FurnaceBlockEntity {
+    id: &'static str,
+    position: BlockPos,
+    pub items: [Option<ItemStack>; 3],
+    data: NbtCompound,
+}
+
+impl FurnaceBlockEntity {
+    #[must_use]
+    pub fn new(id: &'static str, position: BlockPos) -> Self {
+        Self {
+            id,
+            position,
+            items: Default::default(),
+            data: NbtCompound::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn from_nbt(id: &'static str, position: BlockPos, nbt: &NbtCompound) -> Self {
+        let mut furnace = Self::new(id, position);
+        read_items(nbt, &mut furnace.items);
+        furnace.data = nbt.clone();
+        furnace.data.child_tags.retain(|(key, _)| {
+            !matches!(
+                key.as_str(),
+                "id" | "x" | "y" | "z" | "keepPacked" | "Items"
+            )
+        });
+        furnace
+    }
+}
+
+impl BlockEntity for FurnaceBlockEntity {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.extend(self.data.child_tags.iter().cloned());
+        write_items(nbt, &self.items);
+    }
+
+    fn slots(&self) -> Option<&[Option<ItemStack>]> {
+        Some(&self.items)
+    }
+
+    fn slots_mut(&mut self) -> Option<&mut [Option<ItemStack>]> {
+        Some(&mut self.items)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,88 @@
+use std::any::Any;
+
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::position::BlockPos;
+
+use crate::item::ItemStack;
+
+use super::{read_items, write_items, BlockEntity};
+
+pub const HOPPER_SIZE: usize = 5;
+
+/// A hopper, which moves items between containers. After moving an item it waits for its
+/// cooldown, which the server sets.
+pub struct HopperBlockEntity {
+    position: BlockPos,
+    pub items: [Option<ItemStack>; HOPPER_SIZE],
+    /// The name shown when the hopper is opened, as a JSON text component
+    pub custom_name: Option<String>,
+    /// How many ticks are left until the hopper moves items again
+    pub transfer_cooldown: i32,
+}
+
+impl HopperBlockEntity {
+    #[must_use]
+    pub fn new(position: BlockPos) -> Self {
+        Self {
+            position,
+            items: Default::default(),
+            custom_name: None,
+            transfer_cooldown: -1,
+        }
+    }
+
+    #[must_use]
+    pub fn from_nbt(position: BlockPos, nbt: &NbtCompound) -> Self {
+        let mut hopper = Self::new(position);
+        read_items(nbt, &mut hopper.items);
+        hopper.custom_name = nbt.get_string("CustomName").cloned();
+        hopper.transfer_cooldown = nbt.get_int("TransferCooldown").unwrap_or(-1);
+        hopper
+    }
+}
+
+impl BlockEntity for HopperBlockEntity {
+    fn id(&self) -> &str {
+        "hopper"
+    }
+
+    fn position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        if let Some(custom_name) = &self.custom_name {
+            nbt.put("CustomName", custom_name.clone());
+        }
+        nbt.put_int("TransferCooldown", self.transfer_cooldown);
+        write_items(nbt, &self.items);
+    }
+
+    fn tick(&mut self) -> bool {
+        if self.transfer_cooldown <= 0 {
+            return false;
+        }
+        self.transfer_cooldown -= 1;
+        true
+    }
+
+    fn needs_block_tick(&self) -> bool {
+        self.transfer_cooldown <= 0
+    }
+
+    fn slots(&self) -> Option<&[Option<ItemStack>]> {
+        Some(&self.items)
+    }
+
+    fn slots_mut(&mut self) -> Option<&mut [Option<ItemStack>]> {
+        Some(&mut self.items)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
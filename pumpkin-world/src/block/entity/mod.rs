@@ -0,0 +1,402 @@
+//! Block entities hold the data of a block which does not fit into its block state, like the
+//! items of a chest or the text of a sign. They are stored with the chunk they are in.
+
+use std::{any::Any, collections::HashMap, sync::LazyLock};
+
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+use crate::item::ItemStack;
+
+use super::registry::{get_state_by_state_id, BLOCKS};
+
+pub mod chest;
+pub mod dispenser;
+pub mod furnace;
+pub mod hopper;
+pub mod piston;
+pub mod sign;
+pub mod spawner;
+
+pub use chest::ChestBlockEntity;
+pub use dispenser::DispenserBlockEntity;
+pub use furnace::FurnaceBlockEntity;
+pub use hopper::HopperBlockEntity;
+pub use piston::PistonBlockEntity;
+pub use sign::SignBlockEntity;
+pub use spawner::SpawnerBlockEntity;
+
+pub trait BlockEntity: Send + Sync {
+    /// The block entity type without namespace, e.g. `chest`.
+    fn id(&self) -> &str;
+
+    fn position(&self) -> BlockPos;
+
+    /// Writes the data of the block entity. The id and position are added by
+    /// [`write_block_entity`].
+    fn write_nbt(&self, nbt: &mut NbtCompound);
+
+    /// The data clients need to render the block entity, e.g. the text of a sign.
+    fn client_nbt(&self) -> Option<NbtCompound> {
+        None
+    }
+
+    /// Called every tick while the chunk is loaded. Returns whether the block entity changed, so
+    /// the chunk has to be saved.
+    fn tick(&mut self) -> bool {
+        false
+    }
+
+    /// Whether the block of the block entity has something to do this tick, like a hopper whose
+    /// cooldown is over. The server then runs the behavior of the block, which can reach the
+    /// world, unlike [`BlockEntity::tick`].
+    fn needs_block_tick(&self) -> bool {
+        false
+    }
+
+    /// The slots of a container, like a chest.
+    fn slots(&self) -> Option<&[Option<ItemStack>]> {
+        None
+    }
+
+    fn slots_mut(&mut self) -> Option<&mut [Option<ItemStack>]> {
+        None
+    }
+
+    /// Fills a container placed by world generation from its loot table. This happens the first
+    /// time it is opened or broken. Returns whether the container was filled.
+    fn unpack_loot(&mut self) -> bool {
+        false
+    }
+
+    /// Takes the items which are dropped when the block is broken.
+    fn take_drops(&mut self) -> Vec<ItemStack> {
+        self.unpack_loot();
+        self.slots_mut()
+            .map(|slots| slots.iter_mut().filter_map(Option::take).collect())
+            .unwrap_or_default()
+    }
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// Creates and loads the block entities of one type.
+pub struct BlockEntityType {
+    pub create: fn(&'static str, BlockPos) -> Box<dyn BlockEntity>,
+    pub load: fn(&'static str, BlockPos, &NbtCompound) -> Box<dyn BlockEntity>,
+}
+
+/// The block entity types the server knows, by id. Other types are kept as
+/// [`GenericBlockEntity`], so their data is not lost.
+static BLOCK_ENTITY_TYPES: LazyLock<HashMap<&'static str, BlockEntityType>> = LazyLock::new(|| {
+    let mut types = HashMap::new();
+    let chest = || BlockEntityType {
+        create: |id, position| Box::new(ChestBlockEntity::new(id, position)),
+        load: |id, position, nbt| Box::new(ChestBlockEntity::from_nbt(id, position, nbt)),
+    };
+    types.insert("chest", chest());
+    types.insert("trapped_chest", chest());
+    let furnace = || BlockEntityType {
+        create: |id, position| Box::new(FurnaceBlockEntity::new(id, position)),
+        load: |id, position, nbt| Box::new(FurnaceBlockEntity::from_nbt(id, position, nbt)),
+    };
+    types.insert("furnace", furnace());
+    types.insert("blast_furnace", furnace());
+    types.insert("smoker", furnace());
+    let dispenser = || BlockEntityType {
+        create: |id, position| Box::new(DispenserBlockEntity::new(id, position)),
+        load: |id, position, nbt| Box::new(DispenserBlockEntity::from_nbt(id, position, nbt)),
+    };
+    types.insert("dispenser", dispenser());
+    types.insert("dropper", dispenser());
+    types.insert(
+        "hopper",
+        BlockEntityType {
+            create: |_, position| Box::new(HopperBlockEntity::new(position)),
+            load: |_, position, nbt| Box::new(HopperBlockEntity::from_nbt(position, nbt)),
+        },
+    );
+    let sign = || BlockEntityType {
+        create: |id, position| Box::new(SignBlockEntity::new(id, position)),
+        load: |id, position, nbt| Box::new(SignBlockEntity::from_nbt(id, position, nbt)),
+    };
+    types.insert("sign", sign());
+    types.insert("hanging_sign", sign());
+    types.insert(
+        "piston",
+        BlockEntityType {
+            create: |_, position| Box::new(PistonBlockEntity::new(position)),
+            load: |_, position, nbt| Box::new(PistonBlockEntity::from_nbt(position, nbt)),
+        },
+    );
+    types.insert(
+        "mob_spawner",
+        BlockEntityType {
+            create: |_, position| Box::new(SpawnerBlockEntity::new(position)),
+            load: |_, position, nbt| Box::new(SpawnerBlockEntity::from_nbt(position, nbt)),
+        },
+    );
+    types
+});
+
+/// Gets the network id of a block entity type, e.g. `chest`.
+#[must_use]
+pub fn block_entity_type_id(id: &str) -> Option<u32> {
+    BLOCKS
+        .block_entity_types
+        .iter()
+        .position(|block_entity_type| block_entity_type == id)
+        .map(|index| index as u32)
+}
+
+fn block_entity_type_name(id: &str) -> Option<&'static str> {
+    BLOCKS
+        .block_entity_types
+        .iter()
+        .find(|block_entity_type| *block_entity_type == id)
+        .map(String::as_str)
+}
+
+/// Creates the block entity of a block state, if it has one.
+#[must_use]
+pub fn create_block_entity(state_id: u16, position: BlockPos) -> Option<Box<dyn BlockEntity>> {
+    let type_index = get_state_by_state_id(state_id)?.block_entity_type?;
+    let id = BLOCKS.block_entity_types.get(type_index as usize)?.as_str();
+    Some(match BLOCK_ENTITY_TYPES.get(id) {
+        Some(block_entity_type) => (block_entity_type.create)(id, position),
+        None => Box::new(GenericBlockEntity::new(id, position, NbtCompound::new())),
+    })
+}
+
+/// Reads a block entity in the format of the chunk NBT, e.g.
+/// `{id: "minecraft:chest", x: 1, y: 2, z: 3, Items: [...]}`.
+#[must_use]
+pub fn read_block_entity(nbt: &NbtCompound) -> Option<Box<dyn BlockEntity>> {
+    let id = nbt.get_string("id")?;
+    let id = block_entity_type_name(id.strip_prefix("minecraft:").unwrap_or(id))?;
+    let position = BlockPos(Vector3::new(
+        nbt.get_int("x")?,
+        nbt.get_int("y")?,
+        nbt.get_int("z")?,
+    ));
+    Some(match BLOCK_ENTITY_TYPES.get(id) {
+        Some(block_entity_type) => (block_entity_type.load)(id, position, nbt),
+        None => Box::new(GenericBlockEntity::new(id, position, nbt.clone())),
+    })
+}
+
+/// Writes a block entity in the format of the chunk NBT.
+#[must_use]
+pub fn write_block_entity(block_entity: &dyn BlockEntity) -> NbtCompound {
+    let position = block_entity.position();
+    let mut nbt = NbtCompound::new();
+    nbt.put("id", format!("minecraft:{}", block_entity.id()));
+    nbt.put_int("x", position.0.x);
+    nbt.put_int("y", position.0.y);
+    nbt.put_int("z", position.0.z);
+    nbt.put_bool("keepPacked", false);
+    block_entity.write_nbt(&mut nbt);
+    nbt
+}
+
+/// Reads the `Items` of a container into its slots. Each item is stored with its slot.
+pub(crate) fn read_items(nbt: &NbtCompound, slots: &mut [Option<ItemStack>]) {
+    for item in nbt.get_list("Items").unwrap_or_default() {
+        let Some(item) = item.extract_compound() else {
+            continue;
+        };
+        let Some(slot) = item.get_byte("Slot") else {
+            continue;
+        };
+        if let Some(stack) = slots.get_mut(slot as usize) {
+            *stack = ItemStack::read_nbt(item);
+        }
+    }
+}
+
+/// Writes the slots of a container as its `Items`, leaving out the empty ones.
+pub(crate) fn write_items(nbt: &mut NbtCompound, slots: &[Option<ItemStack>]) {
+    let items: Vec<NbtTag> = slots
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, stack)| {
+            let mut item = stack.as_ref()?.write_nbt();
+            item.put_byte("Slot", slot as i8);
+            Some(NbtTag::Compound(item))
+        })
+        .collect();
+    nbt.put("Items", NbtTag::List(items.into_boxed_slice()));
+}
+
+/// A block entity the server does not implement yet, e.g. a brewing stand. Its data is kept as
+/// it was loaded.
+pub struct GenericBlockEntity {
+    id: &'static str,
+    position: BlockPos,
+    data: NbtCompound,
+}
+
+impl GenericBlockEntity {
+    #[must_use]
+    pub fn new(id: &'static str, position: BlockPos, mut data: NbtCompound) -> Self {
+        data.child_tags
+            .retain(|(key, _)| !matches!(key.as_str(), "id" | "x" | "y" | "z" | "keepPacked"));
+        Self { id, position, data }
+    }
+}
+
+impl BlockEntity for GenericBlockEntity {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.extend(self.data.child_tags.iter().cloned());
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_nbt::compound::NbtCompound;
+
+    use crate::block::{
+        registry::{get_block, get_state_id_with_property},
+        BlockDirection,
+    };
+
+    use super::{
+        read_block_entity, write_block_entity, ChestBlockEntity, HopperBlockEntity,
+        PistonBlockEntity, SignBlockEntity,
+    };
+
+    fn block_entity_nbt(id: &str) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.put("id", id);
+        nbt.put_int("x", 1);
+        nbt.put_int("y", -2);
+        nbt.put_int("z", 3);
+        nbt
+    }
+
+    #[test]
+    fn unknown_data_is_kept() {
+        let mut furnace = block_entity_nbt("minecraft:furnace");
+        furnace.put_short("BurnTime", 200);
+
+        let block_entity = read_block_entity(&furnace).unwrap();
+        assert_eq!(block_entity.id(), "furnace");
+        let written = write_block_entity(block_entity.as_ref());
+        assert_eq!(
+            written.get_string("id").map(String::as_str),
+            Some("minecraft:furnace")
+        );
+        assert_eq!(written.get_int("y"), Some(-2));
+        assert_eq!(written.get_short("BurnTime"), Some(200));
+    }
+
+    #[test]
+    fn sign_text() {
+        let mut block_entity = read_block_entity(&block_entity_nbt("minecraft:sign")).unwrap();
+        let sign = block_entity
+            .as_any_mut()
+            .downcast_mut::<SignBlockEntity>()
+            .unwrap();
+        sign.set_lines(
+            true,
+            [
+                "Hello \"world\"".into(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ],
+        );
+
+        let written = write_block_entity(block_entity.as_ref());
+        let reread = read_block_entity(&written).unwrap();
+        let sign = reread.as_any().downcast_ref::<SignBlockEntity>().unwrap();
+        assert_eq!(sign.front_text.messages[0], r#""Hello \"world\"""#);
+        assert_eq!(sign.front_text.messages[1], r#""""#);
+        assert!(read_block_entity(&block_entity_nbt("minecraft:not_a_block_entity")).is_none());
+    }
+
+    #[test]
+    fn chest_loot_is_unpacked_once() {
+        let mut nbt = block_entity_nbt("minecraft:chest");
+        nbt.put("LootTable", "minecraft:chests/simple_dungeon");
+        nbt.put_long("LootTableSeed", 7);
+
+        let mut block_entity = read_block_entity(&nbt).unwrap();
+        let written = write_block_entity(block_entity.as_ref());
+        assert_eq!(written.get_long("LootTableSeed"), Some(7));
+        assert!(written.get_list("Items").is_none());
+
+        assert!(block_entity.slots().unwrap().iter().all(Option::is_none));
+        assert!(block_entity.unpack_loot());
+        assert!(!block_entity.unpack_loot());
+        let chest = block_entity
+            .as_any()
+            .downcast_ref::<ChestBlockEntity>()
+            .unwrap();
+        assert!(chest.loot_table.is_none());
+        assert!(chest.items.iter().any(Option::is_some));
+    }
+
+    #[test]
+    fn moved_block_state_is_kept() {
+        let piston = get_block("minecraft:sticky_piston")
+            .unwrap()
+            .default_state_id;
+        let piston = get_state_id_with_property(piston, "facing", "up").unwrap();
+        let mut block_entity = read_block_entity(&block_entity_nbt("minecraft:piston")).unwrap();
+        let moving = block_entity
+            .as_any_mut()
+            .downcast_mut::<PistonBlockEntity>()
+            .unwrap();
+        moving.block_state = piston;
+        moving.facing = BlockDirection::Top;
+        moving.source = true;
+        assert!(block_entity.tick());
+        assert!(block_entity.tick());
+        assert!(!block_entity.tick());
+
+        let written = write_block_entity(block_entity.as_ref());
+        let reread = read_block_entity(&written).unwrap();
+        let moving = reread.as_any().downcast_ref::<PistonBlockEntity>().unwrap();
+        assert_eq!(moving.block_state, piston);
+        assert!(moving.facing == BlockDirection::Top);
+        assert!(moving.source && !moving.extending);
+        assert!((moving.progress - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn hopper_waits_for_cooldown() {
+        let mut nbt = block_entity_nbt("minecraft:hopper");
+        nbt.put_int("TransferCooldown", 2);
+        let mut block_entity = read_block_entity(&nbt).unwrap();
+        assert_eq!(block_entity.slots().map(<[_]>::len), Some(5));
+        assert!(!block_entity.needs_block_tick());
+        assert!(block_entity.tick());
+        assert!(block_entity.tick());
+        assert!(block_entity.needs_block_tick());
+        assert!(!block_entity.tick());
+
+        let written = write_block_entity(block_entity.as_ref());
+        let reread = read_block_entity(&written).unwrap();
+        let hopper = reread.as_any().downcast_ref::<HopperBlockEntity>().unwrap();
+        assert_eq!(hopper.transfer_cooldown, 0);
+    }
+}
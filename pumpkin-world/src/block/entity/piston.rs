@@ -0,0 +1,130 @@
+use std::{any::Any, collections::HashMap};
+
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::position::BlockPos;
+
+use crate::block::{
+    registry::{get_block_by_state_id, get_state_id_by_properties, get_state_properties},
+    BlockDirection,
+};
+
+use super::BlockEntity;
+
+/// A block which is moved by a piston. It is a moving piston block until the move is done,
+/// then it becomes the moved block again.
+pub struct PistonBlockEntity {
+    position: BlockPos,
+    /// The state of the moved block
+    pub block_state: u16,
+    /// The direction the piston pushing or pulling the block faces
+    pub facing: BlockDirection,
+    /// How far the block has moved, from 0 to 1
+    pub progress: f32,
+    /// Whether the piston extends, or retracts
+    pub extending: bool,
+    /// Whether the moved block is the piston itself or its head
+    pub source: bool,
+}
+
+impl PistonBlockEntity {
+    /// How far a block moves each tick
+    const SPEED: f32 = 0.5;
+
+    #[must_use]
+    pub fn new(position: BlockPos) -> Self {
+        Self {
+            position,
+            block_state: 0,
+            facing: BlockDirection::North,
+            progress: 0.0,
+            extending: false,
+            source: false,
+        }
+    }
+
+    #[must_use]
+    pub fn from_nbt(position: BlockPos, nbt: &NbtCompound) -> Self {
+        let mut piston = Self::new(position);
+        piston.block_state = nbt
+            .get_compound("blockState")
+            .and_then(read_block_state)
+            .unwrap_or(0);
+        piston.facing = nbt
+            .get_int("facing")
+            .and_then(|facing| BlockDirection::try_from(facing).ok())
+            .unwrap_or(piston.facing);
+        piston.progress = nbt.get_float("progress").unwrap_or(0.0);
+        piston.extending = nbt.get_bool("extending").unwrap_or(false);
+        piston.source = nbt.get_bool("source").unwrap_or(false);
+        piston
+    }
+}
+
+/// Reads a block state in the format of the chunk palette, e.g.
+/// `{Name: "minecraft:piston", Properties: {facing: "up", extended: "false"}}`.
+fn read_block_state(nbt: &NbtCompound) -> Option<u16> {
+    let name = nbt.get_string("Name")?;
+    let properties: HashMap<String, String> = nbt
+        .get_compound("Properties")
+        .map(|properties| {
+            properties
+                .child_tags
+                .iter()
+                .filter_map(|(name, value)| match value {
+                    NbtTag::String(value) => Some((name.clone(), value.clone())),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    get_state_id_by_properties(name, &properties)
+}
+
+fn write_block_state(state_id: u16) -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    if let Some(block) = get_block_by_state_id(state_id) {
+        nbt.put("Name", format!("minecraft:{}", block.name));
+    }
+    if let Some(properties) = get_state_properties(state_id).filter(|p| !p.is_empty()) {
+        let mut compound = NbtCompound::new();
+        for (name, value) in properties {
+            compound.put(name, value);
+        }
+        nbt.put("Properties", compound);
+    }
+    nbt
+}
+
+impl BlockEntity for PistonBlockEntity {
+    fn id(&self) -> &str {
+        "piston"
+    }
+
+    fn position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put("blockState", write_block_state(self.block_state));
+        nbt.put_int("facing", self.facing as i32);
+        nbt.put_float("progress", self.progress);
+        nbt.put_bool("extending", self.extending);
+        nbt.put_bool("source", self.source);
+    }
+
+    fn tick(&mut self) -> bool {
+        if self.progress >= 1.0 {
+            return false;
+        }
+        self.progress = (self.progress + Self::SPEED).min(1.0);
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,129 @@
+use std::any::Any;
+
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::position::BlockPos;
+
+use super::BlockEntity;
+
+/// One side of a sign.
+#[derive(Clone, Default)]
+pub struct SignText {
+    /// The four lines as JSON text components
+    pub messages: [String; 4],
+    pub color: Option<String>,
+    pub has_glowing_text: bool,
+}
+
+impl SignText {
+    fn from_nbt(nbt: &NbtCompound) -> Self {
+        let mut text = Self::default();
+        for (line, message) in text.messages.iter_mut().zip(
+            nbt.get_list("messages")
+                .unwrap_or_default()
+                .iter()
+                .filter_map(NbtTag::extract_string),
+        ) {
+            line.clone_from(message);
+        }
+        text.color = nbt.get_string("color").cloned();
+        text.has_glowing_text = nbt.get_bool("has_glowing_text").unwrap_or(false);
+        text
+    }
+
+    fn to_nbt(&self) -> NbtCompound {
+        let messages: Vec<NbtTag> = self
+            .messages
+            .iter()
+            .map(|message| {
+                // Empty lines are written as empty text components
+                if message.is_empty() {
+                    NbtTag::String("\"\"".to_string())
+                } else {
+                    NbtTag::String(message.clone())
+                }
+            })
+            .collect();
+        let mut nbt = NbtCompound::new();
+        nbt.put("messages", NbtTag::List(messages.into_boxed_slice()));
+        nbt.put("color", self.color.as_deref().unwrap_or("black"));
+        nbt.put_bool("has_glowing_text", self.has_glowing_text);
+        nbt
+    }
+}
+
+/// A standing, wall or hanging sign.
+pub struct SignBlockEntity {
+    id: &'static str,
+    position: BlockPos,
+    pub front_text: SignText,
+    pub back_text: SignText,
+    pub is_waxed: bool,
+}
+
+impl SignBlockEntity {
+    #[must_use]
+    pub fn new(id: &'static str, position: BlockPos) -> Self {
+        Self {
+            id,
+            position,
+            front_text: SignText::default(),
+            back_text: SignText::default(),
+            is_waxed: false,
+        }
+    }
+
+    #[must_use]
+    pub fn from_nbt(id: &'static str, position: BlockPos, nbt: &NbtCompound) -> Self {
+        let mut sign = Self::new(id, position);
+        if let Some(front_text) = nbt.get_compound("front_text") {
+            sign.front_text = SignText::from_nbt(front_text);
+        }
+        if let Some(back_text) = nbt.get_compound("back_text") {
+            sign.back_text = SignText::from_nbt(back_text);
+        }
+        sign.is_waxed = nbt.get_bool("is_waxed").unwrap_or(false);
+        sign
+    }
+
+    /// Sets the lines of one side of the sign, which are plain text.
+    pub fn set_lines(&mut self, is_front: bool, lines: [String; 4]) {
+        let text = if is_front {
+            &mut self.front_text
+        } else {
+            &mut self.back_text
+        };
+        text.messages = lines.map(|line| {
+            serde_json::to_string(&line).expect("strings can always be serialized to JSON")
+        });
+    }
+}
+
+impl BlockEntity for SignBlockEntity {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put_component("front_text", self.front_text.to_nbt());
+        nbt.put_component("back_text", self.back_text.to_nbt());
+        nbt.put_bool("is_waxed", self.is_waxed);
+    }
+
+    fn client_nbt(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.write_nbt(&mut nbt);
+        Some(nbt)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,110 @@
+use std::any::Any;
+
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::position::BlockPos;
+
+use super::BlockEntity;
+
+/// A monster spawner. The client shows the entity to spawn spinning inside the cage.
+pub struct SpawnerBlockEntity {
+    position: BlockPos,
+    /// The entity to spawn, e.g. `minecraft:zombie`
+    pub entity: Option<String>,
+    /// Ticks until the next spawn
+    pub delay: i16,
+    pub min_spawn_delay: i16,
+    pub max_spawn_delay: i16,
+    pub spawn_count: i16,
+    pub max_nearby_entities: i16,
+    pub required_player_range: i16,
+    pub spawn_range: i16,
+}
+
+impl SpawnerBlockEntity {
+    #[must_use]
+    pub fn new(position: BlockPos) -> Self {
+        Self {
+            position,
+            entity: None,
+            delay: 20,
+            min_spawn_delay: 200,
+            max_spawn_delay: 800,
+            spawn_count: 4,
+            max_nearby_entities: 6,
+            required_player_range: 16,
+            spawn_range: 4,
+        }
+    }
+
+    /// A spawner of the given entity, e.g. `minecraft:skeleton`
+    #[must_use]
+    pub fn with_entity(position: BlockPos, entity: &str) -> Self {
+        let mut spawner = Self::new(position);
+        spawner.entity = Some(entity.to_string());
+        spawner
+    }
+
+    #[must_use]
+    pub fn from_nbt(position: BlockPos, nbt: &NbtCompound) -> Self {
+        let mut spawner = Self::new(position);
+        spawner.entity = nbt
+            .get_compound("SpawnData")
+            .and_then(|data| data.get_compound("entity"))
+            .and_then(|entity| entity.get_string("id"))
+            .cloned();
+        let get = |name, default| nbt.get_short(name).unwrap_or(default);
+        spawner.delay = get("Delay", spawner.delay);
+        spawner.min_spawn_delay = get("MinSpawnDelay", spawner.min_spawn_delay);
+        spawner.max_spawn_delay = get("MaxSpawnDelay", spawner.max_spawn_delay);
+        spawner.spawn_count = get("SpawnCount", spawner.spawn_count);
+        spawner.max_nearby_entities = get("MaxNearbyEntities", spawner.max_nearby_entities);
+        spawner.required_player_range = get("RequiredPlayerRange", spawner.required_player_range);
+        spawner.spawn_range = get("SpawnRange", spawner.spawn_range);
+        spawner
+    }
+
+    fn write_spawn_data(&self, nbt: &mut NbtCompound) {
+        let mut entity = NbtCompound::new();
+        if let Some(id) = &self.entity {
+            entity.put("id", id.clone());
+        }
+        let mut spawn_data = NbtCompound::new();
+        spawn_data.put("entity", NbtTag::Compound(entity));
+        nbt.put("SpawnData", NbtTag::Compound(spawn_data));
+    }
+}
+
+impl BlockEntity for SpawnerBlockEntity {
+    fn id(&self) -> &str {
+        "mob_spawner"
+    }
+
+    fn position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.write_spawn_data(nbt);
+        nbt.put_short("Delay", self.delay);
+        nbt.put_short("MinSpawnDelay", self.min_spawn_delay);
+        nbt.put_short("MaxSpawnDelay", self.max_spawn_delay);
+        nbt.put_short("SpawnCount", self.spawn_count);
+        nbt.put_short("MaxNearbyEntities", self.max_nearby_entities);
+        nbt.put_short("RequiredPlayerRange", self.required_player_range);
+        nbt.put_short("SpawnRange", self.spawn_range);
+    }
+
+    fn client_nbt(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.write_spawn_data(&mut nbt);
+        Some(nbt)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -1,4 +1,4 @@
-pub mod interactive;
+pub mod entity;
 pub mod registry;
 pub mod state;
 
@@ -36,6 +36,39 @@ impl TryFrom<i32> for BlockDirection {
 }
 
 impl BlockDirection {
+    pub fn all() -> [BlockDirection; 6] {
+        [
+            BlockDirection::Bottom,
+            BlockDirection::Top,
+            BlockDirection::North,
+            BlockDirection::South,
+            BlockDirection::West,
+            BlockDirection::East,
+        ]
+    }
+
+    /// The order in which vanilla updates the neighbors of a block. Redstone contraptions like
+    /// clocks depend on it
+    pub fn update_order() -> [BlockDirection; 6] {
+        [
+            BlockDirection::West,
+            BlockDirection::East,
+            BlockDirection::Bottom,
+            BlockDirection::Top,
+            BlockDirection::North,
+            BlockDirection::South,
+        ]
+    }
+
+    pub fn horizontal() -> [BlockDirection; 4] {
+        [
+            BlockDirection::North,
+            BlockDirection::South,
+            BlockDirection::West,
+            BlockDirection::East,
+        ]
+    }
+
     pub fn to_offset(&self) -> Vector3<i32> {
         match self {
             BlockDirection::Bottom => (0, -1, 0),
@@ -96,6 +96,73 @@ pub fn get_block_by_item<'a>(item_id: u16) -> Option<&'a Block> {
     BLOCKS_BY_ID.get(block_id).cloned()
 }
 
+/// Gets the properties of a block state as name and value pairs, in the order the block declares
+/// them.
+pub fn get_state_properties(state_id: u16) -> Option<Vec<(&'static str, &'static str)>> {
+    let block = get_block_by_state_id(state_id)?;
+    let mut index = *STATE_INDEX_BY_STATE_ID.get(&state_id)? as usize;
+
+    // States are the cartesian product of all property values, with the last property changing
+    // the fastest
+    let mut properties = Vec::with_capacity(block.properties.len());
+    for property in block.properties.iter().rev() {
+        let count = property.values.len();
+        properties.push((
+            property.name.as_str(),
+            property.values[index % count].as_str(),
+        ));
+        index /= count;
+    }
+    properties.reverse();
+    Some(properties)
+}
+
+/// Gets the id of the block state with the given properties. Properties which are not given keep
+/// the value of the default state, unknown properties and values are ignored.
+pub fn get_state_id_by_properties(
+    registry_id: &str,
+    properties: &HashMap<String, String>,
+) -> Option<u16> {
+    let block = get_block(registry_id)?;
+    let default_properties = get_state_properties(block.default_state_id)?;
+
+    let mut index = 0;
+    for (property, (_, default_value)) in block.properties.iter().zip(default_properties) {
+        let value = properties
+            .get(&property.name)
+            .and_then(|value| property.values.iter().position(|v| v == value))
+            .or_else(|| property.values.iter().position(|v| v == default_value))?;
+        index = index * property.values.len() + value;
+    }
+    block.states.get(index).map(|state| state.id)
+}
+
+/// Gets the value of a property of a block state, or `None` if the block has no such property
+pub fn get_state_property(state_id: u16, property: &str) -> Option<&'static str> {
+    get_state_properties(state_id)?
+        .into_iter()
+        .find_map(|(name, value)| (name == property).then_some(value))
+}
+
+/// Gets the id of the state of the same block with one property changed. Returns `None` if the
+/// block has no such property or value.
+pub fn get_state_id_with_property(state_id: u16, property: &str, value: &str) -> Option<u16> {
+    let block = get_block_by_state_id(state_id)?;
+    let mut properties: HashMap<String, String> = get_state_properties(state_id)?
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    block
+        .properties
+        .iter()
+        .find(|block_property| block_property.name == property)?
+        .values
+        .iter()
+        .find(|block_value| *block_value == value)?;
+    properties.insert(property.to_string(), value.to_string());
+    get_state_id_by_properties(&block.name, &properties)
+}
+
 pub fn get_block_collision_shapes(block_id: u16) -> Option<Vec<Shape>> {
     let block = BLOCKS_BY_ID.get(&BLOCK_ID_BY_STATE_ID[&block_id])?;
     let state = &block.states[STATE_INDEX_BY_STATE_ID[&block_id] as usize];
@@ -2,25 +2,33 @@ use bytes::*;
 use flate2::bufread::{GzDecoder, GzEncoder, ZlibDecoder, ZlibEncoder};
 use indexmap::IndexMap;
 use pumpkin_config::ADVANCED_CONFIG;
-use pumpkin_nbt::serializer::to_bytes;
+use pumpkin_nbt::{deserializer::ReadAdaptor, serializer::to_bytes, tag::NbtTag, Nbt};
 use pumpkin_util::math::ceil_log2;
+use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::{
-    collections::HashSet,
     fs::OpenOptions,
     io::{Read, Seek, SeekFrom, Write},
 };
 
-use crate::block::registry::STATE_ID_TO_REGISTRY_ID;
+use crate::block::entity::write_block_entity;
+use crate::block::registry::{get_state_properties, STATE_ID_TO_REGISTRY_ID};
+use crate::scheduled_tick::write_block_tick;
 use crate::{chunk::ChunkWritingError, level::LevelFolder};
 
 use super::{
-    ChunkData, ChunkNbt, ChunkReader, ChunkReadingError, ChunkSection, ChunkSectionBlockStates,
-    ChunkSerializingError, ChunkWriter, CompressionError, PaletteEntry,
+    ChunkData, ChunkNbt, ChunkReader, ChunkReadingError, ChunkSection, ChunkSectionBiomes,
+    ChunkSectionBlockStates, ChunkSerializingError, ChunkWriter, CompressionError, PaletteEntry,
+    LOWEST_SECTION_Y,
 };
 
 // 1.21.4
-const WORLD_DATA_VERSION: i32 = 4189;
+pub(crate) const WORLD_DATA_VERSION: i32 = 4189;
+
+/// Region files are split into sectors of 4 KiB.
+const SECTOR_BYTES: usize = 4096;
+/// The location and timestamp tables take up the first two sectors.
+const HEADER_SECTORS: u64 = 2;
 
 #[derive(Clone, Default)]
 pub struct AnvilChunkFormat;
@@ -148,15 +156,37 @@ impl ChunkReader for AnvilChunkFormat {
         save_file: &LevelFolder,
         at: &pumpkin_util::math::vector2::Vector2<i32>,
     ) -> Result<super::ChunkData, ChunkReadingError> {
+        let chunk_data = Self::read_region_data(&save_file.region_folder, at)?;
+        ChunkData::from_bytes(&chunk_data, *at).map_err(ChunkReadingError::ParsingError)
+    }
+}
+
+impl ChunkWriter for AnvilChunkFormat {
+    fn write_chunk(
+        &self,
+        chunk_data: &ChunkData,
+        level_folder: &LevelFolder,
+        at: &pumpkin_util::math::vector2::Vector2<i32>,
+    ) -> Result<(), super::ChunkWritingError> {
+        // Serialize chunk data
+        let raw_bytes = Self::to_bytes(chunk_data)
+            .map_err(|err| ChunkWritingError::ChunkSerializingError(err.to_string()))?;
+        Self::write_region_data(&level_folder.region_folder, at, &raw_bytes)
+    }
+}
+
+impl AnvilChunkFormat {
+    /// Reads the decompressed data stored for the chunk at `at` from the region files in
+    /// `region_folder`.
+    pub(crate) fn read_region_data(
+        region_folder: &Path,
+        at: &pumpkin_util::math::vector2::Vector2<i32>,
+    ) -> Result<Vec<u8>, ChunkReadingError> {
         let region = (at.x >> 5, at.z >> 5);
 
         let mut region_file = OpenOptions::new()
             .read(true)
-            .open(
-                save_file
-                    .region_folder
-                    .join(format!("r.{}.{}.mca", region.0, region.1)),
-            )
+            .open(region_folder.join(format!("r.{}.{}.mca", region.0, region.1)))
             .map_err(|err| match err.kind() {
                 std::io::ErrorKind::NotFound => ChunkReadingError::ChunkNotExist,
                 kind => ChunkReadingError::IoError(kind),
@@ -199,13 +229,17 @@ impl ChunkReader for AnvilChunkFormat {
             out
         };
 
-        let mut header: Bytes = file_buf.drain(0..5).collect();
-        if header.remaining() != 5 {
+        if file_buf.len() < 5 {
             return Err(ChunkReadingError::InvalidHeader);
         }
+        let mut header: Bytes = file_buf.drain(0..5).collect();
 
         let size = header.get_u32();
         let compression = header.get_u8();
+        // size includes the compression scheme byte
+        if size == 0 || size as usize - 1 > file_buf.len() {
+            return Err(ChunkReadingError::InvalidHeader);
+        }
 
         let compression = Compression::from_byte(compression)
             .map_err(|_| ChunkReadingError::Compression(CompressionError::UnknownCompression))?;
@@ -213,25 +247,22 @@ impl ChunkReader for AnvilChunkFormat {
         // size includes the compression scheme byte, so we need to subtract 1
         let chunk_data: Vec<u8> = file_buf.drain(0..size as usize - 1).collect();
 
-        let decompressed_chunk = if let Some(compression) = compression {
+        if let Some(compression) = compression {
             compression
                 .decompress_data(&chunk_data)
-                .map_err(ChunkReadingError::Compression)?
+                .map_err(ChunkReadingError::Compression)
         } else {
-            chunk_data
-        };
-
-        ChunkData::from_bytes(&decompressed_chunk, *at).map_err(ChunkReadingError::ParsingError)
+            Ok(chunk_data)
+        }
     }
-}
 
-impl ChunkWriter for AnvilChunkFormat {
-    fn write_chunk(
-        &self,
-        chunk_data: &ChunkData,
-        level_folder: &LevelFolder,
+    /// Compresses `raw_bytes` and stores them for the chunk at `at` in the region files in
+    /// `region_folder`.
+    pub(crate) fn write_region_data(
+        region_folder: &Path,
         at: &pumpkin_util::math::vector2::Vector2<i32>,
-    ) -> Result<(), super::ChunkWritingError> {
+        raw_bytes: &[u8],
+    ) -> Result<(), ChunkWritingError> {
         let region = (at.x >> 5, at.z >> 5);
 
         let mut region_file = OpenOptions::new()
@@ -239,21 +270,13 @@ impl ChunkWriter for AnvilChunkFormat {
             .write(true)
             .create(true)
             .truncate(false)
-            .open(
-                level_folder
-                    .region_folder
-                    .join(format!("./r.{}.{}.mca", region.0, region.1)),
-            )
+            .open(region_folder.join(format!("./r.{}.{}.mca", region.0, region.1)))
             .map_err(|err| ChunkWritingError::IoError(err.kind()))?;
 
-        // Serialize chunk data
-        let raw_bytes = Self::to_bytes(chunk_data)
-            .map_err(|err| ChunkWritingError::ChunkSerializingError(err.to_string()))?;
-
         // Compress chunk data
         let compression: Compression = ADVANCED_CONFIG.chunk.compression.algorithm.clone().into();
         let compressed_data = compression
-            .compress_data(&raw_bytes, ADVANCED_CONFIG.chunk.compression.level)
+            .compress_data(raw_bytes, ADVANCED_CONFIG.chunk.compression.level)
             .map_err(ChunkWritingError::Compression)?;
 
         // Length of compressed data + compression type
@@ -261,14 +284,19 @@ impl ChunkWriter for AnvilChunkFormat {
 
         // | 0 1 2 3 |        4         |        5..      |
         // | length  | compression type | compressed data |
-        let mut chunk_payload = BytesMut::with_capacity(5);
+        let mut chunk_payload = BytesMut::with_capacity(5 + compressed_data.len());
         // Payload Header + Body
         chunk_payload.put_u32(length);
         chunk_payload.put_u8(compression as u8);
         chunk_payload.put_slice(&compressed_data);
 
-        // Calculate sector size
-        let sector_size = chunk_payload.len().div_ceil(4096);
+        // Chunks are stored in whole sectors
+        let sector_count = chunk_payload.len().div_ceil(SECTOR_BYTES);
+        // TODO: Vanilla stores larger chunks in separate .mcc files
+        if sector_count > usize::from(u8::MAX) {
+            return Err(ChunkWritingError::ChunkTooLarge(sector_count));
+        }
+        chunk_payload.resize(sector_count * SECTOR_BYTES, 0);
 
         // Region file header tables
         let mut location_table = [0u8; 4096];
@@ -296,27 +324,21 @@ impl ChunkWriter for AnvilChunkFormat {
 
         // | 0 1 2  |      3       |
         // | offset | sector count |
-        // Get the entry from the current location table and check
-        // if the new chunk fits in the space of the old chunk
-        let chunk_location = &location_table[table_index..table_index + 4];
-        let chunk_data_location: u64 = if chunk_location[3] >= sector_size as u8 {
-            // Return old chunk location
-            u32::from_be_bytes([0, chunk_location[0], chunk_location[1], chunk_location[2]]) as u64
-        } else {
-            // Retrieve next writable sector
-            self.find_free_sector(&location_table, sector_size) as u64
-        };
-
-        assert!(
-            chunk_data_location > 1,
-            "This should never happen. The header would be corrupted"
-        );
+        // Keep the chunk where it is if it still fits, otherwise move it to free sectors. The
+        // sectors of the old location become free
+        let (old_offset, old_sector_count) = Self::read_location(&location_table, table_index);
+        let chunk_data_location =
+            if old_offset >= HEADER_SECTORS && old_sector_count >= sector_count as u64 {
+                old_offset
+            } else {
+                Self::find_free_sector(&location_table, table_index, sector_count)
+            };
 
         // Construct location header
         location_table[table_index] = (chunk_data_location >> 16) as u8;
         location_table[table_index + 1] = (chunk_data_location >> 8) as u8;
         location_table[table_index + 2] = chunk_data_location as u8;
-        location_table[table_index + 3] = sector_size as u8;
+        location_table[table_index + 3] = sector_count as u8;
 
         // Get epoch may result in errors if after the year 2106 :(
         let epoch = SystemTime::now()
@@ -330,30 +352,24 @@ impl ChunkWriter for AnvilChunkFormat {
         timestamp_table[table_index + 2] = (epoch >> 8) as u8;
         timestamp_table[table_index + 3] = epoch as u8;
 
-        // Write new location and timestamp table
-        region_file.seek(SeekFrom::Start(0)).unwrap();
-        region_file
-            .write_all(&[location_table, timestamp_table].concat())
-            .map_err(|e| ChunkWritingError::IoError(e.kind()))?;
-
         // Seek to where the chunk is located
         region_file
-            .seek(SeekFrom::Start(chunk_data_location * 4096))
+            .seek(SeekFrom::Start(chunk_data_location * SECTOR_BYTES as u64))
             .map_err(|err| ChunkWritingError::IoError(err.kind()))?;
 
-        // Write header and payload
+        // Write header and payload, which is already padded to whole sectors
         region_file
             .write_all(&chunk_payload)
             .map_err(|err| ChunkWritingError::IoError(err.kind()))?;
 
-        // Calculate padding to fill the sectors
-        // (length + 4) 3 bits for length and 1 for compression type + payload length
-        let padding = ((sector_size * 4096) as u32 - ((length + 4) & 0xFFF)) & 0xFFF;
-
-        // Write padding
+        // Write new location and timestamp table after the chunk, so the old chunk stays
+        // readable if writing the new one fails
         region_file
-            .write_all(&vec![0u8; padding as usize])
+            .seek(SeekFrom::Start(0))
             .map_err(|err| ChunkWritingError::IoError(err.kind()))?;
+        region_file
+            .write_all(&[location_table, timestamp_table].concat())
+            .map_err(|e| ChunkWritingError::IoError(e.kind()))?;
 
         region_file
             .flush()
@@ -361,93 +377,90 @@ impl ChunkWriter for AnvilChunkFormat {
 
         Ok(())
     }
-}
 
-impl AnvilChunkFormat {
     pub fn to_bytes(chunk_data: &ChunkData) -> Result<Vec<u8>, ChunkSerializingError> {
         let mut sections = Vec::new();
 
         for (i, blocks) in chunk_data.subchunks.array_iter().enumerate() {
-            // get unique blocks
-            let unique_blocks: HashSet<_> = blocks.iter().collect();
-
-            let palette: IndexMap<_, _> = unique_blocks
-                .into_iter()
-                .enumerate()
-                .map(|(i, block)| {
-                    let name = STATE_ID_TO_REGISTRY_ID.get(block).unwrap();
-                    (block, (name, i))
-                })
-                .collect();
+            // Map every unique block state to its index in the palette
+            let mut palette: IndexMap<u16, usize> = IndexMap::new();
+            for block in blocks.iter() {
+                let next_index = palette.len();
+                palette.entry(*block).or_insert(next_index);
+            }
 
-            // Determine the number of bits needed to represent the largest index in the palette
-            let block_bit_size = if palette.len() < 16 {
-                4
+            // A subchunk with only one block state does not store any data
+            let data = if palette.len() == 1 {
+                None
             } else {
-                ceil_log2(palette.len() as u32).max(4)
-            };
-
-            let mut section_longs = Vec::new();
-            let mut current_pack_long: i64 = 0;
-            let mut bits_used_in_pack: u32 = 0;
-
-            // Empty data if the palette only contains one index https://minecraft.fandom.com/wiki/Chunk_format
-            // if palette.len() > 1 {}
-            // TODO: Update to write empty data. Rn or read does not handle this elegantly
-            for block in blocks.iter() {
-                // Push if next bit does not fit
-                if bits_used_in_pack + block_bit_size as u32 > 64 {
-                    section_longs.push(current_pack_long);
-                    current_pack_long = 0;
-                    bits_used_in_pack = 0;
+                // Determine the number of bits needed to represent the largest index in the palette
+                let block_bit_size = if palette.len() < 16 {
+                    4
+                } else {
+                    ceil_log2(palette.len() as u32).max(4)
+                };
+
+                let mut section_longs = Vec::new();
+                let mut current_pack_long: i64 = 0;
+                let mut bits_used_in_pack: u32 = 0;
+
+                for block in blocks.iter() {
+                    // Blocks don't span multiple longs, so push if the next block does not fit
+                    if bits_used_in_pack + block_bit_size as u32 > 64 {
+                        section_longs.push(current_pack_long);
+                        current_pack_long = 0;
+                        bits_used_in_pack = 0;
+                    }
+                    let index = palette[block];
+                    current_pack_long |= (index as i64) << bits_used_in_pack;
+                    bits_used_in_pack += block_bit_size as u32;
                 }
-                let index = palette.get(block).expect("Just added all unique").1;
-                current_pack_long |= (index as i64) << bits_used_in_pack;
-                bits_used_in_pack += block_bit_size as u32;
 
-                assert!(bits_used_in_pack <= 64);
-
-                // If the current 64-bit integer is full, push it to the section_longs and start a new one
-                if bits_used_in_pack >= 64 {
+                // Push the last 64-bit integer if it contains any data
+                if bits_used_in_pack > 0 {
                     section_longs.push(current_pack_long);
-                    current_pack_long = 0;
-                    bits_used_in_pack = 0;
                 }
-            }
+                Some(section_longs.into_boxed_slice())
+            };
 
-            // Push the last 64-bit integer if it contains any data
-            if bits_used_in_pack > 0 {
-                section_longs.push(current_pack_long);
-            }
+            let palette = palette
+                .keys()
+                .map(|state_id| {
+                    let name = STATE_ID_TO_REGISTRY_ID
+                        .get(state_id)
+                        .ok_or(ChunkSerializingError::UnknownBlockState(*state_id))?;
+                    let properties = get_state_properties(*state_id)
+                        .filter(|properties| !properties.is_empty())
+                        .map(|properties| {
+                            properties
+                                .into_iter()
+                                .map(|(name, value)| (name.to_string(), value.to_string()))
+                                .collect()
+                        });
+                    Ok(PaletteEntry {
+                        name: format!("minecraft:{name}"),
+                        properties,
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+
+            let biomes = chunk_data
+                .biomes
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| ChunkSectionBiomes::single("minecraft:plains"));
 
             sections.push(ChunkSection {
-                y: i as i8 - 4,
-                block_states: Some(ChunkSectionBlockStates {
-                    data: Some(section_longs.into_boxed_slice()),
-                    palette: palette
-                        .into_iter()
-                        .map(|entry| PaletteEntry {
-                            name: entry.1 .0.to_string(),
-                            properties: {
-                                /*
-                                let properties = &get_block(entry.1 .0).unwrap().properties;
-                                let mut map = HashMap::new();
-                                for property in properties {
-                                    map.insert(property.name.to_string(), property.values.clone());
-                                }
-                                Some(map)
-                                */
-                                None
-                            },
-                        })
-                        .collect(),
-                }),
+                y: i as i8 + LOWEST_SECTION_Y,
+                block_states: Some(ChunkSectionBlockStates { data, palette }),
+                biomes: Some(biomes),
             });
         }
 
         let nbt = ChunkNbt {
             data_version: WORLD_DATA_VERSION,
             x_pos: chunk_data.position.x,
+            y_pos: i32::from(LOWEST_SECTION_Y),
             z_pos: chunk_data.position.z,
             status: super::ChunkStatus::Full,
             heightmaps: chunk_data.heightmap.clone(),
@@ -456,58 +469,103 @@ impl AnvilChunkFormat {
 
         let mut result = Vec::new();
         to_bytes(&nbt, &mut result).map_err(ChunkSerializingError::ErrorSerializingChunk)?;
-        Ok(result)
-    }
 
-    /// Returns the next free writable sector
-    /// The sector is absolute which means it always has a spacing of 2 sectors
-    fn find_free_sector(&self, location_table: &[u8; 4096], sector_size: usize) -> usize {
-        let mut used_sectors: Vec<u16> = Vec::new();
-        for i in 0..1024 {
-            let entry_offset = i * 4;
-            let location_offset = u32::from_be_bytes([
-                0,
-                location_table[entry_offset],
-                location_table[entry_offset + 1],
-                location_table[entry_offset + 2],
-            ]) as u64;
-            let length = location_table[entry_offset + 3] as u64;
-            let sector_count = location_offset;
-            for used_sector in sector_count..sector_count + length {
-                used_sectors.push(used_sector as u16);
+        // Block entities can contain anything, so they are added without serde, like the block
+        // ticks which are stored with the block names
+        if !chunk_data.block_entities.is_empty() || !chunk_data.block_ticks.is_empty() {
+            let mut nbt = Nbt::read(&mut ReadAdaptor::new(result.as_slice()))
+                .map_err(ChunkSerializingError::ErrorSerializingChunk)?;
+            if !chunk_data.block_entities.is_empty() {
+                let block_entities: Vec<NbtTag> = chunk_data
+                    .block_entities
+                    .values()
+                    .map(|block_entity| NbtTag::Compound(write_block_entity(block_entity.as_ref())))
+                    .collect();
+                nbt.root_tag.put(
+                    "block_entities",
+                    NbtTag::List(block_entities.into_boxed_slice()),
+                );
+            }
+            if !chunk_data.block_ticks.is_empty() {
+                let block_ticks: Vec<NbtTag> = chunk_data
+                    .block_ticks
+                    .iter()
+                    .filter_map(write_block_tick)
+                    .map(NbtTag::Compound)
+                    .collect();
+                nbt.root_tag
+                    .put("block_ticks", NbtTag::List(block_ticks.into_boxed_slice()));
             }
+            result = nbt.write().to_vec();
         }
 
-        if used_sectors.is_empty() {
-            return 2;
-        }
+        Ok(result)
+    }
 
-        used_sectors.sort();
+    /// Reads the offset and the number of sectors of a chunk from the location table.
+    fn read_location(location_table: &[u8; 4096], table_index: usize) -> (u64, u64) {
+        let offset = u32::from_be_bytes([
+            0,
+            location_table[table_index],
+            location_table[table_index + 1],
+            location_table[table_index + 2],
+        ]);
+        (
+            u64::from(offset),
+            u64::from(location_table[table_index + 3]),
+        )
+    }
 
-        let mut prev_sector = &used_sectors[0];
-        for sector in used_sectors[1..].iter() {
-            // Iterate over consecutive pairs
-            if sector - prev_sector > sector_size as u16 {
-                return (prev_sector + 1) as usize;
+    /// Returns the first sector after the header from which on `sector_count` sectors are not
+    /// used by any chunk, except for the chunk at `table_index` which is being moved.
+    fn find_free_sector(
+        location_table: &[u8; 4096],
+        table_index: usize,
+        sector_count: usize,
+    ) -> u64 {
+        let mut used: Vec<(u64, u64)> = (0..1024)
+            .map(|i| i * 4)
+            .filter(|index| *index != table_index)
+            .map(|index| Self::read_location(location_table, index))
+            .filter(|(offset, count)| *offset >= HEADER_SECTORS && *count > 0)
+            .collect();
+        used.sort_unstable();
+
+        let mut candidate = HEADER_SECTORS;
+        for (offset, count) in used {
+            if offset >= candidate + sector_count as u64 {
+                break;
             }
-            prev_sector = sector;
+            candidate = candidate.max(offset + count);
         }
-
-        (*used_sectors.last().unwrap() + 1) as usize
+        candidate
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use pumpkin_util::math::vector2::Vector2;
+    use flate2::bufread::ZlibEncoder;
+    use pumpkin_nbt::{compound::NbtCompound, deserializer::ReadAdaptor, tag::NbtTag, Nbt};
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+    use std::collections::HashMap;
     use std::fs;
-    use std::path::PathBuf;
+    use std::io::Read;
+    use std::path::{Path, PathBuf};
     use temp_dir::TempDir;
 
+    use super::Compression;
+    use crate::block::entity::ChestBlockEntity;
+    use crate::block::registry::get_state_id_by_properties;
     use crate::chunk::ChunkWriter;
-    use crate::generation::{get_world_gen, Seed};
+    use crate::coordinates::{ChunkRelativeBlockCoordinates, Height};
+    use crate::generation::{get_world_gen, Seed, DEFAULT_GENERATOR};
+    use crate::global_path;
+    use crate::scheduled_tick::TickPriority;
     use crate::{
-        chunk::{anvil::AnvilChunkFormat, ChunkReader, ChunkReadingError},
+        chunk::{
+            anvil::AnvilChunkFormat, ChunkData, ChunkParsingError, ChunkReader, ChunkReadingError,
+            ChunkSectionBiomes,
+        },
         level::LevelFolder,
     };
 
@@ -526,7 +584,7 @@ mod tests {
 
     #[test]
     fn test_writing() {
-        let generator = get_world_gen(Seed(0));
+        let generator = get_world_gen(DEFAULT_GENERATOR, Seed(0)).unwrap();
 
         let temp_dir = TempDir::new().unwrap();
         let level_folder = LevelFolder {
@@ -573,6 +631,590 @@ mod tests {
         println!("Checked chunks successfully");
     }
 
+    fn long_array(values: Vec<i64>) -> NbtTag {
+        NbtTag::LongArray(values.into_boxed_slice())
+    }
+
+    fn list(values: Vec<NbtTag>) -> NbtTag {
+        NbtTag::List(values.into_boxed_slice())
+    }
+
+    fn palette_entry(name: &str, properties: &[(&str, &str)]) -> NbtTag {
+        let mut entry = NbtCompound::new();
+        entry.put("Name", name);
+        if !properties.is_empty() {
+            let mut compound = NbtCompound::new();
+            for (name, value) in properties {
+                compound.put(name, *value);
+            }
+            entry.put("Properties", compound);
+        }
+        NbtTag::Compound(entry)
+    }
+
+    /// Builds a chunk the way vanilla saves it, including data Pumpkin does not use.
+    fn vanilla_chunk(x: i32, z: i32) -> NbtCompound {
+        let mut light_section = NbtCompound::new();
+        light_section.put("Y", NbtTag::Byte(-5));
+        light_section.put(
+            "SkyLight",
+            NbtTag::ByteArray(vec![0xFF; 2048].into_boxed_slice()),
+        );
+
+        let mut stone_section = NbtCompound::new();
+        stone_section.put("Y", NbtTag::Byte(-4));
+        let mut block_states = NbtCompound::new();
+        block_states.put("palette", list(vec![palette_entry("minecraft:stone", &[])]));
+        stone_section.put("block_states", block_states);
+        let mut biomes = NbtCompound::new();
+        biomes.put(
+            "palette",
+            list(vec![NbtTag::String("minecraft:desert".into())]),
+        );
+        stone_section.put("biomes", biomes);
+
+        // The first block is a stair, all other blocks are air
+        let mut stairs_section = NbtCompound::new();
+        stairs_section.put("Y", NbtTag::Byte(0));
+        let mut block_states = NbtCompound::new();
+        block_states.put(
+            "palette",
+            list(vec![
+                palette_entry("minecraft:air", &[]),
+                palette_entry(
+                    "minecraft:oak_stairs",
+                    &[
+                        ("facing", "east"),
+                        ("half", "top"),
+                        ("shape", "straight"),
+                        ("waterlogged", "false"),
+                    ],
+                ),
+            ]),
+        );
+        let mut data = vec![0; 256];
+        data[0] = 1;
+        block_states.put("data", long_array(data));
+        stairs_section.put("block_states", block_states);
+        let mut biomes = NbtCompound::new();
+        biomes.put(
+            "palette",
+            list(vec![
+                NbtTag::String("minecraft:plains".into()),
+                NbtTag::String("minecraft:desert".into()),
+            ]),
+        );
+        biomes.put("data", long_array(vec![0x00FF_00FF_00FF_00FF]));
+        stairs_section.put("biomes", biomes);
+
+        let mut heightmaps = NbtCompound::new();
+        heightmaps.put("MOTION_BLOCKING", long_array(vec![0; 37]));
+        heightmaps.put("WORLD_SURFACE", long_array(vec![0; 37]));
+
+        let mut chest = NbtCompound::new();
+        chest.put("id", "minecraft:chest");
+        chest.put("x", x * 16);
+        chest.put("y", -64);
+        chest.put("z", z * 16);
+        let mut diamonds = NbtCompound::new();
+        diamonds.put("Slot", NbtTag::Byte(3));
+        diamonds.put("id", "minecraft:diamond");
+        diamonds.put("count", 5);
+        chest.put("Items", list(vec![NbtTag::Compound(diamonds)]));
+
+        let mut chunk = NbtCompound::new();
+        chunk.put("DataVersion", 4189);
+        chunk.put("xPos", x);
+        chunk.put("yPos", -4);
+        chunk.put("zPos", z);
+        chunk.put("Status", "minecraft:full");
+        chunk.put("LastUpdate", NbtTag::Long(1234));
+        chunk.put("InhabitedTime", NbtTag::Long(0));
+        chunk.put("isLightOn", NbtTag::Byte(1));
+        chunk.put("Heightmaps", heightmaps);
+        chunk.put(
+            "sections",
+            list(vec![
+                NbtTag::Compound(light_section),
+                NbtTag::Compound(stone_section),
+                NbtTag::Compound(stairs_section),
+            ]),
+        );
+        let mut water_tick = NbtCompound::new();
+        water_tick.put("i", "minecraft:water");
+        water_tick.put("x", x * 16 + 1);
+        water_tick.put("y", 64);
+        water_tick.put("z", z * 16);
+        water_tick.put("t", 5);
+        water_tick.put("p", -1);
+
+        chunk.put("block_entities", list(vec![NbtTag::Compound(chest)]));
+        chunk.put("block_ticks", list(vec![NbtTag::Compound(water_tick)]));
+        chunk
+    }
+
+    /// Writes a region file with a single zlib compressed chunk, like vanilla does.
+    fn write_vanilla_region(path: &Path, at: Vector2<i32>, chunk: NbtCompound) {
+        let bytes = Nbt::new(String::new(), chunk).write();
+        let mut encoder = ZlibEncoder::new(&bytes[..], flate2::Compression::default());
+        let mut compressed = Vec::new();
+        encoder.read_to_end(&mut compressed).unwrap();
+
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&(compressed.len() as u32 + 1).to_be_bytes());
+        payload.push(Compression::ZLib as u8);
+        payload.extend_from_slice(&compressed);
+        let sectors = payload.len().div_ceil(4096);
+        payload.resize(sectors * 4096, 0);
+
+        let mut header = vec![0u8; 8192];
+        let index = ((at.x & 31) + (at.z & 31) * 32) as usize * 4;
+        header[index..index + 4].copy_from_slice(&[0, 0, 2, sectors as u8]);
+        header.extend_from_slice(&payload);
+        fs::write(path, header).unwrap();
+    }
+
+    /// Reads the uncompressed NBT of a chunk from a region file.
+    fn read_raw_chunk(path: &Path, at: Vector2<i32>) -> NbtCompound {
+        let region = fs::read(path).unwrap();
+        let index = ((at.x & 31) + (at.z & 31) * 32) as usize * 4;
+        let offset = u32::from_be_bytes([0, region[index], region[index + 1], region[index + 2]])
+            as usize
+            * 4096;
+        let length = u32::from_be_bytes(region[offset..offset + 4].try_into().unwrap()) as usize;
+        let compression = Compression::from_byte(region[offset + 4]).unwrap().unwrap();
+        let data = compression
+            .decompress_data(&region[offset + 5..offset + 4 + length])
+            .unwrap();
+        Nbt::read(&mut ReadAdaptor::new(data.as_slice()))
+            .unwrap()
+            .root_tag
+    }
+
+    #[test]
+    fn vanilla_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_folder = LevelFolder {
+            root_folder: temp_dir.path().to_path_buf(),
+            region_folder: temp_dir.path().join("region"),
+        };
+        fs::create_dir(&level_folder.region_folder).unwrap();
+        let region_path = level_folder.region_folder.join("r.0.0.mca");
+
+        let at = Vector2::new(1, 2);
+        write_vanilla_region(&region_path, at, vanilla_chunk(at.x, at.z));
+
+        let mut chunk = AnvilChunkFormat.read_chunk(&level_folder, &at).unwrap();
+        assert!(!chunk.dirty);
+
+        let stairs = get_state_id_by_properties(
+            "oak_stairs",
+            &HashMap::from([
+                ("facing".to_string(), "east".to_string()),
+                ("half".to_string(), "top".to_string()),
+            ]),
+        )
+        .unwrap();
+        let stone = get_state_id_by_properties("stone", &HashMap::new()).unwrap();
+        let position = |y: u16| ChunkRelativeBlockCoordinates {
+            x: 0u8.into(),
+            y: Height::from_absolute(y),
+            z: 0u8.into(),
+        };
+        assert_eq!(chunk.get_block(position(0)), Some(stone));
+        assert_eq!(chunk.get_block(position(15)), Some(stone));
+        assert_eq!(chunk.get_block(position(64)), Some(stairs));
+        assert_eq!(chunk.get_block(position(65)), Some(0));
+
+        let chest_position = BlockPos(Vector3::new(at.x * 16, -64, at.z * 16));
+        let chest = chunk
+            .get_block_entity(&chest_position)
+            .and_then(|block_entity| block_entity.as_any().downcast_ref::<ChestBlockEntity>())
+            .unwrap();
+        let diamonds = chest.items[3].as_ref().unwrap();
+        assert_eq!(diamonds.item.registry_key(), "diamond");
+        assert_eq!(diamonds.item_count, 5);
+
+        assert_eq!(chunk.block_ticks.len(), 1);
+        let water_tick = chunk.block_ticks[0];
+        assert!(water_tick.position == BlockPos(Vector3::new(at.x * 16 + 1, 64, at.z * 16)));
+        assert_eq!(water_tick.delay, 5);
+        assert_eq!(water_tick.priority, TickPriority::High);
+
+        chunk.set_block(position(100), stone);
+        assert!(chunk.dirty);
+        AnvilChunkFormat
+            .write_chunk(&chunk, &level_folder, &at)
+            .unwrap();
+
+        let read_chunk = AnvilChunkFormat.read_chunk(&level_folder, &at).unwrap();
+        assert_eq!(chunk.subchunks, read_chunk.subchunks);
+        assert_eq!(chunk.biomes, read_chunk.biomes);
+        assert_eq!(chunk.block_ticks, read_chunk.block_ticks);
+
+        // Check the saved data is what vanilla expects
+        let raw = read_raw_chunk(&region_path, at);
+        assert_eq!(raw.get_int("DataVersion"), Some(4189));
+        assert_eq!(raw.get_int("yPos"), Some(-4));
+        assert_eq!(
+            raw.get_string("Status").map(String::as_str),
+            Some("minecraft:full")
+        );
+        assert!(raw.get_compound("Heightmaps").is_some());
+
+        let sections = raw.get_list("sections").unwrap();
+        assert_eq!(sections.len(), 24);
+        let section = |y: i8| {
+            sections
+                .iter()
+                .filter_map(NbtTag::extract_compound)
+                .find(|section| section.get_byte("Y") == Some(y))
+                .unwrap()
+        };
+
+        let stone_states = section(-4).get_compound("block_states").unwrap();
+        assert!(stone_states.get("data").is_none());
+        let biome = section(-4)
+            .get_compound("biomes")
+            .unwrap()
+            .get_list("palette")
+            .unwrap()[0]
+            .extract_string()
+            .unwrap()
+            .clone();
+        assert_eq!(biome, "minecraft:desert");
+
+        let stairs_entry = section(0)
+            .get_compound("block_states")
+            .unwrap()
+            .get_list("palette")
+            .unwrap()
+            .iter()
+            .filter_map(NbtTag::extract_compound)
+            .find(|entry| {
+                entry.get_string("Name").map(String::as_str) == Some("minecraft:oak_stairs")
+            })
+            .unwrap();
+        let properties = stairs_entry.get_compound("Properties").unwrap();
+        assert_eq!(
+            properties.get_string("facing").map(String::as_str),
+            Some("east")
+        );
+        assert_eq!(
+            properties.get_string("half").map(String::as_str),
+            Some("top")
+        );
+
+        let block_entities = raw.get_list("block_entities").unwrap();
+        assert_eq!(block_entities.len(), 1);
+        let chest = block_entities[0].extract_compound().unwrap();
+        assert_eq!(
+            chest.get_string("id").map(String::as_str),
+            Some("minecraft:chest")
+        );
+        assert_eq!(chest.get_int("x"), Some(at.x * 16));
+        let items = chest.get_list("Items").unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0].extract_compound().unwrap().get_byte("Slot"),
+            Some(3)
+        );
+
+        let block_ticks = raw.get_list("block_ticks").unwrap();
+        assert_eq!(block_ticks.len(), 1);
+        let water_tick = block_ticks[0].extract_compound().unwrap();
+        assert_eq!(
+            water_tick.get_string("i").map(String::as_str),
+            Some("minecraft:water")
+        );
+        assert_eq!(water_tick.get_int("t"), Some(5));
+        assert_eq!(water_tick.get_int("p"), Some(-1));
+    }
+
+    /// Reads and writes again every chunk of the region files in `assets/regions`, which have to
+    /// be written by vanilla. The chunks must be the same after reading them back, and keep the
+    /// tags vanilla needs to load them.
+    #[test]
+    #[ignore = "needs region files written by vanilla in assets/regions"]
+    fn vanilla_region_files_round_trip() {
+        let mut chunks = 0;
+        for entry in fs::read_dir(global_path!("../../assets/regions")).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_str().unwrap().to_string();
+            // Region files are named r.<x>.<z>.mca
+            let coordinates: Vec<i32> = name
+                .split('.')
+                .filter_map(|part| part.parse().ok())
+                .collect();
+            let [region_x, region_z] = coordinates[..] else {
+                continue;
+            };
+
+            let temp_dir = TempDir::new().unwrap();
+            let level_folder = LevelFolder {
+                root_folder: temp_dir.path().to_path_buf(),
+                region_folder: temp_dir.path().join("region"),
+            };
+            fs::create_dir(&level_folder.region_folder).unwrap();
+            let region_path = level_folder.region_folder.join(&name);
+            fs::copy(&path, &region_path).unwrap();
+
+            for (x, z) in (0..32).flat_map(|x| (0..32).map(move |z| (x, z))) {
+                let at = Vector2::new(region_x * 32 + x, region_z * 32 + z);
+                let vanilla = match AnvilChunkFormat.read_chunk(&level_folder, &at) {
+                    Ok(chunk) => chunk,
+                    Err(ChunkReadingError::ChunkNotExist) => continue,
+                    Err(err) => panic!("Failed to read chunk {at:?} of {name}: {err}"),
+                };
+                let raw = read_raw_chunk(&region_path, at);
+
+                AnvilChunkFormat
+                    .write_chunk(&vanilla, &level_folder, &at)
+                    .unwrap();
+                let read = AnvilChunkFormat.read_chunk(&level_folder, &at).unwrap();
+                assert_eq!(read.subchunks, vanilla.subchunks, "{at:?} of {name}");
+                assert_eq!(read.biomes, vanilla.biomes, "{at:?} of {name}");
+                assert_eq!(read.block_ticks, vanilla.block_ticks, "{at:?} of {name}");
+
+                let written = read_raw_chunk(&region_path, at);
+                for tag in ["xPos", "yPos", "zPos", "Status"] {
+                    assert_eq!(written.get(tag), raw.get(tag), "{tag} of {at:?} of {name}");
+                }
+                let block_entities =
+                    |chunk: &NbtCompound| chunk.get_list("block_entities").map_or(0, <[_]>::len);
+                assert_eq!(block_entities(&written), block_entities(&raw));
+                chunks += 1;
+            }
+        }
+        assert!(chunks > 0, "There are no vanilla chunks in assets/regions");
+    }
+
+    /// Packs the blocks of a section like 1.13 to 1.15 did, where a block can span two longs
+    fn pack_spanning(indices: &[u64], bits: usize) -> Vec<i64> {
+        let mut data = vec![0u64; (indices.len() * bits).div_ceil(64)];
+        for (i, index) in indices.iter().enumerate() {
+            let (long, offset) = ((i * bits) / 64, (i * bits) % 64);
+            data[long] |= index << offset;
+            if offset + bits > 64 {
+                data[long + 1] |= index >> (64 - offset);
+            }
+        }
+        data.into_iter().map(|long| long as i64).collect()
+    }
+
+    /// Builds a chunk the way 1.13 to 1.17 saved it, with its data in a `Level` compound
+    fn legacy_chunk(
+        data_version: i32,
+        at: Vector2<i32>,
+        section: NbtCompound,
+        tile_entities: Vec<NbtTag>,
+    ) -> NbtCompound {
+        let mut level = NbtCompound::new();
+        level.put("xPos", at.x);
+        level.put("zPos", at.z);
+        level.put("Status", "full");
+        level.put("LastUpdate", NbtTag::Long(1234));
+        // Desert everywhere
+        level.put("Biomes", NbtTag::IntArray(vec![2; 1024].into_boxed_slice()));
+        level.put("Sections", list(vec![NbtTag::Compound(section)]));
+        level.put("TileEntities", list(tile_entities));
+
+        let mut chunk = NbtCompound::new();
+        chunk.put("DataVersion", data_version);
+        chunk.put("Level", level);
+        chunk
+    }
+
+    /// Writes the chunk into a region of its own and reads it back
+    fn read_single_chunk(
+        chunk: impl FnOnce(Vector2<i32>) -> NbtCompound,
+    ) -> Result<ChunkData, ChunkReadingError> {
+        let temp_dir = TempDir::new().unwrap();
+        let level_folder = LevelFolder {
+            root_folder: temp_dir.path().to_path_buf(),
+            region_folder: temp_dir.path().join("region"),
+        };
+        fs::create_dir(&level_folder.region_folder).unwrap();
+        let at = Vector2::new(3, 4);
+        write_vanilla_region(&level_folder.region_folder.join("r.0.0.mca"), at, chunk(at));
+        AnvilChunkFormat.read_chunk(&level_folder, &at)
+    }
+
+    fn block_at(chunk: &ChunkData, x: u8, y: u16, z: u8) -> Option<u16> {
+        chunk.get_block(ChunkRelativeBlockCoordinates {
+            x: x.into(),
+            y: Height::from_absolute(y),
+            z: z.into(),
+        })
+    }
+
+    #[test]
+    fn read_1_17_chunk() {
+        let chunk = read_single_chunk(|at| {
+            // The section at y 0, whose lowest layer is cobblestone
+            let mut section = NbtCompound::new();
+            section.put("Y", NbtTag::Byte(0));
+            section.put(
+                "Palette",
+                list(vec![
+                    palette_entry("minecraft:air", &[]),
+                    palette_entry("minecraft:cobblestone", &[]),
+                ]),
+            );
+            let mut data = vec![0; 256];
+            data[..16].fill(0x1111_1111_1111_1111);
+            section.put("BlockStates", long_array(data));
+
+            let mut diamonds = NbtCompound::new();
+            diamonds.put("Slot", NbtTag::Byte(0));
+            diamonds.put("id", "minecraft:diamond");
+            diamonds.put("Count", NbtTag::Byte(7));
+            let mut chest = NbtCompound::new();
+            chest.put("id", "minecraft:chest");
+            chest.put("x", at.x * 16);
+            chest.put("y", 1);
+            chest.put("z", at.z * 16);
+            chest.put("Items", list(vec![NbtTag::Compound(diamonds)]));
+
+            legacy_chunk(2730, at, section, vec![NbtTag::Compound(chest)])
+        })
+        .unwrap();
+
+        // Old chunks are saved in the current layout again
+        assert!(chunk.dirty);
+        let cobblestone = get_state_id_by_properties("cobblestone", &HashMap::new()).unwrap();
+        assert_eq!(block_at(&chunk, 0, 64, 0), Some(cobblestone));
+        assert_eq!(block_at(&chunk, 15, 64, 15), Some(cobblestone));
+        assert_eq!(block_at(&chunk, 0, 65, 0), Some(0));
+        // Below the old bottom of the world is empty
+        assert_eq!(block_at(&chunk, 0, 0, 0), Some(0));
+        assert_eq!(
+            chunk.biomes[0],
+            ChunkSectionBiomes::single("minecraft:desert")
+        );
+        assert_eq!(
+            chunk.biomes[10],
+            ChunkSectionBiomes::single("minecraft:desert")
+        );
+
+        let chest_position = BlockPos(Vector3::new(3 * 16, 1, 4 * 16));
+        let chest = chunk
+            .get_block_entity(&chest_position)
+            .and_then(|block_entity| block_entity.as_any().downcast_ref::<ChestBlockEntity>())
+            .unwrap();
+        assert_eq!(chest.items[0].as_ref().unwrap().item_count, 7);
+    }
+
+    #[test]
+    fn read_1_15_chunk() {
+        // 17 block states need 5 bits, so some blocks span two longs
+        let names = [
+            "minecraft:air",
+            "minecraft:grass_path",
+            "minecraft:grass",
+            "minecraft:white_wool",
+            "minecraft:orange_wool",
+            "minecraft:magenta_wool",
+            "minecraft:light_blue_wool",
+            "minecraft:yellow_wool",
+            "minecraft:lime_wool",
+            "minecraft:pink_wool",
+            "minecraft:gray_wool",
+            "minecraft:light_gray_wool",
+            "minecraft:cyan_wool",
+            "minecraft:purple_wool",
+            "minecraft:blue_wool",
+            "minecraft:brown_wool",
+            "minecraft:green_wool",
+        ];
+        let chunk = read_single_chunk(|at| {
+            let mut section = NbtCompound::new();
+            section.put("Y", NbtTag::Byte(0));
+            section.put(
+                "Palette",
+                list(names.iter().map(|name| palette_entry(name, &[])).collect()),
+            );
+            let indices: Vec<u64> = (0..4096).map(|i| i % names.len() as u64).collect();
+            section.put("BlockStates", long_array(pack_spanning(&indices, 5)));
+            legacy_chunk(2230, at, section, Vec::new())
+        })
+        .unwrap();
+
+        let state = |name: &str| get_state_id_by_properties(name, &HashMap::new()).unwrap();
+        // The 13th block is split between the first two longs
+        assert_eq!(block_at(&chunk, 12, 64, 0), Some(state("cyan_wool")));
+        assert_eq!(block_at(&chunk, 13, 64, 0), Some(state("purple_wool")));
+        // Renamed blocks get their new name
+        assert_eq!(block_at(&chunk, 1, 64, 0), Some(state("dirt_path")));
+        assert_eq!(block_at(&chunk, 2, 64, 0), Some(state("short_grass")));
+    }
+
+    #[test]
+    fn read_1_20_chunk_with_renamed_block() {
+        let chunk = read_single_chunk(|at| {
+            let mut chunk = vanilla_chunk(at.x, at.z);
+            let mut section = NbtCompound::new();
+            section.put("Y", NbtTag::Byte(1));
+            let mut block_states = NbtCompound::new();
+            block_states.put("palette", list(vec![palette_entry("minecraft:grass", &[])]));
+            section.put("block_states", block_states);
+            let sections = chunk.get_list("sections").unwrap().to_vec();
+            chunk
+                .child_tags
+                .retain(|(name, _)| name != "sections" && name != "DataVersion");
+            // 1.20.1, before grass was renamed
+            chunk.put("DataVersion", 3465);
+            chunk.put(
+                "sections",
+                list(
+                    sections
+                        .into_iter()
+                        .chain([NbtTag::Compound(section)])
+                        .collect(),
+                ),
+            );
+            chunk
+        })
+        .unwrap();
+
+        let grass = get_state_id_by_properties("short_grass", &HashMap::new()).unwrap();
+        assert_eq!(block_at(&chunk, 5, 80, 5), Some(grass));
+    }
+
+    #[test]
+    fn pre_flattening_chunk_is_not_read() {
+        let result = read_single_chunk(|at| {
+            let mut chunk = NbtCompound::new();
+            // 1.12.2
+            chunk.put("DataVersion", 1343);
+            let mut level = NbtCompound::new();
+            level.put("xPos", at.x);
+            level.put("zPos", at.z);
+            chunk.put("Level", level);
+            chunk
+        });
+        assert!(matches!(
+            result,
+            Err(ChunkReadingError::ParsingError(
+                ChunkParsingError::UnsupportedDataVersion(1343)
+            ))
+        ));
+    }
+
+    #[test]
+    fn moved_chunk_frees_sectors() {
+        let mut location_table = [0u8; 4096];
+        // Chunk 0 uses sectors 2..4, chunk 1 uses sectors 4..5
+        location_table[0..4].copy_from_slice(&[0, 0, 2, 2]);
+        location_table[4..8].copy_from_slice(&[0, 0, 4, 1]);
+
+        // A new chunk goes after all used sectors
+        assert_eq!(AnvilChunkFormat::find_free_sector(&location_table, 8, 1), 5);
+        // Chunk 0 growing moves it, the sectors it used before can be reused
+        assert_eq!(AnvilChunkFormat::find_free_sector(&location_table, 0, 3), 5);
+        assert_eq!(AnvilChunkFormat::find_free_sector(&location_table, 0, 2), 2);
+    }
+
     // TODO
     /*
     #[test]
@@ -0,0 +1,160 @@
+use std::path::Path;
+
+use pumpkin_nbt::{compound::NbtCompound, deserializer::ReadAdaptor, tag::NbtTag, Nbt};
+use pumpkin_util::math::vector2::Vector2;
+
+use super::{
+    anvil::{AnvilChunkFormat, WORLD_DATA_VERSION},
+    ChunkParsingError, ChunkReadingError, ChunkWritingError,
+};
+
+/// Reads the entities saved for the chunk at `at` from the region files in `entities_folder`.
+///
+/// Entities are stored like vanilla does, in region files separate from the chunk data.
+pub fn read_entities(
+    entities_folder: &Path,
+    at: &Vector2<i32>,
+) -> Result<Vec<NbtCompound>, ChunkReadingError> {
+    let data = match AnvilChunkFormat::read_region_data(entities_folder, at) {
+        Ok(data) => data,
+        Err(ChunkReadingError::ChunkNotExist) => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let nbt = Nbt::read(&mut ReadAdaptor::new(data.as_slice())).map_err(|err| {
+        ChunkReadingError::ParsingError(ChunkParsingError::ErrorDeserializingChunk(err.to_string()))
+    })?;
+
+    Ok(nbt
+        .root_tag
+        .get_list("Entities")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entity| entity.extract_compound().cloned())
+        .collect())
+}
+
+/// Writes the entities of the chunk at `at` to the region files in `entities_folder`, replacing
+/// the previously saved ones.
+pub fn write_entities(
+    entities_folder: &Path,
+    at: &Vector2<i32>,
+    entities: &[NbtCompound],
+) -> Result<(), ChunkWritingError> {
+    let region = (at.x >> 5, at.z >> 5);
+    let region_path = entities_folder.join(format!("r.{}.{}.mca", region.0, region.1));
+    // There is nothing to replace
+    if entities.is_empty() && !region_path.exists() {
+        return Ok(());
+    }
+    std::fs::create_dir_all(entities_folder)
+        .map_err(|err| ChunkWritingError::IoError(err.kind()))?;
+
+    let mut nbt = NbtCompound::new();
+    nbt.put_int("DataVersion", WORLD_DATA_VERSION);
+    nbt.put(
+        "Position",
+        NbtTag::IntArray(vec![at.x, at.z].into_boxed_slice()),
+    );
+    nbt.put(
+        "Entities",
+        NbtTag::List(
+            entities
+                .iter()
+                .cloned()
+                .map(NbtTag::Compound)
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+        ),
+    );
+    let raw_bytes = Nbt::new(String::new(), nbt).write();
+
+    AnvilChunkFormat::write_region_data(entities_folder, at, &raw_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+    use pumpkin_util::math::vector2::Vector2;
+    use temp_dir::TempDir;
+
+    use super::{read_entities, write_entities};
+
+    fn doubles(values: &[f64]) -> NbtTag {
+        NbtTag::List(values.iter().map(|value| NbtTag::Double(*value)).collect())
+    }
+
+    fn item(id: &str, count: i32) -> NbtTag {
+        let mut item = NbtCompound::new();
+        item.put("id", id.to_string());
+        item.put_int("count", count);
+        NbtTag::Compound(item)
+    }
+
+    fn entity(id: &str, uuid: [i32; 4], pos: [f64; 3]) -> NbtCompound {
+        let mut entity = NbtCompound::new();
+        entity.put("id", id.to_string());
+        entity.put("UUID", NbtTag::IntArray(Box::new(uuid)));
+        entity.put("Pos", doubles(&pos));
+        entity.put("Motion", doubles(&[0.0, 0.0, 0.0]));
+        entity.put(
+            "Rotation",
+            NbtTag::List(Box::new([NbtTag::Float(90.0), NbtTag::Float(0.0)])),
+        );
+        entity
+    }
+
+    #[test]
+    fn entities_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let entities_folder = temp_dir.path().join("entities");
+        let at = Vector2::new(-1, 33);
+
+        let mut item_entity = entity("minecraft:item", [1, 2, 3, 4], [-8.5, 64.0, 530.5]);
+        item_entity.put("Item", item("minecraft:diamond", 3));
+        item_entity.put_short("PickupDelay", 10);
+
+        let mut zombie = entity("minecraft:zombie", [5, 6, 7, 8], [-2.0, 70.0, 540.0]);
+        zombie.put_float("Health", 14.0);
+        zombie.put(
+            "HandItems",
+            NbtTag::List(Box::new([
+                item("minecraft:iron_sword", 1),
+                NbtTag::Compound(NbtCompound::new()),
+            ])),
+        );
+        zombie.put(
+            "ArmorItems",
+            NbtTag::List(Box::new([
+                NbtTag::Compound(NbtCompound::new()),
+                NbtTag::Compound(NbtCompound::new()),
+                NbtTag::Compound(NbtCompound::new()),
+                item("minecraft:golden_helmet", 1),
+            ])),
+        );
+
+        let mut cow = entity("minecraft:cow", [9, 10, 11, 12], [-15.0, 65.0, 543.0]);
+        cow.put_float("Health", 10.0);
+        cow.put("CustomName", r#"{"text":"Bessie"}"#.to_string());
+
+        let entities = vec![item_entity, zombie, cow];
+        write_entities(&entities_folder, &at, &entities).unwrap();
+        assert_eq!(read_entities(&entities_folder, &at).unwrap(), entities);
+
+        // Other chunks in the same region are not affected
+        assert!(read_entities(&entities_folder, &Vector2::new(0, 33))
+            .unwrap()
+            .is_empty());
+
+        // Saving a chunk without entities removes the previously saved ones
+        write_entities(&entities_folder, &at, &[]).unwrap();
+        assert!(read_entities(&entities_folder, &at).unwrap().is_empty());
+    }
+
+    #[test]
+    fn no_entities_are_not_written() {
+        let temp_dir = TempDir::new().unwrap();
+        let entities_folder = temp_dir.path().join("entities");
+        write_entities(&entities_folder, &Vector2::new(0, 0), &[]).unwrap();
+        assert!(!entities_folder.exists());
+    }
+}
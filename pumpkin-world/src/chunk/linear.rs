@@ -464,7 +464,7 @@ mod tests {
     use temp_dir::TempDir;
 
     use crate::chunk::ChunkWriter;
-    use crate::generation::{get_world_gen, Seed};
+    use crate::generation::{get_world_gen, Seed, DEFAULT_GENERATOR};
     use crate::{
         chunk::{linear::LinearChunkFormat, ChunkReader, ChunkReadingError},
         level::LevelFolder,
@@ -485,7 +485,7 @@ mod tests {
 
     #[test]
     fn test_writing() {
-        let generator = get_world_gen(Seed(0));
+        let generator = get_world_gen(DEFAULT_GENERATOR, Seed(0)).unwrap();
 
         let temp_dir = TempDir::new().unwrap();
         let level_folder = LevelFolder {
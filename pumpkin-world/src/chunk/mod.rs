@@ -2,9 +2,11 @@ use dashmap::{
     mapref::one::{Ref, RefMut},
     DashMap,
 };
-use pumpkin_data::chunk::ChunkStatus;
-use pumpkin_nbt::{deserializer::from_bytes, nbt_long_array};
-use pumpkin_util::math::{ceil_log2, vector2::Vector2};
+use pumpkin_data::chunk::{Biome, ChunkStatus};
+use pumpkin_nbt::{
+    deserializer::from_bytes, deserializer::ReadAdaptor, nbt_long_array, tag::NbtTag, Nbt,
+};
+use pumpkin_util::math::{ceil_log2, position::BlockPos, vector2::Vector2};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
@@ -15,19 +17,29 @@ use std::{
 use thiserror::Error;
 
 use crate::{
-    block::BlockState,
+    block::{
+        entity::{read_block_entity, BlockEntity},
+        registry::{get_block_and_state_by_state_id, Block, State},
+    },
     coordinates::{ChunkRelativeBlockCoordinates, Height},
     level::LevelFolder,
-    WORLD_HEIGHT,
+    light::ChunkLight,
+    scheduled_tick::{read_block_tick, ScheduledTick},
+    WORLD_HEIGHT, WORLD_LOWEST_Y, WORLD_MAX_Y,
 };
 
 pub mod anvil;
+pub mod entities;
 pub mod linear;
+pub mod ticket;
+pub mod upgrade;
 
 pub const CHUNK_AREA: usize = 16 * 16;
 pub const SUBCHUNK_VOLUME: usize = CHUNK_AREA * 16;
 pub const SUBCHUNKS_COUNT: usize = WORLD_HEIGHT / 16;
 pub const CHUNK_VOLUME: usize = CHUNK_AREA * WORLD_HEIGHT;
+/// The section y of the lowest subchunk.
+pub const LOWEST_SECTION_Y: i8 = (WORLD_LOWEST_Y / 16) as i8;
 
 /// File locks manager to prevent multiple threads from writing to the same file at the same time
 /// but allowing multiple threads to read from the same file at the same time.
@@ -73,6 +85,8 @@ pub enum ChunkWritingError {
     Compression(CompressionError),
     #[error("Chunk serializing error: {0}")]
     ChunkSerializingError(String),
+    #[error("Chunk is too large, it takes up {0} sectors")]
+    ChunkTooLarge(usize),
 }
 
 #[derive(Error, Debug)]
@@ -114,13 +128,25 @@ pub struct FileLocksManager {
     locks: DashMap<PathBuf, ()>,
 }
 
-#[derive(Clone)]
 pub struct ChunkData {
     /// See description in `Subchunks`
     pub subchunks: Subchunks,
     /// See `https://minecraft.wiki/w/Heightmap` for more info
     pub heightmap: ChunkHeightmaps,
     pub position: Vector2<i32>,
+    /// The biomes of each subchunk from the bottom up, or empty if they are not known, e.g. for
+    /// generated chunks. Unknown biomes are saved as plains.
+    pub biomes: Vec<ChunkSectionBiomes>,
+    /// The block entities in the chunk, by their position in the world.
+    pub block_entities: HashMap<BlockPos, Box<dyn BlockEntity>>,
+    /// Whether the chunk changed since it was last saved. Chunks which are not dirty are not
+    /// written when saving.
+    pub dirty: bool,
+    /// The light of the chunk, computed when it is loaded instead of being read from the save
+    pub light: ChunkLight,
+    /// The block ticks which were pending in the chunk when it was saved. Once it is loaded they
+    /// are scheduled again, see [`crate::level::Level::scheduled_ticks`].
+    pub block_ticks: Vec<ScheduledTick>,
 }
 
 /// # Subchunks
@@ -180,6 +206,8 @@ struct ChunkSection {
     y: i8,
     #[serde(skip_serializing_if = "Option::is_none")]
     block_states: Option<ChunkSectionBlockStates>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    biomes: Option<ChunkSectionBiomes>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -192,14 +220,94 @@ struct ChunkSectionBlockStates {
     palette: Vec<PaletteEntry>,
 }
 
+/// The biomes of a subchunk, stored for each 4x4x4 area of blocks.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChunkSectionBiomes {
+    #[serde(
+        serialize_with = "nbt_long_array",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub data: Option<Box<[i64]>>,
+    pub palette: Vec<String>,
+}
+
+/// The number of 4x4x4 biome areas in a subchunk
+pub const BIOMES_PER_SUBCHUNK: usize = 64;
+
+impl ChunkSectionBiomes {
+    /// Biomes of a subchunk which consists of only one biome.
+    pub fn single(biome: &str) -> Self {
+        Self {
+            data: None,
+            palette: vec![biome.to_string()],
+        }
+    }
+
+    /// Packs the biomes of a subchunk, given in yzx order.
+    pub fn from_biomes(biomes: &[Biome; BIOMES_PER_SUBCHUNK]) -> Self {
+        let mut palette: Vec<Biome> = Vec::new();
+        let indices = biomes.map(|biome| {
+            palette.iter().position(|b| *b == biome).unwrap_or_else(|| {
+                palette.push(biome);
+                palette.len() - 1
+            })
+        });
+        let palette = palette
+            .into_iter()
+            .map(|biome| format!("minecraft:{}", biome.to_name()))
+            .collect::<Vec<_>>();
+        if palette.len() == 1 {
+            return Self {
+                data: None,
+                palette,
+            };
+        }
+
+        let bits = ceil_log2(palette.len() as u32) as usize;
+        let data = indices
+            .chunks(64 / bits)
+            .map(|clump| {
+                clump
+                    .iter()
+                    .rev()
+                    .fold(0i64, |long, index| long << bits | *index as i64)
+            })
+            .collect();
+        Self {
+            data: Some(data),
+            palette,
+        }
+    }
+
+    /// The index into the palette of each 4x4x4 area, in yzx order.
+    pub fn palette_indices(&self) -> [usize; BIOMES_PER_SUBCHUNK] {
+        let Some(data) = &self.data else {
+            return [0; BIOMES_PER_SUBCHUNK];
+        };
+        let bits = ceil_log2(self.palette.len() as u32).max(1) as usize;
+        let per_long = 64 / bits;
+        let mask = (1 << bits) - 1;
+        std::array::from_fn(|i| {
+            let long = data.get(i / per_long).copied().unwrap_or(0);
+            let index = (long >> ((i % per_long) * bits)) as usize & mask;
+            // Corrupted data could point outside of the palette
+            if index < self.palette.len() {
+                index
+            } else {
+                0
+            }
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct ChunkNbt {
     data_version: i32,
     #[serde(rename = "xPos")]
     x_pos: i32,
-    // #[serde(rename = "yPos")]
-    //y_pos: i32,
+    #[serde(rename = "yPos", default)]
+    y_pos: i32,
     #[serde(rename = "zPos")]
     z_pos: i32,
     status: ChunkStatus,
@@ -245,6 +353,31 @@ impl Default for ChunkHeightmaps {
     }
 }
 
+impl ChunkHeightmaps {
+    /// How many bits one height takes in the packed heightmaps
+    fn bits() -> usize {
+        ceil_log2(WORLD_HEIGHT as u32 + 1) as usize
+    }
+
+    /// Gets the height of the column, counted from the bottom of the world, or 0 if it is empty
+    fn get(heights: &[i64], column: usize) -> i64 {
+        let bits = Self::bits();
+        let per_long = 64 / bits;
+        heights.get(column / per_long).map_or(0, |long| {
+            long >> (column % per_long * bits) & ((1 << bits) - 1)
+        })
+    }
+
+    fn set(heights: &mut [i64], column: usize, height: i64) {
+        let bits = Self::bits();
+        let per_long = 64 / bits;
+        let shift = column % per_long * bits;
+        if let Some(long) = heights.get_mut(column / per_long) {
+            *long = *long & !(((1 << bits) - 1) << shift) | height << shift;
+        }
+    }
+}
+
 impl Subchunk {
     /// Gets the given block in the chunk
     pub fn get_block(&self, position: ChunkRelativeBlockCoordinates) -> Option<u16> {
@@ -327,7 +460,7 @@ impl Subchunks {
         match self {
             Self::Single(block) => {
                 if *block != new_block {
-                    let mut subchunks = vec![Subchunk::Single(0); SUBCHUNKS_COUNT];
+                    let mut subchunks = vec![Subchunk::Single(*block); SUBCHUNKS_COUNT];
 
                     subchunks[(position.y.get_absolute() / 16) as usize]
                         .set_block(position, new_block);
@@ -362,15 +495,88 @@ impl Subchunks {
 }
 
 impl ChunkData {
+    /// Creates a chunk which has not been saved yet, so it is dirty.
+    pub fn new(subchunks: Subchunks, heightmap: ChunkHeightmaps, position: Vector2<i32>) -> Self {
+        Self {
+            subchunks,
+            heightmap,
+            position,
+            biomes: Vec::new(),
+            block_entities: HashMap::new(),
+            dirty: true,
+            light: ChunkLight::default(),
+            block_ticks: Vec::new(),
+        }
+    }
+
     /// Gets the given block in the chunk
     pub fn get_block(&self, position: ChunkRelativeBlockCoordinates) -> Option<u16> {
         self.subchunks.get_block(position)
     }
 
-    /// Sets the given block in the chunk, returning the old block
+    /// Gets the biome of the 4x4x4 area the position is in, or `None` if the biomes of the chunk
+    /// are not known
+    pub fn get_biome(&self, position: ChunkRelativeBlockCoordinates) -> Option<Biome> {
+        let y = position.y.get_absolute();
+        let section = self.biomes.get(usize::from(y / 16))?;
+        let index = usize::from(y % 16 / 4) * 16
+            + usize::from(*position.z / 4) * 4
+            + usize::from(*position.x / 4);
+        let name = section.palette.get(section.palette_indices()[index])?;
+        Biome::from_name(name.strip_prefix("minecraft:").unwrap_or(name))
+    }
+
+    /// Sets the given block in the chunk and updates the heightmaps
     pub fn set_block(&mut self, position: ChunkRelativeBlockCoordinates, block_id: u16) {
-        // TODO @LUK_ESC? update the heightmap
         self.subchunks.set_block(position, block_id);
+        self.update_heightmaps(position);
+        self.dirty = true;
+    }
+
+    /// Updates the heightmaps of the column after the block at the position changed
+    fn update_heightmaps(&mut self, position: ChunkRelativeBlockCoordinates) {
+        let column = usize::from(*position.z) * 16 + usize::from(*position.x);
+        let world_surface = self.updated_height(
+            position,
+            ChunkHeightmaps::get(&self.heightmap.world_surface, column),
+            |_, state| !state.air,
+        );
+        ChunkHeightmaps::set(&mut self.heightmap.world_surface, column, world_surface);
+        let motion_blocking = self.updated_height(
+            position,
+            ChunkHeightmaps::get(&self.heightmap.motion_blocking, column),
+            |block, state| !state.air && blocks_motion(block, state),
+        );
+        ChunkHeightmaps::set(&mut self.heightmap.motion_blocking, column, motion_blocking);
+    }
+
+    /// The height of a column after the block at the position changed, given the height before
+    fn updated_height(
+        &self,
+        position: ChunkRelativeBlockCoordinates,
+        height: i64,
+        counts: impl Fn(&Block, &State) -> bool,
+    ) -> i64 {
+        let counts_at = |y: u16| {
+            self.get_block(ChunkRelativeBlockCoordinates {
+                y: Height::from_absolute(y),
+                ..position
+            })
+            .and_then(get_block_and_state_by_state_id)
+            .is_some_and(|(block, state)| counts(block, state))
+        };
+        let y = position.y.get_absolute();
+        if counts_at(y) {
+            return height.max(i64::from(y) + 1);
+        }
+        if i64::from(y) + 1 != height {
+            return height;
+        }
+        // The highest block was removed, so the next one below is the new highest
+        (0..y)
+            .rev()
+            .find(|y| counts_at(*y))
+            .map_or(0, |y| i64::from(y) + 1)
     }
 
     /// Sets the given block in the chunk, returning the old block
@@ -385,13 +591,123 @@ impl ChunkData {
     ) {
         self.subchunks
             .set_block_no_heightmap_update(position, block);
+        self.dirty = true;
     }
 
-    #[expect(dead_code)]
-    fn calculate_heightmap(&self) -> ChunkHeightmaps {
-        // figure out how LongArray is formatted
-        // figure out how to find out if block is motion blocking
-        todo!()
+    pub fn get_block_entity(&self, position: &BlockPos) -> Option<&dyn BlockEntity> {
+        self.block_entities.get(position).map(Box::as_ref)
+    }
+
+    /// Gets a block entity to change it, which marks the chunk as dirty.
+    pub fn get_block_entity_mut(
+        &mut self,
+        position: &BlockPos,
+    ) -> Option<&mut Box<dyn BlockEntity>> {
+        self.dirty = true;
+        self.block_entities.get_mut(position)
+    }
+
+    /// Adds a block entity, replacing the one at the same position.
+    pub fn add_block_entity(&mut self, block_entity: Box<dyn BlockEntity>) {
+        self.block_entities
+            .insert(block_entity.position(), block_entity);
+        self.dirty = true;
+    }
+
+    pub fn remove_block_entity(&mut self, position: &BlockPos) -> Option<Box<dyn BlockEntity>> {
+        let removed = self.block_entities.remove(position);
+        if removed.is_some() {
+            self.dirty = true;
+        }
+        removed
+    }
+
+    /// Ticks all block entities, marking the chunk as dirty if any of them changed. Returns the
+    /// positions of the block entities whose block has to be ticked too, see
+    /// [`BlockEntity::needs_block_tick`].
+    pub fn tick_block_entities(&mut self) -> Vec<BlockPos> {
+        let mut block_ticks = Vec::new();
+        for block_entity in self.block_entities.values_mut() {
+            if block_entity.tick() {
+                self.dirty = true;
+            }
+            if block_entity.needs_block_tick() {
+                block_ticks.push(block_entity.position());
+            }
+        }
+        block_ticks
+    }
+
+    /// The y position above the highest block of the column which blocks motion or contains a
+    /// fluid, from the `MOTION_BLOCKING` heightmap
+    #[must_use]
+    pub fn motion_blocking_height(&self, x: u8, z: u8) -> i32 {
+        let column = usize::from(z) * 16 + usize::from(x);
+        let height = ChunkHeightmaps::get(&self.heightmap.motion_blocking, column);
+        i32::from(WORLD_LOWEST_Y) + height as i32
+    }
+
+    /// The y position above the highest block of the column which is not air, from the
+    /// `WORLD_SURFACE` heightmap
+    #[must_use]
+    pub fn world_surface_height(&self, x: u8, z: u8) -> i32 {
+        let column = usize::from(z) * 16 + usize::from(x);
+        let height = ChunkHeightmaps::get(&self.heightmap.world_surface, column);
+        i32::from(WORLD_LOWEST_Y) + height as i32
+    }
+
+    /// Calculates the heightmaps from the blocks, e.g. after generating the chunk.
+    pub fn calculate_heightmap(&self) -> ChunkHeightmaps {
+        // The first block from the top which is not air, and the first which blocks motion or
+        // contains a fluid, stored as the height above the bottom of the world
+        let mut world_surface = [0; CHUNK_AREA];
+        let mut motion_blocking = [0; CHUNK_AREA];
+        for z in 0..16 {
+            for x in 0..16 {
+                let column = z * 16 + x;
+                for y in (WORLD_LOWEST_Y..WORLD_MAX_Y).rev() {
+                    let coordinates = ChunkRelativeBlockCoordinates {
+                        x: (x as u8).into(),
+                        y: y.into(),
+                        z: (z as u8).into(),
+                    };
+                    let Some((block, state)) = self
+                        .get_block(coordinates)
+                        .and_then(get_block_and_state_by_state_id)
+                    else {
+                        continue;
+                    };
+                    if state.air {
+                        continue;
+                    }
+                    let height = i64::from(y - WORLD_LOWEST_Y + 1);
+                    if world_surface[column] == 0 {
+                        world_surface[column] = height;
+                    }
+                    if blocks_motion(block, state) {
+                        motion_blocking[column] = height;
+                        break;
+                    }
+                }
+            }
+        }
+
+        let bits = ChunkHeightmaps::bits();
+        let pack = |heights: [i64; CHUNK_AREA]| {
+            heights
+                .chunks(64 / bits)
+                .map(|clump| {
+                    clump
+                        .iter()
+                        .rev()
+                        .fold(0i64, |long, height| long << bits | *height)
+                })
+                .collect()
+        };
+        ChunkHeightmaps {
+            motion_blocking: pack(motion_blocking),
+            world_surface: pack(world_surface),
+        }
     }
 }
 
@@ -404,10 +720,21 @@ pub struct ChunkStatusWrapper {
 
 impl ChunkData {
     pub fn from_bytes(
-        chunk_data: &[u8],
+        chunk_bytes: &[u8],
         position: Vector2<i32>,
     ) -> Result<Self, ChunkParsingError> {
-        if from_bytes::<ChunkStatusWrapper>(chunk_data)
+        let nbt = Nbt::read(&mut ReadAdaptor::new(chunk_bytes))
+            .map_err(|e| ChunkParsingError::ErrorDeserializingChunk(e.to_string()))?;
+        let data_version = nbt.root_tag.get_int("DataVersion").unwrap_or(0);
+        if data_version < upgrade::FLATTENING_DATA_VERSION {
+            return Err(ChunkParsingError::UnsupportedDataVersion(data_version));
+        }
+        // Chunks saved before 1.18 keep their data in a `Level` compound
+        if let Some(level) = nbt.root_tag.get_compound("Level") {
+            return upgrade::read_legacy_chunk(level, data_version, position);
+        }
+
+        if from_bytes::<ChunkStatusWrapper>(chunk_bytes)
             .map_err(ChunkParsingError::FailedReadStatus)?
             .status
             != ChunkStatus::Full
@@ -415,7 +742,7 @@ impl ChunkData {
             return Err(ChunkParsingError::ChunkNotGenerated);
         }
 
-        let chunk_data = from_bytes::<ChunkNbt>(chunk_data)
+        let chunk_data = from_bytes::<ChunkNbt>(chunk_bytes)
             .map_err(|e| ChunkParsingError::ErrorDeserializingChunk(e.to_string()))?;
 
         if chunk_data.x_pos != position.x || chunk_data.z_pos != position.z {
@@ -429,82 +756,137 @@ impl ChunkData {
             // lets still continue
         }
 
-        // this needs to be boxed, otherwise it will cause a stack-overflow
-        let mut subchunks = Subchunks::Single(0);
-        let mut block_index = 0; // which block we're currently at
+        let mut subchunks = vec![Subchunk::Single(0); SUBCHUNKS_COUNT];
+        let mut biomes = vec![ChunkSectionBiomes::single("minecraft:plains"); SUBCHUNKS_COUNT];
+
+        for section in chunk_data.sections {
+            // Vanilla also saves sections above and below the world which only contain light
+            let Some(index) = section
+                .y
+                .checked_sub(LOWEST_SECTION_Y)
+                .and_then(|index| usize::try_from(index).ok())
+                .filter(|index| *index < SUBCHUNKS_COUNT)
+            else {
+                continue;
+            };
+
+            if let Some(section_biomes) = section.biomes {
+                biomes[index] = section_biomes;
+            }
 
-        for section in chunk_data.sections.into_iter() {
-            let block_states = match section.block_states {
-                Some(states) => states,
-                None => continue, // TODO @lukas0008 this should instead fill all blocks with the only element of the palette
+            let Some(block_states) = section.block_states else {
+                continue;
             };
 
             let palette = block_states
                 .palette
                 .iter()
-                .map(|entry| match BlockState::new(&entry.name) {
-                    // Block not found, Often the case when World has an newer or older version then block registry
-                    None => BlockState::AIR,
-                    Some(state) => state,
+                .map(|entry| {
+                    let properties = entry.properties.clone().unwrap_or_default();
+                    upgrade::upgrade_state(&entry.name, properties, chunk_data.data_version)
                 })
                 .collect::<Vec<_>>();
+            subchunks[index] = unpack_subchunk(&palette, block_states.data.as_deref(), false);
+        }
 
-            let block_data = match block_states.data {
-                None => {
-                    // We skipped placing an empty subchunk.
-                    // We need to increase the y coordinate of the next subchunk being placed.
-                    block_index += SUBCHUNK_VOLUME;
-                    continue;
-                }
-                Some(d) => d,
-            };
-
-            // How many bits each block has in one of the palette u64s
-            let block_bit_size = if palette.len() < 16 {
-                4
-            } else {
-                ceil_log2(palette.len() as u32).max(4)
-            };
-            // How many blocks there are in one of the palettes u64s
-            let blocks_in_palette = 64 / block_bit_size;
-
-            let mask = (1 << block_bit_size) - 1;
-            'block_loop: for block in block_data.iter() {
-                for i in 0..blocks_in_palette {
-                    let index = (block >> (i * block_bit_size)) & mask;
-                    let block = &palette[index as usize];
-
-                    // TODO allow indexing blocks directly so we can just use block_index and save some time?
-                    // this is fine because we initialized the heightmap of `blocks`
-                    // from the cached value in the world file
-                    subchunks.set_block_no_heightmap_update(
-                        ChunkRelativeBlockCoordinates {
-                            z: ((block_index % CHUNK_AREA) / 16).into(),
-                            y: Height::from_absolute((block_index / CHUNK_AREA) as u16),
-                            x: (block_index % 16).into(),
-                        },
-                        block.get_id(),
-                    );
-
-                    block_index += 1;
-
-                    // if `SUBCHUNK_VOLUME `is not divisible by `blocks_in_palette` the block_data
-                    // can sometimes spill into other subchunks. We avoid that by aborting early
-                    if (block_index % SUBCHUNK_VOLUME) == 0 {
-                        break 'block_loop;
-                    }
-                }
+        let subchunks = match subchunks.as_slice() {
+            [Subchunk::Single(block), rest @ ..]
+                if rest
+                    .iter()
+                    .all(|subchunk| *subchunk == Subchunk::Single(*block)) =>
+            {
+                Subchunks::Single(*block)
             }
-        }
+            _ => Subchunks::Multi(subchunks.try_into().unwrap()),
+        };
+
+        // Block entities can contain anything, so they are read without serde
+        let block_entities = nbt
+            .get_list("block_entities")
+            .map(|list| {
+                list.iter()
+                    .filter_map(NbtTag::extract_compound)
+                    .filter_map(read_block_entity)
+                    .map(|block_entity| (block_entity.position(), block_entity))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let block_ticks = nbt
+            .get_list("block_ticks")
+            .map(|list| {
+                list.iter()
+                    .filter_map(NbtTag::extract_compound)
+                    .filter_map(read_block_tick)
+                    .collect()
+            })
+            .unwrap_or_default();
 
         Ok(ChunkData {
             subchunks,
             heightmap: chunk_data.heightmaps,
             position,
+            biomes,
+            block_entities,
+            dirty: false,
+            light: ChunkLight::default(),
+            block_ticks,
         })
     }
 }
 
+/// Whether the block counts for the `MOTION_BLOCKING` heightmap: It collides with entities or
+/// contains a fluid
+fn blocks_motion(block: &Block, state: &State) -> bool {
+    !state.collision_shapes.is_empty() || block.name == "water" || block.name == "lava"
+}
+
+/// Unpacks the blocks of a subchunk, which are stored as indices into the palette. Before 1.16,
+/// the indices could be split between two longs.
+fn unpack_subchunk(palette: &[u16], data: Option<&[i64]>, spanning: bool) -> Subchunk {
+    // A subchunk with only one block state does not store any data
+    let Some(data) = data else {
+        return Subchunk::Single(palette.first().copied().unwrap_or(0));
+    };
+
+    // How many bits each block has in one of the palette u64s
+    let block_bit_size = if palette.len() < 16 {
+        4
+    } else {
+        ceil_log2(palette.len() as u32).max(4) as usize
+    };
+    // How many blocks there are in one of the palettes u64s
+    let blocks_in_palette = 64 / block_bit_size;
+
+    let mask = (1u64 << block_bit_size) - 1;
+    let blocks: Box<[u16; SUBCHUNK_VOLUME]> = Box::new(std::array::from_fn(|block_index| {
+        let palette_index = if spanning {
+            let bit = block_index * block_bit_size;
+            let (long, offset) = (bit / 64, bit % 64);
+            let low = data.get(long).copied().unwrap_or(0) as u64 >> offset;
+            let high = if offset + block_bit_size > 64 {
+                (data.get(long + 1).copied().unwrap_or(0) as u64) << (64 - offset)
+            } else {
+                0
+            };
+            (low | high) & mask
+        } else {
+            // Blocks don't span multiple u64s, so the last bits of each u64 can be unused
+            let packed = data
+                .get(block_index / blocks_in_palette)
+                .copied()
+                .unwrap_or(0) as u64;
+            (packed >> ((block_index % blocks_in_palette) * block_bit_size)) & mask
+        };
+        palette.get(palette_index as usize).copied().unwrap_or(0)
+    }));
+
+    if blocks.iter().all(|block| *block == blocks[0]) {
+        Subchunk::Single(blocks[0])
+    } else {
+        Subchunk::Multi(blocks)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ChunkParsingError {
     #[error("Failed reading chunk status {0}")]
@@ -513,6 +895,8 @@ pub enum ChunkParsingError {
     ChunkNotGenerated,
     #[error("Error deserializing chunk: {0}")]
     ErrorDeserializingChunk(String),
+    #[error("Chunks saved by data version {0} can't be read, they are from before 1.13")]
+    UnsupportedDataVersion(i32),
 }
 
 fn convert_index(index: ChunkRelativeBlockCoordinates) -> usize {
@@ -523,4 +907,48 @@ fn convert_index(index: ChunkRelativeBlockCoordinates) -> usize {
 pub enum ChunkSerializingError {
     #[error("Error serializing chunk: {0}")]
     ErrorSerializingChunk(pumpkin_nbt::Error),
+    #[error("Block state {0} does not exist")]
+    UnknownBlockState(u16),
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector2::Vector2;
+
+    use crate::{
+        coordinates::ChunkRelativeBlockCoordinates, generation::feature::default_state,
+        WORLD_LOWEST_Y,
+    };
+
+    use super::{ChunkData, Subchunks};
+
+    #[test]
+    fn set_block_updates_heightmaps() {
+        let mut chunk =
+            ChunkData::new(Subchunks::Single(0), Default::default(), Vector2::new(0, 0));
+        let at = |y: i16| ChunkRelativeBlockCoordinates {
+            x: 3u8.into(),
+            y: y.into(),
+            z: 5u8.into(),
+        };
+        let bottom = i32::from(WORLD_LOWEST_Y);
+        assert_eq!(chunk.motion_blocking_height(3, 5), bottom);
+
+        chunk.set_block(at(10), default_state("stone"));
+        chunk.set_block(at(64), default_state("water"));
+        chunk.set_block(at(70), default_state("short_grass"));
+        assert_eq!(chunk.motion_blocking_height(3, 5), 65);
+        assert_eq!(chunk.world_surface_height(3, 5), 71);
+        // Other columns stay empty
+        assert_eq!(chunk.motion_blocking_height(4, 5), bottom);
+
+        // Removing the highest block finds the next one below
+        chunk.set_block(at(64), 0);
+        assert_eq!(chunk.motion_blocking_height(3, 5), 11);
+        chunk.set_block(at(70), 0);
+        assert_eq!(chunk.world_surface_height(3, 5), 11);
+        chunk.set_block(at(10), 0);
+        assert_eq!(chunk.motion_blocking_height(3, 5), bottom);
+        assert_eq!(chunk.world_surface_height(3, 5), bottom);
+    }
 }
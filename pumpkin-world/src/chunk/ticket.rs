@@ -0,0 +1,314 @@
+//! Chunk tickets decide which chunks stay loaded and which of them are ticked, like in vanilla.
+//!
+//! A ticket is placed on a chunk with a level. The chunks around it get the level plus their
+//! distance to it, and each chunk has the lowest level any ticket gives it. The lower the level,
+//! the more happens in the chunk:
+//!
+//! - [`FULL_CHUNK_LEVEL`] and below: the chunk is kept loaded once something loaded it, like a
+//!   border chunk at the edge of the view distance
+//! - [`BLOCK_TICKING_LEVEL`] and below: blocks are ticked, e.g. random ticks and block entities
+//! - [`ENTITY_TICKING_LEVEL`] and below: entities are ticked as well
+//!
+//! Chunks without a level are unloaded.
+
+use std::collections::HashMap;
+
+use pumpkin_util::math::vector2::Vector2;
+
+/// The highest level at which a chunk stays loaded
+pub const FULL_CHUNK_LEVEL: u8 = 33;
+/// The highest level at which the blocks of a chunk are ticked
+pub const BLOCK_TICKING_LEVEL: u8 = 32;
+/// The highest level at which the entities in a chunk are ticked
+pub const ENTITY_TICKING_LEVEL: u8 = 31;
+
+/// Why a chunk is kept loaded
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TicketType {
+    /// The chunks players can see, and the chunks around them which are ticked
+    Player,
+    /// The chunks around the spawn point of the world, which are loaded at startup
+    Spawn,
+    /// The chunks a portal leads to, so the portal on the other side can be found or built
+    Portal,
+    /// The chunks a player is teleported to, until the player has arrived
+    Teleport,
+    /// Chunks kept loaded by an operator, like with `/forceload`
+    Forced,
+    /// Chunks kept loaded by the plugin with the given name
+    Plugin(String),
+    /// Chunks kept loaded while they are accessed, e.g. to change a block in them
+    Temporary,
+}
+
+impl TicketType {
+    /// How many ticks tickets of this type last, or `None` if they last until they are removed
+    #[must_use]
+    pub const fn lifetime(&self) -> Option<u64> {
+        match self {
+            Self::Portal => Some(300),
+            Self::Teleport => Some(5),
+            Self::Player | Self::Spawn | Self::Forced | Self::Plugin(_) | Self::Temporary => None,
+        }
+    }
+}
+
+/// What is done in a chunk, derived from its level
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChunkLevelType {
+    /// The chunk is not kept loaded
+    Inaccessible,
+    /// The chunk is kept loaded, but nothing in it is ticked
+    Border,
+    /// The blocks of the chunk are ticked
+    BlockTicking,
+    /// The blocks and the entities of the chunk are ticked
+    EntityTicking,
+}
+
+impl ChunkLevelType {
+    #[must_use]
+    pub const fn from_level(level: Option<u8>) -> Self {
+        match level {
+            Some(level) if level <= ENTITY_TICKING_LEVEL => Self::EntityTicking,
+            Some(level) if level <= BLOCK_TICKING_LEVEL => Self::BlockTicking,
+            Some(level) if level <= FULL_CHUNK_LEVEL => Self::Border,
+            _ => Self::Inaccessible,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Ticket {
+    ticket_type: TicketType,
+    level: u8,
+    /// The tick after which the ticket is removed, see [`TicketType::lifetime`]
+    expires_at: Option<u64>,
+}
+
+/// The tickets of a level and the chunk levels they result in
+#[derive(Default)]
+pub struct ChunkTickets {
+    tickets: HashMap<Vector2<i32>, Vec<Ticket>>,
+    /// The levels each chunk gets from the tickets around it, a chunk has the lowest of them
+    levels: HashMap<Vector2<i32>, Vec<u8>>,
+    /// How many times the tickets were ticked, which tickets expire by
+    ticks: u64,
+}
+
+impl ChunkTickets {
+    /// The level of the chunk, or `None` if no ticket reaches it
+    #[must_use]
+    pub fn level(&self, chunk: &Vector2<i32>) -> Option<u8> {
+        self.levels
+            .get(chunk)
+            .and_then(|levels| levels.iter().min().copied())
+    }
+
+    #[must_use]
+    pub fn level_type(&self, chunk: &Vector2<i32>) -> ChunkLevelType {
+        ChunkLevelType::from_level(self.level(chunk))
+    }
+
+    /// Whether a ticket of the type with the level is placed on the chunk
+    #[must_use]
+    pub fn has_ticket(&self, chunk: &Vector2<i32>, ticket_type: &TicketType, level: u8) -> bool {
+        self.tickets.get(chunk).is_some_and(|tickets| {
+            tickets
+                .iter()
+                .any(|ticket| ticket.ticket_type == *ticket_type && ticket.level == level)
+        })
+    }
+
+    /// The chunks with a ticket of the type, e.g. to list the force loaded chunks
+    #[must_use]
+    pub fn chunks_with(&self, ticket_type: &TicketType) -> Vec<Vector2<i32>> {
+        self.tickets
+            .iter()
+            .filter(|(_, tickets)| {
+                tickets
+                    .iter()
+                    .any(|ticket| ticket.ticket_type == *ticket_type)
+            })
+            .map(|(chunk, _)| *chunk)
+            .collect()
+    }
+
+    /// Places a ticket on the chunk. The same ticket can be placed more than once, then it has to
+    /// be removed as often. Tickets which expire are renewed instead.
+    pub fn add(&mut self, chunk: Vector2<i32>, ticket_type: TicketType, level: u8) {
+        let expires_at = ticket_type.lifetime().map(|lifetime| self.ticks + lifetime);
+        let tickets = self.tickets.entry(chunk).or_default();
+        if expires_at.is_some() {
+            if let Some(ticket) = tickets
+                .iter_mut()
+                .find(|ticket| ticket.ticket_type == ticket_type && ticket.level == level)
+            {
+                ticket.expires_at = expires_at;
+                return;
+            }
+        }
+        tickets.push(Ticket {
+            ticket_type,
+            level,
+            expires_at,
+        });
+        self.spread(chunk, level, |levels, level| levels.push(level));
+    }
+
+    /// Removes a ticket of the type with the level from the chunk. Returns the chunks which are
+    /// no longer kept loaded because of it.
+    pub fn remove(
+        &mut self,
+        chunk: Vector2<i32>,
+        ticket_type: &TicketType,
+        level: u8,
+    ) -> Vec<Vector2<i32>> {
+        let Some(tickets) = self.tickets.get_mut(&chunk) else {
+            return Vec::new();
+        };
+        let Some(index) = tickets
+            .iter()
+            .position(|ticket| ticket.ticket_type == *ticket_type && ticket.level == level)
+        else {
+            return Vec::new();
+        };
+        tickets.swap_remove(index);
+        if tickets.is_empty() {
+            self.tickets.remove(&chunk);
+        }
+        self.unspread(chunk, level)
+    }
+
+    /// Advances the time of the tickets and removes the expired ones. Returns the chunks which
+    /// are no longer kept loaded.
+    pub fn tick(&mut self) -> Vec<Vector2<i32>> {
+        self.ticks += 1;
+        let ticks = self.ticks;
+        let mut expired = Vec::new();
+        self.tickets.retain(|chunk, tickets| {
+            tickets.retain(|ticket| {
+                let keep = ticket
+                    .expires_at
+                    .is_none_or(|expires_at| expires_at > ticks);
+                if !keep {
+                    expired.push((*chunk, ticket.level));
+                }
+                keep
+            });
+            !tickets.is_empty()
+        });
+
+        let mut unloaded = Vec::new();
+        for (chunk, level) in expired {
+            unloaded.extend(self.unspread(chunk, level));
+        }
+        unloaded
+    }
+
+    /// Takes the levels a ticket gave to the chunks around it back
+    fn unspread(&mut self, chunk: Vector2<i32>, level: u8) -> Vec<Vector2<i32>> {
+        let mut unloaded = Vec::new();
+        self.spread(chunk, level, |levels, level| {
+            if let Some(index) = levels.iter().position(|existing| *existing == level) {
+                levels.swap_remove(index);
+            }
+        });
+        // A ticket only reaches chunks up to the full chunk level, so only those have to be
+        // checked
+        let radius = i32::from(FULL_CHUNK_LEVEL.saturating_sub(level));
+        for x in -radius..=radius {
+            for z in -radius..=radius {
+                let around = Vector2::new(chunk.x + x, chunk.z + z);
+                if self.levels.get(&around).is_some_and(Vec::is_empty) {
+                    self.levels.remove(&around);
+                    unloaded.push(around);
+                }
+            }
+        }
+        unloaded
+    }
+
+    /// Calls `apply` with the level the ticket gives each chunk it reaches
+    fn spread(&mut self, chunk: Vector2<i32>, level: u8, mut apply: impl FnMut(&mut Vec<u8>, u8)) {
+        let radius = FULL_CHUNK_LEVEL.saturating_sub(level);
+        let reach = i32::from(radius);
+        for x in -reach..=reach {
+            for z in -reach..=reach {
+                let distance = u8::try_from(x.abs().max(z.abs())).unwrap_or(radius);
+                let around = Vector2::new(chunk.x + x, chunk.z + z);
+                apply(self.levels.entry(around).or_default(), level + distance);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn levels_spread_around_tickets() {
+        let mut tickets = ChunkTickets::default();
+        tickets.add(Vector2::new(0, 0), TicketType::Forced, ENTITY_TICKING_LEVEL);
+
+        assert_eq!(
+            tickets.level_type(&Vector2::new(0, 0)),
+            ChunkLevelType::EntityTicking
+        );
+        assert_eq!(
+            tickets.level_type(&Vector2::new(1, -1)),
+            ChunkLevelType::BlockTicking
+        );
+        assert_eq!(
+            tickets.level_type(&Vector2::new(-2, 1)),
+            ChunkLevelType::Border
+        );
+        assert_eq!(
+            tickets.level_type(&Vector2::new(3, 0)),
+            ChunkLevelType::Inaccessible
+        );
+
+        let unloaded = tickets.remove(
+            Vector2::new(0, 0),
+            &TicketType::Forced,
+            ENTITY_TICKING_LEVEL,
+        );
+        assert_eq!(unloaded.len(), 25);
+        assert_eq!(tickets.level(&Vector2::new(0, 0)), None);
+    }
+
+    #[test]
+    fn chunks_stay_loaded_while_a_ticket_is_left() {
+        let mut tickets = ChunkTickets::default();
+        let chunk = Vector2::new(4, 2);
+        tickets.add(chunk, TicketType::Player, FULL_CHUNK_LEVEL);
+        tickets.add(chunk, TicketType::Player, FULL_CHUNK_LEVEL);
+        tickets.add(chunk, TicketType::Spawn, BLOCK_TICKING_LEVEL);
+
+        assert!(tickets
+            .remove(chunk, &TicketType::Player, FULL_CHUNK_LEVEL)
+            .is_empty());
+        assert!(tickets
+            .remove(chunk, &TicketType::Spawn, BLOCK_TICKING_LEVEL)
+            .iter()
+            .all(|unloaded| *unloaded != chunk));
+        assert_eq!(tickets.level(&chunk), Some(FULL_CHUNK_LEVEL));
+        assert_eq!(
+            tickets.remove(chunk, &TicketType::Player, FULL_CHUNK_LEVEL),
+            vec![chunk]
+        );
+    }
+
+    #[test]
+    fn tickets_expire() {
+        let mut tickets = ChunkTickets::default();
+        let chunk = Vector2::new(0, 0);
+        tickets.add(chunk, TicketType::Teleport, FULL_CHUNK_LEVEL);
+        for _ in 0..4 {
+            assert!(tickets.tick().is_empty());
+        }
+        assert_eq!(tickets.tick(), vec![chunk]);
+        assert!(!tickets.has_ticket(&chunk, &TicketType::Teleport, FULL_CHUNK_LEVEL));
+    }
+}
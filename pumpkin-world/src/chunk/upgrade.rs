@@ -0,0 +1,258 @@
+//! Reads chunks saved by older vanilla versions, so existing worlds can be imported.
+//!
+//! Chunks from 1.13 to 1.17 keep their data in a `Level` compound, with 16 sections between y 0
+//! and 256 and numeric biome ids. They are converted into the current layout when they are read
+//! and written in it once they are saved again. Blocks which were renamed since are upgraded in
+//! chunks of any version.
+//!
+//! Chunks saved before 1.13 use numeric block ids instead of block names, those can't be read.
+
+use std::collections::HashMap;
+
+use pumpkin_data::chunk::Biome;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::vector2::Vector2;
+
+use crate::block::{entity::read_block_entity, registry::get_state_id_by_properties};
+
+use super::{
+    unpack_subchunk, ChunkData, ChunkParsingError, ChunkSectionBiomes, Subchunk, Subchunks,
+    BIOMES_PER_SUBCHUNK, LOWEST_SECTION_Y, SUBCHUNKS_COUNT,
+};
+
+/// The first data version with block names instead of numeric ids (17w47a)
+pub const FLATTENING_DATA_VERSION: i32 = 1451;
+/// The first data version whose block states don't span two longs (20w17a)
+const NON_SPANNING_DATA_VERSION: i32 = 2529;
+/// The first data version which stores biomes in 4x4x4 areas instead of columns (19w36a)
+const BIOMES_3D_DATA_VERSION: i32 = 2203;
+/// The first data version in which cauldrons with water are their own block (1.17)
+const WATER_CAULDRON_DATA_VERSION: i32 = 2724;
+
+/// Blocks which were renamed, with the first release which uses the new name
+const RENAMED_BLOCKS: [(i32, &str, &str); 5] = [
+    // 1.14 added stone slabs, the old ones are smooth stone slabs
+    (1952, "minecraft:stone_slab", "minecraft:smooth_stone_slab"),
+    (1952, "minecraft:sign", "minecraft:oak_sign"),
+    (1952, "minecraft:wall_sign", "minecraft:oak_wall_sign"),
+    (2724, "minecraft:grass_path", "minecraft:dirt_path"),
+    (3698, "minecraft:grass", "minecraft:short_grass"),
+];
+
+/// The states old chunks have which don't exist anymore: Pre 1.13 statuses, and statuses without
+/// namespace before 1.18
+const FULL_STATUSES: [&str; 4] = ["minecraft:full", "full", "fullchunk", "postprocessed"];
+
+/// Gets the state of a block saved by the given data version, upgrading blocks which were
+/// renamed or split since. Unknown blocks are air.
+pub(crate) fn upgrade_state(
+    name: &str,
+    mut properties: HashMap<String, String>,
+    data_version: i32,
+) -> u16 {
+    let mut name = name;
+    for (version, old_name, new_name) in RENAMED_BLOCKS {
+        if data_version < version && name == old_name {
+            name = new_name;
+        }
+    }
+    if data_version < WATER_CAULDRON_DATA_VERSION && name == "minecraft:cauldron" {
+        match properties.get("level").map(String::as_str) {
+            Some("0") | None => properties.clear(),
+            Some(_) => name = "minecraft:water_cauldron",
+        }
+    }
+    get_state_id_by_properties(name, &properties).unwrap_or_else(|| {
+        log::debug!("Unknown block {name} in chunk saved by data version {data_version}");
+        0
+    })
+}
+
+/// The biome with the numeric id biomes had before 1.18, mapped to the biome which replaced it
+fn legacy_biome(id: i32) -> Biome {
+    let name = match id {
+        0 => "ocean",
+        2 | 17 | 130 => "desert",
+        3 | 20 => "windswept_hills",
+        4 | 18 => "forest",
+        5 | 19 | 133 => "taiga",
+        6 | 134 => "swamp",
+        7 => "river",
+        8 => "nether_wastes",
+        9 => "the_end",
+        10 => "frozen_ocean",
+        11 => "frozen_river",
+        12 | 13 => "snowy_plains",
+        14 | 15 => "mushroom_fields",
+        16 => "beach",
+        21 | 22 | 149 => "jungle",
+        23 | 151 => "sparse_jungle",
+        24 => "deep_ocean",
+        25 => "stony_shore",
+        26 => "snowy_beach",
+        27 | 28 => "birch_forest",
+        29 | 157 => "dark_forest",
+        30 | 31 | 158 => "snowy_taiga",
+        32 | 33 => "old_growth_pine_taiga",
+        34 => "windswept_forest",
+        35 => "savanna",
+        36 => "savanna_plateau",
+        37 | 39 | 167 => "badlands",
+        38 | 166 => "wooded_badlands",
+        40 => "small_end_islands",
+        41 => "end_midlands",
+        42 => "end_highlands",
+        43 => "end_barrens",
+        44 | 47 => "warm_ocean",
+        45 => "lukewarm_ocean",
+        46 => "cold_ocean",
+        48 => "deep_lukewarm_ocean",
+        49 => "deep_cold_ocean",
+        50 => "deep_frozen_ocean",
+        127 => "the_void",
+        129 => "sunflower_plains",
+        131 | 162 => "windswept_gravelly_hills",
+        132 => "flower_forest",
+        140 => "ice_spikes",
+        155 | 156 => "old_growth_birch_forest",
+        160 | 161 => "old_growth_spruce_taiga",
+        163 | 164 => "windswept_savanna",
+        165 => "eroded_badlands",
+        168 | 169 => "bamboo_jungle",
+        170 => "soul_sand_valley",
+        171 => "crimson_forest",
+        172 => "warped_forest",
+        173 => "basalt_deltas",
+        174 => "dripstone_caves",
+        175 => "lush_caves",
+        _ => "plains",
+    };
+    Biome::from_name(name).unwrap_or(Biome::Plains)
+}
+
+/// Converts the biome ids of an old chunk into the biomes of each subchunk. Before 1.15 there is
+/// one biome per column, after that one per 4x4x4 area between y 0 and 256.
+fn legacy_biomes(ids: &[i32], data_version: i32) -> Vec<ChunkSectionBiomes> {
+    let biome_at = |x: usize, cell_y: usize, z: usize| {
+        let id = if data_version < BIOMES_3D_DATA_VERSION {
+            ids.get((z * 4) * 16 + x * 4)
+        } else {
+            ids.get((cell_y.min(63) * 4 + z) * 4 + x)
+        };
+        legacy_biome(id.copied().unwrap_or(1))
+    };
+
+    (0..SUBCHUNKS_COUNT)
+        .map(|index| {
+            let section_y = index as i32 + i32::from(LOWEST_SECTION_Y);
+            let biomes: [Biome; BIOMES_PER_SUBCHUNK] = std::array::from_fn(|i| {
+                let (y, z, x) = (i / 16, (i / 4) % 4, i % 4);
+                // The new depths and heights get the biomes of the old bottom and top
+                let cell_y = (section_y * 4 + y as i32).clamp(0, 63) as usize;
+                biome_at(x, cell_y, z)
+            });
+            ChunkSectionBiomes::from_biomes(&biomes)
+        })
+        .collect()
+}
+
+/// Reads a chunk saved by 1.13 to 1.17, whose data is in the `Level` compound
+pub(crate) fn read_legacy_chunk(
+    level: &NbtCompound,
+    data_version: i32,
+    position: Vector2<i32>,
+) -> Result<ChunkData, ChunkParsingError> {
+    let status = level.get_string("Status").map(String::as_str);
+    if !status.is_some_and(|status| FULL_STATUSES.contains(&status)) {
+        return Err(ChunkParsingError::ChunkNotGenerated);
+    }
+
+    let (x_pos, z_pos) = (level.get_int("xPos"), level.get_int("zPos"));
+    if x_pos != Some(position.x) || z_pos != Some(position.z) {
+        log::error!(
+            "Expected chunk at {}:{}, but got {:?}:{:?}",
+            position.x,
+            position.z,
+            x_pos,
+            z_pos
+        );
+        // lets still continue
+    }
+
+    let spanning = data_version < NON_SPANNING_DATA_VERSION;
+    let mut subchunks = vec![Subchunk::Single(0); SUBCHUNKS_COUNT];
+    for section in level.get_list("Sections").unwrap_or_default() {
+        let Some(section) = section.extract_compound() else {
+            continue;
+        };
+        // Sections below and above the world only contain light
+        let Some(index) = section
+            .get_byte("Y")
+            .and_then(|y| y.checked_sub(LOWEST_SECTION_Y))
+            .and_then(|index| usize::try_from(index).ok())
+            .filter(|index| *index < SUBCHUNKS_COUNT)
+        else {
+            continue;
+        };
+        let Some(palette) = section.get_list("Palette") else {
+            continue;
+        };
+        let palette: Vec<u16> = palette
+            .iter()
+            .filter_map(NbtTag::extract_compound)
+            .map(|entry| {
+                let name = entry
+                    .get_string("Name")
+                    .map_or("minecraft:air", String::as_str);
+                let properties = entry
+                    .get_compound("Properties")
+                    .map(|properties| {
+                        properties
+                            .child_tags
+                            .iter()
+                            .filter_map(|(name, value)| {
+                                Some((name.clone(), value.extract_string()?.clone()))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                upgrade_state(name, properties, data_version)
+            })
+            .collect();
+        subchunks[index] =
+            unpack_subchunk(&palette, section.get_long_array("BlockStates"), spanning);
+    }
+
+    let subchunks = match subchunks.as_slice() {
+        [Subchunk::Single(block), rest @ ..]
+            if rest
+                .iter()
+                .all(|subchunk| *subchunk == Subchunk::Single(*block)) =>
+        {
+            Subchunks::Single(*block)
+        }
+        _ => Subchunks::Multi(subchunks.try_into().unwrap()),
+    };
+
+    // TODO: Entities were stored in the chunk before 1.17, read them into the entities folder
+    let block_entities = level
+        .get_list("TileEntities")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(NbtTag::extract_compound)
+        .filter_map(read_block_entity)
+        .map(|block_entity| (block_entity.position(), block_entity))
+        .collect();
+
+    let mut chunk = ChunkData::new(subchunks, Default::default(), position);
+    // The heightmaps of old chunks only cover the old height of the world
+    chunk.heightmap = chunk.calculate_heightmap();
+    chunk.biomes = legacy_biomes(
+        level.get_int_array("Biomes").unwrap_or_default(),
+        data_version,
+    );
+    chunk.block_entities = block_entities;
+    // Saved in the current layout once it is unloaded
+    chunk.dirty = true;
+    Ok(chunk)
+}
@@ -10,12 +10,12 @@ pub enum Dimension {
 }
 
 impl Dimension {
-    pub fn into_level(&self, mut base_directory: PathBuf) -> Level {
-        match self {
-            Dimension::OverWorld => {}
-            Dimension::Nether => base_directory.push("DIM-1"),
-            Dimension::End => base_directory.push("DIM1"),
-        }
-        Level::from_root_folder(base_directory)
+    pub fn into_level(&self, base_directory: PathBuf) -> Level {
+        let folder = match self {
+            Dimension::OverWorld => return Level::from_root_folder(base_directory),
+            Dimension::Nether => base_directory.join("DIM-1"),
+            Dimension::End => base_directory.join("DIM1"),
+        };
+        Level::from_dimension_folder(base_directory, folder)
     }
 }
@@ -0,0 +1,486 @@
+use std::{collections::HashSet, f32::consts::PI, sync::LazyLock};
+
+use pumpkin_data::tag::{get_tag_values, RegistryKey};
+use pumpkin_util::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+
+use crate::{
+    block::{registry::get_block, BlockDirection},
+    WORLD_HEIGHT, WORLD_LOWEST_Y,
+};
+
+use super::feature::{
+    default_state, large_feature_random,
+    ore::{HeightProvider, VerticalAnchor},
+    ChunkRegion,
+};
+
+/// How many chunks away from its start a carver can reach
+const CARVER_RANGE: i32 = 8;
+/// The range of a single tunnel system in chunks, which limits the length of its tunnels
+const TUNNEL_RANGE: i32 = 4;
+/// The top blocks of the world which are never carved, so caves do not cut the build limit
+const PROTECTED_BLOCKS_ON_TOP: i32 = 7;
+/// Carved blocks at or below this height become lava
+const LAVA_LEVEL: i32 = WORLD_LOWEST_Y as i32 + 8;
+
+struct CarverBlocks {
+    air: u16,
+    lava: u16,
+    grass_block: u16,
+    mycelium: u16,
+    dirt: u16,
+    /// The states carvers cut through, from `#minecraft:overworld_carver_replaceables`
+    replaceable: HashSet<u16>,
+    /// Water and lava, which caves do not cut into
+    fluids: HashSet<u16>,
+}
+
+fn all_states(blocks: impl IntoIterator<Item = impl AsRef<str>>) -> HashSet<u16> {
+    blocks
+        .into_iter()
+        .filter_map(|block| {
+            let states = get_block(block.as_ref())?.states.iter();
+            Some(states.map(|state| state.id).collect::<Vec<_>>())
+        })
+        .flatten()
+        .collect()
+}
+
+static BLOCKS: LazyLock<CarverBlocks> = LazyLock::new(|| CarverBlocks {
+    air: default_state("air"),
+    lava: default_state("lava"),
+    grass_block: default_state("grass_block"),
+    mycelium: default_state("mycelium"),
+    dirt: default_state("dirt"),
+    replaceable: all_states(
+        get_tag_values(
+            RegistryKey::Block,
+            "minecraft:overworld_carver_replaceables",
+        )
+        .into_iter()
+        .flatten()
+        .flatten(),
+    ),
+    fluids: all_states(["water", "lava"]),
+});
+
+/// A configured cave carver, like `minecraft:cave` of vanilla
+struct CaveCarver {
+    probability: f32,
+    y: HeightProvider,
+}
+
+/// The carvers of the overworld in the order vanilla runs them. All overworld biomes have the same
+/// carvers, so the biome is not checked.
+const CAVE: CaveCarver = CaveCarver {
+    probability: 0.15,
+    y: HeightProvider::Uniform {
+        min_inclusive: VerticalAnchor::AboveBottom(8),
+        max_inclusive: VerticalAnchor::Absolute(180),
+    },
+};
+const CAVE_EXTRA_UNDERGROUND: CaveCarver = CaveCarver {
+    probability: 0.07,
+    y: HeightProvider::Uniform {
+        min_inclusive: VerticalAnchor::AboveBottom(8),
+        max_inclusive: VerticalAnchor::Absolute(47),
+    },
+};
+const CANYON_PROBABILITY: f32 = 0.01;
+const CANYON_Y: HeightProvider = HeightProvider::Uniform {
+    min_inclusive: VerticalAnchor::Absolute(10),
+    max_inclusive: VerticalAnchor::Absolute(67),
+};
+
+fn uniform(random: &mut LegacyRand, min: f32, max: f32) -> f32 {
+    min + random.next_f32() * (max - min)
+}
+
+/// Carves the caves and canyons reaching into the chunk of the region, after the base terrain
+/// and before features. Like vanilla, carvers starting up to 8 chunks away are generated and cut
+/// to the region, so caves cross chunk borders.
+///
+/// Carved blocks become air, or lava near the bottom of the world. Aquifers are not considered
+/// yet, so blocks next to water or lava are left in place to keep oceans from draining into caves.
+pub fn carve(region: &mut ChunkRegion, seed: u64) {
+    let mut carving = Carving {
+        region,
+        mask: vec![false; 16 * 16 * WORLD_HEIGHT],
+        blocks: &BLOCKS,
+    };
+    let center = carving.region.chunk_position();
+    for chunk_x in center.x - CARVER_RANGE..=center.x + CARVER_RANGE {
+        for chunk_z in center.z - CARVER_RANGE..=center.z + CARVER_RANGE {
+            let chunk = Vector2::new(chunk_x, chunk_z);
+            for (index, cave) in [CAVE, CAVE_EXTRA_UNDERGROUND].iter().enumerate() {
+                let mut random = large_feature_random(seed.wrapping_add(index as u64), chunk);
+                if random.next_f32() <= cave.probability {
+                    carving.cave(&mut random, cave, chunk);
+                }
+            }
+            let mut random = large_feature_random(seed.wrapping_add(2), chunk);
+            if random.next_f32() <= CANYON_PROBABILITY {
+                carving.canyon(&mut random, chunk);
+            }
+        }
+    }
+}
+
+struct Carving<'r, 'a> {
+    region: &'r mut ChunkRegion<'a>,
+    /// The blocks of the chunk which were already carved, in yzx order
+    mask: Vec<bool>,
+    blocks: &'static CarverBlocks,
+}
+
+impl Carving<'_, '_> {
+    fn cave(&mut self, random: &mut LegacyRand, carver: &CaveCarver, chunk: Vector2<i32>) {
+        let max_distance = (TUNNEL_RANGE * 2 - 1) * 16;
+        let bound = random.next_bounded_i32(15) + 1;
+        let bound = random.next_bounded_i32(bound) + 1;
+        let tunnels = random.next_bounded_i32(bound);
+        for _ in 0..tunnels {
+            let x = f64::from(chunk.x * 16 + random.next_bounded_i32(16));
+            let y = f64::from(carver.y.sample(random));
+            let z = f64::from(chunk.z * 16 + random.next_bounded_i32(16));
+            let horizontal_multiplier = f64::from(uniform(random, 0.7, 1.4));
+            let vertical_multiplier = f64::from(uniform(random, 0.8, 1.3));
+            let floor_level = f64::from(uniform(random, -1.0, -0.4));
+            let skip = |dx: f64, dy: f64, dz: f64, _y: i32| {
+                dy <= floor_level || dx * dx + dy * dy + dz * dz >= 1.0
+            };
+
+            let mut rooms = 1;
+            if random.next_bounded_i32(4) == 0 {
+                let y_scale = f64::from(uniform(random, 0.1, 0.9));
+                let thickness = 1.0 + random.next_f32() * 6.0;
+                let horizontal_radius = 1.5 + f64::from(thickness);
+                self.carve_ellipsoid(
+                    [x + 1.0, y, z],
+                    horizontal_radius,
+                    horizontal_radius * y_scale,
+                    &skip,
+                );
+                rooms += random.next_bounded_i32(4);
+            }
+
+            for _ in 0..rooms {
+                let yaw = random.next_f32() * PI * 2.0;
+                let pitch = (random.next_f32() - 0.5) / 4.0;
+                let mut thickness = random.next_f32() * 2.0 + random.next_f32();
+                if random.next_bounded_i32(10) == 0 {
+                    thickness *= random.next_f32() * random.next_f32() * 3.0 + 1.0;
+                }
+                let branch_count = max_distance - random.next_bounded_i32(max_distance / 4);
+                let tunnel = Tunnel {
+                    position: [x, y, z],
+                    thickness,
+                    yaw,
+                    pitch,
+                    step: 0,
+                    branch_count,
+                    y_scale: 1.0,
+                };
+                let seed = random.next_i64() as u64;
+                self.tunnel(
+                    seed,
+                    tunnel,
+                    [horizontal_multiplier, vertical_multiplier],
+                    &skip,
+                );
+            }
+        }
+    }
+
+    /// Carves a winding tunnel, which splits into two at a random step if it is thick enough
+    fn tunnel(
+        &mut self,
+        seed: u64,
+        tunnel: Tunnel,
+        multipliers: [f64; 2],
+        skip: &dyn Fn(f64, f64, f64, i32) -> bool,
+    ) {
+        let Tunnel {
+            position: [mut x, mut y, mut z],
+            thickness,
+            mut yaw,
+            mut pitch,
+            step,
+            branch_count,
+            y_scale,
+        } = tunnel;
+        let mut random = LegacyRand::from_seed(seed);
+        let split_step = random.next_bounded_i32(branch_count / 2) + branch_count / 4;
+        let steep = random.next_bounded_i32(6) == 0;
+        let mut yaw_change = 0.0f32;
+        let mut pitch_change = 0.0f32;
+
+        for step in step..branch_count {
+            let horizontal_radius =
+                1.5 + f64::from((step as f32 * PI / branch_count as f32).sin() * thickness);
+            let vertical_radius = horizontal_radius * y_scale;
+            let cos_pitch = pitch.cos();
+            x += f64::from(yaw.cos() * cos_pitch);
+            y += f64::from(pitch.sin());
+            z += f64::from(yaw.sin() * cos_pitch);
+            pitch *= if steep { 0.92 } else { 0.7 };
+            pitch += pitch_change * 0.1;
+            yaw += yaw_change * 0.1;
+            pitch_change *= 0.9;
+            yaw_change *= 0.75;
+            pitch_change += (random.next_f32() - random.next_f32()) * random.next_f32() * 2.0;
+            yaw_change += (random.next_f32() - random.next_f32()) * random.next_f32() * 4.0;
+
+            if step == split_step && thickness > 1.0 {
+                for direction in [-1.0, 1.0] {
+                    let seed = random.next_i64() as u64;
+                    let branch = Tunnel {
+                        position: [x, y, z],
+                        thickness: random.next_f32() * 0.5 + 0.5,
+                        yaw: yaw + direction * PI / 2.0,
+                        pitch: pitch / 3.0,
+                        step,
+                        branch_count,
+                        y_scale: 1.0,
+                    };
+                    self.tunnel(seed, branch, multipliers, skip);
+                }
+                return;
+            }
+
+            if random.next_bounded_i32(4) != 0 {
+                if !self.can_reach([x, z], step, branch_count, thickness) {
+                    return;
+                }
+                self.carve_ellipsoid(
+                    [x, y, z],
+                    horizontal_radius * multipliers[0],
+                    vertical_radius * multipliers[1],
+                    skip,
+                );
+            }
+        }
+    }
+
+    /// Carves a canyon: a long tunnel which is much taller than wide, with uneven walls
+    fn canyon(&mut self, random: &mut LegacyRand, chunk: Vector2<i32>) {
+        let max_distance = (TUNNEL_RANGE * 2 - 1) * 16;
+        let mut x = f64::from(chunk.x * 16 + random.next_bounded_i32(16));
+        let mut y = f64::from(CANYON_Y.sample(random));
+        let mut z = f64::from(chunk.z * 16 + random.next_bounded_i32(16));
+        let mut yaw = random.next_f32() * PI * 2.0;
+        let mut pitch = uniform(random, -0.125, 0.125);
+        let y_scale = 3.0;
+        // A trapezoid from 0 to 6 with a plateau of 2
+        let thickness = random.next_f32() * 4.0 + random.next_f32() * 2.0;
+        let branch_count = (max_distance as f32 * uniform(random, 0.75, 1.0)) as i32;
+
+        let mut random = LegacyRand::from_seed(random.next_i64() as u64);
+        // How much wider the canyon is at each height, which makes its walls uneven
+        let mut width_factor = 1.0;
+        let width_factors: Vec<f32> = (0..WORLD_HEIGHT)
+            .map(|i| {
+                if i == 0 || random.next_bounded_i32(3) == 0 {
+                    width_factor = 1.0 + random.next_f32() * random.next_f32();
+                }
+                width_factor * width_factor
+            })
+            .collect();
+        let skip = |dx: f64, dy: f64, dz: f64, y: i32| {
+            let index = (y - i32::from(WORLD_LOWEST_Y) - 1) as usize;
+            (dx * dx + dz * dz) * f64::from(width_factors[index]) + dy * dy / 6.0 >= 1.0
+        };
+
+        let mut yaw_change = 0.0f32;
+        let mut pitch_change = 0.0f32;
+        for step in 0..branch_count {
+            let horizontal_radius =
+                1.5 + f64::from((step as f32 * PI / branch_count as f32).sin() * thickness);
+            let vertical_radius = horizontal_radius * y_scale;
+            let horizontal_radius = horizontal_radius * f64::from(uniform(&mut random, 0.75, 1.0));
+            let vertical_radius = vertical_radius * f64::from(uniform(&mut random, 0.75, 1.0));
+            let cos_pitch = pitch.cos();
+            x += f64::from(yaw.cos() * cos_pitch);
+            y += f64::from(pitch.sin());
+            z += f64::from(yaw.sin() * cos_pitch);
+            pitch *= 0.7;
+            pitch += pitch_change * 0.05;
+            yaw += yaw_change * 0.05;
+            pitch_change *= 0.8;
+            yaw_change *= 0.5;
+            pitch_change += (random.next_f32() - random.next_f32()) * random.next_f32() * 2.0;
+            yaw_change += (random.next_f32() - random.next_f32()) * random.next_f32() * 4.0;
+
+            if random.next_bounded_i32(4) != 0 {
+                if !self.can_reach([x, z], step, branch_count, thickness) {
+                    return;
+                }
+                self.carve_ellipsoid([x, y, z], horizontal_radius, vertical_radius, &skip);
+            }
+        }
+    }
+
+    /// Whether a tunnel can still reach the chunk with its remaining steps
+    fn can_reach(&self, [x, z]: [f64; 2], step: i32, branch_count: i32, thickness: f32) -> bool {
+        let chunk = self.region.chunk_position();
+        let dx = x - f64::from(chunk.x * 16 + 8);
+        let dz = z - f64::from(chunk.z * 16 + 8);
+        let remaining = f64::from(branch_count - step);
+        let radius = f64::from(thickness + 2.0 + 16.0);
+        dx * dx + dz * dz - remaining * remaining <= radius * radius
+    }
+
+    fn carve_ellipsoid(
+        &mut self,
+        [x, y, z]: [f64; 3],
+        horizontal_radius: f64,
+        vertical_radius: f64,
+        skip: &dyn Fn(f64, f64, f64, i32) -> bool,
+    ) {
+        let chunk = self.region.chunk_position();
+        let (min_x, min_z) = (chunk.x * 16, chunk.z * 16);
+        let max_range = 16.0 + horizontal_radius * 2.0;
+        if (x - f64::from(min_x + 8)).abs() > max_range
+            || (z - f64::from(min_z + 8)).abs() > max_range
+        {
+            return;
+        }
+
+        let bottom = i32::from(WORLD_LOWEST_Y);
+        let x0 = ((x - horizontal_radius).floor() as i32 - min_x - 1).max(0);
+        let x1 = ((x + horizontal_radius).floor() as i32 - min_x).min(15);
+        let z0 = ((z - horizontal_radius).floor() as i32 - min_z - 1).max(0);
+        let z1 = ((z + horizontal_radius).floor() as i32 - min_z).min(15);
+        let y0 = ((y - vertical_radius).floor() as i32 - 1).max(bottom + 1);
+        let y1 = ((y + vertical_radius).floor() as i32 + 1)
+            .min(bottom + WORLD_HEIGHT as i32 - 1 - PROTECTED_BLOCKS_ON_TOP);
+
+        for relative_x in x0..=x1 {
+            let dx = (f64::from(min_x + relative_x) + 0.5 - x) / horizontal_radius;
+            for relative_z in z0..=z1 {
+                let dz = (f64::from(min_z + relative_z) + 0.5 - z) / horizontal_radius;
+                if dx * dx + dz * dz >= 1.0 {
+                    continue;
+                }
+                let mut reached_surface = false;
+                for block_y in (y0 + 1..=y1).rev() {
+                    let dy = (f64::from(block_y) - 0.5 - y) / vertical_radius;
+                    let index = ((block_y - bottom) * 256 + relative_z * 16 + relative_x) as usize;
+                    if skip(dx, dy, dz, block_y) || self.mask[index] {
+                        continue;
+                    }
+                    self.mask[index] = true;
+                    let position = Vector3::new(min_x + relative_x, block_y, min_z + relative_z);
+                    self.carve_block(position, &mut reached_surface);
+                }
+            }
+        }
+    }
+
+    fn carve_block(&mut self, position: Vector3<i32>, reached_surface: &mut bool) {
+        let blocks = self.blocks;
+        let state = self.region.get(position);
+        if state == blocks.grass_block || state == blocks.mycelium {
+            *reached_surface = true;
+        }
+        // Fluids can not flow up, so only fluids above and next to the block keep it in place
+        if !blocks.replaceable.contains(&state)
+            || BlockDirection::all()
+                .iter()
+                .filter(|direction| **direction != BlockDirection::Bottom)
+                .any(|direction| {
+                    blocks
+                        .fluids
+                        .contains(&self.region.get(position + direction.to_offset()))
+                })
+        {
+            return;
+        }
+
+        let carved = if position.y <= LAVA_LEVEL {
+            blocks.lava
+        } else {
+            blocks.air
+        };
+        self.region.set(position, carved);
+
+        // Dirt uncovered below the surface grows grass, like the top of the terrain
+        let below = position + Vector3::new(0, -1, 0);
+        if *reached_surface && self.region.get(below) == blocks.dirt {
+            self.region.set(below, blocks.grass_block);
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Tunnel {
+    position: [f64; 3],
+    thickness: f32,
+    yaw: f32,
+    pitch: f32,
+    step: i32,
+    branch_count: i32,
+    y_scale: f64,
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
+
+    use crate::{
+        chunk::{ChunkData, Subchunks},
+        generation::feature::{default_state, ChunkRegion},
+    };
+
+    use super::carve;
+
+    fn carve_chunk(seed: u64, position: Vector2<i32>) -> ChunkData {
+        let mut chunk = ChunkData::new(
+            Subchunks::Single(default_state("stone")),
+            Default::default(),
+            position,
+        );
+        carve(&mut ChunkRegion::new(&mut chunk), seed);
+        chunk
+    }
+
+    #[test]
+    fn caves_are_seeded_and_cross_chunk_borders() {
+        let seed = 1234;
+        let (stone, lava) = (default_state("stone"), default_state("lava"));
+        // The carved blocks on both sides of the borders between chunks
+        let (mut carved_west, mut carved_east) = (0, 0);
+        for chunk_x in 0..4 {
+            let mut west = carve_chunk(seed, Vector2::new(chunk_x, 0));
+            let mut east = carve_chunk(seed, Vector2::new(chunk_x + 1, 0));
+            assert_eq!(
+                west.subchunks,
+                carve_chunk(seed, Vector2::new(chunk_x, 0)).subchunks
+            );
+            let west = ChunkRegion::new(&mut west);
+            let east = ChunkRegion::new(&mut east);
+            let x = chunk_x * 16 + 16;
+            for y in -64..320 {
+                for z in 0..16 {
+                    for (region, x, carved) in [
+                        (&west, x - 1, &mut carved_west),
+                        (&east, x, &mut carved_east),
+                    ] {
+                        let state = region.get(Vector3::new(x, y, z));
+                        // Lava only replaces blocks near the bottom
+                        assert!(state != lava || y <= -56);
+                        if state != stone {
+                            *carved += 1;
+                        }
+                    }
+                }
+            }
+        }
+        // Tunnels continue across the borders, so both sides are carved about as much
+        assert!(carved_west > 0 && carved_east > 0);
+        assert!(carved_west <= carved_east * 2 && carved_east <= carved_west * 2);
+    }
+}
@@ -0,0 +1,213 @@
+use std::sync::LazyLock;
+
+use pumpkin_util::{
+    math::{position::BlockPos, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+
+use crate::{
+    block::entity::{ChestBlockEntity, SpawnerBlockEntity},
+    loot::chest::SIMPLE_DUNGEON,
+};
+
+use super::{default_state, feature_random, state_with, ChunkRegion, UNDERGROUND_STRUCTURES};
+
+struct DungeonBlocks {
+    cave_air: u16,
+    cobblestone: u16,
+    mossy_cobblestone: u16,
+    spawner: u16,
+    /// Chests facing north, east, south and west
+    chests: [u16; 4],
+}
+
+static BLOCKS: LazyLock<DungeonBlocks> = LazyLock::new(|| DungeonBlocks {
+    cave_air: default_state("cave_air"),
+    cobblestone: default_state("cobblestone"),
+    mossy_cobblestone: default_state("mossy_cobblestone"),
+    spawner: default_state("spawner"),
+    chests: ["north", "east", "south", "west"]
+        .map(|facing| state_with("chest", &[("facing", facing)])),
+});
+
+/// The entities a dungeon spawner can spawn, zombies are twice as likely
+const ENTITIES: [&str; 4] = [
+    "minecraft:skeleton",
+    "minecraft:zombie",
+    "minecraft:zombie",
+    "minecraft:spider",
+];
+
+/// Horizontal neighbours in the order of [`DungeonBlocks::chests`]
+const HORIZONTAL: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Generates the monster rooms of a chunk: cobblestone rooms in caves with a spawner and up to
+/// two chests. Like vanilla, there are 10 attempts from y 0 to the top of the world and 4 attempts
+/// below y 0, and a room is only placed where its floor and ceiling are solid and it has 1 to 5
+/// openings.
+///
+/// Rooms are kept inside of the chunk, so they are generated with the chunk alone.
+pub fn generate(region: &mut ChunkRegion, seed: u64) {
+    let chunk = region.chunk_position();
+    let mut random = feature_random(seed, chunk, 2, UNDERGROUND_STRUCTURES);
+    for (attempts, min_y, max_y) in [(10, 0, 319), (4, -58, -1)] {
+        for _ in 0..attempts {
+            let origin = Vector3::new(
+                chunk.x * 16 + 4 + random.next_bounded_i32(8),
+                random.next_inbetween_i32(min_y, max_y),
+                chunk.z * 16 + 4 + random.next_bounded_i32(8),
+            );
+            try_place(region, &mut random, origin);
+        }
+    }
+}
+
+fn try_place(region: &mut ChunkRegion, random: &mut LegacyRand, origin: Vector3<i32>) -> bool {
+    let x_radius = random.next_bounded_i32(2) + 2;
+    let z_radius = random.next_bounded_i32(2) + 2;
+    let (min_x, max_x) = (-x_radius - 1, x_radius + 1);
+    let (min_z, max_z) = (-z_radius - 1, z_radius + 1);
+    let is_wall = |x: i32, z: i32| x == min_x || x == max_x || z == min_z || z == max_z;
+
+    let mut openings = 0;
+    for x in min_x..=max_x {
+        for y in -1..=4 {
+            for z in min_z..=max_z {
+                let position = origin + Vector3::new(x, y, z);
+                if (y == -1 || y == 4) && !region.is_solid(position) {
+                    return false;
+                }
+                if is_wall(x, z)
+                    && y == 0
+                    && region.is_air(position)
+                    && region.is_air(position + Vector3::new(0, 1, 0))
+                {
+                    openings += 1;
+                }
+            }
+        }
+    }
+    if !(1..=5).contains(&openings) {
+        return false;
+    }
+
+    let blocks = &*BLOCKS;
+    for x in min_x..=max_x {
+        for y in (-1..=3).rev() {
+            for z in min_z..=max_z {
+                let position = origin + Vector3::new(x, y, z);
+                if !is_wall(x, z) && y != -1 {
+                    region.set(position, blocks.cave_air);
+                } else if y >= 0 && !region.is_solid(position + Vector3::new(0, -1, 0)) {
+                    region.set(position, blocks.cave_air);
+                } else if region.is_solid(position) {
+                    let block = if y == -1 && random.next_bounded_i32(4) != 0 {
+                        blocks.mossy_cobblestone
+                    } else {
+                        blocks.cobblestone
+                    };
+                    region.set(position, block);
+                }
+            }
+        }
+    }
+
+    for _ in 0..2 {
+        for _ in 0..3 {
+            let position = Vector3::new(
+                origin.x + random.next_bounded_i32(x_radius * 2 + 1) - x_radius,
+                origin.y,
+                origin.z + random.next_bounded_i32(z_radius * 2 + 1) - z_radius,
+            );
+            if !region.is_air(position) {
+                continue;
+            }
+            // Chests are only placed against exactly one wall, facing away from it
+            let mut walls = HORIZONTAL
+                .iter()
+                .enumerate()
+                .filter(|(_, (x, z))| region.is_solid(position + Vector3::new(*x, 0, *z)));
+            let (Some((wall, _)), None) = (walls.next(), walls.next()) else {
+                continue;
+            };
+            region.set(position, blocks.chests[(wall + 2) % 4]);
+            let mut chest = ChestBlockEntity::new("chest", BlockPos(position));
+            chest.loot_table = Some(SIMPLE_DUNGEON.name.to_string());
+            chest.loot_table_seed = random.next_i64();
+            region.add_block_entity(Box::new(chest));
+            break;
+        }
+    }
+
+    region.set(origin, blocks.spawner);
+    let entity = ENTITIES[random.next_bounded_i32(ENTITIES.len() as i32) as usize];
+    region.add_block_entity(Box::new(SpawnerBlockEntity::with_entity(
+        BlockPos(origin),
+        entity,
+    )));
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::{
+        math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+        random::{legacy_rand::LegacyRand, RandomImpl},
+    };
+
+    use crate::{
+        block::entity::{ChestBlockEntity, SpawnerBlockEntity},
+        chunk::{ChunkData, Subchunks},
+        generation::feature::{default_state, ChunkRegion},
+    };
+
+    use super::try_place;
+
+    #[test]
+    fn places_room_in_cave() {
+        let mut chunk = ChunkData::new(
+            Subchunks::Single(default_state("stone")),
+            Default::default(),
+            Vector2::new(0, 0),
+        );
+        let origin = Vector3::new(8, 20, 8);
+        let mut region = ChunkRegion::new(&mut chunk);
+        // Solid stone has no openings
+        assert!(!try_place(
+            &mut region,
+            &mut LegacyRand::from_seed(1),
+            origin
+        ));
+
+        // A tunnel from the middle through one of the walls
+        for x in 8..16 {
+            for y in 20..22 {
+                region.set(Vector3::new(x, y, 8), 0);
+            }
+        }
+        assert!(try_place(
+            &mut region,
+            &mut LegacyRand::from_seed(1),
+            origin
+        ));
+        assert_eq!(region.get(origin), default_state("spawner"));
+
+        let spawner = chunk
+            .get_block_entity(&BlockPos(origin))
+            .and_then(|block_entity| block_entity.as_any().downcast_ref::<SpawnerBlockEntity>())
+            .unwrap();
+        assert!(spawner.entity.is_some());
+        let chests: Vec<_> = chunk
+            .block_entities
+            .values()
+            .filter_map(|block_entity| block_entity.as_any().downcast_ref::<ChestBlockEntity>())
+            .collect();
+        assert!(chests.len() <= 2);
+        for chest in chests {
+            assert_eq!(
+                chest.loot_table.as_deref(),
+                Some("minecraft:chests/simple_dungeon")
+            );
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use pumpkin_util::{
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+    random::{hash_block_pos, legacy_rand::LegacyRand, RandomImpl},
+};
+
+use crate::{
+    block::{
+        entity::BlockEntity,
+        registry::{get_block, get_state_by_state_id, get_state_id_by_properties},
+    },
+    chunk::ChunkData,
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+
+pub mod dungeon;
+pub mod ore;
+pub mod tree;
+
+/// The generation step of dungeons and mineshafts, which seeds their random
+pub const UNDERGROUND_STRUCTURES: i64 = 3;
+/// The generation step of ores, which seeds their random
+pub const UNDERGROUND_ORES: i64 = 6;
+
+/// The blocks of a chunk which is being generated, by their position in the world.
+///
+/// Blocks outside of the chunk are ignored, so features and structure pieces crossing chunk borders
+/// can be placed once for every chunk they touch.
+pub struct ChunkRegion<'a> {
+    chunk: &'a mut ChunkData,
+}
+
+impl<'a> ChunkRegion<'a> {
+    pub fn new(chunk: &'a mut ChunkData) -> Self {
+        Self { chunk }
+    }
+
+    pub fn chunk_position(&self) -> Vector2<i32> {
+        self.chunk.position
+    }
+
+    pub fn contains(&self, position: Vector3<i32>) -> bool {
+        position.x >> 4 == self.chunk.position.x
+            && position.z >> 4 == self.chunk.position.z
+            && (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&position.y)
+    }
+
+    /// Gets the state at a position, or air outside of the chunk
+    pub fn get(&self, position: Vector3<i32>) -> u16 {
+        if !self.contains(position) {
+            return 0;
+        }
+        self.chunk
+            .get_block(Vector3::new(position.x & 15, position.y, position.z & 15).into())
+            .unwrap_or(0)
+    }
+
+    pub fn set(&mut self, position: Vector3<i32>, state_id: u16) {
+        if self.contains(position) {
+            self.chunk.set_block(
+                Vector3::new(position.x & 15, position.y, position.z & 15).into(),
+                state_id,
+            );
+            self.chunk.remove_block_entity(&BlockPos(position));
+        }
+    }
+
+    /// Whether the state at a position blocks movement, like stone but unlike air or water
+    pub fn is_solid(&self, position: Vector3<i32>) -> bool {
+        get_state_by_state_id(self.get(position))
+            .is_some_and(|state| !state.air && !state.collision_shapes.is_empty())
+    }
+
+    /// Whether the state at a position is air, which is always the case outside of the chunk
+    pub fn is_air(&self, position: Vector3<i32>) -> bool {
+        get_state_by_state_id(self.get(position)).is_some_and(|state| state.air)
+    }
+
+    /// Adds a block entity if it is inside of the chunk
+    pub fn add_block_entity(&mut self, block_entity: Box<dyn BlockEntity>) {
+        if self.contains(block_entity.position().0) {
+            self.chunk.add_block_entity(block_entity);
+        }
+    }
+}
+
+/// Gets the default state of a block
+pub fn default_state(block: &str) -> u16 {
+    get_block(block)
+        .map(|block| block.default_state_id)
+        .expect("Block exists")
+}
+
+/// Gets a state of a block with the given properties
+pub fn state_with(block: &str, properties: &[(&str, &str)]) -> u16 {
+    let properties: HashMap<String, String> = properties
+        .iter()
+        .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+        .collect();
+    get_state_id_by_properties(block, &properties).expect("Block exists")
+}
+
+/// The random of a feature in a chunk, seeded like vanilla from the world seed, the chunk, the
+/// generation step and the index of the feature in the step
+pub fn feature_random(seed: u64, chunk: Vector2<i32>, index: i64, step: i64) -> LegacyRand {
+    let mut random = LegacyRand::from_seed(seed);
+    let a = random.next_i64() | 1;
+    let b = random.next_i64() | 1;
+    let decoration_seed = i64::from(chunk.x * 16)
+        .wrapping_mul(a)
+        .wrapping_add(i64::from(chunk.z * 16).wrapping_mul(b))
+        ^ seed as i64;
+    LegacyRand::from_seed(decoration_seed.wrapping_add(index + 10000 * step) as u64)
+}
+
+/// The random of a structure start or carver, seeded like vanilla from the world seed and the chunk
+pub fn large_feature_random(seed: u64, chunk: Vector2<i32>) -> LegacyRand {
+    let mut random = LegacyRand::from_seed(seed);
+    let a = random.next_i64();
+    let b = random.next_i64();
+    let chunk_seed =
+        i64::from(chunk.x).wrapping_mul(a) ^ i64::from(chunk.z).wrapping_mul(b) ^ seed as i64;
+    LegacyRand::from_seed(chunk_seed as u64)
+}
+
+/// Whether a random block is placed at a position, with the given chance. The result only depends
+/// on the seed and the position, so every chunk a feature crosses makes the same choices.
+pub fn position_chance(seed: u64, position: Vector3<i32>, probability: f32) -> bool {
+    let position_seed = hash_block_pos(position.x, position.y, position.z) as u64;
+    LegacyRand::from_seed(seed ^ position_seed).next_f32() < probability
+}
@@ -0,0 +1,422 @@
+use std::{f32::consts::PI, sync::LazyLock};
+
+use pumpkin_data::{
+    chunk::Biome,
+    tag::{get_tag_values, RegistryKey},
+};
+use pumpkin_util::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+use serde::Deserialize;
+
+use crate::{
+    block::{registry::get_block, BlockDirection},
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+
+use super::{default_state, feature_random, position_chance, ChunkRegion, UNDERGROUND_ORES};
+
+/// A height in the world, relative to the bottom or top so it works for any world height
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum VerticalAnchor {
+    Absolute(i32),
+    AboveBottom(i32),
+    BelowTop(i32),
+}
+
+impl VerticalAnchor {
+    pub fn resolve(self) -> i32 {
+        match self {
+            Self::Absolute(y) => y,
+            Self::AboveBottom(offset) => i32::from(WORLD_LOWEST_Y) + offset,
+            Self::BelowTop(offset) => i32::from(WORLD_MAX_Y) - 1 - offset,
+        }
+    }
+}
+
+/// Chooses the height of an ore vein
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HeightProvider {
+    Uniform {
+        min_inclusive: VerticalAnchor,
+        max_inclusive: VerticalAnchor,
+    },
+    /// Prefers heights in the middle. Without a plateau, the chance falls off linearly to both
+    /// ends like a triangle.
+    Trapezoid {
+        min_inclusive: VerticalAnchor,
+        max_inclusive: VerticalAnchor,
+        #[serde(default)]
+        plateau: i32,
+    },
+}
+
+impl HeightProvider {
+    pub fn sample(&self, random: &mut LegacyRand) -> i32 {
+        match *self {
+            Self::Uniform {
+                min_inclusive,
+                max_inclusive,
+            } => {
+                let (min, max) = (min_inclusive.resolve(), max_inclusive.resolve());
+                if min > max {
+                    return min;
+                }
+                random.next_inbetween_i32(min, max)
+            }
+            Self::Trapezoid {
+                min_inclusive,
+                max_inclusive,
+                plateau,
+            } => {
+                let (min, max) = (min_inclusive.resolve(), max_inclusive.resolve());
+                if min > max {
+                    return min;
+                }
+                let range = max - min;
+                if plateau >= range {
+                    return random.next_inbetween_i32(min, max);
+                }
+                let slope = (range - plateau) / 2;
+                min + random.next_inbetween_i32(0, range - slope)
+                    + random.next_inbetween_i32(0, slope)
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(untagged)]
+pub enum IntProvider {
+    Constant(i32),
+    Uniform {
+        min_inclusive: i32,
+        max_inclusive: i32,
+    },
+}
+
+impl IntProvider {
+    pub fn sample(&self, random: &mut LegacyRand) -> i32 {
+        match *self {
+            Self::Constant(value) => value,
+            Self::Uniform {
+                min_inclusive,
+                max_inclusive,
+            } => random.next_inbetween_i32(min_inclusive, max_inclusive),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct OreTarget {
+    /// The block which is replaced, or a block tag like `#minecraft:stone_ore_replaceables`
+    pub target: String,
+    /// The ore which replaces it
+    pub state: String,
+}
+
+/// The configuration of an ore, in the format of `assets/ore_features.json`
+#[derive(Deserialize, Clone, Debug)]
+pub struct OreFeature {
+    pub name: String,
+    /// How many blocks a vein has at most
+    pub size: i32,
+    /// The chance that a block of the vein which touches air is not placed
+    pub discard_chance_on_air_exposure: f32,
+    /// The first target matching a block decides the ore it becomes
+    pub targets: Vec<OreTarget>,
+    /// How many veins are tried in each chunk
+    pub count: IntProvider,
+    /// If set, the veins only generate in one of this many chunks on average
+    #[serde(default)]
+    pub rarity: Option<i32>,
+    pub height: HeightProvider,
+    /// The biomes the ore generates in, or all biomes if not set
+    #[serde(default)]
+    pub biomes: Option<Vec<String>>,
+}
+
+/// The ores of the overworld, in the order vanilla places them
+pub static ORE_FEATURES: LazyLock<Vec<OreFeature>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../../../assets/ore_features.json"))
+        .expect("Could not parse ore_features.json")
+});
+
+/// An [`OreFeature`] with the block states resolved
+struct Ore {
+    feature: &'static OreFeature,
+    /// The states which are replaced and the ore replacing them
+    targets: Vec<(Vec<u16>, u16)>,
+    biomes: Option<Vec<Biome>>,
+}
+
+fn block_states(block: &str) -> Vec<u16> {
+    get_block(block)
+        .map(|block| block.states.iter().map(|state| state.id).collect())
+        .unwrap_or_default()
+}
+
+impl Ore {
+    fn new(feature: &'static OreFeature) -> Self {
+        let targets = feature
+            .targets
+            .iter()
+            .map(|target| {
+                let replaced = match target.target.strip_prefix('#') {
+                    Some(tag) => get_tag_values(RegistryKey::Block, tag)
+                        .into_iter()
+                        .flatten()
+                        .flatten()
+                        .flat_map(|block| block_states(block))
+                        .collect(),
+                    None => block_states(&target.target),
+                };
+                (replaced, default_state(&target.state))
+            })
+            .collect();
+        let biomes = feature.biomes.as_ref().map(|biomes| {
+            biomes
+                .iter()
+                .filter_map(|biome| {
+                    Biome::from_name(biome.strip_prefix("minecraft:").unwrap_or(biome))
+                })
+                .collect()
+        });
+        Self {
+            feature,
+            targets,
+            biomes,
+        }
+    }
+
+    /// Places the veins starting in `chunk` which reach into the region
+    fn generate(
+        &self,
+        region: &mut ChunkRegion,
+        random: &mut LegacyRand,
+        seed: u64,
+        chunk: Vector2<i32>,
+    ) {
+        if let Some(rarity) = self.feature.rarity {
+            if random.next_f32() >= 1.0 / rarity as f32 {
+                return;
+            }
+        }
+        for _ in 0..self.feature.count.sample(random) {
+            let x = chunk.x * 16 + random.next_bounded_i32(16);
+            let z = chunk.z * 16 + random.next_bounded_i32(16);
+            let y = self.feature.height.sample(random);
+            self.place_vein(region, random, seed, Vector3::new(x, y, z));
+        }
+    }
+
+    /// Places a vein like vanilla: spheres along a random line through the origin, which are
+    /// largest in the middle.
+    fn place_vein(
+        &self,
+        region: &mut ChunkRegion,
+        random: &mut LegacyRand,
+        seed: u64,
+        origin: Vector3<i32>,
+    ) {
+        let size = self.feature.size;
+        let angle = random.next_f32() * PI;
+        let spread = size as f32 / 8.0;
+        let radius_bound = ((size as f32 / 16.0 * 2.0 + 1.0) / 2.0).ceil() as i32;
+        let offset_x = f64::from(angle).sin() * f64::from(spread);
+        let offset_z = f64::from(angle).cos() * f64::from(spread);
+        let start = [
+            f64::from(origin.x) + offset_x,
+            f64::from(origin.y + random.next_bounded_i32(3) - 2),
+            f64::from(origin.z) + offset_z,
+        ];
+        let end = [
+            f64::from(origin.x) - offset_x,
+            f64::from(origin.y + random.next_bounded_i32(3) - 2),
+            f64::from(origin.z) - offset_z,
+        ];
+
+        let mut spheres: Vec<[f64; 4]> = (0..size)
+            .map(|i| {
+                let t = i as f32 / size as f32;
+                let lerp = |a: f64, b: f64| a + f64::from(t) * (b - a);
+                let radius = random.next_f64() * f64::from(size) / 16.0;
+                let radius = (f64::from((PI * t).sin() + 1.0) * radius + 1.0) / 2.0;
+                [
+                    lerp(start[0], end[0]),
+                    lerp(start[1], end[1]),
+                    lerp(start[2], end[2]),
+                    radius,
+                ]
+            })
+            .collect();
+
+        let min_x = origin.x - spread.ceil() as i32 - radius_bound;
+        let min_y = origin.y - 2 - radius_bound;
+        let min_z = origin.z - spread.ceil() as i32 - radius_bound;
+        let width = 2 * (spread.ceil() as i32 + radius_bound);
+        let chunk = region.chunk_position();
+        if min_x > chunk.x * 16 + 15
+            || min_x + width < chunk.x * 16
+            || min_z > chunk.z * 16 + 15
+            || min_z + width < chunk.z * 16
+        {
+            return;
+        }
+
+        // Spheres inside of larger spheres add nothing
+        for i in 0..spheres.len() {
+            for j in i + 1..spheres.len() {
+                let (a, b) = (spheres[i], spheres[j]);
+                if a[3] <= 0.0 || b[3] <= 0.0 {
+                    continue;
+                }
+                let distance =
+                    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2);
+                let radius_difference = a[3] - b[3];
+                if radius_difference * radius_difference > distance {
+                    if radius_difference > 0.0 {
+                        spheres[j][3] = -1.0;
+                    } else {
+                        spheres[i][3] = -1.0;
+                    }
+                }
+            }
+        }
+
+        for [center_x, center_y, center_z, radius] in spheres {
+            if radius < 0.0 {
+                continue;
+            }
+            let from = |center: f64, min: i32| ((center - radius).floor() as i32).max(min);
+            let (x0, y0, z0) = (
+                from(center_x, min_x),
+                from(center_y, min_y),
+                from(center_z, min_z),
+            );
+            let x1 = ((center_x + radius).floor() as i32)
+                .max(x0)
+                .min(chunk.x * 16 + 15);
+            let y1 = ((center_y + radius).floor() as i32).max(y0);
+            let z1 = ((center_z + radius).floor() as i32)
+                .max(z0)
+                .min(chunk.z * 16 + 15);
+            for x in x0.max(chunk.x * 16)..=x1 {
+                let dx = (f64::from(x) + 0.5 - center_x) / radius;
+                for y in y0..=y1 {
+                    let dy = (f64::from(y) + 0.5 - center_y) / radius;
+                    for z in z0.max(chunk.z * 16)..=z1 {
+                        let dz = (f64::from(z) + 0.5 - center_z) / radius;
+                        if dx * dx + dy * dy + dz * dz < 1.0 {
+                            self.place_block(region, seed, Vector3::new(x, y, z));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn place_block(&self, region: &mut ChunkRegion, seed: u64, position: Vector3<i32>) {
+        if !region.contains(position) {
+            return;
+        }
+        let state = region.get(position);
+        let Some((_, ore)) = self
+            .targets
+            .iter()
+            .find(|(replaced, _)| replaced.contains(&state))
+        else {
+            return;
+        };
+        let discard_chance = self.feature.discard_chance_on_air_exposure;
+        let check_air = discard_chance >= 1.0
+            || (discard_chance > 0.0 && position_chance(seed, position, discard_chance));
+        // Neighbours outside of the chunk are not known yet, so they do not count as air
+        if check_air
+            && BlockDirection::all().iter().any(|direction| {
+                let neighbour = position + direction.to_offset();
+                region.contains(neighbour) && region.is_air(neighbour)
+            })
+        {
+            return;
+        }
+        region.set(position, *ore);
+    }
+}
+
+static ORES: LazyLock<Vec<Ore>> = LazyLock::new(|| ORE_FEATURES.iter().map(Ore::new).collect());
+
+/// Generates the ores reaching into the chunk of the region. Veins are generated for the
+/// surrounding chunks too and cut to the region, so they cross chunk borders.
+///
+/// `biome_at` returns the biome at a block position. Ores limited to some biomes check the biome
+/// in the middle of the chunk the vein starts in.
+pub fn generate(region: &mut ChunkRegion, seed: u64, biome_at: impl Fn(Vector3<i32>) -> Biome) {
+    let center = region.chunk_position();
+    for chunk_x in center.x - 1..=center.x + 1 {
+        for chunk_z in center.z - 1..=center.z + 1 {
+            let chunk = Vector2::new(chunk_x, chunk_z);
+            let mut biome = None;
+            for (index, ore) in ORES.iter().enumerate() {
+                if let Some(biomes) = &ore.biomes {
+                    let biome = *biome.get_or_insert_with(|| {
+                        biome_at(Vector3::new(chunk.x * 16 + 8, 64, chunk.z * 16 + 8))
+                    });
+                    if !biomes.contains(&biome) {
+                        continue;
+                    }
+                }
+                let mut random = feature_random(seed, chunk, index as i64, UNDERGROUND_ORES);
+                ore.generate(region, &mut random, seed, chunk);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::chunk::Biome;
+    use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
+
+    use crate::{
+        chunk::{ChunkData, Subchunks},
+        generation::feature::{default_state, ChunkRegion},
+    };
+
+    use super::generate;
+
+    fn count_ores(seed: u64) -> [usize; 3] {
+        let mut chunk = ChunkData::new(
+            Subchunks::Single(default_state("stone")),
+            Default::default(),
+            Vector2::new(3, -7),
+        );
+        generate(&mut ChunkRegion::new(&mut chunk), seed, |_| Biome::Plains);
+        let region = ChunkRegion::new(&mut chunk);
+        let ores = ["coal_ore", "iron_ore", "emerald_ore"].map(default_state);
+        let mut counts = [0; 3];
+        for x in 48..64 {
+            for y in -64..320 {
+                for z in -112..-96 {
+                    let state = region.get(Vector3::new(x, y, z));
+                    if let Some(index) = ores.iter().position(|ore| *ore == state) {
+                        counts[index] += 1;
+                    }
+                }
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn ores_are_seeded_and_filtered_by_biome() {
+        let counts = count_ores(42);
+        assert_eq!(counts, count_ores(42));
+        assert!(counts[0] > 0 && counts[1] > 0);
+        // Emeralds only generate in mountains
+        assert_eq!(counts[2], 0);
+    }
+}
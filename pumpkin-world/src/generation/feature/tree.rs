@@ -0,0 +1,438 @@
+//! Trees like the ones saplings grow into, with the trunk and foliage shapes of vanilla's tree
+//! features.
+//!
+//! Leaves get their `distance` to the nearest log of the tree, so they do not decay. Leaves
+//! which would be too far away from the trunk are left out.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use pumpkin_util::{math::vector3::Vector3, random::RandomImpl};
+
+use super::{default_state, state_with};
+
+/// Leaves this far away from a log, or further, decay unless they are persistent
+pub const MAX_LEAF_DISTANCE: u8 = 7;
+
+/// The offsets of the six neighbours of a block
+const NEIGHBORS: [(i32, i32, i32); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+const HORIZONTAL: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeKind {
+    Oak,
+    Birch,
+    Spruce,
+    Jungle,
+    Acacia,
+    /// Cherry trees get a wide blob of leaves instead of their curved branches
+    Cherry,
+    DarkOak,
+    /// Pale oak trees have the shape of dark oak trees
+    PaleOak,
+    MegaSpruce,
+    MegaJungle,
+}
+
+impl TreeKind {
+    /// The tree a single sapling of the wood grows into, or `None` if it needs four saplings
+    #[must_use]
+    pub fn from_wood(wood: &str) -> Option<Self> {
+        match wood {
+            "oak" => Some(Self::Oak),
+            "birch" => Some(Self::Birch),
+            "spruce" => Some(Self::Spruce),
+            "jungle" => Some(Self::Jungle),
+            "acacia" => Some(Self::Acacia),
+            "cherry" => Some(Self::Cherry),
+            _ => None,
+        }
+    }
+
+    /// The tree four saplings of the wood in a square grow into, or `None` if they grow alone
+    #[must_use]
+    pub fn mega_from_wood(wood: &str) -> Option<Self> {
+        match wood {
+            "spruce" => Some(Self::MegaSpruce),
+            "jungle" => Some(Self::MegaJungle),
+            "dark_oak" => Some(Self::DarkOak),
+            "pale_oak" => Some(Self::PaleOak),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn wood(self) -> &'static str {
+        match self {
+            Self::Oak => "oak",
+            Self::Birch => "birch",
+            Self::Spruce | Self::MegaSpruce => "spruce",
+            Self::Jungle | Self::MegaJungle => "jungle",
+            Self::Acacia => "acacia",
+            Self::Cherry => "cherry",
+            Self::DarkOak => "dark_oak",
+            Self::PaleOak => "pale_oak",
+        }
+    }
+
+    /// Whether the trunk is two by two blocks, grown from the origin towards positive x and z
+    #[must_use]
+    pub const fn is_mega(self) -> bool {
+        matches!(
+            self,
+            Self::DarkOak | Self::PaleOak | Self::MegaSpruce | Self::MegaJungle
+        )
+    }
+}
+
+/// The blocks of a tree before it is placed
+pub struct Tree {
+    pub kind: TreeKind,
+    pub logs: Vec<Vector3<i32>>,
+    pub leaves: Vec<Vector3<i32>>,
+}
+
+impl Tree {
+    /// Plans a tree of the kind whose trunk starts at the origin
+    pub fn plan(kind: TreeKind, origin: Vector3<i32>, random: &mut impl RandomImpl) -> Self {
+        let mut shape = Shape {
+            origin,
+            double_trunk: kind.is_mega(),
+            logs: Vec::new(),
+            leaves: HashSet::new(),
+        };
+        match kind {
+            TreeKind::Oak => shape.blob_tree(random, 4, 2, 2),
+            TreeKind::Birch => shape.blob_tree(random, 5, 2, 2),
+            TreeKind::Jungle => shape.blob_tree(random, 4, 8, 2),
+            TreeKind::Cherry => shape.blob_tree(random, 5, 2, 3),
+            TreeKind::Spruce => shape.spruce(random),
+            TreeKind::Acacia => shape.acacia(random),
+            TreeKind::DarkOak | TreeKind::PaleOak => shape.dark_oak(random),
+            TreeKind::MegaSpruce => shape.mega_spruce(random),
+            TreeKind::MegaJungle => shape.mega_jungle(random),
+        }
+
+        let logs: HashSet<_> = shape.logs.iter().copied().collect();
+        let mut leaves: Vec<_> = shape
+            .leaves
+            .into_iter()
+            .filter(|leaf| !logs.contains(leaf))
+            .collect();
+        leaves.sort_by_key(|leaf| (leaf.y, leaf.x, leaf.z));
+        Self {
+            kind,
+            logs: shape.logs,
+            leaves,
+        }
+    }
+
+    /// The state of the logs, standing upright
+    #[must_use]
+    pub fn log_state(&self) -> u16 {
+        default_state(&format!("{}_log", self.kind.wood()))
+    }
+
+    /// The leaves with their state, which has the distance to the nearest log. Leaves which are
+    /// too far away from the logs are left out.
+    #[must_use]
+    pub fn leaf_states(&self) -> Vec<(Vector3<i32>, u16)> {
+        let leaves_block = format!("{}_leaves", self.kind.wood());
+        let distances = leaf_distances(&self.logs, &self.leaves);
+        self.leaves
+            .iter()
+            .filter_map(|leaf| {
+                let distance = distances.get(leaf)?.to_string();
+                let state = state_with(
+                    &leaves_block,
+                    &[("distance", &distance), ("persistent", "false")],
+                );
+                Some((*leaf, state))
+            })
+            .collect()
+    }
+}
+
+/// How far each of the leaves is away from the nearest log, going through other leaves. Like
+/// in vanilla, leaves next to a log have a distance of 1. Leaves which are
+/// [`MAX_LEAF_DISTANCE`] or further away are not included.
+#[must_use]
+pub fn leaf_distances(logs: &[Vector3<i32>], leaves: &[Vector3<i32>]) -> HashMap<Vector3<i32>, u8> {
+    let leaves: HashSet<_> = leaves.iter().copied().collect();
+    let mut distances = HashMap::new();
+    let mut queue: VecDeque<_> = logs.iter().map(|log| (*log, 0)).collect();
+    while let Some((position, distance)) = queue.pop_front() {
+        if distance + 1 >= MAX_LEAF_DISTANCE {
+            continue;
+        }
+        for (x, y, z) in NEIGHBORS {
+            let neighbor = position.add_raw(x, y, z);
+            if leaves.contains(&neighbor) && !distances.contains_key(&neighbor) {
+                distances.insert(neighbor, distance + 1);
+                queue.push_back((neighbor, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Which leaves of a square row are left out, given their distances from the middle
+type Skip<'a> = &'a mut dyn FnMut(i32, i32, i32) -> bool;
+
+struct Shape {
+    origin: Vector3<i32>,
+    /// Whether the trunk is two by two blocks, so the rows of leaves are one block wider
+    double_trunk: bool,
+    logs: Vec<Vector3<i32>>,
+    leaves: HashSet<Vector3<i32>>,
+}
+
+impl Shape {
+    /// Vanilla's trunk heights: a base height plus two random parts
+    fn trunk_height(random: &mut impl RandomImpl, base: i32, first: i32, second: i32) -> i32 {
+        base + random.next_bounded_i32(first + 1) + random.next_bounded_i32(second + 1)
+    }
+
+    /// A straight trunk up from the origin, two by two blocks for double trunks
+    fn straight_trunk(&mut self, height: i32) {
+        let width = if self.double_trunk { 2 } else { 1 };
+        for y in 0..height {
+            for x in 0..width {
+                for z in 0..width {
+                    self.logs.push(self.origin.add_raw(x, y, z));
+                }
+            }
+        }
+    }
+
+    /// A square row of leaves around the center, one block wider towards positive x and z for
+    /// double trunks. The skip function gets the distances from the middle, like in vanilla.
+    fn leaves_row(&mut self, center: Vector3<i32>, radius: i32, y: i32, skip: Skip) {
+        let extra = i32::from(self.double_trunk);
+        for x in -radius..=radius + extra {
+            for z in -radius..=radius + extra {
+                let dx = if self.double_trunk && x > 0 { x - 1 } else { x };
+                let dz = if self.double_trunk && z > 0 { z - 1 } else { z };
+                if !skip(dx.abs(), y, dz.abs()) {
+                    self.leaves.insert(center.add_raw(x, y, z));
+                }
+            }
+        }
+    }
+
+    /// Vanilla's blob foliage: Rows getting narrower towards the top, whose corners are left out
+    /// at random and always at the top
+    fn blob(&mut self, random: &mut impl RandomImpl, top: Vector3<i32>, radius: i32, height: i32) {
+        for y in (-height..=0).rev() {
+            let row_radius = (radius - 1 - y / 2).max(0);
+            self.leaves_row(top, row_radius, y, &mut |dx, y, dz| {
+                dx == row_radius && dz == row_radius && (random.next_bounded_i32(2) == 0 || y == 0)
+            });
+        }
+    }
+
+    /// A straight trunk with a blob of leaves, like oak, birch and jungle trees
+    fn blob_tree(&mut self, random: &mut impl RandomImpl, base: i32, extra: i32, radius: i32) {
+        let height = Self::trunk_height(random, base, extra, 0);
+        self.straight_trunk(height);
+        let top = self.origin.add_raw(0, height, 0);
+        self.blob(random, top, radius, 3);
+    }
+
+    /// A straight trunk with rings of leaves getting wider and narrower again towards the
+    /// bottom, like spruce trees
+    fn spruce(&mut self, random: &mut impl RandomImpl) {
+        let height = Self::trunk_height(random, 5, 2, 1);
+        self.straight_trunk(height);
+        let max_radius = 2 + random.next_bounded_i32(2);
+        let offset = random.next_bounded_i32(3);
+        let foliage_height = (height - 1 - random.next_bounded_i32(2)).max(4);
+        let top = self.origin.add_raw(0, height, 0);
+
+        let mut radius = random.next_bounded_i32(2);
+        let mut widest = 1;
+        let mut next = 0;
+        for y in (-foliage_height..=offset).rev() {
+            let row_radius = radius;
+            self.leaves_row(top, row_radius, y, &mut |dx, _, dz| {
+                dx == row_radius && dz == row_radius && row_radius > 0
+            });
+            if radius >= widest {
+                radius = next;
+                next = 1;
+                widest = (widest + 1).min(max_radius);
+            } else {
+                radius += 1;
+            }
+        }
+    }
+
+    /// A trunk which bends sideways towards the top, sometimes with a second branch, and flat
+    /// crowns of leaves, like acacia trees
+    fn acacia(&mut self, random: &mut impl RandomImpl) {
+        let height = Self::trunk_height(random, 5, 2, 2);
+        let (dx, dz) = HORIZONTAL[random.next_bounded_i32(4) as usize];
+        let bend_start = height - random.next_bounded_i32(4) - 1;
+        let mut bend = 3 - random.next_bounded_i32(3);
+        let (mut x, mut z) = (0, 0);
+        for y in 0..height {
+            if y >= bend_start && bend > 0 {
+                x += dx;
+                z += dz;
+                bend -= 1;
+            }
+            self.logs.push(self.origin.add_raw(x, y, z));
+        }
+        self.acacia_crown(self.origin.add_raw(x, height, z));
+
+        let (branch_dx, branch_dz) = HORIZONTAL[random.next_bounded_i32(4) as usize];
+        if (branch_dx, branch_dz) == (dx, dz) {
+            return;
+        }
+        let branch_start = bend_start - random.next_bounded_i32(2) - 1;
+        let branch_length = 1 + random.next_bounded_i32(3);
+        let (mut x, mut z) = (0, 0);
+        let mut top = None;
+        for step in 0..branch_length {
+            let y = branch_start + step;
+            if y >= height {
+                break;
+            }
+            x += branch_dx;
+            z += branch_dz;
+            self.logs.push(self.origin.add_raw(x, y, z));
+            top = Some(self.origin.add_raw(x, y + 1, z));
+        }
+        if let Some(top) = top {
+            self.acacia_crown(top);
+        }
+    }
+
+    fn acacia_crown(&mut self, top: Vector3<i32>) {
+        self.leaves_row(top, 2, -1, &mut |dx, _, dz| dx == 2 && dz == 2);
+        self.leaves_row(top, 1, 0, &mut |_, _, _| false);
+    }
+
+    /// A thick trunk with a wide, flat crown, like dark oak trees
+    fn dark_oak(&mut self, random: &mut impl RandomImpl) {
+        let height = Self::trunk_height(random, 6, 2, 1);
+        self.straight_trunk(height);
+        let top = self.origin.add_raw(0, height, 0);
+        for (y, radius) in [(-1, 2), (0, 3), (1, 2)] {
+            self.leaves_row(top, radius, y, &mut |dx, _, dz| {
+                dx == radius && dz == radius
+            });
+        }
+        if random.next_bool() {
+            self.leaves_row(top, 0, 2, &mut |_, _, _| false);
+        }
+    }
+
+    /// A thick trunk with a tall cone of leaves, like giant spruce trees
+    fn mega_spruce(&mut self, random: &mut impl RandomImpl) {
+        let height = Self::trunk_height(random, 13, 2, 14);
+        self.straight_trunk(height);
+        let top = self.origin.add_raw(0, height, 0);
+        let crown_height = 13 + random.next_bounded_i32(5);
+
+        let mut last_radius = 0;
+        for y in (height - crown_height)..=height {
+            let below_top = height - y;
+            let radius = (below_top as f32 / crown_height as f32 * 3.5).floor() as i32;
+            // Every other ring sticks out a bit, which makes the cone look ragged
+            let row_radius = if below_top > 0 && radius == last_radius && y & 1 == 0 {
+                radius + 1
+            } else {
+                radius
+            };
+            self.leaves_row(top, row_radius, y - height, &mut |dx, _, dz| {
+                dx + dz >= 7 || dx * dx + dz * dz > row_radius * row_radius
+            });
+            last_radius = radius;
+        }
+    }
+
+    /// Round, tapering rows of leaves, like the crowns of giant jungle trees
+    fn mega_jungle_crown(&mut self, top: Vector3<i32>, radius: i32, height: i32) {
+        for y in (-height..=0).rev() {
+            let row_radius = radius + 1 - y;
+            self.leaves_row(top, row_radius, y, &mut |dx, _, dz| {
+                dx + dz >= 7 || dx * dx + dz * dz > row_radius * row_radius
+            });
+        }
+    }
+
+    /// A thick trunk with branches sticking out at random angles, like giant jungle trees
+    fn mega_jungle(&mut self, random: &mut impl RandomImpl) {
+        let height = Self::trunk_height(random, 10, 2, 19);
+        self.straight_trunk(height);
+        self.mega_jungle_crown(self.origin.add_raw(0, height, 0), 2, 2);
+
+        let mut y = height - 2 - random.next_bounded_i32(4);
+        while y > height / 2 {
+            let angle = random.next_f32() * std::f32::consts::TAU;
+            let (mut x, mut z) = (0, 0);
+            for step in 0..5 {
+                x = (1.5 + angle.cos() * step as f32) as i32;
+                z = (1.5 + angle.sin() * step as f32) as i32;
+                self.logs.push(self.origin.add_raw(x, y - 3 + step / 2, z));
+            }
+            // The branches are single logs, so their leaves are not wider on one side
+            let double_trunk = std::mem::replace(&mut self.double_trunk, false);
+            let crown_height = 1 + random.next_bounded_i32(2);
+            self.mega_jungle_crown(self.origin.add_raw(x, y, z), 0, crown_height);
+            self.double_trunk = double_trunk;
+            y -= 2 + random.next_bounded_i32(4);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::random::legacy_rand::LegacyRand;
+
+    use super::*;
+
+    #[test]
+    fn leaves_are_near_logs() {
+        for kind in [
+            TreeKind::Oak,
+            TreeKind::Spruce,
+            TreeKind::Acacia,
+            TreeKind::DarkOak,
+            TreeKind::MegaSpruce,
+            TreeKind::MegaJungle,
+        ] {
+            let tree = Tree::plan(kind, Vector3::new(0, 64, 0), &mut LegacyRand::from_seed(7));
+            let leaves = tree.leaf_states();
+            assert!(!leaves.is_empty());
+            let distances = leaf_distances(&tree.logs, &tree.leaves);
+            for (leaf, _) in leaves {
+                assert!(!tree.logs.contains(&leaf));
+                assert!(distances[&leaf] < MAX_LEAF_DISTANCE);
+            }
+        }
+    }
+
+    #[test]
+    fn distance_goes_through_leaves() {
+        let logs = [Vector3::new(0, 0, 0)];
+        let leaves = [
+            Vector3::new(1, 0, 0),
+            Vector3::new(2, 0, 0),
+            Vector3::new(3, 1, 0),
+        ];
+        let distances = leaf_distances(&logs, &leaves);
+        assert_eq!(distances.get(&Vector3::new(1, 0, 0)), Some(&1));
+        assert_eq!(distances.get(&Vector3::new(2, 0, 0)), Some(&2));
+        // Not connected to the other leaves
+        assert_eq!(distances.get(&Vector3::new(3, 1, 0)), None);
+    }
+}
@@ -6,14 +6,42 @@ use pumpkin_util::math::vector3::Vector3;
 use crate::block::state::BlockState;
 use crate::chunk::{ChunkData, Subchunks};
 use crate::coordinates::{BlockCoordinates, ChunkRelativeBlockCoordinates, XZBlockCoordinates};
+use crate::generation::structure::{StructureSet, StructureStart, StructureType};
 use crate::generation::Seed;
 
 pub trait GeneratorInit {
     fn new(seed: Seed) -> Self;
 }
 
+/// Generates the chunks of a world which are not on disk yet.
+///
+/// Generators are called from the parallel chunk loading pipeline, so they have to be `Send + Sync`.
+/// Custom generators are made available to worlds by name, see [`register_world_gen`](super::register_world_gen).
 pub trait WorldGenerator: Sync + Send {
     fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData;
+
+    /// Returns the biome at the given block position.
+    fn get_biome(&self, _at: Vector3<i32>) -> Biome {
+        Biome::Plains
+    }
+
+    /// Finds the closest start of the structures of the set accepted by `filter`, searching up to
+    /// `radius` regions around the given chunk. Generators without structures find none.
+    fn locate_structure(
+        &self,
+        _set: &StructureSet,
+        _center: Vector2<i32>,
+        _radius: i32,
+        _filter: &dyn Fn(StructureType) -> bool,
+    ) -> Option<StructureStart> {
+        None
+    }
+
+    /// Returns the Y coordinate players spawn at in the given block column,
+    /// or `None` to spawn them on top of the highest block.
+    fn get_spawn_height(&self, _at: Vector2<i32>) -> Option<i32> {
+        None
+    }
 }
 
 pub(crate) trait BiomeGenerator: Sync + Send {
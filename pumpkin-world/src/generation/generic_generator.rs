@@ -1,9 +1,12 @@
 use noise::{NoiseFn, Perlin};
-use pumpkin_util::math::vector2::Vector2;
+use pumpkin_data::chunk::Biome;
+use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
 
 use crate::{
     chunk::{ChunkData, Subchunks},
-    coordinates::{ChunkRelativeBlockCoordinates, ChunkRelativeXZBlockCoordinates},
+    coordinates::{
+        ChunkRelativeBlockCoordinates, ChunkRelativeXZBlockCoordinates, XZBlockCoordinates,
+    },
     WORLD_LOWEST_Y,
 };
 
@@ -33,6 +36,11 @@ impl<B: BiomeGenerator + GeneratorInit, T: PerlinTerrainGenerator + GeneratorIni
 }
 
 impl<B: BiomeGenerator, T: PerlinTerrainGenerator> WorldGenerator for GenericGenerator<B, T> {
+    fn get_biome(&self, at: Vector3<i32>) -> Biome {
+        self.biome_generator
+            .generate_biome(XZBlockCoordinates { x: at.x, z: at.z })
+    }
+
     fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
         let mut subchunks = Subchunks::Single(0);
         self.terrain_generator.prepare_chunk(&at, &self.perlin);
@@ -72,11 +80,9 @@ impl<B: BiomeGenerator, T: PerlinTerrainGenerator> WorldGenerator for GenericGen
             }
         }
 
-        ChunkData {
-            subchunks,
-            heightmap: Default::default(),
-            position: at,
-        }
+        let mut chunk = ChunkData::new(subchunks, Default::default(), at);
+        chunk.heightmap = chunk.calculate_heightmap();
+        chunk
     }
 }
 
@@ -0,0 +1,205 @@
+use pumpkin_data::chunk::Biome;
+use pumpkin_util::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{xoroshiro128::Xoroshiro, RandomGenerator, RandomImpl},
+};
+
+use crate::{
+    chunk::{ChunkData, ChunkSectionBiomes, Subchunks, BIOMES_PER_SUBCHUNK, SUBCHUNKS_COUNT},
+    coordinates::ChunkRelativeBlockCoordinates,
+    generation::{
+        feature::default_state, generator::GeneratorInit, noise::perlin::OctavePerlinNoiseSampler,
+        noise_router::density_function::misc::EndIsland, Seed, WorldGenerator,
+    },
+};
+
+/// The height the islands are centered around
+const ISLAND_Y: f64 = 48.0;
+/// The size of the cells the island height is sampled in
+const CELL_SIZE: i32 = 8;
+/// How far from the center of the exit portal its blocks reach
+const EXIT_PORTAL_RADIUS: i32 = 4;
+/// How high the bedrock pillar in the middle of the exit portal is
+const EXIT_PORTAL_PILLAR_HEIGHT: i32 = 4;
+
+/// Generates the end: the central island with the exit portal on top, surrounded by the outer
+/// islands more than 1024 blocks away.
+pub struct EndGenerator {
+    islands: EndIsland,
+    surface: OctavePerlinNoiseSampler,
+}
+
+impl GeneratorInit for EndGenerator {
+    fn new(seed: Seed) -> Self {
+        let mut random = RandomGenerator::Xoroshiro(Xoroshiro::from_seed(seed.0));
+        let (first_octave, amplitudes) =
+            OctavePerlinNoiseSampler::calculate_amplitudes(&[-3, -2, -1]);
+        Self {
+            islands: EndIsland::new(seed.0),
+            surface: OctavePerlinNoiseSampler::new(&mut random, first_octave, &amplitudes, false),
+        }
+    }
+}
+
+impl EndGenerator {
+    /// The island height at a block column, interpolated between the cells
+    fn island_height(&self, x: i32, z: i32) -> f64 {
+        let (cell_x, cell_z) = (x.div_euclid(CELL_SIZE), z.div_euclid(CELL_SIZE));
+        let delta_x = f64::from(x.rem_euclid(CELL_SIZE)) / f64::from(CELL_SIZE);
+        let delta_z = f64::from(z.rem_euclid(CELL_SIZE)) / f64::from(CELL_SIZE);
+        let height = |x, z| f64::from(self.islands.island_height(x, z));
+        let north = height(cell_x, cell_z)
+            + (height(cell_x + 1, cell_z) - height(cell_x, cell_z)) * delta_x;
+        let south = height(cell_x, cell_z + 1)
+            + (height(cell_x + 1, cell_z + 1) - height(cell_x, cell_z + 1)) * delta_x;
+        north + (south - north) * delta_z
+    }
+
+    /// The lowest and highest end stone of a block column, or `None` if it is empty
+    fn column(&self, x: i32, z: i32) -> Option<(i32, i32)> {
+        let height = self.island_height(x, z);
+        if height <= 0.0 {
+            return None;
+        }
+        let bumps = self.surface.sample(f64::from(x), 0.0, f64::from(z)) * 2.0;
+        let top = (ISLAND_Y + height * 0.2 + bumps).floor() as i32;
+        let bottom = (ISLAND_Y - height * 0.5 + bumps).ceil() as i32;
+        (bottom <= top).then_some((bottom, top))
+    }
+
+    /// The block above the middle of the central island, where the exit portal is
+    fn exit_portal_origin(&self) -> Vector3<i32> {
+        let top = self.column(0, 0).map_or(ISLAND_Y as i32, |(_, top)| top);
+        Vector3::new(0, top + 1, 0)
+    }
+
+    /// The state of the exit portal at the position, like vanilla's end podium with the portal
+    /// already active, or `None` if the exit portal does not change the block
+    fn exit_portal_block(origin: Vector3<i32>, position: Vector3<i32>) -> Option<&'static str> {
+        let (x, y, z) = (
+            position.x - origin.x,
+            position.y - origin.y,
+            position.z - origin.z,
+        );
+        if x.abs() > EXIT_PORTAL_RADIUS || z.abs() > EXIT_PORTAL_RADIUS || y < -1 {
+            return None;
+        }
+        if x == 0 && z == 0 && (0..EXIT_PORTAL_PILLAR_HEIGHT).contains(&y) {
+            return Some("bedrock");
+        }
+        let distance = (f64::from(x) + 0.5).powi(2) + (f64::from(z) + 0.5).powi(2);
+        if distance > 3.5 * 3.5 {
+            return None;
+        }
+        let rim = distance > 2.5 * 2.5;
+        match y {
+            -1 if rim => Some("end_stone"),
+            -1 => Some("bedrock"),
+            0 if rim => Some("bedrock"),
+            0 => Some("end_portal"),
+            _ => None,
+        }
+    }
+
+    fn biome_at_chunk(&self, chunk_x: i32, chunk_z: i32) -> Biome {
+        if i64::from(chunk_x).pow(2) + i64::from(chunk_z).pow(2) <= 4096 {
+            return Biome::TheEnd;
+        }
+        let density =
+            (f64::from(self.islands.island_height(chunk_x * 2 + 1, chunk_z * 2 + 1)) - 8.0) / 128.0;
+        if density > 0.25 {
+            Biome::EndHighlands
+        } else if density >= -0.0625 {
+            Biome::EndMidlands
+        } else if density < -0.21875 {
+            Biome::SmallEndIslands
+        } else {
+            Biome::EndBarrens
+        }
+    }
+}
+
+impl WorldGenerator for EndGenerator {
+    fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
+        let end_stone = default_state("end_stone");
+        let origin = self.exit_portal_origin();
+
+        let mut subchunks = Subchunks::Single(0);
+        for x in 0..16 {
+            for z in 0..16 {
+                let world_x = at.x * 16 + x;
+                let world_z = at.z * 16 + z;
+                let mut set_block = |y: i32, state: u16| {
+                    let coordinates = ChunkRelativeBlockCoordinates {
+                        x: (x as u8).into(),
+                        y: y.into(),
+                        z: (z as u8).into(),
+                    };
+                    subchunks.set_block(coordinates, state);
+                };
+                if let Some((bottom, top)) = self.column(world_x, world_z) {
+                    for y in bottom..=top {
+                        set_block(y, end_stone);
+                    }
+                }
+                if (world_x - origin.x).abs() <= EXIT_PORTAL_RADIUS
+                    && (world_z - origin.z).abs() <= EXIT_PORTAL_RADIUS
+                {
+                    for y in origin.y - 1..origin.y + EXIT_PORTAL_PILLAR_HEIGHT {
+                        let position = Vector3::new(world_x, y, world_z);
+                        if let Some(block) = Self::exit_portal_block(origin, position) {
+                            set_block(y, default_state(block));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut chunk = ChunkData::new(subchunks, Default::default(), at);
+        let biome = self.biome_at_chunk(at.x, at.z);
+        chunk.biomes =
+            vec![ChunkSectionBiomes::from_biomes(&[biome; BIOMES_PER_SUBCHUNK]); SUBCHUNKS_COUNT];
+        chunk.heightmap = chunk.calculate_heightmap();
+        chunk
+    }
+
+    fn get_biome(&self, at: Vector3<i32>) -> Biome {
+        self.biome_at_chunk(at.x >> 4, at.z >> 4)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector2::Vector2;
+
+    use crate::{
+        coordinates::ChunkRelativeBlockCoordinates,
+        generation::{feature::default_state, generator::GeneratorInit, Seed, WorldGenerator},
+    };
+
+    use super::EndGenerator;
+
+    #[test]
+    fn central_island_has_exit_portal() {
+        let generator = EndGenerator::new(Seed(42));
+        let chunk = generator.generate_chunk(Vector2::new(0, 0));
+        let block = |x: u8, y: i32, z: u8| {
+            chunk
+                .subchunks
+                .get_block(ChunkRelativeBlockCoordinates {
+                    x: x.into(),
+                    y: y.into(),
+                    z: z.into(),
+                })
+                .unwrap()
+        };
+        let origin = generator.exit_portal_origin();
+        assert_eq!(block(1, origin.y, 1), default_state("end_portal"));
+        assert_eq!(block(0, origin.y + 1, 0), default_state("bedrock"));
+        assert_eq!(block(8, origin.y - 2, 8), default_state("end_stone"));
+
+        // The void between the central and the outer islands
+        let empty = generator.generate_chunk(Vector2::new(20, 0));
+        assert!(empty.subchunks == crate::chunk::Subchunks::Single(0));
+    }
+}
@@ -1,3 +1,5 @@
+pub mod end;
+pub mod nether;
 pub mod overworld;
 pub mod superflat;
 pub mod test;
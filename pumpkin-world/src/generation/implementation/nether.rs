@@ -0,0 +1,245 @@
+use pumpkin_data::chunk::Biome;
+use pumpkin_util::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{xoroshiro128::Xoroshiro, RandomGenerator, RandomImpl},
+};
+
+use crate::{
+    chunk::{ChunkData, ChunkSectionBiomes, Subchunks, BIOMES_PER_SUBCHUNK, SUBCHUNKS_COUNT},
+    coordinates::ChunkRelativeBlockCoordinates,
+    generation::{
+        biome_coords,
+        feature::{default_state, position_chance},
+        generator::GeneratorInit,
+        noise::perlin::OctavePerlinNoiseSampler,
+        Seed, WorldGenerator,
+    },
+};
+
+/// The lowest block of the nether terrain, which is always bedrock
+const FLOOR_Y: i32 = 0;
+/// The highest block of the nether terrain, which is always bedrock
+const ROOF_Y: i32 = 127;
+/// Open space up to this height is filled with lava
+const LAVA_SEA_Y: i32 = 31;
+/// How many blocks above the floor and below the roof bedrock can generate
+const BEDROCK_LAYERS: i32 = 5;
+
+/// The climate of each nether biome, as (temperature, humidity), like the vanilla biome source
+const BIOMES: [(Biome, f64, f64); 5] = [
+    (Biome::NetherWastes, 0.0, 0.0),
+    (Biome::SoulSandValley, 0.0, -0.5),
+    (Biome::CrimsonForest, 0.4, 0.0),
+    (Biome::WarpedForest, 0.0, 0.5),
+    (Biome::BasaltDeltas, -0.5, 0.0),
+];
+
+/// Generates the nether: netherrack caverns between a bedrock floor and roof, with lava seas
+/// at the bottom and a surface depending on the nether biome.
+pub struct NetherGenerator {
+    seed: Seed,
+    terrain: OctavePerlinNoiseSampler,
+    temperature: OctavePerlinNoiseSampler,
+    humidity: OctavePerlinNoiseSampler,
+}
+
+impl GeneratorInit for NetherGenerator {
+    fn new(seed: Seed) -> Self {
+        let mut random = RandomGenerator::Xoroshiro(Xoroshiro::from_seed(seed.0));
+        let (first_octave, amplitudes) =
+            OctavePerlinNoiseSampler::calculate_amplitudes(&[-4, -3, -2, -1, 0]);
+        let terrain = OctavePerlinNoiseSampler::new(&mut random, first_octave, &amplitudes, false);
+        let (first_octave, amplitudes) = OctavePerlinNoiseSampler::calculate_amplitudes(&[-7, -6]);
+        let temperature =
+            OctavePerlinNoiseSampler::new(&mut random, first_octave, &amplitudes, false);
+        let humidity = OctavePerlinNoiseSampler::new(&mut random, first_octave, &amplitudes, false);
+        Self {
+            seed,
+            terrain,
+            temperature,
+            humidity,
+        }
+    }
+}
+
+impl NetherGenerator {
+    /// Whether the block is netherrack, ignoring bedrock. Positive noise is solid, and the
+    /// terrain gets denser towards the floor and the roof so the caverns are closed off.
+    fn is_solid(&self, x: i32, y: i32, z: i32) -> bool {
+        let noise = self.terrain.sample(
+            f64::from(x) * 0.25,
+            f64::from(y) * 0.375,
+            f64::from(z) * 0.25,
+        );
+        let floor = (f64::from(LAVA_SEA_Y - y) / 16.0).max(0.0);
+        let roof = (f64::from(y - (ROOF_Y - 24)) / 12.0).max(0.0);
+        noise + floor + roof > 0.0
+    }
+
+    /// Whether bedrock generates at the given position, always at the floor and roof and less
+    /// likely the further away from them
+    fn is_bedrock(&self, position: Vector3<i32>) -> bool {
+        let depth = (position.y - FLOOR_Y).min(ROOF_Y - position.y);
+        depth < BEDROCK_LAYERS
+            && position_chance(
+                self.seed.0,
+                position,
+                1.0 - depth as f32 / BEDROCK_LAYERS as f32,
+            )
+    }
+
+    fn biome_at(&self, biome_x: i32, biome_z: i32) -> Biome {
+        let temperature = self
+            .temperature
+            .sample(f64::from(biome_x), 0.0, f64::from(biome_z));
+        let humidity = self
+            .humidity
+            .sample(f64::from(biome_x), 0.0, f64::from(biome_z));
+        BIOMES
+            .iter()
+            .min_by(|(_, t1, h1), (_, t2, h2)| {
+                let d1 = (temperature - t1).powi(2) + (humidity - h1).powi(2);
+                let d2 = (temperature - t2).powi(2) + (humidity - h2).powi(2);
+                d1.total_cmp(&d2)
+            })
+            .map(|(biome, _, _)| *biome)
+            .expect("There are nether biomes")
+    }
+}
+
+/// The block covering exposed floors in a biome
+fn surface_block(biome: Biome) -> &'static str {
+    match biome {
+        Biome::SoulSandValley => "soul_sand",
+        Biome::CrimsonForest => "crimson_nylium",
+        Biome::WarpedForest => "warped_nylium",
+        Biome::BasaltDeltas => "basalt",
+        _ => "netherrack",
+    }
+}
+
+impl WorldGenerator for NetherGenerator {
+    fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
+        let netherrack = default_state("netherrack");
+        let bedrock = default_state("bedrock");
+        let lava = default_state("lava");
+
+        let start_biome_x = biome_coords::from_block(at.x * 16);
+        let start_biome_z = biome_coords::from_block(at.z * 16);
+        // The nether biomes only change horizontally, indexed by zx
+        let column_biomes: Vec<Biome> = (0..16)
+            .map(|index| self.biome_at(start_biome_x + index % 4, start_biome_z + index / 4))
+            .collect();
+
+        let mut subchunks = Subchunks::Single(0);
+        for x in 0..16 {
+            for z in 0..16 {
+                let world_x = at.x * 16 + x;
+                let world_z = at.z * 16 + z;
+                let surface =
+                    default_state(surface_block(column_biomes[(z / 4 * 4 + x / 4) as usize]));
+                let mut above_open = false;
+                for y in (FLOOR_Y..=ROOF_Y).rev() {
+                    let position = Vector3::new(world_x, y, world_z);
+                    let solid = self.is_solid(world_x, y, world_z);
+                    let block = if self.is_bedrock(position) {
+                        bedrock
+                    } else if solid && above_open {
+                        surface
+                    } else if solid {
+                        netherrack
+                    } else if y <= LAVA_SEA_Y {
+                        lava
+                    } else {
+                        0
+                    };
+                    // Floors below the lava sea are not exposed
+                    above_open = !solid && y > LAVA_SEA_Y;
+                    if block != 0 {
+                        let coordinates = ChunkRelativeBlockCoordinates {
+                            x: (x as u8).into(),
+                            y: y.into(),
+                            z: (z as u8).into(),
+                        };
+                        subchunks.set_block(coordinates, block);
+                    }
+                }
+            }
+        }
+
+        let mut chunk = ChunkData::new(subchunks, Default::default(), at);
+        let mut section_biomes = [Biome::NetherWastes; BIOMES_PER_SUBCHUNK];
+        for (index, biome) in section_biomes.iter_mut().enumerate() {
+            *biome = column_biomes[index % 16];
+        }
+        chunk.biomes = vec![ChunkSectionBiomes::from_biomes(&section_biomes); SUBCHUNKS_COUNT];
+        chunk.heightmap = chunk.calculate_heightmap();
+        chunk
+    }
+
+    fn get_biome(&self, at: Vector3<i32>) -> Biome {
+        self.biome_at(
+            biome_coords::from_block(at.x),
+            biome_coords::from_block(at.z),
+        )
+    }
+
+    fn get_spawn_height(&self, at: Vector2<i32>) -> Option<i32> {
+        // The lowest open space above the lava sea with ground below it
+        (LAVA_SEA_Y + 1..ROOF_Y - BEDROCK_LAYERS).find(|&y| {
+            self.is_solid(at.x, y - 1, at.z)
+                && !self.is_solid(at.x, y, at.z)
+                && !self.is_solid(at.x, y + 1, at.z)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector2::Vector2;
+
+    use crate::{
+        coordinates::ChunkRelativeBlockCoordinates,
+        generation::{feature::default_state, generator::GeneratorInit, Seed, WorldGenerator},
+    };
+
+    use super::{NetherGenerator, LAVA_SEA_Y, ROOF_Y};
+
+    #[test]
+    fn nether_has_bedrock_and_lava_seas() {
+        let generator = NetherGenerator::new(Seed(42));
+        let (bedrock, lava) = (default_state("bedrock"), default_state("lava"));
+        let mut lava_blocks = 0;
+        for chunk_x in 0..4 {
+            let chunk = generator.generate_chunk(Vector2::new(chunk_x, -2));
+            let block = |x: u8, y: i32, z: u8| {
+                chunk
+                    .subchunks
+                    .get_block(ChunkRelativeBlockCoordinates {
+                        x: x.into(),
+                        y: y.into(),
+                        z: z.into(),
+                    })
+                    .unwrap()
+            };
+            for x in 0..16 {
+                for z in 0..16 {
+                    assert_eq!(block(x, 0, z), bedrock);
+                    assert_eq!(block(x, ROOF_Y, z), bedrock);
+                    assert_eq!(block(x, ROOF_Y + 1, z), 0);
+                    assert_eq!(block(x, -1, z), 0);
+                    for y in 0..=ROOF_Y {
+                        if block(x, y, z) == lava {
+                            assert!(y <= LAVA_SEA_Y);
+                            lava_blocks += 1;
+                        }
+                    }
+                }
+            }
+
+            let again = NetherGenerator::new(Seed(42)).generate_chunk(chunk.position);
+            assert!(again.subchunks == chunk.subchunks);
+        }
+        assert!(lava_blocks > 0);
+    }
+}
@@ -1,49 +1,47 @@
-use std::ops::Add;
-
-use pumpkin_macros::block_state;
-
-use crate::{block::BlockState, coordinates::ChunkRelativeBlockCoordinates};
-
+use pumpkin_util::{
+    math::vector3::Vector3,
+    random::{hash_block_pos, legacy_rand::LegacyRand, RandomImpl},
+};
+
+use crate::{
+    block::{registry::get_block_by_state_id, BlockState},
+    coordinates::ChunkRelativeBlockCoordinates,
+    generation::feature::tree::{Tree, TreeKind},
+};
+
+/// Plans an oak tree growing up from the coordinates. Its leaves have their distance to the
+/// trunk set, so they don't decay. Blocks which would be outside of the chunk are left out.
 pub fn generate_tree(
     chunk_relative_coordinates: ChunkRelativeBlockCoordinates,
 ) -> Vec<(ChunkRelativeBlockCoordinates, BlockState)> {
-    let x = chunk_relative_coordinates.x;
-    let z = chunk_relative_coordinates.z;
-
-    // TODO: Adjust tree height and trunk width based on biome
-    let tree_height: i8 = 7;
-    let trunk_width: i8 = 1;
-
-    let mut tree_blocks = Vec::new();
-
-    // Generate trunk
-    for y in 0..tree_height {
-        for dx in 0 - trunk_width..=trunk_width {
-            for dz in 0 - trunk_width..=trunk_width {
-                let block_coordinates = ChunkRelativeBlockCoordinates {
-                    x: x.add(dx as u8).into(),
-                    y: (chunk_relative_coordinates.y.add(y as i16)).into(),
-                    z: z.add(dz as u8).into(),
-                };
-                tree_blocks.push((block_coordinates, block_state!("oak_log")));
-            }
-        }
-    }
-
-    // Generate leaves
-    let leaf_radius = trunk_width + 1;
-    for y in tree_height..tree_height + 3 {
-        for dx in 0 - leaf_radius..=leaf_radius {
-            for dz in 0 - leaf_radius..=leaf_radius {
-                let block_coordinates = ChunkRelativeBlockCoordinates {
-                    x: x.add(dx as u8).into(),
-                    y: (chunk_relative_coordinates.y.add(y as i16)).into(),
-                    z: z.add(dz as u8).into(),
-                };
-                tree_blocks.push((block_coordinates, block_state!("oak_leaves")));
-            }
-        }
-    }
-
-    tree_blocks
+    let origin = Vector3::new(
+        i32::from(*chunk_relative_coordinates.x),
+        i32::from(*chunk_relative_coordinates.y),
+        i32::from(*chunk_relative_coordinates.z),
+    );
+    // TODO: Choose the tree based on the biome
+    let mut random = LegacyRand::from_seed(hash_block_pos(origin.x, origin.y, origin.z) as u64);
+    let tree = Tree::plan(TreeKind::Oak, origin, &mut random);
+    let log = tree.log_state();
+
+    tree.logs
+        .iter()
+        .map(|position| (*position, log))
+        .chain(tree.leaf_states())
+        .filter(|(position, _)| (0..16).contains(&position.x) && (0..16).contains(&position.z))
+        .filter_map(|(position, state_id)| {
+            let block = get_block_by_state_id(state_id)?;
+            Some((
+                ChunkRelativeBlockCoordinates {
+                    x: (position.x as u8).into(),
+                    y: position.y.into(),
+                    z: (position.z as u8).into(),
+                },
+                BlockState {
+                    state_id,
+                    block_id: block.id,
+                },
+            ))
+        })
+        .collect()
 }
@@ -1,19 +1,82 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use pumpkin_data::chunk::Biome;
 use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
 
 use crate::{
+    biome::{BiomeSupplier, BiomeSupplierImpl, MultiNoiseBiomeSupplier},
     chunk::{ChunkData, Subchunks},
     coordinates::ChunkRelativeBlockCoordinates,
     generation::{
-        generator::GeneratorInit, noise_router::proto_noise_router::GlobalProtoNoiseRouter,
-        proto_chunk::ProtoChunk, GlobalRandomConfig, Seed, WorldGenerator,
+        biome_coords, carver,
+        feature::{dungeon, ore, ChunkRegion},
+        generator::GeneratorInit,
+        noise_router::{
+            multi_noise_sampler::{MultiNoiseSampler, MultiNoiseSamplerBuilderOptions},
+            proto_noise_router::GlobalProtoNoiseRouter,
+        },
+        proto_chunk::ProtoChunk,
+        structure::{
+            mineshaft::{BlockBox, Mineshaft},
+            village::{self, Village},
+            StructureSet, StructureStart, StructureType, VILLAGES,
+        },
+        GlobalRandomConfig, Seed, WorldGenerator,
     },
     noise_router::NOISE_ROUTER_ASTS,
     WORLD_LOWEST_Y, WORLD_MAX_Y,
 };
 
+/// How many generated villages are kept. A chunk only reaches the few villages starting near it,
+/// so this only has to cover the chunks generating at the same time.
+const MAX_CACHED_VILLAGES: usize = 64;
+
+/// The villages which were generated most recently, by their start chunk
+#[derive(Default)]
+struct VillageCache {
+    villages: HashMap<Vector2<i32>, Arc<Village>>,
+    /// The start chunks, from the least to the most recently used
+    order: VecDeque<Vector2<i32>>,
+}
+
+impl VillageCache {
+    fn get(&mut self, start: Vector2<i32>) -> Option<Arc<Village>> {
+        let village = self.villages.get(&start)?.clone();
+        if let Some(i) = self.order.iter().position(|chunk| *chunk == start) {
+            self.order.remove(i);
+        }
+        self.order.push_back(start);
+        Some(village)
+    }
+
+    /// Adds a village, unless another chunk added it in the meantime, and evicts the least
+    /// recently used one when the cache is full
+    fn insert(&mut self, start: Vector2<i32>, village: Arc<Village>) -> Arc<Village> {
+        if let Some(village) = self.get(start) {
+            return village;
+        }
+        self.villages.insert(start, village.clone());
+        self.order.push_back(start);
+        if self.order.len() > MAX_CACHED_VILLAGES {
+            if let Some(evicted) = self.order.pop_front() {
+                self.villages.remove(&evicted);
+            }
+        }
+        village
+    }
+}
+
 pub struct TestGenerator {
+    seed: Seed,
     random_config: GlobalRandomConfig,
     base_router: GlobalProtoNoiseRouter,
+    /// The villages which were generated, by their start chunk. Every chunk a village reaches
+    /// references its start here, so they all place pieces of the same village. Villages are
+    /// generated the same way again after being evicted, only slower.
+    villages: Mutex<VillageCache>,
 }
 
 impl GeneratorInit for TestGenerator {
@@ -22,16 +85,67 @@ impl GeneratorInit for TestGenerator {
         let base_router =
             GlobalProtoNoiseRouter::generate(&NOISE_ROUTER_ASTS.overworld, &random_config);
         Self {
+            seed,
             random_config,
             base_router,
+            villages: Mutex::default(),
         }
     }
 }
 
+impl TestGenerator {
+    /// All villages which can reach into a chunk
+    fn villages_near(&self, chunk: Vector2<i32>) -> Vec<Arc<Village>> {
+        let chunk_bounds = BlockBox::chunk(chunk);
+        VILLAGES
+            .starts_near(self.seed.0, chunk, village::CHUNK_RANGE, |at| {
+                self.get_biome(at)
+            })
+            .into_iter()
+            .map(|start| self.village(start))
+            .filter(|village| village.intersects(&chunk_bounds))
+            .collect()
+    }
+
+    /// Gets the village of a start, generating it the first time a chunk it reaches generates
+    fn village(&self, start: StructureStart) -> Arc<Village> {
+        if let Some(village) = self.villages.lock().unwrap().get(start.chunk) {
+            return village;
+        }
+        // Sampling the terrain is slow, so it happens outside of the lock. Chunks generating at
+        // the same time may both generate the village, which gives the same pieces.
+        let mut heights = HashMap::new();
+        let village = Arc::new(Village::generate(self.seed.0, start, |at| {
+            let chunk = Vector2::new(at.x >> 4, at.z >> 4);
+            let heights = heights
+                .entry(chunk)
+                .or_insert_with(|| self.terrain_heights(chunk));
+            heights[((at.x & 15) * 16 + (at.z & 15)) as usize]
+        }));
+        self.villages.lock().unwrap().insert(start.chunk, village)
+    }
+
+    /// The height of the top block of each column of a chunk, from the noise alone
+    fn terrain_heights(&self, chunk: Vector2<i32>) -> Vec<i32> {
+        let mut proto_chunk = ProtoChunk::new(chunk, &self.base_router, &self.random_config);
+        proto_chunk.populate_noise();
+        (0..16)
+            .flat_map(|x| (0..16).map(move |z| (x, z)))
+            .map(|(x, z)| {
+                (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y))
+                    .rev()
+                    .find(|&y| !proto_chunk.get_block_state(&Vector3::new(x, y, z)).is_air())
+                    .unwrap_or(WORLD_LOWEST_Y.into())
+            })
+            .collect()
+    }
+}
+
 impl WorldGenerator for TestGenerator {
     fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
         let mut subchunks = Subchunks::Single(0);
         let mut proto_chunk = ProtoChunk::new(at, &self.base_router, &self.random_config);
+        proto_chunk.populate_biomes(&BiomeSupplier::MultiNoise(MultiNoiseBiomeSupplier));
         proto_chunk.populate_noise();
 
         for x in 0..16u8 {
@@ -53,10 +167,74 @@ impl WorldGenerator for TestGenerator {
             }
         }
 
-        ChunkData {
-            subchunks,
-            heightmap: Default::default(),
-            position: at,
+        let mut chunk = ChunkData::new(subchunks, Default::default(), at);
+        chunk.biomes = proto_chunk.section_biomes();
+
+        let mut region = ChunkRegion::new(&mut chunk);
+        carver::carve(&mut region, self.seed.0);
+        for mineshaft in Mineshaft::near(self.seed.0, at) {
+            mineshaft.place(&mut region);
+        }
+        dungeon::generate(&mut region, self.seed.0);
+        ore::generate(&mut region, self.seed.0, |at| self.get_biome(at));
+        for village in self.villages_near(at) {
+            village.place(&mut region);
+        }
+        chunk.heightmap = chunk.calculate_heightmap();
+        chunk
+    }
+
+    fn get_biome(&self, at: Vector3<i32>) -> Biome {
+        let biome_x = biome_coords::from_block(at.x);
+        let biome_z = biome_coords::from_block(at.z);
+        let mut noise = MultiNoiseSampler::generate(
+            &self.base_router,
+            &MultiNoiseSamplerBuilderOptions::new(biome_x, biome_z, 0),
+        );
+        MultiNoiseBiomeSupplier.biome(biome_x, biome_coords::from_block(at.y), biome_z, &mut noise)
+    }
+
+    fn locate_structure(
+        &self,
+        set: &StructureSet,
+        center: Vector2<i32>,
+        radius: i32,
+        filter: &dyn Fn(StructureType) -> bool,
+    ) -> Option<StructureStart> {
+        set.locate(self.seed.0, center, radius, filter, |at| self.get_biome(at))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::{VillageCache, MAX_CACHED_VILLAGES};
+    use crate::generation::structure::{village::Village, StructureStart, StructureType};
+
+    #[test]
+    fn village_cache_evicts_the_least_recently_used() {
+        let village = |x| {
+            let start = StructureStart {
+                structure: StructureType::VillagePlains,
+                chunk: Vector2::new(x, 0),
+            };
+            Arc::new(Village::generate(0, start, |_| 64))
+        };
+        let mut cache = VillageCache::default();
+        for x in 0..MAX_CACHED_VILLAGES as i32 {
+            cache.insert(Vector2::new(x, 0), village(x));
         }
+        // Using the first village makes the second one the least recently used
+        assert!(cache.get(Vector2::new(0, 0)).is_some());
+        let x = MAX_CACHED_VILLAGES as i32;
+        cache.insert(Vector2::new(x, 0), village(x));
+
+        assert_eq!(cache.villages.len(), MAX_CACHED_VILLAGES);
+        assert!(cache.get(Vector2::new(0, 0)).is_some());
+        assert!(cache.get(Vector2::new(1, 0)).is_none());
+        assert!(cache.get(Vector2::new(x, 0)).is_some());
     }
 }
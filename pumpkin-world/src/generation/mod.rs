@@ -2,7 +2,9 @@
 
 pub mod aquifer_sampler;
 mod blender;
+pub mod carver;
 pub mod chunk_noise;
+pub mod feature;
 pub mod generation_shapes;
 mod generator;
 mod generic_generator;
@@ -13,25 +15,19 @@ pub mod noise_router;
 pub mod ore_sampler;
 mod positions;
 pub mod proto_chunk;
+mod registry;
 mod seed;
+pub mod structure;
 
 use derive_getters::Getters;
 pub use generator::WorldGenerator;
-use implementation::{
-    //overworld::biome::plains::PlainsGenerator,
-    test::TestGenerator,
-};
 use pumpkin_util::random::{xoroshiro128::Xoroshiro, RandomDeriver, RandomImpl};
+pub use registry::{
+    get_world_gen, is_world_gen_registered, register_world_gen, unregister_world_gen,
+    GeneratorFactory, DEFAULT_GENERATOR, END_GENERATOR, NETHER_GENERATOR,
+};
 pub use seed::Seed;
 
-use generator::GeneratorInit;
-
-pub fn get_world_gen(seed: Seed) -> Box<dyn WorldGenerator> {
-    // TODO decide which WorldGenerator to pick based on config.
-    //Box::new(PlainsGenerator::new(seed))
-    Box::new(TestGenerator::new(seed))
-}
-
 #[derive(Getters)]
 pub struct GlobalRandomConfig {
     seed: u64,
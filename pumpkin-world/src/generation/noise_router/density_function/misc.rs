@@ -36,6 +36,12 @@ impl EndIsland {
         }
     }
 
+    /// The height value of the islands in a cell of 8x8 blocks, from -100 to 80. The islands
+    /// are solid where it is positive.
+    pub fn island_height(&self, cell_x: i32, cell_z: i32) -> f32 {
+        Self::sample_2d(&self.sampler, cell_x, cell_z)
+    }
+
     fn sample_2d(sampler: &SimplexNoiseSampler, x: i32, z: i32) -> f32 {
         let i = x / 2;
         let j = z / 2;
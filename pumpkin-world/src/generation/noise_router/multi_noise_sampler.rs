@@ -1,6 +1,6 @@
 use crate::{
-    generation::biome_coords, noise_router::density_function_ast::WrapperType,
-    GlobalProtoNoiseRouter,
+    biome::multi_noise::NoiseValuePoint, generation::biome_coords,
+    noise_router::density_function_ast::WrapperType, GlobalProtoNoiseRouter,
 };
 
 use super::{
@@ -45,7 +45,7 @@ pub struct MultiNoiseSampler<'a> {
 }
 
 impl<'a> MultiNoiseSampler<'a> {
-    pub fn sample(&mut self, biome_x: i32, biome_y: i32, biome_z: i32) {
+    pub fn sample(&mut self, biome_x: i32, biome_y: i32, biome_z: i32) -> NoiseValuePoint {
         let block_x = biome_coords::to_block(biome_x);
         let block_y = biome_coords::to_block(biome_y);
         let block_z = biome_coords::to_block(biome_z);
@@ -54,43 +54,50 @@ impl<'a> MultiNoiseSampler<'a> {
         let sample_options =
             ChunkNoiseFunctionSampleOptions::new(false, SampleAction::SkipCellCaches, 0, 0, 0);
 
-        let _temperature = ChunkNoiseFunctionComponent::sample_from_stack(
+        let temperature = ChunkNoiseFunctionComponent::sample_from_stack(
             &mut self.component_stack[..=self.temperature],
             &pos,
             &sample_options,
         ) as f32;
 
-        let _humidity = ChunkNoiseFunctionComponent::sample_from_stack(
+        let humidity = ChunkNoiseFunctionComponent::sample_from_stack(
             &mut self.component_stack[..=self.vegetation],
             &pos,
             &sample_options,
         ) as f32;
 
-        let _continentalness = ChunkNoiseFunctionComponent::sample_from_stack(
+        let continentalness = ChunkNoiseFunctionComponent::sample_from_stack(
             &mut self.component_stack[..=self.continents],
             &pos,
             &sample_options,
         ) as f32;
 
-        let _erosion = ChunkNoiseFunctionComponent::sample_from_stack(
+        let erosion = ChunkNoiseFunctionComponent::sample_from_stack(
             &mut self.component_stack[..=self.erosion],
             &pos,
             &sample_options,
         ) as f32;
 
-        let _depth = ChunkNoiseFunctionComponent::sample_from_stack(
+        let depth = ChunkNoiseFunctionComponent::sample_from_stack(
             &mut self.component_stack[..=self.depth],
             &pos,
             &sample_options,
         ) as f32;
 
-        let _weirdness = ChunkNoiseFunctionComponent::sample_from_stack(
+        let weirdness = ChunkNoiseFunctionComponent::sample_from_stack(
             &mut self.component_stack[..=self.ridges],
             &pos,
             &sample_options,
         ) as f32;
 
-        // TODO: Multi noise value here
+        NoiseValuePoint::new(
+            temperature,
+            humidity,
+            continentalness,
+            erosion,
+            depth,
+            weirdness,
+        )
     }
 
     pub fn generate(
@@ -186,8 +193,17 @@ impl<'a> MultiNoiseSampler<'a> {
                                 ChunkSpecificNoiseFunctionComponent::FlatCache(flat_cache),
                             ))
                         }
-                        _ => {
-                            panic!("These density functions should not be a part of the MultiNoiseSampler! We probably need to re-write code");
+                        // The climate is sampled at single positions, outside of the cells of
+                        // the chunk, so these wrappers sample their input directly. Only the
+                        // final density is interpolated, which the biomes do not depend on.
+                        WrapperType::CacheOnce
+                        | WrapperType::CellCache
+                        | WrapperType::Interpolated => {
+                            ChunkNoiseFunctionComponent::PassThrough(PassThrough {
+                                input_index: wrapper.input_index,
+                                max_value,
+                                min_value,
+                            })
                         }
                     }
                 }
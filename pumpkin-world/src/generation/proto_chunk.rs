@@ -1,16 +1,23 @@
+use pumpkin_data::chunk::Biome;
 use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
 
 use crate::{
+    biome::{BiomeSupplier, BiomeSupplierImpl},
     block::BlockState,
+    chunk::{ChunkSectionBiomes, BIOMES_PER_SUBCHUNK},
     generation::{
-        chunk_noise::CHUNK_DIM, generation_shapes::GenerationShape, positions::chunk_pos,
+        biome_coords, chunk_noise::CHUNK_DIM, generation_shapes::GenerationShape,
+        positions::chunk_pos,
     },
 };
 
 use super::{
     aquifer_sampler::{FluidLevel, FluidLevelSampler, FluidLevelSamplerImpl},
     chunk_noise::{ChunkNoiseGenerator, LAVA_BLOCK, STONE_BLOCK, WATER_BLOCK},
-    noise_router::proto_noise_router::GlobalProtoNoiseRouter,
+    noise_router::{
+        multi_noise_sampler::{MultiNoiseSampler, MultiNoiseSamplerBuilderOptions},
+        proto_noise_router::GlobalProtoNoiseRouter,
+    },
     positions::chunk_pos::{start_block_x, start_block_z},
     GlobalRandomConfig,
 };
@@ -44,11 +51,18 @@ impl FluidLevelSamplerImpl for StandardChunkFluidLevelSampler {
     }
 }
 
+/// The number of biome areas along each horizontal axis of a chunk
+const BIOMES_PER_AXIS: usize = CHUNK_DIM as usize >> 2;
+const BIOME_LAYER_SIZE: usize = BIOMES_PER_AXIS * BIOMES_PER_AXIS;
+
 pub struct ProtoChunk<'a> {
     chunk_pos: Vector2<i32>,
+    base_router: &'a GlobalProtoNoiseRouter,
     sampler: ChunkNoiseGenerator<'a>,
     // These are local positions
     flat_block_map: Vec<BlockState>,
+    // These are local biome positions in yzx order, so each subchunk is a contiguous slice
+    flat_biome_map: Vec<Biome>,
     // may want to use chunk status
 }
 
@@ -83,8 +97,13 @@ impl<'a> ProtoChunk<'a> {
 
         Self {
             chunk_pos,
+            base_router,
             sampler,
             flat_block_map: vec![BlockState::AIR; CHUNK_DIM as usize * CHUNK_DIM as usize * height],
+            flat_biome_map: vec![
+                Biome::Plains;
+                BIOME_LAYER_SIZE * biome_coords::from_block(height)
+            ],
         }
     }
 
@@ -115,6 +134,52 @@ impl<'a> ProtoChunk<'a> {
         }
     }
 
+    /// Returns the biome of the 4x4x4 area containing the local block position.
+    pub fn get_biome(&self, local_pos: &Vector3<i32>) -> Biome {
+        let biome_y = biome_coords::from_block(local_pos.y - self.sampler.min_y() as i32);
+        let biome_y = biome_y.clamp(
+            0,
+            biome_coords::from_block(self.sampler.height() as i32) - 1,
+        );
+        let index = biome_y as usize * BIOME_LAYER_SIZE
+            + biome_coords::from_block(local_pos.z & 15) as usize * BIOMES_PER_AXIS
+            + biome_coords::from_block(local_pos.x & 15) as usize;
+        self.flat_biome_map[index]
+    }
+
+    /// The biomes of each subchunk from the bottom up.
+    pub fn section_biomes(&self) -> Vec<ChunkSectionBiomes> {
+        self.flat_biome_map
+            .chunks_exact(BIOMES_PER_SUBCHUNK)
+            .map(|biomes| {
+                ChunkSectionBiomes::from_biomes(biomes.try_into().expect("Chunks are exact"))
+            })
+            .collect()
+    }
+
+    /// Samples the biome of each 4x4x4 area of the chunk from the climate noise.
+    pub fn populate_biomes(&mut self, supplier: &BiomeSupplier) {
+        let start_biome_x = biome_coords::from_block(chunk_pos::start_block_x(&self.chunk_pos));
+        let start_biome_z = biome_coords::from_block(chunk_pos::start_block_z(&self.chunk_pos));
+        let min_biome_y = biome_coords::from_block(self.sampler.min_y() as i32);
+
+        let mut noise = MultiNoiseSampler::generate(
+            self.base_router,
+            &MultiNoiseSamplerBuilderOptions::new(start_biome_x, start_biome_z, BIOMES_PER_AXIS),
+        );
+        for (index, biome) in self.flat_biome_map.iter_mut().enumerate() {
+            let x = (index % BIOMES_PER_AXIS) as i32;
+            let z = (index / BIOMES_PER_AXIS % BIOMES_PER_AXIS) as i32;
+            let y = (index / BIOME_LAYER_SIZE) as i32;
+            *biome = supplier.biome(
+                start_biome_x + x,
+                min_biome_y + y,
+                start_biome_z + z,
+                &mut noise,
+            );
+        }
+    }
+
     pub fn populate_noise(&mut self) {
         let horizontal_cell_block_count = self.sampler.horizontal_cell_block_count();
         let vertical_cell_block_count = self.sampler.vertical_cell_block_count();
@@ -235,9 +300,11 @@ impl<'a> ProtoChunk<'a> {
 mod test {
     use std::sync::LazyLock;
 
-    use pumpkin_util::math::vector2::Vector2;
+    use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
 
     use crate::{
+        biome::{BiomeSupplier, MultiNoiseBiomeSupplier},
+        chunk::SUBCHUNKS_COUNT,
         generation::{
             noise_router::{
                 density_function::{NoiseFunctionComponentRange, PassThrough},
@@ -246,7 +313,7 @@ mod test {
             GlobalRandomConfig,
         },
         noise_router::{density_function_ast::WrapperType, NOISE_ROUTER_ASTS},
-        read_data_from_file,
+        read_data_from_file, WORLD_LOWEST_Y,
     };
 
     use super::ProtoChunk;
@@ -494,4 +561,28 @@ mod test {
                 .collect::<Vec<u16>>()
         );
     }
+
+    #[test]
+    fn test_section_biomes_match_populated_biomes() {
+        let mut chunk = ProtoChunk::new(Vector2::new(7, 4), &BASE_NOISE_ROUTER, &RANDOM_CONFIG);
+        chunk.populate_biomes(&BiomeSupplier::MultiNoise(MultiNoiseBiomeSupplier));
+
+        let sections = chunk.section_biomes();
+        assert_eq!(sections.len(), SUBCHUNKS_COUNT);
+        for (section, biomes) in sections.iter().enumerate() {
+            let indices = biomes.palette_indices();
+            for (index, palette_index) in indices.into_iter().enumerate() {
+                let local_pos = Vector3::new(
+                    (index & 3) as i32 * 4,
+                    WORLD_LOWEST_Y as i32 + section as i32 * 16 + (index >> 4) as i32 * 4,
+                    (index >> 2 & 3) as i32 * 4,
+                );
+                let expected = chunk.get_biome(&local_pos);
+                assert_eq!(
+                    biomes.palette[palette_index],
+                    format!("minecraft:{}", expected.to_name())
+                );
+            }
+        }
+    }
 }
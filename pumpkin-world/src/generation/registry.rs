@@ -0,0 +1,109 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use super::{
+    generator::GeneratorInit,
+    implementation::{end::EndGenerator, nether::NetherGenerator, test::TestGenerator},
+    Seed, WorldGenerator,
+};
+
+/// Creates a world generator for the given seed.
+pub type GeneratorFactory = Arc<dyn Fn(Seed) -> Box<dyn WorldGenerator> + Send + Sync>;
+
+/// The name of the built-in generator, which is used unless a world selects another one.
+pub const DEFAULT_GENERATOR: &str = "default";
+
+/// The name of the built-in generator of the nether.
+pub const NETHER_GENERATOR: &str = "nether";
+
+/// The name of the built-in generator of the end.
+pub const END_GENERATOR: &str = "end";
+
+static GENERATORS: LazyLock<RwLock<HashMap<String, GeneratorFactory>>> = LazyLock::new(|| {
+    let mut generators: HashMap<String, GeneratorFactory> = HashMap::new();
+    generators.insert(
+        DEFAULT_GENERATOR.to_string(),
+        // TODO: Use PlainsGenerator once it is finished
+        Arc::new(|seed| Box::new(TestGenerator::new(seed))),
+    );
+    generators.insert(
+        NETHER_GENERATOR.to_string(),
+        Arc::new(|seed| Box::new(NetherGenerator::new(seed))),
+    );
+    generators.insert(
+        END_GENERATOR.to_string(),
+        Arc::new(|seed| Box::new(EndGenerator::new(seed))),
+    );
+    RwLock::new(generators)
+});
+
+/// Makes a world generator available under the given name, so worlds can select it in the config.
+///
+/// # Returns
+/// An error if a generator with that name is already registered.
+pub fn register_world_gen(name: &str, factory: GeneratorFactory) -> Result<(), String> {
+    let mut generators = GENERATORS.write().unwrap();
+    if generators.contains_key(name) {
+        return Err(format!("World generator {name} is already registered"));
+    }
+    generators.insert(name.to_string(), factory);
+    Ok(())
+}
+
+/// Removes a world generator. The default generator can not be removed.
+///
+/// Worlds which already use the generator keep using it.
+///
+/// # Returns
+/// Whether the generator was removed.
+pub fn unregister_world_gen(name: &str) -> bool {
+    if name == DEFAULT_GENERATOR {
+        return false;
+    }
+    GENERATORS.write().unwrap().remove(name).is_some()
+}
+
+/// Returns whether a world generator with the given name is registered.
+pub fn is_world_gen_registered(name: &str) -> bool {
+    GENERATORS.read().unwrap().contains_key(name)
+}
+
+/// Creates the world generator with the given name, or `None` if no such generator is registered.
+pub fn get_world_gen(name: &str, seed: Seed) -> Option<Box<dyn WorldGenerator>> {
+    // Clone the factory so the lock is not held while the generator is created
+    let factory = GENERATORS.read().unwrap().get(name).cloned()?;
+    Some(factory(seed))
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector2::Vector2;
+
+    use crate::chunk::{ChunkData, Subchunks};
+
+    use super::*;
+
+    struct VoidGenerator;
+
+    impl WorldGenerator for VoidGenerator {
+        fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
+            ChunkData::new(Subchunks::Single(0), Default::default(), at)
+        }
+    }
+
+    #[test]
+    fn register_and_unregister() {
+        assert!(is_world_gen_registered(DEFAULT_GENERATOR));
+        assert!(!is_world_gen_registered("test_void"));
+
+        register_world_gen("test_void", Arc::new(|_| Box::new(VoidGenerator))).unwrap();
+        assert!(register_world_gen("test_void", Arc::new(|_| Box::new(VoidGenerator))).is_err());
+        assert!(get_world_gen("test_void", Seed(0)).is_some());
+
+        assert!(unregister_world_gen("test_void"));
+        assert!(!unregister_world_gen(DEFAULT_GENERATOR));
+        assert!(get_world_gen("test_void", Seed(0)).is_none());
+    }
+}
@@ -0,0 +1,675 @@
+use std::sync::LazyLock;
+
+use pumpkin_util::{
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+
+use crate::{
+    block::{entity::SpawnerBlockEntity, BlockDirection},
+    generation::feature::{
+        default_state, large_feature_random, position_chance, state_with, ChunkRegion,
+    },
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+
+/// The chance of a mineshaft starting in a chunk
+const START_PROBABILITY: f64 = 0.004;
+/// How many pieces can be chained after the room
+const MAX_DEPTH: u32 = 8;
+/// How far pieces can start from the room, in blocks
+const MAX_DISTANCE: i32 = 80;
+/// How far a mineshaft can reach from its start chunk, in chunks
+pub const CHUNK_RANGE: i32 = 8;
+const SEA_LEVEL: i32 = 63;
+
+struct MineshaftBlocks {
+    cave_air: u16,
+    dirt: u16,
+    planks: u16,
+    fence: u16,
+    cobweb: u16,
+    spawner: u16,
+    rail_north_south: u16,
+    rail_east_west: u16,
+}
+
+static BLOCKS: LazyLock<MineshaftBlocks> = LazyLock::new(|| MineshaftBlocks {
+    cave_air: default_state("cave_air"),
+    dirt: default_state("dirt"),
+    planks: default_state("oak_planks"),
+    fence: default_state("oak_fence"),
+    cobweb: default_state("cobweb"),
+    spawner: default_state("spawner"),
+    rail_north_south: state_with("rail", &[("shape", "north_south")]),
+    rail_east_west: state_with("rail", &[("shape", "east_west")]),
+});
+
+/// A box of blocks, including both corners
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockBox {
+    pub min: Vector3<i32>,
+    pub max: Vector3<i32>,
+}
+
+impl BlockBox {
+    pub const fn new(
+        min_x: i32,
+        min_y: i32,
+        min_z: i32,
+        max_x: i32,
+        max_y: i32,
+        max_z: i32,
+    ) -> Self {
+        Self {
+            min: Vector3::new(min_x, min_y, min_z),
+            max: Vector3::new(max_x, max_y, max_z),
+        }
+    }
+
+    /// All blocks of a chunk
+    pub fn chunk(chunk: Vector2<i32>) -> Self {
+        Self::new(
+            chunk.x * 16,
+            WORLD_LOWEST_Y.into(),
+            chunk.z * 16,
+            chunk.x * 16 + 15,
+            i32::from(WORLD_MAX_Y) - 1,
+            chunk.z * 16 + 15,
+        )
+    }
+
+    /// A box `width` blocks wide, `height` blocks high and `length` blocks long, going in the
+    /// direction from the given corner
+    pub(super) fn oriented(
+        corner: Vector3<i32>,
+        width: i32,
+        height: i32,
+        length: i32,
+        direction: BlockDirection,
+    ) -> Self {
+        let Vector3 { x, y, z } = corner;
+        let max_y = y + height - 1;
+        match direction {
+            BlockDirection::North => Self::new(x, y, z - length + 1, x + width - 1, max_y, z),
+            BlockDirection::South => Self::new(x, y, z, x + width - 1, max_y, z + length - 1),
+            BlockDirection::West => Self::new(x - length + 1, y, z, x, max_y, z + width - 1),
+            _ => Self::new(x, y, z, x + length - 1, max_y, z + width - 1),
+        }
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+            && self.min.z <= other.max.z
+            && self.max.z >= other.min.z
+    }
+
+    /// The blocks inside of both boxes
+    pub(super) fn intersection(&self, other: &Self) -> Option<Self> {
+        self.intersects(other).then(|| Self {
+            min: Vector3::new(
+                self.min.x.max(other.min.x),
+                self.min.y.max(other.min.y),
+                self.min.z.max(other.min.z),
+            ),
+            max: Vector3::new(
+                self.max.x.min(other.max.x),
+                self.max.y.min(other.max.y),
+                self.max.z.min(other.max.z),
+            ),
+        })
+    }
+
+    fn encompass(&self, other: &Self) -> Self {
+        Self {
+            min: Vector3::new(
+                self.min.x.min(other.min.x),
+                self.min.y.min(other.min.y),
+                self.min.z.min(other.min.z),
+            ),
+            max: Vector3::new(
+                self.max.x.max(other.max.x),
+                self.max.y.max(other.max.y),
+                self.max.z.max(other.max.z),
+            ),
+        }
+    }
+
+    pub(super) fn positions(&self) -> impl Iterator<Item = Vector3<i32>> + '_ {
+        (self.min.x..=self.max.x).flat_map(move |x| {
+            (self.min.y..=self.max.y)
+                .flat_map(move |y| (self.min.z..=self.max.z).map(move |z| Vector3::new(x, y, z)))
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PieceKind {
+    /// The dirt floored cave the mineshaft starts in
+    Room,
+    /// A tunnel with wooden supports every 5 blocks. Spider corridors are full of cobwebs and have
+    /// a cave spider spawner.
+    Corridor {
+        rails: bool,
+        spider: bool,
+    },
+    Crossing {
+        two_floors: bool,
+    },
+    /// Goes down 5 blocks
+    Stairs,
+}
+
+#[derive(Clone, Copy)]
+struct Piece {
+    kind: PieceKind,
+    bounds: BlockBox,
+    direction: BlockDirection,
+    /// Seeds the random blocks of the piece, like rails and cobwebs. They are chosen by position,
+    /// so every chunk the piece crosses places the same blocks.
+    seed: u64,
+}
+
+/// The pieces of an abandoned mineshaft, generated from the world seed and the start chunk
+pub struct Mineshaft {
+    pieces: Vec<Piece>,
+}
+
+pub(super) const fn left(direction: BlockDirection) -> BlockDirection {
+    match direction {
+        BlockDirection::North => BlockDirection::West,
+        BlockDirection::West => BlockDirection::South,
+        BlockDirection::South => BlockDirection::East,
+        _ => BlockDirection::North,
+    }
+}
+
+pub(super) const fn is_along_x(direction: BlockDirection) -> bool {
+    matches!(direction, BlockDirection::West | BlockDirection::East)
+}
+
+struct MineshaftBuilder {
+    random: LegacyRand,
+    start: Vector3<i32>,
+    pieces: Vec<Piece>,
+}
+
+impl MineshaftBuilder {
+    fn collides(&self, bounds: &BlockBox) -> bool {
+        self.pieces
+            .iter()
+            .any(|piece| piece.bounds.intersects(bounds))
+    }
+
+    fn push(&mut self, kind: PieceKind, bounds: BlockBox, direction: BlockDirection) {
+        let seed = self.random.next_i64() as u64;
+        self.pieces.push(Piece {
+            kind,
+            bounds,
+            direction,
+            seed,
+        });
+    }
+
+    /// Adds a random piece entered at the given corner
+    fn add_piece(&mut self, entry: Vector3<i32>, direction: BlockDirection, depth: u32) {
+        if depth > MAX_DEPTH
+            || (entry.x - self.start.x).abs() > MAX_DISTANCE
+            || (entry.z - self.start.z).abs() > MAX_DISTANCE
+        {
+            return;
+        }
+        let roll = self.random.next_bounded_i32(100);
+        if roll >= 80 {
+            self.add_crossing(entry, direction, depth);
+        } else if roll >= 70 {
+            self.add_stairs(entry, direction, depth);
+        } else {
+            self.add_corridor(entry, direction, depth);
+        }
+    }
+
+    fn add_room(&mut self, chunk: Vector2<i32>) {
+        let (x, z) = (chunk.x * 16 + 2, chunk.z * 16 + 2);
+        let bounds = BlockBox::new(
+            x,
+            50,
+            z,
+            x + 7 + self.random.next_bounded_i32(6),
+            54 + self.random.next_bounded_i32(6),
+            z + 7 + self.random.next_bounded_i32(6),
+        );
+        self.push(PieceKind::Room, bounds, BlockDirection::North);
+
+        // Corridors leave the room on every side
+        let y = bounds.min.y + 1;
+        let width = bounds.max.x - bounds.min.x + 1;
+        let depth = bounds.max.z - bounds.min.z + 1;
+        for direction in [
+            BlockDirection::North,
+            BlockDirection::South,
+            BlockDirection::West,
+            BlockDirection::East,
+        ] {
+            let side = if is_along_x(direction) { depth } else { width };
+            let mut offset = 0;
+            loop {
+                offset += self.random.next_bounded_i32(side);
+                if offset + 3 > side {
+                    break;
+                }
+                let entry = match direction {
+                    BlockDirection::North => Vector3::new(x + offset, y, bounds.min.z - 1),
+                    BlockDirection::South => Vector3::new(x + offset, y, bounds.max.z + 1),
+                    BlockDirection::West => Vector3::new(bounds.min.x - 1, y, z + offset),
+                    _ => Vector3::new(bounds.max.x + 1, y, z + offset),
+                };
+                self.add_piece(entry, direction, 1);
+                offset += 4;
+            }
+        }
+    }
+
+    fn add_corridor(&mut self, entry: Vector3<i32>, direction: BlockDirection, depth: u32) {
+        let mut sections = self.random.next_bounded_i32(3) + 2;
+        let bounds = loop {
+            if sections == 0 {
+                return;
+            }
+            let bounds = BlockBox::oriented(entry, 3, 3, sections * 5, direction);
+            if !self.collides(&bounds) {
+                break bounds;
+            }
+            sections -= 1;
+        };
+        let rails = self.random.next_bounded_i32(3) == 0;
+        let spider = !rails && self.random.next_bounded_i32(23) == 0;
+        self.push(PieceKind::Corridor { rails, spider }, bounds, direction);
+
+        // Continue straight or turn at the end
+        let next = match self.random.next_bounded_i32(4) {
+            0 => left(direction),
+            1 => left(direction).opposite(),
+            _ => direction,
+        };
+        self.add_piece(end_exit(&bounds, direction, next), next, depth + 1);
+
+        // Branch off to the sides
+        if depth < MAX_DEPTH {
+            let y = bounds.min.y;
+            let (start, end) = if is_along_x(direction) {
+                (bounds.min.x, bounds.max.x)
+            } else {
+                (bounds.min.z, bounds.max.z)
+            };
+            let mut along = start + 3;
+            while along + 3 <= end {
+                let side = match self.random.next_bounded_i32(5) {
+                    0 => left(direction),
+                    1 => left(direction).opposite(),
+                    _ => {
+                        along += 5;
+                        continue;
+                    }
+                };
+                let entry = match side {
+                    BlockDirection::North => Vector3::new(along, y, bounds.min.z - 1),
+                    BlockDirection::South => Vector3::new(along, y, bounds.max.z + 1),
+                    BlockDirection::West => Vector3::new(bounds.min.x - 1, y, along),
+                    _ => Vector3::new(bounds.max.x + 1, y, along),
+                };
+                self.add_piece(entry, side, depth + 1);
+                along += 5;
+            }
+        }
+    }
+
+    fn add_crossing(&mut self, entry: Vector3<i32>, direction: BlockDirection, depth: u32) {
+        let two_floors = self.random.next_bounded_i32(4) == 0;
+        let top = entry.y + if two_floors { 6 } else { 2 };
+        let Vector3 { x, y, z } = entry;
+        let bounds = match direction {
+            BlockDirection::North => BlockBox::new(x - 1, y, z - 4, x + 3, top, z),
+            BlockDirection::South => BlockBox::new(x - 1, y, z, x + 3, top, z + 4),
+            BlockDirection::West => BlockBox::new(x - 4, y, z - 1, x, top, z + 3),
+            _ => BlockBox::new(x, y, z - 1, x + 4, top, z + 3),
+        };
+        if self.collides(&bounds) {
+            return;
+        }
+        self.push(PieceKind::Crossing { two_floors }, bounds, direction);
+
+        let floors: &[i32] = if two_floors { &[0, 4] } else { &[0] };
+        for &floor in floors {
+            for exit in [
+                BlockDirection::North,
+                BlockDirection::South,
+                BlockDirection::West,
+                BlockDirection::East,
+            ] {
+                if exit == direction.opposite() && floor == 0 {
+                    continue;
+                }
+                if floor != 0 && self.random.next_bool() {
+                    continue;
+                }
+                let y = bounds.min.y + floor;
+                let entry = match exit {
+                    BlockDirection::North => Vector3::new(bounds.min.x + 1, y, bounds.min.z - 1),
+                    BlockDirection::South => Vector3::new(bounds.min.x + 1, y, bounds.max.z + 1),
+                    BlockDirection::West => Vector3::new(bounds.min.x - 1, y, bounds.min.z + 1),
+                    _ => Vector3::new(bounds.max.x + 1, y, bounds.min.z + 1),
+                };
+                self.add_piece(entry, exit, depth + 1);
+            }
+        }
+    }
+
+    fn add_stairs(&mut self, entry: Vector3<i32>, direction: BlockDirection, depth: u32) {
+        let bounds = BlockBox::oriented(entry + Vector3::new(0, -5, 0), 3, 8, 9, direction);
+        if bounds.min.y <= i32::from(WORLD_LOWEST_Y) + 10 || self.collides(&bounds) {
+            return;
+        }
+        self.push(PieceKind::Stairs, bounds, direction);
+        self.add_piece(
+            end_exit(&bounds, direction, direction),
+            direction,
+            depth + 1,
+        );
+    }
+}
+
+/// The corner a piece continues from at the end of a piece going in `direction`, when the next
+/// piece goes in `next`
+pub(super) fn end_exit(
+    bounds: &BlockBox,
+    direction: BlockDirection,
+    next: BlockDirection,
+) -> Vector3<i32> {
+    let BlockBox { min, max } = *bounds;
+    let y = min.y;
+    match (direction, next) {
+        (BlockDirection::North, BlockDirection::West) => Vector3::new(min.x - 1, y, min.z),
+        (BlockDirection::North, BlockDirection::East) => Vector3::new(max.x + 1, y, min.z),
+        (BlockDirection::South, BlockDirection::West) => Vector3::new(min.x - 1, y, max.z - 2),
+        (BlockDirection::South, BlockDirection::East) => Vector3::new(max.x + 1, y, max.z - 2),
+        (BlockDirection::West, BlockDirection::North) => Vector3::new(min.x, y, min.z - 1),
+        (BlockDirection::West, BlockDirection::South) => Vector3::new(min.x, y, max.z + 1),
+        (BlockDirection::East, BlockDirection::North) => Vector3::new(max.x - 2, y, min.z - 1),
+        (BlockDirection::East, BlockDirection::South) => Vector3::new(max.x - 2, y, max.z + 1),
+        (_, BlockDirection::North) => Vector3::new(min.x, y, min.z - 1),
+        (_, BlockDirection::South) => Vector3::new(min.x, y, max.z + 1),
+        (_, BlockDirection::West) => Vector3::new(min.x - 1, y, min.z),
+        _ => Vector3::new(max.x + 1, y, min.z),
+    }
+}
+
+impl Mineshaft {
+    /// Generates the mineshaft starting in a chunk, if one starts there. The same seed always
+    /// gives the same pieces.
+    pub fn generate(seed: u64, chunk: Vector2<i32>) -> Option<Self> {
+        let mut random = large_feature_random(seed, chunk);
+        if random.next_f64() >= START_PROBABILITY {
+            return None;
+        }
+        let mut builder = MineshaftBuilder {
+            random,
+            start: Vector3::new(chunk.x * 16 + 2, 50, chunk.z * 16 + 2),
+            pieces: Vec::new(),
+        };
+        builder.add_room(chunk);
+
+        // Moves the mineshaft to a random height at least 10 blocks below sea level, like vanilla
+        let bounds = builder
+            .pieces
+            .iter()
+            .map(|piece| piece.bounds)
+            .reduce(|a, b| a.encompass(&b))?;
+        let max_top = SEA_LEVEL - 10;
+        let mut top = bounds.max.y - bounds.min.y + 1 + i32::from(WORLD_LOWEST_Y) + 1;
+        if top < max_top {
+            top += builder.random.next_bounded_i32(max_top - top);
+        }
+        let offset = Vector3::new(0, top - bounds.max.y, 0);
+        for piece in &mut builder.pieces {
+            piece.bounds.min = piece.bounds.min + offset;
+            piece.bounds.max = piece.bounds.max + offset;
+        }
+
+        Some(Self {
+            pieces: builder.pieces,
+        })
+    }
+
+    /// All mineshafts which can reach into a chunk
+    pub fn near(seed: u64, chunk: Vector2<i32>) -> Vec<Self> {
+        let chunk_bounds = BlockBox::chunk(chunk);
+        let mut mineshafts = Vec::new();
+        for x in -CHUNK_RANGE..=CHUNK_RANGE {
+            for z in -CHUNK_RANGE..=CHUNK_RANGE {
+                let start = Vector2::new(chunk.x + x, chunk.z + z);
+                if let Some(mineshaft) = Self::generate(seed, start) {
+                    if mineshaft.intersects(&chunk_bounds) {
+                        mineshafts.push(mineshaft);
+                    }
+                }
+            }
+        }
+        mineshafts
+    }
+
+    pub fn intersects(&self, bounds: &BlockBox) -> bool {
+        self.pieces
+            .iter()
+            .any(|piece| piece.bounds.intersects(bounds))
+    }
+
+    /// Places the blocks of the pieces which are inside of the chunk of the region
+    pub fn place(&self, region: &mut ChunkRegion) {
+        let chunk_bounds = BlockBox::chunk(region.chunk_position());
+        for piece in &self.pieces {
+            if let Some(bounds) = piece.bounds.intersection(&chunk_bounds) {
+                piece.place(region, &bounds);
+            }
+        }
+    }
+}
+
+impl Piece {
+    /// Places the blocks of the piece inside of `clip`
+    fn place(&self, region: &mut ChunkRegion, clip: &BlockBox) {
+        let blocks = &*BLOCKS;
+        let BlockBox { min, max } = self.bounds;
+        match self.kind {
+            PieceKind::Room => {
+                let center_x = (min.x + max.x) as f32 / 2.0;
+                let center_z = (min.z + max.z) as f32 / 2.0;
+                let radius_x = (max.x - min.x) as f32 / 2.0;
+                let radius_z = (max.z - min.z) as f32 / 2.0;
+                let radius_y = (max.y - min.y - 3).max(1) as f32;
+                for position in clip.positions() {
+                    let y = position.y - min.y;
+                    if y == 0 {
+                        region.set(position, blocks.dirt);
+                    } else if y <= 3 {
+                        region.set(position, blocks.cave_air);
+                    } else {
+                        // The ceiling is a dome
+                        let dx = (position.x as f32 - center_x) / radius_x;
+                        let dy = (y - 3) as f32 / radius_y;
+                        let dz = (position.z as f32 - center_z) / radius_z;
+                        if dx * dx + dy * dy + dz * dz <= 1.05 {
+                            region.set(position, blocks.cave_air);
+                        }
+                    }
+                }
+            }
+            PieceKind::Corridor { rails, spider } => {
+                let along_x = is_along_x(self.direction);
+                let length = if along_x {
+                    max.x - min.x + 1
+                } else {
+                    max.z - min.z + 1
+                };
+                for position in clip.positions() {
+                    let (along, across) = if along_x {
+                        (position.x - min.x, position.z - min.z)
+                    } else {
+                        (position.z - min.z, position.x - min.x)
+                    };
+                    let y = position.y - min.y;
+                    let support = along % 5 == 2;
+                    let cobweb_chance = if spider { 0.6 } else { 0.05 };
+
+                    let state = if support && y < 2 && across != 1 {
+                        blocks.fence
+                    } else if support && y == 2 {
+                        blocks.planks
+                    } else if spider && along == length / 2 && across == 1 && y == 0 {
+                        region.set(position, blocks.spawner);
+                        region.add_block_entity(Box::new(SpawnerBlockEntity::with_entity(
+                            BlockPos(position),
+                            "minecraft:cave_spider",
+                        )));
+                        continue;
+                    } else if (y == 2 || (spider && across != 1))
+                        && position_chance(self.seed, position, cobweb_chance)
+                    {
+                        blocks.cobweb
+                    } else if rails
+                        && y == 0
+                        && across == 1
+                        && position_chance(self.seed, position, 0.7)
+                    {
+                        if along_x {
+                            blocks.rail_east_west
+                        } else {
+                            blocks.rail_north_south
+                        }
+                    } else {
+                        blocks.cave_air
+                    };
+                    region.set(position, state);
+                }
+                self.place_floor(region, clip, |_, _| true);
+            }
+            PieceKind::Crossing { two_floors } => {
+                let (width, depth) = (max.x - min.x, max.z - min.z);
+                let is_arm = |x: i32, z: i32| (1..width).contains(&x) || (1..depth).contains(&z);
+                for position in clip.positions() {
+                    let (x, y, z) = (position.x - min.x, position.y - min.y, position.z - min.z);
+                    let pillar = (x == 1 || x == width - 1) && (z == 1 || z == depth - 1);
+                    let open = if two_floors && y == 3 {
+                        (1..width).contains(&x) && (1..depth).contains(&z)
+                    } else {
+                        is_arm(x, z)
+                    };
+                    if pillar {
+                        region.set(position, blocks.planks);
+                    } else if open {
+                        region.set(position, blocks.cave_air);
+                    }
+                }
+                self.place_floor(region, clip, is_arm);
+            }
+            PieceKind::Stairs => {
+                for position in clip.positions() {
+                    // The stairs go down from the entry of the piece
+                    let step = match self.direction {
+                        BlockDirection::North => max.z - position.z,
+                        BlockDirection::South => position.z - min.z,
+                        BlockDirection::West => max.x - position.x,
+                        _ => position.x - min.x,
+                    };
+                    let y = position.y - min.y;
+                    let floor = match step {
+                        0..=1 => 5,
+                        2..=6 => {
+                            let i = step - 2;
+                            5 - i - i32::from(i < 4)
+                        }
+                        _ => 0,
+                    };
+                    let ceiling = match step {
+                        0..=1 => 7,
+                        2..=6 => 7 - (step - 2),
+                        _ => 2,
+                    };
+                    if (floor..=ceiling).contains(&y) {
+                        region.set(position, blocks.cave_air);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fills holes below the piece with planks, so it can cross caves
+    fn place_floor(
+        &self,
+        region: &mut ChunkRegion,
+        clip: &BlockBox,
+        is_inside: impl Fn(i32, i32) -> bool,
+    ) {
+        let floor_y = self.bounds.min.y - 1;
+        if floor_y < i32::from(WORLD_LOWEST_Y) {
+            return;
+        }
+        for x in clip.min.x..=clip.max.x {
+            for z in clip.min.z..=clip.max.z {
+                let position = Vector3::new(x, floor_y, z);
+                if is_inside(x - self.bounds.min.x, z - self.bounds.min.z)
+                    && !region.is_solid(position)
+                {
+                    region.set(position, BLOCKS.planks);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::Mineshaft;
+
+    fn find_start(seed: u64) -> (Vector2<i32>, Mineshaft) {
+        (0..100)
+            .flat_map(|x| (0..100).map(move |z| Vector2::new(x, z)))
+            .find_map(|chunk| Mineshaft::generate(seed, chunk).map(|shaft| (chunk, shaft)))
+            .expect("Mineshafts start in about every 250th chunk")
+    }
+
+    #[test]
+    fn pieces_are_seeded_and_do_not_overlap() {
+        let (chunk, mineshaft) = find_start(1234);
+        assert!(mineshaft.pieces.len() > 1);
+        for (i, a) in mineshaft.pieces.iter().enumerate() {
+            for b in &mineshaft.pieces[i + 1..] {
+                assert!(!a.bounds.intersects(&b.bounds));
+            }
+            // Below sea level
+            assert!(a.bounds.max.y < 63);
+        }
+
+        let again = Mineshaft::generate(1234, chunk).unwrap();
+        assert_eq!(
+            mineshaft
+                .pieces
+                .iter()
+                .map(|piece| piece.bounds)
+                .collect::<Vec<_>>(),
+            again
+                .pieces
+                .iter()
+                .map(|piece| piece.bounds)
+                .collect::<Vec<_>>()
+        );
+        assert!(Mineshaft::near(1234, chunk)
+            .iter()
+            .any(|near| near.pieces[0].bounds == mineshaft.pieces[0].bounds));
+    }
+}
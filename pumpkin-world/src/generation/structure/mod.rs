@@ -0,0 +1,239 @@
+use pumpkin_data::chunk::Biome;
+use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
+
+use placement::{RandomSpreadStructurePlacement, SpreadType};
+
+pub mod mineshaft;
+pub mod placement;
+pub mod village;
+
+/// The structures which can generate in the world
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StructureType {
+    VillagePlains,
+    VillageDesert,
+    VillageSavanna,
+    VillageSnowy,
+    VillageTaiga,
+}
+
+impl StructureType {
+    /// Parses a structure from its name, without the namespace
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "village_plains" => Some(Self::VillagePlains),
+            "village_desert" => Some(Self::VillageDesert),
+            "village_savanna" => Some(Self::VillageSavanna),
+            "village_snowy" => Some(Self::VillageSnowy),
+            "village_taiga" => Some(Self::VillageTaiga),
+            _ => None,
+        }
+    }
+
+    pub const fn to_name(&self) -> &'static str {
+        match self {
+            Self::VillagePlains => "village_plains",
+            Self::VillageDesert => "village_desert",
+            Self::VillageSavanna => "village_savanna",
+            Self::VillageSnowy => "village_snowy",
+            Self::VillageTaiga => "village_taiga",
+        }
+    }
+
+    /// The biomes the structure can start in
+    pub const fn biomes(&self) -> &'static [Biome] {
+        match self {
+            Self::VillagePlains => &[Biome::Plains, Biome::Meadow],
+            Self::VillageDesert => &[Biome::Desert],
+            Self::VillageSavanna => &[Biome::Savanna],
+            Self::VillageSnowy => &[Biome::SnowyPlains],
+            Self::VillageTaiga => &[Biome::Taiga],
+        }
+    }
+}
+
+/// Structures which share a placement, so at most one of them starts in each region
+pub struct StructureSet {
+    pub name: &'static str,
+    pub structures: &'static [StructureType],
+    pub placement: RandomSpreadStructurePlacement,
+}
+
+pub const VILLAGES: StructureSet = StructureSet {
+    name: "villages",
+    structures: &[
+        StructureType::VillagePlains,
+        StructureType::VillageDesert,
+        StructureType::VillageSavanna,
+        StructureType::VillageSnowy,
+        StructureType::VillageTaiga,
+    ],
+    placement: RandomSpreadStructurePlacement {
+        spacing: 34,
+        separation: 8,
+        salt: 10_387_312,
+        spread_type: SpreadType::Linear,
+    },
+};
+
+pub const STRUCTURE_SETS: [&StructureSet; 1] = [&VILLAGES];
+
+/// Returns the structure set containing the structure
+pub fn structure_set(structure: StructureType) -> &'static StructureSet {
+    STRUCTURE_SETS
+        .into_iter()
+        .find(|set| set.structures.contains(&structure))
+        .expect("Every structure belongs to a set")
+}
+
+/// A structure starting in a chunk
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StructureStart {
+    pub structure: StructureType,
+    pub chunk: Vector2<i32>,
+}
+
+impl StructureSet {
+    /// Returns the structure of the set which starts in the chunk, if any.
+    ///
+    /// `biome_at` returns the biome at a block position. The biome is checked in the middle of the
+    /// start chunk at sea level, as the pieces and height of the structure are not known yet.
+    pub fn start_in(
+        &self,
+        seed: u64,
+        chunk: Vector2<i32>,
+        biome_at: impl Fn(Vector3<i32>) -> Biome,
+    ) -> Option<StructureStart> {
+        if !self.placement.is_start_chunk(seed, chunk) {
+            return None;
+        }
+        let biome = biome_at(Vector3::new(chunk.x * 16 + 8, 63, chunk.z * 16 + 8));
+        self.structures
+            .iter()
+            .find(|structure| structure.biomes().contains(&biome))
+            .map(|structure| StructureStart {
+                structure: *structure,
+                chunk,
+            })
+    }
+
+    /// Returns the starts of the set within `range` chunks of the given chunk, which are the ones
+    /// whose pieces can reach into it.
+    pub fn starts_near(
+        &self,
+        seed: u64,
+        chunk: Vector2<i32>,
+        range: i32,
+        biome_at: impl Fn(Vector3<i32>) -> Biome,
+    ) -> Vec<StructureStart> {
+        let spacing = self.placement.spacing;
+        let mut starts = Vec::new();
+        for region_x in
+            (chunk.x - range).div_euclid(spacing)..=(chunk.x + range).div_euclid(spacing)
+        {
+            for region_z in
+                (chunk.z - range).div_euclid(spacing)..=(chunk.z + range).div_euclid(spacing)
+            {
+                let region_chunk = Vector2::new(region_x * spacing, region_z * spacing);
+                let start_chunk = self.placement.start_chunk(seed, region_chunk);
+                if (start_chunk.x - chunk.x).abs() > range
+                    || (start_chunk.z - chunk.z).abs() > range
+                {
+                    continue;
+                }
+                if let Some(start) = self.start_in(seed, start_chunk, &biome_at) {
+                    starts.push(start);
+                }
+            }
+        }
+        starts
+    }
+
+    /// Finds the closest start of the structures accepted by `filter`, searching the regions in
+    /// growing squares around the center chunk like vanilla. `radius` is in regions.
+    pub fn locate(
+        &self,
+        seed: u64,
+        center: Vector2<i32>,
+        radius: i32,
+        filter: impl Fn(StructureType) -> bool,
+        biome_at: impl Fn(Vector3<i32>) -> Biome,
+    ) -> Option<StructureStart> {
+        let spacing = self.placement.spacing;
+        for ring in 0..=radius {
+            let mut closest: Option<(i64, StructureStart)> = None;
+            for x in -ring..=ring {
+                for z in -ring..=ring {
+                    if x.abs() != ring && z.abs() != ring {
+                        continue;
+                    }
+                    let region_chunk = Vector2::new(center.x + spacing * x, center.z + spacing * z);
+                    let chunk = self.placement.start_chunk(seed, region_chunk);
+                    let Some(start) = self
+                        .start_in(seed, chunk, &biome_at)
+                        .filter(|start| filter(start.structure))
+                    else {
+                        continue;
+                    };
+
+                    let dx = i64::from(chunk.x - center.x);
+                    let dz = i64::from(chunk.z - center.z);
+                    let distance = dx * dx + dz * dz;
+                    if closest.is_none_or(|(closest, _)| distance < closest) {
+                        closest = Some((distance, start));
+                    }
+                }
+            }
+            if let Some((_, start)) = closest {
+                return Some(start);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::chunk::Biome;
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::{StructureType, VILLAGES};
+
+    #[test]
+    fn starts_are_spread_out() {
+        let placement = VILLAGES.placement;
+        for region_x in -5..5 {
+            for region_z in -5..5 {
+                let region = Vector2::new(region_x * 34, region_z * 34);
+                let start = placement.start_chunk(1234, region);
+                assert!((start.x - region.x) < 34 - 8 && start.x >= region.x);
+                assert!((start.z - region.z) < 34 - 8 && start.z >= region.z);
+                // Every chunk of the region agrees on the start
+                assert_eq!(
+                    placement.start_chunk(1234, region + Vector2::new(33, 33)),
+                    start
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn locates_closest_village() {
+        let biome_at = |_| Biome::Desert;
+        let start = VILLAGES
+            .locate(1234, Vector2::new(0, 0), 10, |_| true, biome_at)
+            .unwrap();
+        assert_eq!(start.structure, StructureType::VillageDesert);
+        assert!(VILLAGES.placement.is_start_chunk(1234, start.chunk));
+
+        // The region of the center is searched first
+        assert_eq!(
+            start.chunk,
+            VILLAGES.placement.start_chunk(1234, Vector2::new(0, 0))
+        );
+
+        assert!(VILLAGES
+            .locate(1234, Vector2::new(0, 0), 10, |_| true, |_| Biome::Ocean)
+            .is_none());
+    }
+}
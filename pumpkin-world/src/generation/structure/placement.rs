@@ -0,0 +1,63 @@
+use pumpkin_util::{
+    math::vector2::Vector2,
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+
+/// How the start chunk is chosen within its region
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpreadType {
+    Linear,
+    /// Prefers the center of the region
+    Triangular,
+}
+
+impl SpreadType {
+    fn get(self, random: &mut LegacyRand, bound: i32) -> i32 {
+        match self {
+            Self::Linear => random.next_bounded_i32(bound),
+            Self::Triangular => {
+                (random.next_bounded_i32(bound) + random.next_bounded_i32(bound)) / 2
+            }
+        }
+    }
+}
+
+/// Places one structure start in each square region of `spacing` chunks, at a position chosen by
+/// the seed, keeping at least `separation` chunks between starts of neighbouring regions.
+#[derive(Clone, Copy, Debug)]
+pub struct RandomSpreadStructurePlacement {
+    pub spacing: i32,
+    pub separation: i32,
+    pub salt: i32,
+    pub spread_type: SpreadType,
+}
+
+impl RandomSpreadStructurePlacement {
+    /// Returns the chunk the structure of the region containing the given chunk starts in.
+    pub fn start_chunk(&self, seed: u64, chunk: Vector2<i32>) -> Vector2<i32> {
+        let region_x = chunk.x.div_euclid(self.spacing);
+        let region_z = chunk.z.div_euclid(self.spacing);
+
+        let mut random = LegacyRand::from_seed(region_seed(seed, region_x, region_z, self.salt));
+        let bound = self.spacing - self.separation;
+        let offset_x = self.spread_type.get(&mut random, bound);
+        let offset_z = self.spread_type.get(&mut random, bound);
+        Vector2::new(
+            region_x * self.spacing + offset_x,
+            region_z * self.spacing + offset_z,
+        )
+    }
+
+    /// Whether a structure of this placement can start in the given chunk
+    pub fn is_start_chunk(&self, seed: u64, chunk: Vector2<i32>) -> bool {
+        self.start_chunk(seed, chunk) == chunk
+    }
+}
+
+fn region_seed(seed: u64, region_x: i32, region_z: i32, salt: i32) -> u64 {
+    (i64::from(region_x)
+        .wrapping_mul(341_873_128_712)
+        .wrapping_add(i64::from(region_z).wrapping_mul(132_897_987_541))
+        .wrapping_add(seed as i64)
+        .wrapping_add(i64::from(salt))) as u64
+}
@@ -0,0 +1,590 @@
+use std::sync::LazyLock;
+
+use pumpkin_util::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+
+use crate::{
+    block::BlockDirection,
+    generation::feature::{
+        default_state, large_feature_random, position_chance, state_with, ChunkRegion,
+    },
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+
+use super::{
+    mineshaft::{end_exit, is_along_x, left, BlockBox},
+    StructureStart, StructureType,
+};
+
+/// How far a village can reach from its start chunk, in chunks
+pub const CHUNK_RANGE: i32 = 7;
+/// How many streets can be chained after the ones leaving the well
+const MAX_DEPTH: u32 = 3;
+/// How far streets can start from the well, in blocks
+const MAX_DISTANCE: i32 = 64;
+/// How far foundations reach down to the terrain below a piece, and how far terrain above a piece
+/// is cleared
+const MAX_ADAPTATION: i32 = 12;
+
+/// The blocks a village type is built from
+struct Palette {
+    foundation: u16,
+    planks: u16,
+    log: u16,
+    roof: u16,
+    fence: u16,
+    door: &'static str,
+    path: u16,
+    /// Blocks of the pieces which are replaced by another block with a chance, like cobblestone
+    /// turning mossy
+    processors: Vec<(u16, u16, f32)>,
+}
+
+impl Palette {
+    /// Applies the processors to a block of a piece. The result only depends on the seed and the
+    /// position, so every chunk a piece crosses makes the same choices.
+    fn process(&self, seed: u64, position: Vector3<i32>, state: u16) -> u16 {
+        self.processors
+            .iter()
+            .find(|(from, _, chance)| *from == state && position_chance(seed, position, *chance))
+            .map_or(state, |(_, to, _)| *to)
+    }
+}
+
+struct VillageBlocks {
+    air: u16,
+    water: u16,
+    glass_pane: u16,
+    farmland: u16,
+    young_wheat: u16,
+    wheat: u16,
+    crafting_table: u16,
+}
+
+static BLOCKS: LazyLock<VillageBlocks> = LazyLock::new(|| VillageBlocks {
+    air: default_state("air"),
+    water: default_state("water"),
+    glass_pane: default_state("glass_pane"),
+    farmland: state_with("farmland", &[("moisture", "7")]),
+    young_wheat: state_with("wheat", &[("age", "3")]),
+    wheat: state_with("wheat", &[("age", "7")]),
+    crafting_table: default_state("crafting_table"),
+});
+
+fn palette(wood: &str, foundation: &str, mossy_chance: f32) -> Palette {
+    let foundation = default_state(foundation);
+    let cobblestone = default_state("cobblestone");
+    Palette {
+        foundation,
+        planks: default_state(&format!("{wood}_planks")),
+        log: default_state(&format!("{wood}_log")),
+        roof: state_with(&format!("{wood}_slab"), &[("type", "bottom")]),
+        fence: default_state(&format!("{wood}_fence")),
+        door: match wood {
+            "acacia" => "acacia_door",
+            "spruce" => "spruce_door",
+            _ => "oak_door",
+        },
+        path: default_state("dirt_path"),
+        processors: if foundation == cobblestone {
+            vec![(
+                cobblestone,
+                default_state("mossy_cobblestone"),
+                mossy_chance,
+            )]
+        } else {
+            Vec::new()
+        },
+    }
+}
+
+static PLAINS: LazyLock<Palette> = LazyLock::new(|| palette("oak", "cobblestone", 0.1));
+static SAVANNA: LazyLock<Palette> = LazyLock::new(|| palette("acacia", "cobblestone", 0.1));
+static TAIGA: LazyLock<Palette> = LazyLock::new(|| palette("spruce", "cobblestone", 0.2));
+static SNOWY: LazyLock<Palette> = LazyLock::new(|| Palette {
+    log: default_state("stripped_spruce_log"),
+    roof: default_state("snow_block"),
+    ..palette("spruce", "cobblestone", 0.05)
+});
+static DESERT: LazyLock<Palette> = LazyLock::new(|| Palette {
+    planks: default_state("smooth_sandstone"),
+    log: default_state("cut_sandstone"),
+    roof: state_with("smooth_sandstone_slab", &[("type", "bottom")]),
+    processors: vec![(
+        default_state("sandstone"),
+        default_state("chiseled_sandstone"),
+        0.05,
+    )],
+    ..palette("oak", "sandstone", 0.0)
+});
+
+fn palette_of(structure: StructureType) -> &'static Palette {
+    match structure {
+        StructureType::VillagePlains => &PLAINS,
+        StructureType::VillageDesert => &DESERT,
+        StructureType::VillageSavanna => &SAVANNA,
+        StructureType::VillageSnowy => &SNOWY,
+        StructureType::VillageTaiga => &TAIGA,
+    }
+}
+
+const fn direction_name(direction: BlockDirection) -> &'static str {
+    match direction {
+        BlockDirection::North => "north",
+        BlockDirection::South => "south",
+        BlockDirection::West => "west",
+        _ => "east",
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PieceKind {
+    /// The meeting point in the start chunk, which the first streets leave from
+    Well,
+    /// A path 3 blocks wide which follows the terrain
+    Street,
+    SmallHouse,
+    LargeHouse,
+    /// Wheat around a channel of water
+    Farm,
+}
+
+impl PieceKind {
+    /// The width along the street, the depth away from it and the height of the piece
+    const fn size(self) -> (i32, i32, i32) {
+        match self {
+            Self::Well => (6, 6, 5),
+            Self::Street => (3, 0, 0),
+            Self::SmallHouse => (5, 5, 5),
+            Self::LargeHouse => (7, 7, 6),
+            Self::Farm => (7, 9, 2),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Piece {
+    kind: PieceKind,
+    bounds: BlockBox,
+    /// The side of the piece which faces the street, or the direction a street goes in
+    facing: BlockDirection,
+    /// Seeds the processors of the piece
+    seed: u64,
+}
+
+/// The pieces of a village, generated from the world seed and its start. Pieces which don't follow
+/// the terrain are placed at the height of the terrain below their middle, which is sampled from
+/// the noise when the village is generated, so every chunk places them at the same height.
+///
+/// Vanilla assembles villages from the jigsaw pieces of structure templates. The templates are not
+/// part of the assets, so the pieces here are laid out and built in code instead.
+// TODO: Assemble villages from the vanilla template pools once the templates are extracted
+pub struct Village {
+    structure: StructureType,
+    pieces: Vec<Piece>,
+}
+
+struct VillageBuilder<H> {
+    random: LegacyRand,
+    start: Vector3<i32>,
+    pieces: Vec<Piece>,
+    height_at: H,
+}
+
+/// Whether two pieces take up the same columns. Pieces of a village never stack, and streets reach
+/// from the bottom to the top of the world.
+fn overlaps(a: &BlockBox, b: &BlockBox) -> bool {
+    a.min.x <= b.max.x && a.max.x >= b.min.x && a.min.z <= b.max.z && a.max.z >= b.min.z
+}
+
+impl<H: FnMut(Vector2<i32>) -> i32> VillageBuilder<H> {
+    fn collides(&self, bounds: &BlockBox) -> bool {
+        self.pieces
+            .iter()
+            .any(|piece| overlaps(&piece.bounds, bounds))
+    }
+
+    fn push(&mut self, kind: PieceKind, bounds: BlockBox, facing: BlockDirection) {
+        let seed = self.random.next_i64() as u64;
+        self.pieces.push(Piece {
+            kind,
+            bounds,
+            facing,
+            seed,
+        });
+    }
+
+    /// Adds a rigid piece with the given footprint, standing on the terrain below its middle
+    fn add_rigid(
+        &mut self,
+        kind: PieceKind,
+        min: Vector2<i32>,
+        max: Vector2<i32>,
+        facing: BlockDirection,
+    ) {
+        let (_, _, height) = kind.size();
+        let footprint = BlockBox::new(min.x, 0, min.z, max.x, 0, max.z);
+        if self.collides(&footprint) {
+            return;
+        }
+        let ground = (self.height_at)(Vector2::new((min.x + max.x) / 2, (min.z + max.z) / 2));
+        // The well is sunk into the ground
+        let base = if kind == PieceKind::Well {
+            ground - 1
+        } else {
+            ground
+        };
+        let bounds = BlockBox::new(min.x, base, min.z, max.x, base + height - 1, max.z);
+        self.push(kind, bounds, facing);
+    }
+
+    fn add_well(&mut self) {
+        let Vector3 { x, z, .. } = self.start;
+        let (width, depth, _) = PieceKind::Well.size();
+        let min = Vector2::new(x - width / 2, z - depth / 2);
+        let max = Vector2::new(min.x + width - 1, min.z + depth - 1);
+        self.add_rigid(PieceKind::Well, min, max, BlockDirection::North);
+
+        let well = self.pieces[0].bounds;
+        let (center_x, center_z) = (min.x + width / 2, min.z + depth / 2);
+        for direction in BlockDirection::horizontal() {
+            let entry = match direction {
+                BlockDirection::North => Vector3::new(center_x - 1, 0, well.min.z - 1),
+                BlockDirection::South => Vector3::new(center_x - 1, 0, well.max.z + 1),
+                BlockDirection::West => Vector3::new(well.min.x - 1, 0, center_z - 1),
+                _ => Vector3::new(well.max.x + 1, 0, center_z - 1),
+            };
+            self.add_street(entry, direction, 1);
+        }
+    }
+
+    fn add_street(&mut self, entry: Vector3<i32>, direction: BlockDirection, depth: u32) {
+        if depth > MAX_DEPTH
+            || (entry.x - self.start.x).abs() > MAX_DISTANCE
+            || (entry.z - self.start.z).abs() > MAX_DISTANCE
+        {
+            return;
+        }
+        let height = i32::from(WORLD_MAX_Y) - i32::from(WORLD_LOWEST_Y);
+        let corner = Vector3::new(entry.x, WORLD_LOWEST_Y.into(), entry.z);
+        let mut length = 12 + self.random.next_bounded_i32(17);
+        let bounds = loop {
+            if length < 8 {
+                return;
+            }
+            let bounds = BlockBox::oriented(corner, 3, height, length, direction);
+            if !self.collides(&bounds) {
+                break bounds;
+            }
+            length -= 4;
+        };
+        self.push(PieceKind::Street, bounds, direction);
+
+        // Buildings line both sides of the street, facing it
+        let (start, end) = if is_along_x(direction) {
+            (bounds.min.x, bounds.max.x)
+        } else {
+            (bounds.min.z, bounds.max.z)
+        };
+        let mut along = start + 1;
+        while along < end {
+            let kind = match self.random.next_bounded_i32(10) {
+                0..=4 => PieceKind::SmallHouse,
+                5..=7 => PieceKind::LargeHouse,
+                _ => PieceKind::Farm,
+            };
+            let (width, _, _) = kind.size();
+            if along + width - 1 > end {
+                break;
+            }
+            for side in [left(direction), left(direction).opposite()] {
+                if self.random.next_bounded_i32(3) != 0 {
+                    self.add_building(kind, &bounds, side, along);
+                }
+            }
+            along += width + 1;
+        }
+
+        // Continue straight or branch off at the end
+        let exits = match self.random.next_bounded_i32(4) {
+            0 => vec![left(direction)],
+            1 => vec![left(direction).opposite()],
+            2 => vec![left(direction), left(direction).opposite()],
+            _ => vec![direction],
+        };
+        for next in exits {
+            self.add_street(end_exit(&bounds, direction, next), next, depth + 1);
+        }
+    }
+
+    /// Adds a building on the given side of a street, starting `along` blocks into it
+    fn add_building(
+        &mut self,
+        kind: PieceKind,
+        street: &BlockBox,
+        side: BlockDirection,
+        along: i32,
+    ) {
+        let (width, depth, _) = kind.size();
+        let (min, max) = match side {
+            BlockDirection::North => (
+                Vector2::new(along, street.min.z - depth),
+                Vector2::new(along + width - 1, street.min.z - 1),
+            ),
+            BlockDirection::South => (
+                Vector2::new(along, street.max.z + 1),
+                Vector2::new(along + width - 1, street.max.z + depth),
+            ),
+            BlockDirection::West => (
+                Vector2::new(street.min.x - depth, along),
+                Vector2::new(street.min.x - 1, along + width - 1),
+            ),
+            _ => (
+                Vector2::new(street.max.x + 1, along),
+                Vector2::new(street.max.x + depth, along + width - 1),
+            ),
+        };
+        self.add_rigid(kind, min, max, side.opposite());
+    }
+}
+
+impl Village {
+    /// Generates the village of a structure start. `height_at` returns the height of the top block
+    /// of the terrain in a column. The same seed and terrain always give the same pieces.
+    pub fn generate(
+        seed: u64,
+        start: StructureStart,
+        height_at: impl FnMut(Vector2<i32>) -> i32,
+    ) -> Self {
+        let chunk = start.chunk;
+        let mut builder = VillageBuilder {
+            random: large_feature_random(seed, chunk),
+            start: Vector3::new(chunk.x * 16 + 8, 0, chunk.z * 16 + 8),
+            pieces: Vec::new(),
+            height_at,
+        };
+        builder.add_well();
+        Self {
+            structure: start.structure,
+            pieces: builder.pieces,
+        }
+    }
+
+    pub fn intersects(&self, bounds: &BlockBox) -> bool {
+        self.pieces
+            .iter()
+            .any(|piece| piece.bounds.intersects(bounds))
+    }
+
+    /// Places the blocks of the pieces which are inside of the chunk of the region
+    pub fn place(&self, region: &mut ChunkRegion) {
+        let chunk_bounds = BlockBox::chunk(region.chunk_position());
+        let palette = palette_of(self.structure);
+        // Streets follow the terrain, so they are placed before the buildings change it
+        let (streets, buildings): (Vec<&Piece>, Vec<&Piece>) = self
+            .pieces
+            .iter()
+            .partition(|piece| piece.kind == PieceKind::Street);
+        for piece in streets.into_iter().chain(buildings) {
+            if let Some(bounds) = piece.bounds.intersection(&chunk_bounds) {
+                piece.place(region, &bounds, palette);
+            }
+        }
+    }
+}
+
+/// The top block of a column which isn't air
+fn surface_y(region: &ChunkRegion, x: i32, z: i32) -> i32 {
+    (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y))
+        .rev()
+        .find(|&y| !region.is_air(Vector3::new(x, y, z)))
+        .unwrap_or(WORLD_LOWEST_Y.into())
+}
+
+impl Piece {
+    /// The position of a block inside of the piece, as the offset along its front, its height and
+    /// its offset away from its front
+    fn local(&self, position: Vector3<i32>) -> (i32, i32, i32) {
+        let BlockBox { min, max } = self.bounds;
+        let y = position.y - min.y;
+        match self.facing {
+            BlockDirection::North => (position.x - min.x, y, position.z - min.z),
+            BlockDirection::South => (max.x - position.x, y, max.z - position.z),
+            BlockDirection::West => (max.z - position.z, y, position.x - min.x),
+            _ => (position.z - min.z, y, max.x - position.x),
+        }
+    }
+
+    /// Places the blocks of the piece inside of `clip`
+    fn place(&self, region: &mut ChunkRegion, clip: &BlockBox, palette: &Palette) {
+        if self.kind == PieceKind::Street {
+            self.place_street(region, clip, palette);
+            return;
+        }
+        for position in clip.positions() {
+            let (i, y, j) = self.local(position);
+            let state = self.state_at(palette, position, i, y, j);
+            region.set(position, palette.process(self.seed, position, state));
+        }
+        self.adapt_terrain(region, clip, palette);
+    }
+
+    fn place_street(&self, region: &mut ChunkRegion, clip: &BlockBox, palette: &Palette) {
+        for x in clip.min.x..=clip.max.x {
+            for z in clip.min.z..=clip.max.z {
+                let y = surface_y(region, x, z);
+                let surface = Vector3::new(x, y, z);
+                // Streets cross water on planks
+                let state = if region.is_solid(surface) {
+                    palette.path
+                } else {
+                    palette.planks
+                };
+                region.set(surface, state);
+                for above in 1..=2 {
+                    region.set(Vector3::new(x, y + above, z), BLOCKS.air);
+                }
+            }
+        }
+    }
+
+    fn state_at(&self, palette: &Palette, position: Vector3<i32>, i: i32, y: i32, j: i32) -> u16 {
+        let blocks = &*BLOCKS;
+        let (width, depth, height) = self.kind.size();
+        let edge = i == 0 || i == width - 1 || j == 0 || j == depth - 1;
+        let corner = (i == 0 || i == width - 1) && (j == 0 || j == depth - 1);
+        match self.kind {
+            PieceKind::Well => {
+                let ring = (1..=4).contains(&i) && (1..=4).contains(&j);
+                let water = (2..=3).contains(&i) && (2..=3).contains(&j);
+                let post = (i == 1 || i == 4) && (j == 1 || j == 4);
+                match y {
+                    0 => palette.foundation,
+                    1 if water => blocks.water,
+                    1 if ring => palette.foundation,
+                    1 => palette.path,
+                    2 if post => palette.fence,
+                    2 if ring && !water => palette.foundation,
+                    3 if post => palette.fence,
+                    4 if ring => palette.foundation,
+                    _ => blocks.air,
+                }
+            }
+            PieceKind::SmallHouse | PieceKind::LargeHouse => {
+                let roof = height - 1;
+                if y == 0 {
+                    palette.foundation
+                } else if y == roof {
+                    palette.roof
+                } else if corner {
+                    palette.log
+                } else if edge {
+                    let window = y == 2
+                        && (((i == 0 || i == width - 1) && j == depth / 2)
+                            || (j == depth - 1 && i == width / 2));
+                    if j == 0 && i == width / 2 && y <= 2 {
+                        state_with(
+                            palette.door,
+                            &[
+                                ("facing", direction_name(self.facing.opposite())),
+                                ("half", if y == 1 { "lower" } else { "upper" }),
+                            ],
+                        )
+                    } else if window {
+                        blocks.glass_pane
+                    } else {
+                        palette.planks
+                    }
+                } else if y == 1 && i == 1 && j == depth - 2 {
+                    blocks.crafting_table
+                } else {
+                    blocks.air
+                }
+            }
+            PieceKind::Farm => match y {
+                0 if edge => palette.log,
+                0 if i == width / 2 => blocks.water,
+                0 => blocks.farmland,
+                _ if edge || i == width / 2 => blocks.air,
+                _ if position_chance(self.seed, position, 0.5) => blocks.wheat,
+                _ => blocks.young_wheat,
+            },
+            PieceKind::Street => unreachable!("Streets follow the terrain"),
+        }
+    }
+
+    /// Fills the gaps below the piece with its foundation and clears the terrain above it
+    fn adapt_terrain(&self, region: &mut ChunkRegion, clip: &BlockBox, palette: &Palette) {
+        let BlockBox { min, max } = self.bounds;
+        for x in clip.min.x..=clip.max.x {
+            for z in clip.min.z..=clip.max.z {
+                for y in (min.y - MAX_ADAPTATION..min.y).rev() {
+                    let position = Vector3::new(x, y, z);
+                    if region.is_solid(position) {
+                        break;
+                    }
+                    region.set(
+                        position,
+                        palette.process(self.seed, position, palette.foundation),
+                    );
+                }
+                for y in max.y + 1..=max.y + MAX_ADAPTATION {
+                    let position = Vector3::new(x, y, z);
+                    if region.is_air(position) {
+                        break;
+                    }
+                    region.set(position, BLOCKS.air);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::{overlaps, PieceKind, Village, CHUNK_RANGE};
+    use crate::generation::structure::{StructureStart, StructureType};
+
+    #[test]
+    fn pieces_are_seeded_and_do_not_overlap() {
+        let start = StructureStart {
+            structure: StructureType::VillagePlains,
+            chunk: Vector2::new(3, -5),
+        };
+        let village = Village::generate(1234, start, |at| 64 + (at.x & 3));
+        assert_eq!(village.pieces[0].kind, PieceKind::Well);
+        assert!(village
+            .pieces
+            .iter()
+            .any(|piece| piece.kind == PieceKind::Street));
+
+        for (i, a) in village.pieces.iter().enumerate() {
+            for b in &village.pieces[i + 1..] {
+                assert!(!overlaps(&a.bounds, &b.bounds));
+            }
+            // Every chunk the village reaches finds its start
+            assert!((a.bounds.min.x >> 4) - start.chunk.x >= -CHUNK_RANGE);
+            assert!((a.bounds.max.x >> 4) - start.chunk.x <= CHUNK_RANGE);
+            assert!((a.bounds.min.z >> 4) - start.chunk.z >= -CHUNK_RANGE);
+            assert!((a.bounds.max.z >> 4) - start.chunk.z <= CHUNK_RANGE);
+        }
+
+        let again = Village::generate(1234, start, |at| 64 + (at.x & 3));
+        assert_eq!(
+            village
+                .pieces
+                .iter()
+                .map(|piece| piece.bounds)
+                .collect::<Vec<_>>(),
+            again
+                .pieces
+                .iter()
+                .map(|piece| piece.bounds)
+                .collect::<Vec<_>>()
+        );
+    }
+}
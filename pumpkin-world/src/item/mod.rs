@@ -1,4 +1,5 @@
 use pumpkin_data::item::Item;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 
 mod categories;
 #[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -11,21 +12,104 @@ pub enum Rarity {
     Epic,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// The component which holds arbitrary data of plugins and data packs
+pub const CUSTOM_DATA: &str = "minecraft:custom_data";
+
+#[derive(Clone, Debug)]
 pub struct ItemStack {
     pub item_count: u8,
     pub item: Item,
+    /// The components which differ from the defaults of the item, by id like
+    /// `minecraft:custom_data`. Components the server does not know are kept as they are.
+    pub components: NbtCompound,
 }
 
+/// Stacks are equal if they can be merged, so the count is ignored
 impl PartialEq for ItemStack {
     fn eq(&self, other: &Self) -> bool {
-        self.item.id == other.item.id
+        self.item.id == other.item.id && self.components == other.components
+    }
+}
+
+fn namespaced(id: &str) -> String {
+    if id.contains(':') {
+        id.to_string()
+    } else {
+        format!("minecraft:{id}")
     }
 }
 
 impl ItemStack {
     pub fn new(item_count: u8, item: Item) -> Self {
-        Self { item_count, item }
+        Self {
+            item_count,
+            item,
+            components: NbtCompound::new(),
+        }
+    }
+
+    /// Returns a component set on this stack, e.g. `minecraft:lore`.
+    pub fn get_component(&self, id: &str) -> Option<&NbtTag> {
+        self.components.get(&namespaced(id))
+    }
+
+    /// Sets a component, replacing its previous value.
+    ///
+    /// Components use the format of the `/give` command, e.g. `minecraft:custom_name` is a JSON
+    /// text component.
+    pub fn set_component(&mut self, id: &str, value: impl Into<NbtTag>) {
+        let id = namespaced(id);
+        self.components.remove(&id);
+        self.components.put(&id, value);
+    }
+
+    /// Removes a component, returning it if it was set.
+    pub fn remove_component(&mut self, id: &str) -> Option<NbtTag> {
+        self.components.remove(&namespaced(id))
+    }
+
+    /// Returns the `minecraft:custom_data` compound, if it is set.
+    pub fn custom_data(&self) -> Option<&NbtCompound> {
+        self.components.get_compound(CUSTOM_DATA)
+    }
+
+    /// Returns the `minecraft:custom_data` compound, which is created if it is not set yet.
+    pub fn custom_data_mut(&mut self) -> &mut NbtCompound {
+        if self.custom_data().is_none() {
+            self.set_component(CUSTOM_DATA, NbtCompound::new());
+        }
+        match self.components.get_mut(CUSTOM_DATA) {
+            Some(NbtTag::Compound(data)) => data,
+            _ => unreachable!("custom data was just set to a compound"),
+        }
+    }
+
+    /// Writes the stack in the format used by the world and player data, e.g.
+    /// `{id: "minecraft:stone", count: 1, components: {...}}`.
+    pub fn write_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.put("id", format!("minecraft:{}", self.item.registry_key()));
+        nbt.put_int("count", i32::from(self.item_count));
+        if !self.components.is_empty() {
+            nbt.put_component("components", self.components.clone());
+        }
+        nbt
+    }
+
+    /// Reads a stack written by [`ItemStack::write_nbt`], returning `None` if the item is unknown.
+    pub fn read_nbt(nbt: &NbtCompound) -> Option<Self> {
+        let id = nbt.get_string("id")?;
+        let item = Item::from_name(id.strip_prefix("minecraft:").unwrap_or(id))?;
+        // Before 1.20.5 the count was a byte named `Count`
+        let item_count = nbt
+            .get_int("count")
+            .or_else(|| nbt.get_byte("Count").map(i32::from))
+            .map_or(1, |count| count.clamp(1, i32::from(u8::MAX)) as u8);
+        Some(Self {
+            item_count,
+            item,
+            components: nbt.get_compound("components").cloned().unwrap_or_default(),
+        })
     }
 
     pub fn get_speed(&self, block: &str) -> f32 {
@@ -53,3 +137,47 @@ impl ItemStack {
         false
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+    use super::ItemStack;
+
+    #[test]
+    fn nbt_round_trip_keeps_unknown_components() {
+        let mut stack = ItemStack::new(5, Item::DIAMOND);
+        stack.custom_data_mut().put_int("level", 3);
+        let mut unknown = NbtCompound::new();
+        unknown.put_bool("flag", true);
+        stack.set_component("my_plugin:extra", unknown);
+
+        let read = ItemStack::read_nbt(&stack.write_nbt()).unwrap();
+        assert_eq!(read.item_count, 5);
+        assert_eq!(read, stack);
+        assert_eq!(read.custom_data().unwrap().get_int("level"), Some(3));
+        assert!(read.get_component("my_plugin:extra").is_some());
+    }
+
+    #[test]
+    fn set_component_replaces_the_value() {
+        let mut stack = ItemStack::new(1, Item::STICK);
+        stack.set_component("custom_model_data", NbtTag::Int(1));
+        stack.set_component("minecraft:custom_model_data", NbtTag::Int(2));
+        assert_eq!(
+            stack.get_component("custom_model_data"),
+            Some(&NbtTag::Int(2))
+        );
+        assert!(stack.remove_component("custom_model_data").is_some());
+        assert!(stack.components.is_empty());
+    }
+
+    #[test]
+    fn stacks_with_different_components_are_not_equal() {
+        let plain = ItemStack::new(1, Item::STICK);
+        let mut marked = plain.clone();
+        marked.custom_data_mut().put_bool("marked", true);
+        assert_ne!(plain, marked);
+    }
+}
@@ -1,28 +1,45 @@
-use std::{fs, path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+};
 
-use dashmap::{DashMap, Entry};
-use num_traits::Zero;
+use dashmap::DashMap;
 use pumpkin_config::{chunk::ChunkFormat, ADVANCED_CONFIG};
-use pumpkin_util::math::vector2::Vector2;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use tokio::{
     runtime::Handle,
     sync::{mpsc, RwLock},
+    task::JoinHandle,
 };
 
 use crate::{
     chunk::{
-        anvil::AnvilChunkFormat, linear::LinearChunkFormat, ChunkData, ChunkParsingError,
-        ChunkReader, ChunkReadingError, ChunkWriter,
+        anvil::AnvilChunkFormat,
+        entities,
+        linear::LinearChunkFormat,
+        ticket::{ChunkLevelType, ChunkTickets, TicketType, FULL_CHUNK_LEVEL},
+        ChunkData, ChunkParsingError, ChunkReader, ChunkReadingError, ChunkWriter, Subchunks,
     },
-    generation::{get_world_gen, Seed, WorldGenerator},
+    generation::{get_world_gen, Seed, WorldGenerator, DEFAULT_GENERATOR},
+    light::{self, LightNeighborhood, LightUpdate},
     lock::{anvil::AnvilLevelLocker, LevelLocker},
+    scheduled_tick::ScheduledTicks,
     world_info::{
         anvil::{AnvilLevelInfo, LEVEL_DAT_BACKUP_FILE_NAME, LEVEL_DAT_FILE_NAME},
-        LevelData, WorldInfoError, WorldInfoReader, WorldInfoWriter,
+        GameRules, LevelData, WorldInfoError, WorldInfoReader, WorldInfoWriter,
     },
 };
 
+/// How many light updates are applied each tick, the rest waits for the next ones
+const LIGHT_UPDATES_PER_TICK: usize = 64;
+
 /// The `Level` module provides functionality for working with chunks within or outside a Minecraft world.
 ///
 /// Key features include:
@@ -30,18 +47,37 @@ use crate::{
 /// - **Chunk Loading:** Efficiently loads chunks from disk.
 /// - **Chunk Caching:** Stores accessed chunks in memory for faster access.
 /// - **Chunk Generation:** Generates new chunks on-demand using a specified `WorldGenerator`.
+/// - **Chunk Tickets:** Keeps chunks loaded and ticked while tickets are placed on them, see
+///   [`crate::chunk::ticket`].
 ///
 /// For more details on world generation, refer to the `WorldGenerator` module.
 pub struct Level {
     pub seed: Seed,
     pub level_info: LevelData,
     world_info_writer: Arc<dyn WorldInfoWriter>,
+    /// Whether this level writes the level.dat, which only the overworld does. The other
+    /// dimensions share the world info of the overworld.
+    owns_world_info: bool,
     level_folder: LevelFolder,
     loaded_chunks: Arc<DashMap<Vector2<i32>, Arc<RwLock<ChunkData>>>>,
-    chunk_watchers: Arc<DashMap<Vector2<i32>, usize>>,
+    /// Decide which chunks stay loaded and which are ticked
+    tickets: Mutex<ChunkTickets>,
     chunk_reader: Arc<dyn ChunkReader>,
     chunk_writer: Arc<dyn ChunkWriter>,
-    world_gen: Arc<dyn WorldGenerator>,
+    /// The name of the generator selected for this level, see [`Level::set_generator`].
+    generator_name: std::sync::RwLock<String>,
+    /// The generator is resolved when the first chunk is generated, so plugins have a chance to
+    /// register theirs.
+    world_gen: OnceLock<Arc<dyn WorldGenerator>>,
+    /// The smallest and the largest chunk new chunks are generated between, see
+    /// [`Level::set_generation_bounds`]
+    generation_bounds: std::sync::RwLock<Option<(Vector2<i32>, Vector2<i32>)>>,
+    /// Light updates are applied in batches each tick, so changing blocks stays cheap
+    light_updates: Arc<Mutex<VecDeque<LightUpdate>>>,
+    /// The block ticks scheduled in the loaded chunks. They are saved with their chunk.
+    pub scheduled_ticks: Arc<ScheduledTicks>,
+    /// Whether saving is turned off, see [`Level::set_saving_paused`]
+    saving_paused: AtomicBool,
     // Gets unlocked when dropped
     // TODO: Make this a trait
     _locker: Arc<AnvilLevelLocker>,
@@ -55,6 +91,23 @@ pub struct LevelFolder {
 
 impl Level {
     pub fn from_root_folder(root_folder: PathBuf) -> Self {
+        Self::open(root_folder, None, None)
+    }
+
+    /// Like [`Level::from_root_folder`], but a new world is created with the given seed instead
+    /// of the seed of the config. Existing worlds keep the seed of their level.dat.
+    pub fn from_root_folder_with_seed(root_folder: PathBuf, seed: Seed) -> Self {
+        Self::open(root_folder, None, Some(seed))
+    }
+
+    /// Loads the level of a dimension which is stored in a folder of the world, like `DIM-1` for
+    /// the nether. The seed and the rest of the world info are read from the level.dat of the
+    /// world, which is not written by this level.
+    pub fn from_dimension_folder(world_folder: PathBuf, dimension_folder: PathBuf) -> Self {
+        Self::open(dimension_folder, Some(world_folder), None)
+    }
+
+    fn open(root_folder: PathBuf, world_folder: Option<PathBuf>, seed: Option<Seed>) -> Self {
         // If we are using an already existing world we want to read the seed from the level.dat, If not we want to check if there is a seed in the config, if not lets create a random one
         let region_folder = root_folder.join("region");
         if !region_folder.exists() {
@@ -69,8 +122,17 @@ impl Level {
         // So TODO
         let locker = AnvilLevelLocker::look(&level_folder).expect("Failed to lock level");
 
+        let owns_world_info = world_folder.is_none();
+        let info_folder = match world_folder {
+            Some(world_folder) => LevelFolder {
+                region_folder: world_folder.join("region"),
+                root_folder: world_folder,
+            },
+            None => level_folder.clone(),
+        };
+
         // TODO: Load info correctly based on world format type
-        let level_info = AnvilLevelInfo.read_world_info(&level_folder);
+        let level_info = AnvilLevelInfo.read_world_info(&info_folder);
         if let Err(error) = &level_info {
             match error {
                 // If it doesn't exist, just make a new one
@@ -84,7 +146,7 @@ impl Level {
                     panic!("World Error {}", e);
                 }
             }
-        } else {
+        } else if owns_world_info {
             let dat_path = level_folder.root_folder.join(LEVEL_DAT_FILE_NAME);
             if dat_path.exists() {
                 let backup_path = level_folder.root_folder.join(LEVEL_DAT_BACKUP_FILE_NAME);
@@ -92,14 +154,20 @@ impl Level {
             }
         }
 
-        let level_info = level_info.unwrap_or_default(); // TODO: Improve error handling
+        let level_info = level_info.unwrap_or_else(|_| {
+            let mut level_info = LevelData::default();
+            if let Some(seed) = seed {
+                level_info.world_gen_settings.seed = seed.0 as i64;
+            }
+            level_info.game_rules = GameRules::from_config(&ADVANCED_CONFIG.world);
+            level_info
+        }); // TODO: Improve error handling
         log::info!(
             "Loading world with seed: {}",
             level_info.world_gen_settings.seed
         );
 
         let seed = Seed(level_info.world_gen_settings.seed as u64);
-        let world_gen = get_world_gen(seed).into();
 
         let chunk_format: (Arc<dyn ChunkReader>, Arc<dyn ChunkWriter>) =
             match ADVANCED_CONFIG.chunk.format {
@@ -109,35 +177,117 @@ impl Level {
 
         Self {
             seed,
-            world_gen,
+            generator_name: std::sync::RwLock::new(DEFAULT_GENERATOR.to_string()),
+            world_gen: OnceLock::new(),
+            generation_bounds: std::sync::RwLock::new(None),
             world_info_writer: Arc::new(AnvilLevelInfo),
+            owns_world_info,
             level_folder,
             chunk_reader: chunk_format.0,
             chunk_writer: chunk_format.1,
             loaded_chunks: Arc::new(DashMap::new()),
-            chunk_watchers: Arc::new(DashMap::new()),
+            tickets: Mutex::new(ChunkTickets::default()),
+            light_updates: Arc::new(Mutex::new(VecDeque::new())),
+            scheduled_ticks: Arc::new(ScheduledTicks::default()),
+            saving_paused: AtomicBool::new(false),
             level_info,
             _locker: Arc::new(locker),
         }
     }
 
-    pub async fn save(&self) {
+    /// Selects the generator, by its registered name, new chunks of this level are generated with.
+    ///
+    /// This has no effect once the first chunk has been generated.
+    pub fn set_generator(&self, name: &str) {
+        if self.world_gen.get().is_some() {
+            log::warn!("Can not change the world generator after chunks have been generated");
+            return;
+        }
+        *self.generator_name.write().unwrap() = name.to_string();
+    }
+
+    /// Returns the generator new chunks of this level are generated with.
+    ///
+    /// If the selected generator is not registered, the default generator is used instead.
+    pub fn world_gen(&self) -> Arc<dyn WorldGenerator> {
+        self.world_gen
+            .get_or_init(|| {
+                let name = self.generator_name.read().unwrap().clone();
+                get_world_gen(&name, self.seed)
+                    .unwrap_or_else(|| {
+                        log::error!(
+                            "World generator {name} is not registered, using the default generator"
+                        );
+                        get_world_gen(DEFAULT_GENERATOR, self.seed)
+                            .expect("The default generator is always registered")
+                    })
+                    .into()
+            })
+            .clone()
+    }
+
+    /// Limits where new chunks are generated to the chunks touching the area between the two
+    /// block coordinates, e.g. inside of the world border. Chunks beyond it which were not
+    /// generated yet are loaded empty and not saved, so they are generated once they are inside.
+    pub fn set_generation_bounds(&self, min: Vector2<i32>, max: Vector2<i32>) {
+        *self.generation_bounds.write().unwrap() = Some((
+            Vector2::new(min.x >> 4, min.z >> 4),
+            Vector2::new(max.x >> 4, max.z >> 4),
+        ));
+    }
+
+    /// Whether the chunk may be generated, see [`Level::set_generation_bounds`]
+    pub fn can_generate_chunk(&self, chunk: &Vector2<i32>) -> bool {
+        self.generation_bounds
+            .read()
+            .unwrap()
+            .is_none_or(|(min, max)| {
+                (min.x..=max.x).contains(&chunk.x) && (min.z..=max.z).contains(&chunk.z)
+            })
+    }
+
+    /// Generates the chunk, or returns an empty chunk which is not saved if it is beyond the
+    /// generation bounds
+    fn generate_chunk(&self, chunk_pos: Vector2<i32>) -> ChunkData {
+        if self.can_generate_chunk(&chunk_pos) {
+            return self.world_gen().generate_chunk(chunk_pos);
+        }
+        let mut chunk = ChunkData::new(Subchunks::Single(0), Default::default(), chunk_pos);
+        chunk.dirty = false;
+        chunk
+    }
+
+    /// Writes all loaded chunks and the level info. The level info is passed in, as parts of it
+    /// like the weather change while the server is running.
+    pub async fn save(&self, level_info: LevelData) {
         log::info!("Saving level...");
 
         // chunks are automatically saved when all players get removed
         // TODO: Await chunks that have been called by this ^
 
-        // save all stragling chunks
-        for chunk in self.loaded_chunks.iter() {
-            let pos = chunk.key();
-            let chunk = chunk.value();
-            self.write_chunk((*pos, chunk.clone())).await;
+        // save all stragling chunks and wait until they are written
+        let writes: Vec<_> = self
+            .loaded_chunks
+            .iter()
+            .map(|chunk| self.write_chunk((*chunk.key(), chunk.value().clone())))
+            .collect();
+        for write in writes {
+            let _ = write.await;
+        }
+
+        self.save_level_info(level_info);
+    }
+
+    /// Writes the level info, unless it belongs to another level, see [`Level::save`]
+    pub fn save_level_info(&self, level_info: LevelData) {
+        if !self.owns_world_info {
+            return;
         }
 
         // then lets save the world info
         let result = self
             .world_info_writer
-            .write_world_info(self.level_info.clone(), &self.level_folder);
+            .write_world_info(level_info, &self.level_folder);
 
         // Lets not stop the overall save for this
         if let Err(err) = result {
@@ -145,80 +295,229 @@ impl Level {
         }
     }
 
+    /// The loaded chunks which changed since they were last saved
+    pub async fn dirty_chunks(&self) -> Vec<Vector2<i32>> {
+        let chunks: Vec<_> = self
+            .loaded_chunks
+            .iter()
+            .map(|chunk| (*chunk.key(), chunk.value().clone()))
+            .collect();
+        let mut dirty = Vec::new();
+        for (position, chunk) in chunks {
+            if chunk.read().await.dirty {
+                dirty.push(position);
+            }
+        }
+        dirty
+    }
+
+    /// Writes the chunk in the background if it is still loaded and changed since it was last
+    /// saved. Unloaded chunks were already written when they were unloaded.
+    pub fn save_chunk(&self, chunk: &Vector2<i32>) -> Option<JoinHandle<()>> {
+        let data = self.loaded_chunks.get(chunk)?.value().clone();
+        Some(self.write_chunk((*chunk, data)))
+    }
+
     pub fn get_block() {}
 
     pub fn loaded_chunk_count(&self) -> usize {
         self.loaded_chunks.len()
     }
 
-    pub fn list_cached(&self) {
-        for entry in self.loaded_chunks.iter() {
-            log::debug!("In map: {:?}", entry.key());
+    /// Ticks the block entities of the chunks whose blocks are ticked. Returns the positions of
+    /// the block entities whose block has to be ticked too.
+    pub async fn tick_block_entities(&self) -> Vec<BlockPos> {
+        let chunks: Vec<_> = self
+            .get_ticking_chunks()
+            .into_iter()
+            .map(|(_, chunk)| chunk)
+            .collect();
+        let mut block_ticks = Vec::new();
+        for chunk in chunks {
+            if chunk.read().await.block_entities.is_empty() {
+                continue;
+            }
+            block_ticks.extend(chunk.write().await.tick_block_entities());
         }
+        block_ticks
     }
 
-    /// Marks chunks as "watched" by a unique player. When no players are watching a chunk,
-    /// it is removed from memory. Should only be called on chunks the player was not watching
-    /// before
-    pub fn mark_chunks_as_newly_watched(&self, chunks: &[Vector2<i32>]) {
-        chunks.iter().for_each(|chunk| {
-            self.mark_chunk_as_newly_watched(*chunk);
-        });
+    /// All loaded chunks with their positions
+    pub fn get_loaded_chunks(&self) -> Vec<(Vector2<i32>, Arc<RwLock<ChunkData>>)> {
+        self.loaded_chunks
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect()
+    }
+
+    /// The loaded chunks whose blocks are ticked, see [`ChunkLevelType::BlockTicking`]
+    pub fn get_ticking_chunks(&self) -> Vec<(Vector2<i32>, Arc<RwLock<ChunkData>>)> {
+        let tickets = self.tickets.lock().unwrap();
+        self.loaded_chunks
+            .iter()
+            .filter(|entry| tickets.level_type(entry.key()) >= ChunkLevelType::BlockTicking)
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect()
+    }
+
+    pub fn get_loaded_chunk(&self, chunk: &Vector2<i32>) -> Option<Arc<RwLock<ChunkData>>> {
+        self.loaded_chunks
+            .get(chunk)
+            .map(|entry| entry.value().clone())
     }
 
-    pub fn mark_chunk_as_newly_watched(&self, chunk: Vector2<i32>) {
-        log::trace!("{:?} marked as newly watched", chunk);
-        match self.chunk_watchers.entry(chunk) {
-            Entry::Occupied(mut occupied) => {
-                let value = occupied.get_mut();
-                if let Some(new_value) = value.checked_add(1) {
-                    *value = new_value;
-                    //log::debug!("Watch value for {:?}: {}", chunk, value);
-                } else {
-                    log::error!("Watching overflow on chunk {:?}", chunk);
+    /// Queues the light around a changed block to be updated in one of the next ticks.
+    pub fn queue_light_update(&self, pos: BlockPos) {
+        self.light_updates
+            .lock()
+            .unwrap()
+            .push_back(LightUpdate::Block(pos));
+    }
+
+    /// Applies a batch of the queued light updates.
+    ///
+    /// Returns the changed subchunks of each chunk as a bit mask, where bit 0 is the section
+    /// below the world, like in light packets.
+    pub async fn tick_light(&self) -> HashMap<Vector2<i32>, u32> {
+        let updates: Vec<_> = {
+            let mut queue = self.light_updates.lock().unwrap();
+            let count = queue.len().min(LIGHT_UPDATES_PER_TICK);
+            queue.drain(..count).collect()
+        };
+
+        let mut changed: HashMap<Vector2<i32>, u32> = HashMap::new();
+        for update in updates {
+            let center = match update {
+                LightUpdate::Block(pos) => Vector2::new(pos.0.x >> 4, pos.0.z >> 4),
+                LightUpdate::ChunkBorders(chunk) => chunk,
+            };
+            if !self.loaded_chunks.contains_key(&center) {
+                continue;
+            }
+
+            // Always locked in the same order, so updates can not deadlock each other
+            let mut chunks = Vec::with_capacity(9);
+            for x in -1..=1 {
+                for z in -1..=1 {
+                    if let Some(chunk) =
+                        self.get_loaded_chunk(&Vector2::new(center.x + x, center.z + z))
+                    {
+                        chunks.push(chunk.write_owned().await);
+                    }
                 }
             }
-            Entry::Vacant(vacant) => {
-                vacant.insert(1);
+            let mut neighborhood = LightNeighborhood::new(chunks);
+            match update {
+                LightUpdate::Block(pos) => light::update_block(&mut neighborhood, &pos),
+                LightUpdate::ChunkBorders(chunk) => {
+                    light::spread_chunk_borders(&mut neighborhood, chunk);
+                }
+            }
+            for (chunk, sections) in neighborhood.changed {
+                *changed.entry(chunk).or_default() |= sections;
             }
         }
+        changed
     }
 
-    /// Marks chunks no longer "watched" by a unique player. When no players are watching a chunk,
-    /// it is removed from memory. Should only be called on chunks the player was watching before
-    pub fn mark_chunks_as_not_watched(&self, chunks: &[Vector2<i32>]) -> Vec<Vector2<i32>> {
-        chunks
-            .iter()
-            .filter(|chunk| self.mark_chunk_as_not_watched(**chunk))
-            .copied()
-            .collect()
+    pub fn list_cached(&self) {
+        for entry in self.loaded_chunks.iter() {
+            log::debug!("In map: {:?}", entry.key());
+        }
     }
 
-    /// Returns whether the chunk should be removed from memory
-    pub fn mark_chunk_as_not_watched(&self, chunk: Vector2<i32>) -> bool {
-        log::trace!("{:?} marked as no longer watched", chunk);
-        match self.chunk_watchers.entry(chunk) {
-            Entry::Occupied(mut occupied) => {
-                let value = occupied.get_mut();
-                *value = value.saturating_sub(1);
+    /// Places a ticket on the chunk, which keeps the chunks around it loaded and ticked depending
+    /// on the level, see [`crate::chunk::ticket`]. Chunks are not loaded by this, but once they
+    /// are, they stay loaded until the ticket is removed.
+    pub fn add_ticket(&self, chunk: Vector2<i32>, ticket_type: TicketType, level: u8) {
+        log::trace!("{:?} ticket added to {:?}", ticket_type, chunk);
+        self.tickets.lock().unwrap().add(chunk, ticket_type, level);
+    }
 
-                if *value == 0 {
-                    occupied.remove_entry();
-                    true
-                } else {
-                    false
-                }
-            }
-            Entry::Vacant(_) => {
-                // This can be:
-                // - Player disconnecting before all packets have been sent
-                // - Player moving so fast that the chunk leaves the render distance before it
-                // is loaded into memory
-                true
-            }
+    pub fn add_tickets(&self, chunks: &[Vector2<i32>], ticket_type: &TicketType, level: u8) {
+        let mut tickets = self.tickets.lock().unwrap();
+        for chunk in chunks {
+            tickets.add(*chunk, ticket_type.clone(), level);
         }
     }
 
+    /// Removes a ticket placed with [`Level::add_ticket`]. The chunks which are no longer kept
+    /// loaded are unloaded, and written to disk if they changed.
+    pub async fn remove_ticket(&self, chunk: Vector2<i32>, ticket_type: &TicketType, level: u8) {
+        let unloaded = self
+            .tickets
+            .lock()
+            .unwrap()
+            .remove(chunk, ticket_type, level);
+        self.clean_chunks(&unloaded).await;
+    }
+
+    pub async fn remove_tickets(
+        &self,
+        chunks: &[Vector2<i32>],
+        ticket_type: &TicketType,
+        level: u8,
+    ) {
+        let unloaded: Vec<_> = {
+            let mut tickets = self.tickets.lock().unwrap();
+            chunks
+                .iter()
+                .flat_map(|chunk| tickets.remove(*chunk, ticket_type, level))
+                .collect()
+        };
+        self.clean_chunks(&unloaded).await;
+    }
+
+    /// The chunks with a ticket of the type, e.g. all force loaded chunks
+    pub fn get_ticket_chunks(&self, ticket_type: &TicketType) -> Vec<Vector2<i32>> {
+        self.tickets.lock().unwrap().chunks_with(ticket_type)
+    }
+
+    /// Removes the tickets which expired and unloads the chunks they kept loaded
+    pub async fn tick_tickets(&self) {
+        let unloaded = self.tickets.lock().unwrap().tick();
+        self.clean_chunks(&unloaded).await;
+    }
+
+    /// What is done in the chunk, depending on the tickets around it
+    pub fn chunk_level_type(&self, chunk: &Vector2<i32>) -> ChunkLevelType {
+        self.tickets.lock().unwrap().level_type(chunk)
+    }
+
+    /// Whether the blocks of the chunk are ticked, like random ticks and block entities
+    pub fn is_chunk_ticking(&self, chunk: &Vector2<i32>) -> bool {
+        self.chunk_level_type(chunk) >= ChunkLevelType::BlockTicking
+    }
+
+    /// Advances the scheduled block ticks by one tick. Returns the due ticks of the chunks whose
+    /// blocks are ticked, as the position and the id of the block which is ticked.
+    pub fn due_block_ticks(&self) -> Vec<(BlockPos, u16)> {
+        self.scheduled_ticks
+            .advance(|chunk| self.is_chunk_ticking(chunk))
+    }
+
+    /// Whether the entities in the chunk are ticked
+    pub fn is_chunk_entity_ticking(&self, chunk: &Vector2<i32>) -> bool {
+        self.chunk_level_type(chunk) == ChunkLevelType::EntityTicking
+    }
+
+    /// Places player tickets on the chunks a player started to watch, which keep them loaded.
+    /// Should only be called on chunks the player was not watching before
+    pub fn mark_chunks_as_newly_watched(&self, chunks: &[Vector2<i32>]) {
+        self.add_tickets(chunks, &TicketType::Player, FULL_CHUNK_LEVEL);
+    }
+
+    /// Removes the player tickets of chunks a player no longer watches. Should only be called on
+    /// chunks the player was watching before. Returns the chunks which should be removed from
+    /// memory, which are all chunks no longer kept loaded by any ticket.
+    pub fn mark_chunks_as_not_watched(&self, chunks: &[Vector2<i32>]) -> Vec<Vector2<i32>> {
+        let mut tickets = self.tickets.lock().unwrap();
+        chunks
+            .iter()
+            .flat_map(|chunk| tickets.remove(*chunk, &TicketType::Player, FULL_CHUNK_LEVEL))
+            .collect()
+    }
+
     pub async fn clean_chunks(&self, chunks: &[Vector2<i32>]) {
         for chunk_pos in chunks {
             //log::debug!("Unloading {:?}", chunk_pos);
@@ -226,53 +525,112 @@ impl Level {
         }
     }
 
+    /// Turns writing chunks when they are unloaded off or on, like `/save-off` and `/save-on`.
+    /// While it is off, chunks no ticket keeps loaded stay in memory instead, and are unloaded
+    /// once it is turned on again.
+    pub async fn set_saving_paused(&self, paused: bool) {
+        self.saving_paused.store(paused, Ordering::Relaxed);
+        if !paused {
+            let chunks: Vec<_> = self
+                .loaded_chunks
+                .iter()
+                .map(|chunk| *chunk.key())
+                .collect();
+            self.clean_chunks(&chunks).await;
+        }
+    }
+
+    /// Unloads the chunk unless a ticket keeps it loaded, writing it to disk if it changed
     pub async fn clean_chunk(&self, chunk: &Vector2<i32>) {
+        if self.is_chunk_watched(chunk) || self.saving_paused.load(Ordering::Relaxed) {
+            return;
+        }
         log::trace!("{:?} is being cleaned", chunk);
         if let Some(data) = self.loaded_chunks.remove(chunk) {
-            self.write_chunk(data).await;
+            self.write_chunk(data);
         }
     }
 
+    /// Whether a ticket keeps the chunk loaded
     pub fn is_chunk_watched(&self, chunk: &Vector2<i32>) -> bool {
-        self.chunk_watchers.get(chunk).is_some()
+        self.chunk_level_type(chunk) != ChunkLevelType::Inaccessible
+    }
+
+    pub fn is_chunk_loaded(&self, chunk: &Vector2<i32>) -> bool {
+        self.loaded_chunks.contains_key(chunk)
     }
 
+    /// Reads the entities saved for a chunk, these are stored separately from the chunk itself.
+    pub fn read_entities(&self, chunk: &Vector2<i32>) -> Vec<NbtCompound> {
+        entities::read_entities(&self.entities_folder(), chunk).unwrap_or_else(|err| {
+            log::error!("Failed to read entities of chunk {:?}: {}", chunk, err);
+            Vec::new()
+        })
+    }
+
+    /// Replaces the saved entities of a chunk.
+    pub fn write_entities(&self, chunk: &Vector2<i32>, entities: &[NbtCompound]) {
+        if let Err(err) = entities::write_entities(&self.entities_folder(), chunk, entities) {
+            log::error!("Failed to write entities of chunk {:?}: {}", chunk, err);
+        }
+    }
+
+    fn entities_folder(&self) -> PathBuf {
+        self.level_folder.root_folder.join("entities")
+    }
+
+    /// Unloads the chunks no ticket keeps loaded, like chunks which finished loading after they
+    /// were unwatched
     pub fn clean_memory(&self, chunks_to_check: &[Vector2<i32>]) {
+        if self.saving_paused.load(Ordering::Relaxed) {
+            return;
+        }
         chunks_to_check.iter().for_each(|chunk| {
-            if let Some(entry) = self.chunk_watchers.get(chunk) {
-                if entry.value().is_zero() {
-                    self.chunk_watchers.remove(chunk);
-                }
+            if self.is_chunk_watched(chunk) {
+                return;
             }
-
-            if self.chunk_watchers.get(chunk).is_none() {
-                self.loaded_chunks.remove(chunk);
+            if let Some(data) = self.loaded_chunks.remove(chunk) {
+                self.write_chunk(data);
             }
         });
         self.loaded_chunks.shrink_to_fit();
-        self.chunk_watchers.shrink_to_fit();
     }
 
-    pub async fn write_chunk(&self, chunk_to_write: (Vector2<i32>, Arc<RwLock<ChunkData>>)) {
+    /// Writes the chunk to disk in the background if it changed since it was last saved.
+    pub fn write_chunk(
+        &self,
+        chunk_to_write: (Vector2<i32>, Arc<RwLock<ChunkData>>),
+    ) -> JoinHandle<()> {
         let chunk_writer = self.chunk_writer.clone();
         let level_folder = self.level_folder.clone();
+        // The ticks of unloaded chunks are scheduled again once they are loaded
+        let unloaded = !self.loaded_chunks.contains_key(&chunk_to_write.0);
+        let block_ticks = self.scheduled_ticks.save_chunk(&chunk_to_write.0, unloaded);
 
-        // TODO: Save the join handles to await them when stopping the server
         tokio::spawn(async move {
-            let data = chunk_to_write.1.read().await;
-            if let Err(error) = chunk_writer.write_chunk(&data, &level_folder, &chunk_to_write.0) {
-                log::error!("Failed writing Chunk to disk {}", error.to_string());
+            // Hold the write lock, so changes made while writing are not marked as saved
+            let mut data = chunk_to_write.1.write().await;
+            if data.block_ticks != block_ticks {
+                data.block_ticks = block_ticks;
+                data.dirty = true;
             }
-        });
+            if !data.dirty {
+                return;
+            }
+            match chunk_writer.write_chunk(&data, &level_folder, &chunk_to_write.0) {
+                Ok(()) => data.dirty = false,
+                Err(error) => log::error!("Failed writing Chunk to disk {}", error.to_string()),
+            }
+        })
     }
 
     fn load_chunk_from_save(
         chunk_reader: Arc<dyn ChunkReader>,
         save_file: &LevelFolder,
         chunk_pos: Vector2<i32>,
-    ) -> Result<Option<Arc<RwLock<ChunkData>>>, ChunkReadingError> {
+    ) -> Result<Option<ChunkData>, ChunkReadingError> {
         match chunk_reader.read_chunk(save_file, &chunk_pos) {
-            Ok(data) => Ok(Some(Arc::new(RwLock::new(data)))),
+            Ok(data) => Ok(Some(data)),
             Err(
                 ChunkReadingError::ChunkNotExist
                 | ChunkReadingError::ParsingError(ChunkParsingError::ChunkNotGenerated),
@@ -296,9 +654,7 @@ impl Level {
             let channel = channel.clone();
             let loaded_chunks = self.loaded_chunks.clone();
             let chunk_reader = self.chunk_reader.clone();
-            let chunk_writer = self.chunk_writer.clone();
             let level_folder = self.level_folder.clone();
-            let world_gen = self.world_gen.clone();
             let chunk_pos = *at;
             let mut first_load = false;
 
@@ -308,24 +664,10 @@ impl Level {
                 .unwrap_or_else(|| {
                     first_load = true;
 
-                    let loaded_chunk =
+                    // Generated chunks are dirty, so they are saved once they are unloaded
+                    let mut loaded_chunk =
                         match Self::load_chunk_from_save(chunk_reader, &level_folder, chunk_pos) {
-                            Ok(chunk) => {
-                                // Save new Chunk
-                                if let Some(chunk) = &chunk {
-                                    if let Err(error) = chunk_writer.write_chunk(
-                                        &chunk.blocking_read(),
-                                        &level_folder,
-                                        &chunk_pos,
-                                    ) {
-                                        log::error!(
-                                            "Failed writing Chunk to disk {}",
-                                            error.to_string()
-                                        );
-                                    };
-                                }
-                                chunk
-                            }
+                            Ok(chunk) => chunk,
                             Err(err) => {
                                 log::error!(
                                     "Failed to read chunk (regenerating) {:?}: {:?}",
@@ -335,13 +677,12 @@ impl Level {
                                 None
                             }
                         }
-                        .unwrap_or_else(|| {
-                            Arc::new(RwLock::new(world_gen.generate_chunk(chunk_pos)))
-                            // Arc::new(RwLock::new(ChunkData {
-                            //     blocks: ChunkBlocks::default(),
-                            //     position: chunk_pos,
-                            // }))
-                        });
+                        .unwrap_or_else(|| self.generate_chunk(chunk_pos));
+                    light::light_chunk(&mut loaded_chunk);
+                    // Loading them twice if another thread loads the chunk too keeps them once
+                    self.scheduled_ticks
+                        .load(std::mem::take(&mut loaded_chunk.block_ticks));
+                    let loaded_chunk = Arc::new(RwLock::new(loaded_chunk));
 
                     if let Some(data) = loaded_chunks.get(&chunk_pos) {
                         // Another thread populated in between the previous check and now
@@ -350,6 +691,10 @@ impl Level {
                         data.value().clone()
                     } else {
                         loaded_chunks.insert(chunk_pos, loaded_chunk.clone());
+                        self.light_updates
+                            .lock()
+                            .unwrap()
+                            .push_back(LightUpdate::ChunkBorders(chunk_pos));
                         loaded_chunk
                     }
                 });
@@ -7,12 +7,14 @@ pub mod chunk;
 pub mod coordinates;
 pub mod cylindrical_chunk_iterator;
 pub mod dimension;
-mod generation;
+pub mod generation;
 pub mod item;
 pub mod level;
+pub mod light;
 mod lock;
 pub mod loot;
 mod noise_router;
+pub mod scheduled_tick;
 pub mod world_info;
 pub const WORLD_HEIGHT: usize = 384;
 pub const WORLD_LOWEST_Y: i16 = -64;
@@ -0,0 +1,603 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::LazyLock,
+};
+
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use tokio::sync::OwnedRwLockWriteGuard;
+
+use crate::{
+    block::registry::BLOCKS,
+    chunk::{ChunkData, Subchunk, Subchunks, SUBCHUNKS_COUNT, SUBCHUNK_VOLUME},
+    WORLD_HEIGHT, WORLD_LOWEST_Y,
+};
+
+pub const MAX_LIGHT: u8 = 15;
+/// Light levels are stored as one nibble per block
+pub const LIGHT_SECTION_BYTES: usize = SUBCHUNK_VOLUME / 2;
+
+/// The offsets to the neighbors of a block, starting with the one below
+const DIRECTIONS: [(i32, i32, i32); 6] = [
+    (0, -1, 0),
+    (0, 1, 0),
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+const DOWN: usize = 0;
+
+/// The luminance and opacity of every block state, by state id
+static LIGHT_PROPERTIES: LazyLock<Vec<(u8, u8)>> = LazyLock::new(|| {
+    let mut properties = Vec::new();
+    for block in &BLOCKS.blocks {
+        for state in &block.states {
+            let index = usize::from(state.id);
+            if properties.len() <= index {
+                properties.resize(index + 1, (0, 0));
+            }
+            properties[index] = (
+                state.luminance.min(MAX_LIGHT),
+                state
+                    .opacity
+                    .map_or(0, |opacity| opacity.min(u32::from(MAX_LIGHT)) as u8),
+            );
+        }
+    }
+    properties
+});
+
+/// The block light emitted by a block state
+pub fn luminance(state_id: u16) -> u8 {
+    LIGHT_PROPERTIES
+        .get(usize::from(state_id))
+        .map_or(0, |properties| properties.0)
+}
+
+/// How much light is lowered when passing through a block state
+pub fn opacity(state_id: u16) -> u8 {
+    LIGHT_PROPERTIES
+        .get(usize::from(state_id))
+        .map_or(0, |properties| properties.1)
+}
+
+/// Whether replacing a block state with another one changes the light around it
+pub fn changes_light(old_state_id: u16, new_state_id: u16) -> bool {
+    luminance(old_state_id) != luminance(new_state_id)
+        || opacity(old_state_id) != opacity(new_state_id)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LightKind {
+    /// Light coming from the sky, which does not lower when going straight down
+    Sky,
+    /// Light emitted by blocks like torches
+    Block,
+}
+
+/// The light levels of a subchunk.
+///
+/// Uniform means all blocks of the subchunk have the same level, which is the case for most
+/// subchunks, e.g. the ones completely underground or in the sky.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LightSection {
+    Uniform(u8),
+    /// Ordering: yzx (y being the most significant), two levels per byte with the first one in
+    /// the lower bits, like vanilla stores them
+    Nibbles(Box<[u8; LIGHT_SECTION_BYTES]>),
+}
+
+impl LightSection {
+    pub fn get(&self, index: usize) -> u8 {
+        match self {
+            Self::Uniform(level) => *level,
+            Self::Nibbles(nibbles) => (nibbles[index >> 1] >> ((index & 1) * 4)) & 0xF,
+        }
+    }
+
+    pub fn set(&mut self, index: usize, level: u8) {
+        if let Self::Uniform(uniform) = self {
+            if *uniform == level {
+                return;
+            }
+            *self = Self::Nibbles(Box::new([*uniform | (*uniform << 4); LIGHT_SECTION_BYTES]));
+        }
+        if let Self::Nibbles(nibbles) = self {
+            let shift = (index & 1) * 4;
+            let byte = &mut nibbles[index >> 1];
+            *byte = (*byte & !(0xF << shift)) | (level << shift);
+        }
+    }
+
+    pub fn to_bytes(&self) -> Box<[u8; LIGHT_SECTION_BYTES]> {
+        match self {
+            Self::Uniform(level) => Box::new([*level | (*level << 4); LIGHT_SECTION_BYTES]),
+            Self::Nibbles(nibbles) => nibbles.clone(),
+        }
+    }
+}
+
+/// The sky and block light of a chunk, for each subchunk from the bottom up
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChunkLight {
+    pub sky: Box<[LightSection]>,
+    pub block: Box<[LightSection]>,
+}
+
+impl Default for ChunkLight {
+    fn default() -> Self {
+        Self {
+            sky: vec![LightSection::Uniform(0); SUBCHUNKS_COUNT].into_boxed_slice(),
+            block: vec![LightSection::Uniform(0); SUBCHUNKS_COUNT].into_boxed_slice(),
+        }
+    }
+}
+
+impl ChunkLight {
+    pub fn sections(&self, kind: LightKind) -> &[LightSection] {
+        match kind {
+            LightKind::Sky => &self.sky,
+            LightKind::Block => &self.block,
+        }
+    }
+
+    fn sections_mut(&mut self, kind: LightKind) -> &mut [LightSection] {
+        match kind {
+            LightKind::Sky => &mut self.sky,
+            LightKind::Block => &mut self.block,
+        }
+    }
+
+    /// Gets the light at a position in the chunk, where `y` starts at the bottom of the world
+    pub fn get(&self, kind: LightKind, x: usize, y: usize, z: usize) -> u8 {
+        self.sections(kind)[y / 16].get(index_in_section(x, y, z))
+    }
+
+    pub fn set(&mut self, kind: LightKind, x: usize, y: usize, z: usize, level: u8) {
+        self.sections_mut(kind)[y / 16].set(index_in_section(x, y, z), level);
+    }
+}
+
+/// A light update, which is applied in one of the next ticks
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LightUpdate {
+    /// The block at the position changed
+    Block(BlockPos),
+    /// The chunk was loaded, so light has to spread between it and its neighbors
+    ChunkBorders(Vector2<i32>),
+}
+
+const fn index_in_section(x: usize, y: usize, z: usize) -> usize {
+    (y % 16) << 8 | z << 4 | x
+}
+
+/// Splits a position into its chunk and its position in the chunk, where `y` starts at the
+/// bottom of the world. Returns `None` for positions outside of the world.
+fn split_position(pos: &BlockPos) -> Option<(Vector2<i32>, usize, usize, usize)> {
+    let y = pos.0.y - i32::from(WORLD_LOWEST_Y);
+    if !(0..WORLD_HEIGHT as i32).contains(&y) {
+        return None;
+    }
+    Some((
+        Vector2::new(pos.0.x >> 4, pos.0.z >> 4),
+        (pos.0.x & 15) as usize,
+        y as usize,
+        (pos.0.z & 15) as usize,
+    ))
+}
+
+fn state_at(chunk: &ChunkData, x: usize, y: usize, z: usize) -> u16 {
+    match &chunk.subchunks {
+        Subchunks::Single(state) => *state,
+        Subchunks::Multi(subchunks) => match &subchunks[y / 16] {
+            Subchunk::Single(state) => *state,
+            Subchunk::Multi(states) => states[index_in_section(x, y, z)],
+        },
+    }
+}
+
+fn offset(pos: &BlockPos, (x, y, z): (i32, i32, i32)) -> BlockPos {
+    BlockPos(Vector3::new(pos.0.x + x, pos.0.y + y, pos.0.z + z))
+}
+
+/// Access to the blocks and the light of one or more chunks, which light is propagated in
+pub trait LightAccess {
+    /// Gets the block state at the position, or `None` if it can not be accessed
+    fn state(&self, pos: &BlockPos) -> Option<u16>;
+
+    /// Gets the light at the position, or `None` if it can not be accessed
+    fn light(&self, kind: LightKind, pos: &BlockPos) -> Option<u8>;
+
+    fn set_light(&mut self, kind: LightKind, pos: &BlockPos, level: u8);
+}
+
+impl LightAccess for ChunkData {
+    fn state(&self, pos: &BlockPos) -> Option<u16> {
+        let (_, x, y, z) = split_position(pos).filter(|(chunk, ..)| *chunk == self.position)?;
+        Some(state_at(self, x, y, z))
+    }
+
+    fn light(&self, kind: LightKind, pos: &BlockPos) -> Option<u8> {
+        let (_, x, y, z) = split_position(pos).filter(|(chunk, ..)| *chunk == self.position)?;
+        Some(self.light.get(kind, x, y, z))
+    }
+
+    fn set_light(&mut self, kind: LightKind, pos: &BlockPos, level: u8) {
+        if let Some((_, x, y, z)) =
+            split_position(pos).filter(|(chunk, ..)| *chunk == self.position)
+        {
+            self.light.set(kind, x, y, z, level);
+        }
+    }
+}
+
+/// A chunk and its loaded neighbors, which is as far as light from inside the chunk can spread
+pub struct LightNeighborhood {
+    chunks: Vec<OwnedRwLockWriteGuard<ChunkData>>,
+    /// The changed subchunks of each chunk, where bit 0 is the section below the world, like
+    /// in light packets
+    pub changed: HashMap<Vector2<i32>, u32>,
+}
+
+impl LightNeighborhood {
+    /// The chunks have to be locked in a consistent order, to not deadlock with other updates
+    pub fn new(chunks: Vec<OwnedRwLockWriteGuard<ChunkData>>) -> Self {
+        Self {
+            chunks,
+            changed: HashMap::new(),
+        }
+    }
+
+    fn chunk(&self, position: &Vector2<i32>) -> Option<&ChunkData> {
+        self.chunks
+            .iter()
+            .find(|chunk| chunk.position == *position)
+            .map(|chunk| &**chunk)
+    }
+}
+
+impl LightAccess for LightNeighborhood {
+    fn state(&self, pos: &BlockPos) -> Option<u16> {
+        let (chunk, x, y, z) = split_position(pos)?;
+        Some(state_at(self.chunk(&chunk)?, x, y, z))
+    }
+
+    fn light(&self, kind: LightKind, pos: &BlockPos) -> Option<u8> {
+        let (chunk, x, y, z) = split_position(pos)?;
+        Some(self.chunk(&chunk)?.light.get(kind, x, y, z))
+    }
+
+    fn set_light(&mut self, kind: LightKind, pos: &BlockPos, level: u8) {
+        let Some((position, x, y, z)) = split_position(pos) else {
+            return;
+        };
+        let Some(chunk) = self
+            .chunks
+            .iter_mut()
+            .find(|chunk| chunk.position == position)
+        else {
+            return;
+        };
+        chunk.light.set(kind, x, y, z, level);
+        *self.changed.entry(position).or_default() |= 1 << (y / 16 + 1);
+    }
+}
+
+/// The light a block has on its own, without any light spreading to it
+fn source_light(kind: LightKind, pos: &BlockPos, state: u16) -> u8 {
+    match kind {
+        LightKind::Block => luminance(state),
+        // The sky is right above the highest blocks
+        LightKind::Sky if pos.0.y == WORLD_HEIGHT as i32 + i32::from(WORLD_LOWEST_Y) - 1 => {
+            MAX_LIGHT.saturating_sub(opacity(state))
+        }
+        LightKind::Sky => 0,
+    }
+}
+
+/// The light spreading from a block with `level` to its neighbor in `direction`
+fn spread_light(kind: LightKind, level: u8, direction: usize, neighbor_state: u16) -> u8 {
+    let opacity = opacity(neighbor_state);
+    if kind == LightKind::Sky && direction == DOWN && level == MAX_LIGHT && opacity == 0 {
+        MAX_LIGHT
+    } else {
+        level.saturating_sub(opacity.max(1))
+    }
+}
+
+/// Spreads the light of the queued positions to their neighbors, as long as it makes them
+/// brighter
+fn increase(access: &mut impl LightAccess, kind: LightKind, queue: &mut VecDeque<BlockPos>) {
+    while let Some(pos) = queue.pop_front() {
+        let Some(level) = access.light(kind, &pos) else {
+            continue;
+        };
+        if level <= 1 {
+            continue;
+        }
+        for (direction, offsets) in DIRECTIONS.iter().enumerate() {
+            let neighbor = offset(&pos, *offsets);
+            let Some(state) = access.state(&neighbor) else {
+                continue;
+            };
+            let new_level = spread_light(kind, level, direction, state);
+            if new_level > access.light(kind, &neighbor).unwrap_or(MAX_LIGHT) {
+                access.set_light(kind, &neighbor, new_level);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+}
+
+/// Removes the light which spread from the queued positions, which had the given levels. The
+/// positions which still have light, that may spread to the darkened area, are queued to `relight`.
+fn decrease(
+    access: &mut impl LightAccess,
+    kind: LightKind,
+    queue: &mut VecDeque<(BlockPos, u8)>,
+    relight: &mut VecDeque<BlockPos>,
+) {
+    while let Some((pos, level)) = queue.pop_front() {
+        for (direction, offsets) in DIRECTIONS.iter().enumerate() {
+            let neighbor = offset(&pos, *offsets);
+            let Some(neighbor_level) = access.light(kind, &neighbor) else {
+                continue;
+            };
+            if neighbor_level == 0 {
+                continue;
+            }
+            let spread_from_here = neighbor_level < level
+                || (kind == LightKind::Sky
+                    && direction == DOWN
+                    && level == MAX_LIGHT
+                    && neighbor_level == MAX_LIGHT);
+            if !spread_from_here {
+                relight.push_back(neighbor);
+                continue;
+            }
+
+            access.set_light(kind, &neighbor, 0);
+            queue.push_back((neighbor, neighbor_level));
+            // Light sources keep their own light
+            let source = access
+                .state(&neighbor)
+                .map_or(0, |state| source_light(kind, &neighbor, state));
+            if source > 0 {
+                access.set_light(kind, &neighbor, source);
+                relight.push_back(neighbor);
+            }
+        }
+    }
+}
+
+/// Updates the light around a block, after its state changed
+pub fn update_block(access: &mut impl LightAccess, pos: &BlockPos) {
+    let Some(state) = access.state(pos) else {
+        return;
+    };
+    for kind in [LightKind::Sky, LightKind::Block] {
+        let mut removed = VecDeque::new();
+        let mut relight = VecDeque::new();
+
+        let old_level = access.light(kind, pos).unwrap_or(0);
+        if old_level > 0 {
+            access.set_light(kind, pos, 0);
+            removed.push_back((*pos, old_level));
+        }
+        decrease(access, kind, &mut removed, &mut relight);
+
+        let source = source_light(kind, pos, state);
+        if source > access.light(kind, pos).unwrap_or(0) {
+            access.set_light(kind, pos, source);
+        }
+        relight.push_back(*pos);
+        // The neighbors may now spread their light through the block
+        relight.extend(DIRECTIONS.iter().map(|offsets| offset(pos, *offsets)));
+        increase(access, kind, &mut relight);
+    }
+}
+
+/// Spreads the light between a chunk and its neighbors, after it has been loaded
+pub fn spread_chunk_borders(access: &mut impl LightAccess, chunk: Vector2<i32>) {
+    let (min_x, min_z) = (chunk.x << 4, chunk.z << 4);
+    // The outermost columns of the chunk and the adjacent columns of its neighbors
+    let mut columns = Vec::with_capacity(16 * 8);
+    for i in 0..16 {
+        columns.extend([
+            (min_x + i, min_z),
+            (min_x + i, min_z - 1),
+            (min_x + i, min_z + 15),
+            (min_x + i, min_z + 16),
+            (min_x, min_z + i),
+            (min_x - 1, min_z + i),
+            (min_x + 15, min_z + i),
+            (min_x + 16, min_z + i),
+        ]);
+    }
+
+    for kind in [LightKind::Sky, LightKind::Block] {
+        let mut queue = VecDeque::new();
+        for (x, z) in &columns {
+            for y in 0..WORLD_HEIGHT as i32 {
+                let pos = BlockPos(Vector3::new(*x, y + i32::from(WORLD_LOWEST_Y), *z));
+                if access.light(kind, &pos).is_some_and(|level| level > 1) {
+                    queue.push_back(pos);
+                }
+            }
+        }
+        increase(access, kind, &mut queue);
+    }
+}
+
+/// Computes the light of a chunk on its own. Light spreading from or to neighboring chunks is
+/// added by [`spread_chunk_borders`].
+pub fn light_chunk(chunk: &mut ChunkData) {
+    let mut light = ChunkLight::default();
+
+    // Subchunks above the highest block are lit by the sky completely, light does not need to
+    // spread in them
+    let mut open_sections = SUBCHUNKS_COUNT;
+    while open_sections > 0 {
+        let state = match &chunk.subchunks {
+            Subchunks::Single(state) => Some(*state),
+            Subchunks::Multi(subchunks) => match &subchunks[open_sections - 1] {
+                Subchunk::Single(state) => Some(*state),
+                Subchunk::Multi(_) => None,
+            },
+        };
+        if !state.is_some_and(|state| opacity(state) == 0) {
+            break;
+        }
+        open_sections -= 1;
+        light.sky[open_sections] = LightSection::Uniform(MAX_LIGHT);
+    }
+
+    let mut sky_queue = VecDeque::new();
+    for x in 0..16 {
+        for z in 0..16 {
+            let mut level = MAX_LIGHT;
+            for y in (0..open_sections * 16).rev() {
+                level = spread_light(LightKind::Sky, level, DOWN, state_at(chunk, x, y, z));
+                if level == 0 {
+                    break;
+                }
+                light.set(LightKind::Sky, x, y, z, level);
+                if level > 1 {
+                    sky_queue.push_back(chunk_block_pos(chunk.position, x, y, z));
+                }
+            }
+        }
+    }
+
+    let mut block_queue = VecDeque::new();
+    for y in 0..WORLD_HEIGHT {
+        let section_is_dark = match &chunk.subchunks {
+            Subchunks::Single(state) => luminance(*state) == 0,
+            Subchunks::Multi(subchunks) => match &subchunks[y / 16] {
+                Subchunk::Single(state) => luminance(*state) == 0,
+                Subchunk::Multi(_) => false,
+            },
+        };
+        if section_is_dark {
+            continue;
+        }
+        for z in 0..16 {
+            for x in 0..16 {
+                let luminance = luminance(state_at(chunk, x, y, z));
+                if luminance > 0 {
+                    light.set(LightKind::Block, x, y, z, luminance);
+                    block_queue.push_back(chunk_block_pos(chunk.position, x, y, z));
+                }
+            }
+        }
+    }
+
+    chunk.light = light;
+    increase(chunk, LightKind::Sky, &mut sky_queue);
+    increase(chunk, LightKind::Block, &mut block_queue);
+}
+
+fn chunk_block_pos(chunk: Vector2<i32>, x: usize, y: usize, z: usize) -> BlockPos {
+    BlockPos(Vector3::new(
+        (chunk.x << 4) + x as i32,
+        y as i32 + i32::from(WORLD_LOWEST_Y),
+        (chunk.z << 4) + z as i32,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+
+    use super::{light_chunk, update_block, LightAccess, LightKind, MAX_LIGHT};
+    use crate::{
+        block::registry::get_block,
+        chunk::{ChunkData, Subchunks},
+        coordinates::ChunkRelativeBlockCoordinates,
+    };
+
+    fn state(name: &str) -> u16 {
+        get_block(name).unwrap().default_state_id
+    }
+
+    fn set_block(chunk: &mut ChunkData, x: i32, y: i32, z: i32, state: u16) {
+        chunk.set_block(
+            ChunkRelativeBlockCoordinates::from(Vector3::new(x, y, z)),
+            state,
+        );
+    }
+
+    fn light(chunk: &ChunkData, kind: LightKind, x: i32, y: i32, z: i32) -> u8 {
+        chunk.light(kind, &BlockPos(Vector3::new(x, y, z))).unwrap()
+    }
+
+    #[test]
+    fn torch_in_tunnel() {
+        let stone = state("stone");
+        let mut chunk = ChunkData::new(
+            Subchunks::Single(stone),
+            Default::default(),
+            Vector2::new(0, 0),
+        );
+        for x in 0..16 {
+            set_block(&mut chunk, x, 10, 8, 0);
+        }
+        light_chunk(&mut chunk);
+        for x in 0..16 {
+            assert_eq!(light(&chunk, LightKind::Sky, x, 10, 8), 0);
+            assert_eq!(light(&chunk, LightKind::Block, x, 10, 8), 0);
+        }
+
+        set_block(&mut chunk, 2, 10, 8, state("torch"));
+        update_block(&mut chunk, &BlockPos(Vector3::new(2, 10, 8)));
+        assert_eq!(light(&chunk, LightKind::Block, 2, 10, 8), 14);
+        assert_eq!(light(&chunk, LightKind::Block, 1, 10, 8), 13);
+        assert_eq!(light(&chunk, LightKind::Block, 5, 10, 8), 11);
+        assert_eq!(light(&chunk, LightKind::Block, 15, 10, 8), 1);
+        // Light does not go through the walls
+        assert_eq!(light(&chunk, LightKind::Block, 2, 11, 8), 0);
+        assert_eq!(light(&chunk, LightKind::Block, 2, 10, 9), 0);
+
+        set_block(&mut chunk, 2, 10, 8, 0);
+        update_block(&mut chunk, &BlockPos(Vector3::new(2, 10, 8)));
+        for x in 0..16 {
+            assert_eq!(light(&chunk, LightKind::Block, x, 10, 8), 0);
+        }
+    }
+
+    #[test]
+    fn hole_in_ceiling() {
+        let stone = state("stone");
+        let mut chunk =
+            ChunkData::new(Subchunks::Single(0), Default::default(), Vector2::new(0, 0));
+        for x in 0..16 {
+            for z in 0..16 {
+                set_block(&mut chunk, x, 100, z, stone);
+                set_block(&mut chunk, x, 80, z, stone);
+            }
+        }
+        light_chunk(&mut chunk);
+        assert_eq!(light(&chunk, LightKind::Sky, 8, 101, 8), MAX_LIGHT);
+        assert_eq!(light(&chunk, LightKind::Sky, 8, 100, 8), 0);
+        assert_eq!(light(&chunk, LightKind::Sky, 8, 90, 8), 0);
+
+        set_block(&mut chunk, 8, 100, 8, 0);
+        update_block(&mut chunk, &BlockPos(Vector3::new(8, 100, 8)));
+        // The sky light goes straight down without getting darker
+        assert_eq!(light(&chunk, LightKind::Sky, 8, 100, 8), MAX_LIGHT);
+        assert_eq!(light(&chunk, LightKind::Sky, 8, 81, 8), MAX_LIGHT);
+        assert_eq!(light(&chunk, LightKind::Sky, 8, 80, 8), 0);
+        // And spreads out below the ceiling
+        assert_eq!(light(&chunk, LightKind::Sky, 9, 99, 8), 14);
+        assert_eq!(light(&chunk, LightKind::Sky, 12, 99, 8), 11);
+        assert_eq!(light(&chunk, LightKind::Sky, 12, 99, 11), 8);
+
+        set_block(&mut chunk, 8, 100, 8, stone);
+        update_block(&mut chunk, &BlockPos(Vector3::new(8, 100, 8)));
+        for y in 81..=100 {
+            assert_eq!(light(&chunk, LightKind::Sky, 8, y, 8), 0);
+            assert_eq!(light(&chunk, LightKind::Sky, 12, y, 11), 0);
+        }
+        assert_eq!(light(&chunk, LightKind::Sky, 8, 101, 8), MAX_LIGHT);
+    }
+}
@@ -0,0 +1,168 @@
+use pumpkin_data::item::Item;
+use pumpkin_util::random::{get_seed, legacy_rand::LegacyRand, RandomImpl};
+
+use crate::item::ItemStack;
+
+/// An item a pool can choose, with its chance relative to the other entries of the pool
+struct LootEntry {
+    item: Item,
+    weight: i32,
+    min_count: u8,
+    max_count: u8,
+}
+
+const fn entry(item: Item, weight: i32, min_count: u8, max_count: u8) -> LootEntry {
+    LootEntry {
+        item,
+        weight,
+        min_count,
+        max_count,
+    }
+}
+
+/// Chooses a number of weighted entries
+struct LootPool {
+    min_rolls: i32,
+    max_rolls: i32,
+    entries: &'static [LootEntry],
+}
+
+/// The loot of a chest placed by world generation, filled in when the chest is first opened
+pub struct ChestLootTable {
+    /// The resource location, e.g. `minecraft:chests/simple_dungeon`
+    pub name: &'static str,
+    pools: &'static [LootPool],
+}
+
+// Enchanted books are left out, as items can not be enchanted yet
+pub const SIMPLE_DUNGEON: ChestLootTable = ChestLootTable {
+    name: "minecraft:chests/simple_dungeon",
+    pools: &[
+        LootPool {
+            min_rolls: 1,
+            max_rolls: 3,
+            entries: &[
+                entry(Item::SADDLE, 20, 1, 1),
+                entry(Item::GOLDEN_APPLE, 15, 1, 1),
+                entry(Item::ENCHANTED_GOLDEN_APPLE, 2, 1, 1),
+                entry(Item::MUSIC_DISC_OTHERSIDE, 2, 1, 1),
+                entry(Item::MUSIC_DISC_13, 15, 1, 1),
+                entry(Item::MUSIC_DISC_CAT, 15, 1, 1),
+                entry(Item::NAME_TAG, 20, 1, 1),
+                entry(Item::GOLDEN_HORSE_ARMOR, 10, 1, 1),
+                entry(Item::IRON_HORSE_ARMOR, 15, 1, 1),
+                entry(Item::DIAMOND_HORSE_ARMOR, 5, 1, 1),
+            ],
+        },
+        LootPool {
+            min_rolls: 1,
+            max_rolls: 4,
+            entries: &[
+                entry(Item::IRON_INGOT, 10, 1, 4),
+                entry(Item::GOLD_INGOT, 5, 1, 4),
+                entry(Item::BREAD, 20, 1, 1),
+                entry(Item::WHEAT, 20, 1, 4),
+                entry(Item::BUCKET, 10, 1, 1),
+                entry(Item::REDSTONE, 15, 1, 4),
+                entry(Item::COAL, 15, 1, 4),
+                entry(Item::MELON_SEEDS, 10, 2, 4),
+                entry(Item::PUMPKIN_SEEDS, 10, 2, 4),
+                entry(Item::BEETROOT_SEEDS, 10, 2, 4),
+            ],
+        },
+        LootPool {
+            min_rolls: 3,
+            max_rolls: 3,
+            entries: &[
+                entry(Item::BONE, 10, 1, 8),
+                entry(Item::GUNPOWDER, 10, 1, 8),
+                entry(Item::ROTTEN_FLESH, 10, 1, 8),
+                entry(Item::STRING, 10, 1, 8),
+            ],
+        },
+    ],
+};
+
+const CHEST_LOOT_TABLES: [&ChestLootTable; 1] = [&SIMPLE_DUNGEON];
+
+/// Gets a chest loot table by its resource location, with or without namespace
+#[must_use]
+pub fn get_chest_loot_table(name: &str) -> Option<&'static ChestLootTable> {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    CHEST_LOOT_TABLES
+        .into_iter()
+        .find(|table| table.name.strip_prefix("minecraft:") == Some(name))
+}
+
+impl ChestLootTable {
+    /// Rolls the items of the table. The same seed always gives the same items.
+    #[must_use]
+    pub fn roll(&self, random: &mut LegacyRand) -> Vec<ItemStack> {
+        let mut items = Vec::new();
+        for pool in self.pools {
+            let rolls = random.next_inbetween_i32(pool.min_rolls, pool.max_rolls);
+            let total_weight: i32 = pool.entries.iter().map(|entry| entry.weight).sum();
+            for _ in 0..rolls {
+                let mut choice = random.next_bounded_i32(total_weight);
+                let Some(entry) = pool.entries.iter().find(|entry| {
+                    choice -= entry.weight;
+                    choice < 0
+                }) else {
+                    continue;
+                };
+                let count =
+                    random.next_inbetween_i32(entry.min_count.into(), entry.max_count.into()) as u8;
+                if entry.item.id != Item::AIR.id {
+                    items.push(ItemStack::new(count, entry.item));
+                }
+            }
+        }
+        items
+    }
+
+    /// Rolls the items of the table and puts them into random empty slots, like vanilla does
+    /// when a chest is opened. A seed of 0 means a random seed.
+    pub fn fill(&self, seed: i64, slots: &mut [Option<ItemStack>]) {
+        let seed = if seed == 0 { get_seed() } else { seed as u64 };
+        let mut random = LegacyRand::from_seed(seed);
+        let items = self.roll(&mut random);
+
+        let mut empty: Vec<usize> = (0..slots.len()).filter(|i| slots[*i].is_none()).collect();
+        for item in items {
+            if empty.is_empty() {
+                break;
+            }
+            let index = random.next_bounded_i32(empty.len() as i32) as usize;
+            slots[empty.swap_remove(index)] = Some(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::item::ItemStack;
+
+    use super::{get_chest_loot_table, SIMPLE_DUNGEON};
+
+    #[test]
+    fn filling_is_seeded() {
+        let mut first: [Option<ItemStack>; 27] = Default::default();
+        let mut second: [Option<ItemStack>; 27] = Default::default();
+        SIMPLE_DUNGEON.fill(42, &mut first);
+        SIMPLE_DUNGEON.fill(42, &mut second);
+
+        let filled = first.iter().flatten().count();
+        // At least the 3 rolls of the last pool, at most all rolls
+        assert!((5..=10).contains(&filled));
+        for (a, b) in first.iter().zip(&second) {
+            assert_eq!(
+                a.as_ref().map(|stack| (stack.item.id, stack.item_count)),
+                b.as_ref().map(|stack| (stack.item.id, stack.item_count))
+            );
+        }
+
+        assert!(get_chest_loot_table("chests/simple_dungeon").is_some());
+        assert!(get_chest_loot_table("minecraft:chests/simple_dungeon").is_some());
+        assert!(get_chest_loot_table("minecraft:chests/end_city_treasure").is_none());
+    }
+}
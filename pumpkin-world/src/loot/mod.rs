@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+pub mod chest;
+
 #[expect(dead_code)]
 #[derive(Deserialize, Clone)]
 pub struct LootTable {
@@ -0,0 +1,284 @@
+//! Block ticks which are scheduled some ticks ahead, e.g. to let fluids flow one step at a time.
+//!
+//! The ticks of a chunk are saved with it when it is written, see [`ScheduledTicks::save_chunk`],
+//! and scheduled again when it is loaded.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+
+use crate::block::registry::{get_block, get_block_by_id};
+
+/// How many scheduled ticks run at most each tick, like in vanilla. The others wait for the next
+/// tick.
+const MAX_TICKS_PER_TICK: usize = 65536;
+
+/// Which of the ticks due in the same tick run first, like in vanilla. Most ticks have the
+/// normal priority.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TickPriority {
+    ExtremelyHigh = -3,
+    VeryHigh = -2,
+    High = -1,
+    #[default]
+    Normal = 0,
+    Low = 1,
+    VeryLow = 2,
+    ExtremelyLow = 3,
+}
+
+impl TickPriority {
+    /// Reads the priority saved in a chunk, clamping values which are out of range
+    #[must_use]
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            i32::MIN..=-3 => Self::ExtremelyHigh,
+            -2 => Self::VeryHigh,
+            -1 => Self::High,
+            0 => Self::Normal,
+            1 => Self::Low,
+            2 => Self::VeryLow,
+            3..=i32::MAX => Self::ExtremelyLow,
+        }
+    }
+}
+
+/// A pending tick as it is saved in a chunk
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScheduledTick {
+    pub position: BlockPos,
+    /// The block which is ticked. If the block was replaced by then, the tick is skipped.
+    pub block_id: u16,
+    /// In how many ticks the tick is due
+    pub delay: u64,
+    pub priority: TickPriority,
+}
+
+/// Reads a pending tick in the format of the chunk NBT. Ticks of unknown blocks are skipped.
+#[must_use]
+pub fn read_block_tick(nbt: &NbtCompound) -> Option<ScheduledTick> {
+    let block = get_block(nbt.get_string("i")?)?;
+    Some(ScheduledTick {
+        position: BlockPos(Vector3::new(
+            nbt.get_int("x")?,
+            nbt.get_int("y")?,
+            nbt.get_int("z")?,
+        )),
+        block_id: block.id,
+        delay: u64::try_from(nbt.get_int("t")?).unwrap_or(0),
+        priority: TickPriority::from_i32(nbt.get_int("p").unwrap_or(0)),
+    })
+}
+
+/// Writes a pending tick in the format of the chunk NBT, like vanilla
+#[must_use]
+pub fn write_block_tick(tick: &ScheduledTick) -> Option<NbtCompound> {
+    let block = get_block_by_id(tick.block_id)?;
+    let mut nbt = NbtCompound::new();
+    nbt.put("i", format!("minecraft:{}", block.name));
+    nbt.put_int("x", tick.position.0.x);
+    nbt.put_int("y", tick.position.0.y);
+    nbt.put_int("z", tick.position.0.z);
+    nbt.put_int("t", i32::try_from(tick.delay).unwrap_or(i32::MAX));
+    nbt.put_int("p", tick.priority as i32);
+    Some(nbt)
+}
+
+/// The order ticks run in: When they are due, then by priority, then in the order they were
+/// scheduled
+type TickOrder = (u64, TickPriority, u64);
+
+#[derive(Default)]
+pub struct ScheduledTicks {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Counts the ticks since the level was loaded
+    current_tick: u64,
+    /// Counts the scheduled ticks, so ticks due at the same time keep their order
+    scheduled_count: u64,
+    /// The pending ticks in the order they run
+    queue: BTreeMap<TickOrder, (BlockPos, u16)>,
+    /// Where each pending tick is in the queue. A block only has one pending tick.
+    pending: HashMap<(BlockPos, u16), TickOrder>,
+}
+
+impl Inner {
+    fn remove(&mut self, key: &(BlockPos, u16)) -> Option<TickOrder> {
+        let order = self.pending.remove(key)?;
+        self.queue.remove(&order);
+        Some(order)
+    }
+}
+
+impl ScheduledTicks {
+    /// Schedules a tick of the block at the position in `delay` ticks. A block only has one
+    /// pending tick, so if there is one already, the earlier of both is kept.
+    pub fn schedule(&self, position: BlockPos, block_id: u16, delay: u64, priority: TickPriority) {
+        let mut inner = self.inner.lock().unwrap();
+        let due = inner.current_tick + delay.max(1);
+        let key = (position, block_id);
+        if let Some(order) = inner.pending.get(&key) {
+            if order.0 <= due {
+                return;
+            }
+            inner.remove(&key);
+        }
+        let order = (due, priority, inner.scheduled_count);
+        inner.scheduled_count += 1;
+        inner.queue.insert(order, key);
+        inner.pending.insert(key, order);
+    }
+
+    /// Whether the block at the position has a pending tick
+    pub fn is_scheduled(&self, position: BlockPos, block_id: u16) -> bool {
+        self.inner
+            .lock()
+            .unwrap()
+            .pending
+            .contains_key(&(position, block_id))
+    }
+
+    /// Advances by one tick and removes and returns the ticks which are due, in the order they
+    /// run. Ticks in chunks which are not `ticking` stay pending until they are.
+    pub fn advance(&self, ticking: impl Fn(&Vector2<i32>) -> bool) -> Vec<(BlockPos, u16)> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.current_tick += 1;
+        let current_tick = inner.current_tick;
+
+        let due: Vec<_> = inner
+            .queue
+            .iter()
+            .take_while(|(order, _)| order.0 <= current_tick)
+            .filter(|(_, (position, _))| ticking(&position.chunk_and_chunk_relative_position().0))
+            .take(MAX_TICKS_PER_TICK)
+            .map(|(_, key)| *key)
+            .collect();
+        for key in &due {
+            inner.remove(key);
+        }
+        due
+    }
+
+    /// The pending ticks in the chunk, to save them with it. When the chunk is `unloaded`, they
+    /// are removed too and are scheduled again with [`ScheduledTicks::load`] once it is loaded.
+    pub fn save_chunk(&self, chunk: &Vector2<i32>, unloaded: bool) -> Vec<ScheduledTick> {
+        let mut inner = self.inner.lock().unwrap();
+        let current_tick = inner.current_tick;
+        let ticks: Vec<_> = inner
+            .queue
+            .iter()
+            .filter(|(_, (position, _))| position.chunk_and_chunk_relative_position().0 == *chunk)
+            .map(|((due, priority, _), (position, block_id))| ScheduledTick {
+                position: *position,
+                block_id: *block_id,
+                delay: due.saturating_sub(current_tick),
+                priority: *priority,
+            })
+            .collect();
+        if unloaded {
+            for tick in &ticks {
+                inner.remove(&(tick.position, tick.block_id));
+            }
+        }
+        ticks
+    }
+
+    /// Schedules the ticks which were saved with a chunk
+    pub fn load(&self, ticks: Vec<ScheduledTick>) {
+        for tick in ticks {
+            self.schedule(tick.position, tick.block_id, tick.delay, tick.priority);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+
+    use super::{ScheduledTicks, TickPriority};
+
+    const BLOCK: u16 = 1;
+
+    #[test]
+    fn ticks_run_when_due() {
+        let ticks = ScheduledTicks::default();
+        let first = BlockPos(Vector3::new(0, 64, 0));
+        let second = BlockPos(Vector3::new(1, 64, 0));
+        ticks.schedule(second, BLOCK, 3, TickPriority::Normal);
+        ticks.schedule(first, BLOCK, 2, TickPriority::Normal);
+        // Scheduling again keeps the earlier tick
+        ticks.schedule(first, BLOCK, 5, TickPriority::Normal);
+
+        assert!(ticks.advance(|_| true).is_empty());
+        assert!(ticks.advance(|_| true) == vec![(first, BLOCK)]);
+        assert!(!ticks.is_scheduled(first, BLOCK));
+        assert!(ticks.advance(|_| true) == vec![(second, BLOCK)]);
+        assert!(ticks.advance(|_| true).is_empty());
+    }
+
+    #[test]
+    fn ticks_run_by_priority() {
+        let ticks = ScheduledTicks::default();
+        let first = BlockPos(Vector3::new(0, 64, 0));
+        let second = BlockPos(Vector3::new(1, 64, 0));
+        let third = BlockPos(Vector3::new(2, 64, 0));
+        ticks.schedule(third, BLOCK, 1, TickPriority::Low);
+        ticks.schedule(second, BLOCK, 1, TickPriority::Normal);
+        ticks.schedule(first, BLOCK, 1, TickPriority::High);
+        // A position can have ticks for different blocks
+        ticks.schedule(first, BLOCK + 1, 1, TickPriority::Low);
+
+        assert!(
+            ticks.advance(|_| true)
+                == vec![
+                    (first, BLOCK),
+                    (second, BLOCK),
+                    (third, BLOCK),
+                    (first, BLOCK + 1)
+                ]
+        );
+    }
+
+    #[test]
+    fn ticks_wait_for_ticking_chunks() {
+        let ticks = ScheduledTicks::default();
+        let position = BlockPos(Vector3::new(0, 64, 0));
+        ticks.schedule(position, BLOCK, 1, TickPriority::Normal);
+
+        assert!(ticks.advance(|_| false).is_empty());
+        assert!(ticks.advance(|_| true) == vec![(position, BLOCK)]);
+    }
+
+    #[test]
+    fn ticks_are_saved_with_chunks() {
+        let ticks = ScheduledTicks::default();
+        let inside = BlockPos(Vector3::new(3, 64, 5));
+        let outside = BlockPos(Vector3::new(16, 64, 5));
+        ticks.schedule(inside, BLOCK, 4, TickPriority::High);
+        ticks.schedule(outside, BLOCK, 3, TickPriority::Normal);
+        ticks.advance(|_| true);
+
+        let chunk = Vector2::new(0, 0);
+        let saved = ticks.save_chunk(&chunk, false);
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].delay, 3);
+        assert_eq!(saved[0].priority, TickPriority::High);
+        assert!(ticks.is_scheduled(inside, BLOCK));
+
+        // Unloading removes the ticks until the chunk is loaded again
+        let saved = ticks.save_chunk(&chunk, true);
+        assert!(!ticks.is_scheduled(inside, BLOCK));
+        assert!(ticks.is_scheduled(outside, BLOCK));
+        ticks.load(saved);
+        assert!(ticks.advance(|_| true).is_empty());
+        assert!(ticks.advance(|_| true) == vec![(outside, BLOCK)]);
+        assert!(ticks.advance(|_| true) == vec![(inside, BLOCK)]);
+    }
+}
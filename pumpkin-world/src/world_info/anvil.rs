@@ -12,10 +12,14 @@ use crate::{
     world_info::{MAXIMUM_SUPPORTED_WORLD_DATA_VERSION, MINIMUM_SUPPORTED_WORLD_DATA_VERSION},
 };
 
-use super::{LevelData, WorldInfoError, WorldInfoReader, WorldInfoWriter};
+use super::{
+    LevelData, WorldGenSettings, WorldInfoError, WorldInfoReader, WorldInfoWriter, WorldVersion,
+};
 
 pub const LEVEL_DAT_FILE_NAME: &str = "level.dat";
 pub const LEVEL_DAT_BACKUP_FILE_NAME: &str = "level.dat_old";
+/// The `version` vanilla expects in the level.dat of anvil worlds
+const ANVIL_NBT_VERSION: i32 = 19133;
 
 pub struct AnvilLevelInfo;
 
@@ -53,6 +57,27 @@ fn check_file_data_version(raw_nbt: &[u8]) -> Result<(), WorldInfoError> {
     }
 }
 
+/// The seed of a level.dat saved before 1.16, which has no `WorldGenSettings` yet
+fn read_legacy_seed(raw_nbt: &[u8]) -> Option<i64> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct LevelData {
+        random_seed: Option<i64>,
+        world_gen_settings: Option<WorldGenSettings>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct LevelDat {
+        data: LevelData,
+    }
+
+    let info: LevelDat = pumpkin_nbt::from_bytes(raw_nbt).ok()?;
+    if info.data.world_gen_settings.is_some() {
+        return None;
+    }
+    info.data.random_seed
+}
+
 impl WorldInfoReader for AnvilLevelInfo {
     fn read_world_info(&self, level_folder: &LevelFolder) -> Result<LevelData, WorldInfoError> {
         let path = level_folder.root_folder.join(LEVEL_DAT_FILE_NAME);
@@ -63,10 +88,13 @@ impl WorldInfoReader for AnvilLevelInfo {
         let _ = compression_reader.read_to_end(&mut buf)?;
 
         check_file_data_version(&buf)?;
-        let info = pumpkin_nbt::from_bytes::<LevelDat>(&buf[..])
+        let mut info = pumpkin_nbt::from_bytes::<LevelDat>(&buf[..])
             .map_err(|e| WorldInfoError::DeserializationError(e.to_string()))?;
 
-        // TODO: check version
+        // Before 1.16 the seed was saved directly in the level data
+        if let Some(seed) = read_legacy_seed(&buf) {
+            info.data.world_gen_settings.seed = seed;
+        }
 
         Ok(info.data)
     }
@@ -84,6 +112,11 @@ impl WorldInfoWriter for AnvilLevelInfo {
             .expect("Time went backwards");
         let mut level_data = info.clone();
         level_data.last_played = since_the_epoch.as_millis() as i64;
+        // The level is written in the layout of the current version, even if it was read from an
+        // older one
+        level_data.data_version = MAXIMUM_SUPPORTED_WORLD_DATA_VERSION;
+        level_data.version = WorldVersion::default();
+        level_data.nbt_version = ANVIL_NBT_VERSION;
         let level = LevelDat { data: level_data };
 
         // open file
@@ -113,16 +146,30 @@ pub struct LevelDat {
 #[cfg(test)]
 mod test {
 
-    use std::{fs, sync::LazyLock};
+    use std::{
+        fs,
+        io::{Read, Write},
+        path::Path,
+        sync::LazyLock,
+    };
 
-    use flate2::read::GzDecoder;
-    use pumpkin_nbt::{deserializer::from_bytes, serializer::to_bytes};
+    use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+    use pumpkin_nbt::{
+        compound::NbtCompound,
+        deserializer::{from_bytes, ReadAdaptor},
+        serializer::to_bytes,
+        tag::NbtTag,
+        Nbt,
+    };
     use temp_dir::TempDir;
 
     use crate::{
         global_path,
         level::LevelFolder,
-        world_info::{DataPacks, LevelData, WorldGenSettings, WorldInfoError, WorldVersion},
+        world_info::{
+            DataPacks, GameRules, LevelData, WorldGenSettings, WorldInfoError, WorldVersion,
+            MAXIMUM_SUPPORTED_WORLD_DATA_VERSION,
+        },
     };
 
     use super::{AnvilLevelInfo, LevelDat, WorldInfoReader, WorldInfoWriter, LEVEL_DAT_FILE_NAME};
@@ -177,10 +224,14 @@ mod test {
             world_gen_settings: WorldGenSettings { seed: 1 },
             last_played: 1733847709327,
             level_name: "New World".to_string(),
+            raining: false,
+            rain_time: 80942,
             spawn_x: 160,
             spawn_y: 70,
             spawn_z: 160,
             spawn_angle: 0.0,
+            thundering: false,
+            thunder_time: 74299,
             nbt_version: 19133,
             version: WorldVersion {
                 name: "1.21.4".to_string(),
@@ -188,9 +239,108 @@ mod test {
                 snapshot: false,
                 series: "main".to_string(),
             },
+            game_rules: GameRules::default(),
+            time: 1727,
         },
     });
 
+    /// Reads the level data of a level.dat without deserializing it, with all of its tags
+    fn read_raw_level_dat(path: &Path) -> NbtCompound {
+        let mut raw_nbt = Vec::new();
+        GzDecoder::new(fs::File::open(path).unwrap())
+            .read_to_end(&mut raw_nbt)
+            .unwrap();
+        let nbt = Nbt::read(&mut ReadAdaptor::new(&raw_nbt[..])).unwrap();
+        nbt.root_tag.get_compound("Data").unwrap().clone()
+    }
+
+    #[test]
+    fn level_dat_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_folder = LevelFolder {
+            root_folder: temp_dir.path().to_path_buf(),
+            region_folder: temp_dir.path().join("region"),
+        };
+        let path = level_folder.root_folder.join(LEVEL_DAT_FILE_NAME);
+
+        // Written by vanilla 1.21.4
+        fs::copy(global_path!("../../assets/level_1_21_4.dat"), &path).unwrap();
+        let vanilla = read_raw_level_dat(&path);
+        let data = AnvilLevelInfo.read_world_info(&level_folder).unwrap();
+        assert_eq!(data, LEVEL_DAT.data);
+
+        // Everything written is the same as what vanilla wrote, except the time it was played
+        AnvilLevelInfo
+            .write_world_info(data.clone(), &level_folder)
+            .unwrap();
+        let mut written = read_raw_level_dat(&path);
+        assert!(written.get_long("LastPlayed").unwrap() > data.last_played);
+        written.remove("LastPlayed");
+        assert!(written.is_contained_in(&vanilla));
+
+        // What changes while playing is read back as it was written
+        let mut data = AnvilLevelInfo.read_world_info(&level_folder).unwrap();
+        data.game_rules.set("keepInventory", true);
+        data.game_rules.set("randomTickSpeed", 10);
+        data.game_rules.set("somePluginRule", "kept");
+        data.border_center_x = 120.5;
+        data.border_size = 5000.0;
+        data.border_size_lerp_target = 1000.0;
+        data.border_size_lerp_time = 60_000;
+        data.clear_weather_time = 500;
+        data.day_time = 30_000;
+        data.difficulty = 3;
+        data.raining = true;
+        data.thundering = true;
+        data.spawn_x = -100;
+        data.spawn_angle = 90.0;
+        data.time = 123_456;
+        AnvilLevelInfo
+            .write_world_info(data.clone(), &level_folder)
+            .unwrap();
+        let read = AnvilLevelInfo.read_world_info(&level_folder).unwrap();
+        assert_eq!(
+            read,
+            LevelData {
+                last_played: read.last_played,
+                ..data
+            }
+        );
+        assert_eq!(read.game_rules.get_bool("keepInventory"), Some(true));
+        assert_eq!(read.game_rules.get_int("randomTickSpeed"), Some(10));
+        assert_eq!(read.game_rules.get("somePluginRule"), Some("kept"));
+    }
+
+    #[test]
+    fn older_level_dat_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_folder = LevelFolder {
+            root_folder: temp_dir.path().to_path_buf(),
+            region_folder: temp_dir.path().join("region"),
+        };
+
+        // Written by vanilla 1.20, and upgraded to the current version when it is written again
+        let test_dat = global_path!("../../assets/level_1_20.dat");
+        fs::copy(test_dat, level_folder.root_folder.join(LEVEL_DAT_FILE_NAME)).unwrap();
+        let data = AnvilLevelInfo.read_world_info(&level_folder).unwrap();
+        AnvilLevelInfo
+            .write_world_info(data.clone(), &level_folder)
+            .unwrap();
+        let read = AnvilLevelInfo.read_world_info(&level_folder).unwrap();
+
+        assert_eq!(read.data_version, MAXIMUM_SUPPORTED_WORLD_DATA_VERSION);
+        assert_eq!(
+            read,
+            LevelData {
+                last_played: read.last_played,
+                data_version: read.data_version,
+                nbt_version: read.nbt_version,
+                version: read.version.clone(),
+                ..data
+            }
+        );
+    }
+
     #[test]
     fn test_deserialize_level_dat() {
         let raw_compressed_nbt = include_bytes!("../../assets/level_1_21_4.dat");
@@ -216,7 +366,7 @@ mod test {
     }
 
     #[test]
-    fn failed_deserialize_old_level_dat() {
+    fn deserialize_older_level_dat() {
         let temp_dir = TempDir::new().unwrap();
         let level_folder = LevelFolder {
             root_folder: temp_dir.path().to_path_buf(),
@@ -226,11 +376,63 @@ mod test {
         let test_dat = global_path!("../../assets/level_1_20.dat");
         fs::copy(test_dat, level_folder.root_folder.join(LEVEL_DAT_FILE_NAME)).unwrap();
 
-        let result = AnvilLevelInfo.read_world_info(&level_folder);
-        match result {
-            Ok(_) => panic!("This should fail!"),
-            Err(WorldInfoError::UnsupportedVersion(_)) => {}
-            Err(_) => panic!("Wrong error!"),
+        let data = AnvilLevelInfo.read_world_info(&level_folder).unwrap();
+        assert_eq!(data.data_version, 3463);
+        assert_eq!(data.level_name, "SKYBLOCK 1.20");
+    }
+
+    /// Writes a level.dat with only the given level data, like a very old version would
+    fn write_raw_level_dat(level_folder: &LevelFolder, data: NbtCompound) {
+        let mut root = NbtCompound::new();
+        root.put_component("Data", data);
+        let file = fs::File::create(level_folder.root_folder.join(LEVEL_DAT_FILE_NAME)).unwrap();
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        encoder
+            .write_all(&Nbt::new(String::new(), root).write())
+            .unwrap();
+        encoder.finish().unwrap();
+    }
+
+    #[test]
+    fn missing_fields_and_legacy_seed() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_folder = LevelFolder {
+            root_folder: temp_dir.path().to_path_buf(),
+            region_folder: temp_dir.path().join("region"),
+        };
+
+        // A level.dat of 1.15, without the fields newer versions added
+        let mut data = NbtCompound::new();
+        data.put_int("DataVersion", 2230);
+        data.put_long("RandomSeed", 42);
+        data.put("LevelName", NbtTag::String("Old World".to_string()));
+        data.put_int("SpawnX", 16);
+        write_raw_level_dat(&level_folder, data);
+
+        let data = AnvilLevelInfo.read_world_info(&level_folder).unwrap();
+        assert_eq!(data.world_gen_settings.seed, 42);
+        assert_eq!(data.level_name, "Old World");
+        assert_eq!(data.spawn_x, 16);
+        assert_eq!(data.border_size, LevelData::default().border_size);
+        assert_eq!(data.game_rules.get_bool("doDaylightCycle"), Some(true));
+    }
+
+    #[test]
+    fn failed_deserialize_pre_flattening_level_dat() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_folder = LevelFolder {
+            root_folder: temp_dir.path().to_path_buf(),
+            region_folder: temp_dir.path().join("region"),
+        };
+
+        // 1.12.2 still used numeric block ids
+        let mut data = NbtCompound::new();
+        data.put_int("DataVersion", 1343);
+        write_raw_level_dat(&level_folder, data);
+
+        match AnvilLevelInfo.read_world_info(&level_folder) {
+            Err(WorldInfoError::UnsupportedVersion(1343)) => {}
+            _ => panic!("Pre flattening worlds should not be read"),
         }
     }
 }
@@ -1,13 +1,14 @@
-use pumpkin_config::BASIC_CONFIG;
-use pumpkin_util::Difficulty;
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use pumpkin_config::{WorldConfig, BASIC_CONFIG};
+use serde::{Deserialize, Deserializer, Serialize};
 use thiserror::Error;
 
 use crate::{generation::Seed, level::LevelFolder};
 
 pub mod anvil;
 
-pub const MINIMUM_SUPPORTED_WORLD_DATA_VERSION: i32 = 4080; // 1.21.2
+pub const MINIMUM_SUPPORTED_WORLD_DATA_VERSION: i32 = 1451; // 17w47a, the first version with block names
 pub const MAXIMUM_SUPPORTED_WORLD_DATA_VERSION: i32 = 4189; // 1.21.4
 
 pub(crate) trait WorldInfoReader {
@@ -22,8 +23,9 @@ pub(crate) trait WorldInfoWriter: Sync + Send {
     ) -> Result<(), WorldInfoError>;
 }
 
+/// Fields which older versions did not save yet get their default value
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-#[serde(rename_all = "PascalCase")]
+#[serde(rename_all = "PascalCase", default)]
 pub struct LevelData {
     // true if cheats are enabled.
     #[serde(rename = "allowCommands")]
@@ -69,6 +71,12 @@ pub struct LevelData {
     pub last_played: i64,
     // The name of the level.
     pub level_name: String,
+    // true if the level is currently experiencing rain, snow, and cloud cover.
+    #[serde(rename = "raining", default)]
+    pub raining: bool,
+    // The number of ticks before "raining" is toggled and this value gets set to another random value.
+    #[serde(rename = "rainTime", default)]
+    pub rain_time: i32,
     // The X coordinate of the world spawn.
     pub spawn_x: i32,
     // The Y coordinate of the world spawn.
@@ -77,14 +85,148 @@ pub struct LevelData {
     pub spawn_z: i32,
     // The Yaw rotation of the world spawn.
     pub spawn_angle: f32,
+    // true if the rain/snow/cloud cover is a lightning storm and dark enough for mobs to spawn under the sky.
+    #[serde(rename = "thundering", default)]
+    pub thundering: bool,
+    // The number of ticks before "thundering" is toggled and this value gets set to another random value.
+    #[serde(rename = "thunderTime", default)]
+    pub thunder_time: i32,
     #[serde(rename = "version")]
     // The NBT version of the level
     pub nbt_version: i32,
     #[serde(rename = "Version")]
     pub version: WorldVersion,
+    // The game rules of the world.
+    pub game_rules: GameRules,
+    // The number of ticks since the start of the level.
+    pub time: i64,
     // TODO: Implement the rest of the fields
 }
 
+/// The game rules of vanilla 1.21.4 with their default values
+const DEFAULT_GAME_RULES: [(&str, &str); 52] = [
+    ("announceAdvancements", "true"),
+    ("blockExplosionDropDecay", "true"),
+    ("commandBlockOutput", "true"),
+    ("commandModificationBlockLimit", "32768"),
+    ("disableElytraMovementCheck", "false"),
+    ("disablePlayerMovementCheck", "false"),
+    ("disableRaids", "false"),
+    ("doDaylightCycle", "true"),
+    ("doEntityDrops", "true"),
+    ("doFireTick", "true"),
+    ("doImmediateRespawn", "false"),
+    ("doInsomnia", "true"),
+    ("doLimitedCrafting", "false"),
+    ("doMobLoot", "true"),
+    ("doMobSpawning", "true"),
+    ("doPatrolSpawning", "true"),
+    ("doTileDrops", "true"),
+    ("doTraderSpawning", "true"),
+    ("doVinesSpread", "true"),
+    ("doWardenSpawning", "true"),
+    ("doWeatherCycle", "true"),
+    ("drowningDamage", "true"),
+    ("enderPearlsVanishOnDeath", "true"),
+    ("fallDamage", "true"),
+    ("fireDamage", "true"),
+    ("forgiveDeadPlayers", "true"),
+    ("freezeDamage", "true"),
+    ("globalSoundEvents", "true"),
+    ("keepInventory", "false"),
+    ("lavaSourceConversion", "false"),
+    ("logAdminCommands", "true"),
+    ("maxCommandChainLength", "65536"),
+    ("maxCommandForkCount", "65536"),
+    ("maxEntityCramming", "24"),
+    ("mobExplosionDropDecay", "true"),
+    ("mobGriefing", "true"),
+    ("naturalRegeneration", "true"),
+    ("playersNetherPortalCreativeDelay", "0"),
+    ("playersNetherPortalDefaultDelay", "80"),
+    ("playersSleepingPercentage", "100"),
+    ("projectilesCanBreakBlocks", "true"),
+    ("randomTickSpeed", "3"),
+    ("reducedDebugInfo", "false"),
+    ("sendCommandFeedback", "true"),
+    ("showDeathMessages", "true"),
+    ("snowAccumulationHeight", "1"),
+    ("spawnChunkRadius", "2"),
+    ("spawnRadius", "10"),
+    ("spectatorsGenerateChunks", "true"),
+    ("tntExplosionDropDecay", "false"),
+    ("universalAnger", "false"),
+    ("waterSourceConversion", "true"),
+];
+
+/// The game rules of a level, which vanilla saves as strings. Rules which are missing, e.g.
+/// because they were added after the level was saved, get their default value. Rules Pumpkin
+/// does not know are kept as they are.
+#[derive(Serialize, Clone, PartialEq, Debug)]
+#[serde(transparent)]
+pub struct GameRules(BTreeMap<String, String>);
+
+impl GameRules {
+    /// The vanilla game rules, with the ones which are set in the config. New worlds start with
+    /// these, so vanilla behaves the same when it opens the world.
+    #[must_use]
+    pub fn from_config(config: &WorldConfig) -> Self {
+        let mut rules = Self::default();
+        rules.set("randomTickSpeed", config.random_tick_speed);
+        rules.set("doFireTick", config.do_fire_tick);
+        rules.set("doMobSpawning", config.do_mob_spawning);
+        rules.set("doDaylightCycle", config.do_daylight_cycle);
+        rules.set("doWeatherCycle", config.do_weather_cycle);
+        rules.set("snowAccumulationHeight", config.snow_accumulation_height);
+        rules.set(
+            "playersSleepingPercentage",
+            config.players_sleeping_percentage,
+        );
+        rules.set("spawnChunkRadius", config.spawn_chunk_radius);
+        rules
+    }
+
+    #[must_use]
+    pub fn get(&self, rule: &str) -> Option<&str> {
+        self.0.get(rule).map(String::as_str)
+    }
+
+    #[must_use]
+    pub fn get_bool(&self, rule: &str) -> Option<bool> {
+        self.get(rule)?.parse().ok()
+    }
+
+    #[must_use]
+    pub fn get_int(&self, rule: &str) -> Option<i32> {
+        self.get(rule)?.parse().ok()
+    }
+
+    pub fn set(&mut self, rule: &str, value: impl ToString) {
+        self.0.insert(rule.to_string(), value.to_string());
+    }
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self(
+            DEFAULT_GAME_RULES
+                .iter()
+                .map(|(rule, value)| ((*rule).to_string(), (*value).to_string()))
+                .collect(),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for GameRules {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut rules = Self::default();
+        rules
+            .0
+            .extend(BTreeMap::<String, String>::deserialize(deserializer)?);
+        Ok(rules)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct WorldGenSettings {
     // the numerical seed of the world
@@ -114,7 +256,7 @@ impl Default for WorldGenSettings {
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-#[serde(rename_all = "PascalCase")]
+#[serde(rename_all = "PascalCase", default)]
 pub struct WorldVersion {
     // The version name as a string, e.g. "15w32b".
     pub name: String,
@@ -129,8 +271,8 @@ pub struct WorldVersion {
 impl Default for WorldVersion {
     fn default() -> Self {
         Self {
-            name: "1.24.4".to_string(),
-            id: -1,
+            name: "1.21.4".to_string(),
+            id: MAXIMUM_SUPPORTED_WORLD_DATA_VERSION,
             snapshot: false,
             series: "main".to_string(),
         }
@@ -144,9 +286,9 @@ impl Default for LevelData {
             border_center_x: 0.0,
             border_center_z: 0.0,
             border_damage_per_block: 0.2,
-            border_size: 60_000_000.0,
+            border_size: 59_999_968.0,
             border_safe_zone: 5.0,
-            border_size_lerp_target: 60_000_000.0,
+            border_size_lerp_target: 59_999_968.0,
             border_size_lerp_time: 0,
             border_warning_blocks: 5.0,
             border_warning_time: 15.0,
@@ -157,17 +299,23 @@ impl Default for LevelData {
             },
             data_version: MAXIMUM_SUPPORTED_WORLD_DATA_VERSION,
             day_time: 0,
-            difficulty: Difficulty::Normal as i8,
+            difficulty: BASIC_CONFIG.default_difficulty.clone() as i8,
             difficulty_locked: false,
             world_gen_settings: Default::default(),
             last_played: -1,
             level_name: "world".to_string(),
+            raining: false,
+            rain_time: 0,
             spawn_x: 0,
             spawn_y: 200,
             spawn_z: 0,
             spawn_angle: 0.0,
+            thundering: false,
+            thunder_time: 0,
             nbt_version: -1,
             version: Default::default(),
+            game_rules: GameRules::default(),
+            time: 0,
         }
     }
 }
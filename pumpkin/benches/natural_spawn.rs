@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pumpkin::world::natural_spawn::pick_start;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_world::generation::{get_world_gen, Seed, DEFAULT_GENERATOR};
+use rand::thread_rng;
+
+/// The spawnable chunks around a single player, in which natural spawning picks a start each tick
+const SPAWNABLE_CHUNKS: usize = 17 * 17;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let generator = get_world_gen(DEFAULT_GENERATOR, Seed(0)).unwrap();
+    let chunks: Vec<_> = (0..4)
+        .flat_map(|x| (0..4).map(move |z| Vector2::new(x, z)))
+        .map(|at| generator.generate_chunk(at))
+        .collect();
+    let mut rng = thread_rng();
+
+    c.bench_function("natural spawn starts", |b| {
+        b.iter(|| {
+            for chunk in chunks.iter().cycle().take(SPAWNABLE_CHUNKS) {
+                black_box(pick_start(chunk, &mut rng));
+            }
+        });
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,272 @@
+use async_trait::async_trait;
+use pumpkin_data::{damage::DamageType, entity::SpawnGroup, item::Item};
+use pumpkin_registry::DimensionType;
+use pumpkin_util::{
+    math::{position::BlockPos, vector3::Vector3},
+    text::TextComponent,
+    GameMode,
+};
+use pumpkin_world::block::registry::{
+    get_block_by_state_id, get_state_id_with_property, get_state_property, Block,
+};
+
+use crate::{
+    block::{pumpkin_block::PumpkinBlock, registry::BlockActionResult},
+    entity::player::{Player, RespawnPoint, TitleMode},
+    server::Server,
+    world::{explosion, World},
+};
+
+/// The beds of all colors
+pub const BEDS: [&str; 16] = [
+    "white_bed",
+    "orange_bed",
+    "magenta_bed",
+    "light_blue_bed",
+    "yellow_bed",
+    "lime_bed",
+    "pink_bed",
+    "gray_bed",
+    "light_gray_bed",
+    "cyan_bed",
+    "purple_bed",
+    "blue_bed",
+    "brown_bed",
+    "black_bed",
+    "green_bed",
+    "red_bed",
+];
+
+/// Like vanilla, players have to be within this many blocks horizontally of the bed to sleep
+const MAX_HORIZONTAL_DISTANCE: f64 = 3.0;
+/// Like vanilla, players have to be within this many blocks vertically of the bed to sleep
+const MAX_VERTICAL_DISTANCE: f64 = 2.0;
+
+/// Like in vanilla, beds used outside of the overworld explode with this power
+const EXPLOSION_POWER: f32 = 5.0;
+
+/// Monsters this many blocks around the bed horizontally keep players awake
+const MONSTER_HORIZONTAL_RANGE: f64 = 8.0;
+/// Monsters this many blocks around the bed vertically keep players awake
+const MONSTER_VERTICAL_RANGE: f64 = 5.0;
+
+pub struct BedBlock;
+
+impl BedBlock {
+    async fn sleep(player: &Player, location: BlockPos, server: &Server) {
+        if player.living_entity.is_sleeping() {
+            return;
+        }
+        let world = player.world().await;
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let Some(head) = head_position(state_id, location) else {
+            return;
+        };
+        if !is_overworld(&world) {
+            Self::explode(server, &world, location, head).await;
+            return;
+        }
+        let Ok(head_state_id) = world.get_block_state_id(&head).await else {
+            return;
+        };
+
+        if let Some(error) = Self::reach_error(player, &world, head, head_state_id).await {
+            Self::show_error(player, error).await;
+            return;
+        }
+        // Like in vanilla, the respawn point is set even if the player can't sleep right now
+        player
+            .set_respawn_point(RespawnPoint {
+                world: world.name(),
+                position: head,
+                angle: player.living_entity.entity.yaw.load(),
+            })
+            .await;
+        if let Some(error) = Self::sleep_error(player, &world, head).await {
+            Self::show_error(player, error).await;
+            return;
+        }
+        player.living_entity.start_sleeping(head).await;
+    }
+
+    async fn show_error(player: &Player, error: &str) {
+        player
+            .show_title(&TextComponent::translate(error, []), &TitleMode::ActionBar)
+            .await;
+    }
+
+    /// Like in vanilla, beds explode outside of the overworld instead of setting the respawn
+    /// point
+    async fn explode(server: &Server, world: &World, location: BlockPos, head: BlockPos) {
+        let Some(world) = server.get_world_handle(world).await else {
+            return;
+        };
+        world.set_block_state(&location, 0).await;
+        world.set_block_state(&head, 0).await;
+        world.update_neighbors(server, &location).await;
+        world.update_neighbors(server, &head).await;
+
+        let center = Vector3::new(
+            f64::from(head.0.x) + 0.5,
+            f64::from(head.0.y) + 0.5,
+            f64::from(head.0.z) + 0.5,
+        );
+        explosion::explode(
+            server,
+            &world,
+            center,
+            EXPLOSION_POWER,
+            true,
+            DamageType::BAD_RESPAWN_POINT,
+        )
+        .await;
+    }
+
+    /// The translation key of the reason the player can't use the bed at all, if there is one
+    async fn reach_error(
+        player: &Player,
+        world: &World,
+        head: BlockPos,
+        head_state_id: u16,
+    ) -> Option<&'static str> {
+        if get_state_property(head_state_id, "occupied") == Some("true") {
+            return Some("block.minecraft.bed.occupied");
+        }
+
+        let position = player.living_entity.entity.pos.load();
+        let center = bed_center(head);
+        if (position.x - center.x).abs() > MAX_HORIZONTAL_DISTANCE
+            || (position.y - center.y).abs() > MAX_VERTICAL_DISTANCE
+            || (position.z - center.z).abs() > MAX_HORIZONTAL_DISTANCE
+        {
+            return Some("block.minecraft.bed.too_far_away");
+        }
+
+        let above = BlockPos(head.0.add_raw(0, 1, 0));
+        if world
+            .get_block_state(&above)
+            .await
+            .is_ok_and(|state| !state.collision_shapes.is_empty())
+        {
+            return Some("block.minecraft.bed.obstructed");
+        }
+        None
+    }
+
+    /// The translation key of the reason the player can't sleep right now, if there is one
+    async fn sleep_error(player: &Player, world: &World, head: BlockPos) -> Option<&'static str> {
+        if !is_sleeping_time(world).await {
+            return Some("block.minecraft.bed.no_sleep");
+        }
+
+        if player.gamemode.load() != GameMode::Creative
+            && monsters_nearby(world, bed_center(head)).await
+        {
+            return Some("block.minecraft.bed.not_safe");
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for BedBlock {
+    async fn normal_use(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        Self::sleep(player, location, server).await;
+    }
+
+    async fn use_with_item(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        server: &Server,
+    ) -> BlockActionResult {
+        Self::sleep(player, location, server).await;
+        BlockActionResult::Consume
+    }
+}
+
+/// The position of the head part of the bed, which is where the player sleeps. The `facing` of
+/// a bed points from its foot to its head.
+fn head_position(state_id: u16, location: BlockPos) -> Option<BlockPos> {
+    if get_state_property(state_id, "part")? == "head" {
+        return Some(location);
+    }
+    let (x, z) = match get_state_property(state_id, "facing")? {
+        "north" => (0, -1),
+        "south" => (0, 1),
+        "west" => (-1, 0),
+        "east" => (1, 0),
+        _ => return None,
+    };
+    Some(BlockPos(location.0.add_raw(x, 0, z)))
+}
+
+fn bed_center(head: BlockPos) -> Vector3<f64> {
+    Vector3::new(
+        f64::from(head.0.x) + 0.5,
+        f64::from(head.0.y),
+        f64::from(head.0.z) + 0.5,
+    )
+}
+
+/// Whether players can set their respawn point with beds in the world, instead of them exploding
+pub fn is_overworld(world: &World) -> bool {
+    matches!(
+        world.dimension_type,
+        DimensionType::Overworld | DimensionType::OverworldCaves
+    )
+}
+
+/// Whether the block state belongs to a bed
+pub fn is_bed(state_id: u16) -> bool {
+    get_block_by_state_id(state_id).is_some_and(|block| BEDS.contains(&block.name.as_str()))
+}
+
+/// Marks the bed at the position as occupied or free, so nobody else can sleep in it
+pub async fn set_occupied(world: &World, head: &BlockPos, occupied: bool) {
+    let Ok(state_id) = world.get_block_state_id(head).await else {
+        return;
+    };
+    if !is_bed(state_id) {
+        return;
+    }
+    if let Some(new_state_id) =
+        get_state_id_with_property(state_id, "occupied", &occupied.to_string())
+    {
+        if new_state_id != state_id {
+            world.set_block_state(head, new_state_id).await;
+        }
+    }
+}
+
+/// Like vanilla, players can sleep at night and during thunderstorms
+async fn is_sleeping_time(world: &World) -> bool {
+    if world.weather.lock().await.thundering {
+        return true;
+    }
+    let time_of_day = world.level_time.lock().await.query_daytime();
+    (12542..23460).contains(&time_of_day)
+}
+
+async fn monsters_nearby(world: &World, center: Vector3<f64>) -> bool {
+    world.entities.read().await.values().any(|entity| {
+        let entity = entity.get_entity();
+        if entity.entity_type.spawn_group != SpawnGroup::Monster {
+            return false;
+        }
+        let position = entity.pos.load();
+        (position.x - center.x).abs() <= MONSTER_HORIZONTAL_RANGE
+            && (position.y - center.y).abs() <= MONSTER_VERTICAL_RANGE
+            && (position.z - center.z).abs() <= MONSTER_HORIZONTAL_RANGE
+    })
+}
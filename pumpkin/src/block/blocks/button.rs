@@ -0,0 +1,177 @@
+use async_trait::async_trait;
+use pumpkin_data::{
+    item::Item,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_protocol::server::play::SUseItemOn;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::{
+    registry::{get_state_id_with_property, get_state_property, Block},
+    BlockDirection,
+};
+
+use crate::{
+    block::{properties::Direction, pumpkin_block::PumpkinBlock, registry::BlockActionResult},
+    entity::player::Player,
+    server::Server,
+    world::World,
+};
+
+use super::redstone;
+
+/// The buttons of all materials
+pub const BUTTONS: [&str; 14] = [
+    "stone_button",
+    "oak_button",
+    "spruce_button",
+    "birch_button",
+    "jungle_button",
+    "acacia_button",
+    "cherry_button",
+    "dark_oak_button",
+    "pale_oak_button",
+    "mangrove_button",
+    "bamboo_button",
+    "crimson_button",
+    "warped_button",
+    "polished_blackstone_button",
+];
+
+/// How many ticks stone buttons stay pressed, like in vanilla
+const STONE_PRESS_TICKS: u64 = 20;
+/// How many ticks wooden buttons stay pressed, like in vanilla
+const WOODEN_PRESS_TICKS: u64 = 30;
+
+fn is_stone(block: &Block) -> bool {
+    matches!(
+        block.name.as_str(),
+        "stone_button" | "polished_blackstone_button"
+    )
+}
+
+/// A button, which powers the blocks around it like a lever for a moment after it was pressed
+pub struct ButtonBlock;
+
+impl ButtonBlock {
+    async fn press(block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        let world = player.world().await;
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        if get_state_property(state_id, "powered") == Some("true") {
+            return;
+        }
+        let delay = if is_stone(block) {
+            STONE_PRESS_TICKS
+        } else {
+            WOODEN_PRESS_TICKS
+        };
+        Self::set_powered(server, &world, block, state_id, location, true).await;
+        world.schedule_block_tick(location, block, delay);
+    }
+
+    async fn set_powered(
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+        powered: bool,
+    ) {
+        let Some(new_state_id) =
+            get_state_id_with_property(state_id, "powered", &powered.to_string())
+        else {
+            return;
+        };
+        world.set_block_state(&location, new_state_id).await;
+        let sound = match (is_stone(block), powered) {
+            (true, true) => Sound::BlockStoneButtonClickOn,
+            (true, false) => Sound::BlockStoneButtonClickOff,
+            (false, true) => Sound::BlockWoodenButtonClickOn,
+            (false, false) => Sound::BlockWoodenButtonClickOff,
+        };
+        world
+            .play_block_sound(sound, SoundCategory::Blocks, location)
+            .await;
+        redstone::update_around_source(
+            server,
+            world,
+            &location,
+            redstone::attached_direction(state_id),
+        )
+        .await;
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for ButtonBlock {
+    async fn on_place(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        face: &BlockDirection,
+        block_pos: &BlockPos,
+        use_item_on: &SUseItemOn,
+        player_direction: &Direction,
+        other: bool,
+    ) -> u16 {
+        // Buttons are placed like levers, facing away from the block they are on
+        let face = match face {
+            BlockDirection::Bottom | BlockDirection::Top => *face,
+            _ => face.opposite(),
+        };
+
+        server
+            .block_properties_manager
+            .on_place_state(
+                world,
+                block,
+                &face,
+                block_pos,
+                use_item_on,
+                player_direction,
+                other,
+            )
+            .await
+    }
+
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        Self::press(block, player, location, server).await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        server: &Server,
+    ) -> BlockActionResult {
+        Self::press(block, player, location, server).await;
+        BlockActionResult::Consume
+    }
+
+    async fn on_scheduled_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        if get_state_property(state_id, "powered") == Some("true") {
+            Self::set_powered(server, world, block, state_id, location, false).await;
+        }
+    }
+
+    async fn broken(&self, _block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        redstone::update_around(server, &player.world().await, &location).await;
+    }
+}
@@ -64,6 +64,9 @@ impl PumpkinBlock for ChestBlock {
         container: &mut OpenContainer,
     ) {
         container.remove_player(player.entity_id());
+        if container.get_number_of_players() == 0 {
+            super::save_container_items(&player.world().await, location, container).await;
+        }
 
         self.play_chest_action(container, player, location, server, ChestState::IsClosed)
             .await;
@@ -0,0 +1,292 @@
+//! Access to the items of containers like chests, furnaces and hoppers, for the blocks which move
+//! items between them. While players have a container open, the open container holds its items,
+//! otherwise its block entity does.
+
+use std::collections::HashMap;
+
+use pumpkin_data::item::Item;
+use pumpkin_inventory::OpenContainer;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    block::{
+        entity::furnace::{FUEL_SLOT, INPUT_SLOT, RESULT_SLOT},
+        registry::Block,
+        BlockDirection,
+    },
+    item::ItemStack,
+};
+
+use crate::{server::Server, world::World};
+
+/// The blocks which smelt items, which take and give out items only through some of their faces
+pub const FURNACES: [&str; 3] = ["furnace", "blast_furnace", "smoker"];
+
+/// Whether items can be put into a slot through a face of the container. Furnaces take the items
+/// to smelt from the top and fuel from the other faces.
+///
+/// TODO: Only fuel should be put into the fuel slot of furnaces
+fn can_insert(block: &Block, face: BlockDirection, slot: usize) -> bool {
+    if !FURNACES.contains(&block.name.as_str()) {
+        return true;
+    }
+    if face == BlockDirection::Top {
+        slot == INPUT_SLOT
+    } else {
+        slot == FUEL_SLOT
+    }
+}
+
+/// Whether the item in a slot can be taken through a face of the container. Like in vanilla,
+/// furnaces give out the smelted items and emptied buckets through the bottom.
+fn can_extract(block: &Block, face: BlockDirection, slot: usize, stack: &ItemStack) -> bool {
+    if !FURNACES.contains(&block.name.as_str()) {
+        return true;
+    }
+    match face {
+        BlockDirection::Bottom => {
+            slot == RESULT_SLOT || (slot == FUEL_SLOT && stack.item.id == Item::BUCKET.id)
+        }
+        BlockDirection::Top => slot == INPUT_SLOT,
+        _ => slot == FUEL_SLOT,
+    }
+}
+
+/// Puts as much of the stack into the slots as fits, in the order of the slots, onto stacks of
+/// the same item or into empty slots. Returns the items which did not fit.
+pub fn add_to_slots(
+    slots: &mut [&mut Option<ItemStack>],
+    can_insert: impl Fn(usize) -> bool,
+    mut stack: ItemStack,
+) -> Option<ItemStack> {
+    let max_stack_size = stack.item.components.max_stack_size;
+    for (index, slot) in slots.iter_mut().enumerate() {
+        if stack.item_count == 0 {
+            break;
+        }
+        if !can_insert(index) {
+            continue;
+        }
+        match slot {
+            Some(existing) if *existing == stack => {
+                let moved = max_stack_size
+                    .saturating_sub(existing.item_count)
+                    .min(stack.item_count);
+                existing.item_count += moved;
+                stack.item_count -= moved;
+            }
+            Some(_) => {}
+            None => {
+                let mut moved = stack.clone();
+                moved.item_count = stack.item_count.min(max_stack_size);
+                stack.item_count -= moved.item_count;
+                **slot = Some(moved);
+            }
+        }
+    }
+    (stack.item_count > 0).then_some(stack)
+}
+
+/// The container open at the position which players use. Only blocks with a block entity which
+/// holds their items count, not e.g. crafting tables.
+async fn used_container<'a>(
+    open_containers: &'a HashMap<u64, OpenContainer>,
+    world: &World,
+    location: &BlockPos,
+) -> Option<&'a OpenContainer> {
+    let open_container = open_containers.values().find(|container| {
+        container.is_location(*location) && container.get_number_of_players() > 0
+    })?;
+    world
+        .get_block_entity(location, |block_entity| block_entity.slots().is_some())
+        .await
+        .unwrap_or(false)
+        .then_some(open_container)
+}
+
+/// Changes the slots of the container at the position. Returns `None` if there is no container
+/// there or its chunk is not loaded.
+pub async fn update_slots<R: Send>(
+    server: &Server,
+    world: &World,
+    location: &BlockPos,
+    update: impl FnOnce(&mut [&mut Option<ItemStack>]) -> R + Send,
+) -> Option<R> {
+    if !world.is_loaded(location) {
+        return None;
+    }
+    // Players can't open the container until it was changed, so the change is not lost
+    let open_containers = server.open_containers.read().await;
+    let Some(open_container) = used_container(&open_containers, world, location).await else {
+        return world
+            .update_block_entity(location, |block_entity| {
+                block_entity.unpack_loot();
+                let mut slots: Vec<_> = block_entity.slots_mut()?.iter_mut().collect();
+                Some(update(&mut slots))
+            })
+            .await
+            .flatten();
+    };
+    let result = open_container.update_items(update).await;
+    let player_ids = open_container.all_player_ids();
+    drop(open_containers);
+
+    for id in player_ids {
+        if let Some(player) = world.get_player_by_id(id).await {
+            let container = player.get_open_container(server).await;
+            let mut container = match container.as_ref() {
+                Some(container) => Some(container.lock().await),
+                None => None,
+            };
+            player.set_container_content(container.as_deref_mut()).await;
+        }
+    }
+    Some(result)
+}
+
+/// A copy of the items of the container at the position
+pub async fn get_slots(
+    server: &Server,
+    world: &World,
+    location: &BlockPos,
+) -> Option<Vec<Option<ItemStack>>> {
+    if !world.is_loaded(location) {
+        return None;
+    }
+    let open_containers = server.open_containers.read().await;
+    if let Some(open_container) = used_container(&open_containers, world, location).await {
+        return Some(open_container.get_items().await);
+    }
+    world
+        .get_block_entity(location, |block_entity| {
+            block_entity.slots().map(<[_]>::to_vec)
+        })
+        .await
+        .flatten()
+}
+
+/// Whether the whole stack fits into the container at the position through one of its faces
+pub async fn fits(
+    server: &Server,
+    world: &World,
+    location: &BlockPos,
+    face: BlockDirection,
+    stack: &ItemStack,
+) -> bool {
+    let Ok(block) = world.get_block(location).await else {
+        return false;
+    };
+    let Some(mut slots) = get_slots(server, world, location).await else {
+        return false;
+    };
+    let mut slots: Vec<_> = slots.iter_mut().collect();
+    add_to_slots(
+        &mut slots,
+        |slot| can_insert(block, face, slot),
+        stack.clone(),
+    )
+    .is_none()
+}
+
+/// Puts the stack into the container at the position through one of its faces. Returns the
+/// items which did not fit, which are all of them if there is no container.
+pub async fn insert(
+    server: &Server,
+    world: &World,
+    location: &BlockPos,
+    face: BlockDirection,
+    stack: ItemStack,
+) -> Option<ItemStack> {
+    let Ok(block) = world.get_block(location).await else {
+        return Some(stack);
+    };
+    let unchanged = stack.clone();
+    update_slots(server, world, location, |slots| {
+        add_to_slots(slots, |slot| can_insert(block, face, slot), stack)
+    })
+    .await
+    .unwrap_or(Some(unchanged))
+}
+
+/// Takes one item out of the container at the position through one of its faces, from the first
+/// slot whose item `accept` accepts.
+pub async fn take_one(
+    server: &Server,
+    world: &World,
+    location: &BlockPos,
+    face: BlockDirection,
+    accept: impl Fn(&ItemStack) -> bool + Send,
+) -> Option<ItemStack> {
+    let block = world.get_block(location).await.ok()?;
+    update_slots(server, world, location, |slots| {
+        let index = (0..slots.len()).find(|&index| {
+            slots[index]
+                .as_ref()
+                .is_some_and(|stack| can_extract(block, face, index, stack) && accept(stack))
+        })?;
+        let slot = &mut *slots[index];
+        let stack = slot.as_mut()?;
+        let mut taken = stack.clone();
+        taken.item_count = 1;
+        stack.item_count -= 1;
+        if stack.item_count == 0 {
+            *slot = None;
+        }
+        Some(taken)
+    })
+    .await
+    .flatten()
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::block::registry::get_block;
+
+    use super::*;
+
+    #[test]
+    fn stacks_are_merged_before_the_rest_is_returned() {
+        let mut first = Some(ItemStack::new(60, Item::STONE));
+        let mut second = None;
+        let mut third = Some(ItemStack::new(1, Item::DIRT));
+        let mut slots = [&mut first, &mut second, &mut third];
+
+        let rest = add_to_slots(&mut slots, |_| true, ItemStack::new(10, Item::STONE));
+        assert!(rest.is_none());
+        assert_eq!(first.as_ref().map(|stack| stack.item_count), Some(64));
+        assert_eq!(second.as_ref().map(|stack| stack.item_count), Some(6));
+
+        let mut slots = [&mut first, &mut second, &mut third];
+        let rest = add_to_slots(&mut slots, |slot| slot != 1, ItemStack::new(3, Item::STONE));
+        assert_eq!(rest.map(|stack| stack.item_count), Some(3));
+    }
+
+    #[test]
+    fn furnaces_use_their_faces() {
+        let furnace = get_block("minecraft:furnace").unwrap();
+        assert!(can_insert(furnace, BlockDirection::Top, INPUT_SLOT));
+        assert!(can_insert(furnace, BlockDirection::North, FUEL_SLOT));
+        assert!(!can_insert(furnace, BlockDirection::Top, RESULT_SLOT));
+
+        let result = ItemStack::new(1, Item::IRON_INGOT);
+        assert!(can_extract(
+            furnace,
+            BlockDirection::Bottom,
+            RESULT_SLOT,
+            &result
+        ));
+        let coal = ItemStack::new(1, Item::COAL);
+        assert!(!can_extract(
+            furnace,
+            BlockDirection::Bottom,
+            FUEL_SLOT,
+            &coal
+        ));
+        let bucket = ItemStack::new(1, Item::BUCKET);
+        assert!(can_extract(
+            furnace,
+            BlockDirection::Bottom,
+            FUEL_SLOT,
+            &bucket
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::registry::Block;
+use rand::{thread_rng, Rng};
+
+use crate::{block::pumpkin_block::PumpkinBlock, server::Server, world::World};
+
+use super::{get_int_property, with_int_property};
+
+/// The crops growing on farmland
+pub const CROPS: [&str; 4] = ["wheat", "carrots", "potatoes", "beetroots"];
+
+/// Crops need at least this much light to grow
+const MIN_LIGHT: u8 = 9;
+
+pub struct CropBlock;
+
+impl CropBlock {
+    fn max_age(block: &Block) -> u32 {
+        if block.name == "beetroots" {
+            3
+        } else {
+            7
+        }
+    }
+
+    /// How fast the crop grows, like vanilla it is faster on moist farmland and slower if the
+    /// same crop is planted next to it in both directions or diagonally
+    async fn growth_speed(world: &World, block: &Block, location: &BlockPos) -> f32 {
+        let mut speed = 1.0;
+        for x in -1..=1 {
+            for z in -1..=1 {
+                let below = BlockPos(location.0.add_raw(x, -1, z));
+                let Ok(state_id) = world.get_block_state_id(&below).await else {
+                    continue;
+                };
+                let mut farmland_speed = match get_int_property(state_id, "moisture") {
+                    Some(0) => 1.0,
+                    Some(_) => 3.0,
+                    None => 0.0,
+                };
+                if x != 0 || z != 0 {
+                    farmland_speed /= 4.0;
+                }
+                speed += farmland_speed;
+            }
+        }
+
+        let north = Self::is_same(world, block, location, 0, -1).await;
+        let south = Self::is_same(world, block, location, 0, 1).await;
+        let west = Self::is_same(world, block, location, -1, 0).await;
+        let east = Self::is_same(world, block, location, 1, 0).await;
+        let mut crowded = (west || east) && (north || south);
+        for (x, z) in [(-1, -1), (1, -1), (1, 1), (-1, 1)] {
+            crowded = crowded || Self::is_same(world, block, location, x, z).await;
+        }
+        if crowded {
+            speed /= 2.0;
+        }
+        speed
+    }
+
+    async fn is_same(world: &World, block: &Block, location: &BlockPos, x: i32, z: i32) -> bool {
+        world
+            .get_block(&BlockPos(location.0.add_raw(x, 0, z)))
+            .await
+            .is_ok_and(|other| other.id == block.id)
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for CropBlock {
+    fn has_random_ticks(&self) -> bool {
+        true
+    }
+
+    async fn random_tick(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let Some(age) = get_int_property(state_id, "age") else {
+            return;
+        };
+        if age >= Self::max_age(block) || world.get_brightness(&location).await < MIN_LIGHT {
+            return;
+        }
+
+        let speed = Self::growth_speed(world, block, &location).await;
+        let grows = {
+            let mut rng = thread_rng();
+            // Beetroots only get a third of the random ticks
+            (block.name != "beetroots" || rng.gen_range(0..3) == 0)
+                && rng.gen_range(0..=(25.0 / speed) as u32) == 0
+        };
+        if !grows {
+            return;
+        }
+
+        if let Some(grown) = with_int_property(state_id, "age", age + 1) {
+            world.set_block_state(&location, grown).await;
+        }
+    }
+
+    async fn can_fertilize(
+        &self,
+        _world: &World,
+        block: &Block,
+        state_id: u16,
+        _location: BlockPos,
+    ) -> bool {
+        get_int_property(state_id, "age").is_some_and(|age| age < Self::max_age(block))
+    }
+
+    /// Bone meal lets the crop grow 2 to 5 stages, beetroots only a third of that
+    async fn fertilize(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let Some(age) = get_int_property(state_id, "age") else {
+            return;
+        };
+        let mut increase = thread_rng().gen_range(2..=5);
+        if block.name == "beetroots" {
+            increase /= 3;
+        }
+        let age = (age + increase).min(Self::max_age(block));
+        if let Some(grown) = with_int_property(state_id, "age", age) {
+            world.set_block_state(&location, grown).await;
+        }
+    }
+}
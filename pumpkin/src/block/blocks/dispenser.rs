@@ -0,0 +1,454 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_data::{
+    entity::EntityType,
+    item::Item,
+    screen::WindowType,
+    sound::{Sound, SoundCategory},
+    world::WorldEvent,
+};
+use pumpkin_inventory::{Dispenser, Dropper, OpenContainer};
+use pumpkin_registry::DimensionType;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{
+    block::{
+        registry::{
+            get_block_and_state_by_state_id, get_state_id_with_property, get_state_property, Block,
+        },
+        BlockDirection,
+    },
+    item::ItemStack,
+};
+use rand::Rng;
+
+use crate::{
+    block::{pumpkin_block::PumpkinBlock, registry::BlockActionResult},
+    entity::{
+        item::ItemEntity,
+        player::Player,
+        projectile::arrow::{ArrowEntity, ArrowPickup},
+    },
+    server::Server,
+    world::World,
+};
+
+use super::{
+    container,
+    fluid::{place_fluid, Fluid, FluidState},
+    piston::PistonBlock,
+    redstone,
+};
+
+/// The dispensers, which shoot or use some items, and the droppers, which only drop them or put
+/// them into the container they face
+pub const DISPENSERS: [&str; 2] = ["dispenser", "dropper"];
+
+/// How many ticks a dispenser waits after it was powered, like in vanilla
+const DISPENSE_DELAY: u64 = 4;
+
+/// How fast and how precise dispensers shoot arrows, like in vanilla
+const ARROW_POWER: f64 = 1.1;
+const ARROW_UNCERTAINTY: f64 = 6.0;
+
+fn facing(state_id: u16) -> BlockDirection {
+    get_state_property(state_id, "facing")
+        .and_then(redstone::direction_from_name)
+        .unwrap_or(BlockDirection::North)
+}
+
+/// Where items leave the dispenser, just in front of the face it faces with
+fn dispense_position(location: &BlockPos, facing: BlockDirection) -> Vector3<f64> {
+    let offset = facing.to_offset();
+    Vector3::new(
+        f64::from(location.0.x) + 0.5 + 0.7 * f64::from(offset.x),
+        f64::from(location.0.y) + 0.5 + 0.7 * f64::from(offset.y),
+        f64::from(location.0.z) + 0.5 + 0.7 * f64::from(offset.z),
+    )
+}
+
+/// Whether a fluid can be placed at the position, replacing the block there
+async fn is_replaceable(world: &World, position: &BlockPos) -> bool {
+    redstone::state_id_at(world, position)
+        .await
+        .and_then(get_block_and_state_by_state_id)
+        .is_some_and(|(_, state)| state.air || state.replaceable)
+}
+
+/// A dispenser or dropper. Each time it gets powered, it dispenses one item of a random slot.
+pub struct DispenserBlock;
+
+impl DispenserBlock {
+    async fn open(block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        if block.name == "dropper" {
+            super::standard_open_container::<Dropper>(
+                block,
+                player,
+                location,
+                server,
+                WindowType::Generic3x3,
+            )
+            .await;
+        } else {
+            super::standard_open_container::<Dispenser>(
+                block,
+                player,
+                location,
+                server,
+                WindowType::Generic3x3,
+            )
+            .await;
+        }
+    }
+
+    /// Like pistons, dispensers are also powered like the block above them if
+    /// quasi-connectivity is enabled
+    async fn is_powered(world: &World, location: &BlockPos) -> bool {
+        redstone::is_powered(world, location).await
+            || (ADVANCED_CONFIG.world.quasi_connectivity
+                && redstone::is_powered(world, &redstone::offset(location, BlockDirection::Top))
+                    .await)
+    }
+
+    /// Takes one item out of a random slot which is not empty
+    async fn take_random(
+        server: &Server,
+        world: &World,
+        location: &BlockPos,
+    ) -> Option<(usize, ItemStack)> {
+        container::update_slots(server, world, location, |slots| {
+            let filled: Vec<usize> = (0..slots.len())
+                .filter(|&index| slots[index].is_some())
+                .collect();
+            if filled.is_empty() {
+                return None;
+            }
+            let index = filled[rand::thread_rng().gen_range(0..filled.len())];
+            let slot = &mut *slots[index];
+            let stack = slot.as_mut()?;
+            let mut taken = stack.clone();
+            taken.item_count = 1;
+            stack.item_count -= 1;
+            if stack.item_count == 0 {
+                *slot = None;
+            }
+            Some((index, taken))
+        })
+        .await
+        .flatten()
+    }
+
+    /// Puts an item back into the dispenser, into the slot it came from if that is empty, like
+    /// an emptied bucket. Items which don't fit are dropped.
+    async fn give_back(
+        server: &Server,
+        world: &World,
+        location: &BlockPos,
+        facing: BlockDirection,
+        slot: usize,
+        stack: ItemStack,
+    ) {
+        let unchanged = stack.clone();
+        let rest = container::update_slots(server, world, location, |slots| {
+            if slots.get(slot).is_some_and(|empty| empty.is_none()) {
+                *slots[slot] = Some(stack);
+                return None;
+            }
+            container::add_to_slots(slots, |_| true, stack)
+        })
+        .await
+        .unwrap_or(Some(unchanged));
+        if let Some(rest) = rest {
+            Self::eject(server, world, location, facing, &rest).await;
+        }
+    }
+
+    /// Drops an item out of the dispenser, flying a bit in the direction it faces
+    async fn eject(
+        server: &Server,
+        world: &World,
+        location: &BlockPos,
+        facing: BlockDirection,
+        stack: &ItemStack,
+    ) {
+        let Some(world) = server.get_world_handle(world).await else {
+            return;
+        };
+        let mut position = dispense_position(location, facing);
+        // Items are spawned at their bottom, so they leave the dispenser at its center
+        position.y -= if facing == BlockDirection::Top || facing == BlockDirection::Bottom {
+            0.125
+        } else {
+            0.156_25
+        };
+        let offset = facing.to_offset();
+        let velocity = {
+            let mut rng = rand::thread_rng();
+            let speed = rng.gen_range(0.2..0.3);
+            let mut spread = || (rng.gen::<f64>() - rng.gen::<f64>()) * 0.103_365;
+            Vector3::new(
+                f64::from(offset.x) * speed + spread(),
+                0.2 + spread(),
+                f64::from(offset.z) * speed + spread(),
+            )
+        };
+
+        let entity = server.add_entity(position, EntityType::ITEM, &world);
+        entity.velocity.store(velocity);
+        let item_entity = Arc::new(ItemEntity::new(entity, stack));
+        world.spawn_entity(item_entity.clone()).await;
+        item_entity.send_meta_packet().await;
+    }
+
+    /// Shoots an arrow in the direction the dispenser faces
+    async fn shoot_arrow(
+        server: &Server,
+        world: &World,
+        location: &BlockPos,
+        facing: BlockDirection,
+    ) {
+        let Some(world) = server.get_world_handle(world).await else {
+            return;
+        };
+        let entity = server.add_entity(
+            dispense_position(location, facing),
+            EntityType::ARROW,
+            &world,
+        );
+        let arrow = ArrowEntity::without_owner(entity);
+        arrow.set_pickup(ArrowPickup::Allowed);
+        let offset = facing.to_offset();
+        arrow.projectile.set_velocity(
+            f64::from(offset.x),
+            f64::from(offset.y) + 0.1,
+            f64::from(offset.z),
+            ARROW_POWER,
+            ARROW_UNCERTAINTY,
+        );
+        world.spawn_entity(Arc::new(arrow)).await;
+    }
+
+    /// Empties a bucket in front of the dispenser. Returns whether the fluid could be placed.
+    async fn empty_bucket(server: &Server, world: &World, target: &BlockPos, fluid: Fluid) -> bool {
+        if !is_replaceable(world, target).await {
+            return false;
+        }
+        let sound =
+            if fluid == Fluid::Water && matches!(world.dimension_type, DimensionType::TheNether) {
+                // Water evaporates in the nether
+                Sound::BlockFireExtinguish
+            } else {
+                place_fluid(server, world, target, FluidState::source(fluid)).await;
+                match fluid {
+                    Fluid::Water => Sound::ItemBucketEmpty,
+                    Fluid::Lava => Sound::ItemBucketEmptyLava,
+                }
+            };
+        world
+            .play_block_sound(sound, SoundCategory::Blocks, *target)
+            .await;
+        true
+    }
+
+    /// Fills a bucket with the fluid source in front of the dispenser. Returns the filled bucket.
+    async fn fill_bucket(server: &Server, world: &World, target: &BlockPos) -> Option<Item> {
+        let state_id = redstone::state_id_at(world, target).await?;
+        let fluid = FluidState::from_state_id(state_id).filter(|fluid| fluid.source)?;
+        world.set_block_state(target, 0).await;
+        world.update_neighbors(server, target).await;
+        let (sound, bucket) = match fluid.fluid {
+            Fluid::Water => (Sound::ItemBucketFill, Item::WATER_BUCKET),
+            Fluid::Lava => (Sound::ItemBucketFillLava, Item::LAVA_BUCKET),
+        };
+        world
+            .play_block_sound(sound, SoundCategory::Blocks, *target)
+            .await;
+        Some(bucket)
+    }
+
+    /// Drops the item, like droppers and dispensers do with items they can't use
+    async fn drop_item(
+        server: &Server,
+        world: &World,
+        location: &BlockPos,
+        facing: BlockDirection,
+        stack: &ItemStack,
+    ) {
+        Self::eject(server, world, location, facing, stack).await;
+        world
+            .sync_world_event(WorldEvent::DispenserDispenses, *location, 0)
+            .await;
+        world
+            .sync_world_event(WorldEvent::DispenserActivated, *location, facing as i32)
+            .await;
+    }
+
+    /// Uses the item like a dispenser, or drops it. Returns the item which is left, like an
+    /// emptied bucket.
+    async fn dispense_item(
+        server: &Server,
+        world: &World,
+        location: &BlockPos,
+        facing: BlockDirection,
+        stack: &ItemStack,
+    ) -> Option<ItemStack> {
+        let target = redstone::offset(location, facing);
+        let id = stack.item.id;
+        if id == Item::ARROW.id {
+            Self::shoot_arrow(server, world, location, facing).await;
+            world
+                .sync_world_event(WorldEvent::DispenserLaunchesProjectile, *location, 0)
+                .await;
+            world
+                .sync_world_event(WorldEvent::DispenserActivated, *location, facing as i32)
+                .await;
+            return None;
+        }
+        let fluid = if id == Item::WATER_BUCKET.id {
+            Some(Fluid::Water)
+        } else if id == Item::LAVA_BUCKET.id {
+            Some(Fluid::Lava)
+        } else {
+            None
+        };
+        if let Some(fluid) = fluid {
+            if Self::empty_bucket(server, world, &target, fluid).await {
+                return Some(ItemStack::new(1, Item::BUCKET));
+            }
+        } else if id == Item::BUCKET.id {
+            if let Some(bucket) = Self::fill_bucket(server, world, &target).await {
+                return Some(ItemStack::new(1, bucket));
+            }
+        }
+        Self::drop_item(server, world, location, facing, stack).await;
+        None
+    }
+
+    /// Dispenses one item of a random slot. Droppers put it into the container they face.
+    async fn dispense(
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let facing = facing(state_id);
+        let Some((slot, stack)) = Self::take_random(server, world, &location).await else {
+            world
+                .sync_world_event(WorldEvent::DispenserFails, location, 0)
+                .await;
+            return;
+        };
+
+        let rest = if block.name == "dropper" {
+            let target = redstone::offset(&location, facing);
+            if container::get_slots(server, world, &target).await.is_some() {
+                container::insert(server, world, &target, facing.opposite(), stack).await
+            } else {
+                Self::drop_item(server, world, &location, facing, &stack).await;
+                None
+            }
+        } else {
+            Self::dispense_item(server, world, &location, facing, &stack).await
+        };
+        if let Some(rest) = rest {
+            Self::give_back(server, world, &location, facing, slot, rest).await;
+        }
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for DispenserBlock {
+    async fn placed(&self, _block: &Block, player: &Player, location: BlockPos, _server: &Server) {
+        let world = player.world().await;
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        // Like pistons, dispensers face the player, also up and down
+        let facing = PistonBlock::looking_direction(player).opposite();
+        if let Some(new_state_id) =
+            get_state_id_with_property(state_id, "facing", redstone::direction_name(facing))
+        {
+            world.set_block_state(&location, new_state_id).await;
+        }
+    }
+
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        Self::open(block, player, location, server).await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        server: &Server,
+    ) -> BlockActionResult {
+        Self::open(block, player, location, server).await;
+        BlockActionResult::Consume
+    }
+
+    async fn broken(&self, block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        super::standard_on_broken_with_container(block, player, location, server).await;
+    }
+
+    async fn close(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        container: &mut OpenContainer,
+    ) {
+        container.remove_player(player.entity_id());
+        if container.get_number_of_players() == 0 {
+            super::save_container_items(&player.world().await, location, container).await;
+        }
+    }
+
+    /// Dispensers dispense when they get powered, not while they are powered
+    async fn on_neighbor_update(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        let Some(state_id) = redstone::state_id_at(world, &location).await else {
+            return;
+        };
+        let powered = Self::is_powered(world, &location).await;
+        let triggered = get_state_property(state_id, "triggered") == Some("true");
+        if powered == triggered {
+            return;
+        }
+        if let Some(new_state_id) =
+            get_state_id_with_property(state_id, "triggered", &powered.to_string())
+        {
+            world.set_block_state(&location, new_state_id).await;
+        }
+        if powered {
+            world.schedule_block_tick(location, block, DISPENSE_DELAY);
+        }
+    }
+
+    async fn on_scheduled_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        Self::dispense(server, world, block, state_id, location).await;
+    }
+}
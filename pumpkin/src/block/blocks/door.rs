@@ -0,0 +1,289 @@
+use async_trait::async_trait;
+use pumpkin_data::{
+    item::Item,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::{
+    registry::{get_block_by_state_id, get_state_id_with_property, get_state_property, Block},
+    BlockDirection,
+};
+
+use crate::{
+    block::{pumpkin_block::PumpkinBlock, registry::BlockActionResult},
+    entity::player::Player,
+    server::Server,
+    world::World,
+};
+
+use super::redstone;
+
+/// The doors of all materials
+pub const DOORS: [&str; 21] = [
+    "oak_door",
+    "iron_door",
+    "spruce_door",
+    "birch_door",
+    "jungle_door",
+    "acacia_door",
+    "cherry_door",
+    "dark_oak_door",
+    "pale_oak_door",
+    "mangrove_door",
+    "bamboo_door",
+    "crimson_door",
+    "warped_door",
+    "copper_door",
+    "exposed_copper_door",
+    "oxidized_copper_door",
+    "weathered_copper_door",
+    "waxed_copper_door",
+    "waxed_exposed_copper_door",
+    "waxed_oxidized_copper_door",
+    "waxed_weathered_copper_door",
+];
+
+/// The trapdoors of all materials
+pub const TRAPDOORS: [&str; 21] = [
+    "oak_trapdoor",
+    "spruce_trapdoor",
+    "birch_trapdoor",
+    "jungle_trapdoor",
+    "acacia_trapdoor",
+    "cherry_trapdoor",
+    "dark_oak_trapdoor",
+    "pale_oak_trapdoor",
+    "mangrove_trapdoor",
+    "bamboo_trapdoor",
+    "iron_trapdoor",
+    "crimson_trapdoor",
+    "warped_trapdoor",
+    "copper_trapdoor",
+    "exposed_copper_trapdoor",
+    "oxidized_copper_trapdoor",
+    "weathered_copper_trapdoor",
+    "waxed_copper_trapdoor",
+    "waxed_exposed_copper_trapdoor",
+    "waxed_oxidized_copper_trapdoor",
+    "waxed_weathered_copper_trapdoor",
+];
+
+/// Iron doors and trapdoors only open with redstone power
+fn opens_by_hand(block: &Block) -> bool {
+    !block.name.starts_with("iron_")
+}
+
+fn is_on(state_id: u16, property: &str) -> bool {
+    get_state_property(state_id, property) == Some("true")
+}
+
+/// Changes whether the door or trapdoor at the position is open, and if given, whether it is
+/// powered
+async fn set_state(
+    world: &World,
+    location: &BlockPos,
+    state_id: u16,
+    open: bool,
+    powered: Option<bool>,
+) {
+    let mut new_state_id = get_state_id_with_property(state_id, "open", &open.to_string());
+    if let Some(powered) = powered {
+        new_state_id = new_state_id.and_then(|new_state_id| {
+            get_state_id_with_property(new_state_id, "powered", &powered.to_string())
+        });
+    }
+    if let Some(new_state_id) = new_state_id {
+        if new_state_id != state_id {
+            world.set_block_state(location, new_state_id).await;
+        }
+    }
+}
+
+async fn play_sound(world: &World, block: &Block, location: BlockPos, open: bool, trapdoor: bool) {
+    let name = block.name.as_str();
+    let sound = match (trapdoor, open) {
+        (false, true) if name.starts_with("iron_") => Sound::BlockIronDoorOpen,
+        (false, false) if name.starts_with("iron_") => Sound::BlockIronDoorClose,
+        (false, true) if name.contains("copper") => Sound::BlockCopperDoorOpen,
+        (false, false) if name.contains("copper") => Sound::BlockCopperDoorClose,
+        (false, true) => Sound::BlockWoodenDoorOpen,
+        (false, false) => Sound::BlockWoodenDoorClose,
+        (true, true) if name.starts_with("iron_") => Sound::BlockIronTrapdoorOpen,
+        (true, false) if name.starts_with("iron_") => Sound::BlockIronTrapdoorClose,
+        (true, true) if name.contains("copper") => Sound::BlockCopperTrapdoorOpen,
+        (true, false) if name.contains("copper") => Sound::BlockCopperTrapdoorClose,
+        (true, true) => Sound::BlockWoodenTrapdoorOpen,
+        (true, false) => Sound::BlockWoodenTrapdoorClose,
+    };
+    world
+        .play_block_sound(sound, SoundCategory::Blocks, location)
+        .await;
+}
+
+/// A door, which opens while it is powered. Doors which are not made of iron also open by hand.
+pub struct DoorBlock;
+
+impl DoorBlock {
+    /// The other half of the door, if it is still there
+    async fn other_half(
+        world: &World,
+        state_id: u16,
+        location: &BlockPos,
+    ) -> Option<(BlockPos, u16)> {
+        let direction = if get_state_property(state_id, "half")? == "upper" {
+            BlockDirection::Bottom
+        } else {
+            BlockDirection::Top
+        };
+        let position = redstone::offset(location, direction);
+        let other_state_id = redstone::state_id_at(world, &position).await?;
+        let same_block =
+            get_block_by_state_id(other_state_id)?.id == get_block_by_state_id(state_id)?.id;
+        same_block.then_some((position, other_state_id))
+    }
+
+    async fn set_both_halves(
+        world: &World,
+        location: &BlockPos,
+        state_id: u16,
+        open: bool,
+        powered: Option<bool>,
+    ) {
+        if let Some((other, other_state_id)) = Self::other_half(world, state_id, location).await {
+            set_state(world, &other, other_state_id, open, powered).await;
+        }
+        set_state(world, location, state_id, open, powered).await;
+    }
+
+    async fn toggle(block: &Block, player: &Player, location: BlockPos) {
+        let world = player.world().await;
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let open = !is_on(state_id, "open");
+        Self::set_both_halves(&world, &location, state_id, open, None).await;
+        play_sound(&world, block, location, open, false).await;
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for DoorBlock {
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+    ) {
+        if opens_by_hand(block) {
+            Self::toggle(block, player, location).await;
+        }
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        _server: &Server,
+    ) -> BlockActionResult {
+        if !opens_by_hand(block) {
+            return BlockActionResult::Continue;
+        }
+        Self::toggle(block, player, location).await;
+        BlockActionResult::Consume
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let other = Self::other_half(world, state_id, &location).await;
+        let mut powered = redstone::is_powered(world, &location).await;
+        if let Some((other, _)) = other {
+            powered = powered || redstone::is_powered(world, &other).await;
+        }
+        if powered == is_on(state_id, "powered") {
+            return;
+        }
+        if powered != is_on(state_id, "open") {
+            play_sound(world, block, location, powered, false).await;
+        }
+        Self::set_both_halves(world, &location, state_id, powered, Some(powered)).await;
+    }
+}
+
+/// A trapdoor, which opens while it is powered. Trapdoors which are not made of iron also open
+/// by hand.
+pub struct TrapdoorBlock;
+
+impl TrapdoorBlock {
+    async fn toggle(block: &Block, player: &Player, location: BlockPos) {
+        let world = player.world().await;
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let open = !is_on(state_id, "open");
+        set_state(&world, &location, state_id, open, None).await;
+        play_sound(&world, block, location, open, true).await;
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for TrapdoorBlock {
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+    ) {
+        if opens_by_hand(block) {
+            Self::toggle(block, player, location).await;
+        }
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        _server: &Server,
+    ) -> BlockActionResult {
+        if !opens_by_hand(block) {
+            return BlockActionResult::Continue;
+        }
+        Self::toggle(block, player, location).await;
+        BlockActionResult::Consume
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let powered = redstone::is_powered(world, &location).await;
+        if powered == is_on(state_id, "powered") {
+            return;
+        }
+        if powered != is_on(state_id, "open") {
+            play_sound(world, block, location, powered, true).await;
+        }
+        set_state(world, &location, state_id, powered, Some(powered)).await;
+    }
+}
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::{entity::EntityType, item::Item};
+use pumpkin_protocol::server::play::SUseItemOn;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{
+    block::{
+        registry::{get_block, get_block_and_state_by_state_id, get_block_by_state_id, Block},
+        BlockDirection,
+    },
+    item::ItemStack,
+    WORLD_LOWEST_Y,
+};
+
+use crate::{
+    block::{drop_stack, properties::Direction, pumpkin_block::PumpkinBlock},
+    entity::{falling_block::FallingBlockEntity, player::Player},
+    server::Server,
+    world::World,
+};
+
+/// The blocks which fall down when there is nothing below them. Concrete powder also turns into
+/// concrete when it touches water.
+pub const FALLING_BLOCKS: [&str; 19] = [
+    "sand",
+    "red_sand",
+    "gravel",
+    "white_concrete_powder",
+    "orange_concrete_powder",
+    "magenta_concrete_powder",
+    "light_blue_concrete_powder",
+    "yellow_concrete_powder",
+    "lime_concrete_powder",
+    "pink_concrete_powder",
+    "gray_concrete_powder",
+    "light_gray_concrete_powder",
+    "cyan_concrete_powder",
+    "purple_concrete_powder",
+    "blue_concrete_powder",
+    "brown_concrete_powder",
+    "green_concrete_powder",
+    "red_concrete_powder",
+    "black_concrete_powder",
+];
+
+/// Like in vanilla, blocks start falling two ticks after they lost their support
+const FALL_DELAY: u64 = 2;
+
+pub struct FallingBlock;
+
+/// Whether a falling block falls through the block and can land in its place, like air, water,
+/// fire or grass
+pub fn is_free(state_id: u16) -> bool {
+    get_block_and_state_by_state_id(state_id)
+        .is_some_and(|(_, state)| state.air || state.replaceable)
+}
+
+/// The state of the concrete a concrete powder block turns into in water, or `None` for other
+/// blocks
+pub fn solidified(block: &Block) -> Option<u16> {
+    let color = block.name.strip_suffix("_concrete_powder")?;
+    get_block(&format!("minecraft:{color}_concrete")).map(|concrete| concrete.default_state_id)
+}
+
+pub fn is_water(state_id: u16) -> bool {
+    get_block_by_state_id(state_id).is_some_and(|block| block.name == "water")
+}
+
+/// Whether there is water at the position or next to it, except below
+async fn touches_water(world: &World, position: &BlockPos) -> bool {
+    let mut positions = vec![*position];
+    positions.extend(
+        BlockDirection::all()
+            .into_iter()
+            .filter(|direction| *direction != BlockDirection::Bottom)
+            .map(|direction| BlockPos(position.0 + direction.to_offset())),
+    );
+    for position in positions {
+        if world
+            .get_block_state_id(&position)
+            .await
+            .is_ok_and(is_water)
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Places the block of a falling block entity where it landed. Concrete powder landing in or next
+/// to water becomes concrete. If the place is taken by a block which is not replaceable, like a
+/// torch, the block drops as an item instead.
+pub async fn land(server: &Server, world: &Arc<World>, state_id: u16, position: BlockPos) {
+    let Some(block) = get_block_by_state_id(state_id) else {
+        return;
+    };
+    let free = world.get_block_state_id(&position).await.is_ok_and(is_free);
+    if !free {
+        if let Some(item) = Item::from_id(block.item_id) {
+            drop_stack(server, world, &ItemStack::new(1, item), &position).await;
+        }
+        return;
+    }
+
+    let state_id = match solidified(block) {
+        Some(concrete) if touches_water(world, &position).await => concrete,
+        _ => state_id,
+    };
+    world.set_block_state(&position, state_id).await;
+    world.update_neighbors(server, &position).await;
+}
+
+#[async_trait]
+impl PumpkinBlock for FallingBlock {
+    async fn on_place(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        face: &BlockDirection,
+        block_pos: &BlockPos,
+        use_item_on: &SUseItemOn,
+        player_direction: &Direction,
+        other: bool,
+    ) -> u16 {
+        if let Some(concrete) = solidified(block) {
+            if touches_water(world, block_pos).await {
+                return concrete;
+            }
+        }
+        server
+            .block_properties_manager
+            .on_place_state(
+                world,
+                block,
+                face,
+                block_pos,
+                use_item_on,
+                player_direction,
+                other,
+            )
+            .await
+    }
+
+    async fn placed(&self, block: &Block, player: &Player, location: BlockPos, _server: &Server) {
+        player
+            .world()
+            .await
+            .schedule_block_tick(location, block, FALL_DELAY);
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        if let Some(concrete) = solidified(block) {
+            if touches_water(world, &location).await {
+                world.set_block_state(&location, concrete).await;
+                world.update_neighbors(server, &location).await;
+                return;
+            }
+        }
+        world.schedule_block_tick(location, block, FALL_DELAY);
+    }
+
+    async fn on_scheduled_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        _block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let below = BlockPos(location.0.add_raw(0, -1, 0));
+        if below.0.y < i32::from(WORLD_LOWEST_Y)
+            || !world.get_block_state_id(&below).await.is_ok_and(is_free)
+        {
+            return;
+        }
+        let Some(world) = server.get_world_handle(world).await else {
+            return;
+        };
+
+        let position = Vector3::new(
+            f64::from(location.0.x) + 0.5,
+            f64::from(location.0.y),
+            f64::from(location.0.z) + 0.5,
+        );
+        let entity = server.add_entity(position, EntityType::FALLING_BLOCK, &world);
+        world.set_block_state(&location, 0).await;
+        world
+            .spawn_entity(Arc::new(FallingBlockEntity::new(entity, state_id)))
+            .await;
+        // The blocks on top fall too
+        world.update_neighbors(server, &location).await;
+    }
+}
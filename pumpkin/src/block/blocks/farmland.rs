@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::registry::{get_block, Block};
+
+use crate::{block::pumpkin_block::PumpkinBlock, server::Server, world::World};
+
+use super::{
+    crop::CROPS,
+    fluid::{Fluid, FluidState},
+    get_int_property, with_int_property,
+};
+
+/// The moisture of farmland which is next to water or rained on
+const MAX_MOISTURE: u32 = 7;
+
+/// Water moistens farmland up to this many blocks away horizontally
+const WATER_RANGE: i32 = 4;
+
+/// Farmland, which stays moist next to water and in the rain, so the crops on it grow faster.
+/// Dry farmland without a crop turns back into dirt.
+#[pumpkin_block("minecraft:farmland")]
+pub struct FarmlandBlock;
+
+impl FarmlandBlock {
+    async fn is_near_water(world: &World, location: &BlockPos) -> bool {
+        for x in -WATER_RANGE..=WATER_RANGE {
+            for y in 0..=1 {
+                for z in -WATER_RANGE..=WATER_RANGE {
+                    let position = BlockPos(location.0.add_raw(x, y, z));
+                    let Ok(state_id) = world.get_block_state_id(&position).await else {
+                        continue;
+                    };
+                    if FluidState::from_state_id(state_id)
+                        .is_some_and(|state| state.fluid == Fluid::Water)
+                    {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    async fn has_crop(world: &World, above: &BlockPos) -> bool {
+        world
+            .get_block(above)
+            .await
+            .is_ok_and(|block| CROPS.contains(&block.name.as_str()))
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for FarmlandBlock {
+    fn has_random_ticks(&self) -> bool {
+        true
+    }
+
+    async fn random_tick(
+        &self,
+        _server: &Server,
+        world: &World,
+        _block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let Some(moisture) = get_int_property(state_id, "moisture") else {
+            return;
+        };
+        let above = BlockPos(location.0.add_raw(0, 1, 0));
+
+        let moist =
+            world.is_raining_at(&above).await || Self::is_near_water(world, &location).await;
+        let new_state_id = if moist {
+            if moisture == MAX_MOISTURE {
+                return;
+            }
+            with_int_property(state_id, "moisture", MAX_MOISTURE)
+        } else if moisture > 0 {
+            with_int_property(state_id, "moisture", moisture - 1)
+        } else if Self::has_crop(world, &above).await {
+            return;
+        } else {
+            get_block("minecraft:dirt").map(|dirt| dirt.default_state_id)
+        };
+        if let Some(new_state_id) = new_state_id {
+            world.set_block_state(&location, new_state_id).await;
+        }
+    }
+}
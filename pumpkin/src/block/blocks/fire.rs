@@ -0,0 +1,546 @@
+//! Fire, which burns flammable blocks away and spreads to the blocks around them, ported from
+//! vanilla.
+//!
+//! Fire ticks every 30 to 40 ticks. It gets older with each tick and less likely to spread the
+//! older it is, and fire it spreads to starts at least as old, so a fire dies down the further it
+//! gets from where it started.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_registry::DimensionType;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    block::{
+        registry::{
+            get_block, get_block_by_state_id, get_state_by_state_id, get_state_id_by_properties,
+            Block,
+        },
+        BlockDirection,
+    },
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+use rand::{thread_rng, Rng};
+
+use crate::{
+    block::pumpkin_block::PumpkinBlock, plugin::api::events::block::block_burn::BlockBurnEvent,
+    server::Server, world::World, PLUGIN_MANAGER,
+};
+
+use super::{fluid::FluidState, get_int_property, with_int_property};
+
+/// The fire blocks
+pub const FIRES: [&str; 2] = ["fire", "soul_fire"];
+
+/// Fire is at most this old
+const MAX_AGE: u32 = 15;
+
+/// The woods which burn, unlike crimson and warped wood from the nether
+const FLAMMABLE_WOODS: [&str; 10] = [
+    "oak", "spruce", "birch", "jungle", "acacia", "dark_oak", "pale_oak", "mangrove", "cherry",
+    "bamboo",
+];
+
+const FLOWERS: [&str; 19] = [
+    "dandelion",
+    "poppy",
+    "blue_orchid",
+    "allium",
+    "azure_bluet",
+    "red_tulip",
+    "orange_tulip",
+    "white_tulip",
+    "pink_tulip",
+    "oxeye_daisy",
+    "cornflower",
+    "lily_of_the_valley",
+    "wither_rose",
+    "torchflower",
+    "pink_petals",
+    "sunflower",
+    "lilac",
+    "rose_bush",
+    "peony",
+];
+
+/// How a block burns, with the same values as vanilla
+#[derive(Clone, Copy)]
+struct BurnOdds {
+    /// How likely fire spreads into air next to the block
+    encouragement: u32,
+    /// How likely the block burns away when fire is next to it
+    flammability: u32,
+}
+
+const fn odds(encouragement: u32, flammability: u32) -> Option<BurnOdds> {
+    Some(BurnOdds {
+        encouragement,
+        flammability,
+    })
+}
+
+/// How the block burns, or `None` if it does not burn
+fn burn_odds(name: &str) -> Option<BurnOdds> {
+    let wood = FLAMMABLE_WOODS.iter().find_map(|wood| {
+        name.strip_prefix("stripped_")
+            .unwrap_or(name)
+            .strip_prefix(wood)?
+            .strip_prefix('_')
+    });
+    if let Some(kind) = wood {
+        return match kind {
+            "planks" | "slab" | "stairs" | "fence" | "fence_gate" | "mosaic" | "mosaic_slab"
+            | "mosaic_stairs" => odds(5, 20),
+            "log" | "wood" | "block" => odds(5, 5),
+            "leaves" => odds(30, 60),
+            _ => None,
+        };
+    }
+    if FLOWERS.contains(&name) {
+        return odds(60, 100);
+    }
+    if name == "moss_carpet" || name == "moss_block" {
+        return odds(5, 100);
+    }
+    if name.ends_with("_wool") {
+        return odds(30, 60);
+    }
+    if name.ends_with("_carpet") {
+        return odds(60, 20);
+    }
+    match name {
+        "short_grass" | "tall_grass" | "fern" | "large_fern" | "dead_bush" | "sweet_berry_bush"
+        | "spore_blossom" => odds(60, 100),
+        "bookshelf" | "lectern" | "bee_nest" | "target" => odds(30, 20),
+        "tnt" => odds(15, 100),
+        "vine" | "glow_lichen" | "big_dripleaf" | "big_dripleaf_stem" | "small_dripleaf" => {
+            odds(15, 100)
+        }
+        "coal_block" => odds(5, 5),
+        "hay_block" => odds(60, 20),
+        "dried_kelp_block" | "azalea_leaves" | "flowering_azalea_leaves" | "hanging_roots" => {
+            odds(30, 60)
+        }
+        "azalea" | "flowering_azalea" => odds(30, 60),
+        "cave_vines" | "cave_vines_plant" => odds(15, 60),
+        "bamboo" | "scaffolding" => odds(60, 60),
+        "composter" | "beehive" | "chiseled_bookshelf" => odds(5, 20),
+        _ => None,
+    }
+}
+
+/// The burn odds of a block state. Waterlogged blocks do not burn.
+fn state_burn_odds(state_id: u16) -> Option<BurnOdds> {
+    if FluidState::from_state_id(state_id).is_some() {
+        return None;
+    }
+    burn_odds(&get_block_by_state_id(state_id)?.name)
+}
+
+/// Whether fire can burn the block state away
+fn is_flammable(state_id: u16) -> bool {
+    state_burn_odds(state_id).is_some_and(|odds| odds.flammability > 0)
+}
+
+/// Whether fire can stand on top of the block state
+fn is_sturdy(state_id: u16) -> bool {
+    get_state_by_state_id(state_id)
+        .is_some_and(|state| !state.air && !state.collision_shapes.is_empty())
+}
+
+fn is_air(state_id: u16) -> bool {
+    get_state_by_state_id(state_id).is_some_and(|state| state.air)
+}
+
+/// A random number from 0 up to the bound, excluding it
+fn random(bound: u32) -> u32 {
+    thread_rng().gen_range(0..bound)
+}
+
+fn offset(position: &BlockPos, direction: BlockDirection) -> BlockPos {
+    BlockPos(position.0 + direction.to_offset())
+}
+
+/// The block state at the position, or `None` if it is outside of the world or of the loaded
+/// chunks, where fire does not spread
+async fn state_id_at(world: &World, position: &BlockPos) -> Option<u16> {
+    let in_world = (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&position.0.y);
+    if !in_world || !world.is_loaded(position) {
+        return None;
+    }
+    world.get_block_state_id(position).await.ok()
+}
+
+/// The name of the fire property for a side, fire below a block has no own property
+const fn side_property(direction: BlockDirection) -> Option<&'static str> {
+    match direction {
+        BlockDirection::Bottom => None,
+        BlockDirection::Top => Some("up"),
+        BlockDirection::North => Some("north"),
+        BlockDirection::South => Some("south"),
+        BlockDirection::West => Some("west"),
+        BlockDirection::East => Some("east"),
+    }
+}
+
+/// The fire block state for the position, or `None` if fire can not be there. Fire stands on top
+/// of sturdy or flammable blocks, otherwise it clings to the sides of flammable blocks around it.
+/// On soul sand and soul soil, it is soul fire.
+pub async fn fire_state_at(world: &World, position: &BlockPos, age: u32) -> Option<u16> {
+    let below = state_id_at(world, &offset(position, BlockDirection::Bottom)).await?;
+    let below_name = get_block_by_state_id(below).map(|block| block.name.as_str());
+    if matches!(below_name, Some("soul_sand" | "soul_soil")) {
+        return get_block("minecraft:soul_fire").map(|block| block.default_state_id);
+    }
+
+    let mut properties = HashMap::from([("age".to_string(), age.min(MAX_AGE).to_string())]);
+    let on_ground = is_sturdy(below) || is_flammable(below);
+    let mut clings = false;
+    for direction in BlockDirection::all() {
+        let Some(property) = side_property(direction) else {
+            continue;
+        };
+        let flammable = !on_ground
+            && state_id_at(world, &offset(position, direction))
+                .await
+                .is_some_and(is_flammable);
+        clings |= flammable;
+        properties.insert(property.to_string(), flammable.to_string());
+    }
+    if !on_ground && !clings {
+        return None;
+    }
+    get_state_id_by_properties("minecraft:fire", &properties)
+}
+
+/// How many ticks fire waits between ticks
+fn tick_delay() -> u64 {
+    30 + u64::from(random(10))
+}
+
+/// Sets fire at the position if fire can be there, replacing the block there, and returns
+/// whether it did
+pub async fn place_fire(server: &Server, world: &World, position: &BlockPos, age: u32) -> bool {
+    let Some(state_id) = fire_state_at(world, position, age).await else {
+        return false;
+    };
+    world.set_block_state(position, state_id).await;
+    world.update_neighbors(server, position).await;
+    if let Some(block) = get_block_by_state_id(state_id) {
+        world.schedule_block_tick(*position, block, tick_delay());
+    }
+    true
+}
+
+async fn remove_fire(server: &Server, world: &World, position: &BlockPos) {
+    world.set_block_state(position, 0).await;
+    world.update_neighbors(server, position).await;
+}
+
+/// Whether there are flammable blocks around the position
+async fn is_near_flammable(world: &World, position: &BlockPos) -> bool {
+    for direction in BlockDirection::all() {
+        if state_id_at(world, &offset(position, direction))
+            .await
+            .is_some_and(is_flammable)
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// How likely fire spreads into the position, which is the highest encouragement of the blocks
+/// around it. Only air catches fire this way.
+async fn encouragement_at(world: &World, position: &BlockPos) -> u32 {
+    if !state_id_at(world, position).await.is_some_and(is_air) {
+        return 0;
+    }
+    let mut encouragement = 0;
+    for direction in BlockDirection::all() {
+        let odds = state_id_at(world, &offset(position, direction))
+            .await
+            .and_then(state_burn_odds);
+        if let Some(odds) = odds {
+            encouragement = encouragement.max(odds.encouragement);
+        }
+    }
+    encouragement
+}
+
+/// Whether it rains on the position or next to it
+async fn is_raining_around(world: &World, position: &BlockPos) -> bool {
+    if world.is_raining_at(position).await {
+        return true;
+    }
+    for direction in BlockDirection::horizontal() {
+        if world.is_raining_at(&offset(position, direction)).await {
+            return true;
+        }
+    }
+    false
+}
+
+/// Fire burns forever on these blocks
+fn is_infiniburn(world: &World, state_id: u16) -> bool {
+    get_block_by_state_id(state_id).is_some_and(|block| match block.name.as_str() {
+        "netherrack" | "magma_block" => true,
+        "bedrock" => matches!(world.dimension_type, DimensionType::TheEnd),
+        _ => false,
+    })
+}
+
+/// Fire, which spreads and burns flammable blocks away, and soul fire, which only stays on soul
+/// sand and soul soil
+pub struct FireBlock;
+
+impl FireBlock {
+    /// Lets fire burn the block at the position away with a chance depending on its
+    /// flammability. The fire spreads into the burnt block sometimes.
+    async fn try_burn(
+        server: &Server,
+        world: &World,
+        fire: &Block,
+        position: &BlockPos,
+        chance: u32,
+        age: u32,
+    ) {
+        let Some(state_id) = state_id_at(world, position).await else {
+            return;
+        };
+        let Some(odds) = state_burn_odds(state_id) else {
+            return;
+        };
+        if random(chance) >= odds.flammability {
+            return;
+        }
+        let Some(burnt) = get_block_by_state_id(state_id) else {
+            return;
+        };
+
+        let event = BlockBurnEvent::new(fire.clone(), burnt.clone());
+        let event = PLUGIN_MANAGER
+            .lock()
+            .await
+            .fire::<BlockBurnEvent>(event)
+            .await;
+        if event.cancelled {
+            return;
+        }
+
+        // TODO: Prime TNT instead of burning it away
+        if random(age + 10) < 5 && !world.is_raining_at(position).await {
+            let new_age = (age + random(5) / 4).min(MAX_AGE);
+            // The burnt block is gone, so the fire might not find anything to cling to
+            world.set_block_state(position, 0).await;
+            if !place_fire(server, world, position, new_age).await {
+                world.update_neighbors(server, position).await;
+            }
+        } else {
+            remove_fire(server, world, position).await;
+        }
+    }
+
+    /// Lets fire spread into the air around it, up to four blocks above and one below
+    async fn spread(server: &Server, world: &World, position: &BlockPos, age: u32) {
+        let difficulty = BASIC_CONFIG.default_difficulty as u32;
+        for x in -1..=1 {
+            for z in -1..=1 {
+                for y in -1..=4 {
+                    if x == 0 && y == 0 && z == 0 {
+                        continue;
+                    }
+                    // Fire spreads upwards less the higher it is
+                    let chance = if y > 1 {
+                        100 + (y as u32 - 1) * 100
+                    } else {
+                        100
+                    };
+                    let target = BlockPos(position.0.add_raw(x, y, z));
+                    let encouragement = encouragement_at(world, &target).await;
+                    if encouragement == 0 {
+                        continue;
+                    }
+                    let odds = (encouragement + 40 + difficulty * 7) / (age + 30);
+                    if odds == 0 || random(chance) > odds {
+                        continue;
+                    }
+                    if is_raining_around(world, &target).await {
+                        continue;
+                    }
+                    let new_age = (age + random(5) / 4).min(MAX_AGE);
+                    place_fire(server, world, &target, new_age).await;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for FireBlock {
+    async fn on_scheduled_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        // Soul fire does not age or spread
+        let Some(age) = get_int_property(state_id, "age") else {
+            return;
+        };
+        world.schedule_block_tick(location, block, tick_delay());
+        if !world.game_rules.do_fire_tick {
+            return;
+        }
+        if fire_state_at(world, &location, age).await.is_none() {
+            remove_fire(server, world, &location).await;
+            return;
+        }
+
+        let Some(below) = state_id_at(world, &offset(&location, BlockDirection::Bottom)).await
+        else {
+            return;
+        };
+        let infiniburn = is_infiniburn(world, below);
+        let extinguish_chance = 0.2 + age as f32 * 0.03;
+        if !infiniburn
+            && is_raining_around(world, &location).await
+            && rand::random::<f32>() < extinguish_chance
+        {
+            remove_fire(server, world, &location).await;
+            return;
+        }
+
+        let new_age = (age + random(3) / 2).min(MAX_AGE);
+        if new_age != age {
+            if let Some(older) = with_int_property(state_id, "age", new_age) {
+                world.set_block_state(&location, older).await;
+            }
+        }
+
+        if !infiniburn {
+            if !is_near_flammable(world, &location).await {
+                // Fire without fuel goes out, right away if it has nothing to stand on
+                if !is_sturdy(below) || age > 3 {
+                    remove_fire(server, world, &location).await;
+                }
+                return;
+            }
+            if age == MAX_AGE && random(4) == 0 && !is_flammable(below) {
+                remove_fire(server, world, &location).await;
+                return;
+            }
+        }
+
+        // Fire burns the blocks next to it, those below and above it a bit less
+        for direction in BlockDirection::all() {
+            let chance = match direction {
+                BlockDirection::Bottom | BlockDirection::Top => 250,
+                _ => 300,
+            };
+            let target = offset(&location, direction);
+            Self::try_burn(server, world, block, &target, chance, new_age).await;
+        }
+        Self::spread(server, world, &location, new_age).await;
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        let stays = if block.name == "soul_fire" {
+            let below = state_id_at(world, &offset(&location, BlockDirection::Bottom)).await;
+            below
+                .and_then(get_block_by_state_id)
+                .is_some_and(|below| matches!(below.name.as_str(), "soul_sand" | "soul_soil"))
+        } else {
+            let Ok(state_id) = world.get_block_state_id(&location).await else {
+                return;
+            };
+            let age = get_int_property(state_id, "age").unwrap_or(0);
+            match fire_state_at(world, &location, age).await {
+                Some(new_state) if new_state != state_id => {
+                    // The fire clings to other sides now
+                    world.set_block_state(&location, new_state).await;
+                    true
+                }
+                Some(_) => true,
+                None => false,
+            }
+        };
+        if !stays {
+            remove_fire(server, world, &location).await;
+        }
+    }
+}
+
+/// Lets lava set fire to flammable blocks around it, which happens in its random ticks
+pub async fn ignite_around_lava(server: &Server, world: &World, location: &BlockPos) {
+    if !world.game_rules.do_fire_tick {
+        return;
+    }
+    let tries = random(3);
+    if tries > 0 {
+        // Looks for air above the lava which is next to flammable blocks
+        let mut position = *location;
+        for _ in 0..tries {
+            position = BlockPos(
+                position
+                    .0
+                    .add_raw(random(3) as i32 - 1, 1, random(3) as i32 - 1),
+            );
+            let Some(state_id) = state_id_at(world, &position).await else {
+                return;
+            };
+            if is_air(state_id) {
+                if is_near_flammable(world, &position).await {
+                    place_fire(server, world, &position, 0).await;
+                    return;
+                }
+            } else if is_sturdy(state_id) {
+                return;
+            }
+        }
+    } else {
+        // Sets fire on top of flammable blocks next to the lava
+        for _ in 0..3 {
+            let position = BlockPos(location.0.add_raw(
+                random(3) as i32 - 1,
+                0,
+                random(3) as i32 - 1,
+            ));
+            let above = offset(&position, BlockDirection::Top);
+            let above_is_air = state_id_at(world, &above).await.is_some_and(is_air);
+            if above_is_air
+                && state_id_at(world, &position)
+                    .await
+                    .is_some_and(is_flammable)
+            {
+                place_fire(server, world, &above, 0).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::burn_odds;
+
+    #[test]
+    fn wood_burns_unless_from_the_nether() {
+        assert!(burn_odds("oak_planks").is_some_and(|odds| odds.flammability == 20));
+        assert!(burn_odds("stripped_dark_oak_log").is_some_and(|odds| odds.flammability == 5));
+        assert!(burn_odds("birch_leaves").is_some_and(|odds| odds.encouragement == 30));
+        assert!(burn_odds("crimson_planks").is_none());
+        assert!(burn_odds("warped_stem").is_none());
+        assert!(burn_odds("oak_door").is_none());
+        assert!(burn_odds("stone").is_none());
+    }
+}
@@ -0,0 +1,567 @@
+//! Flowing water and lava, ported from vanilla's flowing fluids.
+//!
+//! A fluid block is either a source, a falling fluid below other fluid of its kind, or a flowing
+//! fluid, which gets less the further it is from its source. Each step of the flow happens in a
+//! scheduled tick of the fluid block, which is scheduled again whenever a neighbour changes.
+
+use std::{collections::HashMap, future::Future, pin::Pin};
+
+use async_trait::async_trait;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_registry::DimensionType;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    block::{
+        registry::{
+            get_block, get_block_and_state_by_state_id, get_block_by_state_id,
+            get_state_id_by_properties, get_state_property, Block,
+        },
+        BlockDirection,
+    },
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+
+use crate::{block::pumpkin_block::PumpkinBlock, server::Server, world::World};
+
+use super::{fire::ignite_around_lava, get_int_property};
+
+/// The amount of a source or of falling fluid
+const FULL: u8 = 8;
+
+/// Flowing fluid searches this far at most for a way down, see [`Fluid::slope_find_distance`]
+const NO_SLOPE: i32 = 1000;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Fluid {
+    Water,
+    Lava,
+}
+
+impl Fluid {
+    #[must_use]
+    pub fn from_block(block: &Block) -> Option<Self> {
+        match block.name.as_str() {
+            "water" => Some(Self::Water),
+            "lava" => Some(Self::Lava),
+            _ => None,
+        }
+    }
+
+    const fn block_name(self) -> &'static str {
+        match self {
+            Self::Water => "minecraft:water",
+            Self::Lava => "minecraft:lava",
+        }
+    }
+
+    /// Lava is faster and flows further in the nether
+    fn is_ultra_warm(world: &World) -> bool {
+        matches!(world.dimension_type, DimensionType::TheNether)
+    }
+
+    /// How many ticks the fluid waits between steps
+    #[must_use]
+    pub fn tick_delay(self, world: &World) -> u64 {
+        match self {
+            Self::Water => 5,
+            Self::Lava if Self::is_ultra_warm(world) => 10,
+            Self::Lava => 30,
+        }
+    }
+
+    /// How much less fluid there is with each block away from the source
+    fn level_decrease(self, world: &World) -> u8 {
+        match self {
+            Self::Lava if !Self::is_ultra_warm(world) => 2,
+            _ => 1,
+        }
+    }
+
+    /// How far the fluid looks for a way down to decide where it flows
+    fn slope_find_distance(self, world: &World) -> i32 {
+        match self {
+            Self::Lava if !Self::is_ultra_warm(world) => 2,
+            _ => 4,
+        }
+    }
+
+    /// Whether two sources next to each other create a new source
+    const fn is_infinite(self) -> bool {
+        matches!(self, Self::Water)
+    }
+}
+
+/// The fluid in a block
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct FluidState {
+    pub fluid: Fluid,
+    /// From 1 to 8, which is the amount of sources and falling fluid
+    pub amount: u8,
+    pub source: bool,
+    pub falling: bool,
+}
+
+impl FluidState {
+    #[must_use]
+    pub const fn source(fluid: Fluid) -> Self {
+        Self {
+            fluid,
+            amount: FULL,
+            source: true,
+            falling: false,
+        }
+    }
+
+    const fn flowing(fluid: Fluid, amount: u8, falling: bool) -> Self {
+        Self {
+            fluid,
+            amount,
+            source: false,
+            falling,
+        }
+    }
+
+    /// The fluid of a block state. Waterlogged blocks count as water sources.
+    #[must_use]
+    pub fn from_state_id(state_id: u16) -> Option<Self> {
+        let (block, _) = get_block_and_state_by_state_id(state_id)?;
+        let Some(fluid) = Fluid::from_block(block) else {
+            return (get_state_property(state_id, "waterlogged") == Some("true"))
+                .then_some(Self::source(Fluid::Water));
+        };
+        // Levels 1 to 7 are flowing fluid, 8 and above is falling fluid
+        Some(match get_int_property(state_id, "level")? {
+            0 => Self::source(fluid),
+            level @ 1..=7 => Self::flowing(fluid, FULL - level as u8, false),
+            _ => Self::flowing(fluid, FULL, true),
+        })
+    }
+
+    /// The block state of the fluid
+    #[must_use]
+    pub fn to_state_id(self) -> Option<u16> {
+        let level = if self.source {
+            0
+        } else if self.falling {
+            8
+        } else {
+            FULL - self.amount
+        };
+        let properties = HashMap::from([("level".to_string(), level.to_string())]);
+        get_state_id_by_properties(self.fluid.block_name(), &properties)
+    }
+}
+
+/// Sets a block and lets its neighbours react, so fluids next to it flow again
+async fn set_block(server: &Server, world: &World, position: &BlockPos, state_id: u16) {
+    world.set_block_state(position, state_id).await;
+    world.update_neighbors(server, position).await;
+}
+
+/// Places fluid at the position, replacing the block there, and lets it start flowing
+pub async fn place_fluid(server: &Server, world: &World, position: &BlockPos, state: FluidState) {
+    let Some(state_id) = state.to_state_id() else {
+        return;
+    };
+    set_block(server, world, position, state_id).await;
+    if let Some(block) = get_block_by_state_id(state_id) {
+        world.schedule_block_tick(*position, block, state.fluid.tick_delay(world));
+    }
+}
+
+fn offset(position: &BlockPos, direction: BlockDirection) -> BlockPos {
+    BlockPos(position.0 + direction.to_offset())
+}
+
+fn state_id_of(name: &str) -> Option<u16> {
+    get_block(name).map(|block| block.default_state_id)
+}
+
+/// Reads the blocks around flowing fluid once per step, as the search for the way down reads the
+/// same blocks again and again
+struct FlowContext<'a> {
+    world: &'a World,
+    fluid: Fluid,
+    states: HashMap<BlockPos, Option<u16>>,
+}
+
+impl<'a> FlowContext<'a> {
+    fn new(world: &'a World, fluid: Fluid) -> Self {
+        Self {
+            world,
+            fluid,
+            states: HashMap::new(),
+        }
+    }
+
+    /// The block state at the position, or `None` if it is outside of the world or of the loaded
+    /// chunks, where fluid does not flow
+    async fn state_id(&mut self, position: &BlockPos) -> Option<u16> {
+        if let Some(state_id) = self.states.get(position) {
+            return *state_id;
+        }
+        let in_world = (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&position.0.y);
+        let state_id = if in_world && self.world.is_loaded(position) {
+            self.world.get_block_state_id(position).await.ok()
+        } else {
+            None
+        };
+        self.states.insert(*position, state_id);
+        state_id
+    }
+
+    async fn fluid_state(&mut self, position: &BlockPos) -> Option<FluidState> {
+        self.state_id(position)
+            .await
+            .and_then(FluidState::from_state_id)
+    }
+
+    /// Whether the fluid can flow into the position in the direction, replacing the block there
+    async fn can_flow_into(&mut self, position: &BlockPos, direction: BlockDirection) -> bool {
+        let Some(state_id) = self.state_id(position).await else {
+            return false;
+        };
+        let Some((block, state)) = get_block_and_state_by_state_id(state_id) else {
+            return false;
+        };
+        match (Fluid::from_block(block), self.fluid) {
+            // Lava flowing down into water turns it into stone
+            (Some(Fluid::Water), Fluid::Lava) => direction == BlockDirection::Bottom,
+            // Fluid already there updates itself
+            (Some(_), _) => false,
+            // TODO: Fill waterloggable blocks
+            (None, _) if FluidState::from_state_id(state_id).is_some() => false,
+            (None, _) => state.air || state.replaceable,
+        }
+    }
+
+    /// Whether the fluid can flow down at the position, because there is fluid of its kind or
+    /// space below
+    async fn is_hole(&mut self, position: &BlockPos) -> bool {
+        let below = offset(position, BlockDirection::Bottom);
+        if self
+            .fluid_state(&below)
+            .await
+            .is_some_and(|below| below.fluid == self.fluid)
+        {
+            return true;
+        }
+        self.can_flow_into(&below, BlockDirection::Bottom).await
+    }
+
+    /// How many sources of the fluid are next to the position
+    async fn count_sources_around(&mut self, position: &BlockPos) -> usize {
+        let mut sources = 0;
+        for direction in BlockDirection::horizontal() {
+            if self
+                .fluid_state(&offset(position, direction))
+                .await
+                .is_some_and(|neighbor| neighbor.fluid == self.fluid && neighbor.source)
+            {
+                sources += 1;
+            }
+        }
+        sources
+    }
+
+    /// The fluid which should be at the position given its neighbours, or `None` if it dries up.
+    /// Like vanilla, there is one block less with each step, two sources next to each other make
+    /// a new water source and fluid above makes falling fluid.
+    async fn new_liquid(&mut self, position: &BlockPos) -> Option<FluidState> {
+        let mut max_amount = 0;
+        let mut sources = 0;
+        for direction in BlockDirection::horizontal() {
+            let Some(neighbor) = self.fluid_state(&offset(position, direction)).await else {
+                continue;
+            };
+            if neighbor.fluid != self.fluid {
+                continue;
+            }
+            if neighbor.source {
+                sources += 1;
+            }
+            max_amount = max_amount.max(neighbor.amount);
+        }
+
+        if self.fluid.is_infinite() && sources >= 2 {
+            let below = offset(position, BlockDirection::Bottom);
+            let below_is_solid = self
+                .state_id(&below)
+                .await
+                .and_then(get_block_and_state_by_state_id)
+                .is_some_and(|(_, state)| !state.air && !state.collision_shapes.is_empty());
+            let below_is_source = self
+                .fluid_state(&below)
+                .await
+                .is_some_and(|below| below.fluid == self.fluid && below.source);
+            if below_is_solid || below_is_source {
+                return Some(FluidState::source(self.fluid));
+            }
+        }
+
+        let above = offset(position, BlockDirection::Top);
+        if self
+            .fluid_state(&above)
+            .await
+            .is_some_and(|above| above.fluid == self.fluid)
+        {
+            return Some(FluidState::flowing(self.fluid, FULL, true));
+        }
+
+        let amount = max_amount.saturating_sub(self.fluid.level_decrease(self.world));
+        (amount > 0).then(|| FluidState::flowing(self.fluid, amount, false))
+    }
+
+    /// How many blocks away the nearest way down is, searching up to the slope find distance
+    fn slope_distance<'b>(
+        &'b mut self,
+        position: BlockPos,
+        depth: i32,
+        from: BlockDirection,
+    ) -> Pin<Box<dyn Future<Output = i32> + Send + 'b>> {
+        Box::pin(async move {
+            let mut min = NO_SLOPE;
+            for direction in BlockDirection::horizontal() {
+                if direction == from {
+                    continue;
+                }
+                let target = offset(&position, direction);
+                if !self.can_flow_into(&target, direction).await {
+                    continue;
+                }
+                if self.is_hole(&target).await {
+                    return depth;
+                }
+                if depth < self.fluid.slope_find_distance(self.world) {
+                    let distance = self
+                        .slope_distance(target, depth + 1, direction.opposite())
+                        .await;
+                    min = min.min(distance);
+                }
+            }
+            min
+        })
+    }
+
+    /// The positions next to the fluid it flows to, which are the ones closest to a way down
+    async fn spread_targets(&mut self, position: &BlockPos) -> Vec<(BlockPos, BlockDirection)> {
+        let mut min = NO_SLOPE;
+        let mut targets = Vec::new();
+        for direction in BlockDirection::horizontal() {
+            let target = offset(position, direction);
+            if !self.can_flow_into(&target, direction).await {
+                continue;
+            }
+            let distance = if self.is_hole(&target).await {
+                0
+            } else {
+                self.slope_distance(target, 1, direction.opposite()).await
+            };
+            if distance < min {
+                targets.clear();
+                min = distance;
+            }
+            if distance <= min {
+                targets.push((target, direction));
+            }
+        }
+        targets
+    }
+}
+
+/// Water or lava, lava also sets fire to flammable blocks around it in its random ticks
+pub struct FluidBlock(pub Fluid);
+
+impl FluidBlock {
+    /// Turns lava touching water into obsidian if it is a source, or cobblestone otherwise.
+    /// Returns whether the lava was turned into a block.
+    async fn solidify_lava(
+        server: &Server,
+        world: &World,
+        position: &BlockPos,
+        state: FluidState,
+    ) -> bool {
+        if state.fluid != Fluid::Lava {
+            return false;
+        }
+        // Water below does not count, lava flowing down onto it makes stone instead
+        for direction in BlockDirection::all() {
+            if direction == BlockDirection::Bottom {
+                continue;
+            }
+            let touches_water = world
+                .get_block_state_id(&offset(position, direction))
+                .await
+                .ok()
+                .and_then(FluidState::from_state_id)
+                .is_some_and(|neighbor| neighbor.fluid == Fluid::Water);
+            if !touches_water {
+                continue;
+            }
+            let name = if state.source {
+                "minecraft:obsidian"
+            } else {
+                "minecraft:cobblestone"
+            };
+            if let Some(state_id) = state_id_of(name) {
+                set_block(server, world, position, state_id).await;
+                Self::fizz(world, position).await;
+                return true;
+            }
+        }
+        false
+    }
+
+    async fn fizz(world: &World, position: &BlockPos) {
+        world
+            .play_block_sound(Sound::BlockLavaExtinguish, SoundCategory::Blocks, *position)
+            .await;
+    }
+
+    /// Lets the fluid flow into a neighbouring position
+    async fn flow_to(
+        server: &Server,
+        world: &World,
+        position: &BlockPos,
+        direction: BlockDirection,
+        state: FluidState,
+    ) {
+        let replaced = world.get_block_state_id(position).await.ok();
+        let replaced_water = replaced
+            .and_then(FluidState::from_state_id)
+            .is_some_and(|replaced| replaced.fluid == Fluid::Water);
+        if state.fluid == Fluid::Lava && direction == BlockDirection::Bottom && replaced_water {
+            if let Some(stone) = state_id_of("minecraft:stone") {
+                set_block(server, world, position, stone).await;
+                Self::fizz(world, position).await;
+            }
+            return;
+        }
+        // TODO: Drop the loot of replaced blocks like grass, which needs the world as an `Arc`
+        place_fluid(server, world, position, state).await;
+    }
+
+    /// Lets the fluid flow to the sides, towards the nearest way down
+    async fn flow_to_sides(
+        server: &Server,
+        world: &World,
+        context: &mut FlowContext<'_>,
+        position: &BlockPos,
+        state: FluidState,
+    ) {
+        let amount = if state.falling {
+            FULL - 1
+        } else {
+            state
+                .amount
+                .saturating_sub(state.fluid.level_decrease(world))
+        };
+        if amount == 0 {
+            return;
+        }
+        for (target, direction) in context.spread_targets(position).await {
+            Self::flow_to(
+                server,
+                world,
+                &target,
+                direction,
+                FluidState::flowing(state.fluid, amount, false),
+            )
+            .await;
+        }
+    }
+
+    /// Lets the fluid flow down if it can, and to the sides otherwise
+    async fn spread(server: &Server, world: &World, position: &BlockPos, state: FluidState) {
+        let mut context = FlowContext::new(world, state.fluid);
+        let below = offset(position, BlockDirection::Bottom);
+        if context.can_flow_into(&below, BlockDirection::Bottom).await {
+            Self::flow_to(
+                server,
+                world,
+                &below,
+                BlockDirection::Bottom,
+                FluidState::flowing(state.fluid, FULL, true),
+            )
+            .await;
+            // Fluid between sources also spreads while flowing down, so pools fill up
+            if context.count_sources_around(position).await >= 3 {
+                Self::flow_to_sides(server, world, &mut context, position, state).await;
+            }
+        } else if state.source || !context.is_hole(position).await {
+            Self::flow_to_sides(server, world, &mut context, position, state).await;
+        }
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for FluidBlock {
+    fn has_random_ticks(&self) -> bool {
+        self.0 == Fluid::Lava
+    }
+
+    async fn random_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        _block: &Block,
+        _state_id: u16,
+        location: BlockPos,
+    ) {
+        ignite_around_lava(server, world, &location).await;
+    }
+
+    async fn on_scheduled_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        _block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let Some(mut state) = FluidState::from_state_id(state_id) else {
+            return;
+        };
+        if Self::solidify_lava(server, world, &location, state).await {
+            return;
+        }
+
+        if !state.source {
+            let mut context = FlowContext::new(world, state.fluid);
+            match context.new_liquid(&location).await {
+                // The fluid lost its source
+                None => {
+                    set_block(server, world, &location, 0).await;
+                    return;
+                }
+                Some(new_state) if new_state != state => {
+                    place_fluid(server, world, &location, new_state).await;
+                    state = new_state;
+                }
+                Some(_) => {}
+            }
+        }
+
+        Self::spread(server, world, &location, state).await;
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let Some(state) = FluidState::from_state_id(state_id) else {
+            return;
+        };
+        if Self::solidify_lava(server, world, &location, state).await {
+            return;
+        }
+        world.schedule_block_tick(location, block, state.fluid.tick_delay(world));
+    }
+}
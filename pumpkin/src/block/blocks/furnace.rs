@@ -3,7 +3,7 @@ use crate::entity::player::Player;
 use async_trait::async_trait;
 use pumpkin_data::item::Item;
 use pumpkin_data::screen::WindowType;
-use pumpkin_inventory::Furnace;
+use pumpkin_inventory::{Furnace, OpenContainer};
 use pumpkin_macros::pumpkin_block;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::block::registry::Block;
@@ -42,6 +42,20 @@ impl PumpkinBlock for FurnaceBlock {
     async fn broken(&self, block: &Block, player: &Player, location: BlockPos, server: &Server) {
         super::standard_on_broken_with_container(block, player, location, server).await;
     }
+
+    async fn close(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        container: &mut OpenContainer,
+    ) {
+        container.remove_player(player.entity_id());
+        if container.get_number_of_players() == 0 {
+            super::save_container_items(&player.world().await, location, container).await;
+        }
+    }
 }
 
 impl FurnaceBlock {
@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::registry::{get_state_by_state_id, Block};
+
+use crate::{block::pumpkin_block::PumpkinBlock, server::Server, world::World};
+
+use super::{get_int_property, with_int_property};
+
+/// The plants which grow upwards by stacking themselves
+pub const GROWING_PLANTS: [&str; 2] = ["sugar_cane", "cactus"];
+
+/// How tall the plants grow at most on their own
+const MAX_HEIGHT: i32 = 3;
+
+/// The age at which the plant grows by one block
+const MAX_AGE: u32 = 15;
+
+/// Sugar cane and cactus, which age with each random tick and then grow a block taller
+pub struct GrowingPlantBlock;
+
+impl GrowingPlantBlock {
+    async fn is_air(world: &World, position: &BlockPos) -> bool {
+        world
+            .get_block_state_id(position)
+            .await
+            .is_ok_and(|state_id| get_state_by_state_id(state_id).is_some_and(|state| state.air))
+    }
+
+    /// Cactus breaks next to other blocks, so it only grows where its sides are free
+    async fn has_free_sides(world: &World, position: &BlockPos) -> bool {
+        for (x, z) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+            if !Self::is_air(world, &BlockPos(position.0.add_raw(x, 0, z))).await {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for GrowingPlantBlock {
+    fn has_random_ticks(&self) -> bool {
+        true
+    }
+
+    async fn random_tick(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let above = BlockPos(location.0.add_raw(0, 1, 0));
+        if !Self::is_air(world, &above).await {
+            return;
+        }
+
+        let mut height = 1;
+        while height < MAX_HEIGHT
+            && world
+                .get_block(&BlockPos(location.0.add_raw(0, -height, 0)))
+                .await
+                .is_ok_and(|below| below.id == block.id)
+        {
+            height += 1;
+        }
+        if height >= MAX_HEIGHT {
+            return;
+        }
+
+        let Some(age) = get_int_property(state_id, "age") else {
+            return;
+        };
+        if age < MAX_AGE {
+            if let Some(aged) = with_int_property(state_id, "age", age + 1) {
+                world.set_block_state(&location, aged).await;
+            }
+            return;
+        }
+
+        if block.name == "cactus" && !Self::has_free_sides(world, &above).await {
+            return;
+        }
+        world.set_block_state(&above, block.default_state_id).await;
+        if let Some(young) = with_int_property(state_id, "age", 0) {
+            world.set_block_state(&location, young).await;
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::{item::Item, screen::WindowType};
+use pumpkin_inventory::{Hopper, OpenContainer};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::server::play::SUseItemOn;
+use pumpkin_util::math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3};
+use pumpkin_world::{
+    block::{
+        entity::HopperBlockEntity,
+        registry::{get_state_id_with_property, get_state_property, Block},
+        BlockDirection,
+    },
+    item::ItemStack,
+};
+
+use crate::{
+    block::{
+        self, properties::Direction, pumpkin_block::PumpkinBlock, registry::BlockActionResult,
+    },
+    entity::{player::Player, EntityBase},
+    server::Server,
+    world::World,
+};
+
+use super::{container, redstone};
+
+/// How many ticks a hopper waits after moving items, like in vanilla
+const TRANSFER_COOLDOWN: i32 = 8;
+
+/// A hopper, which takes items from the container above it or picks up items lying on it, and
+/// puts them into the container it faces. A powered hopper is locked.
+#[pumpkin_block("minecraft:hopper")]
+pub struct HopperBlock;
+
+impl HopperBlock {
+    async fn open(block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        super::standard_open_container::<Hopper>(
+            block,
+            player,
+            location,
+            server,
+            WindowType::Hopper,
+        )
+        .await;
+    }
+
+    /// Locks the hopper while it is powered
+    async fn update_enabled(world: &World, location: BlockPos) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let enabled = !redstone::is_powered(world, &location).await;
+        if let Some(new_state_id) =
+            get_state_id_with_property(state_id, "enabled", &enabled.to_string())
+        {
+            if new_state_id != state_id {
+                world.set_block_state(&location, new_state_id).await;
+            }
+        }
+    }
+
+    async fn set_cooldown(world: &World, location: &BlockPos, cooldown: i32) {
+        world
+            .update_block_entity(location, |block_entity| {
+                if let Some(hopper) = block_entity
+                    .as_any_mut()
+                    .downcast_mut::<HopperBlockEntity>()
+                {
+                    hopper.transfer_cooldown = cooldown;
+                }
+            })
+            .await;
+    }
+
+    /// Moves one item out of a container into another one. The item is taken out first, so it
+    /// can't be in both at the same time. If it does not fit after all, it is put back.
+    async fn transfer(
+        server: &Server,
+        world: &World,
+        from: &BlockPos,
+        from_face: BlockDirection,
+        to: &BlockPos,
+        to_face: BlockDirection,
+    ) -> bool {
+        let Some(slots) = container::get_slots(server, world, from).await else {
+            return false;
+        };
+        // Each kind of item is only tried once, the other stacks of it would not fit either
+        let mut tried: Vec<ItemStack> = Vec::new();
+        for stack in slots.into_iter().flatten() {
+            if tried.contains(&stack) {
+                continue;
+            }
+            let mut one = stack.clone();
+            one.item_count = 1;
+            tried.push(stack);
+            if !container::fits(server, world, to, to_face, &one).await {
+                continue;
+            }
+            let Some(taken) =
+                container::take_one(server, world, from, from_face, |item| *item == one).await
+            else {
+                continue;
+            };
+            let Some(rest) = container::insert(server, world, to, to_face, taken).await else {
+                return true;
+            };
+            Self::give_back(server, world, from, from_face, rest).await;
+        }
+        false
+    }
+
+    /// Puts items which could not be moved back into the container they came from, or drops
+    /// them if a player took their place meanwhile, so they are never lost
+    async fn give_back(
+        server: &Server,
+        world: &World,
+        location: &BlockPos,
+        face: BlockDirection,
+        stack: ItemStack,
+    ) {
+        let Some(rest) = container::insert(server, world, location, face, stack).await else {
+            return;
+        };
+        if let Some(world) = server.get_world_handle(world).await {
+            block::drop_stack(server, &world, &rest, location).await;
+        }
+    }
+
+    /// Puts one item into the container the hopper faces
+    async fn push(server: &Server, world: &World, location: &BlockPos, state_id: u16) -> bool {
+        let facing = get_state_property(state_id, "facing")
+            .and_then(redstone::direction_from_name)
+            .unwrap_or(BlockDirection::Bottom);
+        let target = redstone::offset(location, facing);
+        let Ok(target_block) = world.get_block(&target).await else {
+            return false;
+        };
+        // Like in vanilla, a hopper which gets its first item waits before passing it on, so
+        // items move along a line of hoppers at the same speed
+        let target_was_empty = target_block.name == "hopper"
+            && container::get_slots(server, world, &target)
+                .await
+                .is_some_and(|slots| slots.iter().all(Option::is_none));
+
+        let pushed =
+            Self::transfer(server, world, location, facing, &target, facing.opposite()).await;
+        if pushed && target_was_empty {
+            Self::set_cooldown(world, &target, TRANSFER_COOLDOWN).await;
+        }
+        pushed
+    }
+
+    /// Takes one item out of the container above the hopper, or picks up items lying on it
+    async fn pull(server: &Server, world: &World, location: &BlockPos) -> bool {
+        let above = redstone::offset(location, BlockDirection::Top);
+        if container::get_slots(server, world, &above).await.is_some() {
+            return Self::transfer(
+                server,
+                world,
+                &above,
+                BlockDirection::Bottom,
+                location,
+                BlockDirection::Top,
+            )
+            .await;
+        }
+        Self::pick_up_items(server, world, location).await
+    }
+
+    /// Picks up the items lying in or on the hopper, as many of them as fit
+    async fn pick_up_items(server: &Server, world: &World, location: &BlockPos) -> bool {
+        let (x, y, z) = (
+            f64::from(location.0.x),
+            f64::from(location.0.y),
+            f64::from(location.0.z),
+        );
+        let area = BoundingBox::new(
+            Vector3::new(x, y + 0.6875, z),
+            Vector3::new(x + 1.0, y + 2.0, z + 1.0),
+        );
+        // The items are collected first, as picking them up removes them from the world
+        let entities: Vec<Arc<dyn EntityBase>> = world
+            .entities
+            .read()
+            .await
+            .values()
+            .filter(|entity| {
+                entity.get_item_entity().is_some()
+                    && entity.get_entity().bounding_box.load().intersects(&area)
+            })
+            .cloned()
+            .collect();
+
+        for entity in entities {
+            let Some(item_entity) = entity.get_item_entity() else {
+                continue;
+            };
+            let mut stack = item_entity.lock_stack().await;
+            if stack.item_count == 0 {
+                continue;
+            }
+            let rest =
+                container::insert(server, world, location, BlockDirection::Top, stack.clone())
+                    .await;
+            let rest_count = rest.map_or(0, |rest| rest.item_count);
+            if rest_count == stack.item_count {
+                continue;
+            }
+            stack.item_count = rest_count;
+            item_entity.stack_changed(&stack).await;
+            return true;
+        }
+        false
+    }
+
+    fn is_full(slots: &[Option<ItemStack>]) -> bool {
+        slots.iter().all(|slot| {
+            slot.as_ref()
+                .is_some_and(|stack| stack.item_count >= stack.item.components.max_stack_size)
+        })
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for HopperBlock {
+    async fn on_place(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        face: &BlockDirection,
+        block_pos: &BlockPos,
+        use_item_on: &SUseItemOn,
+        player_direction: &Direction,
+        other: bool,
+    ) -> u16 {
+        let state_id = server
+            .block_properties_manager
+            .on_place_state(
+                world,
+                block,
+                face,
+                block_pos,
+                use_item_on,
+                player_direction,
+                other,
+            )
+            .await;
+        // Hoppers face into the block they were placed against, but never up
+        let facing = match face.opposite() {
+            BlockDirection::Top => BlockDirection::Bottom,
+            facing => facing,
+        };
+        let enabled = !redstone::is_powered(world, block_pos).await;
+        let state_id =
+            get_state_id_with_property(state_id, "facing", redstone::direction_name(facing))
+                .unwrap_or(state_id);
+        get_state_id_with_property(state_id, "enabled", &enabled.to_string()).unwrap_or(state_id)
+    }
+
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        Self::open(block, player, location, server).await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        server: &Server,
+    ) -> BlockActionResult {
+        Self::open(block, player, location, server).await;
+        BlockActionResult::Consume
+    }
+
+    async fn broken(&self, block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        super::standard_on_broken_with_container(block, player, location, server).await;
+    }
+
+    async fn close(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        container: &mut OpenContainer,
+    ) {
+        container.remove_player(player.entity_id());
+        if container.get_number_of_players() == 0 {
+            super::save_container_items(&player.world().await, location, container).await;
+        }
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        _server: &Server,
+        world: &World,
+        _block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        Self::update_enabled(world, location).await;
+    }
+
+    async fn on_block_entity_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        _block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        if get_state_property(state_id, "enabled") == Some("false") {
+            return;
+        }
+        let Some(slots) = container::get_slots(server, world, &location).await else {
+            return;
+        };
+        let mut moved = false;
+        if slots.iter().any(Option::is_some) {
+            moved = Self::push(server, world, &location, state_id).await;
+        }
+        if !Self::is_full(&slots) {
+            moved |= Self::pull(server, world, &location).await;
+        }
+        if moved {
+            Self::set_cooldown(world, &location, TRANSFER_COOLDOWN).await;
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use async_trait::async_trait;
+use pumpkin_data::tag::{get_tag_values, RegistryKey};
+use pumpkin_protocol::server::play::SUseItemOn;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    block::{
+        registry::{get_state_id_with_property, get_state_property, Block},
+        BlockDirection,
+    },
+    generation::feature::tree::MAX_LEAF_DISTANCE,
+};
+
+use crate::{
+    block::{properties::Direction, pumpkin_block::PumpkinBlock},
+    server::Server,
+    world::World,
+};
+
+use super::{get_int_property, with_int_property};
+
+/// The leaves which decay once they are too far away from logs
+pub const LEAVES: [&str; 11] = [
+    "oak_leaves",
+    "spruce_leaves",
+    "birch_leaves",
+    "jungle_leaves",
+    "acacia_leaves",
+    "cherry_leaves",
+    "dark_oak_leaves",
+    "pale_oak_leaves",
+    "mangrove_leaves",
+    "azalea_leaves",
+    "flowering_azalea_leaves",
+];
+
+/// Leaves keep track of their `distance` to the nearest log, through other leaves. Unless a player
+/// placed them, they decay once it reaches [`MAX_LEAF_DISTANCE`], e.g. after their tree was cut.
+pub struct LeavesBlock;
+
+impl LeavesBlock {
+    fn distance(state_id: u16) -> u32 {
+        get_int_property(state_id, "distance").unwrap_or(u32::from(MAX_LEAF_DISTANCE))
+    }
+
+    fn is_log(block: &Block) -> bool {
+        get_tag_values(RegistryKey::Block, "minecraft:logs")
+            .is_some_and(|logs| logs.iter().flatten().any(|log| *log == block.name))
+    }
+
+    /// The distance the leaves at the location should have, from their neighbours
+    async fn updated_distance(world: &World, location: &BlockPos) -> u32 {
+        let mut distance = u32::from(MAX_LEAF_DISTANCE);
+        for direction in BlockDirection::all() {
+            let neighbor = BlockPos(location.0 + direction.to_offset());
+            let Ok(state_id) = world.get_block_state_id(&neighbor).await else {
+                continue;
+            };
+            let Ok(block) = world.get_block(&neighbor).await else {
+                continue;
+            };
+            if Self::is_log(block) {
+                return 1;
+            }
+            if LEAVES.contains(&block.name.as_str()) {
+                distance = distance.min(Self::distance(state_id) + 1);
+            }
+        }
+        distance
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for LeavesBlock {
+    /// Leaves placed by players never decay
+    async fn on_place(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        face: &BlockDirection,
+        block_pos: &BlockPos,
+        use_item_on: &SUseItemOn,
+        player_direction: &Direction,
+        other: bool,
+    ) -> u16 {
+        let state_id = server
+            .block_properties_manager
+            .on_place_state(
+                world,
+                block,
+                face,
+                block_pos,
+                use_item_on,
+                player_direction,
+                other,
+            )
+            .await;
+        let distance = Self::updated_distance(world, block_pos).await;
+        get_state_id_with_property(state_id, "persistent", "true")
+            .and_then(|state_id| with_int_property(state_id, "distance", distance))
+            .unwrap_or(state_id)
+    }
+
+    fn has_random_ticks(&self) -> bool {
+        true
+    }
+
+    async fn random_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        _block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        if get_state_property(state_id, "persistent") == Some("true")
+            || Self::distance(state_id) < u32::from(MAX_LEAF_DISTANCE)
+        {
+            return;
+        }
+        // TODO: Drop saplings, sticks and apples from the loot table
+        world.set_block_state(&location, 0).await;
+        world.update_neighbors(server, &location).await;
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        // Like in vanilla, the distance changes a tick later, so it spreads one block per tick
+        if Self::updated_distance(world, &location).await != Self::distance(state_id) {
+            world.schedule_block_tick(location, block, 1);
+        }
+    }
+
+    async fn on_scheduled_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        _block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let distance = Self::updated_distance(world, &location).await;
+        if distance == Self::distance(state_id) {
+            return;
+        }
+        if let Some(updated) = with_int_property(state_id, "distance", distance) {
+            world.set_block_state(&location, updated).await;
+            world.update_neighbors(server, &location).await;
+        }
+    }
+}
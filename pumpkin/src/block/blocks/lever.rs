@@ -1,10 +1,16 @@
 use crate::entity::player::Player;
 use async_trait::async_trait;
-use pumpkin_data::item::Item;
+use pumpkin_data::{
+    item::Item,
+    sound::{Sound, SoundCategory},
+};
 use pumpkin_macros::pumpkin_block;
 use pumpkin_protocol::server::play::SUseItemOn;
-use pumpkin_util::math::position::BlockPos;
-use pumpkin_world::block::{registry::Block, BlockDirection};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::block::{
+    registry::{get_state_id_with_property, get_state_property, Block},
+    BlockDirection,
+};
 
 use crate::{
     block::{properties::Direction, pumpkin_block::PumpkinBlock, registry::BlockActionResult},
@@ -12,9 +18,51 @@ use crate::{
     world::World,
 };
 
+use super::redstone;
+
+/// A lever, which powers the blocks around it and strongly powers the block it is on while it is
+/// switched on
 #[pumpkin_block("minecraft:lever")]
 pub struct LeverBlock;
 
+impl LeverBlock {
+    async fn toggle(player: &Player, location: BlockPos, server: &Server) {
+        let world = player.world().await;
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let powered = get_state_property(state_id, "powered") != Some("true");
+        let Some(new_state_id) =
+            get_state_id_with_property(state_id, "powered", &powered.to_string())
+        else {
+            return;
+        };
+        world.set_block_state(&location, new_state_id).await;
+        let position = Vector3::new(
+            f64::from(location.0.x) + 0.5,
+            f64::from(location.0.y) + 0.5,
+            f64::from(location.0.z) + 0.5,
+        );
+        let pitch = if powered { 0.6 } else { 0.5 };
+        world
+            .play_sound_raw(
+                Sound::BlockLeverClick as u16,
+                SoundCategory::Blocks,
+                &position,
+                0.3,
+                pitch,
+            )
+            .await;
+        redstone::update_around_source(
+            server,
+            &world,
+            &location,
+            redstone::attached_direction(state_id),
+        )
+        .await;
+    }
+}
+
 #[async_trait]
 impl PumpkinBlock for LeverBlock {
     async fn on_place(
@@ -47,14 +95,30 @@ impl PumpkinBlock for LeverBlock {
             .await
     }
 
+    async fn normal_use(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        Self::toggle(player, location, server).await;
+    }
+
     async fn use_with_item(
         &self,
         _block: &Block,
-        _player: &Player,
-        _location: BlockPos,
+        player: &Player,
+        location: BlockPos,
         _item: &Item,
-        _server: &Server,
+        server: &Server,
     ) -> BlockActionResult {
+        Self::toggle(player, location, server).await;
         BlockActionResult::Consume
     }
+
+    async fn broken(&self, _block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        // The lever may have powered the block it was on
+        redstone::update_around(server, &player.world().await, &location).await;
+    }
 }
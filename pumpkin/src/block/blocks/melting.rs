@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_registry::DimensionType;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    biome::climate,
+    block::registry::Block,
+    light::{self, LightKind},
+};
+
+use crate::{block::pumpkin_block::PumpkinBlock, server::Server, world::World};
+
+use super::fluid::{place_fluid, Fluid, FluidState};
+
+/// The blocks which melt near light sources and in warm biomes
+pub const MELTING: [&str; 2] = ["ice", "snow"];
+
+/// Ice and snow layers melt when the block light gets brighter than this. Sunlight does not
+/// melt them.
+const MAX_LIGHT: u8 = 11;
+
+/// Ice, which melts into water, and snow layers, which melt away
+pub struct MeltingBlock;
+
+#[async_trait]
+impl PumpkinBlock for MeltingBlock {
+    fn has_random_ticks(&self) -> bool {
+        true
+    }
+
+    async fn random_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let block_light = world.get_light(LightKind::Block, &location).await;
+        // Ice lets less light through, so it melts a bit earlier
+        let threshold = if block.name == "ice" {
+            MAX_LIGHT.saturating_sub(light::opacity(state_id))
+        } else {
+            MAX_LIGHT
+        };
+        if block_light <= threshold && !melts_in_weather(world, &location).await {
+            return;
+        }
+
+        // Water evaporates in the nether, so ice melts away there
+        let evaporates = matches!(world.dimension_type, DimensionType::TheNether);
+        if block.name == "ice" && !evaporates {
+            place_fluid(server, world, &location, FluidState::source(Fluid::Water)).await;
+        } else {
+            world.set_block_state(&location, 0).await;
+            world.update_neighbors(server, &location).await;
+        }
+    }
+}
+
+/// Whether the block lies under the open sky where it is too warm for snow and ice, and the
+/// weather is allowed to change blocks
+async fn melts_in_weather(world: &World, location: &BlockPos) -> bool {
+    if !ADVANCED_CONFIG.world.weather_changes_blocks
+        || world.get_light(LightKind::Sky, location).await < light::MAX_LIGHT
+    {
+        return false;
+    }
+    let biome = world.get_biome(location).await;
+    !climate::is_cold_enough_to_snow(biome, location.0)
+}
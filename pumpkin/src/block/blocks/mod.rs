@@ -1,15 +1,48 @@
 use pumpkin_data::screen::WindowType;
 use pumpkin_inventory::{Container, OpenContainer};
 use pumpkin_util::math::position::BlockPos;
-use pumpkin_world::block::registry::Block;
+use pumpkin_world::block::registry::{get_state_id_with_property, get_state_property, Block};
 
-use crate::{entity::player::Player, server::Server};
+use crate::{entity::player::Player, server::Server, world::World};
 
+pub(crate) mod bed;
+pub(crate) mod button;
 pub(crate) mod chest;
+pub(crate) mod container;
 pub(crate) mod crafting_table;
+pub(crate) mod crop;
+pub(crate) mod dispenser;
+pub(crate) mod door;
+pub(crate) mod falling;
+pub(crate) mod farmland;
+pub(crate) mod fire;
+pub(crate) mod fluid;
 pub(crate) mod furnace;
+pub(crate) mod growing_plant;
+pub(crate) mod hopper;
 pub(crate) mod jukebox;
+pub(crate) mod leaves;
 pub(crate) mod lever;
+pub(crate) mod melting;
+pub(crate) mod piston;
+pub(crate) mod pressure_plate;
+pub(crate) mod redstone;
+pub(crate) mod redstone_lamp;
+pub(crate) mod redstone_torch;
+pub(crate) mod redstone_wire;
+pub(crate) mod respawn_anchor;
+pub(crate) mod sapling;
+pub(crate) mod spreading_dirt;
+
+/// Reads an integer property of a block state, like the `age` of a crop
+pub fn get_int_property(state_id: u16, property: &str) -> Option<u32> {
+    get_state_property(state_id, property)?.parse().ok()
+}
+
+/// Gets the block state with an integer property changed, like the `age` of a crop
+pub fn with_int_property(state_id: u16, property: &str, value: u32) -> Option<u16> {
+    get_state_id_with_property(state_id, property, &value.to_string())
+}
 
 /// The standard destroy with container removes the player forcibly from the container,
 /// drops items to the floor, and back to the player's inventory if the item stack is in movement.
@@ -49,6 +82,11 @@ pub async fn standard_open_container<C: Container + Default + 'static>(
         let mut open_containers = server.open_containers.write().await;
         log::debug!("Using previous standard container ID: {}", container_id);
         if let Some(container) = open_containers.get_mut(&u64::from(container_id)) {
+            // Without players, the container is not used, so the items of the block entity might
+            // have been changed meanwhile, e.g. by a hopper
+            if container.get_number_of_players() == 0 {
+                load_container_items(&player.world().await, location, container).await;
+            }
             container.add_player(entity_id);
             player.open_container.store(Some(container_id.into()));
         }
@@ -58,6 +96,7 @@ pub async fn standard_open_container<C: Container + Default + 'static>(
         log::debug!("Creating new standard container ID: {}", new_id);
         let open_container =
             OpenContainer::new_empty_container::<C>(entity_id, Some(location), Some(block.clone()));
+        load_container_items(&player.world().await, location, &open_container).await;
         open_containers.insert(new_id.into(), open_container);
         player.open_container.store(Some(new_id.into()));
     }
@@ -111,6 +150,47 @@ pub async fn standard_open_container_unique<C: Container + Default + 'static>(
     player.open_container(server, window_type).await;
 }
 
+/// Fills a newly opened container with the items of the block entity at its location. A container
+/// placed by world generation gets its loot the first time it is opened.
+async fn load_container_items(world: &World, location: BlockPos, container: &OpenContainer) {
+    let items = world
+        .update_block_entity(&location, |block_entity| {
+            block_entity.unpack_loot();
+            block_entity.slots().map(<[_]>::to_vec)
+        })
+        .await
+        .flatten();
+    if let Some(items) = items {
+        container.set_items(&items).await;
+    }
+}
+
+/// Writes the items of an open container back to the block entity at its location, so they are
+/// saved with the chunk. While a container is open, it holds the current items.
+pub async fn save_container_items(world: &World, location: BlockPos, container: &OpenContainer) {
+    let items = container.get_items().await;
+    world
+        .update_block_entity(&location, |block_entity| {
+            if let Some(slots) = block_entity.slots_mut() {
+                for (slot, item) in slots.iter_mut().zip(items) {
+                    *slot = item;
+                }
+            }
+        })
+        .await;
+}
+
+/// Writes the items of all containers open at a location back to the block entity there.
+/// Containers which all players closed already saved their items.
+pub async fn save_open_containers(server: &Server, world: &World, location: &BlockPos) {
+    let open_containers = server.open_containers.read().await;
+    for container in open_containers.values().filter(|container| {
+        container.is_location(*location) && container.get_number_of_players() > 0
+    }) {
+        save_container_items(world, *location, container).await;
+    }
+}
+
 pub async fn close_all_in_container(player: &Player, container: &OpenContainer) {
     for id in container.all_player_ids() {
         if let Some(remote_player) = player.world().await.get_player_by_id(id).await {
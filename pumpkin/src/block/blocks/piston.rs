@@ -0,0 +1,668 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::{client::play::CBlockAction, codec::var_int::VarInt};
+use pumpkin_util::{
+    math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_world::block::{
+    entity::PistonBlockEntity,
+    registry::{
+        get_block_by_state_id, get_state_by_state_id, get_state_id_by_properties,
+        get_state_id_with_property, get_state_property, Block,
+    },
+    BlockDirection,
+};
+
+use crate::{
+    block::{self, pumpkin_block::PumpkinBlock},
+    entity::player::Player,
+    server::Server,
+    world::World,
+};
+
+use super::redstone;
+
+/// The pistons, which push blocks, and the sticky pistons, which also pull them back
+pub const PISTONS: [&str; 2] = ["piston", "sticky_piston"];
+
+/// How many blocks a piston can push, like in vanilla
+const PUSH_LIMIT: usize = 12;
+
+/// How many ticks a block moved by a piston takes to arrive
+const MOVE_TICKS: u64 = 2;
+
+/// The block events which tell clients to animate a piston
+const EXTEND_EVENT: u8 = 0;
+const RETRACT_EVENT: u8 = 1;
+
+/// Blocks which pistons can't move, in addition to blocks which can't be broken and most blocks
+/// with a block entity
+const IMMOVABLE: [&str; 5] = [
+    "obsidian",
+    "crying_obsidian",
+    "respawn_anchor",
+    "reinforced_deepslate",
+    "piston_head",
+];
+
+/// Blocks which are broken and dropped when a piston pushes them, in addition to blocks without
+/// collision like torches, redstone wire and flowers
+const DESTROYED: [&str; 13] = [
+    "cactus",
+    "pumpkin",
+    "carved_pumpkin",
+    "jack_o_lantern",
+    "melon",
+    "dragon_egg",
+    "cocoa",
+    "bamboo",
+    "chorus_plant",
+    "chorus_flower",
+    "lantern",
+    "soul_lantern",
+    "flower_pot",
+];
+
+/// Like [`DESTROYED`], for the blocks of many materials or colors
+const DESTROYED_SUFFIXES: [&str; 5] = ["_bed", "_door", "_head", "_skull", "_pressure_plate"];
+
+/// What happens to a block when a piston pushes it
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum PushReaction {
+    /// The block moves with the piston
+    Normal,
+    /// The block is broken and dropped, which also stops the piston from pushing further
+    Destroy,
+    /// The piston can't move the block, so it doesn't extend
+    Block,
+}
+
+fn push_reaction(state_id: u16) -> PushReaction {
+    let (Some(block), Some(state)) = (
+        get_block_by_state_id(state_id),
+        get_state_by_state_id(state_id),
+    ) else {
+        return PushReaction::Block;
+    };
+    let name = block.name.as_str();
+    if PISTONS.contains(&name) {
+        // Extended pistons can't be moved together with their head
+        return if is_on(state_id, "extended") {
+            PushReaction::Block
+        } else {
+            PushReaction::Normal
+        };
+    }
+    if IMMOVABLE.contains(&name) || state.hardness < 0.0 {
+        return PushReaction::Block;
+    }
+    let without_collision = state.collision_shapes.is_empty() && !name.ends_with("rail");
+    if state.replaceable
+        || without_collision
+        || DESTROYED.contains(&name)
+        || name.starts_with("potted_")
+        || DESTROYED_SUFFIXES
+            .iter()
+            .any(|suffix| name.ends_with(suffix))
+    {
+        return PushReaction::Destroy;
+    }
+    if state.block_entity_type.is_some() {
+        return PushReaction::Block;
+    }
+    PushReaction::Normal
+}
+
+fn is_on(state_id: u16, property: &str) -> bool {
+    get_state_property(state_id, property) == Some("true")
+}
+
+fn is_sticky(block: &Block) -> bool {
+    block.name == "sticky_piston"
+}
+
+fn facing(state_id: u16) -> Option<BlockDirection> {
+    redstone::direction_from_name(get_state_property(state_id, "facing")?)
+}
+
+/// The state of a block used by pistons, like the piston head, with the given facing and type
+fn piston_part_state(name: &str, facing: BlockDirection, sticky: bool) -> Option<u16> {
+    let properties = HashMap::from([
+        (
+            "facing".to_string(),
+            redstone::direction_name(facing).to_string(),
+        ),
+        (
+            "type".to_string(),
+            if sticky { "sticky" } else { "normal" }.to_string(),
+        ),
+    ]);
+    get_state_id_by_properties(name, &properties)
+}
+
+/// The blocks a piston pushes when it extends
+struct PushedBlocks {
+    /// The blocks which move, nearest to the piston first
+    moved: Vec<(BlockPos, u16)>,
+    /// The block which is broken, if pushing stops at one
+    destroyed: Option<(BlockPos, u16)>,
+}
+
+/// Finds the blocks in front of a piston which it pushes, or `None` if it can't extend because
+/// an immovable block, the end of the world or more than [`PUSH_LIMIT`] blocks are in the way
+async fn pushed_blocks(
+    world: &World,
+    location: &BlockPos,
+    facing: BlockDirection,
+) -> Option<PushedBlocks> {
+    let mut pushed = PushedBlocks {
+        moved: Vec::new(),
+        destroyed: None,
+    };
+    let mut position = *location;
+    loop {
+        position = redstone::offset(&position, facing);
+        let state_id = redstone::state_id_at(world, &position).await?;
+        if get_state_by_state_id(state_id).is_some_and(|state| state.air) {
+            return Some(pushed);
+        }
+        match push_reaction(state_id) {
+            PushReaction::Block => return None,
+            PushReaction::Destroy => {
+                pushed.destroyed = Some((position, state_id));
+                return Some(pushed);
+            }
+            PushReaction::Normal => {
+                if pushed.moved.len() == PUSH_LIMIT {
+                    return None;
+                }
+                pushed.moved.push((position, state_id));
+            }
+        }
+    }
+}
+
+/// Whether a piston is powered. Like in vanilla, pistons are not powered from the front, but with
+/// quasi-connectivity also like the block above them.
+async fn should_extend(world: &World, location: &BlockPos, facing: BlockDirection) -> bool {
+    for direction in BlockDirection::all() {
+        if direction != facing
+            && redstone::power_from(
+                world,
+                &redstone::offset(location, direction),
+                direction.opposite(),
+                true,
+            )
+            .await
+                > 0
+        {
+            return true;
+        }
+    }
+    if ADVANCED_CONFIG.world.quasi_connectivity {
+        let above = redstone::offset(location, BlockDirection::Top);
+        for direction in BlockDirection::all() {
+            if direction != BlockDirection::Bottom
+                && redstone::power_from(
+                    world,
+                    &redstone::offset(&above, direction),
+                    direction.opposite(),
+                    true,
+                )
+                .await
+                    > 0
+            {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Replaces the block at the position with a moving piston block, which becomes the moved block
+/// after [`MOVE_TICKS`]
+async fn start_moving(
+    world: &World,
+    position: &BlockPos,
+    moved_state_id: u16,
+    facing: BlockDirection,
+    extending: bool,
+    source: bool,
+    sticky: bool,
+) {
+    let Some(moving) = piston_part_state("minecraft:moving_piston", facing, sticky) else {
+        return;
+    };
+    world.set_block_state(position, moving).await;
+    world
+        .update_block_entity(position, |block_entity| {
+            if let Some(piston) = block_entity
+                .as_any_mut()
+                .downcast_mut::<PistonBlockEntity>()
+            {
+                piston.block_state = moved_state_id;
+                piston.facing = facing;
+                piston.extending = extending;
+                piston.source = source;
+            }
+        })
+        .await;
+    if let Some(block) = get_block_by_state_id(moving) {
+        world.schedule_block_tick(*position, block, MOVE_TICKS);
+    }
+}
+
+/// Places the block which is moving at the position. The block is updated, like its neighbors,
+/// so e.g. a retracted piston extends again if it is still powered.
+pub async fn finish_moving(server: &Server, world: &World, location: &BlockPos) {
+    let moved_state_id = world
+        .get_block_entity(location, |block_entity| {
+            block_entity
+                .as_any()
+                .downcast_ref::<PistonBlockEntity>()
+                .map(|piston| piston.block_state)
+        })
+        .await
+        .flatten();
+    let Some(moved_state_id) = moved_state_id else {
+        return;
+    };
+    world.set_block_state(location, moved_state_id).await;
+    world.update_neighbors(server, location).await;
+    if let Ok(block) = world.get_block(location).await {
+        server
+            .block_registry
+            .on_neighbor_update(server, world, block, *location, *location)
+            .await;
+    }
+}
+
+fn is_moving_piston(state_id: Option<u16>) -> bool {
+    state_id
+        .and_then(get_block_by_state_id)
+        .is_some_and(|block| block.name == "moving_piston")
+}
+
+/// Moves the entities in the way of the pushed blocks one block further, so they are not stuck in
+/// them
+async fn push_entities(world: &World, positions: &[BlockPos], facing: BlockDirection) {
+    let boxes: Vec<BoundingBox> = positions
+        .iter()
+        .map(|position| {
+            let min = Vector3::new(
+                f64::from(position.0.x),
+                f64::from(position.0.y),
+                f64::from(position.0.z),
+            );
+            BoundingBox::new(min, Vector3::new(min.x + 1.0, min.y + 1.0, min.z + 1.0))
+        })
+        .collect();
+    let in_the_way = |bounding_box: &BoundingBox| {
+        boxes
+            .iter()
+            .any(|block_box| bounding_box.intersects(block_box))
+    };
+    let offset = facing.to_offset();
+    let offset = Vector3::new(
+        f64::from(offset.x),
+        f64::from(offset.y),
+        f64::from(offset.z),
+    );
+
+    let players: Vec<_> = world.players.read().await.values().cloned().collect();
+    for player in players {
+        let entity = &player.living_entity.entity;
+        if player.gamemode.load() != GameMode::Spectator && in_the_way(&entity.bounding_box.load())
+        {
+            player
+                .request_teleport(
+                    entity.pos.load().add(&offset),
+                    entity.yaw.load(),
+                    entity.pitch.load(),
+                )
+                .await;
+        }
+    }
+    let entities: Vec<_> = world.entities.read().await.values().cloned().collect();
+    for entity in entities {
+        let entity = entity.get_entity();
+        if in_the_way(&entity.bounding_box.load()) {
+            entity
+                .teleport(
+                    entity.pos.load().add(&offset),
+                    entity.yaw.load(),
+                    entity.pitch.load(),
+                )
+                .await;
+        }
+    }
+}
+
+/// A piston, which pushes up to [`PUSH_LIMIT`] blocks in front of it while it is powered. Sticky
+/// pistons also pull the block in front of them back when they retract.
+///
+/// TODO: Slime and honey blocks should push and pull the blocks stuck to them
+pub struct PistonBlock;
+
+impl PistonBlock {
+    /// Extends or retracts the piston if its power changed
+    async fn update(server: &Server, world: &World, block: &Block, location: BlockPos) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let Some(facing) = facing(state_id) else {
+            return;
+        };
+        let extended = is_on(state_id, "extended");
+        let powered = should_extend(world, &location, facing).await;
+        if powered && !extended {
+            Self::extend(server, world, block, location, state_id, facing).await;
+        } else if !powered && extended {
+            Self::retract(server, world, block, location, state_id, facing).await;
+        }
+    }
+
+    async fn extend(
+        server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        state_id: u16,
+        facing: BlockDirection,
+    ) {
+        let Some(pushed) = pushed_blocks(world, &location, facing).await else {
+            return;
+        };
+        let Some(extended) = get_state_id_with_property(state_id, "extended", "true") else {
+            return;
+        };
+        let sticky = is_sticky(block);
+        world
+            .broadcast_packet_all(&CBlockAction::new(
+                &location,
+                EXTEND_EVENT,
+                facing as u8,
+                VarInt(i32::from(block.id)),
+            ))
+            .await;
+        world
+            .play_block_sound(Sound::BlockPistonExtend, SoundCategory::Blocks, location)
+            .await;
+
+        let head = redstone::offset(&location, facing);
+        let mut changed = vec![location, head];
+        changed.extend(
+            pushed
+                .moved
+                .iter()
+                .map(|(position, _)| redstone::offset(position, facing)),
+        );
+        push_entities(world, &changed[1..], facing).await;
+
+        if let Some((position, destroyed_state_id)) = pushed.destroyed {
+            world.set_block_state(&position, 0).await;
+            let destroyed = get_block_by_state_id(destroyed_state_id);
+            if let (Some(destroyed), Some(world)) =
+                (destroyed, server.get_world_handle(world).await)
+            {
+                if destroyed.item_id != 0 {
+                    block::drop_loot(server, &world, destroyed, &position).await;
+                }
+            }
+            changed.push(position);
+        }
+        // The farthest block moves first, so each block moves into a free space
+        for (position, moved_state_id) in pushed.moved.iter().rev() {
+            let destination = redstone::offset(position, facing);
+            start_moving(
+                world,
+                &destination,
+                *moved_state_id,
+                facing,
+                true,
+                false,
+                sticky,
+            )
+            .await;
+        }
+        if let Some(head_state_id) = piston_part_state("minecraft:piston_head", facing, sticky) {
+            start_moving(world, &head, head_state_id, facing, true, true, sticky).await;
+        }
+        world.set_block_state(&location, extended).await;
+
+        for position in changed {
+            world.update_neighbors(server, &position).await;
+        }
+    }
+
+    async fn retract(
+        server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        state_id: u16,
+        facing: BlockDirection,
+    ) {
+        let Some(retracted) = get_state_id_with_property(state_id, "extended", "false") else {
+            return;
+        };
+        let sticky = is_sticky(block);
+        let head = redstone::offset(&location, facing);
+        // A head which is still moving out arrives at once
+        if is_moving_piston(redstone::state_id_at(world, &head).await) {
+            finish_moving(server, world, &head).await;
+        }
+        world
+            .broadcast_packet_all(&CBlockAction::new(
+                &location,
+                RETRACT_EVENT,
+                facing as u8,
+                VarInt(i32::from(block.id)),
+            ))
+            .await;
+        world
+            .play_block_sound(Sound::BlockPistonContract, SoundCategory::Blocks, location)
+            .await;
+
+        start_moving(world, &location, retracted, facing, false, true, sticky).await;
+        let mut changed = vec![location, head];
+
+        let pulled_position = redstone::offset(&head, facing);
+        let pulled = if sticky {
+            redstone::state_id_at(world, &pulled_position)
+                .await
+                .filter(|state_id| {
+                    get_state_by_state_id(*state_id).is_some_and(|state| !state.air)
+                        && push_reaction(*state_id) == PushReaction::Normal
+                })
+        } else {
+            None
+        };
+        if let Some(pulled_state_id) = pulled {
+            start_moving(world, &head, pulled_state_id, facing, false, false, sticky).await;
+            world.set_block_state(&pulled_position, 0).await;
+            changed.push(pulled_position);
+        } else if redstone::state_id_at(world, &head)
+            .await
+            .and_then(get_block_by_state_id)
+            .is_some_and(|head| head.name == "piston_head")
+        {
+            world.set_block_state(&head, 0).await;
+        }
+
+        for position in changed {
+            world.update_neighbors(server, &position).await;
+        }
+    }
+
+    /// The direction a player looks in, which is where a piston they place faces away from
+    pub fn looking_direction(player: &Player) -> BlockDirection {
+        let entity = &player.living_entity.entity;
+        let pitch = entity.pitch.load();
+        if pitch < -45.0 {
+            return BlockDirection::Top;
+        }
+        if pitch > 45.0 {
+            return BlockDirection::Bottom;
+        }
+        let yaw = (entity.yaw.load() % 360.0 + 360.0) % 360.0;
+        match yaw {
+            45.0..=135.0 => BlockDirection::West,
+            135.0..=225.0 => BlockDirection::North,
+            225.0..=315.0 => BlockDirection::East,
+            _ => BlockDirection::South,
+        }
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for PistonBlock {
+    async fn placed(&self, block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        let world = player.world().await;
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        // Like in vanilla, pistons face the player, also up and down
+        let facing = Self::looking_direction(player).opposite();
+        if let Some(new_state_id) =
+            get_state_id_with_property(state_id, "facing", redstone::direction_name(facing))
+        {
+            world.set_block_state(&location, new_state_id).await;
+        }
+        Self::update(server, &world, block, location).await;
+    }
+
+    async fn broken(&self, _block: &Block, player: &Player, location: BlockPos, _server: &Server) {
+        let world = player.world().await;
+        for direction in BlockDirection::all() {
+            let position = redstone::offset(&location, direction);
+            let Some(state_id) = redstone::state_id_at(&world, &position).await else {
+                continue;
+            };
+            let is_own_head = get_block_by_state_id(state_id)
+                .is_some_and(|block| block.name == "piston_head")
+                && facing(state_id) == Some(direction);
+            if is_own_head {
+                world.set_block_state(&position, 0).await;
+            }
+        }
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        Self::update(server, world, block, location).await;
+    }
+}
+
+/// The head of an extended piston. Breaking it also breaks the piston.
+#[pumpkin_block("minecraft:piston_head")]
+pub struct PistonHeadBlock;
+
+#[async_trait]
+impl PumpkinBlock for PistonHeadBlock {
+    async fn broken(&self, _block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        let world = player.world().await;
+        for direction in BlockDirection::all() {
+            let position = redstone::offset(&location, direction);
+            let Some(state_id) = redstone::state_id_at(&world, &position).await else {
+                continue;
+            };
+            let is_own_piston = get_block_by_state_id(state_id)
+                .is_some_and(|block| PISTONS.contains(&block.name.as_str()))
+                && is_on(state_id, "extended")
+                && facing(state_id) == Some(direction.opposite());
+            if is_own_piston {
+                let drop = player.gamemode.load() != GameMode::Creative;
+                world.break_block(server, &position, None, drop).await;
+            }
+        }
+    }
+}
+
+/// A block which a piston is moving. It becomes the moved block when it arrives.
+#[pumpkin_block("minecraft:moving_piston")]
+pub struct MovingPistonBlock;
+
+#[async_trait]
+impl PumpkinBlock for MovingPistonBlock {
+    async fn on_scheduled_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        _block: &Block,
+        _state_id: u16,
+        location: BlockPos,
+    ) {
+        finish_moving(server, world, &location).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::block::registry::get_block;
+
+    use super::*;
+
+    fn default_state(name: &str) -> u16 {
+        get_block(name).unwrap().default_state_id
+    }
+
+    #[test]
+    fn push_reactions() {
+        assert_eq!(
+            push_reaction(default_state("minecraft:stone")),
+            PushReaction::Normal
+        );
+        assert_eq!(
+            push_reaction(default_state("minecraft:obsidian")),
+            PushReaction::Block
+        );
+        assert_eq!(
+            push_reaction(default_state("minecraft:bedrock")),
+            PushReaction::Block
+        );
+        assert_eq!(
+            push_reaction(default_state("minecraft:chest")),
+            PushReaction::Block
+        );
+        assert_eq!(
+            push_reaction(default_state("minecraft:torch")),
+            PushReaction::Destroy
+        );
+        assert_eq!(
+            push_reaction(default_state("minecraft:oak_door")),
+            PushReaction::Destroy
+        );
+        assert_eq!(
+            push_reaction(default_state("minecraft:rail")),
+            PushReaction::Normal
+        );
+    }
+
+    #[test]
+    fn extended_pistons_are_immovable() {
+        let piston = default_state("minecraft:piston");
+        assert_eq!(push_reaction(piston), PushReaction::Normal);
+        let extended = get_state_id_with_property(piston, "extended", "true").unwrap();
+        assert_eq!(push_reaction(extended), PushReaction::Block);
+    }
+
+    #[test]
+    fn piston_head_state() {
+        let head = piston_part_state("minecraft:piston_head", BlockDirection::Top, true).unwrap();
+        assert_eq!(get_state_property(head, "facing"), Some("up"));
+        assert_eq!(get_state_property(head, "type"), Some("sticky"));
+        assert_eq!(get_state_property(head, "short"), Some("false"));
+    }
+}
@@ -0,0 +1,155 @@
+use async_trait::async_trait;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::{
+    math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_world::block::{
+    registry::{get_state_id_with_property, get_state_property, Block},
+    BlockDirection,
+};
+
+use crate::{
+    block::pumpkin_block::PumpkinBlock, entity::player::Player, entity::EntityBase, server::Server,
+    world::World,
+};
+
+use super::redstone;
+
+/// The pressure plates which are pressed by any entity or only by mobs and players. Weighted
+/// pressure plates are not included, as their power depends on how many entities are on them.
+pub const PRESSURE_PLATES: [&str; 14] = [
+    "stone_pressure_plate",
+    "polished_blackstone_pressure_plate",
+    "oak_pressure_plate",
+    "spruce_pressure_plate",
+    "birch_pressure_plate",
+    "jungle_pressure_plate",
+    "acacia_pressure_plate",
+    "cherry_pressure_plate",
+    "dark_oak_pressure_plate",
+    "pale_oak_pressure_plate",
+    "mangrove_pressure_plate",
+    "bamboo_pressure_plate",
+    "crimson_pressure_plate",
+    "warped_pressure_plate",
+];
+
+/// How many ticks a pressed plate waits before it checks whether something is still on it, like
+/// in vanilla
+const PRESSED_TICKS: u64 = 20;
+
+/// Like in vanilla, stone plates are only pressed by mobs and players, wooden ones by any entity
+fn is_stone(block: &Block) -> bool {
+    matches!(
+        block.name.as_str(),
+        "stone_pressure_plate" | "polished_blackstone_pressure_plate"
+    )
+}
+
+/// A pressure plate, which powers the blocks around it and strongly powers the block below while
+/// entities stand on it
+pub struct PressurePlateBlock;
+
+impl PressurePlateBlock {
+    /// Whether entities which press the plate are on it
+    async fn is_pressed(world: &World, block: &Block, location: &BlockPos) -> bool {
+        let (x, y, z) = (
+            f64::from(location.0.x),
+            f64::from(location.0.y),
+            f64::from(location.0.z),
+        );
+        let area = BoundingBox::new(
+            Vector3::new(x + 0.125, y, z + 0.125),
+            Vector3::new(x + 0.875, y + 0.25, z + 0.875),
+        );
+        let players_on = world.players.read().await.values().any(|player| {
+            player.gamemode.load() != GameMode::Spectator
+                && player
+                    .living_entity
+                    .entity
+                    .bounding_box
+                    .load()
+                    .intersects(&area)
+        });
+        if players_on {
+            return true;
+        }
+        let mobs_only = is_stone(block);
+        world.entities.read().await.values().any(|entity| {
+            (!mobs_only || entity.get_living_entity().is_some())
+                && entity.get_entity().bounding_box.load().intersects(&area)
+        })
+    }
+
+    /// Powers the plate while it is pressed. A pressed plate checks again later, as nothing
+    /// tells it when the entities leave.
+    async fn update(
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let pressed = Self::is_pressed(world, block, &location).await;
+        let powered = get_state_property(state_id, "powered") == Some("true");
+        if pressed != powered {
+            if let Some(new_state_id) =
+                get_state_id_with_property(state_id, "powered", &pressed.to_string())
+            {
+                world.set_block_state(&location, new_state_id).await;
+                let sound = match (is_stone(block), pressed) {
+                    (true, true) => Sound::BlockStonePressurePlateClickOn,
+                    (true, false) => Sound::BlockStonePressurePlateClickOff,
+                    (false, true) => Sound::BlockWoodenPressurePlateClickOn,
+                    (false, false) => Sound::BlockWoodenPressurePlateClickOff,
+                };
+                world
+                    .play_block_sound(sound, SoundCategory::Blocks, location)
+                    .await;
+                redstone::update_around_source(
+                    server,
+                    world,
+                    &location,
+                    Some(BlockDirection::Bottom),
+                )
+                .await;
+            }
+        }
+        if pressed {
+            world.schedule_block_tick(location, block, PRESSED_TICKS);
+        }
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for PressurePlateBlock {
+    async fn on_entity_collision(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+        _entity: &dyn EntityBase,
+    ) {
+        if get_state_property(state_id, "powered") != Some("true") {
+            Self::update(server, world, block, state_id, location).await;
+        }
+    }
+
+    async fn on_scheduled_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        Self::update(server, world, block, state_id, location).await;
+    }
+
+    async fn broken(&self, _block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        redstone::update_around(server, &player.world().await, &location).await;
+    }
+}
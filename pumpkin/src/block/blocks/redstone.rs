@@ -0,0 +1,309 @@
+//! The redstone signal model. Power sources like levers and redstone torches emit power from 0 to
+//! 15 into the blocks next to them. Weak power only reaches the block next to the source, while
+//! strong power also passes through conductors like stone into the blocks around them.
+
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    block::{
+        registry::{get_block_by_state_id, get_state_by_state_id, get_state_property},
+        BlockDirection,
+    },
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+
+use crate::{server::Server, world::World};
+
+use super::{button::BUTTONS, get_int_property, piston::PISTONS, pressure_plate::PRESSURE_PLATES};
+
+/// The strongest power, which sources emit
+pub const MAX_POWER: u32 = 15;
+
+/// The block next to the position in the direction
+pub fn offset(position: &BlockPos, direction: BlockDirection) -> BlockPos {
+    BlockPos(position.0 + direction.to_offset())
+}
+
+/// The block state at the position, or `None` if it is outside of the world or of the loaded
+/// chunks, which neither emit nor receive power
+pub async fn state_id_at(world: &World, position: &BlockPos) -> Option<u16> {
+    let in_world = (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&position.0.y);
+    if !in_world || !world.is_loaded(position) {
+        return None;
+    }
+    world.get_block_state_id(position).await.ok()
+}
+
+/// The direction of a `facing` property value, e.g. `north`
+pub fn direction_from_name(name: &str) -> Option<BlockDirection> {
+    match name {
+        "down" => Some(BlockDirection::Bottom),
+        "up" => Some(BlockDirection::Top),
+        "north" => Some(BlockDirection::North),
+        "south" => Some(BlockDirection::South),
+        "west" => Some(BlockDirection::West),
+        "east" => Some(BlockDirection::East),
+        _ => None,
+    }
+}
+
+/// The `facing` property value of a direction
+pub const fn direction_name(direction: BlockDirection) -> &'static str {
+    match direction {
+        BlockDirection::Bottom => "down",
+        BlockDirection::Top => "up",
+        BlockDirection::North => "north",
+        BlockDirection::South => "south",
+        BlockDirection::West => "west",
+        BlockDirection::East => "east",
+    }
+}
+
+fn is_on(state_id: u16, property: &str) -> bool {
+    get_state_property(state_id, property) == Some("true")
+}
+
+/// Whether power passes through the block, which is the case for full opaque blocks like stone,
+/// but not for glass, slabs or pistons
+pub fn is_conductor(state_id: u16) -> bool {
+    let is_piston =
+        get_block_by_state_id(state_id).is_some_and(|block| PISTONS.contains(&block.name.as_str()));
+    !is_piston
+        && get_state_by_state_id(state_id).is_some_and(|state| {
+            !state.air && state.opacity == Some(15) && state.collision_shapes == [0]
+        })
+}
+
+/// The direction from a lever, button, pressure plate or redstone torch to the block it is
+/// attached to
+pub fn attached_direction(state_id: u16) -> Option<BlockDirection> {
+    let block = get_block_by_state_id(state_id)?;
+    match block.name.as_str() {
+        "redstone_torch" => Some(BlockDirection::Bottom),
+        "redstone_wall_torch" => direction_from_name(get_state_property(state_id, "facing")?)
+            .map(|facing| facing.opposite()),
+        name if PRESSURE_PLATES.contains(&name) => Some(BlockDirection::Bottom),
+        _ => match get_state_property(state_id, "face")? {
+            "floor" => Some(BlockDirection::Bottom),
+            "ceiling" => Some(BlockDirection::Top),
+            _ => direction_from_name(get_state_property(state_id, "facing")?)
+                .map(|facing| facing.opposite()),
+        },
+    }
+}
+
+/// Whether redstone wire connects to the block, which it does to other wire and power sources
+pub fn is_signal_source(state_id: u16) -> bool {
+    get_block_by_state_id(state_id).is_some_and(|block| {
+        let name = block.name.as_str();
+        matches!(
+            name,
+            "redstone_wire" | "redstone_block" | "redstone_torch" | "redstone_wall_torch" | "lever"
+        ) || BUTTONS.contains(&name)
+            || PRESSURE_PLATES.contains(&name)
+    })
+}
+
+/// The power the block state emits into its neighbor in the direction. Strong power also powers
+/// conductors, so they pass it on. Power of redstone wire is left out if `wire` is false, which
+/// wire needs to calculate its own power.
+pub fn emitted_power(state_id: u16, direction: BlockDirection, strong: bool, wire: bool) -> u32 {
+    let Some(block) = get_block_by_state_id(state_id) else {
+        return 0;
+    };
+    match block.name.as_str() {
+        "redstone_block" if !strong => MAX_POWER,
+        "redstone_wire" if wire => {
+            let connected = direction == BlockDirection::Bottom
+                || wire_side_property(direction)
+                    .and_then(|property| get_state_property(state_id, property))
+                    .is_some_and(|side| side != "none");
+            if connected {
+                get_int_property(state_id, "power").unwrap_or(0)
+            } else {
+                0
+            }
+        }
+        "redstone_torch" | "redstone_wall_torch" if is_on(state_id, "lit") => {
+            let powers = if strong {
+                direction == BlockDirection::Top
+            } else {
+                attached_direction(state_id) != Some(direction)
+            };
+            if powers {
+                MAX_POWER
+            } else {
+                0
+            }
+        }
+        name if (name == "lever" || BUTTONS.contains(&name) || PRESSURE_PLATES.contains(&name))
+            && is_on(state_id, "powered") =>
+        {
+            if !strong || attached_direction(state_id) == Some(direction) {
+                MAX_POWER
+            } else {
+                0
+            }
+        }
+        _ => 0,
+    }
+}
+
+/// The property of redstone wire for the side in the direction, which is `None` above and below
+pub const fn wire_side_property(direction: BlockDirection) -> Option<&'static str> {
+    match direction {
+        BlockDirection::North => Some("north"),
+        BlockDirection::South => Some("south"),
+        BlockDirection::West => Some("west"),
+        BlockDirection::East => Some("east"),
+        BlockDirection::Bottom | BlockDirection::Top => None,
+    }
+}
+
+/// The strong power the block at the position gets from its neighbors, which a conductor passes on
+async fn strong_power_into(world: &World, position: &BlockPos, wire: bool) -> u32 {
+    let mut power = 0;
+    for direction in BlockDirection::all() {
+        if let Some(state_id) = state_id_at(world, &offset(position, direction)).await {
+            power = power.max(emitted_power(state_id, direction.opposite(), true, wire));
+        }
+    }
+    power
+}
+
+/// The power the block at the position gives its neighbor in the direction. Conductors give the
+/// strong power they get from their neighbors.
+pub async fn power_from(
+    world: &World,
+    position: &BlockPos,
+    direction: BlockDirection,
+    wire: bool,
+) -> u32 {
+    let Some(state_id) = state_id_at(world, position).await else {
+        return 0;
+    };
+    let power = emitted_power(state_id, direction, false, wire);
+    if power < MAX_POWER && is_conductor(state_id) {
+        return power.max(strong_power_into(world, position, wire).await);
+    }
+    power
+}
+
+/// The strongest power the block at the position gets from any of its neighbors
+pub async fn received_power(world: &World, position: &BlockPos, wire: bool) -> u32 {
+    let mut power = 0;
+    for direction in BlockDirection::all() {
+        power = power.max(
+            power_from(
+                world,
+                &offset(position, direction),
+                direction.opposite(),
+                wire,
+            )
+            .await,
+        );
+        if power == MAX_POWER {
+            break;
+        }
+    }
+    power
+}
+
+/// Whether the block at the position is powered by any of its neighbors
+pub async fn is_powered(world: &World, position: &BlockPos) -> bool {
+    received_power(world, position, true).await > 0
+}
+
+/// Updates the neighbors of a power source whose power changed, and the neighbors of the block it
+/// is attached to, which it powers strongly
+pub async fn update_around_source(
+    server: &Server,
+    world: &World,
+    location: &BlockPos,
+    attached: Option<BlockDirection>,
+) {
+    world.update_neighbors(server, location).await;
+    if let Some(attached) = attached {
+        world
+            .update_neighbors(server, &offset(location, attached))
+            .await;
+    }
+}
+
+/// Updates the neighbors of the position and the neighbors of each of them, as a source which
+/// powers blocks in all directions changed
+pub async fn update_around(server: &Server, world: &World, location: &BlockPos) {
+    world.update_neighbors(server, location).await;
+    for direction in BlockDirection::update_order() {
+        world
+            .update_neighbors(server, &offset(location, direction))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::block::registry::get_state_id_by_properties;
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn state(block: &str, properties: &[(&str, &str)]) -> u16 {
+        let properties: HashMap<String, String> = properties
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect();
+        get_state_id_by_properties(block, &properties).unwrap()
+    }
+
+    #[test]
+    fn torch_does_not_power_its_block() {
+        let torch = state("minecraft:redstone_torch", &[("lit", "true")]);
+        assert_eq!(emitted_power(torch, BlockDirection::Bottom, false, true), 0);
+        assert_eq!(
+            emitted_power(torch, BlockDirection::North, false, true),
+            MAX_POWER
+        );
+        assert_eq!(
+            emitted_power(torch, BlockDirection::Top, true, true),
+            MAX_POWER
+        );
+        assert_eq!(emitted_power(torch, BlockDirection::North, true, true), 0);
+    }
+
+    #[test]
+    fn lever_strongly_powers_its_block() {
+        let lever = state(
+            "minecraft:lever",
+            &[("face", "wall"), ("facing", "north"), ("powered", "true")],
+        );
+        // The lever faces away from the block it is on
+        assert_eq!(attached_direction(lever), Some(BlockDirection::South));
+        assert_eq!(
+            emitted_power(lever, BlockDirection::South, true, true),
+            MAX_POWER
+        );
+        assert_eq!(emitted_power(lever, BlockDirection::North, true, true), 0);
+        assert_eq!(
+            emitted_power(lever, BlockDirection::North, false, true),
+            MAX_POWER
+        );
+    }
+
+    #[test]
+    fn wire_powers_connected_sides() {
+        let wire = state(
+            "minecraft:redstone_wire",
+            &[
+                ("north", "side"),
+                ("south", "side"),
+                ("east", "none"),
+                ("west", "none"),
+                ("power", "7"),
+            ],
+        );
+        assert_eq!(emitted_power(wire, BlockDirection::North, false, true), 7);
+        assert_eq!(emitted_power(wire, BlockDirection::Bottom, true, true), 7);
+        assert_eq!(emitted_power(wire, BlockDirection::East, false, true), 0);
+        assert_eq!(emitted_power(wire, BlockDirection::North, false, false), 0);
+    }
+}
@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::server::play::SUseItemOn;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::{
+    registry::{get_state_id_with_property, get_state_property, Block},
+    BlockDirection,
+};
+
+use crate::{
+    block::{properties::Direction, pumpkin_block::PumpkinBlock},
+    server::Server,
+    world::World,
+};
+
+use super::redstone;
+
+/// How many ticks a lamp stays lit after it lost its power, like in vanilla
+const TURN_OFF_DELAY: u64 = 4;
+
+/// A redstone lamp, which lights up while it is powered
+#[pumpkin_block("minecraft:redstone_lamp")]
+pub struct RedstoneLampBlock;
+
+#[async_trait]
+impl PumpkinBlock for RedstoneLampBlock {
+    async fn on_place(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        face: &BlockDirection,
+        block_pos: &BlockPos,
+        use_item_on: &SUseItemOn,
+        player_direction: &Direction,
+        other: bool,
+    ) -> u16 {
+        let state_id = server
+            .block_properties_manager
+            .on_place_state(
+                world,
+                block,
+                face,
+                block_pos,
+                use_item_on,
+                player_direction,
+                other,
+            )
+            .await;
+        let lit = redstone::is_powered(world, block_pos).await;
+        get_state_id_with_property(state_id, "lit", &lit.to_string()).unwrap_or(state_id)
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let lit = get_state_property(state_id, "lit") == Some("true");
+        let powered = redstone::is_powered(world, &location).await;
+        if lit && !powered {
+            world.schedule_block_tick(location, block, TURN_OFF_DELAY);
+        } else if !lit && powered {
+            if let Some(new_state_id) = get_state_id_with_property(state_id, "lit", "true") {
+                world.set_block_state(&location, new_state_id).await;
+            }
+        }
+    }
+
+    async fn on_scheduled_tick(
+        &self,
+        _server: &Server,
+        world: &World,
+        _block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        if get_state_property(state_id, "lit") == Some("true")
+            && !redstone::is_powered(world, &location).await
+        {
+            if let Some(new_state_id) = get_state_id_with_property(state_id, "lit", "false") {
+                world.set_block_state(&location, new_state_id).await;
+            }
+        }
+    }
+}
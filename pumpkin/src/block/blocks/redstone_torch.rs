@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_protocol::server::play::SUseItemOn;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::{
+    registry::{get_state_id_with_property, get_state_property, Block},
+    BlockDirection,
+};
+
+use crate::{
+    block::{properties::Direction, pumpkin_block::PumpkinBlock},
+    entity::player::Player,
+    server::Server,
+    world::World,
+};
+
+use super::redstone;
+
+/// The redstone torches standing on a block and on the side of a block
+pub const REDSTONE_TORCHES: [&str; 2] = ["redstone_torch", "redstone_wall_torch"];
+
+/// How many ticks a torch waits before it reacts to a change of its power, like in vanilla
+const TOGGLE_DELAY: u64 = 2;
+/// Like in vanilla, toggles of the last this many ticks count towards burning out
+const RECENT_TOGGLE_TICKS: i64 = 60;
+/// Like in vanilla, a torch which toggled this often recently burns out
+const MAX_RECENT_TOGGLES: usize = 8;
+/// How many ticks a burnt out torch waits before it may light again, like in vanilla
+const RESTART_DELAY: u64 = 160;
+
+/// A redstone torch, which is lit unless the block it is on is powered. Lit torches power the
+/// blocks around them, except for the one they are on, and strongly power the block above.
+///
+/// Torches which toggle too often, like in a clock which is too fast, burn out for a while.
+pub struct RedstoneTorchBlock;
+
+impl RedstoneTorchBlock {
+    /// Whether the block the torch is on is powered, which turns the torch off
+    async fn has_power(world: &World, state_id: u16, location: &BlockPos) -> bool {
+        let Some(attached) = redstone::attached_direction(state_id) else {
+            return false;
+        };
+        redstone::power_from(
+            world,
+            &redstone::offset(location, attached),
+            attached.opposite(),
+            true,
+        )
+        .await
+            > 0
+    }
+
+    /// Whether the torch toggled too often recently, optionally counting a new toggle first
+    async fn is_toggled_too_often(world: &World, location: &BlockPos, add_toggle: bool) -> bool {
+        let now = world.level_time.lock().await.world_age;
+        let mut toggles = world.redstone_torch_toggles.lock().await;
+        if add_toggle {
+            toggles.push((*location, now));
+        }
+        toggles
+            .iter()
+            .filter(|(position, _)| position == location)
+            .count()
+            >= MAX_RECENT_TOGGLES
+    }
+
+    async fn set_lit(
+        server: &Server,
+        world: &World,
+        state_id: u16,
+        location: &BlockPos,
+        lit: bool,
+    ) {
+        let Some(new_state_id) = get_state_id_with_property(state_id, "lit", &lit.to_string())
+        else {
+            return;
+        };
+        world.set_block_state(location, new_state_id).await;
+        redstone::update_around(server, world, location).await;
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for RedstoneTorchBlock {
+    async fn on_place(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        face: &BlockDirection,
+        block_pos: &BlockPos,
+        use_item_on: &SUseItemOn,
+        player_direction: &Direction,
+        other: bool,
+    ) -> u16 {
+        let state_id = server
+            .block_properties_manager
+            .on_place_state(
+                world,
+                block,
+                face,
+                block_pos,
+                use_item_on,
+                player_direction,
+                other,
+            )
+            .await;
+        let lit = !Self::has_power(world, state_id, block_pos).await;
+        get_state_id_with_property(state_id, "lit", &lit.to_string()).unwrap_or(state_id)
+    }
+
+    async fn placed(&self, _block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        redstone::update_around(server, &player.world().await, &location).await;
+    }
+
+    async fn broken(&self, _block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        redstone::update_around(server, &player.world().await, &location).await;
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let lit = get_state_property(state_id, "lit") == Some("true");
+        if lit == Self::has_power(world, state_id, &location).await {
+            world.schedule_block_tick(location, block, TOGGLE_DELAY);
+        }
+    }
+
+    async fn on_scheduled_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        {
+            let now = world.level_time.lock().await.world_age;
+            world
+                .redstone_torch_toggles
+                .lock()
+                .await
+                .retain(|(_, tick)| now - tick <= RECENT_TOGGLE_TICKS);
+        }
+
+        let lit = get_state_property(state_id, "lit") == Some("true");
+        let has_power = Self::has_power(world, state_id, &location).await;
+        if lit && has_power {
+            Self::set_lit(server, world, state_id, &location, false).await;
+            if Self::is_toggled_too_often(world, &location, true).await {
+                world
+                    .play_block_sound(
+                        Sound::BlockRedstoneTorchBurnout,
+                        SoundCategory::Blocks,
+                        location,
+                    )
+                    .await;
+                world.schedule_block_tick(location, block, RESTART_DELAY);
+            }
+        } else if !lit && !has_power && !Self::is_toggled_too_often(world, &location, false).await {
+            Self::set_lit(server, world, state_id, &location, true).await;
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use async_trait::async_trait;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::server::play::SUseItemOn;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::{
+    registry::{get_block_by_state_id, get_state_id_with_property, Block},
+    BlockDirection,
+};
+
+use crate::{
+    block::{properties::Direction, pumpkin_block::PumpkinBlock},
+    entity::player::Player,
+    server::Server,
+    world::World,
+};
+
+use super::{get_int_property, redstone, with_int_property};
+
+/// How redstone wire connects to the side in a direction
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Side {
+    None,
+    Side,
+    /// Up the side of the block next to the wire, to wire on top of it
+    Up,
+}
+
+impl Side {
+    const fn value(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Side => "side",
+            Self::Up => "up",
+        }
+    }
+}
+
+fn is_wire(state_id: Option<u16>) -> bool {
+    state_id
+        .and_then(get_block_by_state_id)
+        .is_some_and(|block| block.name == "redstone_wire")
+}
+
+/// Redstone wire, which carries power from sources to other blocks, losing one level of power
+/// per block. It connects to other wire, also one block up or down, and to power sources.
+///
+/// TODO: Wire should break when the block below it is removed
+#[pumpkin_block("minecraft:redstone_wire")]
+pub struct RedstoneWireBlock;
+
+impl RedstoneWireBlock {
+    /// How the wire at the position connects to the side in the direction
+    async fn side(
+        world: &World,
+        location: &BlockPos,
+        direction: BlockDirection,
+        above_is_conductor: bool,
+    ) -> Side {
+        let neighbor = redstone::offset(location, direction);
+        let Some(neighbor_state_id) = redstone::state_id_at(world, &neighbor).await else {
+            return Side::None;
+        };
+        let neighbor_is_conductor = redstone::is_conductor(neighbor_state_id);
+        if !above_is_conductor
+            && neighbor_is_conductor
+            && is_wire(
+                redstone::state_id_at(world, &redstone::offset(&neighbor, BlockDirection::Top))
+                    .await,
+            )
+        {
+            return Side::Up;
+        }
+        if redstone::is_signal_source(neighbor_state_id) {
+            return Side::Side;
+        }
+        if !neighbor_is_conductor
+            && is_wire(
+                redstone::state_id_at(world, &redstone::offset(&neighbor, BlockDirection::Bottom))
+                    .await,
+            )
+        {
+            return Side::Side;
+        }
+        Side::None
+    }
+
+    /// The state of the wire with the sides it connects to. Like in vanilla, wire which only
+    /// connects on one axis extends to both ends of it, and wire which connects to nothing is a
+    /// cross.
+    async fn connected_state(world: &World, location: &BlockPos, state_id: u16) -> u16 {
+        let above_is_conductor =
+            redstone::state_id_at(world, &redstone::offset(location, BlockDirection::Top))
+                .await
+                .is_some_and(redstone::is_conductor);
+
+        let mut sides = [Side::None; 4];
+        let directions = BlockDirection::horizontal();
+        for (side, direction) in sides.iter_mut().zip(directions) {
+            *side = Self::side(world, location, direction, above_is_conductor).await;
+        }
+        // The horizontal directions are north, south, west and east
+        let north_south = sides[0] != Side::None || sides[1] != Side::None;
+        let west_east = sides[2] != Side::None || sides[3] != Side::None;
+        for (index, side) in sides.iter_mut().enumerate() {
+            let on_north_south = index < 2;
+            if *side == Side::None
+                && ((on_north_south && !west_east) || (!on_north_south && !north_south))
+            {
+                *side = Side::Side;
+            }
+        }
+
+        let mut new_state_id = state_id;
+        for (side, direction) in sides.into_iter().zip(directions) {
+            let Some(property) = redstone::wire_side_property(direction) else {
+                continue;
+            };
+            if let Some(state_id) = get_state_id_with_property(new_state_id, property, side.value())
+            {
+                new_state_id = state_id;
+            }
+        }
+        new_state_id
+    }
+
+    /// The power the wire at the position should have. It is the power it gets from sources
+    /// and conductors, or the power of the wire next to it minus one if that is stronger.
+    async fn target_power(world: &World, location: &BlockPos) -> u32 {
+        let block_power = redstone::received_power(world, location, false).await;
+        if block_power == redstone::MAX_POWER {
+            return block_power;
+        }
+
+        let above_is_conductor =
+            redstone::state_id_at(world, &redstone::offset(location, BlockDirection::Top))
+                .await
+                .is_some_and(redstone::is_conductor);
+        let mut wire_power = 0;
+        for direction in BlockDirection::horizontal() {
+            let neighbor = redstone::offset(location, direction);
+            let Some(neighbor_state_id) = redstone::state_id_at(world, &neighbor).await else {
+                continue;
+            };
+            let mut candidates = vec![neighbor_state_id];
+            if redstone::is_conductor(neighbor_state_id) {
+                if !above_is_conductor {
+                    let above = redstone::offset(&neighbor, BlockDirection::Top);
+                    candidates.extend(redstone::state_id_at(world, &above).await);
+                }
+            } else {
+                let below = redstone::offset(&neighbor, BlockDirection::Bottom);
+                candidates.extend(redstone::state_id_at(world, &below).await);
+            }
+            for candidate in candidates {
+                if is_wire(Some(candidate)) {
+                    wire_power = wire_power.max(get_int_property(candidate, "power").unwrap_or(0));
+                }
+            }
+        }
+        block_power.max(wire_power.saturating_sub(1))
+    }
+
+    /// Updates the connections and power of the wire. When its power changes, the blocks around
+    /// it and around its neighbors are updated, like in vanilla, which lets the change travel
+    /// along the wire.
+    async fn update(server: &Server, world: &World, location: BlockPos) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let power = Self::target_power(world, &location).await;
+        let connected = Self::connected_state(world, &location, state_id).await;
+        let new_state_id = with_int_property(connected, "power", power).unwrap_or(connected);
+        if new_state_id == state_id {
+            return;
+        }
+        world.set_block_state(&location, new_state_id).await;
+        if get_int_property(state_id, "power") != Some(power) {
+            redstone::update_around(server, world, &location).await;
+        }
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for RedstoneWireBlock {
+    async fn on_place(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        face: &BlockDirection,
+        block_pos: &BlockPos,
+        use_item_on: &SUseItemOn,
+        player_direction: &Direction,
+        other: bool,
+    ) -> u16 {
+        let state_id = server
+            .block_properties_manager
+            .on_place_state(
+                world,
+                block,
+                face,
+                block_pos,
+                use_item_on,
+                player_direction,
+                other,
+            )
+            .await;
+        let connected = Self::connected_state(world, block_pos, state_id).await;
+        let power = Self::target_power(world, block_pos).await;
+        with_int_property(connected, "power", power).unwrap_or(connected)
+    }
+
+    async fn placed(&self, _block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        let world = player.world().await;
+        redstone::update_around(server, &world, &location).await;
+    }
+
+    async fn broken(&self, _block: &Block, player: &Player, location: BlockPos, server: &Server) {
+        let world = player.world().await;
+        redstone::update_around(server, &world, &location).await;
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        server: &Server,
+        world: &World,
+        _block: &Block,
+        location: BlockPos,
+        _source: BlockPos,
+    ) {
+        Self::update(server, world, location).await;
+    }
+}
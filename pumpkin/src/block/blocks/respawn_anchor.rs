@@ -0,0 +1,185 @@
+use async_trait::async_trait;
+use pumpkin_data::{
+    damage::DamageType,
+    item::Item,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_registry::DimensionType;
+use pumpkin_util::{
+    math::{position::BlockPos, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_world::block::registry::{get_block_by_state_id, Block};
+
+use crate::{
+    block::{pumpkin_block::PumpkinBlock, registry::BlockActionResult},
+    entity::player::{Player, RespawnPoint},
+    server::Server,
+    world::{explosion, World},
+};
+
+use super::{get_int_property, with_int_property};
+
+/// Like in vanilla, an anchor can be charged up to this many times
+pub const MAX_CHARGES: u32 = 4;
+
+/// Like in vanilla, respawn anchors used outside of the nether explode with this power
+const EXPLOSION_POWER: f32 = 5.0;
+
+#[pumpkin_block("minecraft:respawn_anchor")]
+pub struct RespawnAnchorBlock;
+
+impl RespawnAnchorBlock {
+    /// Charges the anchor with the glowstone the player holds
+    async fn charge(player: &Player, world: &World, location: BlockPos, state_id: u16) {
+        let Some(charged) = with_int_property(state_id, "charges", charges(state_id) + 1) else {
+            return;
+        };
+        world.set_block_state(&location, charged).await;
+        world
+            .play_block_sound(
+                Sound::BlockRespawnAnchorCharge,
+                SoundCategory::Blocks,
+                location,
+            )
+            .await;
+
+        if player.gamemode.load() != GameMode::Creative {
+            player
+                .modify_held_item(|held| {
+                    if let Some(stack) = held {
+                        stack.item_count -= 1;
+                        if stack.item_count == 0 {
+                            *held = None;
+                        }
+                    }
+                })
+                .await;
+        }
+    }
+
+    /// Sets the respawn point of the player in the nether. Anywhere else, the anchor explodes.
+    async fn activate(player: &Player, world: &World, location: BlockPos, server: &Server) {
+        if !is_nether(world) {
+            Self::explode(server, world, location).await;
+            return;
+        }
+        player
+            .set_respawn_point(RespawnPoint {
+                world: world.name(),
+                position: location,
+                angle: 0.0,
+            })
+            .await;
+        world
+            .play_block_sound(
+                Sound::BlockRespawnAnchorSetSpawn,
+                SoundCategory::Blocks,
+                location,
+            )
+            .await;
+    }
+
+    async fn explode(server: &Server, world: &World, location: BlockPos) {
+        let Some(world) = server.get_world_handle(world).await else {
+            return;
+        };
+        // TODO: Like in vanilla, water next to the anchor should protect the blocks around it
+        world.set_block_state(&location, 0).await;
+        world.update_neighbors(server, &location).await;
+
+        let center = Vector3::new(
+            f64::from(location.0.x) + 0.5,
+            f64::from(location.0.y) + 0.5,
+            f64::from(location.0.z) + 0.5,
+        );
+        explosion::explode(
+            server,
+            &world,
+            center,
+            EXPLOSION_POWER,
+            true,
+            DamageType::BAD_RESPAWN_POINT,
+        )
+        .await;
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for RespawnAnchorBlock {
+    async fn normal_use(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        let world = player.world().await;
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        if charges(state_id) > 0 {
+            Self::activate(player, &world, location, server).await;
+        }
+    }
+
+    async fn use_with_item(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        item: &Item,
+        server: &Server,
+    ) -> BlockActionResult {
+        let world = player.world().await;
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return BlockActionResult::Continue;
+        };
+        let charges = charges(state_id);
+        if item.id == Item::GLOWSTONE.id && charges < MAX_CHARGES {
+            Self::charge(player, &world, location, state_id).await;
+            return BlockActionResult::Consume;
+        }
+        // Uncharged anchors don't do anything, so the item is used instead
+        if charges == 0 {
+            return BlockActionResult::Continue;
+        }
+        Self::activate(player, &world, location, server).await;
+        BlockActionResult::Consume
+    }
+}
+
+/// How many times the respawn anchor is charged, which is how many times players can respawn at
+/// it
+pub fn charges(state_id: u16) -> u32 {
+    get_int_property(state_id, "charges").unwrap_or(0)
+}
+
+/// Whether the block state belongs to a respawn anchor
+pub fn is_respawn_anchor(state_id: u16) -> bool {
+    get_block_by_state_id(state_id).is_some_and(|block| block.name == "respawn_anchor")
+}
+
+/// Whether players can set their respawn point with respawn anchors in the world, instead of
+/// them exploding
+pub fn is_nether(world: &World) -> bool {
+    matches!(world.dimension_type, DimensionType::TheNether)
+}
+
+/// Uses up one charge of the anchor, after a player respawned at it
+pub async fn deplete(world: &World, location: BlockPos, state_id: u16) {
+    let Some(depleted) =
+        with_int_property(state_id, "charges", charges(state_id).saturating_sub(1))
+    else {
+        return;
+    };
+    world.set_block_state(&location, depleted).await;
+    world
+        .play_block_sound(
+            Sound::BlockRespawnAnchorDeplete,
+            SoundCategory::Blocks,
+            location,
+        )
+        .await;
+}
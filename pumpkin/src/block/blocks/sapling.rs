@@ -0,0 +1,205 @@
+use async_trait::async_trait;
+use pumpkin_util::{
+    math::{position::BlockPos, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+use pumpkin_world::{
+    block::registry::{get_block, get_block_and_state_by_state_id, Block},
+    generation::feature::tree::{Tree, TreeKind},
+};
+use rand::{thread_rng, Rng};
+
+use crate::{block::pumpkin_block::PumpkinBlock, server::Server, world::World};
+
+use super::{get_int_property, with_int_property};
+
+/// The saplings growing into trees of their wood. Dark oak and pale oak saplings need four
+/// saplings in a square, spruce and jungle saplings grow into giant trees from four saplings.
+pub const SAPLINGS: [&str; 8] = [
+    "oak_sapling",
+    "spruce_sapling",
+    "birch_sapling",
+    "jungle_sapling",
+    "acacia_sapling",
+    "cherry_sapling",
+    "dark_oak_sapling",
+    "pale_oak_sapling",
+];
+
+/// The blocks a tree can grow on
+const SOILS: [&str; 7] = [
+    "dirt",
+    "grass_block",
+    "podzol",
+    "coarse_dirt",
+    "rooted_dirt",
+    "mycelium",
+    "moss_block",
+];
+
+/// Saplings need at least this much light to grow
+const MIN_LIGHT: u8 = 9;
+
+/// Bone meal advances a sapling with this chance, like in vanilla
+const BONE_MEAL_CHANCE: f64 = 0.45;
+
+/// The north west corners of the squares of four saplings the sapling can be part of
+const SQUARE_CORNERS: [(i32, i32); 4] = [(0, 0), (-1, 0), (0, -1), (-1, -1)];
+
+pub struct SaplingBlock;
+
+impl SaplingBlock {
+    /// Grows the sapling to its next stage, or into a tree once it is fully grown
+    async fn advance(world: &World, block: &Block, state_id: u16, location: BlockPos) {
+        match get_int_property(state_id, "stage") {
+            Some(0) => {
+                if let Some(grown) = with_int_property(state_id, "stage", 1) {
+                    world.set_block_state(&location, grown).await;
+                }
+            }
+            Some(_) => Self::grow_tree(world, block, location).await,
+            None => {}
+        }
+    }
+
+    /// Grows a giant tree if the sapling is part of a square of four, otherwise a normal tree
+    async fn grow_tree(world: &World, block: &Block, location: BlockPos) {
+        let Some(wood) = block.name.strip_suffix("_sapling") else {
+            return;
+        };
+
+        if let Some(kind) = TreeKind::mega_from_wood(wood) {
+            for (x, z) in SQUARE_CORNERS {
+                let corner = location.0.add_raw(x, 0, z);
+                let square = [(0, 0), (1, 0), (0, 1), (1, 1)]
+                    .map(|(x, z)| BlockPos(corner.add_raw(x, 0, z)));
+                if Self::are_saplings(world, block, &square).await
+                    && Self::try_grow(world, kind, corner, &square).await
+                {
+                    return;
+                }
+            }
+        }
+        if let Some(kind) = TreeKind::from_wood(wood) {
+            Self::try_grow(world, kind, location.0, &[location]).await;
+        }
+    }
+
+    async fn are_saplings(world: &World, sapling: &Block, positions: &[BlockPos]) -> bool {
+        for position in positions {
+            if !world
+                .get_block(position)
+                .await
+                .is_ok_and(|block| block.id == sapling.id)
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Grows the tree in place of the saplings if they are on soil and the trunk has space.
+    /// Returns whether it grew.
+    async fn try_grow(
+        world: &World,
+        kind: TreeKind,
+        origin: Vector3<i32>,
+        saplings: &[BlockPos],
+    ) -> bool {
+        for sapling in saplings {
+            let below = BlockPos(sapling.0.add_raw(0, -1, 0));
+            if !world
+                .get_block(&below)
+                .await
+                .is_ok_and(|soil| SOILS.contains(&soil.name.as_str()))
+            {
+                return false;
+            }
+        }
+
+        let mut random = LegacyRand::from_seed(thread_rng().gen());
+        let tree = Tree::plan(kind, origin, &mut random);
+        for log in &tree.logs {
+            let position = BlockPos(*log);
+            if !saplings.contains(&position) && !Self::is_replaceable(world, &position).await {
+                return false;
+            }
+        }
+
+        let log_state = tree.log_state();
+        for log in &tree.logs {
+            world.set_block_state(&BlockPos(*log), log_state).await;
+        }
+        // Leaves do not replace other blocks, but logs have checked their space already
+        for (leaf, state_id) in tree.leaf_states() {
+            let position = BlockPos(leaf);
+            if Self::is_replaceable(world, &position).await {
+                world.set_block_state(&position, state_id).await;
+            }
+        }
+        if let Some(dirt) = get_block("minecraft:dirt") {
+            for sapling in saplings {
+                let below = BlockPos(sapling.0.add_raw(0, -1, 0));
+                world.set_block_state(&below, dirt.default_state_id).await;
+            }
+        }
+        true
+    }
+
+    async fn is_replaceable(world: &World, position: &BlockPos) -> bool {
+        let Ok(state_id) = world.get_block_state_id(position).await else {
+            return false;
+        };
+        get_block_and_state_by_state_id(state_id).is_some_and(|(block, state)| {
+            state.air
+                || state.replaceable
+                || block.name.ends_with("_leaves")
+                || block.name.ends_with("_sapling")
+        })
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for SaplingBlock {
+    fn has_random_ticks(&self) -> bool {
+        true
+    }
+
+    async fn random_tick(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let above = BlockPos(location.0.add_raw(0, 1, 0));
+        if world.get_brightness(&above).await < MIN_LIGHT || thread_rng().gen_range(0..7) != 0 {
+            return;
+        }
+        Self::advance(world, block, state_id, location).await;
+    }
+
+    async fn can_fertilize(
+        &self,
+        _world: &World,
+        _block: &Block,
+        _state_id: u16,
+        _location: BlockPos,
+    ) -> bool {
+        true
+    }
+
+    async fn fertilize(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        if thread_rng().gen_bool(BONE_MEAL_CHANCE) {
+            Self::advance(world, block, state_id, location).await;
+        }
+    }
+}
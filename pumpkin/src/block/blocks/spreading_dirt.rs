@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    block::registry::{
+        get_block, get_block_and_state_by_state_id, get_state_id_by_properties, Block,
+    },
+    light,
+};
+use rand::{seq::SliceRandom, thread_rng, Rng};
+
+use crate::{block::pumpkin_block::PumpkinBlock, server::Server, world::World};
+
+/// The blocks which spread onto dirt next to them
+pub const SPREADING_DIRT: [&str; 2] = ["grass_block", "mycelium"];
+
+/// Spreading needs at least this much light above the block
+const MIN_LIGHT: u8 = 9;
+
+/// Like in vanilla, bone meal tries this many times to plant something around the grass
+const BONE_MEAL_ATTEMPTS: u32 = 128;
+
+/// The flowers bone meal plants on grass
+/// TODO: Plant the flowers of the biome instead
+const BONE_MEAL_FLOWERS: [&str; 2] = ["dandelion", "poppy"];
+
+/// Grass and mycelium, which spread onto dirt in the light and decay to dirt when covered
+pub struct SpreadingDirtBlock;
+
+impl SpreadingDirtBlock {
+    /// Whether the block can stay, which it can not below blocks which let no light through
+    async fn can_survive(world: &World, location: &BlockPos) -> bool {
+        let above = BlockPos(location.0.add_raw(0, 1, 0));
+        world
+            .get_block_state_id(&above)
+            .await
+            .is_ok_and(|state_id| light::opacity(state_id) < light::MAX_LIGHT)
+    }
+
+    /// Whether the block can spread to the location, which also needs no water above
+    async fn can_spread(world: &World, location: &BlockPos) -> bool {
+        let above = BlockPos(location.0.add_raw(0, 1, 0));
+        Self::can_survive(world, location).await
+            && !world
+                .get_block(&above)
+                .await
+                .is_ok_and(|block| block.name == "water")
+    }
+
+    /// Turns short grass into tall grass if there is space above it
+    async fn grow_tall_grass(world: &World, location: &BlockPos) {
+        let above = BlockPos(location.0.add_raw(0, 1, 0));
+        if !world
+            .get_block_state(&above)
+            .await
+            .is_ok_and(|state| state.air)
+        {
+            return;
+        }
+        let half = |half: &str| {
+            let properties = HashMap::from([("half".to_string(), half.to_string())]);
+            get_state_id_by_properties("minecraft:tall_grass", &properties)
+        };
+        if let (Some(lower), Some(upper)) = (half("lower"), half("upper")) {
+            world.set_block_state(location, lower).await;
+            world.set_block_state(&above, upper).await;
+        }
+    }
+
+    /// Picks a position around the grass by walking randomly over the grass, further the more
+    /// attempts were made. Returns `None` if the walk left the grass.
+    async fn walk_over_grass(
+        world: &World,
+        grass: &Block,
+        location: &BlockPos,
+        attempt: u32,
+    ) -> Option<BlockPos> {
+        let mut position = BlockPos(location.0.add_raw(0, 1, 0));
+        for _ in 0..attempt / 16 {
+            let (x, y, z) = {
+                let mut rng = thread_rng();
+                (
+                    rng.gen_range(-1..=1),
+                    rng.gen_range(-1..=1) * rng.gen_range(0..3) / 2,
+                    rng.gen_range(-1..=1),
+                )
+            };
+            position = BlockPos(position.0.add_raw(x, y, z));
+            let below = BlockPos(position.0.add_raw(0, -1, 0));
+            let on_grass = world
+                .get_block(&below)
+                .await
+                .is_ok_and(|below| below.id == grass.id);
+            let is_full_block = world
+                .get_block_state(&position)
+                .await
+                .is_ok_and(|state| state.collision_shapes == [0]);
+            if !on_grass || is_full_block {
+                return None;
+            }
+        }
+        Some(position)
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for SpreadingDirtBlock {
+    fn has_random_ticks(&self) -> bool {
+        true
+    }
+
+    async fn random_tick(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        _state_id: u16,
+        location: BlockPos,
+    ) {
+        let Some(dirt) = get_block("minecraft:dirt") else {
+            return;
+        };
+        if !Self::can_survive(world, &location).await {
+            world
+                .set_block_state(&location, dirt.default_state_id)
+                .await;
+            return;
+        }
+
+        let above = BlockPos(location.0.add_raw(0, 1, 0));
+        if world.get_brightness(&above).await < MIN_LIGHT {
+            return;
+        }
+        for _ in 0..4 {
+            let target = {
+                let mut rng = thread_rng();
+                BlockPos(location.0.add_raw(
+                    rng.gen_range(-1..=1),
+                    rng.gen_range(-3..=1),
+                    rng.gen_range(-1..=1),
+                ))
+            };
+            if world
+                .get_block(&target)
+                .await
+                .is_ok_and(|target_block| target_block.id == dirt.id)
+                && Self::can_spread(world, &target).await
+            {
+                world.set_block_state(&target, block.default_state_id).await;
+            }
+        }
+    }
+
+    async fn can_fertilize(
+        &self,
+        world: &World,
+        block: &Block,
+        _state_id: u16,
+        location: BlockPos,
+    ) -> bool {
+        let above = BlockPos(location.0.add_raw(0, 1, 0));
+        block.name == "grass_block"
+            && world
+                .get_block_state(&above)
+                .await
+                .is_ok_and(|state| state.air)
+    }
+
+    /// Plants short grass and a few flowers on the grass around, and lets some of the short
+    /// grass there grow tall
+    async fn fertilize(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        _state_id: u16,
+        location: BlockPos,
+    ) {
+        for attempt in 0..BONE_MEAL_ATTEMPTS {
+            let Some(position) = Self::walk_over_grass(world, block, &location, attempt).await
+            else {
+                continue;
+            };
+            let Ok(state_id) = world.get_block_state_id(&position).await else {
+                continue;
+            };
+            let Some((target, state)) = get_block_and_state_by_state_id(state_id) else {
+                continue;
+            };
+            if target.name == "short_grass" && thread_rng().gen_range(0..10) == 0 {
+                Self::grow_tall_grass(world, &position).await;
+            }
+            if !state.air {
+                continue;
+            }
+            let plant = {
+                let mut rng = thread_rng();
+                if rng.gen_range(0..8) == 0 {
+                    BONE_MEAL_FLOWERS
+                        .choose(&mut rng)
+                        .copied()
+                        .unwrap_or("poppy")
+                } else {
+                    "short_grass"
+                }
+            };
+            if let Some(plant) = get_block(&format!("minecraft:{plant}")) {
+                world
+                    .set_block_state(&position, plant.default_state_id)
+                    .await;
+            }
+        }
+    }
+}
@@ -1,4 +1,29 @@
-use blocks::{chest::ChestBlock, furnace::FurnaceBlock, lever::LeverBlock};
+use blocks::{
+    bed::{BedBlock, BEDS},
+    button::{ButtonBlock, BUTTONS},
+    chest::ChestBlock,
+    crop::{CropBlock, CROPS},
+    dispenser::{DispenserBlock, DISPENSERS},
+    door::{DoorBlock, TrapdoorBlock, DOORS, TRAPDOORS},
+    falling::{FallingBlock, FALLING_BLOCKS},
+    farmland::FarmlandBlock,
+    fire::{FireBlock, FIRES},
+    fluid::{Fluid, FluidBlock},
+    furnace::FurnaceBlock,
+    growing_plant::{GrowingPlantBlock, GROWING_PLANTS},
+    hopper::HopperBlock,
+    leaves::{LeavesBlock, LEAVES},
+    lever::LeverBlock,
+    melting::{MeltingBlock, MELTING},
+    piston::{MovingPistonBlock, PistonBlock, PistonHeadBlock, PISTONS},
+    pressure_plate::{PressurePlateBlock, PRESSURE_PLATES},
+    redstone_lamp::RedstoneLampBlock,
+    redstone_torch::{RedstoneTorchBlock, REDSTONE_TORCHES},
+    redstone_wire::RedstoneWireBlock,
+    respawn_anchor::RespawnAnchorBlock,
+    sapling::{SaplingBlock, SAPLINGS},
+    spreading_dirt::{SpreadingDirtBlock, SPREADING_DIRT},
+};
 use properties::{
     age::Age,
     attachment::Attachment,
@@ -26,13 +51,13 @@ use rand::Rng;
 
 use crate::block::blocks::jukebox::JukeboxBlock;
 use crate::block::registry::BlockRegistry;
-use crate::entity::item::ItemEntity;
+use crate::entity::item::{self, ItemEntity};
 use crate::server::Server;
 use crate::world::World;
 use crate::{block::blocks::crafting_table::CraftingTableBlock, entity::player::Player};
 use std::sync::Arc;
 
-mod blocks;
+pub(crate) mod blocks;
 pub mod properties;
 pub mod pumpkin_block;
 pub mod registry;
@@ -46,12 +71,48 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     manager.register(FurnaceBlock);
     manager.register(ChestBlock);
     manager.register(LeverBlock);
+    manager.register(FarmlandBlock);
+    manager.register(RedstoneWireBlock);
+    manager.register(RedstoneLampBlock);
+    manager.register(PistonHeadBlock);
+    manager.register(MovingPistonBlock);
+    manager.register(HopperBlock);
+    manager.register(RespawnAnchorBlock);
+    manager.register_shared(&CROPS, CropBlock);
+    manager.register_shared(&SAPLINGS, SaplingBlock);
+    manager.register_shared(&LEAVES, LeavesBlock);
+    manager.register_shared(&SPREADING_DIRT, SpreadingDirtBlock);
+    manager.register_shared(&GROWING_PLANTS, GrowingPlantBlock);
+    manager.register_shared(&MELTING, MeltingBlock);
+    manager.register_shared(&FALLING_BLOCKS, FallingBlock);
+    manager.register_shared(&["water"], FluidBlock(Fluid::Water));
+    manager.register_shared(&["lava"], FluidBlock(Fluid::Lava));
+    manager.register_shared(&FIRES, FireBlock);
+    manager.register_shared(&BEDS, BedBlock);
+    manager.register_shared(&BUTTONS, ButtonBlock);
+    manager.register_shared(&PRESSURE_PLATES, PressurePlateBlock);
+    manager.register_shared(&REDSTONE_TORCHES, RedstoneTorchBlock);
+    manager.register_shared(&DOORS, DoorBlock);
+    manager.register_shared(&TRAPDOORS, TrapdoorBlock);
+    manager.register_shared(&PISTONS, PistonBlock);
+    manager.register_shared(&DISPENSERS, DispenserBlock);
 
     Arc::new(manager)
 }
 
 pub async fn drop_loot(server: &Server, world: &Arc<World>, block: &Block, pos: &BlockPos) {
     // TODO: Currently only the item block is dropped, We should drop the loop table
+    drop_stack(
+        server,
+        world,
+        &ItemStack::new(1, Item::from_id(block.item_id).unwrap()),
+        pos,
+    )
+    .await;
+}
+
+/// Drops an item stack at a random position in the block.
+pub async fn drop_stack(server: &Server, world: &Arc<World>, stack: &ItemStack, pos: &BlockPos) {
     let height = EntityType::ITEM.dimension[1] / 2.0;
     let pos = Vector3::new(
         f64::from(pos.0.x) + 0.5 + rand::thread_rng().gen_range(-0.25..0.25),
@@ -60,10 +121,8 @@ pub async fn drop_loot(server: &Server, world: &Arc<World>, block: &Block, pos:
     );
 
     let entity = server.add_entity(pos, EntityType::ITEM, world);
-    let item_entity = Arc::new(ItemEntity::new(
-        entity,
-        &ItemStack::new(1, Item::from_id(block.item_id).unwrap()),
-    ));
+    entity.velocity.store(item::random_velocity());
+    let item_entity = Arc::new(ItemEntity::new(entity, stack));
     world.spawn_entity(item_entity.clone()).await;
     item_entity.send_meta_packet().await;
 }
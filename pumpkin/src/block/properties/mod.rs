@@ -220,11 +220,12 @@ impl BlockPropertiesManager {
     }
 
     pub async fn on_interact(&self, block: &Block, block_state: &State, item: &ItemStack) -> u16 {
+        // The block might have been replaced while it was used, like beds which explode
+        let Some(offset) = block_state.id.checked_sub(block.states[0].id) else {
+            return block_state.id;
+        };
         if let Some(properties) = self.properties_registry.get(&block.id) {
-            if let Some(states) = properties
-                .property_mappings
-                .get(&(block_state.id - block.states[0].id))
-            {
+            if let Some(states) = properties.property_mappings.get(&offset) {
                 let mut hmap_key: Vec<String> = Vec::with_capacity(block.properties.len());
 
                 for (i, raw_property) in block.properties.iter().enumerate() {
@@ -233,9 +234,9 @@ impl BlockPropertiesManager {
                         let state = property.on_interact(states[i].clone(), block, item).await;
                         hmap_key.push(state);
                     } else {
-                        log::warn!("Unknown Block Property: {}", &raw_property.name);
-                        // if one property is not found everything will not work
-                        return block.default_state_id;
+                        // If one property is not found, the state can not be changed. Blocks
+                        // like redstone wire change their other properties themselves
+                        return block_state.id;
                     }
                 }
                 // Base state id plus offset
@@ -1,5 +1,6 @@
 use crate::block::registry::BlockActionResult;
 use crate::entity::player::Player;
+use crate::entity::EntityBase;
 use crate::server::Server;
 use crate::world::World;
 use async_trait::async_trait;
@@ -94,4 +95,93 @@ pub trait PumpkinBlock: Send + Sync {
         _container: &mut OpenContainer,
     ) {
     }
+
+    /// Whether the block is picked by random ticks, see [`PumpkinBlock::random_tick`]
+    fn has_random_ticks(&self) -> bool {
+        false
+    }
+
+    /// Called when a random tick picked the block. Each tick, `random_tick_speed` random blocks
+    /// of every loaded subchunk are picked, which lets crops grow or ice melt.
+    async fn random_tick(
+        &self,
+        _server: &Server,
+        _world: &World,
+        _block: &Block,
+        _state_id: u16,
+        _location: BlockPos,
+    ) {
+    }
+
+    /// Whether bone meal can be used on the block, like on crops which are not fully grown
+    async fn can_fertilize(
+        &self,
+        _world: &World,
+        _block: &Block,
+        _state_id: u16,
+        _location: BlockPos,
+    ) -> bool {
+        false
+    }
+
+    /// Called when bone meal is used on the block, see [`PumpkinBlock::can_fertilize`]. The bone
+    /// meal is used up even if it does not always help, like with saplings.
+    async fn fertilize(
+        &self,
+        _server: &Server,
+        _world: &World,
+        _block: &Block,
+        _state_id: u16,
+        _location: BlockPos,
+    ) {
+    }
+
+    /// Called when a tick scheduled through [`World::schedule_block_tick`] is due and the block is
+    /// still there
+    async fn on_scheduled_tick(
+        &self,
+        _server: &Server,
+        _world: &World,
+        _block: &Block,
+        _state_id: u16,
+        _location: BlockPos,
+    ) {
+    }
+
+    /// Called each tick in which the block entity of the block needs it, see
+    /// [`BlockEntity::needs_block_tick`], e.g. to let a hopper move items
+    ///
+    /// [`BlockEntity::needs_block_tick`]: pumpkin_world::block::entity::BlockEntity::needs_block_tick
+    async fn on_block_entity_tick(
+        &self,
+        _server: &Server,
+        _world: &World,
+        _block: &Block,
+        _state_id: u16,
+        _location: BlockPos,
+    ) {
+    }
+
+    /// Called when the block next to this one at `source` was changed
+    async fn on_neighbor_update(
+        &self,
+        _server: &Server,
+        _world: &World,
+        _block: &Block,
+        _location: BlockPos,
+        _source: BlockPos,
+    ) {
+    }
+
+    /// Called each tick for every entity which touches the block, e.g. to press pressure plates
+    async fn on_entity_collision(
+        &self,
+        _server: &Server,
+        _world: &World,
+        _block: &Block,
+        _state_id: u16,
+        _location: BlockPos,
+        _entity: &dyn EntityBase,
+    ) {
+    }
 }
@@ -1,5 +1,6 @@
 use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
 use crate::entity::player::Player;
+use crate::entity::EntityBase;
 use crate::server::Server;
 use crate::world::World;
 use pumpkin_data::item::Item;
@@ -8,7 +9,7 @@ use pumpkin_protocol::server::play::SUseItemOn;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::block::registry::Block;
 use pumpkin_world::block::BlockDirection;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use super::properties::Direction;
@@ -23,13 +24,119 @@ pub enum BlockActionResult {
 #[derive(Default)]
 pub struct BlockRegistry {
     blocks: HashMap<String, Arc<dyn PumpkinBlock>>,
+    /// The names of the blocks with random ticks, without namespace, so random ticks can skip
+    /// other blocks quickly
+    random_ticking: HashSet<String>,
 }
 
 impl BlockRegistry {
     pub fn register<T: PumpkinBlock + BlockMetadata + 'static>(&mut self, block: T) {
+        if block.has_random_ticks() {
+            self.random_ticking.insert(T::ID.to_string());
+        }
         self.blocks.insert(block.name(), Arc::new(block));
     }
 
+    /// Registers one behavior for several vanilla blocks, e.g. for all crops
+    pub fn register_shared<T: PumpkinBlock + 'static>(&mut self, names: &[&str], block: T) {
+        let block: Arc<dyn PumpkinBlock> = Arc::new(block);
+        for name in names {
+            if block.has_random_ticks() {
+                self.random_ticking.insert((*name).to_string());
+            }
+            self.blocks
+                .insert(format!("minecraft:{name}"), block.clone());
+        }
+    }
+
+    /// Whether the block is picked by random ticks, see [`PumpkinBlock::random_tick`]
+    #[must_use]
+    pub fn has_random_ticks(&self, block: &Block) -> bool {
+        self.random_ticking.contains(&block.name)
+    }
+
+    pub async fn random_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .random_tick(server, world, block, state_id, location)
+                .await;
+        }
+    }
+
+    /// Whether bone meal can be used on the block, see [`PumpkinBlock::can_fertilize`]
+    pub async fn can_fertilize(
+        &self,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) -> bool {
+        match self.get_pumpkin_block(block) {
+            Some(pumpkin_block) => {
+                pumpkin_block
+                    .can_fertilize(world, block, state_id, location)
+                    .await
+            }
+            None => false,
+        }
+    }
+
+    pub async fn fertilize(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .fertilize(server, world, block, state_id, location)
+                .await;
+        }
+    }
+
+    pub async fn on_scheduled_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_scheduled_tick(server, world, block, state_id, location)
+                .await;
+        }
+    }
+
+    pub async fn on_block_entity_tick(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_block_entity_tick(server, world, block, state_id, location)
+                .await;
+        }
+    }
+
     pub async fn on_use(
         &self,
         block: &Block,
@@ -145,6 +252,39 @@ impl BlockRegistry {
         }
     }
 
+    pub async fn on_neighbor_update(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        location: BlockPos,
+        source: BlockPos,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_neighbor_update(server, world, block, location, source)
+                .await;
+        }
+    }
+
+    pub async fn on_entity_collision(
+        &self,
+        server: &Server,
+        world: &World,
+        block: &Block,
+        state_id: u16,
+        location: BlockPos,
+        entity: &dyn EntityBase,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_entity_collision(server, world, block, state_id, location, entity)
+                .await;
+        }
+    }
+
     #[must_use]
     pub fn get_pumpkin_block(&self, block: &Block) -> Option<&Arc<dyn PumpkinBlock>> {
         self.blocks
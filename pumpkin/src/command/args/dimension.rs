@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::command::args::{
+    Arg, ArgumentConsumer, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::server::Server;
+use crate::world::World;
+
+/// `ArgumentConsumer` for dimension ids (e.g. `minecraft:the_nether`), which only accepts
+/// dimensions of loaded worlds and resolves to that world. Worlds from the config are addressed
+/// by their own dimension key, see [`World::dimension_key`]
+pub struct DimensionArgumentConsumer;
+
+/// A dimension id which no loaded world has
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownDimension {
+    pub name: String,
+    /// The ids of the loaded dimensions, which could be meant instead
+    pub loaded: Vec<String>,
+}
+
+impl std::fmt::Display for UnknownDimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unknown dimension '{}', the loaded dimensions are {}",
+            self.name,
+            self.loaded.join(", ")
+        )
+    }
+}
+
+impl GetClientSideArgParser for DimensionArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::Dimension
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        // The client would suggest every registered dimension, not only the loaded ones
+        Some(SuggestionProviders::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for DimensionArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let s = args.pop()?;
+        let name = if s.contains(':') {
+            s.to_string()
+        } else {
+            format!("minecraft:{s}")
+        };
+
+        let worlds = server.worlds.read().await;
+        let world = worlds
+            .iter()
+            .find(|world| world.dimension_key().to_string() == name)
+            .cloned()
+            .ok_or_else(|| UnknownDimension {
+                name,
+                loaded: worlds
+                    .iter()
+                    .map(|world| world.dimension_key().to_string())
+                    .collect(),
+            });
+        Some(Arg::Dimension(world))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        let suggestions = server
+            .worlds
+            .read()
+            .await
+            .iter()
+            .map(|world| CommandSuggestion::new(world.dimension_key().to_string(), None))
+            .collect();
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for DimensionArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "dimension"
+    }
+}
+
+impl<'a> FindArg<'a> for DimensionArgumentConsumer {
+    type Data = &'a Arc<World>;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Dimension(Ok(data))) => Ok(data),
+            Some(Arg::Dimension(Err(error))) => {
+                Err(CommandError::GeneralCommandIssue(error.to_string()))
+            }
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
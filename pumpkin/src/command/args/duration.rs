@@ -0,0 +1,251 @@
+use async_trait::async_trait;
+use chrono::TimeDelta;
+use pumpkin_protocol::client::play::{
+    ArgumentType, CommandSuggestion, StringProtoArgBehavior, SuggestionProviders,
+};
+
+use crate::command::{
+    args::{Arg, ArgumentConsumer, DefaultNameArgConsumer, FindArg, GetClientSideArgParser},
+    dispatcher::CommandError,
+    tree::RawArgs,
+    CommandSender,
+};
+use crate::server::Server;
+
+/// A duration like `30m` or `1d12h`, in weeks, days, hours, minutes, seconds and ticks. Numbers
+/// without a unit are ticks, so `100` is five seconds, unless a unit is required.
+pub struct DurationArgumentConsumer {
+    allow_zero: bool,
+    require_unit: bool,
+}
+
+impl DurationArgumentConsumer {
+    pub(crate) const fn new() -> Self {
+        Self {
+            allow_zero: false,
+            require_unit: false,
+        }
+    }
+
+    /// Also accepts zero, like for how long a title fades in
+    pub(crate) const fn allow_zero(mut self) -> Self {
+        self.allow_zero = true;
+        self
+    }
+
+    /// Leaves words ending in a number without a unit to the following arguments, so the reason of
+    /// `/ban Griefer 3 strikes` is not taken as a ban of 3 ticks
+    pub(crate) const fn require_unit(mut self) -> Self {
+        self.require_unit = true;
+        self
+    }
+
+    /// Whether a word is taken as the duration. Other words are left to the following arguments,
+    /// like the reason of a mute.
+    fn accepts(&self, word: &str) -> bool {
+        word.starts_with(|c: char| c.is_ascii_digit())
+            && !(self.require_unit && word.ends_with(|c: char| c.is_ascii_digit()))
+    }
+}
+
+impl GetClientSideArgParser for DurationArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::String(StringProtoArgBehavior::SingleWord)
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+/// The units a duration can be given in
+const UNITS: &str = "w (weeks), d (days), h (hours), m (minutes), s (seconds) and t (ticks)";
+
+/// How long a game tick is, in milliseconds
+const MILLIS_PER_TICK: i64 = 50;
+
+/// Why a duration could not be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DurationError {
+    UnknownUnit(String),
+    Malformed,
+    NotPositive,
+    TooLong,
+}
+
+impl std::fmt::Display for DurationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownUnit(unit) => {
+                write!(f, "Unknown time unit '{unit}', supported units are {UNITS}")
+            }
+            Self::Malformed => write!(f, "Invalid duration, use a number followed by {UNITS}"),
+            Self::NotPositive => write!(f, "The duration must be longer than zero"),
+            Self::TooLong => write!(f, "The duration is too long"),
+        }
+    }
+}
+
+/// The duration in game ticks, rounded down
+#[must_use]
+pub fn to_ticks(duration: TimeDelta) -> i64 {
+    duration.num_milliseconds() / MILLIS_PER_TICK
+}
+
+fn parse_duration(s: &str, allow_zero: bool) -> Result<TimeDelta, DurationError> {
+    let mut duration = TimeDelta::zero();
+    let mut rest = s;
+    while !rest.is_empty() {
+        let unit_start = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let number = rest[..unit_start]
+            .parse::<i64>()
+            .map_err(|_| DurationError::Malformed)?;
+        let unit_end = rest[unit_start..]
+            .find(|c: char| c.is_ascii_digit())
+            .map_or(rest.len(), |end| unit_start + end);
+        let unit = &rest[unit_start..unit_end];
+        rest = &rest[unit_end..];
+
+        let part = match unit {
+            "w" => TimeDelta::try_weeks(number),
+            "d" => TimeDelta::try_days(number),
+            "h" => TimeDelta::try_hours(number),
+            "m" => TimeDelta::try_minutes(number),
+            "s" => TimeDelta::try_seconds(number),
+            "t" | "" => number
+                .checked_mul(MILLIS_PER_TICK)
+                .and_then(TimeDelta::try_milliseconds),
+            unit => return Err(DurationError::UnknownUnit(unit.to_string())),
+        }
+        .ok_or(DurationError::TooLong)?;
+        duration = duration.checked_add(&part).ok_or(DurationError::TooLong)?;
+    }
+    if duration == TimeDelta::zero() && !allow_zero {
+        return Err(DurationError::NotPositive);
+    }
+    Ok(duration)
+}
+
+#[async_trait]
+impl ArgumentConsumer for DurationArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let s = args.pop()?;
+        if !self.accepts(s) {
+            return None;
+        }
+        Some(Arg::Duration(parse_duration(s, self.allow_zero)))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for DurationArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "duration"
+    }
+}
+
+impl<'a> FindArg<'a> for DurationArgumentConsumer {
+    type Data = TimeDelta;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Duration(Ok(duration))) => Ok(*duration),
+            Some(Arg::Duration(Err(error))) => {
+                Err(CommandError::GeneralCommandIssue(error.to_string()))
+            }
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeDelta;
+
+    use super::{parse_duration, to_ticks, DurationArgumentConsumer, DurationError};
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(
+            parse_duration("30s", false).ok(),
+            TimeDelta::try_seconds(30)
+        );
+        assert_eq!(parse_duration("5m", false).ok(), TimeDelta::try_minutes(5));
+        assert_eq!(parse_duration("2h", false).ok(), TimeDelta::try_hours(2));
+        assert_eq!(parse_duration("1d", false).ok(), TimeDelta::try_days(1));
+        assert_eq!(parse_duration("2w", false).ok(), TimeDelta::try_days(14));
+    }
+
+    #[test]
+    fn parses_ticks() {
+        assert_eq!(
+            parse_duration("10", false).ok(),
+            TimeDelta::try_milliseconds(500)
+        );
+        assert_eq!(parse_duration("20t", false).ok(), TimeDelta::try_seconds(1));
+        assert_eq!(parse_duration("1m", false).map(to_ticks), Ok(1200));
+        assert_eq!(parse_duration("0", true), Ok(TimeDelta::zero()));
+    }
+
+    #[test]
+    fn parses_mixed_units() {
+        assert_eq!(
+            parse_duration("1d12h", false).ok(),
+            TimeDelta::try_hours(36)
+        );
+        assert_eq!(
+            parse_duration("1h30m", false).ok(),
+            TimeDelta::try_minutes(90)
+        );
+        assert_eq!(parse_duration("1m10", false).map(to_ticks), Ok(1210));
+        assert_eq!(
+            parse_duration("1s1s", false).ok(),
+            TimeDelta::try_seconds(2)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert_eq!(
+            parse_duration("5y", false),
+            Err(DurationError::UnknownUnit("y".to_string()))
+        );
+        assert_eq!(
+            parse_duration("5min", false),
+            Err(DurationError::UnknownUnit("min".to_string()))
+        );
+        assert_eq!(parse_duration("0m", false), Err(DurationError::NotPositive));
+        assert_eq!(parse_duration("spam", false), Err(DurationError::Malformed));
+        assert_eq!(parse_duration("h5", false), Err(DurationError::Malformed));
+        assert_eq!(
+            parse_duration("99999999999999999w", false),
+            Err(DurationError::TooLong)
+        );
+    }
+
+    #[test]
+    fn leaves_bare_numbers_when_a_unit_is_required() {
+        let consumer = DurationArgumentConsumer::new().require_unit();
+        assert!(consumer.accepts("30m"));
+        assert!(consumer.accepts("1d12h"));
+        assert!(!consumer.accepts("3"));
+        assert!(!consumer.accepts("1m10"));
+        assert!(!consumer.accepts("strikes"));
+        assert!(DurationArgumentConsumer::new().accepts("3"));
+    }
+}
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    ArgumentType, CommandSuggestion, StringProtoArgBehavior, SuggestionProviders,
+};
+
+use crate::command::args::{Arg, ArgumentConsumer, DefaultNameArgConsumer, GetClientSideArgParser};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::data::home_data::HOME_LIST;
+use crate::server::Server;
+
+/// The name of the respawn point of a player for `/home`, which can not be used for a home
+pub const BED: &str = "bed";
+
+/// `ArgumentConsumer` for the names of the homes of the player running the command, see
+/// [`crate::data::home_data::HomeList`]. Any name is accepted, so commands can tell that a home
+/// does not exist, but only existing ones are suggested.
+pub struct HomeArgumentConsumer {
+    /// Whether [`BED`] is suggested too
+    pub with_bed: bool,
+}
+
+impl GetClientSideArgParser for HomeArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::String(StringProtoArgBehavior::SingleWord)
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        Some(SuggestionProviders::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for HomeArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::Simple(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        let Some(player) = sender.as_player() else {
+            return Ok(None);
+        };
+        let mut suggestions: Vec<CommandSuggestion> = HOME_LIST
+            .read()
+            .await
+            .homes_of(&player.gameprofile.id)
+            .map(|home| CommandSuggestion::new(home.name.clone(), None))
+            .collect();
+        if self.with_bed {
+            suggestions.push(CommandSuggestion::new(BED.to_string(), None));
+        }
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for HomeArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "name"
+    }
+}
@@ -6,11 +6,12 @@ use pumpkin_data::damage::DamageType;
 use pumpkin_data::particle::Particle;
 use pumpkin_data::sound::SoundCategory;
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
-use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
 use pumpkin_util::{
     math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
     GameMode,
 };
+use rotation::Rotation;
 
 use super::{
     dispatcher::CommandError,
@@ -18,6 +19,7 @@ use super::{
     CommandSender,
 };
 use crate::world::bossbar::{BossbarColor, BossbarDivisions};
+use crate::world::World;
 use crate::{entity::player::Player, server::Server};
 
 pub mod block;
@@ -25,12 +27,16 @@ pub mod bool;
 pub mod bossbar_color;
 pub mod bossbar_style;
 pub mod bounded_num;
+pub mod color;
 pub mod command;
 mod coordinate;
 pub mod damage_type;
+pub mod dimension;
+pub mod duration;
 pub mod entities;
 pub mod entity;
 pub mod gamemode;
+pub mod home;
 pub mod item;
 pub mod message;
 pub mod particle;
@@ -38,14 +44,18 @@ pub mod players;
 pub mod position_2d;
 pub mod position_3d;
 pub mod position_block;
+pub mod predicate;
 pub mod resource_location;
 pub mod rotation;
+pub mod selector;
 pub mod simple;
 pub mod sound;
 pub mod sound_category;
 pub mod summonable_entities;
 pub mod textcomponent;
 pub mod time;
+pub mod warp;
+pub mod world;
 
 /// see [`crate::commands::tree::builder::argument`]
 #[async_trait]
@@ -83,11 +93,12 @@ pub trait DefaultNameArgConsumer: ArgumentConsumer {
 pub enum Arg<'a> {
     Entities(Vec<Arc<Player>>),
     Entity(Arc<Player>),
+    EntitySelector(Result<selector::EntitySelector, selector::SelectorError>),
     Players(Vec<Arc<Player>>),
     BlockPos(BlockPos),
     Pos3D(Vector3<f64>),
     Pos2D(Vector2<f64>),
-    Rotation(f32, f32),
+    Rotation(Rotation),
     GameMode(GameMode),
     CommandTree(CommandTree),
     Item(&'a str),
@@ -95,16 +106,21 @@ pub enum Arg<'a> {
     Block(&'a str),
     BossbarColor(BossbarColor),
     BossbarStyle(BossbarDivisions),
+    Color(NamedColor),
     Particle(Particle),
     Msg(String),
     TextComponent(TextComponent),
     Time(i32),
+    Duration(Result<chrono::TimeDelta, duration::DurationError>),
+    Predicate(Result<predicate::Predicate, predicate::PredicateError>),
     Num(Result<Number, NotInBounds>),
     Bool(bool),
     #[allow(unused)]
     Simple(&'a str),
     SoundCategory(SoundCategory),
     DamageType(DamageType),
+    Dimension(Result<Arc<World>, dimension::UnknownDimension>),
+    World(Arc<World>),
 }
 
 /// see [`crate::commands::tree::builder::argument`] and [`CommandTree::execute`]/[`crate::commands::tree::builder::NonLeafNodeBuilder::execute`]
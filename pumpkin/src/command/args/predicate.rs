@@ -0,0 +1,493 @@
+use async_trait::async_trait;
+use pumpkin_data::{
+    item::Item,
+    tag::{get_tag_values, RegistryKey},
+};
+use pumpkin_nbt::{
+    compound::NbtCompound,
+    snbt::{from_snbt, tag_from_snbt, SnbtError},
+    tag::NbtTag,
+};
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    block::{
+        entity::write_block_entity,
+        registry::{get_block, get_block_by_state_id, get_state_property, Block},
+    },
+    item::ItemStack,
+};
+
+use crate::command::{
+    args::{Arg, ArgumentConsumer, DefaultNameArgConsumer, FindArg, GetClientSideArgParser},
+    dispatcher::CommandError,
+    tree::RawArgs,
+    CommandSender,
+};
+use crate::{server::Server, world::World};
+
+/// Whether a predicate matches blocks or items
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PredicateKind {
+    Block,
+    Item,
+}
+
+impl PredicateKind {
+    const fn registry(self) -> RegistryKey {
+        match self {
+            Self::Block => RegistryKey::Block,
+            Self::Item => RegistryKey::Item,
+        }
+    }
+}
+
+/// Why a predicate could not be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PredicateError {
+    UnknownBlock(String),
+    UnknownItem(String),
+    UnknownTag(String),
+    Malformed(String),
+    InvalidNbt(SnbtError),
+}
+
+impl std::fmt::Display for PredicateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownBlock(name) => write!(f, "Block {name} does not exist."),
+            Self::UnknownItem(name) => write!(f, "Item {name} does not exist."),
+            Self::UnknownTag(tag) => write!(f, "Tag #{tag} does not exist."),
+            Self::Malformed(input) => write!(
+                f,
+                "Invalid predicate {input}, use a name or #tag, followed by [properties] and {{nbt}}"
+            ),
+            Self::InvalidNbt(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+fn strip_namespace(name: &str) -> &str {
+    name.strip_prefix("minecraft:").unwrap_or(name)
+}
+
+fn namespaced(name: &str) -> String {
+    if name.contains(':') {
+        name.to_string()
+    } else {
+        format!("minecraft:{name}")
+    }
+}
+
+/// Tests blocks or items, like the `block_predicate` and `item_predicate` arguments of vanilla,
+/// e.g. `#minecraft:logs[axis=y]` or `chest{Items: [{id: "minecraft:diamond"}]}`. The NBT has
+/// to be contained in the NBT of the block entity or the custom data of the item.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Predicate {
+    /// The names of the blocks or items which match, without namespace, or `None` for all of
+    /// them (`*`)
+    names: Option<Vec<String>>,
+    /// The values block states must have
+    properties: Vec<(String, String)>,
+    /// The values item components must contain, by namespaced id
+    components: Vec<(String, NbtTag)>,
+    nbt: Option<NbtCompound>,
+}
+
+impl Predicate {
+    pub fn parse(input: &str, kind: PredicateKind) -> Result<Self, PredicateError> {
+        let malformed = || PredicateError::Malformed(input.to_string());
+        let name_end = input.find(['[', '{']).unwrap_or(input.len());
+        let (name, mut rest) = input.split_at(name_end);
+
+        let names = if name == "*" {
+            None
+        } else if let Some(tag) = name.strip_prefix('#') {
+            let tag = namespaced(tag);
+            let values = get_tag_values(kind.registry(), &tag)
+                .ok_or_else(|| PredicateError::UnknownTag(tag.clone()))?;
+            Some(values.iter().flatten().cloned().collect())
+        } else {
+            let exists = match kind {
+                PredicateKind::Block => get_block(name).is_some(),
+                PredicateKind::Item => Item::from_name(strip_namespace(name)).is_some(),
+            };
+            if !exists {
+                return Err(match kind {
+                    PredicateKind::Block => PredicateError::UnknownBlock(name.to_string()),
+                    PredicateKind::Item => PredicateError::UnknownItem(name.to_string()),
+                });
+            }
+            Some(vec![strip_namespace(name).to_string()])
+        };
+
+        let mut properties = Vec::new();
+        let mut components = Vec::new();
+        if rest.starts_with('[') {
+            let end = closing_bracket(rest).ok_or_else(malformed)?;
+            for property in split_top_level(&rest[1..end]) {
+                let (key, value) = property.split_once('=').ok_or_else(malformed)?;
+                let (key, value) = (key.trim(), value.trim());
+                match kind {
+                    PredicateKind::Block => properties.push((key.to_string(), value.to_string())),
+                    PredicateKind::Item => components.push((
+                        namespaced(key),
+                        tag_from_snbt(value).map_err(PredicateError::InvalidNbt)?,
+                    )),
+                }
+            }
+            rest = &rest[end + 1..];
+        }
+
+        let nbt = match rest {
+            "" => None,
+            rest if rest.starts_with('{') => {
+                Some(from_snbt(rest).map_err(PredicateError::InvalidNbt)?)
+            }
+            _ => return Err(malformed()),
+        };
+
+        Ok(Self {
+            names,
+            properties,
+            components,
+            nbt,
+        })
+    }
+
+    fn matches_name(&self, name: &str) -> bool {
+        self.names
+            .as_ref()
+            .is_none_or(|names| names.iter().any(|entry| entry == name))
+    }
+
+    /// Whether the NBT has to be checked, which needs the NBT of the block entity
+    pub fn needs_nbt(&self) -> bool {
+        self.nbt.is_some()
+    }
+
+    /// Whether the block state matches. `block_entity` is the NBT of the block entity at the
+    /// block, which is only needed if [`Predicate::needs_nbt`].
+    pub fn matches_block(
+        &self,
+        block: &Block,
+        state_id: u16,
+        block_entity: Option<&NbtCompound>,
+    ) -> bool {
+        self.matches_name(&block.name)
+            && self
+                .properties
+                .iter()
+                .all(|(key, value)| get_state_property(state_id, key) == Some(value.as_str()))
+            && self.nbt.as_ref().is_none_or(|nbt| {
+                block_entity.is_some_and(|block_entity| nbt.is_contained_in(block_entity))
+            })
+    }
+
+    /// Whether the block at the position in the world matches
+    pub async fn matches_block_at(&self, world: &World, position: &BlockPos) -> bool {
+        let Ok(state_id) = world.get_block_state_id(position).await else {
+            return false;
+        };
+        let Some(block) = get_block_by_state_id(state_id) else {
+            return false;
+        };
+        let block_entity = if self.needs_nbt() {
+            world.get_block_entity(position, write_block_entity).await
+        } else {
+            None
+        };
+        self.matches_block(block, state_id, block_entity.as_ref())
+    }
+
+    /// Whether the item stack matches
+    pub fn matches_item(&self, stack: &ItemStack) -> bool {
+        self.matches_name(stack.item.registry_key())
+            && self.components.iter().all(|(id, value)| {
+                stack
+                    .get_component(id)
+                    .is_some_and(|component| value.is_contained_in(component))
+            })
+            && self.nbt.as_ref().is_none_or(|nbt| {
+                stack
+                    .custom_data()
+                    .is_some_and(|custom_data| nbt.is_contained_in(custom_data))
+            })
+    }
+}
+
+/// The position of the bracket closing the one the input starts with. Brackets in quotes and
+/// in NBT values don't count.
+fn closing_bracket(input: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in input.char_indices() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            (None, _) => {}
+        }
+    }
+    None
+}
+
+/// Splits at the commas which are not inside of NBT values
+fn split_top_level(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while start < input.len() {
+        let rest = &input[start..];
+        let mut end = rest.len();
+        let mut i = 0;
+        while i < rest.len() {
+            let c = rest[i..].chars().next().unwrap_or_default();
+            if matches!(c, '[' | '{') {
+                i += closing_bracket(&rest[i..]).unwrap_or(rest.len() - i - 1);
+            } else if c == ',' {
+                end = i;
+                break;
+            }
+            i += c.len_utf8();
+        }
+        if !rest[..end].trim().is_empty() {
+            parts.push(&rest[..end]);
+        }
+        start += end + 1;
+    }
+    parts
+}
+
+/// Whether the brackets and quotes of the words so far are still open, so the predicate
+/// continues in the next word, like in `{CustomName: "Secret Chest"}`
+fn is_open(input: &str) -> bool {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in input.chars() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            (None, _) => {}
+        }
+    }
+    depth > 0 || quote.is_some()
+}
+
+/// Consumes a block predicate, see [`Predicate`]
+pub struct BlockPredicateArgumentConsumer;
+
+/// Consumes an item predicate, see [`Predicate`]
+pub struct ItemPredicateArgumentConsumer;
+
+impl GetClientSideArgParser for BlockPredicateArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::BlockPredicate
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+impl GetClientSideArgParser for ItemPredicateArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::ItemPredicate
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+fn consume_predicate<'a>(args: &mut RawArgs<'a>, kind: PredicateKind) -> Option<Arg<'a>> {
+    let mut input = args.pop()?.to_string();
+    while is_open(&input) {
+        let Some(word) = args.pop() else {
+            break;
+        };
+        input.push(' ');
+        input.push_str(word);
+    }
+    Some(Arg::Predicate(Predicate::parse(&input, kind)))
+}
+
+#[async_trait]
+impl ArgumentConsumer for BlockPredicateArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        consume_predicate(args, PredicateKind::Block)
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for ItemPredicateArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        consume_predicate(args, PredicateKind::Item)
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for BlockPredicateArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "block"
+    }
+}
+
+impl DefaultNameArgConsumer for ItemPredicateArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "item"
+    }
+}
+
+fn find_predicate<'a>(
+    args: &'a super::ConsumedArgs,
+    name: &str,
+) -> Result<&'a Predicate, CommandError> {
+    match args.get(name) {
+        Some(Arg::Predicate(Ok(predicate))) => Ok(predicate),
+        Some(Arg::Predicate(Err(error))) => {
+            Err(CommandError::GeneralCommandIssue(error.to_string()))
+        }
+        _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+    }
+}
+
+impl<'a> FindArg<'a> for BlockPredicateArgumentConsumer {
+    type Data = &'a Predicate;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        find_predicate(args, name)
+    }
+}
+
+impl<'a> FindArg<'a> for ItemPredicateArgumentConsumer {
+    type Data = &'a Predicate;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        find_predicate(args, name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_nbt::snbt::from_snbt;
+    use pumpkin_world::{
+        block::registry::{get_block, get_state_id_with_property},
+        item::ItemStack,
+    };
+
+    use super::{is_open, Predicate, PredicateError, PredicateKind};
+
+    #[test]
+    fn matches_blocks() {
+        let oak_log = get_block("oak_log").unwrap();
+        let horizontal = get_state_id_with_property(oak_log.default_state_id, "axis", "x").unwrap();
+        let stone = get_block("stone").unwrap();
+
+        let logs = Predicate::parse("#minecraft:logs", PredicateKind::Block).unwrap();
+        assert!(logs.matches_block(oak_log, horizontal, None));
+        assert!(!logs.matches_block(stone, stone.default_state_id, None));
+
+        let upright = Predicate::parse("#logs[axis=y]", PredicateKind::Block).unwrap();
+        assert!(upright.matches_block(oak_log, oak_log.default_state_id, None));
+        assert!(!upright.matches_block(oak_log, horizontal, None));
+
+        let any = Predicate::parse("*", PredicateKind::Block).unwrap();
+        assert!(any.matches_block(stone, stone.default_state_id, None));
+    }
+
+    #[test]
+    fn matches_block_entities() {
+        let chest = get_block("chest").unwrap();
+        let predicate = Predicate::parse(
+            "minecraft:chest{Items: [{id: \"minecraft:diamond\"}]}",
+            PredicateKind::Block,
+        )
+        .unwrap();
+        assert!(predicate.needs_nbt());
+        let nbt = from_snbt("{Items: [{Slot: 3b, id: \"minecraft:diamond\", count: 5}]}").unwrap();
+        assert!(predicate.matches_block(chest, chest.default_state_id, Some(&nbt)));
+        assert!(!predicate.matches_block(chest, chest.default_state_id, None));
+    }
+
+    #[test]
+    fn matches_items() {
+        let mut stack = ItemStack::new(1, Item::DIAMOND_SWORD);
+        stack.set_component("damage", 3);
+        stack.custom_data_mut().put_int("level", 2);
+
+        let damaged = Predicate::parse("diamond_sword[damage=3]", PredicateKind::Item).unwrap();
+        assert!(damaged.matches_item(&stack));
+        let custom = Predicate::parse("*{level: 2}", PredicateKind::Item).unwrap();
+        assert!(custom.matches_item(&stack));
+        let other = Predicate::parse("diamond_sword{level: 3}", PredicateKind::Item).unwrap();
+        assert!(!other.matches_item(&stack));
+    }
+
+    #[test]
+    fn rejects_invalid_predicates() {
+        assert_eq!(
+            Predicate::parse("not_a_block", PredicateKind::Block),
+            Err(PredicateError::UnknownBlock("not_a_block".to_string()))
+        );
+        assert_eq!(
+            Predicate::parse("#not_a_tag", PredicateKind::Item),
+            Err(PredicateError::UnknownTag(
+                "minecraft:not_a_tag".to_string()
+            ))
+        );
+        assert!(Predicate::parse("stone[axis", PredicateKind::Block).is_err());
+        assert!(Predicate::parse("stone{", PredicateKind::Block).is_err());
+        assert!(Predicate::parse("stone]", PredicateKind::Block).is_err());
+    }
+
+    #[test]
+    fn predicates_continue_in_open_brackets() {
+        assert!(is_open("chest{CustomName:"));
+        assert!(is_open("chest{CustomName: \"Secret"));
+        assert!(!is_open("chest{CustomName: \"Secret Chest\"}"));
+    }
+}
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use async_trait::async_trait;
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
 
@@ -12,6 +14,52 @@ use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 /// yaw and pitch
 pub struct RotationArgumentConsumer;
 
+#[derive(Clone, Copy)]
+enum MaybeRelativeAngle {
+    Absolute(f32),
+    Relative(f32),
+}
+
+impl TryFrom<&str> for MaybeRelativeAngle {
+    type Error = <f32 as FromStr>::Err;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        if let Some(s) = s.strip_prefix('~') {
+            let offset = if s.is_empty() { 0.0 } else { s.parse()? };
+            Ok(Self::Relative(offset))
+        } else {
+            Ok(Self::Absolute(s.parse()?))
+        }
+    }
+}
+
+impl MaybeRelativeAngle {
+    fn into_absolute(self, origin: f32) -> f32 {
+        match self {
+            Self::Absolute(v) => v,
+            Self::Relative(offset) => origin + offset,
+        }
+    }
+}
+
+/// A yaw and pitch, each of which may be relative to the current rotation of an entity
+#[derive(Clone, Copy)]
+pub struct Rotation {
+    yaw: MaybeRelativeAngle,
+    pitch: MaybeRelativeAngle,
+}
+
+impl Rotation {
+    /// Resolves the rotation against the given yaw and pitch.
+    /// The yaw is wrapped to [-180, 180) and the pitch is clamped to [-90, 90].
+    #[must_use]
+    pub fn into_absolute(self, yaw: f32, pitch: f32) -> (f32, f32) {
+        let yaw = (self.yaw.into_absolute(yaw) + 180.0).rem_euclid(360.0) - 180.0;
+        let pitch = self.pitch.into_absolute(pitch).clamp(-90.0, 90.0);
+        (yaw, pitch)
+    }
+}
+
 impl GetClientSideArgParser for RotationArgumentConsumer {
     fn get_client_side_parser(&self) -> ArgumentType {
         ArgumentType::Rotation
@@ -30,22 +78,10 @@ impl ArgumentConsumer for RotationArgumentConsumer {
         _server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        let yaw = args.pop()?;
-        let pitch = args.pop()?;
-
-        let mut yaw = yaw.parse::<f32>().ok()?;
-        let mut pitch = pitch.parse::<f32>().ok()?;
-
-        yaw %= 360.0;
-        if yaw >= 180.0 {
-            yaw -= 360.0;
-        };
-        pitch %= 360.0;
-        if pitch >= 180.0 {
-            pitch -= 360.0;
-        };
-
-        Some(Arg::Rotation(yaw, pitch))
+        let yaw = MaybeRelativeAngle::try_from(args.pop()?).ok()?;
+        let pitch = MaybeRelativeAngle::try_from(args.pop()?).ok()?;
+
+        Some(Arg::Rotation(Rotation { yaw, pitch }))
     }
 
     async fn suggest<'a>(
@@ -65,12 +101,49 @@ impl DefaultNameArgConsumer for RotationArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for RotationArgumentConsumer {
-    type Data = (f32, f32);
+    type Data = Rotation;
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Rotation(yaw, pitch)) => Ok((*yaw, *pitch)),
+            Some(Arg::Rotation(rotation)) => Ok(*rotation),
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rotation(yaw: &str, pitch: &str) -> Rotation {
+        Rotation {
+            yaw: MaybeRelativeAngle::try_from(yaw).unwrap(),
+            pitch: MaybeRelativeAngle::try_from(pitch).unwrap(),
+        }
+    }
+
+    #[test]
+    fn relative_rotation() {
+        assert_eq!(
+            rotation("~90", "~").into_absolute(10.0, 20.0),
+            (100.0, 20.0)
+        );
+        assert_eq!(
+            rotation("~", "~-30").into_absolute(10.0, 20.0),
+            (10.0, -10.0)
+        );
+    }
+
+    #[test]
+    fn wraps_yaw_and_clamps_pitch() {
+        assert_eq!(
+            rotation("~90", "~80").into_absolute(170.0, 20.0),
+            (-100.0, 90.0)
+        );
+        assert_eq!(
+            rotation("-270", "-120").into_absolute(0.0, 0.0),
+            (90.0, -90.0)
+        );
+        assert_eq!(rotation("180", "0").into_absolute(0.0, 0.0), (-180.0, 0.0));
+    }
+}
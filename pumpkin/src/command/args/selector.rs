@@ -0,0 +1,334 @@
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::command::args::{
+    Arg, ArgumentConsumer, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::server::Server;
+
+/// `ArgumentConsumer` for target selectors like `@e[type=zombie,limit=5]` or a player name, which
+/// select all entities, not only players. Only the `type` and `limit` options are supported.
+pub struct EntitySelectorArgumentConsumer;
+
+/// Which entities a selector picks from
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum SelectorKind {
+    /// `@e`
+    AllEntities,
+    /// `@a`
+    AllPlayers,
+    /// `@s`
+    Sender,
+    /// `@p`
+    NearestPlayer,
+    /// `@n`
+    NearestEntity,
+    /// `@r`
+    RandomPlayer,
+    /// A player by their name
+    Name(String),
+}
+
+/// A parsed target selector
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntitySelector {
+    kind: SelectorKind,
+    /// The `type` option, without the namespace, and whether it is negated like `type=!zombie`
+    entity_type: Option<(String, bool)>,
+    limit: Option<usize>,
+}
+
+/// Why a selector could not be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SelectorError {
+    UnknownSelector(String),
+    UnknownOption(String),
+    UnknownEntityType(String),
+    InvalidLimit(String),
+    Malformed,
+}
+
+impl std::fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownSelector(selector) => write!(f, "Unknown selector type '{selector}'"),
+            Self::UnknownOption(option) => write!(f, "Unknown option '{option}'"),
+            Self::UnknownEntityType(name) => write!(f, "Unknown entity type '{name}'"),
+            Self::InvalidLimit(limit) => write!(f, "Invalid limit '{limit}'"),
+            Self::Malformed => write!(f, "Expected options like [type=zombie,limit=1]"),
+        }
+    }
+}
+
+impl EntitySelector {
+    pub fn parse(s: &str) -> Result<Self, SelectorError> {
+        let Some(selector) = s.strip_prefix('@') else {
+            return Ok(Self {
+                kind: SelectorKind::Name(s.to_string()),
+                entity_type: None,
+                limit: None,
+            });
+        };
+        let (name, options) = match selector.split_once('[') {
+            Some((name, options)) => (
+                name,
+                Some(options.strip_suffix(']').ok_or(SelectorError::Malformed)?),
+            ),
+            None => (selector, None),
+        };
+        let kind = match name {
+            "e" => SelectorKind::AllEntities,
+            "a" => SelectorKind::AllPlayers,
+            "s" => SelectorKind::Sender,
+            "p" => SelectorKind::NearestPlayer,
+            "n" => SelectorKind::NearestEntity,
+            "r" => SelectorKind::RandomPlayer,
+            _ => return Err(SelectorError::UnknownSelector(format!("@{name}"))),
+        };
+
+        let mut selector = Self {
+            kind,
+            entity_type: None,
+            limit: None,
+        };
+        for option in options.iter().flat_map(|options| options.split(',')) {
+            if option.is_empty() {
+                continue;
+            }
+            let (key, value) = option.split_once('=').ok_or(SelectorError::Malformed)?;
+            match key.trim() {
+                "type" => {
+                    let value = value.trim();
+                    let (value, negated) = value
+                        .strip_prefix('!')
+                        .map_or((value, false), |value| (value, true));
+                    let name = value.strip_prefix("minecraft:").unwrap_or(value);
+                    if EntityType::from_name(name).is_none() {
+                        return Err(SelectorError::UnknownEntityType(value.to_string()));
+                    }
+                    selector.entity_type = Some((name.to_string(), negated));
+                }
+                "limit" => {
+                    let limit = value
+                        .trim()
+                        .parse()
+                        .ok()
+                        .filter(|limit| *limit > 0)
+                        .ok_or_else(|| SelectorError::InvalidLimit(value.to_string()))?;
+                    selector.limit = Some(limit);
+                }
+                key => return Err(SelectorError::UnknownOption(key.to_string())),
+            }
+        }
+        Ok(selector)
+    }
+
+    /// The most entities the selector selects. Selectors which pick a single entity, like `@p`,
+    /// select at most one.
+    fn limit(&self) -> Option<usize> {
+        self.limit.or(match self.kind {
+            SelectorKind::AllEntities | SelectorKind::AllPlayers => None,
+            _ => Some(1),
+        })
+    }
+
+    /// Counts the selected entities among the candidates, given by the names of their types
+    fn count_matching<'b>(&self, candidates: impl IntoIterator<Item = &'b str>) -> usize {
+        let matching = candidates
+            .into_iter()
+            .filter(|candidate| {
+                self.entity_type
+                    .as_ref()
+                    .is_none_or(|(name, negated)| (candidate == name) != *negated)
+            })
+            .count();
+        self.limit().map_or(matching, |limit| matching.min(limit))
+    }
+
+    /// Counts the entities the selector selects, in all worlds
+    pub async fn count(&self, sender: &CommandSender<'_>, server: &Server) -> usize {
+        let player = EntityType::PLAYER.resource_name;
+        let candidates = match &self.kind {
+            SelectorKind::Sender => sender.as_player().map(|_| vec![player]).unwrap_or_default(),
+            SelectorKind::Name(name) => server
+                .get_player_by_name(name)
+                .await
+                .map(|_| vec![player])
+                .unwrap_or_default(),
+            SelectorKind::AllPlayers | SelectorKind::NearestPlayer | SelectorKind::RandomPlayer => {
+                vec![player; server.get_all_players().await.len()]
+            }
+            SelectorKind::AllEntities | SelectorKind::NearestEntity => {
+                let mut candidates = vec![player; server.get_all_players().await.len()];
+                for world in server.worlds.read().await.iter() {
+                    for entity in world.entities.read().await.values() {
+                        candidates.push(entity.get_entity().entity_type.resource_name);
+                    }
+                }
+                candidates
+            }
+        };
+        self.count_matching(candidates)
+    }
+}
+
+impl GetClientSideArgParser for EntitySelectorArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::Entity { flags: 0 }
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for EntitySelectorArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let s = args.pop()?;
+        Some(Arg::EntitySelector(EntitySelector::parse(s)))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for EntitySelectorArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "targets"
+    }
+}
+
+impl<'a> FindArg<'a> for EntitySelectorArgumentConsumer {
+    type Data = &'a EntitySelector;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::EntitySelector(Ok(selector))) => Ok(selector),
+            Some(Arg::EntitySelector(Err(error))) => {
+                Err(CommandError::GeneralCommandIssue(error.to_string()))
+            }
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_util::math::vector3::Vector3;
+    use temp_dir::TempDir;
+    use tokio::sync::mpsc;
+    use uuid::Uuid;
+
+    use super::{EntitySelector, SelectorError};
+    use crate::command::CommandSender;
+    use crate::entity::{mob::MobEntity, EntityBase};
+    use crate::net::{Client, GameProfile};
+    use crate::server::Server;
+
+    #[test]
+    fn counts_the_selected_entities() {
+        let entities = ["player", "zombie", "zombie", "item", "player"];
+        let count = |s| EntitySelector::parse(s).unwrap().count_matching(entities);
+        assert_eq!(count("@e"), 5);
+        assert_eq!(count("@e[type=zombie]"), 2);
+        assert_eq!(count("@e[type=minecraft:zombie]"), 2);
+        assert_eq!(count("@e[type=!zombie]"), 3);
+        assert_eq!(count("@e[type=zombie,limit=1]"), 1);
+        assert_eq!(count("@e[type=creeper]"), 0);
+        assert_eq!(count("@n"), 1);
+    }
+
+    #[tokio::test]
+    async fn counts_the_entities_of_the_worlds() {
+        let dir = TempDir::new().unwrap();
+        let server = Server::with_world_folder(dir.path().join("world"));
+
+        // A player without a connection, the packets sent to them are dropped
+        let client = Client::new(mpsc::channel(1).0, "127.0.0.1:25565".parse().unwrap(), 0);
+        *client.gameprofile.lock().await = Some(GameProfile {
+            id: Uuid::new_v4(),
+            name: "Steve".to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        });
+        let (player, world) = server.add_player(Arc::new(client)).await;
+
+        for (entity_type, x) in [
+            (EntityType::ZOMBIE, 0.0),
+            (EntityType::ZOMBIE, 4.0),
+            (EntityType::COW, 8.0),
+        ] {
+            let entity = server.add_entity(Vector3::new(x, 64.0, 0.0), entity_type, &world);
+            let mob: Arc<dyn EntityBase> = Arc::new(MobEntity::new(entity).await);
+            world
+                .entities
+                .write()
+                .await
+                .insert(mob.get_entity().entity_uuid, mob);
+        }
+
+        let player = CommandSender::Player(player);
+        let console = CommandSender::Console;
+        for (selector, sender, expected) in [
+            ("@e", &player, 4),
+            ("@e[type=zombie]", &player, 2),
+            ("@e[type=!player]", &player, 3),
+            ("@e[type=cow,limit=1]", &player, 1),
+            ("@a", &player, 1),
+            ("@s", &player, 1),
+            ("Steve", &player, 1),
+            ("Alex", &player, 0),
+            ("@s", &console, 0),
+        ] {
+            let selected = EntitySelector::parse(selector).unwrap();
+            assert_eq!(
+                selected.count(sender, &server).await,
+                expected,
+                "{selector}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_selectors() {
+        assert_eq!(
+            EntitySelector::parse("@x"),
+            Err(SelectorError::UnknownSelector("@x".to_string()))
+        );
+        assert_eq!(
+            EntitySelector::parse("@e[type=nothing]"),
+            Err(SelectorError::UnknownEntityType("nothing".to_string()))
+        );
+        assert_eq!(
+            EntitySelector::parse("@e[limit=0]"),
+            Err(SelectorError::InvalidLimit("0".to_string()))
+        );
+        assert_eq!(
+            EntitySelector::parse("@e[distance=..5]"),
+            Err(SelectorError::UnknownOption("distance".to_string()))
+        );
+        assert_eq!(
+            EntitySelector::parse("@e[type=zombie"),
+            Err(SelectorError::Malformed)
+        );
+    }
+}
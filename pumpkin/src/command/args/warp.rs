@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    ArgumentType, CommandSuggestion, StringProtoArgBehavior, SuggestionProviders,
+};
+
+use crate::command::args::{Arg, ArgumentConsumer, DefaultNameArgConsumer, GetClientSideArgParser};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::data::warp_data::WARP_LIST;
+use crate::server::Server;
+
+/// `ArgumentConsumer` for the names of warps, see [`crate::data::warp_data::WarpList`]. Any name
+/// is accepted, so commands can tell that a warp does not exist, but only existing ones are
+/// suggested.
+pub struct WarpArgumentConsumer;
+
+impl GetClientSideArgParser for WarpArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::String(StringProtoArgBehavior::SingleWord)
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        Some(SuggestionProviders::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for WarpArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::Simple(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        let suggestions = WARP_LIST
+            .read()
+            .await
+            .warps
+            .iter()
+            .map(|warp| CommandSuggestion::new(warp.name.clone(), None))
+            .collect();
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for WarpArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "name"
+    }
+}
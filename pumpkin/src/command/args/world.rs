@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    ArgumentType, CommandSuggestion, StringProtoArgBehavior, SuggestionProviders,
+};
+
+use crate::command::args::{
+    Arg, ArgumentConsumer, ConsumedArgs, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::server::Server;
+use crate::world::World;
+
+/// `ArgumentConsumer` for the names of loaded worlds (e.g. `world_nether`), see [`World::name`]
+pub struct WorldArgumentConsumer;
+
+impl GetClientSideArgParser for WorldArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::String(StringProtoArgBehavior::SingleWord)
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        Some(SuggestionProviders::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for WorldArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let name = args.pop()?;
+        server.get_world_by_name(name).await.map(Arg::World)
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        let suggestions = server
+            .worlds
+            .read()
+            .await
+            .iter()
+            .map(|world| CommandSuggestion::new(world.name(), None))
+            .collect();
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for WorldArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "world"
+    }
+}
+
+impl<'a> FindArg<'a> for WorldArgumentConsumer {
+    type Data = &'a Arc<World>;
+
+    fn find_arg(args: &'a ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::World(data)) => Ok(data),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
+
+impl WorldArgumentConsumer {
+    /// The world given by the argument, or [`CommandSender::current_world`] if it was left out
+    pub async fn find_arg_or_current(
+        args: &ConsumedArgs<'_>,
+        name: &str,
+        sender: &CommandSender<'_>,
+        server: &Server,
+    ) -> Result<Arc<World>, CommandError> {
+        match Self::find_arg(args, name) {
+            Ok(world) => Ok(world.clone()),
+            Err(_) => sender.current_world(server).await,
+        }
+    }
+}
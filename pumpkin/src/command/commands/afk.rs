@@ -0,0 +1,91 @@
+use std::sync::{atomic::Ordering, Arc};
+
+use async_trait::async_trait;
+use pumpkin_util::{text::TextComponent, PermissionLvl};
+
+use crate::{
+    command::{
+        args::{players::PlayersArgumentConsumer, ConsumedArgs, FindArg},
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::Player,
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["afk"];
+
+const DESCRIPTION: &str = "Marks players as away from keyboard, or as back again.";
+
+const ARG_TARGETS: &str = "targets";
+
+async fn toggle(sender: &mut CommandSender<'_>, server: &Server, targets: &[Arc<Player>]) {
+    for target in targets {
+        let afk = !target.afk.load(Ordering::Relaxed);
+        target.set_afk(server, afk).await;
+
+        let message = if afk {
+            "You are now AFK"
+        } else {
+            "You are no longer AFK"
+        };
+        target
+            .send_system_message(&TextComponent::text(message))
+            .await;
+
+        let is_sender = sender
+            .as_player()
+            .is_some_and(|player| player.gameprofile.id == target.gameprofile.id);
+        if !is_sender {
+            let message = if afk {
+                format!("Marked {} as AFK", target.gameprofile.name)
+            } else {
+                format!("{} is no longer AFK", target.gameprofile.name)
+            };
+            sender.send_message(TextComponent::text(message)).await;
+        }
+    }
+}
+
+struct SelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for SelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        toggle(sender, server, &[player]).await;
+        Ok(())
+    }
+}
+
+struct TargetsExecutor;
+
+#[async_trait]
+impl CommandExecutor for TargetsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        toggle(sender, server, targets).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
+        .then(
+            require(|sender| sender.has_permission_lvl(PermissionLvl::Two))
+                .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(TargetsExecutor)),
+        )
+}
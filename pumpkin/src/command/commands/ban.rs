@@ -1,6 +1,9 @@
 use crate::{
     command::{
-        args::{message::MsgArgConsumer, players::PlayersArgumentConsumer, Arg, ConsumedArgs},
+        args::{
+            duration::DurationArgumentConsumer, message::MsgArgConsumer,
+            players::PlayersArgumentConsumer, Arg, ConsumedArgs, FindArg,
+        },
         tree::{builder::argument, CommandTree},
         CommandError, CommandExecutor, CommandSender,
     },
@@ -11,19 +14,21 @@ use crate::{
     entity::player::Player,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, Local};
 use pumpkin_util::text::TextComponent;
-use CommandError::InvalidConsumption;
+use CommandError::{GeneralCommandIssue, InvalidConsumption};
 
 const NAMES: [&str; 1] = ["ban"];
-const DESCRIPTION: &str = "bans a player";
+const DESCRIPTION: &str = "bans a player, optionally for a limited time";
 
 const ARG_TARGET: &str = "player";
+const ARG_DURATION: &str = "duration";
 const ARG_REASON: &str = "reason";
 
-struct BanNoReasonExecutor;
+struct BanExecutor;
 
 #[async_trait]
-impl CommandExecutor for BanNoReasonExecutor {
+impl CommandExecutor for BanExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
@@ -33,36 +38,34 @@ impl CommandExecutor for BanNoReasonExecutor {
         let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
             return Err(InvalidConsumption(Some(ARG_TARGET.into())));
         };
-
-        ban_player(sender, &targets[0], None).await;
-        Ok(())
-    }
-}
-
-struct BanReasonExecutor;
-
-#[async_trait]
-impl CommandExecutor for BanReasonExecutor {
-    async fn execute<'a>(
-        &self,
-        sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
-        args: &ConsumedArgs<'a>,
-    ) -> Result<(), CommandError> {
-        let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
-        };
-
-        let Some(Arg::Msg(reason)) = args.get(ARG_REASON) else {
-            return Err(InvalidConsumption(Some(ARG_REASON.into())));
+        // The duration and reason are both optional
+        let expires = args
+            .contains_key(ARG_DURATION)
+            .then(|| {
+                let duration = DurationArgumentConsumer::find_arg(args, ARG_DURATION)?;
+                Local::now()
+                    .fixed_offset()
+                    .checked_add_signed(duration)
+                    .ok_or_else(|| GeneralCommandIssue("The duration is too long".to_string()))
+            })
+            .transpose()?;
+        let reason = match args.get(ARG_REASON) {
+            Some(Arg::Msg(reason)) => Some(reason.clone()),
+            Some(_) => return Err(InvalidConsumption(Some(ARG_REASON.into()))),
+            None => None,
         };
 
-        ban_player(sender, &targets[0], Some(reason.to_string())).await;
+        ban_player(sender, &targets[0], expires, reason).await;
         Ok(())
     }
 }
 
-async fn ban_player(sender: &CommandSender<'_>, player: &Player, reason: Option<String>) {
+async fn ban_player(
+    sender: &CommandSender<'_>,
+    player: &Player,
+    expires: Option<DateTime<FixedOffset>>,
+    reason: Option<String>,
+) {
     let mut banned_players = BANNED_PLAYER_LIST.write().await;
 
     let reason = reason.unwrap_or_else(|| "Banned by an operator.".to_string());
@@ -78,7 +81,7 @@ async fn ban_player(sender: &CommandSender<'_>, player: &Player, reason: Option<
     banned_players.banned_players.push(BannedPlayerEntry::new(
         profile,
         sender.to_string(),
-        None,
+        expires,
         reason.clone(),
     ));
 
@@ -107,7 +110,12 @@ async fn ban_player(sender: &CommandSender<'_>, player: &Player, reason: Option<
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_TARGET, PlayersArgumentConsumer)
-            .execute(BanNoReasonExecutor)
-            .then(argument(ARG_REASON, MsgArgConsumer).execute(BanReasonExecutor)),
+            .execute(BanExecutor)
+            .then(
+                argument(ARG_DURATION, DurationArgumentConsumer::new().require_unit())
+                    .execute(BanExecutor)
+                    .then(argument(ARG_REASON, MsgArgConsumer).execute(BanExecutor)),
+            )
+            .then(argument(ARG_REASON, MsgArgConsumer).execute(BanExecutor)),
     )
 }
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{message::MsgArgConsumer, Arg, ConsumedArgs},
+        tree::builder::{argument, literal},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::TitleMode,
+    server::motd,
+};
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 2] = ["broadcast", "alert"];
+
+const DESCRIPTION: &str = "Announce a message to all Players.";
+
+const ARG_MESSAGE: &str = "message";
+
+/// The mode the message is shown with in addition to the chat, if any
+struct BroadcastExecutor(Option<TitleMode>);
+
+#[async_trait]
+impl CommandExecutor for BroadcastExecutor {
+    async fn execute<'a>(
+        &self,
+        _sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Msg(msg)) = args.get(ARG_MESSAGE) else {
+            return Err(InvalidConsumption(Some(ARG_MESSAGE.into())));
+        };
+
+        let config = &ADVANCED_CONFIG.broadcast;
+        // The message inherits the color of its parent, unless it sets its own
+        let message = TextComponent::text("")
+            .color_named(config.color)
+            .add_child(motd::parse(msg));
+        let text = TextComponent::text("")
+            .color_named(config.color)
+            .add_child(
+                TextComponent::text(config.prefix.clone())
+                    .color_named(config.prefix_color)
+                    .bold(),
+            )
+            .add_child(message.clone());
+
+        for player in server.get_all_players().await {
+            player.send_system_message(&text).await;
+            match &self.0 {
+                Some(TitleMode::Title) => player.show_title(&message, &TitleMode::Title).await,
+                Some(mode) => player.show_title(&text, mode).await,
+                None => {}
+            }
+        }
+        log::info!("{}", text.to_pretty_console());
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("actionbar").then(
+                argument(ARG_MESSAGE, MsgArgConsumer)
+                    .execute(BroadcastExecutor(Some(TitleMode::ActionBar))),
+            ),
+        )
+        .then(
+            literal("title").then(
+                argument(ARG_MESSAGE, MsgArgConsumer)
+                    .execute(BroadcastExecutor(Some(TitleMode::Title))),
+            ),
+        )
+        .then(argument(ARG_MESSAGE, MsgArgConsumer).execute(BroadcastExecutor(None)))
+}
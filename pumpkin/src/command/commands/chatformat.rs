@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{
+            players::PlayersArgumentConsumer, textcomponent::TextComponentArgConsumer,
+            ConsumedArgs, FindArg,
+        },
+        tree::builder::{argument, literal},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{
+        chat_format_data::{ChatFormatPart, CHAT_FORMAT_LIST},
+        SaveJSONConfiguration,
+    },
+    entity::player::Player,
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["chatformat"];
+
+const DESCRIPTION: &str = "Sets the prefix and suffix shown around the names of players in chat.";
+
+const ARG_TARGETS: &str = "targets";
+
+const ARG_TEXT: &str = "text";
+
+fn part_name(part: ChatFormatPart) -> &'static str {
+    match part {
+        ChatFormatPart::Prefix => "prefix",
+        ChatFormatPart::Suffix => "suffix",
+    }
+}
+
+fn target_name(targets: &[Arc<Player>]) -> String {
+    match targets {
+        [target] => target.gameprofile.name.clone(),
+        _ => format!("{} players", targets.len()),
+    }
+}
+
+async fn set_part(targets: &[Arc<Player>], part: ChatFormatPart, text: Option<&TextComponent>) {
+    let mut chat_formats = CHAT_FORMAT_LIST.write().await;
+    for target in targets {
+        chat_formats.set(
+            target.gameprofile.id,
+            &target.gameprofile.name,
+            part,
+            text.cloned(),
+        );
+    }
+    chat_formats.save();
+}
+
+struct SetExecutor(ChatFormatPart);
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let text = TextComponentArgConsumer::find_arg(args, ARG_TEXT)?;
+
+        set_part(targets, self.0, Some(&text)).await;
+        sender
+            .send_message(
+                TextComponent::text(format!(
+                    "Set the chat {} of {} to ",
+                    part_name(self.0),
+                    target_name(targets)
+                ))
+                .add_child(text),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+struct ClearExecutor(ChatFormatPart);
+
+#[async_trait]
+impl CommandExecutor for ClearExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        set_part(targets, self.0, None).await;
+        sender
+            .send_message(TextComponent::text(format!(
+                "Cleared the chat {} of {}",
+                part_name(self.0),
+                target_name(targets)
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let parts = [ChatFormatPart::Prefix, ChatFormatPart::Suffix];
+
+    let mut set = literal("set");
+    let mut clear = literal("clear");
+    for part in parts {
+        set = set.then(
+            literal(part_name(part)).then(
+                argument(ARG_TARGETS, PlayersArgumentConsumer)
+                    .then(argument(ARG_TEXT, TextComponentArgConsumer).execute(SetExecutor(part))),
+            ),
+        );
+        clear = clear.then(
+            literal(part_name(part))
+                .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(ClearExecutor(part))),
+        );
+    }
+
+    CommandTree::new(NAMES, DESCRIPTION).then(set).then(clear)
+}
@@ -7,36 +7,75 @@ use pumpkin_util::text::color::NamedColor;
 use pumpkin_util::text::hover::HoverEvent;
 use pumpkin_util::text::TextComponent;
 
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
 use crate::command::args::entities::EntitiesArgumentConsumer;
-use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::args::predicate::{ItemPredicateArgumentConsumer, Predicate};
+use crate::command::args::{Arg, ConsumedArgs, FindArg};
 use crate::command::tree::builder::{argument, require};
 use crate::command::tree::CommandTree;
 use crate::command::{CommandError, CommandExecutor, CommandSender};
 use crate::entity::player::Player;
-use CommandError::InvalidConsumption;
+use CommandError::{GeneralCommandIssue, InvalidConsumption};
 
 const NAMES: [&str; 1] = ["clear"];
 const DESCRIPTION: &str = "Clear yours or targets inventory.";
 
 const ARG_TARGET: &str = "target";
+const ARG_ITEM: &str = "item";
+const ARG_MAX_COUNT: &str = "maxCount";
 
-async fn clear_player(target: &Player) -> usize {
+fn max_count_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_MAX_COUNT).min(0)
+}
+
+/// Removes the items matching the predicate, or all items without one, and returns how many
+/// were removed. At most `max_count` items are removed, if it is zero they are only counted.
+async fn clear_player(
+    target: &Player,
+    predicate: Option<&Predicate>,
+    max_count: Option<usize>,
+) -> usize {
     let mut inventory = target.inventory().lock().await;
 
-    let slots = inventory.all_slots();
-    let items_count = slots
-        .iter()
-        .filter_map(|slot| slot.as_ref().map(|slot| slot.item_count as usize))
-        .sum();
-    for slot in slots {
-        *slot = None;
+    let mut remaining = max_count.unwrap_or(usize::MAX);
+    let mut item_count = 0;
+    for slot in inventory.all_slots() {
+        let Some(stack) = slot.as_mut() else {
+            continue;
+        };
+        if predicate.is_some_and(|predicate| !predicate.matches_item(stack)) {
+            continue;
+        }
+        if max_count == Some(0) {
+            item_count += usize::from(stack.item_count);
+            continue;
+        }
+        let removed = stack
+            .item_count
+            .min(u8::try_from(remaining).unwrap_or(u8::MAX));
+        remaining -= usize::from(removed);
+        item_count += usize::from(removed);
+        if removed == stack.item_count {
+            *slot = None;
+        } else {
+            stack.item_count -= removed;
+        }
+        if remaining == 0 {
+            break;
+        }
     }
     drop(inventory);
-    target.set_container_content(None).await;
-    items_count
+    if max_count != Some(0) {
+        target.set_container_content(None).await;
+    }
+    item_count
 }
 
-fn clear_command_text_output(item_count: usize, targets: &[Arc<Player>]) -> TextComponent {
+fn clear_command_text_output(
+    item_count: usize,
+    targets: &[Arc<Player>],
+    only_count: bool,
+) -> TextComponent {
     match targets {
         [target] if item_count == 0 => TextComponent::translate(
             "clear.failed.single",
@@ -44,7 +83,11 @@ fn clear_command_text_output(item_count: usize, targets: &[Arc<Player>]) -> Text
         )
         .color_named(NamedColor::Red),
         [target] => TextComponent::translate(
-            "commands.clear.success.single",
+            if only_count {
+                "commands.clear.test.single"
+            } else {
+                "commands.clear.success.single"
+            },
             [
                 TextComponent::text(item_count.to_string()),
                 TextComponent::text(target.gameprofile.name.clone())
@@ -64,7 +107,11 @@ fn clear_command_text_output(item_count: usize, targets: &[Arc<Player>]) -> Text
         )
         .color_named(NamedColor::Red),
         targets => TextComponent::translate(
-            "commands.clear.success.multiple",
+            if only_count {
+                "commands.clear.test.multiple"
+            } else {
+                "commands.clear.success.multiple"
+            },
             [
                 TextComponent::text(item_count.to_string()),
                 TextComponent::text(targets.len().to_string()),
@@ -87,12 +134,29 @@ impl CommandExecutor for ClearExecutor {
             return Err(InvalidConsumption(Some(ARG_TARGET.into())));
         };
 
+        let predicate = if args.contains_key(ARG_ITEM) {
+            Some(ItemPredicateArgumentConsumer::find_arg(args, ARG_ITEM)?)
+        } else {
+            None
+        };
+        let max_count = if args.contains_key(ARG_MAX_COUNT) {
+            let Ok(max_count) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_MAX_COUNT)?
+            else {
+                return Err(GeneralCommandIssue(
+                    "The maximum count can't be negative".to_string(),
+                ));
+            };
+            Some(usize::try_from(max_count).unwrap_or_default())
+        } else {
+            None
+        };
+
         let mut item_count = 0;
         for target in targets {
-            item_count += clear_player(target).await;
+            item_count += clear_player(target, predicate, max_count).await;
         }
 
-        let msg = clear_command_text_output(item_count, targets);
+        let msg = clear_command_text_output(item_count, targets, max_count == Some(0));
 
         sender.send_message(msg).await;
 
@@ -112,10 +176,10 @@ impl CommandExecutor for ClearSelfExecutor {
     ) -> Result<(), CommandError> {
         let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
 
-        let item_count = clear_player(&target).await;
+        let item_count = clear_player(&target, None, None).await;
 
         let hold_target = [target];
-        let msg = clear_command_text_output(item_count, &hold_target);
+        let msg = clear_command_text_output(item_count, &hold_target, false);
 
         sender.send_message(msg).await;
 
@@ -126,6 +190,14 @@ impl CommandExecutor for ClearSelfExecutor {
 #[allow(clippy::redundant_closure_for_method_calls)] // causes lifetime issues
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
-        .then(argument(ARG_TARGET, EntitiesArgumentConsumer).execute(ClearExecutor))
+        .then(
+            argument(ARG_TARGET, EntitiesArgumentConsumer)
+                .then(
+                    argument(ARG_ITEM, ItemPredicateArgumentConsumer)
+                        .then(argument(ARG_MAX_COUNT, max_count_consumer()).execute(ClearExecutor))
+                        .execute(ClearExecutor),
+                )
+                .execute(ClearExecutor),
+        )
         .then(require(|sender| sender.is_player()).execute(ClearSelfExecutor))
 }
@@ -0,0 +1,103 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{
+            command::CommandTreeArgumentConsumer, simple::SimpleArgConsumer, Arg, ConsumedArgs,
+            FindArg,
+        },
+        builtin_dispatcher,
+        tree::builder::{argument, literal},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+use CommandError::{GeneralCommandIssue, InvalidConsumption};
+
+const NAMES: [&str; 1] = ["command"];
+
+const DESCRIPTION: &str = "Enables or disables built-in commands until the server restarts.";
+
+const ARG_NAME: &str = "name";
+
+struct EnableExecutor;
+
+#[async_trait]
+impl CommandExecutor for EnableExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+            return Err(InvalidConsumption(Some(ARG_NAME.into())));
+        };
+        let Ok(tree) = builtin_dispatcher().get_tree(name).cloned() else {
+            return Err(GeneralCommandIssue(format!(
+                "There is no built-in command called {name}"
+            )));
+        };
+        let primary_name = &tree.names[0];
+        if server
+            .command_dispatcher
+            .read()
+            .await
+            .get_tree(primary_name)
+            .is_ok()
+        {
+            return Err(GeneralCommandIssue(format!(
+                "/{primary_name} is already enabled"
+            )));
+        }
+
+        server.toggle_command(primary_name.clone(), true).await;
+        sender
+            .send_message(TextComponent::text(format!("Enabled /{primary_name}")))
+            .await;
+        Ok(())
+    }
+}
+
+struct DisableExecutor;
+
+#[async_trait]
+impl CommandExecutor for DisableExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let tree = CommandTreeArgumentConsumer::find_arg(args, ARG_NAME)?;
+        let primary_name = &tree.names[0];
+        // Otherwise it could not be enabled again
+        if NAMES.contains(&primary_name.as_str()) {
+            return Err(GeneralCommandIssue(
+                "This command can't be disabled".to_string(),
+            ));
+        }
+        if builtin_dispatcher().get_tree(primary_name).is_err() {
+            return Err(GeneralCommandIssue(format!(
+                "/{primary_name} is not a built-in command"
+            )));
+        }
+
+        server.toggle_command(primary_name.clone(), false).await;
+        sender
+            .send_message(TextComponent::text(format!("Disabled /{primary_name}")))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("enable").then(argument(ARG_NAME, SimpleArgConsumer).execute(EnableExecutor)))
+        .then(
+            literal("disable")
+                .then(argument(ARG_NAME, CommandTreeArgumentConsumer).execute(DisableExecutor)),
+        )
+}
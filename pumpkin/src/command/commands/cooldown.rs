@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use pumpkin_protocol::{
+    client::play::CCooldown,
+    codec::{identifier::Identifier, var_int::VarInt},
+};
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::command::{
+    args::{
+        bounded_num::BoundedNumArgumentConsumer, item::ItemArgumentConsumer,
+        players::PlayersArgumentConsumer, ConsumedArgs, FindArg,
+    },
+    tree::builder::argument,
+    tree::CommandTree,
+    CommandError, CommandExecutor, CommandSender,
+};
+
+const NAMES: [&str; 1] = ["cooldown"];
+
+const DESCRIPTION: &str = "Applies an item use cooldown to player(s). 0 ticks clears it.";
+
+const ARG_TARGETS: &str = "targets";
+
+const ARG_ITEM: &str = "item";
+
+const ARG_TICKS: &str = "ticks";
+
+fn ticks_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_TICKS).min(0)
+}
+
+struct CooldownExecutor;
+
+#[async_trait]
+impl CommandExecutor for CooldownExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        // Validates that the item exists
+        let (item_name, item) = ItemArgumentConsumer::find_arg(args, ARG_ITEM)?;
+        let Ok(ticks) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_TICKS)? else {
+            sender
+                .send_message(
+                    TextComponent::text("The cooldown can't be negative")
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        };
+
+        // Items use their own id as cooldown group
+        let group = Identifier::vanilla(item_name.strip_prefix("minecraft:").unwrap_or(item_name));
+        for target in targets {
+            target
+                .client
+                .send_packet(&CCooldown::new(group.clone(), VarInt(ticks)))
+                .await;
+        }
+
+        let targets_text = if let [target] = targets {
+            target.gameprofile.name.clone()
+        } else {
+            format!("{} players", targets.len())
+        };
+        let message = if ticks == 0 {
+            TextComponent::text("Cleared the cooldown of ")
+                .add_child(item.translated_name())
+                .add_child(TextComponent::text(format!(" for {targets_text}")))
+        } else {
+            TextComponent::text("Applied a cooldown of ")
+                .add_child(TextComponent::text(format!("{ticks} ticks to ")))
+                .add_child(item.translated_name())
+                .add_child(TextComponent::text(format!(" for {targets_text}")))
+        };
+        sender.send_message(message).await;
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGETS, PlayersArgumentConsumer).then(
+            argument(ARG_ITEM, ItemArgumentConsumer)
+                .then(argument(ARG_TICKS, ticks_consumer()).execute(CooldownExecutor)),
+        ),
+    )
+}
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use pumpkin_data::screen::WindowType;
+use pumpkin_inventory::OpenContainer;
+
+use crate::{
+    command::{
+        args::{players::PlayersArgumentConsumer, ConsumedArgs, FindArg},
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+use CommandError::{GeneralCommandIssue, InvalidRequirement};
+
+const NAMES: [&str; 3] = ["enderchest", "echest", "ec"];
+const DESCRIPTION: &str = "Opens your ender chest, or the ender chest of another player.";
+
+const ARG_PLAYER: &str = "player";
+
+struct EnderChestExecutor;
+
+#[async_trait]
+impl CommandExecutor for EnderChestExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let owner = if args.contains_key(ARG_PLAYER) {
+            match PlayersArgumentConsumer::find_arg(args, ARG_PLAYER)? {
+                [owner] => owner.clone(),
+                _ => {
+                    return Err(GeneralCommandIssue(
+                        "Only one player can be selected".to_string(),
+                    ))
+                }
+            }
+        } else {
+            player.clone()
+        };
+
+        // The container is shared with the owner, so changes are seen by both right away
+        let container =
+            OpenContainer::new_virtual_container(player.entity_id(), owner.ender_chest.clone());
+        player
+            .open_virtual_container(server, container, WindowType::Generic9x3)
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(EnderChestExecutor)
+            .then(argument(ARG_PLAYER, PlayersArgumentConsumer).execute(EnderChestExecutor)),
+    )
+}
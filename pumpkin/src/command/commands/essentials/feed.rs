@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::permission::PermissionLvl;
+
+use crate::{
+    command::{
+        args::{players::PlayersArgumentConsumer, ConsumedArgs, FindArg},
+        dispatcher::CommandDispatcher,
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::Player,
+    server::Server,
+};
+
+use super::notify;
+
+const NAMES: [&str; 1] = ["feed"];
+
+const DESCRIPTION: &str = "Fills the hunger bar of players.";
+
+const ARG_TARGETS: &str = "targets";
+
+/// The food level of a full hunger bar
+pub const MAX_FOOD: u32 = 20;
+
+/// The saturation can't be higher than the food level
+pub const MAX_SATURATION: f32 = 20.0;
+
+async fn feed(sender: &mut CommandSender<'_>, targets: &[Arc<Player>]) {
+    for target in targets {
+        target.hunger_manager.level.store(MAX_FOOD);
+        target.hunger_manager.saturation.store(MAX_SATURATION);
+        target.send_health().await;
+        notify(
+            sender,
+            target,
+            "Your hunger has been satisfied",
+            format!("Fed {}", target.gameprofile.name),
+        )
+        .await;
+    }
+}
+
+struct SelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for SelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        feed(sender, &[player]).await;
+        Ok(())
+    }
+}
+
+struct TargetsExecutor;
+
+#[async_trait]
+impl CommandExecutor for TargetsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        feed(sender, targets).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
+        .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(TargetsExecutor))
+}
+
+pub fn register(dispatcher: &mut CommandDispatcher) {
+    dispatcher.register(init_command_tree(), "pumpkin.feed", PermissionLvl::Two);
+}
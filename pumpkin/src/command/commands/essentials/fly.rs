@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::permission::PermissionLvl;
+
+use crate::{
+    command::{
+        args::{players::PlayersArgumentConsumer, ConsumedArgs, FindArg},
+        dispatcher::CommandDispatcher,
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::Player,
+    server::Server,
+};
+
+use super::notify;
+
+const NAMES: [&str; 1] = ["fly"];
+
+const DESCRIPTION: &str = "Allows players to fly, or takes it away again.";
+
+const ARG_TARGETS: &str = "targets";
+
+async fn toggle(sender: &mut CommandSender<'_>, targets: &[Arc<Player>]) {
+    for target in targets {
+        let allowed = {
+            let mut abilities = target.abilities.lock().await;
+            abilities.allow_flying = !abilities.allow_flying;
+            // Players who may no longer fly fall down
+            abilities.flying &= abilities.allow_flying;
+            abilities.allow_flying
+        };
+        target.send_abilities_update().await;
+
+        let (to_target, to_sender) = if allowed {
+            (
+                "You can fly now",
+                format!("{} can fly now", target.gameprofile.name),
+            )
+        } else {
+            (
+                "You can no longer fly",
+                format!("{} can no longer fly", target.gameprofile.name),
+            )
+        };
+        notify(sender, target, to_target, to_sender).await;
+    }
+}
+
+struct SelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for SelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        toggle(sender, &[player]).await;
+        Ok(())
+    }
+}
+
+struct TargetsExecutor;
+
+#[async_trait]
+impl CommandExecutor for TargetsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        toggle(sender, targets).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
+        .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(TargetsExecutor))
+}
+
+pub fn register(dispatcher: &mut CommandDispatcher) {
+    dispatcher.register(init_command_tree(), "pumpkin.fly", PermissionLvl::Two);
+}
@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use pumpkin_data::item::Item;
+use pumpkin_protocol::client::play::EquipmentSlot;
+use pumpkin_util::permission::PermissionLvl;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::item::ItemStack;
+
+use crate::{
+    command::{
+        args::ConsumedArgs, dispatcher::CommandDispatcher, tree::builder::require,
+        tree::CommandTree, CommandError, CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+use CommandError::{GeneralCommandIssue, InvalidRequirement};
+
+const NAMES: [&str; 1] = ["hat"];
+const DESCRIPTION: &str = "Wears the item in your main hand as a hat.";
+
+/// The slot of the helmet in the player's inventory
+const HELMET_SLOT: usize = 5;
+
+struct HatExecutor;
+
+#[async_trait]
+impl CommandExecutor for HatExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+
+        let mut inventory = player.inventory().lock().await;
+        let Some(hat) = inventory.held_item_mut().take() else {
+            return Err(GeneralCommandIssue(
+                "You are not holding an item".to_string(),
+            ));
+        };
+        let name = hat.item.translated_name();
+        let Ok(helmet_slot) = inventory.get_slot(HELMET_SLOT) else {
+            *inventory.held_item_mut() = Some(hat);
+            return Err(GeneralCommandIssue(
+                "You can't wear a hat right now".to_string(),
+            ));
+        };
+        // The old helmet goes back into the hand
+        let old_helmet = helmet_slot.replace(hat.clone());
+        inventory.held_item_mut().clone_from(&old_helmet);
+        drop(inventory);
+
+        player.set_container_content(None).await;
+        let empty = ItemStack::new(0, Item::AIR);
+        player
+            .living_entity
+            .send_equipment_changes(&[
+                (EquipmentSlot::Head, hat),
+                (EquipmentSlot::MainHand, old_helmet.unwrap_or(empty)),
+            ])
+            .await;
+
+        sender
+            .send_message(TextComponent::text("Enjoy your new hat: ").add_child(name))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(HatExecutor))
+}
+
+pub fn register(dispatcher: &mut CommandDispatcher) {
+    dispatcher.register(init_command_tree(), "pumpkin.hat", PermissionLvl::Two);
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::permission::PermissionLvl;
+
+use crate::{
+    command::{
+        args::{players::PlayersArgumentConsumer, ConsumedArgs, FindArg},
+        dispatcher::CommandDispatcher,
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::Player,
+    server::Server,
+};
+
+use super::{
+    feed::{MAX_FOOD, MAX_SATURATION},
+    notify,
+};
+
+const NAMES: [&str; 1] = ["heal"];
+
+const DESCRIPTION: &str = "Restores the health and hunger of players and puts out their fire.";
+
+const ARG_TARGETS: &str = "targets";
+
+/// The health of a player with full hearts
+const MAX_HEALTH: f32 = 20.0; // TODO: Use the max health attribute
+
+async fn heal(sender: &mut CommandSender<'_>, targets: &[Arc<Player>]) {
+    for target in targets {
+        target.living_entity.entity.extinguish().await;
+        target.hunger_manager.level.store(MAX_FOOD);
+        target.hunger_manager.saturation.store(MAX_SATURATION);
+        target.set_health(MAX_HEALTH).await;
+        notify(
+            sender,
+            target,
+            "You have been healed",
+            format!("Healed {}", target.gameprofile.name),
+        )
+        .await;
+    }
+}
+
+struct SelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for SelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        heal(sender, &[player]).await;
+        Ok(())
+    }
+}
+
+struct TargetsExecutor;
+
+#[async_trait]
+impl CommandExecutor for TargetsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        heal(sender, targets).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
+        .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(TargetsExecutor))
+}
+
+pub fn register(dispatcher: &mut CommandDispatcher) {
+    dispatcher.register(init_command_tree(), "pumpkin.heal", PermissionLvl::Two);
+}
@@ -0,0 +1,52 @@
+//! Convenience commands for server admins, like `/heal` and `/fly`. [`register`] registers the
+//! whole group as configured, but every command can also be registered on its own through the
+//! `register` function of its module.
+
+use std::sync::Arc;
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{dispatcher::CommandDispatcher, CommandSender},
+    entity::player::Player,
+};
+
+pub mod feed;
+pub mod fly;
+pub mod god;
+pub mod hat;
+pub mod heal;
+pub mod repair;
+
+/// Registers the essentials, unless they are disabled in the config. Single commands can be
+/// disabled like any other command.
+pub fn register(dispatcher: &mut CommandDispatcher) {
+    if !ADVANCED_CONFIG.commands.essentials {
+        return;
+    }
+    feed::register(dispatcher);
+    fly::register(dispatcher);
+    god::register(dispatcher);
+    hat::register(dispatcher);
+    heal::register(dispatcher);
+    repair::register(dispatcher);
+}
+
+/// Tells the target what happened to them, and the sender too if they are someone else
+async fn notify(
+    sender: &mut CommandSender<'_>,
+    target: &Arc<Player>,
+    to_target: &'static str,
+    to_sender: String,
+) {
+    target
+        .send_system_message(&TextComponent::text(to_target))
+        .await;
+    let is_sender = sender
+        .as_player()
+        .is_some_and(|player| player.gameprofile.id == target.gameprofile.id);
+    if !is_sender {
+        sender.send_message(TextComponent::text(to_sender)).await;
+    }
+}
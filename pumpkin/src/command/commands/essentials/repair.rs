@@ -0,0 +1,179 @@
+use async_trait::async_trait;
+use pumpkin_config::{ADVANCED_CONFIG, BASIC_CONFIG};
+use pumpkin_nbt::tag::NbtTag;
+use pumpkin_util::permission::PermissionLvl;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+use pumpkin_world::item::ItemStack;
+
+use crate::{
+    command::{
+        args::ConsumedArgs,
+        dispatcher::CommandDispatcher,
+        tree::builder::{literal, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{
+        duration_format::format_duration, repair_usage_data::REPAIR_USAGE_LIST,
+        SaveJSONConfiguration,
+    },
+    entity::player::Player,
+    server::Server,
+};
+use CommandError::{GeneralCommandIssue, InvalidRequirement};
+
+const NAMES: [&str; 1] = ["repair"];
+const DESCRIPTION: &str = "Repairs the item in your main hand, or all your items.";
+
+/// Whether the item loses durability, like tools and armor
+fn is_damageable(stack: &ItemStack) -> bool {
+    stack.item.components.max_damage.is_some()
+}
+
+fn is_damaged(stack: &ItemStack) -> bool {
+    let damage = stack
+        .get_component("damage")
+        .and_then(NbtTag::extract_int)
+        .unwrap_or_else(|| stack.item.components.damage.map_or(0, i32::from));
+    damage > 0
+}
+
+/// Gives the item its full durability. Returns whether it was damaged.
+fn repair(stack: &mut ItemStack) -> bool {
+    if !is_damageable(stack) || !is_damaged(stack) {
+        return false;
+    }
+    // Items are not damaged by default, so the damage only has to be removed
+    stack.remove_component("damage");
+    true
+}
+
+/// Checks the cooldown of players who are not operators. Returns whether the player can repair
+/// now, otherwise they are told how long to wait.
+async fn check_cooldown(sender: &mut CommandSender<'_>, player: &Player) -> bool {
+    let cooldown = ADVANCED_CONFIG.repair.cooldown;
+    if cooldown == 0 || sender.has_permission_lvl(BASIC_CONFIG.op_permission_level) {
+        return true;
+    }
+    let remaining = REPAIR_USAGE_LIST
+        .read()
+        .await
+        .remaining_cooldown(&player.gameprofile.id, cooldown);
+    let Some(remaining) = remaining else {
+        return true;
+    };
+    sender
+        .send_message(
+            TextComponent::text(format!(
+                "You can repair again in {}",
+                format_duration(remaining)
+            ))
+            .color_named(NamedColor::Red),
+        )
+        .await;
+    false
+}
+
+/// Starts the cooldown of players who are not operators
+async fn set_used(sender: &CommandSender<'_>, player: &Player) {
+    if ADVANCED_CONFIG.repair.cooldown > 0
+        && !sender.has_permission_lvl(BASIC_CONFIG.op_permission_level)
+    {
+        let mut usages = REPAIR_USAGE_LIST.write().await;
+        usages.set_used(player.gameprofile.id);
+        usages.save();
+    }
+}
+
+struct RepairHandExecutor;
+
+#[async_trait]
+impl CommandExecutor for RepairHandExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        if !check_cooldown(sender, &player).await {
+            return Ok(());
+        }
+
+        let mut inventory = player.inventory().lock().await;
+        let Some(stack) = inventory.held_item_mut() else {
+            return Err(GeneralCommandIssue(
+                "You are not holding an item".to_string(),
+            ));
+        };
+        if !is_damageable(stack) {
+            return Err(GeneralCommandIssue(
+                "This item can't be repaired".to_string(),
+            ));
+        }
+        if !repair(stack) {
+            return Err(GeneralCommandIssue("This item is not damaged".to_string()));
+        }
+        let name = stack.item.translated_name();
+        drop(inventory);
+        player.set_container_content(None).await;
+        set_used(sender, &player).await;
+
+        sender
+            .send_message(TextComponent::text("Repaired ").add_child(name))
+            .await;
+        Ok(())
+    }
+}
+
+struct RepairAllExecutor;
+
+#[async_trait]
+impl CommandExecutor for RepairAllExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        if !check_cooldown(sender, &player).await {
+            return Ok(());
+        }
+
+        let mut inventory = player.inventory().lock().await;
+        let mut repaired = 0;
+        for stack in inventory.slots_mut().into_iter().flatten() {
+            if repair(stack) {
+                repaired += 1;
+            }
+        }
+        drop(inventory);
+        if repaired == 0 {
+            return Err(GeneralCommandIssue("You have no damaged items".to_string()));
+        }
+        player.set_container_content(None).await;
+        set_used(sender, &player).await;
+
+        let message = if repaired == 1 {
+            "Repaired 1 item".to_string()
+        } else {
+            format!("Repaired {repaired} items")
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(RepairHandExecutor)
+            .then(literal("all").execute(RepairAllExecutor)),
+    )
+}
+
+pub fn register(dispatcher: &mut CommandDispatcher) {
+    dispatcher.register(init_command_tree(), "pumpkin.repair", PermissionLvl::Two);
+}
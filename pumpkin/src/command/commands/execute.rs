@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::{
+    args::{selector::EntitySelectorArgumentConsumer, ConsumedArgs, FindArg},
+    tree::builder::{argument, literal},
+    tree::CommandTree,
+    CommandError, CommandExecutor, CommandSender,
+};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["execute"];
+
+const DESCRIPTION: &str = "Tests conditions, like whether entities exist.";
+
+const ARG_TARGETS: &str = "targets";
+
+/// The outcome of a condition: whether it passed, and its result. The result of `if entity` is
+/// the number of matching entities, which is 0 when the test fails, and the result of `unless`
+/// is 1 when it passes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ConditionResult {
+    success: bool,
+    result: i32,
+}
+
+fn test_entities(count: usize, negated: bool) -> ConditionResult {
+    let success = (count > 0) != negated;
+    let result = if negated {
+        i32::from(success)
+    } else {
+        i32::try_from(count).unwrap_or(i32::MAX)
+    };
+    ConditionResult { success, result }
+}
+
+/// `execute if entity` or, when negated, `execute unless entity`
+struct EntityConditionExecutor {
+    negated: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for EntityConditionExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let selector = EntitySelectorArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let count = selector.count(sender, server).await;
+        let ConditionResult { success, result } = test_entities(count, self.negated);
+
+        let message = match (success, self.negated) {
+            (true, true) => TextComponent::translate("commands.execute.conditional.pass", []),
+            (true, false) => TextComponent::translate(
+                "commands.execute.conditional.pass_count",
+                [TextComponent::text(result.to_string())],
+            ),
+            (false, true) => TextComponent::translate(
+                "commands.execute.conditional.fail_count",
+                [TextComponent::text(count.to_string())],
+            ),
+            (false, false) => TextComponent::translate("commands.execute.conditional.fail", []),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("if").then(
+                literal("entity").then(
+                    argument(ARG_TARGETS, EntitySelectorArgumentConsumer)
+                        .execute(EntityConditionExecutor { negated: false }),
+                ),
+            ),
+        )
+        .then(
+            literal("unless").then(
+                literal("entity").then(
+                    argument(ARG_TARGETS, EntitySelectorArgumentConsumer)
+                        .execute(EntityConditionExecutor { negated: true }),
+                ),
+            ),
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{test_entities, ConditionResult};
+
+    #[test]
+    fn entity_conditions_return_the_count() {
+        assert_eq!(
+            test_entities(3, false),
+            ConditionResult {
+                success: true,
+                result: 3
+            }
+        );
+        // No matches fail the command, but still return 0
+        assert_eq!(
+            test_entities(0, false),
+            ConditionResult {
+                success: false,
+                result: 0
+            }
+        );
+        assert_eq!(
+            test_entities(0, true),
+            ConditionResult {
+                success: true,
+                result: 1
+            }
+        );
+        assert_eq!(
+            test_entities(2, true),
+            ConditionResult {
+                success: false,
+                result: 0
+            }
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use crate::command::args::block::BlockArgumentConsumer;
 use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::predicate::BlockPredicateArgumentConsumer;
 use crate::command::args::{ConsumedArgs, FindArg};
-use crate::command::tree::builder::{argument, literal};
+use crate::command::tree::builder::{argument, literal, NonLeafNodeBuilder};
 use crate::command::tree::CommandTree;
 use crate::command::{CommandError, CommandExecutor, CommandSender};
 
@@ -17,6 +18,8 @@ const DESCRIPTION: &str = "Fills all or parts of a region with a specific block.
 const ARG_BLOCK: &str = "block";
 const ARG_FROM: &str = "from";
 const ARG_TO: &str = "to";
+const ARG_FILTER: &str = "filter";
+const NO_UPDATE: &str = "noupdate";
 
 #[derive(Clone, Copy, Default)]
 enum Mode {
@@ -28,14 +31,17 @@ enum Mode {
     Keep,
     /// Like Hollow but doesn't replace inner blocks with air, just the outline
     Outline,
-    /// Replaces all blocks with the new block state, without particles
+    /// Replaces all blocks with the new block state, without particles. With a filter, only the
+    /// blocks matching it are replaced.
     #[default]
     Replace,
 }
 
-struct SetblockExecutor(Mode);
+/// Fills the region in the mode. With the `noupdate` flag, the neighbors are not updated while
+/// the blocks are set, but once for all of them afterwards, and the players get the blocks
+/// chunk section by chunk section.
+struct SetblockExecutor(Mode, bool);
 
-#[expect(clippy::too_many_lines)]
 #[async_trait]
 impl CommandExecutor for SetblockExecutor {
     async fn execute<'a>(
@@ -48,7 +54,12 @@ impl CommandExecutor for SetblockExecutor {
         let block_state_id = block.default_state_id;
         let from = BlockPosArgumentConsumer::find_arg(args, ARG_FROM)?;
         let to = BlockPosArgumentConsumer::find_arg(args, ARG_TO)?;
-        let mode = self.0;
+        let filter = if args.contains_key(ARG_FILTER) {
+            Some(BlockPredicateArgumentConsumer::find_arg(args, ARG_FILTER)?)
+        } else {
+            None
+        };
+        let (mode, suppress_updates) = (self.0, self.1);
 
         let start_x = from.0.x.min(to.0.x);
         let start_y = from.0.y.min(to.0.y);
@@ -62,91 +73,70 @@ impl CommandExecutor for SetblockExecutor {
             .world()
             .await
             .ok_or(CommandError::InvalidRequirement)?;
-        let mut placed_blocks = 0;
-
-        match mode {
-            Mode::Destroy => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = BlockPos(Vector3 { x, y, z });
-                            world
-                                .break_block(server, &block_position, None, false)
-                                .await;
-                            world.set_block_state(&block_position, block_state_id).await;
-                            placed_blocks += 1;
-                        }
-                    }
-                }
-            }
-            Mode::Replace => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = BlockPos(Vector3 { x, y, z });
-                            world.set_block_state(&block_position, block_state_id).await;
-                            placed_blocks += 1;
-                        }
-                    }
-                }
-            }
-            Mode::Keep => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = BlockPos(Vector3 { x, y, z });
-                            match world.get_block_state(&block_position).await {
-                                Ok(old_state) if old_state.air => {
-                                    world.set_block_state(&block_position, block_state_id).await;
-                                    placed_blocks += 1;
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
-            }
-            Mode::Hollow => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = BlockPos(Vector3::new(x, y, z));
-                            let is_edge = x == start_x
-                                || x == end_x
-                                || y == start_y
-                                || y == end_y
-                                || z == start_z
-                                || z == end_z;
-                            if is_edge {
-                                world.set_block_state(&block_position, block_state_id).await;
+
+        let mut blocks = Vec::new();
+        for x in start_x..=end_x {
+            for y in start_y..=end_y {
+                for z in start_z..=end_z {
+                    let block_position = BlockPos(Vector3::new(x, y, z));
+                    let is_edge = x == start_x
+                        || x == end_x
+                        || y == start_y
+                        || y == end_y
+                        || z == start_z
+                        || z == end_z;
+                    let new_state_id = match mode {
+                        Mode::Destroy => {
+                            if suppress_updates {
+                                world
+                                    .break_block_without_update(
+                                        server,
+                                        &block_position,
+                                        None,
+                                        false,
+                                    )
+                                    .await;
                             } else {
-                                world.set_block_state(&block_position, 0).await;
+                                world
+                                    .break_block(server, &block_position, None, false)
+                                    .await;
                             }
-                            placed_blocks += 1;
+                            Some(block_state_id)
                         }
-                    }
-                }
-            }
-            Mode::Outline => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = BlockPos(Vector3::new(x, y, z));
-                            let is_edge = x == start_x
-                                || x == end_x
-                                || y == start_y
-                                || y == end_y
-                                || z == start_z
-                                || z == end_z;
-                            if is_edge {
-                                world.set_block_state(&block_position, block_state_id).await;
-                                placed_blocks += 1;
+                        Mode::Replace => {
+                            if let Some(filter) = filter {
+                                if !filter.matches_block_at(&world, &block_position).await {
+                                    continue;
+                                }
                             }
+                            Some(block_state_id)
                         }
+                        Mode::Keep => world
+                            .get_block_state(&block_position)
+                            .await
+                            .is_ok_and(|old_state| old_state.air)
+                            .then_some(block_state_id),
+                        Mode::Hollow => Some(if is_edge { block_state_id } else { 0 }),
+                        Mode::Outline => is_edge.then_some(block_state_id),
+                    };
+                    if let Some(new_state_id) = new_state_id {
+                        blocks.push((block_position, new_state_id));
                     }
                 }
             }
-        };
+        }
+
+        if suppress_updates {
+            world.set_block_states(&blocks).await;
+            let positions: Vec<_> = blocks.iter().map(|(position, _)| *position).collect();
+            world.update_neighbors_bulk(server, &positions).await;
+        } else {
+            for (position, new_state_id) in &blocks {
+                world.set_block_state(position, *new_state_id).await;
+                world.update_neighbors(server, position).await;
+            }
+        }
+        let placed_blocks = blocks.len();
 
         sender
             .send_message(TextComponent::translate(
@@ -159,17 +149,34 @@ impl CommandExecutor for SetblockExecutor {
     }
 }
 
+/// The literal of the mode, which may be followed by the `noupdate` flag
+fn mode_literal(name: &str, mode: Mode) -> NonLeafNodeBuilder {
+    literal(name)
+        .then(literal(NO_UPDATE).execute(SetblockExecutor(mode, true)))
+        .execute(SetblockExecutor(mode, false))
+}
+
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_FROM, BlockPosArgumentConsumer).then(
             argument(ARG_TO, BlockPosArgumentConsumer).then(
                 argument(ARG_BLOCK, BlockArgumentConsumer)
-                    .then(literal("destroy").execute(SetblockExecutor(Mode::Destroy)))
-                    .then(literal("hollow").execute(SetblockExecutor(Mode::Hollow)))
-                    .then(literal("keep").execute(SetblockExecutor(Mode::Keep)))
-                    .then(literal("outline").execute(SetblockExecutor(Mode::Outline)))
-                    .then(literal("replace").execute(SetblockExecutor(Mode::Replace)))
-                    .execute(SetblockExecutor(Mode::Replace)),
+                    .then(mode_literal("destroy", Mode::Destroy))
+                    .then(mode_literal("hollow", Mode::Hollow))
+                    .then(mode_literal("keep", Mode::Keep))
+                    .then(mode_literal("outline", Mode::Outline))
+                    .then(
+                        mode_literal("replace", Mode::Replace).then(
+                            argument(ARG_FILTER, BlockPredicateArgumentConsumer)
+                                .then(
+                                    literal(NO_UPDATE)
+                                        .execute(SetblockExecutor(Mode::Replace, true)),
+                                )
+                                .execute(SetblockExecutor(Mode::Replace, false)),
+                        ),
+                    )
+                    .then(literal(NO_UPDATE).execute(SetblockExecutor(Mode::Replace, true)))
+                    .execute(SetblockExecutor(Mode::Replace, false)),
             ),
         ),
     )
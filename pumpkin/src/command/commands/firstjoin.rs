@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{
+            players::PlayersArgumentConsumer, simple::SimpleArgConsumer, Arg, ConsumedArgs, FindArg,
+        },
+        tree::builder::{argument, literal},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{first_join_data::JOINED_PLAYER_LIST, SaveJSONConfiguration},
+    server::Server,
+};
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 1] = ["firstjoin"];
+const DESCRIPTION: &str =
+    "Tests the first join commands or lets a player join for the first time again.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_NAME: &str = "name";
+
+struct TestExecutor;
+
+#[async_trait]
+impl CommandExecutor for TestExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        if ADVANCED_CONFIG.first_join.commands.is_empty() {
+            sender
+                .send_message(TextComponent::text(
+                    "There are no first join commands configured",
+                ))
+                .await;
+            return Ok(());
+        }
+        for target in targets {
+            server.run_first_join_commands(target).await;
+            sender
+                .send_message(TextComponent::text(format!(
+                    "Ran the first join commands for {}",
+                    target.gameprofile.name
+                )))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct ResetExecutor;
+
+#[async_trait]
+impl CommandExecutor for ResetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+            return Err(InvalidConsumption(Some(ARG_NAME.into())));
+        };
+
+        let mut joined = JOINED_PLAYER_LIST.write().await;
+        let message = match joined.remove(name) {
+            Some(entry) => {
+                joined.save();
+                format!(
+                    "{} will join for the first time again on their next join",
+                    entry.name
+                )
+            }
+            None => format!("{name} has not joined this server yet"),
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("test")
+                .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(TestExecutor)),
+        )
+        .then(literal("reset").then(argument(ARG_NAME, SimpleArgConsumer).execute(ResetExecutor)))
+}
@@ -0,0 +1,74 @@
+use std::sync::{atomic::Ordering, Arc};
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{entities::EntitiesArgumentConsumer, ConsumedArgs, FindArg},
+        tree::builder::argument,
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::Player,
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["freeze"];
+
+const DESCRIPTION: &str = "Freezes or unfreezes entities, stopping their AI and movement.";
+
+const ARG_TARGETS: &str = "targets";
+
+async fn report(sender: &mut CommandSender<'_>, targets: &[Arc<Player>], frozen: bool) {
+    let message = match targets {
+        [target] if frozen => format!("Froze {}", target.gameprofile.name),
+        [target] => format!("Unfroze {}", target.gameprofile.name),
+        _ if frozen => format!("Froze {} entities", targets.len()),
+        _ => format!("Unfroze {} entities", targets.len()),
+    };
+    sender.send_message(TextComponent::text(message)).await;
+}
+
+struct ToggleExecutor;
+
+#[async_trait]
+impl CommandExecutor for ToggleExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        let (frozen, not_frozen): (Vec<_>, Vec<_>) = targets
+            .iter()
+            .cloned()
+            .partition(|target| target.living_entity.entity.frozen.load(Ordering::Relaxed));
+        for target in &frozen {
+            target.living_entity.entity.set_frozen(false);
+        }
+        for target in &not_frozen {
+            let entity = &target.living_entity.entity;
+            entity.set_frozen(true);
+            // Stops the movement the client already started
+            target
+                .request_teleport(entity.pos.load(), entity.yaw.load(), entity.pitch.load())
+                .await;
+        }
+
+        if !not_frozen.is_empty() {
+            report(sender, &not_frozen, true).await;
+        }
+        if !frozen.is_empty() {
+            report(sender, &frozen, false).await;
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_TARGETS, EntitiesArgumentConsumer).execute(ToggleExecutor))
+}
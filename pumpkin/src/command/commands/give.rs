@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use pumpkin_util::text::click::ClickEvent;
 use pumpkin_util::text::color::{Color, NamedColor};
@@ -8,9 +10,11 @@ use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
 use crate::command::args::item::ItemArgumentConsumer;
 use crate::command::args::players::PlayersArgumentConsumer;
 use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
-use crate::command::tree::builder::{argument, argument_default_name};
+use crate::command::tree::builder::{argument, argument_default_name, literal, NonLeafNodeBuilder};
 use crate::command::tree::CommandTree;
 use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::server::Server;
 
 const NAMES: [&str; 1] = ["give"];
 
@@ -18,6 +22,13 @@ const DESCRIPTION: &str = "Give items to player(s).";
 
 const ARG_ITEM: &str = "item";
 
+/// Gives the items to every online player instead of the targets
+const FLAG_ALL_ONLINE: &str = "--all";
+
+/// How many players are given items before yielding to other tasks, so giving items to a lot of
+/// players does not hold up the tick
+const BATCH_SIZE: usize = 16;
+
 fn item_count_consumer() -> BoundedNumArgumentConsumer<i32> {
     BoundedNumArgumentConsumer::new()
         .name("count")
@@ -25,17 +36,25 @@ fn item_count_consumer() -> BoundedNumArgumentConsumer<i32> {
         .max(i32::MAX)
 }
 
-struct GiveExecutor;
+struct GiveExecutor {
+    all_online: bool,
+}
 
 #[async_trait]
 impl CommandExecutor for GiveExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
+        server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
+        let online_players;
+        let targets: &[Arc<Player>] = if self.all_online {
+            online_players = server.get_all_players().await;
+            &online_players
+        } else {
+            PlayersArgumentConsumer.find_arg_default_name(args)?
+        };
 
         let (item_name, item) = ItemArgumentConsumer::find_arg(args, ARG_ITEM)?;
 
@@ -53,35 +72,53 @@ impl CommandExecutor for GiveExecutor {
             }
         };
 
-        for target in targets {
-            target.give_items(item, item_count as u32).await;
+        // Like vanilla, at most 100 stacks can be given at once
+        let max_count = i32::from(item.components.max_stack_size) * 100;
+        if item_count > max_count {
+            sender
+                .send_message(
+                    TextComponent::translate(
+                        "commands.give.failed.toomanyitems",
+                        [
+                            TextComponent::text(max_count.to_string()),
+                            item.translated_name(),
+                        ],
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
         }
-        let msg = if targets.len() == 1 {
+
+        for batch in targets.chunks(BATCH_SIZE) {
+            for target in batch {
+                target.give_items(server, item, item_count as u32).await;
+            }
+            tokio::task::yield_now().await;
+        }
+
+        let item_component = TextComponent::text("[")
+            .add_child(item.translated_name())
+            .add_child(TextComponent::text("]"))
+            .hover_event(HoverEvent::ShowItem {
+                id: item_name.to_string().into(),
+                count: Some(item_count),
+                tag: None,
+            });
+        let msg = if let [target] = targets {
             TextComponent::translate(
                 "commands.give.success.single",
                 [
                     TextComponent::text(item_count.to_string()),
-                    TextComponent::text("[")
-                        .add_child(item.translated_name())
-                        .add_child(TextComponent::text("]"))
-                        .hover_event(HoverEvent::ShowItem {
-                            id: item_name.to_string().into(),
-                            count: Some(item_count),
-                            tag: None,
-                        }),
-                    TextComponent::text(targets[0].gameprofile.name.to_string())
+                    item_component,
+                    TextComponent::text(target.gameprofile.name.to_string())
                         .hover_event(HoverEvent::show_entity(
-                            targets[0].living_entity.entity.entity_uuid.to_string(),
-                            targets[0]
-                                .living_entity
-                                .entity
-                                .entity_type
-                                .resource_name
-                                .into(),
-                            Some(TextComponent::text(targets[0].gameprofile.name.clone())),
+                            target.living_entity.entity.entity_uuid.to_string(),
+                            target.living_entity.entity.entity_type.resource_name.into(),
+                            Some(TextComponent::text(target.gameprofile.name.clone())),
                         ))
                         .click_event(ClickEvent::SuggestCommand(
-                            format!("/tell {} ", targets[0].gameprofile.name.clone()).into(),
+                            format!("/tell {} ", target.gameprofile.name.clone()).into(),
                         )),
                 ],
             )
@@ -90,14 +127,7 @@ impl CommandExecutor for GiveExecutor {
                 "commands.give.success.multiple",
                 [
                     TextComponent::text(item_count.to_string()),
-                    TextComponent::text("[")
-                        .add_child(item.translated_name())
-                        .add_child(TextComponent::text("]"))
-                        .hover_event(HoverEvent::ShowItem {
-                            id: item_name.to_string().into(),
-                            count: Some(item_count),
-                            tag: None,
-                        }),
+                    item_component,
                     TextComponent::text(targets.len().to_string()),
                 ],
             )
@@ -108,12 +138,14 @@ impl CommandExecutor for GiveExecutor {
     }
 }
 
+fn item_and_count(all_online: bool) -> NonLeafNodeBuilder {
+    argument(ARG_ITEM, ItemArgumentConsumer)
+        .execute(GiveExecutor { all_online })
+        .then(argument_default_name(item_count_consumer()).execute(GiveExecutor { all_online }))
+}
+
 pub fn init_command_tree() -> CommandTree {
-    CommandTree::new(NAMES, DESCRIPTION).then(
-        argument_default_name(PlayersArgumentConsumer).then(
-            argument(ARG_ITEM, ItemArgumentConsumer)
-                .execute(GiveExecutor)
-                .then(argument_default_name(item_count_consumer()).execute(GiveExecutor)),
-        ),
-    )
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal(FLAG_ALL_ONLINE).then(item_and_count(true)))
+        .then(argument_default_name(PlayersArgumentConsumer).then(item_and_count(false)))
 }
@@ -0,0 +1,153 @@
+use std::sync::{atomic::Ordering, Arc};
+
+use async_trait::async_trait;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::{
+    command::{
+        args::{
+            bounded_num::BoundedNumArgumentConsumer, color::ColorArgumentConsumer,
+            entities::EntitiesArgumentConsumer, Arg, ConsumedArgs, FindArg,
+        },
+        tree::builder::{argument, literal},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::Player,
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["glow"];
+
+const DESCRIPTION: &str = "Toggles the glowing outline of entities, optionally colored and timed.";
+
+const ARG_TARGETS: &str = "targets";
+
+const ARG_COLOR: &str = "color";
+
+const ARG_SECONDS: &str = "seconds";
+
+fn seconds_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_SECONDS).min(1)
+}
+
+async fn report(sender: &mut CommandSender<'_>, targets: &[Arc<Player>], glowing: bool) {
+    let message = match targets {
+        [target] if glowing => format!("{} is now glowing", target.gameprofile.name),
+        [target] => format!("{} is no longer glowing", target.gameprofile.name),
+        _ if glowing => format!("{} entities are now glowing", targets.len()),
+        _ => format!("{} entities are no longer glowing", targets.len()),
+    };
+    sender.send_message(TextComponent::text(message)).await;
+}
+
+struct ToggleExecutor;
+
+#[async_trait]
+impl CommandExecutor for ToggleExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        let (glowing, not_glowing): (Vec<_>, Vec<_>) = targets
+            .iter()
+            .cloned()
+            .partition(|target| target.living_entity.entity.glowing.load(Ordering::Relaxed));
+        for target in &glowing {
+            server.glowing.remove(server, target).await;
+        }
+        for target in &not_glowing {
+            server.glowing.set(server, target, None, None).await;
+        }
+
+        if !not_glowing.is_empty() {
+            report(sender, &not_glowing, true).await;
+        }
+        if !glowing.is_empty() {
+            report(sender, &glowing, false).await;
+        }
+        Ok(())
+    }
+}
+
+struct OnExecutor;
+
+#[async_trait]
+impl CommandExecutor for OnExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let color = match args.get(ARG_COLOR) {
+            Some(Arg::Color(color)) => Some(*color),
+            _ => None,
+        };
+        let ticks = match args.get(ARG_SECONDS) {
+            Some(Arg::Num(_)) => {
+                let Ok(seconds) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_SECONDS)?
+                else {
+                    sender
+                        .send_message(
+                            TextComponent::text("The duration must be at least 1 second")
+                                .color_named(NamedColor::Red),
+                        )
+                        .await;
+                    return Ok(());
+                };
+                // Bounded to be positive by the argument consumer
+                Some((seconds as u32).saturating_mul(20))
+            }
+            _ => None,
+        };
+
+        for target in targets {
+            server.glowing.set(server, target, color, ticks).await;
+        }
+        report(sender, targets, true).await;
+        Ok(())
+    }
+}
+
+struct OffExecutor;
+
+#[async_trait]
+impl CommandExecutor for OffExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        for target in targets {
+            server.glowing.remove(server, target).await;
+        }
+        report(sender, targets, false).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGETS, EntitiesArgumentConsumer)
+            .then(
+                literal("on")
+                    .then(
+                        argument(ARG_COLOR, ColorArgumentConsumer)
+                            .then(argument(ARG_SECONDS, seconds_consumer()).execute(OnExecutor))
+                            .execute(OnExecutor),
+                    )
+                    .then(argument(ARG_SECONDS, seconds_consumer()).execute(OnExecutor))
+                    .execute(OnExecutor),
+            )
+            .then(literal("off").execute(OffExecutor))
+            .execute(ToggleExecutor),
+    )
+}
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use pumpkin_data::screen::WindowType;
+use pumpkin_inventory::{Grindstone, OpenContainer};
+
+use crate::{
+    command::{
+        args::ConsumedArgs, tree::builder::require, tree::CommandTree, CommandError,
+        CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+use CommandError::InvalidRequirement;
+
+const NAMES: [&str; 1] = ["grindstone"];
+const DESCRIPTION: &str = "Opens a grindstone.";
+
+struct GrindstoneExecutor;
+
+#[async_trait]
+impl CommandExecutor for GrindstoneExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let container =
+            OpenContainer::new_empty_container::<Grindstone>(player.entity_id(), None, None);
+        player
+            .open_virtual_container(server, container, WindowType::Grindstone)
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(GrindstoneExecutor))
+}
@@ -0,0 +1,324 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::{
+    math::{vector2::Vector2, vector3::Vector3},
+    text::{color::NamedColor, TextComponent},
+};
+
+use crate::{
+    command::{
+        args::{
+            home::{HomeArgumentConsumer, BED},
+            simple::SimpleArgConsumer,
+            Arg, ConsumedArgs,
+        },
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{
+        home_data::{HomeEntry, HOME_LIST},
+        SaveJSONConfiguration,
+    },
+    entity::player::Player,
+    plugin::api::events::player::player_teleport::TeleportCause,
+    server::Server,
+    world::{respawn::find_respawn, World},
+};
+use CommandError::{InvalidConsumption, InvalidRequirement};
+
+const NAMES: [&str; 1] = ["home"];
+const DESCRIPTION: &str = "Teleports you to one of your homes, or to your respawn point with bed.";
+
+const SETHOME_NAMES: [&str; 1] = ["sethome"];
+const SETHOME_DESCRIPTION: &str = "Sets a home at your position, or moves an existing one.";
+
+const DELHOME_NAMES: [&str; 1] = ["delhome"];
+const DELHOME_DESCRIPTION: &str = "Deletes one of your homes.";
+
+const HOMES_NAMES: [&str; 1] = ["homes"];
+const HOMES_DESCRIPTION: &str = "Lists your homes.";
+
+const ARG_NAME: &str = "name";
+
+/// The home used when no name is given
+const DEFAULT_HOME: &str = "home";
+
+/// The name of the home, or [`DEFAULT_HOME`] if it was left out
+fn find_name<'a>(args: &'a ConsumedArgs) -> Result<&'a str, CommandError> {
+    match args.get(ARG_NAME) {
+        Some(Arg::Simple(name)) => Ok(name),
+        None => Ok(DEFAULT_HOME),
+        _ => Err(InvalidConsumption(Some(ARG_NAME.into()))),
+    }
+}
+
+async fn send_error(sender: &mut CommandSender<'_>, message: String) {
+    sender
+        .send_message(TextComponent::text(message).color_named(NamedColor::Red))
+        .await;
+}
+
+/// Teleports the player after the warmup, loading the chunk of the destination first
+async fn teleport(
+    server: &Server,
+    player: &Arc<Player>,
+    world: Arc<World>,
+    position: Vector3<f64>,
+    yaw: f32,
+    pitch: f32,
+) -> bool {
+    if !server.teleport_warmups.wait(player).await {
+        return false;
+    }
+    let chunk_pos = Vector2::new(
+        (position.x.floor() as i32) >> 4,
+        (position.z.floor() as i32) >> 4,
+    );
+    world.receive_chunk(chunk_pos).await;
+    player
+        .teleport(world, position, yaw, pitch, TeleportCause::Command)
+        .await
+}
+
+struct HomeExecutor;
+
+#[async_trait]
+impl CommandExecutor for HomeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = find_name(args)?;
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+
+        if name.eq_ignore_ascii_case(BED) {
+            let Some(respawn) = find_respawn(server, &player, false).await else {
+                return Ok(());
+            };
+            let pitch = player.living_entity.entity.pitch.load();
+            if teleport(
+                server,
+                &player,
+                respawn.world,
+                respawn.position,
+                respawn.yaw,
+                pitch,
+            )
+            .await
+            {
+                sender
+                    .send_message(TextComponent::text("Teleported to your respawn point"))
+                    .await;
+            }
+            return Ok(());
+        }
+
+        let home = HOME_LIST
+            .read()
+            .await
+            .get(&player.gameprofile.id, name)
+            .cloned();
+        let Some(home) = home else {
+            send_error(sender, format!("You have no home called {name}")).await;
+            return Ok(());
+        };
+        let Some(world) = server.get_world_by_name(&home.world).await else {
+            send_error(
+                sender,
+                format!(
+                    "The world {} of your home {} is not loaded",
+                    home.world, home.name
+                ),
+            )
+            .await;
+            return Ok(());
+        };
+
+        if teleport(
+            server,
+            &player,
+            world,
+            home.position(),
+            home.yaw,
+            home.pitch,
+        )
+        .await
+        {
+            sender
+                .send_message(TextComponent::text(format!(
+                    "Teleported to your home {}",
+                    home.name
+                )))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct SetHomeExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetHomeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = find_name(args)?;
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        if name.eq_ignore_ascii_case(BED) {
+            send_error(
+                sender,
+                format!("A home can not be called {BED}, it is used for your respawn point"),
+            )
+            .await;
+            return Ok(());
+        }
+
+        let uuid = player.gameprofile.id;
+        let mut homes = HOME_LIST.write().await;
+        // Moving an existing home does not count towards the limit
+        let limit = ADVANCED_CONFIG.homes.limit;
+        if limit > 0
+            && homes.get(&uuid, name).is_none()
+            && homes.homes_of(&uuid).count() >= limit as usize
+        {
+            drop(homes);
+            send_error(
+                sender,
+                format!("You can not have more than {limit} homes, delete one with /delhome first"),
+            )
+            .await;
+            return Ok(());
+        }
+
+        let entity = &player.living_entity.entity;
+        let home = HomeEntry::new(
+            uuid,
+            name,
+            player.world().await.name(),
+            entity.pos.load(),
+            entity.yaw.load(),
+            entity.pitch.load(),
+        );
+        let replaced = homes.set(home);
+        homes.save();
+        drop(homes);
+
+        let message = if replaced {
+            format!("Moved your home {name} to your position")
+        } else {
+            format!("Set your home {name}")
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct DelHomeExecutor;
+
+#[async_trait]
+impl CommandExecutor for DelHomeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = find_name(args)?;
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+
+        let mut homes = HOME_LIST.write().await;
+        if !homes.remove(&player.gameprofile.id, name) {
+            drop(homes);
+            send_error(sender, format!("You have no home called {name}")).await;
+            return Ok(());
+        }
+        homes.save();
+        drop(homes);
+
+        sender
+            .send_message(TextComponent::text(format!("Deleted your home {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+struct HomesExecutor;
+
+#[async_trait]
+impl CommandExecutor for HomesExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+
+        let homes = HOME_LIST.read().await;
+        let mut names: Vec<&str> = homes
+            .homes_of(&player.gameprofile.id)
+            .map(|home| home.name.as_str())
+            .collect();
+        names.sort_unstable();
+        let limit = ADVANCED_CONFIG.homes.limit;
+        let count = if limit > 0 {
+            format!("{}/{limit}", names.len())
+        } else {
+            names.len().to_string()
+        };
+        let message = if names.is_empty() {
+            "You have no homes".to_string()
+        } else {
+            format!("Homes ({count}): {}", names.join(", "))
+        };
+        drop(homes);
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(HomeExecutor)
+            .then(
+                argument(ARG_NAME, HomeArgumentConsumer { with_bed: true }).execute(HomeExecutor),
+            ),
+    )
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_sethome_command_tree() -> CommandTree {
+    CommandTree::new(SETHOME_NAMES, SETHOME_DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(SetHomeExecutor)
+            .then(argument(ARG_NAME, SimpleArgConsumer).execute(SetHomeExecutor)),
+    )
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_delhome_command_tree() -> CommandTree {
+    CommandTree::new(DELHOME_NAMES, DELHOME_DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(DelHomeExecutor)
+            .then(
+                argument(ARG_NAME, HomeArgumentConsumer { with_bed: false })
+                    .execute(DelHomeExecutor),
+            ),
+    )
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_homes_command_tree() -> CommandTree {
+    CommandTree::new(HOMES_NAMES, HOMES_DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(HomesExecutor))
+}
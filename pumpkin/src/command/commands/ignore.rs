@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::{text::TextComponent, PermissionLvl};
+
+use crate::{
+    command::{
+        args::{
+            players::PlayersArgumentConsumer, simple::SimpleArgConsumer, Arg, ConsumedArgs, FindArg,
+        },
+        tree::builder::{argument, literal},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{ignore_data::IGNORE_LIST, SaveJSONConfiguration},
+    server::Server,
+};
+use CommandError::{InvalidConsumption, InvalidRequirement};
+
+const NAMES: [&str; 1] = ["ignore"];
+const DESCRIPTION: &str = "Hides the chat and private messages of other players from you.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_NAME: &str = "name";
+
+struct IgnoreExecutor;
+
+#[async_trait]
+impl CommandExecutor for IgnoreExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        let mut ignore_list = IGNORE_LIST.write().await;
+        for target in targets {
+            let name = &target.gameprofile.name;
+            let message = if target.gameprofile.id == player.gameprofile.id {
+                "You can not ignore yourself".to_string()
+            } else if !ADVANCED_CONFIG.commands.allow_ignoring_operators
+                && target.permission_lvl.load() != PermissionLvl::Zero
+            {
+                format!("You can not ignore {name}, as they are an operator")
+            } else if ignore_list.add(&player.gameprofile, &target.gameprofile) {
+                format!("You are now ignoring {name}")
+            } else {
+                format!("You are already ignoring {name}")
+            };
+            player
+                .send_system_message(&TextComponent::text(message))
+                .await;
+        }
+        ignore_list.save();
+        Ok(())
+    }
+}
+
+struct RemoveExecutor;
+
+#[async_trait]
+impl CommandExecutor for RemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+            return Err(InvalidConsumption(Some(ARG_NAME.into())));
+        };
+
+        let mut ignore_list = IGNORE_LIST.write().await;
+        let message = match ignore_list.remove(&player.gameprofile.id, name) {
+            Some(ignored) => {
+                ignore_list.save();
+                format!("You are no longer ignoring {}", ignored.name)
+            }
+            None => format!("You are not ignoring {name}"),
+        };
+        player
+            .send_system_message(&TextComponent::text(message))
+            .await;
+        Ok(())
+    }
+}
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+
+        let ignore_list = IGNORE_LIST.read().await;
+        let ignored = ignore_list.get_ignored(&player.gameprofile.id);
+        let message = if ignored.is_empty() {
+            "You are not ignoring anyone".to_string()
+        } else {
+            let names: Vec<&str> = ignored
+                .iter()
+                .map(|ignored| ignored.name.as_str())
+                .collect();
+            format!(
+                "You are ignoring {} player(s): {}",
+                names.len(),
+                names.join(", ")
+            )
+        };
+        player
+            .send_system_message(&TextComponent::text(message))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("remove").then(argument(ARG_NAME, SimpleArgConsumer).execute(RemoveExecutor)))
+        .then(literal("list").execute(ListExecutor))
+        .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(IgnoreExecutor))
+}
@@ -0,0 +1,174 @@
+use std::future::Future;
+
+use async_trait::async_trait;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::WORLD_MAX_Y;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::builder::require;
+use crate::command::tree::CommandTree;
+use crate::command::{CommandExecutor, CommandSender};
+use crate::plugin::api::events::player::player_teleport::TeleportCause;
+use crate::server::Server;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["jump"];
+
+const DESCRIPTION: &str = "Teleports you to the block you are looking at.";
+
+/// The maximum distance in blocks to look for a block
+const MAX_DISTANCE: f64 = 128.0;
+
+struct JumpExecutor;
+
+#[async_trait]
+impl CommandExecutor for JumpExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::InvalidRequirement);
+        };
+        let entity = &player.living_entity.entity;
+
+        let pos = entity.pos.load();
+        let eyes = Vector3::new(pos.x, pos.y + f64::from(entity.standing_eye_height), pos.z);
+        let yaw = f64::from(entity.yaw.load()).to_radians();
+        let pitch = f64::from(entity.pitch.load()).to_radians();
+        let direction = Vector3::new(
+            -yaw.sin() * pitch.cos(),
+            -pitch.sin(),
+            yaw.cos() * pitch.cos(),
+        );
+
+        let world = entity.world.read().await.clone();
+        let Some(hit) = world.raycast_block(eyes, direction, MAX_DISTANCE).await else {
+            return Err(CommandError::GeneralCommandIssue(
+                "No block in sight (or too far away)".to_string(),
+            ));
+        };
+
+        // Stand on top of the hit block, not inside it, or above it if something overhangs it
+        let Some(y) = find_standing_y(&world, hit).await else {
+            return Err(CommandError::GeneralCommandIssue(
+                "There is no room to stand above that block".to_string(),
+            ));
+        };
+        let destination = Vector3::new(
+            f64::from(hit.0.x) + 0.5,
+            f64::from(y),
+            f64::from(hit.0.z) + 0.5,
+        );
+        let (yaw, pitch) = (entity.yaw.load(), entity.pitch.load());
+        if !player
+            .teleport(world, destination, yaw, pitch, TeleportCause::Command)
+            .await
+        {
+            return Ok(());
+        }
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.teleport.success.location.single",
+                [
+                    TextComponent::text(player.gameprofile.name.clone()),
+                    TextComponent::text(format!("{:.2}", destination.x)),
+                    TextComponent::text(format!("{:.2}", destination.y)),
+                    TextComponent::text(format!("{:.2}", destination.z)),
+                ],
+            ))
+            .await;
+
+        Ok(())
+    }
+}
+
+/// Finds the lowest y above the hit block where the player fits, or `None` if there is no room up
+/// to the top of the world
+async fn find_standing_y(world: &World, hit: BlockPos) -> Option<i32> {
+    let bottom = hit.0.y + 1;
+    let height = usize::try_from(i32::from(WORLD_MAX_Y) - bottom).unwrap_or(0);
+    let offset = first_free_space(height, |offset| async move {
+        let pos = BlockPos(Vector3::new(hit.0.x, bottom + offset as i32, hit.0.z));
+        let state = world.get_block_state(&pos).await;
+        state.is_ok_and(|state| state.collision_shapes.is_empty())
+    })
+    .await?;
+    Some(bottom + offset as i32)
+}
+
+/// Gets the index of the first two free blocks on top of each other in a column of the given
+/// height, where everything above the column is free. The blocks above them are not checked.
+async fn first_free_space<F: Future<Output = bool>>(
+    height: usize,
+    mut is_free: impl FnMut(usize) -> F,
+) -> Option<usize> {
+    let mut below_free = false;
+    for index in 0..height {
+        let free = is_free(index).await;
+        if below_free && free {
+            return Some(index - 1);
+        }
+        below_free = free;
+    }
+    below_free.then(|| height - 1)
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(JumpExecutor))
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+
+    async fn first_free(free: &[bool]) -> Option<usize> {
+        first_free_space(free.len(), |index| {
+            let free = free[index];
+            async move { free }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn stands_on_hit_block() {
+        assert_eq!(first_free(&[true, true, false]).await, Some(0));
+    }
+
+    #[tokio::test]
+    async fn skips_overhang() {
+        // A block right above the head, and a one block gap under the next one
+        assert_eq!(
+            first_free(&[true, false, true, false, true, true]).await,
+            Some(4)
+        );
+    }
+
+    #[tokio::test]
+    async fn top_of_world() {
+        assert_eq!(first_free(&[false, true]).await, Some(1));
+        assert_eq!(first_free(&[false, false]).await, None);
+    }
+
+    #[tokio::test]
+    async fn stops_at_first_space() {
+        let checked = Cell::new(0);
+        let found = first_free_space(100, |_| {
+            checked.set(checked.get() + 1);
+            async { true }
+        })
+        .await;
+        assert_eq!(found, Some(0));
+        assert_eq!(checked.get(), 2);
+    }
+}
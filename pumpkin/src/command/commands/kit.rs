@@ -0,0 +1,220 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use async_trait::async_trait;
+use pumpkin_config::{KitConfig, ADVANCED_CONFIG};
+use pumpkin_data::item::Item;
+use pumpkin_util::{
+    text::{color::NamedColor, TextComponent},
+    PermissionLvl,
+};
+
+use crate::{
+    command::{
+        args::{
+            players::PlayersArgumentConsumer, simple::SimpleArgConsumer, Arg, ConsumedArgs, FindArg,
+        },
+        tree::builder::{argument, literal, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{
+        duration_format::format_duration, kit_usage_data::KIT_USAGE_LIST, SaveJSONConfiguration,
+    },
+    entity::player::Player,
+    server::Server,
+};
+use CommandError::{InvalidConsumption, InvalidRequirement};
+
+const NAMES: [&str; 1] = ["kit"];
+const DESCRIPTION: &str = "Gives a kit of items configured on the server.";
+
+const ARG_NAME: &str = "name";
+const ARG_TARGETS: &str = "targets";
+
+/// A kit from the config with its items looked up
+struct Kit {
+    items: Vec<(Item, u32)>,
+    cooldown: u64,
+}
+
+impl Kit {
+    /// Looks up the items of the kit, leaving out and warning about the ones which are invalid
+    fn load(name: &str, config: &KitConfig) -> Self {
+        let items = config
+            .items
+            .iter()
+            .filter_map(|entry| {
+                let item = parse_item(entry);
+                if item.is_none() {
+                    log::warn!("Kit {name} has an invalid item \"{entry}\", leaving it out");
+                }
+                item
+            })
+            .collect();
+        Self {
+            items,
+            cooldown: config.cooldown,
+        }
+    }
+
+    async fn give(&self, player: &Player, server: &Server) {
+        for (item, count) in &self.items {
+            // Items which do not fit into the inventory are dropped at the player's feet
+            player.give_items(server, *item, *count).await;
+        }
+    }
+}
+
+/// Parses an item of a kit like `minecraft:bread 8`, where the count is optional
+fn parse_item(entry: &str) -> Option<(Item, u32)> {
+    let mut parts = entry.split_whitespace();
+    let id = parts.next()?;
+    let item = Item::from_name(id.strip_prefix("minecraft:").unwrap_or(id))?;
+    let count = match parts.next() {
+        Some(count) => count.parse().ok().filter(|count| *count > 0)?,
+        None => 1,
+    };
+    parts.next().is_none().then_some((item, count))
+}
+
+static KITS: LazyLock<HashMap<String, Kit>> = LazyLock::new(|| {
+    ADVANCED_CONFIG
+        .kits
+        .kits
+        .iter()
+        .map(|(name, config)| (name.clone(), Kit::load(name, config)))
+        .collect()
+});
+
+async fn unknown_kit(sender: &mut CommandSender<'_>, name: &str) {
+    sender
+        .send_message(
+            TextComponent::text(format!("There is no kit called {name}"))
+                .color_named(NamedColor::Red),
+        )
+        .await;
+}
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let mut names: Vec<&str> = KITS.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        let message = if names.is_empty() {
+            "There are no kits".to_string()
+        } else {
+            format!("Kits: {}", names.join(", "))
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct KitSelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for KitSelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+            return Err(InvalidConsumption(Some(ARG_NAME.into())));
+        };
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let Some(kit) = KITS.get(*name) else {
+            unknown_kit(sender, name).await;
+            return Ok(());
+        };
+
+        if kit.cooldown > 0 {
+            let mut usages = KIT_USAGE_LIST.write().await;
+            if let Some(remaining) =
+                usages.remaining_cooldown(&player.gameprofile.id, name, kit.cooldown)
+            {
+                drop(usages);
+                sender
+                    .send_message(
+                        TextComponent::text(format!(
+                            "You can redeem the kit {name} again in {}",
+                            format_duration(remaining)
+                        ))
+                        .color_named(NamedColor::Red),
+                    )
+                    .await;
+                return Ok(());
+            }
+            usages.set_used(player.gameprofile.id, name);
+            usages.save();
+        }
+
+        kit.give(&player, server).await;
+        sender
+            .send_message(TextComponent::text(format!("You received the kit {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+/// Gives a kit to other players, without the cooldown
+struct KitOthersExecutor;
+
+#[async_trait]
+impl CommandExecutor for KitOthersExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+            return Err(InvalidConsumption(Some(ARG_NAME.into())));
+        };
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let Some(kit) = KITS.get(*name) else {
+            unknown_kit(sender, name).await;
+            return Ok(());
+        };
+
+        for target in targets {
+            kit.give(target, server).await;
+        }
+        let targets_text = if let [target] = targets {
+            target.gameprofile.name.clone()
+        } else {
+            format!("{} players", targets.len())
+        };
+        sender
+            .send_message(TextComponent::text(format!(
+                "Gave the kit {name} to {targets_text}"
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    // Warn about invalid items when the server starts instead of when a kit is first used
+    LazyLock::force(&KITS);
+
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("list").execute(ListExecutor))
+        .then(
+            argument(ARG_NAME, SimpleArgConsumer)
+                .then(require(|sender| sender.is_player()).execute(KitSelfExecutor))
+                .then(
+                    require(|sender| sender.has_permission_lvl(PermissionLvl::Two)).then(
+                        argument(ARG_TARGETS, PlayersArgumentConsumer).execute(KitOthersExecutor),
+                    ),
+                ),
+        )
+}
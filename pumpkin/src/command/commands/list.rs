@@ -1,8 +1,7 @@
-use std::sync::Arc;
+use std::sync::{atomic::Ordering, Arc};
 
 use async_trait::async_trait;
-use pumpkin_config::BASIC_CONFIG;
-use pumpkin_util::text::TextComponent;
+use pumpkin_util::{text::TextComponent, PermissionLvl};
 
 use crate::{
     command::{
@@ -25,14 +24,18 @@ impl CommandExecutor for ListExecutor {
         server: &crate::server::Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let players: Vec<Arc<Player>> = server.get_all_players().await;
+        let mut players: Vec<Arc<Player>> = server.get_all_players().await;
+        // Only operators know about vanished players
+        if !sender.has_permission_lvl(PermissionLvl::Three) {
+            players.retain(|player| !player.vanished.load(Ordering::Relaxed));
+        }
 
         sender
             .send_message(TextComponent::translate(
                 "commands.list.players",
                 [
                     TextComponent::text(players.len().to_string()),
-                    TextComponent::text(BASIC_CONFIG.max_players.to_string()),
+                    TextComponent::text(server.max_players().to_string()),
                     TextComponent::text(get_player_names(players)),
                 ],
             ))
@@ -0,0 +1,133 @@
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use pumpkin_util::{
+    math::vector2::Vector2,
+    text::{click::ClickEvent, color::NamedColor, hover::HoverEvent, TextComponent},
+};
+use pumpkin_world::generation::structure::{structure_set, StructureType, VILLAGES};
+
+use crate::{
+    command::{
+        args::{resource_location::ResourceLocationArgumentConsumer, ConsumedArgs, FindArg},
+        tree::builder::{argument, literal},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+use CommandError::GeneralCommandIssue;
+
+const NAMES: [&str; 1] = ["locate"];
+
+const DESCRIPTION: &str = "Finds the closest structure of a type.";
+
+const ARG_STRUCTURE: &str = "structure";
+
+/// How many regions of the structure set are searched around the sender, like vanilla
+const SEARCH_RADIUS: i32 = 100;
+
+/// The structures which can be searched for, either by the name of a structure or of all
+/// villages
+fn parse_structure(name: &str) -> Option<(&'static str, Vec<StructureType>)> {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    if name == "village" {
+        return Some(("village", VILLAGES.structures.to_vec()));
+    }
+    let structure = StructureType::from_name(name)?;
+    Some((structure.to_name(), vec![structure]))
+}
+
+struct StructureExecutor;
+
+#[async_trait]
+impl CommandExecutor for StructureExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = ResourceLocationArgumentConsumer::find_arg(args, ARG_STRUCTURE)?;
+        let Some((structure_name, structures)) = parse_structure(name) else {
+            return Err(GeneralCommandIssue(format!(
+                "There is no structure with type \"{name}\""
+            )));
+        };
+
+        let world = match sender.world().await {
+            Some(world) => world,
+            None => server
+                .worlds
+                .read()
+                .await
+                .first()
+                .cloned()
+                .ok_or_else(|| GeneralCommandIssue("There is no world".to_string()))?,
+        };
+        let position = sender.position().unwrap_or_default();
+        let origin = Vector2::new(position.x.floor() as i32, position.z.floor() as i32);
+        let origin_chunk = Vector2::new(origin.x >> 4, origin.z >> 4);
+
+        // Searching samples the biome noise a lot, so it happens off the async runtime
+        let generator = world.level.world_gen();
+        let set = structure_set(structures[0]);
+        let (send, receive) = tokio::sync::oneshot::channel();
+        rayon::spawn(move || {
+            let start = generator.locate_structure(set, origin_chunk, SEARCH_RADIUS, &|found| {
+                structures.contains(&found)
+            });
+            let _ = send.send(start);
+        });
+        let Some(start) = receive.await.ok().flatten() else {
+            return Err(GeneralCommandIssue(format!(
+                "Could not find a structure of type \"minecraft:{structure_name}\" nearby"
+            )));
+        };
+
+        let x = start.chunk.x * 16;
+        let z = start.chunk.z * 16;
+        let dx = f64::from(x - origin.x);
+        let dz = f64::from(z - origin.z);
+        let distance = dx.hypot(dz).floor() as i64;
+        let coordinates = TextComponent::text("[")
+            .add_child(TextComponent::translate(
+                "chat.coordinates",
+                [
+                    TextComponent::text(x.to_string()),
+                    TextComponent::text("~"),
+                    TextComponent::text(z.to_string()),
+                ],
+            ))
+            .add_text("]")
+            .color_named(NamedColor::Green)
+            .click_event(ClickEvent::SuggestCommand(Cow::from(format!(
+                "/tp @s {x} ~ {z}"
+            ))))
+            .hover_event(HoverEvent::show_text(TextComponent::translate(
+                "chat.coordinates.tooltip",
+                [],
+            )));
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.locate.structure.success",
+                [
+                    TextComponent::text(format!("minecraft:{structure_name}")),
+                    coordinates,
+                    TextComponent::text(distance.to_string()),
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        literal("structure").then(
+            argument(ARG_STRUCTURE, ResourceLocationArgumentConsumer::new(false))
+                .execute(StructureExecutor),
+        ),
+    )
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use pumpkin_config::BasicConfiguration;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::{
+    command::{
+        args::{bounded_num::BoundedNumArgumentConsumer, ConsumedArgs, FindArg},
+        tree::builder::argument,
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["maxplayers"];
+
+const DESCRIPTION: &str = "Shows or changes the maximum number of players.";
+
+const ARG_COUNT: &str = "count";
+
+fn count_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_COUNT).min(1)
+}
+
+struct ShowExecutor;
+
+#[async_trait]
+impl CommandExecutor for ShowExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let message = match server.max_players() {
+            0 => "There is no player limit".to_string(),
+            max_players => format!(
+                "{} of {max_players} players are online",
+                server.get_player_count().await
+            ),
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct SetExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Ok(max_players) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_COUNT)? else {
+            sender
+                .send_message(
+                    TextComponent::text("The maximum number of players must be positive")
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        };
+        // Bounded to be positive by the argument consumer
+        let max_players = max_players as u32;
+
+        server.set_max_players(max_players).await;
+        if let Err(err) = BasicConfiguration::save_max_players(max_players) {
+            log::error!("Failed to save the maximum number of players to the configuration: {err}");
+            sender
+                .send_message(
+                    TextComponent::text(
+                        "The maximum number of players was changed, but could not be saved and will be reset on restart",
+                    )
+                    .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        }
+
+        let online = server.get_player_count().await;
+        let mut message = format!("Set the maximum number of players to {max_players}");
+        if online >= max_players as usize {
+            message.push_str(&format!(
+                ". {online} players are online, so no one can join until there is room"
+            ));
+        }
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_COUNT, count_consumer()).execute(SetExecutor))
+        .execute(ShowExecutor)
+}
@@ -1,36 +1,78 @@
+pub mod afk;
+pub mod anvil;
 pub mod ban;
 pub mod banip;
 pub mod banlist;
 pub mod bossbar;
+pub mod broadcast;
+pub mod chatformat;
 pub mod clear;
+pub mod command;
+pub mod cooldown;
 pub mod damage;
 pub mod deop;
+pub mod enderchest;
+pub mod essentials;
+pub mod execute;
 pub mod experience;
 pub mod fill;
+pub mod firstjoin;
+pub mod freeze;
 pub mod gamemode;
 pub mod give;
+pub mod glow;
+pub mod grindstone;
 pub mod help;
+pub mod home;
+pub mod ignore;
+pub mod jump;
 pub mod kick;
 pub mod kill;
+pub mod kit;
 pub mod list;
+pub mod locate;
+pub mod maxplayers;
 pub mod me;
+pub mod more;
+pub mod motd;
 pub mod msg;
+pub mod mute;
+pub mod nbt;
+pub mod nick;
 pub mod op;
 pub mod pardon;
 pub mod pardonip;
 pub mod particle;
 pub mod playsound;
+pub mod playtime;
 pub mod plugin;
 pub mod plugins;
 pub mod pumpkin;
+pub mod rtp;
+pub mod saveall;
+pub mod saveoff;
+pub mod saveon;
 pub mod say;
 pub mod seed;
+pub mod seen;
 pub mod setblock;
+pub mod skull;
+pub mod sleeppercentage;
+pub mod spawn;
+pub mod spawnprotection;
 pub mod stop;
 pub mod summon;
 pub mod teleport;
 pub mod time;
 pub mod title;
+pub mod top;
+pub mod tpa;
 pub mod transfer;
+pub mod trash;
+pub mod vanish;
+pub mod warp;
 pub mod weather;
+pub mod workbench;
+pub mod world;
 pub mod worldborder;
+pub mod worldinfo;
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        args::ConsumedArgs, tree::builder::require, tree::CommandTree, CommandError,
+        CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+use CommandError::{GeneralCommandIssue, InvalidRequirement};
+
+const NAMES: [&str; 1] = ["more"];
+const DESCRIPTION: &str = "Fills the stack in your main hand up to its max stack size.";
+
+struct MoreExecutor;
+
+#[async_trait]
+impl CommandExecutor for MoreExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+
+        let mut inventory = player.inventory().lock().await;
+        let Some(stack) = inventory.held_item_mut() else {
+            return Err(GeneralCommandIssue(
+                "You are not holding an item".to_string(),
+            ));
+        };
+        let max_stack_size = stack.item.components.max_stack_size;
+        if stack.item_count >= max_stack_size {
+            return Err(GeneralCommandIssue(
+                "This stack is already full".to_string(),
+            ));
+        }
+        stack.item_count = max_stack_size;
+        let name = stack.item.translated_name();
+        drop(inventory);
+        player.set_container_content(None).await;
+
+        sender
+            .send_message(
+                TextComponent::text(format!("You now have {max_stack_size} ")).add_child(name),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(MoreExecutor))
+}
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use pumpkin_config::BasicConfiguration;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::{
+    command::{
+        args::{message::MsgArgConsumer, Arg, ConsumedArgs},
+        tree::builder::{argument, literal},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    server::{motd, Server},
+};
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 1] = ["motd"];
+
+const DESCRIPTION: &str = "Shows or changes the message of the day displayed in the server list.";
+
+const ARG_MOTD: &str = "motd";
+
+struct ShowExecutor;
+
+#[async_trait]
+impl CommandExecutor for ShowExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let motd = motd::parse(server.get_status().lock().await.motd());
+        sender
+            .send_message(TextComponent::text("The MOTD is:\n").add_child(motd))
+            .await;
+        Ok(())
+    }
+}
+
+struct SetExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Msg(msg)) = args.get(ARG_MOTD) else {
+            return Err(InvalidConsumption(Some(ARG_MOTD.into())));
+        };
+
+        // Chat can't contain line breaks, so allow them to be escaped
+        let new_motd = msg.replace("\\n", "\n");
+        let component = motd::parse(&new_motd);
+        if let Err(err) = motd::validate(&component) {
+            sender
+                .send_message(TextComponent::text(err).color_named(NamedColor::Red))
+                .await;
+            return Ok(());
+        }
+
+        server.get_status().lock().await.set_motd(new_motd.clone());
+        if let Err(err) = BasicConfiguration::save_motd(&new_motd) {
+            log::error!("Failed to save the MOTD to the configuration: {err}");
+            sender
+                .send_message(
+                    TextComponent::text(
+                        "The MOTD was changed, but could not be saved and will be reset on restart",
+                    )
+                    .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        }
+
+        sender
+            .send_message(TextComponent::text("Changed the MOTD to:\n").add_child(component))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("set").then(argument(ARG_MOTD, MsgArgConsumer).execute(SetExecutor)))
+        .execute(ShowExecutor)
+}
@@ -34,6 +34,10 @@ impl CommandExecutor for MsgExecutor {
         };
         let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
         let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        if let Some(notice) = player.mute_notice().await {
+            player.send_system_message(&notice).await;
+            return Ok(());
+        }
 
         for target in targets {
             player
@@ -56,6 +60,10 @@ impl CommandExecutor for MsgExecutor {
                 .await;
         }
         for target in targets {
+            // The sender is not told, so ignoring someone stays private
+            if target.is_ignoring(player).await {
+                continue;
+            }
             target
                 .send_message(
                     &TextComponent::text(msg.clone()),
@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use chrono::{Local, TimeDelta};
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::{
+    command::{
+        args::{
+            duration::DurationArgumentConsumer, message::MsgArgConsumer,
+            players::PlayersArgumentConsumer, Arg, ConsumedArgs, FindArg,
+        },
+        tree::builder::argument,
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{
+        banlist_serializer::MutedPlayerEntry, duration_format::format_duration,
+        muted_player_data::MUTED_PLAYER_LIST, SaveJSONConfiguration,
+    },
+    server::Server,
+};
+use CommandError::{GeneralCommandIssue, InvalidConsumption};
+
+const NAMES: [&str; 1] = ["mute"];
+const DESCRIPTION: &str = "Prevents players from chatting, optionally for a limited time.";
+
+const UNMUTE_NAMES: [&str; 1] = ["unmute"];
+const UNMUTE_DESCRIPTION: &str = "Allows muted players to chat again.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_DURATION: &str = "duration";
+const ARG_REASON: &str = "reason";
+
+const DEFAULT_REASON: &str = "Muted by an operator.";
+
+struct MuteExecutor;
+
+#[async_trait]
+impl CommandExecutor for MuteExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        // The duration and reason are both optional
+        let duration = args
+            .contains_key(ARG_DURATION)
+            .then(|| DurationArgumentConsumer::find_arg(args, ARG_DURATION))
+            .transpose()?;
+        let reason = match args.get(ARG_REASON) {
+            Some(Arg::Msg(reason)) => reason.clone(),
+            Some(_) => return Err(InvalidConsumption(Some(ARG_REASON.into()))),
+            None => DEFAULT_REASON.to_string(),
+        };
+
+        let expires = duration
+            .map(|duration| {
+                Local::now()
+                    .fixed_offset()
+                    .checked_add_signed(duration)
+                    .ok_or_else(|| GeneralCommandIssue("The duration is too long".to_string()))
+            })
+            .transpose()?;
+        let duration_text = duration.map_or_else(
+            || "permanently".to_string(),
+            |duration| format!("for {}", format_duration(duration)),
+        );
+
+        let mut muted_players = MUTED_PLAYER_LIST.write().await;
+        for target in targets {
+            // Muting a muted player again replaces their mute
+            muted_players.remove(&target.gameprofile);
+            muted_players.muted_players.push(MutedPlayerEntry::new(
+                &target.gameprofile,
+                sender.to_string(),
+                expires,
+                reason.clone(),
+            ));
+        }
+        muted_players.save();
+        drop(muted_players);
+
+        for target in targets {
+            target
+                .send_system_message(
+                    &TextComponent::text(format!(
+                        "You have been muted {duration_text}. Reason: {reason}"
+                    ))
+                    .color_named(NamedColor::Red),
+                )
+                .await;
+            sender
+                .send_message(TextComponent::text(format!(
+                    "Muted {} {duration_text}: {reason}",
+                    target.gameprofile.name
+                )))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct UnmuteExecutor;
+
+#[async_trait]
+impl CommandExecutor for UnmuteExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        let mut muted_players = MUTED_PLAYER_LIST.write().await;
+        let mut unmuted = Vec::new();
+        for target in targets {
+            if muted_players.remove(&target.gameprofile) {
+                unmuted.push(target);
+            } else {
+                sender
+                    .send_message(TextComponent::text(format!(
+                        "{} is not muted",
+                        target.gameprofile.name
+                    )))
+                    .await;
+            }
+        }
+        muted_players.save();
+        drop(muted_players);
+
+        for target in unmuted {
+            target
+                .send_system_message(&TextComponent::text("You are no longer muted"))
+                .await;
+            sender
+                .send_message(TextComponent::text(format!(
+                    "Unmuted {}",
+                    target.gameprofile.name
+                )))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGETS, PlayersArgumentConsumer)
+            .execute(MuteExecutor)
+            .then(
+                argument(ARG_DURATION, DurationArgumentConsumer::new().require_unit())
+                    .execute(MuteExecutor)
+                    .then(argument(ARG_REASON, MsgArgConsumer).execute(MuteExecutor)),
+            )
+            .then(argument(ARG_REASON, MsgArgConsumer).execute(MuteExecutor)),
+    )
+}
+
+pub fn init_unmute_command_tree() -> CommandTree {
+    CommandTree::new(UNMUTE_NAMES, UNMUTE_DESCRIPTION)
+        .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(UnmuteExecutor))
+}
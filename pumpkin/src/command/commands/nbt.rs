@@ -0,0 +1,274 @@
+use async_trait::async_trait;
+use pumpkin_data::item::Operation;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+use pumpkin_world::item::ItemStack;
+
+use crate::command::{
+    args::ConsumedArgs, tree::builder::require, tree::CommandTree, CommandError, CommandExecutor,
+    CommandSender,
+};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["nbt"];
+
+const DESCRIPTION: &str = "Shows the NBT of the item in your main hand.";
+
+const INDENT: &str = "  ";
+
+/// Builds the NBT of an item stack, in the format of the `/give` and `/data` commands
+fn item_to_nbt(stack: &ItemStack) -> NbtCompound {
+    let item = &stack.item;
+    let mut components = NbtCompound::new();
+    if let Some(name) = item.components.item_name {
+        components.put("minecraft:item_name", NbtTag::String(name.to_string()));
+    }
+    components.put_int(
+        "minecraft:max_stack_size",
+        i32::from(item.components.max_stack_size),
+    );
+    if let Some(damage) = item.components.damage {
+        components.put_int("minecraft:damage", i32::from(damage));
+    }
+    if let Some(max_damage) = item.components.max_damage {
+        components.put_int("minecraft:max_damage", i32::from(max_damage));
+    }
+    if let Some(playable) = item.components.jukebox_playable {
+        let mut jukebox_playable = NbtCompound::new();
+        jukebox_playable.put("song", NbtTag::String(playable.song.to_string()));
+        components.put_component("minecraft:jukebox_playable", jukebox_playable);
+    }
+    if let Some(attributes) = item.components.attribute_modifiers {
+        let modifiers = attributes
+            .modifiers
+            .iter()
+            .map(|modifier| {
+                let mut nbt = NbtCompound::new();
+                nbt.put("type", NbtTag::String(modifier.r#type.to_string()));
+                nbt.put("id", NbtTag::String(modifier.id.to_string()));
+                nbt.put_double("amount", modifier.amount);
+                let operation = match modifier.operation {
+                    Operation::AddValue => "add_value",
+                    Operation::AddMultipliedBase => "add_multiplied_base",
+                    Operation::AddMultipliedTotal => "add_multiplied_total",
+                };
+                nbt.put("operation", NbtTag::String(operation.to_string()));
+                nbt.put("slot", NbtTag::String(modifier.slot.to_string()));
+                NbtTag::Compound(nbt)
+            })
+            .collect();
+        let mut attribute_modifiers = NbtCompound::new();
+        attribute_modifiers.put("modifiers", NbtTag::List(modifiers));
+        components.put_component("minecraft:attribute_modifiers", attribute_modifiers);
+    }
+    if let Some(tool) = item.components.tool {
+        let rules = tool
+            .rules
+            .iter()
+            .map(|rule| {
+                let mut nbt = NbtCompound::new();
+                let blocks = rule
+                    .blocks
+                    .iter()
+                    .map(|block| NbtTag::String((*block).to_string()))
+                    .collect();
+                nbt.put("blocks", NbtTag::List(blocks));
+                if let Some(speed) = rule.speed {
+                    nbt.put_float("speed", speed);
+                }
+                if let Some(correct_for_drops) = rule.correct_for_drops {
+                    nbt.put_bool("correct_for_drops", correct_for_drops);
+                }
+                NbtTag::Compound(nbt)
+            })
+            .collect();
+        let mut nbt = NbtCompound::new();
+        nbt.put("rules", NbtTag::List(rules));
+        if let Some(speed) = tool.default_mining_speed {
+            nbt.put_float("default_mining_speed", speed);
+        }
+        if let Some(damage) = tool.damage_per_block {
+            nbt.put_int("damage_per_block", damage as i32);
+        }
+        components.put_component("minecraft:tool", nbt);
+    }
+    // Components set on the stack, e.g. by plugins, replace the defaults of the item
+    for (key, tag) in &stack.components.child_tags {
+        components.remove(key);
+        components.put(key, tag.clone());
+    }
+
+    let mut nbt = NbtCompound::new();
+    nbt.put(
+        "id",
+        NbtTag::String(format!("minecraft:{}", item.registry_key())),
+    );
+    nbt.put_int("count", i32::from(stack.item_count));
+    nbt.put_component("components", components);
+    nbt
+}
+
+/// Formats NBT like the vanilla `/data` command: keys are aqua, strings green, numbers gold and
+/// number type suffixes red
+#[derive(Default)]
+struct NbtFormatter {
+    parts: Vec<TextComponent>,
+}
+
+impl NbtFormatter {
+    fn push(&mut self, text: impl Into<String>) {
+        self.parts.push(TextComponent::text(text.into()));
+    }
+
+    fn push_colored(&mut self, text: impl Into<String>, color: NamedColor) {
+        self.parts
+            .push(TextComponent::text(text.into()).color_named(color));
+    }
+
+    fn push_number(&mut self, number: String, suffix: &str) {
+        self.push_colored(number, NamedColor::Gold);
+        if !suffix.is_empty() {
+            self.push_colored(suffix, NamedColor::Red);
+        }
+    }
+
+    fn push_string(&mut self, string: &str, color: NamedColor) {
+        let escaped = string.replace('\\', "\\\\").replace('"', "\\\"");
+        self.push_colored(format!("\"{escaped}\""), color);
+    }
+
+    fn push_key(&mut self, key: &str) {
+        let is_plain = !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+'));
+        if is_plain {
+            self.push_colored(key, NamedColor::Aqua);
+        } else {
+            self.push_string(key, NamedColor::Aqua);
+        }
+    }
+
+    fn push_newline(&mut self, depth: usize) {
+        self.push(format!("\n{}", INDENT.repeat(depth)));
+    }
+
+    fn push_compound(&mut self, compound: &NbtCompound, depth: usize) {
+        if compound.child_tags.is_empty() {
+            self.push("{}");
+            return;
+        }
+        self.push("{");
+        for (i, (key, tag)) in compound.child_tags.iter().enumerate() {
+            if i > 0 {
+                self.push(",");
+            }
+            self.push_newline(depth + 1);
+            self.push_key(key);
+            self.push(": ");
+            self.push_tag(tag, depth + 1);
+        }
+        self.push_newline(depth);
+        self.push("}");
+    }
+
+    fn push_array<T: ToString>(&mut self, prefix: &str, values: &[T], suffix: &str) {
+        self.push("[");
+        self.push_colored(prefix, NamedColor::Red);
+        self.push(";");
+        for (i, value) in values.iter().enumerate() {
+            self.push(if i == 0 { " " } else { ", " });
+            self.push_number(value.to_string(), suffix);
+        }
+        self.push("]");
+    }
+
+    fn push_list(&mut self, list: &[NbtTag], depth: usize) {
+        if list.is_empty() {
+            self.push("[]");
+            return;
+        }
+        // Only lists of compounds and lists get a line per element
+        let multiline = list
+            .iter()
+            .any(|tag| matches!(tag, NbtTag::Compound(_) | NbtTag::List(_)));
+        self.push("[");
+        for (i, tag) in list.iter().enumerate() {
+            if i > 0 {
+                self.push(",");
+            }
+            if multiline {
+                self.push_newline(depth + 1);
+            } else if i > 0 {
+                self.push(" ");
+            }
+            self.push_tag(tag, depth + 1);
+        }
+        if multiline {
+            self.push_newline(depth);
+        }
+        self.push("]");
+    }
+
+    fn push_tag(&mut self, tag: &NbtTag, depth: usize) {
+        match tag {
+            NbtTag::End => {}
+            NbtTag::Byte(value) => self.push_number(value.to_string(), "b"),
+            NbtTag::Short(value) => self.push_number(value.to_string(), "s"),
+            NbtTag::Int(value) => self.push_number(value.to_string(), ""),
+            NbtTag::Long(value) => self.push_number(value.to_string(), "L"),
+            NbtTag::Float(value) => self.push_number(value.to_string(), "f"),
+            NbtTag::Double(value) => self.push_number(value.to_string(), "d"),
+            NbtTag::ByteArray(values) => {
+                let values: Vec<i8> = values.iter().map(|value| *value as i8).collect();
+                self.push_array("B", &values, "b");
+            }
+            NbtTag::IntArray(values) => self.push_array("I", values, ""),
+            NbtTag::LongArray(values) => self.push_array("L", values, "L"),
+            NbtTag::String(value) => self.push_string(value, NamedColor::Green),
+            NbtTag::List(list) => self.push_list(list, depth),
+            NbtTag::Compound(compound) => self.push_compound(compound, depth),
+        }
+    }
+}
+
+/// Formats a compound with indentation and colors
+fn format_nbt(compound: &NbtCompound) -> TextComponent {
+    let mut formatter = NbtFormatter::default();
+    formatter.push_compound(compound, 0);
+    formatter
+        .parts
+        .into_iter()
+        .fold(TextComponent::text(""), TextComponent::add_child)
+}
+
+struct NbtExecutor;
+
+#[async_trait]
+impl CommandExecutor for NbtExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::InvalidRequirement);
+        };
+        let Some(stack) = player.inventory().lock().await.held_item().copied() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "You are not holding an item".to_string(),
+            ));
+        };
+
+        sender.send_message(format_nbt(&item_to_nbt(&stack))).await;
+
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(NbtExecutor))
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::text::{TextComponent, TextComponentBase, TextContent};
+
+use crate::{
+    command::{
+        args::{
+            players::PlayersArgumentConsumer, textcomponent::TextComponentArgConsumer,
+            ConsumedArgs, FindArg,
+        },
+        tree::builder::{argument, literal},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::Player,
+    server::Server,
+};
+use CommandError::GeneralCommandIssue;
+
+const NAMES: [&str; 1] = ["nick"];
+
+const DESCRIPTION: &str = "Sets the name shown for players in chat and the player list.";
+
+const ARG_TARGETS: &str = "targets";
+
+const ARG_NAME: &str = "name";
+
+/// The maximum length of the plain text of a nickname, without its formatting
+const MAX_NICKNAME_LENGTH: usize = 32;
+
+/// Strips the characters and events which are not allowed in nicknames, returning the length of
+/// the remaining plain text.
+fn sanitize(component: &mut TextComponentBase) -> Result<usize, CommandError> {
+    let TextContent::Text { text } = &mut component.content else {
+        return Err(GeneralCommandIssue(
+            "Nicknames can only contain plain text".to_string(),
+        ));
+    };
+    let stripped: String = text
+        .chars()
+        .filter(|c| *c != '§' && !c.is_control())
+        .collect();
+    let mut length = stripped.chars().count();
+    *text = stripped.into();
+
+    // Nicknames are shown to everyone, so they must not run commands or open links
+    component.style.click_event = None;
+    component.style.hover_event = None;
+    component.style.insertion = None;
+
+    for extra in &mut component.extra {
+        length += sanitize(extra)?;
+    }
+    Ok(length)
+}
+
+fn target_name(targets: &[Arc<Player>]) -> String {
+    match targets {
+        [target] => target.gameprofile.name.clone(),
+        _ => format!("{} players", targets.len()),
+    }
+}
+
+struct SetExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let mut nickname = TextComponentArgConsumer::find_arg(args, ARG_NAME)?;
+
+        let length = sanitize(&mut nickname.0)?;
+        if length == 0 {
+            return Err(GeneralCommandIssue(
+                "Nicknames can not be empty".to_string(),
+            ));
+        }
+        if length > MAX_NICKNAME_LENGTH {
+            return Err(GeneralCommandIssue(format!(
+                "Nicknames can be at most {MAX_NICKNAME_LENGTH} characters long"
+            )));
+        }
+
+        for target in targets {
+            target.set_nickname(Some(nickname.clone())).await;
+        }
+        sender
+            .send_message(
+                TextComponent::text(format!("Set the nickname of {} to ", target_name(targets)))
+                    .add_child(nickname),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+struct ResetExecutor;
+
+#[async_trait]
+impl CommandExecutor for ResetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        for target in targets {
+            target.set_nickname(None).await;
+        }
+        sender
+            .send_message(TextComponent::text(format!(
+                "Reset the nickname of {}",
+                target_name(targets)
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGETS, PlayersArgumentConsumer)
+            .then(literal("reset").execute(ResetExecutor))
+            .then(argument(ARG_NAME, TextComponentArgConsumer).execute(SetExecutor)),
+    )
+}
@@ -0,0 +1,84 @@
+use std::sync::atomic::Ordering;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{simple::SimpleArgConsumer, Arg, ConsumedArgs},
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{duration_format::format_playtime, playtime_data::PLAYTIME_LIST},
+    server::Server,
+};
+use CommandError::{InvalidConsumption, InvalidRequirement};
+
+const NAMES: [&str; 1] = ["playtime"];
+const DESCRIPTION: &str = "Shows how long a player was online in total.";
+
+const ARG_PLAYER: &str = "player";
+
+struct PlaytimeSelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlaytimeSelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let playtime = format_playtime(player.playtime.load(Ordering::Relaxed));
+        sender
+            .send_message(TextComponent::text(format!(
+                "You have played for {playtime}"
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct PlaytimeExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlaytimeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Simple(name)) = args.get(ARG_PLAYER) else {
+            return Err(InvalidConsumption(Some(ARG_PLAYER.into())));
+        };
+
+        // Online players have played longer than what was saved
+        let message = if let Some(player) = server.get_player_by_name(name).await {
+            format!(
+                "{} has played for {}",
+                player.gameprofile.name,
+                format_playtime(player.playtime.load(Ordering::Relaxed))
+            )
+        } else {
+            match PLAYTIME_LIST.read().await.get_entry_by_name(name) {
+                Some(entry) => format!(
+                    "{} has played for {}",
+                    entry.name,
+                    format_playtime(entry.ticks)
+                ),
+                None => format!("{name} has never joined this server"),
+            }
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(PlaytimeSelfExecutor))
+        .then(argument(ARG_PLAYER, SimpleArgConsumer).execute(PlaytimeExecutor))
+}
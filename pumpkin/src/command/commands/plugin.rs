@@ -1,6 +1,8 @@
+use std::borrow::Cow;
+
 use async_trait::async_trait;
 use pumpkin_util::{
-    text::{color::NamedColor, hover::HoverEvent, TextComponent},
+    text::{click::ClickEvent, color::NamedColor, TextComponent},
     PermissionLvl,
 };
 
@@ -16,6 +18,7 @@ use crate::{
     PLUGIN_MANAGER,
 };
 
+use super::plugins::{plugin_list, state_color};
 use crate::command::CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["plugin"];
@@ -34,38 +37,68 @@ impl CommandExecutor for ListExecutor {
         _server: &crate::server::Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let plugin_manager = PLUGIN_MANAGER.lock().await;
-        let plugins = plugin_manager.list_plugins();
-
-        let message_text = if plugins.is_empty() {
-            "There are no loaded plugins.".to_string()
-        } else if plugins.len() == 1 {
-            "There is 1 plugin loaded:\n".to_string()
-        } else {
-            format!("There are {} plugins loaded:\n", plugins.len())
+        let message = plugin_list(&*PLUGIN_MANAGER.lock().await).await;
+        sender.send_message(message).await;
+
+        Ok(())
+    }
+}
+
+struct InfoExecutor;
+
+#[async_trait]
+impl CommandExecutor for InfoExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Simple(plugin_name)) = args.get(PLUGIN_NAME) else {
+            return Err(InvalidConsumption(Some(PLUGIN_NAME.into())));
+        };
+        let Some(info) = PLUGIN_MANAGER.lock().await.plugin_info(plugin_name).await else {
+            sender
+                .send_message(
+                    TextComponent::text(format!("Plugin {plugin_name} not found"))
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
         };
-        let mut message = TextComponent::text(message_text);
-
-        for (i, (metadata, loaded)) in plugins.clone().into_iter().enumerate() {
-            let fmt = if i == plugins.len() - 1 {
-                metadata.name.to_string()
-            } else {
-                format!("{}, ", metadata.name)
-            };
-            let hover_text = format!(
-                "Version: {}\nAuthors: {}\nDescription: {}",
-                metadata.version, metadata.authors, metadata.description
-            );
-            let component = if *loaded {
-                TextComponent::text(fmt)
-                    .color_named(NamedColor::Green)
-                    .hover_event(HoverEvent::show_text(TextComponent::text(hover_text)))
-            } else {
-                TextComponent::text(fmt)
-                    .color_named(NamedColor::Red)
-                    .hover_event(HoverEvent::show_text(TextComponent::text(hover_text)))
-            };
-            message = message.add_child(component);
+
+        let metadata = &info.metadata;
+        // Children inherit the style of their parent, so the header must be a sibling
+        let mut message = TextComponent::text("").add_child(
+            TextComponent::text(format!("{} {}", metadata.name, metadata.version))
+                .color_named(state_color(&info.state))
+                .bold(),
+        );
+        let mut details = format!(
+            "\nState: {}\nAuthors: {}\nDescription: {}",
+            info.state, metadata.authors, metadata.description
+        );
+        if !metadata.depends.is_empty() {
+            details.push_str(&format!("\nDepends: {}", metadata.depends.join(", ")));
+        }
+        if !metadata.soft_depends.is_empty() {
+            details.push_str(&format!(
+                "\nSoft depends: {}",
+                metadata.soft_depends.join(", ")
+            ));
+        }
+        details.push_str(&format!("\nCommands: {}", join_or_none(&info.commands)));
+        details.push_str(&format!("\nListens to: {}", join_or_none(&info.events)));
+        message = message.add_child(TextComponent::text(details));
+
+        if !metadata.website.is_empty() {
+            message = message
+                .add_child(TextComponent::text("\nWebsite: "))
+                .add_child(
+                    TextComponent::text(metadata.website)
+                        .underlined()
+                        .click_event(ClickEvent::OpenUrl(Cow::Borrowed(metadata.website))),
+                );
         }
 
         sender.send_message(message).await;
@@ -74,6 +107,18 @@ impl CommandExecutor for ListExecutor {
     }
 }
 
+fn join_or_none<T: AsRef<str>>(items: &[T]) -> String {
+    if items.is_empty() {
+        "none".to_string()
+    } else {
+        items
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 struct LoadExecutor;
 
 #[async_trait]
@@ -174,6 +219,62 @@ impl CommandExecutor for UnloadExecutor {
     }
 }
 
+struct ReloadExecutor;
+
+#[async_trait]
+impl CommandExecutor for ReloadExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Simple(plugin_name)) = args.get(PLUGIN_NAME) else {
+            return Err(InvalidConsumption(Some(PLUGIN_NAME.into())));
+        };
+        let mut plugin_manager = PLUGIN_MANAGER.lock().await;
+
+        if !plugin_manager.is_plugin_loaded(plugin_name) {
+            sender
+                .send_message(
+                    TextComponent::text(format!("Plugin {plugin_name} is not loaded"))
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        }
+
+        let dependents = plugin_manager.loaded_dependents(plugin_name);
+        let result = plugin_manager.reload_plugin(plugin_name).await;
+
+        match result {
+            Ok(()) => {
+                let message = if dependents.is_empty() {
+                    format!("Plugin {plugin_name} reloaded successfully")
+                } else {
+                    format!(
+                        "Plugin {plugin_name} reloaded successfully, along with its dependents: {}",
+                        dependents.join(", ")
+                    )
+                };
+                sender
+                    .send_message(TextComponent::text(message).color_named(NamedColor::Green))
+                    .await;
+            }
+            Err(e) => {
+                sender
+                    .send_message(
+                        TextComponent::text(format!("Failed to reload plugin {plugin_name}: {e}"))
+                            .color_named(NamedColor::Red),
+                    )
+                    .await;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         require(|sender| {
@@ -185,6 +286,11 @@ pub fn init_command_tree() -> CommandTree {
             literal("unload")
                 .then(argument(PLUGIN_NAME, SimpleArgConsumer).execute(UnloadExecutor)),
         )
-        .then(literal("list").execute(ListExecutor)),
+        .then(
+            literal("reload")
+                .then(argument(PLUGIN_NAME, SimpleArgConsumer).execute(ReloadExecutor)),
+        )
+        .then(literal("list").execute(ListExecutor))
+        .then(literal("info").then(argument(PLUGIN_NAME, SimpleArgConsumer).execute(InfoExecutor))),
     )
 }
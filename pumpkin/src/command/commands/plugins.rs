@@ -5,6 +5,7 @@ use crate::{
     command::{
         args::ConsumedArgs, tree::CommandTree, CommandError, CommandExecutor, CommandSender,
     },
+    plugin::{PluginInfo, PluginManager, PluginState},
     PLUGIN_MANAGER,
 };
 
@@ -12,6 +13,83 @@ const NAMES: [&str; 1] = ["plugins"];
 
 const DESCRIPTION: &str = "List all available plugins.";
 
+/// The color a plugin is shown in, depending on its state.
+pub(super) fn state_color(state: &PluginState) -> NamedColor {
+    match state {
+        PluginState::Loaded => NamedColor::Green,
+        PluginState::MissingDependencies(_) | PluginState::DependencyCycle => NamedColor::Gold,
+        PluginState::Unloaded | PluginState::Failed(_) => NamedColor::Red,
+    }
+}
+
+fn hover_text(info: &PluginInfo) -> String {
+    let metadata = &info.metadata;
+    let mut hover_text = format!(
+        "Version: {}\nAuthors: {}\nDescription: {}",
+        metadata.version, metadata.authors, metadata.description
+    );
+    if !metadata.website.is_empty() {
+        hover_text.push_str(&format!("\nWebsite: {}", metadata.website));
+    }
+    if !metadata.depends.is_empty() {
+        hover_text.push_str(&format!("\nDepends: {}", metadata.depends.join(", ")));
+    }
+    if !metadata.soft_depends.is_empty() {
+        hover_text.push_str(&format!(
+            "\nSoft depends: {}",
+            metadata.soft_depends.join(", ")
+        ));
+    }
+    hover_text.push_str(&format!("\nState: {}", info.state));
+    hover_text
+}
+
+/// Builds the list of all plugins, including libraries which could not be opened, colored by
+/// their state and with details on hover.
+pub(super) async fn plugin_list(plugin_manager: &PluginManager) -> TextComponent {
+    let plugins = plugin_manager.plugin_infos().await;
+    let failed_libraries = plugin_manager.list_failed_libraries();
+
+    let mut entries: Vec<(String, NamedColor, String)> = plugins
+        .iter()
+        .map(|info| {
+            (
+                info.metadata.name.to_string(),
+                state_color(&info.state),
+                hover_text(info),
+            )
+        })
+        .collect();
+    entries.extend(failed_libraries.iter().map(|(file, err)| {
+        (
+            file.clone(),
+            NamedColor::Red,
+            format!("Failed to open library: {err}"),
+        )
+    }));
+
+    let message_text = match entries.len() {
+        0 => "There are no plugins.".to_string(),
+        1 => "There is 1 plugin:\n".to_string(),
+        count => format!("There are {count} plugins:\n"),
+    };
+    let mut message = TextComponent::text(message_text);
+
+    let count = entries.len();
+    for (i, (name, color, hover_text)) in entries.into_iter().enumerate() {
+        let fmt = if i == count - 1 {
+            name
+        } else {
+            format!("{name}, ")
+        };
+        let component = TextComponent::text(fmt)
+            .color_named(color)
+            .hover_event(HoverEvent::show_text(TextComponent::text(hover_text)));
+        message = message.add_child(component);
+    }
+    message
+}
+
 struct ListExecutor;
 
 #[async_trait]
@@ -22,40 +100,7 @@ impl CommandExecutor for ListExecutor {
         _server: &crate::server::Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let plugin_manager = PLUGIN_MANAGER.lock().await;
-        let plugins = plugin_manager.list_plugins();
-
-        let message_text = if plugins.is_empty() {
-            "There are no loaded plugins.".to_string()
-        } else if plugins.len() == 1 {
-            "There is 1 plugin loaded:\n".to_string()
-        } else {
-            format!("There are {} plugins loaded:\n", plugins.len())
-        };
-        let mut message = TextComponent::text(message_text);
-
-        for (i, (metadata, loaded)) in plugins.clone().into_iter().enumerate() {
-            let fmt = if i == plugins.len() - 1 {
-                metadata.name.to_string()
-            } else {
-                format!("{}, ", metadata.name)
-            };
-            let hover_text = format!(
-                "Version: {}\nAuthors: {}\nDescription: {}",
-                metadata.version, metadata.authors, metadata.description
-            );
-            let component = if *loaded {
-                TextComponent::text(fmt)
-                    .color_named(NamedColor::Green)
-                    .hover_event(HoverEvent::show_text(TextComponent::text(hover_text)))
-            } else {
-                TextComponent::text(fmt)
-                    .color_named(NamedColor::Red)
-                    .hover_event(HoverEvent::show_text(TextComponent::text(hover_text)))
-            };
-            message = message.add_child(component);
-        }
-
+        let message = plugin_list(&*PLUGIN_MANAGER.lock().await).await;
         sender.send_message(message).await;
 
         Ok(())
@@ -0,0 +1,217 @@
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_registry::DimensionType;
+use pumpkin_util::{
+    math::{vector2::Vector2, vector3::Vector3},
+    text::{color::NamedColor, TextComponent},
+};
+use pumpkin_world::{
+    block::registry::get_block_and_state_by_state_id, chunk::ChunkData,
+    coordinates::ChunkRelativeBlockCoordinates, WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+use rand::Rng;
+
+use crate::{
+    block::blocks::fluid::FluidState,
+    command::{
+        args::ConsumedArgs, tree::builder::require, tree::CommandTree, CommandError,
+        CommandExecutor, CommandSender,
+    },
+    data::{
+        duration_format::format_duration, random_teleport_data::RANDOM_TELEPORT_USAGE_LIST,
+        SaveJSONConfiguration,
+    },
+    plugin::api::events::player::player_teleport::TeleportCause,
+    server::Server,
+    world::World,
+};
+use CommandError::{GeneralCommandIssue, InvalidRequirement};
+
+const NAMES: [&str; 1] = ["rtp"];
+const DESCRIPTION: &str = "Teleports you to a random safe place on the surface.";
+
+/// How many random places are tried before giving up
+const MAX_ATTEMPTS: u32 = 16;
+
+/// The highest y to stand at in the nether, so players are not placed on top of the bedrock roof
+const NETHER_MAX_Y: i32 = 120;
+
+/// Blocks with a collision shape which still hurt players standing on them
+const UNSAFE_GROUND: [&str; 4] = ["magma_block", "cactus", "campfire", "soul_campfire"];
+
+/// Blocks without a collision shape which still hurt players standing in them
+const UNSAFE_SPACE: [&str; 2] = ["fire", "soul_fire"];
+
+/// Whether a player can stand on top of the block state
+fn is_safe_ground(state_id: u16) -> bool {
+    FluidState::from_state_id(state_id).is_none()
+        && get_block_and_state_by_state_id(state_id).is_some_and(|(block, state)| {
+            !state.air
+                && !state.collision_shapes.is_empty()
+                && !UNSAFE_GROUND.contains(&block.name.as_str())
+        })
+}
+
+/// Whether a player can stand inside the block state, like air or short grass
+fn is_free(state_id: u16) -> bool {
+    FluidState::from_state_id(state_id).is_none()
+        && get_block_and_state_by_state_id(state_id).is_some_and(|(block, state)| {
+            state.air
+                || (state.replaceable
+                    && state.collision_shapes.is_empty()
+                    && !UNSAFE_SPACE.contains(&block.name.as_str()))
+        })
+}
+
+/// Finds the y a player can stand at in the column of the chunk, or `None` if the surface is
+/// unsafe, e.g. because it is water or lava. With a ceiling, the highest free space below it is
+/// used instead of the surface.
+fn find_standing_y(chunk: &ChunkData, x: u8, z: u8, ceiling: bool) -> Option<i32> {
+    let state_at = |y: i32| {
+        chunk.get_block(ChunkRelativeBlockCoordinates::from(Vector3::new(
+            i32::from(x),
+            y,
+            i32::from(z),
+        )))
+    };
+    let top = if ceiling {
+        NETHER_MAX_Y
+    } else {
+        i32::from(WORLD_MAX_Y) - 1
+    };
+
+    let mut free = 0;
+    for y in (i32::from(WORLD_LOWEST_Y)..=top).rev() {
+        let state_id = state_at(y)?;
+        if is_free(state_id) {
+            free += 1;
+            continue;
+        }
+        // The player needs two free blocks above the ground
+        if free >= 2 && is_safe_ground(state_id) {
+            return Some(y + 1);
+        }
+        if !ceiling {
+            // The surface is the first block from the top, so there is no other place to try
+            return None;
+        }
+        free = 0;
+    }
+    None
+}
+
+/// Picks a random column within the radius around the center of the world border
+async fn random_column(world: &World) -> Vector2<i32> {
+    let (center_x, center_z, border_radius) = {
+        let border = world.worldborder.lock().await;
+        (border.center_x, border.center_z, border.diameter() / 2.0)
+    };
+    // Stay a block away from the border, so players do not end up in it
+    let radius = f64::from(ADVANCED_CONFIG.random_teleport.radius)
+        .min(border_radius - 1.0)
+        .max(0.0);
+
+    let mut rng = rand::thread_rng();
+    Vector2::new(
+        (center_x + rng.gen_range(-radius..=radius)).floor() as i32,
+        (center_z + rng.gen_range(-radius..=radius)).floor() as i32,
+    )
+}
+
+/// Looks for a safe place to stand on in random columns, loading their chunks
+async fn find_destination(world: &World) -> Option<Vector3<f64>> {
+    let ceiling = matches!(world.dimension_type, DimensionType::TheNether);
+    for _ in 0..MAX_ATTEMPTS {
+        let column = random_column(world).await;
+        let chunk_pos = Vector2::new(column.x >> 4, column.z >> 4);
+        let chunk = world.receive_chunk(chunk_pos).await.0;
+        let y = find_standing_y(
+            &*chunk.read().await,
+            (column.x & 15) as u8,
+            (column.z & 15) as u8,
+            ceiling,
+        );
+        if let Some(y) = y {
+            return Some(Vector3::new(
+                f64::from(column.x) + 0.5,
+                f64::from(y),
+                f64::from(column.z) + 0.5,
+            ));
+        }
+    }
+    None
+}
+
+struct RtpExecutor;
+
+#[async_trait]
+impl CommandExecutor for RtpExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let uuid = player.gameprofile.id;
+
+        let cooldown = ADVANCED_CONFIG.random_teleport.cooldown;
+        if cooldown > 0 {
+            let remaining = RANDOM_TELEPORT_USAGE_LIST
+                .read()
+                .await
+                .remaining_cooldown(&uuid, cooldown);
+            if let Some(remaining) = remaining {
+                sender
+                    .send_message(
+                        TextComponent::text(format!(
+                            "You can teleport randomly again in {}",
+                            format_duration(remaining)
+                        ))
+                        .color_named(NamedColor::Red),
+                    )
+                    .await;
+                return Ok(());
+            }
+        }
+
+        if !server.teleport_warmups.wait(&player).await {
+            return Ok(());
+        }
+        let world = player.world().await;
+        let Some(destination) = find_destination(&world).await else {
+            return Err(GeneralCommandIssue(
+                "Could not find a safe place to teleport to, please try again".into(),
+            ));
+        };
+        let entity = &player.living_entity.entity;
+        let (yaw, pitch) = (entity.yaw.load(), entity.pitch.load());
+        if !player
+            .teleport(world, destination, yaw, pitch, TeleportCause::Command)
+            .await
+        {
+            return Ok(());
+        }
+
+        if cooldown > 0 {
+            let mut usages = RANDOM_TELEPORT_USAGE_LIST.write().await;
+            usages.set_used(uuid);
+            usages.save();
+        }
+        sender
+            .send_message(TextComponent::text(format!(
+                "Teleported to {}, {}, {}",
+                destination.x.floor(),
+                destination.y,
+                destination.z.floor()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(RtpExecutor))
+}
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::tree::CommandTree;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+const NAMES: [&str; 1] = ["save-all"];
+
+const DESCRIPTION: &str = "Saves all changed chunks and the world data to disk.";
+
+struct SaveAllExecutor;
+
+#[async_trait]
+impl CommandExecutor for SaveAllExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        sender
+            .send_message(TextComponent::translate("commands.save.saving", []))
+            .await;
+        server.save().await;
+        sender
+            .send_message(TextComponent::translate("commands.save.success", []))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(SaveAllExecutor)
+}
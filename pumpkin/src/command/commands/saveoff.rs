@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::tree::CommandTree;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+const NAMES: [&str; 1] = ["save-off"];
+
+const DESCRIPTION: &str = "Turns off saving the worlds to disk, for example while backing them up.";
+
+struct SaveOffExecutor;
+
+#[async_trait]
+impl CommandExecutor for SaveOffExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let key = if server.set_saving_paused(true).await {
+            "commands.save.disabled"
+        } else {
+            "commands.save.alreadyOff"
+        };
+        sender.send_message(TextComponent::translate(key, [])).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(SaveOffExecutor)
+}
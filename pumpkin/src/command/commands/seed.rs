@@ -1,5 +1,8 @@
 use crate::command::{
-    args::ConsumedArgs, tree::CommandTree, CommandError, CommandExecutor, CommandSender,
+    args::{world::WorldArgumentConsumer, ConsumedArgs},
+    tree::builder::argument,
+    tree::CommandTree,
+    CommandError, CommandExecutor, CommandSender,
 };
 use async_trait::async_trait;
 use pumpkin_util::text::click::ClickEvent;
@@ -11,6 +14,8 @@ const NAMES: [&str; 1] = ["seed"];
 
 const DESCRIPTION: &str = "Displays the world seed.";
 
+const ARG_WORLD: &str = "world";
+
 struct PumpkinExecutor;
 
 #[async_trait]
@@ -19,22 +24,11 @@ impl CommandExecutor for PumpkinExecutor {
         &self,
         sender: &mut CommandSender<'a>,
         server: &crate::server::Server,
-        _args: &ConsumedArgs<'a>,
+        args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let seed = match sender {
-            CommandSender::Player(player) => {
-                player.living_entity.entity.world.read().await.level.seed.0
-            }
-            // TODO: Maybe ask player for world, or get the current world
-            _ => match server.worlds.read().await.first() {
-                Some(world) => world.level.seed.0,
-                None => {
-                    return Err(CommandError::GeneralCommandIssue(
-                        "Unable to get Seed".to_string(),
-                    ))
-                }
-            },
-        };
+        let world =
+            WorldArgumentConsumer::find_arg_or_current(args, ARG_WORLD, sender, server).await?;
+        let seed = world.level.seed.0;
         let seed = (seed as i64).to_string();
 
         sender
@@ -54,5 +48,7 @@ impl CommandExecutor for PumpkinExecutor {
 }
 
 pub fn init_command_tree() -> CommandTree {
-    CommandTree::new(NAMES, DESCRIPTION).execute(PumpkinExecutor)
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(PumpkinExecutor)
+        .then(argument(ARG_WORLD, WorldArgumentConsumer).execute(PumpkinExecutor))
 }
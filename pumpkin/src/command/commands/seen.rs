@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use chrono::Local;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{simple::SimpleArgConsumer, Arg, ConsumedArgs},
+        tree::builder::argument,
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{duration_format::format_duration, last_seen_data::LAST_SEEN_LIST},
+    server::Server,
+};
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 1] = ["seen"];
+const DESCRIPTION: &str = "Shows when a player was last online.";
+
+const ARG_PLAYER: &str = "player";
+
+struct SeenExecutor;
+
+#[async_trait]
+impl CommandExecutor for SeenExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Simple(name)) = args.get(ARG_PLAYER) else {
+            return Err(InvalidConsumption(Some(ARG_PLAYER.into())));
+        };
+
+        let message = if let Some(player) = server.get_player_by_name(name).await {
+            format!("{} is currently online", player.gameprofile.name)
+        } else {
+            match LAST_SEEN_LIST.read().await.get_entry(name) {
+                Some(entry) => format!(
+                    "{} was last seen {} ago",
+                    entry.name,
+                    format_duration(Local::now().signed_duration_since(entry.last_seen))
+                ),
+                None => format!("{name} has never joined this server"),
+            }
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_PLAYER, SimpleArgConsumer).execute(SeenExecutor))
+}
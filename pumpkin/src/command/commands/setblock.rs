@@ -1,12 +1,15 @@
 use async_trait::async_trait;
+use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::text::TextComponent;
 
 use crate::command::args::block::BlockArgumentConsumer;
 use crate::command::args::position_block::BlockPosArgumentConsumer;
 use crate::command::args::{ConsumedArgs, FindArg};
-use crate::command::tree::builder::{argument, literal};
+use crate::command::tree::builder::{argument, literal, NonLeafNodeBuilder};
 use crate::command::tree::CommandTree;
 use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+use crate::world::World;
 
 const NAMES: [&str; 1] = ["setblock"];
 
@@ -14,57 +17,63 @@ const DESCRIPTION: &str = "Place a block.";
 
 const ARG_BLOCK: &str = "block";
 const ARG_BLOCK_POS: &str = "pos";
+const NO_UPDATE: &str = "noupdate";
 
 #[derive(Clone, Copy)]
 enum Mode {
-    /// with particles + item drops
+    /// Breaks the old block first, with particles and item drops
     Destroy,
 
-    /// only replaces air
+    /// Only places the block where there is air
     Keep,
 
-    /// default; without particles
+    /// The default, overwrites the old block without particles or drops
     Replace,
 }
 
-struct SetblockExecutor(Mode);
+/// Sets the block in the mode. With the `noupdate` flag, the neighbors of the block are not
+/// updated.
+struct SetblockExecutor(Mode, bool);
 
 #[async_trait]
 impl CommandExecutor for SetblockExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        server: &crate::server::Server,
+        server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let block = BlockArgumentConsumer::find_arg(args, ARG_BLOCK)?;
         let block_state_id = block.default_state_id;
         let pos = BlockPosArgumentConsumer::find_arg(args, ARG_BLOCK_POS)?;
-        let mode = self.0;
+        let (mode, suppress_updates) = (self.0, self.1);
         // TODO: allow console to use the command (seed sender.world)
         let world = sender
             .world()
             .await
             .ok_or(CommandError::InvalidRequirement)?;
 
+        let old_state = world
+            .get_block_state(&pos)
+            .await
+            .map_err(|e| CommandError::OtherPumpkin(e.into()))?;
         let success = match mode {
             Mode::Destroy => {
-                world.clone().break_block(server, &pos, None, false).await;
-                world.set_block_state(&pos, block_state_id).await;
-                true
+                if !old_state.air {
+                    if suppress_updates {
+                        world
+                            .break_block_without_update(server, &pos, None, true)
+                            .await;
+                    } else {
+                        world.clone().break_block(server, &pos, None, true).await;
+                    }
+                }
+                place(server, &world, &pos, block_state_id, suppress_updates).await
             }
-            Mode::Replace => {
-                world.set_block_state(&pos, block_state_id).await;
-                true
+            Mode::Replace => place(server, &world, &pos, block_state_id, suppress_updates).await,
+            Mode::Keep => {
+                old_state.air && place(server, &world, &pos, block_state_id, suppress_updates).await
             }
-            Mode::Keep => match world.get_block_state(&pos).await {
-                Ok(old_state) if old_state.air => {
-                    world.set_block_state(&pos, block_state_id).await;
-                    true
-                }
-                Ok(_) => false,
-                Err(e) => return Err(CommandError::OtherPumpkin(e.into())),
-            },
         };
 
         sender
@@ -86,14 +95,45 @@ impl CommandExecutor for SetblockExecutor {
     }
 }
 
+/// Sets the block and updates its neighbors, unless `suppress_updates`. Returns whether the block
+/// changed, like vanilla, which fails to set a block which is already there.
+async fn place(
+    server: &Server,
+    world: &World,
+    pos: &BlockPos,
+    block_state_id: u16,
+    suppress_updates: bool,
+) -> bool {
+    if world
+        .get_block_state_id(pos)
+        .await
+        .is_ok_and(|state_id| state_id == block_state_id)
+    {
+        return false;
+    }
+    world.set_block_state(pos, block_state_id).await;
+    if !suppress_updates {
+        world.update_neighbors(server, pos).await;
+    }
+    true
+}
+
+/// The literal of the mode, which may be followed by the `noupdate` flag
+fn mode_literal(name: &str, mode: Mode) -> NonLeafNodeBuilder {
+    literal(name)
+        .then(literal(NO_UPDATE).execute(SetblockExecutor(mode, true)))
+        .execute(SetblockExecutor(mode, false))
+}
+
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_BLOCK_POS, BlockPosArgumentConsumer).then(
             argument(ARG_BLOCK, BlockArgumentConsumer)
-                .then(literal("replace").execute(SetblockExecutor(Mode::Replace)))
-                .then(literal("destroy").execute(SetblockExecutor(Mode::Destroy)))
-                .then(literal("keep").execute(SetblockExecutor(Mode::Keep)))
-                .execute(SetblockExecutor(Mode::Replace)),
+                .then(mode_literal("replace", Mode::Replace))
+                .then(mode_literal("destroy", Mode::Destroy))
+                .then(mode_literal("keep", Mode::Keep))
+                .then(literal(NO_UPDATE).execute(SetblockExecutor(Mode::Replace, true)))
+                .execute(SetblockExecutor(Mode::Replace, false)),
         ),
     )
 }
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::item::Item;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::item::ItemStack;
+use uuid::Uuid;
+
+use crate::{
+    command::{
+        args::{
+            players::PlayersArgumentConsumer, simple::SimpleArgConsumer, Arg, ConsumedArgs, FindArg,
+        },
+        tree::builder::argument,
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::Player,
+    net::{
+        authentication::{self, AuthError},
+        GameProfile,
+    },
+    server::Server,
+};
+use CommandError::{GeneralCommandIssue, InvalidConsumption, InvalidRequirement};
+
+const NAMES: [&str; 2] = ["skull", "head"];
+const DESCRIPTION: &str = "Gives a player head with the skin of a player.";
+
+const ARG_PLAYER: &str = "player";
+const ARG_TARGETS: &str = "targets";
+
+/// Finds the profile of the player by name or UUID. Online players are used as they are, so
+/// this also works in offline mode. Other players are looked up at Mojang in online mode.
+async fn resolve_profile(server: &Server, name_or_uuid: &str) -> Result<GameProfile, String> {
+    let uuid = Uuid::parse_str(name_or_uuid).ok();
+    let online = match uuid {
+        Some(uuid) => server.get_player_by_uuid(uuid).await,
+        None => server.get_player_by_name(name_or_uuid).await,
+    };
+    if let Some(player) = online {
+        return Ok(player.gameprofile.clone());
+    }
+
+    let Some(auth_client) = &server.auth_client else {
+        // In offline mode only the name is known, the client looks up the skin for it
+        if uuid.is_some() {
+            return Err(format!(
+                "{name_or_uuid} is not online, offline players can only be given by name in offline mode"
+            ));
+        }
+        if name_or_uuid.len() > 16 {
+            return Err(format!("{name_or_uuid} is not a valid player name"));
+        }
+        return Ok(GameProfile {
+            id: Uuid::nil(),
+            name: name_or_uuid.to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        });
+    };
+    authentication::fetch_profile(name_or_uuid, auth_client)
+        .await
+        .map_err(|err| match err {
+            AuthError::UnknownProfile => format!("There is no player called {name_or_uuid}"),
+            err => format!("Could not look up {name_or_uuid}: {err}"),
+        })
+}
+
+/// A player head with the profile in the format of the `minecraft:profile` component
+fn player_head(profile: &GameProfile) -> ItemStack {
+    let mut component = NbtCompound::new();
+    component.put("name", profile.name.clone());
+    if !profile.id.is_nil() {
+        let uuid = profile.id.as_u128();
+        component.put(
+            "id",
+            NbtTag::IntArray(
+                vec![
+                    (uuid >> 96) as i32,
+                    (uuid >> 64) as i32,
+                    (uuid >> 32) as i32,
+                    uuid as i32,
+                ]
+                .into_boxed_slice(),
+            ),
+        );
+    }
+    let properties = profile
+        .properties
+        .iter()
+        .map(|property| {
+            let mut nbt = NbtCompound::new();
+            nbt.put("name", property.name.clone());
+            nbt.put("value", property.value.clone());
+            if let Some(signature) = &property.signature {
+                nbt.put("signature", signature.clone());
+            }
+            NbtTag::Compound(nbt)
+        })
+        .collect::<Vec<_>>();
+    component.put("properties", NbtTag::List(properties.into_boxed_slice()));
+
+    let mut head = ItemStack::new(1, Item::PLAYER_HEAD);
+    head.set_component("profile", component);
+    head
+}
+
+struct SkullExecutor;
+
+#[async_trait]
+impl CommandExecutor for SkullExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Simple(name)) = args.get(ARG_PLAYER) else {
+            return Err(InvalidConsumption(Some(ARG_PLAYER.into())));
+        };
+        let own;
+        let targets: &[Arc<Player>] = if args.contains_key(ARG_TARGETS) {
+            PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?
+        } else {
+            own = [sender.as_player().ok_or(InvalidRequirement)?];
+            &own
+        };
+
+        let profile = resolve_profile(server, name)
+            .await
+            .map_err(GeneralCommandIssue)?;
+        let head = player_head(&profile);
+        for target in targets {
+            target.give_stack(server, head.clone()).await;
+        }
+
+        let message = match targets {
+            [target] => format!(
+                "Gave the head of {} to {}",
+                profile.name, target.gameprofile.name
+            ),
+            _ => format!(
+                "Gave the head of {} to {} players",
+                profile.name,
+                targets.len()
+            ),
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_PLAYER, SimpleArgConsumer)
+            .execute(SkullExecutor)
+            .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(SkullExecutor)),
+    )
+}
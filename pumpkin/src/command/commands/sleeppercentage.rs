@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use pumpkin_config::AdvancedConfiguration;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::{
+    command::{
+        args::{bounded_num::BoundedNumArgumentConsumer, ConsumedArgs, FindArg},
+        tree::builder::argument,
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["sleeppercentage"];
+
+const DESCRIPTION: &str =
+    "Shows or changes how many percent of the players have to sleep to skip the night.";
+
+const ARG_PERCENTAGE: &str = "percentage";
+
+fn percentage_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name(ARG_PERCENTAGE)
+        .min(0)
+}
+
+fn describe(percentage: u32) -> String {
+    match percentage {
+        0 => "The night is skipped when any player sleeps".to_string(),
+        101.. => "The night can't be skipped by sleeping".to_string(),
+        percentage => format!("{percentage}% of the players have to sleep to skip the night"),
+    }
+}
+
+struct ShowExecutor;
+
+#[async_trait]
+impl CommandExecutor for ShowExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let message = describe(server.players_sleeping_percentage());
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct SetExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Ok(percentage) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_PERCENTAGE)?
+        else {
+            sender
+                .send_message(
+                    TextComponent::text("The percentage can't be negative")
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        };
+        // Bounded to be non-negative by the argument consumer
+        let percentage = percentage as u32;
+
+        server.set_players_sleeping_percentage(percentage);
+        if let Err(err) = AdvancedConfiguration::save_players_sleeping_percentage(percentage) {
+            log::error!(
+                "Failed to save the players sleeping percentage to the configuration: {err}"
+            );
+            sender
+                .send_message(
+                    TextComponent::text(
+                        "The percentage was changed, but could not be saved and will be reset on restart",
+                    )
+                    .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        }
+
+        sender
+            .send_message(TextComponent::text(describe(percentage)))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_PERCENTAGE, percentage_consumer()).execute(SetExecutor))
+        .execute(ShowExecutor)
+}
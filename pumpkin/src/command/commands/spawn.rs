@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::{
+    math::vector2::Vector2,
+    text::{color::NamedColor, TextComponent},
+    PermissionLvl,
+};
+
+use crate::{
+    command::{
+        args::{players::PlayersArgumentConsumer, ConsumedArgs, FindArg},
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::Player,
+    plugin::api::events::player::player_teleport::TeleportCause,
+    server::Server,
+};
+use CommandError::InvalidRequirement;
+
+const NAMES: [&str; 1] = ["spawn"];
+const DESCRIPTION: &str = "Teleports a player to the spawn of their world.";
+
+const ARG_TARGETS: &str = "targets";
+
+/// Teleports the player to the spawn of the world they would respawn in, loading the chunk of the
+/// spawn first. Returns whether the player was teleported.
+async fn teleport_to_spawn(server: &Server, player: &Arc<Player>) -> bool {
+    let Some(world) = server.get_respawn_world(&player.world().await).await else {
+        return false;
+    };
+    let position = world.get_spawn_position().await;
+    let chunk_pos = Vector2::new(
+        (position.x.floor() as i32) >> 4,
+        (position.z.floor() as i32) >> 4,
+    );
+    world.receive_chunk(chunk_pos).await;
+
+    let yaw = world.level.level_info.spawn_angle;
+    let pitch = player.living_entity.entity.pitch.load();
+    player
+        .teleport(world, position, yaw, pitch, TeleportCause::Command)
+        .await
+}
+
+struct SpawnSelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for SpawnSelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        if !server.teleport_warmups.wait(&player).await {
+            return Ok(());
+        }
+        if teleport_to_spawn(server, &player).await {
+            sender
+                .send_message(TextComponent::text("Teleported to spawn"))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct SpawnTargetsExecutor;
+
+#[async_trait]
+impl CommandExecutor for SpawnTargetsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        let mut teleported = Vec::new();
+        for target in targets {
+            if teleport_to_spawn(server, target).await {
+                teleported.push(target);
+            }
+        }
+
+        let message = match teleported.as_slice() {
+            [] => {
+                sender
+                    .send_message(
+                        TextComponent::text("No player was teleported to spawn")
+                            .color_named(NamedColor::Red),
+                    )
+                    .await;
+                return Ok(());
+            }
+            [target] => format!("Teleported {} to spawn", target.gameprofile.name),
+            targets => format!("Teleported {} players to spawn", targets.len()),
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(SpawnSelfExecutor))
+        .then(
+            require(|sender| sender.has_permission_lvl(PermissionLvl::Two))
+                .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(SpawnTargetsExecutor)),
+        )
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use pumpkin_config::BasicConfiguration;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::{
+    command::{
+        args::{bounded_num::BoundedNumArgumentConsumer, ConsumedArgs, FindArg},
+        tree::builder::argument,
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["spawnprotection"];
+
+const DESCRIPTION: &str =
+    "Shows or changes the radius around spawn in which only operators can build.";
+
+const ARG_RADIUS: &str = "radius";
+
+fn radius_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_RADIUS).min(0)
+}
+
+struct ShowExecutor;
+
+#[async_trait]
+impl CommandExecutor for ShowExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let message = match server.spawn_protection() {
+            0 => "Spawn protection is disabled".to_string(),
+            radius => format!("The spawn protection radius is {radius} blocks"),
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct SetExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Ok(radius) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_RADIUS)? else {
+            sender
+                .send_message(
+                    TextComponent::text("The spawn protection radius can't be negative")
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        };
+        // Bounded to be non-negative by the argument consumer
+        let radius = radius as u32;
+
+        server.set_spawn_protection(radius);
+        if let Err(err) = BasicConfiguration::save_spawn_protection(radius) {
+            log::error!("Failed to save the spawn protection radius to the configuration: {err}");
+            sender
+                .send_message(
+                    TextComponent::text(
+                        "Spawn protection was changed, but could not be saved and will be reset on restart",
+                    )
+                    .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        }
+
+        let message = if radius == 0 {
+            "Disabled spawn protection".to_string()
+        } else {
+            format!("Set the spawn protection radius to {radius} blocks")
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_RADIUS, radius_consumer()).execute(SetExecutor))
+        .execute(ShowExecutor)
+}
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
 use pumpkin_util::text::TextComponent;
 
 use crate::{
@@ -12,6 +13,7 @@ use crate::{
         CommandError, CommandExecutor, CommandSender,
     },
     entity::mob,
+    world::lightning,
 };
 const NAMES: [&str; 1] = ["summon"];
 
@@ -37,8 +39,13 @@ impl CommandExecutor for SummonExecutor {
         // TODO: Make this work in console
         if let Some(player) = sender.as_player() {
             let pos = pos.unwrap_or(player.living_entity.entity.pos.load());
-            let mob = mob::from_type(entity, server, pos, &player.world().await).await;
-            player.world().await.spawn_entity(mob).await;
+            let world = player.world().await;
+            if entity == EntityType::LIGHTNING_BOLT {
+                lightning::strike(&world, server, pos).await;
+            } else {
+                let mob = mob::from_type(entity, server, pos, &world).await;
+                world.spawn_entity(mob).await;
+            }
             sender
                 .send_message(TextComponent::translate(
                     "commands.summon.success",
@@ -1,7 +1,11 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use pumpkin_util::math::vector2::Vector2;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::text::TextComponent;
 
+use crate::command::args::dimension::DimensionArgumentConsumer;
 use crate::command::args::entities::EntitiesArgumentConsumer;
 use crate::command::args::entity::EntityArgumentConsumer;
 use crate::command::args::position_3d::Position3DArgumentConsumer;
@@ -12,6 +16,9 @@ use crate::command::tree::builder::{argument, literal};
 use crate::command::tree::CommandTree;
 use crate::command::CommandError;
 use crate::command::{CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::entity::Entity;
+use crate::plugin::api::events::player::player_teleport::TeleportCause;
 
 const NAMES: [&str; 2] = ["teleport", "tp"];
 const DESCRIPTION: &str = "Teleports entities, including players."; // todo
@@ -25,6 +32,9 @@ const ARG_DESTINATION: &str = "destination";
 /// multiple entities
 const ARG_TARGETS: &str = "targets";
 
+/// dimension of a loaded world
+const ARG_DIMENSION: &str = "dimension";
+
 /// rotation: yaw/pitch
 const ARG_ROTATION: &str = "rotation";
 
@@ -34,11 +44,22 @@ const ARG_FACING_ENTITY: &str = "facingEntity";
 /// position
 const ARG_FACING_LOCATION: &str = "facingLocation";
 
+/// Returns where the eyes of the entity would be if it stood at the given position
+fn eye_position(entity: &Entity, pos: Vector3<f64>) -> Vector3<f64> {
+    Vector3::new(pos.x, pos.y + f64::from(entity.standing_eye_height), pos.z)
+}
+
+/// Returns the yaw and pitch needed to look from one position towards another,
+/// or `None` if both positions are the same
 fn yaw_pitch_facing_position(
     looking_from: &Vector3<f64>,
     looking_towards: &Vector3<f64>,
-) -> (f32, f32) {
-    let direction_vector = (looking_towards.sub(looking_from)).normalize();
+) -> Option<(f32, f32)> {
+    let direction = looking_towards.sub(looking_from);
+    if direction.length_squared() == 0.0 {
+        return None;
+    }
+    let direction_vector = direction.normalize();
 
     let yaw_radians = -direction_vector.x.atan2(direction_vector.z);
     let pitch_radians = (-direction_vector.y).asin();
@@ -46,7 +67,60 @@ fn yaw_pitch_facing_position(
     let yaw_degrees = yaw_radians.to_degrees();
     let pitch_degrees = pitch_radians.to_degrees();
 
-    (yaw_degrees as f32, pitch_degrees as f32)
+    Some((yaw_degrees as f32, pitch_degrees as f32))
+}
+
+/// Teleports the player to a position in the world it is currently in
+async fn teleport_in_world(target: &Arc<Player>, pos: Vector3<f64>, yaw: f32, pitch: f32) {
+    target
+        .teleport(
+            target.world().await,
+            pos,
+            yaw,
+            pitch,
+            TeleportCause::Command,
+        )
+        .await;
+}
+
+/// Teleports the entities to the position, turning their eyes towards the facing position
+async fn teleport_facing(targets: &[Arc<Player>], pos: Vector3<f64>, facing_pos: Vector3<f64>) {
+    for target in targets {
+        let entity = &target.living_entity.entity;
+        let (yaw, pitch) = yaw_pitch_facing_position(&eye_position(entity, pos), &facing_pos)
+            .unwrap_or_else(|| (entity.yaw.load(), entity.pitch.load()));
+        teleport_in_world(target, pos, yaw, pitch).await;
+    }
+}
+
+struct TpEntitiesToDimensionPosExecutor;
+
+#[async_trait]
+impl CommandExecutor for TpEntitiesToDimensionPosExecutor {
+    async fn execute<'a>(
+        &self,
+        _sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let world = DimensionArgumentConsumer::find_arg(args, ARG_DIMENSION)?;
+        let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
+
+        // Make sure the destination exists before anyone is moved there
+        let chunk_pos = Vector2::new((pos.x.floor() as i32) >> 4, (pos.z.floor() as i32) >> 4);
+        world.receive_chunk(chunk_pos).await;
+
+        for target in targets {
+            let entity = &target.living_entity.entity;
+            let (yaw, pitch) = (entity.yaw.load(), entity.pitch.load());
+            target
+                .teleport(world.clone(), pos, yaw, pitch, TeleportCause::Command)
+                .await;
+        }
+
+        Ok(())
+    }
 }
 
 struct TpEntitiesToEntityExecutor;
@@ -63,11 +137,14 @@ impl CommandExecutor for TpEntitiesToEntityExecutor {
 
         let destination = EntityArgumentConsumer::find_arg(args, ARG_DESTINATION)?;
         let pos = destination.living_entity.entity.pos.load();
+        let world = destination.world().await;
 
         for target in targets {
             let yaw = target.living_entity.entity.yaw.load();
             let pitch = target.living_entity.entity.pitch.load();
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            target
+                .teleport(world.clone(), pos, yaw, pitch, TeleportCause::Command)
+                .await;
         }
 
         Ok(())
@@ -89,11 +166,8 @@ impl CommandExecutor for TpEntitiesToPosFacingPosExecutor {
         let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
 
         let facing_pos = Position3DArgumentConsumer::find_arg(args, ARG_FACING_LOCATION)?;
-        let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_pos);
 
-        for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
-        }
+        teleport_facing(targets, pos, facing_pos).await;
 
         Ok(())
     }
@@ -116,11 +190,9 @@ impl CommandExecutor for TpEntitiesToPosFacingEntityExecutor {
         let facing_entity = &EntityArgumentConsumer::find_arg(args, ARG_FACING_ENTITY)?
             .living_entity
             .entity;
-        let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_entity.pos.load());
+        let facing_pos = eye_position(facing_entity, facing_entity.pos.load());
 
-        for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
-        }
+        teleport_facing(targets, pos, facing_pos).await;
 
         Ok(())
     }
@@ -140,10 +212,12 @@ impl CommandExecutor for TpEntitiesToPosWithRotationExecutor {
 
         let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
 
-        let (yaw, pitch) = RotationArgumentConsumer::find_arg(args, ARG_ROTATION)?;
+        let rotation = RotationArgumentConsumer::find_arg(args, ARG_ROTATION)?;
 
         for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            let entity = &target.living_entity.entity;
+            let (yaw, pitch) = rotation.into_absolute(entity.yaw.load(), entity.pitch.load());
+            teleport_in_world(target, pos, yaw, pitch).await;
         }
 
         Ok(())
@@ -167,7 +241,7 @@ impl CommandExecutor for TpEntitiesToPosExecutor {
         for target in targets {
             let yaw = target.living_entity.entity.yaw.load();
             let pitch = target.living_entity.entity.pitch.load();
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            teleport_in_world(target, pos, yaw, pitch).await;
         }
 
         Ok(())
@@ -191,7 +265,15 @@ impl CommandExecutor for TpSelfToEntityExecutor {
             CommandSender::Player(player) => {
                 let yaw = player.living_entity.entity.yaw.load();
                 let pitch = player.living_entity.entity.pitch.load();
-                player.living_entity.entity.teleport(pos, yaw, pitch).await;
+                player
+                    .teleport(
+                        destination.world().await,
+                        pos,
+                        yaw,
+                        pitch,
+                        TeleportCause::Command,
+                    )
+                    .await;
             }
             _ => {
                 sender
@@ -219,7 +301,7 @@ impl CommandExecutor for TpSelfToPosExecutor {
                 let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
                 let yaw = player.living_entity.entity.yaw.load();
                 let pitch = player.living_entity.entity.pitch.load();
-                player.living_entity.entity.teleport(pos, yaw, pitch).await;
+                teleport_in_world(player, pos, yaw, pitch).await;
             }
             _ => {
                 sender
@@ -262,6 +344,12 @@ pub fn init_command_tree() -> CommandTree {
                 .then(
                     argument(ARG_DESTINATION, EntityArgumentConsumer)
                         .execute(TpEntitiesToEntityExecutor),
+                )
+                .then(
+                    argument(ARG_DIMENSION, DimensionArgumentConsumer).then(
+                        argument(ARG_LOCATION, Position3DArgumentConsumer)
+                            .execute(TpEntitiesToDimensionPosExecutor),
+                    ),
                 ),
         )
 }
@@ -2,8 +2,8 @@ use async_trait::async_trait;
 use pumpkin_util::text::color::{Color, NamedColor};
 use pumpkin_util::text::TextComponent;
 
-use crate::command::args::{time::TimeArgumentConsumer, FindArg};
-use crate::command::tree::builder::{argument, literal};
+use crate::command::args::{time::TimeArgumentConsumer, world::WorldArgumentConsumer, FindArg};
+use crate::command::tree::builder::{argument, literal, NonLeafNodeBuilder};
 use crate::command::{
     tree::CommandTree, CommandError, CommandExecutor, CommandSender, ConsumedArgs,
 };
@@ -11,22 +11,49 @@ use crate::command::{
 const NAMES: [&str; 1] = ["time"];
 const DESCRIPTION: &str = "Query the world time.";
 const ARG_TIME: &str = "time";
+const ARG_WORLD: &str = "world";
 
+/// The times of day `/time set` has names for, which also have their own commands like `/day`
 #[derive(Clone, Copy)]
-enum PresetTime {
+pub enum PresetTime {
     Day,
     Noon,
+    Sunset,
     Night,
     Midnight,
+    Sunrise,
 }
 
 impl PresetTime {
+    pub const ALL: [Self; 6] = [
+        Self::Day,
+        Self::Noon,
+        Self::Sunset,
+        Self::Night,
+        Self::Midnight,
+        Self::Sunrise,
+    ];
+
     fn to_ticks(self) -> i32 {
         match self {
             Self::Day => 1000,
             Self::Noon => 6000,
+            Self::Sunset => 12000,
             Self::Night => 13000,
             Self::Midnight => 18000,
+            Self::Sunrise => 23000,
+        }
+    }
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Noon => "noon",
+            Self::Sunset => "sunset",
+            Self::Night => "night",
+            Self::Midnight => "midnight",
+            Self::Sunrise => "sunrise",
         }
     }
 }
@@ -44,6 +71,7 @@ enum QueryMode {
     Day,
 }
 
+#[derive(Clone, Copy)]
 struct TimeQueryExecutor(QueryMode);
 
 #[async_trait]
@@ -52,14 +80,11 @@ impl CommandExecutor for TimeQueryExecutor {
         &self,
         sender: &mut CommandSender<'a>,
         server: &crate::server::Server,
-        _args: &ConsumedArgs<'a>,
+        args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let mode = self.0;
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world =
+            WorldArgumentConsumer::find_arg_or_current(args, ARG_WORLD, sender, server).await?;
         let level_time = world.level_time.lock().await;
 
         let msg = match mode {
@@ -91,6 +116,7 @@ impl CommandExecutor for TimeQueryExecutor {
     }
 }
 
+#[derive(Clone, Copy)]
 struct TimeChangeExecutor(Mode);
 
 #[async_trait]
@@ -116,18 +142,15 @@ impl CommandExecutor for TimeChangeExecutor {
         };
 
         let mode = self.0;
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world =
+            WorldArgumentConsumer::find_arg_or_current(args, ARG_WORLD, sender, server).await?;
         let mut level_time = world.level_time.lock().await;
 
         let msg = match mode {
             Mode::Add => {
                 // add
                 level_time.add_time(time_count.into());
-                level_time.send_time(world).await;
+                level_time.send_time(&world).await;
                 let curr_time = level_time.query_daytime();
                 TextComponent::translate(
                     "commands.time.add",
@@ -137,7 +160,7 @@ impl CommandExecutor for TimeChangeExecutor {
             Mode::Set(_) => {
                 // set
                 level_time.set_time(time_count.into());
-                level_time.send_time(world).await;
+                level_time.send_time(&world).await;
                 TextComponent::translate(
                     "commands.time.set",
                     [TextComponent::text(time_count.to_string())],
@@ -150,36 +173,56 @@ impl CommandExecutor for TimeChangeExecutor {
     }
 }
 
+/// Executes at the node, in the world of the sender or in the world given after it
+fn in_world<E>(node: NonLeafNodeBuilder, executor: E) -> NonLeafNodeBuilder
+where
+    E: CommandExecutor + Copy + Send + 'static,
+{
+    node.execute(executor)
+        .then(argument(ARG_WORLD, WorldArgumentConsumer).execute(executor))
+}
+
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
-        .then(
-            literal("add").then(
-                argument(ARG_TIME, TimeArgumentConsumer).execute(TimeChangeExecutor(Mode::Add)),
-            ),
-        )
+        .then(literal("add").then(in_world(
+            argument(ARG_TIME, TimeArgumentConsumer),
+            TimeChangeExecutor(Mode::Add),
+        )))
         .then(
             literal("query")
-                .then(literal("daytime").execute(TimeQueryExecutor(QueryMode::DayTime)))
-                .then(literal("gametime").execute(TimeQueryExecutor(QueryMode::GameTime)))
-                .then(literal("day").execute(TimeQueryExecutor(QueryMode::Day))),
+                .then(in_world(
+                    literal("daytime"),
+                    TimeQueryExecutor(QueryMode::DayTime),
+                ))
+                .then(in_world(
+                    literal("gametime"),
+                    TimeQueryExecutor(QueryMode::GameTime),
+                ))
+                .then(in_world(literal("day"), TimeQueryExecutor(QueryMode::Day))),
         )
         .then(
-            literal("set")
-                .then(literal("day").execute(TimeChangeExecutor(Mode::Set(Some(PresetTime::Day)))))
-                .then(
-                    literal("noon").execute(TimeChangeExecutor(Mode::Set(Some(PresetTime::Noon)))),
-                )
-                .then(
-                    literal("night")
-                        .execute(TimeChangeExecutor(Mode::Set(Some(PresetTime::Night)))),
-                )
-                .then(
-                    literal("midnight")
-                        .execute(TimeChangeExecutor(Mode::Set(Some(PresetTime::Midnight)))),
-                )
-                .then(
-                    argument(ARG_TIME, TimeArgumentConsumer)
-                        .execute(TimeChangeExecutor(Mode::Set(None))),
-                ),
+            PresetTime::ALL
+                .into_iter()
+                .fold(literal("set"), |node, preset| {
+                    node.then(in_world(
+                        literal(preset.name()),
+                        TimeChangeExecutor(Mode::Set(Some(preset))),
+                    ))
+                })
+                .then(in_world(
+                    argument(ARG_TIME, TimeArgumentConsumer),
+                    TimeChangeExecutor(Mode::Set(None)),
+                )),
         )
 }
+
+/// A shorthand like `/day` for `/time set day`
+pub fn init_preset_command_tree(preset: PresetTime) -> CommandTree {
+    let executor = TimeChangeExecutor(Mode::Set(Some(preset)));
+    CommandTree::new(
+        [preset.name()],
+        format!("Sets the world time to {}.", preset.name()),
+    )
+    .execute(executor)
+    .then(argument(ARG_WORLD, WorldArgumentConsumer).execute(executor))
+}
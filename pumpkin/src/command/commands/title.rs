@@ -1,12 +1,14 @@
 use async_trait::async_trait;
-use pumpkin_protocol::client::play::CClearTtitle;
+use pumpkin_protocol::client::play::{CClearTtitle, CTitleAnimation};
 use pumpkin_util::text::TextComponent;
 
 use crate::{
     command::{
         args::{
-            players::PlayersArgumentConsumer, textcomponent::TextComponentArgConsumer, Arg,
-            ConsumedArgs, FindArg,
+            duration::{to_ticks, DurationArgumentConsumer},
+            players::PlayersArgumentConsumer,
+            textcomponent::TextComponentArgConsumer,
+            Arg, ConsumedArgs, FindArg,
         },
         tree::builder::{argument, literal},
         tree::CommandTree,
@@ -22,6 +24,10 @@ const ARG_TARGETS: &str = "targets";
 
 const ARG_TITLE: &str = "title";
 
+const ARG_FADE_IN: &str = "fadeIn";
+const ARG_STAY: &str = "stay";
+const ARG_FADE_OUT: &str = "fadeOut";
+
 /// bool: Whether to reset or not
 struct ClearOrResetExecutor(bool);
 
@@ -107,6 +113,50 @@ impl CommandExecutor for TitleExecutor {
     }
 }
 
+struct TimesExecutor;
+
+#[async_trait]
+impl CommandExecutor for TimesExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let mut ticks = [0; 3];
+        for (ticks, name) in ticks.iter_mut().zip([ARG_FADE_IN, ARG_STAY, ARG_FADE_OUT]) {
+            let duration = DurationArgumentConsumer::find_arg(args, name)?;
+            *ticks = i32::try_from(to_ticks(duration)).map_err(|_| {
+                CommandError::GeneralCommandIssue("The duration is too long".to_string())
+            })?;
+        }
+        let [fade_in, stay, fade_out] = ticks;
+
+        for target in targets {
+            target
+                .client
+                .send_packet(&CTitleAnimation::new(fade_in, stay, fade_out))
+                .await;
+        }
+        sender
+            .send_message(if targets.len() == 1 {
+                TextComponent::translate(
+                    "commands.title.times.single",
+                    [TextComponent::text(targets[0].gameprofile.name.clone())],
+                )
+            } else {
+                TextComponent::translate(
+                    "commands.title.times.multiple",
+                    [TextComponent::text(targets.len().to_string())],
+                )
+            })
+            .await;
+
+        Ok(())
+    }
+}
+
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_TARGETS, PlayersArgumentConsumer)
@@ -129,7 +179,16 @@ pub fn init_command_tree() -> CommandTree {
                     argument(ARG_TITLE, TextComponentArgConsumer)
                         .execute(TitleExecutor(TitleMode::ActionBar)),
                 ),
+            )
+            .then(
+                literal("times").then(
+                    argument(ARG_FADE_IN, DurationArgumentConsumer::new().allow_zero()).then(
+                        argument(ARG_STAY, DurationArgumentConsumer::new().allow_zero()).then(
+                            argument(ARG_FADE_OUT, DurationArgumentConsumer::new().allow_zero())
+                                .execute(TimesExecutor),
+                        ),
+                    ),
+                ),
             ),
-        // TODO: times
     )
 }
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::builder::{argument, require};
+use crate::command::tree::CommandTree;
+use crate::command::{CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::plugin::api::events::player::player_teleport::TeleportCause;
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["top"];
+
+const DESCRIPTION: &str = "Teleports a player to the highest block at their position.";
+
+const ARG_TARGETS: &str = "targets";
+
+/// Teleports the player on top of the highest solid block in their current column
+async fn teleport_to_top(
+    sender: &CommandSender<'_>,
+    target: &Arc<Player>,
+) -> Result<(), CommandError> {
+    let entity = &target.living_entity.entity;
+    let pos = entity.pos.load();
+    let (x, z) = (pos.x.floor() as i32, pos.z.floor() as i32);
+
+    let world = entity.world.read().await.clone();
+    let Some(top) = world.get_top_solid_block(Vector2::new(x, z)).await else {
+        return Err(CommandError::GeneralCommandIssue(format!(
+            "There is no block below {}",
+            target.gameprofile.name
+        )));
+    };
+
+    let destination = Vector3::new(f64::from(x) + 0.5, f64::from(top + 1), f64::from(z) + 0.5);
+    let (yaw, pitch) = (entity.yaw.load(), entity.pitch.load());
+    if !target
+        .teleport(world, destination, yaw, pitch, TeleportCause::Command)
+        .await
+    {
+        return Ok(());
+    }
+
+    sender
+        .send_message(TextComponent::translate(
+            "commands.teleport.success.location.single",
+            [
+                TextComponent::text(target.gameprofile.name.clone()),
+                TextComponent::text(format!("{:.2}", destination.x)),
+                TextComponent::text(format!("{:.2}", destination.y)),
+                TextComponent::text(format!("{:.2}", destination.z)),
+            ],
+        ))
+        .await;
+
+    Ok(())
+}
+
+struct TopSelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for TopSelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::InvalidRequirement);
+        };
+        teleport_to_top(sender, &player).await
+    }
+}
+
+struct TopTargetsExecutor;
+
+#[async_trait]
+impl CommandExecutor for TopTargetsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        for target in targets {
+            teleport_to_top(sender, target).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(TopSelfExecutor))
+        .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(TopTargetsExecutor))
+}
@@ -0,0 +1,326 @@
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::text::{click::ClickEvent, color::NamedColor, TextComponent};
+
+use crate::{
+    command::{
+        args::{entity::EntityArgumentConsumer, ConsumedArgs, FindArg},
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    plugin::api::events::player::player_teleport::TeleportCause,
+    server::{
+        teleport_requests::{TeleportRequest, TeleportRequestKind},
+        Server,
+    },
+};
+use CommandError::InvalidRequirement;
+
+const TPA_NAMES: [&str; 1] = ["tpa"];
+const TPA_DESCRIPTION: &str = "Asks another player whether you may teleport to them.";
+
+const TPAHERE_NAMES: [&str; 1] = ["tpahere"];
+const TPAHERE_DESCRIPTION: &str = "Asks another player to teleport to you.";
+
+const TPACCEPT_NAMES: [&str; 1] = ["tpaccept"];
+const TPACCEPT_DESCRIPTION: &str =
+    "Accepts a teleport request, the newest one if no player is given.";
+
+const TPDENY_NAMES: [&str; 1] = ["tpdeny"];
+const TPDENY_DESCRIPTION: &str = "Denies a teleport request, the newest one if no player is given.";
+
+const TPCANCEL_NAMES: [&str; 1] = ["tpcancel"];
+const TPCANCEL_DESCRIPTION: &str = "Cancels your outgoing teleport request.";
+
+const ARG_PLAYER: &str = "player";
+
+async fn send_error(sender: &mut CommandSender<'_>, message: String) {
+    sender
+        .send_message(TextComponent::text(message).color_named(NamedColor::Red))
+        .await;
+}
+
+/// Clickable buttons for accepting or denying a request from the player
+fn answer_buttons(requester: &str) -> TextComponent {
+    TextComponent::text("")
+        .add_child(
+            TextComponent::text("[Accept]")
+                .color_named(NamedColor::Green)
+                .click_event(ClickEvent::RunCommand(
+                    format!("/tpaccept {requester}").into(),
+                )),
+        )
+        .add_child(TextComponent::text(" "))
+        .add_child(
+            TextComponent::text("[Deny]")
+                .color_named(NamedColor::Red)
+                .click_event(ClickEvent::RunCommand(
+                    format!("/tpdeny {requester}").into(),
+                )),
+        )
+}
+
+struct TpaExecutor(TeleportRequestKind);
+
+#[async_trait]
+impl CommandExecutor for TpaExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let target = EntityArgumentConsumer::find_arg(args, ARG_PLAYER)?;
+        let target_name = &target.gameprofile.name;
+
+        if target.gameprofile.id == player.gameprofile.id {
+            send_error(
+                sender,
+                "You can not send a teleport request to yourself".into(),
+            )
+            .await;
+            return Ok(());
+        }
+        if target.is_ignoring(&player).await {
+            send_error(sender, format!("{target_name} is ignoring you")).await;
+            return Ok(());
+        }
+
+        let replaced = server
+            .teleport_requests
+            .send(&player, &target, self.0)
+            .await;
+        if let Some(replaced) = replaced {
+            if let Some(previous) = server.get_player_by_uuid(replaced.target).await {
+                previous
+                    .send_system_message(&TextComponent::text(format!(
+                        "{} cancelled their teleport request",
+                        player.gameprofile.name
+                    )))
+                    .await;
+            }
+        }
+
+        let request = match self.0 {
+            TeleportRequestKind::ToTarget => "has requested to teleport to you",
+            TeleportRequestKind::ToRequester => "has requested that you teleport to them",
+        };
+        target
+            .send_system_message(
+                &TextComponent::text(format!("{} {request} ", player.gameprofile.name))
+                    .color_named(NamedColor::Gold)
+                    .add_child(answer_buttons(&player.gameprofile.name)),
+            )
+            .await;
+        sender
+            .send_message(TextComponent::text(format!(
+                "Sent a teleport request to {target_name}, which expires in {} seconds",
+                ADVANCED_CONFIG.teleport_requests.timeout
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+/// Takes the request to the sender from the given player, or the newest one
+async fn take_request(
+    sender: &mut CommandSender<'_>,
+    server: &Server,
+    args: &ConsumedArgs<'_>,
+) -> Result<Option<TeleportRequest>, CommandError> {
+    let player = sender.as_player().ok_or(InvalidRequirement)?;
+    let requester = EntityArgumentConsumer::find_arg(args, ARG_PLAYER).ok();
+
+    let request = server
+        .teleport_requests
+        .take(
+            player.gameprofile.id,
+            requester.as_ref().map(|requester| requester.gameprofile.id),
+        )
+        .await;
+    if request.is_none() {
+        let message = match requester {
+            Some(requester) => format!(
+                "You have no teleport request from {}",
+                requester.gameprofile.name
+            ),
+            None => "You have no teleport requests".to_string(),
+        };
+        send_error(sender, message).await;
+    }
+    Ok(request)
+}
+
+struct TpAcceptExecutor;
+
+#[async_trait]
+impl CommandExecutor for TpAcceptExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(request) = take_request(sender, server, args).await? else {
+            return Ok(());
+        };
+        let Some(requester) = server.get_player_by_uuid(request.requester).await else {
+            send_error(
+                sender,
+                format!("{} is no longer online", request.requester_name),
+            )
+            .await;
+            return Ok(());
+        };
+
+        let (traveller, destination) = request.traveller_and_destination();
+        let (Some(traveller), Some(destination)) = (
+            server.get_player_by_uuid(traveller).await,
+            server.get_player_by_uuid(destination).await,
+        ) else {
+            return Ok(());
+        };
+        let entity = &destination.living_entity.entity;
+        if !traveller
+            .teleport(
+                destination.world().await,
+                entity.pos.load(),
+                entity.yaw.load(),
+                entity.pitch.load(),
+                TeleportCause::Command,
+            )
+            .await
+        {
+            return Ok(());
+        }
+
+        sender
+            .send_message(TextComponent::text(format!(
+                "Accepted the teleport request from {}",
+                request.requester_name
+            )))
+            .await;
+        requester
+            .send_system_message(&TextComponent::text(format!(
+                "{} accepted your teleport request",
+                request.target_name
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct TpDenyExecutor;
+
+#[async_trait]
+impl CommandExecutor for TpDenyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(request) = take_request(sender, server, args).await? else {
+            return Ok(());
+        };
+
+        sender
+            .send_message(TextComponent::text(format!(
+                "Denied the teleport request from {}",
+                request.requester_name
+            )))
+            .await;
+        if let Some(requester) = server.get_player_by_uuid(request.requester).await {
+            requester
+                .send_system_message(
+                    &TextComponent::text(format!(
+                        "{} denied your teleport request",
+                        request.target_name
+                    ))
+                    .color_named(NamedColor::Red),
+                )
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct TpCancelExecutor;
+
+#[async_trait]
+impl CommandExecutor for TpCancelExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let Some(request) = server.teleport_requests.cancel(player.gameprofile.id).await else {
+            send_error(sender, "You have no outgoing teleport request".into()).await;
+            return Ok(());
+        };
+
+        sender
+            .send_message(TextComponent::text(format!(
+                "Cancelled your teleport request to {}",
+                request.target_name
+            )))
+            .await;
+        if let Some(target) = server.get_player_by_uuid(request.target).await {
+            target
+                .send_system_message(&TextComponent::text(format!(
+                    "{} cancelled their teleport request",
+                    request.requester_name
+                )))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(TPA_NAMES, TPA_DESCRIPTION).then(
+        require(|sender| sender.is_player()).then(
+            argument(ARG_PLAYER, EntityArgumentConsumer)
+                .execute(TpaExecutor(TeleportRequestKind::ToTarget)),
+        ),
+    )
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_tpahere_command_tree() -> CommandTree {
+    CommandTree::new(TPAHERE_NAMES, TPAHERE_DESCRIPTION).then(
+        require(|sender| sender.is_player()).then(
+            argument(ARG_PLAYER, EntityArgumentConsumer)
+                .execute(TpaExecutor(TeleportRequestKind::ToRequester)),
+        ),
+    )
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_tpaccept_command_tree() -> CommandTree {
+    CommandTree::new(TPACCEPT_NAMES, TPACCEPT_DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(TpAcceptExecutor)
+            .then(argument(ARG_PLAYER, EntityArgumentConsumer).execute(TpAcceptExecutor)),
+    )
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_tpdeny_command_tree() -> CommandTree {
+    CommandTree::new(TPDENY_NAMES, TPDENY_DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(TpDenyExecutor)
+            .then(argument(ARG_PLAYER, EntityArgumentConsumer).execute(TpDenyExecutor)),
+    )
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_tpcancel_command_tree() -> CommandTree {
+    CommandTree::new(TPCANCEL_NAMES, TPCANCEL_DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(TpCancelExecutor))
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use pumpkin_data::screen::WindowType;
+use pumpkin_inventory::{OpenContainer, Trash};
+
+use crate::{
+    command::{
+        args::ConsumedArgs, tree::builder::require, tree::CommandTree, CommandError,
+        CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+use CommandError::InvalidRequirement;
+
+const NAMES: [&str; 2] = ["trash", "disposal"];
+const DESCRIPTION: &str = "Opens a trash can, whose items are deleted once it is closed.";
+
+struct TrashExecutor;
+
+#[async_trait]
+impl CommandExecutor for TrashExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let container = OpenContainer::new_empty_container::<Trash>(player.entity_id(), None, None);
+        player
+            .open_virtual_container(server, container, WindowType::Generic9x4)
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(TrashExecutor))
+}
@@ -0,0 +1,88 @@
+use std::sync::{atomic::Ordering, Arc};
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{players::PlayersArgumentConsumer, ConsumedArgs, FindArg},
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    entity::player::Player,
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["vanish"];
+
+const DESCRIPTION: &str = "Hides players from all other players, or shows them again.";
+
+const ARG_TARGETS: &str = "targets";
+
+async fn toggle(sender: &mut CommandSender<'_>, targets: &[Arc<Player>]) {
+    for target in targets {
+        let vanished = !target.vanished.load(Ordering::Relaxed);
+        target.set_vanished(vanished).await;
+
+        let message = if vanished {
+            "You are now vanished"
+        } else {
+            "You are no longer vanished"
+        };
+        target
+            .send_system_message(&TextComponent::text(message))
+            .await;
+
+        let is_sender = sender
+            .as_player()
+            .is_some_and(|player| player.gameprofile.id == target.gameprofile.id);
+        if !is_sender {
+            let message = if vanished {
+                format!("Vanished {}", target.gameprofile.name)
+            } else {
+                format!("{} is no longer vanished", target.gameprofile.name)
+            };
+            sender.send_message(TextComponent::text(message)).await;
+        }
+    }
+}
+
+struct SelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for SelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        toggle(sender, &[player]).await;
+        Ok(())
+    }
+}
+
+struct TargetsExecutor;
+
+#[async_trait]
+impl CommandExecutor for TargetsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        toggle(sender, targets).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
+        .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(TargetsExecutor))
+}
@@ -0,0 +1,284 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::{
+    math::vector2::Vector2,
+    text::{color::NamedColor, TextComponent},
+    PermissionLvl,
+};
+
+use crate::{
+    command::{
+        args::{
+            players::PlayersArgumentConsumer, simple::SimpleArgConsumer,
+            warp::WarpArgumentConsumer, Arg, ConsumedArgs, FindArg,
+        },
+        tree::builder::{argument, require},
+        tree::CommandTree,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{
+        warp_data::{WarpEntry, WARP_LIST},
+        SaveJSONConfiguration,
+    },
+    entity::player::Player,
+    plugin::api::events::player::player_teleport::TeleportCause,
+    server::Server,
+};
+use CommandError::{InvalidConsumption, InvalidRequirement};
+
+const NAMES: [&str; 1] = ["warp"];
+const DESCRIPTION: &str = "Teleports to a warp.";
+
+const SETWARP_NAMES: [&str; 1] = ["setwarp"];
+const SETWARP_DESCRIPTION: &str = "Creates a warp at your position, or moves an existing one.";
+
+const DELWARP_NAMES: [&str; 1] = ["delwarp"];
+const DELWARP_DESCRIPTION: &str = "Deletes a warp.";
+
+const WARPS_NAMES: [&str; 1] = ["warps"];
+const WARPS_DESCRIPTION: &str = "Lists the warps.";
+
+const ARG_NAME: &str = "name";
+const ARG_TARGETS: &str = "targets";
+
+fn find_name<'a>(args: &'a ConsumedArgs) -> Result<&'a str, CommandError> {
+    match args.get(ARG_NAME) {
+        Some(Arg::Simple(name)) => Ok(name),
+        _ => Err(InvalidConsumption(Some(ARG_NAME.into()))),
+    }
+}
+
+async fn unknown_warp(sender: &mut CommandSender<'_>, name: &str) {
+    sender
+        .send_message(
+            TextComponent::text(format!("There is no warp called {name}"))
+                .color_named(NamedColor::Red),
+        )
+        .await;
+}
+
+/// Teleports the players to the warp, loading the chunk it is in first. Returns whether the warp
+/// exists and its world is loaded.
+async fn warp(
+    sender: &mut CommandSender<'_>,
+    server: &Server,
+    name: &str,
+    targets: &[Arc<Player>],
+) -> bool {
+    let Some(warp) = WARP_LIST.read().await.get(name).cloned() else {
+        unknown_warp(sender, name).await;
+        return false;
+    };
+    let Some(world) = server.get_world_by_name(&warp.world).await else {
+        sender
+            .send_message(
+                TextComponent::text(format!(
+                    "The world {} of the warp {} is not loaded",
+                    warp.world, warp.name
+                ))
+                .color_named(NamedColor::Red),
+            )
+            .await;
+        return false;
+    };
+
+    // Make sure the destination exists before anyone is moved there
+    let position = warp.position();
+    let chunk_pos = Vector2::new(
+        (position.x.floor() as i32) >> 4,
+        (position.z.floor() as i32) >> 4,
+    );
+    world.receive_chunk(chunk_pos).await;
+
+    for target in targets {
+        target
+            .teleport(
+                world.clone(),
+                position,
+                warp.yaw,
+                warp.pitch,
+                TeleportCause::Command,
+            )
+            .await;
+    }
+    true
+}
+
+struct WarpSelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for WarpSelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = find_name(args)?;
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+
+        // Tell about unknown warps right away instead of after the warmup
+        if WARP_LIST.read().await.get(name).is_none() {
+            unknown_warp(sender, name).await;
+            return Ok(());
+        }
+        if !server.teleport_warmups.wait(&player).await {
+            return Ok(());
+        }
+        if warp(sender, server, name, &[player]).await {
+            sender
+                .send_message(TextComponent::text(format!("Warped to {name}")))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+/// Teleports other players to a warp
+struct WarpOthersExecutor;
+
+#[async_trait]
+impl CommandExecutor for WarpOthersExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = find_name(args)?;
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        if warp(sender, server, name, targets).await {
+            let targets_text = if let [target] = targets {
+                target.gameprofile.name.clone()
+            } else {
+                format!("{} players", targets.len())
+            };
+            sender
+                .send_message(TextComponent::text(format!(
+                    "Warped {targets_text} to {name}"
+                )))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct SetWarpExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetWarpExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = find_name(args)?;
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let entity = &player.living_entity.entity;
+        let warp = WarpEntry::new(
+            name,
+            player.world().await.name(),
+            entity.pos.load(),
+            entity.yaw.load(),
+            entity.pitch.load(),
+        );
+
+        let mut warps = WARP_LIST.write().await;
+        let replaced = warps.set(warp);
+        warps.save();
+        drop(warps);
+
+        let message = if replaced {
+            format!("Moved the warp {name} to your position")
+        } else {
+            format!("Created the warp {name}")
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct DelWarpExecutor;
+
+#[async_trait]
+impl CommandExecutor for DelWarpExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = find_name(args)?;
+
+        let mut warps = WARP_LIST.write().await;
+        if !warps.remove(name) {
+            drop(warps);
+            unknown_warp(sender, name).await;
+            return Ok(());
+        }
+        warps.save();
+        drop(warps);
+
+        sender
+            .send_message(TextComponent::text(format!("Deleted the warp {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+struct WarpsExecutor;
+
+#[async_trait]
+impl CommandExecutor for WarpsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let warps = WARP_LIST.read().await;
+        let mut names: Vec<&str> = warps.warps.iter().map(|warp| warp.name.as_str()).collect();
+        names.sort_unstable();
+        let message = if names.is_empty() {
+            "There are no warps".to_string()
+        } else {
+            format!("Warps: {}", names.join(", "))
+        };
+        drop(warps);
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_NAME, WarpArgumentConsumer)
+            .then(require(|sender| sender.is_player()).execute(WarpSelfExecutor))
+            .then(
+                require(|sender| sender.has_permission_lvl(PermissionLvl::Two)).then(
+                    argument(ARG_TARGETS, PlayersArgumentConsumer).execute(WarpOthersExecutor),
+                ),
+            ),
+    )
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_setwarp_command_tree() -> CommandTree {
+    CommandTree::new(SETWARP_NAMES, SETWARP_DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .then(argument(ARG_NAME, SimpleArgConsumer).execute(SetWarpExecutor)),
+    )
+}
+
+pub fn init_delwarp_command_tree() -> CommandTree {
+    CommandTree::new(DELWARP_NAMES, DELWARP_DESCRIPTION)
+        .then(argument(ARG_NAME, WarpArgumentConsumer).execute(DelWarpExecutor))
+}
+
+pub fn init_warps_command_tree() -> CommandTree {
+    CommandTree::new(WARPS_NAMES, WARPS_DESCRIPTION).execute(WarpsExecutor)
+}
@@ -2,20 +2,24 @@ use async_trait::async_trait;
 use pumpkin_util::text::TextComponent;
 
 use crate::command::{
-    args::{time::TimeArgumentConsumer, ConsumedArgs, FindArg},
-    tree::builder::{argument, literal},
+    args::{time::TimeArgumentConsumer, world::WorldArgumentConsumer, ConsumedArgs, FindArg},
+    tree::builder::{argument, literal, NonLeafNodeBuilder},
     tree::CommandTree,
     CommandError, CommandExecutor, CommandSender,
 };
+use crate::world::weather::Weather;
 
 const NAMES: [&str; 1] = ["weather"];
 const DESCRIPTION: &str = "Changes the weather.";
 const ARG_DURATION: &str = "duration";
+const ARG_WORLD: &str = "world";
 
+#[derive(Clone, Copy)]
 struct WeatherExecutor {
     mode: WeatherMode,
 }
 
+#[derive(Clone, Copy)]
 enum WeatherMode {
     Clear,
     Rain,
@@ -27,20 +31,25 @@ impl CommandExecutor for WeatherExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
+        server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = sender
-            .world()
-            .await
-            .ok_or(CommandError::InvalidRequirement)?;
-        let duration = TimeArgumentConsumer::find_arg(args, ARG_DURATION).unwrap_or(6000);
+        let world =
+            WorldArgumentConsumer::find_arg_or_current(args, ARG_WORLD, sender, server).await?;
+        // Like in vanilla, the weather lasts a random time if no duration is given
+        let duration = TimeArgumentConsumer::find_arg(args, ARG_DURATION).ok();
         let mut weather = world.weather.lock().await;
 
         match self.mode {
             WeatherMode::Clear => {
                 weather
-                    .set_weather_parameters(&world, duration, 0, false, false)
+                    .set_weather_parameters(
+                        &world,
+                        duration.unwrap_or_else(Weather::random_clear_time),
+                        0,
+                        false,
+                        false,
+                    )
                     .await;
                 sender
                     .send_message(TextComponent::translate("commands.weather.set.clear", []))
@@ -48,7 +57,13 @@ impl CommandExecutor for WeatherExecutor {
             }
             WeatherMode::Rain => {
                 weather
-                    .set_weather_parameters(&world, 0, duration, true, false)
+                    .set_weather_parameters(
+                        &world,
+                        0,
+                        duration.unwrap_or_else(Weather::random_rain_time),
+                        true,
+                        false,
+                    )
                     .await;
                 sender
                     .send_message(TextComponent::translate("commands.weather.set.rain", []))
@@ -56,7 +71,13 @@ impl CommandExecutor for WeatherExecutor {
             }
             WeatherMode::Thunder => {
                 weather
-                    .set_weather_parameters(&world, 0, duration, true, true)
+                    .set_weather_parameters(
+                        &world,
+                        0,
+                        duration.unwrap_or_else(Weather::random_thunder_time),
+                        true,
+                        true,
+                    )
                     .await;
                 sender
                     .send_message(TextComponent::translate("commands.weather.set.thunder", []))
@@ -68,39 +89,47 @@ impl CommandExecutor for WeatherExecutor {
     }
 }
 
-pub fn init_command_tree() -> CommandTree {
-    CommandTree::new(NAMES, DESCRIPTION)
-        .then(
-            literal("clear")
-                .then(
-                    argument(ARG_DURATION, TimeArgumentConsumer).execute(WeatherExecutor {
-                        mode: WeatherMode::Clear,
-                    }),
-                )
-                .execute(WeatherExecutor {
-                    mode: WeatherMode::Clear,
-                }),
-        )
+/// `<weather> [duration] [world]`, where the world defaults to the world of the sender
+fn weather(name: &str, mode: WeatherMode) -> NonLeafNodeBuilder {
+    let executor = WeatherExecutor { mode };
+    let world = || argument(ARG_WORLD, WorldArgumentConsumer).execute(executor);
+    literal(name)
+        .execute(executor)
         .then(
-            literal("rain")
-                .then(
-                    argument(ARG_DURATION, TimeArgumentConsumer).execute(WeatherExecutor {
-                        mode: WeatherMode::Rain,
-                    }),
-                )
-                .execute(WeatherExecutor {
-                    mode: WeatherMode::Rain,
-                }),
+            argument(ARG_DURATION, TimeArgumentConsumer)
+                .execute(executor)
+                .then(world()),
         )
+        .then(world())
+}
+
+/// A shorthand for `/weather <weather> [duration] [world]`
+fn shorthand(name: &'static str, description: &str, mode: WeatherMode) -> CommandTree {
+    let executor = WeatherExecutor { mode };
+    let world = || argument(ARG_WORLD, WorldArgumentConsumer).execute(executor);
+    CommandTree::new([name], description)
+        .execute(executor)
         .then(
-            literal("thunder")
-                .then(
-                    argument(ARG_DURATION, TimeArgumentConsumer).execute(WeatherExecutor {
-                        mode: WeatherMode::Thunder,
-                    }),
-                )
-                .execute(WeatherExecutor {
-                    mode: WeatherMode::Thunder,
-                }),
+            argument(ARG_DURATION, TimeArgumentConsumer)
+                .execute(executor)
+                .then(world()),
         )
+        .then(world())
+}
+
+/// `/sun`, the same as `/weather clear`
+pub fn init_sun_command_tree() -> CommandTree {
+    shorthand("sun", "Clears the weather.", WeatherMode::Clear)
+}
+
+/// `/rain`, the same as `/weather rain`
+pub fn init_rain_command_tree() -> CommandTree {
+    shorthand("rain", "Makes it rain.", WeatherMode::Rain)
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(weather("clear", WeatherMode::Clear))
+        .then(weather("rain", WeatherMode::Rain))
+        .then(weather("thunder", WeatherMode::Thunder))
 }
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use pumpkin_data::screen::WindowType;
+use pumpkin_inventory::{CraftingTable, OpenContainer};
+
+use crate::{
+    command::{
+        args::ConsumedArgs, tree::builder::require, tree::CommandTree, CommandError,
+        CommandExecutor, CommandSender,
+    },
+    server::Server,
+};
+use CommandError::InvalidRequirement;
+
+const NAMES: [&str; 3] = ["workbench", "craft", "wb"];
+const DESCRIPTION: &str = "Opens a crafting table.";
+
+struct WorkbenchExecutor;
+
+#[async_trait]
+impl CommandExecutor for WorkbenchExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(InvalidRequirement)?;
+        let container =
+            OpenContainer::new_empty_container::<CraftingTable>(player.entity_id(), None, None);
+        player
+            .open_virtual_container(server, container, WindowType::Crafting)
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(WorkbenchExecutor))
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::world::WorldArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::builder::{argument, literal, require};
+use crate::command::tree::CommandTree;
+use crate::command::{CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::plugin::api::events::player::player_teleport::TeleportCause;
+use crate::server::Server;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["world"];
+
+const DESCRIPTION: &str = "Lists the loaded worlds and teleports players between them.";
+
+const ARG_WORLD: &str = "world";
+
+const ARG_TARGETS: &str = "targets";
+
+/// Moves the player to the spawn of the given world, unless they are already in it
+async fn teleport_to_world(sender: &CommandSender<'_>, target: &Arc<Player>, world: &Arc<World>) {
+    let current_world = target.world().await;
+    if Arc::ptr_eq(&current_world, world) {
+        sender
+            .send_message(TextComponent::text(format!(
+                "{} is already in world {}",
+                target.gameprofile.name,
+                world.name()
+            )))
+            .await;
+        return;
+    }
+
+    let position = world.get_spawn_position().await;
+    let yaw = world.level.level_info.spawn_angle;
+    if !target
+        .teleport(world.clone(), position, yaw, 10.0, TeleportCause::Command)
+        .await
+    {
+        return;
+    }
+
+    sender
+        .send_message(TextComponent::text(format!(
+            "Teleported {} to world {}",
+            target.gameprofile.name,
+            world.name()
+        )))
+        .await;
+}
+
+struct WorldListExecutor;
+
+#[async_trait]
+impl CommandExecutor for WorldListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let worlds = server.worlds.read().await.clone();
+
+        let mut message = TextComponent::text(if worlds.len() == 1 {
+            "There is 1 world loaded:".to_string()
+        } else {
+            format!("There are {} worlds loaded:", worlds.len())
+        });
+        for world in &worlds {
+            let player_count = world.players.read().await.len();
+            message = message.add_child(
+                TextComponent::text(format!("\n{}", world.name())).color_named(NamedColor::Green),
+            );
+            message = message.add_child(TextComponent::text(format!(
+                " ({}, {player_count} players)",
+                world.dimension_type.name()
+            )));
+        }
+
+        sender.send_message(message).await;
+
+        Ok(())
+    }
+}
+
+struct WorldTpSelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for WorldTpSelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::InvalidRequirement);
+        };
+        let world = WorldArgumentConsumer::find_arg(args, ARG_WORLD)?;
+
+        teleport_to_world(sender, &player, world).await;
+
+        Ok(())
+    }
+}
+
+struct WorldTpTargetsExecutor;
+
+#[async_trait]
+impl CommandExecutor for WorldTpTargetsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = WorldArgumentConsumer::find_arg(args, ARG_WORLD)?;
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        for target in targets {
+            teleport_to_world(sender, target, world).await;
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("list").execute(WorldListExecutor))
+        .then(
+            literal("tp").then(
+                argument(ARG_WORLD, WorldArgumentConsumer)
+                    .then(require(|sender| sender.is_player()).execute(WorldTpSelfExecutor))
+                    .then(
+                        argument(ARG_TARGETS, PlayersArgumentConsumer)
+                            .execute(WorldTpTargetsExecutor),
+                    ),
+            ),
+        )
+}
@@ -58,14 +58,10 @@ impl CommandExecutor for WorldborderGetExecutor {
         server: &Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = sender.current_world(server).await?;
         let border = world.worldborder.lock().await;
 
-        let diameter = border.new_diameter.round() as i32;
+        let diameter = border.diameter().round() as i32;
         sender
             .send_message(TextComponent::translate(
                 "commands.worldborder.get",
@@ -86,11 +82,7 @@ impl CommandExecutor for WorldborderSetExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = sender.current_world(server).await?;
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -106,7 +98,7 @@ impl CommandExecutor for WorldborderSetExecutor {
             return Ok(());
         };
 
-        if (distance - border.new_diameter).abs() < f64::EPSILON {
+        if (distance - border.diameter()).abs() < f64::EPSILON {
             sender
                 .send_message(
                     TextComponent::translate(NOTHING_CHANGED_EXCEPTION, [])
@@ -123,7 +115,7 @@ impl CommandExecutor for WorldborderSetExecutor {
                 [TextComponent::text(dist)],
             ))
             .await;
-        border.set_diameter(world, distance, None).await;
+        border.set_diameter(&world, distance, None).await;
         Ok(())
     }
 }
@@ -138,11 +130,7 @@ impl CommandExecutor for WorldborderSetTimeExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = sender.current_world(server).await?;
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -170,7 +158,7 @@ impl CommandExecutor for WorldborderSetTimeExecutor {
             return Ok(());
         };
 
-        match distance.total_cmp(&border.new_diameter) {
+        match distance.total_cmp(&border.diameter()) {
             std::cmp::Ordering::Equal => {
                 sender
                     .send_message(
@@ -207,7 +195,7 @@ impl CommandExecutor for WorldborderSetTimeExecutor {
         }
 
         border
-            .set_diameter(world, distance, Some(i64::from(time) * 1000))
+            .set_diameter(&world, distance, Some(i64::from(time) * 1000))
             .await;
         Ok(())
     }
@@ -223,11 +211,7 @@ impl CommandExecutor for WorldborderAddExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = sender.current_world(server).await?;
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -253,7 +237,7 @@ impl CommandExecutor for WorldborderAddExecutor {
             return Ok(());
         }
 
-        let distance = border.new_diameter + distance;
+        let distance = border.diameter() + distance;
 
         let dist = format!("{distance:.1}");
         sender
@@ -262,7 +246,7 @@ impl CommandExecutor for WorldborderAddExecutor {
                 [TextComponent::text(dist)],
             ))
             .await;
-        border.set_diameter(world, distance, None).await;
+        border.set_diameter(&world, distance, None).await;
         Ok(())
     }
 }
@@ -277,11 +261,7 @@ impl CommandExecutor for WorldborderAddTimeExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = sender.current_world(server).await?;
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -309,9 +289,10 @@ impl CommandExecutor for WorldborderAddTimeExecutor {
             return Ok(());
         };
 
-        let distance = distance + border.new_diameter;
+        let diameter = border.diameter();
+        let distance = distance + diameter;
 
-        match distance.total_cmp(&border.new_diameter) {
+        match distance.total_cmp(&diameter) {
             std::cmp::Ordering::Equal => {
                 sender
                     .send_message(
@@ -348,7 +329,7 @@ impl CommandExecutor for WorldborderAddTimeExecutor {
         }
 
         border
-            .set_diameter(world, distance, Some(i64::from(time) * 1000))
+            .set_diameter(&world, distance, Some(i64::from(time) * 1000))
             .await;
         Ok(())
     }
@@ -364,11 +345,7 @@ impl CommandExecutor for WorldborderCenterExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = sender.current_world(server).await?;
         let mut border = world.worldborder.lock().await;
 
         let Vector2 { x, z } = Position2DArgumentConsumer.find_arg_default_name(args)?;
@@ -382,7 +359,7 @@ impl CommandExecutor for WorldborderCenterExecutor {
                 ],
             ))
             .await;
-        border.set_center(world, x, z).await;
+        border.set_center(&world, x, z).await;
         Ok(())
     }
 }
@@ -397,11 +374,7 @@ impl CommandExecutor for WorldborderDamageAmountExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = sender.current_world(server).await?;
         let mut border = world.worldborder.lock().await;
 
         let Ok(damage_per_block) = damage_per_block_consumer().find_arg_default_name(args)? else {
@@ -449,11 +422,7 @@ impl CommandExecutor for WorldborderDamageBufferExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = sender.current_world(server).await?;
         let mut border = world.worldborder.lock().await;
 
         let Ok(buffer) = damage_buffer_consumer().find_arg_default_name(args)? else {
@@ -501,11 +470,7 @@ impl CommandExecutor for WorldborderWarningDistanceExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = sender.current_world(server).await?;
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = warning_distance_consumer().find_arg_default_name(args)? else {
@@ -537,7 +502,7 @@ impl CommandExecutor for WorldborderWarningDistanceExecutor {
                 [TextComponent::text(distance.to_string())],
             ))
             .await;
-        border.set_warning_distance(world, distance).await;
+        border.set_warning_distance(&world, distance).await;
         Ok(())
     }
 }
@@ -552,11 +517,7 @@ impl CommandExecutor for WorldborderWarningTimeExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = sender.current_world(server).await?;
         let mut border = world.worldborder.lock().await;
 
         let Ok(time) = time_consumer().find_arg_default_name(args)? else {
@@ -588,7 +549,7 @@ impl CommandExecutor for WorldborderWarningTimeExecutor {
                 [TextComponent::text(time.to_string())],
             ))
             .await;
-        border.set_warning_delay(world, time).await;
+        border.set_warning_delay(&world, time).await;
         Ok(())
     }
 }
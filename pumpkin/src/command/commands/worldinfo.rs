@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::command::{
+    args::{world::WorldArgumentConsumer, ConsumedArgs},
+    tree::builder::argument,
+    tree::CommandTree,
+    CommandError, CommandExecutor, CommandSender,
+};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["worldinfo"];
+
+const DESCRIPTION: &str = "Displays information about the current world.";
+
+const ARG_WORLD: &str = "world";
+
+struct WorldInfoExecutor;
+
+#[async_trait]
+impl CommandExecutor for WorldInfoExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world =
+            WorldArgumentConsumer::find_arg_or_current(args, ARG_WORLD, sender, server).await?;
+
+        let info = &world.level.level_info;
+        let (world_age, time_of_day) = {
+            let level_time = world.level_time.lock().await;
+            (level_time.world_age, level_time.time_of_day)
+        };
+        let weather = {
+            let weather = world.weather.lock().await;
+            if weather.thundering {
+                "thunder"
+            } else if weather.raining {
+                "rain"
+            } else {
+                "clear"
+            }
+        };
+
+        let entries = [
+            ("Name", world.name()),
+            ("Dimension", world.dimension_type.name().to_string()),
+            ("Seed", (world.level.seed.0 as i64).to_string()),
+            (
+                "Spawn",
+                format!("{} {} {}", info.spawn_x, info.spawn_y, info.spawn_z),
+            ),
+            ("Time", time_of_day.to_string()),
+            ("Day", (world_age / 24000).to_string()),
+            ("Weather", weather.to_string()),
+            (
+                "Loaded chunks",
+                world.level.loaded_chunk_count().to_string(),
+            ),
+            ("Players", world.players.read().await.len().to_string()),
+            ("Entities", world.entities.read().await.len().to_string()),
+        ];
+
+        // Only players get colors, so the console and RCON get plain `key: value` lines
+        let colored = sender.is_player();
+        let mut message = TextComponent::text("");
+        for (i, (key, value)) in entries.into_iter().enumerate() {
+            let key = if i == 0 {
+                format!("{key}: ")
+            } else {
+                format!("\n{key}: ")
+            };
+            let key = TextComponent::text(key);
+            let key = if colored {
+                key.color_named(NamedColor::Gold)
+            } else {
+                key
+            };
+            message = message.add_child(key).add_child(TextComponent::text(value));
+        }
+
+        sender.send_message(message).await;
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(WorldInfoExecutor)
+        .then(argument(ARG_WORLD, WorldArgumentConsumer).execute(WorldInfoExecutor))
+}
@@ -57,10 +57,21 @@ pub struct CommandDispatcher {
     pub(crate) commands: HashMap<String, Command>,
     pub(crate) permissions: HashMap<String, String>,
     pub(crate) permission_lvl: HashMap<String, PermissionLvl>,
+    /// The names and aliases of commands which are not registered, see
+    /// [`CommandDispatcher::disable`]
+    pub(crate) disabled: HashSet<String>,
 }
 
 /// Stores registered [`CommandTree`]s and dispatches commands to them.
 impl CommandDispatcher {
+    /// A dispatcher which does not register the commands with the names or aliases
+    pub fn with_disabled(disabled: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            disabled: disabled.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
     pub async fn handle_command<'a>(
         &'a self,
         sender: &mut CommandSender<'a>,
@@ -323,6 +334,9 @@ impl CommandDispatcher {
         permission: &str,
         permission_lvl: PermissionLvl,
     ) {
+        if tree.names.iter().any(|name| self.disabled.contains(name)) {
+            return;
+        }
         let mut names = tree.names.iter();
 
         let primary_name = names.next().expect("at least one name must be provided");
@@ -359,14 +373,48 @@ impl CommandDispatcher {
         for key in to_remove {
             self.commands.remove(&key);
             self.permissions.remove(&key);
+            self.permission_lvl.remove(&key);
         }
     }
+
+    /// Unregisters a command by its name or an alias and keeps it from being registered again.
+    /// Returns whether the command was registered.
+    pub(crate) fn disable(&mut self, name: &str) -> bool {
+        let Ok(tree) = self.get_tree(name) else {
+            return false;
+        };
+        let names = tree.names.clone();
+        self.unregister(&names[0]);
+        self.disabled.extend(names);
+        true
+    }
+
+    /// Registers a disabled command again, taking it from `commands`, which has to have it
+    /// registered. Returns whether it did.
+    pub(crate) fn enable(&mut self, name: &str, commands: &Self) -> bool {
+        let Ok(tree) = commands.get_tree(name) else {
+            return false;
+        };
+        let primary_name = &tree.names[0];
+        let (Some(permission), Some(permission_lvl)) = (
+            commands.permissions.get(primary_name),
+            commands.permission_lvl.get(primary_name),
+        ) else {
+            return false;
+        };
+        for name in &tree.names {
+            self.disabled.remove(name);
+        }
+        self.register(tree.clone(), permission, *permission_lvl);
+        true
+    }
 }
 
 #[cfg(test)]
 mod test {
     use pumpkin_util::PermissionLvl;
 
+    use super::CommandDispatcher;
     use crate::command::{default_dispatcher, tree::CommandTree};
     #[test]
     fn test_dynamic_command() {
@@ -374,4 +422,28 @@ mod test {
         let tree = CommandTree::new(["test"], "test_desc");
         dispatcher.register(tree, "", PermissionLvl::Zero);
     }
+
+    #[test]
+    fn test_disabled_command() {
+        let mut dispatcher = CommandDispatcher::with_disabled(["test".to_string()]);
+        dispatcher.register(
+            CommandTree::new(["test"], "test_desc"),
+            "",
+            PermissionLvl::Zero,
+        );
+        assert!(dispatcher.get_tree("test").is_err());
+
+        let mut commands = CommandDispatcher::default();
+        commands.register(
+            CommandTree::new(["test", "t"], "test_desc"),
+            "",
+            PermissionLvl::Zero,
+        );
+        assert!(dispatcher.enable("t", &commands));
+        assert!(dispatcher.get_tree("test").is_ok());
+
+        assert!(dispatcher.disable("test"));
+        assert!(dispatcher.get_tree("t").is_err());
+        assert!(!dispatcher.disable("test"));
+    }
 }
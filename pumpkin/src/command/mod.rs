@@ -10,11 +10,16 @@ use crate::world::World;
 use args::ConsumedArgs;
 use async_trait::async_trait;
 use commands::{
-    ban, banip, banlist, clear, damage, deop, experience, fill, gamemode, give, help, kick, kill,
-    list, me, msg, op, pardon, pardonip, particle, playsound, plugin, plugins, pumpkin, say,
-    setblock, stop, summon, teleport, time, title, weather, worldborder,
+    afk, anvil, ban, banip, banlist, broadcast, chatformat, clear, command, cooldown, damage, deop,
+    enderchest, essentials, execute, experience, fill, firstjoin, freeze, gamemode, give, glow,
+    grindstone, help, home, ignore, jump, kick, kill, kit, list, locate, maxplayers, me, more,
+    motd, msg, mute, nbt, nick, op, pardon, pardonip, particle, playsound, playtime, plugin,
+    plugins, pumpkin, rtp, saveall, saveoff, saveon, say, seen, setblock, skull, sleeppercentage,
+    spawn, spawnprotection, stop, summon, teleport, time, title, top, tpa, trash, vanish, warp,
+    weather, workbench, world, worldborder, worldinfo,
 };
 use dispatcher::CommandError;
+use pumpkin_config::ADVANCED_CONFIG;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::permission::PermissionLvl;
 use pumpkin_util::text::TextComponent;
@@ -119,13 +124,38 @@ impl CommandSender<'_> {
             CommandSender::Player(p) => Some(p.living_entity.entity.world.read().await.clone()),
         }
     }
+
+    /// The world commands apply to unless they are given one: the world of a player, or the first
+    /// world for the console and RCON
+    pub async fn current_world(&self, server: &Server) -> Result<Arc<World>, CommandError> {
+        if let Some(world) = self.world().await {
+            return Ok(world);
+        }
+        server.worlds.read().await.first().cloned().ok_or_else(|| {
+            CommandError::GeneralCommandIssue("There is no world loaded".to_string())
+        })
+    }
 }
 
+/// The dispatcher with the built-in commands, except those disabled in the config
 #[must_use]
-#[allow(clippy::too_many_lines)]
 pub fn default_dispatcher() -> CommandDispatcher {
+    let mut dispatcher =
+        CommandDispatcher::with_disabled(ADVANCED_CONFIG.commands.disabled.iter().cloned());
+    register_builtin(&mut dispatcher);
+    dispatcher
+}
+
+/// A dispatcher with all built-in commands, even the disabled ones
+#[must_use]
+pub fn builtin_dispatcher() -> CommandDispatcher {
     let mut dispatcher = CommandDispatcher::default();
+    register_builtin(&mut dispatcher);
+    dispatcher
+}
 
+#[allow(clippy::too_many_lines)]
+fn register_builtin(dispatcher: &mut CommandDispatcher) {
     dispatcher.register(
         pumpkin::init_command_tree(),
         "pumpkin.pumpkin",
@@ -143,6 +173,75 @@ pub fn default_dispatcher() -> CommandDispatcher {
     );
     dispatcher.register(me::init_command_tree(), "pumpkin.me", PermissionLvl::Zero);
     dispatcher.register(msg::init_command_tree(), "pumpkin.msg", PermissionLvl::Zero);
+    dispatcher.register(
+        playtime::init_command_tree(),
+        "pumpkin.playtime",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(kit::init_command_tree(), "pumpkin.kit", PermissionLvl::Zero);
+    dispatcher.register(afk::init_command_tree(), "pumpkin.afk", PermissionLvl::Zero);
+    dispatcher.register(
+        ignore::init_command_tree(),
+        "pumpkin.ignore",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(
+        home::init_command_tree(),
+        "pumpkin.home",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(
+        home::init_sethome_command_tree(),
+        "pumpkin.sethome",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(
+        home::init_delhome_command_tree(),
+        "pumpkin.delhome",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(
+        home::init_homes_command_tree(),
+        "pumpkin.homes",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(
+        warp::init_command_tree(),
+        "pumpkin.warp",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(
+        warp::init_warps_command_tree(),
+        "pumpkin.warps",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(tpa::init_command_tree(), "pumpkin.tpa", PermissionLvl::Zero);
+    dispatcher.register(
+        tpa::init_tpahere_command_tree(),
+        "pumpkin.tpahere",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(
+        tpa::init_tpaccept_command_tree(),
+        "pumpkin.tpaccept",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(
+        tpa::init_tpdeny_command_tree(),
+        "pumpkin.tpdeny",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(
+        tpa::init_tpcancel_command_tree(),
+        "pumpkin.tpcancel",
+        PermissionLvl::Zero,
+    );
+    dispatcher.register(rtp::init_command_tree(), "pumpkin.rtp", PermissionLvl::Zero);
+    dispatcher.register(
+        spawn::init_command_tree(),
+        "pumpkin.spawn",
+        PermissionLvl::Zero,
+    );
     dispatcher.register(
         kill::init_command_tree(),
         "pumpkin.kill",
@@ -153,16 +252,50 @@ pub fn default_dispatcher() -> CommandDispatcher {
         "pumpkin.worldborder",
         PermissionLvl::Two,
     );
+    dispatcher.register(
+        world::init_command_tree(),
+        "pumpkin.world",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        worldinfo::init_command_tree(),
+        "pumpkin.worldinfo",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(nbt::init_command_tree(), "pumpkin.nbt", PermissionLvl::Two);
+    dispatcher.register(
+        nick::init_command_tree(),
+        "pumpkin.nick",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        broadcast::init_command_tree(),
+        "pumpkin.broadcast",
+        PermissionLvl::Two,
+    );
     dispatcher.register(
         teleport::init_command_tree(),
         "pumpkin.teleport",
         PermissionLvl::Two,
     );
+    dispatcher.register(top::init_command_tree(), "pumpkin.top", PermissionLvl::Two);
+    dispatcher.register(
+        jump::init_command_tree(),
+        "pumpkin.jump",
+        PermissionLvl::Two,
+    );
     dispatcher.register(
         time::init_command_tree(),
         "pumpkin.time",
         PermissionLvl::Two,
     );
+    for preset in time::PresetTime::ALL {
+        dispatcher.register(
+            time::init_preset_command_tree(preset),
+            &format!("pumpkin.{}", preset.name()),
+            PermissionLvl::Two,
+        );
+    }
     dispatcher.register(
         give::init_command_tree(),
         "pumpkin.give",
@@ -173,6 +306,41 @@ pub fn default_dispatcher() -> CommandDispatcher {
         "pumpkin.clear",
         PermissionLvl::Two,
     );
+    dispatcher.register(
+        cooldown::init_command_tree(),
+        "pumpkin.cooldown",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        freeze::init_command_tree(),
+        "pumpkin.freeze",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        glow::init_command_tree(),
+        "pumpkin.glow",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        warp::init_setwarp_command_tree(),
+        "pumpkin.setwarp",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        warp::init_delwarp_command_tree(),
+        "pumpkin.delwarp",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        locate::init_command_tree(),
+        "pumpkin.locate",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        execute::init_command_tree(),
+        "pumpkin.execute",
+        PermissionLvl::Two,
+    );
     dispatcher.register(
         setblock::init_command_tree(),
         "pumpkin.setblock",
@@ -208,11 +376,57 @@ pub fn default_dispatcher() -> CommandDispatcher {
         "pumpkin.experience",
         PermissionLvl::Two,
     );
+    essentials::register(dispatcher);
+    dispatcher.register(
+        more::init_command_tree(),
+        "pumpkin.more",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        skull::init_command_tree(),
+        "pumpkin.skull",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        workbench::init_command_tree(),
+        "pumpkin.workbench",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        enderchest::init_command_tree(),
+        "pumpkin.enderchest",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        anvil::init_command_tree(),
+        "pumpkin.anvil",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        grindstone::init_command_tree(),
+        "pumpkin.grindstone",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        trash::init_command_tree(),
+        "pumpkin.trash",
+        PermissionLvl::Zero,
+    );
     dispatcher.register(
         weather::init_command_tree(),
         "pumpkin.weather",
         PermissionLvl::Two,
     );
+    dispatcher.register(
+        weather::init_sun_command_tree(),
+        "pumpkin.sun",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        weather::init_rain_command_tree(),
+        "pumpkin.rain",
+        PermissionLvl::Two,
+    );
     dispatcher.register(
         particle::init_command_tree(),
         "pumpkin.particle",
@@ -229,6 +443,11 @@ pub fn default_dispatcher() -> CommandDispatcher {
         PermissionLvl::Two,
     );
     dispatcher.register(say::init_command_tree(), "pumpkin.say", PermissionLvl::Two);
+    dispatcher.register(
+        seen::init_command_tree(),
+        "pumpkin.seen",
+        PermissionLvl::Two,
+    );
     dispatcher.register(
         gamemode::init_command_tree(),
         "pumpkin.gamemode",
@@ -250,11 +469,51 @@ pub fn default_dispatcher() -> CommandDispatcher {
         "pumpkin.kick",
         PermissionLvl::Three,
     );
+    dispatcher.register(
+        firstjoin::init_command_tree(),
+        "pumpkin.firstjoin",
+        PermissionLvl::Three,
+    );
     dispatcher.register(
         plugin::init_command_tree(),
         "pumpkin.plugin",
         PermissionLvl::Three,
     );
+    dispatcher.register(
+        motd::init_command_tree(),
+        "pumpkin.motd",
+        PermissionLvl::Three,
+    );
+    dispatcher.register(
+        sleeppercentage::init_command_tree(),
+        "pumpkin.sleeppercentage",
+        PermissionLvl::Two,
+    );
+    dispatcher.register(
+        spawnprotection::init_command_tree(),
+        "pumpkin.spawnprotection",
+        PermissionLvl::Three,
+    );
+    dispatcher.register(
+        chatformat::init_command_tree(),
+        "pumpkin.chatformat",
+        PermissionLvl::Three,
+    );
+    dispatcher.register(
+        mute::init_command_tree(),
+        "pumpkin.mute",
+        PermissionLvl::Three,
+    );
+    dispatcher.register(
+        mute::init_unmute_command_tree(),
+        "pumpkin.unmute",
+        PermissionLvl::Three,
+    );
+    dispatcher.register(
+        vanish::init_command_tree(),
+        "pumpkin.vanish",
+        PermissionLvl::Three,
+    );
     dispatcher.register(
         plugins::init_command_tree(),
         "pumpkin.plugins",
@@ -285,13 +544,36 @@ pub fn default_dispatcher() -> CommandDispatcher {
         "pumpkin.pardonip",
         PermissionLvl::Three,
     );
+    dispatcher.register(
+        command::init_command_tree(),
+        "pumpkin.command",
+        PermissionLvl::Four,
+    );
     dispatcher.register(
         stop::init_command_tree(),
         "pumpkin.stop",
         PermissionLvl::Four,
     );
-
-    dispatcher
+    dispatcher.register(
+        saveall::init_command_tree(),
+        "pumpkin.save-all",
+        PermissionLvl::Four,
+    );
+    dispatcher.register(
+        saveoff::init_command_tree(),
+        "pumpkin.save-off",
+        PermissionLvl::Four,
+    );
+    dispatcher.register(
+        saveon::init_command_tree(),
+        "pumpkin.save-on",
+        PermissionLvl::Four,
+    );
+    dispatcher.register(
+        maxplayers::init_command_tree(),
+        "pumpkin.maxplayers",
+        PermissionLvl::Four,
+    );
 }
 
 #[async_trait]
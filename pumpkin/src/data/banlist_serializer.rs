@@ -66,6 +66,109 @@ impl BannedIpEntry {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MutedPlayerEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    #[serde(with = "format::date")]
+    pub created: DateTime<FixedOffset>,
+    pub source: String,
+    #[serde(with = "format::option_date")]
+    pub expires: Option<DateTime<FixedOffset>>,
+    pub reason: String,
+}
+
+impl MutedPlayerEntry {
+    #[must_use]
+    pub fn new(
+        profile: &GameProfile,
+        source: String,
+        expires: Option<DateTime<FixedOffset>>,
+        reason: String,
+    ) -> Self {
+        Self {
+            uuid: profile.id,
+            name: profile.name.clone(),
+            created: Local::now().fixed_offset(),
+            source,
+            expires,
+            reason,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LastSeenEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    #[serde(with = "format::date")]
+    pub last_seen: DateTime<FixedOffset>,
+}
+
+impl LastSeenEntry {
+    #[must_use]
+    pub fn new(profile: &GameProfile) -> Self {
+        Self {
+            uuid: profile.id,
+            name: profile.name.clone(),
+            last_seen: Local::now().fixed_offset(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KitUsageEntry {
+    pub uuid: Uuid,
+    pub kit: String,
+    #[serde(with = "format::date")]
+    pub last_used: DateTime<FixedOffset>,
+}
+
+impl KitUsageEntry {
+    #[must_use]
+    pub fn new(uuid: Uuid, kit: &str) -> Self {
+        Self {
+            uuid,
+            kit: kit.to_string(),
+            last_used: Local::now().fixed_offset(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RandomTeleportUsageEntry {
+    pub uuid: Uuid,
+    #[serde(with = "format::date")]
+    pub last_used: DateTime<FixedOffset>,
+}
+
+impl RandomTeleportUsageEntry {
+    #[must_use]
+    pub fn new(uuid: Uuid) -> Self {
+        Self {
+            uuid,
+            last_used: Local::now().fixed_offset(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepairUsageEntry {
+    pub uuid: Uuid,
+    #[serde(with = "format::date")]
+    pub last_used: DateTime<FixedOffset>,
+}
+
+impl RepairUsageEntry {
+    #[must_use]
+    pub fn new(uuid: Uuid) -> Self {
+        Self {
+            uuid,
+            last_used: Local::now().fixed_offset(),
+        }
+    }
+}
+
 mod format {
     const FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
 
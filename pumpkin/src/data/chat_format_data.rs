@@ -0,0 +1,96 @@
+use std::{path::Path, sync::LazyLock};
+
+use pumpkin_util::text::TextComponent;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{assert_named, assert_unique, LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static CHAT_FORMAT_LIST: LazyLock<tokio::sync::RwLock<ChatFormatList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(ChatFormatList::load()));
+
+/// Where a text is shown around the name of a player in chat
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ChatFormatPart {
+    Prefix,
+    Suffix,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct ChatFormatEntry {
+    pub uuid: Uuid,
+    /// The name of the player when the entry was last changed, to make the file readable
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<TextComponent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<TextComponent>,
+}
+
+impl ChatFormatEntry {
+    fn part_mut(&mut self, part: ChatFormatPart) -> &mut Option<TextComponent> {
+        match part {
+            ChatFormatPart::Prefix => &mut self.prefix,
+            ChatFormatPart::Suffix => &mut self.suffix,
+        }
+    }
+}
+
+/// The prefixes and suffixes shown around the names of players in chat
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct ChatFormatList {
+    pub players: Vec<ChatFormatEntry>,
+}
+
+impl ChatFormatList {
+    #[must_use]
+    pub fn get_entry(&self, uuid: &Uuid) -> Option<&ChatFormatEntry> {
+        self.players.iter().find(|entry| entry.uuid == *uuid)
+    }
+
+    /// Sets or removes the prefix or suffix of a player. Entries without either are removed.
+    pub fn set(
+        &mut self,
+        uuid: Uuid,
+        name: &str,
+        part: ChatFormatPart,
+        text: Option<TextComponent>,
+    ) {
+        let index = self
+            .players
+            .iter()
+            .position(|entry| entry.uuid == uuid)
+            .unwrap_or_else(|| {
+                self.players.push(ChatFormatEntry {
+                    uuid,
+                    name: name.to_string(),
+                    prefix: None,
+                    suffix: None,
+                });
+                self.players.len() - 1
+            });
+
+        let entry = &mut self.players[index];
+        entry.name = name.to_string();
+        *entry.part_mut(part) = text;
+        if entry.prefix.is_none() && entry.suffix.is_none() {
+            self.players.remove(index);
+        }
+    }
+}
+
+impl LoadJSONConfiguration for ChatFormatList {
+    fn get_path() -> &'static Path {
+        Path::new("chat-formats.json")
+    }
+    fn validate(&self) {
+        let path = Self::get_path();
+        assert_unique(path, self.players.iter().map(|entry| entry.uuid));
+        for entry in &self.players {
+            assert_named(path, &entry.name);
+        }
+    }
+}
+
+impl SaveJSONConfiguration for ChatFormatList {}
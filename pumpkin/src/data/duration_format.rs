@@ -0,0 +1,35 @@
+//! Formatting of durations in messages, like the time left of a mute or cooldown.
+
+use chrono::TimeDelta;
+
+/// Formats a duration like `1d 2h 3m 4s`, leaving out units which are zero
+#[must_use]
+pub fn format_duration(duration: TimeDelta) -> String {
+    let seconds = duration.num_seconds().max(1);
+    let parts = [
+        (seconds / 86400, "d"),
+        (seconds / 3600 % 24, "h"),
+        (seconds / 60 % 60, "m"),
+        (seconds % 60, "s"),
+    ];
+    parts
+        .into_iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{value}{unit}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Formats ticks of playtime like `1d 2h 3m`, leaving out days and hours which are zero
+#[must_use]
+pub fn format_playtime(ticks: u64) -> String {
+    let minutes = ticks / 20 / 60;
+    let parts = [(minutes / 1440, "d"), (minutes / 60 % 24, "h")];
+    parts
+        .into_iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{value}{unit}"))
+        .chain(std::iter::once(format!("{}m", minutes % 60)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,68 @@
+use std::{path::Path, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::net::GameProfile;
+
+use super::{assert_named, assert_unique, LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static JOINED_PLAYER_LIST: LazyLock<tokio::sync::RwLock<JoinedPlayerList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(JoinedPlayerList::load()));
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct JoinedPlayerEntry {
+    pub uuid: Uuid,
+    /// The name of the player when they first joined, to look up offline players
+    pub name: String,
+}
+
+/// The players who joined the server before, so joining for the first time can be detected
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct JoinedPlayerList {
+    pub players: Vec<JoinedPlayerEntry>,
+}
+
+impl JoinedPlayerList {
+    #[must_use]
+    pub fn has_joined(&self, uuid: &Uuid) -> bool {
+        self.players.iter().any(|entry| entry.uuid == *uuid)
+    }
+
+    /// Records that the player joined, returning whether they did not join before
+    pub fn add(&mut self, profile: &GameProfile) -> bool {
+        if self.has_joined(&profile.id) {
+            return false;
+        }
+        self.players.push(JoinedPlayerEntry {
+            uuid: profile.id,
+            name: profile.name.clone(),
+        });
+        true
+    }
+
+    /// Forgets that the player joined, so their next join counts as the first one again
+    pub fn remove(&mut self, name: &str) -> Option<JoinedPlayerEntry> {
+        let index = self
+            .players
+            .iter()
+            .position(|entry| entry.name.eq_ignore_ascii_case(name))?;
+        Some(self.players.remove(index))
+    }
+}
+
+impl LoadJSONConfiguration for JoinedPlayerList {
+    fn get_path() -> &'static Path {
+        Path::new("joined-players.json")
+    }
+    fn validate(&self) {
+        let path = Self::get_path();
+        assert_unique(path, self.players.iter().map(|entry| entry.uuid));
+        for entry in &self.players {
+            assert_named(path, &entry.name);
+        }
+    }
+}
+
+impl SaveJSONConfiguration for JoinedPlayerList {}
@@ -0,0 +1,117 @@
+use std::{path::Path, sync::LazyLock};
+
+use pumpkin_util::math::vector3::Vector3;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{assert_named, assert_unique, LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static HOME_LIST: LazyLock<tokio::sync::RwLock<HomeList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(HomeList::load()));
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct HomeEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    /// The name of the world the home is in, see [`crate::world::World::name`]
+    pub world: String,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl HomeEntry {
+    #[must_use]
+    pub fn new(
+        uuid: Uuid,
+        name: &str,
+        world: String,
+        position: Vector3<f64>,
+        yaw: f32,
+        pitch: f32,
+    ) -> Self {
+        Self {
+            uuid,
+            name: name.to_string(),
+            world,
+            x: position.x,
+            y: position.y,
+            z: position.z,
+            yaw,
+            pitch,
+        }
+    }
+
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        Vector3::new(self.x, self.y, self.z)
+    }
+}
+
+/// The homes players set with `/sethome`
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct HomeList {
+    pub homes: Vec<HomeEntry>,
+}
+
+impl HomeList {
+    /// The homes of the player
+    pub fn homes_of<'a>(&'a self, uuid: &'a Uuid) -> impl Iterator<Item = &'a HomeEntry> + 'a {
+        self.homes.iter().filter(move |home| home.uuid == *uuid)
+    }
+
+    /// Finds a home of the player by its name, ignoring the case
+    #[must_use]
+    pub fn get(&self, uuid: &Uuid, name: &str) -> Option<&HomeEntry> {
+        self.homes_of(uuid)
+            .find(|home| home.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Adds the home, replacing the one of the player with the same name. Returns whether one was
+    /// replaced.
+    pub fn set(&mut self, home: HomeEntry) -> bool {
+        let replaced = self.remove(&home.uuid, &home.name);
+        self.homes.push(home);
+        replaced
+    }
+
+    /// Removes the home of the player with the name. Returns whether there was one.
+    pub fn remove(&mut self, uuid: &Uuid, name: &str) -> bool {
+        let count = self.homes.len();
+        self.homes
+            .retain(|home| home.uuid != *uuid || !home.name.eq_ignore_ascii_case(name));
+        self.homes.len() != count
+    }
+}
+
+impl LoadJSONConfiguration for HomeList {
+    fn get_path() -> &'static Path {
+        Path::new("homes.json")
+    }
+    fn validate(&self) {
+        let path = Self::get_path();
+        // Homes are looked up ignoring the case of their name
+        assert_unique(
+            path,
+            self.homes
+                .iter()
+                .map(|home| (home.uuid, home.name.to_ascii_lowercase())),
+        );
+        for home in &self.homes {
+            assert_named(path, &home.name);
+            assert!(
+                [home.x, home.y, home.z]
+                    .iter()
+                    .all(|value| value.is_finite()),
+                "{} contains the home {} at an invalid position",
+                path.display(),
+                home.name
+            );
+        }
+    }
+}
+
+impl SaveJSONConfiguration for HomeList {}
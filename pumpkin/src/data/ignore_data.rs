@@ -0,0 +1,121 @@
+use std::{path::Path, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::net::GameProfile;
+
+use super::{assert_named, assert_unique, LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static IGNORE_LIST: LazyLock<tokio::sync::RwLock<IgnoreList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(IgnoreList::load()));
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct IgnoredPlayer {
+    pub uuid: Uuid,
+    /// The name of the player when they were ignored, so they can be unignored while offline
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct IgnoreEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    pub ignored: Vec<IgnoredPlayer>,
+}
+
+/// The players each player ignores with `/ignore`
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct IgnoreList {
+    pub players: Vec<IgnoreEntry>,
+}
+
+impl IgnoreList {
+    /// The players the player ignores
+    #[must_use]
+    pub fn get_ignored(&self, uuid: &Uuid) -> &[IgnoredPlayer] {
+        self.players
+            .iter()
+            .find(|entry| entry.uuid == *uuid)
+            .map_or(&[], |entry| &entry.ignored)
+    }
+
+    #[must_use]
+    pub fn is_ignoring(&self, uuid: &Uuid, sender: &Uuid) -> bool {
+        self.get_ignored(uuid)
+            .iter()
+            .any(|ignored| ignored.uuid == *sender)
+    }
+
+    /// Ignores a player, returning whether they were not ignored before
+    pub fn add(&mut self, profile: &GameProfile, target: &GameProfile) -> bool {
+        let index = self
+            .players
+            .iter()
+            .position(|entry| entry.uuid == profile.id)
+            .unwrap_or_else(|| {
+                self.players.push(IgnoreEntry {
+                    uuid: profile.id,
+                    name: profile.name.clone(),
+                    ignored: Vec::new(),
+                });
+                self.players.len() - 1
+            });
+
+        let entry = &mut self.players[index];
+        entry.name.clone_from(&profile.name);
+        if entry
+            .ignored
+            .iter()
+            .any(|ignored| ignored.uuid == target.id)
+        {
+            return false;
+        }
+        entry.ignored.push(IgnoredPlayer {
+            uuid: target.id,
+            name: target.name.clone(),
+        });
+        true
+    }
+
+    /// Stops ignoring the player with the given name, returning them if they were ignored
+    pub fn remove(&mut self, uuid: &Uuid, name: &str) -> Option<IgnoredPlayer> {
+        let index = self.players.iter().position(|entry| entry.uuid == *uuid)?;
+        let entry = &mut self.players[index];
+        let ignored_index = entry
+            .ignored
+            .iter()
+            .position(|ignored| ignored.name.eq_ignore_ascii_case(name))?;
+        let ignored = entry.ignored.remove(ignored_index);
+        if entry.ignored.is_empty() {
+            self.players.remove(index);
+        }
+        Some(ignored)
+    }
+}
+
+impl LoadJSONConfiguration for IgnoreList {
+    fn get_path() -> &'static Path {
+        Path::new("ignored-players.json")
+    }
+    fn validate(&self) {
+        let path = Self::get_path();
+        assert_unique(path, self.players.iter().map(|entry| entry.uuid));
+        for entry in &self.players {
+            assert_named(path, &entry.name);
+            assert_unique(path, entry.ignored.iter().map(|ignored| ignored.uuid));
+            for ignored in &entry.ignored {
+                assert_named(path, &ignored.name);
+                assert!(
+                    ignored.uuid != entry.uuid,
+                    "{} contains {} ignoring themselves",
+                    path.display(),
+                    entry.name
+                );
+            }
+        }
+    }
+}
+
+impl SaveJSONConfiguration for IgnoreList {}
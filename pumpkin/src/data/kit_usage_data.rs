@@ -0,0 +1,66 @@
+use std::{path::Path, sync::LazyLock};
+
+use chrono::{Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    assert_named, assert_unique, banlist_serializer::KitUsageEntry, LoadJSONConfiguration,
+    SaveJSONConfiguration,
+};
+
+pub static KIT_USAGE_LIST: LazyLock<tokio::sync::RwLock<KitUsageList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(KitUsageList::load()));
+
+/// When each player last redeemed each kit, for the kit cooldowns
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct KitUsageList {
+    pub usages: Vec<KitUsageEntry>,
+}
+
+impl KitUsageList {
+    /// How long the player has to wait until they can redeem the kit again, or `None` if they can
+    /// redeem it now
+    #[must_use]
+    pub fn remaining_cooldown(&self, uuid: &Uuid, kit: &str, cooldown: u64) -> Option<TimeDelta> {
+        let entry = self
+            .usages
+            .iter()
+            .find(|entry| entry.uuid == *uuid && entry.kit == kit)?;
+        let cooldown = i64::try_from(cooldown)
+            .ok()
+            .and_then(TimeDelta::try_seconds)
+            .unwrap_or(TimeDelta::MAX);
+        let remaining = cooldown - Local::now().signed_duration_since(entry.last_used);
+        (remaining > TimeDelta::zero()).then_some(remaining)
+    }
+
+    /// Records that the player redeemed the kit now, which is saved with
+    /// [`SaveJSONConfiguration::save`]
+    pub fn set_used(&mut self, uuid: Uuid, kit: &str) {
+        self.usages
+            .retain(|entry| entry.uuid != uuid || entry.kit != kit);
+        self.usages.push(KitUsageEntry::new(uuid, kit));
+    }
+}
+
+impl LoadJSONConfiguration for KitUsageList {
+    fn get_path() -> &'static Path {
+        Path::new("kit-usages.json")
+    }
+    fn validate(&self) {
+        let path = Self::get_path();
+        assert_unique(
+            path,
+            self.usages
+                .iter()
+                .map(|entry| (entry.uuid, entry.kit.as_str())),
+        );
+        for entry in &self.usages {
+            assert_named(path, &entry.kit);
+        }
+    }
+}
+
+impl SaveJSONConfiguration for KitUsageList {}
@@ -0,0 +1,52 @@
+use std::{path::Path, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::net::GameProfile;
+
+use super::{
+    assert_named, assert_unique, banlist_serializer::LastSeenEntry, LoadJSONConfiguration,
+    SaveJSONConfiguration,
+};
+
+pub static LAST_SEEN_LIST: LazyLock<tokio::sync::RwLock<LastSeenList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(LastSeenList::load()));
+
+/// When each player who joined the server last left it
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct LastSeenList {
+    pub players: Vec<LastSeenEntry>,
+}
+
+impl LastSeenList {
+    /// Finds the player by the name they had when they last left
+    #[must_use]
+    pub fn get_entry(&self, name: &str) -> Option<&LastSeenEntry> {
+        self.players
+            .iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Records that the player left now
+    pub fn update(&mut self, profile: &GameProfile) {
+        // Players can change their name, so the old name is replaced
+        self.players.retain(|entry| entry.uuid != profile.id);
+        self.players.push(LastSeenEntry::new(profile));
+    }
+}
+
+impl LoadJSONConfiguration for LastSeenList {
+    fn get_path() -> &'static Path {
+        Path::new("last-seen.json")
+    }
+    fn validate(&self) {
+        let path = Self::get_path();
+        assert_unique(path, self.players.iter().map(|entry| entry.uuid));
+        for entry in &self.players {
+            assert_named(path, &entry.name);
+        }
+    }
+}
+
+impl SaveJSONConfiguration for LastSeenList {}
@@ -1,4 +1,4 @@
-use std::{env, fs, path::Path};
+use std::{collections::HashSet, env, fmt::Debug, fs, hash::Hash, path::Path};
 
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +9,18 @@ pub mod op_data;
 pub mod banlist_serializer;
 pub mod banned_ip_data;
 pub mod banned_player_data;
+pub mod chat_format_data;
+pub mod duration_format;
+pub mod first_join_data;
+pub mod home_data;
+pub mod ignore_data;
+pub mod kit_usage_data;
+pub mod last_seen_data;
+pub mod muted_player_data;
+pub mod playtime_data;
+pub mod random_teleport_data;
+pub mod repair_usage_data;
+pub mod warp_data;
 
 pub trait LoadJSONConfiguration {
     #[must_use]
@@ -54,6 +66,29 @@ pub trait LoadJSONConfiguration {
     fn validate(&self);
 }
 
+/// Asserts that no two entries of a data file have the same key, like two entries for one
+/// player. Only the first of them would ever be found, changed or removed.
+fn assert_unique<K: Eq + Hash + Debug>(file: &Path, keys: impl IntoIterator<Item = K>) {
+    let mut seen = HashSet::new();
+    for key in keys {
+        assert!(
+            !seen.contains(&key),
+            "{} contains {key:?} more than once",
+            file.display()
+        );
+        seen.insert(key);
+    }
+}
+
+/// Asserts that a name in a data file is not empty
+fn assert_named(file: &Path, name: &str) {
+    assert!(
+        !name.trim().is_empty(),
+        "{} contains an entry without a name",
+        file.display()
+    );
+}
+
 pub trait SaveJSONConfiguration: LoadJSONConfiguration {
     // suppress clippy warning
 
@@ -61,32 +96,30 @@ pub trait SaveJSONConfiguration: LoadJSONConfiguration {
     where
         Self: Sized + Default + Serialize + for<'de> Deserialize<'de>,
     {
-        let exe_dir = env::current_dir().unwrap();
+        if let Err(err) = self.try_save() {
+            log::warn!(
+                "Couldn't write data config to {:?}. Reason: {}",
+                Self::get_path(),
+                err
+            );
+        }
+    }
+
+    /// Like [`SaveJSONConfiguration::save`], but returns whether the file could be written
+    /// instead of logging it, for data which must only be used once it is saved.
+    fn try_save(&self) -> std::io::Result<()>
+    where
+        Self: Sized + Default + Serialize + for<'de> Deserialize<'de>,
+    {
+        let exe_dir = env::current_dir()?;
         let data_dir = exe_dir.join(DATA_FOLDER);
         if !data_dir.exists() {
             log::debug!("creating new data root folder");
-            fs::create_dir(&data_dir).expect("Failed to create data root folder");
+            fs::create_dir(&data_dir)?;
         }
         let path = data_dir.join(Self::get_path());
 
-        let content = match serde_json::to_string_pretty(self) {
-            Ok(content) => content,
-            Err(err) => {
-                log::warn!(
-                    "Couldn't serialize operator data config to {:?}. Reason: {}",
-                    path,
-                    err
-                );
-                return;
-            }
-        };
-
-        if let Err(err) = std::fs::write(&path, content) {
-            log::warn!(
-                "Couldn't write operator config to {:?}. Reason: {}",
-                path,
-                err
-            );
-        }
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&path, content)
     }
 }
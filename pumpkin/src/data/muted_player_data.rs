@@ -0,0 +1,82 @@
+use std::{path::Path, sync::LazyLock};
+
+use chrono::Local;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+use serde::{Deserialize, Serialize};
+
+use crate::net::GameProfile;
+
+use super::{
+    assert_named, assert_unique, banlist_serializer::MutedPlayerEntry,
+    duration_format::format_duration, LoadJSONConfiguration, SaveJSONConfiguration,
+};
+
+pub static MUTED_PLAYER_LIST: LazyLock<tokio::sync::RwLock<MutedPlayerList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(MutedPlayerList::load()));
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct MutedPlayerList {
+    pub muted_players: Vec<MutedPlayerEntry>,
+}
+
+impl MutedPlayerList {
+    /// Returns the mute of the player, unmuting them first if their mute expired.
+    #[must_use]
+    pub fn get_entry(&mut self, profile: &GameProfile) -> Option<&MutedPlayerEntry> {
+        self.remove_invalid_entries();
+        self.muted_players
+            .iter()
+            .find(|entry| entry.uuid == profile.id)
+    }
+
+    /// Removes the mute of the player, returning whether they were muted
+    pub fn remove(&mut self, profile: &GameProfile) -> bool {
+        let original_len = self.muted_players.len();
+        self.muted_players.retain(|entry| entry.uuid != profile.id);
+        original_len != self.muted_players.len()
+    }
+
+    fn remove_invalid_entries(&mut self) {
+        let original_len = self.muted_players.len();
+
+        self.muted_players
+            .retain(|entry| entry.expires.is_none_or(|expires| expires >= Local::now()));
+
+        if original_len != self.muted_players.len() {
+            self.save();
+        }
+    }
+}
+
+/// The message telling a muted player why they can not chat
+#[must_use]
+pub fn mute_notice(entry: &MutedPlayerEntry) -> TextComponent {
+    let duration = match entry.expires {
+        Some(expires) => format!(
+            "for another {}",
+            format_duration(expires.signed_duration_since(Local::now()))
+        ),
+        None => "permanently".to_string(),
+    };
+    TextComponent::text(format!(
+        "You are muted {duration}. Reason: {}",
+        entry.reason
+    ))
+    .color_named(NamedColor::Red)
+}
+
+impl LoadJSONConfiguration for MutedPlayerList {
+    fn get_path() -> &'static Path {
+        Path::new("muted-players.json")
+    }
+    fn validate(&self) {
+        let path = Self::get_path();
+        assert_unique(path, self.muted_players.iter().map(|entry| entry.uuid));
+        for entry in &self.muted_players {
+            assert_named(path, &entry.name);
+        }
+    }
+}
+
+impl SaveJSONConfiguration for MutedPlayerList {}
@@ -0,0 +1,79 @@
+use std::{path::Path, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::net::GameProfile;
+
+use super::{assert_named, assert_unique, LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static PLAYTIME_LIST: LazyLock<tokio::sync::RwLock<PlaytimeList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(PlaytimeList::load()));
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct PlaytimeEntry {
+    pub uuid: Uuid,
+    /// The name of the player when the playtime was last saved, to look up offline players
+    pub name: String,
+    /// The ticks the player was online in total
+    pub ticks: u64,
+}
+
+/// The playtime of every player who joined the server
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct PlaytimeList {
+    pub players: Vec<PlaytimeEntry>,
+}
+
+impl PlaytimeList {
+    /// The ticks the player was online before, or 0 if they never joined
+    #[must_use]
+    pub fn get_ticks(&self, uuid: &Uuid) -> u64 {
+        self.players
+            .iter()
+            .find(|entry| entry.uuid == *uuid)
+            .map_or(0, |entry| entry.ticks)
+    }
+
+    #[must_use]
+    pub fn get_entry_by_name(&self, name: &str) -> Option<&PlaytimeEntry> {
+        self.players
+            .iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Sets the total playtime of the player, which is saved with [`SaveJSONConfiguration::save`]
+    pub fn update(&mut self, profile: &GameProfile, ticks: u64) {
+        match self
+            .players
+            .iter_mut()
+            .find(|entry| entry.uuid == profile.id)
+        {
+            Some(entry) => {
+                entry.name.clone_from(&profile.name);
+                entry.ticks = ticks;
+            }
+            None => self.players.push(PlaytimeEntry {
+                uuid: profile.id,
+                name: profile.name.clone(),
+                ticks,
+            }),
+        }
+    }
+}
+
+impl LoadJSONConfiguration for PlaytimeList {
+    fn get_path() -> &'static Path {
+        Path::new("playtime.json")
+    }
+    fn validate(&self) {
+        let path = Self::get_path();
+        assert_unique(path, self.players.iter().map(|entry| entry.uuid));
+        for entry in &self.players {
+            assert_named(path, &entry.name);
+        }
+    }
+}
+
+impl SaveJSONConfiguration for PlaytimeList {}
@@ -0,0 +1,53 @@
+use std::{path::Path, sync::LazyLock};
+
+use chrono::{Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    assert_unique, banlist_serializer::RepairUsageEntry, LoadJSONConfiguration,
+    SaveJSONConfiguration,
+};
+
+pub static REPAIR_USAGE_LIST: LazyLock<tokio::sync::RwLock<RepairUsageList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(RepairUsageList::load()));
+
+/// When each player last used `/repair`, for its cooldown
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct RepairUsageList {
+    pub usages: Vec<RepairUsageEntry>,
+}
+
+impl RepairUsageList {
+    /// How long the player has to wait until they can use `/repair` again, or `None` if they can
+    /// use it now
+    #[must_use]
+    pub fn remaining_cooldown(&self, uuid: &Uuid, cooldown: u64) -> Option<TimeDelta> {
+        let entry = self.usages.iter().find(|entry| entry.uuid == *uuid)?;
+        let cooldown = i64::try_from(cooldown)
+            .ok()
+            .and_then(TimeDelta::try_seconds)
+            .unwrap_or(TimeDelta::MAX);
+        let remaining = cooldown - Local::now().signed_duration_since(entry.last_used);
+        (remaining > TimeDelta::zero()).then_some(remaining)
+    }
+
+    /// Records that the player used `/repair` now, which is saved with
+    /// [`SaveJSONConfiguration::save`]
+    pub fn set_used(&mut self, uuid: Uuid) {
+        self.usages.retain(|entry| entry.uuid != uuid);
+        self.usages.push(RepairUsageEntry::new(uuid));
+    }
+}
+
+impl LoadJSONConfiguration for RepairUsageList {
+    fn get_path() -> &'static Path {
+        Path::new("repair-usages.json")
+    }
+    fn validate(&self) {
+        assert_unique(Self::get_path(), self.usages.iter().map(|entry| entry.uuid));
+    }
+}
+
+impl SaveJSONConfiguration for RepairUsageList {}
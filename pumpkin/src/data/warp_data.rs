@@ -0,0 +1,100 @@
+use std::{path::Path, sync::LazyLock};
+
+use pumpkin_util::math::vector3::Vector3;
+use serde::{Deserialize, Serialize};
+
+use super::{assert_named, assert_unique, LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static WARP_LIST: LazyLock<tokio::sync::RwLock<WarpList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(WarpList::load()));
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct WarpEntry {
+    pub name: String,
+    /// The name of the world the warp is in, see [`crate::world::World::name`]
+    pub world: String,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl WarpEntry {
+    #[must_use]
+    pub fn new(name: &str, world: String, position: Vector3<f64>, yaw: f32, pitch: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            world,
+            x: position.x,
+            y: position.y,
+            z: position.z,
+            yaw,
+            pitch,
+        }
+    }
+
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        Vector3::new(self.x, self.y, self.z)
+    }
+}
+
+/// The named positions players can teleport to with `/warp`
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct WarpList {
+    pub warps: Vec<WarpEntry>,
+}
+
+impl WarpList {
+    /// Finds the warp by its name, ignoring the case
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&WarpEntry> {
+        self.warps
+            .iter()
+            .find(|warp| warp.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Adds the warp, replacing the one with the same name. Returns whether one was replaced.
+    pub fn set(&mut self, warp: WarpEntry) -> bool {
+        let replaced = self.remove(&warp.name);
+        self.warps.push(warp);
+        replaced
+    }
+
+    /// Removes the warp with the name. Returns whether there was one.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let count = self.warps.len();
+        self.warps
+            .retain(|warp| !warp.name.eq_ignore_ascii_case(name));
+        self.warps.len() != count
+    }
+}
+
+impl LoadJSONConfiguration for WarpList {
+    fn get_path() -> &'static Path {
+        Path::new("warps.json")
+    }
+    fn validate(&self) {
+        let path = Self::get_path();
+        // Warps are looked up ignoring the case of their name
+        assert_unique(
+            path,
+            self.warps.iter().map(|warp| warp.name.to_ascii_lowercase()),
+        );
+        for warp in &self.warps {
+            assert_named(path, &warp.name);
+            assert!(
+                [warp.x, warp.y, warp.z]
+                    .iter()
+                    .all(|value| value.is_finite()),
+                "{} contains the warp {} at an invalid position",
+                path.display(),
+                warp.name
+            );
+        }
+    }
+}
+
+impl SaveJSONConfiguration for WarpList {}
@@ -1,16 +1,25 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
 
 use async_trait::async_trait;
+use rand::Rng;
 use tokio::sync::Mutex;
 
 use crate::entity::{mob::MobEntity, player::Player};
 
-use super::Goal;
+use super::{Control, Goal};
+
+/// How likely mobs start looking at a player each tick, like in vanilla
+const CHANCE: f64 = 0.02;
 
 pub struct LookAtEntityGoal {
     // TODO: make this an entity
     target: Mutex<Option<Arc<Player>>>,
     range: f64,
+    /// How many more ticks the mob keeps looking
+    look_time: AtomicU32,
 }
 
 impl LookAtEntityGoal {
@@ -19,6 +28,7 @@ impl LookAtEntityGoal {
         Self {
             target: Mutex::new(None),
             range,
+            look_time: AtomicU32::new(0),
         }
     }
 }
@@ -26,6 +36,9 @@ impl LookAtEntityGoal {
 #[async_trait]
 impl Goal for LookAtEntityGoal {
     async fn can_start(&self, mob: &crate::entity::mob::MobEntity) -> bool {
+        if rand::thread_rng().gen::<f64>() >= CHANCE {
+            return false;
+        }
         // TODO: make this an entity
         let mut target = self.target.lock().await;
 
@@ -41,6 +54,9 @@ impl Goal for LookAtEntityGoal {
     }
 
     async fn should_continue(&self, mob: &MobEntity) -> bool {
+        if self.look_time.load(Ordering::Relaxed) == 0 {
+            return false;
+        }
         if let Some(target) = self.target.lock().await.as_ref() {
             let mob_pos = mob.living_entity.entity.pos.load();
             let target_pos = target.living_entity.entity.pos.load();
@@ -49,10 +65,33 @@ impl Goal for LookAtEntityGoal {
         false
     }
 
+    async fn start(&self, _mob: &MobEntity) {
+        // Mobs look for 2 to 4 seconds, like in vanilla
+        let ticks = 40 + rand::thread_rng().gen_range(0..40);
+        self.look_time.store(ticks, Ordering::Relaxed);
+    }
+
+    async fn stop(&self, _mob: &MobEntity) {
+        *self.target.lock().await = None;
+    }
+
     async fn tick(&self, mob: &MobEntity) {
+        let _ = self
+            .look_time
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |ticks| {
+                ticks.checked_sub(1)
+            });
         if let Some(target) = self.target.lock().await.as_ref() {
-            let target_pos = target.living_entity.entity.pos.load();
-            mob.living_entity.entity.look_at(target_pos).await;
+            let entity = &target.living_entity.entity;
+            let eyes = entity
+                .pos
+                .load()
+                .add_raw(0.0, f64::from(entity.standing_eye_height), 0.0);
+            mob.look_at(eyes).await;
         }
     }
+
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Look]
+    }
 }
@@ -0,0 +1,171 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use async_trait::async_trait;
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_data::damage::DamageType;
+use pumpkin_protocol::client::play::{Animation, CEntityAnimation};
+use pumpkin_util::Difficulty;
+use rand::Rng;
+
+use crate::entity::{combat, mob::MobEntity, player::Player};
+
+use super::{Control, Goal};
+
+/// How many ticks mobs wait between attacks, like in vanilla
+const ATTACK_COOLDOWN: u32 = 20;
+
+/// How strongly attacks knock players back, like in vanilla
+const KNOCKBACK: f64 = 0.8;
+
+/// Makes the mob walk to its target and hit it when it is close enough
+pub struct MeleeAttackGoal {
+    /// How much faster than normal the mob walks to its target
+    speed: f64,
+    /// The damage of each hit in normal difficulty
+    damage: f32,
+    /// Ticks until the mob can hit again
+    cooldown: AtomicU32,
+    /// Ticks until the mob looks for a new path to its target
+    repath_delay: AtomicU32,
+}
+
+impl MeleeAttackGoal {
+    #[must_use]
+    pub fn new(speed: f64, damage: f32) -> Self {
+        Self {
+            speed,
+            damage,
+            cooldown: AtomicU32::new(0),
+            repath_delay: AtomicU32::new(0),
+        }
+    }
+
+    /// Finds a new path when the target moved away from the end of the current one. Far away
+    /// targets are looked for less often, like in vanilla.
+    async fn follow(&self, mob: &MobEntity, target: &Player) {
+        let delay = self.repath_delay.load(Ordering::Relaxed);
+        if delay > 0 {
+            self.repath_delay.store(delay - 1, Ordering::Relaxed);
+            return;
+        }
+        let entity = &mob.living_entity.entity;
+        let target_entity = &target.living_entity.entity;
+        let target_block = target_entity.block_pos.load();
+        let mut navigator = mob.navigator.lock().await;
+        let moved = navigator
+            .destination()
+            .is_none_or(|destination| destination != target_block);
+        if !moved {
+            return;
+        }
+        let distance = entity
+            .pos
+            .load()
+            .squared_distance_to_vec(target_entity.pos.load());
+        let mut delay = 4 + rand::thread_rng().gen_range(0..7);
+        if distance > 32.0 * 32.0 {
+            delay += 10;
+        } else if distance > 16.0 * 16.0 {
+            delay += 5;
+        }
+        if !navigator.move_to(entity, target_block, self.speed).await {
+            delay += 15;
+        }
+        self.repath_delay.store(delay, Ordering::Relaxed);
+    }
+
+    /// Hits the target if it is close enough and the cooldown is over
+    async fn attack(&self, mob: &MobEntity, target: &Player) {
+        let cooldown = self.cooldown.load(Ordering::Relaxed);
+        if cooldown > 0 {
+            self.cooldown.store(cooldown - 1, Ordering::Relaxed);
+            return;
+        }
+        let entity = &mob.living_entity.entity;
+        let target_entity = &target.living_entity.entity;
+        // Mobs reach twice their width, plus the width of the target, like in vanilla
+        let width = f64::from(entity.width());
+        let reach = (width * 2.0).powi(2) + f64::from(target_entity.width());
+        if entity
+            .pos
+            .load()
+            .squared_distance_to_vec(target_entity.pos.load())
+            > reach
+        {
+            return;
+        }
+        self.cooldown.store(ATTACK_COOLDOWN, Ordering::Relaxed);
+
+        let world = entity.world.read().await.clone();
+        world
+            .broadcast_packet_all(&CEntityAnimation::new(
+                entity.entity_id.into(),
+                Animation::SwingMainArm as u8,
+            ))
+            .await;
+        let damage = scale_damage(self.damage, &BASIC_CONFIG.default_difficulty);
+        if !target.living_entity.check_damage(damage) {
+            return;
+        }
+        target
+            .living_entity
+            .damage_with_context(
+                damage,
+                DamageType::MOB_ATTACK,
+                None,
+                Some(entity),
+                Some(entity),
+            )
+            .await;
+        combat::handle_knockback(entity, &world, target_entity, KNOCKBACK).await;
+    }
+}
+
+#[async_trait]
+impl Goal for MeleeAttackGoal {
+    async fn can_start(&self, mob: &MobEntity) -> bool {
+        mob.target.lock().await.is_some()
+    }
+
+    async fn should_continue(&self, mob: &MobEntity) -> bool {
+        mob.target.lock().await.is_some()
+    }
+
+    async fn start(&self, _mob: &MobEntity) {
+        self.cooldown.store(0, Ordering::Relaxed);
+        self.repath_delay.store(0, Ordering::Relaxed);
+    }
+
+    async fn stop(&self, mob: &MobEntity) {
+        mob.navigator.lock().await.stop();
+    }
+
+    async fn tick(&self, mob: &MobEntity) {
+        let Some(target) = mob.target.lock().await.clone() else {
+            return;
+        };
+        let target_entity = &target.living_entity.entity;
+        let eyes = target_entity.pos.load().add_raw(
+            0.0,
+            f64::from(target_entity.standing_eye_height),
+            0.0,
+        );
+        mob.look_at(eyes).await;
+        self.follow(mob, &target).await;
+        self.attack(mob, &target).await;
+    }
+
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move, Control::Look]
+    }
+}
+
+/// How much damage a hit of a mob does to players in the difficulty, like in vanilla
+fn scale_damage(damage: f32, difficulty: &Difficulty) -> f32 {
+    match difficulty {
+        Difficulty::Peaceful => 0.0,
+        Difficulty::Easy => (damage / 2.0 + 1.0).min(damage),
+        Difficulty::Normal => damage,
+        Difficulty::Hard => damage * 1.5,
+    }
+}
@@ -1,9 +1,22 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 
 use crate::entity::mob::MobEntity;
 
 pub mod look_at_entity;
+pub mod melee_attack;
 pub mod target_goal;
+pub mod wander_around;
+
+/// What a goal takes over while it runs. Goals which need the same control can't run at the same
+/// time, like in vanilla.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Move,
+    Look,
+    Target,
+}
 
 #[async_trait]
 pub trait Goal: Send + Sync {
@@ -11,6 +24,103 @@ pub trait Goal: Send + Sync {
     async fn can_start(&self, mob: &MobEntity) -> bool;
     /// When its started, How it should Continue to run
     async fn should_continue(&self, mob: &MobEntity) -> bool;
+    /// Gets called once when the Goal starts running
+    async fn start(&self, _mob: &MobEntity) {}
+    /// Gets called once when the Goal stops running, either because it is done or because a
+    /// more important Goal took over
+    async fn stop(&self, _mob: &MobEntity) {}
     /// If the Goal is running, this gets called every tick
     async fn tick(&self, mob: &MobEntity);
+    /// What the Goal takes over while it runs
+    fn controls(&self) -> &'static [Control];
+}
+
+struct PrioritizedGoal {
+    priority: u8,
+    goal: Arc<dyn Goal>,
+    running: bool,
+}
+
+impl PrioritizedGoal {
+    fn shares_controls(&self, other: &Self) -> bool {
+        self.goal
+            .controls()
+            .iter()
+            .any(|control| other.goal.controls().contains(control))
+    }
+}
+
+/// The goals of a mob, where goals with a lower priority number are more important, like in
+/// vanilla
+#[derive(Default)]
+pub struct GoalSelector {
+    goals: Vec<PrioritizedGoal>,
+}
+
+impl GoalSelector {
+    pub fn add(&mut self, priority: u8, goal: Arc<dyn Goal>) {
+        let index = self
+            .goals
+            .partition_point(|other| other.priority <= priority);
+        self.goals.insert(
+            index,
+            PrioritizedGoal {
+                priority,
+                goal,
+                running: false,
+            },
+        );
+    }
+
+    /// Stops the goals which are done, starts the goals which can start and whose controls are
+    /// free or taken by less important goals, and ticks the running goals
+    pub async fn tick(&mut self, mob: &MobEntity) {
+        for goal in &mut self.goals {
+            if goal.running && !goal.goal.should_continue(mob).await {
+                goal.goal.stop(mob).await;
+                goal.running = false;
+            }
+        }
+
+        for index in 0..self.goals.len() {
+            let candidate = &self.goals[index];
+            if candidate.running {
+                continue;
+            }
+            let blocked = self.goals.iter().any(|other| {
+                other.running
+                    && other.priority <= candidate.priority
+                    && other.shares_controls(candidate)
+            });
+            if blocked || !candidate.goal.can_start(mob).await {
+                continue;
+            }
+            for other in 0..self.goals.len() {
+                if self.goals[other].running
+                    && self.goals[other].shares_controls(&self.goals[index])
+                {
+                    self.goals[other].goal.stop(mob).await;
+                    self.goals[other].running = false;
+                }
+            }
+            self.goals[index].goal.start(mob).await;
+            self.goals[index].running = true;
+        }
+
+        for goal in &self.goals {
+            if goal.running {
+                goal.goal.tick(mob).await;
+            }
+        }
+    }
+
+    /// Stops all running goals, e.g. when the mob stops thinking
+    pub async fn stop_all(&mut self, mob: &MobEntity) {
+        for goal in &mut self.goals {
+            if goal.running {
+                goal.goal.stop(mob).await;
+                goal.running = false;
+            }
+        }
+    }
 }
@@ -1,73 +1,101 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use tokio::sync::Mutex;
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_util::{Difficulty, GameMode};
+use rand::Rng;
 
-use crate::entity::{ai::path::NavigatorGoal, mob::MobEntity, player::Player};
+use crate::entity::{mob::MobEntity, player::Player};
 
-use super::Goal;
+use super::{Control, Goal};
 
+/// Mobs look for a target one in this many ticks, like in vanilla
+const RECIPROCAL_CHANCE: u32 = 10;
+
+/// Makes the mob target the closest player, which other goals like
+/// [`super::melee_attack::MeleeAttackGoal`] then go after
 pub struct TargetGoal {
-    // TODO: make this an entity
-    target: Mutex<Option<Arc<Player>>>,
     range: f64,
 }
 
 impl TargetGoal {
     #[must_use]
     pub fn new(range: f64) -> Self {
-        Self {
-            target: Mutex::new(None),
-            range,
+        Self { range }
+    }
+
+    /// Whether the mob may target the player. Mobs leave players alone in peaceful difficulty.
+    async fn can_target(&self, mob: &MobEntity, player: &Player) -> bool {
+        if BASIC_CONFIG.default_difficulty == Difficulty::Peaceful
+            || player.gamemode.load() == GameMode::Spectator
+            || player.living_entity.health.load() <= 0.0
+            || player.is_ignored_by_mobs()
+            || player.abilities.lock().await.invulnerable
+        {
+            return false;
         }
+        let mob_entity = &mob.living_entity.entity;
+        Arc::ptr_eq(&player.world().await, &*mob_entity.world.read().await)
+            && mob_entity
+                .pos
+                .load()
+                .squared_distance_to_vec(player.living_entity.entity.pos.load())
+                <= self.range * self.range
     }
 }
 
 #[async_trait]
 impl Goal for TargetGoal {
     async fn can_start(&self, mob: &MobEntity) -> bool {
-        // TODO: make this an entity
-        let mut target = self.target.lock().await;
-
-        // gets the closest entity (currently player)
-        *target = mob
-            .living_entity
-            .entity
+        if rand::thread_rng().gen_range(0..RECIPROCAL_CHANCE) != 0 {
+            return false;
+        }
+        // gets the closest player the mob may target
+        let entity = &mob.living_entity.entity;
+        let pos = entity.pos.load();
+        let mut players: Vec<_> = entity
             .world
             .read()
             .await
-            .get_closest_player(mob.living_entity.entity.pos.load(), self.range)
-            .await;
+            .get_nearby_players(pos, self.range)
+            .await
+            .into_values()
+            .collect();
+        players.sort_by(|a, b| {
+            let distance = |player: &Player| {
+                player
+                    .living_entity
+                    .entity
+                    .pos
+                    .load()
+                    .squared_distance_to_vec(pos)
+            };
+            distance(a).total_cmp(&distance(b))
+        });
         // we can't use filter, because of async clousrers
-        if let Some(player) = target.as_ref() {
-            if player.abilities.lock().await.invulnerable {
-                *target = None;
+        for player in players {
+            if self.can_target(mob, &player).await {
+                *mob.target.lock().await = Some(player);
+                return true;
             }
         }
-
-        target.is_some()
+        false
     }
+
     async fn should_continue(&self, mob: &MobEntity) -> bool {
-        // if an entity is found, lets check so its in range
-        if let Some(target) = self.target.lock().await.as_ref() {
-            let mob_pos = mob.living_entity.entity.pos.load();
-            let target_pos = target.living_entity.entity.pos.load();
-            let abilities = target.abilities.lock().await;
-            return !abilities.invulnerable
-                && mob_pos.squared_distance_to_vec(target_pos) <= (self.range * self.range);
-        }
-        false
+        let Some(player) = mob.target.lock().await.clone() else {
+            return false;
+        };
+        self.can_target(mob, &player).await
     }
-    async fn tick(&self, mob: &MobEntity) {
-        if let Some(target) = self.target.lock().await.as_ref() {
-            let mut navigator = mob.navigator.lock().await;
-            let target_player = target.living_entity.entity.pos.load();
 
-            navigator.set_progress(NavigatorGoal {
-                current_progress: mob.living_entity.entity.pos.load(),
-                destination: target_player,
-                speed: 0.1,
-            });
-        }
+    async fn stop(&self, mob: &MobEntity) {
+        *mob.target.lock().await = None;
+    }
+
+    async fn tick(&self, _mob: &MobEntity) {}
+
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Target]
     }
 }
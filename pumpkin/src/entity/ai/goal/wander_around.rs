@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use rand::Rng;
+
+use crate::entity::mob::MobEntity;
+
+use super::{Control, Goal};
+
+/// Mobs start wandering around one in this many ticks, like in vanilla
+const RECIPROCAL_CHANCE: u32 = 120;
+
+/// How far away mobs wander, horizontally and vertically, like in vanilla
+const HORIZONTAL_RANGE: i32 = 10;
+const VERTICAL_RANGE: i32 = 7;
+
+/// Makes the mob walk to a random place nearby from time to time
+pub struct WanderAroundGoal {
+    /// How much faster than normal the mob walks
+    speed: f64,
+}
+
+impl WanderAroundGoal {
+    #[must_use]
+    pub fn new(speed: f64) -> Self {
+        Self { speed }
+    }
+}
+
+#[async_trait]
+impl Goal for WanderAroundGoal {
+    async fn can_start(&self, mob: &MobEntity) -> bool {
+        let entity = &mob.living_entity.entity;
+        let destination = {
+            let mut rng = rand::thread_rng();
+            if rng.gen_range(0..RECIPROCAL_CHANCE) != 0 {
+                return false;
+            }
+            let offset = Vector3::new(
+                rng.gen_range(-HORIZONTAL_RANGE..=HORIZONTAL_RANGE),
+                rng.gen_range(-VERTICAL_RANGE..=VERTICAL_RANGE),
+                rng.gen_range(-HORIZONTAL_RANGE..=HORIZONTAL_RANGE),
+            );
+            BlockPos(entity.block_pos.load().0 + offset)
+        };
+        let mut navigator = mob.navigator.lock().await;
+        navigator.is_idle() && navigator.move_to(entity, destination, self.speed).await
+    }
+
+    async fn should_continue(&self, mob: &MobEntity) -> bool {
+        !mob.navigator.lock().await.is_idle()
+    }
+
+    async fn stop(&self, mob: &MobEntity) {
+        mob.navigator.lock().await.stop();
+    }
+
+    async fn tick(&self, _mob: &MobEntity) {}
+
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move]
+    }
+}
@@ -1,110 +1,493 @@
-use pumpkin_protocol::client::play::CUpdateEntityPos;
-use pumpkin_util::math::vector3::Vector3;
+//! Finds paths over the block grid with A* and walks mobs along them. Mobs walk up single
+//! blocks, drop down a few, go around fences and walls and stay out of closed doors, lava and
+//! fire.
 
-use crate::entity::living::LivingEntity;
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    f64::consts::SQRT_2,
+};
 
-#[derive(Default)]
-pub struct Navigator {
-    current_goal: Option<NavigatorGoal>,
+use async_trait::async_trait;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::block::registry::{
+    get_block_by_state_id, get_block_collision_shapes, get_state_property,
+};
+
+use crate::{
+    entity::{physics, Entity},
+    world::World,
+};
+
+/// The most nodes one search looks at, so mobs looking for unreachable places don't stall the
+/// server
+const MAX_VISITED: usize = 1000;
+
+/// How many blocks mobs walk down in one go, like in vanilla
+const MAX_DROP: i32 = 3;
+
+/// The extra cost of walking up a block, since mobs have to jump
+const STEP_UP_COST: f64 = 0.5;
+
+/// The extra cost of swimming, so mobs walk around water if they can, like in vanilla
+const WATER_COST: f64 = 8.0;
+
+/// How close to the center of a node mobs have to get before they walk to the next one
+const NODE_REACH: f64 = 0.5;
+
+/// After this many ticks without reaching the next node, mobs give up on the path
+const STUCK_TICKS: u32 = 60;
+
+/// The upwards speed of jumps, like in vanilla
+const JUMP_VELOCITY: f64 = 0.42;
+
+/// How mobs can move through a block
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BlockKind {
+    /// Nothing to bump into, like air, grass or open doors
+    Open,
+    /// Mobs can walk on top of it, but not through it
+    Solid,
+    /// Taller than a block, like fences and walls, so mobs can neither walk through them nor
+    /// step onto them
+    Fence,
+    /// Closed doors, which mobs can't open yet
+    ClosedDoor,
+    /// Mobs can swim through water, but avoid it
+    Water,
+    /// Mobs never walk into lava, fire or cacti
+    Danger,
+}
+
+impl BlockKind {
+    pub fn of(state_id: u16) -> Self {
+        let Some(block) = get_block_by_state_id(state_id) else {
+            return Self::Open;
+        };
+        match block.name.as_str() {
+            "water" => return Self::Water,
+            "lava" | "fire" | "soul_fire" | "cactus" | "sweet_berry_bush" => return Self::Danger,
+            name if name.ends_with("_door") => {
+                return if get_state_property(state_id, "open") == Some("true") {
+                    Self::Open
+                } else {
+                    Self::ClosedDoor
+                };
+            }
+            _ => {}
+        }
+        let shapes = get_block_collision_shapes(state_id).unwrap_or_default();
+        if shapes.is_empty() {
+            Self::Open
+        } else if shapes.iter().any(|shape| shape.max[1] > 1.0) {
+            Self::Fence
+        } else {
+            Self::Solid
+        }
+    }
 }
 
-pub struct NavigatorGoal {
-    pub current_progress: Vector3<f64>,
-    pub destination: Vector3<f64>,
-    pub speed: f64,
+/// Where the pathfinder gets the blocks from
+#[async_trait]
+pub trait BlockView: Send {
+    async fn kind(&mut self, position: BlockPos) -> BlockKind;
 }
 
-impl Navigator {
-    pub fn set_progress(&mut self, goal: NavigatorGoal) {
-        self.current_goal = Some(goal);
+/// The blocks of a world, each looked up only once per search
+pub struct WorldView<'a> {
+    world: &'a World,
+    cache: HashMap<BlockPos, BlockKind>,
+}
+
+impl<'a> WorldView<'a> {
+    pub fn new(world: &'a World) -> Self {
+        Self {
+            world,
+            cache: HashMap::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl BlockView for WorldView<'_> {
+    async fn kind(&mut self, position: BlockPos) -> BlockKind {
+        if let Some(kind) = self.cache.get(&position) {
+            return *kind;
+        }
+        // Unloaded blocks are treated like walls
+        let kind = self
+            .world
+            .get_block_state_id(&position)
+            .await
+            .map_or(BlockKind::Solid, BlockKind::of);
+        self.cache.insert(position, kind);
+        kind
     }
+}
 
-    pub fn cancel(&mut self) {
-        self.current_goal = None;
+/// The blocks a mob walks through, one after another
+pub struct Path {
+    nodes: Vec<BlockPos>,
+    next: usize,
+}
+
+impl Path {
+    /// Where the path ends, which is not always where the mob wanted to go when it can't get
+    /// there
+    pub fn end(&self) -> Option<BlockPos> {
+        self.nodes.last().copied()
     }
+}
 
-    pub async fn tick(&mut self, entity: &LivingEntity) {
-        if let Some(goal) = &mut self.current_goal {
-            // first lets check if we reached destination
-            if goal.current_progress == goal.destination {
-                // if yes, we are done here
-                self.current_goal = None;
-                return;
+/// A node waiting to be looked at, the one with the lowest estimated cost first
+struct OpenNode {
+    estimate: f64,
+    position: BlockPos,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate.total_cmp(&other.estimate) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+fn offset(position: BlockPos, x: i32, y: i32, z: i32) -> BlockPos {
+    BlockPos(position.0 + Vector3::new(x, y, z))
+}
+
+fn distance(from: BlockPos, to: BlockPos) -> f64 {
+    let delta = to.0.sub(&from.0);
+    Vector3::new(f64::from(delta.x), f64::from(delta.y), f64::from(delta.z)).length()
+}
+
+/// Whether a mob of the height fits into the position. Returns the extra cost of going there.
+async fn passable(view: &mut impl BlockView, position: BlockPos, height: i32) -> Option<f64> {
+    let mut cost = 0.0;
+    for y in 0..height {
+        match view.kind(offset(position, 0, y, 0)).await {
+            BlockKind::Open => {}
+            BlockKind::Water => cost = WATER_COST,
+            _ => return None,
+        }
+    }
+    Some(cost)
+}
+
+/// Whether a mob of the height can stand or swim at the position. Returns the extra cost of
+/// going there.
+async fn standable(view: &mut impl BlockView, position: BlockPos, height: i32) -> Option<f64> {
+    let cost = passable(view, position, height).await?;
+    let swimming = view.kind(position).await == BlockKind::Water;
+    (swimming || view.kind(offset(position, 0, -1, 0)).await == BlockKind::Solid).then_some(cost)
+}
+
+/// The nodes a mob can walk to from the node in one step, with what it costs
+async fn neighbors(view: &mut impl BlockView, node: BlockPos, height: i32) -> Vec<(BlockPos, f64)> {
+    const DIRECTIONS: [(i32, i32); 8] = [
+        (1, 0),
+        (-1, 0),
+        (0, 1),
+        (0, -1),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+    ];
+
+    let mut neighbors = Vec::new();
+    for (x, z) in DIRECTIONS {
+        let position = offset(node, x, 0, z);
+        if x != 0 && z != 0 {
+            // Diagonals only on flat ground, and without cutting corners
+            if passable(view, offset(node, x, 0, 0), height)
+                .await
+                .is_none()
+                || passable(view, offset(node, 0, 0, z), height)
+                    .await
+                    .is_none()
+            {
+                continue;
             }
+            if let Some(cost) = standable(view, position, height).await {
+                neighbors.push((position, SQRT_2 + cost));
+            }
+            continue;
+        }
 
-            // A star algorithm
-            let mut best_move = Vector3::new(0.0, 0.0, 0.0);
-            let mut lowest_cost = f64::MAX;
-
-            for x in -1..=1 {
-                for z in -1..=1 {
-                    let x = f64::from(x);
-                    let z = f64::from(z);
-                    let potential_pos = Vector3::new(
-                        goal.current_progress.x + x,
-                        goal.current_progress.y,
-                        goal.current_progress.z + z,
-                    );
-
-                    let node = Node::new(potential_pos);
-                    let cost = node.get_expense(goal.destination);
-
-                    if cost < lowest_cost {
-                        lowest_cost = cost;
-                        best_move = Vector3::new(x, 0.0, z);
+        if let Some(cost) = standable(view, position, height).await {
+            neighbors.push((position, 1.0 + cost));
+            continue;
+        }
+        match view.kind(position).await {
+            BlockKind::Solid => {
+                // Jump onto the block, if there is room above the mob
+                let above = offset(position, 0, 1, 0);
+                if view.kind(offset(node, 0, height, 0)).await != BlockKind::Open {
+                    continue;
+                }
+                if let Some(cost) = standable(view, above, height).await {
+                    neighbors.push((above, 1.0 + STEP_UP_COST + cost));
+                }
+            }
+            BlockKind::Open | BlockKind::Water => {
+                // Walk off the edge, if the fall isn't too deep
+                if passable(view, position, height).await.is_none() {
+                    continue;
+                }
+                for drop in 1..=MAX_DROP {
+                    let below = offset(position, 0, -drop, 0);
+                    if !matches!(view.kind(below).await, BlockKind::Open | BlockKind::Water) {
+                        break;
+                    }
+                    if let Some(cost) = standable(view, below, height).await {
+                        neighbors.push((below, 1.0 + f64::from(drop) + cost));
+                        break;
                     }
                 }
             }
+            _ => {}
+        }
+    }
+    neighbors
+}
+
+/// Finds the cheapest path for a mob of the height from the start to the end with A*. When the
+/// end can't be reached, the path leads as close to it as possible. Returns `None` if the mob
+/// can't get any closer.
+pub async fn find_path(
+    view: &mut impl BlockView,
+    start: BlockPos,
+    end: BlockPos,
+    height: i32,
+) -> Option<Path> {
+    let mut open = BinaryHeap::new();
+    let mut costs = HashMap::from([(start, 0.0)]);
+    let mut previous: HashMap<BlockPos, BlockPos> = HashMap::new();
+    let mut closest = (start, distance(start, end));
+    open.push(OpenNode {
+        estimate: closest.1,
+        position: start,
+    });
 
-            // this is important, first this saves us many packets when we don't actually move, and second this prevents division using zero
-            // when normalize
-            if best_move.x == 0.0 && best_move.z == 0.0 {
-                return;
+    let mut visited = 0;
+    while let Some(OpenNode { position, .. }) = open.pop() {
+        if position == end {
+            closest = (end, 0.0);
+            break;
+        }
+        visited += 1;
+        if visited > MAX_VISITED {
+            break;
+        }
+        let cost = costs[&position];
+        for (neighbor, step_cost) in neighbors(view, position, height).await {
+            let neighbor_cost = cost + step_cost;
+            if costs
+                .get(&neighbor)
+                .is_some_and(|&known| known <= neighbor_cost)
+            {
+                continue;
             }
-            // Update current progress based on the best move
-            goal.current_progress += best_move.normalize() * goal.speed;
-
-            // now lets move
-            entity.set_pos(goal.current_progress);
-            let pos = entity.entity.pos.load();
-            let last_pos = entity.last_pos.load();
-
-            entity
-                .entity
-                .world
-                .read()
-                .await
-                .broadcast_packet_all(&CUpdateEntityPos::new(
-                    entity.entity.entity_id.into(),
-                    Vector3::new(
-                        pos.x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
-                        pos.y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
-                        pos.z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
-                    ),
-                    entity
-                        .entity
-                        .on_ground
-                        .load(std::sync::atomic::Ordering::Relaxed),
-                ))
-                .await;
+            costs.insert(neighbor, neighbor_cost);
+            previous.insert(neighbor, position);
+            let remaining = distance(neighbor, end);
+            if remaining < closest.1 {
+                closest = (neighbor, remaining);
+            }
+            open.push(OpenNode {
+                estimate: neighbor_cost + remaining,
+                position: neighbor,
+            });
+        }
+    }
+
+    let (mut node, _) = closest;
+    if node == start {
+        return None;
+    }
+    let mut nodes = vec![node];
+    while let Some(&before) = previous.get(&node) {
+        if before == start {
+            break;
         }
+        nodes.push(before);
+        node = before;
     }
+    nodes.reverse();
+    Some(Path { nodes, next: 0 })
 }
 
-pub struct Node {
-    pub location: Vector3<f64>,
+/// Walks a mob along a path. The goals of the mob decide where it goes.
+pub struct Navigator {
+    path: Option<Path>,
+    /// How fast the mob walks normally, like the movement speed attribute in vanilla
+    movement_speed: f64,
+    /// How much faster than normal the mob walks along the current path
+    speed: f64,
+    /// Ticks since the mob reached the last node, to notice when it is stuck
+    ticks_on_node: u32,
 }
 
-impl Node {
+impl Navigator {
     #[must_use]
-    pub fn new(location: Vector3<f64>) -> Self {
-        Self { location }
+    pub fn new(movement_speed: f64) -> Self {
+        Self {
+            path: None,
+            movement_speed,
+            speed: 1.0,
+            ticks_on_node: 0,
+        }
     }
-    /// How expensive is it to go to a location
-    ///
-    /// Returns a f64, Higher = More Expensive
-    #[must_use]
-    pub fn get_expense(&self, end: Vector3<f64>) -> f64 {
-        self.location.squared_distance_to_vec(end).sqrt()
+
+    pub fn set_movement_speed(&mut self, movement_speed: f64) {
+        self.movement_speed = movement_speed;
+    }
+
+    /// Finds a path for the entity to the destination. Returns whether it can get any closer.
+    pub async fn move_to(&mut self, entity: &Entity, destination: BlockPos, speed: f64) -> bool {
+        let world = entity.world.read().await.clone();
+        let height = f64::from(entity.height()).ceil() as i32;
+        self.path = find_path(
+            &mut WorldView::new(&world),
+            entity.block_pos.load(),
+            destination,
+            height,
+        )
+        .await;
+        self.speed = speed;
+        self.ticks_on_node = 0;
+        self.path.is_some()
+    }
+
+    pub fn stop(&mut self) {
+        self.path = None;
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.path.is_none()
+    }
+
+    /// Where the current path ends
+    pub fn destination(&self) -> Option<BlockPos> {
+        self.path.as_ref().and_then(Path::end)
+    }
+
+    /// Turns the entity to the next node and accelerates it there. It moves with the rest of
+    /// its physics afterwards, so the players see it walk smoothly.
+    pub async fn tick(&mut self, entity: &Entity) {
+        let Some(path) = &mut self.path else {
+            return;
+        };
+        let pos = entity.pos.load();
+        let mut delta = Vector3::new(0.0, 0.0, 0.0);
+        while let Some(node) = path.nodes.get(path.next) {
+            let center = Vector3::new(
+                f64::from(node.0.x) + 0.5,
+                f64::from(node.0.y),
+                f64::from(node.0.z) + 0.5,
+            );
+            delta = center.sub(&pos);
+            if delta.horizontal_length() >= NODE_REACH || delta.y.abs() >= 1.0 {
+                break;
+            }
+            path.next += 1;
+            self.ticks_on_node = 0;
+        }
+        if path.next >= path.nodes.len() {
+            self.path = None;
+            return;
+        }
+        self.ticks_on_node += 1;
+        if self.ticks_on_node > STUCK_TICKS {
+            self.path = None;
+            return;
+        }
+
+        let horizontal = Vector3::new(delta.x, 0.0, delta.z);
+        if horizontal.length_squared() < 1.0e-6 {
+            return;
+        }
+        let direction = horizontal.normalize();
+        // Mobs accelerate by the square of their speed, like in vanilla
+        let speed = self.movement_speed * self.speed;
+        let mut velocity = entity.velocity.load();
+        velocity.x += direction.x * speed * speed * 0.98;
+        velocity.z += direction.z * speed * speed * 0.98;
+        if delta.y > 0.5 && entity.on_ground.load(std::sync::atomic::Ordering::Relaxed) {
+            velocity.y = JUMP_VELOCITY;
+        }
+        entity.velocity.store(velocity);
+
+        let yaw = (direction.z.atan2(direction.x) as f32).to_degrees() - 90.0;
+        entity.set_rotation(yaw, entity.pitch.load());
+        let world = entity.world.read().await.clone();
+        physics::send_rotation(entity, &world).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use async_trait::async_trait;
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::{find_path, BlockKind, BlockView};
+
+    /// A floor of solid blocks at y = 0 with the given blocks on top
+    struct Grid(HashMap<BlockPos, BlockKind>);
+
+    #[async_trait]
+    impl BlockView for Grid {
+        async fn kind(&mut self, position: BlockPos) -> BlockKind {
+            if position.0.y <= 0 {
+                return BlockKind::Solid;
+            }
+            self.0.get(&position).copied().unwrap_or(BlockKind::Open)
+        }
+    }
+
+    fn pos(x: i32, y: i32, z: i32) -> BlockPos {
+        BlockPos(Vector3::new(x, y, z))
+    }
+
+    #[tokio::test]
+    async fn walks_up_a_step() {
+        let mut grid = Grid((-5..=5).map(|z| (pos(2, 1, z), BlockKind::Solid)).collect());
+        let path = find_path(&mut grid, pos(0, 1, 0), pos(2, 2, 0), 2)
+            .await
+            .unwrap();
+        assert_eq!(path.end(), Some(pos(2, 2, 0)));
+        assert_eq!(path.nodes, vec![pos(1, 1, 0), pos(2, 2, 0)]);
+    }
+
+    #[tokio::test]
+    async fn goes_around_fences_and_closed_doors() {
+        let mut blocks: HashMap<_, _> =
+            (-3..=3).map(|z| (pos(2, 1, z), BlockKind::Fence)).collect();
+        blocks.insert(pos(2, 1, 0), BlockKind::ClosedDoor);
+        blocks.insert(pos(2, 2, 0), BlockKind::ClosedDoor);
+        let path = find_path(&mut Grid(blocks), pos(0, 1, 0), pos(4, 1, 0), 2)
+            .await
+            .unwrap();
+        assert_eq!(path.end(), Some(pos(4, 1, 0)));
+        assert!(path
+            .nodes
+            .iter()
+            .all(|node| node.0.z.abs() > 3 || node.0.x != 2));
     }
 }
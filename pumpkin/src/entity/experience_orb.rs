@@ -0,0 +1,333 @@
+use std::sync::{
+    atomic::{AtomicI16, AtomicI32, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::client::play::{CAddExperienceOrb, CTakeItemEntity};
+use pumpkin_util::{
+    math::{boundingbox::BoundingBox, vector3::Vector3},
+    GameMode,
+};
+use rand::Rng;
+
+use crate::{server::Server, world::World};
+
+use super::{living::LivingEntity, physics, player::Player, Entity, EntityBase, NBTStorage};
+
+/// The values orbs are split into, from the largest, like in vanilla. The client picks the size
+/// of an orb by its value.
+const ORB_VALUES: [i16; 10] = [2477, 1237, 617, 307, 149, 73, 37, 17, 7, 3];
+
+/// Orbs despawn after 5 minutes, like in vanilla
+const DESPAWN_AGE: i16 = 6000;
+
+/// How much faster orbs fall each tick
+const GRAVITY: f64 = 0.03;
+
+/// How close a player has to be for orbs to fly towards them
+const ATTRACTION_RANGE: f64 = 8.0;
+
+/// How many ticks a player has to wait between picking up orbs, like in vanilla
+const PICKUP_DELAY: u32 = 2;
+
+/// Orbs touching a player in a box this much larger are picked up, like in vanilla
+const PICKUP_RANGE: (f64, f64, f64) = (1.0, 0.5, 1.0);
+
+/// Orbs in a box this much larger are merged, like in vanilla
+const MERGE_RANGE: f64 = 0.5;
+
+/// How many ticks orbs wait between looking for others to merge with
+const MERGE_INTERVAL: i16 = 20;
+
+pub struct ExperienceOrbEntity {
+    entity: Entity,
+    /// The experience points a single orb gives
+    value: i16,
+    /// How many orbs of the same value were merged into this one. Each pickup takes one of them.
+    count: AtomicI32,
+    /// Ticks since the orb appeared, see [`DESPAWN_AGE`]
+    age: AtomicI16,
+}
+
+impl ExperienceOrbEntity {
+    pub fn new(entity: Entity, value: i16) -> Self {
+        Self {
+            entity,
+            value,
+            count: AtomicI32::new(1),
+            age: AtomicI16::new(0),
+        }
+    }
+
+    /// Spawns orbs worth the experience points at the position, split into the sizes vanilla
+    /// uses. Orbs are merged into orbs of the same value lying there, so e.g. a furnace or many
+    /// bottles o' enchanting don't create lots of entities.
+    pub async fn spawn(server: &Server, world: &Arc<World>, position: Vector3<f64>, amount: i32) {
+        let mut amount = amount;
+        while amount > 0 {
+            let value = orb_value(amount);
+            amount -= i32::from(value);
+            if Self::merge_into_existing(world, position, value).await {
+                continue;
+            }
+
+            let entity = server.add_entity(position, EntityType::EXPERIENCE_ORB, world);
+            {
+                let mut rng = rand::thread_rng();
+                entity.yaw.store(rng.gen::<f32>() * 360.0);
+                entity.velocity.store(Vector3::new(
+                    (rng.gen::<f64>() * 0.2 - 0.1) * 2.0,
+                    rng.gen::<f64>() * 0.2 * 2.0,
+                    (rng.gen::<f64>() * 0.2 - 0.1) * 2.0,
+                ));
+            }
+            world.spawn_entity(Arc::new(Self::new(entity, value))).await;
+        }
+    }
+
+    /// Adds an orb of the value to an orb lying at the position, if there is one
+    async fn merge_into_existing(world: &World, position: Vector3<f64>, value: i16) -> bool {
+        let area = BoundingBox::new(
+            position.add_raw(-MERGE_RANGE, -MERGE_RANGE, -MERGE_RANGE),
+            position.add_raw(MERGE_RANGE, MERGE_RANGE, MERGE_RANGE),
+        );
+        let entities = world.entities.read().await;
+        let existing = entities.values().find_map(|other| {
+            other.get_experience_orb().filter(|orb| {
+                orb.value == value
+                    && orb.count.load(Ordering::Relaxed) > 0
+                    && orb.entity.bounding_box.load().intersects(&area)
+            })
+        });
+        existing.is_some_and(|orb| {
+            orb.count.fetch_add(1, Ordering::Relaxed);
+            true
+        })
+    }
+
+    /// The packet which shows the orb to players
+    pub fn spawn_packet(&self) -> CAddExperienceOrb {
+        CAddExperienceOrb::new(
+            self.entity.entity_id.into(),
+            self.entity.pos.load(),
+            self.value,
+        )
+    }
+
+    /// Accelerates the orb towards the closest player in range, like in vanilla
+    async fn follow_player(&self, world: &World) {
+        let pos = self.entity.pos.load();
+        let closest = world
+            .players
+            .read()
+            .await
+            .values()
+            .filter(|player| player.gamemode.load() != GameMode::Spectator)
+            .map(|player| {
+                let entity = &player.living_entity.entity;
+                let target = entity.pos.load().add_raw(
+                    0.0,
+                    f64::from(entity.standing_eye_height) / 2.0,
+                    0.0,
+                );
+                target.sub(&pos)
+            })
+            .min_by(|a, b| a.length_squared().total_cmp(&b.length_squared()));
+        let Some(direction) = closest else {
+            return;
+        };
+        let distance = direction.length();
+        if distance >= ATTRACTION_RANGE || distance <= 0.0 {
+            return;
+        }
+        let strength = 1.0 - distance / ATTRACTION_RANGE;
+        let velocity = self.entity.velocity.load();
+        self.entity
+            .velocity
+            .store(velocity.add(&(direction.normalize() * (strength * strength * 0.1))));
+    }
+
+    /// Merges nearby orbs of the same value into this one
+    async fn merge_nearby(&self, world: &World) {
+        let area = self
+            .entity
+            .bounding_box
+            .load()
+            .expand(MERGE_RANGE, MERGE_RANGE, MERGE_RANGE);
+        let others: Vec<_> = world
+            .entities
+            .read()
+            .await
+            .values()
+            .filter(|other| {
+                other.get_entity().entity_uuid != self.entity.entity_uuid
+                    && other.get_entity().bounding_box.load().intersects(&area)
+            })
+            .cloned()
+            .collect();
+
+        for other in others {
+            let Some(other) = other.get_experience_orb() else {
+                continue;
+            };
+            if other.value != self.value || self.count.load(Ordering::Relaxed) == 0 {
+                continue;
+            }
+            let count = other.count.swap(0, Ordering::Relaxed);
+            if count == 0 {
+                continue;
+            }
+            self.count.fetch_add(count, Ordering::Relaxed);
+            self.age
+                .fetch_min(other.age.load(Ordering::Relaxed), Ordering::Relaxed);
+            other.entity.remove().await;
+        }
+    }
+
+    /// Gives the orb to a player touching it, one of the merged orbs at a time
+    async fn pickup(&self, world: &World) {
+        let area = self.entity.bounding_box.load();
+        let (x, y, z) = PICKUP_RANGE;
+        let player = world
+            .players
+            .read()
+            .await
+            .values()
+            .find(|player| {
+                player.gamemode.load() != GameMode::Spectator
+                    && player.living_entity.health.load() > 0.0
+                    && player.experience_pickup_delay.load(Ordering::Relaxed) == 0
+                    && player
+                        .living_entity
+                        .entity
+                        .bounding_box
+                        .load()
+                        .expand(x, y, z)
+                        .intersects(&area)
+            })
+            .cloned();
+        if let Some(player) = player {
+            self.pickup_by(world, &player).await;
+        }
+    }
+
+    async fn pickup_by(&self, world: &World, player: &Player) {
+        // Another player was faster
+        let taken = self
+            .count
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                (count > 0).then_some(count - 1)
+            });
+        let Ok(count) = taken else {
+            return;
+        };
+
+        player
+            .experience_pickup_delay
+            .store(PICKUP_DELAY, Ordering::Relaxed);
+        // Also makes the clients play the pickup sound
+        world
+            .broadcast_packet_all(&CTakeItemEntity::new(
+                self.entity.entity_id.into(),
+                player.entity_id().into(),
+                1.into(),
+            ))
+            .await;
+        // TODO: Repair items enchanted with Mending first, once there are enchantments
+        player.add_experience_points(i32::from(self.value)).await;
+        if count == 1 {
+            self.entity.remove().await;
+        }
+    }
+}
+
+#[async_trait]
+impl EntityBase for ExperienceOrbEntity {
+    async fn tick(&self, _server: &Server) {
+        let world = self.entity.world.read().await.clone();
+        if self.count.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+
+        let age = self.age.fetch_add(1, Ordering::Relaxed) + 1;
+        if age >= DESPAWN_AGE {
+            self.entity.remove().await;
+            return;
+        }
+
+        self.follow_player(&world).await;
+        physics::tick_movement(&self.entity, &world, GRAVITY).await;
+        if age % MERGE_INTERVAL == 1 {
+            self.merge_nearby(&world).await;
+        }
+        self.pickup(&world).await;
+    }
+
+    async fn save_nbt(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.entity.write_identity(&mut nbt);
+        self.write_nbt(&mut nbt).await;
+        Some(nbt)
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+
+    fn get_experience_orb(&self) -> Option<&ExperienceOrbEntity> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl NBTStorage for ExperienceOrbEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.entity.write_nbt(nbt).await;
+        nbt.put_short("Value", self.value);
+        nbt.put_int("Count", self.count.load(Ordering::Relaxed));
+        nbt.put_short("Age", self.age.load(Ordering::Relaxed));
+    }
+
+    async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
+        self.entity.read_nbt(nbt).await;
+        if let Some(value) = nbt.get_short("Value") {
+            self.value = value;
+        }
+        if let Some(count) = nbt.get_int("Count") {
+            *self.count.get_mut() = count.max(1);
+        }
+        if let Some(age) = nbt.get_short("Age") {
+            *self.age.get_mut() = age;
+        }
+    }
+}
+
+/// The value of the largest orb which is not worth more than the amount
+fn orb_value(amount: i32) -> i16 {
+    ORB_VALUES
+        .into_iter()
+        .find(|&value| amount >= i32::from(value))
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::orb_value;
+
+    #[test]
+    fn splits_into_vanilla_sizes() {
+        assert_eq!(orb_value(1), 1);
+        assert_eq!(orb_value(2), 1);
+        assert_eq!(orb_value(3), 3);
+        assert_eq!(orb_value(16), 7);
+        assert_eq!(orb_value(100), 73);
+        assert_eq!(orb_value(10_000), 2477);
+    }
+}
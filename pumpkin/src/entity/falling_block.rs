@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::CUpdateEntityPos;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{
+    block::registry::{get_block_by_state_id, get_state_by_state_id},
+    WORLD_LOWEST_Y,
+};
+
+use crate::{
+    block::blocks::falling::{self, is_water, solidified},
+    server::Server,
+};
+
+use super::{living::LivingEntity, Entity, EntityBase};
+
+/// How much faster a falling block falls each tick
+const GRAVITY: f64 = 0.04;
+
+/// How much of its speed a falling block keeps each tick, because of air resistance
+const DRAG: f64 = 0.98;
+
+/// A block falling down because there is nothing below it, like sand. Once it lands, it becomes
+/// a block again, see [`falling::land`].
+pub struct FallingBlockEntity {
+    entity: Entity,
+    /// The block state which is falling
+    state_id: u16,
+    /// Concrete powder lands as soon as it falls into water
+    solidifies: bool,
+}
+
+impl FallingBlockEntity {
+    pub fn new(entity: Entity, state_id: u16) -> Self {
+        let solidifies =
+            get_block_by_state_id(state_id).is_some_and(|block| solidified(block).is_some());
+        Self {
+            entity,
+            state_id,
+            solidifies,
+        }
+    }
+
+    /// Whether the falling block can't fall through the block
+    fn collides(state_id: u16) -> bool {
+        get_state_by_state_id(state_id).is_some_and(|state| !state.collision_shapes.is_empty())
+    }
+}
+
+#[async_trait]
+impl EntityBase for FallingBlockEntity {
+    async fn tick(&self, server: &Server) {
+        let entity = &self.entity;
+        let world = entity.world.read().await.clone();
+        let pos = entity.pos.load();
+        let velocity = entity.velocity.load();
+        let velocity_y = velocity.y - GRAVITY;
+        let target_y = pos.y + velocity_y;
+
+        // The blocks the falling block passes this tick, from the top
+        let x = pos.x.floor() as i32;
+        let z = pos.z.floor() as i32;
+        let bottom = (target_y.floor() as i32).max(i32::from(WORLD_LOWEST_Y));
+        let mut landing = None;
+        for y in (bottom..pos.y.ceil() as i32).rev() {
+            let position = BlockPos(Vector3::new(x, y, z));
+            let Ok(state_id) = world.get_block_state_id(&position).await else {
+                break;
+            };
+            if self.solidifies && is_water(state_id) {
+                landing = Some(position);
+                break;
+            }
+            if Self::collides(state_id) {
+                landing = Some(BlockPos(position.0.add_raw(0, 1, 0)));
+                break;
+            }
+        }
+
+        if let Some(landing) = landing {
+            entity.remove().await;
+            falling::land(server, &world, self.state_id, landing).await;
+            return;
+        }
+        // Like in vanilla, blocks falling out of the world are gone
+        if target_y < f64::from(WORLD_LOWEST_Y) - 64.0 {
+            entity.remove().await;
+            return;
+        }
+
+        let new_pos = Vector3::new(pos.x, target_y, pos.z);
+        entity.set_pos(new_pos);
+        entity
+            .velocity
+            .store(Vector3::new(0.0, velocity_y * DRAG, 0.0));
+        world
+            .broadcast_packet_all(&CUpdateEntityPos::new(
+                entity.entity_id.into(),
+                Vector3::new(0, (velocity_y * 4096.0) as i16, 0),
+                false,
+            ))
+            .await;
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+
+    fn spawn_data(&self) -> i32 {
+        i32::from(self.state_id)
+    }
+}
@@ -1,73 +1,265 @@
-use std::sync::{atomic::AtomicI8, Arc};
+use std::sync::atomic::{AtomicI16, Ordering};
 
 use async_trait::async_trait;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_protocol::{
     client::play::{CTakeItemEntity, MetaDataType, Metadata},
-    codec::{slot::Slot, var_int::VarInt},
+    codec::slot::Slot,
 };
+use pumpkin_util::{math::vector3::Vector3, GameMode};
 use pumpkin_world::item::ItemStack;
+use rand::Rng;
+use tokio::sync::{Mutex, MutexGuard};
 
-use super::{living::LivingEntity, player::Player, Entity, EntityBase};
+use crate::{block::blocks::container, server::Server, world::World};
+
+use super::{living::LivingEntity, physics, player::Player, Entity, EntityBase, NBTStorage};
+
+/// How many ticks items can't be picked up after they appeared, like in vanilla
+pub const DEFAULT_PICKUP_DELAY: i16 = 10;
+
+/// How many ticks items thrown by players can't be picked up, like in vanilla
+pub const THROWN_PICKUP_DELAY: i16 = 40;
+
+/// A pickup delay of this many ticks never runs out, like in vanilla
+const NEVER_PICKUP: i16 = i16::MAX;
+
+/// Items despawn after 5 minutes, like in vanilla
+const DESPAWN_AGE: i16 = 6000;
+
+/// An age of this many ticks never despawns, like in vanilla
+const NEVER_DESPAWN: i16 = i16::MIN;
+
+/// How much faster items fall each tick
+const GRAVITY: f64 = 0.04;
+
+/// Items touching a player in a box this much larger are picked up, like in vanilla
+const PICKUP_RANGE: (f64, f64, f64) = (1.0, 0.5, 1.0);
+
+/// Items in a box this much larger are merged, like in vanilla
+const MERGE_RANGE: (f64, f64, f64) = (0.5, 0.0, 0.5);
 
 pub struct ItemEntity {
     entity: Entity,
-    item: Slot,
-    id: u16,
-    count: u8,
-    pickup_delay: AtomicI8,
+    /// The items of the entity. An empty stack means they were picked up, even if the entity was
+    /// not removed yet.
+    stack: Mutex<ItemStack>,
+    pickup_delay: AtomicI16,
+    /// Ticks since the items were dropped, see [`DESPAWN_AGE`]
+    age: AtomicI16,
 }
 
 impl ItemEntity {
     pub fn new(entity: Entity, stack: &ItemStack) -> Self {
-        let slot = Slot::from(stack);
         Self {
             entity,
-            id: stack.item.id,
-            count: stack.item_count,
-            item: slot,
-            pickup_delay: AtomicI8::new(10), // Vanilla
+            stack: Mutex::new(stack.clone()),
+            pickup_delay: AtomicI16::new(DEFAULT_PICKUP_DELAY),
+            age: AtomicI16::new(0),
         }
     }
+
+    /// Sets for how many ticks the items can't be picked up, e.g. [`THROWN_PICKUP_DELAY`]
+    pub fn set_pickup_delay(&self, ticks: i16) {
+        self.pickup_delay.store(ticks, Ordering::Relaxed);
+    }
+
     pub async fn send_meta_packet(&self) {
+        let stack = self.stack.lock().await;
+        self.send_stack(&stack).await;
+    }
+
+    async fn send_stack(&self, stack: &ItemStack) {
         self.entity
-            .send_meta_data(Metadata::new(8, MetaDataType::ItemStack, &self.item))
+            .send_meta_data(Metadata::new(
+                8,
+                MetaDataType::ItemStack,
+                &Slot::from(stack),
+            ))
             .await;
     }
+
+    /// Locks the items of the entity to take some of them, e.g. by a hopper. Afterwards,
+    /// [`ItemEntity::stack_changed`] has to be called with the changed stack.
+    pub async fn lock_stack(&self) -> MutexGuard<'_, ItemStack> {
+        self.stack.lock().await
+    }
+
+    /// Shows the players the changed items of the entity, or removes it if none are left
+    pub async fn stack_changed(&self, stack: &ItemStack) {
+        if stack.item_count == 0 {
+            self.entity.remove().await;
+        } else {
+            self.send_stack(stack).await;
+        }
+    }
+
+    /// Merges the items into nearby items of the same kind, the smaller stack into the larger
+    async fn merge_nearby(&self, world: &World) {
+        if self.pickup_delay.load(Ordering::Relaxed) == NEVER_PICKUP
+            || self.age.load(Ordering::Relaxed) == NEVER_DESPAWN
+        {
+            return;
+        }
+        let (x, y, z) = MERGE_RANGE;
+        let area = self.entity.bounding_box.load().expand(x, y, z);
+        let others: Vec<_> = world
+            .entities
+            .read()
+            .await
+            .values()
+            .filter(|other| {
+                other.get_entity().entity_uuid != self.entity.entity_uuid
+                    && other.get_item_entity().is_some()
+                    && other.get_entity().bounding_box.load().intersects(&area)
+            })
+            .cloned()
+            .collect();
+
+        for other in others {
+            let Some(other) = other.get_item_entity() else {
+                continue;
+            };
+            if other.pickup_delay.load(Ordering::Relaxed) == NEVER_PICKUP
+                || other.age.load(Ordering::Relaxed) == NEVER_DESPAWN
+            {
+                continue;
+            }
+            let mut stack = self.stack.lock().await;
+            // A hopper may be taking the other items right now
+            let Ok(mut other_stack) = other.stack.try_lock() else {
+                continue;
+            };
+            let max_stack_size = stack.item.components.max_stack_size;
+            if stack.item_count == 0
+                || other_stack.item_count == 0
+                || *stack != *other_stack
+                || u16::from(stack.item_count) + u16::from(other_stack.item_count)
+                    > u16::from(max_stack_size)
+            {
+                continue;
+            }
+
+            let (into, from, into_stack, from_stack) = if stack.item_count >= other_stack.item_count
+            {
+                (self, other, &mut *stack, &mut *other_stack)
+            } else {
+                (other, self, &mut *other_stack, &mut *stack)
+            };
+            into_stack.item_count += from_stack.item_count;
+            from_stack.item_count = 0;
+            into.pickup_delay
+                .fetch_max(from.pickup_delay.load(Ordering::Relaxed), Ordering::Relaxed);
+            into.age
+                .fetch_min(from.age.load(Ordering::Relaxed), Ordering::Relaxed);
+            into.send_stack(into_stack).await;
+            from.entity.remove().await;
+            if stack.item_count == 0 {
+                return;
+            }
+        }
+    }
+
+    /// Gives the items to a player close enough to pick them up. Players with a full inventory
+    /// only take what fits.
+    async fn pickup(&self, world: &World) {
+        let area = self.entity.bounding_box.load();
+        let (x, y, z) = PICKUP_RANGE;
+        let players: Vec<_> = world
+            .players
+            .read()
+            .await
+            .values()
+            .filter(|player| {
+                player.gamemode.load() != GameMode::Spectator
+                    && player.living_entity.health.load() > 0.0
+                    && player
+                        .living_entity
+                        .entity
+                        .bounding_box
+                        .load()
+                        .expand(x, y, z)
+                        .intersects(&area)
+            })
+            .cloned()
+            .collect();
+        for player in players {
+            if self.pickup_by(world, &player).await {
+                return;
+            }
+        }
+    }
+
+    /// Puts as many of the items as fit into the inventory of the player. Returns whether all
+    /// of them were taken.
+    async fn pickup_by(&self, world: &World, player: &Player) -> bool {
+        let mut stack = self.stack.lock().await;
+        // Another player or a hopper was faster
+        if stack.item_count == 0 {
+            return true;
+        }
+        let mut inventory = player.inventory().lock().await;
+        let mut slots: Vec<_> = inventory.slots_with_hotbar_first().collect();
+        let rest = container::add_to_slots(&mut slots, |_| true, stack.clone());
+        drop(slots);
+        drop(inventory);
+        let rest_count = rest.map_or(0, |rest| rest.item_count);
+        let picked_up = stack.item_count - rest_count;
+        if picked_up == 0 {
+            return false;
+        }
+
+        player.set_container_content(None).await;
+        world
+            .broadcast_packet_all(&CTakeItemEntity::new(
+                self.entity.entity_id.into(),
+                player.entity_id().into(),
+                i32::from(picked_up).into(),
+            ))
+            .await;
+        stack.item_count = rest_count;
+        self.stack_changed(&stack).await;
+        rest_count == 0
+    }
 }
 
 #[async_trait]
 impl EntityBase for ItemEntity {
-    async fn tick(&self) {
-        if self.pickup_delay.load(std::sync::atomic::Ordering::Relaxed) > 0 {
-            self.pickup_delay
-                .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+    async fn tick(&self, _server: &Server) {
+        let world = self.entity.world.read().await.clone();
+        if self.stack.lock().await.item_count == 0 {
+            return;
         }
-    }
-    async fn on_player_collision(&self, player: Arc<Player>) {
-        if self.pickup_delay.load(std::sync::atomic::Ordering::Relaxed) == 0 {
-            let mut inv = player.inventory.lock().await;
-            // Check if we have space in inv
-            if let Some(slot) = inv.collect_item_slot(self.id) {
-                let mut item = self.item.clone();
-                if let Some(stack) = inv.get_slot(slot).unwrap() {
-                    // If we merge into an existing stack lets increase its count
-                    stack.item_count += self.count;
-                    // Since we set the slot with the item, we need to also have the new item count,
-                    // So existing count + self.count
-                    item.item_count = VarInt(i32::from(stack.item_count));
-                }
-                player.update_single_slot(&mut inv, slot as i16, item).await;
-                player
-                    .client
-                    .send_packet(&CTakeItemEntity::new(
-                        self.entity.entity_id.into(),
-                        player.entity_id().into(),
-                        1.into(),
-                    ))
-                    .await;
+
+        let age = self.age.load(Ordering::Relaxed);
+        if age != NEVER_DESPAWN {
+            if age + 1 >= DESPAWN_AGE {
                 self.entity.remove().await;
+                return;
             }
+            self.age.store(age + 1, Ordering::Relaxed);
+        }
+        let pickup_delay = self.pickup_delay.load(Ordering::Relaxed);
+        if pickup_delay > 0 && pickup_delay != NEVER_PICKUP {
+            self.pickup_delay.store(pickup_delay - 1, Ordering::Relaxed);
+        }
+
+        let moved = physics::tick_movement(&self.entity, &world, GRAVITY).await;
+        // Like in vanilla, items lying still look for others to merge with less often
+        let merge_interval = if moved { 2 } else { 40 };
+        if age % merge_interval == 0 {
+            self.merge_nearby(&world).await;
         }
+        if self.pickup_delay.load(Ordering::Relaxed) == 0 {
+            self.pickup(&world).await;
+        }
+    }
+
+    async fn save_nbt(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.entity.write_identity(&mut nbt);
+        self.write_nbt(&mut nbt).await;
+        Some(nbt)
     }
 
     fn get_entity(&self) -> &Entity {
@@ -77,4 +269,58 @@ impl EntityBase for ItemEntity {
     fn get_living_entity(&self) -> Option<&LivingEntity> {
         None
     }
+
+    fn get_item_entity(&self) -> Option<&ItemEntity> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl NBTStorage for ItemEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.entity.write_nbt(nbt).await;
+        nbt.put(
+            "Item",
+            NbtTag::Compound(self.stack.lock().await.write_nbt()),
+        );
+        nbt.put_short("PickupDelay", self.pickup_delay.load(Ordering::Relaxed));
+        nbt.put_short("Age", self.age.load(Ordering::Relaxed));
+    }
+
+    async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
+        self.entity.read_nbt(nbt).await;
+        if let Some(stack) = nbt.get_compound("Item").and_then(ItemStack::read_nbt) {
+            *self.stack.get_mut() = stack;
+        }
+        if let Some(pickup_delay) = nbt.get_short("PickupDelay") {
+            *self.pickup_delay.get_mut() = pickup_delay.max(0);
+        }
+        if let Some(age) = nbt.get_short("Age") {
+            *self.age.get_mut() = age;
+        }
+    }
+}
+
+/// The velocity of items which are dropped at a position, like the drops of a broken block, so
+/// they jump up a little in a random direction
+pub fn random_velocity() -> Vector3<f64> {
+    let mut rng = rand::thread_rng();
+    Vector3::new(
+        rng.gen::<f64>() * 0.2 - 0.1,
+        0.2,
+        rng.gen::<f64>() * 0.2 - 0.1,
+    )
+}
+
+/// The velocity of items a player throws, in the direction they look, like in vanilla
+pub fn throw_velocity(yaw: f32, pitch: f32) -> Vector3<f64> {
+    let mut rng = rand::thread_rng();
+    let (yaw, pitch) = (f64::from(yaw).to_radians(), f64::from(pitch).to_radians());
+    let angle = rng.gen::<f64>() * std::f64::consts::TAU;
+    let spread = rng.gen::<f64>() * 0.02;
+    Vector3::new(
+        -yaw.sin() * pitch.cos() * 0.3 + angle.cos() * spread,
+        -pitch.sin() * 0.3 + 0.1 + (rng.gen::<f64>() - rng.gen::<f64>()) * 0.1,
+        yaw.cos() * pitch.cos() * 0.3 + angle.sin() * spread,
+    )
 }
@@ -0,0 +1,43 @@
+use std::sync::atomic::{AtomicI32, Ordering};
+
+use async_trait::async_trait;
+
+use crate::server::Server;
+
+use super::{living::LivingEntity, Entity, EntityBase};
+
+/// How many ticks a lightning bolt is visible before it disappears
+const LIFETIME_TICKS: i32 = 3;
+
+/// The bolt of a lightning strike. Clients draw and flash it while it exists, the effects of the
+/// strike happen in [`crate::world::lightning::strike`].
+pub struct LightningBolt {
+    entity: Entity,
+    life: AtomicI32,
+}
+
+impl LightningBolt {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            life: AtomicI32::new(LIFETIME_TICKS),
+        }
+    }
+}
+
+#[async_trait]
+impl EntityBase for LightningBolt {
+    async fn tick(&self, _server: &Server) {
+        if self.life.fetch_sub(1, Ordering::Relaxed) <= 1 {
+            self.entity.remove().await;
+        }
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+}
@@ -1,20 +1,63 @@
-use std::sync::atomic::AtomicI32;
+use std::sync::atomic::{AtomicI32, Ordering};
 
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
-use pumpkin_data::{damage::DamageType, sound::Sound};
+use pumpkin_data::{
+    damage::DamageType,
+    entity::{EntityPose, EntityType},
+    sound::Sound,
+};
 use pumpkin_nbt::tag::NbtTag;
 use pumpkin_protocol::{
     client::play::{
-        CDamageEvent, CEntityStatus, CSetEquipment, EquipmentSlot, MetaDataType, Metadata,
+        Animation, CDamageEvent, CEntityAnimation, CEntityStatus, CSetEquipment, EquipmentSlot,
+        MetaDataType, Metadata,
     },
     codec::slot::Slot,
 };
-use pumpkin_util::math::vector3::Vector3;
-use pumpkin_world::item::ItemStack;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{
+    block::registry::get_block_by_state_id,
+    item::ItemStack,
+    light::{self, LightKind},
+};
+
+use crate::{
+    block::blocks::{
+        bed,
+        fire::FIRES,
+        fluid::{Fluid, FluidState},
+    },
+    world::World,
+};
 
 use super::{Entity, EntityId, NBTStorage};
 
+/// The metadata index of the bed a living entity is sleeping in
+const SLEEPING_POSITION_INDEX: u8 = 14;
+
+/// How many ticks an entity burns after touching fire, like in vanilla
+const FIRE_TICKS: i32 = 8 * 20;
+
+/// How many ticks an entity burns after touching lava, like in vanilla
+const LAVA_FIRE_TICKS: i32 = 15 * 20;
+
+/// How many ticks an undead mob burns after being in the sunlight, like in vanilla
+const DAYLIGHT_FIRE_TICKS: i32 = 8 * 20;
+
+/// How many ticks an entity counts as hurt by a player after being hit, like in vanilla
+const HURT_BY_PLAYER_TICKS: i32 = 100;
+
+/// The mobs which catch fire in the sunlight
+const BURNS_IN_DAYLIGHT: [EntityType; 6] = [
+    EntityType::ZOMBIE,
+    EntityType::ZOMBIE_VILLAGER,
+    EntityType::DROWNED,
+    EntityType::SKELETON,
+    EntityType::STRAY,
+    EntityType::PHANTOM,
+];
+
 /// Represents a living entity within the game world.
 ///
 /// This struct encapsulates the core properties and behaviors of living entities, including players, mobs, and other creatures.
@@ -31,6 +74,11 @@ pub struct LivingEntity {
     pub health: AtomicCell<f32>,
     /// The distance the entity has been falling
     pub fall_distance: AtomicCell<f32>,
+    /// The head of the bed the entity is sleeping in, if it is sleeping
+    pub sleeping_position: AtomicCell<Option<BlockPos>>,
+    /// Ticks left in which the entity counts as hurt by a player, so it drops experience when
+    /// it dies, like in vanilla
+    pub last_hurt_by_player: AtomicI32,
 }
 impl LivingEntity {
     pub const fn new(entity: Entity) -> Self {
@@ -41,6 +89,8 @@ impl LivingEntity {
             last_damage_taken: AtomicCell::new(0.0),
             health: AtomicCell::new(20.0),
             fall_distance: AtomicCell::new(0.0),
+            sleeping_position: AtomicCell::new(None),
+            last_hurt_by_player: AtomicI32::new(0),
         }
     }
 
@@ -106,6 +156,7 @@ impl LivingEntity {
         if self.entity.is_invulnerable_to(&damage_type) {
             return false;
         }
+        self.wake_up().await;
 
         self.entity
             .world
@@ -120,6 +171,14 @@ impl LivingEntity {
             ))
             .await;
 
+        if source
+            .or(cause)
+            .is_some_and(|entity| entity.entity_type == EntityType::PLAYER)
+        {
+            self.last_hurt_by_player
+                .store(HURT_BY_PLAYER_TICKS, Ordering::Relaxed);
+        }
+
         let new_health = (self.health.load() - amount).max(0.0);
 
         if new_health == 0.0 {
@@ -188,6 +247,155 @@ impl LivingEntity {
         }
     }
 
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping_position.load().is_some()
+    }
+
+    /// Lays the entity down in the bed with its head at the position. The sleeping position is
+    /// synced to all players, which then show the entity lying in the bed.
+    pub async fn start_sleeping(&self, head: BlockPos) {
+        let world = self.entity.world.read().await.clone();
+        bed::set_occupied(&world, &head, true).await;
+        self.set_pos(Vector3::new(
+            f64::from(head.0.x) + 0.5,
+            f64::from(head.0.y) + 0.6875,
+            f64::from(head.0.z) + 0.5,
+        ));
+        self.entity.set_pose(EntityPose::Sleeping).await;
+        self.sleeping_position.store(Some(head));
+        self.entity
+            .send_meta_data(Metadata::new(
+                SLEEPING_POSITION_INDEX,
+                MetaDataType::OptionalBlockPos,
+                Some(head),
+            ))
+            .await;
+    }
+
+    /// Gets the entity out of its bed, if it is sleeping
+    pub async fn wake_up(&self) {
+        let Some(head) = self.sleeping_position.load() else {
+            return;
+        };
+        let world = self.entity.world.read().await.clone();
+        // The client gets out of the bed itself when it sees this animation, so it has to be sent
+        // while the sleeping position is still known
+        world
+            .broadcast_packet_all(&CEntityAnimation::new(
+                self.entity_id().into(),
+                Animation::LeaveBed as u8,
+            ))
+            .await;
+        bed::set_occupied(&world, &head, false).await;
+        self.sleeping_position.store(None);
+        self.entity.set_pose(EntityPose::Standing).await;
+        self.entity
+            .send_meta_data(Metadata::new(
+                SLEEPING_POSITION_INDEX,
+                MetaDataType::OptionalBlockPos,
+                None::<BlockPos>,
+            ))
+            .await;
+    }
+
+    /// Damages the entity unless it was damaged recently, see [`Self::check_damage`]
+    async fn damage_once(&self, amount: f32, damage_type: DamageType) {
+        if self.check_damage(amount) {
+            self.damage(amount, damage_type).await;
+        }
+    }
+
+    /// Whether the entity catches fire in the sunlight this tick. Like in vanilla, this only
+    /// happens to some undead mobs, which are safe in water, in the rain and under a roof.
+    async fn is_burning_in_daylight(&self, world: &World, in_water: bool) -> bool {
+        let entity = &self.entity;
+        if in_water || !BURNS_IN_DAYLIGHT.contains(&entity.entity_type) {
+            return false;
+        }
+        // The chance is about the one of vanilla in full sunlight
+        if rand::random::<f32>() * 30.0 >= 1.2 || !world.is_day().await {
+            return false;
+        }
+        let position = entity.pos.load();
+        let eyes = BlockPos(Vector3::new(
+            position.x.floor() as i32,
+            (position.y + f64::from(entity.standing_eye_height)).floor() as i32,
+            position.z.floor() as i32,
+        ));
+        world.get_light(LightKind::Sky, &eyes).await == light::MAX_LIGHT
+            && !world.is_raining_at(&eyes).await
+    }
+
+    /// Sets the entity on fire when it touches fire or lava, or is an undead mob in the sunlight,
+    /// and puts it out in water or rain. Burning entities take damage once a second.
+    pub async fn tick_fire(&self) {
+        if self.health.load() <= 0.0 {
+            return;
+        }
+        let entity = &self.entity;
+        let world = entity.world.read().await.clone();
+
+        // Looks at all blocks the entity touches
+        let bounding_box = entity.bounding_box.load();
+        let (mut in_water, mut in_lava, mut fire_damage) = (false, false, None);
+        let min = bounding_box.min.add_raw(1.0e-3, 1.0e-3, 1.0e-3);
+        let max = bounding_box.max.add_raw(-1.0e-3, -1.0e-3, -1.0e-3);
+        for x in min.x.floor() as i32..=max.x.floor() as i32 {
+            for y in min.y.floor() as i32..=max.y.floor() as i32 {
+                for z in min.z.floor() as i32..=max.z.floor() as i32 {
+                    let position = BlockPos(Vector3::new(x, y, z));
+                    let Ok(state_id) = world.get_block_state_id(&position).await else {
+                        continue;
+                    };
+                    match FluidState::from_state_id(state_id).map(|state| state.fluid) {
+                        Some(Fluid::Water) => in_water = true,
+                        Some(Fluid::Lava) => in_lava = true,
+                        None => {}
+                    }
+                    match get_block_by_state_id(state_id).map(|block| block.name.as_str()) {
+                        Some("soul_fire") => fire_damage = Some(2.0),
+                        Some(name) if FIRES.contains(&name) => {
+                            fire_damage = fire_damage.or(Some(1.0));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        if self.is_burning_in_daylight(&world, in_water).await {
+            entity.set_on_fire_for(DAYLIGHT_FIRE_TICKS).await;
+        }
+        if in_water || (entity.is_on_fire() && world.is_raining_at(&entity.block_pos.load()).await)
+        {
+            entity.extinguish().await;
+        }
+        let fire_immune = entity.entity_type.fire_immune;
+        if in_lava {
+            entity.set_on_fire_for(LAVA_FIRE_TICKS).await;
+            if !fire_immune {
+                self.damage_once(4.0, DamageType::LAVA).await;
+            }
+        } else if let Some(damage) = fire_damage.filter(|_| !in_water) {
+            entity.set_on_fire_for(FIRE_TICKS).await;
+            if !fire_immune {
+                self.damage_once(damage, DamageType::IN_FIRE).await;
+            }
+        }
+
+        let fire_ticks = entity.fire_ticks.load(Ordering::Relaxed);
+        if fire_ticks <= 0 {
+            return;
+        }
+        if fire_ticks % 20 == 0 && !in_lava {
+            self.damage_once(1.0, DamageType::ON_FIRE).await;
+        }
+        if entity.fire_ticks.fetch_sub(1, Ordering::Relaxed) == 1 {
+            // The entity stopped burning
+            entity.send_flags().await;
+        }
+    }
+
     fn get_fall_sound(distance: i32) -> Sound {
         if distance > 4 {
             Sound::EntityGenericBigFall
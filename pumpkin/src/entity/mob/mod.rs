@@ -1,44 +1,93 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
 
 use async_trait::async_trait;
-use pumpkin_data::entity::EntityType;
+use pumpkin_data::entity::{EntityType, SpawnGroup};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::client::play::EquipmentSlot;
 use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::item::ItemStack;
+use rand::Rng;
 use tokio::sync::Mutex;
 use zombie::Zombie;
 
 use crate::{server::Server, world::World};
 
 use super::{
-    ai::{goal::Goal, path::Navigator},
+    ai::{
+        goal::{Goal, GoalSelector},
+        path::Navigator,
+    },
+    experience_orb::ExperienceOrbEntity,
     living::LivingEntity,
-    Entity, EntityBase,
+    physics,
+    player::Player,
+    Entity, EntityBase, NBTStorage,
 };
 
 pub mod zombie;
 
+/// How many ticks dead mobs lie on the ground before they disappear, like in vanilla
+const DEATH_TICKS: u32 = 20;
+
+/// How much faster mobs fall each tick, like in vanilla
+const GRAVITY: f64 = 0.08;
+
+/// How fast mobs walk unless their type says otherwise, like the movement speed attribute in
+/// vanilla
+const DEFAULT_MOVEMENT_SPEED: f64 = 0.25;
+
 pub struct MobEntity {
     pub living_entity: LivingEntity,
-    pub goals: Mutex<Vec<(Arc<dyn Goal>, bool)>>,
+    pub goals: Mutex<GoalSelector>,
     pub navigator: Mutex<Navigator>,
+    // TODO: make this an entity
+    /// The player the mob goes after, chosen by its target goal
+    pub target: Mutex<Option<Arc<Player>>>,
+    /// Mobs without AI don't think or move at all, like with the `NoAI` tag in vanilla
+    pub no_ai: AtomicBool,
+    /// The items held in the main hand and the off hand
+    pub hand_items: Mutex<[Option<ItemStack>; 2]>,
+    /// The armor worn on the feet, legs, chest and head
+    pub armor_items: Mutex<[Option<ItemStack>; 4]>,
+    /// How many ticks the mob has been dead, see [`DEATH_TICKS`]
+    pub death_ticks: AtomicU32,
 }
 
 #[async_trait]
 impl EntityBase for MobEntity {
-    async fn tick(&self) {
-        let mut goals = self.goals.lock().await;
-        for (goal, running) in goals.iter_mut() {
-            if *running {
-                if goal.should_continue(self).await {
-                    goal.tick(self).await;
-                } else {
-                    *running = false;
-                }
-            } else {
-                *running = goal.can_start(self).await;
-            }
+    async fn tick(&self, server: &Server) {
+        if self.living_entity.entity.frozen.load(Ordering::Relaxed) {
+            return;
+        }
+        if self.living_entity.health.load() <= 0.0 {
+            self.tick_death(server).await;
+            return;
         }
-        let mut navigator = self.navigator.lock().await;
-        navigator.tick(&self.living_entity).await;
+        let _ = self.living_entity.last_hurt_by_player.fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |ticks| (ticks > 0).then_some(ticks - 1),
+        );
+        if self.no_ai.load(Ordering::Relaxed) {
+            self.goals.lock().await.stop_all(self).await;
+            return;
+        }
+        self.goals.lock().await.tick(self).await;
+
+        let entity = &self.living_entity.entity;
+        self.navigator.lock().await.tick(entity).await;
+        let world = entity.world.read().await.clone();
+        physics::tick_movement(entity, &world, GRAVITY).await;
+    }
+
+    async fn save_nbt(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.living_entity.entity.write_identity(&mut nbt);
+        self.write_nbt(&mut nbt).await;
+        Some(nbt)
     }
 
     fn get_entity(&self) -> &Entity {
@@ -57,22 +106,139 @@ pub async fn from_type(
     world: &Arc<World>,
 ) -> Arc<dyn EntityBase> {
     let entity = server.add_entity(position, entity_type, world);
-    let mob = MobEntity {
-        living_entity: LivingEntity::new(entity),
-        goals: Mutex::new(vec![]),
-        navigator: Mutex::new(Navigator::default()),
-    };
-    #[expect(clippy::single_match)]
-    match entity_type {
-        EntityType::ZOMBIE => Zombie::make(&mob).await,
-        // TODO
-        _ => (),
-    }
-    Arc::new(mob)
+    Arc::new(MobEntity::new(entity).await)
 }
 
 impl MobEntity {
-    pub async fn goal<T: Goal + 'static>(&self, goal: T) {
-        self.goals.lock().await.push((Arc::new(goal), false));
+    /// Creates a mob with the goals of its entity type
+    pub async fn new(entity: Entity) -> Self {
+        let entity_type = entity.entity_type;
+        let mob = Self {
+            living_entity: LivingEntity::new(entity),
+            goals: Mutex::new(GoalSelector::default()),
+            navigator: Mutex::new(Navigator::new(DEFAULT_MOVEMENT_SPEED)),
+            target: Mutex::new(None),
+            no_ai: AtomicBool::new(false),
+            hand_items: Mutex::new([None, None]),
+            armor_items: Mutex::new([None, None, None, None]),
+            death_ticks: AtomicU32::new(0),
+        };
+        #[expect(clippy::single_match)]
+        match entity_type {
+            EntityType::ZOMBIE => Zombie::make(&mob).await,
+            // TODO
+            _ => (),
+        }
+        mob
+    }
+
+    /// Sends the held items and the armor of the mob to all players
+    pub async fn send_equipment(&self) {
+        let hand_items = self.hand_items.lock().await.clone();
+        let armor_items = self.armor_items.lock().await.clone();
+        let slots = [EquipmentSlot::MainHand, EquipmentSlot::OffHand]
+            .into_iter()
+            .zip(hand_items)
+            .chain(
+                [
+                    EquipmentSlot::Feet,
+                    EquipmentSlot::Legs,
+                    EquipmentSlot::Chest,
+                    EquipmentSlot::Head,
+                ]
+                .into_iter()
+                .zip(armor_items),
+            );
+        let equipment: Vec<_> = slots
+            .filter_map(|(slot, stack)| stack.map(|stack| (slot, stack)))
+            .collect();
+        if !equipment.is_empty() {
+            self.living_entity.send_equipment_changes(&equipment).await;
+        }
+    }
+
+    /// Adds a goal, where goals with a lower priority number are more important
+    pub async fn goal<T: Goal + 'static>(&self, priority: u8, goal: T) {
+        self.goals.lock().await.add(priority, Arc::new(goal));
+    }
+
+    /// Turns the head of the mob to the position
+    pub async fn look_at(&self, target: Vector3<f64>) {
+        let entity = &self.living_entity.entity;
+        // The entity looks from its feet, while mobs look with their eyes
+        entity
+            .look_at(target.add_raw(0.0, -f64::from(entity.standing_eye_height), 0.0))
+            .await;
+    }
+
+    /// Drops the experience of the mob when it died and removes it once the death animation
+    /// is over
+    async fn tick_death(&self, server: &Server) {
+        let entity = &self.living_entity.entity;
+        let ticks = self.death_ticks.fetch_add(1, Ordering::Relaxed) + 1;
+        if ticks == 1
+            && self
+                .living_entity
+                .last_hurt_by_player
+                .load(Ordering::Relaxed)
+                > 0
+        {
+            let world = entity.world.read().await.clone();
+            ExperienceOrbEntity::spawn(server, &world, entity.pos.load(), self.experience_reward())
+                .await;
+        }
+        if ticks >= DEATH_TICKS {
+            entity.remove().await;
+        }
+    }
+
+    /// The experience points the mob drops when killed by a player, like in vanilla
+    fn experience_reward(&self) -> i32 {
+        match self.living_entity.entity.entity_type.spawn_group {
+            SpawnGroup::Monster => 5,
+            SpawnGroup::Creature
+            | SpawnGroup::Axolotls
+            | SpawnGroup::UndergroundWaterCreature
+            | SpawnGroup::WaterCreature
+            | SpawnGroup::WaterAmbient => 1 + rand::thread_rng().gen_range(0..3),
+            SpawnGroup::Ambient | SpawnGroup::Misc => 0,
+        }
+    }
+}
+
+/// Writes items like vanilla does, where empty slots are empty compounds
+fn write_items(items: &[Option<ItemStack>]) -> NbtTag {
+    NbtTag::List(
+        items
+            .iter()
+            .map(|stack| {
+                NbtTag::Compound(stack.as_ref().map(ItemStack::write_nbt).unwrap_or_default())
+            })
+            .collect(),
+    )
+}
+
+fn read_items<const N: usize>(nbt: &NbtCompound, name: &str) -> [Option<ItemStack>; N] {
+    let mut items = [const { None }; N];
+    for (slot, item) in items.iter_mut().zip(nbt.get_list(name).unwrap_or_default()) {
+        *slot = item.extract_compound().and_then(ItemStack::read_nbt);
+    }
+    items
+}
+
+#[async_trait]
+impl NBTStorage for MobEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.living_entity.write_nbt(nbt).await;
+        nbt.put("HandItems", write_items(&*self.hand_items.lock().await));
+        nbt.put("ArmorItems", write_items(&*self.armor_items.lock().await));
+        nbt.put_bool("NoAI", self.no_ai.load(Ordering::Relaxed));
+    }
+
+    async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
+        self.living_entity.read_nbt(nbt).await;
+        *self.hand_items.get_mut() = read_items(nbt, "HandItems");
+        *self.armor_items.get_mut() = read_items(nbt, "ArmorItems");
+        *self.no_ai.get_mut() = nbt.get_bool("NoAI").unwrap_or(false);
     }
 }
@@ -1,12 +1,30 @@
-use crate::entity::ai::goal::{look_at_entity::LookAtEntityGoal, target_goal::TargetGoal};
+use crate::entity::ai::goal::{
+    look_at_entity::LookAtEntityGoal, melee_attack::MeleeAttackGoal, target_goal::TargetGoal,
+    wander_around::WanderAroundGoal,
+};
 
 use super::MobEntity;
 
+/// How fast zombies walk, like their movement speed attribute in vanilla
+const MOVEMENT_SPEED: f64 = 0.23;
+
+/// How much damage zombies do in normal difficulty, like their attack damage attribute in vanilla
+const ATTACK_DAMAGE: f32 = 3.0;
+
+/// How far away zombies notice players, like their follow range attribute in vanilla
+const FOLLOW_RANGE: f64 = 35.0;
+
 pub struct Zombie;
 
 impl Zombie {
     pub async fn make(mob: &MobEntity) {
-        mob.goal(LookAtEntityGoal::new(8.0)).await;
-        mob.goal(TargetGoal::new(16.0)).await;
+        mob.navigator
+            .lock()
+            .await
+            .set_movement_speed(MOVEMENT_SPEED);
+        mob.goal(2, TargetGoal::new(FOLLOW_RANGE)).await;
+        mob.goal(2, MeleeAttackGoal::new(1.0, ATTACK_DAMAGE)).await;
+        mob.goal(7, WanderAroundGoal::new(1.0)).await;
+        mob.goal(8, LookAtEntityGoal::new(8.0)).await;
     }
 }
@@ -1,10 +1,17 @@
 use core::f32;
-use std::sync::{atomic::AtomicBool, Arc};
+use std::sync::{
+    atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering},
+    Arc,
+};
 
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
+use experience_orb::ExperienceOrbEntity;
+use item::ItemEntity;
 use living::LivingEntity;
+use mob::MobEntity;
 use player::Player;
+use projectile::arrow::ArrowEntity;
 use pumpkin_data::{
     damage::DamageType,
     entity::{EntityPose, EntityType},
@@ -26,16 +33,22 @@ use pumpkin_util::math::{
     vector3::Vector3,
     wrap_degrees,
 };
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::item::ItemStack;
 use serde::Serialize;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
-use crate::world::World;
+use crate::{server::Server, world::World};
 
 pub mod ai;
+pub mod experience_orb;
+pub mod falling_block;
 pub mod hunger;
 pub mod item;
+pub mod lightning;
 pub mod living;
 pub mod mob;
+pub mod physics;
 pub mod player;
 pub mod projectile;
 
@@ -46,11 +59,27 @@ pub type EntityId = i32;
 #[async_trait]
 pub trait EntityBase: Send + Sync {
     /// Gets Called every tick
-    async fn tick(&self) {}
+    async fn tick(&self, _server: &Server) {}
     /// Called when a player collides with the entity
     async fn on_player_collision(&self, _player: Arc<Player>) {}
+    /// Writes the entity, so it can be saved with the chunk it is in.
+    ///
+    /// Returns `None` for entities which are not saved with chunks, e.g. players
+    async fn save_nbt(&self) -> Option<NbtCompound> {
+        None
+    }
     fn get_entity(&self) -> &Entity;
     fn get_living_entity(&self) -> Option<&LivingEntity>;
+    fn get_item_entity(&self) -> Option<&ItemEntity> {
+        None
+    }
+    fn get_experience_orb(&self) -> Option<&ExperienceOrbEntity> {
+        None
+    }
+    /// The data of the entity's spawn packet, e.g. the block state of a falling block
+    fn spawn_data(&self) -> i32 {
+        0
+    }
 }
 
 /// Represents a not living Entity (e.g. Item, Egg, Snowball...)
@@ -75,6 +104,10 @@ pub struct Entity {
     pub sprinting: AtomicBool,
     /// Indicates whether the entity is flying due to a fall
     pub fall_flying: AtomicBool,
+    /// Indicates whether the entity has a glowing outline
+    pub glowing: AtomicBool,
+    /// Indicates whether the entity is frozen, which stops its AI and movement
+    pub frozen: AtomicBool,
     /// The entity's current velocity vector, aka Knockback
     pub velocity: AtomicCell<Vector3<f64>>,
     /// Indicates whether the entity is on the ground (may not always be accurate).
@@ -97,6 +130,16 @@ pub struct Entity {
     pub invulnerable: AtomicBool,
     /// List of damage types this entity is immune to
     pub damage_immunities: Vec<DamageType>,
+    /// Arbitrary data of plugins, which is saved with the entity
+    pub custom_data: Mutex<NbtCompound>,
+    /// The name of the entity, e.g. given using a name tag
+    pub custom_name: Mutex<Option<TextComponent>>,
+    /// How many ticks the entity has been standing in a nether portal
+    pub portal_time: AtomicU32,
+    /// Ticks left until the entity can use a portal again
+    pub portal_cooldown: AtomicU32,
+    /// Ticks left until the entity stops burning
+    pub fire_ticks: AtomicI32,
 }
 
 impl Entity {
@@ -123,12 +166,17 @@ impl Entity {
             on_ground: AtomicBool::new(false),
             pos: AtomicCell::new(position),
             block_pos: AtomicCell::new(BlockPos(Vector3::new(floor_x, floor_y, floor_z))),
-            chunk_pos: AtomicCell::new(Vector2::new(floor_x, floor_z)),
+            chunk_pos: AtomicCell::new(Vector2::new(
+                get_section_cord(floor_x),
+                get_section_cord(floor_z),
+            )),
             sneaking: AtomicBool::new(false),
             world: Arc::new(RwLock::new(world)),
             // TODO: Load this from previous instance
             sprinting: AtomicBool::new(false),
             fall_flying: AtomicBool::new(false),
+            glowing: AtomicBool::new(false),
+            frozen: AtomicBool::new(false),
             yaw: AtomicCell::new(0.0),
             head_yaw: AtomicCell::new(0.0),
             pitch: AtomicCell::new(0.0),
@@ -139,7 +187,63 @@ impl Entity {
             bounding_box_size,
             invulnerable: AtomicBool::new(invulnerable),
             damage_immunities: Vec::new(),
+            custom_data: Mutex::new(NbtCompound::new()),
+            custom_name: Mutex::new(None),
+            portal_time: AtomicU32::new(0),
+            portal_cooldown: AtomicU32::new(0),
+            fire_ticks: AtomicI32::new(0),
+        }
+    }
+
+    /// Counts the ticks the entity stands in a nether portal, returning whether it should travel
+    /// through it now. Entities can not travel again until they left the portal for `cooldown`
+    /// ticks, so they do not get sent back right away when arriving in a portal.
+    pub async fn tick_portal(&self, wait: u32, cooldown: u32) -> bool {
+        let in_portal = self
+            .world
+            .read()
+            .await
+            .get_block(&self.block_pos.load())
+            .await
+            .is_ok_and(|block| block.name == "nether_portal");
+        if !in_portal {
+            self.portal_time.store(0, Ordering::Relaxed);
+            let _ =
+                self.portal_cooldown
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |ticks| {
+                        ticks.checked_sub(1)
+                    });
+            return false;
+        }
+        if self.portal_cooldown.load(Ordering::Relaxed) > 0 {
+            self.portal_cooldown.store(cooldown, Ordering::Relaxed);
+            return false;
+        }
+        if self.portal_time.fetch_add(1, Ordering::Relaxed) + 1 < wait {
+            return false;
+        }
+        self.portal_time.store(0, Ordering::Relaxed);
+        self.portal_cooldown.store(cooldown, Ordering::Relaxed);
+        true
+    }
+
+    /// Whether the entity stands in an end portal and should travel through it now. Unlike nether
+    /// portals this happens right away, and starts the cooldown shared with [`Self::tick_portal`].
+    pub async fn enter_end_portal(&self, cooldown: u32) -> bool {
+        if self.portal_cooldown.load(Ordering::Relaxed) > 0 {
+            return false;
+        }
+        let in_portal = self
+            .world
+            .read()
+            .await
+            .get_block(&self.block_pos.load())
+            .await
+            .is_ok_and(|block| block.name == "end_portal");
+        if in_portal {
+            self.portal_cooldown.store(cooldown, Ordering::Relaxed);
         }
+        in_portal
     }
 
     /// Updates the entity's position, block position, and chunk position.
@@ -150,9 +254,9 @@ impl Entity {
         if pos != new_position {
             self.pos.store(new_position);
             self.bounding_box.store(BoundingBox::new_from_pos(
-                pos.x,
-                pos.y,
-                pos.z,
+                new_position.x,
+                new_position.y,
+                new_position.z,
                 &self.bounding_box_size.load(),
             ));
 
@@ -258,7 +362,7 @@ impl Entity {
         self.world.read().await.remove_entity(self).await;
     }
 
-    pub fn create_spawn_packet(&self) -> CSpawnEntity {
+    pub fn create_spawn_packet(&self, data: i32) -> CSpawnEntity {
         let entity_loc = self.pos.load();
         let entity_vel = self.velocity.load();
         CSpawnEntity::new(
@@ -269,7 +373,7 @@ impl Entity {
             self.pitch.load(),
             self.yaw.load(),
             self.head_yaw.load(), // todo: head_yaw and yaw are swapped, find out why
-            0.into(),
+            data.into(),
             entity_vel,
         )
     }
@@ -285,6 +389,9 @@ impl Entity {
     ///
     /// This function calculates the entity's new velocity based on the specified knockback strength and direction.
     pub fn knockback(&self, strength: f64, x: f64, z: f64) {
+        if self.frozen.load(std::sync::atomic::Ordering::Relaxed) {
+            return;
+        }
         // This has some vanilla magic
         let mut x = x;
         let mut z = z;
@@ -306,11 +413,21 @@ impl Entity {
         ));
     }
 
+    /// Freezes or unfreezes the entity. Frozen entities keep their position until they are
+    /// unfrozen.
+    pub fn set_frozen(&self, frozen: bool) {
+        self.frozen
+            .store(frozen, std::sync::atomic::Ordering::Relaxed);
+        if frozen {
+            self.velocity.store(Vector3::new(0.0, 0.0, 0.0));
+        }
+    }
+
     pub async fn set_sneaking(&self, sneaking: bool) {
         assert!(self.sneaking.load(std::sync::atomic::Ordering::Relaxed) != sneaking);
         self.sneaking
             .store(sneaking, std::sync::atomic::Ordering::Relaxed);
-        self.set_flag(Flag::Sneaking, sneaking).await;
+        self.send_flags().await;
         if sneaking {
             self.set_pose(EntityPose::Crouching).await;
         } else {
@@ -322,7 +439,7 @@ impl Entity {
         assert!(self.sprinting.load(std::sync::atomic::Ordering::Relaxed) != sprinting);
         self.sprinting
             .store(sprinting, std::sync::atomic::Ordering::Relaxed);
-        self.set_flag(Flag::Sprinting, sprinting).await;
+        self.send_flags().await;
     }
 
     pub fn check_fall_flying(&self) -> bool {
@@ -333,16 +450,52 @@ impl Entity {
         assert!(self.fall_flying.load(std::sync::atomic::Ordering::Relaxed) != fall_flying);
         self.fall_flying
             .store(fall_flying, std::sync::atomic::Ordering::Relaxed);
-        self.set_flag(Flag::FallFlying, fall_flying).await;
+        self.send_flags().await;
+    }
+
+    pub async fn set_glowing(&self, glowing: bool) {
+        self.glowing
+            .store(glowing, std::sync::atomic::Ordering::Relaxed);
+        self.send_flags().await;
+    }
+
+    pub fn is_on_fire(&self) -> bool {
+        self.fire_ticks.load(Ordering::Relaxed) > 0
+    }
+
+    /// Sets the entity on fire for at least the given ticks. Fire immune entities do not burn.
+    pub async fn set_on_fire_for(&self, ticks: i32) {
+        if self.entity_type.fire_immune {
+            return;
+        }
+        let previous = self.fire_ticks.fetch_max(ticks, Ordering::Relaxed);
+        if previous <= 0 && ticks > 0 {
+            self.send_flags().await;
+        }
     }
 
-    async fn set_flag(&self, flag: Flag, value: bool) {
-        let index = flag as u8;
+    pub async fn extinguish(&self) {
+        if self.fire_ticks.swap(0, Ordering::Relaxed) > 0 {
+            self.send_flags().await;
+        }
+    }
+
+    /// Sends all flags at once, as the client replaces the whole flags byte
+    async fn send_flags(&self) {
+        let flags = [
+            (Flag::Sneaking, &self.sneaking),
+            (Flag::Sprinting, &self.sprinting),
+            (Flag::Glowing, &self.glowing),
+            (Flag::FallFlying, &self.fall_flying),
+        ];
         let mut b = 0i8;
-        if value {
-            b |= 1 << index;
-        } else {
-            b &= !(1 << index);
+        for (flag, value) in flags {
+            if value.load(std::sync::atomic::Ordering::Relaxed) {
+                b |= 1 << flag as u8;
+            }
+        }
+        if self.is_on_fire() {
+            b |= 1 << Flag::OnFire as u8;
         }
         self.send_meta_data(Metadata::new(0, MetaDataType::Byte, b))
             .await;
@@ -379,11 +532,147 @@ impl Entity {
         self.invulnerable.load(std::sync::atomic::Ordering::Relaxed)
             || self.damage_immunities.contains(damage_type)
     }
+
+    /// Writes the type and UUID of the entity, which are needed to load it again
+    pub fn write_identity(&self, nbt: &mut NbtCompound) {
+        nbt.put(
+            "id",
+            format!("minecraft:{}", self.entity_type.resource_name),
+        );
+        let uuid = self.entity_uuid.as_u128();
+        nbt.put(
+            "UUID",
+            NbtTag::IntArray(
+                vec![
+                    (uuid >> 96) as i32,
+                    (uuid >> 64) as i32,
+                    (uuid >> 32) as i32,
+                    uuid as i32,
+                ]
+                .into_boxed_slice(),
+            ),
+        );
+    }
+}
+
+impl Entity {
+    /// Creates an entity of the type with a new UUID, see [`Server::add_entity`]
+    #[must_use]
+    pub fn from_type(
+        entity_id: EntityId,
+        position: Vector3<f64>,
+        entity_type: EntityType,
+        world: &Arc<World>,
+    ) -> Self {
+        // TODO: this should be resolved to a integer using a macro when calling this function
+        let bounding_box_size = EntityDimensions {
+            width: entity_type.dimension[0],
+            height: entity_type.dimension[1],
+        };
+
+        // TODO: standing eye height should be per mob
+        let new_uuid = uuid::Uuid::new_v4();
+        Self::new(
+            entity_id,
+            new_uuid,
+            world.clone(),
+            position,
+            entity_type,
+            entity_type.eye_height,
+            AtomicCell::new(BoundingBox::new_from_pos(
+                position.x,
+                position.y,
+                position.z,
+                &bounding_box_size,
+            )),
+            AtomicCell::new(bounding_box_size),
+            false,
+        )
+    }
+}
+
+/// Reads the UUID written by [`Entity::write_identity`]
+#[must_use]
+pub fn uuid_from_nbt(nbt: &NbtCompound) -> Option<uuid::Uuid> {
+    let [a, b, c, d] = nbt.get_int_array("UUID")? else {
+        return None;
+    };
+    Some(uuid::Uuid::from_u128(
+        (u128::from(*a as u32) << 96)
+            | (u128::from(*b as u32) << 64)
+            | (u128::from(*c as u32) << 32)
+            | u128::from(*d as u32),
+    ))
+}
+
+/// Creates an entity saved with [`EntityBase::save_nbt`] and spawns it in the world.
+///
+/// Returns `None` if the entity could not be loaded, e.g. because its type is not supported yet.
+pub async fn spawn_from_nbt(
+    server: &Server,
+    world: &Arc<World>,
+    nbt: &mut NbtCompound,
+) -> Option<Arc<dyn EntityBase>> {
+    spawn_from_nbt_with_id(server.new_entity_id(), world, nbt).await
+}
+
+/// Like [`spawn_from_nbt`], but the entity gets the given id instead of a new one of the server
+pub async fn spawn_from_nbt_with_id(
+    entity_id: EntityId,
+    world: &Arc<World>,
+    nbt: &mut NbtCompound,
+) -> Option<Arc<dyn EntityBase>> {
+    let id = nbt.get_string("id")?;
+    let entity_type = EntityType::from_name(id.strip_prefix("minecraft:").unwrap_or(id))?;
+    let position = nbt.get_list("Pos")?;
+    let position = Vector3::new(
+        position.first()?.extract_double()?,
+        position.get(1)?.extract_double()?,
+        position.get(2)?.extract_double()?,
+    );
+
+    let mut entity = Entity::from_type(entity_id, position, entity_type, world);
+    if let Some(uuid) = uuid_from_nbt(nbt) {
+        entity.entity_uuid = uuid;
+    }
+
+    if entity_type == EntityType::ITEM {
+        let stack = ItemStack::read_nbt(nbt.get_compound("Item")?)?;
+        let mut item_entity = ItemEntity::new(entity, &stack);
+        item_entity.read_nbt(nbt).await;
+        let item_entity = Arc::new(item_entity);
+        world.spawn_entity(item_entity.clone()).await;
+        item_entity.send_meta_packet().await;
+        Some(item_entity)
+    } else if entity_type == EntityType::EXPERIENCE_ORB {
+        let mut orb = ExperienceOrbEntity::new(entity, nbt.get_short("Value")?);
+        orb.read_nbt(nbt).await;
+        let orb = Arc::new(orb);
+        world.spawn_entity(orb.clone()).await;
+        Some(orb)
+    } else if entity_type == EntityType::ARROW {
+        let mut arrow = ArrowEntity::without_owner(entity);
+        arrow.read_nbt(nbt).await;
+        let arrow = Arc::new(arrow);
+        world.spawn_entity(arrow.clone()).await;
+        arrow.send_meta_packet().await;
+        Some(arrow)
+    } else if entity_type != EntityType::PLAYER && entity_type.max_health.is_some() {
+        let mut mob = MobEntity::new(entity).await;
+        mob.read_nbt(nbt).await;
+        let mob = Arc::new(mob);
+        world.spawn_entity(mob.clone()).await;
+        mob.send_equipment().await;
+        Some(mob)
+    } else {
+        // TODO: Thrown items, Vehicles...
+        None
+    }
 }
 
 #[async_trait]
 impl EntityBase for Entity {
-    async fn tick(&self) {}
+    async fn tick(&self, _server: &Server) {}
 
     fn get_entity(&self) -> &Entity {
         self
@@ -415,6 +704,23 @@ impl NBTStorage for Entity {
             "Rotation",
             NbtTag::List(vec![self.yaw.load().into(), self.pitch.load().into()].into_boxed_slice()),
         );
+        nbt.put(
+            "Fire",
+            NbtTag::Short(
+                self.fire_ticks
+                    .load(Ordering::Relaxed)
+                    .clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16,
+            ),
+        );
+        let custom_data = self.custom_data.lock().await;
+        if !custom_data.is_empty() {
+            nbt.put_component("data", custom_data.clone());
+        }
+        if let Some(custom_name) = self.custom_name.lock().await.as_ref() {
+            if let Ok(custom_name) = serde_json::to_string(custom_name) {
+                nbt.put("CustomName", custom_name);
+            }
+        }
 
         // todo more...
     }
@@ -435,6 +741,11 @@ impl NBTStorage for Entity {
         let pitch = rotation[1].extract_float().unwrap_or(0.0);
         self.yaw.store(yaw);
         self.pitch.store(pitch);
+        *self.fire_ticks.get_mut() = nbt.get_short("Fire").map_or(0, i32::from);
+        *self.custom_data.get_mut() = nbt.get_compound("data").cloned().unwrap_or_default();
+        *self.custom_name.get_mut() = nbt
+            .get_string("CustomName")
+            .and_then(|custom_name| serde_json::from_str(custom_name).ok());
 
         // todo more...
     }
@@ -471,3 +782,100 @@ pub enum Flag {
     /// Indicates if the entity is flying due to a fall.
     FallFlying = 7,
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_registry::DimensionType;
+    use pumpkin_world::level::Level;
+    use temp_dir::TempDir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn entities_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let level = Level::from_root_folder(temp_dir.path().to_path_buf());
+        let world = Arc::new(World::load(level, DimensionType::Overworld));
+
+        let item_entity = ItemEntity::new(
+            Entity::from_type(1, Vector3::new(0.5, 64.0, 0.5), EntityType::ITEM, &world),
+            &ItemStack::new(3, Item::DIAMOND),
+        );
+
+        let zombie = MobEntity::new(Entity::from_type(
+            2,
+            Vector3::new(4.0, 70.0, 8.0),
+            EntityType::ZOMBIE,
+            &world,
+        ))
+        .await;
+        zombie.living_entity.health.store(14.0);
+        zombie.hand_items.lock().await[0] = Some(ItemStack::new(1, Item::IRON_SWORD));
+        zombie.armor_items.lock().await[3] = Some(ItemStack::new(1, Item::GOLDEN_HELMET));
+
+        let cow = MobEntity::new(Entity::from_type(
+            3,
+            Vector3::new(12.0, 65.0, 3.0),
+            EntityType::COW,
+            &world,
+        ))
+        .await;
+        cow.living_entity.entity.yaw.store(90.0);
+        *cow.living_entity.entity.custom_name.lock().await = Some(TextComponent::text("Bessie"));
+
+        let entities: [Arc<dyn EntityBase>; 3] =
+            [Arc::new(item_entity), Arc::new(zombie), Arc::new(cow)];
+        let mut saved = Vec::new();
+        for entity in &entities {
+            saved.push(entity.save_nbt().await.unwrap());
+        }
+        let chunk = Vector2::new(0, 0);
+        world.level.write_entities(&chunk, &saved);
+
+        let mut loaded = Vec::new();
+        for (id, mut nbt) in (10..).zip(world.level.read_entities(&chunk)) {
+            loaded.push(spawn_from_nbt_with_id(id, &world, &mut nbt).await.unwrap());
+        }
+        assert_eq!(loaded.len(), entities.len());
+        for (entity, loaded) in entities.iter().zip(&loaded) {
+            let (entity, loaded) = (entity.get_entity(), loaded.get_entity());
+            assert_eq!(loaded.entity_uuid, entity.entity_uuid);
+            assert_eq!(loaded.entity_type, entity.entity_type);
+            assert_eq!(loaded.pos.load(), entity.pos.load());
+            assert!(world
+                .entities
+                .read()
+                .await
+                .contains_key(&loaded.entity_uuid));
+        }
+
+        let stack = loaded[0]
+            .get_item_entity()
+            .unwrap()
+            .lock_stack()
+            .await
+            .clone();
+        assert_eq!(stack.item, Item::DIAMOND);
+        assert_eq!(stack.item_count, 3);
+
+        // Saving the loaded entities again writes the same, so nothing got lost, like the
+        // health and the equipment of the zombie or the name and rotation of the cow
+        for (loaded, saved) in loaded.iter().zip(&saved) {
+            assert_eq!(loaded.save_nbt().await.as_ref(), Some(saved));
+        }
+        let zombie = &saved[1];
+        assert_eq!(zombie.get_float("Health").map(f64::from), Some(14.0));
+        let hand = zombie.get_list("HandItems").unwrap()[0]
+            .extract_compound()
+            .cloned();
+        assert_eq!(
+            hand.as_ref().and_then(ItemStack::read_nbt),
+            Some(ItemStack::new(1, Item::IRON_SWORD))
+        );
+        assert_eq!(
+            *loaded[2].get_entity().custom_name.lock().await,
+            Some(TextComponent::text("Bessie"))
+        );
+    }
+}
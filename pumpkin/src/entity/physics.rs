@@ -0,0 +1,188 @@
+//! Simple movement for entities, like dropped items, experience orbs and walking mobs
+
+use std::sync::atomic::Ordering;
+
+use pumpkin_protocol::client::play::{CUpdateEntityPos, CUpdateEntityRot};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{
+    block::registry::{get_block_by_state_id, get_state_by_state_id},
+    WORLD_LOWEST_Y,
+};
+
+use crate::{block::blocks::falling::is_water, world::World};
+
+use super::Entity;
+
+/// How much of their speed entities keep each tick, because of air resistance
+const DRAG: f64 = 0.98;
+
+/// Lets the entity fall, slide on the ground and float up in water, like items and experience
+/// orbs do. Returns whether it moved.
+pub async fn tick_movement(entity: &Entity, world: &World, gravity: f64) -> bool {
+    let pos = entity.pos.load();
+    let mut velocity = entity.velocity.load();
+
+    let in_water = world
+        .get_block_state_id(&entity.block_pos.load())
+        .await
+        .is_ok_and(is_water);
+    if in_water {
+        // They float up to the surface, like in vanilla
+        velocity = velocity.multiply(0.99, 0.99, 0.99);
+        if velocity.y < 0.06 {
+            velocity.y += 5.0e-4;
+        }
+    } else {
+        velocity.y -= gravity;
+    }
+
+    let mut new_pos = pos;
+    let mut on_ground = false;
+    let target_y = pos.y + velocity.y;
+    if velocity.y < 0.0 {
+        // The blocks the entity falls through this tick, from the top
+        let bottom = (target_y.floor() as i32).max(i32::from(WORLD_LOWEST_Y) - 64);
+        let mut landing = None;
+        for y in (bottom..pos.y.ceil() as i32).rev() {
+            let below = BlockPos(Vector3::new(pos.x.floor() as i32, y, pos.z.floor() as i32));
+            if f64::from(y) + 1.0 <= pos.y && collides(world, &below).await {
+                landing = Some(f64::from(y) + 1.0);
+                break;
+            }
+        }
+        if let Some(ground) = landing {
+            new_pos.y = ground;
+            velocity.y = 0.0;
+            on_ground = true;
+        } else {
+            new_pos.y = target_y;
+        }
+    } else {
+        let height = f64::from(entity.height());
+        let above = BlockPos(Vector3::new(
+            pos.x.floor() as i32,
+            (target_y + height).floor() as i32,
+            pos.z.floor() as i32,
+        ));
+        if collides(world, &above).await {
+            velocity.y = 0.0;
+        } else {
+            new_pos.y = target_y;
+        }
+    }
+
+    // It stops at walls, one axis at a time
+    let half_width = f64::from(entity.width()) / 2.0;
+    let target_x = new_pos.x + velocity.x;
+    let wall_x = BlockPos(Vector3::new(
+        (target_x + half_width.copysign(velocity.x)).floor() as i32,
+        new_pos.y.floor() as i32,
+        new_pos.z.floor() as i32,
+    ));
+    if velocity.x.abs() > 0.0 && collides(world, &wall_x).await {
+        velocity.x = 0.0;
+    } else {
+        new_pos.x = target_x;
+    }
+    let target_z = new_pos.z + velocity.z;
+    let wall_z = BlockPos(Vector3::new(
+        new_pos.x.floor() as i32,
+        new_pos.y.floor() as i32,
+        (target_z + half_width.copysign(velocity.z)).floor() as i32,
+    ));
+    if velocity.z.abs() > 0.0 && collides(world, &wall_z).await {
+        velocity.z = 0.0;
+    } else {
+        new_pos.z = target_z;
+    }
+
+    let friction = if on_ground {
+        let ground = BlockPos(Vector3::new(
+            new_pos.x.floor() as i32,
+            new_pos.y.floor() as i32 - 1,
+            new_pos.z.floor() as i32,
+        ));
+        slipperiness(world, &ground).await * DRAG
+    } else {
+        DRAG
+    };
+    velocity = velocity.multiply(friction, DRAG, friction);
+    if velocity.horizontal_length_squared() < 1.0e-6 {
+        velocity.x = 0.0;
+        velocity.z = 0.0;
+    }
+    entity.velocity.store(velocity);
+    entity.on_ground.store(on_ground, Ordering::Relaxed);
+
+    if new_pos == pos {
+        return false;
+    }
+    // Like in vanilla, entities falling out of the world are gone
+    if new_pos.y < f64::from(WORLD_LOWEST_Y) - 64.0 {
+        entity.remove().await;
+        return false;
+    }
+    entity.set_pos(new_pos);
+    send_movement(entity, world, pos, new_pos, on_ground).await;
+    true
+}
+
+/// Shows the players that the entity moved from one position to the other
+pub async fn send_movement(
+    entity: &Entity,
+    world: &World,
+    from: Vector3<f64>,
+    to: Vector3<f64>,
+    on_ground: bool,
+) {
+    let delta = |new: f64, old: f64| {
+        ((new - old) * 4096.0).clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16
+    };
+    world
+        .broadcast_packet_all(&CUpdateEntityPos::new(
+            entity.entity_id.into(),
+            Vector3::new(
+                delta(to.x, from.x),
+                delta(to.y, from.y),
+                delta(to.z, from.z),
+            ),
+            on_ground,
+        ))
+        .await;
+}
+
+/// Shows the players which way the entity faces
+pub async fn send_rotation(entity: &Entity, world: &World) {
+    let angle = |degrees: f32| (degrees * 256.0 / 360.0).rem_euclid(256.0) as u8;
+    world
+        .broadcast_packet_all(&CUpdateEntityRot::new(
+            entity.entity_id.into(),
+            angle(entity.yaw.load()),
+            angle(entity.pitch.load()),
+            entity.on_ground.load(Ordering::Relaxed),
+        ))
+        .await;
+}
+
+/// Whether entities can't move through the block
+async fn collides(world: &World, position: &BlockPos) -> bool {
+    world
+        .get_block_state_id(position)
+        .await
+        .ok()
+        .and_then(get_state_by_state_id)
+        .is_some_and(|state| !state.collision_shapes.is_empty())
+}
+
+/// How much of their speed entities keep each tick when sliding over the block
+async fn slipperiness(world: &World, position: &BlockPos) -> f64 {
+    let Ok(state_id) = world.get_block_state_id(position).await else {
+        return 0.6;
+    };
+    match get_block_by_state_id(state_id).map(|block| block.name.as_str()) {
+        Some("ice" | "packed_ice" | "frosted_ice") => 0.98,
+        Some("blue_ice") => 0.989,
+        Some("slime_block") => 0.8,
+        _ => 0.6,
+    }
+}
@@ -2,7 +2,7 @@ use pumpkin_world::block::registry::State;
 use std::{
     num::NonZeroU8,
     sync::{
-        atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU32, Ordering},
+        atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU32, AtomicU64, Ordering},
         Arc,
     },
     time::{Duration, Instant},
@@ -11,16 +11,29 @@ use std::{
 use super::{
     combat::{self, player_attack_sound, AttackType},
     hunger::HungerManager,
-    item::ItemEntity,
     Entity, EntityBase, EntityId, NBTStorage,
 };
 use crate::{
     block,
     command::{client_suggestions, dispatcher::CommandDispatcher},
-    data::op_data::OPERATOR_CONFIG,
+    data::{
+        chat_format_data::CHAT_FORMAT_LIST,
+        ignore_data::IGNORE_LIST,
+        last_seen_data::LAST_SEEN_LIST,
+        muted_player_data::{mute_notice, MUTED_PLAYER_LIST},
+        op_data::OPERATOR_CONFIG,
+        playtime_data::PLAYTIME_LIST,
+        SaveJSONConfiguration,
+    },
     net::{Client, PlayerConfig},
+    plugin::api::{
+        events::player::player_teleport::{PlayerTeleportEvent, TeleportCause},
+        menu::Menu,
+        placeholder,
+    },
     server::Server,
-    world::World,
+    world::{chunker, World},
+    PLUGIN_MANAGER,
 };
 use crate::{error::PumpkinError, net::GameProfile};
 use async_trait::async_trait;
@@ -33,29 +46,31 @@ use pumpkin_data::{
     particle::Particle,
     sound::{Sound, SoundCategory},
 };
-use pumpkin_inventory::player::PlayerInventory;
-use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_inventory::{player::PlayerInventory, Container, EnderChest};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_protocol::{
     bytebuf::packet::Packet,
     client::play::{
-        CAcknowledgeBlockChange, CActionBar, CCombatDeath, CDisguisedChatMessage, CEntityStatus,
-        CGameEvent, CHurtAnimation, CKeepAlive, CParticle, CPlayDisconnect, CPlayerAbilities,
-        CPlayerInfoUpdate, CPlayerPosition, CRespawn, CSetExperience, CSetHealth, CSubtitle,
-        CSystemChatMessage, CTitleText, CUnloadChunk, GameEvent, MetaDataType, PlayerAction,
+        CAcknowledgeBlockChange, CActionBar, CCombatDeath, CCustomPayload, CDisguisedChatMessage,
+        CEntityStatus, CGameEvent, CHurtAnimation, CKeepAlive, CParticle, CPlayDisconnect,
+        CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition, CRemoveEntities, CRemovePlayerInfo,
+        CRespawn, CSetExperience, CSetHealth, CSpawnEntity, CSubtitle, CSystemChatMessage,
+        CTabList, CTitleText, CUnloadChunk, GameEvent, MetaDataType, PlayerAction,
     },
     server::play::{
         SChatCommand, SChatMessage, SClientCommand, SClientInformationPlay, SClientTickEnd,
         SCommandSuggestion, SConfirmTeleport, SInteract, SPickItemFromBlock, SPlayerAbilities,
         SPlayerAction, SPlayerCommand, SPlayerInput, SPlayerPosition, SPlayerPositionRotation,
-        SPlayerRotation, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm, SUpdateSign,
-        SUseItem, SUseItemOn,
+        SPlayerRotation, SRenameItem, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm,
+        SUpdateSign, SUseItem, SUseItemOn,
     },
     RawPacket, ServerPacket,
 };
 use pumpkin_protocol::{
     client::play::CSoundEffect,
     server::play::{
-        SCloseContainer, SCookieResponse as SPCookieResponse, SPlayPingRequest, SPlayerLoaded,
+        SCloseContainer, SCookieResponse as SPCookieResponse, SCustomPayload, SPlayPingRequest,
+        SPlayerLoaded,
     },
 };
 use pumpkin_protocol::{client::play::CUpdateTime, codec::var_int::VarInt};
@@ -63,24 +78,36 @@ use pumpkin_protocol::{
     client::play::Metadata,
     server::play::{SClickContainer, SKeepAlive},
 };
+use pumpkin_registry::DimensionType;
 use pumpkin_util::atomic_linked_list::AtomicLinkedList;
 use pumpkin_util::{
     math::{
         boundingbox::{BoundingBox, EntityDimensions},
-        experience,
+        experience, get_section_cord,
         position::BlockPos,
         vector2::Vector2,
         vector3::Vector3,
     },
     permission::PermissionLvl,
-    text::TextComponent,
+    text::{color::NamedColor, TextComponent},
     GameMode,
 };
-use pumpkin_world::{cylindrical_chunk_iterator::Cylindrical, item::ItemStack};
+use pumpkin_world::{
+    chunk::ticket::{TicketType, BLOCK_TICKING_LEVEL},
+    cylindrical_chunk_iterator::Cylindrical,
+    item::ItemStack,
+};
 use tokio::sync::{Mutex, Notify, RwLock};
 
 use super::living::LivingEntity;
 
+/// Like vanilla, players have to sleep this many ticks before they count for skipping the night
+pub const DEEP_SLEEP_TICKS: u32 = 100;
+
+/// The player info actions adding a player to the player list: add player, update listed and
+/// update display name
+pub const PLAYER_INFO_ACTIONS: i8 = 0x01 | 0x08 | 0x20;
+
 /// Represents a Minecraft player entity.
 ///
 /// A `Player` is a special type of entity that represents a human player connected to the server.
@@ -93,6 +120,8 @@ pub struct Player {
     pub client: Arc<Client>,
     /// Players Inventory
     pub inventory: Mutex<PlayerInventory>,
+    /// The items in the player's ender chest
+    pub ender_chest: Arc<Mutex<Box<dyn Container>>>,
     /// The player's configuration settings. Changes when the Player changes their settings.
     pub config: Mutex<PlayerConfig>,
     /// The player's current gamemode (e.g., Survival, Creative, Adventure).
@@ -101,6 +130,8 @@ pub struct Player {
     pub hunger_manager: HungerManager,
     /// The ID of the currently open container (if any).
     pub open_container: AtomicCell<Option<u64>>,
+    /// The plugin menu the player currently has open (if any).
+    pub open_menu: Mutex<Option<Arc<Menu>>>,
     /// The item currently being held by the player.
     pub carried_item: AtomicCell<Option<ItemStack>>,
     /// send `send_abilities_update` when changed
@@ -130,6 +161,8 @@ pub struct Player {
     pub keep_alive_id: AtomicI64,
     /// Last time we send a keep alive
     pub last_keep_alive_time: AtomicCell<Instant>,
+    /// How long the client took to answer the last keep alive, in milliseconds
+    pub ping: AtomicU32,
     /// Amount of ticks since last attack
     pub last_attacked_ticks: AtomicU32,
     /// The players op permission level
@@ -148,6 +181,24 @@ pub struct Player {
     pub experience_progress: AtomicCell<f32>,
     /// The player's total experience points
     pub experience_points: AtomicI32,
+    /// The tick the player started using the held item, e.g. drawing a bow, if they are using it
+    pub item_use_start: AtomicCell<Option<i32>>,
+    /// Ticks left until the player can pick up the next experience orb
+    pub experience_pickup_delay: AtomicU32,
+    /// Whether the player is hidden from all other players
+    pub vanished: AtomicBool,
+    /// The ticks the player was online in total, including earlier sessions
+    pub playtime: AtomicU64,
+    /// How many ticks the player has been sleeping, up to [`DEEP_SLEEP_TICKS`]
+    pub sleep_ticks: AtomicU32,
+    /// Whether the player is away from keyboard, set with `/afk` or after being idle
+    pub afk: AtomicBool,
+    /// How many ticks the player has not moved, looked around or done anything
+    pub idle_ticks: AtomicU32,
+    /// The position, yaw and pitch of the player when they were last checked for being idle
+    idle_look: AtomicCell<(Vector3<f64>, f32, f32)>,
+    /// Where the player respawns after dying, if they set a bed or respawn anchor
+    pub respawn_point: Mutex<Option<RespawnPoint>>,
 }
 
 impl Player {
@@ -198,6 +249,7 @@ impl Player {
             hunger_manager: HungerManager::default(),
             current_block_destroy_stage: AtomicI32::new(-1),
             open_container: AtomicCell::new(None),
+            open_menu: Mutex::new(None),
             tick_counter: AtomicI32::new(0),
             packet_sequence: AtomicI32::new(-1),
             start_mining_time: AtomicI32::new(0),
@@ -217,6 +269,7 @@ impl Player {
             wait_for_keep_alive: AtomicBool::new(false),
             keep_alive_id: AtomicI64::new(0),
             last_keep_alive_time: AtomicCell::new(std::time::Instant::now()),
+            ping: AtomicU32::new(0),
             last_attacked_ticks: AtomicU32::new(0),
             cancel_tasks: Notify::new(),
             client_loaded: AtomicBool::new(false),
@@ -234,10 +287,20 @@ impl Player {
                     |op| AtomicCell::new(op.level),
                 ),
             inventory: Mutex::new(PlayerInventory::new()),
+            ender_chest: Arc::new(Mutex::new(Box::new(EnderChest::default()))),
             experience_level: AtomicI32::new(0),
             experience_progress: AtomicCell::new(0.0),
             experience_points: AtomicI32::new(0),
+            item_use_start: AtomicCell::new(None),
+            experience_pickup_delay: AtomicU32::new(0),
             permissions: AtomicLinkedList::new(),
+            vanished: AtomicBool::new(false),
+            playtime: AtomicU64::new(PLAYTIME_LIST.read().await.get_ticks(&gameprofile_clone.id)),
+            sleep_ticks: AtomicU32::new(0),
+            afk: AtomicBool::new(false),
+            idle_ticks: AtomicU32::new(0),
+            idle_look: AtomicCell::new((Vector3::new(0.0, 0.0, 0.0), 0.0, 0.0)),
+            respawn_point: Mutex::new(None),
         }
     }
 
@@ -250,9 +313,21 @@ impl Player {
     pub async fn remove(self: Arc<Self>) {
         let world = self.world().await;
         self.cancel_tasks.notify_waiters();
+        self.menu_closed().await;
+        // Frees the bed for other players
+        self.living_entity.wake_up().await;
 
         world.remove_player(self.clone(), true).await;
 
+        let mut last_seen = LAST_SEEN_LIST.write().await;
+        last_seen.update(&self.gameprofile);
+        last_seen.save();
+        drop(last_seen);
+        let mut playtime = PLAYTIME_LIST.write().await;
+        playtime.update(&self.gameprofile, self.playtime.load(Ordering::Relaxed));
+        playtime.save();
+        drop(playtime);
+
         let cylindrical = self.watched_section.load();
 
         // Radial chunks are all of the chunks the player is theoretically viewing
@@ -268,11 +343,8 @@ impl Player {
 
         let level = &world.level;
 
-        // Decrement value of watched chunks
-        let chunks_to_clean = level.mark_chunks_as_not_watched(&radial_chunks);
-
-        // Remove chunks with no watchers from the cache
-        level.clean_chunks(&chunks_to_clean).await;
+        // Remove the player tickets and the chunks no longer kept loaded from the cache
+        chunker::unwatch_section(level, cylindrical).await;
         // Remove left over entries from all possiblily loaded chunks
         level.clean_memory(&radial_chunks);
 
@@ -364,7 +436,13 @@ impl Player {
 
         if let Some(living) = victim_living_entity {
             living
-                .damage(damage as f32, DamageType::PLAYER_ATTACK)
+                .damage_with_context(
+                    damage as f32,
+                    DamageType::PLAYER_ATTACK,
+                    None,
+                    Some(attacker_entity),
+                    Some(attacker_entity),
+                )
                 .await;
         }
 
@@ -465,6 +543,13 @@ impl Player {
         }
 
         self.tick_counter.fetch_add(1, Ordering::Relaxed);
+        self.playtime.fetch_add(1, Ordering::Relaxed);
+        let _ = self.experience_pickup_delay.fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |delay| delay.checked_sub(1),
+        );
+        self.tick_sleep().await;
 
         if self.mining.load(Ordering::Relaxed) {
             let pos = self.mining_pos.lock().await;
@@ -541,6 +626,34 @@ impl Player {
         }
     }
 
+    /// Counts how long the player has been sleeping and wakes them up if their bed is gone
+    async fn tick_sleep(&self) {
+        let Some(head) = self.living_entity.sleeping_position.load() else {
+            self.sleep_ticks.store(0, Ordering::Relaxed);
+            return;
+        };
+        let bed_exists = self
+            .world()
+            .await
+            .get_block_state_id(&head)
+            .await
+            .is_ok_and(block::blocks::bed::is_bed);
+        if !bed_exists {
+            self.living_entity.wake_up().await;
+            return;
+        }
+        let ticks = self.sleep_ticks.load(Ordering::Relaxed);
+        if ticks < DEEP_SLEEP_TICKS {
+            self.sleep_ticks.store(ticks + 1, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether the player has been sleeping long enough to count for skipping the night
+    pub fn is_sleeping_deeply(&self) -> bool {
+        self.living_entity.is_sleeping()
+            && self.sleep_ticks.load(Ordering::Relaxed) >= DEEP_SLEEP_TICKS
+    }
+
     pub async fn jump(&self) {
         if self.living_entity.entity.sprinting.load(Ordering::Relaxed) {
             self.add_exhaustion(0.2).await;
@@ -669,7 +782,7 @@ impl Player {
             .send_packet(&CUpdateTime::new(
                 l_world.world_age,
                 l_world.time_of_day,
-                true,
+                world.game_rules.do_daylight_cycle,
             ))
             .await;
     }
@@ -679,17 +792,19 @@ impl Player {
     pub async fn send_mobs(&self, world: &World) {
         let entities = world.entities.read().await.clone();
         for (_, entity) in entities {
-            self.client
-                .send_packet(&entity.get_entity().create_spawn_packet())
-                .await;
+            if let Some(orb) = entity.get_experience_orb() {
+                self.client.send_packet(&orb.spawn_packet()).await;
+            } else {
+                self.client
+                    .send_packet(&entity.get_entity().create_spawn_packet(entity.spawn_data()))
+                    .await;
+            }
         }
     }
 
     async fn unload_watched_chunks(&self, world: &World) {
-        let radial_chunks = self.watched_section.load().all_chunks_within();
-        let level = &world.level;
-        let chunks_to_clean = level.mark_chunks_as_not_watched(&radial_chunks);
-        level.clean_chunks(&chunks_to_clean).await;
+        let chunks_to_clean =
+            chunker::unwatch_section(&world.level, self.watched_section.load()).await;
         let client = self.client.clone();
         tokio::spawn(async move {
             for chunk in chunks_to_clean {
@@ -715,15 +830,9 @@ impl Player {
         yaw: Option<f32>,
         pitch: Option<f32>,
     ) {
-        self.set_client_loaded(false);
-        let current_world = self.living_entity.entity.world.read().await.clone();
-        let uuid = self.gameprofile.id;
-        current_world.remove_player(self.clone(), false).await;
-        *self.living_entity.entity.world.write().await = new_world.clone();
-        new_world.players.write().await.insert(uuid, self.clone());
-        self.unload_watched_chunks(&current_world).await;
+        self.move_to_world(new_world.clone()).await;
         let last_pos = self.living_entity.last_pos.load();
-        let death_dimension = self.world().await.dimension_type.name();
+        let death_dimension = self.world().await.dimension_key();
         let death_location = BlockPos(Vector3::new(
             last_pos.x.round() as i32,
             last_pos.y.round() as i32,
@@ -732,7 +841,7 @@ impl Player {
         self.client
             .send_packet(&CRespawn::new(
                 (new_world.dimension_type as u8).into(),
-                new_world.dimension_type.name(),
+                new_world.dimension_key(),
                 0, // seed
                 self.gamemode.load() as u8,
                 self.gamemode.load() as i8,
@@ -746,32 +855,177 @@ impl Player {
             .await;
         self.send_abilities_update().await;
         self.send_permission_lvl_update().await;
-        let info = &new_world.level.level_info;
         let position = if let Some(pos) = position {
             pos
         } else {
-            Vector3::new(
-                f64::from(info.spawn_x),
-                f64::from(
-                    new_world
-                        .get_top_block(Vector2::new(
-                            f64::from(info.spawn_x) as i32,
-                            f64::from(info.spawn_x) as i32,
-                        ))
-                        .await
-                        + 1,
-                ),
-                f64::from(info.spawn_z),
-            )
+            new_world.get_spawn_position().await
         };
-        let yaw = yaw.unwrap_or(info.spawn_angle);
+        let yaw = yaw.unwrap_or(new_world.level.level_info.spawn_angle);
         let pitch = pitch.unwrap_or(10.0);
         self.request_teleport(position, yaw, pitch).await;
         self.living_entity.last_pos.store(position);
 
+        self.send_world_players(&new_world).await;
         new_world.send_world_info(&self, position, yaw, pitch).await;
     }
 
+    /// Removes the player from its current world and adds it to another one, without telling the
+    /// client about the new world yet. That is left to a respawn packet followed by
+    /// [`World::send_world_info`].
+    pub(crate) async fn move_to_world(self: &Arc<Self>, new_world: Arc<World>) {
+        self.set_client_loaded(false);
+        let current_world = self.living_entity.entity.world.read().await.clone();
+        current_world.remove_player(self.clone(), false).await;
+        // The players of the old world are no longer visible to us
+        let old_players: Vec<uuid::Uuid> =
+            current_world.players.read().await.keys().copied().collect();
+        if !old_players.is_empty() {
+            self.client
+                .send_packet(&CRemovePlayerInfo::new(
+                    (old_players.len() as i32).into(),
+                    &old_players,
+                ))
+                .await;
+        }
+        *self.living_entity.entity.world.write().await = new_world.clone();
+        new_world
+            .players
+            .write()
+            .await
+            .insert(self.gameprofile.id, self.clone());
+        self.unload_watched_chunks(&current_world).await;
+    }
+
+    /// Teleports the player, moving it to another world if needed.
+    ///
+    /// A [`PlayerTeleportEvent`] is fired first, which can change the destination. If it is
+    /// cancelled, the client is told its current position again, since it may have moved already
+    /// (e.g. through a portal). Returns whether the player was teleported.
+    pub async fn teleport(
+        self: &Arc<Self>,
+        world: Arc<World>,
+        position: Vector3<f64>,
+        yaw: f32,
+        pitch: f32,
+        cause: TeleportCause,
+    ) -> bool {
+        let entity = &self.living_entity.entity;
+        let event = PlayerTeleportEvent::new(
+            self.clone(),
+            cause,
+            entity.pos.load(),
+            position,
+            yaw,
+            pitch,
+            world,
+        );
+        let event = PLUGIN_MANAGER
+            .lock()
+            .await
+            .fire::<PlayerTeleportEvent>(event)
+            .await;
+
+        if event.cancelled {
+            self.request_teleport(entity.pos.load(), entity.yaw.load(), entity.pitch.load())
+                .await;
+            return false;
+        }
+
+        // Portals may lead beyond the world border, so they take the player onto it instead
+        let destination = {
+            let border = event.world.worldborder.lock().await;
+            if border.contains(event.to.x, event.to.z) {
+                Some(event.to)
+            } else if cause == TeleportCause::Portal {
+                Some(border.clamp(event.to))
+            } else {
+                None
+            }
+        };
+        let Some(destination) = destination else {
+            self.send_system_message(
+                &TextComponent::text("You can not teleport beyond the world border")
+                    .color_named(NamedColor::Red),
+            )
+            .await;
+            self.request_teleport(entity.pos.load(), entity.yaw.load(), entity.pitch.load())
+                .await;
+            return false;
+        };
+
+        // Keeps the destination loaded until the player arrived, even if nobody else is there
+        event.world.level.add_ticket(
+            Vector2::new(
+                get_section_cord(destination.x.floor() as i32),
+                get_section_cord(destination.z.floor() as i32),
+            ),
+            TicketType::Teleport,
+            BLOCK_TICKING_LEVEL,
+        );
+
+        if Arc::ptr_eq(&self.world().await, &event.world) {
+            entity.teleport(destination, event.yaw, event.pitch).await;
+        } else {
+            self.clone()
+                .teleport_world(
+                    event.world,
+                    Some(destination),
+                    Some(event.yaw),
+                    Some(event.pitch),
+                )
+                .await;
+        }
+        true
+    }
+
+    /// Exchanges the player info with all other players in the world and spawns their entities
+    /// for this player. Our own entity is spawned for them by [`World::send_world_info`].
+    pub(crate) async fn send_world_players(&self, world: &World) {
+        let gameprofile = &self.gameprofile;
+        let player_info = CPlayerInfoUpdate::new(PLAYER_INFO_ACTIONS, &[self.player_info().await]);
+        if self.vanished.load(Ordering::Relaxed) {
+            self.client.send_packet(&player_info).await;
+        } else {
+            world.broadcast_packet_all(&player_info).await;
+        }
+
+        let players = world.players.read().await;
+        let others: Vec<&Arc<Self>> = players
+            .values()
+            .filter(|player| {
+                player.gameprofile.id != gameprofile.id && !player.vanished.load(Ordering::Relaxed)
+            })
+            .collect();
+        if others.is_empty() {
+            return;
+        }
+
+        let mut entries = Vec::with_capacity(others.len());
+        for player in &others {
+            entries.push(player.player_info().await);
+        }
+        self.client
+            .send_packet(&CPlayerInfoUpdate::new(PLAYER_INFO_ACTIONS, &entries))
+            .await;
+
+        for player in others {
+            let entity = &player.living_entity.entity;
+            self.client
+                .send_packet(&CSpawnEntity::new(
+                    player.entity_id().into(),
+                    player.gameprofile.id,
+                    i32::from(EntityType::PLAYER.id).into(),
+                    entity.pos.load(),
+                    entity.pitch.load(),
+                    entity.yaw.load(),
+                    entity.head_yaw.load(),
+                    0.into(),
+                    Vector3::new(0.0, 0.0, 0.0),
+                ))
+                .await;
+        }
+    }
+
     /// Yaw and Pitch in degrees
     /// Rarly used, For example when waking up player from bed or first time spawn. Otherwise entity teleport is used
     /// Player should respond with the `SConfirmTeleport` packet
@@ -800,6 +1054,40 @@ impl Player {
             .await;
     }
 
+    /// Whether the player may not build at the position because it is too close to the world spawn.
+    /// Like in vanilla, this only applies in the overworld and when there is at least one operator.
+    pub async fn is_spawn_protected(
+        &self,
+        server: &Server,
+        world: &World,
+        position: &BlockPos,
+    ) -> bool {
+        let radius = server.spawn_protection();
+        if radius == 0
+            || !matches!(
+                world.dimension_type,
+                DimensionType::Overworld | DimensionType::OverworldCaves
+            )
+            || self.permission_lvl.load() >= PermissionLvl::One
+            || OPERATOR_CONFIG.read().await.ops.is_empty()
+        {
+            return false;
+        }
+
+        let info = &world.level.level_info;
+        let distance = (position.0.x - info.spawn_x)
+            .abs()
+            .max((position.0.z - info.spawn_z).abs());
+        distance <= radius as i32
+    }
+
+    /// Whether the player may place and break blocks at the position, which must be inside of
+    /// the world border and not spawn protected
+    pub async fn may_build_at(&self, server: &Server, world: &World, position: &BlockPos) -> bool {
+        world.worldborder.lock().await.contains_block(position)
+            && !self.is_spawn_protected(server, world, position).await
+    }
+
     pub fn block_interaction_range(&self) -> f64 {
         if self.gamemode.load() == GameMode::Creative {
             5.0
@@ -960,6 +1248,208 @@ impl Player {
             .await;
     }
 
+    /// Hides the player from all other players, removing them from the player list and
+    /// despawning their entity, or shows them again.
+    pub async fn set_vanished(&self, vanished: bool) {
+        if self.vanished.swap(vanished, Ordering::Relaxed) == vanished {
+            return;
+        }
+        let world = self.world().await;
+        let uuid = self.gameprofile.id;
+        if vanished {
+            world
+                .broadcast_packet_except(&[uuid], &CRemovePlayerInfo::new(1.into(), &[uuid]))
+                .await;
+            world
+                .broadcast_packet_except(&[uuid], &CRemoveEntities::new(&[self.entity_id().into()]))
+                .await;
+            return;
+        }
+
+        world
+            .broadcast_packet_except(
+                &[uuid],
+                &CPlayerInfoUpdate::new(PLAYER_INFO_ACTIONS, &[self.player_info().await]),
+            )
+            .await;
+        world
+            .broadcast_packet_except(&[uuid], &self.living_entity.entity.create_spawn_packet(0))
+            .await;
+        self.send_client_information().await;
+    }
+
+    /// The entry adding the player to the player list of others, with the actions in
+    /// [`PLAYER_INFO_ACTIONS`]
+    pub async fn player_info(&self) -> pumpkin_protocol::client::play::Player<'_> {
+        pumpkin_protocol::client::play::Player {
+            uuid: self.gameprofile.id,
+            actions: vec![
+                PlayerAction::AddPlayer {
+                    name: &self.gameprofile.name,
+                    properties: &self.gameprofile.properties,
+                },
+                PlayerAction::UpdateListed(true),
+                PlayerAction::UpdateDisplayName(self.list_name().await),
+            ],
+        }
+    }
+
+    /// The nickname of the player, which is shown instead of the account name in chat and the
+    /// player list. It is stored as the custom name of the player entity.
+    pub async fn nickname(&self) -> Option<TextComponent> {
+        self.living_entity.entity.custom_name.lock().await.clone()
+    }
+
+    /// The name of the player shown to others, which is their nickname if they have one
+    pub async fn display_name(&self) -> TextComponent {
+        self.nickname()
+            .await
+            .unwrap_or_else(|| TextComponent::text(self.gameprofile.name.clone()))
+    }
+
+    /// Returns the notice to show the player when they try to chat while muted, or `None` if
+    /// they are not muted
+    pub async fn mute_notice(&self) -> Option<TextComponent> {
+        MUTED_PLAYER_LIST
+            .write()
+            .await
+            .get_entry(&self.gameprofile)
+            .map(mute_notice)
+    }
+
+    /// Whether the player ignores the chat and private messages of the sender with `/ignore`.
+    /// Operators can not be ignored if that is disabled in the config.
+    pub async fn is_ignoring(&self, sender: &Self) -> bool {
+        if !ADVANCED_CONFIG.commands.allow_ignoring_operators
+            && sender.permission_lvl.load() != PermissionLvl::Zero
+        {
+            return false;
+        }
+        IGNORE_LIST
+            .read()
+            .await
+            .is_ignoring(&self.gameprofile.id, &sender.gameprofile.id)
+    }
+
+    /// The name of the player shown in chat, which is the display name surrounded by the prefix
+    /// and suffix set with `/chatformat`
+    pub async fn chat_name(&self) -> TextComponent {
+        let display_name = self.display_name().await;
+        let chat_formats = CHAT_FORMAT_LIST.read().await;
+        let Some(entry) = chat_formats.get_entry(&self.gameprofile.id) else {
+            return display_name;
+        };
+
+        // Children inherit the style of their parent, so the parts are siblings to keep the
+        // formatting of the prefix from spilling into the name
+        let mut name = TextComponent::text("");
+        if let Some(prefix) = &entry.prefix {
+            name = name.add_child(prefix.clone());
+        }
+        name = name.add_child(display_name);
+        if let Some(suffix) = &entry.suffix {
+            name = name.add_child(suffix.clone());
+        }
+        name
+    }
+
+    /// Sets the nickname of the player, or removes it. The account name used for bans and
+    /// permissions stays the same, as does the name above the player, which can only be changed
+    /// using teams.
+    pub async fn set_nickname(&self, nickname: Option<TextComponent>) {
+        *self.living_entity.entity.custom_name.lock().await = nickname;
+        self.update_list_name().await;
+    }
+
+    /// The name of the player in the player list, which is their nickname marked while they are
+    /// AFK
+    async fn list_name(&self) -> Option<TextComponent> {
+        if !self.afk.load(Ordering::Relaxed) {
+            return self.nickname().await;
+        }
+        Some(
+            TextComponent::text("")
+                .add_child(TextComponent::text("[AFK] ").color_named(NamedColor::Gray))
+                .add_child(self.display_name().await),
+        )
+    }
+
+    /// Sends the changed name of the player in the player list, only to themselves while they
+    /// are vanished
+    async fn update_list_name(&self) {
+        let packet = CPlayerInfoUpdate::new(
+            0x20,
+            &[pumpkin_protocol::client::play::Player {
+                uuid: self.gameprofile.id,
+                actions: vec![PlayerAction::UpdateDisplayName(self.list_name().await)],
+            }],
+        );
+        if self.vanished.load(Ordering::Relaxed) {
+            self.client.send_packet(&packet).await;
+        } else {
+            self.world().await.broadcast_packet_all(&packet).await;
+        }
+    }
+
+    /// Marks the player as AFK or back, which is shown in the player list and told to everyone if
+    /// enabled in the config
+    pub async fn set_afk(&self, server: &Server, afk: bool) {
+        if self.afk.swap(afk, Ordering::Relaxed) == afk {
+            return;
+        }
+        self.idle_ticks.store(0, Ordering::Relaxed);
+        self.update_list_name().await;
+
+        if !ADVANCED_CONFIG.afk.broadcast || self.vanished.load(Ordering::Relaxed) {
+            return;
+        }
+        let status = if afk {
+            " is now AFK"
+        } else {
+            " is no longer AFK"
+        };
+        let message = TextComponent::text("")
+            .add_child(self.display_name().await)
+            .add_child(TextComponent::text(status))
+            .color_named(NamedColor::Gray);
+        for player in server.get_all_players().await {
+            player.send_system_message(&message).await;
+        }
+    }
+
+    /// Resets how long the player has been idle and brings them back if they are AFK
+    pub async fn mark_active(&self, server: &Server) {
+        self.idle_ticks.store(0, Ordering::Relaxed);
+        if self.afk.load(Ordering::Relaxed) {
+            self.set_afk(server, false).await;
+        }
+    }
+
+    /// Counts how long the player has not moved or looked around and marks them as AFK once it is
+    /// longer than configured
+    pub async fn tick_afk(&self, server: &Server) {
+        let entity = &self.living_entity.entity;
+        let look = (entity.pos.load(), entity.yaw.load(), entity.pitch.load());
+        if self.idle_look.swap(look) != look {
+            self.mark_active(server).await;
+            return;
+        }
+
+        let idle_ticks = self.idle_ticks.fetch_add(1, Ordering::Relaxed) + 1;
+        let max_idle_ticks = ADVANCED_CONFIG.afk.idle_seconds.saturating_mul(20);
+        if max_idle_ticks != 0 && idle_ticks >= max_idle_ticks && !self.afk.load(Ordering::Relaxed)
+        {
+            self.set_afk(server, true).await;
+        }
+    }
+
+    /// Whether mobs leave the player alone, as they are vanished or AFK if that is enabled in the
+    /// config
+    pub fn is_ignored_by_mobs(&self) -> bool {
+        self.vanished.load(Ordering::Relaxed)
+            || (ADVANCED_CONFIG.afk.ignored_by_mobs && self.afk.load(Ordering::Relaxed))
+    }
+
     pub async fn can_harvest(&self, block: &State, block_name: &str) -> bool {
         !block.tool_required
             || self
@@ -1003,22 +1493,28 @@ impl Player {
 
     pub async fn drop_item(&self, server: &Server, drop_stack: bool) {
         let mut inv = self.inventory.lock().await;
-        if let Some(item) = inv.held_item_mut() {
-            let drop_amount = if drop_stack { item.item_count } else { 1 };
-            let entity = server.add_entity(
-                self.living_entity.entity.pos.load(),
-                EntityType::ITEM,
-                &self.world().await,
-            );
-            let item_entity = Arc::new(ItemEntity::new(
-                entity,
-                &ItemStack::new(drop_amount, item.item),
-            ));
-            self.world().await.spawn_entity(item_entity.clone()).await;
-            item_entity.send_meta_packet().await;
-            // decrase item in hotbar
-            inv.decrease_current_stack(drop_amount);
+        let Some(item) = inv.held_item_mut() else {
+            return;
+        };
+        let drop_amount = if drop_stack { item.item_count } else { 1 };
+        let mut dropped = item.clone();
+        dropped.item_count = drop_amount;
+        // decrase item in hotbar
+        inv.decrease_current_stack(drop_amount);
+        drop(inv);
+        self.drop_stack(server, &dropped).await;
+    }
+
+    /// Sets where the player respawns, telling them when it changed
+    pub async fn set_respawn_point(&self, point: RespawnPoint) {
+        let mut respawn_point = self.respawn_point.lock().await;
+        if respawn_point.as_ref() == Some(&point) {
+            return;
         }
+        *respawn_point = Some(point);
+        drop(respawn_point);
+        self.send_system_message(&TextComponent::translate("block.minecraft.set_spawn", []))
+            .await;
     }
 
     pub async fn send_system_message(&self, text: &TextComponent) {
@@ -1031,6 +1527,23 @@ impl Player {
             .await;
     }
 
+    /// Sends a plugin message (custom payload) on the given channel to the client
+    pub async fn send_plugin_message(&self, channel: &str, data: &[u8]) {
+        self.client
+            .send_packet(&CCustomPayload::new(channel, data))
+            .await;
+    }
+
+    /// Sends the tab list header and footer from the config, with placeholders resolved for this player
+    pub async fn send_tab_list(&self) {
+        let config = &ADVANCED_CONFIG.tab_list;
+        let header = TextComponent::text(placeholder::resolve(&config.header, Some(self)).await);
+        let footer = TextComponent::text(placeholder::resolve(&config.footer, Some(self)).await);
+        self.client
+            .send_packet(&CTabList::new(&header, &footer))
+            .await;
+    }
+
     /// Sets the player's experience level and updates the client
     pub async fn set_experience(&self, level: i32, progress: f32, points: i32) {
         self.experience_level.store(level, Ordering::Relaxed);
@@ -1107,25 +1620,108 @@ impl Player {
     }
 }
 
+/// Converts a slot of the inventory window to the slot number of saved player data, which are
+/// 0-8 for the hotbar, 9-35 for the main inventory, 100-103 for armor and -106 for the offhand.
+/// The crafting grid is not saved.
+fn inventory_slot_to_nbt(slot: usize) -> Option<i8> {
+    match slot {
+        5..=8 => Some(108 - slot as i8),
+        9..=35 => Some(slot as i8),
+        36..=44 => Some(slot as i8 - 36),
+        45 => Some(-106),
+        _ => None,
+    }
+}
+
+fn nbt_slot_to_inventory(slot: i8) -> Option<usize> {
+    match slot {
+        100..=103 => Some(108 - slot as usize),
+        9..=35 => Some(slot as usize),
+        0..=8 => Some(slot as usize + 36),
+        -106 => Some(45),
+        _ => None,
+    }
+}
+
 #[async_trait]
 impl NBTStorage for Player {
     async fn write_nbt(&self, nbt: &mut NbtCompound) {
         self.living_entity.write_nbt(nbt).await;
-        nbt.put_int(
-            "SelectedItemSlot",
-            self.inventory.lock().await.selected as i32,
-        );
+        let inventory = self.inventory.lock().await;
+        nbt.put_int("SelectedItemSlot", inventory.selected as i32);
+        let items = inventory
+            .slots()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(slot, stack)| {
+                let mut item = stack?.write_nbt();
+                item.put_byte("Slot", inventory_slot_to_nbt(slot)?);
+                Some(NbtTag::Compound(item))
+            })
+            .collect();
+        nbt.put("Inventory", NbtTag::List(items));
+        drop(inventory);
+        let ender_items = self
+            .ender_chest
+            .lock()
+            .await
+            .all_slots_ref()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(slot, stack)| {
+                let mut item = stack?.write_nbt();
+                item.put_byte("Slot", slot as i8);
+                Some(NbtTag::Compound(item))
+            })
+            .collect();
+        nbt.put("EnderItems", NbtTag::List(ender_items));
         self.abilities.lock().await.write_nbt(nbt).await;
 
         // Store total XP instead of individual components
         let total_exp = experience::points_to_level(self.experience_level.load(Ordering::Relaxed))
             + self.experience_points.load(Ordering::Relaxed);
         nbt.put_int("XpTotal", total_exp);
+
+        if let Some(point) = self.respawn_point.lock().await.as_ref() {
+            nbt.put_int("SpawnX", point.position.0.x);
+            nbt.put_int("SpawnY", point.position.0.y);
+            nbt.put_int("SpawnZ", point.position.0.z);
+            nbt.put_float("SpawnAngle", point.angle);
+            nbt.put("SpawnWorld", point.world.as_str());
+        }
     }
 
     async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
         self.living_entity.read_nbt(nbt).await;
-        self.inventory.lock().await.selected = nbt.get_int("SelectedItemSlot").unwrap_or(0) as u32;
+        let mut inventory = self.inventory.lock().await;
+        inventory.selected = nbt.get_int("SelectedItemSlot").unwrap_or(0) as u32;
+        for item in nbt.get_list("Inventory").unwrap_or_default() {
+            let Some(item) = item.extract_compound() else {
+                continue;
+            };
+            let Some(slot) = item.get_byte("Slot").and_then(nbt_slot_to_inventory) else {
+                continue;
+            };
+            // Items the server does not know are dropped, everything else keeps its components
+            let _ = inventory.set_slot(slot, ItemStack::read_nbt(item), true);
+        }
+        drop(inventory);
+        let mut ender_chest = self.ender_chest.lock().await;
+        let mut ender_slots = ender_chest.all_slots();
+        for item in nbt.get_list("EnderItems").unwrap_or_default() {
+            let Some(item) = item.extract_compound() else {
+                continue;
+            };
+            let Some(slot) = item
+                .get_byte("Slot")
+                .and_then(|slot| ender_slots.get_mut(usize::try_from(slot).ok()?))
+            else {
+                continue;
+            };
+            **slot = ItemStack::read_nbt(item);
+        }
+        drop(ender_slots);
+        drop(ender_chest);
         self.abilities.lock().await.read_nbt(nbt).await;
 
         // Load from total XP
@@ -1135,6 +1731,19 @@ impl NBTStorage for Player {
         self.experience_level.store(level, Ordering::Relaxed);
         self.experience_progress.store(progress);
         self.experience_points.store(points, Ordering::Relaxed);
+
+        if let (Some(x), Some(y), Some(z), Some(world)) = (
+            nbt.get_int("SpawnX"),
+            nbt.get_int("SpawnY"),
+            nbt.get_int("SpawnZ"),
+            nbt.get_string("SpawnWorld"),
+        ) {
+            *self.respawn_point.lock().await = Some(RespawnPoint {
+                world: world.clone(),
+                position: BlockPos(Vector3::new(x, y, z)),
+                angle: nbt.get_float("SpawnAngle").unwrap_or(0.0),
+            });
+        }
     }
 }
 
@@ -1187,6 +1796,21 @@ impl Player {
         packet: &mut RawPacket,
     ) -> Result<(), Box<dyn PumpkinError>> {
         let bytebuf = &mut packet.bytebuf;
+        // Commands don't count, so `/afk` can mark the player as AFK
+        if matches!(
+            packet.id.0,
+            SChatMessage::PACKET_ID
+                | SInteract::PACKET_ID
+                | SPlayerAction::PACKET_ID
+                | SPlayerCommand::PACKET_ID
+                | SClickContainer::PACKET_ID
+                | SSetHeldItem::PACKET_ID
+                | SSwingArm::PACKET_ID
+                | SUseItemOn::PACKET_ID
+                | SUseItem::PACKET_ID
+        ) {
+            self.mark_active(server).await;
+        }
         match packet.id.0 {
             SConfirmTeleport::PACKET_ID => {
                 self.handle_confirm_teleport(SConfirmTeleport::read(bytebuf)?)
@@ -1203,7 +1827,7 @@ impl Player {
                     .await;
             }
             SClientCommand::PACKET_ID => {
-                self.handle_client_status(SClientCommand::read(bytebuf)?)
+                self.handle_client_status(server, SClientCommand::read(bytebuf)?)
                     .await;
             }
             SPlayerInput::PACKET_ID => {
@@ -1257,6 +1881,10 @@ impl Player {
                 self.handle_click_container(server, SClickContainer::read(bytebuf)?)
                     .await?;
             }
+            SRenameItem::PACKET_ID => {
+                self.handle_rename_item(server, SRenameItem::read(bytebuf)?)
+                    .await;
+            }
             SSetHeldItem::PACKET_ID => {
                 self.handle_set_held_item(SSetHeldItem::read(bytebuf)?)
                     .await;
@@ -1286,6 +1914,10 @@ impl Player {
             SPCookieResponse::PACKET_ID => {
                 self.handle_cookie_response(SPCookieResponse::read(bytebuf)?);
             }
+            SCustomPayload::PACKET_ID => {
+                self.handle_custom_payload(server, SCustomPayload::read(bytebuf)?)
+                    .await;
+            }
             SCloseContainer::PACKET_ID => {
                 self.handle_close_container(server, SCloseContainer::read(bytebuf)?)
                     .await;
@@ -1307,6 +1939,18 @@ pub enum TitleMode {
     ActionBar,
 }
 
+/// Where a player respawns after dying, set by sleeping in a bed or using a charged respawn
+/// anchor. If the block is gone or obstructed when they die, they respawn at the world spawn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RespawnPoint {
+    /// The name of the world the block is in, see [`World::name`]
+    pub world: String,
+    /// The position of the bed head or respawn anchor
+    pub position: BlockPos,
+    /// The yaw the player respawns with
+    pub angle: f32,
+}
+
 /// Represents a player's abilities and special powers.
 ///
 /// This struct contains information about the player's current abilities, such as flight, invulnerability, and creative mode.
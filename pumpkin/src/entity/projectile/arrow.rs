@@ -0,0 +1,400 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_data::{
+    damage::DamageType,
+    item::Item,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::{
+    client::play::{CEntityVelocity, CTakeItemEntity, MetaDataType, Metadata},
+    codec::var_int::VarInt,
+};
+use pumpkin_util::{
+    math::{position::BlockPos, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_world::{block::registry::get_block_collision_shapes, item::ItemStack};
+use rand::Rng;
+
+use crate::{
+    block::blocks::container,
+    entity::{living::LivingEntity, player::Player, Entity, EntityBase, NBTStorage},
+    server::Server,
+    world::World,
+};
+
+use super::{Hit, Projectile};
+
+/// How much faster arrows fall each tick
+const GRAVITY: f64 = 0.05;
+
+/// How much of their speed arrows keep each tick in the air and in water
+const DRAG: f64 = 0.99;
+const WATER_DRAG: f64 = 0.6;
+
+/// The damage of an arrow flying one block per tick, like in vanilla
+const BASE_DAMAGE: f64 = 2.0;
+
+/// How strongly arrows knock back what they hit, like in vanilla
+const KNOCKBACK: f64 = 0.4;
+
+/// Arrows despawn after lying in the ground for a minute, like in vanilla
+const DESPAWN_TICKS: u32 = 1200;
+
+/// Arrows touching a player in a box this much larger are picked up, like in vanilla
+const PICKUP_RANGE: (f64, f64, f64) = (1.0, 0.5, 1.0);
+
+/// The metadata index of the flags of an arrow, where the lowest bit means critical
+const FLAGS_INDEX: u8 = 8;
+
+/// The metadata index of whether an arrow is stuck in a block
+const IN_GROUND_INDEX: u8 = 10;
+
+/// Who can pick up an arrow lying in the ground
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArrowPickup {
+    Disallowed,
+    Allowed,
+    /// Only players in creative mode, who don't get the arrow, e.g. arrows shot in creative
+    CreativeOnly,
+}
+
+impl ArrowPickup {
+    fn from_id(id: i8) -> Self {
+        match id {
+            1 => Self::Allowed,
+            2 => Self::CreativeOnly,
+            _ => Self::Disallowed,
+        }
+    }
+
+    fn id(self) -> i8 {
+        self as i8
+    }
+}
+
+pub struct ArrowEntity {
+    pub projectile: Projectile,
+    /// The block the arrow is stuck in, if it is not flying
+    stuck_in: AtomicCell<Option<BlockPos>>,
+    /// Ticks the arrow has been lying in the ground, see [`DESPAWN_TICKS`]
+    life: AtomicU32,
+    /// Critical arrows, from fully drawn bows, do more damage
+    critical: AtomicBool,
+    pickup: AtomicCell<ArrowPickup>,
+}
+
+impl ArrowEntity {
+    pub fn new(entity: Entity, owner: &Entity) -> Self {
+        Self::from_projectile(Projectile::new(entity, owner))
+    }
+
+    /// An arrow which nobody shot, e.g. from a dispenser
+    pub fn without_owner(entity: Entity) -> Self {
+        Self::from_projectile(Projectile::without_owner(entity))
+    }
+
+    fn from_projectile(projectile: Projectile) -> Self {
+        Self {
+            projectile,
+            stuck_in: AtomicCell::new(None),
+            life: AtomicU32::new(0),
+            critical: AtomicBool::new(false),
+            pickup: AtomicCell::new(ArrowPickup::Disallowed),
+        }
+    }
+
+    pub fn set_critical(&self, critical: bool) {
+        self.critical.store(critical, Ordering::Relaxed);
+    }
+
+    pub fn set_pickup(&self, pickup: ArrowPickup) {
+        self.pickup.store(pickup);
+    }
+
+    /// Shows the players whether the arrow is critical and whether it is stuck in a block
+    pub async fn send_meta_packet(&self) {
+        let entity = &self.projectile.entity;
+        entity
+            .send_meta_data(Metadata::new(
+                FLAGS_INDEX,
+                MetaDataType::Byte,
+                u8::from(self.critical.load(Ordering::Relaxed)),
+            ))
+            .await;
+        entity
+            .send_meta_data(Metadata::new(
+                IN_GROUND_INDEX,
+                MetaDataType::Boolean,
+                self.stuck_in.load().is_some(),
+            ))
+            .await;
+    }
+
+    /// Sticks the arrow into the block it hit
+    async fn stick(&self, world: &World, block: BlockPos) {
+        let entity = &self.projectile.entity;
+        self.stuck_in.store(Some(block));
+        self.critical.store(false, Ordering::Relaxed);
+        entity.velocity.store(Vector3::new(0.0, 0.0, 0.0));
+        self.projectile.send_velocity(world).await;
+        self.send_meta_packet().await;
+        let pitch = 1.2 / rand::thread_rng().gen_range(0.9..1.1);
+        world
+            .play_sound_raw(
+                Sound::EntityArrowHit as u16,
+                SoundCategory::Neutral,
+                &entity.pos.load(),
+                1.0,
+                pitch,
+            )
+            .await;
+    }
+
+    /// Damages and knocks back the entity the arrow hit, more the faster it flies
+    async fn hit_entity(&self, world: &World, target: &Arc<dyn EntityBase>) {
+        let entity = &self.projectile.entity;
+        let Some(living) = target.get_living_entity() else {
+            return;
+        };
+        let velocity = entity.velocity.load();
+        let mut damage = (velocity.length() * BASE_DAMAGE)
+            .clamp(0.0, f64::from(i32::MAX))
+            .ceil();
+        if self.critical.load(Ordering::Relaxed) {
+            let bonus = rand::thread_rng().gen_range(0..damage as i32 / 2 + 2);
+            damage = (damage + f64::from(bonus)).min(f64::from(i32::MAX));
+        }
+
+        let owner = self.projectile.owner(world).await;
+        let hurt = living.check_damage(damage as f32)
+            && living
+                .damage_with_context(
+                    damage as f32,
+                    DamageType::ARROW,
+                    Some(entity.pos.load()),
+                    Some(entity),
+                    owner.as_ref().map(|owner| owner.get_entity()),
+                )
+                .await;
+        if !hurt {
+            // Arrows bounce off entities they can't hurt
+            entity.velocity.store(velocity * -0.1);
+            self.projectile.send_velocity(world).await;
+            return;
+        }
+
+        let target_entity = target.get_entity();
+        target_entity.knockback(KNOCKBACK, -velocity.x, -velocity.z);
+        let target_velocity = target_entity.velocity.load();
+        world
+            .broadcast_packet_all(&CEntityVelocity::new(
+                &VarInt(target_entity.entity_id),
+                target_velocity.x,
+                target_velocity.y,
+                target_velocity.z,
+            ))
+            .await;
+        world
+            .play_sound(
+                Sound::EntityArrowHit,
+                SoundCategory::Neutral,
+                &entity.pos.load(),
+            )
+            .await;
+        entity.remove().await;
+    }
+
+    /// Lets a player touching the arrow pick it up, if it is allowed
+    async fn pickup(&self, world: &World) {
+        let pickup = self.pickup.load();
+        if pickup == ArrowPickup::Disallowed {
+            return;
+        }
+        let area = self.projectile.entity.bounding_box.load();
+        let (x, y, z) = PICKUP_RANGE;
+        let players: Vec<_> = world
+            .players
+            .read()
+            .await
+            .values()
+            .filter(|player| {
+                let gamemode = player.gamemode.load();
+                gamemode != GameMode::Spectator
+                    && (pickup == ArrowPickup::Allowed || gamemode == GameMode::Creative)
+                    && player.living_entity.health.load() > 0.0
+                    && player
+                        .living_entity
+                        .entity
+                        .bounding_box
+                        .load()
+                        .expand(x, y, z)
+                        .intersects(&area)
+            })
+            .cloned()
+            .collect();
+        for player in players {
+            if self.pickup_by(world, &player, pickup).await {
+                return;
+            }
+        }
+    }
+
+    /// Gives the arrow to the player, if it fits into their inventory. Returns whether it was
+    /// taken.
+    async fn pickup_by(&self, world: &World, player: &Player, pickup: ArrowPickup) -> bool {
+        // Another player may have been faster
+        if self.pickup.swap(ArrowPickup::Disallowed) == ArrowPickup::Disallowed {
+            return true;
+        }
+        if pickup == ArrowPickup::Allowed {
+            let mut inventory = player.inventory().lock().await;
+            let mut slots: Vec<_> = inventory.slots_with_hotbar_first().collect();
+            let rest =
+                container::add_to_slots(&mut slots, |_| true, ItemStack::new(1, Item::ARROW));
+            drop(slots);
+            drop(inventory);
+            if rest.is_some() {
+                self.pickup.store(pickup);
+                return false;
+            }
+            player.set_container_content(None).await;
+        }
+
+        let entity = &self.projectile.entity;
+        world
+            .broadcast_packet_all(&CTakeItemEntity::new(
+                entity.entity_id.into(),
+                player.entity_id().into(),
+                1.into(),
+            ))
+            .await;
+        entity.remove().await;
+        true
+    }
+
+    /// Whether the block the arrow is stuck in is gone, so it falls down
+    async fn lost_block(&self, world: &World) -> bool {
+        let Some(block) = self.stuck_in.load() else {
+            return false;
+        };
+        world
+            .get_block_state_id(&block)
+            .await
+            .ok()
+            .and_then(get_block_collision_shapes)
+            .is_none_or(|shapes| shapes.is_empty())
+    }
+
+    /// The block in front of the arrow, which an arrow loaded from the ground is stuck in
+    fn block_in_front(&self) -> BlockPos {
+        let entity = &self.projectile.entity;
+        let (yaw, pitch) = (
+            f64::from(entity.yaw.load()).to_radians(),
+            f64::from(entity.pitch.load()).to_radians(),
+        );
+        let direction = Vector3::new(
+            yaw.sin() * pitch.cos(),
+            pitch.sin(),
+            yaw.cos() * pitch.cos(),
+        );
+        let front = entity.pos.load().add(&(direction * 0.1));
+        BlockPos(Vector3::new(
+            front.x.floor() as i32,
+            front.y.floor() as i32,
+            front.z.floor() as i32,
+        ))
+    }
+}
+
+#[async_trait]
+impl EntityBase for ArrowEntity {
+    async fn tick(&self, _server: &Server) {
+        let entity = &self.projectile.entity;
+        let world = entity.world.read().await.clone();
+
+        if self.stuck_in.load().is_some() {
+            if self.lost_block(&world).await {
+                // Falls down a little randomly, like in vanilla
+                self.stuck_in.store(None);
+                self.life.store(0, Ordering::Relaxed);
+                let velocity = {
+                    let mut rng = rand::thread_rng();
+                    Vector3::new(
+                        rng.gen::<f64>() * 0.2,
+                        rng.gen::<f64>() * 0.2,
+                        rng.gen::<f64>() * 0.2,
+                    )
+                };
+                entity.velocity.store(velocity);
+                self.send_meta_packet().await;
+            } else {
+                if self.life.fetch_add(1, Ordering::Relaxed) + 1 >= DESPAWN_TICKS {
+                    entity.remove().await;
+                    return;
+                }
+                self.pickup(&world).await;
+                return;
+            }
+        }
+
+        match self.projectile.fly(&world, GRAVITY, DRAG, WATER_DRAG).await {
+            Some(Hit::Block(block)) => self.stick(&world, block).await,
+            Some(Hit::Entity(target)) => self.hit_entity(&world, &target).await,
+            None => {}
+        }
+    }
+
+    async fn save_nbt(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.projectile.entity.write_identity(&mut nbt);
+        self.write_nbt(&mut nbt).await;
+        Some(nbt)
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.projectile.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+
+    fn spawn_data(&self) -> i32 {
+        self.projectile.spawn_data()
+    }
+}
+
+#[async_trait]
+impl NBTStorage for ArrowEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.projectile.entity.write_nbt(nbt).await;
+        nbt.put_bool("inGround", self.stuck_in.load().is_some());
+        nbt.put_short(
+            "life",
+            i16::try_from(self.life.load(Ordering::Relaxed)).unwrap_or(i16::MAX),
+        );
+        nbt.put_bool("crit", self.critical.load(Ordering::Relaxed));
+        nbt.put_byte("pickup", self.pickup.load().id());
+    }
+
+    async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
+        self.projectile.entity.read_nbt(nbt).await;
+        if nbt.get_bool("inGround").unwrap_or(false) {
+            self.stuck_in.store(Some(self.block_in_front()));
+        }
+        if let Some(life) = nbt.get_short("life") {
+            *self.life.get_mut() = u32::try_from(life).unwrap_or(0);
+        }
+        *self.critical.get_mut() = nbt.get_bool("crit").unwrap_or(false);
+        if let Some(pickup) = nbt.get_byte("pickup") {
+            self.pickup.store(ArrowPickup::from_id(pickup));
+        }
+    }
+}
@@ -1,20 +1,84 @@
-use std::f32::{self};
+//! Projectiles fly with gravity and drag and hit the first block or entity on their way.
+//! [`Projectile`] does the flying, while e.g. [`arrow::ArrowEntity`] and
+//! [`thrown_item::ThrownItemEntity`] decide what happens when they hit something.
 
-use pumpkin_util::math::vector3::Vector3;
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
 
-use super::{living::LivingEntity, Entity, EntityBase};
+use pumpkin_protocol::{client::play::CEntityVelocity, codec::var_int::VarInt};
+use pumpkin_util::{
+    math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_world::block::registry::get_block_collision_shapes;
 
-pub struct ThrownItemEntity {
-    entity: Entity,
+use crate::{block::blocks::falling::is_water, world::World};
+
+use super::{physics, Entity, EntityBase, EntityId};
+
+pub mod arrow;
+pub mod thrown_item;
+
+/// For how many ticks a projectile can't hit the entity which shot it, so it doesn't hit them
+/// right away
+const OWNER_IMMUNITY_TICKS: u32 = 5;
+
+/// How much larger entities are for projectiles, like in vanilla
+const HIT_MARGIN: f64 = 0.3;
+
+/// What a projectile hit
+pub enum Hit {
+    Block(BlockPos),
+    Entity(Arc<dyn EntityBase>),
+}
+
+/// The part all projectiles share: moving and finding what they hit
+pub struct Projectile {
+    pub entity: Entity,
+    /// The entity which shot or threw the projectile, if any
+    owner: Option<(EntityId, uuid::Uuid)>,
+    /// How many ticks the projectile has been flying
+    flight_ticks: AtomicU32,
 }
 
-impl ThrownItemEntity {
+impl Projectile {
+    /// A projectile which starts at the eyes of its owner
     pub fn new(entity: Entity, owner: &Entity) -> Self {
         let mut owner_pos = owner.pos.load();
         owner_pos.y = (owner_pos.y + f64::from(owner.standing_eye_height)) - 0.1;
-        entity.pos.store(owner_pos);
-        Self { entity }
+        entity.set_pos(owner_pos);
+        Self {
+            entity,
+            owner: Some((owner.entity_id, owner.entity_uuid)),
+            flight_ticks: AtomicU32::new(0),
+        }
+    }
+
+    /// A projectile which nobody threw, e.g. an arrow shot by a dispenser
+    pub fn without_owner(entity: Entity) -> Self {
+        Self {
+            entity,
+            owner: None,
+            flight_ticks: AtomicU32::new(0),
+        }
     }
+
+    /// The entity which shot or threw the projectile, if it is still in the world
+    pub async fn owner(&self, world: &World) -> Option<Arc<dyn EntityBase>> {
+        let (_, uuid) = self.owner?;
+        if let Some(player) = world.get_player_by_uuid(uuid).await {
+            return Some(player as Arc<dyn EntityBase>);
+        }
+        world.entities.read().await.get(&uuid).cloned()
+    }
+
+    /// The data of the spawn packet, which tells the client who shot the projectile
+    pub fn spawn_data(&self) -> i32 {
+        self.owner.map_or(0, |(id, _)| id)
+    }
+
     pub fn set_velocity_from(
         &self,
         shooter: &Entity,
@@ -43,7 +107,7 @@ impl ThrownItemEntity {
             .velocity
             .store(self.entity.velocity.load().add_raw(
                 shooter_vel.x,
-                if shooter.on_ground.load(std::sync::atomic::Ordering::Relaxed) {
+                if shooter.on_ground.load(Ordering::Relaxed) {
                     0.0
                 } else {
                     shooter_vel.y
@@ -51,6 +115,7 @@ impl ThrownItemEntity {
                 shooter_vel.z,
             ));
     }
+
     /// The velocity and rotation will be set to the same direction.
     pub fn set_velocity(&self, x: f64, y: f64, z: f64, power: f64, uncertainty: f64) {
         fn next_triangular(mode: f64, deviation: f64) -> f64 {
@@ -65,20 +130,210 @@ impl ThrownItemEntity {
             )
             .multiply(power, power, power);
         self.entity.velocity.store(velocity);
+        self.face_velocity();
+    }
+
+    /// Turns the projectile into the direction it flies
+    fn face_velocity(&self) {
+        let velocity = self.entity.velocity.load();
         let len = velocity.horizontal_length();
         self.entity.set_rotation(
             velocity.x.atan2(velocity.z) as f32 * 57.295_776,
             velocity.y.atan2(len) as f32 * 57.295_776,
         );
     }
+
+    /// Moves the projectile by its velocity, up to the first block or entity in the way, and
+    /// slows it down afterwards. Returns what it hit.
+    pub async fn fly(
+        &self,
+        world: &World,
+        gravity: f64,
+        drag: f64,
+        water_drag: f64,
+    ) -> Option<Hit> {
+        let entity = &self.entity;
+        let pos = entity.pos.load();
+        let velocity = entity.velocity.load();
+        let target = pos.add(&velocity);
+
+        let block_hit = raycast_blocks(world, pos, velocity).await;
+        let reach = block_hit.as_ref().map_or(1.0, |(_, t)| *t);
+        let entity_hit = self.raycast_entities(world, pos, velocity * reach).await;
+        let (hit, new_pos) = match (entity_hit, block_hit) {
+            (Some((hit, t)), _) => (Some(Hit::Entity(hit)), pos.add(&(velocity * (reach * t)))),
+            (None, Some((block, t))) => {
+                // Stay a little in front of the block, like in vanilla
+                let back = velocity.normalize() * 0.05;
+                (Some(Hit::Block(block)), pos.add(&(velocity * t)).sub(&back))
+            }
+            (None, None) => (None, target),
+        };
+
+        entity.set_pos(new_pos);
+        physics::send_movement(entity, world, pos, new_pos, false).await;
+        if hit.is_none() {
+            self.face_velocity();
+            let in_water = world
+                .get_block_state_id(&entity.block_pos.load())
+                .await
+                .is_ok_and(is_water);
+            let drag = if in_water { water_drag } else { drag };
+            let mut velocity = velocity * drag;
+            velocity.y -= gravity;
+            entity.velocity.store(velocity);
+        }
+        self.flight_ticks.fetch_add(1, Ordering::Relaxed);
+        hit
+    }
+
+    /// Finds the closest entity the projectile can hit on its way
+    async fn raycast_entities(
+        &self,
+        world: &World,
+        from: Vector3<f64>,
+        delta: Vector3<f64>,
+    ) -> Option<(Arc<dyn EntityBase>, f64)> {
+        let owner_immune = self.flight_ticks.load(Ordering::Relaxed) < OWNER_IMMUNITY_TICKS;
+        let mut targets: Vec<Arc<dyn EntityBase>> =
+            world.entities.read().await.values().cloned().collect();
+        for player in world.players.read().await.values() {
+            if player.gamemode.load() != GameMode::Spectator {
+                targets.push(player.clone());
+            }
+        }
+
+        targets
+            .into_iter()
+            .filter(|target| {
+                let target_entity = target.get_entity();
+                target_entity.entity_uuid != self.entity.entity_uuid
+                    && !(owner_immune
+                        && self
+                            .owner
+                            .is_some_and(|(_, uuid)| uuid == target_entity.entity_uuid))
+                    && target
+                        .get_living_entity()
+                        .is_some_and(|living| living.health.load() > 0.0)
+            })
+            .filter_map(|target| {
+                let area = target
+                    .get_entity()
+                    .bounding_box
+                    .load()
+                    .expand(HIT_MARGIN, HIT_MARGIN, HIT_MARGIN);
+                let t = ray_intersection(from, delta, &area)?;
+                Some((target, t))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+
+    /// Shows the players the velocity of the projectile, e.g. after it bounced off something
+    pub async fn send_velocity(&self, world: &World) {
+        let velocity = self.entity.velocity.load();
+        world
+            .broadcast_packet_all(&CEntityVelocity::new(
+                &VarInt(self.entity.entity_id),
+                velocity.x,
+                velocity.y,
+                velocity.z,
+            ))
+            .await;
+    }
 }
 
-impl EntityBase for ThrownItemEntity {
-    fn get_entity(&self) -> &Entity {
-        &self.entity
+/// Finds the first block with a collision shape on the way, and how far along the way it is,
+/// from 0 to 1
+async fn raycast_blocks(
+    world: &World,
+    from: Vector3<f64>,
+    delta: Vector3<f64>,
+) -> Option<(BlockPos, f64)> {
+    let to = from.add(&delta);
+    let mut closest: Option<(BlockPos, f64)> = None;
+    for x in from.x.min(to.x).floor() as i32..=from.x.max(to.x).floor() as i32 {
+        for y in from.y.min(to.y).floor() as i32..=from.y.max(to.y).floor() as i32 {
+            for z in from.z.min(to.z).floor() as i32..=from.z.max(to.z).floor() as i32 {
+                let block = BlockPos(Vector3::new(x, y, z));
+                let Ok(state_id) = world.get_block_state_id(&block).await else {
+                    continue;
+                };
+                let offset = Vector3::new(f64::from(x), f64::from(y), f64::from(z));
+                for shape in get_block_collision_shapes(state_id).unwrap_or_default() {
+                    let area = BoundingBox::new(
+                        Vector3::new(shape.min[0], shape.min[1], shape.min[2]).add(&offset),
+                        Vector3::new(shape.max[0], shape.max[1], shape.max[2]).add(&offset),
+                    );
+                    if let Some(t) = ray_intersection(from, delta, &area) {
+                        if closest.is_none_or(|(_, closest)| t < closest) {
+                            closest = Some((block, t));
+                        }
+                    }
+                }
+            }
+        }
     }
+    closest
+}
 
-    fn get_living_entity(&self) -> Option<&LivingEntity> {
-        None
+/// Where the way from `from` by `delta` enters the box, from 0 to 1. Ways starting inside the
+/// box enter it at 0.
+fn ray_intersection(from: Vector3<f64>, delta: Vector3<f64>, area: &BoundingBox) -> Option<f64> {
+    let mut enter = 0.0_f64;
+    let mut exit = 1.0_f64;
+    for (start, delta, min, max) in [
+        (from.x, delta.x, area.min.x, area.max.x),
+        (from.y, delta.y, area.min.y, area.max.y),
+        (from.z, delta.z, area.min.z, area.max.z),
+    ] {
+        if delta.abs() < 1.0e-9 {
+            if start < min || start > max {
+                return None;
+            }
+            continue;
+        }
+        let (a, b) = ((min - start) / delta, (max - start) / delta);
+        enter = enter.max(a.min(b));
+        exit = exit.min(a.max(b));
+        if enter > exit {
+            return None;
+        }
+    }
+    Some(enter)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{boundingbox::BoundingBox, vector3::Vector3};
+
+    use super::ray_intersection;
+
+    #[test]
+    fn finds_where_the_way_enters_a_box() {
+        let area = BoundingBox::new(Vector3::new(2.0, 0.0, 0.0), Vector3::new(3.0, 1.0, 1.0));
+        let from = Vector3::new(0.0, 0.5, 0.5);
+        assert_eq!(
+            ray_intersection(from, Vector3::new(4.0, 0.0, 0.0), &area),
+            Some(0.5)
+        );
+        // Too short
+        assert_eq!(
+            ray_intersection(from, Vector3::new(1.0, 0.0, 0.0), &area),
+            None
+        );
+        // Passes above
+        assert_eq!(
+            ray_intersection(from, Vector3::new(4.0, 2.0, 0.0), &area),
+            None
+        );
+        // Starts inside
+        assert_eq!(
+            ray_intersection(
+                Vector3::new(2.5, 0.5, 0.5),
+                Vector3::new(1.0, 0.0, 0.0),
+                &area
+            ),
+            Some(0.0)
+        );
     }
 }
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    damage::DamageType,
+    entity::EntityType,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_protocol::client::play::CEntityStatus;
+use rand::Rng;
+
+use crate::{
+    entity::{living::LivingEntity, mob, Entity, EntityBase},
+    plugin::api::events::player::player_teleport::TeleportCause,
+    server::Server,
+    world::World,
+};
+
+use super::{Hit, Projectile};
+
+/// How much faster thrown items fall each tick
+const GRAVITY: f64 = 0.03;
+
+/// How much of their speed thrown items keep each tick in the air and in water
+const DRAG: f64 = 0.99;
+const WATER_DRAG: f64 = 0.8;
+
+/// How much damage an ender pearl does to the player it teleports, like in vanilla
+const ENDER_PEARL_DAMAGE: f32 = 5.0;
+
+/// Items which are thrown and break when they hit something: snowballs, eggs and ender pearls
+pub struct ThrownItemEntity {
+    pub projectile: Projectile,
+}
+
+impl ThrownItemEntity {
+    pub fn new(entity: Entity, owner: &Entity) -> Self {
+        Self {
+            projectile: Projectile::new(entity, owner),
+        }
+    }
+
+    /// A thrown item which nobody threw, e.g. a snowball shot by a dispenser
+    pub fn without_owner(entity: Entity) -> Self {
+        Self {
+            projectile: Projectile::without_owner(entity),
+        }
+    }
+
+    async fn on_hit(&self, server: &Server, world: &Arc<World>, hit: Hit) {
+        let entity = &self.projectile.entity;
+        let owner = self.projectile.owner(world).await;
+        if let Hit::Entity(target) = &hit {
+            if let Some(living) = target.get_living_entity() {
+                // Snowballs only hurt blazes, but everything is knocked back
+                let damage = if entity.entity_type == EntityType::SNOWBALL
+                    && target.get_entity().entity_type == EntityType::BLAZE
+                {
+                    3.0
+                } else {
+                    0.0
+                };
+                living
+                    .damage_with_context(
+                        damage,
+                        DamageType::THROWN,
+                        Some(entity.pos.load()),
+                        Some(entity),
+                        owner.as_ref().map(|owner| owner.get_entity()),
+                    )
+                    .await;
+            }
+        }
+
+        match entity.entity_type {
+            EntityType::EGG => self.hatch(server, world).await,
+            EntityType::ENDER_PEARL => self.teleport_owner(world).await,
+            _ => {}
+        }
+        // Shows the particles of the item breaking
+        world
+            .broadcast_packet_all(&CEntityStatus::new(entity.entity_id, 3))
+            .await;
+        entity.remove().await;
+    }
+
+    /// Eggs hatch a chicken one in eight times, and sometimes four, like in vanilla
+    async fn hatch(&self, server: &Server, world: &Arc<World>) {
+        let count = {
+            let mut rng = rand::thread_rng();
+            if rng.gen_range(0..8) != 0 {
+                return;
+            }
+            if rng.gen_range(0..32) == 0 {
+                4
+            } else {
+                1
+            }
+        };
+        let position = self.projectile.entity.pos.load();
+        for _ in 0..count {
+            // TODO: Hatch baby chickens, once mobs can be babies
+            let chicken = mob::from_type(EntityType::CHICKEN, server, position, world).await;
+            world.spawn_entity(chicken).await;
+        }
+    }
+
+    /// Teleports the player who threw the ender pearl to where it landed
+    async fn teleport_owner(&self, world: &Arc<World>) {
+        let Some((_, uuid)) = self.projectile.owner else {
+            return;
+        };
+        // Players can only teleport within the world the pearl is in
+        let Some(player) = world.get_player_by_uuid(uuid).await else {
+            return;
+        };
+        let position = self.projectile.entity.pos.load();
+        let entity = &player.living_entity.entity;
+        if !player
+            .teleport(
+                world.clone(),
+                position,
+                entity.yaw.load(),
+                entity.pitch.load(),
+                TeleportCause::EnderPearl,
+            )
+            .await
+        {
+            return;
+        }
+        world
+            .play_sound(
+                Sound::EntityPlayerTeleport,
+                SoundCategory::Players,
+                &position,
+            )
+            .await;
+        player
+            .living_entity
+            .damage(ENDER_PEARL_DAMAGE, DamageType::ENDER_PEARL)
+            .await;
+    }
+}
+
+#[async_trait]
+impl EntityBase for ThrownItemEntity {
+    async fn tick(&self, server: &Server) {
+        let world = self.projectile.entity.world.read().await.clone();
+        if let Some(hit) = self.projectile.fly(&world, GRAVITY, DRAG, WATER_DRAG).await {
+            self.on_hit(server, &world, hit).await;
+        }
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.projectile.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+
+    fn spawn_data(&self) -> i32 {
+        self.projectile.spawn_data()
+    }
+}
@@ -0,0 +1,61 @@
+use crate::entity::player::Player;
+use crate::item::pumpkin_item::PumpkinItem;
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_data::item::Item;
+use pumpkin_data::world::WorldEvent;
+use pumpkin_macros::pumpkin_item;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::GameMode;
+use pumpkin_world::block::{registry::Block, BlockDirection};
+
+/// How many particles appear where bone meal was used
+const PARTICLES: i32 = 15;
+
+#[pumpkin_item("bone_meal")]
+pub struct BoneMealItem;
+
+#[async_trait]
+impl PumpkinItem for BoneMealItem {
+    async fn use_on_block(
+        &self,
+        _item: &Item,
+        player: &Player,
+        location: BlockPos,
+        _face: &BlockDirection,
+        block: &Block,
+        server: &Server,
+    ) {
+        let world = player.world().await;
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        if !server
+            .block_registry
+            .can_fertilize(&world, block, state_id, location)
+            .await
+        {
+            return;
+        }
+        server
+            .block_registry
+            .fertilize(server, &world, block, state_id, location)
+            .await;
+
+        if player.gamemode.load() != GameMode::Creative {
+            player
+                .modify_held_item(|held| {
+                    if let Some(stack) = held {
+                        stack.item_count -= 1;
+                        if stack.item_count == 0 {
+                            *held = None;
+                        }
+                    }
+                })
+                .await;
+        }
+        world
+            .sync_world_event(WorldEvent::BoneMealUsed, location, PARTICLES)
+            .await;
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+use crate::entity::projectile::arrow::{ArrowEntity, ArrowPickup};
+use crate::item::pumpkin_item::PumpkinItem;
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_item;
+use pumpkin_util::GameMode;
+use rand::Rng;
+
+#[pumpkin_item("bow")]
+pub struct BowItem;
+
+/// The speed of arrows from a fully drawn bow
+const POWER: f32 = 3.0;
+
+/// Bows drawn less than this shoot no arrow, like in vanilla
+const MIN_DRAW: f32 = 0.1;
+
+/// How far the bow is drawn after the ticks, from 0 to 1, like in vanilla
+fn draw_progress(ticks: u32) -> f32 {
+    let seconds = ticks as f32 / 20.0;
+    ((seconds * seconds + seconds * 2.0) / 3.0).min(1.0)
+}
+
+/// Takes an arrow from the inventory of the player. Returns whether there was one.
+async fn take_arrow(player: &Player) -> bool {
+    let mut inventory = player.inventory().lock().await;
+    let Some(slot) = inventory.slots_with_hotbar_first().find(|slot| {
+        slot.as_ref()
+            .is_some_and(|stack| stack.item.id == Item::ARROW.id)
+    }) else {
+        return false;
+    };
+    if let Some(stack) = slot {
+        stack.item_count -= 1;
+        if stack.item_count == 0 {
+            *slot = None;
+        }
+    }
+    drop(inventory);
+    player.set_container_content(None).await;
+    true
+}
+
+#[async_trait]
+impl PumpkinItem for BowItem {
+    async fn normal_use(&self, _item: &Item, player: &Player, _server: &Server) {
+        // The bow is drawn until the player lets go, see `release_use`
+        player.item_use_start.store(Some(
+            player
+                .tick_counter
+                .load(std::sync::atomic::Ordering::Relaxed),
+        ));
+    }
+
+    async fn release_use(&self, _item: &Item, player: &Player, ticks: u32, server: &Server) {
+        let power = draw_progress(ticks);
+        if power < MIN_DRAW {
+            return;
+        }
+        let creative = player.gamemode.load() == GameMode::Creative;
+        // Players in creative mode don't need arrows, and can't collect the ones they shot
+        if !creative && !take_arrow(player).await {
+            return;
+        }
+
+        let world = player.world().await;
+        let shooter = &player.living_entity.entity;
+        let entity = server.add_entity(shooter.pos.load(), EntityType::ARROW, &world);
+        let arrow = ArrowEntity::new(entity, shooter);
+        arrow.projectile.set_velocity_from(
+            shooter,
+            shooter.pitch.load(),
+            shooter.yaw.load(),
+            0.0,
+            power * POWER,
+            1.0,
+        );
+        arrow.set_critical(power >= 1.0);
+        arrow.set_pickup(if creative {
+            ArrowPickup::CreativeOnly
+        } else {
+            ArrowPickup::Allowed
+        });
+        let arrow = Arc::new(arrow);
+        world.spawn_entity(arrow.clone()).await;
+        arrow.send_meta_packet().await;
+
+        let pitch = 1.0 / rand::thread_rng().gen_range(1.2..1.6) + power * 0.5;
+        world
+            .play_sound_raw(
+                Sound::EntityArrowShoot as u16,
+                SoundCategory::Players,
+                &shooter.pos.load(),
+                1.0,
+                pitch,
+            )
+            .await;
+    }
+}
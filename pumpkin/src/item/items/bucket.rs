@@ -0,0 +1,203 @@
+use crate::block::blocks::fluid::{place_fluid, Fluid, FluidState};
+use crate::entity::player::Player;
+use crate::item::pumpkin_item::PumpkinItem;
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_item;
+use pumpkin_registry::DimensionType;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::GameMode;
+use pumpkin_world::block::registry::{
+    get_block_and_state_by_state_id, get_state_id_with_property, get_state_property,
+};
+use pumpkin_world::item::ItemStack;
+
+/// How far away a bucket reaches blocks
+const REACH: f64 = 5.0;
+
+#[pumpkin_item("bucket")]
+pub struct BucketItem;
+
+#[pumpkin_item("water_bucket")]
+pub struct WaterBucketItem;
+
+#[pumpkin_item("lava_bucket")]
+pub struct LavaBucketItem;
+
+/// The position of the player's eyes and the direction they look at
+fn look(player: &Player) -> (Vector3<f64>, Vector3<f64>) {
+    let entity = &player.living_entity.entity;
+    let pos = entity.pos.load();
+    let eyes = Vector3::new(pos.x, pos.y + f64::from(entity.standing_eye_height), pos.z);
+    let yaw = f64::from(entity.yaw.load()).to_radians();
+    let pitch = f64::from(entity.pitch.load()).to_radians();
+    let direction = Vector3::new(
+        -yaw.sin() * pitch.cos(),
+        -pitch.sin(),
+        yaw.cos() * pitch.cos(),
+    );
+    (eyes, direction)
+}
+
+/// Replaces one bucket in the main hand with another one, keeping the rest of the stack. In
+/// creative mode, the held bucket stays and the new one is only added if the player has none.
+async fn exchange_bucket(player: &Player, server: &Server, new_bucket: Item) {
+    if player.gamemode.load() == GameMode::Creative {
+        let has_bucket = player
+            .inventory()
+            .lock()
+            .await
+            .slots()
+            .iter()
+            .any(|slot| slot.is_some_and(|stack| stack.item.id == new_bucket.id));
+        if !has_bucket {
+            player.give_items(server, new_bucket, 1).await;
+        }
+        return;
+    }
+
+    let rest = player
+        .modify_held_item(|held| match held {
+            Some(stack) if stack.item_count > 1 => {
+                stack.item_count -= 1;
+                true
+            }
+            _ => {
+                *held = Some(ItemStack::new(1, new_bucket));
+                false
+            }
+        })
+        .await;
+    if rest {
+        player.give_items(server, new_bucket, 1).await;
+    }
+}
+
+/// Picks up the fluid source the player looks at, including the water of waterlogged blocks
+async fn fill(player: &Player, server: &Server) {
+    let world = player.world().await;
+    let (eyes, direction) = look(player);
+    let hit = world
+        .raycast_block_matching(eyes, direction, REACH, |state_id| {
+            FluidState::from_state_id(state_id).is_some_and(|fluid| fluid.source)
+                || get_block_and_state_by_state_id(state_id)
+                    .is_some_and(|(_, state)| !state.air && !state.collision_shapes.is_empty())
+        })
+        .await;
+    let Some((position, _)) = hit else {
+        return;
+    };
+    let Ok(state_id) = world.get_block_state_id(&position).await else {
+        return;
+    };
+    let Some(fluid) = FluidState::from_state_id(state_id).filter(|fluid| fluid.source) else {
+        return;
+    };
+    if !player.may_build_at(server, &world, &position).await {
+        return;
+    }
+
+    let emptied = if get_state_property(state_id, "waterlogged").is_some() {
+        get_state_id_with_property(state_id, "waterlogged", "false")
+    } else {
+        Some(0)
+    };
+    let Some(emptied) = emptied else {
+        return;
+    };
+    world.set_block_state(&position, emptied).await;
+    world.update_neighbors(server, &position).await;
+
+    let (sound, bucket) = match fluid.fluid {
+        Fluid::Water => (Sound::ItemBucketFill, Item::WATER_BUCKET),
+        Fluid::Lava => (Sound::ItemBucketFillLava, Item::LAVA_BUCKET),
+    };
+    world
+        .play_block_sound(sound, SoundCategory::Blocks, position)
+        .await;
+    exchange_bucket(player, server, bucket).await;
+}
+
+/// Places a fluid source where the player looks, or waterlogs the block they look at
+async fn empty(player: &Player, server: &Server, fluid: Fluid) {
+    let world = player.world().await;
+    let (eyes, direction) = look(player);
+    let Some((hit, before)) = world
+        .raycast_block_matching(eyes, direction, REACH, |state_id| {
+            get_block_and_state_by_state_id(state_id)
+                .is_some_and(|(_, state)| !state.air && !state.collision_shapes.is_empty())
+        })
+        .await
+    else {
+        return;
+    };
+    let Ok(hit_state_id) = world.get_block_state_id(&hit).await else {
+        return;
+    };
+
+    let waterlogs =
+        fluid == Fluid::Water && get_state_property(hit_state_id, "waterlogged") == Some("false");
+    let position = if waterlogs
+        || get_block_and_state_by_state_id(hit_state_id).is_some_and(|(_, state)| state.replaceable)
+    {
+        hit
+    } else {
+        before
+    };
+    let Ok(state_id) = world.get_block_state_id(&position).await else {
+        return;
+    };
+    let replaceable = waterlogs
+        || get_block_and_state_by_state_id(state_id)
+            .is_some_and(|(_, state)| state.air || state.replaceable);
+    if !replaceable || !player.may_build_at(server, &world, &position).await {
+        return;
+    }
+
+    let sound = if fluid == Fluid::Water && matches!(world.dimension_type, DimensionType::TheNether)
+    {
+        // Water evaporates in the nether
+        Sound::BlockFireExtinguish
+    } else if waterlogs {
+        // TODO: Let the water of waterlogged blocks flow
+        if let Some(waterlogged) = get_state_id_with_property(state_id, "waterlogged", "true") {
+            world.set_block_state(&position, waterlogged).await;
+            world.update_neighbors(server, &position).await;
+        }
+        Sound::ItemBucketEmpty
+    } else {
+        // TODO: Drop the loot of replaced blocks like grass
+        place_fluid(server, &world, &position, FluidState::source(fluid)).await;
+        match fluid {
+            Fluid::Water => Sound::ItemBucketEmpty,
+            Fluid::Lava => Sound::ItemBucketEmptyLava,
+        }
+    };
+    world
+        .play_block_sound(sound, SoundCategory::Blocks, position)
+        .await;
+    exchange_bucket(player, server, Item::BUCKET).await;
+}
+
+#[async_trait]
+impl PumpkinItem for BucketItem {
+    async fn normal_use(&self, _item: &Item, player: &Player, server: &Server) {
+        fill(player, server).await;
+    }
+}
+
+#[async_trait]
+impl PumpkinItem for WaterBucketItem {
+    async fn normal_use(&self, _item: &Item, player: &Player, server: &Server) {
+        empty(player, server, Fluid::Water).await;
+    }
+}
+
+#[async_trait]
+impl PumpkinItem for LavaBucketItem {
+    async fn normal_use(&self, _item: &Item, player: &Player, server: &Server) {
+        empty(player, server, Fluid::Lava).await;
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::entity::player::Player;
-use crate::entity::projectile::ThrownItemEntity;
+use crate::entity::projectile::thrown_item::ThrownItemEntity;
 use crate::item::pumpkin_item::PumpkinItem;
 use crate::server::Server;
 use async_trait::async_trait;
@@ -27,12 +27,18 @@ impl PumpkinItem for EggItem {
                 &position,
             )
             .await;
-        // TODO: Implement eggs the right way, so there is a chance of spawning chickens
         let entity = server.add_entity(position, EntityType::EGG, &world);
         let snowball = ThrownItemEntity::new(entity, &player.living_entity.entity);
         let yaw = player.living_entity.entity.yaw.load();
         let pitch = player.living_entity.entity.pitch.load();
-        snowball.set_velocity_from(&player.living_entity.entity, pitch, yaw, 0.0, POWER, 1.0);
+        snowball.projectile.set_velocity_from(
+            &player.living_entity.entity,
+            pitch,
+            yaw,
+            0.0,
+            POWER,
+            1.0,
+        );
         world.spawn_entity(Arc::new(snowball)).await;
     }
 }
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+use crate::entity::projectile::thrown_item::ThrownItemEntity;
+use crate::item::pumpkin_item::PumpkinItem;
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::Sound;
+use pumpkin_macros::pumpkin_item;
+
+#[pumpkin_item("ender_pearl")]
+pub struct EnderPearlItem;
+
+const POWER: f32 = 1.5;
+
+#[async_trait]
+impl PumpkinItem for EnderPearlItem {
+    async fn normal_use(&self, _block: &Item, player: &Player, server: &Server) {
+        let position = player.position();
+        let world = player.world().await;
+        world
+            .play_sound(
+                Sound::EntityEnderPearlThrow,
+                pumpkin_data::sound::SoundCategory::Neutral,
+                &position,
+            )
+            .await;
+        let entity = server.add_entity(position, EntityType::ENDER_PEARL, &world);
+        let ender_pearl = ThrownItemEntity::new(entity, &player.living_entity.entity);
+        let yaw = player.living_entity.entity.yaw.load();
+        let pitch = player.living_entity.entity.pitch.load();
+        ender_pearl.projectile.set_velocity_from(
+            &player.living_entity.entity,
+            pitch,
+            yaw,
+            0.0,
+            POWER,
+            1.0,
+        );
+        world.spawn_entity(Arc::new(ender_pearl)).await;
+    }
+}
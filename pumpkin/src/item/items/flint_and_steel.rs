@@ -0,0 +1,47 @@
+use crate::block::blocks::fire::place_fire;
+use crate::entity::player::Player;
+use crate::item::pumpkin_item::PumpkinItem;
+use crate::server::Server;
+use crate::world::portal::PortalShape;
+use async_trait::async_trait;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_item;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::{registry::Block, BlockDirection};
+
+#[pumpkin_item("flint_and_steel")]
+pub struct FlintAndSteelItem;
+
+#[async_trait]
+impl PumpkinItem for FlintAndSteelItem {
+    async fn use_on_block(
+        &self,
+        _item: &Item,
+        player: &Player,
+        location: BlockPos,
+        face: &BlockDirection,
+        _block: &Block,
+        server: &Server,
+    ) {
+        let world = player.world().await;
+        let position = BlockPos(location.0 + face.to_offset());
+        if let Some(portal) = PortalShape::find(&world, position).await {
+            portal.light(&world).await;
+        } else {
+            let is_air = world
+                .get_block_state(&position)
+                .await
+                .is_ok_and(|state| state.air);
+            if !is_air
+                || !player.may_build_at(server, &world, &position).await
+                || !place_fire(server, &world, &position, 0).await
+            {
+                return;
+            }
+        }
+        world
+            .play_block_sound(Sound::ItemFlintandsteelUse, SoundCategory::Blocks, position)
+            .await;
+    }
+}
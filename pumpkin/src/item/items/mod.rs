@@ -1,2 +1,7 @@
+pub mod bone_meal;
+pub mod bow;
+pub mod bucket;
 pub mod egg;
+pub mod ender_pearl;
+pub mod flint_and_steel;
 pub mod snowball;
@@ -1,4 +1,12 @@
-use items::{egg::EggItem, snowball::SnowBallItem};
+use items::{
+    bone_meal::BoneMealItem,
+    bow::BowItem,
+    bucket::{BucketItem, LavaBucketItem, WaterBucketItem},
+    egg::EggItem,
+    ender_pearl::EnderPearlItem,
+    flint_and_steel::FlintAndSteelItem,
+    snowball::SnowBallItem,
+};
 use registry::ItemRegistry;
 
 use std::sync::Arc;
@@ -13,6 +21,13 @@ pub fn default_registry() -> Arc<ItemRegistry> {
 
     manager.register(SnowBallItem);
     manager.register(EggItem);
+    manager.register(FlintAndSteelItem);
+    manager.register(BucketItem);
+    manager.register(WaterBucketItem);
+    manager.register(LavaBucketItem);
+    manager.register(BoneMealItem);
+    manager.register(EnderPearlItem);
+    manager.register(BowItem);
 
     Arc::new(manager)
 }
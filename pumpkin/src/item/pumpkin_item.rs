@@ -3,7 +3,7 @@ use crate::server::Server;
 use async_trait::async_trait;
 use pumpkin_data::item::Item;
 use pumpkin_util::math::position::BlockPos;
-use pumpkin_world::block::registry::Block;
+use pumpkin_world::block::{registry::Block, BlockDirection};
 
 pub trait ItemMetadata {
     const ID: u16;
@@ -12,11 +12,15 @@ pub trait ItemMetadata {
 #[async_trait]
 pub trait PumpkinItem: Send + Sync {
     async fn normal_use(&self, _block: &Item, _player: &Player, _server: &Server) {}
+    /// Called when the player stops using the item, e.g. lets go of a drawn bow, after using it
+    /// for the given number of ticks
+    async fn release_use(&self, _item: &Item, _player: &Player, _ticks: u32, _server: &Server) {}
     async fn use_on_block(
         &self,
         _item: &Item,
         _player: &Player,
         _location: BlockPos,
+        _face: &BlockDirection,
         _block: &Block,
         _server: &Server,
     ) {
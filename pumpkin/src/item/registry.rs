@@ -2,7 +2,7 @@ use crate::entity::player::Player;
 use crate::server::Server;
 use pumpkin_data::item::Item;
 use pumpkin_util::math::position::BlockPos;
-use pumpkin_world::block::registry::Block;
+use pumpkin_world::block::{registry::Block, BlockDirection};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -25,18 +25,25 @@ impl ItemRegistry {
         }
     }
 
+    pub async fn on_release(&self, item: &Item, player: &Player, ticks: u32, server: &Server) {
+        if let Some(pumpkin_item) = self.get_pumpkin_item(item.id) {
+            pumpkin_item.release_use(item, player, ticks, server).await;
+        }
+    }
+
     pub async fn use_on_block(
         &self,
         item: &Item,
         player: &Player,
         location: BlockPos,
+        face: &BlockDirection,
         block: &Block,
         server: &Server,
     ) {
         let pumpkin_item = self.get_pumpkin_item(item.id);
         if let Some(pumpkin_item) = pumpkin_item {
             return pumpkin_item
-                .use_on_block(item, player, location, block, server)
+                .use_on_block(item, player, location, face, block, server)
                 .await;
         }
     }
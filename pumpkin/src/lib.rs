@@ -123,6 +123,10 @@ pub struct PumpkinServer {
 impl PumpkinServer {
     pub async fn new() -> Self {
         let server = Arc::new(Server::new());
+        if let Some(world) = server.worlds.read().await.first() {
+            world.load_spawn_chunks().await;
+        }
+        plugin::api::placeholder::register_builtin_placeholders(&server);
 
         // Setup the TCP server socket.
         let listener = tokio::net::TcpListener::bind(BASIC_CONFIG.server_address)
@@ -280,6 +284,7 @@ impl PumpkinServer {
                     world
                         .spawn_player(&BASIC_CONFIG, player.clone(), &server)
                         .await;
+                    server.handle_first_join(&player).await;
 
                     // poll Player
                     while !player
@@ -293,6 +298,12 @@ impl PumpkinServer {
                         };
                     }
                     log::debug!("Cleaning up player for id {}", id);
+                    server
+                        .teleport_requests
+                        .remove_player(&server, &player)
+                        .await;
+                    // Gives back the items in a crafting container, before the player is saved
+                    player.leave_open_container(&server).await;
                     player.remove().await;
                     server.remove_player().await;
                     tasks_clone.lock().await.remove(&id);
@@ -336,6 +347,10 @@ impl PumpkinServer {
             }
         }
 
+        log::info!("Unloading plugins");
+
+        PLUGIN_MANAGER.lock().await.unload_all().await;
+
         self.server.save().await;
 
         log::info!("Completed save!");
@@ -30,6 +30,9 @@ pub struct Texture {
 
 const MOJANG_AUTHENTICATION_URL: &str = "https://sessionserver.mojang.com/session/minecraft/hasJoined?username={username}&serverId={server_hash}";
 const MOJANG_PREVENT_PROXY_AUTHENTICATION_URL: &str = "https://sessionserver.mojang.com/session/minecraft/hasJoined?username={username}&serverId={server_hash}";
+const MOJANG_UUID_URL: &str = "https://api.mojang.com/users/profiles/minecraft/{username}";
+const MOJANG_PROFILE_URL: &str =
+    "https://sessionserver.mojang.com/session/minecraft/profile/{uuid}?unsigned=false";
 
 /// Sends a GET request to Mojang's authentication servers to verify a client's Minecraft account.
 ///
@@ -93,6 +96,47 @@ pub async fn authenticate(
     Ok(profile)
 }
 
+/// Looks up the profile of a Minecraft account by its name or UUID, including its signed skin
+/// textures. Returns [`AuthError::UnknownProfile`] if there is no such account.
+pub async fn fetch_profile(
+    name_or_uuid: &str,
+    auth_client: &reqwest::Client,
+) -> Result<GameProfile, AuthError> {
+    #[derive(Deserialize)]
+    struct ProfileId {
+        id: Uuid,
+    }
+
+    let uuid = if let Ok(uuid) = Uuid::parse_str(name_or_uuid) {
+        uuid
+    } else {
+        let response = auth_client
+            .get(MOJANG_UUID_URL.replace("{username}", name_or_uuid))
+            .send()
+            .await
+            .map_err(|_| AuthError::FailedResponse)?;
+        match response.status() {
+            StatusCode::OK => {}
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Err(AuthError::UnknownProfile)?,
+            other => Err(AuthError::UnknownStatusCode(other))?,
+        }
+        let profile: ProfileId = response.json().await.map_err(|_| AuthError::FailedParse)?;
+        profile.id
+    };
+
+    let response = auth_client
+        .get(MOJANG_PROFILE_URL.replace("{uuid}", &uuid.simple().to_string()))
+        .send()
+        .await
+        .map_err(|_| AuthError::FailedResponse)?;
+    match response.status() {
+        StatusCode::OK => {}
+        StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Err(AuthError::UnknownProfile)?,
+        other => Err(AuthError::UnknownStatusCode(other))?,
+    }
+    response.json().await.map_err(|_| AuthError::FailedParse)
+}
+
 pub fn validate_textures(property: &Property, config: &TextureConfig) -> Result<(), TextureError> {
     let from64 = general_purpose::STANDARD
         .decode(&property.value)
@@ -145,6 +189,8 @@ pub enum AuthError {
     FailedParse,
     #[error("Unknown Status Code {0}")]
     UnknownStatusCode(StatusCode),
+    #[error("There is no account with this name or UUID")]
+    UnknownProfile,
 }
 
 #[derive(Error, Debug)]
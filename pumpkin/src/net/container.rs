@@ -1,25 +1,32 @@
+use crate::block::blocks::container;
+use crate::entity::item::{self, ItemEntity};
 use crate::entity::player::Player;
 use crate::server::Server;
+use pumpkin_data::entity::EntityType;
 use pumpkin_data::item::Item;
 use pumpkin_data::screen::WindowType;
 use pumpkin_inventory::container_click::{
     Click, ClickType, KeyClick, MouseClick, MouseDragState, MouseDragType,
 };
 use pumpkin_inventory::drag_handler::DragHandler;
-use pumpkin_inventory::window_property::{WindowProperty, WindowPropertyTrait};
-use pumpkin_inventory::Container;
+use pumpkin_inventory::window_property::{self, WindowProperty, WindowPropertyTrait};
 use pumpkin_inventory::{container_click, InventoryError, OptionallyCombinedContainer};
+use pumpkin_inventory::{Container, OpenContainer, TOO_EXPENSIVE_COST};
 use pumpkin_protocol::client::play::{
     CCloseContainer, COpenScreen, CSetContainerContent, CSetContainerProperty, CSetContainerSlot,
 };
 use pumpkin_protocol::codec::slot::Slot;
 use pumpkin_protocol::codec::var_int::VarInt;
-use pumpkin_protocol::server::play::SClickContainer;
+use pumpkin_protocol::server::play::{SClickContainer, SRenameItem};
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::GameMode;
 use pumpkin_world::item::ItemStack;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+/// The longest name an item can get in an anvil
+const MAX_ITEM_NAME_LENGTH: usize = 50;
+
 impl Player {
     pub async fn open_container(&self, server: &Server, window_type: WindowType) {
         let mut inventory = self.inventory().lock().await;
@@ -65,18 +72,18 @@ impl Player {
             .map(Slot::from)
             .collect();
 
-        let carried_item = self
-            .carried_item
-            .load()
+        let carried_item = self.carried_item.take();
+        let carried_slot = carried_item
             .as_ref()
             .map_or_else(Slot::empty, std::convert::Into::into);
+        self.carried_item.store(carried_item);
 
         inventory.state_id += 1;
         let packet = CSetContainerContent::new(
             id.into(),
             (inventory.state_id).into(),
             &slots,
-            &carried_item,
+            &carried_slot,
         );
         self.client.send_packet(&packet).await;
     }
@@ -94,7 +101,7 @@ impl Player {
     }
 
     pub async fn set_container_property<T: WindowPropertyTrait>(
-        &mut self,
+        &self,
         window_property: WindowProperty<T>,
     ) {
         let (id, value) = window_property.into_tuple();
@@ -108,10 +115,15 @@ impl Player {
     }
 
     pub async fn handle_click_container(
-        &self,
+        self: &Arc<Self>,
         server: &Arc<Server>,
         packet: SClickContainer,
     ) -> Result<(), InventoryError> {
+        let open_menu = self.open_menu.lock().await.clone();
+        if let Some(menu) = open_menu {
+            return self.handle_menu_click(menu, packet).await;
+        }
+
         let opened_container = self.get_open_container(server).await;
         let mut opened_container = match opened_container.as_ref() {
             Some(container) => Some(container.lock().await),
@@ -137,13 +149,14 @@ impl Player {
         }
 
         let click = Click::new(packet.mode, packet.button, packet.slot)?;
-        let (crafted_item, crafted_item_slot) = {
+        let (crafted_item, crafted_item_slot, level_cost) = {
             let mut inventory = self.inventory().lock().await;
             let combined =
                 OptionallyCombinedContainer::new(&mut inventory, opened_container.as_deref_mut());
             (
                 combined.crafted_item_slot(),
                 combined.crafting_output_slot(),
+                combined.level_cost(),
             )
         };
         let crafted_is_picked = crafted_item.is_some()
@@ -153,6 +166,12 @@ impl Player {
                 }
                 container_click::Slot::OutsideInventory => false,
             };
+        // Like in an anvil, the crafted item can't be taken without the levels it costs
+        if crafted_is_picked && !self.can_pay_levels(level_cost) {
+            self.set_container_content(opened_container.as_deref_mut())
+                .await;
+            return Ok(());
+        }
         let mut update_whole_container = false;
 
         let click_slot = click.slot;
@@ -169,17 +188,32 @@ impl Player {
             let mut inventory = self.inventory().lock().await;
             let mut combined =
                 OptionallyCombinedContainer::new(&mut inventory, opened_container.as_deref_mut());
-            if combined.crafted_item_slot().is_none() && crafted_item.is_some() {
+            let crafted_item_taken =
+                combined.crafted_item_slot().is_none() && crafted_item.is_some();
+            if crafted_item_taken {
                 combined.recipe_used();
             }
 
             // TODO: `combined.craft` uses rayon! It should be called from `rayon::spawn` and its
             // result passed to the tokio runtime via a channel!
-            if combined.craft() {
-                drop(inventory);
+            let crafted = combined.craft();
+            let new_level_cost = combined.level_cost();
+            let is_anvil = *combined.window_type() == WindowType::Anvil;
+            drop(inventory);
+            if crafted_item_taken && level_cost > 0 && self.gamemode.load() != GameMode::Creative {
+                self.add_experience_levels(-level_cost).await;
+            }
+            if crafted {
                 self.set_container_content(opened_container.as_deref_mut())
                     .await;
             }
+            if is_anvil {
+                self.set_container_property(WindowProperty::new(
+                    window_property::Anvil::RepairCost,
+                    new_level_cost as i16,
+                ))
+                .await;
+            }
         }
 
         if let Some(mut opened_container) = opened_container {
@@ -201,6 +235,61 @@ impl Player {
         Ok(())
     }
 
+    /// Whether the player has the experience levels something costs. In creative mode everything
+    /// is free, but what is too expensive can't be made at all in other modes.
+    fn can_pay_levels(&self, cost: i32) -> bool {
+        cost <= 0
+            || self.gamemode.load() == GameMode::Creative
+            || (cost < TOO_EXPENSIVE_COST && self.experience_level.load(Ordering::Relaxed) >= cost)
+    }
+
+    /// Opens a container which does not belong to a block, like the crafting table of
+    /// `/workbench`. Crafting containers give their items back when they are closed.
+    pub async fn open_virtual_container(
+        self: &Arc<Self>,
+        server: &Server,
+        container: OpenContainer,
+        window_type: WindowType,
+    ) {
+        self.menu_closed().await;
+        self.leave_open_container(server).await;
+
+        let id = server.new_container_id();
+        server
+            .open_containers
+            .write()
+            .await
+            .insert(id.into(), container);
+        self.open_container.store(Some(id.into()));
+        self.open_container(server, window_type).await;
+    }
+
+    /// Recomputes what the open container crafts after the player entered a name in an anvil
+    pub async fn handle_rename_item(&self, server: &Server, packet: SRenameItem) {
+        // Like in vanilla, longer names are ignored
+        if packet.item_name.chars().count() > MAX_ITEM_NAME_LENGTH {
+            return;
+        }
+        let Some(container) = self.get_open_container(server).await else {
+            return;
+        };
+        let mut container = container.lock().await;
+        if *container.window_type() != WindowType::Anvil {
+            return;
+        }
+        container.set_item_name(&packet.item_name);
+        if container.craft() {
+            self.set_container_content(Some(&mut *container)).await;
+        }
+        let level_cost = container.level_cost();
+        drop(container);
+        self.set_container_property(WindowProperty::new(
+            window_property::Anvil::RepairCost,
+            level_cost as i16,
+        ))
+        .await;
+    }
+
     pub async fn handle_decrease_item(
         &self,
         _server: &Server,
@@ -291,7 +380,7 @@ impl Player {
         let mut container = OptionallyCombinedContainer::new(&mut inventory, opened_container);
         match slot {
             container_click::Slot::Normal(slot) => {
-                let mut carried_item = self.carried_item.load();
+                let mut carried_item = self.carried_item.take();
                 let res = container.handle_item_change(
                     &mut carried_item,
                     slot,
@@ -323,8 +412,7 @@ impl Player {
                     let find_condition = |(slot_number, slot): (usize, &mut Option<ItemStack>)| {
                         // TODO: Check for max item count here
                         match slot {
-                            Some(item) => (item.item.id == item_in_pressed_slot.item.id
-                                && item.item_count != 64)
+                            Some(item) => (*item == item_in_pressed_slot && item.item_count != 64)
                                 .then_some(slot_number),
                             None => Some(slot_number),
                         }
@@ -336,7 +424,7 @@ impl Player {
                         slots.skip(36).rev().find_map(find_condition)
                     };
                     if let Some(slot) = slots {
-                        let mut item_slot = container.all_slots()[slot].map(|i| i);
+                        let mut item_slot = container.all_slots()[slot].clone();
                         container.handle_item_change(
                             &mut item_slot,
                             slot,
@@ -405,13 +493,12 @@ impl Player {
         let Some(item) = slots.get_mut(slot) else {
             return Ok(());
         };
-        let Some(mut carried_item) = **item else {
+        let Some(mut carried_item) = item.take() else {
             return Ok(());
         };
-        **item = None;
 
         for slot in slots.iter_mut().filter_map(|slot| slot.as_mut()) {
-            if slot.item.id == carried_item.item.id {
+            if *slot == carried_item {
                 // TODO: Check for max stack size
                 if slot.item_count + carried_item.item_count <= 64 {
                     slot.item_count = 0;
@@ -460,7 +547,7 @@ impl Player {
                 let mut inventory = self.inventory().lock().await;
                 let mut container =
                     OptionallyCombinedContainer::new(&mut inventory, opened_container);
-                let mut carried_item = self.carried_item.load();
+                let mut carried_item = self.carried_item.take();
                 let res = drag_handler
                     .apply_drag(&mut carried_item, &mut container, &container_id, player_id)
                     .await;
@@ -555,17 +642,21 @@ impl Player {
         }
     }
 
-    async fn pickup_items(&self, item: Item, mut amount: u32) {
+    /// Returns the amount which did not fit into the inventory
+    async fn pickup_items(&self, item: Item, mut amount: u32) -> u32 {
         let max_stack = item.components.max_stack_size;
         let mut inventory = self.inventory().lock().await;
         let slots = inventory.slots_with_hotbar_first();
 
         let matching_slots = slots.filter_map(|slot| {
             if let Some(item_slot) = slot.as_mut() {
-                (item_slot.item.id == item.id && item_slot.item_count < max_stack).then(|| {
-                    let item_count = item_slot.item_count;
-                    (item_slot, item_count)
-                })
+                (item_slot.item.id == item.id
+                    && item_slot.components.is_empty()
+                    && item_slot.item_count < max_stack)
+                    .then(|| {
+                        let item_count = item_slot.item_count;
+                        (item_slot, item_count)
+                    })
             } else {
                 None
             }
@@ -573,21 +664,15 @@ impl Player {
 
         for (slot, item_count) in matching_slots {
             if amount == 0 {
-                return;
+                return 0;
             }
             let amount_to_add = max_stack - item_count;
             if let Some(amount_left) = amount.checked_sub(u32::from(amount_to_add)) {
                 amount = amount_left;
-                *slot = ItemStack {
-                    item,
-                    item_count: item.components.max_stack_size,
-                };
+                *slot = ItemStack::new(item.components.max_stack_size, item);
             } else {
-                *slot = ItemStack {
-                    item,
-                    item_count: max_stack - (amount_to_add - amount as u8),
-                };
-                return;
+                *slot = ItemStack::new(max_stack - (amount_to_add - amount as u8), item);
+                return 0;
             }
         }
 
@@ -596,32 +681,102 @@ impl Player {
             .filter(|slot| slot.is_none());
         for slot in empty_slots {
             if amount == 0 {
-                return;
+                return 0;
             }
             if let Some(remaining_amount) = amount.checked_sub(u32::from(max_stack)) {
                 amount = remaining_amount;
-                *slot = Some(ItemStack {
-                    item,
-                    item_count: max_stack,
-                });
+                *slot = Some(ItemStack::new(max_stack, item));
             } else {
-                *slot = Some(ItemStack {
-                    item,
-                    item_count: amount as u8,
-                });
-                return;
+                *slot = Some(ItemStack::new(amount as u8, item));
+                return 0;
             }
         }
-        log::warn!(
-            "{amount} items were discarded because dropping them to the ground is not implemented"
-        );
+        amount
+    }
+
+    /// Changes a slot of the inventory, e.g. the components of its item, and sends the slot to the
+    /// client.
+    ///
+    /// # Arguments
+    /// - `slot`: The slot in the numbering of the inventory window, e.g. `36` to `44` is the hotbar.
+    pub async fn modify_inventory_slot<R>(
+        &self,
+        slot: usize,
+        modify: impl FnOnce(&mut Option<ItemStack>) -> R,
+    ) -> Result<R, InventoryError> {
+        let mut inventory = self.inventory().lock().await;
+        let stack = inventory.get_slot(slot)?;
+        let result = modify(stack);
+        if stack.as_ref().is_some_and(|stack| stack.item_count == 0) {
+            *stack = None;
+        }
+        let slot_data = Slot::from(stack.as_ref());
+        inventory.state_id += 1;
+        let packet = CSetContainerSlot::new(0, inventory.state_id as i32, slot as i16, &slot_data);
+        drop(inventory);
+        self.client.send_packet(&packet).await;
+        Ok(result)
+    }
+
+    /// Changes the item in the main hand and sends it to the client.
+    pub async fn modify_held_item<R>(&self, modify: impl FnOnce(&mut Option<ItemStack>) -> R) -> R {
+        let slot = self.inventory().lock().await.get_selected() as usize;
+        self.modify_inventory_slot(slot, modify)
+            .await
+            .expect("the selected slot is always in the hotbar")
     }
 
     /// Add items to inventory if there's space, else drop them to the ground.
     ///
     /// This method automatically syncs changes with the client.
-    pub async fn give_items(&self, item: Item, amount: u32) {
-        self.pickup_items(item, amount).await;
+    pub async fn give_items(&self, server: &Server, item: Item, amount: u32) {
+        let overflow = self.pickup_items(item, amount).await;
         self.set_container_content(None).await;
+        if overflow > 0 {
+            self.drop_items(server, item, overflow).await;
+        }
+    }
+
+    /// Adds a stack to the inventory, keeping its components, onto stacks it can be merged with
+    /// or into empty slots. What does not fit is dropped.
+    ///
+    /// This method automatically syncs changes with the client.
+    pub async fn give_stack(&self, server: &Server, stack: ItemStack) {
+        let mut inventory = self.inventory().lock().await;
+        let mut slots: Vec<_> = inventory.slots_with_hotbar_first().collect();
+        let rest = container::add_to_slots(&mut slots, |_| true, stack);
+        drop(inventory);
+        self.set_container_content(None).await;
+        if let Some(rest) = rest {
+            self.drop_stack(server, &rest).await;
+        }
+    }
+
+    /// Drops items at the feet of the player, in stacks of at most the item's max stack size.
+    async fn drop_items(&self, server: &Server, item: Item, mut amount: u32) {
+        let max_stack = u32::from(item.components.max_stack_size);
+        while amount > 0 {
+            let count = amount.min(max_stack);
+            amount -= count;
+            self.drop_stack(server, &ItemStack::new(count as u8, item))
+                .await;
+        }
+    }
+
+    /// Throws items in the direction the player looks, like pressing Q. For a moment, nobody
+    /// can pick them up.
+    pub async fn drop_stack(&self, server: &Server, stack: &ItemStack) {
+        let world = self.world().await;
+        let player = &self.living_entity.entity;
+        let mut pos = player.pos.load();
+        pos.y += f64::from(player.standing_eye_height) - 0.3;
+        let entity = server.add_entity(pos, EntityType::ITEM, &world);
+        entity
+            .velocity
+            .store(item::throw_velocity(player.yaw.load(), player.pitch.load()));
+        let item_entity = Arc::new(ItemEntity::new(entity, stack));
+        item_entity.set_pickup_delay(item::THROWN_PICKUP_DELAY);
+        world.spawn_entity(item_entity.clone()).await;
+        item_entity.send_meta_packet().await;
     }
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::VecDeque,
+    collections::{HashSet, VecDeque},
     net::SocketAddr,
     num::NonZeroU8,
     sync::{
@@ -45,7 +45,7 @@ use tokio::sync::Mutex;
 
 use thiserror::Error;
 use uuid::Uuid;
-mod authentication;
+pub mod authentication;
 mod container;
 pub mod lan_broadcast;
 mod packet;
@@ -125,6 +125,8 @@ pub struct Client {
     pub config: Mutex<Option<PlayerConfig>>,
     /// The client's brand or modpack information, Optional.
     pub brand: Mutex<Option<String>>,
+    /// The plugin messaging channels the client has registered to listen on.
+    pub plugin_channels: Mutex<HashSet<String>>,
     /// The minecraft protocol version used by the client.
     pub protocol_version: AtomicI32,
     /// The Address used to connect to the Server, Send in the Handshake
@@ -160,6 +162,7 @@ impl Client {
             gameprofile: Mutex::new(None),
             config: Mutex::new(None),
             brand: Mutex::new(None),
+            plugin_channels: Mutex::new(HashSet::new()),
             server_address: Mutex::new(String::new()),
             address: Mutex::new(address),
             connection_state: AtomicCell::new(ConnectionState::HandShake),
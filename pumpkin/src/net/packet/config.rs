@@ -3,6 +3,7 @@ use std::num::NonZeroU8;
 use crate::{
     entity::player::{ChatMode, Hand},
     net::{Client, PlayerConfig},
+    plugin::messaging,
     server::Server,
 };
 use core::str;
@@ -64,6 +65,16 @@ impl Client {
                 Ok(brand) => *self.brand.lock().await = Some(brand.to_string()),
                 Err(e) => self.kick(&TextComponent::text(e.to_string())).await,
             }
+        } else if plugin_message.channel.to_string() == messaging::REGISTER_CHANNEL {
+            messaging::register_channels(
+                &mut *self.plugin_channels.lock().await,
+                &plugin_message.data,
+            );
+        } else if plugin_message.channel.to_string() == messaging::UNREGISTER_CHANNEL {
+            let mut channels = self.plugin_channels.lock().await;
+            for channel in messaging::decode_channels(&plugin_message.data) {
+                channels.remove(&channel);
+            }
         }
     }
 
@@ -91,7 +91,7 @@ impl Client {
         // Don't allow new logons when server is full.
         // If max players is set to zero, then there is no max player count enforced.
         // TODO: If client is an operator or otherwise suitable elevated permissions, allow client to bypass this requirement.
-        let max_players = BASIC_CONFIG.max_players;
+        let max_players = server.max_players();
         if max_players > 0 && server.get_player_count().await >= max_players as usize {
             self.kick(&TextComponent::translate(
                 "multiplayer.disconnect.server_full",
@@ -10,8 +10,10 @@ use crate::{
     command::CommandSender,
     entity::player::{ChatMode, Hand, Player},
     error::PumpkinError,
+    plugin::{messaging, player::player_plugin_message::PlayerPluginMessageEvent},
     server::Server,
-    world::chunker,
+    world::{chunker, respawn::find_respawn},
+    PLUGIN_MANAGER,
 };
 use pumpkin_config::ADVANCED_CONFIG;
 use pumpkin_data::entity::{entity_from_egg, EntityType};
@@ -20,14 +22,16 @@ use pumpkin_data::sound::Sound;
 use pumpkin_data::sound::SoundCategory;
 use pumpkin_data::world::CHAT;
 use pumpkin_inventory::player::PlayerInventory;
-use pumpkin_inventory::InventoryError;
+use pumpkin_inventory::{InventoryError, OpenContainer};
 use pumpkin_macros::block_entity;
 use pumpkin_protocol::client::play::{
-    CBlockEntityData, COpenSignEditor, CSetContainerSlot, CSetHeldItem, EquipmentSlot,
+    COpenSignEditor, CSetContainerSlot, CSetHeldItem, EquipmentSlot,
 };
 use pumpkin_protocol::codec::slot::Slot;
 use pumpkin_protocol::codec::var_int::VarInt;
-use pumpkin_protocol::server::play::{SCookieResponse as SPCookieResponse, SUpdateSign};
+use pumpkin_protocol::server::play::{
+    SCookieResponse as SPCookieResponse, SCustomPayload, SUpdateSign,
+};
 use pumpkin_protocol::{
     client::play::{
         Animation, CCommandSuggestions, CEntityAnimation, CHeadRot, CPingResponse,
@@ -49,7 +53,7 @@ use pumpkin_util::{
     text::TextComponent,
     GameMode,
 };
-use pumpkin_world::block::interactive::sign::Sign;
+use pumpkin_world::block::entity::SignBlockEntity;
 use pumpkin_world::block::registry::get_block_collision_shapes;
 use pumpkin_world::block::registry::Block;
 use pumpkin_world::block::{registry::get_block_by_item, BlockDirection};
@@ -130,6 +134,44 @@ impl Player {
         pos.clamp(-2.0E7, 2.0E7)
     }
 
+    /// Moves frozen players back to where they were frozen. Returns whether the movement was
+    /// rejected.
+    async fn reject_frozen_movement(&self, position: Vector3<f64>, yaw: f32, pitch: f32) -> bool {
+        let entity = &self.living_entity.entity;
+        if !entity.frozen.load(std::sync::atomic::Ordering::Relaxed)
+            || position == entity.pos.load()
+        {
+            return false;
+        }
+        // Movement sent before the client accepted the last teleport is outdated anyway
+        if self.awaiting_teleport.lock().await.is_none() {
+            self.request_teleport(entity.pos.load(), yaw, pitch).await;
+        }
+        true
+    }
+
+    /// Moves players who try to leave the world border back onto it. Returns whether the
+    /// movement was rejected. Players who are outside already, e.g. because the border shrank,
+    /// may move freely so they can get back in.
+    async fn reject_border_movement(&self, position: Vector3<f64>, yaw: f32, pitch: f32) -> bool {
+        if self.gamemode.load() == GameMode::Spectator {
+            return false;
+        }
+        let last_pos = self.living_entity.entity.pos.load();
+        let destination = {
+            let world = self.world().await;
+            let border = world.worldborder.lock().await;
+            if border.contains(position.x, position.z) || !border.contains(last_pos.x, last_pos.z) {
+                return false;
+            }
+            border.clamp(position)
+        };
+        if self.awaiting_teleport.lock().await.is_none() {
+            self.request_teleport(destination, yaw, pitch).await;
+        }
+        true
+    }
+
     pub fn handle_player_loaded(self: &Arc<Self>) {
         self.set_client_loaded(true);
     }
@@ -154,6 +196,15 @@ impl Player {
             Self::clamp_horizontal(position.z),
         );
         let entity = &self.living_entity.entity;
+        if self
+            .reject_frozen_movement(position, entity.yaw.load(), entity.pitch.load())
+            .await
+            || self
+                .reject_border_movement(position, entity.yaw.load(), entity.pitch.load())
+                .await
+        {
+            return;
+        }
         let last_pos = entity.pos.load();
         self.living_entity.set_pos(position);
 
@@ -244,6 +295,16 @@ impl Player {
             Self::clamp_horizontal(position.z),
         );
         let entity = &self.living_entity.entity;
+        // Frozen players can still look around, as long as they stay in place
+        if self
+            .reject_frozen_movement(position, packet.yaw, packet.pitch)
+            .await
+            || self
+                .reject_border_movement(position, packet.yaw, packet.pitch)
+                .await
+        {
+            return;
+        }
         let last_pos = entity.pos.load();
         self.living_entity.set_pos(position);
 
@@ -397,16 +458,14 @@ impl Player {
         &self,
         inventory: &mut tokio::sync::MutexGuard<'_, PlayerInventory>,
         slot: i16,
-        slot_data: Slot,
+        stack: Option<ItemStack>,
     ) {
         inventory.state_id += 1;
+        let slot_data = Slot::from(stack.as_ref());
         let dest_packet = CSetContainerSlot::new(0, inventory.state_id as i32, slot, &slot_data);
         self.client.send_packet(&dest_packet).await;
 
-        if inventory
-            .set_slot(slot as usize, slot_data.to_item(), false)
-            .is_err()
-        {
+        if inventory.set_slot(slot as usize, stack, false).is_err() {
             log::error!("Pick item set slot error!");
         }
     }
@@ -432,9 +491,9 @@ impl Player {
         let source_slot = inventory.get_slot_with_item(block.item_id, 64);
         let mut dest_slot = inventory.get_empty_hotbar_slot() as usize;
 
-        let dest_slot_data = match inventory.get_slot(dest_slot + 36) {
-            Ok(Some(stack)) => Slot::from(&*stack),
-            _ => Slot::from(None),
+        let dest_stack = match inventory.get_slot(dest_slot + 36) {
+            Ok(stack) => stack.clone(),
+            Err(_) => None,
         };
 
         // Early return if no source slot and not in creative mode
@@ -451,28 +510,27 @@ impl Player {
                 // Case where item is in inventory
 
                 // Update destination slot
-                let source_slot_data = match inventory.get_slot(slot_index) {
-                    Ok(Some(stack)) => Slot::from(&*stack),
+                let source_stack = match inventory.get_slot(slot_index) {
+                    Ok(Some(stack)) => stack.clone(),
                     _ => return,
                 };
-                self.update_single_slot(&mut inventory, dest_slot as i16 + 36, source_slot_data)
+                self.update_single_slot(&mut inventory, dest_slot as i16 + 36, Some(source_stack))
                     .await;
 
                 // Update source slot
-                self.update_single_slot(&mut inventory, slot_index as i16, dest_slot_data)
+                self.update_single_slot(&mut inventory, slot_index as i16, dest_stack)
                     .await;
             }
             None if self.gamemode.load() == GameMode::Creative => {
                 // Case where item is not present, if in creative mode create the item
                 let item_stack = ItemStack::new(1, Item::from_id(block.item_id).unwrap());
-                let slot_data = Slot::from(&item_stack);
-                self.update_single_slot(&mut inventory, dest_slot as i16 + 36, slot_data)
+                self.update_single_slot(&mut inventory, dest_slot as i16 + 36, Some(item_stack))
                     .await;
 
                 // Check if there is any empty slot in the player inventory
                 if let Some(slot_index) = inventory.get_empty_slot() {
                     inventory.state_id += 1;
-                    self.update_single_slot(&mut inventory, slot_index as i16, dest_slot_data)
+                    self.update_single_slot(&mut inventory, slot_index as i16, dest_stack)
                         .await;
                 }
             }
@@ -483,7 +541,7 @@ impl Player {
         inventory.set_selected(dest_slot as u32);
         let empty = &ItemStack::new(0, Item::AIR);
         let stack = inventory.held_item().unwrap_or(empty);
-        let equipment = &[(EquipmentSlot::MainHand, *stack)];
+        let equipment = &[(EquipmentSlot::MainHand, stack.clone())];
         self.living_entity.send_equipment_changes(equipment).await;
         self.client
             .send_packet(&CSetHeldItem::new(dest_slot as i8))
@@ -525,8 +583,10 @@ impl Player {
                         entity.set_sprinting(false).await;
                     }
                 }
-                pumpkin_protocol::server::play::Action::LeaveBed
-                | pumpkin_protocol::server::play::Action::StartHorseJump
+                pumpkin_protocol::server::play::Action::LeaveBed => {
+                    self.living_entity.wake_up().await;
+                }
+                pumpkin_protocol::server::play::Action::StartHorseJump
                 | pumpkin_protocol::server::play::Action::StopHorseJump
                 | pumpkin_protocol::server::play::Action::OpenVehicleInventory => {
                     log::debug!("todo");
@@ -593,27 +653,35 @@ impl Player {
             return;
         }
 
+        if let Some(notice) = self.mute_notice().await {
+            self.send_system_message(&notice).await;
+            return;
+        }
+
         let gameprofile = &self.gameprofile;
         log::info!("<chat>{}: {}", gameprofile.name, message);
 
         let entity = &self.living_entity.entity;
         let world = &entity.world.read().await;
-        world
-            .broadcast_packet_all(&CPlayerChatMessage::new(
-                gameprofile.id,
-                1.into(),
-                chat_message.signature.as_deref(),
-                &message,
-                chat_message.timestamp,
-                chat_message.salt,
-                &[],
-                Some(TextComponent::text(message.clone())),
-                FilterType::PassThrough,
-                (CHAT + 1).into(),
-                TextComponent::text(gameprofile.name.clone()),
-                None,
-            ))
-            .await;
+        let packet = CPlayerChatMessage::new(
+            gameprofile.id,
+            1.into(),
+            chat_message.signature.as_deref(),
+            &message,
+            chat_message.timestamp,
+            chat_message.salt,
+            &[],
+            Some(TextComponent::text(message.clone())),
+            FilterType::PassThrough,
+            (CHAT + 1).into(),
+            self.chat_name().await,
+            None,
+        );
+        for player in world.players.read().await.values() {
+            if !player.is_ignoring(self).await {
+                player.client.send_packet(&packet).await;
+            }
+        }
 
         /* server.broadcast_packet(
             self,
@@ -698,17 +766,21 @@ impl Player {
         }
     }
 
-    pub async fn handle_client_status(self: &Arc<Self>, client_status: SClientCommand) {
+    pub async fn handle_client_status(
+        self: &Arc<Self>,
+        server: &Server,
+        client_status: SClientCommand,
+    ) {
         match client_status.action_id.0 {
             0 => {
                 // Perform Respawn
                 if self.living_entity.health.load() > 0.0 {
                     return;
                 }
-                self.world()
-                    .await
-                    .respawn_player(&self.clone(), false)
-                    .await;
+                let Some(respawn) = find_respawn(server, self, true).await else {
+                    return;
+                };
+                respawn.world.respawn_player(self, false, &respawn).await;
 
                 // Restore abilities based on gamemode after respawn
                 let mut abilities = self.abilities.lock().await;
@@ -828,6 +900,11 @@ impl Player {
                     let location = player_action.location;
                     let entity = &self.living_entity.entity;
                     let world = &entity.world.read().await;
+                    if !self.may_build_at(server, world, &location).await {
+                        // The client puts the block back once the sequence is acknowledged
+                        self.update_sequence(player_action.sequence.0);
+                        return;
+                    }
                     let block = world.get_block(&location).await;
                     let state = world.get_block_state(&location).await.unwrap();
 
@@ -911,6 +988,10 @@ impl Player {
                     self.mining
                         .store(false, std::sync::atomic::Ordering::Relaxed);
                     world.set_block_breaking(entity, location, -1).await;
+                    if !self.may_build_at(server, world, &location).await {
+                        self.update_sequence(player_action.sequence.0);
+                        return;
+                    }
                     let block = world.get_block(&location).await;
                     let state = world.get_block_state(&location).await;
                     if let Ok(block) = block {
@@ -934,7 +1015,21 @@ impl Player {
                 Status::DropItemStack => {
                     self.drop_item(server, true).await;
                 }
-                Status::ShootArrowOrFinishEating | Status::SwapItem => {
+                Status::ShootArrowOrFinishEating => {
+                    let Some(start) = self.item_use_start.take() else {
+                        return;
+                    };
+                    let ticks =
+                        self.tick_counter.load(std::sync::atomic::Ordering::Relaxed) - start;
+                    let held = self.inventory().lock().await.held_item().cloned();
+                    if let Some(held) = held {
+                        server
+                            .item_registry
+                            .on_release(&held.item, &self, ticks.max(0) as u32, server)
+                            .await;
+                    }
+                }
+                Status::SwapItem => {
                     log::debug!("todo");
                 }
             },
@@ -953,6 +1048,9 @@ impl Player {
         {
             self.wait_for_keep_alive
                 .store(false, std::sync::atomic::Ordering::Relaxed);
+            let ping = self.last_keep_alive_time.load().elapsed().as_millis();
+            self.ping
+                .store(ping as u32, std::sync::atomic::Ordering::Relaxed);
         } else {
             self.kick(TextComponent::text("Timeout")).await;
         }
@@ -1014,7 +1112,7 @@ impl Player {
         let world = &entity.world.read().await;
         let slot_id = inventory.get_selected();
         let mut state_id = inventory.state_id;
-        let item_slot = *inventory.held_item_mut();
+        let item_slot = inventory.held_item().cloned();
         drop(inventory);
 
         let Ok(block) = world.get_block(&location).await else {
@@ -1065,6 +1163,10 @@ impl Player {
                 }
             }
         }
+        server
+            .item_registry
+            .use_on_block(&stack.item, self, location, &face, block, server)
+            .await;
         // check if item is a block, Because Not every item can be placed :D
         if let Some(block) = get_block_by_item(stack.item.id) {
             should_try_decrement = self
@@ -1103,25 +1205,20 @@ impl Player {
 
     pub async fn handle_sign_update(&self, sign_data: SUpdateSign) {
         let world = &self.living_entity.entity.world.read().await;
-        let updated_sign = Sign::new(
-            sign_data.location,
-            sign_data.is_front_text,
-            [
-                sign_data.line_1,
-                sign_data.line_2,
-                sign_data.line_3,
-                sign_data.line_4,
-            ],
-        );
-
-        let mut sign_buf = Vec::new();
-        pumpkin_nbt::serializer::to_bytes_unnamed(&updated_sign, &mut sign_buf).unwrap();
         world
-            .broadcast_packet_all(&CBlockEntityData::new(
-                sign_data.location,
-                VarInt(block_entity!("sign") as i32),
-                sign_buf.into_boxed_slice(),
-            ))
+            .update_block_entity(&sign_data.location, |block_entity| {
+                if let Some(sign) = block_entity.as_any_mut().downcast_mut::<SignBlockEntity>() {
+                    sign.set_lines(
+                        sign_data.is_front_text,
+                        [
+                            sign_data.line_1,
+                            sign_data.line_2,
+                            sign_data.line_3,
+                            sign_data.line_4,
+                        ],
+                    );
+                }
+            })
             .await;
     }
 
@@ -1144,7 +1241,7 @@ impl Player {
         inv.set_selected(slot as u32);
         let empty = &ItemStack::new(0, Item::AIR);
         let stack = inv.held_item().unwrap_or(empty);
-        let equipment = &[(EquipmentSlot::MainHand, *stack)];
+        let equipment = &[(EquipmentSlot::MainHand, stack.clone())];
         self.living_entity.send_equipment_changes(equipment).await;
     }
 
@@ -1170,7 +1267,11 @@ impl Player {
     // TODO:
     // This function will in the future be used to keep track of if the client is in a valid state.
     // But this is not possible yet
-    pub async fn handle_close_container(&self, server: &Server, _packet: SCloseContainer) {
+    pub async fn handle_close_container(
+        self: &Arc<Self>,
+        server: &Server,
+        _packet: SCloseContainer,
+    ) {
         // TODO: This should check if player sent this packet before
         // let Some(_window_type) = WindowType::from_i32(packet.window_id.0) else {
         //     log::info!("Closed ID: {}", packet.window_id.0);
@@ -1178,10 +1279,17 @@ impl Player {
         //     return;
         // };
         // window_id 0 represents both 9x1 Generic AND inventory here
+        self.menu_closed().await;
+        self.leave_open_container(server).await;
+    }
+
+    /// Removes the player from the container they have open, if any
+    pub async fn leave_open_container(&self, server: &Server) {
         let mut inventory = self.inventory().lock().await;
 
         inventory.state_id = 0;
         let open_container = self.open_container.load();
+        let mut returned_items = Vec::new();
         if let Some(id) = open_container {
             let mut open_containers = server.open_containers.write().await;
             if let Some(container) = open_containers.get_mut(&id) {
@@ -1197,8 +1305,23 @@ impl Player {
                 // Remove the player from the container
                 container.remove_player(self.entity_id());
             }
+            // Containers without a block only exist while they are open, the items put into them
+            // to craft something go back to the player. Everything else in them, like the items
+            // in a trash can, is deleted with the container
+            if open_containers
+                .get(&id)
+                .is_some_and(OpenContainer::is_virtual)
+            {
+                if let Some(container) = open_containers.remove(&id) {
+                    returned_items = container.take_crafting_input_items().await;
+                }
+            }
             self.open_container.store(None);
         }
+        drop(inventory);
+        for stack in returned_items {
+            self.give_stack(server, stack).await;
+        }
     }
 
     pub async fn handle_command_suggestion(
@@ -1239,6 +1362,59 @@ impl Player {
         );
     }
 
+    pub async fn handle_custom_payload(self: &Arc<Self>, server: &Server, packet: SCustomPayload) {
+        let channel = packet.channel.to_string();
+        match channel.as_str() {
+            messaging::REGISTER_CHANNEL => {
+                messaging::register_channels(
+                    &mut *self.client.plugin_channels.lock().await,
+                    &packet.data,
+                );
+
+                // Answer with the channels our plugins listen on
+                let channels: Vec<String> = server
+                    .plugin_channels
+                    .read()
+                    .await
+                    .keys()
+                    .cloned()
+                    .collect();
+                if !channels.is_empty() {
+                    self.send_plugin_message(
+                        messaging::REGISTER_CHANNEL,
+                        &messaging::encode_channels(&channels),
+                    )
+                    .await;
+                }
+            }
+            messaging::UNREGISTER_CHANNEL => {
+                let mut channels = self.client.plugin_channels.lock().await;
+                for channel in messaging::decode_channels(&packet.data) {
+                    channels.remove(&channel);
+                }
+            }
+            "minecraft:brand" => match std::str::from_utf8(&packet.data) {
+                Ok(brand) => *self.client.brand.lock().await = Some(brand.to_string()),
+                Err(e) => log::debug!("Received invalid client brand: {e}"),
+            },
+            _ => {
+                if !server.plugin_channels.read().await.contains_key(&channel) {
+                    log::debug!("Dropping plugin message on unregistered channel {channel}");
+                    return;
+                }
+                PLUGIN_MANAGER
+                    .lock()
+                    .await
+                    .fire(PlayerPluginMessageEvent::new(
+                        self.clone(),
+                        channel,
+                        packet.data,
+                    ))
+                    .await;
+            }
+        }
+    }
+
     async fn spawn_entity_from_egg(
         &self,
         entity_type: EntityType,
@@ -1364,6 +1540,10 @@ impl Player {
             (block_pos, &face.opposite())
         };
 
+        if !self.may_build_at(server, world, &final_block_pos).await {
+            return Ok(false);
+        }
+
         let new_state = server
             .block_registry
             .on_place(
@@ -1394,6 +1574,7 @@ impl Player {
         }
         if !intersects {
             let _replaced_id = world.set_block_state(&final_block_pos, new_state).await;
+            world.update_neighbors(server, &final_block_pos).await;
             server
                 .block_registry
                 .on_placed(&block, self, final_block_pos, server)
@@ -1,12 +1,20 @@
 use pumpkin_protocol::{client::status::CPingResponse, server::status::SStatusPingRequest};
 
-use crate::{net::Client, server::Server};
+use crate::{
+    net::Client,
+    plugin::api::placeholder,
+    server::{motd, Server},
+};
 
 impl Client {
     pub async fn handle_status_request(&self, server: &Server) {
         log::debug!("Handling status request");
+        let motd = motd::parse(server.get_status().lock().await.motd());
+        let description = placeholder::resolve_component(motd, None).await;
         let status = server.get_status();
-        self.send_packet(&status.lock().await.get_status()).await;
+        let mut status = status.lock().await;
+        status.set_description(description);
+        self.send_packet(&status.get_status()).await;
     }
 
     pub async fn handle_ping_request(&self, ping_request: SStatusPingRequest) {
@@ -148,7 +148,7 @@ async fn handle_packet(
                                 plugins: CString::new(plugins)?,
                                 map: CString::new("world")?, // TODO: Get actual world name
                                 num_players: server.get_player_count().await,
-                                max_players: BASIC_CONFIG.max_players as usize,
+                                max_players: server.max_players() as usize,
                                 host_port: bound_addr.port(),
                                 host_ip: CString::new(bound_addr.ip().to_string())?,
                                 players,
@@ -163,7 +163,7 @@ async fn handle_packet(
                                 motd: CString::new(BASIC_CONFIG.motd.as_str())?,
                                 map: CString::new("world")?,
                                 num_players: server.get_player_count().await,
-                                max_players: BASIC_CONFIG.max_players as usize,
+                                max_players: server.max_players() as usize,
                                 host_port: bound_addr.port(),
                                 host_ip: CString::new(bound_addr.ip().to_string())?,
                             };
@@ -0,0 +1,236 @@
+//! Boss bars driven by plugins.
+//!
+//! These are separate from the boss bars managed by the `/bossbar` command, which are identified by
+//! a resource location. A plugin boss bar is only identified by its handle, so the two can not clash.
+
+use std::sync::{atomic::Ordering, Arc, Weak};
+
+use pumpkin_protocol::client::play::{BosseventAction, CBossEvent};
+use pumpkin_util::text::TextComponent;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{
+    entity::player::Player,
+    world::bossbar::{BossbarColor, BossbarDivisions, BossbarFlags},
+};
+
+/// The current look of a boss bar and which parts of it changed since the last update was sent.
+struct BossBarState {
+    title: TextComponent,
+    progress: f32,
+    color: BossbarColor,
+    division: BossbarDivisions,
+    flags: u8,
+    title_changed: bool,
+    progress_changed: bool,
+    style_changed: bool,
+    flags_changed: bool,
+}
+
+impl BossBarState {
+    fn add_action(&self) -> BosseventAction {
+        BosseventAction::Add {
+            title: self.title.clone(),
+            health: self.progress,
+            color: (self.color.clone() as u8).into(),
+            division: (self.division.clone() as u8).into(),
+            flags: self.flags,
+        }
+    }
+
+    /// Returns the updates for everything that changed since the last call.
+    fn take_changes(&mut self) -> Vec<BosseventAction> {
+        let mut actions = Vec::new();
+        if std::mem::take(&mut self.title_changed) {
+            actions.push(BosseventAction::UpdateTile(self.title.clone()));
+        }
+        if std::mem::take(&mut self.progress_changed) {
+            actions.push(BosseventAction::UpdateHealth(self.progress));
+        }
+        if std::mem::take(&mut self.style_changed) {
+            actions.push(BosseventAction::UpdateStyle {
+                color: (self.color.clone() as u8).into(),
+                dividers: (self.division.clone() as u8).into(),
+            });
+        }
+        if std::mem::take(&mut self.flags_changed) {
+            actions.push(BosseventAction::UpdateFlags(self.flags));
+        }
+        actions
+    }
+}
+
+/// A boss bar shown to the players added with [`BossBar::add_player`].
+///
+/// Changes are collected and sent once per tick, so animating the progress does not send a
+/// packet for every step. Players who disconnect are removed automatically.
+pub struct BossBar {
+    uuid: Uuid,
+    state: Mutex<BossBarState>,
+    viewers: Mutex<Vec<Weak<Player>>>,
+}
+
+impl BossBar {
+    /// Creates a boss bar with no progress. Plugins should use `Context::create_bossbar`, which
+    /// makes sure updates are sent.
+    #[must_use]
+    pub(crate) fn new(
+        title: TextComponent,
+        color: BossbarColor,
+        division: BossbarDivisions,
+    ) -> Self {
+        Self {
+            uuid: Uuid::new_v4(),
+            state: Mutex::new(BossBarState {
+                title,
+                progress: 0.0,
+                color,
+                division,
+                flags: BossbarFlags::NoFlags as u8,
+                title_changed: false,
+                progress_changed: false,
+                style_changed: false,
+                flags_changed: false,
+            }),
+            viewers: Mutex::new(Vec::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    pub async fn get_title(&self) -> TextComponent {
+        self.state.lock().await.title.clone()
+    }
+
+    pub async fn get_progress(&self) -> f32 {
+        self.state.lock().await.progress
+    }
+
+    /// Shows the boss bar to a player.
+    pub async fn add_player(&self, player: &Arc<Player>) {
+        {
+            let mut viewers = self.viewers.lock().await;
+            if viewers
+                .iter()
+                .any(|viewer| viewer.as_ptr() == Arc::as_ptr(player))
+            {
+                return;
+            }
+            viewers.push(Arc::downgrade(player));
+        }
+        let action = self.state.lock().await.add_action();
+        player
+            .client
+            .send_packet(&CBossEvent::new(&self.uuid, action))
+            .await;
+    }
+
+    /// Hides the boss bar from a player.
+    pub async fn remove_player(&self, player: &Arc<Player>) {
+        {
+            let mut viewers = self.viewers.lock().await;
+            let count = viewers.len();
+            viewers.retain(|viewer| viewer.as_ptr() != Arc::as_ptr(player));
+            if viewers.len() == count {
+                return;
+            }
+        }
+        player.remove_bossbar(self.uuid).await;
+    }
+
+    /// Hides the boss bar from every player it is shown to.
+    pub async fn remove_all_players(&self) {
+        let viewers = std::mem::take(&mut *self.viewers.lock().await);
+        for viewer in viewers.iter().filter_map(Weak::upgrade) {
+            viewer.remove_bossbar(self.uuid).await;
+        }
+    }
+
+    pub async fn set_title(&self, title: TextComponent) {
+        let mut state = self.state.lock().await;
+        if state.title != title {
+            state.title = title;
+            state.title_changed = true;
+        }
+    }
+
+    /// Sets how full the bar is, from 0.0 to 1.0.
+    #[allow(clippy::float_cmp)]
+    pub async fn set_progress(&self, progress: f32) {
+        let progress = progress.clamp(0.0, 1.0);
+        let mut state = self.state.lock().await;
+        if state.progress != progress {
+            state.progress = progress;
+            state.progress_changed = true;
+        }
+    }
+
+    pub async fn set_style(&self, color: BossbarColor, division: BossbarDivisions) {
+        let mut state = self.state.lock().await;
+        if state.color != color || state.division != division {
+            state.color = color;
+            state.division = division;
+            state.style_changed = true;
+        }
+    }
+
+    /// Replaces the flags of the boss bar.
+    pub async fn set_flags(&self, flags: &[BossbarFlags]) {
+        let flags = flags.iter().fold(0, |all, flag| all | flag.clone() as u8);
+        let mut state = self.state.lock().await;
+        if state.flags != flags {
+            state.flags = flags;
+            state.flags_changed = true;
+        }
+    }
+
+    /// Sends everything that changed since the last tick to the players watching the boss bar and
+    /// forgets players who disconnected.
+    pub(crate) async fn flush(&self) {
+        let mut viewers = self.viewers.lock().await;
+        viewers.retain(|viewer| {
+            viewer
+                .upgrade()
+                .is_some_and(|player| !player.client.closed.load(Ordering::Relaxed))
+        });
+
+        let actions = self.state.lock().await.take_changes();
+        for action in actions {
+            let packet = CBossEvent::new(&self.uuid, action);
+            for viewer in viewers.iter().filter_map(Weak::upgrade) {
+                viewer.client.send_packet(&packet).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn changes_are_coalesced() {
+        let bossbar = BossBar::new(
+            TextComponent::text("Test"),
+            BossbarColor::Red,
+            BossbarDivisions::NoDivision,
+        );
+        for step in 0..10 {
+            bossbar.set_progress(step as f32 / 10.0).await;
+        }
+        bossbar.set_title(TextComponent::text("Test")).await;
+        bossbar.set_progress(2.0).await;
+
+        let mut state = bossbar.state.lock().await;
+        let changes = state.take_changes();
+        assert_eq!(changes.len(), 1);
+        assert!(
+            matches!(changes[0], BosseventAction::UpdateHealth(progress) if (progress - 1.0).abs() < f32::EPSILON)
+        );
+        assert!(state.take_changes().is_empty());
+    }
+}
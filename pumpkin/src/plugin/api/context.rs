@@ -1,13 +1,66 @@
-use super::{Event, EventPriority, PluginMetadata};
+use super::{
+    bossbar::BossBar,
+    messaging,
+    placeholder::{self, Placeholder},
+    recipe::Recipe,
+    scoreboard::{Sidebar, Team},
+    world::WorldHandle,
+    Event, EventPriority, PluginMetadata,
+};
 use crate::command::client_suggestions;
+use crate::world::bossbar::{BossbarColor, BossbarDivisions};
 use crate::{
     entity::player::Player,
-    plugin::{EventHandler, HandlerMap, TypedEventHandler},
+    plugin::{EventHandler, HandlerMap, ServiceMap, TypedEventHandler},
     server::Server,
 };
-use pumpkin_config::BASIC_CONFIG;
-use std::{fs, path::Path, sync::Arc};
-use tokio::sync::RwLock;
+use pumpkin_protocol::{
+    client::play::{CRecipeBookAdd, CRecipeBookRemove},
+    codec::var_int::VarInt,
+};
+use pumpkin_registry::{recipe_display, register_recipe, unregister_recipe};
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::generation::{register_world_gen, unregister_world_gen, GeneratorFactory};
+use std::{
+    any::{Any, TypeId},
+    fs,
+    future::Future,
+    path::Path,
+    sync::Arc,
+};
+use tokio::{
+    sync::{Mutex, RwLock},
+    task::AbortHandle,
+};
+
+/// Everything a plugin has registered through its `Context`.
+///
+/// This is tracked so that all of it can be torn down when the plugin is unloaded.
+#[derive(Default)]
+pub struct PluginRegistrations {
+    /// Primary names of the registered commands.
+    pub commands: Vec<String>,
+    /// Names of the events the plugin registered handlers for.
+    pub events: Vec<&'static str>,
+    /// Tasks spawned by the plugin.
+    pub tasks: Vec<AbortHandle>,
+    /// Services provided by the plugin.
+    pub services: Vec<TypeId>,
+    /// Plugin messaging channels registered by the plugin.
+    pub channels: Vec<String>,
+    /// Names of the world generators registered by the plugin.
+    pub generators: Vec<String>,
+    /// Sidebars created by the plugin.
+    pub sidebars: Vec<Arc<Sidebar>>,
+    /// Teams created by the plugin.
+    pub teams: Vec<Arc<Team>>,
+    /// Boss bars created by the plugin.
+    pub bossbars: Vec<Arc<BossBar>>,
+    /// Names of the placeholders registered by the plugin.
+    pub placeholders: Vec<String>,
+    /// Ids of the recipes registered by the plugin.
+    pub recipes: Vec<String>,
+}
 
 /// The `Context` struct represents the context of a plugin, containing metadata,
 /// a server reference, and event handlers.
@@ -16,10 +69,14 @@ use tokio::sync::RwLock;
 /// - `metadata`: Metadata of the plugin.
 /// - `server`: A reference to the server on which the plugin operates.
 /// - `handlers`: A map of event handlers, protected by a read-write lock for safe access across threads.
+/// - `services`: A map of services provided by all plugins.
+/// - `registrations`: Everything this plugin has registered.
 pub struct Context {
     metadata: PluginMetadata<'static>,
     pub server: Arc<Server>,
     handlers: Arc<RwLock<HandlerMap>>,
+    services: Arc<RwLock<ServiceMap>>,
+    registrations: Mutex<PluginRegistrations>,
 }
 impl Context {
     /// Creates a new instance of `Context`.
@@ -28,6 +85,7 @@ impl Context {
     /// - `metadata`: The metadata of the plugin.
     /// - `server`: A reference to the server.
     /// - `handlers`: A collection containing the event handlers.
+    /// - `services`: A collection containing the services provided by plugins.
     ///
     /// # Returns
     /// A new instance of `Context`.
@@ -36,14 +94,33 @@ impl Context {
         metadata: PluginMetadata<'static>,
         server: Arc<Server>,
         handlers: Arc<RwLock<HandlerMap>>,
+        services: Arc<RwLock<ServiceMap>>,
     ) -> Self {
         Self {
             metadata,
             server,
             handlers,
+            services,
+            registrations: Mutex::new(PluginRegistrations::default()),
         }
     }
 
+    /// Retrieves the primary names of the commands this plugin has registered.
+    ///
+    /// # Returns
+    /// A vector of command names.
+    pub async fn get_registered_commands(&self) -> Vec<String> {
+        self.registrations.lock().await.commands.clone()
+    }
+
+    /// Retrieves the names of the events this plugin has registered handlers for.
+    ///
+    /// # Returns
+    /// A vector of event names.
+    pub async fn get_registered_events(&self) -> Vec<&'static str> {
+        self.registrations.lock().await.events.clone()
+    }
+
     /// Retrieves the data folder path for the plugin, creating it if it does not exist.
     ///
     /// # Returns
@@ -68,7 +145,27 @@ impl Context {
         self.server.get_player_by_name(&player_name).await
     }
 
-    /// Asynchronously registers a command with the server.
+    /// Gets handles to all worlds of the server.
+    pub async fn get_worlds(&self) -> Vec<WorldHandle> {
+        self.server
+            .worlds
+            .read()
+            .await
+            .iter()
+            .map(|world| WorldHandle::new(world.clone(), self.server.clone()))
+            .collect()
+    }
+
+    /// Gets a handle to the world with the given name, e.g. `world` or `world_nether`.
+    pub async fn get_world(&self, name: &str) -> Option<WorldHandle> {
+        self.get_worlds()
+            .await
+            .into_iter()
+            .find(|world| world.name() == name)
+    }
+
+    /// Asynchronously registers a command with the server. The command becomes available at the
+    /// next tick, as the dispatcher may be locked by the command that loaded this plugin.
     ///
     /// # Arguments
     /// - `tree`: The command tree to register.
@@ -78,35 +175,24 @@ impl Context {
         tree: crate::command::tree::CommandTree,
         permission: &str,
     ) {
-        {
-            let mut dispatcher_lock = self.server.command_dispatcher.write().await;
-            dispatcher_lock.register(tree, permission, BASIC_CONFIG.op_permission_level);
-        };
-
-        for world in self.server.worlds.read().await.iter() {
-            for player in world.players.read().await.values() {
-                client_suggestions::send_c_commands_packet(player, &self.server.command_dispatcher)
-                    .await;
-            }
-        }
+        let name = tree.names[0].clone();
+        self.server
+            .queue_register_command(tree, permission.to_string())
+            .await;
+        self.registrations.lock().await.commands.push(name);
     }
 
-    /// Asynchronously unregisters a command from the server.
+    /// Asynchronously unregisters a command from the server at the next tick.
     ///
     /// # Arguments
     /// - `name`: The name of the command to unregister.
     pub async fn unregister_command(&self, name: &str) {
-        {
-            let mut dispatcher_lock = self.server.command_dispatcher.write().await;
-            dispatcher_lock.unregister(name);
-        };
-
-        for world in self.server.worlds.read().await.iter() {
-            for player in world.players.read().await.values() {
-                client_suggestions::send_c_commands_packet(player, &self.server.command_dispatcher)
-                    .await;
-            }
-        }
+        self.server.queue_unregister_command(name.to_string()).await;
+        self.registrations
+            .lock()
+            .await
+            .commands
+            .retain(|command| command != name);
     }
 
     /// Asynchronously registers an event handler for a specific event type.
@@ -119,6 +205,8 @@ impl Context {
     /// - `handler`: A reference to the event handler.
     /// - `priority`: The priority of the event handler.
     /// - `blocking`: A boolean indicating whether the handler is blocking.
+    /// - `ignore_cancelled`: A boolean indicating whether the handler skips events which were
+    ///   cancelled by an earlier handler.
     ///
     /// # Constraints
     /// The handler must implement the `EventHandler<E>` trait.
@@ -127,6 +215,7 @@ impl Context {
         handler: Arc<H>,
         priority: EventPriority,
         blocking: bool,
+        ignore_cancelled: bool,
     ) where
         H: EventHandler<E> + 'static,
     {
@@ -140,9 +229,487 @@ impl Context {
             handler,
             priority,
             blocking,
+            ignore_cancelled,
+            plugin: Some(self.metadata.name),
             _phantom: std::marker::PhantomData,
         };
         handlers_vec.push(Box::new(typed_handler));
+
+        let mut registrations = self.registrations.lock().await;
+        if !registrations.events.contains(&E::get_name_static()) {
+            registrations.events.push(E::get_name_static());
+        }
+    }
+
+    /// Spawns a task which is aborted when the plugin is unloaded.
+    ///
+    /// # Arguments
+    /// - `task`: The future to run.
+    ///
+    /// # Returns
+    /// A handle which can be used to abort the task early.
+    pub async fn spawn_task<F>(&self, task: F) -> AbortHandle
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let join_handle = tokio::spawn(task);
+        let mut registrations = self.registrations.lock().await;
+        registrations.tasks.retain(|task| !task.is_finished());
+        registrations.tasks.push(join_handle.abort_handle());
+        join_handle.abort_handle()
+    }
+
+    /// Provides a service to other plugins. A service registered for the same type by another
+    /// plugin is replaced.
+    ///
+    /// # Type Parameters
+    /// - `T`: The type under which the service is registered.
+    ///
+    /// # Arguments
+    /// - `service`: The service to provide.
+    pub async fn register_service<T: Any + Send + Sync>(&self, service: Arc<T>) {
+        self.services
+            .write()
+            .await
+            .insert(TypeId::of::<T>(), (self.metadata.name, service));
+        let mut registrations = self.registrations.lock().await;
+        if !registrations.services.contains(&TypeId::of::<T>()) {
+            registrations.services.push(TypeId::of::<T>());
+        }
+    }
+
+    /// Retrieves a service provided by any loaded plugin.
+    ///
+    /// # Type Parameters
+    /// - `T`: The type under which the service is registered.
+    ///
+    /// # Returns
+    /// The service, or `None` if no plugin provides it.
+    pub async fn get_service<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let services = self.services.read().await;
+        let (_, service) = services.get(&TypeId::of::<T>())?;
+        service.clone().downcast().ok()
+    }
+
+    /// Registers a plugin messaging channel, so messages players send on it fire a
+    /// `PlayerPluginMessageEvent` and the plugin can send messages on it.
+    ///
+    /// # Arguments
+    /// - `channel`: The channel to register, e.g. `bungeecord:main`.
+    ///
+    /// # Returns
+    /// An error if the channel is already registered by another plugin.
+    pub async fn register_plugin_channel(&self, channel: &str) -> Result<(), String> {
+        {
+            let mut channels = self.server.plugin_channels.write().await;
+            if let Some(owner) = channels.get(channel) {
+                if *owner != self.metadata.name {
+                    return Err(format!(
+                        "Channel {channel} is already registered by plugin {owner}"
+                    ));
+                }
+                return Ok(());
+            }
+            channels.insert(channel.to_string(), self.metadata.name);
+        };
+        self.registrations
+            .lock()
+            .await
+            .channels
+            .push(channel.to_string());
+
+        let data = messaging::encode_channels(&[channel]);
+        for player in self.server.get_all_players().await {
+            player
+                .send_plugin_message(messaging::REGISTER_CHANNEL, &data)
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Unregisters a plugin messaging channel previously registered by this plugin.
+    ///
+    /// # Arguments
+    /// - `channel`: The channel to unregister.
+    pub async fn unregister_plugin_channel(&self, channel: &str) {
+        self.registrations
+            .lock()
+            .await
+            .channels
+            .retain(|registered| registered != channel);
+        self.remove_plugin_channels(&[channel.to_string()]).await;
+    }
+
+    /// Sends a plugin message to a player. Players whose client does not listen on the channel are
+    /// not sent the message.
+    ///
+    /// # Arguments
+    /// - `player`: The player to send the message to.
+    /// - `channel`: The channel to send the message on, which has to be registered by this plugin.
+    /// - `data`: The payload of the message.
+    ///
+    /// # Returns
+    /// An error if the channel is not registered by this plugin.
+    pub async fn send_plugin_message(
+        &self,
+        player: &Player,
+        channel: &str,
+        data: &[u8],
+    ) -> Result<(), String> {
+        self.check_channel_owner(channel).await?;
+        if player.client.plugin_channels.lock().await.contains(channel) {
+            player.send_plugin_message(channel, data).await;
+        }
+        Ok(())
+    }
+
+    /// Sends a plugin message to all players whose client listens on the channel.
+    ///
+    /// # Arguments
+    /// - `channel`: The channel to send the message on, which has to be registered by this plugin.
+    /// - `data`: The payload of the message.
+    ///
+    /// # Returns
+    /// An error if the channel is not registered by this plugin.
+    pub async fn broadcast_plugin_message(&self, channel: &str, data: &[u8]) -> Result<(), String> {
+        self.check_channel_owner(channel).await?;
+        for player in self.server.get_all_players().await {
+            if player.client.plugin_channels.lock().await.contains(channel) {
+                player.send_plugin_message(channel, data).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the channel is registered by this plugin, so it may send messages on it.
+    async fn check_channel_owner(&self, channel: &str) -> Result<(), String> {
+        match self.server.plugin_channels.read().await.get(channel) {
+            Some(owner) if *owner == self.metadata.name => Ok(()),
+            Some(owner) => Err(format!("Channel {channel} is registered by plugin {owner}")),
+            None => Err(format!("Channel {channel} is not registered")),
+        }
+    }
+
+    /// Registers a world generator, which worlds can select by name in the world config.
+    ///
+    /// Plugins providing a generator should be loaded before worlds generate their first chunk,
+    /// otherwise those worlds fall back to the default generator.
+    ///
+    /// # Arguments
+    /// - `name`: The name worlds select the generator by.
+    /// - `factory`: Creates the generator for a world's seed.
+    ///
+    /// # Returns
+    /// An error if a generator with that name is already registered.
+    pub async fn register_world_generator(
+        &self,
+        name: &str,
+        factory: GeneratorFactory,
+    ) -> Result<(), String> {
+        register_world_gen(name, factory)?;
+        self.registrations
+            .lock()
+            .await
+            .generators
+            .push(name.to_string());
+        Ok(())
+    }
+
+    /// Creates a sidebar, which is shown to players with [`Sidebar::show`]. Use this to give each
+    /// player their own sidebar.
+    ///
+    /// # Arguments
+    /// - `id`: An identifier unique within the plugin. The objective is named `<plugin>:<id>`.
+    /// - `title`: The title shown above the lines.
+    pub async fn create_sidebar(&self, id: &str, title: TextComponent) -> Arc<Sidebar> {
+        let sidebar = Arc::new(Sidebar::new(format!("{}:{id}", self.metadata.name), title));
+        self.registrations
+            .lock()
+            .await
+            .sidebars
+            .push(sidebar.clone());
+        sidebar
+    }
+
+    /// Creates a sidebar which is shown to all players, including the ones joining later.
+    ///
+    /// # Arguments
+    /// - `id`: An identifier unique within the plugin. The objective is named `<plugin>:<id>`.
+    /// - `title`: The title shown above the lines.
+    pub async fn create_global_sidebar(&self, id: &str, title: TextComponent) -> Arc<Sidebar> {
+        let sidebar = self.create_sidebar(id, title).await;
+        self.server
+            .plugin_scoreboard
+            .add_global_sidebar(&self.server, sidebar.clone())
+            .await;
+        sidebar
+    }
+
+    /// Creates a team, which is sent to all players, including the ones joining later.
+    ///
+    /// # Arguments
+    /// - `id`: An identifier unique within the plugin. The team is named `<plugin>:<id>`.
+    pub async fn create_team(&self, id: &str) -> Arc<Team> {
+        let team = Arc::new(Team::new(format!("{}:{id}", self.metadata.name)));
+        self.server
+            .plugin_scoreboard
+            .add_team(&self.server, team.clone())
+            .await;
+        self.registrations.lock().await.teams.push(team.clone());
+        team
+    }
+
+    /// Removes a sidebar created by this plugin and hides it from all players.
+    pub async fn remove_sidebar(&self, sidebar: &Arc<Sidebar>) {
+        self.registrations
+            .lock()
+            .await
+            .sidebars
+            .retain(|registered| !Arc::ptr_eq(registered, sidebar));
+        self.server
+            .plugin_scoreboard
+            .remove_global_sidebar(sidebar)
+            .await;
+    }
+
+    /// Removes a team created by this plugin for all players.
+    pub async fn remove_team(&self, team: &Arc<Team>) {
+        self.registrations
+            .lock()
+            .await
+            .teams
+            .retain(|registered| !Arc::ptr_eq(registered, team));
+        self.server
+            .plugin_scoreboard
+            .remove_team(&self.server, team)
+            .await;
+    }
+
+    /// Registers a placeholder, which is replaced in the MOTD, the tab list and join and quit
+    /// messages.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the placeholder, e.g. `balance` for `%balance%`.
+    /// - `placeholder`: Resolves the value of the placeholder.
+    ///
+    /// # Returns
+    /// An error if a placeholder with that name is already registered.
+    pub async fn register_placeholder(
+        &self,
+        name: &str,
+        placeholder: Arc<dyn Placeholder>,
+    ) -> Result<(), String> {
+        placeholder::register_placeholder(name, placeholder)?;
+        self.registrations
+            .lock()
+            .await
+            .placeholders
+            .push(name.to_string());
+        Ok(())
+    }
+
+    /// Unregisters a placeholder previously registered by this plugin.
+    pub async fn unregister_placeholder(&self, name: &str) {
+        let mut registrations = self.registrations.lock().await;
+        if registrations
+            .placeholders
+            .iter()
+            .any(|registered| registered == name)
+        {
+            registrations
+                .placeholders
+                .retain(|registered| registered != name);
+            placeholder::unregister_placeholder(name);
+        }
+    }
+
+    /// Registers a recipe, which is crafted like the recipes of data packs and added to the recipe
+    /// book of all players. Use the helpers in [`super::recipe`] to build it.
+    ///
+    /// # Arguments
+    /// - `id`: A namespaced id like `my_plugin:ruby_block`.
+    /// - `recipe`: The recipe.
+    ///
+    /// # Returns
+    /// An error if the id is invalid or a recipe with that id already exists.
+    pub async fn register_recipe(&self, id: &str, recipe: Recipe) -> Result<(), String> {
+        register_recipe(id, recipe)?;
+        self.registrations.lock().await.recipes.push(id.to_string());
+        if let Some(display) = recipe_display(id) {
+            self.server
+                .broadcast_packet_all(&CRecipeBookAdd::new(&[display], false))
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Unregisters a recipe previously registered by this plugin.
+    pub async fn unregister_recipe(&self, id: &str) {
+        let mut registrations = self.registrations.lock().await;
+        if registrations
+            .recipes
+            .iter()
+            .any(|registered| registered == id)
+        {
+            registrations.recipes.retain(|registered| registered != id);
+            drop(registrations);
+            self.remove_recipes(&[id.to_string()]).await;
+        }
+    }
+
+    /// Removes the given recipes and removes them from the recipe book of all players.
+    async fn remove_recipes(&self, ids: &[String]) {
+        let display_ids: Vec<VarInt> = ids
+            .iter()
+            .filter_map(|id| unregister_recipe(id))
+            .map(VarInt)
+            .collect();
+        if !display_ids.is_empty() {
+            self.server
+                .broadcast_packet_all(&CRecipeBookRemove::new(&display_ids))
+                .await;
+        }
+    }
+
+    /// Creates a boss bar, which is shown to players with [`BossBar::add_player`].
+    ///
+    /// # Arguments
+    /// - `title`: The title shown above the bar.
+    /// - `color`: The color of the bar.
+    /// - `division`: The notches the bar is divided into.
+    pub async fn create_bossbar(
+        &self,
+        title: TextComponent,
+        color: BossbarColor,
+        division: BossbarDivisions,
+    ) -> Arc<BossBar> {
+        let bossbar = Arc::new(BossBar::new(title, color, division));
+        self.server
+            .plugin_bossbars
+            .write()
+            .await
+            .push(bossbar.clone());
+        self.registrations
+            .lock()
+            .await
+            .bossbars
+            .push(bossbar.clone());
+        bossbar
+    }
+
+    /// Removes a boss bar created by this plugin and hides it from all players.
+    pub async fn remove_bossbar(&self, bossbar: &Arc<BossBar>) {
+        self.registrations
+            .lock()
+            .await
+            .bossbars
+            .retain(|registered| !Arc::ptr_eq(registered, bossbar));
+        self.remove_bossbars(std::slice::from_ref(bossbar)).await;
+    }
+
+    /// Stops updating the given boss bars and hides them from all players.
+    async fn remove_bossbars(&self, bossbars: &[Arc<BossBar>]) {
+        self.server
+            .plugin_bossbars
+            .write()
+            .await
+            .retain(|registered| {
+                !bossbars
+                    .iter()
+                    .any(|bossbar| Arc::ptr_eq(registered, bossbar))
+            });
+        for bossbar in bossbars {
+            bossbar.remove_all_players().await;
+        }
+    }
+
+    /// Removes the given channels if they are owned by this plugin and tells all players.
+    async fn remove_plugin_channels(&self, channels: &[String]) {
+        let removed: Vec<&String> = {
+            let mut registered = self.server.plugin_channels.write().await;
+            channels
+                .iter()
+                .filter(|channel| {
+                    registered.get(*channel) == Some(&self.metadata.name)
+                        && registered.remove(*channel).is_some()
+                })
+                .collect()
+        };
+        if removed.is_empty() {
+            return;
+        }
+
+        let data = messaging::encode_channels(&removed);
+        for player in self.server.get_all_players().await {
+            player
+                .send_plugin_message(messaging::UNREGISTER_CHANNEL, &data)
+                .await;
+        }
+    }
+
+    /// Removes everything the plugin has registered: commands, event handlers, tasks, services,
+    /// plugin messaging channels, world generators, sidebars, teams, boss bars, placeholders and
+    /// recipes.
+    ///
+    /// The command tree is re-sent to all players afterwards.
+    pub(crate) async fn teardown(&self) {
+        let registrations = std::mem::take(&mut *self.registrations.lock().await);
+        let name = self.metadata.name;
+
+        for task in &registrations.tasks {
+            task.abort();
+        }
+
+        {
+            let mut handlers = self.handlers.write().await;
+            for event in &registrations.events {
+                if let Some(handlers_vec) = handlers.get_mut(event) {
+                    handlers_vec.retain(|handler| handler.get_plugin() != Some(name));
+                }
+            }
+        }
+
+        {
+            let mut services = self.services.write().await;
+            for service in &registrations.services {
+                if services
+                    .get(service)
+                    .is_some_and(|(owner, _)| *owner == name)
+                {
+                    services.remove(service);
+                }
+            }
+        }
+
+        self.remove_plugin_channels(&registrations.channels).await;
+
+        for generator in &registrations.generators {
+            unregister_world_gen(generator);
+        }
+
+        for sidebar in &registrations.sidebars {
+            self.server
+                .plugin_scoreboard
+                .remove_global_sidebar(sidebar)
+                .await;
+        }
+        for team in &registrations.teams {
+            self.server
+                .plugin_scoreboard
+                .remove_team(&self.server, team)
+                .await;
+        }
+        self.remove_bossbars(&registrations.bossbars).await;
+
+        for name in &registrations.placeholders {
+            placeholder::unregister_placeholder(name);
+        }
+
+        self.remove_recipes(&registrations.recipes).await;
+
+        for command in &registrations.commands {
+            self.server.queue_unregister_command(command.clone()).await;
+        }
     }
 
     pub async fn update_suggestions(&self, player: Arc<Player>) {
@@ -16,6 +16,25 @@ pub struct BlockBurnEvent {
     pub block: Block,
 }
 
+impl BlockBurnEvent {
+    /// Creates a new instance of `BlockBurnEvent`.
+    ///
+    /// # Arguments
+    /// - `igniting_block`: The block that is igniting the fire.
+    /// - `block`: The block that is burning.
+    ///
+    /// # Returns
+    /// A new instance of `BlockBurnEvent`.
+    #[must_use]
+    pub fn new(igniting_block: Block, block: Block) -> Self {
+        Self {
+            igniting_block,
+            block,
+            cancelled: false,
+        }
+    }
+}
+
 impl BlockEvent for BlockBurnEvent {
     fn get_block(&self) -> &Block {
         &self.block
@@ -37,6 +37,14 @@ pub trait Event: Send + Sync {
     /// # Returns
     /// An immutable reference to the event as a `dyn Any` trait object.
     fn as_any(&self) -> &dyn Any;
+
+    /// Provides the event as a cancellable event, if it can be cancelled.
+    ///
+    /// # Returns
+    /// The event as a `dyn Cancellable`, or `None` if the event is not cancellable.
+    fn as_cancellable(&self) -> Option<&dyn Cancellable> {
+        None
+    }
 }
 
 /// A trait for cancellable events.
@@ -55,24 +63,31 @@ pub trait Cancellable: Send + Sync {
     /// - `cancelled`: A boolean indicating the new cancellation state.
     fn set_cancelled(&mut self, cancelled: bool);
 }
-/// An enumeration representing the priority levels of events.
+/// An enumeration representing the priority levels of event handlers.
 ///
-/// Events with lower priority values are executed first, allowing higher priority events
-/// to override their changes.
-#[derive(Eq, PartialEq, Ord, PartialOrd, Clone)]
+/// Handlers are called from `Lowest` to `Highest`, so higher priority handlers have the final say
+/// over changes and cancellation. `Monitor` handlers are called last and only observe the final
+/// state of the event. Handlers with the same priority are called in plugin load order.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
 pub enum EventPriority {
-    /// Highest priority level.
-    Highest,
+    /// Lowest priority level, called first.
+    Lowest,
 
-    /// High priority level.
-    High,
+    /// Low priority level.
+    Low,
 
     /// Normal priority level.
     Normal,
 
-    /// Low priority level.
-    Low,
+    /// High priority level.
+    High,
 
-    /// Lowest priority level.
-    Lowest,
+    /// Highest priority level, called last among the handlers which can change the event.
+    Highest,
+
+    /// Called after all other handlers, with the final state of the event.
+    ///
+    /// Monitor handlers are always called with `EventHandler::handle`, even if registered as
+    /// blocking, so they can not change or cancel the event.
+    Monitor,
 }
@@ -1,5 +1,8 @@
 pub mod player_join;
 pub mod player_leave;
+pub mod player_plugin_message;
+pub mod player_respawn;
+pub mod player_teleport;
 
 use std::sync::Arc;
 
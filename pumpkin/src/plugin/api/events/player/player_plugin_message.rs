@@ -0,0 +1,47 @@
+use bytes::Bytes;
+use pumpkin_macros::Event;
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+
+use super::PlayerEvent;
+
+/// An event that occurs when a player sends a plugin message on a channel registered by a plugin.
+///
+/// Messages on channels which no plugin has registered are dropped and never fire this event.
+#[derive(Event, Clone)]
+pub struct PlayerPluginMessageEvent {
+    /// The player who sent the message.
+    pub player: Arc<Player>,
+
+    /// The channel the message was sent on, e.g. `bungeecord:main`.
+    pub channel: String,
+
+    /// The raw payload of the message.
+    pub data: Bytes,
+}
+
+impl PlayerPluginMessageEvent {
+    /// Creates a new instance of `PlayerPluginMessageEvent`.
+    ///
+    /// # Arguments
+    /// - `player`: A reference to the player who sent the message.
+    /// - `channel`: The channel the message was sent on.
+    /// - `data`: The raw payload of the message.
+    ///
+    /// # Returns
+    /// A new instance of `PlayerPluginMessageEvent`.
+    pub fn new(player: Arc<Player>, channel: String, data: Bytes) -> Self {
+        Self {
+            player,
+            channel,
+            data,
+        }
+    }
+}
+
+impl PlayerEvent for PlayerPluginMessageEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
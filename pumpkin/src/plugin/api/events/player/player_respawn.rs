@@ -0,0 +1,74 @@
+use pumpkin_macros::Event;
+use pumpkin_util::math::vector3::Vector3;
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+
+use super::PlayerEvent;
+
+/// Where the respawn location of a player came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RespawnReason {
+    /// The player slept in a bed.
+    Bed,
+    /// The player set a charged respawn anchor.
+    RespawnAnchor,
+    /// The player has no respawn point and spawns at the world spawn.
+    WorldSpawn,
+}
+
+/// An event that occurs when a player respawns after dying.
+///
+/// Changing the location changes where the player respawns.
+#[derive(Event, Clone)]
+pub struct PlayerRespawnEvent {
+    /// The player who is respawning.
+    pub player: Arc<Player>,
+
+    /// Where the respawn location came from.
+    pub reason: RespawnReason,
+
+    /// The position the player respawns at.
+    pub position: Vector3<f64>,
+
+    /// The yaw the player has after respawning.
+    pub yaw: f32,
+
+    /// The pitch the player has after respawning.
+    pub pitch: f32,
+}
+
+impl PlayerRespawnEvent {
+    /// Creates a new instance of `PlayerRespawnEvent`.
+    ///
+    /// # Arguments
+    /// - `player`: A reference to the player respawning.
+    /// - `reason`: Where the respawn location came from.
+    /// - `position`: The respawn position.
+    /// - `yaw`: The respawn yaw.
+    /// - `pitch`: The respawn pitch.
+    ///
+    /// # Returns
+    /// A new instance of `PlayerRespawnEvent`.
+    pub fn new(
+        player: Arc<Player>,
+        reason: RespawnReason,
+        position: Vector3<f64>,
+        yaw: f32,
+        pitch: f32,
+    ) -> Self {
+        Self {
+            player,
+            reason,
+            position,
+            yaw,
+            pitch,
+        }
+    }
+}
+
+impl PlayerEvent for PlayerRespawnEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
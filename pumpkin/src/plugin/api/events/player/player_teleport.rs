@@ -0,0 +1,91 @@
+use pumpkin_macros::{cancellable, Event};
+use pumpkin_util::math::vector3::Vector3;
+use std::sync::Arc;
+
+use crate::{entity::player::Player, world::World};
+
+use super::PlayerEvent;
+
+/// What caused a player to be teleported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeleportCause {
+    /// A command like `/teleport`.
+    Command,
+    /// A thrown ender pearl landed.
+    EnderPearl,
+    /// A plugin teleported the player.
+    Plugin,
+    /// The player went through a portal.
+    Portal,
+}
+
+/// An event that occurs before a player is teleported.
+///
+/// Changing the destination changes where the player ends up. If the event is cancelled, the
+/// player stays where they are.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct PlayerTeleportEvent {
+    /// The player who is being teleported.
+    pub player: Arc<Player>,
+
+    /// What caused the teleport.
+    pub cause: TeleportCause,
+
+    /// The position the player is teleported from.
+    pub from: Vector3<f64>,
+
+    /// The position the player is teleported to.
+    pub to: Vector3<f64>,
+
+    /// The yaw the player has after the teleport.
+    pub yaw: f32,
+
+    /// The pitch the player has after the teleport.
+    pub pitch: f32,
+
+    /// The world the player is teleported to.
+    pub world: Arc<World>,
+}
+
+impl PlayerTeleportEvent {
+    /// Creates a new instance of `PlayerTeleportEvent`.
+    ///
+    /// # Arguments
+    /// - `player`: A reference to the player being teleported.
+    /// - `cause`: What caused the teleport.
+    /// - `from`: The current position of the player.
+    /// - `to`: The destination position.
+    /// - `yaw`: The destination yaw.
+    /// - `pitch`: The destination pitch.
+    /// - `world`: The destination world.
+    ///
+    /// # Returns
+    /// A new instance of `PlayerTeleportEvent`.
+    pub fn new(
+        player: Arc<Player>,
+        cause: TeleportCause,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+        yaw: f32,
+        pitch: f32,
+        world: Arc<World>,
+    ) -> Self {
+        Self {
+            player,
+            cause,
+            from,
+            to,
+            yaw,
+            pitch,
+            world,
+            cancelled: false,
+        }
+    }
+}
+
+impl PlayerEvent for PlayerTeleportEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
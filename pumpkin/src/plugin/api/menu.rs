@@ -0,0 +1,280 @@
+//! Virtual chest-style menus which plugins can show to players.
+//!
+//! A [`Menu`] is not backed by a block. Players can not take items out of it or put items into it,
+//! every click is cancelled and passed to the click handler of the clicked slot instead.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+};
+
+use async_trait::async_trait;
+use pumpkin_data::screen::WindowType;
+use pumpkin_inventory::{
+    container_click::{self, Click, ClickType},
+    Container, InventoryError,
+};
+use pumpkin_protocol::{
+    client::play::{COpenScreen, CSetContainerSlot},
+    codec::{slot::Slot, var_int::VarInt},
+    server::play::SClickContainer,
+};
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::item::ItemStack;
+use tokio::sync::Mutex;
+
+use crate::{entity::player::Player, server::Server};
+
+/// Handles clicks on a slot of a [`Menu`].
+#[async_trait]
+pub trait MenuClickHandler: Send + Sync {
+    /// Called when a player clicks the slot. The click itself has already been cancelled.
+    async fn on_click(&self, player: &Arc<Player>, menu: &Arc<Menu>, click: MenuClick);
+}
+
+/// Handles a [`Menu`] being closed.
+#[async_trait]
+pub trait MenuCloseHandler: Send + Sync {
+    /// Called when the menu was closed, either by the player or by a plugin.
+    async fn on_close(&self, player: &Arc<Player>, menu: &Arc<Menu>);
+}
+
+/// A click on a slot of a [`Menu`].
+pub struct MenuClick {
+    /// The index of the clicked slot.
+    pub slot: usize,
+    /// How the slot was clicked.
+    pub click_type: ClickType,
+}
+
+/// The slots of a menu, so the content can be sent like any other container.
+struct MenuSlots {
+    window_type: &'static WindowType,
+    slots: Vec<Option<ItemStack>>,
+}
+
+impl Container for MenuSlots {
+    fn window_type(&self) -> &'static WindowType {
+        self.window_type
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Menu"
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.slots.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.slots.iter().map(Option::as_ref).collect()
+    }
+}
+
+/// A chest-style menu with a title, items and per-slot click handlers.
+///
+/// Build it with [`Menu::new`] and the `with_*` methods, then show it with [`Player::open_menu`].
+pub struct Menu {
+    title: TextComponent,
+    window_type: &'static WindowType,
+    size: usize,
+    slots: Mutex<Box<dyn Container>>,
+    click_handlers: HashMap<usize, Arc<dyn MenuClickHandler>>,
+    close_handler: Option<Arc<dyn MenuCloseHandler>>,
+    viewers: Mutex<Vec<Weak<Player>>>,
+}
+
+impl Menu {
+    /// Creates an empty menu.
+    ///
+    /// # Arguments
+    /// - `title`: The title shown above the menu.
+    /// - `rows`: The number of rows of 9 slots, clamped to 1 to 6.
+    #[must_use]
+    pub fn new(title: TextComponent, rows: u8) -> Self {
+        let window_type = match rows {
+            0 | 1 => &WindowType::Generic9x1,
+            2 => &WindowType::Generic9x2,
+            3 => &WindowType::Generic9x3,
+            4 => &WindowType::Generic9x4,
+            5 => &WindowType::Generic9x5,
+            _ => &WindowType::Generic9x6,
+        };
+        let size = usize::from(rows.clamp(1, 6)) * 9;
+        Self {
+            title,
+            window_type,
+            size,
+            slots: Mutex::new(Box::new(MenuSlots {
+                window_type,
+                slots: vec![None; size],
+            })),
+            click_handlers: HashMap::new(),
+            close_handler: None,
+            viewers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Puts an item into a slot. Slots outside the menu are ignored.
+    #[must_use]
+    pub fn with_item(mut self, slot: usize, item: ItemStack) -> Self {
+        if let Some(stack) = self.slots.get_mut().all_slots().into_iter().nth(slot) {
+            *stack = Some(item);
+        }
+        self
+    }
+
+    /// Sets the handler called when a player clicks the slot.
+    #[must_use]
+    pub fn with_click_handler(mut self, slot: usize, handler: Arc<dyn MenuClickHandler>) -> Self {
+        if slot < self.size {
+            self.click_handlers.insert(slot, handler);
+        }
+        self
+    }
+
+    /// Sets the handler called when the menu is closed.
+    #[must_use]
+    pub fn with_close_handler(mut self, handler: Arc<dyn MenuCloseHandler>) -> Self {
+        self.close_handler = Some(handler);
+        self
+    }
+
+    #[must_use]
+    pub fn title(&self) -> &TextComponent {
+        &self.title
+    }
+
+    /// Returns the number of slots of the menu.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the item in the given slot.
+    pub async fn get_item(&self, slot: usize) -> Option<ItemStack> {
+        self.slots
+            .lock()
+            .await
+            .all_slots_ref()
+            .get(slot)
+            .copied()
+            .flatten()
+            .cloned()
+    }
+
+    /// Replaces the item in the given slot and updates it for every player viewing the menu.
+    /// Slots outside the menu are ignored.
+    pub async fn set_item(&self, slot: usize, item: Option<ItemStack>) {
+        let slot_data = Slot::from(item.as_ref());
+        {
+            let mut slots = self.slots.lock().await;
+            let Some(stack) = slots.all_slots().into_iter().nth(slot) else {
+                return;
+            };
+            *stack = item;
+        }
+
+        let mut viewers = self.viewers.lock().await;
+        viewers.retain(|viewer| viewer.strong_count() > 0);
+        for viewer in viewers.iter().filter_map(Weak::upgrade) {
+            let mut inventory = viewer.inventory().lock().await;
+            inventory.state_id += 1;
+            let packet = CSetContainerSlot::new(
+                inventory.total_opened_containers as i8,
+                inventory.state_id as i32,
+                slot as i16,
+                &slot_data,
+            );
+            drop(inventory);
+            viewer.client.send_packet(&packet).await;
+        }
+    }
+
+    async fn add_viewer(&self, player: &Arc<Player>) {
+        self.viewers.lock().await.push(Arc::downgrade(player));
+    }
+
+    async fn remove_viewer(&self, player: &Arc<Player>) {
+        let player = Arc::downgrade(player);
+        self.viewers
+            .lock()
+            .await
+            .retain(|viewer| viewer.strong_count() > 0 && !viewer.ptr_eq(&player));
+    }
+}
+
+impl Player {
+    /// Opens a menu for the player, closing any menu the player currently has open.
+    pub async fn open_menu(self: &Arc<Self>, server: &Server, menu: Arc<Menu>) {
+        self.menu_closed().await;
+        self.leave_open_container(server).await;
+
+        let window_id = {
+            let mut inventory = self.inventory().lock().await;
+            inventory.state_id = 0;
+            inventory.total_opened_containers += 1;
+            inventory.total_opened_containers
+        };
+        self.client
+            .send_packet(&COpenScreen::new(
+                window_id.into(),
+                VarInt(*menu.window_type as i32),
+                &menu.title,
+            ))
+            .await;
+        self.set_container_content(Some(&mut *menu.slots.lock().await))
+            .await;
+
+        menu.add_viewer(self).await;
+        *self.open_menu.lock().await = Some(menu);
+    }
+
+    /// Closes the menu the player has open, if any, and calls its close handler.
+    pub async fn close_menu(self: &Arc<Self>) {
+        if self.open_menu.lock().await.is_none() {
+            return;
+        }
+        self.close_container().await;
+        self.menu_closed().await;
+    }
+
+    /// Cleans up after the open menu was closed and calls its close handler.
+    pub(crate) async fn menu_closed(self: &Arc<Self>) {
+        let Some(menu) = self.open_menu.lock().await.take() else {
+            return;
+        };
+        menu.remove_viewer(self).await;
+        if let Some(handler) = &menu.close_handler {
+            handler.on_close(self, &menu).await;
+        }
+    }
+
+    /// Cancels a click in the open menu and passes it to the click handler of the slot.
+    pub(crate) async fn handle_menu_click(
+        self: &Arc<Self>,
+        menu: Arc<Menu>,
+        packet: SClickContainer,
+    ) -> Result<(), InventoryError> {
+        let total_containers = self.inventory().lock().await.total_opened_containers;
+        if packet.window_id.0 != total_containers {
+            return Err(InventoryError::ClosedContainerInteract(self.entity_id()));
+        }
+
+        // Undo whatever the client predicted, including the carried item
+        self.set_container_content(Some(&mut *menu.slots.lock().await))
+            .await;
+
+        let click = Click::new(packet.mode, packet.button, packet.slot)?;
+        if let container_click::Slot::Normal(slot) = click.slot {
+            if let Some(handler) = menu.click_handlers.get(&slot) {
+                let click = MenuClick {
+                    slot,
+                    click_type: click.click_type,
+                };
+                handler.on_click(self, &menu, click).await;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+//! Helpers for plugin messaging, also known as custom payloads.
+//!
+//! Plugins register the channels they want to use through their `Context`. Messages the client
+//! sends on a channel which no plugin has registered are dropped.
+
+use std::collections::HashSet;
+
+/// The channel used by clients and servers to announce which channels they listen on.
+pub const REGISTER_CHANNEL: &str = "minecraft:register";
+/// The channel used by clients and servers to announce which channels they stopped listening on.
+pub const UNREGISTER_CHANNEL: &str = "minecraft:unregister";
+/// The channel used by BungeeCord to communicate with servers behind it.
+pub const BUNGEECORD_CHANNEL: &str = "bungeecord:main";
+/// How many channels a client can register, like Bukkit's limit.
+pub const MAX_CLIENT_CHANNELS: usize = 128;
+/// The longest channel name a client can register in bytes, also like Bukkit.
+pub const MAX_CHANNEL_LENGTH: usize = 64;
+
+/// Encodes a list of channels for a `minecraft:register` or `minecraft:unregister` payload.
+#[must_use]
+pub fn encode_channels<S: AsRef<str>>(channels: &[S]) -> Vec<u8> {
+    channels
+        .iter()
+        .map(AsRef::as_ref)
+        .collect::<Vec<_>>()
+        .join("\0")
+        .into_bytes()
+}
+
+/// Decodes the list of channels from a `minecraft:register` or `minecraft:unregister` payload.
+#[must_use]
+pub fn decode_channels(data: &[u8]) -> Vec<String> {
+    data.split(|byte| *byte == 0)
+        .filter(|channel| !channel.is_empty())
+        .map(|channel| String::from_utf8_lossy(channel).into_owned())
+        .collect()
+}
+
+/// Adds the channels from a `minecraft:register` payload to the channels of a client.
+///
+/// Channels with names longer than [`MAX_CHANNEL_LENGTH`] and any beyond
+/// [`MAX_CLIENT_CHANNELS`] are ignored, so clients can't make the server store arbitrarily
+/// many channels.
+pub fn register_channels(channels: &mut HashSet<String>, data: &[u8]) {
+    for channel in decode_channels(data) {
+        if channel.len() > MAX_CHANNEL_LENGTH {
+            log::debug!("Ignoring registration of too long channel {channel}");
+        } else if channels.len() >= MAX_CLIENT_CHANNELS && !channels.contains(&channel) {
+            log::debug!("Ignoring registration of channel {channel}, too many are registered");
+        } else {
+            channels.insert(channel);
+        }
+    }
+}
+
+/// Writes a string the way Java's `DataOutput::writeUTF` does, which is used by the BungeeCord
+/// message format: a big endian `u16` length followed by the string in modified UTF-8.
+///
+/// # Returns
+/// An error if the encoded string is longer than 65535 bytes.
+pub fn write_utf(buf: &mut Vec<u8>, value: &str) -> Result<(), String> {
+    let mut encoded = Vec::with_capacity(value.len());
+    for unit in value.encode_utf16() {
+        match unit {
+            0x0001..=0x007F => encoded.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => {
+                encoded.push(0xC0 | (unit >> 6) as u8);
+                encoded.push(0x80 | (unit & 0x3F) as u8);
+            }
+            _ => {
+                encoded.push(0xE0 | (unit >> 12) as u8);
+                encoded.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                encoded.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+
+    let len = u16::try_from(encoded.len())
+        .map_err(|_| format!("String is too long: {} bytes", encoded.len()))?;
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(&encoded);
+    Ok(())
+}
+
+/// Reads a string the way Java's `DataInput::readUTF` does, advancing the given slice.
+///
+/// # Returns
+/// The string, or `None` if the data is truncated or not valid modified UTF-8.
+pub fn read_utf(buf: &mut &[u8]) -> Option<String> {
+    let (len, rest) = buf.split_first_chunk::<2>()?;
+    let len = usize::from(u16::from_be_bytes(*len));
+    if rest.len() < len {
+        return None;
+    }
+    let (encoded, rest) = rest.split_at(len);
+
+    let mut units = Vec::with_capacity(len);
+    let mut i = 0;
+    while i < encoded.len() {
+        let byte = u16::from(encoded[i]);
+        let continuation = |offset: usize| {
+            encoded
+                .get(i + offset)
+                .filter(|byte| *byte & 0xC0 == 0x80)
+                .map(|byte| u16::from(*byte & 0x3F))
+        };
+        match byte {
+            0x00..=0x7F => {
+                units.push(byte);
+                i += 1;
+            }
+            0xC0..=0xDF => {
+                units.push(((byte & 0x1F) << 6) | continuation(1)?);
+                i += 2;
+            }
+            0xE0..=0xEF => {
+                units.push(((byte & 0x0F) << 12) | (continuation(1)? << 6) | continuation(2)?);
+                i += 3;
+            }
+            _ => return None,
+        }
+    }
+
+    *buf = rest;
+    String::from_utf16(&units).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn utf_round_trip() {
+        let mut buf = Vec::new();
+        write_utf(&mut buf, "Connect").unwrap();
+        write_utf(&mut buf, "lobby\0ä€😀").unwrap();
+        assert_eq!(&buf[..9], b"\x00\x07Connect");
+
+        let mut slice = buf.as_slice();
+        assert_eq!(read_utf(&mut slice).as_deref(), Some("Connect"));
+        assert_eq!(read_utf(&mut slice).as_deref(), Some("lobby\0ä€😀"));
+        assert!(slice.is_empty());
+    }
+
+    #[test]
+    fn channels_round_trip() {
+        let encoded = encode_channels(&["bungeecord:main", "example:channel"]);
+        assert_eq!(
+            decode_channels(&encoded),
+            vec!["bungeecord:main", "example:channel"]
+        );
+    }
+
+    #[test]
+    fn registered_channels_are_limited() {
+        let mut channels = HashSet::new();
+        let too_long = format!("example:{}", "a".repeat(MAX_CHANNEL_LENGTH));
+        register_channels(
+            &mut channels,
+            &encode_channels(&["example:a", too_long.as_str()]),
+        );
+        assert_eq!(channels, HashSet::from(["example:a".to_string()]));
+
+        let many: Vec<_> = (0..MAX_CLIENT_CHANNELS * 2)
+            .map(|i| format!("example:{i}"))
+            .collect();
+        register_channels(&mut channels, &encode_channels(&many));
+        assert_eq!(channels.len(), MAX_CLIENT_CHANNELS);
+        assert!(channels.contains("example:a"));
+
+        // Registering a channel again is not limited
+        register_channels(&mut channels, &encode_channels(&["example:a"]));
+        assert_eq!(channels.len(), MAX_CLIENT_CHANNELS);
+    }
+}
@@ -1,5 +1,13 @@
+pub mod bossbar;
 pub mod context;
 pub mod events;
+pub mod menu;
+pub mod messaging;
+pub mod nbt;
+pub mod placeholder;
+pub mod recipe;
+pub mod scoreboard;
+pub mod world;
 
 use async_trait::async_trait;
 pub use context::*;
@@ -8,7 +16,7 @@ pub use events::*;
 /// Struct representing metadata for a plugin.
 ///
 /// This struct contains essential information about a plugin, including its name,
-/// version, authors, and a description. It is generic over a lifetime `'s` to allow
+/// version, authors, a description and a website. It is generic over a lifetime `'s` to allow
 /// for string slices that are valid for the lifetime of the plugin metadata.
 #[derive(Debug, Clone)]
 pub struct PluginMetadata<'s> {
@@ -20,6 +28,13 @@ pub struct PluginMetadata<'s> {
     pub authors: &'s str,
     /// A description of the plugin.
     pub description: &'s str,
+    /// The website of the plugin, empty if it has none.
+    pub website: &'s str,
+    /// Plugins which must be loaded before this plugin. If any of them is missing,
+    /// this plugin will not be loaded.
+    pub depends: &'s [&'s str],
+    /// Plugins which should be loaded before this plugin if they are present.
+    pub soft_depends: &'s [&'s str],
 }
 
 /// Trait representing a plugin with asynchronous lifecycle methods.
@@ -0,0 +1,14 @@
+//! NBT access for plugins.
+//!
+//! Plugins keep their own data in the `minecraft:custom_data` component of an item or in the
+//! custom data of an entity, preferably under a key named after the plugin. Components and keys
+//! the server does not know are kept as they are when items and entities are saved and loaded.
+//!
+//! Changing an item in a player's inventory must go through [`Player::modify_inventory_slot`] or
+//! [`Player::modify_held_item`], so the client sees the change.
+//!
+//! [`Player::modify_inventory_slot`]: crate::entity::player::Player::modify_inventory_slot
+//! [`Player::modify_held_item`]: crate::entity::player::Player::modify_held_item
+
+pub use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+pub use pumpkin_world::item::{ItemStack, CUSTOM_DATA};
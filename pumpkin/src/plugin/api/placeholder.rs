@@ -0,0 +1,344 @@
+//! Placeholders like `%player_name%`, which are replaced in the MOTD, the tab list and join and
+//! quit messages.
+//!
+//! Plugins register their own placeholders through `Context::register_placeholder`, so e.g. a rank
+//! plugin providing `%rank%` affects every text which is resolved here.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{atomic::Ordering, Arc, LazyLock, Mutex, RwLock, Weak},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_util::text::{TextComponent, TextComponentBase, TextContent};
+
+use crate::{entity::player::Player, server::Server};
+
+/// Resolves the value of a placeholder.
+#[async_trait]
+pub trait Placeholder: Send + Sync {
+    /// Returns the value of the placeholder, or `None` to leave it as it is, e.g. because it needs a
+    /// player and none was given.
+    async fn resolve(&self, player: Option<&Player>) -> Option<String>;
+
+    /// Whether the value is the same for every player. Such values are cached for a tick.
+    fn is_global(&self) -> bool {
+        false
+    }
+}
+
+static PLACEHOLDERS: LazyLock<RwLock<HashMap<String, Arc<dyn Placeholder>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Values of global placeholders and when they were resolved
+static GLOBAL_CACHE: LazyLock<Mutex<HashMap<String, (Instant, Option<String>)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Makes a placeholder available as `%name%`.
+///
+/// # Returns
+/// An error if a placeholder with that name is already registered.
+pub fn register_placeholder(name: &str, placeholder: Arc<dyn Placeholder>) -> Result<(), String> {
+    if !is_valid_name(name) {
+        return Err(format!("Invalid placeholder name {name}"));
+    }
+    let mut placeholders = PLACEHOLDERS.write().unwrap();
+    if placeholders.contains_key(name) {
+        return Err(format!("Placeholder {name} is already registered"));
+    }
+    placeholders.insert(name.to_string(), placeholder);
+    Ok(())
+}
+
+/// Removes a placeholder.
+///
+/// # Returns
+/// Whether the placeholder was removed.
+pub fn unregister_placeholder(name: &str) -> bool {
+    GLOBAL_CACHE.lock().unwrap().remove(name);
+    PLACEHOLDERS.write().unwrap().remove(name).is_some()
+}
+
+/// Replaces all registered placeholders in the text. Unknown placeholders are left as they are.
+pub async fn resolve(text: &str, player: Option<&Player>) -> String {
+    let values = resolve_all(find_placeholders(text), player).await;
+    replace_placeholders(text, &values).into_owned()
+}
+
+/// Replaces all registered placeholders in the text of the component and its children.
+pub async fn resolve_component(
+    mut component: TextComponent,
+    player: Option<&Player>,
+) -> TextComponent {
+    let mut names = Vec::new();
+    visit_texts(&mut component.0, &mut |text| {
+        names.extend(find_placeholders(text).into_iter().map(str::to_string));
+    });
+    if names.is_empty() {
+        return component;
+    }
+
+    let values = resolve_all(names.iter().map(String::as_str), player).await;
+    visit_texts(&mut component.0, &mut |text| {
+        if let Cow::Owned(replaced) = replace_placeholders(text, &values) {
+            *text = Cow::Owned(replaced);
+        }
+    });
+    component
+}
+
+/// Calls `f` with every text of the component, including translation arguments and children
+fn visit_texts(component: &mut TextComponentBase, f: &mut impl FnMut(&mut Cow<'static, str>)) {
+    match &mut component.content {
+        TextContent::Text { text } => f(text),
+        TextContent::Translate { with, .. } => {
+            for argument in with {
+                visit_texts(argument, f);
+            }
+        }
+        TextContent::EntityNames { .. } | TextContent::Keybind { .. } => {}
+    }
+    for child in &mut component.extra {
+        visit_texts(child, f);
+    }
+}
+
+async fn resolve_all<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    player: Option<&Player>,
+) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    for name in names {
+        if values.contains_key(name) {
+            continue;
+        }
+        if let Some(value) = resolve_placeholder(name, player).await {
+            values.insert(name.to_string(), value);
+        }
+    }
+    values
+}
+
+async fn resolve_placeholder(name: &str, player: Option<&Player>) -> Option<String> {
+    // Clone the placeholder so the lock is not held while it resolves
+    let placeholder = PLACEHOLDERS.read().unwrap().get(name).cloned()?;
+    if !placeholder.is_global() {
+        return placeholder.resolve(player).await;
+    }
+
+    let tick = Duration::from_secs_f32(1.0 / BASIC_CONFIG.tps);
+    if let Some((resolved_at, value)) = GLOBAL_CACHE.lock().unwrap().get(name) {
+        if resolved_at.elapsed() < tick {
+            return value.clone();
+        }
+    }
+    let value = placeholder.resolve(None).await;
+    GLOBAL_CACHE
+        .lock()
+        .unwrap()
+        .insert(name.to_string(), (Instant::now(), value.clone()));
+    value
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'))
+}
+
+/// Returns the names of everything in the text which looks like a placeholder
+fn find_placeholders(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('%') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('%') else {
+            break;
+        };
+        let name = &rest[..end];
+        if is_valid_name(name) {
+            names.push(name);
+            rest = &rest[end + 1..];
+        }
+    }
+    names
+}
+
+/// Replaces the placeholders which have a value, borrowing the text if there are none
+fn replace_placeholders<'a>(text: &'a str, values: &HashMap<String, String>) -> Cow<'a, str> {
+    if values.is_empty() || !text.contains('%') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('%') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('%') else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = &after[..end];
+        if let Some(value) = values.get(name).filter(|_| is_valid_name(name)) {
+            result.push_str(value);
+            rest = &after[end + 1..];
+        } else {
+            // The closing `%` may start the next placeholder
+            result.push('%');
+            rest = after;
+        }
+    }
+    result.push_str(rest);
+    Cow::Owned(result)
+}
+
+struct PlayerName;
+
+#[async_trait]
+impl Placeholder for PlayerName {
+    async fn resolve(&self, player: Option<&Player>) -> Option<String> {
+        Some(player?.gameprofile.name.clone())
+    }
+}
+
+struct PlayerWorld;
+
+#[async_trait]
+impl Placeholder for PlayerWorld {
+    async fn resolve(&self, player: Option<&Player>) -> Option<String> {
+        Some(player?.world().await.name())
+    }
+}
+
+struct PlayerPing;
+
+#[async_trait]
+impl Placeholder for PlayerPing {
+    async fn resolve(&self, player: Option<&Player>) -> Option<String> {
+        Some(player?.ping.load(Ordering::Relaxed).to_string())
+    }
+}
+
+struct ServerOnline(Weak<Server>);
+
+#[async_trait]
+impl Placeholder for ServerOnline {
+    async fn resolve(&self, _player: Option<&Player>) -> Option<String> {
+        Some(self.0.upgrade()?.get_player_count().await.to_string())
+    }
+
+    fn is_global(&self) -> bool {
+        true
+    }
+}
+
+struct ServerTps(Weak<Server>);
+
+#[async_trait]
+impl Placeholder for ServerTps {
+    async fn resolve(&self, _player: Option<&Player>) -> Option<String> {
+        Some(format!("{:.1}", self.0.upgrade()?.tps.load()))
+    }
+
+    fn is_global(&self) -> bool {
+        true
+    }
+}
+
+/// Registers the placeholders which ship with the server.
+pub fn register_builtin_placeholders(server: &Arc<Server>) {
+    let builtin: [(&str, Arc<dyn Placeholder>); 5] = [
+        ("player_name", Arc::new(PlayerName)),
+        ("player_world", Arc::new(PlayerWorld)),
+        ("player_ping", Arc::new(PlayerPing)),
+        (
+            "server_online",
+            Arc::new(ServerOnline(Arc::downgrade(server))),
+        ),
+        ("server_tps", Arc::new(ServerTps(Arc::downgrade(server)))),
+    ];
+    for (name, placeholder) in builtin {
+        // Replace the placeholders in case the server was created before
+        unregister_placeholder(name);
+        register_placeholder(name, placeholder).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn values(values: &[(&str, &str)]) -> HashMap<String, String> {
+        values
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn finds_placeholders() {
+        assert_eq!(
+            find_placeholders("%player_name% has %balance%$"),
+            vec!["player_name", "balance"]
+        );
+        assert_eq!(find_placeholders("100% of %rank%"), vec!["rank"]);
+        assert!(find_placeholders("50% off").is_empty());
+    }
+
+    #[test]
+    fn replaces_known_placeholders() {
+        let values = values(&[("player_name", "Steve"), ("rank", "Admin")]);
+        assert_eq!(
+            replace_placeholders("[%rank%] %player_name%", &values),
+            "[Admin] Steve"
+        );
+        assert_eq!(
+            replace_placeholders("100% %rank% %unknown%", &values),
+            "100% Admin %unknown%"
+        );
+        assert_eq!(replace_placeholders("%rank", &values), "%rank");
+        assert!(matches!(
+            replace_placeholders("no placeholders", &values),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn resolves_components() {
+        struct Rank;
+
+        #[async_trait]
+        impl Placeholder for Rank {
+            async fn resolve(&self, _player: Option<&Player>) -> Option<String> {
+                Some("Admin".to_string())
+            }
+
+            fn is_global(&self) -> bool {
+                true
+            }
+        }
+
+        register_placeholder("test_rank", Arc::new(Rank)).unwrap();
+        assert!(register_placeholder("test_rank", Arc::new(Rank)).is_err());
+
+        let component = TextComponent::translate(
+            "multiplayer.player.joined",
+            [TextComponent::text("[%test_rank%] Steve")],
+        )
+        .add_text(" %test_rank%");
+        let expected = TextComponent::translate(
+            "multiplayer.player.joined",
+            [TextComponent::text("[Admin] Steve")],
+        )
+        .add_text(" Admin");
+        assert_eq!(resolve_component(component, None).await, expected);
+
+        assert!(unregister_placeholder("test_rank"));
+        assert_eq!(resolve("%test_rank%", None).await, "%test_rank%");
+    }
+}
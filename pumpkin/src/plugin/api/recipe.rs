@@ -0,0 +1,92 @@
+//! Helpers to build recipes for `Context::register_recipe`.
+//!
+//! Recipes registered by plugins are crafted exactly like the recipes of data packs and are shown in
+//! the recipe book of every player.
+
+use pumpkin_data::{
+    item::Item,
+    tag::{RegistryEntryList, TagType},
+};
+use pumpkin_registry::RecipeResult;
+pub use pumpkin_registry::{Recipe, RecipeCategory};
+use pumpkin_world::item::ItemStack;
+
+fn item_name(item: &Item) -> String {
+    format!("minecraft:{}", item.registry_key())
+}
+
+/// An ingredient which only accepts the given item.
+#[must_use]
+pub fn item(item: &Item) -> RegistryEntryList {
+    RegistryEntryList::Single(TagType::Item(item_name(item)))
+}
+
+/// An ingredient which accepts any of the given items.
+#[must_use]
+pub fn any_of(items: &[Item]) -> RegistryEntryList {
+    RegistryEntryList::Many(
+        items
+            .iter()
+            .map(|item| TagType::Item(item_name(item)))
+            .collect(),
+    )
+}
+
+/// An ingredient which accepts every item of an item tag, e.g. `minecraft:planks`.
+#[must_use]
+pub fn tag(tag: &str) -> RegistryEntryList {
+    RegistryEntryList::Single(TagType::Tag(tag.trim_start_matches('#').to_string()))
+}
+
+fn result(stack: &ItemStack) -> RecipeResult {
+    RecipeResult::Many {
+        count: stack.item_count,
+        id: item_name(&stack.item),
+        components: None,
+    }
+}
+
+/// Creates a shaped crafting recipe.
+///
+/// # Arguments
+/// - `pattern`: Up to 3 rows of up to 3 characters. A space is an empty slot.
+/// - `keys`: The ingredient of each character used in the pattern.
+/// - `output`: The crafted items.
+///
+/// # Returns
+/// An error if the pattern is too large or uses a character without an ingredient.
+pub fn shaped(
+    pattern: &[&str],
+    keys: impl IntoIterator<Item = (char, RegistryEntryList)>,
+    output: &ItemStack,
+) -> Result<Recipe, String> {
+    Recipe::shaped(pattern, keys.into_iter().collect(), result(output))
+}
+
+/// Creates a shapeless crafting recipe.
+///
+/// # Returns
+/// An error unless there are 1 to 9 ingredients.
+pub fn shapeless(
+    ingredients: Vec<RegistryEntryList>,
+    output: &ItemStack,
+) -> Result<Recipe, String> {
+    Recipe::shapeless(ingredients, result(output))
+}
+
+/// Creates a furnace recipe.
+///
+/// # Arguments
+/// - `ingredient`: The item which is smelted.
+/// - `output`: The smelted items.
+/// - `cooking_time`: How long smelting takes, in ticks. Vanilla uses 200.
+/// - `experience`: The experience given for smelting one item.
+#[must_use]
+pub fn smelting(
+    ingredient: RegistryEntryList,
+    output: &ItemStack,
+    cooking_time: u16,
+    experience: f32,
+) -> Recipe {
+    Recipe::smelting(ingredient, result(output), cooking_time, experience)
+}
@@ -0,0 +1,456 @@
+//! Sidebars and teams for plugins.
+//!
+//! Objectives and teams created through a plugin's `Context` are named `<plugin>:<id>`. Names
+//! created with the `/scoreboard` and `/team` commands can not contain a `:`, so the two never clash.
+
+use std::sync::{Arc, Weak};
+
+use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
+use pumpkin_protocol::{
+    client::play::{
+        CDisplayObjective, CResetScore, CSetPlayerTeam, CUpdateObjectives, CUpdateScore, Mode,
+        RenderType, TeamAction, TeamInfo,
+    },
+    codec::var_int::VarInt,
+    ClientPacket, NumberFormat,
+};
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::{entity::player::Player, server::Server};
+
+/// Returns the score holder used for a line, which is never shown since every line overrides its display name.
+fn line_holder(index: usize) -> String {
+    format!("line_{index}")
+}
+
+/// The sidebar sorts scores in descending order, so the first line gets the highest score.
+fn line_score(index: usize, line_count: usize) -> VarInt {
+    VarInt((line_count - index) as i32)
+}
+
+/// A sidebar with a title and ordered lines, shown to the players added with [`Sidebar::show`].
+///
+/// A client can only display one sidebar at a time, showing another one replaces it.
+pub struct Sidebar {
+    name: String,
+    title: Mutex<TextComponent>,
+    lines: Mutex<Vec<TextComponent>>,
+    viewers: Mutex<Vec<Weak<Player>>>,
+}
+
+impl Sidebar {
+    /// Creates a sidebar without lines. Plugins should use `Context::create_sidebar`, which
+    /// namespaces the objective name.
+    #[must_use]
+    pub fn new(name: String, title: TextComponent) -> Self {
+        Self {
+            name,
+            title: Mutex::new(title),
+            lines: Mutex::new(Vec::new()),
+            viewers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The name of the objective backing this sidebar.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub async fn get_lines(&self) -> Vec<TextComponent> {
+        self.lines.lock().await.clone()
+    }
+
+    /// Shows the sidebar to a player.
+    pub async fn show(&self, player: &Arc<Player>) {
+        {
+            let mut viewers = self.viewers.lock().await;
+            viewers.retain(|viewer| viewer.strong_count() > 0);
+            if viewers
+                .iter()
+                .any(|viewer| viewer.as_ptr() == Arc::as_ptr(player))
+            {
+                return;
+            }
+            viewers.push(Arc::downgrade(player));
+        }
+
+        let title = self.title.lock().await.clone();
+        player
+            .client
+            .send_packet(&CUpdateObjectives::new(
+                &self.name,
+                Mode::Add,
+                title,
+                RenderType::Integer,
+                Some(NumberFormat::Blank),
+            ))
+            .await;
+        let lines = self.lines.lock().await;
+        for (index, line) in lines.iter().enumerate() {
+            let holder = line_holder(index);
+            player
+                .client
+                .send_packet(&CUpdateScore::new(
+                    &holder,
+                    &self.name,
+                    line_score(index, lines.len()),
+                    Some(line.clone()),
+                    None,
+                ))
+                .await;
+        }
+        player
+            .client
+            .send_packet(&CDisplayObjective::new(
+                ScoreboardDisplaySlot::Sidebar,
+                &self.name,
+            ))
+            .await;
+    }
+
+    /// Hides the sidebar from a player.
+    pub async fn hide(&self, player: &Arc<Player>) {
+        {
+            let mut viewers = self.viewers.lock().await;
+            let count = viewers.len();
+            viewers.retain(|viewer| {
+                viewer.strong_count() > 0 && viewer.as_ptr() != Arc::as_ptr(player)
+            });
+            if viewers.len() == count {
+                return;
+            }
+        }
+        player.client.send_packet(&self.remove_packet()).await;
+    }
+
+    /// Whether the sidebar is shown to the player.
+    pub async fn is_shown_to(&self, player: &Arc<Player>) -> bool {
+        self.viewers
+            .lock()
+            .await
+            .iter()
+            .any(|viewer| viewer.as_ptr() == Arc::as_ptr(player))
+    }
+
+    /// Hides the sidebar from every player it is shown to.
+    pub async fn hide_all(&self) {
+        let viewers = std::mem::take(&mut *self.viewers.lock().await);
+        let packet = self.remove_packet();
+        for viewer in viewers.iter().filter_map(Weak::upgrade) {
+            viewer.client.send_packet(&packet).await;
+        }
+    }
+
+    pub async fn set_title(&self, title: TextComponent) {
+        *self.title.lock().await = title.clone();
+        self.broadcast(&CUpdateObjectives::new(
+            &self.name,
+            Mode::Update,
+            title,
+            RenderType::Integer,
+            Some(NumberFormat::Blank),
+        ))
+        .await;
+    }
+
+    /// Replaces all lines, from top to bottom. Only lines which changed are sent, unless the
+    /// number of lines changed.
+    pub async fn set_lines(&self, new_lines: Vec<TextComponent>) {
+        let mut lines = self.lines.lock().await;
+        let same_count = lines.len() == new_lines.len();
+
+        for (index, line) in new_lines.iter().enumerate() {
+            if same_count && lines[index] == *line {
+                continue;
+            }
+            let holder = line_holder(index);
+            self.broadcast(&CUpdateScore::new(
+                &holder,
+                &self.name,
+                line_score(index, new_lines.len()),
+                Some(line.clone()),
+                None,
+            ))
+            .await;
+        }
+        for index in new_lines.len()..lines.len() {
+            self.broadcast(&CResetScore::new(
+                line_holder(index),
+                Some(self.name.clone()),
+            ))
+            .await;
+        }
+
+        *lines = new_lines;
+    }
+
+    /// Replaces a single line without touching the others. Lines past the end are ignored.
+    pub async fn set_line(&self, index: usize, line: TextComponent) {
+        let mut lines = self.lines.lock().await;
+        let line_count = lines.len();
+        let Some(current) = lines.get_mut(index) else {
+            return;
+        };
+        if *current == line {
+            return;
+        }
+        let holder = line_holder(index);
+        self.broadcast(&CUpdateScore::new(
+            &holder,
+            &self.name,
+            line_score(index, line_count),
+            Some(line.clone()),
+            None,
+        ))
+        .await;
+        *current = line;
+    }
+
+    fn remove_packet(&self) -> CUpdateObjectives<'_> {
+        CUpdateObjectives::new(
+            &self.name,
+            Mode::Remove,
+            TextComponent::text(""),
+            RenderType::Integer,
+            None,
+        )
+    }
+
+    async fn broadcast<P: ClientPacket>(&self, packet: &P) {
+        let mut viewers = self.viewers.lock().await;
+        viewers.retain(|viewer| viewer.strong_count() > 0);
+        for viewer in viewers.iter().filter_map(Weak::upgrade) {
+            viewer.client.send_packet(packet).await;
+        }
+    }
+}
+
+/// How a team looks, see [`Team::update_settings`].
+#[derive(Clone)]
+pub struct TeamSettings {
+    pub display_name: TextComponent,
+    /// Shown in front of the names of members.
+    pub prefix: TextComponent,
+    /// Shown behind the names of members.
+    pub suffix: TextComponent,
+    /// The color of the name tags of members.
+    pub color: Option<NamedColor>,
+    pub friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+}
+
+impl Default for TeamSettings {
+    fn default() -> Self {
+        Self {
+            display_name: TextComponent::text(""),
+            prefix: TextComponent::text(""),
+            suffix: TextComponent::text(""),
+            color: None,
+            friendly_fire: true,
+            see_friendly_invisibles: true,
+        }
+    }
+}
+
+impl TeamSettings {
+    fn info(&self) -> TeamInfo<'_> {
+        TeamInfo {
+            display_name: &self.display_name,
+            friendly_flags: u8::from(self.friendly_fire)
+                | (u8::from(self.see_friendly_invisibles) << 1),
+            name_tag_visibility: "always",
+            collision_rule: "always",
+            // 21 resets the color
+            color: VarInt(self.color.map_or(21, |color| color as i32)),
+            prefix: &self.prefix,
+            suffix: &self.suffix,
+        }
+    }
+}
+
+/// A team of players, which is used to color and decorate their name tags. Teams are shown to
+/// every player on the server.
+pub struct Team {
+    name: String,
+    settings: Mutex<TeamSettings>,
+    members: Mutex<Vec<String>>,
+}
+
+impl Team {
+    #[must_use]
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            settings: Mutex::new(TeamSettings::default()),
+            members: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The name of the team.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub async fn get_settings(&self) -> TeamSettings {
+        self.settings.lock().await.clone()
+    }
+
+    pub async fn get_members(&self) -> Vec<String> {
+        self.members.lock().await.clone()
+    }
+
+    /// Changes the settings of the team and sends them to all players.
+    pub async fn update_settings(&self, server: &Server, update: impl FnOnce(&mut TeamSettings)) {
+        let mut settings = self.settings.lock().await;
+        update(&mut settings);
+        server
+            .broadcast_packet_all(&CSetPlayerTeam::new(
+                &self.name,
+                TeamAction::UpdateInfo(settings.info()),
+            ))
+            .await;
+    }
+
+    pub async fn set_prefix(&self, server: &Server, prefix: TextComponent) {
+        self.update_settings(server, |settings| settings.prefix = prefix)
+            .await;
+    }
+
+    pub async fn set_color(&self, server: &Server, color: Option<NamedColor>) {
+        self.update_settings(server, |settings| settings.color = color)
+            .await;
+    }
+
+    /// Adds a player, by name, to the team.
+    pub async fn add_member(&self, server: &Server, name: &str) {
+        let mut members = self.members.lock().await;
+        if members.iter().any(|member| member == name) {
+            return;
+        }
+        members.push(name.to_string());
+        server
+            .broadcast_packet_all(&CSetPlayerTeam::new(
+                &self.name,
+                TeamAction::AddEntities(&[name.to_string()]),
+            ))
+            .await;
+    }
+
+    /// Removes a player, by name, from the team.
+    pub async fn remove_member(&self, server: &Server, name: &str) {
+        let mut members = self.members.lock().await;
+        let count = members.len();
+        members.retain(|member| member != name);
+        if members.len() == count {
+            return;
+        }
+        server
+            .broadcast_packet_all(&CSetPlayerTeam::new(
+                &self.name,
+                TeamAction::RemoveEntities(&[name.to_string()]),
+            ))
+            .await;
+    }
+
+    /// Sends the team with all of its members to a player.
+    pub async fn send_to(&self, player: &Player) {
+        let settings = self.settings.lock().await;
+        let members = self.members.lock().await;
+        player
+            .client
+            .send_packet(&CSetPlayerTeam::new(
+                &self.name,
+                TeamAction::Create {
+                    info: settings.info(),
+                    entities: &members,
+                },
+            ))
+            .await;
+    }
+}
+
+/// The sidebars shown to all players and the teams created by plugins, which are sent to players
+/// when they join.
+#[derive(Default)]
+pub struct PluginScoreboard {
+    global_sidebars: RwLock<Vec<Arc<Sidebar>>>,
+    teams: RwLock<Vec<Arc<Team>>>,
+}
+
+impl PluginScoreboard {
+    /// Shows a sidebar to all players, including the ones joining later.
+    pub async fn add_global_sidebar(&self, server: &Server, sidebar: Arc<Sidebar>) {
+        for player in server.get_all_players().await {
+            sidebar.show(&player).await;
+        }
+        self.global_sidebars.write().await.push(sidebar);
+    }
+
+    /// Hides a sidebar from all players.
+    pub async fn remove_global_sidebar(&self, sidebar: &Arc<Sidebar>) {
+        self.global_sidebars
+            .write()
+            .await
+            .retain(|global| !Arc::ptr_eq(global, sidebar));
+        sidebar.hide_all().await;
+    }
+
+    /// Sends a team to all players, including the ones joining later.
+    pub async fn add_team(&self, server: &Server, team: Arc<Team>) {
+        for player in server.get_all_players().await {
+            team.send_to(&player).await;
+        }
+        self.teams.write().await.push(team);
+    }
+
+    /// Removes a team for all players.
+    pub async fn remove_team(&self, server: &Server, team: &Arc<Team>) {
+        self.teams
+            .write()
+            .await
+            .retain(|registered| !Arc::ptr_eq(registered, team));
+        server
+            .broadcast_packet_all(&CSetPlayerTeam::new(&team.name, TeamAction::Remove))
+            .await;
+    }
+
+    /// Sends all teams and global sidebars to a player who just joined.
+    pub async fn send_to(&self, player: &Arc<Player>) {
+        for team in self.teams.read().await.iter() {
+            team.send_to(player).await;
+        }
+        for sidebar in self.global_sidebars.read().await.iter() {
+            sidebar.show(player).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn first_line_has_highest_score() {
+        assert_eq!(line_score(0, 3).0, 3);
+        assert_eq!(line_score(2, 3).0, 1);
+        assert_ne!(line_holder(0), line_holder(1));
+    }
+
+    #[tokio::test]
+    async fn set_line_only_changes_existing_lines() {
+        let sidebar = Sidebar::new("test:sidebar".to_string(), TextComponent::text("Title"));
+        sidebar
+            .set_lines(vec![TextComponent::text("a"), TextComponent::text("b")])
+            .await;
+        sidebar.set_line(1, TextComponent::text("c")).await;
+        sidebar.set_line(5, TextComponent::text("d")).await;
+
+        assert_eq!(
+            sidebar.get_lines().await,
+            vec![TextComponent::text("a"), TextComponent::text("c")]
+        );
+    }
+}
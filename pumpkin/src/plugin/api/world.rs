@@ -0,0 +1,162 @@
+//! World access for plugins.
+//!
+//! A [`WorldHandle`] goes through the same locks the server uses while ticking, so its methods can
+//! be called from any async context, including event handlers.
+
+use std::sync::Arc;
+
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::math::{
+    boundingbox::BoundingBox, position::BlockPos, vector2::Vector2, vector3::Vector3,
+};
+use pumpkin_world::{
+    block::registry::get_state_by_state_id,
+    chunk::ticket::{ChunkLevelType, TicketType, FULL_CHUNK_LEVEL},
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+use thiserror::Error;
+
+use crate::{
+    entity::{mob, EntityBase},
+    server::Server,
+    world::World,
+};
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldAccessError {
+    #[error("Height {0} is outside of the world")]
+    OutOfWorldBounds(i32),
+    #[error("Block state {0} does not exist")]
+    InvalidBlockState(u16),
+}
+
+/// A handle to one of the worlds of the server.
+#[derive(Clone)]
+pub struct WorldHandle {
+    world: Arc<World>,
+    server: Arc<Server>,
+}
+
+impl WorldHandle {
+    #[must_use]
+    pub fn new(world: Arc<World>, server: Arc<Server>) -> Self {
+        Self { world, server }
+    }
+
+    /// The name of the world, e.g. `world_nether`.
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.world.name()
+    }
+
+    /// Gets the block state id at the given position.
+    ///
+    /// If the chunk is not loaded, it is loaded for the duration of the call.
+    pub async fn get_block_state(&self, position: BlockPos) -> Result<u16, WorldAccessError> {
+        check_height(position)?;
+        self.world
+            .get_block_state_id(&position)
+            .await
+            .map_err(|_| WorldAccessError::OutOfWorldBounds(position.0.y))
+    }
+
+    /// Sets the block state at the given position, sends the change to all players and notifies
+    /// the neighbouring blocks. Returns the block state id that was replaced.
+    ///
+    /// If the chunk is not loaded, it is loaded for the change and written back to disk afterwards.
+    pub async fn set_block_state(
+        &self,
+        position: BlockPos,
+        block_state_id: u16,
+    ) -> Result<u16, WorldAccessError> {
+        check_height(position)?;
+        if get_state_by_state_id(block_state_id).is_none() {
+            return Err(WorldAccessError::InvalidBlockState(block_state_id));
+        }
+
+        // Keep the chunk in memory while changing it, so the change is not lost if nobody
+        // watches the chunk
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        let level = &self.world.level;
+        level.add_ticket(chunk, TicketType::Temporary, FULL_CHUNK_LEVEL);
+        let replaced = self.world.set_block_state(&position, block_state_id).await;
+        self.world.update_neighbors(&self.server, &position).await;
+        level
+            .remove_ticket(chunk, &TicketType::Temporary, FULL_CHUNK_LEVEL)
+            .await;
+
+        Ok(replaced)
+    }
+
+    /// Pins the chunk, so it stays loaded until the plugin removes the ticket again with
+    /// [`WorldHandle::remove_chunk_ticket`]. The lower the level, the more chunks around it are
+    /// kept loaded and ticked, see [`pumpkin_world::chunk::ticket`]. The chunk is loaded right away.
+    ///
+    /// Each ticket a plugin adds has to be removed once, tickets of other plugins are not affected.
+    pub async fn add_chunk_ticket(&self, chunk: Vector2<i32>, plugin: &str, level: u8) {
+        self.world
+            .level
+            .add_ticket(chunk, TicketType::Plugin(plugin.to_string()), level);
+        self.world.receive_chunk(chunk).await;
+    }
+
+    /// Removes a ticket added with [`WorldHandle::add_chunk_ticket`]. The chunks no longer kept
+    /// loaded are unloaded and written to disk if they changed.
+    pub async fn remove_chunk_ticket(&self, chunk: Vector2<i32>, plugin: &str, level: u8) {
+        self.world
+            .level
+            .remove_ticket(chunk, &TicketType::Plugin(plugin.to_string()), level)
+            .await;
+    }
+
+    /// Whether the chunk is kept loaded and what is ticked in it
+    #[must_use]
+    pub fn chunk_level_type(&self, chunk: Vector2<i32>) -> ChunkLevelType {
+        self.world.level.chunk_level_type(&chunk)
+    }
+
+    /// Spawns a new entity of the given type and shows it to all players.
+    pub async fn spawn_entity(
+        &self,
+        entity_type: EntityType,
+        position: Vector3<f64>,
+    ) -> Arc<dyn EntityBase> {
+        let entity = mob::from_type(entity_type, &self.server, position, &self.world).await;
+        self.world.spawn_entity(entity.clone()).await;
+        entity
+    }
+
+    /// Gets all entities, including players, whose bounding box intersects the given box.
+    pub async fn get_entities_in_box(&self, aabb: &BoundingBox) -> Vec<Arc<dyn EntityBase>> {
+        let intersects =
+            |entity: &dyn EntityBase| entity.get_entity().bounding_box.load().intersects(aabb);
+
+        let mut entities: Vec<Arc<dyn EntityBase>> = self
+            .world
+            .players
+            .read()
+            .await
+            .values()
+            .filter(|player| intersects(player.as_ref()))
+            .map(|player| player.clone() as Arc<dyn EntityBase>)
+            .collect();
+        entities.extend(
+            self.world
+                .entities
+                .read()
+                .await
+                .values()
+                .filter(|entity| intersects(entity.as_ref()))
+                .cloned(),
+        );
+        entities
+    }
+}
+
+fn check_height(position: BlockPos) -> Result<(), WorldAccessError> {
+    if (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&position.0.y) {
+        Ok(())
+    } else {
+        Err(WorldAccessError::OutOfWorldBounds(position.0.y))
+    }
+}
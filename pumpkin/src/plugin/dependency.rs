@@ -0,0 +1,192 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::PluginMetadata;
+
+/// The result of resolving the load order of a set of plugins.
+#[derive(Debug, Default)]
+pub struct LoadOrder {
+    /// Indices of the plugins which can be loaded, in the order they should be loaded.
+    pub order: Vec<usize>,
+    /// Plugins which can't be loaded because a hard dependency is missing or unresolvable,
+    /// along with the names of those dependencies.
+    pub missing: Vec<(usize, Vec<String>)>,
+    /// Plugins which can't be loaded because they are part of, or depend on, a dependency cycle.
+    pub cycles: Vec<usize>,
+}
+
+/// Topologically sorts the given plugins so that every plugin is loaded after its hard and
+/// soft dependencies.
+///
+/// Soft dependencies only affect the order and are dropped if they would otherwise form a cycle.
+/// Plugins keep their relative order when there are no dependencies between them.
+#[must_use]
+pub fn resolve_load_order(plugins: &[PluginMetadata]) -> LoadOrder {
+    let indices: HashMap<&str, usize> = plugins
+        .iter()
+        .enumerate()
+        .map(|(i, metadata)| (metadata.name, i))
+        .collect();
+
+    // Find plugins which can never be loaded, including those depending on such plugins
+    let mut unresolved: Vec<Option<Vec<String>>> = vec![None; plugins.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (i, metadata) in plugins.iter().enumerate() {
+            if unresolved[i].is_some() {
+                continue;
+            }
+            let missing: Vec<String> = metadata
+                .depends
+                .iter()
+                .filter(|dep| indices.get(*dep).is_none_or(|&j| unresolved[j].is_some()))
+                .map(ToString::to_string)
+                .collect();
+            if !missing.is_empty() {
+                unresolved[i] = Some(missing);
+                changed = true;
+            }
+        }
+    }
+
+    // Kahn's algorithm over the remaining plugins, `edges[dep]` holds (dependent, is_soft)
+    let mut edges: Vec<Vec<(usize, bool)>> = vec![Vec::new(); plugins.len()];
+    let mut in_degree = vec![0usize; plugins.len()];
+    for (i, metadata) in plugins.iter().enumerate() {
+        if unresolved[i].is_some() {
+            continue;
+        }
+        let hard = metadata.depends.iter().map(|dep| (dep, false));
+        let soft = metadata.soft_depends.iter().map(|dep| (dep, true));
+        for (dep, soft) in hard.chain(soft) {
+            if let Some(&j) = indices.get(dep) {
+                if j != i && unresolved[j].is_none() {
+                    edges[j].push((i, soft));
+                    in_degree[i] += 1;
+                }
+            }
+        }
+    }
+
+    let mut result = LoadOrder::default();
+    let mut visited = vec![false; plugins.len()];
+    let mut queue: VecDeque<usize> = (0..plugins.len())
+        .filter(|&i| unresolved[i].is_none() && in_degree[i] == 0)
+        .collect();
+    let mut dropped_soft = false;
+
+    loop {
+        while let Some(i) = queue.pop_front() {
+            visited[i] = true;
+            result.order.push(i);
+            for &(dependent, _) in &edges[i] {
+                in_degree[dependent] -= 1;
+                if in_degree[dependent] == 0 {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        if dropped_soft {
+            break;
+        }
+
+        // Try to break remaining cycles by ignoring soft dependencies between stuck plugins
+        dropped_soft = true;
+        for i in 0..plugins.len() {
+            if visited[i] || unresolved[i].is_some() {
+                continue;
+            }
+            for &(dependent, soft) in &edges[i] {
+                if soft {
+                    in_degree[dependent] -= 1;
+                }
+            }
+            edges[i].retain(|&(_, soft)| !soft);
+        }
+        queue.extend(
+            (0..plugins.len())
+                .filter(|&i| !visited[i] && unresolved[i].is_none() && in_degree[i] == 0),
+        );
+    }
+
+    for (i, missing) in unresolved.into_iter().enumerate() {
+        if let Some(missing) = missing {
+            result.missing.push((i, missing));
+        } else if !visited[i] {
+            result.cycles.push(i);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn metadata(
+        name: &'static str,
+        depends: &'static [&'static str],
+        soft_depends: &'static [&'static str],
+    ) -> PluginMetadata<'static> {
+        PluginMetadata {
+            name,
+            version: "1.0.0",
+            authors: "",
+            description: "",
+            website: "",
+            depends,
+            soft_depends,
+        }
+    }
+
+    #[test]
+    fn dependencies_load_first() {
+        let plugins = [
+            metadata("a", &["b"], &[]),
+            metadata("b", &[], &["c"]),
+            metadata("c", &[], &[]),
+        ];
+        let order = resolve_load_order(&plugins);
+        assert_eq!(order.order, vec![2, 1, 0]);
+        assert!(order.missing.is_empty());
+        assert!(order.cycles.is_empty());
+    }
+
+    #[test]
+    fn missing_dependencies_propagate() {
+        let plugins = [
+            metadata("a", &["b"], &[]),
+            metadata("b", &["missing"], &[]),
+            metadata("c", &[], &["missing"]),
+        ];
+        let order = resolve_load_order(&plugins);
+        assert_eq!(order.order, vec![2]);
+        assert_eq!(
+            order.missing,
+            vec![(0, vec!["b".to_string()]), (1, vec!["missing".to_string()])]
+        );
+    }
+
+    #[test]
+    fn cycles_are_reported() {
+        let plugins = [
+            metadata("a", &["b"], &[]),
+            metadata("b", &["a"], &[]),
+            metadata("c", &["a"], &[]),
+            metadata("d", &[], &[]),
+        ];
+        let order = resolve_load_order(&plugins);
+        assert_eq!(order.order, vec![3]);
+        assert_eq!(order.cycles, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn soft_cycles_are_broken() {
+        let plugins = [metadata("a", &["b"], &[]), metadata("b", &[], &["a"])];
+        let order = resolve_load_order(&plugins);
+        assert_eq!(order.order, vec![1, 0]);
+        assert!(order.cycles.is_empty());
+    }
+}
@@ -1,19 +1,73 @@
 pub mod api;
+pub mod dependency;
 
 pub use api::*;
 use async_trait::async_trait;
-use std::{collections::HashMap, fs, path::Path, sync::Arc};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt, fs,
+    path::Path,
+    sync::Arc,
+};
 use tokio::sync::RwLock;
 
 use crate::server::Server;
 use thiserror::Error;
 
-type PluginData = (
-    PluginMetadata<'static>,
-    Box<dyn Plugin>,
-    libloading::Library,
-    bool,
-);
+/// A plugin known to the `PluginManager`.
+struct PluginData {
+    metadata: PluginMetadata<'static>,
+    /// Creates a fresh instance of the plugin, used when reloading.
+    plugin_fn: fn() -> Box<dyn Plugin>,
+    plugin: Box<dyn Plugin>,
+    /// The context is kept for the whole lifetime of the plugin, as it tracks everything
+    /// the plugin registered.
+    context: Context,
+    state: PluginState,
+    /// Declared last, so it is dropped after everything the plugin created.
+    _library: libloading::Library,
+}
+
+/// The current state of a plugin known to the `PluginManager`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginState {
+    /// The plugin is loaded and running.
+    Loaded,
+    /// The plugin is known, but not loaded.
+    Unloaded,
+    /// The plugin's `on_load` hook returned an error.
+    Failed(String),
+    /// The plugin can't be loaded because these hard dependencies are missing or not loaded.
+    MissingDependencies(Vec<String>),
+    /// The plugin can't be loaded because it is part of, or depends on, a dependency cycle.
+    DependencyCycle,
+}
+
+impl fmt::Display for PluginState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Loaded => write!(f, "Loaded"),
+            Self::Unloaded => write!(f, "Unloaded"),
+            Self::Failed(err) => write!(f, "Failed to load: {err}"),
+            Self::MissingDependencies(missing) => {
+                write!(f, "Missing dependencies: {}", missing.join(", "))
+            }
+            Self::DependencyCycle => write!(f, "Dependency cycle"),
+        }
+    }
+}
+
+/// A snapshot of everything known about a plugin, e.g. for listing plugins in a dashboard.
+#[derive(Debug, Clone)]
+pub struct PluginInfo {
+    pub metadata: PluginMetadata<'static>,
+    pub state: PluginState,
+    /// Primary names of the commands the plugin has registered.
+    pub commands: Vec<String>,
+    /// Names of the events the plugin listens to.
+    pub events: Vec<&'static str>,
+}
 
 /// A trait for handling events dynamically.
 ///
@@ -43,6 +97,18 @@ pub trait DynEventHandler: Send + Sync {
     /// # Returns
     /// The priority of the event handler.
     fn get_priority(&self) -> EventPriority;
+
+    /// Checks if the event handler skips events which are already cancelled.
+    ///
+    /// # Returns
+    /// A boolean indicating whether cancelled events are skipped.
+    fn ignores_cancelled(&self) -> bool;
+
+    /// Retrieves the name of the plugin which registered the event handler.
+    ///
+    /// # Returns
+    /// The name of the plugin, or `None` if the handler was registered by the server.
+    fn get_plugin(&self) -> Option<&'static str>;
 }
 
 /// A trait for handling specific events.
@@ -69,7 +135,8 @@ pub trait EventHandler<E: Event>: Send + Sync {
 
 /// A struct representing a typed event handler.
 ///
-/// This struct holds a reference to an event handler, its priority, and whether it is blocking.
+/// This struct holds a reference to an event handler, its priority, whether it is blocking and
+/// whether it skips cancelled events.
 struct TypedEventHandler<E, H>
 where
     E: Event + Send + Sync + 'static,
@@ -78,6 +145,8 @@ where
     handler: Arc<H>,
     priority: EventPriority,
     blocking: bool,
+    ignore_cancelled: bool,
+    plugin: Option<&'static str>,
     _phantom: std::marker::PhantomData<E>,
 }
 
@@ -115,7 +184,17 @@ where
 
     /// Retrieves the priority of the handler.
     fn get_priority(&self) -> EventPriority {
-        self.priority.clone()
+        self.priority
+    }
+
+    /// Checks if the handler skips cancelled events.
+    fn ignores_cancelled(&self) -> bool {
+        self.ignore_cancelled
+    }
+
+    /// Retrieves the name of the plugin which registered the handler.
+    fn get_plugin(&self) -> Option<&'static str> {
+        self.plugin
     }
 }
 
@@ -123,11 +202,18 @@ where
 /// and the value is a vector of dynamic event handlers.
 pub type HandlerMap = HashMap<&'static str, Vec<Box<dyn DynEventHandler>>>;
 
+/// A type alias for a map of services provided by plugins, keyed by the type of the service.
+/// The value holds the name of the providing plugin and the service itself.
+pub type ServiceMap = HashMap<TypeId, (&'static str, Arc<dyn Any + Send + Sync>)>;
+
 /// A struct for managing plugins.
 pub struct PluginManager {
     plugins: Vec<PluginData>,
+    /// Plugin libraries which could not be opened, as file name and error.
+    failed_libraries: Vec<(String, String)>,
     server: Option<Arc<Server>>,
     handlers: Arc<RwLock<HandlerMap>>,
+    services: Arc<RwLock<ServiceMap>>,
 }
 
 impl Default for PluginManager {
@@ -146,8 +232,10 @@ impl PluginManager {
     pub fn new() -> Self {
         Self {
             plugins: vec![],
+            failed_libraries: vec![],
             server: None,
             handlers: Arc::new(RwLock::new(HashMap::new())),
+            services: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -161,6 +249,9 @@ impl PluginManager {
 
     /// Asynchronously loads plugins from the specified plugin directory.
     ///
+    /// All plugin libraries are opened first, then the plugins are loaded in dependency order.
+    /// Plugins with missing hard dependencies or dependency cycles are not loaded.
+    ///
     /// # Returns
     /// A result indicating success or failure. If it fails, it returns a `PluginsLoadError`.
     pub async fn load_plugins(&mut self) -> Result<(), PluginsLoadError> {
@@ -180,29 +271,32 @@ impl PluginManager {
                 continue;
             }
             let name = entry.file_name().into_string().unwrap();
-            if let Err(err) = self.try_load_plugin(&entry.path()).await {
+            if let Err(err) = self.try_open_plugin(&entry.path()) {
                 log::error!("Plugin {}: {}", name, err.to_string());
+                self.failed_libraries.push((name, err.to_string()));
             }
         }
 
+        self.load_in_dependency_order().await;
+
         Ok(())
     }
 
-    /// Tries to load a plugin from the specified path.
+    /// Tries to open a plugin library from the specified path without loading the plugin.
     ///
     /// # Arguments
-    /// - `path`: The path to the plugin to load.
+    /// - `path`: The path to the plugin to open.
     ///
     /// # Returns
     /// A result indicating success or failure. If it fails, it returns a `PluginLoadError`.
-    async fn try_load_plugin(&mut self, path: &Path) -> Result<(), PluginLoadError> {
+    fn try_open_plugin(&mut self, path: &Path) -> Result<(), PluginLoadError> {
         let library = unsafe {
             libloading::Library::new(path)
                 .map_err(|e| PluginLoadError::LoadLibrary(e.to_string()))?
         };
 
         let plugin_fn = unsafe {
-            library
+            *library
                 .get::<fn() -> Box<dyn Plugin>>(b"plugin")
                 .map_err(|_| PluginLoadError::GetPluginMain)?
         };
@@ -212,23 +306,83 @@ impl PluginManager {
                 .map_err(|_| PluginLoadError::GetPluginMeta)?
         };
 
-        // Create a context for the plugin.
+        if self
+            .plugins
+            .iter()
+            .any(|plugin| plugin.metadata.name == metadata.name)
+        {
+            return Err(PluginLoadError::Duplicate(metadata.name.to_string()));
+        }
+
         let context = Context::new(
             metadata.clone(),
             self.server.clone().expect("Server not set"),
             self.handlers.clone(),
+            self.services.clone(),
         );
-        let mut plugin_box = plugin_fn();
-        let res = plugin_box.on_load(&context).await;
-        let mut loaded = true;
-        if let Err(e) = res {
-            log::error!("Error loading plugin: {}", e);
-            loaded = false;
+        self.plugins.push(PluginData {
+            metadata: metadata.clone(),
+            plugin_fn,
+            plugin: plugin_fn(),
+            context,
+            state: PluginState::Unloaded,
+            _library: library,
+        });
+        Ok(())
+    }
+
+    /// Sorts the opened plugins topologically by their dependencies and loads them in that order.
+    async fn load_in_dependency_order(&mut self) {
+        let metadata: Vec<_> = self
+            .plugins
+            .iter()
+            .map(|plugin| plugin.metadata.clone())
+            .collect();
+        let load_order = dependency::resolve_load_order(&metadata);
+
+        for (index, missing) in load_order.missing {
+            log::error!(
+                "Plugin {} will not be loaded, missing dependencies: {}",
+                metadata[index].name,
+                missing.join(", ")
+            );
+            self.plugins[index].state = PluginState::MissingDependencies(missing);
         }
 
-        self.plugins
-            .push((metadata.clone(), plugin_box, library, loaded));
-        Ok(())
+        if !load_order.cycles.is_empty() {
+            let names: Vec<_> = load_order
+                .cycles
+                .iter()
+                .map(|&index| metadata[index].name)
+                .collect();
+            log::error!(
+                "Plugins {} will not be loaded, their dependencies form a cycle",
+                names.join(", ")
+            );
+            for &index in &load_order.cycles {
+                self.plugins[index].state = PluginState::DependencyCycle;
+            }
+        }
+
+        // Keep the plugins in load order, so they can be unloaded in reverse order
+        let mut slots: Vec<_> = std::mem::take(&mut self.plugins)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let mut plugins: Vec<_> = load_order
+            .order
+            .iter()
+            .filter_map(|&index| slots[index].take())
+            .collect();
+        plugins.extend(slots.into_iter().flatten());
+        self.plugins = plugins;
+
+        for index in 0..load_order.order.len() {
+            let name = self.plugins[index].metadata.name;
+            if let Err(err) = self.load_plugin(name).await {
+                log::error!("Error loading plugin {}: {}", name, err);
+            }
+        }
     }
 
     /// Checks if a plugin is loaded by its name.
@@ -242,82 +396,240 @@ impl PluginManager {
     pub fn is_plugin_loaded(&self, name: &str) -> bool {
         self.plugins
             .iter()
-            .any(|(metadata, _, _, loaded)| metadata.name == name && *loaded)
+            .any(|plugin| plugin.metadata.name == name && plugin.state == PluginState::Loaded)
+    }
+
+    /// Retrieves the names of all loaded plugins which have a hard dependency on the given plugin.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the dependency.
+    ///
+    /// # Returns
+    /// A vector of the names of the loaded dependents.
+    #[must_use]
+    pub fn loaded_dependents(&self, name: &str) -> Vec<&'static str> {
+        self.plugins
+            .iter()
+            .filter(|plugin| {
+                plugin.state == PluginState::Loaded
+                    && plugin.metadata.depends.iter().any(|dep| *dep == name)
+            })
+            .map(|plugin| plugin.metadata.name)
+            .collect()
     }
 
     /// Asynchronously loads a plugin by its name.
     ///
+    /// The plugin is only loaded if all of its hard dependencies are loaded. If loading fails,
+    /// everything the plugin registered so far is removed again.
+    ///
     /// # Arguments
     /// - `name`: The name of the plugin to load.
     ///
     /// # Returns
     /// A result indicating success or failure. If it fails, it returns an error message.
     pub async fn load_plugin(&mut self, name: &str) -> Result<(), String> {
-        let plugin = self
+        let Some(index) = self
             .plugins
-            .iter_mut()
-            .find(|(metadata, _, _, _)| metadata.name == name);
+            .iter()
+            .position(|plugin| plugin.metadata.name == name)
+        else {
+            return Err(format!("Plugin {name} not found"));
+        };
 
-        if let Some((metadata, plugin, _, loaded)) = plugin {
-            if *loaded {
-                return Err(format!("Plugin {name} is already loaded"));
-            }
+        let missing: Vec<String> = self.plugins[index]
+            .metadata
+            .depends
+            .iter()
+            .filter(|dep| !self.is_plugin_loaded(dep))
+            .map(ToString::to_string)
+            .collect();
 
-            let context = Context::new(
-                metadata.clone(),
-                self.server.clone().expect("Server not set"),
-                self.handlers.clone(),
-            );
-            let res = plugin.on_load(&context).await;
-            res?;
-            *loaded = true;
-            Ok(())
-        } else {
-            Err(format!("Plugin {name} not found"))
+        let plugin = &mut self.plugins[index];
+        if plugin.state == PluginState::Loaded {
+            return Err(format!("Plugin {name} is already loaded"));
+        }
+        if !missing.is_empty() {
+            let err = format!("Missing dependencies: {}", missing.join(", "));
+            plugin.state = PluginState::MissingDependencies(missing);
+            return Err(err);
         }
+
+        if let Err(err) = plugin.plugin.on_load(&plugin.context).await {
+            plugin.context.teardown().await;
+            plugin.state = PluginState::Failed(err.clone());
+            return Err(err);
+        }
+        plugin.state = PluginState::Loaded;
+        Ok(())
     }
 
     /// Asynchronously unloads a plugin by its name.
     ///
+    /// A plugin can't be unloaded while other loaded plugins depend on it. Everything the plugin
+    /// registered is removed, even if its `on_unload` hook fails.
+    ///
     /// # Arguments
     /// - `name`: The name of the plugin to unload.
     ///
     /// # Returns
     /// A result indicating success or failure. If it fails, it returns an error message.
     pub async fn unload_plugin(&mut self, name: &str) -> Result<(), String> {
-        let plugin = self
+        let dependents = self.loaded_dependents(name);
+        if !dependents.is_empty() {
+            return Err(format!(
+                "Plugin {name} is required by {}",
+                dependents.join(", ")
+            ));
+        }
+
+        let Some(plugin) = self
             .plugins
             .iter_mut()
-            .find(|(metadata, _, _, _)| metadata.name == name);
+            .find(|plugin| plugin.metadata.name == name)
+        else {
+            return Err(format!("Plugin {name} not found"));
+        };
 
-        if let Some((metadata, plugin, _, loaded)) = plugin {
-            let context = Context::new(
-                metadata.clone(),
-                self.server.clone().expect("Server not set"),
-                self.handlers.clone(),
-            );
-            let res = plugin.on_unload(&context).await;
-            res?;
-            *loaded = false;
-            Ok(())
-        } else {
-            Err(format!("Plugin {name} not found"))
+        let res = plugin.plugin.on_unload(&plugin.context).await;
+        plugin.context.teardown().await;
+        plugin.state = PluginState::Unloaded;
+        res
+    }
+
+    /// Asynchronously reloads a plugin by its name.
+    ///
+    /// The plugin is unloaded and a fresh instance is created and loaded. Loaded plugins which
+    /// depend on it are unloaded beforehand and loaded again afterwards, each as a fresh instance
+    /// too.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the plugin to reload.
+    ///
+    /// # Returns
+    /// A result indicating success or failure. If it fails, it returns an error message.
+    pub async fn reload_plugin(&mut self, name: &str) -> Result<(), String> {
+        if !self.is_plugin_loaded(name) {
+            return Err(format!("Plugin {name} is not loaded"));
+        }
+
+        // Collect all loaded plugins transitively depending on this plugin
+        let mut affected = vec![name.to_string()];
+        let mut i = 0;
+        while i < affected.len() {
+            for dependent in self.loaded_dependents(&affected[i]) {
+                if !affected.iter().any(|name| name == dependent) {
+                    affected.push(dependent.to_string());
+                }
+            }
+            i += 1;
+        }
+
+        // Plugins are kept in load order, unload dependents first
+        let affected: Vec<&'static str> = self
+            .plugins
+            .iter()
+            .map(|plugin| plugin.metadata.name)
+            .filter(|plugin| affected.iter().any(|name| name == plugin))
+            .collect();
+        for &plugin in affected.iter().rev() {
+            if let Err(err) = self.unload_plugin(plugin).await {
+                log::error!("Error unloading plugin {}: {}", plugin, err);
+            }
+        }
+
+        for plugin in self
+            .plugins
+            .iter_mut()
+            .filter(|plugin| affected.contains(&plugin.metadata.name))
+        {
+            plugin.plugin = (plugin.plugin_fn)();
+        }
+
+        let mut result = Ok(());
+        for &plugin in &affected {
+            if let Err(err) = self.load_plugin(plugin).await {
+                log::error!("Error loading plugin {}: {}", plugin, err);
+                if result.is_ok() {
+                    result = Err(format!("Failed to load plugin {plugin}: {err}"));
+                }
+            }
+        }
+        result
+    }
+
+    /// Asynchronously unloads all loaded plugins, unloading dependents before their dependencies.
+    pub async fn unload_all(&mut self) {
+        // Plugins are kept in load order, so walking them backwards unloads dependents first
+        for index in (0..self.plugins.len()).rev() {
+            let plugin = &self.plugins[index];
+            if plugin.state != PluginState::Loaded {
+                continue;
+            }
+            let name = plugin.metadata.name;
+            if let Err(err) = self.unload_plugin(name).await {
+                log::error!("Error unloading plugin {}: {}", name, err);
+            }
         }
     }
 
-    /// Lists all plugins along with their loaded status.
+    /// Lists all plugins along with their state.
     ///
     /// # Returns
-    /// A vector of tuples containing references to the plugin metadata and a boolean indicating
-    /// whether each plugin is loaded.
+    /// A vector of tuples containing references to the plugin metadata and the state of each plugin.
     #[must_use]
-    pub fn list_plugins(&self) -> Vec<(&PluginMetadata, &bool)> {
+    pub fn list_plugins(&self) -> Vec<(&PluginMetadata, &PluginState)> {
         self.plugins
             .iter()
-            .map(|(metadata, _, _, loaded)| (metadata, loaded))
+            .map(|plugin| (&plugin.metadata, &plugin.state))
             .collect()
     }
 
+    /// Lists the plugin libraries which could not be opened.
+    ///
+    /// # Returns
+    /// A slice of tuples containing the file name of the library and the error.
+    #[must_use]
+    pub fn list_failed_libraries(&self) -> &[(String, String)] {
+        &self.failed_libraries
+    }
+
+    /// Asynchronously collects information about all plugins, including what they registered.
+    ///
+    /// # Returns
+    /// A vector with the information of each plugin, in load order.
+    pub async fn plugin_infos(&self) -> Vec<PluginInfo> {
+        let mut infos = Vec::with_capacity(self.plugins.len());
+        for plugin in &self.plugins {
+            infos.push(Self::plugin_info_of(plugin).await);
+        }
+        infos
+    }
+
+    /// Asynchronously collects information about a plugin by its name.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the plugin.
+    ///
+    /// # Returns
+    /// The information of the plugin, or `None` if it is not known.
+    pub async fn plugin_info(&self, name: &str) -> Option<PluginInfo> {
+        let plugin = self
+            .plugins
+            .iter()
+            .find(|plugin| plugin.metadata.name == name)?;
+        Some(Self::plugin_info_of(plugin).await)
+    }
+
+    async fn plugin_info_of(plugin: &PluginData) -> PluginInfo {
+        PluginInfo {
+            metadata: plugin.metadata.clone(),
+            state: plugin.state.clone(),
+            commands: plugin.context.get_registered_commands().await,
+            events: plugin.context.get_registered_events().await,
+        }
+    }
+
     /// Asynchronously registers an event handler for a specific event type.
     ///
     /// # Type Parameters
@@ -328,6 +640,7 @@ impl PluginManager {
     /// - `handler`: A reference to the event handler.
     /// - `priority`: The priority of the event handler.
     /// - `blocking`: A boolean indicating whether the handler is blocking.
+    /// - `ignore_cancelled`: A boolean indicating whether the handler skips cancelled events.
     ///
     /// # Constraints
     /// The handler must implement the `EventHandler<E>` trait.
@@ -336,6 +649,7 @@ impl PluginManager {
         handler: Arc<H>,
         priority: EventPriority,
         blocking: bool,
+        ignore_cancelled: bool,
     ) where
         H: EventHandler<E> + 'static,
     {
@@ -349,6 +663,8 @@ impl PluginManager {
             handler,
             priority,
             blocking,
+            ignore_cancelled,
+            plugin: None,
             _phantom: std::marker::PhantomData,
         };
 
@@ -357,6 +673,8 @@ impl PluginManager {
 
     /// Asynchronously fires an event, invoking all registered handlers for that event type.
     ///
+    /// Handlers are called by priority, and handlers with the same priority in plugin load order.
+    ///
     /// # Type Parameters
     /// - `E`: The event type to fire.
     ///
@@ -378,25 +696,51 @@ impl PluginManager {
                 E::get_name_static()
             );
 
-            let (blocking_handlers, non_blocking_handlers): (Vec<_>, Vec<_>) = handlers_vec
+            let load_order: Vec<&str> = self
+                .plugins
                 .iter()
-                .partition(|handler| handler.is_blocking());
-
-            // Handle blocking handlers first
-            for handler in blocking_handlers {
-                handler.handle_blocking_dyn(&mut event).await;
-            }
-
-            // TODO: Run non-blocking handlers in parallel
-            for handler in non_blocking_handlers {
-                handler.handle_dyn(&event).await;
-            }
+                .map(|plugin| plugin.metadata.name)
+                .collect();
+            dispatch(handlers_vec, &load_order, &mut event).await;
         }
 
         event
     }
 }
 
+/// Calls the handlers of an event from `Lowest` to `Highest` priority, then the `Monitor` handlers.
+///
+/// Handlers with the same priority are called in the order of `load_order`, after the handlers
+/// registered by the server. Handlers of the same plugin are called in registration order.
+async fn dispatch(
+    handlers: &[Box<dyn DynEventHandler>],
+    load_order: &[&str],
+    event: &mut (dyn Event + Send + Sync),
+) {
+    let load_position = |plugin: Option<&str>| match plugin {
+        None => 0,
+        Some(plugin) => load_order
+            .iter()
+            .position(|name| *name == plugin)
+            .map_or(usize::MAX, |position| position + 1),
+    };
+    let mut ordered: Vec<&dyn DynEventHandler> = handlers.iter().map(AsRef::as_ref).collect();
+    // The sort is stable, which keeps the registration order
+    ordered.sort_by_key(|handler| (handler.get_priority(), load_position(handler.get_plugin())));
+
+    for handler in ordered {
+        if handler.ignores_cancelled() && event.as_cancellable().is_some_and(Cancellable::cancelled)
+        {
+            continue;
+        }
+        if handler.is_blocking() && handler.get_priority() != EventPriority::Monitor {
+            handler.handle_blocking_dyn(event).await;
+        } else {
+            handler.handle_dyn(event).await;
+        }
+    }
+}
+
 /// Error when failed to load the entire Plugin directory
 #[derive(Error, Debug)]
 pub enum PluginsLoadError {
@@ -417,4 +761,209 @@ pub enum PluginLoadError {
     GetPluginMain,
     #[error("Failed to load Plugin Metadata")]
     GetPluginMeta,
+    #[error("A Plugin named {0} is already loaded")]
+    Duplicate(String),
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use pumpkin_macros::{cancellable, Event};
+
+    use super::*;
+
+    #[cancellable]
+    #[derive(Event, Default)]
+    struct TestEvent {
+        /// The handlers which have seen the event, in call order
+        calls: Vec<&'static str>,
+    }
+
+    /// Records the calls of all handlers, including the non-blocking ones
+    type CallLog = Arc<Mutex<Vec<(&'static str, bool)>>>;
+
+    /// A handler of a dummy plugin which records its calls and optionally cancels the event
+    struct RecordingHandler {
+        name: &'static str,
+        cancel: bool,
+        log: CallLog,
+    }
+
+    #[async_trait]
+    impl EventHandler<TestEvent> for RecordingHandler {
+        async fn handle(&self, event: &TestEvent) {
+            self.log.lock().unwrap().push((self.name, event.cancelled));
+        }
+
+        async fn handle_blocking(&self, event: &mut TestEvent) {
+            self.log.lock().unwrap().push((self.name, event.cancelled));
+            event.calls.push(self.name);
+            if self.cancel {
+                event.cancelled = true;
+            }
+        }
+    }
+
+    struct Registration {
+        plugin: Option<&'static str>,
+        name: &'static str,
+        priority: EventPriority,
+        blocking: bool,
+        ignore_cancelled: bool,
+        cancel: bool,
+    }
+
+    impl Registration {
+        fn new(plugin: &'static str, name: &'static str, priority: EventPriority) -> Self {
+            Self {
+                plugin: Some(plugin),
+                name,
+                priority,
+                blocking: true,
+                ignore_cancelled: false,
+                cancel: false,
+            }
+        }
+    }
+
+    async fn fire(
+        registrations: Vec<Registration>,
+        load_order: &[&str],
+    ) -> (TestEvent, Vec<(&'static str, bool)>) {
+        let log = CallLog::default();
+        let handlers: Vec<Box<dyn DynEventHandler>> = registrations
+            .into_iter()
+            .map(|registration| {
+                Box::new(TypedEventHandler {
+                    handler: Arc::new(RecordingHandler {
+                        name: registration.name,
+                        cancel: registration.cancel,
+                        log: log.clone(),
+                    }),
+                    priority: registration.priority,
+                    blocking: registration.blocking,
+                    ignore_cancelled: registration.ignore_cancelled,
+                    plugin: registration.plugin,
+                    _phantom: std::marker::PhantomData,
+                }) as Box<dyn DynEventHandler>
+            })
+            .collect();
+
+        let mut event = TestEvent::default();
+        dispatch(&handlers, load_order, &mut event).await;
+        let calls = log.lock().unwrap().clone();
+        (event, calls)
+    }
+
+    #[tokio::test]
+    async fn handlers_are_called_by_priority() {
+        let (event, _) = fire(
+            vec![
+                Registration::new("cosmetic", "monitor", EventPriority::Monitor),
+                Registration::new("cosmetic", "highest", EventPriority::Highest),
+                Registration::new("cosmetic", "normal", EventPriority::Normal),
+                Registration::new("protection", "lowest", EventPriority::Lowest),
+                Registration::new("protection", "high", EventPriority::High),
+                Registration::new("protection", "low", EventPriority::Low),
+            ],
+            &["cosmetic", "protection"],
+        )
+        .await;
+        // The monitor handler can not change the event
+        assert_eq!(event.calls, ["lowest", "low", "normal", "high", "highest"]);
+    }
+
+    #[tokio::test]
+    async fn same_priority_follows_load_order() {
+        let registrations = || {
+            vec![
+                Registration::new("economy", "economy", EventPriority::Normal),
+                Registration::new("cosmetic", "cosmetic 1", EventPriority::Normal),
+                Registration {
+                    plugin: None,
+                    ..Registration::new("", "server", EventPriority::Normal)
+                },
+                Registration::new("protection", "protection", EventPriority::Normal),
+                Registration::new("cosmetic", "cosmetic 2", EventPriority::Normal),
+            ]
+        };
+
+        let (event, _) = fire(registrations(), &["protection", "cosmetic", "economy"]).await;
+        assert_eq!(
+            event.calls,
+            [
+                "server",
+                "protection",
+                "cosmetic 1",
+                "cosmetic 2",
+                "economy"
+            ]
+        );
+
+        let (event, _) = fire(registrations(), &["economy", "cosmetic", "protection"]).await;
+        assert_eq!(
+            event.calls,
+            [
+                "server",
+                "economy",
+                "cosmetic 1",
+                "cosmetic 2",
+                "protection"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelled_events_are_skipped_if_requested() {
+        let (event, calls) = fire(
+            vec![
+                Registration {
+                    cancel: true,
+                    ..Registration::new("protection", "protection", EventPriority::Low)
+                },
+                Registration {
+                    ignore_cancelled: true,
+                    ..Registration::new("cosmetic", "cosmetic", EventPriority::Normal)
+                },
+                Registration::new("logger", "logger", EventPriority::High),
+                Registration {
+                    blocking: false,
+                    ignore_cancelled: true,
+                    ..Registration::new("stats", "stats", EventPriority::Monitor)
+                },
+                Registration {
+                    blocking: false,
+                    ..Registration::new("audit", "audit", EventPriority::Monitor)
+                },
+            ],
+            &["protection", "cosmetic", "logger", "stats", "audit"],
+        )
+        .await;
+        assert!(event.cancelled);
+        assert_eq!(event.calls, ["protection", "logger"]);
+        assert_eq!(
+            calls,
+            [("protection", false), ("logger", true), ("audit", true)]
+        );
+    }
+
+    #[tokio::test]
+    async fn monitor_handlers_can_not_change_the_event() {
+        let (event, calls) = fire(
+            vec![
+                // Registered as blocking, but still only observes the event
+                Registration {
+                    cancel: true,
+                    ..Registration::new("audit", "audit", EventPriority::Monitor)
+                },
+                Registration::new("protection", "protection", EventPriority::Highest),
+            ],
+            &["audit", "protection"],
+        )
+        .await;
+        assert!(!event.cancelled);
+        assert_eq!(event.calls, ["protection"]);
+        assert_eq!(calls, [("protection", false), ("audit", false)]);
+    }
 }
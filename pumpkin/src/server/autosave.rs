@@ -0,0 +1,123 @@
+//! Saves the worlds every `autosave_interval` ticks, like vanilla. Instead of writing every changed
+//! chunk at once, which would stall the tick, the chunks which changed are written a few each
+//! tick, spread over the interval until the next save. The level data and player data are saved
+//! once all of them were written.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::math::vector2::Vector2;
+use tokio::sync::Mutex;
+
+use crate::world::World;
+
+use super::Server;
+
+#[derive(Default)]
+pub struct AutoSave {
+    /// Whether saving is turned off with `/save-off`
+    paused: AtomicBool,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Ticks since the last save started
+    ticks: u64,
+    /// The changed chunks which still have to be written in the running save
+    pending: VecDeque<(Arc<World>, Vector2<i32>)>,
+    /// When the running save started, if there is one
+    started: Option<Instant>,
+    /// How many chunks the running save wrote so far
+    written: usize,
+}
+
+impl AutoSave {
+    /// Counts the tick, starts a save once the interval passed and writes the next chunks of the
+    /// running save
+    pub async fn tick(&self, server: &Server) {
+        let interval = ADVANCED_CONFIG.world.autosave_interval;
+        if interval == 0 || self.is_paused() {
+            return;
+        }
+
+        let mut state = self.state.lock().await;
+        state.ticks += 1;
+        if state.started.is_none() {
+            if state.ticks < interval {
+                return;
+            }
+            state.ticks = 0;
+            state.started = Some(Instant::now());
+            state.written = 0;
+            for world in server.worlds.read().await.iter() {
+                for chunk in world.level.dirty_chunks().await {
+                    state.pending.push_back((world.clone(), chunk));
+                }
+            }
+            log::debug!("Saving the worlds, {} chunks changed", state.pending.len());
+        }
+
+        // Spread the chunks over the rest of the interval, so they are written before the next
+        // save starts
+        let ticks_left =
+            usize::try_from(interval.saturating_sub(state.ticks).max(1)).unwrap_or(usize::MAX);
+        let count = state
+            .pending
+            .len()
+            .div_ceil(ticks_left)
+            .min(ADVANCED_CONFIG.world.autosave_max_chunks_per_tick.max(1));
+        for _ in 0..count {
+            let Some((world, chunk)) = state.pending.pop_front() else {
+                break;
+            };
+            // Chunks which were unloaded in the meantime were already written when unloading
+            if world.level.save_chunk(&chunk).is_some() {
+                state.written += 1;
+            }
+        }
+
+        if state.pending.is_empty() {
+            let started = state.started.take();
+            let written = state.written;
+            drop(state);
+            for world in server.worlds.read().await.iter() {
+                world.save_data(server).await;
+            }
+            server.save_player_data().await;
+            if let Some(started) = started {
+                log::info!(
+                    "Autosave completed, saved {written} chunks in {:.1}s",
+                    started.elapsed().as_secs_f32()
+                );
+            }
+        }
+    }
+
+    /// Turns the periodic saves off or on, see [`Server::set_saving_paused`]. Returns whether it
+    /// changed.
+    pub fn set_paused(&self, paused: bool) -> bool {
+        self.paused.swap(paused, Ordering::Relaxed) != paused
+    }
+
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Drops the running save after everything was saved at once, see [`Server::save`]. The
+    /// next save starts after a full interval.
+    pub async fn reset(&self) {
+        let mut state = self.state.lock().await;
+        state.ticks = 0;
+        state.pending.clear();
+        state.started = None;
+    }
+}
@@ -14,7 +14,9 @@ use pumpkin_protocol::{
     Players, StatusResponse, Version, CURRENT_MC_PROTOCOL,
 };
 
-use super::CURRENT_MC_VERSION;
+use pumpkin_util::text::TextComponent;
+
+use super::{motd, CURRENT_MC_VERSION};
 
 const DEFAULT_ICON: &[u8] = include_bytes!("../../../assets/default_icon.png");
 
@@ -40,6 +42,8 @@ fn load_icon_from_bytes(png_data: &[u8]) -> Result<String, Box<dyn error::Error>
 }
 
 pub struct CachedStatus {
+    /// The MOTD before placeholders are resolved, which can be changed at runtime
+    motd: String,
     status_response: StatusResponse,
     // We cache the json response here so we don't parse it every time someone makes a Status request.
     // Keep in mind that we must parse this again, when the StatusResponse changes which usually happen when a player joins or leaves
@@ -79,6 +83,7 @@ impl CachedStatus {
             .expect("Failed to parse Status response into JSON");
 
         Self {
+            motd: BASIC_CONFIG.motd.clone(),
             status_response,
             status_response_json,
         }
@@ -109,6 +114,38 @@ impl CachedStatus {
             .expect("Failed to parse Status response into JSON");
     }
 
+    pub fn set_max_players(&mut self, max_players: u32) {
+        let status_response = &mut self.status_response;
+        if let Some(players) = &mut status_response.players {
+            players.max = max_players;
+        }
+
+        self.status_response_json = serde_json::to_string(&status_response)
+            .expect("Failed to parse Status response into JSON");
+    }
+
+    /// The MOTD as configured, either plain text or a JSON text component.
+    #[must_use]
+    pub fn motd(&self) -> &str {
+        &self.motd
+    }
+
+    /// Changes the MOTD used for subsequent status requests.
+    pub fn set_motd(&mut self, motd: String) {
+        self.motd = motd;
+    }
+
+    /// Replaces the description shown in the server list, e.g. after resolving placeholders.
+    pub fn set_description(&mut self, description: TextComponent) {
+        if self.status_response.description == description {
+            return;
+        }
+        self.status_response.description = description;
+
+        self.status_response_json = serde_json::to_string(&self.status_response)
+            .expect("Failed to parse Status response into JSON");
+    }
+
     pub fn build_response(config: &BasicConfiguration) -> StatusResponse {
         let favicon = if config.use_favicon {
             let icon_path = &config.favicon_path;
@@ -154,7 +191,7 @@ impl CachedStatus {
                 online: 0,
                 sample: vec![],
             }),
-            description: config.motd.clone(),
+            description: motd::parse(&config.motd),
             favicon,
             enforce_secure_chat: false,
         }
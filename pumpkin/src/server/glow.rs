@@ -0,0 +1,97 @@
+//! Glowing outlines applied through `/glow`, which can be colored and removed after a while.
+
+use std::{collections::HashMap, sync::Arc};
+
+use pumpkin_util::text::color::NamedColor;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{entity::player::Player, plugin::api::scoreboard::Team};
+
+use super::Server;
+
+#[derive(Default)]
+pub struct Glowing {
+    /// The name and remaining ticks of players whose glow is removed automatically
+    timers: Mutex<HashMap<Uuid, (String, u32)>>,
+    /// The outline takes the color of the entity's team, so there is one team per color
+    teams: Mutex<HashMap<NamedColor, Arc<Team>>>,
+}
+
+impl Glowing {
+    /// Makes a player glow, optionally in a color and for a number of ticks. Glowing again
+    /// replaces the previous color and duration.
+    pub async fn set(
+        &self,
+        server: &Server,
+        player: &Player,
+        color: Option<NamedColor>,
+        ticks: Option<u32>,
+    ) {
+        let name = &player.gameprofile.name;
+        self.leave_teams(server, name).await;
+        if let Some(color) = color {
+            self.team(server, color)
+                .await
+                .add_member(server, name)
+                .await;
+        }
+
+        let mut timers = self.timers.lock().await;
+        match ticks {
+            Some(ticks) => timers.insert(player.gameprofile.id, (name.clone(), ticks)),
+            None => timers.remove(&player.gameprofile.id),
+        };
+        drop(timers);
+
+        player.living_entity.entity.set_glowing(true).await;
+    }
+
+    /// Removes the glow and its color from a player.
+    pub async fn remove(&self, server: &Server, player: &Player) {
+        self.timers.lock().await.remove(&player.gameprofile.id);
+        self.leave_teams(server, &player.gameprofile.name).await;
+        player.living_entity.entity.set_glowing(false).await;
+    }
+
+    /// Counts down the timers and removes the glow once they run out.
+    pub async fn tick(&self, server: &Server) {
+        let mut expired = Vec::new();
+        self.timers.lock().await.retain(|uuid, (name, ticks)| {
+            *ticks = ticks.saturating_sub(1);
+            if *ticks == 0 {
+                expired.push((*uuid, name.clone()));
+            }
+            *ticks > 0
+        });
+
+        for (uuid, name) in expired {
+            // The player may have left in the meantime, but still has to leave the team
+            self.leave_teams(server, &name).await;
+            if let Some(player) = server.get_player_by_uuid(uuid).await {
+                player.living_entity.entity.set_glowing(false).await;
+            }
+        }
+    }
+
+    async fn team(&self, server: &Server, color: NamedColor) -> Arc<Team> {
+        let mut teams = self.teams.lock().await;
+        if let Some(team) = teams.get(&color) {
+            return team.clone();
+        }
+        let team = Arc::new(Team::new(format!("glow_{color:?}").to_lowercase()));
+        server
+            .plugin_scoreboard
+            .add_team(server, team.clone())
+            .await;
+        team.set_color(server, Some(color)).await;
+        teams.insert(color, team.clone());
+        team
+    }
+
+    async fn leave_teams(&self, server: &Server, name: &str) {
+        for team in self.teams.lock().await.values() {
+            team.remove_member(server, name).await;
+        }
+    }
+}
@@ -1,23 +1,24 @@
 use connection_cache::{CachedBranding, CachedStatus};
 use crossbeam::atomic::AtomicCell;
 use key_store::KeyStore;
-use pumpkin_config::{ADVANCED_CONFIG, BASIC_CONFIG};
+use pumpkin_config::{NamedWorldConfig, WorldEnvironment, ADVANCED_CONFIG, BASIC_CONFIG};
 use pumpkin_data::entity::EntityType;
 use pumpkin_inventory::drag_handler::DragHandler;
 use pumpkin_inventory::{Container, OpenContainer};
 use pumpkin_protocol::client::login::CEncryptionRequest;
 use pumpkin_protocol::{client::config::CPluginMessage, ClientPacket};
 use pumpkin_registry::{DimensionType, Registry};
-use pumpkin_util::math::boundingbox::{BoundingBox, EntityDimensions};
 use pumpkin_util::math::position::BlockPos;
-use pumpkin_util::math::vector2::Vector2;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::text::TextComponent;
 use pumpkin_world::block::registry::Block;
 use pumpkin_world::dimension::Dimension;
+use pumpkin_world::generation::Seed;
+use pumpkin_world::level::Level;
 use rand::prelude::SliceRandom;
 use std::collections::HashMap;
 use std::net::IpAddr;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicU32;
 use std::{
     sync::{
@@ -31,23 +32,49 @@ use tokio::sync::{Mutex, RwLock};
 use crate::block::default_block_properties_manager;
 use crate::block::properties::BlockPropertiesManager;
 use crate::block::registry::BlockRegistry;
+use crate::data::{
+    first_join_data::JOINED_PLAYER_LIST, playtime_data::PLAYTIME_LIST, SaveJSONConfiguration,
+};
 use crate::entity::{Entity, EntityId};
 use crate::item::registry::ItemRegistry;
 use crate::net::EncryptionError;
+use crate::plugin::api::bossbar::BossBar;
+use crate::plugin::api::placeholder;
+use crate::plugin::api::scoreboard::PluginScoreboard;
 use crate::world::custom_bossbar::CustomBossbars;
+use crate::world::predicate::Predicates;
 use crate::{
-    command::{default_dispatcher, dispatcher::CommandDispatcher},
+    command::{
+        builtin_dispatcher, client_suggestions, default_dispatcher, dispatcher::CommandDispatcher,
+        tree::CommandTree, CommandSender,
+    },
     entity::player::Player,
     net::Client,
     world::World,
 };
+use autosave::AutoSave;
+use glow::Glowing;
+use teleport_requests::TeleportRequests;
+use teleport_warmups::TeleportWarmups;
 
+pub mod autosave;
 mod connection_cache;
+pub mod glow;
 mod key_store;
+pub mod motd;
+pub mod teleport_requests;
+pub mod teleport_warmups;
 pub mod ticker;
 
 pub const CURRENT_MC_VERSION: &str = "1.21.4";
 
+/// A change to the registered commands, applied at the next tick.
+enum CommandChange {
+    Toggle(String, bool),
+    Register(CommandTree, String),
+    Unregister(String),
+}
+
 /// Represents a Minecraft server instance.
 pub struct Server {
     /// Handles cryptographic keys for secure communication.
@@ -58,6 +85,8 @@ pub struct Server {
     server_branding: CachedBranding,
     /// Saves and Dispatches commands to appropriate handlers.
     pub command_dispatcher: RwLock<CommandDispatcher>,
+    /// Changes to the registered commands to apply at the next tick, see [`Server::toggle_command`]
+    command_changes: Mutex<Vec<CommandChange>>,
     /// Block Behaviour
     pub block_registry: Arc<BlockRegistry>,
     /// Item Behaviour
@@ -82,12 +111,44 @@ pub struct Server {
     pub auth_client: Option<reqwest::Client>,
     /// The server's custom bossbars
     pub bossbars: Mutex<CustomBossbars>,
+    /// Plugin messaging channels registered by plugins, mapped to the name of the registering plugin.
+    pub plugin_channels: RwLock<HashMap<String, &'static str>>,
+    /// Global sidebars and teams created by plugins.
+    pub plugin_scoreboard: PluginScoreboard,
+    /// Boss bars created by plugins, which are updated every tick.
+    pub plugin_bossbars: RwLock<Vec<Arc<BossBar>>>,
+    /// Players glowing through `/glow`.
+    pub glowing: Glowing,
+    /// Pending `/tpa` and `/tpahere` requests.
+    pub teleport_requests: TeleportRequests,
+    /// Players waiting to be teleported by commands like `/home`.
+    pub teleport_warmups: TeleportWarmups,
+    /// The predicates of the datapacks of the world.
+    pub predicates: Predicates,
+    /// Saves the worlds in the background every `autosave_interval` ticks.
+    pub autosave: AutoSave,
+    /// The ticks per second measured over the last second.
+    pub tps: AtomicCell<f32>,
+    /// The maximum number of players, which can be changed at runtime. `0` disables the limit.
+    max_players: AtomicU32,
+    /// The spawn protection radius, which can be changed at runtime. `0` disables it.
+    spawn_protection: AtomicU32,
+    /// How many percent of the players have to sleep to skip the night, which can be changed at
+    /// runtime
+    players_sleeping_percentage: AtomicU32,
 }
 
 impl Server {
     #[allow(clippy::new_without_default)]
     #[must_use]
     pub fn new() -> Self {
+        // TODO: load form config
+        Self::with_world_folder("./world".parse().unwrap())
+    }
+
+    /// Creates a server which loads its worlds and their datapacks from the folder
+    #[must_use]
+    pub fn with_world_folder(world_folder: PathBuf) -> Self {
         let auth_client = BASIC_CONFIG.online_mode.then(|| {
             reqwest::Client::builder()
                 .connect_timeout(Duration::from_millis(u64::from(
@@ -103,17 +164,36 @@ impl Server {
         // First register default command, after that plugins can put in their own
         let command_dispatcher = RwLock::new(default_dispatcher());
 
+        let predicates = Predicates::load(&world_folder);
         let world = World::load(
-            Dimension::OverWorld.into_level(
-                // TODO: load form config
-                "./world".parse().unwrap(),
-            ),
+            Dimension::OverWorld.into_level(world_folder.clone()),
             DimensionType::Overworld,
         );
 
-        // Spawn chunks are never unloaded
-        for chunk in Self::spawn_chunks() {
-            world.level.mark_chunk_as_newly_watched(chunk);
+        let mut worlds = vec![Arc::new(world)];
+        if BASIC_CONFIG.allow_nether {
+            worlds.push(Arc::new(World::load(
+                Dimension::Nether.into_level(world_folder.clone()),
+                DimensionType::TheNether,
+            )));
+        }
+        if BASIC_CONFIG.allow_end {
+            worlds.push(Arc::new(World::load(
+                Dimension::End.into_level(world_folder),
+                DimensionType::TheEnd,
+            )));
+        }
+        // Sorted, so the worlds are always in the same order
+        let mut named_worlds: Vec<_> = ADVANCED_CONFIG.world.worlds.iter().collect();
+        named_worlds.sort_by_key(|(name, _)| *name);
+        for (name, config) in named_worlds {
+            if worlds.iter().any(|world| world.name() == *name) {
+                log::warn!(
+                    "There is already a world called {name}, not loading it from the config"
+                );
+                continue;
+            }
+            worlds.push(Arc::new(Self::load_named_world(name, config)));
         }
 
         Self {
@@ -123,7 +203,7 @@ impl Server {
             // 0 is invalid
             entity_id: 2.into(),
             container_id: 0.into(),
-            worlds: RwLock::new(vec![Arc::new(world)]),
+            worlds: RwLock::new(worlds),
             dimensions: vec![
                 DimensionType::Overworld,
                 DimensionType::OverworldCaves,
@@ -131,6 +211,7 @@ impl Server {
                 DimensionType::TheEnd,
             ],
             command_dispatcher,
+            command_changes: Mutex::new(Vec::new()),
             block_registry: super::block::default_registry(),
             item_registry: super::item::default_registry(),
             block_properties_manager: default_block_properties_manager(),
@@ -139,15 +220,36 @@ impl Server {
             server_listing: Mutex::new(CachedStatus::new()),
             server_branding: CachedBranding::new(),
             bossbars: Mutex::new(CustomBossbars::new()),
+            plugin_channels: RwLock::new(HashMap::new()),
+            plugin_scoreboard: PluginScoreboard::default(),
+            plugin_bossbars: RwLock::new(Vec::new()),
+            glowing: Glowing::default(),
+            teleport_requests: TeleportRequests::default(),
+            teleport_warmups: TeleportWarmups::default(),
+            predicates,
+            autosave: AutoSave::default(),
+            tps: AtomicCell::new(BASIC_CONFIG.tps),
+            max_players: AtomicU32::new(BASIC_CONFIG.max_players),
+            spawn_protection: AtomicU32::new(BASIC_CONFIG.spawn_protection),
+            players_sleeping_percentage: AtomicU32::new(
+                ADVANCED_CONFIG.world.players_sleeping_percentage,
+            ),
         }
     }
 
-    const SPAWN_CHUNK_RADIUS: i32 = 1;
-
-    pub fn spawn_chunks() -> impl Iterator<Item = Vector2<i32>> {
-        (-Self::SPAWN_CHUNK_RADIUS..=Self::SPAWN_CHUNK_RADIUS).flat_map(|x| {
-            (-Self::SPAWN_CHUNK_RADIUS..=Self::SPAWN_CHUNK_RADIUS).map(move |z| Vector2::new(x, z))
-        })
+    /// Loads a world from the worlds section of the config
+    fn load_named_world(name: &str, config: &NamedWorldConfig) -> World {
+        let folder = PathBuf::from(config.folder.as_deref().unwrap_or(name));
+        let level = match &config.seed {
+            Some(seed) => Level::from_root_folder_with_seed(folder, Seed::from(seed.as_str())),
+            None => Level::from_root_folder(folder),
+        };
+        let dimension_type = match config.environment {
+            WorldEnvironment::Overworld => DimensionType::Overworld,
+            WorldEnvironment::Nether => DimensionType::TheNether,
+            WorldEnvironment::End => DimensionType::TheEnd,
+        };
+        World::load_named(level, dimension_type, name.to_string())
     }
 
     /// Adds a new player to the server.
@@ -202,14 +304,78 @@ impl Server {
         self.server_listing.lock().await.remove_player();
     }
 
+    /// Runs the configured first join commands if the player never joined the server before.
+    ///
+    /// The player only counts as joined once that has been saved. If saving fails, the commands
+    /// are not run, so the player gets them on their next join instead of twice.
+    pub async fn handle_first_join(&self, player: &Player) {
+        let mut joined = JOINED_PLAYER_LIST.write().await;
+        if !joined.add(&player.gameprofile) {
+            return;
+        }
+        if let Err(err) = joined.try_save() {
+            joined
+                .players
+                .retain(|entry| entry.uuid != player.gameprofile.id);
+            log::warn!(
+                "Couldn't save that {} joined for the first time: {err}",
+                player.gameprofile.name
+            );
+            return;
+        }
+        drop(joined);
+
+        log::info!("{} joined for the first time", player.gameprofile.name);
+        if ADVANCED_CONFIG.first_join.enabled {
+            self.run_first_join_commands(player).await;
+        }
+    }
+
+    /// Runs the configured first join commands for the player as the console.
+    pub async fn run_first_join_commands(&self, player: &Player) {
+        let dispatcher = self.command_dispatcher.read().await;
+        for command in &ADVANCED_CONFIG.first_join.commands {
+            let command = placeholder::resolve(command, Some(player)).await;
+            dispatcher
+                .handle_command(&mut CommandSender::Console, self, &command)
+                .await;
+        }
+    }
+
+    /// Saves everything at once, like `/save-all` and when stopping. A running autosave is
+    /// completed by this.
     pub async fn save(&self) {
         for world in self.worlds.read().await.iter() {
-            world.save().await;
+            world.save(self).await;
         }
+        self.save_player_data().await;
+        self.autosave.reset().await;
 
         log::info!("Completed world save");
     }
 
+    /// Turns saving off or on, like `/save-off` and `/save-on`. Returns whether it changed.
+    pub async fn set_saving_paused(&self, paused: bool) -> bool {
+        if !self.autosave.set_paused(paused) {
+            return false;
+        }
+        // Otherwise chunks would still be written when they are unloaded
+        for world in self.worlds.read().await.iter() {
+            world.level.set_saving_paused(paused).await;
+        }
+        true
+    }
+
+    /// Saves the data of the players, which is only their playtime so far
+    pub async fn save_player_data(&self) {
+        // The playtime of online players is only saved when they leave otherwise
+        let mut playtime = PLAYTIME_LIST.write().await;
+        for player in self.get_all_players().await {
+            playtime.update(&player.gameprofile, player.playtime.load(Ordering::Relaxed));
+        }
+        playtime.save();
+    }
+
     /// Adds a new living entity to the server. This does not Spawn the entity
     ///
     /// # Returns
@@ -225,32 +391,7 @@ impl Server {
         entity_type: EntityType,
         world: &Arc<World>,
     ) -> Entity {
-        let entity_id = self.new_entity_id();
-
-        // TODO: this should be resolved to a integer using a macro when calling this function
-        let bounding_box_size = EntityDimensions {
-            width: entity_type.dimension[0],
-            height: entity_type.dimension[1],
-        };
-
-        // TODO: standing eye height should be per mob
-        let new_uuid = uuid::Uuid::new_v4();
-        Entity::new(
-            entity_id,
-            new_uuid,
-            world.clone(),
-            position,
-            entity_type,
-            entity_type.eye_height,
-            AtomicCell::new(BoundingBox::new_from_pos(
-                position.x,
-                position.y,
-                position.z,
-                &bounding_box_size,
-            )),
-            AtomicCell::new(bounding_box_size),
-            false,
-        )
+        Entity::from_type(self.new_entity_id(), position, entity_type, world)
     }
 
     pub async fn try_get_container(
@@ -340,6 +481,36 @@ impl Server {
         }
     }
 
+    /// Searches for a loaded world by its name, see [`World::name`].
+    pub async fn get_world_by_name(&self, name: &str) -> Option<Arc<World>> {
+        self.worlds
+            .read()
+            .await
+            .iter()
+            .find(|world| world.name() == name)
+            .cloned()
+    }
+
+    /// Gets the shared handle of a world, which e.g. blocks need to spawn entities, as they only
+    /// get a reference to their world.
+    pub async fn get_world_handle(&self, world: &World) -> Option<Arc<World>> {
+        self.worlds
+            .read()
+            .await
+            .iter()
+            .find(|handle| std::ptr::eq(handle.as_ref(), world))
+            .cloned()
+    }
+
+    /// The world players respawn in after dying in the given world. Worlds of the overworld type
+    /// have their own spawn, players dying in any other world respawn in the first world.
+    pub async fn get_respawn_world(&self, world: &Arc<World>) -> Option<Arc<World>> {
+        match world.dimension_type {
+            DimensionType::Overworld | DimensionType::OverworldCaves => Some(world.clone()),
+            _ => self.worlds.read().await.first().cloned(),
+        }
+    }
+
     /// Searches for a player by their username across all worlds.
     ///
     /// This function iterates through each world managed by the server and attempts to find a player with the specified username.
@@ -416,6 +587,44 @@ impl Server {
         None
     }
 
+    /// The maximum number of players allowed on the server. `0` means there is no limit.
+    #[must_use]
+    pub fn max_players(&self) -> u32 {
+        self.max_players.load(Ordering::Relaxed)
+    }
+
+    /// Changes the maximum number of players, without kicking players who are already online.
+    pub async fn set_max_players(&self, max_players: u32) {
+        self.max_players.store(max_players, Ordering::Relaxed);
+        self.server_listing
+            .lock()
+            .await
+            .set_max_players(max_players);
+    }
+
+    /// The radius around the world spawn in which only operators can build, `0` for none.
+    #[must_use]
+    pub fn spawn_protection(&self) -> u32 {
+        self.spawn_protection.load(Ordering::Relaxed)
+    }
+
+    /// Changes the radius around the world spawn in which only operators can build.
+    pub fn set_spawn_protection(&self, radius: u32) {
+        self.spawn_protection.store(radius, Ordering::Relaxed);
+    }
+
+    /// How many percent of the players in a world have to sleep to skip the night, above 100 never.
+    #[must_use]
+    pub fn players_sleeping_percentage(&self) -> u32 {
+        self.players_sleeping_percentage.load(Ordering::Relaxed)
+    }
+
+    /// Changes how many percent of the players in a world have to sleep to skip the night.
+    pub fn set_players_sleeping_percentage(&self, percentage: u32) {
+        self.players_sleeping_percentage
+            .store(percentage, Ordering::Relaxed);
+    }
+
     /// Counts the total number of players across all worlds.
     ///
     /// This function iterates through each world and sums up the number of players currently connected to that world.
@@ -479,9 +688,83 @@ impl Server {
         self.key_store.get_digest(secret)
     }
 
+    /// Sends the tab list header and footer to all players, if enabled.
+    pub async fn update_tab_list(&self) {
+        if !ADVANCED_CONFIG.tab_list.enabled {
+            return;
+        }
+        for player in self.get_all_players().await {
+            player.send_tab_list().await;
+        }
+    }
+
+    /// Enables or disables a built-in command by its name or an alias at the next tick. This can't
+    /// happen right away, as the dispatcher is locked while commands run.
+    pub async fn toggle_command(&self, name: String, enabled: bool) {
+        self.command_changes
+            .lock()
+            .await
+            .push(CommandChange::Toggle(name, enabled));
+    }
+
+    /// Registers a command at the next tick, see [`Server::toggle_command`].
+    pub async fn queue_register_command(&self, tree: CommandTree, permission: String) {
+        self.command_changes
+            .lock()
+            .await
+            .push(CommandChange::Register(tree, permission));
+    }
+
+    /// Unregisters a command by its primary name at the next tick, see [`Server::toggle_command`].
+    pub async fn queue_unregister_command(&self, name: String) {
+        self.command_changes
+            .lock()
+            .await
+            .push(CommandChange::Unregister(name));
+    }
+
+    async fn apply_command_changes(&self) {
+        let changes = std::mem::take(&mut *self.command_changes.lock().await);
+        if changes.is_empty() {
+            return;
+        }
+        {
+            let builtin = builtin_dispatcher();
+            let mut dispatcher = self.command_dispatcher.write().await;
+            for change in changes {
+                match change {
+                    CommandChange::Toggle(name, true) => {
+                        dispatcher.enable(&name, &builtin);
+                    }
+                    CommandChange::Toggle(name, false) => {
+                        dispatcher.disable(&name);
+                    }
+                    CommandChange::Register(tree, permission) => {
+                        dispatcher.register(tree, &permission, BASIC_CONFIG.op_permission_level);
+                    }
+                    CommandChange::Unregister(name) => dispatcher.unregister(&name),
+                }
+            }
+        }
+        self.resend_commands().await;
+    }
+
+    /// Sends the current command tree to all players, e.g. after commands were registered
+    pub async fn resend_commands(&self) {
+        for player in self.get_all_players().await {
+            client_suggestions::send_c_commands_packet(&player, &self.command_dispatcher).await;
+        }
+    }
+
     async fn tick(&self) {
+        self.apply_command_changes().await;
         for world in self.worlds.read().await.iter() {
-            world.tick().await;
+            world.tick(self).await;
+        }
+        for bossbar in self.plugin_bossbars.read().await.iter() {
+            bossbar.flush().await;
         }
+        self.glowing.tick(self).await;
+        self.teleport_requests.tick(self).await;
     }
 }
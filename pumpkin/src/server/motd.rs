@@ -0,0 +1,86 @@
+use pumpkin_util::text::{TextComponent, TextComponentBase, TextContent};
+
+/// The server list only shows the first two lines of the MOTD.
+pub const MAX_LINES: usize = 2;
+/// Roughly the number of characters that fit into one line of the server list.
+pub const MAX_LINE_LENGTH: usize = 59;
+
+/// Parses a MOTD, which is either a JSON text component or plain text with legacy `§` formatting codes.
+/// Other messages written by admins, like broadcasts, are parsed the same way.
+#[must_use]
+pub fn parse(motd: &str) -> TextComponent {
+    let trimmed = motd.trim();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        if let Ok(component) = serde_json::from_str(trimmed) {
+            return component;
+        }
+    }
+    TextComponent::text(motd.to_string())
+}
+
+/// Checks that the MOTD fits into the server list.
+pub fn validate(motd: &TextComponent) -> Result<(), String> {
+    let mut text = String::new();
+    visible_text(&motd.0, &mut text);
+
+    let lines: Vec<&str> = text.split('\n').collect();
+    if lines.len() > MAX_LINES {
+        return Err(format!(
+            "The MOTD can have at most {MAX_LINES} lines, but has {}",
+            lines.len()
+        ));
+    }
+    for (i, line) in lines.iter().enumerate() {
+        let length = line.chars().count();
+        if length > MAX_LINE_LENGTH {
+            return Err(format!(
+                "Line {} of the MOTD is {length} characters long, but at most {MAX_LINE_LENGTH} fit into the server list",
+                i + 1
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Collects the text the client displays, without legacy formatting codes.
+fn visible_text(component: &TextComponentBase, out: &mut String) {
+    if let TextContent::Text { text } = &component.content {
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '§' {
+                chars.next();
+            } else {
+                out.push(c);
+            }
+        }
+    }
+    for child in &component.extra {
+        visible_text(child, out);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, validate};
+
+    #[test]
+    fn parse_json_and_plain() {
+        let json = parse(r#"{"text":"Hello","color":"red"}"#);
+        assert_eq!(json.get_text(), "Hello");
+        let plain = parse("{not json");
+        assert_eq!(plain.get_text(), "{not json");
+    }
+
+    #[test]
+    fn validate_lines() {
+        assert!(validate(&parse("§aFirst line\n§bSecond line")).is_ok());
+        assert!(validate(&parse("one\ntwo\nthree")).is_err());
+        assert!(validate(&parse(&"a".repeat(60))).is_err());
+        // Formatting codes are not visible
+        assert!(validate(&parse(&"§l".repeat(40))).is_ok());
+        assert!(validate(&parse(
+            r#"{"text":"one\n","extra":[{"text":"two\nthree"}]}"#
+        ))
+        .is_err());
+    }
+}
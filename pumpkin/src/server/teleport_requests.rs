@@ -0,0 +1,177 @@
+//! Pending `/tpa` and `/tpahere` requests, which the target can accept or deny until they expire.
+
+use std::time::{Duration, Instant};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::entity::player::Player;
+
+use super::Server;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TeleportRequestKind {
+    /// The requester wants to teleport to the target, through `/tpa`
+    ToTarget,
+    /// The requester wants the target to teleport to them, through `/tpahere`
+    ToRequester,
+}
+
+#[derive(Clone)]
+pub struct TeleportRequest {
+    pub requester: Uuid,
+    pub requester_name: String,
+    pub target: Uuid,
+    pub target_name: String,
+    pub kind: TeleportRequestKind,
+    expires: Instant,
+}
+
+impl TeleportRequest {
+    /// The player who is teleported and the player they are teleported to
+    #[must_use]
+    pub fn traveller_and_destination(&self) -> (Uuid, Uuid) {
+        match self.kind {
+            TeleportRequestKind::ToTarget => (self.requester, self.target),
+            TeleportRequestKind::ToRequester => (self.target, self.requester),
+        }
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        now >= self.expires
+    }
+}
+
+#[derive(Default)]
+pub struct TeleportRequests {
+    /// The pending requests from oldest to newest. Each player has at most one outgoing request,
+    /// but can receive requests from several players.
+    requests: Mutex<Vec<TeleportRequest>>,
+}
+
+impl TeleportRequests {
+    /// Sends a request from one player to another. A player can only have one outgoing request,
+    /// so this replaces and returns their previous one.
+    pub async fn send(
+        &self,
+        requester: &Player,
+        target: &Player,
+        kind: TeleportRequestKind,
+    ) -> Option<TeleportRequest> {
+        let timeout = Duration::from_secs(ADVANCED_CONFIG.teleport_requests.timeout.into());
+        let mut requests = self.requests.lock().await;
+        let replaced = requests
+            .iter()
+            .position(|request| request.requester == requester.gameprofile.id)
+            .map(|index| requests.remove(index));
+        requests.push(TeleportRequest {
+            requester: requester.gameprofile.id,
+            requester_name: requester.gameprofile.name.clone(),
+            target: target.gameprofile.id,
+            target_name: target.gameprofile.name.clone(),
+            kind,
+            expires: Instant::now() + timeout,
+        });
+        replaced
+    }
+
+    /// Removes and returns a request to the target, either the one from the given requester or
+    /// the newest one.
+    pub async fn take(&self, target: Uuid, requester: Option<Uuid>) -> Option<TeleportRequest> {
+        let now = Instant::now();
+        let mut requests = self.requests.lock().await;
+        let index = requests.iter().rposition(|request| {
+            request.target == target
+                && !request.is_expired(now)
+                && requester.is_none_or(|requester| request.requester == requester)
+        })?;
+        Some(requests.remove(index))
+    }
+
+    /// Removes and returns the outgoing request of a player
+    pub async fn cancel(&self, requester: Uuid) -> Option<TeleportRequest> {
+        let mut requests = self.requests.lock().await;
+        let index = requests
+            .iter()
+            .position(|request| request.requester == requester)?;
+        Some(requests.remove(index))
+    }
+
+    /// Drops all requests from and to a player who left, telling the other players about it.
+    pub async fn remove_player(&self, server: &Server, player: &Player) {
+        let uuid = player.gameprofile.id;
+        let mut removed = Vec::new();
+        self.requests.lock().await.retain(|request| {
+            let involved = request.requester == uuid || request.target == uuid;
+            if involved {
+                removed.push(request.clone());
+            }
+            !involved
+        });
+
+        for request in removed {
+            let (other, message) = if request.requester == uuid {
+                (
+                    request.target,
+                    format!(
+                        "The teleport request from {} was cancelled, as they left",
+                        request.requester_name
+                    ),
+                )
+            } else {
+                (
+                    request.requester,
+                    format!(
+                        "Your teleport request to {} was cancelled, as they left",
+                        request.target_name
+                    ),
+                )
+            };
+            if let Some(other) = server.get_player_by_uuid(other).await {
+                other
+                    .send_system_message(&TextComponent::text(message).color_named(NamedColor::Red))
+                    .await;
+            }
+        }
+    }
+
+    /// Drops the expired requests, telling both players about it.
+    pub async fn tick(&self, server: &Server) {
+        let now = Instant::now();
+        let mut expired = Vec::new();
+        self.requests.lock().await.retain(|request| {
+            if request.is_expired(now) {
+                expired.push(request.clone());
+                return false;
+            }
+            true
+        });
+
+        for request in expired {
+            if let Some(requester) = server.get_player_by_uuid(request.requester).await {
+                requester
+                    .send_system_message(
+                        &TextComponent::text(format!(
+                            "Your teleport request to {} has expired",
+                            request.target_name
+                        ))
+                        .color_named(NamedColor::Red),
+                    )
+                    .await;
+            }
+            if let Some(target) = server.get_player_by_uuid(request.target).await {
+                target
+                    .send_system_message(
+                        &TextComponent::text(format!(
+                            "The teleport request from {} has expired",
+                            request.requester_name
+                        ))
+                        .color_named(NamedColor::Red),
+                    )
+                    .await;
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Warmups before teleports through commands like `/home`, during which moving or taking damage
+//! cancels the teleport.
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::{
+    text::{color::NamedColor, TextComponent},
+    PermissionLvl,
+};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::entity::player::{Player, TitleMode};
+
+/// How often the player is checked for moving or taking damage
+const CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Default)]
+pub struct TeleportWarmups {
+    /// The id of the newest warmup of each player who is waiting. Starting another warmup
+    /// replaces the one running before.
+    running: Mutex<HashMap<Uuid, u64>>,
+    next_id: AtomicU64,
+}
+
+impl TeleportWarmups {
+    /// Waits for the configured warmup before the player is teleported, showing a countdown in
+    /// their action bar. Operators do not wait.
+    ///
+    /// Returns whether the player should be teleported now. It is not the case if they moved to
+    /// another block, took damage, left or started another teleport in the meantime.
+    pub async fn wait(&self, player: &Player) -> bool {
+        let seconds = ADVANCED_CONFIG.teleport_warmup.seconds;
+        if seconds == 0 || player.permission_lvl.load().ge(&PermissionLvl::Two) {
+            return true;
+        }
+
+        let uuid = player.gameprofile.id;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.running.lock().await.insert(uuid, id);
+
+        let entity = &player.living_entity.entity;
+        let start = entity.block_pos.load().0;
+        let mut health = player.living_entity.health.load();
+        let end = Instant::now() + Duration::from_secs(seconds.into());
+        let mut shown = None;
+        let cancelled = loop {
+            if self.running.lock().await.get(&uuid) != Some(&id) {
+                // A newer warmup took over, which shows its own countdown
+                return false;
+            }
+            if player.client.closed.load(Ordering::Relaxed) {
+                break None;
+            }
+            if entity.block_pos.load().0 != start {
+                break Some("Teleport cancelled, as you moved");
+            }
+            // Healing in the meantime does not hide damage taken afterwards
+            let current_health = player.living_entity.health.load();
+            if current_health < health {
+                break Some("Teleport cancelled, as you took damage");
+            }
+            health = current_health;
+
+            let remaining = end.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                self.finish(uuid, id).await;
+                return true;
+            }
+            let remaining_seconds = remaining.as_secs_f32().ceil() as u64;
+            if shown != Some(remaining_seconds) {
+                shown = Some(remaining_seconds);
+                player
+                    .show_title(
+                        &TextComponent::text(format!(
+                            "Teleporting in {remaining_seconds}, do not move"
+                        ))
+                        .color_named(NamedColor::Gold),
+                        &TitleMode::ActionBar,
+                    )
+                    .await;
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        };
+
+        self.finish(uuid, id).await;
+        if let Some(reason) = cancelled {
+            player
+                .show_title(
+                    &TextComponent::text(reason).color_named(NamedColor::Red),
+                    &TitleMode::ActionBar,
+                )
+                .await;
+        }
+        false
+    }
+
+    /// Forgets the warmup, unless a newer one replaced it already
+    async fn finish(&self, uuid: Uuid, id: u64) {
+        let mut running = self.running.lock().await;
+        if running.get(&uuid) == Some(&id) {
+            running.remove(&uuid);
+        }
+    }
+}
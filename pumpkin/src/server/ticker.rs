@@ -9,6 +9,10 @@ use super::Server;
 pub struct Ticker {
     tick_interval: Duration,
     last_tick: Instant,
+    /// When the current measurement of the TPS started
+    second_start: Instant,
+    /// Ticks since `second_start`
+    ticks_this_second: u32,
 }
 
 impl Ticker {
@@ -17,6 +21,8 @@ impl Ticker {
         Self {
             tick_interval: Duration::from_millis((1000.0 / tps) as u64),
             last_tick: Instant::now(),
+            second_start: Instant::now(),
+            ticks_this_second: 0,
         }
     }
 
@@ -29,6 +35,20 @@ impl Ticker {
             if elapsed >= self.tick_interval {
                 server.tick().await;
                 self.last_tick = now;
+                self.ticks_this_second += 1;
+
+                server.autosave.tick(server).await;
+
+                let second_elapsed = now - self.second_start;
+                if second_elapsed >= Duration::from_secs(1) {
+                    server
+                        .tps
+                        .store(self.ticks_this_second as f32 / second_elapsed.as_secs_f32());
+                    self.second_start = now;
+                    self.ticks_this_second = 0;
+
+                    server.update_tab_list().await;
+                }
             } else {
                 // Wait for the remaining time until the next tick
                 let sleep_time = self.tick_interval - elapsed;
@@ -1,18 +1,31 @@
+use std::time::Instant;
+
 use pumpkin_protocol::client::play::{
     CInitializeWorldBorder, CSetBorderCenter, CSetBorderLerpSize, CSetBorderSize,
     CSetBorderWarningDelay, CSetBorderWarningDistance,
 };
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+
+use pumpkin_world::world_info::LevelData;
 
 use crate::net::Client;
 
 use super::World;
 
+/// How far from the center of the world the border can reach at most, like in vanilla
+const MAX_DISTANCE: f64 = 29_999_984.0;
+
 pub struct Worldborder {
     pub center_x: f64,
     pub center_z: f64,
+    /// The diameter the border started changing from, see [`Worldborder::diameter`]
     pub old_diameter: f64,
+    /// The diameter the border is changing to
     pub new_diameter: f64,
+    /// How many milliseconds the border takes to change from the old to the new diameter
     pub speed: i64,
+    /// When the border started changing its diameter
+    lerp_start: Instant,
     pub portal_teleport_boundary: i32,
     pub warning_blocks: i32,
     pub warning_time: i32,
@@ -36,22 +49,54 @@ impl Worldborder {
             old_diameter: diameter,
             new_diameter: diameter,
             speed,
+            lerp_start: Instant::now(),
             portal_teleport_boundary: 29_999_984,
             warning_blocks,
             warning_time,
-            damage_per_block: 0.0,
-            buffer: 0.0,
+            damage_per_block: 0.2,
+            buffer: 5.0,
         }
     }
 
+    /// Restores the border saved in the level data, including a change of its diameter which was
+    /// not finished yet
+    #[must_use]
+    pub fn from_level_data(level_data: &LevelData) -> Self {
+        let mut border = Self::new(
+            level_data.border_center_x,
+            level_data.border_center_z,
+            level_data.border_size,
+            level_data.border_size_lerp_time.max(0),
+            level_data.border_warning_blocks as i32,
+            level_data.border_warning_time as i32,
+        );
+        border.new_diameter = level_data.border_size_lerp_target;
+        border.damage_per_block = level_data.border_damage_per_block as f32;
+        border.buffer = level_data.border_safe_zone as f32;
+        border
+    }
+
+    /// Writes the border to the level data, so it stays the same after a restart
+    pub fn write_level_data(&self, level_data: &mut LevelData) {
+        level_data.border_center_x = self.center_x;
+        level_data.border_center_z = self.center_z;
+        level_data.border_size = self.diameter();
+        level_data.border_size_lerp_target = self.new_diameter;
+        level_data.border_size_lerp_time = self.remaining_time();
+        level_data.border_warning_blocks = f64::from(self.warning_blocks);
+        level_data.border_warning_time = f64::from(self.warning_time);
+        level_data.border_damage_per_block = f64::from(self.damage_per_block);
+        level_data.border_safe_zone = f64::from(self.buffer);
+    }
+
     pub async fn init_client(&self, client: &Client) {
         client
             .send_packet(&CInitializeWorldBorder::new(
                 self.center_x,
                 self.center_z,
-                self.old_diameter,
+                self.diameter(),
                 self.new_diameter,
-                self.speed.into(),
+                self.remaining_time().into(),
                 self.portal_teleport_boundary.into(),
                 self.warning_blocks.into(),
                 self.warning_time.into(),
@@ -59,21 +104,136 @@ impl Worldborder {
             .await;
     }
 
+    /// The current diameter, which moves from the old to the new diameter over `speed`
+    /// milliseconds
+    #[must_use]
+    pub fn diameter(&self) -> f64 {
+        self.diameter_at(Instant::now())
+    }
+
+    fn diameter_at(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.lerp_start).as_millis() as f64;
+        let speed = self.speed as f64;
+        if self.speed <= 0 || elapsed >= speed {
+            return self.new_diameter;
+        }
+        self.old_diameter + (self.new_diameter - self.old_diameter) * (elapsed / speed)
+    }
+
+    /// How many milliseconds the border still takes to reach the new diameter
+    fn remaining_time(&self) -> i64 {
+        let elapsed = i64::try_from(self.lerp_start.elapsed().as_millis()).unwrap_or(i64::MAX);
+        (self.speed - elapsed).max(0)
+    }
+
+    /// Finishes changing the diameter once the time is up
+    pub fn tick(&mut self, world: &World) {
+        if self.speed > 0 && self.remaining_time() == 0 {
+            self.old_diameter = self.new_diameter;
+            self.speed = 0;
+            self.update_generation_bounds(world);
+        }
+    }
+
+    /// The smallest and the largest coordinates inside of the border
+    #[must_use]
+    pub fn bounds(&self) -> (Vector2<f64>, Vector2<f64>) {
+        self.bounds_with_diameter(self.diameter())
+    }
+
+    fn bounds_with_diameter(&self, diameter: f64) -> (Vector2<f64>, Vector2<f64>) {
+        let radius = diameter / 2.0;
+        (
+            Vector2::new(
+                (self.center_x - radius).max(-MAX_DISTANCE),
+                (self.center_z - radius).max(-MAX_DISTANCE),
+            ),
+            Vector2::new(
+                (self.center_x + radius).min(MAX_DISTANCE),
+                (self.center_z + radius).min(MAX_DISTANCE),
+            ),
+        )
+    }
+
+    /// Whether the position is inside of the border
+    #[must_use]
+    pub fn contains(&self, x: f64, z: f64) -> bool {
+        let (min, max) = self.bounds();
+        x >= min.x && x < max.x && z >= min.z && z < max.z
+    }
+
+    /// Whether the block is inside of the border, even if only partly
+    #[must_use]
+    pub fn contains_block(&self, position: &BlockPos) -> bool {
+        let (min, max) = self.bounds();
+        let x = f64::from(position.0.x);
+        let z = f64::from(position.0.z);
+        x + 1.0 > min.x && x < max.x && z + 1.0 > min.z && z < max.z
+    }
+
+    /// How far the position is from the border, negative if it is outside
+    #[must_use]
+    pub fn distance_inside(&self, x: f64, z: f64) -> f64 {
+        let (min, max) = self.bounds();
+        (x - min.x).min(max.x - x).min(z - min.z).min(max.z - z)
+    }
+
+    /// Moves the position onto the border if it is outside, keeping its height
+    #[must_use]
+    pub fn clamp(&self, position: Vector3<f64>) -> Vector3<f64> {
+        let (min, max) = self.bounds();
+        Vector3::new(
+            position.x.min(max.x - 1.0E-5).max(min.x),
+            position.y,
+            position.z.min(max.z - 1.0E-5).max(min.z),
+        )
+    }
+
+    /// The damage taken each second at the position, growing with the distance beyond the
+    /// buffer outside of the border. Returns `None` if the position is safe.
+    #[must_use]
+    pub fn damage_at(&self, x: f64, z: f64) -> Option<f32> {
+        let distance = self.distance_inside(x, z) + f64::from(self.buffer);
+        if distance >= 0.0 || self.damage_per_block <= 0.0 {
+            return None;
+        }
+        Some(
+            (-distance * f64::from(self.damage_per_block))
+                .floor()
+                .max(1.0) as f32,
+        )
+    }
+
+    /// Lets the level generate new chunks only inside of the border. While the border changes,
+    /// the larger diameter counts, so no chunks are left empty before the border gets there.
+    fn update_generation_bounds(&self, world: &World) {
+        let (min, max) = self.bounds_with_diameter(self.old_diameter.max(self.new_diameter));
+        world.level.set_generation_bounds(
+            Vector2::new(min.x.floor() as i32, min.z.floor() as i32),
+            Vector2::new(max.x.ceil() as i32 - 1, max.z.ceil() as i32 - 1),
+        );
+    }
+
     pub async fn set_center(&mut self, world: &World, x: f64, z: f64) {
         self.center_x = x;
         self.center_z = z;
+        self.update_generation_bounds(world);
 
         world
             .broadcast_packet_all(&CSetBorderCenter::new(self.center_x, self.center_z))
             .await;
     }
 
+    /// Changes the diameter, over `speed` milliseconds if given or at once otherwise
     pub async fn set_diameter(&mut self, world: &World, diameter: f64, speed: Option<i64>) {
-        self.old_diameter = self.new_diameter;
-        self.new_diameter = diameter;
-
-        match speed {
+        match speed.filter(|speed| *speed > 0) {
             Some(speed) => {
+                self.old_diameter = self.diameter();
+                self.new_diameter = diameter;
+                self.speed = speed;
+                self.lerp_start = Instant::now();
+                self.update_generation_bounds(world);
+
                 world
                     .broadcast_packet_all(&CSetBorderLerpSize::new(
                         self.old_diameter,
@@ -83,6 +243,11 @@ impl Worldborder {
                     .await;
             }
             None => {
+                self.old_diameter = diameter;
+                self.new_diameter = diameter;
+                self.speed = 0;
+                self.update_generation_bounds(world);
+
                 world
                     .broadcast_packet_all(&CSetBorderSize::new(self.new_diameter))
                     .await;
@@ -91,7 +256,7 @@ impl Worldborder {
     }
 
     pub async fn add_diameter(&mut self, world: &World, offset: f64, speed: Option<i64>) {
-        self.set_diameter(world, self.new_diameter + offset, speed)
+        self.set_diameter(world, self.diameter() + offset, speed)
             .await;
     }
 
@@ -111,3 +276,50 @@ impl Worldborder {
             .await;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::Worldborder;
+
+    #[test]
+    fn diameter_moves_over_time() {
+        let mut border = Worldborder::new(0.0, 0.0, 100.0, 0, 5, 15);
+        border.new_diameter = 200.0;
+        border.speed = 10_000;
+        let start = border.lerp_start;
+        assert!((border.diameter_at(start) - 100.0).abs() < f64::EPSILON);
+        let halfway = border.diameter_at(start + Duration::from_secs(5));
+        assert!((halfway - 150.0).abs() < 1.0E-9);
+        let done = border.diameter_at(start + Duration::from_secs(20));
+        assert!((done - 200.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn contains_and_clamps() {
+        let border = Worldborder::new(10.0, 0.0, 20.0, 0, 5, 15);
+        assert!(border.contains(0.0, -10.0));
+        assert!(!border.contains(20.0, 0.0));
+        assert!(border.contains_block(&BlockPos(Vector3::new(19, 64, 9))));
+        assert!(!border.contains_block(&BlockPos(Vector3::new(20, 64, 0))));
+
+        let clamped = border.clamp(Vector3::new(-5.0, 64.0, 30.0));
+        assert!(clamped.x.abs() < f64::EPSILON);
+        assert!(clamped.z < 10.0 && clamped.z > 9.99);
+        assert!((border.distance_inside(30.0, 0.0) + 10.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn damage_beyond_buffer() {
+        let border = Worldborder::new(0.0, 0.0, 20.0, 0, 5, 15);
+        // Inside of the buffer, which is 5 blocks by default
+        assert_eq!(border.damage_at(13.0, 0.0), None);
+        // 10 blocks beyond the buffer at 0.2 damage per block
+        assert_eq!(border.damage_at(25.0, 0.0), Some(2.0));
+        // Always at least one damage
+        assert_eq!(border.damage_at(16.0, 0.0), Some(1.0));
+    }
+}
@@ -2,8 +2,14 @@ use std::{num::NonZeroU8, sync::Arc};
 
 use pumpkin_config::BASIC_CONFIG;
 use pumpkin_protocol::client::play::{CCenterChunk, CUnloadChunk};
-use pumpkin_util::math::{get_section_cord, position::BlockPos, vector3::Vector3};
-use pumpkin_world::cylindrical_chunk_iterator::Cylindrical;
+use pumpkin_util::math::{
+    get_section_cord, position::BlockPos, vector2::Vector2, vector3::Vector3,
+};
+use pumpkin_world::{
+    chunk::ticket::{TicketType, ENTITY_TICKING_LEVEL},
+    cylindrical_chunk_iterator::Cylindrical,
+    level::Level,
+};
 
 use crate::entity::player::Player;
 
@@ -14,6 +20,28 @@ pub async fn get_view_distance(player: &Player) -> NonZeroU8 {
     )
 }
 
+/// The level of the player ticket at the center of the chunks a player watches. The chunks
+/// within the simulation distance are ticked, but never more than the player can see.
+fn simulation_ticket_level(view_distance: NonZeroU8) -> u8 {
+    let distance = BASIC_CONFIG.simulation_distance.min(view_distance).get();
+    ENTITY_TICKING_LEVEL.saturating_sub(distance)
+}
+
+/// Removes the player tickets of all chunks the player watches and unloads the chunks which are
+/// no longer kept loaded. Returns the watched chunks which were unloaded.
+pub async fn unwatch_section(level: &Level, section: Cylindrical) -> Vec<Vector2<i32>> {
+    let chunks_to_clean = level.mark_chunks_as_not_watched(&section.all_chunks_within());
+    level
+        .remove_ticket(
+            section.center,
+            &TicketType::Player,
+            simulation_ticket_level(section.view_distance),
+        )
+        .await;
+    level.clean_chunks(&chunks_to_clean).await;
+    chunks_to_clean
+}
+
 pub async fn player_join(player: &Arc<Player>) {
     let chunk_pos = player.living_entity.entity.chunk_pos.load();
 
@@ -71,12 +99,26 @@ pub async fn update_position(player: &Arc<Player>) {
         // ensure what we unload when the player disconnects is correct
         let level = &entity.world.read().await.level;
         level.mark_chunks_as_newly_watched(&loading_chunks);
+        // The new ticket is placed first, so the chunks both tickets tick are not unloaded
+        level.add_ticket(
+            new_cylindrical.center,
+            TicketType::Player,
+            simulation_ticket_level(new_cylindrical.view_distance),
+        );
         let chunks_to_clean = level.mark_chunks_as_not_watched(&unloading_chunks);
         player.watched_section.store(new_cylindrical);
+        level
+            .remove_ticket(
+                old_cylindrical.center,
+                &TicketType::Player,
+                simulation_ticket_level(old_cylindrical.view_distance),
+            )
+            .await;
+        level.clean_chunks(&chunks_to_clean).await;
 
-        if !chunks_to_clean.is_empty() {
-            level.clean_chunks(&chunks_to_clean).await;
-
+        // Chunks the player no longer sees may still be kept loaded by other tickets, but the
+        // player has to forget them either way
+        if !unloading_chunks.is_empty() {
             // This can take a little if we are sending a bunch of packets, queue it up :p
             let client = player.client.clone();
             tokio::spawn(async move {
@@ -0,0 +1,205 @@
+use std::{collections::HashSet, sync::Arc};
+
+use pumpkin_data::{
+    damage::DamageType,
+    particle::Particle,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_protocol::{client::play::CEntityVelocity, codec::var_int::VarInt};
+use pumpkin_util::{
+    math::{position::BlockPos, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_world::block::registry::get_block_and_state_by_state_id;
+use rand::{thread_rng, Rng};
+
+use crate::{
+    block::blocks::fire,
+    entity::{living::LivingEntity, EntityBase},
+    server::Server,
+};
+
+use super::World;
+
+/// Like in vanilla, rays are cast from the center to the points of a cube with this many points
+/// along each edge
+const RAYS_PER_EDGE: i32 = 16;
+/// How far a ray moves in each step
+const RAY_STEP: f64 = 0.3;
+/// How much strength a ray loses in each step, besides the resistance of the blocks it passes
+const RAY_DECAY: f32 = 0.225;
+
+/// Explodes at the center. Blocks the explosion is strong enough for are destroyed and living
+/// entities around it are hurt and pushed away. With `fire`, some of the destroyed blocks catch
+/// fire, like when beds explode in the nether.
+pub async fn explode(
+    server: &Server,
+    world: &Arc<World>,
+    center: Vector3<f64>,
+    power: f32,
+    fire: bool,
+    damage_type: DamageType,
+) {
+    let blocks = affected_blocks(world, center, power).await;
+
+    world
+        .play_sound_raw(
+            Sound::EntityGenericExplode as u16,
+            SoundCategory::Blocks,
+            &center,
+            4.0,
+            (1.0 + (rand::random::<f32>() - rand::random::<f32>()) * 0.2) * 0.7,
+        )
+        .await;
+    world
+        .spawn_particle(
+            center,
+            Vector3::new(0.0, 0.0, 0.0),
+            1.0,
+            1,
+            Particle::ExplosionEmitter,
+        )
+        .await;
+
+    for position in &blocks {
+        // Like in vanilla, the bigger the explosion, the less blocks drop
+        let drop = thread_rng().gen_range(0.0..power) < 1.0;
+        world.break_block(server, position, None, drop).await;
+    }
+    if fire {
+        set_fires(server, world, &blocks).await;
+    }
+
+    hurt_entities(world, center, power, damage_type).await;
+}
+
+/// The blocks the explosion destroys. Rays are cast outwards from the center and lose strength
+/// with distance and for every block they pass, depending on how resistant it is.
+async fn affected_blocks(world: &World, center: Vector3<f64>, power: f32) -> Vec<BlockPos> {
+    let mut blocks = HashSet::new();
+    let last = RAYS_PER_EDGE - 1;
+    for x in 0..RAYS_PER_EDGE {
+        for y in 0..RAYS_PER_EDGE {
+            for z in 0..RAYS_PER_EDGE {
+                // Only the points on the surface of the cube
+                if ![x, y, z].iter().any(|i| *i == 0 || *i == last) {
+                    continue;
+                }
+                let to_edge = |i: i32| f64::from(i) / f64::from(last) * 2.0 - 1.0;
+                let direction = Vector3::new(to_edge(x), to_edge(y), to_edge(z)).normalize();
+
+                let mut strength = power * thread_rng().gen_range(0.7..1.3);
+                let mut position = center;
+                while strength > 0.0 {
+                    let block = BlockPos(Vector3::new(
+                        position.x.floor() as i32,
+                        position.y.floor() as i32,
+                        position.z.floor() as i32,
+                    ));
+                    let Ok(state_id) = world.get_block_state_id(&block).await else {
+                        break;
+                    };
+                    if let Some((block_data, state)) = get_block_and_state_by_state_id(state_id) {
+                        if !state.air {
+                            // TODO: Use the blast resistance of blocks, their hardness is close
+                            // for most blocks
+                            if block_data.hardness < 0.0 {
+                                break;
+                            }
+                            strength -= (block_data.hardness + 0.3) * 0.3;
+                            if strength > 0.0 {
+                                blocks.insert(block);
+                            }
+                        }
+                    }
+                    position = position + direction * RAY_STEP;
+                    strength -= RAY_DECAY;
+                }
+            }
+        }
+    }
+    blocks.into_iter().collect()
+}
+
+/// Like in vanilla, a third of the destroyed blocks with a solid block below catch fire
+async fn set_fires(server: &Server, world: &World, blocks: &[BlockPos]) {
+    for position in blocks {
+        if !thread_rng().gen_ratio(1, 3) {
+            continue;
+        }
+        let below = BlockPos(position.0.add_raw(0, -1, 0));
+        let solid_below = world
+            .get_block_state(&below)
+            .await
+            .is_ok_and(|state| !state.collision_shapes.is_empty());
+        let air = world
+            .get_block_state(position)
+            .await
+            .is_ok_and(|state| state.air);
+        if solid_below && air {
+            fire::place_fire(server, world, position, 0).await;
+        }
+    }
+}
+
+/// Hurts and pushes away the living entities within twice the power of the explosion. The
+/// closer they are, the more damage they take.
+async fn hurt_entities(world: &World, center: Vector3<f64>, power: f32, damage_type: DamageType) {
+    let radius = f64::from(power) * 2.0;
+
+    let mut entities: Vec<Arc<dyn EntityBase>> = world
+        .entities
+        .read()
+        .await
+        .values()
+        .filter(|entity| entity.get_living_entity().is_some())
+        .cloned()
+        .collect();
+    entities.extend(
+        world
+            .players
+            .read()
+            .await
+            .values()
+            .filter(|player| {
+                !matches!(
+                    player.gamemode.load(),
+                    GameMode::Creative | GameMode::Spectator
+                )
+            })
+            .map(|player| player.clone() as Arc<dyn EntityBase>),
+    );
+
+    for entity in entities {
+        let Some(living) = entity.get_living_entity() else {
+            continue;
+        };
+        let offset = living.entity.pos.load().sub(&center);
+        let distance = offset.length() / radius;
+        if distance > 1.0 {
+            continue;
+        }
+        // TODO: Blocks between the explosion and the entity should shield it
+        let impact = 1.0 - distance;
+        let damage = ((impact * impact + impact) / 2.0 * 7.0 * radius + 1.0) as f32;
+        living.damage(damage, damage_type).await;
+        push(world, living, offset, impact).await;
+    }
+}
+
+async fn push(world: &World, living: &LivingEntity, offset: Vector3<f64>, impact: f64) {
+    let entity = &living.entity;
+    if offset.length_squared() == 0.0 {
+        return;
+    }
+    let velocity = entity.velocity.load() + offset.normalize() * impact;
+    entity.velocity.store(velocity);
+    world
+        .broadcast_packet_all(&CEntityVelocity::new(
+            &VarInt(entity.entity_id),
+            velocity.x,
+            velocity.y,
+            velocity.z,
+        ))
+        .await;
+}
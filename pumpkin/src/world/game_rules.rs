@@ -0,0 +1,107 @@
+use pumpkin_world::world_info::{GameRules, LevelData};
+
+/// The game rules of vanilla which Pumpkin implements. They are read from the level.dat, like
+/// the rest of the world info, and written back when the world is saved. Other rules in the
+/// level.dat are kept as they are.
+pub struct WorldGameRules {
+    pub random_tick_speed: u32,
+    pub do_fire_tick: bool,
+    pub do_mob_spawning: bool,
+    pub do_daylight_cycle: bool,
+    pub do_weather_cycle: bool,
+    pub snow_accumulation_height: u8,
+    pub spawn_chunk_radius: u8,
+}
+
+impl WorldGameRules {
+    /// Reads the game rules of the level data. Values which are not valid fall back to the
+    /// vanilla defaults.
+    #[must_use]
+    pub fn from_level_data(level_data: &LevelData) -> Self {
+        let rules = &level_data.game_rules;
+        let defaults = GameRules::default();
+        let bool_rule = |rule: &str| {
+            rules
+                .get_bool(rule)
+                .or_else(|| defaults.get_bool(rule))
+                .unwrap_or_default()
+        };
+        let int_rule = |rule: &str| {
+            rules
+                .get_int(rule)
+                .or_else(|| defaults.get_int(rule))
+                .unwrap_or_default()
+        };
+        Self {
+            // Like in vanilla, negative values turn random ticks off
+            random_tick_speed: u32::try_from(int_rule("randomTickSpeed")).unwrap_or(0),
+            do_fire_tick: bool_rule("doFireTick"),
+            do_mob_spawning: bool_rule("doMobSpawning"),
+            do_daylight_cycle: bool_rule("doDaylightCycle"),
+            do_weather_cycle: bool_rule("doWeatherCycle"),
+            snow_accumulation_height: int_rule("snowAccumulationHeight").clamp(0, 8) as u8,
+            spawn_chunk_radius: int_rule("spawnChunkRadius").clamp(0, 32) as u8,
+        }
+    }
+
+    /// Writes the game rules to the level data, so they stay the same after a restart. The
+    /// sleeping percentage is the same in all worlds, see
+    /// [`crate::server::Server::players_sleeping_percentage`].
+    pub fn write_level_data(&self, level_data: &mut LevelData, players_sleeping_percentage: u32) {
+        let rules = &mut level_data.game_rules;
+        rules.set("randomTickSpeed", self.random_tick_speed);
+        rules.set("doFireTick", self.do_fire_tick);
+        rules.set("doMobSpawning", self.do_mob_spawning);
+        rules.set("doDaylightCycle", self.do_daylight_cycle);
+        rules.set("doWeatherCycle", self.do_weather_cycle);
+        rules.set("snowAccumulationHeight", self.snow_accumulation_height);
+        rules.set("playersSleepingPercentage", players_sleeping_percentage);
+        rules.set("spawnChunkRadius", self.spawn_chunk_radius);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn loads_and_saves_game_rules() {
+        let mut level_data = LevelData::default();
+        let rules = &mut level_data.game_rules;
+        rules.set("randomTickSpeed", 20);
+        rules.set("doFireTick", false);
+        rules.set("doMobSpawning", false);
+        rules.set("doDaylightCycle", false);
+        rules.set("doWeatherCycle", false);
+        rules.set("snowAccumulationHeight", 4);
+        rules.set("playersSleepingPercentage", 50);
+        rules.set("spawnChunkRadius", 0);
+        rules.set("keepInventory", true);
+
+        let game_rules = WorldGameRules::from_level_data(&level_data);
+        assert_eq!(game_rules.random_tick_speed, 20);
+        assert!(!game_rules.do_fire_tick);
+        assert!(!game_rules.do_mob_spawning);
+        assert!(!game_rules.do_daylight_cycle);
+        assert!(!game_rules.do_weather_cycle);
+        assert_eq!(game_rules.snow_accumulation_height, 4);
+        assert_eq!(game_rules.spawn_chunk_radius, 0);
+
+        let mut saved = level_data.clone();
+        saved.game_rules = GameRules::default();
+        saved.game_rules.set("keepInventory", true);
+        game_rules.write_level_data(&mut saved, 50);
+        assert_eq!(saved, level_data);
+    }
+
+    #[test]
+    fn invalid_game_rules() {
+        let mut level_data = LevelData::default();
+        level_data.game_rules.set("randomTickSpeed", -5);
+        level_data.game_rules.set("doFireTick", "sometimes");
+
+        let game_rules = WorldGameRules::from_level_data(&level_data);
+        assert_eq!(game_rules.random_tick_speed, 0);
+        assert!(game_rules.do_fire_tick);
+    }
+}
@@ -0,0 +1,291 @@
+use std::sync::Arc;
+
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_data::{
+    damage::DamageType,
+    entity::EntityType,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_protocol::client::play::{MetaDataType, Metadata};
+use pumpkin_util::{
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+    Difficulty, GameMode,
+};
+use pumpkin_world::light::{self, LightKind};
+use rand::{seq::SliceRandom, thread_rng, Rng};
+
+use crate::{
+    block::blocks::fire,
+    entity::{lightning::LightningBolt, living::LivingEntity, mob, Entity, EntityBase},
+    server::Server,
+};
+
+use super::World;
+
+/// Like in vanilla, every loaded chunk is struck with a chance of 1 in this many each tick of a
+/// thunderstorm
+const STRIKE_CHANCE: u32 = 100_000;
+
+/// Living entities this many blocks around the highest block of a column attract the lightning
+const TARGET_RANGE: f64 = 3.0;
+
+/// Entities this many blocks around the strike horizontally are hit
+const HIT_HORIZONTAL_RANGE: f64 = 3.0;
+/// Entities this many blocks below the strike are hit
+const HIT_RANGE_BELOW: f64 = 3.0;
+/// Entities this many blocks above the strike are hit
+const HIT_RANGE_ABOVE: f64 = 9.0;
+
+/// The damage dealt to entities which are hit, like in vanilla
+const HIT_DAMAGE: f32 = 5.0;
+/// How many ticks entities burn after being hit, like in vanilla
+const HIT_FIRE_TICKS: i32 = 8 * 20;
+
+/// Besides the fire at the strike, this many fires are set at random blocks next to it
+const EXTRA_FIRES: usize = 4;
+
+/// The metadata index of whether a creeper is charged
+const CREEPER_POWERED_INDEX: u8 = 17;
+
+/// Strikes the ticking chunks during thunderstorms, each with a chance of 1 in
+/// [`STRIKE_CHANCE`] per tick. The lightning hits the highest block of a random column of the
+/// chunk, or a living entity standing under the open sky near it.
+pub async fn tick(world: &Arc<World>, server: &Server) {
+    {
+        let weather = world.weather.lock().await;
+        if !weather.raining || !weather.thundering {
+            return;
+        }
+    }
+
+    let mut columns = Vec::new();
+    {
+        let mut rng = thread_rng();
+        for (chunk, _) in world.level.get_ticking_chunks() {
+            if rng.gen_ratio(1, STRIKE_CHANCE) {
+                columns.push(Vector2::new(
+                    chunk.x * 16 + rng.gen_range(0..16),
+                    chunk.z * 16 + rng.gen_range(0..16),
+                ));
+            }
+        }
+    }
+
+    for column in columns {
+        let target = find_target(world, column).await;
+        let block = BlockPos(Vector3::new(
+            target.x.floor() as i32,
+            target.y.floor() as i32,
+            target.z.floor() as i32,
+        ));
+        if world.is_raining_at(&block).await {
+            strike(world, server, target).await;
+        }
+    }
+}
+
+/// Where the lightning strikes near the column. Living entities under the open sky attract it,
+/// otherwise it hits the highest block.
+async fn find_target(world: &World, column: Vector2<i32>) -> Vector3<f64> {
+    // TODO: Redirect the lightning to lightning rods nearby
+    let top = Vector3::new(
+        f64::from(column.x) + 0.5,
+        f64::from(world.get_top_block(column).await + 1),
+        f64::from(column.z) + 0.5,
+    );
+
+    let mut candidates: Vec<_> = world
+        .entities
+        .read()
+        .await
+        .values()
+        .filter(|entity| entity.get_living_entity().is_some())
+        .map(|entity| {
+            let entity = entity.get_entity();
+            (entity.pos.load(), entity.standing_eye_height)
+        })
+        .collect();
+    candidates.extend(
+        world
+            .players
+            .read()
+            .await
+            .values()
+            .filter(|player| player.gamemode.load() != GameMode::Spectator)
+            .map(|player| {
+                let entity = &player.living_entity.entity;
+                (entity.pos.load(), entity.standing_eye_height)
+            }),
+    );
+
+    let mut targets = Vec::new();
+    for (position, eye_height) in candidates {
+        if (position.x - top.x).abs() > TARGET_RANGE
+            || (position.z - top.z).abs() > TARGET_RANGE
+            || position.y < top.y - TARGET_RANGE
+        {
+            continue;
+        }
+        let eyes = BlockPos(Vector3::new(
+            position.x.floor() as i32,
+            (position.y + f64::from(eye_height)).floor() as i32,
+            position.z.floor() as i32,
+        ));
+        if world.get_light(LightKind::Sky, &eyes).await == light::MAX_LIGHT {
+            targets.push(position);
+        }
+    }
+    targets.choose(&mut thread_rng()).copied().unwrap_or(top)
+}
+
+/// Strikes lightning at the position. The bolt is shown to the players with thunder, fire is set
+/// around the strike and the entities next to it are hit, which turns some mobs into others.
+///
+/// This is also how `/summon lightning_bolt` strikes.
+pub async fn strike(world: &Arc<World>, server: &Server, position: Vector3<f64>) {
+    let entity = server.add_entity(position, EntityType::LIGHTNING_BOLT, world);
+    world
+        .spawn_entity(Arc::new(LightningBolt::new(entity)))
+        .await;
+
+    let (thunder_pitch, impact_pitch) = (
+        0.8 + rand::random::<f32>() * 0.2,
+        0.5 + rand::random::<f32>() * 0.2,
+    );
+    world
+        .play_sound_raw(
+            Sound::EntityLightningBoltThunder as u16,
+            SoundCategory::Weather,
+            &position,
+            10000.0,
+            thunder_pitch,
+        )
+        .await;
+    world
+        .play_sound_raw(
+            Sound::EntityLightningBoltImpact as u16,
+            SoundCategory::Weather,
+            &position,
+            2.0,
+            impact_pitch,
+        )
+        .await;
+
+    if world.game_rules.do_fire_tick
+        && matches!(
+            BASIC_CONFIG.default_difficulty,
+            Difficulty::Normal | Difficulty::Hard
+        )
+    {
+        let block = BlockPos(Vector3::new(
+            position.x.floor() as i32,
+            position.y.floor() as i32,
+            position.z.floor() as i32,
+        ));
+        set_fires(world, server, block).await;
+    }
+
+    hit_entities(world, server, position).await;
+}
+
+/// Sets fire at the strike and at a few random blocks around it, where there is air
+async fn set_fires(world: &World, server: &Server, block: BlockPos) {
+    let mut positions = vec![block];
+    {
+        let mut rng = thread_rng();
+        for _ in 0..EXTRA_FIRES {
+            positions.push(BlockPos(block.0.add_raw(
+                rng.gen_range(-1..=1),
+                rng.gen_range(-1..=1),
+                rng.gen_range(-1..=1),
+            )));
+        }
+    }
+    for position in positions {
+        if world
+            .get_block_state(&position)
+            .await
+            .is_ok_and(|state| state.air)
+        {
+            fire::place_fire(server, world, &position, 0).await;
+        }
+    }
+}
+
+/// Hits the entities around the strike. Like in vanilla, pigs turn into zombified piglins,
+/// villagers into witches and creepers get charged.
+async fn hit_entities(world: &Arc<World>, server: &Server, position: Vector3<f64>) {
+    let in_range = |entity: &Entity| {
+        let entity_position = entity.pos.load();
+        (entity_position.x - position.x).abs() <= HIT_HORIZONTAL_RANGE
+            && (entity_position.z - position.z).abs() <= HIT_HORIZONTAL_RANGE
+            && entity_position.y >= position.y - HIT_RANGE_BELOW
+            && entity_position.y <= position.y + HIT_RANGE_ABOVE
+    };
+
+    let entities: Vec<_> = world
+        .entities
+        .read()
+        .await
+        .values()
+        .filter(|entity| entity.get_living_entity().is_some() && in_range(entity.get_entity()))
+        .cloned()
+        .collect();
+    let peaceful = BASIC_CONFIG.default_difficulty == Difficulty::Peaceful;
+    for entity in entities {
+        let base = entity.get_entity();
+        match base.entity_type {
+            EntityType::PIG if !peaceful => {
+                convert(world, server, base, EntityType::ZOMBIFIED_PIGLIN).await;
+            }
+            EntityType::VILLAGER if !peaceful => {
+                convert(world, server, base, EntityType::WITCH).await;
+            }
+            entity_type => {
+                if entity_type == EntityType::CREEPER {
+                    base.send_meta_data(Metadata::new(
+                        CREEPER_POWERED_INDEX,
+                        MetaDataType::Boolean,
+                        true,
+                    ))
+                    .await;
+                }
+                if let Some(living) = entity.get_living_entity() {
+                    hit(living).await;
+                }
+            }
+        }
+    }
+
+    let players: Vec<_> = world
+        .players
+        .read()
+        .await
+        .values()
+        .filter(|player| {
+            !matches!(
+                player.gamemode.load(),
+                GameMode::Creative | GameMode::Spectator
+            ) && in_range(&player.living_entity.entity)
+        })
+        .cloned()
+        .collect();
+    for player in players {
+        hit(&player.living_entity).await;
+    }
+}
+
+async fn hit(living: &LivingEntity) {
+    living.entity.set_on_fire_for(HIT_FIRE_TICKS).await;
+    living.damage(HIT_DAMAGE, DamageType::LIGHTNING_BOLT).await;
+}
+
+/// Replaces the mob with a new one of the entity type at the same position
+async fn convert(world: &Arc<World>, server: &Server, entity: &Entity, into: EntityType) {
+    let converted = mob::from_type(into, server, entity.pos.load(), world).await;
+    converted
+        .get_entity()
+        .set_rotation(entity.yaw.load(), entity.pitch.load());
+    entity.remove().await;
+    world.spawn_entity(converted).await;
+}
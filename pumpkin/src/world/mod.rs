@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{atomic::Ordering, Arc},
 };
 
@@ -9,47 +9,76 @@ pub mod time;
 use crate::{
     block,
     command::client_suggestions,
-    entity::{player::Player, Entity, EntityBase, EntityId},
+    entity::{
+        self,
+        player::{Player, PLAYER_INFO_ACTIONS},
+        Entity, EntityBase, EntityId,
+    },
     error::PumpkinError,
     plugin::{
+        api::placeholder,
         block::block_break::BlockBreakEvent,
-        player::{player_join::PlayerJoinEvent, player_leave::PlayerLeaveEvent},
+        player::{
+            player_join::PlayerJoinEvent, player_leave::PlayerLeaveEvent,
+            player_respawn::PlayerRespawnEvent,
+        },
         world::{chunk_load::ChunkLoad, chunk_save::ChunkSave, chunk_send::ChunkSend},
     },
     server::Server,
     PLUGIN_MANAGER,
 };
 use border::Worldborder;
-use pumpkin_config::BasicConfiguration;
+use crossbeam::atomic::AtomicCell;
+use game_rules::WorldGameRules;
+use pumpkin_config::{BasicConfiguration, ADVANCED_CONFIG};
 use pumpkin_data::{
+    chunk::Biome,
+    damage::DamageType,
     entity::EntityType,
     particle::Particle,
     sound::{Sound, SoundCategory},
     world::WorldEvent,
 };
 use pumpkin_macros::send_cancellable;
+use pumpkin_nbt::Nbt;
 use pumpkin_protocol::client::play::{
-    CBlockUpdate, CDisguisedChatMessage, CRespawn, CSetBlockDestroyStage, CWorldEvent,
+    CBlockEntityData, CBlockUpdate, CDisguisedChatMessage, CRespawn, CSectionBlocksUpdate,
+    CSetBlockDestroyStage, CUpdateLight, CWorldEvent,
+};
+use pumpkin_protocol::{
+    client::play::CLevelEvent,
+    codec::{identifier::Identifier, var_int::VarInt},
 };
-use pumpkin_protocol::{client::play::CLevelEvent, codec::identifier::Identifier};
 use pumpkin_protocol::{
     client::play::{
-        CChunkData, CGameEvent, CLogin, CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo,
-        CSpawnEntity, GameEvent, PlayerAction,
+        CChunkData, CGameEvent, CLogin, CPlayerInfoUpdate, CRecipeBookAdd, CRemoveEntities,
+        CRemovePlayerInfo, CSpawnEntity, GameEvent,
     },
     ClientPacket,
 };
-use pumpkin_registry::DimensionType;
+use pumpkin_registry::{recipe_displays, DimensionType};
 use pumpkin_util::math::vector2::Vector2;
 use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
 use pumpkin_util::text::{color::NamedColor, TextComponent};
-use pumpkin_world::chunk::ChunkData;
+use pumpkin_util::GameMode;
+use pumpkin_world::chunk::{
+    ticket::{TicketType, ENTITY_TICKING_LEVEL, FULL_CHUNK_LEVEL},
+    ChunkData,
+};
+use pumpkin_world::generation::{END_GENERATOR, NETHER_GENERATOR};
 use pumpkin_world::level::Level;
+use pumpkin_world::world_info::LevelData;
 use pumpkin_world::{
+    biome::climate,
+    block::entity::{block_entity_type_id, create_block_entity, BlockEntity},
     block::registry::{
-        get_block_and_state_by_state_id, get_block_by_state_id, get_state_by_state_id,
+        get_block_and_state_by_state_id, get_block_by_state_id, get_state_by_state_id, Block,
     },
+    block::BlockDirection,
     coordinates::ChunkRelativeBlockCoordinates,
+    light::{self, LightKind},
+    scheduled_tick::TickPriority,
+    WORLD_HEIGHT, WORLD_LOWEST_Y, WORLD_MAX_Y,
 };
 use rand::{thread_rng, Rng};
 use scoreboard::Scoreboard;
@@ -64,9 +93,20 @@ use tokio::{
 pub mod border;
 pub mod bossbar;
 pub mod custom_bossbar;
+pub mod explosion;
+pub mod game_rules;
+pub mod lightning;
+pub mod natural_spawn;
+pub mod portal;
+pub mod precipitation;
+pub mod predicate;
+pub mod random_tick;
+pub mod respawn;
 pub mod scoreboard;
+pub mod sleep;
 pub mod weather;
 
+use sleep::SleepStatus;
 use weather::Weather;
 
 #[derive(Debug, Error)]
@@ -118,30 +158,150 @@ pub struct World {
     pub worldborder: Mutex<Worldborder>,
     /// The world's time, including counting ticks for weather, time cycles and statistics
     pub level_time: Mutex<LevelTime>,
+    /// The game rules of the world, read from its level.dat
+    pub game_rules: WorldGameRules,
     /// The type of dimension the world is in
     pub dimension_type: DimensionType,
+    /// The name of the world, see [`World::name`]
+    name: String,
+    /// Whether the world is one of the worlds from the config, instead of a vanilla dimension
+    named: bool,
     /// The world's weather, including rain and thunder levels
     pub weather: Mutex<Weather>,
+    /// Chunks of which the saved entities have been loaded
+    entity_chunks: Mutex<HashSet<Vector2<i32>>>,
+    /// Newly loaded chunks, of which the saved entities are loaded in the next tick
+    pending_entity_chunks: Mutex<Vec<Vector2<i32>>>,
+    /// How many players were in bed in the last tick, to tell players when it changes
+    pub sleep_status: AtomicCell<SleepStatus>,
+    /// When redstone torches were toggled in the last few seconds, so torches which toggle too
+    /// often burn out
+    pub redstone_torch_toggles: Mutex<Vec<(BlockPos, i64)>>,
     // TODO: entities
 }
 
 impl World {
+    /// Loads one of the vanilla dimensions of the world, which is named after its folder like
+    /// vanilla does: `world` for the overworld, `world_nether` and `world_the_end` for the other
+    /// dimensions.
     #[must_use]
     pub fn load(level: Level, dimension_type: DimensionType) -> Self {
-        Self {
+        let level_name = &level.level_info.level_name;
+        let name = match dimension_type {
+            DimensionType::Overworld | DimensionType::OverworldCaves => level_name.clone(),
+            DimensionType::TheNether => format!("{level_name}_nether"),
+            DimensionType::TheEnd => format!("{level_name}_the_end"),
+        };
+        Self::load_with_name(level, dimension_type, name, false)
+    }
+
+    /// Loads a world from the worlds section of the config, which has its own dimension on the
+    /// client, see [`World::dimension_key`].
+    #[must_use]
+    pub fn load_named(level: Level, dimension_type: DimensionType, name: String) -> Self {
+        Self::load_with_name(level, dimension_type, name, true)
+    }
+
+    fn load_with_name(
+        level: Level,
+        dimension_type: DimensionType,
+        name: String,
+        named: bool,
+    ) -> Self {
+        let weather = Weather::from_level_data(&level.level_info);
+        let world = Self {
             level: Arc::new(level),
             players: Arc::new(RwLock::new(HashMap::new())),
             entities: Arc::new(RwLock::new(HashMap::new())),
             scoreboard: Mutex::new(Scoreboard::new()),
-            worldborder: Mutex::new(Worldborder::new(0.0, 0.0, 29_999_984.0, 0, 0, 0)),
-            level_time: Mutex::new(LevelTime::new()),
+            worldborder: Mutex::new(Worldborder::from_level_data(&level.level_info)),
+            level_time: Mutex::new(LevelTime::from_level_data(&level.level_info)),
+            game_rules: WorldGameRules::from_level_data(&level.level_info),
             dimension_type,
-            weather: Mutex::new(Weather::new()),
+            name,
+            named,
+            weather: Mutex::new(weather),
+            entity_chunks: Mutex::new(HashSet::new()),
+            pending_entity_chunks: Mutex::new(Vec::new()),
+            sleep_status: AtomicCell::new(SleepStatus::default()),
+            redstone_torch_toggles: Mutex::new(Vec::new()),
+        };
+        let configured = ADVANCED_CONFIG.world.has_generator(&world.name);
+        let generator = match world.dimension_type {
+            DimensionType::TheNether if !configured => NETHER_GENERATOR,
+            DimensionType::TheEnd if !configured => END_GENERATOR,
+            _ => ADVANCED_CONFIG.world.get_generator(&world.name),
+        };
+        world.level.set_generator(generator);
+        world
+    }
+
+    /// Returns the name of the world, following the vanilla folder naming:
+    /// `world` for the overworld, `world_nether` and `world_the_end` for the other dimensions.
+    /// Worlds from the config use the name they are listed under.
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Whether the world is one of the worlds from the config, instead of a vanilla dimension
+    #[must_use]
+    pub const fn is_named(&self) -> bool {
+        self.named
+    }
+
+    /// The key the client tells worlds apart by, like `minecraft:the_nether`. Worlds from the
+    /// config are `pumpkin:<name>`, so the client does not mistake them for the vanilla dimension
+    /// of the same type when switching between them.
+    #[must_use]
+    pub fn dimension_key(&self) -> Identifier {
+        if self.named {
+            // Identifiers only allow some characters
+            let path = self
+                .name
+                .to_lowercase()
+                .chars()
+                .map(|c| match c {
+                    'a'..='z' | '0'..='9' | '_' | '-' | '.' | '/' => c,
+                    _ => '_',
+                })
+                .collect();
+            Identifier {
+                namespace: "pumpkin".to_string(),
+                path,
+            }
+        } else {
+            self.dimension_type.name()
         }
     }
 
-    pub async fn save(&self) {
-        self.level.save().await;
+    pub async fn save(&self, server: &Server) {
+        self.save_entities(false).await;
+        self.level.save(self.level_data(server).await).await;
+    }
+
+    /// Saves the entities and the level.dat, but not the chunks, which the autosave writes a
+    /// few at a time instead
+    pub async fn save_data(&self, server: &Server) {
+        self.save_entities(false).await;
+        self.level.save_level_info(self.level_data(server).await);
+    }
+
+    /// The level info with the current weather, border, time and game rules
+    async fn level_data(&self, server: &Server) -> LevelData {
+        let mut level_info = self.level.level_info.clone();
+        self.weather.lock().await.write_level_data(&mut level_info);
+        self.worldborder
+            .lock()
+            .await
+            .write_level_data(&mut level_info);
+        self.level_time
+            .lock()
+            .await
+            .write_level_data(&mut level_info);
+        self.game_rules
+            .write_level_data(&mut level_info, server.players_sleeping_percentage());
+        level_info
     }
 
     /// Broadcasts a packet to all connected players within the world.
@@ -242,6 +402,12 @@ impl World {
         self.play_sound(sound, category, &new_vec).await;
     }
 
+    /// Lets the players see or hear a world event at a block, e.g. the smoke of a dispenser
+    pub async fn sync_world_event(&self, event: WorldEvent, position: BlockPos, data: i32) {
+        self.broadcast_packet_all(&CLevelEvent::new(event as i32, position, data, false))
+            .await;
+    }
+
     pub async fn play_record(&self, record_id: i32, position: BlockPos) {
         self.broadcast_packet_all(&CLevelEvent::new(
             WorldEvent::JukeboxStartsPlaying as i32,
@@ -262,31 +428,112 @@ impl World {
         .await;
     }
 
-    pub async fn tick(&self) {
+    pub async fn tick(self: &Arc<Self>, server: &Server) {
         // world ticks
-        {
+        let new_second = {
             let mut level_time = self.level_time.lock().await;
-            level_time.tick_time();
-            if level_time.world_age % 20 == 0 {
+            level_time.tick_time(self.game_rules.do_daylight_cycle);
+            let new_second = level_time.world_age % 20 == 0;
+            if new_second {
                 level_time.send_time(self).await;
             }
-        }
+            new_second
+        };
 
         {
             let mut weather = self.weather.lock().await;
             weather.tick_weather(self).await;
         };
+        self.worldborder.lock().await.tick(self);
+
+        self.level.tick_tickets().await;
+        self.tick_block_entities(server).await;
+        random_tick::tick(self, server).await;
+        lightning::tick(self, server).await;
+        precipitation::tick(self, server).await;
+        self.run_scheduled_ticks(server).await;
+        self.tick_light().await;
+
+        self.save_entities(true).await;
+        self.load_entities(server).await;
+        natural_spawn::tick(self, server).await;
 
         // player ticks
+        let mut travelling = Vec::new();
+        let mut travelling_end = Vec::new();
         for player in self.players.read().await.values() {
             player.tick().await;
+            player.tick_afk(server).await;
+            let entity = &player.living_entity.entity;
+            if matches!(
+                player.gamemode.load(),
+                GameMode::Creative | GameMode::Spectator
+            ) {
+                entity.extinguish().await;
+            } else {
+                player.living_entity.tick_fire().await;
+            }
+            if new_second {
+                self.damage_outside_border(player).await;
+            }
+            if entity
+                .enter_end_portal(portal::PLAYER_PORTAL_COOLDOWN)
+                .await
+            {
+                travelling_end.push(player.clone());
+                continue;
+            }
+            let wait = if player.gamemode.load() == GameMode::Creative {
+                1
+            } else {
+                portal::PORTAL_WAIT_TICKS
+            };
+            if entity
+                .tick_portal(wait, portal::PLAYER_PORTAL_COOLDOWN)
+                .await
+            {
+                travelling.push(player.clone());
+            }
+        }
+        // Players leave this world, so the players can not be locked
+        for player in travelling {
+            portal::travel(&player, server).await;
+        }
+        for player in travelling_end {
+            portal::travel_end(&player, server).await;
         }
+        let players: Vec<_> = self.players.read().await.values().cloned().collect();
+        for player in players {
+            if player.gamemode.load() != GameMode::Spectator {
+                self.collide_with_blocks(server, player.as_ref()).await;
+            }
+        }
+        sleep::tick(self, server).await;
 
         let entities_to_tick: Vec<_> = self.entities.read().await.values().cloned().collect();
 
         // entities tick
         for entity in entities_to_tick {
-            entity.tick().await;
+            // Entities stand still in chunks no ticket ticks them in, like at the edge of the
+            // view distance
+            if !self
+                .level
+                .is_chunk_entity_ticking(&entity.get_entity().chunk_pos.load())
+            {
+                continue;
+            }
+            let last_pos = entity.get_entity().pos.load();
+            entity.tick(server).await;
+            if let Some(living_entity) = entity.get_living_entity() {
+                living_entity.tick_fire().await;
+            }
+            self.collide_with_blocks(server, entity.as_ref()).await;
+            self.stop_at_border(entity.get_entity(), last_pos).await;
+            // TODO: Send entities other than players through portals
+            entity
+                .get_entity()
+                .tick_portal(portal::PORTAL_WAIT_TICKS, portal::ENTITY_PORTAL_COOLDOWN)
+                .await;
             // this boolean thing prevents deadlocks, since we lock players we can't broadcast packets
             let mut collied_player = None;
             for player in self.players.read().await.values() {
@@ -307,19 +554,246 @@ impl World {
         }
     }
 
+    /// Lets the blocks the entity touches react to it, see
+    /// [`crate::block::pumpkin_block::PumpkinBlock::on_entity_collision`]
+    async fn collide_with_blocks(&self, server: &Server, entity: &dyn EntityBase) {
+        let bounding_box = entity.get_entity().bounding_box.load();
+        let min = bounding_box.min.add_raw(1.0e-3, 1.0e-3, 1.0e-3);
+        let max = bounding_box.max.add_raw(-1.0e-3, -1.0e-3, -1.0e-3);
+        for x in min.x.floor() as i32..=max.x.floor() as i32 {
+            for y in min.y.floor() as i32..=max.y.floor() as i32 {
+                for z in min.z.floor() as i32..=max.z.floor() as i32 {
+                    let position = BlockPos(Vector3::new(x, y, z));
+                    let Ok(state_id) = self.get_block_state_id(&position).await else {
+                        continue;
+                    };
+                    let Some(block) = get_block_by_state_id(state_id) else {
+                        continue;
+                    };
+                    server
+                        .block_registry
+                        .on_entity_collision(server, self, block, state_id, position, entity)
+                        .await;
+                }
+            }
+        }
+    }
+
+    /// Damages players who are too far outside of the world border, once a second
+    async fn damage_outside_border(&self, player: &Player) {
+        if matches!(
+            player.gamemode.load(),
+            GameMode::Creative | GameMode::Spectator
+        ) {
+            return;
+        }
+        let position = player.living_entity.entity.pos.load();
+        let damage = self
+            .worldborder
+            .lock()
+            .await
+            .damage_at(position.x, position.z);
+        if let Some(damage) = damage {
+            player
+                .living_entity
+                .damage(damage, DamageType::OUTSIDE_BORDER)
+                .await;
+        }
+    }
+
+    /// Moves entities which just crossed the world border back onto it and stops them.
+    /// Entities which were outside already, e.g. because the border shrank, may move back in.
+    async fn stop_at_border(&self, entity: &Entity, last_pos: Vector3<f64>) {
+        let position = entity.pos.load();
+        let destination = {
+            let border = self.worldborder.lock().await;
+            if border.contains(position.x, position.z) || !border.contains(last_pos.x, last_pos.z) {
+                return;
+            }
+            border.clamp(position)
+        };
+        entity.velocity.store(Vector3::new(0.0, 0.0, 0.0));
+        entity
+            .teleport(destination, entity.yaw.load(), entity.pitch.load())
+            .await;
+    }
+
     /// Gets the y position of the first non air block from the top down
     pub async fn get_top_block(&self, position: Vector2<i32>) -> i32 {
-        for y in (-64..=319).rev() {
-            let pos = BlockPos(Vector3::new(position.x, y, position.z));
-            let block = self.get_block_state(&pos).await;
-            if let Ok(block) = block {
-                if block.air {
-                    continue;
+        let height = self
+            .column_height(position, ChunkData::world_surface_height)
+            .await;
+        // An empty column has no top block, so the top of the world is used
+        if height == i32::from(WORLD_LOWEST_Y) {
+            return i32::from(WORLD_MAX_Y) - 1;
+        }
+        height - 1
+    }
+
+    /// Reads the y position above the highest block of the column from a heightmap of its chunk
+    async fn column_height(
+        &self,
+        position: Vector2<i32>,
+        heightmap: impl FnOnce(&ChunkData, u8, u8) -> i32,
+    ) -> i32 {
+        let chunk = self
+            .receive_chunk(Vector2::new(position.x >> 4, position.z >> 4))
+            .await
+            .0;
+        let chunk = chunk.read().await;
+        heightmap(&chunk, (position.x & 15) as u8, (position.z & 15) as u8)
+    }
+
+    /// Gets the y position players spawn at in the given column.
+    /// The world generator can choose it, otherwise it is on top of the highest block
+    pub async fn get_spawn_height(&self, position: Vector2<i32>) -> i32 {
+        if let Some(y) = self.level.world_gen().get_spawn_height(position) {
+            return y;
+        }
+        self.get_top_block(position).await + 1
+    }
+
+    /// Gets the position players spawn at when they have no respawn point
+    pub async fn get_spawn_position(&self) -> Vector3<f64> {
+        let info = &self.level.level_info;
+        let height = self
+            .get_spawn_height(Vector2::new(info.spawn_x, info.spawn_z))
+            .await;
+        Vector3::new(
+            f64::from(info.spawn_x),
+            f64::from(height),
+            f64::from(info.spawn_z),
+        )
+    }
+
+    /// Places the spawn ticket around the spawn point, see
+    /// [`game_rules::WorldGameRules::spawn_chunk_radius`], and loads the chunks it keeps
+    /// loaded, so they are ready before the first player joins
+    pub async fn load_spawn_chunks(&self) {
+        let radius = self.game_rules.spawn_chunk_radius;
+        if radius == 0 {
+            return;
+        }
+        let info = &self.level.level_info;
+        let center = Vector2::new(info.spawn_x >> 4, info.spawn_z >> 4);
+        let level = ENTITY_TICKING_LEVEL.saturating_sub(radius);
+        self.level.add_ticket(center, TicketType::Spawn, level);
+
+        let reach = i32::from(FULL_CHUNK_LEVEL - level);
+        let chunks: Vec<_> = (-reach..=reach)
+            .flat_map(|x| (-reach..=reach).map(move |z| Vector2::new(center.x + x, center.z + z)))
+            .collect();
+        log::info!("Loading {} spawn chunks", chunks.len());
+        let mut receiver = self.receive_chunks(chunks);
+        let mut loaded = Vec::new();
+        while let Some((chunk, first_load)) = receiver.recv().await {
+            if first_load {
+                loaded.push(chunk.read().await.position);
+            }
+        }
+        self.pending_entity_chunks.lock().await.extend(loaded);
+    }
+
+    /// Gets the y position of the highest block which blocks motion or contains a fluid, or `None`
+    /// if the column is empty
+    pub async fn get_top_solid_block(&self, position: Vector2<i32>) -> Option<i32> {
+        let height = self
+            .column_height(position, ChunkData::motion_blocking_height)
+            .await;
+        (height > i32::from(WORLD_LOWEST_Y)).then_some(height - 1)
+    }
+
+    /// Walks along a ray through the block grid and returns the first block with a collision shape.
+    ///
+    /// # Arguments
+    /// - `start`: The origin of the ray.
+    /// - `direction`: The direction of the ray, does not need to be normalized.
+    /// - `max_distance`: The maximum distance in blocks to check.
+    ///
+    /// # Returns
+    /// The position of the hit block, or `None` if nothing was hit within range.
+    pub async fn raycast_block(
+        &self,
+        start: Vector3<f64>,
+        direction: Vector3<f64>,
+        max_distance: f64,
+    ) -> Option<BlockPos> {
+        self.raycast_block_matching(start, direction, max_distance, |state_id| {
+            get_state_by_state_id(state_id)
+                .is_some_and(|state| !state.air && !state.collision_shapes.is_empty())
+        })
+        .await
+        .map(|(hit, _)| hit)
+    }
+
+    /// Walks along a ray through the block grid and returns the first block state matching the
+    /// predicate, e.g. to also hit fluids.
+    ///
+    /// # Returns
+    /// The position of the hit block and of the block the ray passed before it, or `None` if
+    /// nothing was hit within range.
+    pub async fn raycast_block_matching(
+        &self,
+        start: Vector3<f64>,
+        direction: Vector3<f64>,
+        max_distance: f64,
+        predicate: impl Fn(u16) -> bool + Send,
+    ) -> Option<(BlockPos, BlockPos)> {
+        /// Returns the step direction, the distance to the first boundary and the distance
+        /// between boundaries along one axis
+        fn axis(start: f64, block: i32, direction: f64) -> (i32, f64, f64) {
+            if direction > 0.0 {
+                (
+                    1,
+                    (f64::from(block) + 1.0 - start) / direction,
+                    1.0 / direction,
+                )
+            } else if direction < 0.0 {
+                (
+                    -1,
+                    (start - f64::from(block)) / -direction,
+                    -1.0 / direction,
+                )
+            } else {
+                (0, f64::INFINITY, f64::INFINITY)
+            }
+        }
+
+        let direction = direction.normalize();
+        let mut block = Vector3::new(
+            start.x.floor() as i32,
+            start.y.floor() as i32,
+            start.z.floor() as i32,
+        );
+        let mut previous = block;
+        let (step_x, mut t_max_x, t_delta_x) = axis(start.x, block.x, direction.x);
+        let (step_y, mut t_max_y, t_delta_y) = axis(start.y, block.y, direction.y);
+        let (step_z, mut t_max_z, t_delta_z) = axis(start.z, block.z, direction.z);
+
+        let mut distance = 0.0;
+        while distance <= max_distance {
+            if let Ok(state_id) = self.get_block_state_id(&BlockPos(block)).await {
+                if predicate(state_id) {
+                    return Some((BlockPos(block), BlockPos(previous)));
                 }
             }
-            return y;
+
+            previous = block;
+            if t_max_x < t_max_y && t_max_x < t_max_z {
+                block.x += step_x;
+                distance = t_max_x;
+                t_max_x += t_delta_x;
+            } else if t_max_y < t_max_z {
+                block.y += step_y;
+                distance = t_max_y;
+                t_max_y += t_delta_y;
+            } else {
+                block.z += step_z;
+                distance = t_max_z;
+                t_max_z += t_delta_z;
+            }
         }
-        319
+        None
     }
 
     #[expect(clippy::too_many_lines)]
@@ -329,8 +803,14 @@ impl World {
         player: Arc<Player>,
         server: &Server,
     ) {
-        let dimensions: Vec<Identifier> =
+        let mut dimensions: Vec<Identifier> =
             server.dimensions.iter().map(DimensionType::name).collect();
+        // Worlds from the config have their own dimension keys
+        for world in server.worlds.read().await.iter() {
+            if world.is_named() {
+                dimensions.push(world.dimension_key());
+            }
+        }
 
         // This code follows the vanilla packet order
         let entity_id = player.entity_id();
@@ -348,14 +828,14 @@ impl World {
                 entity_id,
                 base_config.hardcore,
                 &dimensions,
-                base_config.max_players.into(),
+                server.max_players().into(),
                 base_config.view_distance.get().into(), //  TODO: view distance
                 base_config.simulation_distance.get().into(), // TODO: sim view dinstance
                 false,
                 true,
                 false,
                 (self.dimension_type as u8).into(),
-                self.dimension_type.name(),
+                self.dimension_key(),
                 0, // seed
                 gamemode as u8,
                 base_config.default_gamemode as i8,
@@ -371,37 +851,21 @@ impl World {
         player.send_permission_lvl_update().await;
         client_suggestions::send_c_commands_packet(&player, &server.command_dispatcher).await;
         // teleport
-        let info = &self.level.level_info;
-        let mut position = Vector3::new(f64::from(info.spawn_x), 120.0, f64::from(info.spawn_z));
-        let yaw = info.spawn_angle;
+        let position = self.get_spawn_position().await;
+        let yaw = self.level.level_info.spawn_angle;
         let pitch = 10.0;
 
-        let top = self
-            .get_top_block(Vector2::new(position.x as i32, position.z as i32))
-            .await;
-        position.y = f64::from(top + 1);
-
         log::debug!("Sending player teleport to {}", player.gameprofile.name);
         player.request_teleport(position, yaw, pitch).await;
 
         player.living_entity.last_pos.store(position);
 
-        let gameprofile = &player.gameprofile;
         // first send info update to our new player, So he can see his Skin
         // also send his info to everyone else
         log::debug!("Broadcasting player info for {}", player.gameprofile.name);
         self.broadcast_packet_all(&CPlayerInfoUpdate::new(
-            0x01 | 0x08,
-            &[pumpkin_protocol::client::play::Player {
-                uuid: gameprofile.id,
-                actions: vec![
-                    PlayerAction::AddPlayer {
-                        name: &gameprofile.name,
-                        properties: &gameprofile.properties,
-                    },
-                    PlayerAction::UpdateListed(true),
-                ],
-            }],
+            PLAYER_INFO_ACTIONS,
+            &[player.player_info().await],
         ))
         .await;
         player.send_client_information().await;
@@ -410,26 +874,15 @@ impl World {
         let mut entries = Vec::new();
         {
             let current_players = self.players.read().await;
-            for (_, playerr) in current_players
-                .iter()
-                .filter(|(c, _)| **c != player.gameprofile.id)
-            {
-                let gameprofile = &playerr.gameprofile;
-                entries.push(pumpkin_protocol::client::play::Player {
-                    uuid: gameprofile.id,
-                    actions: vec![
-                        PlayerAction::AddPlayer {
-                            name: &gameprofile.name,
-                            properties: &gameprofile.properties,
-                        },
-                        PlayerAction::UpdateListed(true),
-                    ],
-                });
+            for (_, playerr) in current_players.iter().filter(|(c, p)| {
+                **c != player.gameprofile.id && !p.vanished.load(Ordering::Relaxed)
+            }) {
+                entries.push(playerr.player_info().await);
             }
             log::debug!("Sending player info to {}", player.gameprofile.name);
             player
                 .client
-                .send_packet(&CPlayerInfoUpdate::new(0x01 | 0x08, &entries))
+                .send_packet(&CPlayerInfoUpdate::new(PLAYER_INFO_ACTIONS, &entries))
                 .await;
         };
 
@@ -455,7 +908,13 @@ impl World {
         .await;
         // spawn players for our client
         let id = player.gameprofile.id;
-        for (_, existing_player) in self.players.read().await.iter().filter(|c| c.0 != &id) {
+        for (_, existing_player) in self
+            .players
+            .read()
+            .await
+            .iter()
+            .filter(|c| c.0 != &id && !c.1.vanished.load(Ordering::Relaxed))
+        {
             let entity = &existing_player.living_entity.entity;
             let pos = entity.pos.load();
             let gameprofile = &existing_player.gameprofile;
@@ -529,6 +988,19 @@ impl World {
         //     }
         // }
 
+        // Send teams and sidebars created by plugins
+        server.plugin_scoreboard.send_to(&player).await;
+
+        // Send the recipes of data packs and plugins
+        player
+            .client
+            .send_packet(&CRecipeBookAdd::new(&recipe_displays(), true))
+            .await;
+
+        if ADVANCED_CONFIG.tab_list.enabled {
+            player.send_tab_list().await;
+        }
+
         player.send_mobs(self).await;
     }
 
@@ -554,22 +1026,24 @@ impl World {
 
         let entity = &player.living_entity.entity;
 
-        self.broadcast_packet_except(
-            &[player.gameprofile.id],
-            // TODO: add velo
-            &CSpawnEntity::new(
-                entity.entity_id.into(),
-                player.gameprofile.id,
-                i32::from(EntityType::PLAYER.id).into(),
-                position,
-                pitch,
-                yaw,
-                yaw,
-                0.into(),
-                Vector3::new(0.0, 0.0, 0.0),
-            ),
-        )
-        .await;
+        if !player.vanished.load(Ordering::Relaxed) {
+            self.broadcast_packet_except(
+                &[player.gameprofile.id],
+                // TODO: add velo
+                &CSpawnEntity::new(
+                    entity.entity_id.into(),
+                    player.gameprofile.id,
+                    i32::from(EntityType::PLAYER.id).into(),
+                    position,
+                    pitch,
+                    yaw,
+                    yaw,
+                    0.into(),
+                    Vector3::new(0.0, 0.0, 0.0),
+                ),
+            )
+            .await;
+        }
         player.send_client_information().await;
 
         chunker::player_join(player).await;
@@ -578,9 +1052,17 @@ impl World {
         player.set_health(20.0).await;
     }
 
-    pub async fn respawn_player(&self, player: &Arc<Player>, alive: bool) {
+    /// Respawns the player in this world at the position found by
+    /// [`respawn::find_respawn`]
+    pub async fn respawn_player(
+        self: &Arc<Self>,
+        player: &Arc<Player>,
+        alive: bool,
+        respawn: &respawn::Respawn,
+    ) {
         let last_pos = player.living_entity.last_pos.load();
-        let death_dimension = player.world().await.dimension_type.name();
+        let death_world = player.world().await;
+        let death_dimension = death_world.dimension_key();
         let death_location = BlockPos(Vector3::new(
             last_pos.x.round() as i32,
             last_pos.y.round() as i32,
@@ -589,13 +1071,16 @@ impl World {
 
         let data_kept = u8::from(alive);
 
-        // TODO: switch world in player entity to new world
+        let changes_world = !Arc::ptr_eq(self, &death_world);
+        if changes_world {
+            player.move_to_world(self.clone()).await;
+        }
 
         player
             .client
             .send_packet(&CRespawn::new(
                 (self.dimension_type as u8).into(),
-                self.dimension_type.name(),
+                self.dimension_key(),
                 0, // seed
                 player.gamemode.load() as u8,
                 player.gamemode.load() as i8,
@@ -614,15 +1099,19 @@ impl World {
         player.send_permission_lvl_update().await;
 
         // teleport
-        let info = &self.level.level_info;
-        let mut position = Vector3::new(f64::from(info.spawn_x), 120.0, f64::from(info.spawn_z));
-        let yaw = info.spawn_angle;
-        let pitch = 10.0;
-
-        let top = self
-            .get_top_block(Vector2::new(position.x as i32, position.z as i32))
+        let event = PlayerRespawnEvent::new(
+            player.clone(),
+            respawn.reason,
+            respawn.position,
+            respawn.yaw,
+            10.0,
+        );
+        let event = PLUGIN_MANAGER
+            .lock()
+            .await
+            .fire::<PlayerRespawnEvent>(event)
             .await;
-        position.y = f64::from(top + 1);
+        let (position, yaw, pitch) = (event.position, event.yaw, event.pitch);
 
         log::debug!("Sending player teleport to {}", player.gameprofile.name);
         player.request_teleport(position, yaw, pitch).await;
@@ -631,6 +1120,9 @@ impl World {
 
         // TODO: difficulty, exp bar, status effect
 
+        if changes_world {
+            player.send_world_players(self).await;
+        }
         self.send_world_info(player, position, yaw, pitch).await;
     }
 
@@ -716,6 +1208,12 @@ impl World {
                             continue 'main;
                         }
                     }}
+                    event
+                        .world
+                        .pending_entity_chunks
+                        .lock()
+                        .await
+                        .push(position);
                     (event.world, event.chunk)
                 } else {
                     (world, chunk)
@@ -884,7 +1382,7 @@ impl World {
         tokio::spawn(async move {
             let msg_comp = TextComponent::translate(
                 "multiplayer.player.joined",
-                [TextComponent::text(player.gameprofile.name.clone())],
+                [player.display_name().await],
             )
             .color_named(NamedColor::Yellow);
             let event = PlayerJoinEvent::new(player.clone(), msg_comp);
@@ -896,12 +1394,14 @@ impl World {
                 .await;
 
             if !event.cancelled {
+                let join_message =
+                    placeholder::resolve_component(event.join_message, Some(&*player)).await;
                 let current_players = current_players.clone();
                 let players = current_players.read().await;
                 for player in players.values() {
-                    player.send_system_message(&event.join_message).await;
+                    player.send_system_message(&join_message).await;
                 }
-                log::info!("{}", event.join_message.clone().to_pretty_console());
+                log::info!("{}", join_message.to_pretty_console());
             }
         });
     }
@@ -919,7 +1419,8 @@ impl World {
     /// # Arguments
     ///
     /// * `player`: A reference to the `Player` object to be removed.
-    /// * `fire_event`: A boolean flag indicating whether to fire a `PlayerLeaveEvent` event.
+    /// * `fire_event`: A boolean flag indicating whether to fire a `PlayerLeaveEvent` event. It is
+    ///   never fired for vanished players.
     ///
     /// # Notes
     ///
@@ -940,12 +1441,11 @@ impl World {
         self.broadcast_packet_all(&CRemoveEntities::new(&[player.entity_id().into()]))
             .await;
 
-        if fire_event {
-            let msg_comp = TextComponent::translate(
-                "multiplayer.player.left",
-                [TextComponent::text(player.gameprofile.name.clone())],
-            )
-            .color_named(NamedColor::Yellow);
+        // Nobody should notice vanished players leaving
+        if fire_event && !player.vanished.load(Ordering::Relaxed) {
+            let msg_comp =
+                TextComponent::translate("multiplayer.player.left", [player.display_name().await])
+                    .color_named(NamedColor::Yellow);
             let event = PlayerLeaveEvent::new(player.clone(), msg_comp);
 
             let event = PLUGIN_MANAGER
@@ -955,11 +1455,13 @@ impl World {
                 .await;
 
             if !event.cancelled {
+                let leave_message =
+                    placeholder::resolve_component(event.leave_message, Some(&*player)).await;
                 let players = self.players.read().await;
                 for player in players.values() {
-                    player.send_system_message(&event.leave_message).await;
+                    player.send_system_message(&leave_message).await;
                 }
-                log::info!("{}", event.leave_message.clone().to_pretty_console());
+                log::info!("{}", leave_message.to_pretty_console());
             }
         }
     }
@@ -975,12 +1477,106 @@ impl World {
     /// * `living_entity`: A `Arc<LivingEntity>` reference to the living entity object.
     pub async fn spawn_entity(&self, entity: Arc<dyn EntityBase>) {
         let base_entity = entity.get_entity();
-        self.broadcast_packet_all(&base_entity.create_spawn_packet())
-            .await;
+        if let Some(orb) = entity.get_experience_orb() {
+            self.broadcast_packet_all(&orb.spawn_packet()).await;
+        } else {
+            self.broadcast_packet_all(&base_entity.create_spawn_packet(entity.spawn_data()))
+                .await;
+        }
         let mut current_living_entities = self.entities.write().await;
         current_living_entities.insert(base_entity.entity_uuid, entity);
     }
 
+    /// Loads the saved entities of the chunks loaded since the last tick
+    async fn load_entities(self: &Arc<Self>, server: &Server) {
+        let mut chunks: HashSet<_> = std::mem::take(&mut *self.pending_entity_chunks.lock().await)
+            .into_iter()
+            .collect();
+        // Entities can also move into chunks which have only been loaded for a short time
+        for entity in self.entities.read().await.values() {
+            chunks.insert(entity.get_entity().chunk_pos.load());
+        }
+        {
+            let mut entity_chunks = self.entity_chunks.lock().await;
+            chunks
+                .retain(|chunk| self.level.is_chunk_loaded(chunk) && entity_chunks.insert(*chunk));
+        }
+
+        for chunk in chunks {
+            for mut nbt in self.level.read_entities(&chunk) {
+                if let Some(uuid) = entity::uuid_from_nbt(&nbt) {
+                    if self.entities.read().await.contains_key(&uuid) {
+                        continue;
+                    }
+                }
+                if entity::spawn_from_nbt(server, self, &mut nbt)
+                    .await
+                    .is_none()
+                {
+                    log::debug!(
+                        "Skipped loading unsupported entity {:?} in chunk {:?}",
+                        nbt.get_string("id"),
+                        chunk
+                    );
+                }
+            }
+        }
+    }
+
+    /// Writes the entities to the chunks they are in. When `unloaded_only` is set, only the
+    /// entities in unloaded chunks are written, which are then removed from the world.
+    ///
+    /// Players are not included, they are saved separately.
+    async fn save_entities(&self, unloaded_only: bool) {
+        let mut chunks: HashMap<Vector2<i32>, Vec<Arc<dyn EntityBase>>> = HashMap::new();
+        for entity in self.entities.read().await.values() {
+            chunks
+                .entry(entity.get_entity().chunk_pos.load())
+                .or_default()
+                .push(entity.clone());
+        }
+
+        let mut entity_chunks = self.entity_chunks.lock().await;
+        for chunk in entity_chunks.iter() {
+            chunks.entry(*chunk).or_default();
+        }
+
+        for (chunk, entities) in chunks {
+            let unload = !self.level.is_chunk_loaded(&chunk);
+            if unloaded_only && !unload {
+                continue;
+            }
+
+            let mut saved = Vec::new();
+            for entity in &entities {
+                if let Some(nbt) = entity.save_nbt().await {
+                    saved.push(nbt);
+                }
+            }
+
+            if entity_chunks.contains(&chunk) {
+                self.level.write_entities(&chunk, &saved);
+            } else if !saved.is_empty() {
+                // The saved entities of the chunk were never loaded, so keep them
+                let mut entities = self.level.read_entities(&chunk);
+                entities.retain(|entity| {
+                    !saved
+                        .iter()
+                        .any(|nbt| nbt.get_int_array("UUID") == entity.get_int_array("UUID"))
+                });
+                entities.extend(saved);
+                self.level.write_entities(&chunk, &entities);
+            }
+
+            if unload {
+                entity_chunks.remove(&chunk);
+                for entity in entities {
+                    self.remove_entity(entity.get_entity()).await;
+                }
+            }
+        }
+    }
+
     pub async fn remove_entity(&self, entity: &Entity) {
         self.entities.write().await.remove(&entity.entity_uuid);
         self.broadcast_packet_all(&CRemoveEntities::new(&[entity.entity_id.into()]))
@@ -997,28 +1593,255 @@ impl World {
 
     /// Sets a block
     pub async fn set_block_state(&self, position: &BlockPos, block_state_id: u16) -> u16 {
+        let replaced_block_state_id = self.replace_block_state(position, block_state_id).await;
+        self.broadcast_packet_all(&CBlockUpdate::new(
+            position,
+            i32::from(block_state_id).into(),
+        ))
+        .await;
+
+        replaced_block_state_id
+    }
+
+    /// Sets many blocks at once. The players get one packet for each chunk section instead of
+    /// one for each block, and the light is updated as usual. Returns the replaced block state
+    /// ids, in the same order.
+    pub async fn set_block_states(&self, blocks: &[(BlockPos, u16)]) -> Vec<u16> {
+        let mut replaced = Vec::with_capacity(blocks.len());
+        let mut sections: HashMap<Vector3<i32>, Vec<(Vector3<u8>, u16)>> = HashMap::new();
+        for (position, block_state_id) in blocks {
+            replaced.push(self.replace_block_state(position, *block_state_id).await);
+            let section = Vector3::new(
+                position.0.x.div_euclid(16),
+                position.0.y.div_euclid(16),
+                position.0.z.div_euclid(16),
+            );
+            // The remainders are always from 0 to 15
+            let relative = Vector3::new(
+                position.0.x.rem_euclid(16) as u8,
+                position.0.y.rem_euclid(16) as u8,
+                position.0.z.rem_euclid(16) as u8,
+            );
+            sections
+                .entry(section)
+                .or_default()
+                .push((relative, *block_state_id));
+        }
+        for (section, blocks) in sections {
+            self.broadcast_packet_all(&CSectionBlocksUpdate::new(section, &blocks))
+                .await;
+        }
+        replaced
+    }
+
+    /// Changes the block in the chunk, without telling the players. Returns the replaced block
+    /// state id.
+    async fn replace_block_state(&self, position: &BlockPos, block_state_id: u16) -> u16 {
         let (chunk_coordinate, relative_coordinates) = position.chunk_and_chunk_relative_position();
 
         // Since we divide by 16 remnant can never exceed u8
         let relative = ChunkRelativeBlockCoordinates::from(relative_coordinates);
 
         let chunk = self.receive_chunk(chunk_coordinate).await.0;
-        let replaced_block_state_id = chunk.read().await.subchunks.get_block(relative).unwrap();
-        chunk
-            .write()
-            .await
-            .subchunks
-            .set_block(relative, block_state_id);
+        let replaced_block_state_id = {
+            let mut chunk = chunk.write().await;
+            let replaced_block_state_id = chunk.subchunks.get_block(relative).unwrap();
+            chunk.set_block(relative, block_state_id);
+
+            // Block entities belong to the block, so they are kept when only its state changes
+            let block_id = |state_id| get_block_by_state_id(state_id).map(|block| block.id);
+            if block_id(replaced_block_state_id) != block_id(block_state_id) {
+                chunk.remove_block_entity(position);
+                if let Some(block_entity) = create_block_entity(block_state_id, *position) {
+                    chunk.add_block_entity(block_entity);
+                }
+            }
+            replaced_block_state_id
+        };
 
-        self.broadcast_packet_all(&CBlockUpdate::new(
-            position,
-            i32::from(block_state_id).into(),
-        ))
-        .await;
+        if light::changes_light(replaced_block_state_id, block_state_id) {
+            self.level.queue_light_update(*position);
+        }
 
         replaced_block_state_id
     }
 
+    /// Whether the chunk of the position is loaded. Blocks in other chunks should not be changed
+    /// by things like flowing fluids, as reading them would load or generate their chunk.
+    #[must_use]
+    pub fn is_loaded(&self, position: &BlockPos) -> bool {
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        self.level.get_loaded_chunk(&chunk).is_some()
+    }
+
+    /// Lets the blocks next to the position react to a change of the block there, which e.g.
+    /// makes fluids flow again. The neighbors are updated in vanilla's order.
+    pub async fn update_neighbors(&self, server: &Server, position: &BlockPos) {
+        for direction in BlockDirection::update_order() {
+            let neighbor = BlockPos(position.0 + direction.to_offset());
+            if !(i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&neighbor.0.y)
+                || !self.is_loaded(&neighbor)
+            {
+                continue;
+            }
+            if let Ok(block) = self.get_block(&neighbor).await {
+                server
+                    .block_registry
+                    .on_neighbor_update(server, self, block, neighbor, *position)
+                    .await;
+            }
+        }
+    }
+
+    /// Updates the blocks next to the changed positions after many blocks were set at once,
+    /// each of them only once. Blocks which are next to several changed positions are updated
+    /// for the first of them.
+    pub async fn update_neighbors_bulk(&self, server: &Server, positions: &[BlockPos]) {
+        let mut updated = HashSet::new();
+        for position in positions {
+            for direction in BlockDirection::update_order() {
+                let neighbor = BlockPos(position.0 + direction.to_offset());
+                if !(i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&neighbor.0.y)
+                    || !self.is_loaded(&neighbor)
+                    || !updated.insert(neighbor)
+                {
+                    continue;
+                }
+                if let Ok(block) = self.get_block(&neighbor).await {
+                    server
+                        .block_registry
+                        .on_neighbor_update(server, self, block, neighbor, *position)
+                        .await;
+                }
+            }
+        }
+    }
+
+    /// Schedules a tick of the block at the position in `delay` ticks, e.g. to let a fluid flow
+    /// one step further. The tick is skipped if the block was replaced by then.
+    pub fn schedule_block_tick(&self, position: BlockPos, block: &Block, delay: u64) {
+        self.schedule_block_tick_with_priority(position, block, delay, TickPriority::Normal);
+    }
+
+    /// Like [`World::schedule_block_tick`], but ticks due in the same tick run by their priority
+    pub fn schedule_block_tick_with_priority(
+        &self,
+        position: BlockPos,
+        block: &Block,
+        delay: u64,
+        priority: TickPriority,
+    ) {
+        self.level
+            .scheduled_ticks
+            .schedule(position, block.id, delay, priority);
+    }
+
+    /// Whether a tick of the block at the position is pending
+    pub fn is_block_tick_scheduled(&self, position: BlockPos, block: &Block) -> bool {
+        self.level.scheduled_ticks.is_scheduled(position, block.id)
+    }
+
+    /// Runs the scheduled block ticks which are due in the ticking chunks, see
+    /// [`World::schedule_block_tick`]
+    async fn run_scheduled_ticks(&self, server: &Server) {
+        for (position, block_id) in self.level.due_block_ticks() {
+            let Ok(state_id) = self.get_block_state_id(&position).await else {
+                continue;
+            };
+            let Some(block) = get_block_by_state_id(state_id) else {
+                continue;
+            };
+            if block.id != block_id {
+                continue;
+            }
+            server
+                .block_registry
+                .on_scheduled_tick(server, self, block, state_id, position)
+                .await;
+        }
+    }
+
+    /// Ticks the block entities, then the blocks whose block entities need it, like hoppers
+    async fn tick_block_entities(&self, server: &Server) {
+        for position in self.level.tick_block_entities().await {
+            let Ok(state_id) = self.get_block_state_id(&position).await else {
+                continue;
+            };
+            let Some(block) = get_block_by_state_id(state_id) else {
+                continue;
+            };
+            server
+                .block_registry
+                .on_block_entity_tick(server, self, block, state_id, position)
+                .await;
+        }
+    }
+
+    /// Applies a batch of the queued light updates and sends the changed light to the players.
+    async fn tick_light(&self) {
+        for (chunk_coordinate, sections) in self.level.tick_light().await {
+            let Some(chunk) = self.level.get_loaded_chunk(&chunk_coordinate) else {
+                continue;
+            };
+            let chunk = chunk.read().await;
+            self.broadcast_packet_all(&CUpdateLight::new(chunk_coordinate, &chunk.light, sections))
+                .await;
+        }
+    }
+
+    /// Reads the block entity at a position. Returns `None` if there is no block entity.
+    pub async fn get_block_entity<R>(
+        &self,
+        position: &BlockPos,
+        read: impl FnOnce(&dyn BlockEntity) -> R + Send,
+    ) -> Option<R> {
+        let (chunk_coordinate, _) = position.chunk_and_chunk_relative_position();
+        let chunk = self.receive_chunk(chunk_coordinate).await.0;
+        let chunk = chunk.read().await;
+        chunk.get_block_entity(position).map(read)
+    }
+
+    /// Removes the block entity at a position without changing the block, e.g. to drop its items
+    /// before the block is broken.
+    pub async fn remove_block_entity(&self, position: &BlockPos) -> Option<Box<dyn BlockEntity>> {
+        let (chunk_coordinate, _) = position.chunk_and_chunk_relative_position();
+        let chunk = self.receive_chunk(chunk_coordinate).await.0;
+        let mut chunk = chunk.write().await;
+        chunk.remove_block_entity(position)
+    }
+
+    /// Changes the block entity at a position and sends its new data to all players. Returns
+    /// `None` if there is no block entity.
+    pub async fn update_block_entity<R>(
+        &self,
+        position: &BlockPos,
+        update: impl FnOnce(&mut dyn BlockEntity) -> R + Send,
+    ) -> Option<R> {
+        let (chunk_coordinate, _) = position.chunk_and_chunk_relative_position();
+        let chunk = self.receive_chunk(chunk_coordinate).await.0;
+        let (result, packet) = {
+            let mut chunk = chunk.write().await;
+            let block_entity = chunk.get_block_entity_mut(position)?;
+            let result = update(block_entity.as_mut());
+            let packet = block_entity.client_nbt().and_then(|nbt| {
+                let id = block_entity_type_id(block_entity.id())?;
+                Some(CBlockEntityData::new(
+                    *position,
+                    VarInt(id as i32),
+                    Nbt::new(String::new(), nbt)
+                        .write_unnamed()
+                        .to_vec()
+                        .into_boxed_slice(),
+                ))
+            });
+            (result, packet)
+        };
+        if let Some(packet) = packet {
+            self.broadcast_packet_all(&packet).await;
+        }
+        Some(result)
+    }
+
     // Stream the chunks (don't collect them and then do stuff with them)
     /// Important: must be called from an async function (or changed to accept a tokio runtime
     /// handle)
@@ -1060,6 +1883,31 @@ impl World {
         position: &BlockPos,
         cause: Option<Arc<Player>>,
         drop: bool,
+    ) {
+        self.destroy_block(server, position, cause, drop, true)
+            .await;
+    }
+
+    /// Breaks a block like [`World::break_block`], but leaves replacing it and updating its
+    /// neighbors to the caller, like `fill` does when it sets all blocks at once.
+    pub async fn break_block_without_update(
+        self: &Arc<Self>,
+        server: &Server,
+        position: &BlockPos,
+        cause: Option<Arc<Player>>,
+        drop: bool,
+    ) {
+        self.destroy_block(server, position, cause, drop, false)
+            .await;
+    }
+
+    async fn destroy_block(
+        self: &Arc<Self>,
+        server: &Server,
+        position: &BlockPos,
+        cause: Option<Arc<Player>>,
+        drop: bool,
+        update: bool,
     ) {
         let block = self.get_block(position).await.unwrap();
         let event = BlockBreakEvent::new(cause.clone(), block.clone(), 0, false);
@@ -1071,7 +1919,20 @@ impl World {
             .await;
 
         if !event.cancelled {
-            let broken_block_state_id = self.set_block_state(position, 0).await;
+            // Containers drop their items even if the block itself is not dropped
+            block::blocks::save_open_containers(server, self, position).await;
+            if let Some(mut block_entity) = self.remove_block_entity(position).await {
+                for stack in block_entity.take_drops() {
+                    block::drop_stack(server, self, &stack, position).await;
+                }
+            }
+            let broken_block_state_id = if update {
+                let broken_block_state_id = self.set_block_state(position, 0).await;
+                self.update_neighbors(server, position).await;
+                broken_block_state_id
+            } else {
+                self.get_block_state_id(position).await.unwrap_or(0)
+            };
 
             let particles_packet = CWorldEvent::new(
                 WorldEvent::BlockBroken as i32,
@@ -1107,6 +1968,72 @@ impl World {
         Ok(id)
     }
 
+    /// Gets the sky or the block light at a position
+    pub async fn get_light(&self, kind: LightKind, position: &BlockPos) -> u8 {
+        let Ok(y) = usize::try_from(position.0.y - i32::from(WORLD_LOWEST_Y)) else {
+            return 0;
+        };
+        if y >= WORLD_HEIGHT {
+            // Only the sky lights up the space above the world
+            return match kind {
+                LightKind::Sky => 15,
+                LightKind::Block => 0,
+            };
+        }
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        let chunk = self.receive_chunk(chunk).await.0;
+        let chunk = chunk.read().await;
+        chunk
+            .light
+            .get(kind, relative.x as usize, y, relative.z as usize)
+    }
+
+    /// The brighter of the sky and the block light at a position. Like vanilla's raw brightness,
+    /// this does not get darker at night.
+    pub async fn get_brightness(&self, position: &BlockPos) -> u8 {
+        let sky = self.get_light(LightKind::Sky, position).await;
+        let block = self.get_light(LightKind::Block, position).await;
+        sky.max(block)
+    }
+
+    /// Gets the biome at a position. Chunks whose biomes are not known use the biomes of the
+    /// world generator instead
+    pub async fn get_biome(&self, position: &BlockPos) -> Biome {
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        let chunk = self.receive_chunk(chunk).await.0;
+        let biome = chunk
+            .read()
+            .await
+            .get_biome(ChunkRelativeBlockCoordinates::from(relative));
+        biome.unwrap_or_else(|| self.level.world_gen().get_biome(position.0))
+    }
+
+    /// How much darker the sky light currently is, see [`natural_spawn::sky_darken`]
+    pub async fn sky_darken(&self) -> u8 {
+        let time_of_day = self.level_time.lock().await.time_of_day;
+        let weather = self.weather.lock().await;
+        natural_spawn::sky_darken(time_of_day, weather.rain_level, weather.thunder_level)
+    }
+
+    /// Whether it is day, which like in vanilla is the case while the sky is not much darker
+    /// than at noon
+    pub async fn is_day(&self) -> bool {
+        self.sky_darken().await < 4
+    }
+
+    /// Whether it rains on the position, which is the case if it rains in the world, the
+    /// position is open to the sky and its biome is neither dry like deserts nor cold enough
+    /// for snow
+    pub async fn is_raining_at(&self, position: &BlockPos) -> bool {
+        if !self.weather.lock().await.raining
+            || self.get_light(LightKind::Sky, position).await < light::MAX_LIGHT
+        {
+            return false;
+        }
+        let biome = self.get_biome(position).await;
+        biome.has_precipitation() && !climate::is_cold_enough_to_snow(biome, position.0)
+    }
+
     /// Gets the Block from the Block Registry, Returns None if the Block has not been found
     pub async fn get_block(
         &self,
@@ -0,0 +1,499 @@
+use std::{collections::HashMap, f64::consts::PI, sync::Arc};
+
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_data::{
+    chunk::{Biome, SpawnEntry},
+    entity::{EntityType, SpawnGroup},
+};
+use pumpkin_registry::DimensionType;
+use pumpkin_util::{
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+    Difficulty, GameMode,
+};
+use pumpkin_world::{
+    block::registry::get_block_and_state_by_state_id,
+    chunk::{ChunkData, Subchunk, Subchunks},
+    coordinates::{ChunkRelativeBlockCoordinates, Height},
+    light::LightKind,
+    WORLD_HEIGHT, WORLD_LOWEST_Y,
+};
+use rand::{seq::SliceRandom, thread_rng, Rng};
+use tokio::sync::RwLock;
+
+use crate::{block::blocks::fluid::FluidState, entity::mob, server::Server};
+
+use super::World;
+
+/// Mobs spawn in the chunks within this many chunks of a player
+const SPAWN_CHUNK_RADIUS: i32 = 8;
+
+/// The mob caps are for this many spawnable chunks, which are the chunks around a single player
+const CHUNKS_PER_CAP: usize =
+    ((SPAWN_CHUNK_RADIUS * 2 + 1) * (SPAWN_CHUNK_RADIUS * 2 + 1)) as usize;
+
+/// Mobs do not spawn closer than this to players and to the world spawn
+const MIN_SPAWN_DISTANCE: f64 = 24.0;
+
+/// Monsters farther away from all players than this despawn right away
+const DESPAWN_DISTANCE: f64 = 128.0;
+
+/// Monsters farther away from all players than this despawn at random
+const RANDOM_DESPAWN_DISTANCE: f64 = 32.0;
+
+/// Monsters far away despawn with a chance of one in this many each tick
+const RANDOM_DESPAWN_CHANCE: u32 = 800;
+
+/// Creatures do not despawn, so they only spawn once in this many ticks
+const CREATURE_SPAWN_INTERVAL: i64 = 400;
+
+/// How many packs are tried from the same random position in a chunk
+const PACKS_PER_CHUNK: u32 = 3;
+
+/// No more mobs than this spawn in a chunk at once
+const MAX_SPAWNS_PER_CHUNK: u32 = 4;
+
+/// The spawn groups which spawn naturally. Mobs living in water do not spawn yet.
+const SPAWNED_GROUPS: [SpawnGroup; 2] = [SpawnGroup::Monster, SpawnGroup::Creature];
+
+/// Mobs which spawn in the nether regardless of the light
+const LIGHT_INDEPENDENT: [&str; 5] = [
+    "ghast",
+    "zombified_piglin",
+    "piglin",
+    "hoglin",
+    "magma_cube",
+];
+
+/// Blocks no mob spawns on
+const NO_SPAWN_GROUND: [&str; 2] = ["bedrock", "barrier"];
+
+/// Blocks without a collision shape which still hurt mobs spawning in them
+const UNSAFE_SPACE: [&str; 2] = ["fire", "soul_fire"];
+
+/// How many mobs of the spawn group may be in the world per [`CHUNKS_PER_CAP`] spawnable chunks
+const fn mob_cap(group: SpawnGroup) -> usize {
+    match group {
+        SpawnGroup::Monster => 70,
+        SpawnGroup::Creature => 10,
+        SpawnGroup::Ambient => 15,
+        SpawnGroup::Axolotls | SpawnGroup::UndergroundWaterCreature | SpawnGroup::WaterCreature => {
+            5
+        }
+        SpawnGroup::WaterAmbient => 20,
+        SpawnGroup::Misc => 0,
+    }
+}
+
+/// The blocks a creature spawns on
+fn creature_ground(entity: &EntityType) -> &'static [&'static str] {
+    match entity.resource_name {
+        "mooshroom" => &["mycelium"],
+        "rabbit" => &["grass_block", "snow", "snow_block", "sand"],
+        "goat" => &[
+            "stone",
+            "snow",
+            "snow_block",
+            "packed_ice",
+            "gravel",
+            "grass_block",
+        ],
+        "polar_bear" => &["grass_block", "ice", "packed_ice"],
+        "turtle" | "camel" => &["sand"],
+        "armadillo" => &["grass_block", "red_sand", "coarse_dirt", "terracotta"],
+        "fox" | "wolf" => &["grass_block", "snow", "snow_block", "podzol", "coarse_dirt"],
+        "frog" => &[
+            "grass_block",
+            "mud",
+            "mangrove_roots",
+            "muddy_mangrove_roots",
+        ],
+        // TODO: Striders spawn on lava
+        "strider" => &[],
+        _ => &["grass_block"],
+    }
+}
+
+/// Spawns mobs in random places near players and despawns monsters which got too far away from
+/// them, like vanilla's natural spawning
+pub async fn tick(world: &Arc<World>, server: &Server) {
+    let players: Vec<Vector3<f64>> = world
+        .players
+        .read()
+        .await
+        .values()
+        .filter(|player| player.gamemode.load() != GameMode::Spectator)
+        .map(|player| player.living_entity.entity.pos.load())
+        .collect();
+    let peaceful = BASIC_CONFIG.default_difficulty == Difficulty::Peaceful;
+    despawn(world, &players, peaceful).await;
+
+    if !world.game_rules.do_mob_spawning || players.is_empty() {
+        return;
+    }
+    let mut chunks = spawnable_chunks(world, &players);
+    chunks.shuffle(&mut thread_rng());
+    let world_age = world.level_time.lock().await.world_age;
+
+    let mut counts: HashMap<SpawnGroup, usize> = HashMap::new();
+    for entity in world.entities.read().await.values() {
+        *counts
+            .entry(entity.get_entity().entity_type.spawn_group)
+            .or_default() += 1;
+    }
+
+    for group in SPAWNED_GROUPS {
+        if group == SpawnGroup::Monster && peaceful {
+            continue;
+        }
+        if group == SpawnGroup::Creature && world_age % CREATURE_SPAWN_INTERVAL != 0 {
+            continue;
+        }
+        let cap = mob_cap(group) * chunks.len() / CHUNKS_PER_CAP;
+        let mut count = counts.get(&group).copied().unwrap_or(0);
+        for (_, chunk) in &chunks {
+            if count >= cap {
+                break;
+            }
+            let start = {
+                let chunk = chunk.read().await;
+                pick_start(&chunk, &mut thread_rng())
+            };
+            if let Some(start) = start {
+                count += spawn_packs(world, server, group, start, &players).await;
+            }
+        }
+    }
+}
+
+/// The loaded chunks within [`SPAWN_CHUNK_RADIUS`] of a player whose entities are ticked
+fn spawnable_chunks(
+    world: &World,
+    players: &[Vector3<f64>],
+) -> Vec<(Vector2<i32>, Arc<RwLock<ChunkData>>)> {
+    let player_chunks: Vec<Vector2<i32>> = players
+        .iter()
+        .map(|pos| Vector2::new((pos.x.floor() as i32) >> 4, (pos.z.floor() as i32) >> 4))
+        .collect();
+    let mut chunks = world.level.get_loaded_chunks();
+    chunks.retain(|(chunk, _)| {
+        world.level.is_chunk_entity_ticking(chunk)
+            && player_chunks.iter().any(|player| {
+                (chunk.x - player.x).abs() <= SPAWN_CHUNK_RADIUS
+                    && (chunk.z - player.z).abs() <= SPAWN_CHUNK_RADIUS
+            })
+    });
+    chunks
+}
+
+/// Picks a random block of the chunk to spawn packs of mobs around, between the bottom of the
+/// world and the highest block of a random column. Returns `None` for an empty column.
+pub fn pick_start(chunk: &ChunkData, rng: &mut impl Rng) -> Option<BlockPos> {
+    let x: u8 = rng.gen_range(0..16);
+    let z: u8 = rng.gen_range(0..16);
+    let top = highest_block(chunk, x, z)?;
+    // Mobs can not spawn at the very bottom, as they need a block to stand on
+    let y = rng.gen_range(1..=(top + 1).min(WORLD_HEIGHT as u16 - 1));
+    Some(BlockPos(Vector3::new(
+        chunk.position.x * 16 + i32::from(x),
+        i32::from(y) + i32::from(WORLD_LOWEST_Y),
+        chunk.position.z * 16 + i32::from(z),
+    )))
+}
+
+/// The absolute height of the highest block in the column which is not air. Subchunks of air are
+/// skipped as a whole, so this does not look at every block of tall columns.
+fn highest_block(chunk: &ChunkData, x: u8, z: u8) -> Option<u16> {
+    let subchunks = match &chunk.subchunks {
+        Subchunks::Single(0) => return None,
+        Subchunks::Single(_) => return Some(WORLD_HEIGHT as u16 - 1),
+        Subchunks::Multi(subchunks) => subchunks,
+    };
+    for (index, subchunk) in subchunks.iter().enumerate().rev() {
+        let bottom = index as u16 * 16;
+        match subchunk {
+            Subchunk::Single(0) => continue,
+            Subchunk::Single(_) => return Some(bottom + 15),
+            Subchunk::Multi(_) => {
+                for y in (bottom..bottom + 16).rev() {
+                    let state_id = subchunk.get_block(ChunkRelativeBlockCoordinates {
+                        x: x.into(),
+                        y: Height::from_absolute(y),
+                        z: z.into(),
+                    });
+                    if state_id.is_some_and(|state_id| state_id != 0) {
+                        return Some(y);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Tries to spawn a few packs of mobs of the spawn group around the start, at the same height.
+/// Returns how many mobs spawned.
+async fn spawn_packs(
+    world: &Arc<World>,
+    server: &Server,
+    group: SpawnGroup,
+    start: BlockPos,
+    players: &[Vector3<f64>],
+) -> usize {
+    let mut spawned = 0;
+    for _ in 0..PACKS_PER_CHUNK {
+        let (mut x, mut z) = (start.0.x, start.0.z);
+        let mut entry: Option<SpawnEntry> = None;
+        let mut spawned_in_pack = 0;
+        let mut tries = (rand::random::<f32>() * 4.0).ceil() as u32;
+        let mut attempt = 0;
+        while attempt < tries {
+            attempt += 1;
+            x += random_offset();
+            z += random_offset();
+            let position = BlockPos(Vector3::new(x, start.0.y, z));
+            if !world.is_loaded(&position) {
+                continue;
+            }
+            let center = Vector3::new(f64::from(x) + 0.5, f64::from(start.0.y), f64::from(z) + 0.5);
+            let Some(distance) = closest_distance(players, center) else {
+                break;
+            };
+            if distance < MIN_SPAWN_DISTANCE || is_near_spawn(world, center) {
+                continue;
+            }
+
+            // The mob of the pack is picked from the biome where the pack first finds a place
+            let current = if let Some(entry) = entry {
+                entry
+            } else {
+                let biome = world.get_biome(&position).await;
+                let Some(picked) = pick_entry(biome, group) else {
+                    break;
+                };
+                tries = thread_rng().gen_range(picked.min_count..=picked.max_count);
+                entry = Some(picked);
+                picked
+            };
+            // Monsters would despawn right away
+            if group == SpawnGroup::Monster && distance > DESPAWN_DISTANCE {
+                continue;
+            }
+            if !can_spawn_at(world, &current.entity, &position).await {
+                continue;
+            }
+
+            let mob = mob::from_type(current.entity, server, center, world).await;
+            mob.get_entity()
+                .set_rotation(rand::random::<f32>() * 360.0, 0.0);
+            world.spawn_entity(mob).await;
+            spawned += 1;
+            spawned_in_pack += 1;
+            if spawned >= MAX_SPAWNS_PER_CHUNK as usize {
+                return spawned;
+            }
+            if spawned_in_pack >= current.max_count {
+                break;
+            }
+        }
+    }
+    spawned
+}
+
+/// Moves packs around by up to 5 blocks, more often by less
+fn random_offset() -> i32 {
+    let mut rng = thread_rng();
+    rng.gen_range(0..6) - rng.gen_range(0..6)
+}
+
+/// The distance to the closest player, or `None` without players
+fn closest_distance(players: &[Vector3<f64>], position: Vector3<f64>) -> Option<f64> {
+    players
+        .iter()
+        .map(|player| player.squared_distance_to_vec(position))
+        .min_by(f64::total_cmp)
+        .map(f64::sqrt)
+}
+
+/// Whether the position is close to the world spawn. Only the horizontal distance counts, as the
+/// height of the spawn is only known once its column is generated.
+fn is_near_spawn(world: &World, position: Vector3<f64>) -> bool {
+    let info = &world.level.level_info;
+    let dx = f64::from(info.spawn_x) + 0.5 - position.x;
+    let dz = f64::from(info.spawn_z) + 0.5 - position.z;
+    dx.hypot(dz) < MIN_SPAWN_DISTANCE
+}
+
+/// Picks one of the mobs of the spawn group which spawn in the biome, by their weights
+fn pick_entry(biome: Biome, group: SpawnGroup) -> Option<SpawnEntry> {
+    let entries = biome.spawn_entries(group);
+    let total: u32 = entries.iter().map(|entry| entry.weight).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut picked = thread_rng().gen_range(0..total);
+    for entry in entries {
+        if picked < entry.weight {
+            return Some(*entry);
+        }
+        picked -= entry.weight;
+    }
+    None
+}
+
+/// Whether the mob can spawn at the position. It needs solid ground below and enough space to
+/// stand in, and monsters need darkness while animals need light.
+async fn can_spawn_at(world: &World, entity: &EntityType, position: &BlockPos) -> bool {
+    let below = BlockPos(Vector3::new(position.0.x, position.0.y - 1, position.0.z));
+    let Ok(ground) = world.get_block_state_id(&below).await else {
+        return false;
+    };
+    let Some((ground_block, ground_state)) = get_block_and_state_by_state_id(ground) else {
+        return false;
+    };
+    if ground_state.air
+        || ground_state.collision_shapes.is_empty()
+        || FluidState::from_state_id(ground).is_some()
+        || NO_SPAWN_GROUND.contains(&ground_block.name.as_str())
+        || (ground_block.name == "magma_block" && !entity.fire_immune)
+    {
+        return false;
+    }
+
+    let height = entity.dimension[1].ceil().max(1.0) as i32;
+    for dy in 0..height {
+        let space = BlockPos(Vector3::new(position.0.x, position.0.y + dy, position.0.z));
+        let Ok(state_id) = world.get_block_state_id(&space).await else {
+            return false;
+        };
+        let free = FluidState::from_state_id(state_id).is_none()
+            && get_block_and_state_by_state_id(state_id).is_some_and(|(block, state)| {
+                state.air
+                    || (state.collision_shapes.is_empty()
+                        && !UNSAFE_SPACE.contains(&block.name.as_str()))
+            });
+        if !free {
+            return false;
+        }
+    }
+
+    match entity.spawn_group {
+        SpawnGroup::Monster => is_monster_spawnable(world, entity, position).await,
+        SpawnGroup::Creature => {
+            creature_ground(entity).contains(&ground_block.name.as_str())
+                && world.get_brightness(position).await > 8
+        }
+        _ => true,
+    }
+}
+
+/// The light rules of monsters, which mostly spawn in the dark
+async fn is_monster_spawnable(world: &World, entity: &EntityType, position: &BlockPos) -> bool {
+    let nether = matches!(world.dimension_type, DimensionType::TheNether);
+    if nether && LIGHT_INDEPENDENT.contains(&entity.resource_name) {
+        return true;
+    }
+    if entity.resource_name == "slime" {
+        return is_slime_spawnable(world, position).await;
+    }
+
+    let sky = world.get_light(LightKind::Sky, position).await;
+    if u32::from(sky) > thread_rng().gen_range(0..32) {
+        return false;
+    }
+    let block = world.get_light(LightKind::Block, position).await;
+    // The nether has no limit for the block light, but the overworld and the end do
+    let (block_limit, max_light) = if nether {
+        (15, 7)
+    } else {
+        (0, thread_rng().gen_range(0..=7))
+    };
+    if block > block_limit {
+        return false;
+    }
+
+    let darken = world.sky_darken().await;
+    sky.saturating_sub(darken).max(block) <= max_light
+}
+
+/// Slimes spawn at the surface of swamps at night, or deep underground in slime chunks
+async fn is_slime_spawnable(world: &World, position: &BlockPos) -> bool {
+    let y = position.0.y;
+    let biome = world.get_biome(position).await;
+    if matches!(biome, Biome::Swamp | Biome::MangroveSwamp) && y > 50 && y < 70 {
+        // TODO: Use the brightness of the moon
+        return rand::random::<bool>()
+            && world.get_brightness(position).await <= thread_rng().gen_range(0..8);
+    }
+    let chunk = Vector2::new(position.0.x >> 4, position.0.z >> 4);
+    y < 40 && thread_rng().gen_range(0..10) == 0 && is_slime_chunk(world.level.seed.0 as i64, chunk)
+}
+
+/// Whether slimes spawn underground in the chunk, which is the case for one in ten chunks
+fn is_slime_chunk(seed: i64, chunk: Vector2<i32>) -> bool {
+    let (x, z) = (chunk.x, chunk.z);
+    let chunk_seed = seed
+        .wrapping_add(i64::from(x.wrapping_mul(x).wrapping_mul(4_987_142)))
+        .wrapping_add(i64::from(x.wrapping_mul(5_947_611)))
+        .wrapping_add(i64::from(z.wrapping_mul(z)).wrapping_mul(4_392_871))
+        .wrapping_add(i64::from(z.wrapping_mul(389_711)))
+        ^ 987_234_911;
+    LegacyRand::from_seed(chunk_seed as u64).next_bounded_i32(10) == 0
+}
+
+/// How much darker the sky light is at the time of day and in the weather, from 0 at noon to 11
+/// at midnight
+pub fn sky_darken(time_of_day: i64, rain_level: f32, thunder_level: f32) -> u8 {
+    let day = (time_of_day.rem_euclid(24000) as f64 / 24000.0 - 0.25).rem_euclid(1.0);
+    let sun_angle = day.mul_add(2.0, 0.5 - (day * PI).cos() / 2.0) / 3.0;
+    let sun = 2.0f64.mul_add((sun_angle * 2.0 * PI).cos().clamp(-0.25, 0.25), 0.5);
+    let rain = 1.0 - f64::from(rain_level) * 5.0 / 16.0;
+    let thunder = 1.0 - f64::from(thunder_level) * 5.0 / 16.0;
+    ((1.0 - sun * rain * thunder) * 11.0) as u8
+}
+
+/// Despawns monsters far away from all players, or all of them in peaceful difficulty. Named
+/// monsters stay.
+async fn despawn(world: &World, players: &[Vector3<f64>], peaceful: bool) {
+    let monsters: Vec<_> = world
+        .entities
+        .read()
+        .await
+        .values()
+        .filter(|entity| entity.get_entity().entity_type.spawn_group == SpawnGroup::Monster)
+        .cloned()
+        .collect();
+    for monster in monsters {
+        let entity = monster.get_entity();
+        if entity.custom_name.lock().await.is_some() {
+            continue;
+        }
+        let despawn = peaceful
+            || closest_distance(players, entity.pos.load()).is_some_and(|distance| {
+                distance > DESPAWN_DISTANCE
+                    || (distance > RANDOM_DESPAWN_DISTANCE
+                        && thread_rng().gen_range(0..RANDOM_DESPAWN_CHANCE) == 0)
+            });
+        if despawn {
+            world.remove_entity(entity).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sky_is_darkest_at_midnight() {
+        assert_eq!(sky_darken(6000, 0.0, 0.0), 0);
+        assert_eq!(sky_darken(18000, 0.0, 0.0), 11);
+        assert!(sky_darken(6000, 1.0, 1.0) > 0);
+    }
+
+    #[test]
+    fn empty_columns_have_no_start() {
+        let chunk = ChunkData::new(Subchunks::Single(0), Default::default(), Vector2::new(0, 0));
+        assert!(pick_start(&chunk, &mut thread_rng()).is_none());
+    }
+}
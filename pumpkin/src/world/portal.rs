@@ -0,0 +1,506 @@
+use std::{collections::HashMap, sync::Arc};
+
+use pumpkin_registry::DimensionType;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::{
+    block::registry::{get_block, get_state_id_by_properties},
+    chunk::ticket::{TicketType, FULL_CHUNK_LEVEL},
+    coordinates::ChunkRelativeBlockCoordinates,
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+
+use crate::{
+    entity::player::Player, plugin::api::events::player::player_teleport::TeleportCause,
+    server::Server,
+};
+
+use super::{respawn::find_respawn, World};
+
+/// The largest width and height of a portal frame, including the obsidian
+const MAX_SIZE: i32 = 21;
+/// The smallest inner width of a portal frame
+const MIN_WIDTH: i32 = 2;
+/// The smallest inner height of a portal frame
+const MIN_HEIGHT: i32 = 3;
+/// How many ticks a player has to stand in a portal before travelling, unless in creative mode
+pub const PORTAL_WAIT_TICKS: u32 = 80;
+/// How many ticks a player can not use a portal after travelling. This is refreshed as long as
+/// the player stands in a portal, so arriving in a portal does not send it back.
+pub const PLAYER_PORTAL_COOLDOWN: u32 = 10;
+/// How many ticks other entities can not use a portal after travelling
+pub const ENTITY_PORTAL_COOLDOWN: u32 = 300;
+/// How far away from the scaled position an existing portal in the overworld is used
+const OVERWORLD_SEARCH_RADIUS: i32 = 128;
+/// How far away from the scaled position an existing portal in the nether is used
+const NETHER_SEARCH_RADIUS: i32 = 16;
+/// The highest block of the nether below its bedrock roof
+const NETHER_MAX_Y: i32 = 122;
+/// Portals are created at least this high when there is no place for them
+const FALLBACK_MIN_Y: i32 = 70;
+/// Where players arrive in the end, on top of the obsidian platform
+const END_SPAWN: Vector3<i32> = Vector3::new(100, 50, 0);
+/// How far the obsidian platform in the end reaches from its center
+const END_PLATFORM_RADIUS: i32 = 2;
+/// How much space is cleared above the obsidian platform in the end
+const END_PLATFORM_CLEARANCE: i32 = 3;
+/// The direction players look in when arriving in the end, towards the central island
+const END_SPAWN_YAW: f32 = 90.0;
+
+/// The direction a portal extends in
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PortalAxis {
+    X,
+    Z,
+}
+
+impl PortalAxis {
+    fn step(self) -> Vector3<i32> {
+        match self {
+            Self::X => Vector3::new(1, 0, 0),
+            Self::Z => Vector3::new(0, 0, 1),
+        }
+    }
+
+    /// The state of a portal block in this direction
+    fn portal_state(self) -> u16 {
+        let axis = match self {
+            Self::X => "x",
+            Self::Z => "z",
+        };
+        get_state_id_by_properties(
+            "nether_portal",
+            &HashMap::from([("axis".to_string(), axis.to_string())]),
+        )
+        .expect("Nether portals have an axis")
+    }
+
+    /// The direction of the portal the given block belongs to, if it is a portal block
+    fn of_state(state_id: u16) -> Option<Self> {
+        [Self::X, Self::Z]
+            .into_iter()
+            .find(|axis| axis.portal_state() == state_id)
+    }
+}
+
+fn offset(position: Vector3<i32>, step: Vector3<i32>, distance: i32) -> Vector3<i32> {
+    Vector3::new(
+        position.x + step.x * distance,
+        position.y + step.y * distance,
+        position.z + step.z * distance,
+    )
+}
+
+fn up(position: Vector3<i32>, distance: i32) -> Vector3<i32> {
+    Vector3::new(position.x, position.y + distance, position.z)
+}
+
+async fn is_obsidian(world: &World, position: Vector3<i32>) -> bool {
+    world
+        .get_block(&BlockPos(position))
+        .await
+        .is_ok_and(|block| block.name == "obsidian")
+}
+
+/// Whether a portal block can be placed at the position when lighting a frame
+async fn is_empty(world: &World, position: Vector3<i32>) -> bool {
+    world
+        .get_block_and_block_state(&BlockPos(position))
+        .await
+        .is_ok_and(|(block, state)| state.air || block.name == "fire")
+}
+
+/// An empty obsidian frame, which can be lit to create a portal
+#[derive(Clone, Copy, Debug)]
+pub struct PortalShape {
+    axis: PortalAxis,
+    /// The lowest inner block of the frame, in the negative direction of the axis
+    bottom_left: Vector3<i32>,
+    width: i32,
+    height: i32,
+}
+
+impl PortalShape {
+    /// Finds an empty frame around the position, in either direction. Works like vanilla: the
+    /// bottom of the frame is found below the position, then its corner and its size.
+    pub async fn find(world: &World, position: BlockPos) -> Option<Self> {
+        for axis in [PortalAxis::X, PortalAxis::Z] {
+            if let Some(shape) = Self::find_with_axis(world, position.0, axis).await {
+                return Some(shape);
+            }
+        }
+        None
+    }
+
+    async fn find_with_axis(
+        world: &World,
+        position: Vector3<i32>,
+        axis: PortalAxis,
+    ) -> Option<Self> {
+        if !is_empty(world, position).await {
+            return None;
+        }
+        let step = axis.step();
+        let left = Vector3::new(-step.x, -step.y, -step.z);
+
+        let mut bottom = position;
+        let lowest = (position.y - MAX_SIZE).max(i32::from(WORLD_LOWEST_Y));
+        while bottom.y > lowest && is_empty(world, up(bottom, -1)).await {
+            bottom = up(bottom, -1);
+        }
+        let bottom_left = offset(
+            bottom,
+            left,
+            Self::distance_to_edge(world, bottom, left).await? - 1,
+        );
+        let width = Self::distance_to_edge(world, bottom_left, step).await?;
+        if !(MIN_WIDTH..=MAX_SIZE).contains(&width) {
+            return None;
+        }
+
+        let mut height = 0;
+        'rows: while height < MAX_SIZE {
+            let row = up(bottom_left, height);
+            if !is_obsidian(world, offset(row, left, 1)).await
+                || !is_obsidian(world, offset(row, step, width)).await
+            {
+                break;
+            }
+            for i in 0..width {
+                if !is_empty(world, offset(row, step, i)).await {
+                    break 'rows;
+                }
+            }
+            height += 1;
+        }
+        if !(MIN_HEIGHT..=MAX_SIZE).contains(&height) {
+            return None;
+        }
+        for i in 0..width {
+            if !is_obsidian(world, offset(up(bottom_left, height), step, i)).await {
+                return None;
+            }
+        }
+
+        Some(Self {
+            axis,
+            bottom_left,
+            width,
+            height,
+        })
+    }
+
+    /// How many empty blocks there are in the direction until the obsidian side of the frame,
+    /// with obsidian below all of them
+    async fn distance_to_edge(
+        world: &World,
+        position: Vector3<i32>,
+        direction: Vector3<i32>,
+    ) -> Option<i32> {
+        for distance in 0..=MAX_SIZE {
+            let current = offset(position, direction, distance);
+            if !is_empty(world, current).await {
+                return is_obsidian(world, current).await.then_some(distance);
+            }
+            if !is_obsidian(world, up(current, -1)).await {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// Fills the frame with portal blocks
+    pub async fn light(&self, world: &World) {
+        let state = self.axis.portal_state();
+        let step = self.axis.step();
+        for y in 0..self.height {
+            for i in 0..self.width {
+                let position = offset(up(self.bottom_left, y), step, i);
+                world.set_block_state(&BlockPos(position), state).await;
+            }
+        }
+    }
+}
+
+/// The chunks within the radius around the position
+fn chunks_around(position: Vector3<i32>, radius: i32) -> Vec<Vector2<i32>> {
+    let (min_x, max_x) = ((position.x - radius) >> 4, (position.x + radius) >> 4);
+    let (min_z, max_z) = ((position.z - radius) >> 4, (position.z + radius) >> 4);
+    (min_x..=max_x)
+        .flat_map(|x| (min_z..=max_z).map(move |z| Vector2::new(x, z)))
+        .collect()
+}
+
+/// Finds the bottom block of the portal closest to the position, within the radius
+async fn find_portal(
+    world: &World,
+    position: Vector3<i32>,
+    radius: i32,
+    chunks: Vec<Vector2<i32>>,
+    max_y: i32,
+) -> Option<Vector3<i32>> {
+    let count = chunks.len();
+    let mut receiver = world.receive_chunks(chunks);
+    let mut closest: Option<(i32, Vector3<i32>)> = None;
+    for _ in 0..count {
+        let Some((chunk, _)) = receiver.recv().await else {
+            break;
+        };
+        let chunk = chunk.read().await;
+        for x in 0..16 {
+            for z in 0..16 {
+                let world_x = chunk.position.x * 16 + x;
+                let world_z = chunk.position.z * 16 + z;
+                if (world_x - position.x).abs() > radius || (world_z - position.z).abs() > radius {
+                    continue;
+                }
+                let mut below_is_portal = false;
+                for y in i32::from(WORLD_LOWEST_Y)..=max_y {
+                    let state = chunk.subchunks.get_block(ChunkRelativeBlockCoordinates {
+                        x: (x as u8).into(),
+                        y: y.into(),
+                        z: (z as u8).into(),
+                    });
+                    let is_portal = state.and_then(PortalAxis::of_state).is_some();
+                    if is_portal && !below_is_portal {
+                        let candidate = Vector3::new(world_x, y, world_z);
+                        let distance = position.squared_distance_to_vec(candidate);
+                        if closest.is_none_or(|(closest, _)| distance < closest) {
+                            closest = Some((distance, candidate));
+                        }
+                    }
+                    below_is_portal = is_portal;
+                }
+            }
+        }
+    }
+    closest.map(|(_, position)| position)
+}
+
+/// Builds a new portal at the position, on the highest ground in its column, or on an obsidian
+/// platform if there is none. Returns the bottom left portal block.
+async fn create_portal(
+    world: &World,
+    position: Vector3<i32>,
+    axis: PortalAxis,
+    min_y: i32,
+    max_y: i32,
+) -> Vector3<i32> {
+    let mut ground = None;
+    for y in (min_y + 1..=max_y - 4).rev() {
+        let at = Vector3::new(position.x, y, position.z);
+        let solid_below = world
+            .get_block_state(&BlockPos(up(at, -1)))
+            .await
+            .is_ok_and(|state| !state.air && !state.replaceable);
+        if !solid_below {
+            continue;
+        }
+        let mut free = true;
+        for height in 0..4 {
+            if !is_empty(world, up(at, height)).await {
+                free = false;
+                break;
+            }
+        }
+        if free {
+            ground = Some(at);
+            break;
+        }
+    }
+
+    let step = axis.step();
+    let across = Vector3::new(step.z, 0, step.x);
+    let obsidian = get_block("obsidian")
+        .expect("Obsidian exists")
+        .default_state_id;
+    let bottom_left = match ground {
+        Some(ground) => ground,
+        None => {
+            let y = position.y.clamp(FALLBACK_MIN_Y.min(max_y - 4), max_y - 4);
+            let bottom_left = Vector3::new(position.x, y, position.z);
+            // A platform to stand on next to the portal, with space above it
+            for side in -1..=1 {
+                for i in 0..MIN_WIDTH {
+                    let floor = offset(offset(bottom_left, step, i), across, side);
+                    world
+                        .set_block_state(&BlockPos(up(floor, -1)), obsidian)
+                        .await;
+                    for height in 0..MIN_HEIGHT {
+                        world.set_block_state(&BlockPos(up(floor, height)), 0).await;
+                    }
+                }
+            }
+            bottom_left
+        }
+    };
+
+    let portal = axis.portal_state();
+    for i in -1..=MIN_WIDTH {
+        for height in -1..=MIN_HEIGHT {
+            let block = up(offset(bottom_left, step, i), height);
+            let frame = i == -1 || i == MIN_WIDTH || height == -1 || height == MIN_HEIGHT;
+            let state = if frame { obsidian } else { portal };
+            world.set_block_state(&BlockPos(block), state).await;
+        }
+    }
+    bottom_left
+}
+
+/// Sends a player standing in a nether portal to the portal on the other side, between the
+/// overworld and the nether. Coordinates are scaled by 8 like in vanilla, and a new portal is
+/// created if there is none close to the scaled position.
+pub async fn travel(player: &Arc<Player>, server: &Server) {
+    let entity = &player.living_entity.entity;
+    let world = player.world().await;
+    let (target_type, scale) = match world.dimension_type {
+        DimensionType::TheNether => (DimensionType::Overworld, 8.0),
+        DimensionType::Overworld => (DimensionType::TheNether, 1.0 / 8.0),
+        _ => return,
+    };
+    let Some(target) = server
+        .worlds
+        .read()
+        .await
+        .iter()
+        .find(|world| world.dimension_type as u8 == target_type as u8)
+        .cloned()
+    else {
+        return;
+    };
+
+    let axis = world
+        .get_block_state_id(&entity.block_pos.load())
+        .await
+        .ok()
+        .and_then(PortalAxis::of_state)
+        .unwrap_or(PortalAxis::X);
+    let (min_y, max_y, radius) = match target_type {
+        DimensionType::TheNether => (1, NETHER_MAX_Y, NETHER_SEARCH_RADIUS),
+        _ => (
+            i32::from(WORLD_LOWEST_Y),
+            i32::from(WORLD_MAX_Y) - 1,
+            OVERWORLD_SEARCH_RADIUS,
+        ),
+    };
+    let position = entity.pos.load();
+    // Like in vanilla, the portal is looked for on the border if the position is beyond it
+    let position = target.worldborder.lock().await.clamp(Vector3::new(
+        position.x * scale,
+        position.y,
+        position.z * scale,
+    ));
+    let scaled = Vector3::new(
+        position.x.floor() as i32,
+        (position.y.floor() as i32).clamp(min_y + 1, max_y - 4),
+        position.z.floor() as i32,
+    );
+
+    // Keep the chunks loaded for a while, so a new portal is saved before the player arrives
+    let chunks = chunks_around(scaled, radius);
+    target
+        .level
+        .add_tickets(&chunks, &TicketType::Portal, FULL_CHUNK_LEVEL);
+    let portal = match find_portal(&target, scaled, radius, chunks, max_y).await {
+        Some(portal) => portal,
+        None => create_portal(&target, scaled, axis, min_y, max_y).await,
+    };
+
+    let destination = Vector3::new(
+        f64::from(portal.x) + 0.5,
+        f64::from(portal.y),
+        f64::from(portal.z) + 0.5,
+    );
+    player
+        .teleport(
+            target.clone(),
+            destination,
+            entity.yaw.load(),
+            entity.pitch.load(),
+            TeleportCause::Portal,
+        )
+        .await;
+}
+
+/// Recreates the obsidian platform players arrive on in the end, clearing the space above it
+/// like vanilla does every time a player enters
+async fn create_end_platform(world: &World) {
+    let obsidian = get_block("obsidian")
+        .expect("Obsidian exists")
+        .default_state_id;
+    for x in -END_PLATFORM_RADIUS..=END_PLATFORM_RADIUS {
+        for z in -END_PLATFORM_RADIUS..=END_PLATFORM_RADIUS {
+            let column = Vector3::new(END_SPAWN.x + x, END_SPAWN.y, END_SPAWN.z + z);
+            world
+                .set_block_state(&BlockPos(up(column, -1)), obsidian)
+                .await;
+            for height in 0..END_PLATFORM_CLEARANCE {
+                world
+                    .set_block_state(&BlockPos(up(column, height)), 0)
+                    .await;
+            }
+        }
+    }
+}
+
+/// Sends a player standing in an end portal to the obsidian platform in the end. Players
+/// standing in the exit portal of the end are sent back to the spawn point of the overworld.
+pub async fn travel_end(player: &Arc<Player>, server: &Server) {
+    let entity = &player.living_entity.entity;
+    let world = player.world().await;
+    let leaving = matches!(world.dimension_type, DimensionType::TheEnd);
+    let target_type = if leaving {
+        DimensionType::Overworld
+    } else {
+        DimensionType::TheEnd
+    };
+    let Some(target) = server
+        .worlds
+        .read()
+        .await
+        .iter()
+        .find(|world| world.dimension_type as u8 == target_type as u8)
+        .cloned()
+    else {
+        return;
+    };
+
+    if leaving {
+        // Like in vanilla, the exit portal leads to the respawn point, without using up a charge
+        // of a respawn anchor
+        let Some(respawn) = find_respawn(server, player, false).await else {
+            return;
+        };
+        player
+            .teleport(
+                respawn.world,
+                respawn.position,
+                respawn.yaw,
+                entity.pitch.load(),
+                TeleportCause::Portal,
+            )
+            .await;
+        return;
+    }
+
+    // Keep the chunks loaded for a while, so the platform is saved before the player arrives
+    let chunks = chunks_around(END_SPAWN, END_PLATFORM_RADIUS);
+    target
+        .level
+        .add_tickets(&chunks, &TicketType::Portal, FULL_CHUNK_LEVEL);
+    create_end_platform(&target).await;
+
+    let destination = Vector3::new(
+        f64::from(END_SPAWN.x) + 0.5,
+        f64::from(END_SPAWN.y),
+        f64::from(END_SPAWN.z) + 0.5,
+    );
+    player
+        .teleport(
+            target.clone(),
+            destination,
+            END_SPAWN_YAW,
+            0.0,
+            TeleportCause::Portal,
+        )
+        .await;
+}
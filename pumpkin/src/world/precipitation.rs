@@ -0,0 +1,189 @@
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{
+    biome::climate,
+    block::registry::{
+        get_block, get_block_by_state_id, get_block_collision_shapes, get_state_by_state_id,
+        get_state_id_with_property,
+    },
+    light::LightKind,
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+use rand::{thread_rng, Rng};
+
+use crate::{block::blocks::get_int_property, server::Server};
+
+use super::World;
+
+/// Like in vanilla, each of the `random_tick_speed` tries of a ticking chunk picks a column with a
+/// chance of 1 in this many each tick
+const COLUMN_CHANCE: u32 = 48;
+
+/// Water only freezes and snow only piles up where the block light is darker than this
+const MAX_BLOCK_LIGHT: u8 = 10;
+
+/// The most layers of snow which fit into one block
+const MAX_SNOW_LAYERS: u8 = 8;
+
+/// Lets the weather change random columns of the ticking chunks: Still water under the open sky
+/// freezes in cold biomes, and while it rains snow piles up on the surface there, see
+/// [`climate::is_cold_enough_to_snow`].
+pub async fn tick(world: &World, server: &Server) {
+    let speed = world.game_rules.random_tick_speed;
+    if !ADVANCED_CONFIG.world.weather_changes_blocks || speed == 0 {
+        return;
+    }
+
+    let mut columns = Vec::new();
+    for (chunk, data) in world.level.get_ticking_chunks() {
+        let picked: Vec<(u8, u8)> = {
+            let mut rng = thread_rng();
+            (0..speed)
+                .filter(|_| rng.gen_ratio(1, COLUMN_CHANCE))
+                .map(|_| (rng.gen_range(0..16), rng.gen_range(0..16)))
+                .collect()
+        };
+        if picked.is_empty() {
+            continue;
+        }
+        let data = data.read().await;
+        for (x, z) in picked {
+            columns.push(BlockPos(Vector3::new(
+                chunk.x * 16 + i32::from(x),
+                data.motion_blocking_height(x, z),
+                chunk.z * 16 + i32::from(z),
+            )));
+        }
+    }
+
+    let raining = world.weather.lock().await.raining;
+    for top in columns {
+        tick_column(world, server, top, raining).await;
+    }
+}
+
+/// Freezes the water below the top of the column and lets snow fall onto it
+async fn tick_column(world: &World, server: &Server, top: BlockPos, raining: bool) {
+    let below = BlockPos(top.0.add_raw(0, -1, 0));
+    if should_freeze(world, &below).await {
+        if let Some(ice) = get_block("minecraft:ice") {
+            world.set_block_state(&below, ice.default_state_id).await;
+            world.update_neighbors(server, &below).await;
+        }
+    }
+
+    let max_layers = world
+        .game_rules
+        .snow_accumulation_height
+        .min(MAX_SNOW_LAYERS);
+    if !raining || max_layers == 0 || !should_snow(world, &top).await {
+        return;
+    }
+    let Ok(state_id) = world.get_block_state_id(&top).await else {
+        return;
+    };
+    let new_state = match snow_layers(state_id) {
+        Some(layers) if layers < u32::from(max_layers) => {
+            get_state_id_with_property(state_id, "layers", &(layers + 1).to_string())
+        }
+        Some(_) => None,
+        None => get_block("minecraft:snow").map(|snow| snow.default_state_id),
+    };
+    if let Some(new_state) = new_state {
+        world.set_block_state(&top, new_state).await;
+        world.update_neighbors(server, &top).await;
+    }
+}
+
+/// The layers of the snow block, or `None` if the block is not snow
+fn snow_layers(state_id: u16) -> Option<u32> {
+    get_block_by_state_id(state_id)
+        .filter(|block| block.name == "snow")
+        .and_then(|_| get_int_property(state_id, "layers"))
+}
+
+fn in_world(position: &BlockPos) -> bool {
+    (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&position.0.y)
+}
+
+async fn is_cold(world: &World, position: &BlockPos) -> bool {
+    let biome = world.get_biome(position).await;
+    climate::is_cold_enough_to_snow(biome, position.0)
+}
+
+/// Whether the block is still water which freezes. Like in vanilla, it only freezes at the edge
+/// of the water, so lakes freeze from their shores.
+async fn should_freeze(world: &World, position: &BlockPos) -> bool {
+    if !in_world(position)
+        || world.get_light(LightKind::Block, position).await >= MAX_BLOCK_LIGHT
+        || !is_still_water(world, position).await
+        || !is_cold(world, position).await
+    {
+        return false;
+    }
+    for (x, z) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        let neighbor = BlockPos(position.0.add_raw(x, 0, z));
+        if !is_still_water(world, &neighbor).await {
+            return true;
+        }
+    }
+    false
+}
+
+async fn is_still_water(world: &World, position: &BlockPos) -> bool {
+    world
+        .get_block_state_id(position)
+        .await
+        .is_ok_and(|state_id| {
+            get_block_by_state_id(state_id).is_some_and(|block| block.name == "water")
+                && get_int_property(state_id, "level") == Some(0)
+        })
+}
+
+/// Whether snow can fall onto the position, which has to be air or snow in a cold biome with
+/// ground snow can lie on
+async fn should_snow(world: &World, position: &BlockPos) -> bool {
+    if !in_world(position)
+        || world.get_light(LightKind::Block, position).await >= MAX_BLOCK_LIGHT
+        || !is_cold(world, position).await
+    {
+        return false;
+    }
+    let Ok(state_id) = world.get_block_state_id(position).await else {
+        return false;
+    };
+    let air = get_state_by_state_id(state_id).is_some_and(|state| state.air);
+    if !air && snow_layers(state_id).is_none() {
+        return false;
+    }
+    let Ok(ground) = world
+        .get_block_state_id(&BlockPos(position.0.add_raw(0, -1, 0)))
+        .await
+    else {
+        return false;
+    };
+    can_hold_snow(ground)
+}
+
+/// Whether snow can lie on the block, like in vanilla: Not on ice, but on blocks with a full top
+/// and on full snow blocks
+fn can_hold_snow(state_id: u16) -> bool {
+    let Some(block) = get_block_by_state_id(state_id) else {
+        return false;
+    };
+    match block.name.as_str() {
+        "ice" | "packed_ice" | "barrier" => false,
+        "honey_block" | "soul_sand" => true,
+        "snow" => get_int_property(state_id, "layers") == Some(u32::from(MAX_SNOW_LAYERS)),
+        _ => get_block_collision_shapes(state_id)
+            .unwrap_or_default()
+            .iter()
+            .any(|shape| {
+                shape.min[0] <= 0.0
+                    && shape.min[2] <= 0.0
+                    && shape.max[0] >= 1.0
+                    && shape.max[1] >= 1.0
+                    && shape.max[2] >= 1.0
+            }),
+    }
+}
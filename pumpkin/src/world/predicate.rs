@@ -0,0 +1,490 @@
+//! The predicates of datapacks, which are JSON files in `data/<namespace>/predicate` like
+//! vanilla. They are made of loot conditions, which loot tables use as well. Only some of the
+//! vanilla conditions are supported so far: `entity_properties`, `location_check`, `inverted`,
+//! `all_of` and `any_of`. Fields of them which are not supported yet are ignored.
+
+use std::{collections::HashMap, fs, future::Future, path::Path, pin::Pin, sync::atomic::Ordering};
+
+use pumpkin_data::tag::{get_tag_values, RegistryKey};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::block::registry::{get_block_by_state_id, get_state_property};
+use serde::Deserialize;
+
+use crate::entity::Entity;
+
+use super::World;
+
+/// A condition which is tested when it is evaluated, see [`LootCondition::test`]
+type ConditionFuture<'a> = Pin<Box<dyn Future<Output = bool> + Send + 'a>>;
+
+/// The predicates loaded from the datapacks of the world, by their namespaced id
+#[derive(Default)]
+pub struct Predicates {
+    predicates: HashMap<String, LootCondition>,
+}
+
+impl Predicates {
+    /// Loads the predicates of the datapacks in the `datapacks` folder of the world. Invalid
+    /// predicates are skipped with a warning.
+    #[must_use]
+    pub fn load(world_folder: &Path) -> Self {
+        let mut predicates = HashMap::new();
+        let Ok(datapacks) = fs::read_dir(world_folder.join("datapacks")) else {
+            return Self { predicates };
+        };
+        for datapack in datapacks.flatten() {
+            let Ok(namespaces) = fs::read_dir(datapack.path().join("data")) else {
+                continue;
+            };
+            for namespace in namespaces.flatten() {
+                let namespace_name = namespace.file_name().to_string_lossy().into_owned();
+                let folder = namespace.path().join("predicate");
+                load_folder(&folder, &folder, &namespace_name, &mut predicates);
+            }
+        }
+        if !predicates.is_empty() {
+            log::info!("Loaded {} predicates from datapacks", predicates.len());
+        }
+        Self { predicates }
+    }
+
+    /// The predicate with the id, like `example:is_sneaking`
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&LootCondition> {
+        self.predicates.get(&namespaced(id))
+    }
+
+    /// Tests the predicate with the id. Returns `None` if there is no such predicate.
+    pub async fn test(&self, id: &str, context: &LootContext<'_>) -> Option<bool> {
+        Some(self.get(id)?.test(context).await)
+    }
+}
+
+/// Reads the predicates in the folder and its subfolders, which become part of their ids
+fn load_folder(
+    root: &Path,
+    folder: &Path,
+    namespace: &str,
+    predicates: &mut HashMap<String, LootCondition>,
+) {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            load_folder(root, &path, namespace, predicates);
+            continue;
+        }
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+        let Ok(relative) = path
+            .with_extension("")
+            .strip_prefix(root)
+            .map(Path::to_path_buf)
+        else {
+            continue;
+        };
+        let id = format!(
+            "{namespace}:{}",
+            relative.to_string_lossy().replace('\\', "/")
+        );
+        let condition = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| LootCondition::from_json(&json).map_err(|err| err.to_string()));
+        match condition {
+            Ok(condition) => {
+                predicates.insert(id, condition);
+            }
+            Err(err) => log::warn!("Skipping the invalid predicate {id}: {err}"),
+        }
+    }
+}
+
+fn namespaced(name: &str) -> String {
+    if name.contains(':') {
+        name.to_string()
+    } else {
+        format!("minecraft:{name}")
+    }
+}
+
+/// What conditions are tested against, like the entity running a command or the block a loot
+/// table drops for
+pub struct LootContext<'a> {
+    pub world: &'a World,
+    pub origin: Vector3<f64>,
+    /// The entity the condition is about, e.g. `@s` of a command
+    pub this: Option<&'a Entity>,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "condition")]
+pub enum LootCondition {
+    #[serde(rename = "minecraft:entity_properties", alias = "entity_properties")]
+    EntityProperties {
+        entity: EntityTarget,
+        #[serde(default)]
+        predicate: EntityPredicate,
+    },
+    #[serde(rename = "minecraft:location_check", alias = "location_check")]
+    LocationCheck {
+        #[serde(rename = "offsetX", default)]
+        offset_x: i32,
+        #[serde(rename = "offsetY", default)]
+        offset_y: i32,
+        #[serde(rename = "offsetZ", default)]
+        offset_z: i32,
+        #[serde(default)]
+        predicate: LocationPredicate,
+    },
+    #[serde(rename = "minecraft:inverted", alias = "inverted")]
+    Inverted { term: Box<LootCondition> },
+    #[serde(rename = "minecraft:all_of", alias = "all_of")]
+    AllOf { terms: Vec<LootCondition> },
+    #[serde(rename = "minecraft:any_of", alias = "any_of")]
+    AnyOf { terms: Vec<LootCondition> },
+}
+
+impl LootCondition {
+    /// Reads a condition, or a list of conditions which all have to pass, like predicate files
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Conditions {
+            One(LootCondition),
+            All(Vec<LootCondition>),
+        }
+        Ok(match serde_json::from_str(json)? {
+            Conditions::One(condition) => condition,
+            Conditions::All(terms) => Self::AllOf { terms },
+        })
+    }
+
+    /// Whether the condition passes in the context
+    pub fn test<'a>(&'a self, context: &'a LootContext<'a>) -> ConditionFuture<'a> {
+        Box::pin(async move {
+            match self {
+                Self::EntityProperties { entity, predicate } => {
+                    // Like vanilla, an empty predicate passes without the entity too
+                    let Some(entity) = entity.resolve(context) else {
+                        return *predicate == EntityPredicate::default();
+                    };
+                    predicate.test(entity).await
+                }
+                Self::LocationCheck {
+                    offset_x,
+                    offset_y,
+                    offset_z,
+                    predicate,
+                } => {
+                    let position = context.origin.add(&Vector3::new(
+                        f64::from(*offset_x),
+                        f64::from(*offset_y),
+                        f64::from(*offset_z),
+                    ));
+                    predicate.test(context.world, position).await
+                }
+                Self::Inverted { term } => !term.test(context).await,
+                Self::AllOf { terms } => {
+                    for term in terms {
+                        if !term.test(context).await {
+                            return false;
+                        }
+                    }
+                    true
+                }
+                Self::AnyOf { terms } => {
+                    for term in terms {
+                        if term.test(context).await {
+                            return true;
+                        }
+                    }
+                    false
+                }
+            }
+        })
+    }
+}
+
+/// Which entity of the context a condition is about. Only `this` is known outside of loot
+/// tables, so conditions about the others do not pass there.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityTarget {
+    This,
+    Attacker,
+    DirectAttacker,
+    AttackingPlayer,
+}
+
+impl EntityTarget {
+    fn resolve<'a>(self, context: &LootContext<'a>) -> Option<&'a Entity> {
+        match self {
+            Self::This => context.this,
+            Self::Attacker | Self::DirectAttacker | Self::AttackingPlayer => None,
+        }
+    }
+}
+
+/// A number which has to be exactly the value or between the bounds, like `5` or
+/// `{"min": 1, "max": 3}`
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum Bounds {
+    Exact(f64),
+    Range { min: Option<f64>, max: Option<f64> },
+}
+
+impl Bounds {
+    #[must_use]
+    pub fn contains(&self, value: f64) -> bool {
+        match *self {
+            Self::Exact(exact) => (value - exact).abs() < f64::EPSILON,
+            Self::Range { min, max } => {
+                min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+            }
+        }
+    }
+}
+
+/// Block ids or entity types, like `"minecraft:stone"`, `"#minecraft:logs"` or a list of ids
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum RegistryFilter {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl RegistryFilter {
+    /// Whether the entry, without namespace, is one of the ids or in the tag
+    fn contains(&self, registry: RegistryKey, name: &str) -> bool {
+        let matches = |id: &str| {
+            id.strip_prefix('#').map_or_else(
+                || id.strip_prefix("minecraft:").unwrap_or(id) == name,
+                |tag| {
+                    get_tag_values(registry, &namespaced(tag))
+                        .is_some_and(|values| values.iter().flatten().any(|value| value == name))
+                },
+            )
+        };
+        match self {
+            Self::One(id) => matches(id),
+            Self::Many(ids) => ids.iter().any(|id| matches(id)),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct EntityPredicate {
+    #[serde(rename = "type")]
+    pub entity_type: Option<RegistryFilter>,
+    pub location: Option<LocationPredicate>,
+    pub flags: Option<EntityFlags>,
+}
+
+impl EntityPredicate {
+    pub async fn test(&self, entity: &Entity) -> bool {
+        if let Some(entity_type) = &self.entity_type {
+            if !entity_type.contains(RegistryKey::EntityType, entity.entity_type.resource_name) {
+                return false;
+            }
+        }
+        if let Some(flags) = &self.flags {
+            if !flags.test(entity) {
+                return false;
+            }
+        }
+        if let Some(location) = &self.location {
+            let world = entity.world.read().await.clone();
+            if !location.test(&world, entity.pos.load()).await {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EntityFlags {
+    pub is_on_fire: Option<bool>,
+    pub is_sneaking: Option<bool>,
+    pub is_sprinting: Option<bool>,
+    pub is_on_ground: Option<bool>,
+    pub is_fall_flying: Option<bool>,
+}
+
+impl EntityFlags {
+    fn test(&self, entity: &Entity) -> bool {
+        let flag = |expected: Option<bool>, actual: bool| expected.is_none_or(|e| e == actual);
+        flag(self.is_on_fire, entity.is_on_fire())
+            && flag(self.is_sneaking, entity.sneaking.load(Ordering::Relaxed))
+            && flag(self.is_sprinting, entity.sprinting.load(Ordering::Relaxed))
+            && flag(self.is_on_ground, entity.on_ground.load(Ordering::Relaxed))
+            && flag(
+                self.is_fall_flying,
+                entity.fall_flying.load(Ordering::Relaxed),
+            )
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct LocationPredicate {
+    pub position: Option<PositionPredicate>,
+    pub dimension: Option<String>,
+    pub block: Option<BlockPredicate>,
+}
+
+impl LocationPredicate {
+    pub async fn test(&self, world: &World, position: Vector3<f64>) -> bool {
+        if let Some(bounds) = &self.position {
+            if !bounds.test(position) {
+                return false;
+            }
+        }
+        if let Some(dimension) = &self.dimension {
+            if world.dimension_key().to_string() != namespaced(dimension) {
+                return false;
+            }
+        }
+        if let Some(block) = &self.block {
+            let block_pos = BlockPos(Vector3::new(
+                position.x.floor() as i32,
+                position.y.floor() as i32,
+                position.z.floor() as i32,
+            ));
+            let Ok(state_id) = world.get_block_state_id(&block_pos).await else {
+                return false;
+            };
+            if !block.test(state_id) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PositionPredicate {
+    pub x: Option<Bounds>,
+    pub y: Option<Bounds>,
+    pub z: Option<Bounds>,
+}
+
+impl PositionPredicate {
+    fn test(&self, position: Vector3<f64>) -> bool {
+        let axis = |bounds: Option<Bounds>, value| bounds.is_none_or(|b| b.contains(value));
+        axis(self.x, position.x) && axis(self.y, position.y) && axis(self.z, position.z)
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BlockPredicate {
+    pub blocks: Option<RegistryFilter>,
+    /// The values the properties of the block state must have, like `"north"`, `true` or
+    /// `{"min": 1}` for numbers
+    #[serde(default)]
+    pub state: HashMap<String, StateValue>,
+}
+
+impl BlockPredicate {
+    #[must_use]
+    pub fn test(&self, state_id: u16) -> bool {
+        let Some(block) = get_block_by_state_id(state_id) else {
+            return false;
+        };
+        if let Some(blocks) = &self.blocks {
+            if !blocks.contains(RegistryKey::Block, &block.name) {
+                return false;
+            }
+        }
+        self.state.iter().all(|(property, expected)| {
+            get_state_property(state_id, property).is_some_and(|value| expected.matches(value))
+        })
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum StateValue {
+    Bool(bool),
+    Bounds(Bounds),
+    String(String),
+}
+
+impl StateValue {
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            Self::Bool(expected) => value == expected.to_string(),
+            Self::Bounds(bounds) => value
+                .parse::<f64>()
+                .is_ok_and(|number| bounds.contains(number)),
+            Self::String(expected) => value == expected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bounds, EntityTarget, LootCondition, RegistryFilter, StateValue};
+    use pumpkin_data::tag::RegistryKey;
+
+    #[test]
+    fn reads_predicates() {
+        let condition = LootCondition::from_json(
+            r##"{
+                "condition": "minecraft:entity_properties",
+                "entity": "this",
+                "predicate": {
+                    "type": "#minecraft:skeletons",
+                    "flags": { "is_sneaking": true },
+                    "location": { "position": { "y": { "min": 64 } } }
+                }
+            }"##,
+        )
+        .unwrap();
+        let LootCondition::EntityProperties { entity, predicate } = condition else {
+            panic!("Expected an entity_properties condition");
+        };
+        assert_eq!(entity, EntityTarget::This);
+        assert_eq!(predicate.flags.unwrap().is_sneaking, Some(true));
+        let y = predicate.location.unwrap().position.unwrap().y.unwrap();
+        assert!(y.contains(64.0) && !y.contains(63.5));
+
+        // A list of conditions all have to pass
+        let condition = LootCondition::from_json(
+            r#"[
+                { "condition": "location_check", "offsetY": -1, "predicate": { "block": { "blocks": "stone" } } },
+                { "condition": "inverted", "term": { "condition": "any_of", "terms": [] } }
+            ]"#,
+        )
+        .unwrap();
+        assert!(matches!(condition, LootCondition::AllOf { terms } if terms.len() == 2));
+
+        assert!(LootCondition::from_json(r#"{ "condition": "minecraft:unknown" }"#).is_err());
+    }
+
+    #[test]
+    fn matches_values() {
+        assert!(Bounds::Exact(3.0).contains(3.0));
+        assert!(!Bounds::Range {
+            min: Some(1.0),
+            max: Some(2.0)
+        }
+        .contains(2.5));
+        assert!(StateValue::Bool(true).matches("true"));
+        assert!(StateValue::Bounds(Bounds::Range {
+            min: Some(2.0),
+            max: None
+        })
+        .matches("3"));
+        assert!(!StateValue::String("north".to_string()).matches("south"));
+
+        let logs = RegistryFilter::One("#minecraft:logs".to_string());
+        assert!(logs.contains(RegistryKey::Block, "oak_log"));
+        assert!(!logs.contains(RegistryKey::Block, "stone"));
+        let blocks = RegistryFilter::Many(vec!["minecraft:stone".to_string(), "dirt".to_string()]);
+        assert!(blocks.contains(RegistryKey::Block, "dirt"));
+    }
+}
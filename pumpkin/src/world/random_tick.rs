@@ -0,0 +1,87 @@
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{
+    block::registry::{get_block_by_state_id, Block},
+    chunk::{ChunkData, Subchunk, Subchunks, SUBCHUNKS_COUNT},
+    coordinates::{ChunkRelativeBlockCoordinates, Height},
+    WORLD_LOWEST_Y,
+};
+use rand::{thread_rng, Rng};
+
+use crate::{block::registry::BlockRegistry, server::Server};
+
+use super::World;
+
+/// Picks `random_tick_speed` random blocks of every subchunk of the ticking chunks and lets the
+/// ones with random ticks update, see [`crate::block::pumpkin_block::PumpkinBlock::random_tick`]
+pub async fn tick(world: &World, server: &Server) {
+    let speed = world.game_rules.random_tick_speed;
+    if speed == 0 {
+        return;
+    }
+
+    let mut picked = Vec::new();
+    for (_, chunk) in world.level.get_ticking_chunks() {
+        let chunk = chunk.read().await;
+        pick_blocks(&chunk, speed, &server.block_registry, &mut picked);
+    }
+
+    for (position, block, state_id) in picked {
+        // An earlier random tick may have changed the block already
+        if world.get_block_state_id(&position).await.ok() != Some(state_id) {
+            continue;
+        }
+        server
+            .block_registry
+            .random_tick(server, world, block, state_id, position)
+            .await;
+    }
+}
+
+fn pick_blocks(
+    chunk: &ChunkData,
+    speed: u32,
+    registry: &BlockRegistry,
+    picked: &mut Vec<(BlockPos, &'static Block, u16)>,
+) {
+    let Subchunks::Multi(subchunks) = &chunk.subchunks else {
+        // Chunks made of a single block are empty or filled with something like stone
+        return;
+    };
+
+    let mut rng = thread_rng();
+    for (index, subchunk) in subchunks.iter().enumerate().take(SUBCHUNKS_COUNT) {
+        if matches!(subchunk, Subchunk::Single(0)) {
+            continue;
+        }
+        for _ in 0..speed {
+            let x: u8 = rng.gen_range(0..16);
+            let y = index as u16 * 16 + rng.gen_range(0..16);
+            let z: u8 = rng.gen_range(0..16);
+            let Some(state_id) = subchunk.get_block(ChunkRelativeBlockCoordinates {
+                x: x.into(),
+                y: Height::from_absolute(y),
+                z: z.into(),
+            }) else {
+                continue;
+            };
+            if state_id == 0 {
+                continue;
+            }
+            let Some(block) = get_block_by_state_id(state_id) else {
+                continue;
+            };
+            if !registry.has_random_ticks(block) {
+                continue;
+            }
+            picked.push((
+                BlockPos(Vector3::new(
+                    chunk.position.x * 16 + i32::from(x),
+                    i32::from(y) + i32::from(WORLD_LOWEST_Y),
+                    chunk.position.z * 16 + i32::from(z),
+                )),
+                block,
+                state_id,
+            ));
+        }
+    }
+}
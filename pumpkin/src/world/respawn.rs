@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use pumpkin_protocol::client::play::{CGameEvent, GameEvent};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+use crate::{
+    block::blocks::{bed, respawn_anchor},
+    entity::player::{Player, RespawnPoint},
+    plugin::player::player_respawn::RespawnReason,
+    server::Server,
+};
+
+use super::World;
+
+/// Like in vanilla, players respawn on one of the blocks around their bed or respawn anchor,
+/// preferring the ones at the same height
+const STAND_UP_OFFSETS: [(i32, i32); 8] = [
+    (0, -1),
+    (-1, 0),
+    (0, 1),
+    (1, 0),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+/// Where a player respawns after dying
+pub struct Respawn {
+    pub world: Arc<World>,
+    pub position: Vector3<f64>,
+    pub yaw: f32,
+    pub reason: RespawnReason,
+}
+
+/// Finds where the player respawns. Like in vanilla, this is their respawn anchor, which loses a
+/// charge if `use_charge` is set, or their bed, and otherwise the world spawn. If the anchor or bed
+/// is gone or obstructed, the player is told and the respawn point is forgotten.
+pub async fn find_respawn(server: &Server, player: &Player, use_charge: bool) -> Option<Respawn> {
+    let point = player.respawn_point.lock().await.clone();
+    if let Some(point) = point {
+        if let Some(respawn) = use_respawn_point(server, &point, use_charge).await {
+            return Some(respawn);
+        }
+        *player.respawn_point.lock().await = None;
+        player
+            .client
+            .send_packet(&CGameEvent::new(GameEvent::NoRespawnBlockAvailable, 0.0))
+            .await;
+    }
+
+    let world = server.get_respawn_world(&player.world().await).await?;
+    Some(Respawn {
+        position: world.get_spawn_position().await,
+        yaw: world.level.level_info.spawn_angle,
+        reason: RespawnReason::WorldSpawn,
+        world,
+    })
+}
+
+/// Where the player respawns at their anchor or bed, if it still works
+async fn use_respawn_point(
+    server: &Server,
+    point: &RespawnPoint,
+    use_charge: bool,
+) -> Option<Respawn> {
+    let world = server.get_world_by_name(&point.world).await?;
+    let state_id = world.get_block_state_id(&point.position).await.ok()?;
+
+    let reason = if respawn_anchor::is_respawn_anchor(state_id)
+        && respawn_anchor::charges(state_id) > 0
+        && respawn_anchor::is_nether(&world)
+    {
+        RespawnReason::RespawnAnchor
+    } else if bed::is_bed(state_id) && bed::is_overworld(&world) {
+        RespawnReason::Bed
+    } else {
+        return None;
+    };
+    let position = stand_up_position(&world, point.position).await?;
+    if use_charge && reason == RespawnReason::RespawnAnchor {
+        respawn_anchor::deplete(&world, point.position, state_id).await;
+    }
+
+    Some(Respawn {
+        world,
+        position,
+        yaw: point.angle,
+        reason,
+    })
+}
+
+/// A free place next to the block where a player can stand, or on top of it if there is none
+async fn stand_up_position(world: &World, block: BlockPos) -> Option<Vector3<f64>> {
+    let candidates = [0, -1, 1]
+        .into_iter()
+        .flat_map(|y| STAND_UP_OFFSETS.map(|(x, z)| (x, y, z)))
+        .chain(std::iter::once((0, 1, 0)));
+    for (x, y, z) in candidates {
+        let position = BlockPos(block.0.add_raw(x, y, z));
+        if can_stand_at(world, position).await {
+            return Some(Vector3::new(
+                f64::from(position.0.x) + 0.5,
+                f64::from(position.0.y),
+                f64::from(position.0.z) + 0.5,
+            ));
+        }
+    }
+    None
+}
+
+/// Whether a player fits at the position, with a solid block below them
+async fn can_stand_at(world: &World, position: BlockPos) -> bool {
+    let solid = |position: BlockPos| async move {
+        world
+            .get_block_state(&position)
+            .await
+            .is_ok_and(|state| !state.collision_shapes.is_empty())
+    };
+    solid(BlockPos(position.0.add_raw(0, -1, 0))).await
+        && !solid(position).await
+        && !solid(BlockPos(position.0.add_raw(0, 1, 0))).await
+}
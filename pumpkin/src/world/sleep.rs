@@ -0,0 +1,115 @@
+use std::sync::atomic::Ordering;
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::{text::TextComponent, GameMode};
+
+use crate::{entity::player::TitleMode, server::Server};
+
+use super::World;
+
+/// Like in vanilla, above this percentage the night is never skipped
+const MAX_PERCENTAGE: u32 = 100;
+
+/// How many of the players of a world are in bed, of the ones counted for skipping the night
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct SleepStatus {
+    active: usize,
+    sleeping: usize,
+}
+
+/// Skips the night once enough players sleep, see [`Server::players_sleeping_percentage`].
+/// Spectators are not counted, and neither are AFK players if that is enabled in the config.
+/// Like in vanilla, the time is set to the next morning, the weather is cleared and everyone in a
+/// bed wakes up.
+///
+/// Whenever a player gets into or out of bed, the players of the world are told how many sleep
+/// and how many have to.
+pub async fn tick(world: &World, server: &Server) {
+    let percentage = server.players_sleeping_percentage();
+    if percentage > MAX_PERCENTAGE {
+        return;
+    }
+
+    let players: Vec<_> = world
+        .players
+        .read()
+        .await
+        .values()
+        .filter(|player| {
+            player.gamemode.load() != GameMode::Spectator
+                && !(ADVANCED_CONFIG.afk.ignored_by_sleep && player.afk.load(Ordering::Relaxed))
+        })
+        .cloned()
+        .collect();
+    let status = SleepStatus {
+        active: players.len(),
+        sleeping: players
+            .iter()
+            .filter(|player| player.living_entity.is_sleeping())
+            .count(),
+    };
+    let required = required_sleepers(status.active, percentage);
+    if status.sleeping == 0 {
+        world.sleep_status.store(status);
+        return;
+    }
+    if world.sleep_status.swap(status) != status {
+        let message = if status.sleeping >= required {
+            TextComponent::translate("sleep.skipping_night", [])
+        } else {
+            TextComponent::translate(
+                "sleep.players_sleeping",
+                [
+                    TextComponent::text(status.sleeping.to_string()),
+                    TextComponent::text(required.to_string()),
+                ],
+            )
+        };
+        for player in world.players.read().await.values() {
+            player.show_title(&message, &TitleMode::ActionBar).await;
+        }
+    }
+
+    let deeply_sleeping = players
+        .iter()
+        .filter(|player| player.is_sleeping_deeply())
+        .count();
+    if status.sleeping < required || deeply_sleeping < required {
+        return;
+    }
+
+    {
+        let mut level_time = world.level_time.lock().await;
+        let time_of_day = level_time.time_of_day + 24000;
+        level_time.set_time(time_of_day - time_of_day % 24000);
+        level_time.send_time(world).await;
+    }
+    {
+        let mut weather = world.weather.lock().await;
+        if weather.raining || weather.thundering {
+            weather.reset_weather_cycle(world).await;
+        }
+    }
+    for player in world.players.read().await.values() {
+        player.living_entity.wake_up().await;
+    }
+}
+
+/// How many of the players have to sleep to skip the night. At least one player always has to,
+/// so at 0% any one player skips it.
+fn required_sleepers(players: usize, percentage: u32) -> usize {
+    (players * percentage as usize).div_ceil(100).max(1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn required_sleepers_rounds_up() {
+        assert_eq!(required_sleepers(3, 100), 3);
+        assert_eq!(required_sleepers(3, 50), 2);
+        assert_eq!(required_sleepers(3, 0), 1);
+        assert_eq!(required_sleepers(0, 100), 1);
+    }
+}
@@ -1,4 +1,5 @@
 use pumpkin_protocol::client::play::CUpdateTime;
+use pumpkin_world::world_info::LevelData;
 
 use super::World;
 
@@ -24,15 +25,38 @@ impl LevelTime {
         }
     }
 
-    pub fn tick_time(&mut self) {
+    /// Continues the time saved in the level data
+    #[must_use]
+    pub fn from_level_data(level_data: &LevelData) -> Self {
+        Self {
+            world_age: level_data.time,
+            time_of_day: level_data.day_time,
+            rain_time: 0,
+        }
+    }
+
+    /// Writes the time to the level data, so it continues after a restart
+    pub fn write_level_data(&self, level_data: &mut LevelData) {
+        level_data.time = self.world_age;
+        level_data.day_time = self.time_of_day;
+    }
+
+    /// Advances the age of the world, and the time of day if the daylight cycle is enabled
+    pub fn tick_time(&mut self, do_daylight_cycle: bool) {
         self.world_age += 1;
-        self.time_of_day += 1;
+        if do_daylight_cycle {
+            self.time_of_day += 1;
+        }
         self.rain_time += 1;
     }
 
     pub async fn send_time(&self, world: &World) {
         world
-            .broadcast_packet_all(&CUpdateTime::new(self.world_age, self.time_of_day, true))
+            .broadcast_packet_all(&CUpdateTime::new(
+                self.world_age,
+                self.time_of_day,
+                world.game_rules.do_daylight_cycle,
+            ))
             .await;
     }
 
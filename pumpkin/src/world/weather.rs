@@ -1,5 +1,7 @@
 use super::World;
 use pumpkin_protocol::client::play::{CGameEvent, GameEvent};
+use pumpkin_registry::DimensionType;
+use pumpkin_world::world_info::LevelData;
 use rand::Rng;
 
 // Weather timing constants
@@ -25,8 +27,6 @@ pub struct Weather {
     pub old_rain_level: f32,
     pub thunder_level: f32,
     pub old_thunder_level: f32,
-
-    pub weather_cycle_enabled: bool,
 }
 
 impl Default for Weather {
@@ -48,10 +48,55 @@ impl Weather {
             old_rain_level: 0.0,
             thunder_level: 0.0,
             old_thunder_level: 0.0,
-            weather_cycle_enabled: true,
         }
     }
 
+    /// Continues the weather saved in the level data. It starts fully raining or thundering, like
+    /// in vanilla.
+    #[must_use]
+    pub fn from_level_data(level_data: &LevelData) -> Self {
+        let rain_level = if level_data.raining { 1.0 } else { 0.0 };
+        let thunder_level = if level_data.thundering { 1.0 } else { 0.0 };
+        Self {
+            clear_weather_time: level_data.clear_weather_time.max(0),
+            raining: level_data.raining,
+            rain_time: level_data.rain_time,
+            thundering: level_data.thundering,
+            thunder_time: level_data.thunder_time,
+            rain_level,
+            old_rain_level: rain_level,
+            thunder_level,
+            old_thunder_level: thunder_level,
+        }
+    }
+
+    /// Writes the weather to the level data, so it continues after a restart
+    pub fn write_level_data(&self, level_data: &mut LevelData) {
+        level_data.clear_weather_time = self.clear_weather_time;
+        level_data.raining = self.raining;
+        level_data.rain_time = self.rain_time;
+        level_data.thundering = self.thundering;
+        level_data.thunder_time = self.thunder_time;
+    }
+
+    /// A random number of ticks for clear weather, which is also the delay until the next rain
+    #[must_use]
+    pub fn random_clear_time() -> i32 {
+        rand::thread_rng().gen_range(RAIN_DELAY_MIN..=RAIN_DELAY_MAX)
+    }
+
+    /// A random number of ticks for rain to last
+    #[must_use]
+    pub fn random_rain_time() -> i32 {
+        rand::thread_rng().gen_range(RAIN_DURATION_MIN..=RAIN_DURATION_MAX)
+    }
+
+    /// A random number of ticks for a thunderstorm to last
+    #[must_use]
+    pub fn random_thunder_time() -> i32 {
+        rand::thread_rng().gen_range(THUNDER_DURATION_MIN..=THUNDER_DURATION_MAX)
+    }
+
     pub async fn set_weather_parameters(
         &mut self,
         world: &World,
@@ -69,20 +114,23 @@ impl Weather {
         self.thundering = thundering;
 
         if was_raining != raining {
-            if was_raining {
-                world
-                    .broadcast_packet_all(&CGameEvent::new(GameEvent::EndRaining, 0.0))
-                    .await;
-            } else {
-                world
-                    .broadcast_packet_all(&CGameEvent::new(GameEvent::BeginRaining, 0.0))
-                    .await;
-            }
+            Self::broadcast_raining(world, raining).await;
         }
     }
 
+    /// Counts down the weather, if `doWeatherCycle` is enabled, and moves the rain and thunder
+    /// levels towards the current weather. Like in vanilla, there is no weather in dimensions
+    /// without a sky.
     pub async fn tick_weather(&mut self, world: &World) {
-        if !self.weather_cycle_enabled {
+        if !matches!(
+            world.dimension_type,
+            DimensionType::Overworld | DimensionType::OverworldCaves
+        ) {
+            return;
+        }
+
+        let was_raining = self.raining;
+        if world.game_rules.do_weather_cycle {
             self.advance_weather_cycle();
         }
 
@@ -120,10 +168,24 @@ impl Weather {
                 ))
                 .await;
         }
+
+        if was_raining != self.raining {
+            Self::broadcast_raining(world, self.raining).await;
+        }
+    }
+
+    async fn broadcast_raining(world: &World, raining: bool) {
+        let event = if raining {
+            GameEvent::BeginRaining
+        } else {
+            GameEvent::EndRaining
+        };
+        world
+            .broadcast_packet_all(&CGameEvent::new(event, 0.0))
+            .await;
     }
 
     fn advance_weather_cycle(&mut self) {
-        // Removed async since there are no await calls
         if self.clear_weather_time > 0 {
             self.clear_weather_time -= 1;
             self.thunder_time = i32::from(!self.thundering);
@@ -138,8 +200,7 @@ impl Weather {
                     self.thundering = !self.thundering;
                 }
             } else if self.thundering {
-                self.thunder_time =
-                    rand::thread_rng().gen_range(THUNDER_DURATION_MIN..=THUNDER_DURATION_MAX);
+                self.thunder_time = Self::random_thunder_time();
             } else {
                 self.thunder_time =
                     rand::thread_rng().gen_range(THUNDER_DELAY_MIN..=THUNDER_DELAY_MAX);
@@ -152,10 +213,9 @@ impl Weather {
                     self.raining = !self.raining;
                 }
             } else if self.raining {
-                self.rain_time =
-                    rand::thread_rng().gen_range(RAIN_DURATION_MIN..=RAIN_DURATION_MAX);
+                self.rain_time = Self::random_rain_time();
             } else {
-                self.rain_time = rand::thread_rng().gen_range(RAIN_DELAY_MIN..=RAIN_DELAY_MAX);
+                self.rain_time = Self::random_clear_time();
             }
         }
     }
@@ -177,7 +237,6 @@ impl Clone for Weather {
             old_rain_level: self.old_rain_level,
             thunder_level: self.thunder_level,
             old_thunder_level: self.old_thunder_level,
-            weather_cycle_enabled: self.weather_cycle_enabled,
         }
     }
 }
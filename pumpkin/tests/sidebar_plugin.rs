@@ -0,0 +1,189 @@
+//! An example plugin which gives every player their own sidebar, updated every second.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use pumpkin::{
+    entity::player::Player,
+    net::{Client, GameProfile},
+    plugin::{api::scoreboard::Sidebar, Context, Plugin, PluginMetadata},
+    server::Server,
+};
+use pumpkin_util::{
+    math::vector3::Vector3,
+    text::{color::NamedColor, TextComponent},
+};
+use temp_dir::TempDir;
+use tokio::{
+    sync::{mpsc, Mutex},
+    task::AbortHandle,
+};
+use uuid::Uuid;
+
+/// Every client only shows its own sidebar, so all sidebars can share the objective name.
+const OBJECTIVE: &str = "sidebar_example:info";
+
+type Sidebars = Arc<Mutex<HashMap<Uuid, Arc<Sidebar>>>>;
+
+#[derive(Default)]
+struct SidebarPlugin {
+    sidebars: Sidebars,
+    updates: Option<AbortHandle>,
+}
+
+fn sidebar_lines(name: &str, pos: Vector3<f64>) -> Vec<TextComponent> {
+    vec![
+        TextComponent::text(name.to_string()).color_named(NamedColor::Gold),
+        TextComponent::text(""),
+        TextComponent::text(format!("X: {:.0}", pos.x)),
+        TextComponent::text(format!("Y: {:.0}", pos.y)),
+        TextComponent::text(format!("Z: {:.0}", pos.z)),
+    ]
+}
+
+async fn update_sidebars(server: &Server, sidebars: &Sidebars) {
+    let players = server.get_all_players().await;
+    let mut sidebars = sidebars.lock().await;
+    sidebars.retain(|id, _| players.iter().any(|player| player.gameprofile.id == *id));
+
+    for player in players {
+        let sidebar = match sidebars.get(&player.gameprofile.id) {
+            Some(sidebar) => sidebar.clone(),
+            None => {
+                let sidebar = Arc::new(Sidebar::new(
+                    OBJECTIVE.to_string(),
+                    TextComponent::text("Info").color_named(NamedColor::Yellow),
+                ));
+                sidebars.insert(player.gameprofile.id, sidebar.clone());
+                sidebar.show(&player).await;
+                sidebar
+            }
+        };
+        let pos = player.living_entity.entity.pos.load();
+        // Only lines which changed since the last update are sent
+        sidebar
+            .set_lines(sidebar_lines(&player.gameprofile.name, pos))
+            .await;
+    }
+}
+
+#[async_trait]
+impl Plugin for SidebarPlugin {
+    async fn on_load(&mut self, context: &Context) -> Result<(), String> {
+        let server = context.server.clone();
+        let sidebars = self.sidebars.clone();
+        let updates = context
+            .spawn_task(async move {
+                let mut interval = tokio::time::interval(Duration::from_secs(1));
+                loop {
+                    interval.tick().await;
+                    update_sidebars(&server, &sidebars).await;
+                }
+            })
+            .await;
+        self.updates = Some(updates);
+        Ok(())
+    }
+
+    async fn on_unload(&mut self, _context: &Context) -> Result<(), String> {
+        // Stop updating first, so no sidebars are shown again after hiding them
+        if let Some(updates) = self.updates.take() {
+            updates.abort();
+        }
+        for (_, sidebar) in self.sidebars.lock().await.drain() {
+            sidebar.hide_all().await;
+        }
+        Ok(())
+    }
+}
+
+const METADATA: PluginMetadata<'static> = PluginMetadata {
+    name: "sidebar_example",
+    version: "1.0.0",
+    authors: "",
+    description: "Shows every player their own sidebar",
+    website: "",
+    depends: &[],
+    soft_depends: &[],
+};
+
+/// Adds a player to the server without a connection, the packets sent to them are dropped.
+async fn join(server: &Server, name: &str) -> Arc<Player> {
+    let (packets, _) = mpsc::channel(1);
+    let client = Client::new(packets, "127.0.0.1:25565".parse().unwrap(), 0);
+    *client.gameprofile.lock().await = Some(GameProfile {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        properties: Vec::new(),
+        profile_actions: None,
+    });
+    server.add_player(Arc::new(client)).await.0
+}
+
+#[tokio::test]
+async fn sidebar_shown_while_loaded() {
+    let dir = TempDir::new().unwrap();
+    let server = Arc::new(Server::with_world_folder(dir.path().join("world")));
+    let player = join(&server, "Steve").await;
+
+    let context = Context::new(METADATA, server.clone(), Arc::default(), Arc::default());
+    let mut plugin = SidebarPlugin::default();
+    let sidebars = plugin.sidebars.clone();
+    plugin.on_load(&context).await.unwrap();
+
+    // The first update runs right after loading
+    let sidebar = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some(sidebar) = sidebars.lock().await.get(&player.gameprofile.id) {
+                break sidebar.clone();
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the sidebar was not created");
+    assert!(sidebar.is_shown_to(&player).await);
+    assert_eq!(
+        sidebar.get_lines().await[0],
+        TextComponent::text("Steve").color_named(NamedColor::Gold)
+    );
+
+    plugin.on_unload(&context).await.unwrap();
+    assert!(!sidebar.is_shown_to(&player).await);
+    assert!(sidebars.lock().await.is_empty());
+
+    // The update task is stopped, so the sidebar does not come back
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert!(sidebars.lock().await.is_empty());
+    assert!(!sidebar.is_shown_to(&player).await);
+}
+
+fn assert_plugin<P: Plugin>(_plugin: &P) {}
+
+#[tokio::test]
+async fn sidebar_updates_lines() {
+    let plugin = SidebarPlugin::default();
+    assert_plugin(&plugin);
+    assert!(plugin.sidebars.lock().await.is_empty());
+
+    let sidebar = Sidebar::new(OBJECTIVE.to_string(), TextComponent::text("Info"));
+    assert_eq!(sidebar.name(), OBJECTIVE);
+
+    sidebar
+        .set_lines(sidebar_lines("Steve", Vector3::new(0.0, 64.0, 0.0)))
+        .await;
+    sidebar
+        .set_lines(sidebar_lines("Steve", Vector3::new(10.0, 64.0, 0.0)))
+        .await;
+    let lines = sidebar.get_lines().await;
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[2], TextComponent::text("X: 10"));
+
+    sidebar.set_line(1, TextComponent::text("Online")).await;
+    assert_eq!(sidebar.get_lines().await[1], TextComponent::text("Online"));
+
+    sidebar
+        .set_lines(vec![TextComponent::text("Only line")])
+        .await;
+    assert_eq!(sidebar.get_lines().await.len(), 1);
+}